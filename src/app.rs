@@ -1,35 +1,81 @@
 //! Application state and event handling for nixmate
 
-use crate::config::Config;
+use crate::config::{Config, DataSource, ThemeName};
 use crate::i18n;
-use crate::modules::config_showcase::ConfigShowcaseState;
+use crate::keymap::Keymap;
+use crate::kiosk::Kiosk;
+use crate::modules::config_showcase::{poster::PosterTheme, ConfigShowcaseState};
 use crate::modules::errors::ErrorsState;
+use crate::modules::external::ExternalModule;
 use crate::modules::flake_inputs::FlakeInputsState;
 use crate::modules::generations::GenerationsState;
 use crate::modules::health::HealthState;
+use crate::modules::jobs::JobManager;
+use crate::modules::key_help::KeyHelp;
+use crate::modules::module::Module;
 use crate::modules::options::OptionsState;
 use crate::modules::packages::PackagesState;
+use crate::modules::palette::{PaletteCommand, PaletteEntry, PaletteState};
 use crate::modules::rebuild::RebuildState;
 use crate::modules::services::ServicesState;
-use crate::modules::splash::{self, ImageCache, ImageProtocol, WelcomeState};
+use crate::modules::splash::{self, ImageCache, ImageProtocol, StartState, WelcomeState};
 use crate::modules::storage::StorageState;
-use crate::types::FlashMessage;
-use crate::ui::{ModuleTab, Theme};
+use crate::modules::vitals::Vitals;
+use crate::nix::availability::{NixAvailability, NixNeed, NixProblem};
+use crate::pipe::{self, PipeKind};
+use crate::session::{self, ModuleSession, Session};
+use crate::staged::{Staged, StagedKind, StagedQueue};
+use crate::types::{ExternalCommand, FlashMessage, PipedInput};
+use crate::ui::layout::Viewport;
+use crate::ui::{theme, ModuleTab, Theme};
 use anyhow::Result;
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use std::collections::HashSet;
+use std::time::{Duration, Instant, SystemTime};
+
+/// Rows in the Settings tab: 3 global + 1 pkg search + 1 path + 6 error translator/AI + 5 refresh
+const SETTINGS_COUNT: usize = 16;
 
 /// Main application state
 pub struct App {
     pub should_quit: bool,
+    /// Quit once the running rebuild has finished activating
+    quit_after_build: bool,
     pub active_tab: ModuleTab,
     pub config: Config,
     pub theme: Theme,
+    /// Modification time of the custom theme file when it was loaded
+    custom_theme_mtime: Option<SystemTime>,
+    theme_checked_at: Instant,
+    /// Modification time of config.toml as last read or written
+    config_mtime: Option<SystemTime>,
+    config_checked_at: Instant,
     pub settings_selected: usize,
     pub settings_editing: bool,
     pub settings_edit_buffer: String,
     pub popup: PopupState,
+    /// Command palette overlay (Ctrl+P)
+    pub palette: PaletteState,
+    /// `?` overlay with the keys of the current module and sub-tab
+    pub key_help: bool,
+    /// Background work of all modules, and the jobs overlay (`J`)
+    pub jobs: JobManager,
+    /// Confirmed deletions waiting out their undo window (`u`)
+    pub staged: StagedQueue,
+    /// Terminal size and compact mode (see `handle_resize`)
+    pub viewport: Viewport,
+    /// Generation, store space and failed units for the status bar
+    pub vitals: Vitals,
+    /// Read-only dashboard rotation (`--dashboard`)
+    pub kiosk: Option<Kiosk>,
+    /// User keybindings from `[keys]`
+    pub keymap: Keymap,
     pub flash_message: Option<FlashMessage>,
+    /// Module whose external command is running (see `take_external_command`)
+    external_source: Option<ModuleTab>,
+    /// Session is restored and saved (`restore_session`; not with piped
+    /// input or in dashboard mode)
+    session_enabled: bool,
 
     // Module intro pages (dismissed per session)
     pub intros_dismissed: HashSet<usize>,
@@ -43,8 +89,13 @@ pub struct App {
     pub image_displayed: bool,
     pub last_image_area: Option<(u16, u16, u16, u16)>,
 
+    // Nix availability (probed at startup, re-probed on retry)
+    pub nix: NixAvailability,
+
     // Module states
     pub welcome: WelcomeState,
+    /// Doctor summary shown on launch (after the first run)
+    pub start: StartState,
     pub generations: GenerationsState,
     pub errors: ErrorsState,
     pub services: ServicesState,
@@ -55,6 +106,8 @@ pub struct App {
     pub health: HealthState,
     pub rebuild: RebuildState,
     pub flake_inputs: FlakeInputsState,
+    /// `[[external_modules]]`, tabs F1-F12
+    pub external: Vec<ExternalModule>,
 }
 
 #[derive(Debug, Clone)]
@@ -64,19 +117,29 @@ pub enum PopupState {
         title: String,
         message: String,
     },
-    #[allow(dead_code)] // Reserved for async operations
-    // Planned for future use
+    /// `q` while a rebuild runs: detach it, stop it, or stay
+    QuitDuringBuild,
+    /// Blocking work with nothing to interact with (staged actions on quit)
     Loading {
         message: String,
     },
 }
 
 impl App {
-    pub fn new(config: Config, piped_input: Option<String>) -> Result<Self> {
+    pub fn new(config: Config, piped_input: Option<PipedInput>) -> Result<Self> {
         let theme = Theme::from_name(config.theme);
 
         // If piped input is provided, auto-analyze in Error Translator (skip welcome)
         let show_welcome = !config.welcome_shown && piped_input.is_none();
+        // Probe Nix first so unavailable modules don't each hit their own timeout
+        let nix = NixAvailability::probe();
+
+        // The Doctor splash scores a NixOS system
+        let show_start = config.welcome_shown
+            && config.doctor_splash
+            && piped_input.is_none()
+            && nix.platform.is_nixos();
+        let session_enabled = config.restore_session && piped_input.is_none();
         let initial_lang = config.language;
 
         // Detect terminal image protocol + prepare image cache
@@ -92,13 +155,35 @@ impl App {
         let mut services = ServicesState::new();
         let mut storage = StorageState::new();
 
-        let (errors, active_tab, intros_dismissed) = if let Some(input) = piped_input {
-            let errors = ErrorsState::new_with_input(input, config.language);
-            let mut dismissed = HashSet::new();
-            dismissed.insert(ModuleTab::Errors.index()); // Skip intro for piped input
-            (errors, ModuleTab::Errors, dismissed)
-        } else {
-            (ErrorsState::new(), ModuleTab::Generations, HashSet::new())
+        // Rebuild runs and flake updates open in their own module, the rest
+        // is analyzed in the Error Translator
+        let pipe_kind = piped_input.as_ref().map(|input| {
+            input
+                .kind
+                .unwrap_or_else(|| pipe::detect(&input.text, input.source.as_deref()))
+        });
+        let mut piped_log = None;
+        let (mut errors, active_tab, intros_dismissed) = match (piped_input, pipe_kind) {
+            (Some(input), Some(kind @ (PipeKind::Rebuild | PipeKind::FlakeUpdate))) => {
+                let tab = if kind == PipeKind::Rebuild {
+                    ModuleTab::Rebuild
+                } else {
+                    ModuleTab::FlakeInputs
+                };
+                piped_log = Some(input.text);
+                (ErrorsState::new(), tab, HashSet::from([tab.index()]))
+            }
+            (Some(input), _) => {
+                let errors = ErrorsState::new_with_input(input, config.language);
+                let mut dismissed = HashSet::new();
+                dismissed.insert(ModuleTab::Errors.index()); // Skip intro for piped input
+                (errors, ModuleTab::Errors, dismissed)
+            }
+            (None, _) => (
+                ErrorsState::new(),
+                ModuleTab::home(nix.platform),
+                HashSet::new(),
+            ),
         };
 
         // Sync language to all modules
@@ -106,10 +191,13 @@ impl App {
         generations.lang = lang;
         services.lang = lang;
         storage.lang = lang;
+        storage.free_target_gb = config.storage_free_target_gb;
+        services.mounts_enabled = config.services_mounts;
         let mut config_showcase = ConfigShowcaseState::new();
         config_showcase.lang = lang;
         let mut options = OptionsState::new();
         options.lang = lang;
+        options.examples_config = config.option_examples.clone();
         let mut packages = PackagesState::new();
         packages.lang = lang;
         let mut health = HealthState::new();
@@ -125,25 +213,94 @@ impl App {
         config_showcase.config_path = cp.clone();
         flake_inputs.config_path = cp.clone();
         options.config_path = cp.clone();
+        health.config_path = cp.clone();
+        errors.config_path = cp.clone();
         packages.config_path = cp;
+        let host = config.nixos_host.clone().filter(|h| !h.is_empty());
+        rebuild.nixos_host = host.clone();
+        options.nixos_host = host.clone();
+        health.nixos_host = host.clone();
+        config_showcase.nixos_host = host.clone();
+        errors.nixos_host = host;
+        flake_inputs.github_token = config.github_token.clone();
+        flake_inputs.commit_template = config.flake_commit_message.clone();
+        flake_inputs.flake_dir = config.flake_path.clone();
+        flake_inputs.flakes = config.flakes.clone();
+        rebuild.flakes = config.flakes.clone();
+        rebuild.dashboard = config.rebuild_dashboard;
+        generations.time_format = config.time_format;
+        rebuild.time_format = config.time_format;
+        storage.time_format = config.time_format;
+        flake_inputs.time_format = config.time_format;
+        rebuild.paste = config.paste.clone();
+        rebuild.backends = config.rebuild_backend.clone();
+        flake_inputs.watch = config.flake_watch.clone();
+        health.flake_watch = config.flake_watch.clone();
+        if show_start {
+            health.ensure_scanned();
+        }
+        if let Some(kind) = pipe_kind.filter(|k| *k != PipeKind::Error) {
+            let s = i18n::get_strings(lang);
+            let flash = FlashMessage::new(s.pipe_detected.replace("{}", kind.label(s)), false);
+            match (kind, piped_log) {
+                (PipeKind::Rebuild, Some(text)) => {
+                    rebuild.load_piped_log(&text);
+                    rebuild.flash_message = Some(flash);
+                }
+                (PipeKind::FlakeUpdate, Some(text)) => {
+                    flake_inputs.load_update_output(&text);
+                    flake_inputs.flash_message = Some(flash);
+                }
+                _ => errors.flash_message = Some(flash),
+            }
+        }
+
+        let keymap = Keymap::from_config(&config.keys);
+        let flash_message = (!keymap.issues.is_empty()).then(|| {
+            FlashMessage::new(i18n::get_strings(lang).keys_issues_flash.to_string(), true)
+        });
 
-        Ok(Self {
+        let external = config
+            .external_modules
+            .iter()
+            .cloned()
+            .map(ExternalModule::new)
+            .collect();
+
+        let mut app = Self {
             should_quit: false,
+            quit_after_build: false,
             active_tab,
             config,
             theme,
+            custom_theme_mtime: None,
+            theme_checked_at: Instant::now(),
+            config_mtime: Config::mtime(),
+            config_checked_at: Instant::now(),
             settings_selected: 0,
             settings_editing: false,
             settings_edit_buffer: String::new(),
             popup: PopupState::None,
-            flash_message: None,
+            palette: PaletteState::default(),
+            key_help: false,
+            jobs: JobManager::default(),
+            staged: StagedQueue::default(),
+            viewport: Viewport::default(),
+            vitals: Vitals::default(),
+            kiosk: None,
+            keymap,
+            flash_message,
+            external_source: None,
+            session_enabled,
             intros_dismissed,
             image_protocol,
             image_cache,
             image_area: None,
             image_displayed: false,
             last_image_area: None,
+            nix,
             welcome: WelcomeState::new(show_welcome, initial_lang),
+            start: StartState::new(show_start),
             generations,
             errors,
             services,
@@ -154,10 +311,363 @@ impl App {
             health,
             rebuild,
             flake_inputs,
-        })
+            external,
+        };
+        app.apply_theme();
+        Ok(app)
+    }
+
+    /// Open the module and apply the sub-tabs, searches and filters saved
+    /// by the last run (not in dashboard mode)
+    pub fn restore_session(&mut self) {
+        if !self.session_enabled {
+            return;
+        }
+        if let Some(session) = session::load() {
+            self.apply_session(&session);
+        }
+    }
+
+    fn apply_session(&mut self, session: &Session) {
+        for (&index, saved) in &session.modules {
+            let Some(&tab) = ModuleTab::all().iter().find(|t| t.index() == index) else {
+                continue;
+            };
+            self.set_sub_tab(tab, saved.sub_tab);
+            match tab {
+                ModuleTab::Services => self.services.restore_session(saved),
+                ModuleTab::Storage => self.storage.restore_session(saved),
+                ModuleTab::Options => self.options.restore_session(saved),
+                ModuleTab::Packages => self.packages.restore_session(saved),
+                ModuleTab::Rebuild => self.rebuild.restore_session(saved),
+                _ => {}
+            }
+        }
+        if let Some(&tab) = ModuleTab::all().iter().find(|t| t.index() == session.tab) {
+            self.active_tab = tab;
+            self.intros_dismissed.insert(tab.index());
+            self.ensure_active_tab_loaded();
+        }
+    }
+
+    /// On quit: remember where the user was for the next launch
+    pub fn save_session(&self) {
+        if !self.session_enabled {
+            return;
+        }
+        let mut session = Session {
+            tab: self.active_tab.index(),
+            ..Default::default()
+        };
+        for &tab in ModuleTab::all() {
+            let mut saved = match tab {
+                ModuleTab::Services => self.services.session(),
+                ModuleTab::Storage => self.storage.session(),
+                ModuleTab::Options => self.options.session(),
+                ModuleTab::Packages => self.packages.session(),
+                ModuleTab::Rebuild => self.rebuild.session(),
+                _ => ModuleSession::default(),
+            };
+            if let Some((sub_tab, _)) = self.sub_tab_position(tab) {
+                saved.sub_tab = sub_tab;
+            }
+            if saved != ModuleSession::default() {
+                session.modules.insert(tab.index(), saved);
+            }
+        }
+        let _ = session::save(&session);
+    }
+
+    /// Set the theme from config: the custom theme file while one is
+    /// selected (the built-in one if it doesn't load), else the built-in.
+    /// False if the file failed to load.
+    fn apply_theme(&mut self) -> bool {
+        let builtin = self.config.theme;
+        let Some(name) = self.config.custom_theme.clone() else {
+            self.custom_theme_mtime = None;
+            self.theme = Theme::from_name(builtin);
+            self.config_showcase.poster_theme = PosterTheme::new(builtin.as_str(), &self.theme);
+            return true;
+        };
+        self.custom_theme_mtime = theme::custom_theme_mtime(&name);
+        match Theme::load_custom(&name) {
+            Ok(loaded) => {
+                self.theme = loaded;
+                self.config_showcase.poster_theme = PosterTheme::new(&name, &self.theme);
+                true
+            }
+            Err(e) => {
+                let s = i18n::get_strings(self.config.language);
+                self.flash_message = Some(FlashMessage::new(
+                    s.theme_load_failed.replacen("{}", &name, 1).replacen(
+                        "{}",
+                        &format!("{:#}", e),
+                        1,
+                    ),
+                    true,
+                ));
+                self.theme = Theme::from_name(builtin);
+                self.config_showcase.poster_theme = PosterTheme::new(builtin.as_str(), &self.theme);
+                false
+            }
+        }
+    }
+
+    /// Reload the custom theme file when it changed on disk
+    fn poll_custom_theme(&mut self) {
+        let Some(name) = self.config.custom_theme.clone() else {
+            return;
+        };
+        if self.theme_checked_at.elapsed() < Duration::from_secs(1) {
+            return;
+        }
+        self.theme_checked_at = Instant::now();
+        if theme::custom_theme_mtime(&name) != self.custom_theme_mtime && self.apply_theme() {
+            let s = i18n::get_strings(self.config.language);
+            self.flash_message = Some(FlashMessage::new(
+                s.theme_reloaded.replace("{}", &name),
+                false,
+            ));
+        }
+    }
+
+    /// Reload config.toml when it was edited outside nixmate. Our own
+    /// saves change the mtime too; those reload to the same config and
+    /// pass silently.
+    fn poll_config_file(&mut self) {
+        if self.config_checked_at.elapsed() < Duration::from_secs(1) {
+            return;
+        }
+        self.config_checked_at = Instant::now();
+        let mtime = Config::mtime();
+        if mtime.is_none() || mtime == self.config_mtime {
+            return;
+        }
+        self.config_mtime = mtime;
+
+        let loaded = match Config::load() {
+            Ok(c) => c,
+            Err(e) => {
+                let s = i18n::get_strings(self.config.language);
+                self.flash_message = Some(FlashMessage::new(
+                    s.config_reload_failed.replace("{}", &format!("{:#}", e)),
+                    true,
+                ));
+                return;
+            }
+        };
+        if toml::to_string(&loaded).ok() == toml::to_string(&self.config).ok() {
+            return;
+        }
+        self.config = loaded;
+        self.apply_config();
+        if self.apply_theme() {
+            let s = i18n::get_strings(self.config.language);
+            self.flash_message = Some(FlashMessage::new(s.config_reloaded.into(), false));
+        }
+    }
+
+    /// Terminal resized (and once at startup): compact mode for the new
+    /// size, and scroll offsets that ran past what it shows pulled back
+    pub fn handle_resize(&mut self, width: u16, height: u16) {
+        self.viewport = Viewport::new(width, height, &self.config);
+        let compact = self.viewport.compact;
+        self.generations.compact = compact;
+        self.services.compact = compact;
+        self.storage.compact = compact;
+        self.flake_inputs.compact = compact;
+        self.rebuild.compact = compact;
+
+        let visible = self.viewport.body_height();
+        self.rebuild.fit_scroll(visible);
+        self.services.fit_scroll(visible);
+    }
+
+    /// Persist the flake and host picked in Rebuild: the flake becomes the
+    /// config path, the host the `nixosConfigurations` attribute every
+    /// module builds and evaluates
+    fn save_rebuild_host(&mut self) {
+        let Some((flake, host)) = self.rebuild.picked.take() else {
+            return;
+        };
+        let new_flake = self.rebuild.flake_path.as_deref() != Some(flake.as_str());
+        if new_flake {
+            self.config.config_path = Some(flake);
+            self.sync_config_path_to_modules();
+        }
+        if new_flake || host != self.config.nixos_host {
+            self.config.nixos_host = host;
+            self.sync_nixos_host_to_modules();
+            self.options.refresh_in_background();
+            let _ = self.config.save();
+        }
     }
 
+    /// Persist flake projects registered or switched to in Flake Inputs
+    fn save_flake_projects(&mut self) {
+        let fi = &self.flake_inputs;
+        if fi.flake_dir != self.config.flake_path || fi.flakes != self.config.flakes {
+            self.config.flake_path = fi.flake_dir.clone();
+            self.config.flakes = fi.flakes.clone();
+            self.rebuild.flakes = fi.flakes.clone();
+            let _ = self.config.save();
+        }
+    }
+
+    /// Push the settings that modules keep their own copy of
+    fn apply_config(&mut self) {
+        self.sync_lang_to_modules();
+        self.sync_config_path_to_modules();
+        self.sync_nixos_host_to_modules();
+        let c = &self.config;
+        self.generations.time_format = c.time_format;
+        self.rebuild.time_format = c.time_format;
+        self.storage.time_format = c.time_format;
+        self.flake_inputs.time_format = c.time_format;
+        self.rebuild.dashboard = c.rebuild_dashboard;
+        self.flake_inputs.flakes = c.flakes.clone();
+        self.rebuild.flakes = c.flakes.clone();
+        self.flake_inputs.switch_project(c.flake_path.clone());
+        // Thresholds may have changed
+        self.handle_resize(self.viewport.width, self.viewport.height);
+        let c = &self.config;
+        if self.services.mounts_enabled != c.services_mounts {
+            self.services.mounts_enabled = c.services_mounts;
+            self.services.mounts = None;
+            if self.services.loaded {
+                self.services.refresh_in_background();
+            }
+        }
+        self.keymap = Keymap::from_config(&c.keys);
+        if self
+            .external
+            .iter()
+            .map(|m| &m.config)
+            .ne(&c.external_modules)
+        {
+            self.external = c
+                .external_modules
+                .iter()
+                .cloned()
+                .map(ExternalModule::new)
+                .collect();
+            if matches!(self.active_tab, ModuleTab::External(i) if i >= self.external.len()) {
+                self.active_tab = ModuleTab::home(self.nix.platform);
+            }
+        }
+    }
+
+    /// Settings: built-in themes in order, then the theme files found
+    fn next_theme(&mut self) {
+        let custom = theme::custom_theme_names();
+        match self.config.custom_theme.take() {
+            Some(current) => {
+                let pos = custom.iter().position(|n| *n == current);
+                match pos.and_then(|i| custom.get(i + 1)) {
+                    Some(next) => self.config.custom_theme = Some(next.clone()),
+                    None => self.config.theme = ThemeName::default(),
+                }
+            }
+            None => {
+                let next = self.config.theme.next();
+                if next == ThemeName::default() && !custom.is_empty() {
+                    self.config.custom_theme = Some(custom[0].clone());
+                } else {
+                    self.config.theme = next;
+                }
+            }
+        }
+        self.apply_theme();
+    }
+
+    /// Enter dashboard mode: no welcome/start screens, no intros, first
+    /// dashboard module shown and loading
+    pub fn start_kiosk(&mut self, kiosk: Kiosk) {
+        self.session_enabled = false;
+        self.welcome.dismiss();
+        self.start.dismiss();
+        for tab in &kiosk.tabs {
+            self.intros_dismissed.insert(tab.index());
+        }
+        self.active_tab = kiosk.current_tab();
+        self.kiosk = Some(kiosk);
+        self.ensure_active_tab_loaded();
+    }
+
+    /// Dashboard mode only knows quit, pause and switching — everything
+    /// else is dropped so nothing can be changed from an unattended screen
+    fn handle_kiosk_key(&mut self, key: KeyEvent) {
+        let Some(kiosk) = self.kiosk.as_mut() else {
+            return;
+        };
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => self.should_quit = true,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.should_quit = true
+            }
+            KeyCode::Char(' ') => kiosk.toggle_pause(),
+            KeyCode::Right | KeyCode::Char('l') | KeyCode::Tab => {
+                self.active_tab = kiosk.step(true);
+                self.ensure_active_tab_loaded();
+            }
+            KeyCode::Left | KeyCode::Char('h') | KeyCode::BackTab => {
+                self.active_tab = kiosk.step(false);
+                self.ensure_active_tab_loaded();
+            }
+            _ => {}
+        }
+    }
+
+    /// Route a key from the terminal, remapped through `[keys]` first
+    /// (not in text fields, popups or the palette)
     pub fn handle_key(&mut self, key: KeyEvent) -> Result<()> {
+        let remappable = !self.welcome.active
+            && !self.palette.active
+            && !self.settings_editing
+            && matches!(self.popup, PopupState::None)
+            && !self.module_capturing(self.active_tab);
+        if !remappable {
+            return self.dispatch_key(key);
+        }
+        match self.keymap.translate(key) {
+            Some(key) => self.dispatch_key(key),
+            None => Ok(()),
+        }
+    }
+
+    /// Bracketed paste. The Error Translator takes it as a whole, newlines
+    /// and all; everywhere else it's typed in key by key, as it was before
+    /// nixmate asked the terminal to mark pastes.
+    pub fn handle_paste(&mut self, text: &str) -> Result<()> {
+        let errors_visible = self.active_tab == ModuleTab::Errors
+            && self.kiosk.is_none()
+            && !self.welcome.active
+            && !self.start.active
+            && !self.palette.active
+            && !self.jobs.overlay
+            && !self.key_help
+            && matches!(self.popup, PopupState::None)
+            && !self.is_intro_showing();
+        if errors_visible && self.errors.paste(text) {
+            return Ok(());
+        }
+        for c in text.replace("\r\n", "\n").chars() {
+            let code = match c {
+                '\n' | '\r' => KeyCode::Enter,
+                '\t' => KeyCode::Tab,
+                c => KeyCode::Char(c),
+            };
+            self.handle_key(KeyEvent::from(code))?;
+        }
+        Ok(())
+    }
+
+    fn dispatch_key(&mut self, key: KeyEvent) -> Result<()> {
+        if self.kiosk.is_some() {
+            self.handle_kiosk_key(key);
+            return Ok(());
+        }
+
         // Welcome screen
         if self.welcome.active {
             if !self.welcome.ready_for_input() {
@@ -190,6 +700,20 @@ impl App {
             return Ok(());
         }
 
+        // Start screen: Enter opens the Doctor on the worst finding, any other key continues
+        if self.start.active {
+            if !self.start.ready_for_input() {
+                return Ok(());
+            }
+            self.start.dismiss();
+            if key.code == KeyCode::Enter {
+                self.active_tab = ModuleTab::Health;
+                self.intros_dismissed.insert(ModuleTab::Health.index());
+                self.health.focus_worst();
+            }
+            return Ok(());
+        }
+
         // Clear expired flash
         if let Some(msg) = &self.flash_message {
             if msg.is_expired(3) {
@@ -197,6 +721,24 @@ impl App {
             }
         }
 
+        // Command palette captures ALL keys while open
+        if self.palette.active {
+            return self.handle_palette_key(key);
+        }
+
+        if self.jobs.overlay {
+            return self.handle_jobs_key(key);
+        }
+
+        // Key overlay: `?` again opens Help / About, anything else closes it
+        if self.key_help {
+            self.key_help = false;
+            if key.code == KeyCode::Char('?') {
+                self.active_tab = ModuleTab::HelpAbout;
+            }
+            return Ok(());
+        }
+
         // App-level popup handling
         match &self.popup {
             PopupState::Error { .. } => {
@@ -208,6 +750,32 @@ impl App {
                 }
                 return Ok(());
             }
+            PopupState::QuitDuringBuild => {
+                let s = i18n::get_strings(self.config.language);
+                let activating = self.rebuild.activating();
+                match key.code {
+                    // Activation can't be stopped or handed over: only wait
+                    KeyCode::Char('w') if activating => {
+                        self.popup = PopupState::None;
+                        self.quit_after_build = true;
+                        self.rebuild.flash_message =
+                            Some(FlashMessage::new(s.rb_quit_waiting.to_string(), false));
+                    }
+                    KeyCode::Char('d') if !activating => {
+                        self.popup = PopupState::None;
+                        self.rebuild.queue_detach();
+                    }
+                    KeyCode::Char('k') if !activating => {
+                        self.rebuild.cancel_build();
+                        self.should_quit = true;
+                    }
+                    KeyCode::Char('n') | KeyCode::Esc => {
+                        self.popup = PopupState::None;
+                    }
+                    _ => {}
+                }
+                return Ok(());
+            }
             PopupState::Loading { .. } => return Ok(()),
             PopupState::None => {}
         }
@@ -218,6 +786,35 @@ impl App {
             return Ok(());
         }
 
+        // Ctrl+P: command palette (not while a module is taking text input)
+        if key.code == KeyCode::Char('p')
+            && key.modifiers.contains(KeyModifiers::CONTROL)
+            && !self.module_capturing(self.active_tab)
+        {
+            let entries = self.palette_entries();
+            self.palette.open(entries);
+            return Ok(());
+        }
+
+        // J: jobs overlay (same rule; no module uses `J`)
+        if key.code == KeyCode::Char('J') && !self.module_capturing(self.active_tab) {
+            self.jobs.overlay = true;
+            return Ok(());
+        }
+
+        // u: take back the latest staged deletion (takes `u` from modules
+        // only while the countdown is showing)
+        if key.code == KeyCode::Char('u') && !self.module_capturing(self.active_tab) {
+            if let Some(undone) = self.staged.undo_latest() {
+                let s = i18n::get_strings(self.config.language);
+                self.flash_message = Some(FlashMessage::new(
+                    s.staged_undone.replace("{}", &undone.label),
+                    false,
+                ));
+                return Ok(());
+            }
+        }
+
         // Module intro page handling
         if self.is_intro_showing() {
             match key.code {
@@ -230,12 +827,30 @@ impl App {
                 | KeyCode::Char('0')
                 | KeyCode::Char(',')
                 | KeyCode::Char('?')
-                | KeyCode::Char('q') => {}
+                | KeyCode::Char('q')
+                | KeyCode::F(_) => {}
                 // All other keys are absorbed by intro
                 _ => return Ok(()),
             }
         }
 
+        // Degraded module (Nix unavailable): only retry + global keys
+        if let Some(problem) = self.degraded_problem(self.active_tab) {
+            match key.code {
+                KeyCode::Char('r') if problem != NixProblem::NotNixOS => {
+                    self.retry_nix_detection();
+                    return Ok(());
+                }
+                KeyCode::Char('1'..='9')
+                | KeyCode::Char('0')
+                | KeyCode::Char(',')
+                | KeyCode::Char('?')
+                | KeyCode::Char('q')
+                | KeyCode::F(_) => {}
+                _ => return Ok(()),
+            }
+        }
+
         // Try to let active module consume the key
         let consumed = self.try_module_key(key)?;
         if consumed {
@@ -245,7 +860,17 @@ impl App {
         // Global keys
         match key.code {
             KeyCode::Char('q') => {
-                self.should_quit = true;
+                if self.rebuild.is_running() {
+                    // Quitting would orphan the build
+                    self.popup = PopupState::QuitDuringBuild;
+                } else if self.rebuild.detaching() {
+                    // The build is stopped already; quitting now would lose it
+                    let s = i18n::get_strings(self.config.language);
+                    self.rebuild.flash_message =
+                        Some(FlashMessage::new(s.rb_detach_stopping.to_string(), false));
+                } else {
+                    self.should_quit = true;
+                }
                 return Ok(());
             }
             KeyCode::Char('1') => self.active_tab = ModuleTab::Generations,
@@ -259,6 +884,10 @@ impl App {
             KeyCode::Char('9') => self.active_tab = ModuleTab::Packages,
             KeyCode::Char('0') => self.active_tab = ModuleTab::Health,
             KeyCode::Char(',') => self.active_tab = ModuleTab::Settings,
+            KeyCode::F(n) if (1..=self.external.len() as u8).contains(&n) => {
+                self.active_tab = ModuleTab::External(n as usize - 1)
+            }
+            KeyCode::Char('?') if self.active_tab.is_module() => self.key_help = true,
             KeyCode::Char('?') => self.active_tab = ModuleTab::HelpAbout,
             _ => {}
         }
@@ -267,358 +896,575 @@ impl App {
             self.handle_settings_key(key)?;
         }
 
-        // Lazy-load installed packages when entering Packages tab
-        // Lazy-load services when entering Services tab
-        if self.active_tab == ModuleTab::Services {
-            self.services.start_loading();
+        self.ensure_active_tab_loaded();
+
+        Ok(())
+    }
+
+    fn handle_palette_key(&mut self, key: KeyEvent) -> Result<()> {
+        match key.code {
+            KeyCode::Esc => self.palette.close(),
+            KeyCode::Enter => {
+                if let Some(entry) = self.palette.take_selected() {
+                    self.run_palette_entry(entry)?;
+                }
+            }
+            KeyCode::Down => self.palette.select_next(),
+            KeyCode::Up => self.palette.select_prev(),
+            KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.palette.select_next()
+            }
+            KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.palette.select_prev()
+            }
+            KeyCode::Backspace => self.palette.pop_char(),
+            KeyCode::Char(c) => self.palette.push_char(c),
+            _ => {}
         }
-        // Lazy-load storage when entering Storage tab
-        if self.active_tab == ModuleTab::Storage {
-            self.storage.start_loading();
+        Ok(())
+    }
+
+    fn handle_jobs_key(&mut self, key: KeyEvent) -> Result<()> {
+        if self.jobs.confirm {
+            self.jobs.confirm = false;
+            if key.code == KeyCode::Char('y') {
+                if let Some(t) = self.jobs.selected_job() {
+                    let (tab, id, name) = (t.tab, t.job.id, t.job.name.clone());
+                    self.cancel_job(tab, id);
+                    self.jobs.mark_cancelled(tab, id);
+                    let s = i18n::get_strings(self.config.language);
+                    self.flash_message = Some(FlashMessage::new(
+                        s.jobs_cancel_done.replace("{}", &name),
+                        false,
+                    ));
+                }
+            }
+            return Ok(());
         }
-        if self.active_tab == ModuleTab::Packages {
-            self.packages
-                .ensure_source_detected(&self.config.nixpkgs_channel);
-            self.packages.ensure_installed_loaded();
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('J') | KeyCode::Char('q') => self.jobs.overlay = false,
+            KeyCode::Down | KeyCode::Char('j') => self.jobs.select_next(),
+            KeyCode::Up | KeyCode::Char('k') => self.jobs.select_prev(),
+            KeyCode::Char('c') | KeyCode::Delete
+                if self.jobs.selected_job().is_some_and(|t| t.job.cancellable) =>
+            {
+                self.jobs.confirm = true;
+            }
+            _ => {}
         }
+        Ok(())
+    }
 
-        // Lazy-load options when entering Options tab
-        if self.active_tab == ModuleTab::Options {
-            self.options.ensure_loaded();
-        }
+    /// What every module is running right now
+    fn sync_jobs(&mut self) {
+        let s = i18n::get_strings(self.config.language);
+        let reports: Vec<_> = self
+            .tabs()
+            .into_iter()
+            .filter_map(|tab| Some((tab, self.module(tab)?.jobs(s))))
+            .collect();
+        self.jobs.sync(
+            reports
+                .into_iter()
+                .flat_map(|(tab, jobs)| jobs.into_iter().map(move |job| (tab, job)))
+                .collect(),
+        );
+    }
 
-        // Lazy-load flake inputs when entering FlakeInputs tab
-        if self.active_tab == ModuleTab::FlakeInputs {
-            self.flake_inputs.ensure_loaded();
+    fn cancel_job(&mut self, tab: ModuleTab, id: &str) {
+        if let Some(module) = self.module_mut(tab) {
+            module.cancel_job(id);
         }
+    }
 
-        // Lazy-load health checks when entering Health tab
-        if self.active_tab == ModuleTab::Health {
-            self.health.ensure_scanned();
-        }
+    /// The module behind a tab (None for Settings and Help)
+    pub fn module(&self, tab: ModuleTab) -> Option<&dyn Module> {
+        Some(match tab {
+            ModuleTab::Generations => &self.generations,
+            ModuleTab::Errors => &self.errors,
+            ModuleTab::Services => &self.services,
+            ModuleTab::Storage => &self.storage,
+            ModuleTab::Config => &self.config_showcase,
+            ModuleTab::Options => &self.options,
+            ModuleTab::Rebuild => &self.rebuild,
+            ModuleTab::FlakeInputs => &self.flake_inputs,
+            ModuleTab::Packages => &self.packages,
+            ModuleTab::Health => &self.health,
+            ModuleTab::External(i) => self.external.get(i)?,
+            ModuleTab::Settings | ModuleTab::HelpAbout => return None,
+        })
+    }
 
-        // Lazy-load config detection when entering Rebuild tab
-        if self.active_tab == ModuleTab::Rebuild {
-            self.rebuild.ensure_detected();
-        }
+    pub fn module_mut(&mut self, tab: ModuleTab) -> Option<&mut dyn Module> {
+        Some(match tab {
+            ModuleTab::Generations => &mut self.generations,
+            ModuleTab::Errors => &mut self.errors,
+            ModuleTab::Services => &mut self.services,
+            ModuleTab::Storage => &mut self.storage,
+            ModuleTab::Config => &mut self.config_showcase,
+            ModuleTab::Options => &mut self.options,
+            ModuleTab::Rebuild => &mut self.rebuild,
+            ModuleTab::FlakeInputs => &mut self.flake_inputs,
+            ModuleTab::Packages => &mut self.packages,
+            ModuleTab::Health => &mut self.health,
+            ModuleTab::External(i) => self.external.get_mut(i)?,
+            ModuleTab::Settings | ModuleTab::HelpAbout => return None,
+        })
+    }
 
-        Ok(())
+    /// Tabs of the external modules, in order
+    pub fn external_tabs(&self) -> impl Iterator<Item = ModuleTab> {
+        (0..self.external.len()).map(ModuleTab::External)
     }
 
-    fn try_module_key(&mut self, key: KeyEvent) -> Result<bool> {
-        match self.active_tab {
-            ModuleTab::Generations => {
-                let gen = &self.generations;
-
-                // Module captures ALL keys when popup or filter active
-                let has_popup =
-                    !matches!(gen.popup, crate::modules::generations::GenPopupState::None);
-                let filter_active = gen.packages_filter_active;
-
-                if has_popup || filter_active {
-                    self.generations.handle_key(key)?;
-                    return Ok(true);
-                }
+    /// Every tab: the built-in ones, then the external modules
+    pub fn tabs(&self) -> Vec<ModuleTab> {
+        ModuleTab::all()
+            .iter()
+            .copied()
+            .chain(self.external_tabs())
+            .collect()
+    }
 
-                // Sub-tab keys always go to module
-                if matches!(key.code, KeyCode::Char('[') | KeyCode::Char(']')) {
-                    self.generations.handle_key(key)?;
-                    return Ok(true);
-                }
+    /// Every module's palette commands, titled "Module: action"
+    fn palette_entries(&self) -> Vec<PaletteEntry> {
+        self.tabs()
+            .into_iter()
+            .flat_map(|tab| {
+                let module = tab.label(self);
+                self.palette_commands(tab)
+                    .into_iter()
+                    .map(move |command| PaletteEntry {
+                        tab,
+                        title: format!("{}: {}", module, command.label),
+                        command,
+                    })
+            })
+            .collect()
+    }
 
-                // Tab-switch keys and quit stay global
-                match key.code {
-                    KeyCode::Char('1'..='9')
-                    | KeyCode::Char('0')
-                    | KeyCode::Char(',')
-                    | KeyCode::Char('?')
-                    | KeyCode::Char('q') => Ok(false),
-                    _ => {
-                        self.generations.handle_key(key)?;
-                        Ok(true)
-                    }
-                }
-            }
-            ModuleTab::Errors => {
-                let err = &self.errors;
+    /// What a tab registers for the palette (and the `?` overlay)
+    fn palette_commands(&self, tab: ModuleTab) -> Vec<PaletteCommand> {
+        let s = i18n::get_strings(self.config.language);
 
-                // Module captures ALL keys when in input mode or AI loading
-                if err.input_mode || err.ai_loading {
-                    let lang = self.config.language;
-                    self.errors.handle_key(key, lang)?;
-                    return Ok(true);
-                }
+        // Settings rows: move to the row, then activate it
+        let setting = |row: usize, label: &str| {
+            let mut keys = vec![KeyCode::Up; SETTINGS_COUNT];
+            keys.extend(vec![KeyCode::Down; row]);
+            keys.push(KeyCode::Enter);
+            PaletteCommand::new(label, None, keys)
+        };
 
-                // Submit tab always captures (it's a form)
-                if err.active_sub_tab == crate::modules::errors::ErrSubTab::Submit {
-                    let lang = self.config.language;
-                    self.errors.handle_key(key, lang)?;
-                    return Ok(true);
-                }
+        match tab {
+            ModuleTab::Settings => vec![
+                setting(0, s.pal_set_theme),
+                setting(1, s.pal_set_language),
+                setting(2, s.pal_set_layout),
+                setting(4, s.pal_set_config_path),
+                setting(5, s.pal_set_ai),
+            ],
+            ModuleTab::HelpAbout => Vec::new(),
+            tab => self.module(tab).map(|m| m.commands(s)).unwrap_or_default(),
+        }
+    }
 
-                // Sub-tab keys always go to module
-                if matches!(key.code, KeyCode::Char('[') | KeyCode::Char(']')) {
-                    let lang = self.config.language;
-                    self.errors.handle_key(key, lang)?;
-                    return Ok(true);
-                }
+    /// Contents of the `?` overlay for the active tab
+    pub fn key_help_contents(&self) -> KeyHelp {
+        let tab = self.active_tab;
+        let sub_tab = self.sub_tab_position(tab).map(|(current, _)| current);
+        let title = match self.sub_tab_label(tab) {
+            Some(sub) => format!("{} › {}", tab.label(self), sub),
+            None => tab.label(self).to_string(),
+        };
+        KeyHelp::new(title, self.palette_commands(tab), sub_tab, &self.keymap)
+    }
 
-                // Tab-switch keys, quit, and Tab (sidebar) stay global
-                match key.code {
-                    KeyCode::Char('1'..='9')
-                    | KeyCode::Char('0')
-                    | KeyCode::Char(',')
-                    | KeyCode::Char('?')
-                    | KeyCode::Char('q') => Ok(false),
-                    _ => {
-                        let lang = self.config.language;
-                        self.errors.handle_key(key, lang)?;
-
-                        // Check if AI analysis was requested
-                        if self.errors.ai_requested {
-                            self.errors.ai_requested = false;
-                            self.handle_ai_request();
-                        }
+    fn sub_tab_label(&self, tab: ModuleTab) -> Option<&'static str> {
+        self.module(tab)?.sub_tab_label(self.config.language)
+    }
 
-                        Ok(true)
-                    }
-                }
+    /// Switch to the entry's module and sub-tab, then replay its keys
+    fn run_palette_entry(&mut self, entry: PaletteEntry) -> Result<()> {
+        self.active_tab = entry.tab;
+        self.intros_dismissed.insert(entry.tab.index());
+        self.ensure_active_tab_loaded();
+        // An open search or popup in the target module would swallow the keys
+        if self.module_capturing(entry.tab) {
+            return Ok(());
+        }
+        if let (Some(target), Some((current, count))) =
+            (entry.command.sub_tab, self.sub_tab_position(entry.tab))
+        {
+            for _ in 0..(target + count - current) % count {
+                self.dispatch_key(KeyEvent::from(KeyCode::Char(']')))?;
             }
-            ModuleTab::Services => {
-                let svc = &self.services;
+        }
+        for code in entry.command.keys {
+            self.dispatch_key(KeyEvent::from(code))?;
+        }
+        Ok(())
+    }
 
-                // Module captures ALL keys when search active or popup open
-                let has_popup = !matches!(svc.popup, crate::modules::services::SvcPopupState::None);
-                let search_active = svc.search_active;
+    /// Whether the module takes every key right now (text input, popup, form)
+    fn module_capturing(&self, tab: ModuleTab) -> bool {
+        match tab {
+            ModuleTab::Settings | ModuleTab::HelpAbout => self.settings_editing,
+            _ => self.module(tab).is_some_and(|m| m.captures_input()),
+        }
+    }
 
-                if has_popup || search_active {
-                    self.services.handle_key(key)?;
-                    return Ok(true);
-                }
+    /// Unit file viewer → Options Explorer on the module behind the unit
+    fn take_services_option(&mut self) {
+        let Some(namespaces) = self.services.pending_option.take() else {
+            return;
+        };
+        if let Some(ns) = self.options.pick_namespace(&namespaces).cloned() {
+            self.active_tab = ModuleTab::Options;
+            self.options.reveal_option(&ns);
+        }
+    }
 
-                // Sub-tab keys always go to module
-                if matches!(key.code, KeyCode::Char('[') | KeyCode::Char(']')) {
-                    self.services.handle_key(key)?;
-                    return Ok(true);
-                }
+    /// (current sub-tab index, sub-tab count) of a module
+    fn sub_tab_position(&self, tab: ModuleTab) -> Option<(usize, usize)> {
+        self.module(tab)?.sub_tab()
+    }
 
-                // Tab-switch keys, quit, and Tab (sidebar) stay global
-                match key.code {
-                    KeyCode::Char('1'..='9')
-                    | KeyCode::Char('0')
-                    | KeyCode::Char(',')
-                    | KeyCode::Char('?')
-                    | KeyCode::Char('q') => Ok(false),
-                    _ => {
-                        self.services.handle_key(key)?;
-                        Ok(true)
-                    }
-                }
+    /// Switch a module to its `index`-th sub-tab; the Error Translator's
+    /// submit form isn't reopened
+    fn set_sub_tab(&mut self, tab: ModuleTab, index: usize) {
+        use crate::modules::{
+            config_showcase::CfgSubTab, errors::ErrSubTab, flake_inputs::FlakeSubTab,
+            generations::GenSubTab, health::HealthSubTab, options::OptSubTab, packages::PkgSubTab,
+            rebuild::RebuildSubTab, services::SvcSubTab, storage::StoSubTab,
+        };
+        fn pick<T: Copy>(all: &[T], index: usize, current: &mut T) {
+            if let Some(tab) = all.get(index) {
+                *current = *tab;
             }
-            ModuleTab::Storage => {
-                let sto = &self.storage;
-
-                // Module captures ALL keys when popup open or search active
-                let has_popup = !matches!(sto.popup, crate::modules::storage::StoPopupState::None);
-                let search_active = sto.explorer_search_active;
+        }
+        match tab {
+            ModuleTab::Generations => pick(
+                GenSubTab::all(),
+                index,
+                &mut self.generations.active_sub_tab,
+            ),
+            ModuleTab::Errors if ErrSubTab::all().get(index) != Some(&ErrSubTab::Submit) => {
+                pick(ErrSubTab::all(), index, &mut self.errors.active_sub_tab)
+            }
+            ModuleTab::Services => pick(SvcSubTab::all(), index, &mut self.services.active_sub_tab),
+            ModuleTab::Storage => pick(StoSubTab::all(), index, &mut self.storage.active_sub_tab),
+            ModuleTab::Config => pick(
+                CfgSubTab::all(),
+                index,
+                &mut self.config_showcase.active_sub_tab,
+            ),
+            ModuleTab::Options => pick(OptSubTab::all(), index, &mut self.options.sub_tab),
+            ModuleTab::Rebuild => pick(RebuildSubTab::all(), index, &mut self.rebuild.sub_tab),
+            ModuleTab::FlakeInputs => {
+                pick(FlakeSubTab::all(), index, &mut self.flake_inputs.sub_tab)
+            }
+            ModuleTab::Health => pick(HealthSubTab::all(), index, &mut self.health.sub_tab),
+            ModuleTab::Packages => pick(PkgSubTab::all(), index, &mut self.packages.sub_tab),
+            _ => {}
+        }
+    }
 
-                if has_popup || search_active {
-                    self.storage.handle_key(key)?;
-                    return Ok(true);
-                }
+    /// Lazy-load data for the active tab (skipped while Nix is unavailable)
+    fn ensure_active_tab_loaded(&mut self) {
+        if self.degraded_problem(self.active_tab).is_some() {
+            return;
+        }
 
-                // Sub-tab keys always go to module
-                if matches!(key.code, KeyCode::Char('[') | KeyCode::Char(']')) {
-                    self.storage.handle_key(key)?;
-                    return Ok(true);
-                }
+        // Lazy-load what the tab shows; Packages needs its source first
+        if self.active_tab == ModuleTab::Packages {
+            self.packages
+                .ensure_source_detected(&self.config.nixpkgs_channel);
+        }
+        if let Some(module) = self.module_mut(self.active_tab) {
+            module.activate();
+        }
 
-                // Tab-switch keys, quit, and Tab (sidebar) stay global
-                match key.code {
-                    KeyCode::Char('1'..='9')
-                    | KeyCode::Char('0')
-                    | KeyCode::Char(',')
-                    | KeyCode::Char('?')
-                    | KeyCode::Char('q') => Ok(false),
-                    _ => {
-                        self.storage.handle_key(key)?;
-                        Ok(true)
-                    }
-                }
+        // Cached data past its TTL is reloaded when coming back to the tab
+        if let Some((source, loaded_at)) = self.active_data_source() {
+            if self.config.refresh.get(source).is_stale(loaded_at) {
+                self.refresh_source(source);
             }
-            ModuleTab::Config => {
-                // Sub-tab keys always go to module
-                if matches!(key.code, KeyCode::Char('[') | KeyCode::Char(']')) {
-                    self.config_showcase.handle_key(key)?;
-                    return Ok(true);
-                }
+        }
+    }
 
-                match key.code {
-                    KeyCode::Char('1'..='9')
-                    | KeyCode::Char('0')
-                    | KeyCode::Char(',')
-                    | KeyCode::Char('?')
-                    | KeyCode::Char('q') => Ok(false),
-                    _ => {
-                        self.config_showcase.handle_key(key)?;
-                        Ok(true)
-                    }
-                }
+    /// Data source shown by the active tab, and when it was loaded
+    pub fn active_data_source(&self) -> Option<(DataSource, Instant)> {
+        use crate::modules::services::SvcSubTab;
+        let (source, loaded_at) = match self.active_tab {
+            ModuleTab::Services if self.services.active_sub_tab == SvcSubTab::Ports => {
+                (DataSource::Ports, self.services.loaded_at)
             }
-            ModuleTab::Packages => {
-                let pkg = &self.packages;
+            ModuleTab::Services => (DataSource::Services, self.services.loaded_at),
+            ModuleTab::FlakeInputs => (DataSource::FlakeInputs, self.flake_inputs.loaded_at),
+            ModuleTab::Options => (DataSource::Options, self.options.loaded_at),
+            ModuleTab::Storage => (DataSource::Storage, self.storage.loaded_at),
+            _ => return None,
+        };
+        Some((source, loaded_at?))
+    }
 
-                // Module captures ALL keys when search active or detail open
-                if pkg.search_active || pkg.detail_open {
-                    self.packages.handle_key(key)?;
-                    return Ok(true);
-                }
+    /// Age of the active tab's data if it is older than its TTL
+    pub fn stale_age(&self) -> Option<Duration> {
+        let (source, loaded_at) = self.active_data_source()?;
+        self.config
+            .refresh
+            .get(source)
+            .is_stale(loaded_at)
+            .then(|| loaded_at.elapsed())
+    }
 
-                match key.code {
-                    KeyCode::Char('1'..='9')
-                    | KeyCode::Char('0')
-                    | KeyCode::Char(',')
-                    | KeyCode::Char('?')
-                    | KeyCode::Char('q') => Ok(false),
-                    _ => {
-                        self.packages.handle_key(key)?;
-                        Ok(true)
-                    }
-                }
-            }
-            ModuleTab::Health => {
-                // Sub-tab keys always go to module
-                if matches!(key.code, KeyCode::Char('[') | KeyCode::Char(']')) {
-                    self.health.handle_key(key)?;
-                    return Ok(true);
-                }
+    /// Reload a data source in the background, keeping what's on screen
+    fn refresh_source(&mut self, source: DataSource) {
+        match source {
+            DataSource::Services | DataSource::Ports => self.services.refresh_in_background(),
+            DataSource::FlakeInputs => self.flake_inputs.refresh_in_background(),
+            DataSource::Options => self.options.refresh_in_background(),
+            DataSource::Storage => self.storage.refresh_in_background(),
+        }
+    }
 
-                match key.code {
-                    KeyCode::Char('1'..='9')
-                    | KeyCode::Char('0')
-                    | KeyCode::Char(',')
-                    | KeyCode::Char('?')
-                    | KeyCode::Char('q') => Ok(false),
-                    _ => {
-                        self.health.handle_key(key)?;
-                        Ok(true)
-                    }
-                }
+    /// Why a module can't work right now (None = available)
+    pub fn degraded_problem(&self, tab: ModuleTab) -> Option<NixProblem> {
+        if tab.nixos_only() && !self.nix.platform.is_nixos() {
+            return Some(NixProblem::NotNixOS);
+        }
+        let need = match tab {
+            ModuleTab::Generations => NixNeed::Store,
+            ModuleTab::Storage
+            | ModuleTab::Options
+            | ModuleTab::FlakeInputs
+            | ModuleTab::Packages => NixNeed::Query,
+            ModuleTab::Rebuild => NixNeed::Build,
+            _ => return None,
+        };
+        self.nix.blocking_problem(need)
+    }
+
+    /// Attention count shown next to a module in the sidebar, so problems
+    /// are visible without opening the module (None = nothing to report)
+    pub fn module_badge(&self, tab: ModuleTab) -> Option<usize> {
+        self.module(tab)?.badge()
+    }
+
+    /// Re-probe Nix (e.g. after the user started the daemon)
+    pub fn retry_nix_detection(&mut self) {
+        let was_ok = self.nix.is_ok();
+        self.nix = NixAvailability::probe();
+        let s = i18n::get_strings(self.config.language);
+
+        if self.nix.is_ok() {
+            if !was_ok {
+                // Generations were read once at startup; read them again
+                let _ = self.generations.refresh_generations();
             }
-            ModuleTab::Rebuild => {
-                let rb = &self.rebuild;
+            self.flash_message = Some(FlashMessage::new(s.nix_retry_ok.to_string(), false));
+            self.ensure_active_tab_loaded();
+        } else {
+            self.flash_message = Some(FlashMessage::new(s.nix_retry_failed.to_string(), true));
+        }
+    }
 
-                // Module captures ALL keys when popup open or search active
-                let has_popup = rb.popup != crate::modules::rebuild::RebuildPopup::None;
-                let search_active = rb.log_search_active;
+    fn try_module_key(&mut self, key: KeyEvent) -> Result<bool> {
+        let tab = self.active_tab;
+        let lang = self.config.language;
+        let Some(module) = self.module_mut(tab) else {
+            return Ok(false);
+        };
 
-                if has_popup || search_active {
-                    self.rebuild.handle_key(key)?;
-                    return Ok(true);
-                }
+        // Tab-switch keys and quit stay global, unless the module takes
+        // every key (popup, search, text input)
+        let global = matches!(
+            key.code,
+            KeyCode::Char('1'..='9')
+                | KeyCode::Char('0')
+                | KeyCode::Char(',')
+                | KeyCode::Char('?')
+                | KeyCode::Char('q')
+                | KeyCode::F(_)
+        );
+        if global && !module.captures_input() {
+            return Ok(false);
+        }
+        module.handle_key(key, lang)?;
+        self.after_module_key(tab);
+        Ok(true)
+    }
 
-                // Sub-tab keys always go to module
-                if matches!(key.code, KeyCode::Char('[') | KeyCode::Char(']')) {
-                    self.rebuild.handle_key(key)?;
-                    return Ok(true);
+    /// What a module's key asked of other modules or the config
+    fn after_module_key(&mut self, tab: ModuleTab) {
+        match tab {
+            ModuleTab::Errors => {
+                // Check if AI analysis was requested
+                if self.errors.ai_requested {
+                    self.errors.ai_requested = false;
+                    self.handle_ai_request();
                 }
-
-                match key.code {
-                    KeyCode::Char('1'..='9')
-                    | KeyCode::Char('0')
-                    | KeyCode::Char(',')
-                    | KeyCode::Char('?')
-                    | KeyCode::Char('q') => Ok(false),
-                    _ => {
-                        self.rebuild.handle_key(key)?;
-                        Ok(true)
-                    }
+                if self.errors.explain_requested {
+                    self.errors.explain_requested = false;
+                    self.handle_explain_request();
                 }
             }
-            ModuleTab::Options => {
-                let opt = &self.options;
-
-                // Module captures ALL keys when search active or detail open
-                if opt.search_active || opt.detail_open {
-                    self.options.handle_key(key)?;
-                    return Ok(true);
+            ModuleTab::Services => self.take_services_option(),
+            // Persist budget target changes
+            ModuleTab::Storage
+                if self.storage.free_target_gb != self.config.storage_free_target_gb =>
+            {
+                self.config.storage_free_target_gb = self.storage.free_target_gb;
+                let _ = self.config.save();
+            }
+            ModuleTab::Config => {
+                // Drill-down from the Services map
+                if let Some(path) = self.config_showcase.pending_option.take() {
+                    self.active_tab = ModuleTab::Options;
+                    self.options.reveal_option(&path);
                 }
-
-                // Sub-tab keys always go to module
-                if matches!(key.code, KeyCode::Char('[') | KeyCode::Char(']')) {
-                    self.options.handle_key(key)?;
-                    return Ok(true);
+            }
+            ModuleTab::Health => {
+                // Failed flake evaluation → Error Translator
+                if let Some(text) = self.health.pending_error.take() {
+                    self.active_tab = ModuleTab::Errors;
+                    self.errors
+                        .load_text(text, Some("nix eval".into()), self.config.language);
                 }
-
-                match key.code {
-                    KeyCode::Char('1'..='9')
-                    | KeyCode::Char('0')
-                    | KeyCode::Char(',')
-                    | KeyCode::Char('?')
-                    | KeyCode::Char('q') => Ok(false),
-                    _ => {
-                        self.options.handle_key(key)?;
-                        Ok(true)
-                    }
+            }
+            ModuleTab::Rebuild => {
+                self.save_rebuild_host();
+                // Warning about an option → Options Explorer
+                if let Some(path) = self.rebuild.pending_option.take() {
+                    self.active_tab = ModuleTab::Options;
+                    self.options.reveal_option(&path);
                 }
             }
             ModuleTab::FlakeInputs => {
-                let fi = &self.flake_inputs;
-
-                // Module captures ALL keys when popup is showing
-                if fi.popup != crate::modules::flake_inputs::FlakePopup::None {
-                    self.flake_inputs.handle_key(key)?;
-                    return Ok(true);
+                self.save_flake_projects();
+                // "Update all and rebuild" continues in the Rebuild tab
+                if let Some(mode) = self.flake_inputs.pending_rebuild.take() {
+                    self.active_tab = ModuleTab::Rebuild;
+                    self.rebuild.prefill(mode);
                 }
+            }
+            _ => {}
+        }
+    }
 
-                // Sub-tab keys always go to module
-                if matches!(key.code, KeyCode::Char('[') | KeyCode::Char(']')) {
-                    self.flake_inputs.handle_key(key)?;
-                    return Ok(true);
-                }
+    /// Queue what modules confirmed and run what's past its undo window
+    fn update_staged(&mut self) {
+        if let Some((label, kind)) = self.generations.pending_staged.take() {
+            self.staged.push(label, kind);
+        }
+        if let Some((label, kind)) = self.storage.pending_staged.take() {
+            self.staged.push(label, kind);
+        }
+        for staged in self.staged.take_due() {
+            self.run_staged(staged);
+        }
+    }
 
-                match key.code {
-                    KeyCode::Char('1'..='9')
-                    | KeyCode::Char('0')
-                    | KeyCode::Char(',')
-                    | KeyCode::Char('?')
-                    | KeyCode::Char('q') => Ok(false),
-                    _ => {
-                        self.flake_inputs.handle_key(key)?;
-                        Ok(true)
-                    }
-                }
+    fn run_staged(&mut self, staged: Staged) {
+        match staged.kind {
+            StagedKind::DeleteGenerations { profile, ids } => {
+                self.generations.run_staged_delete(profile, &ids)
             }
-            _ => Ok(false),
+            StagedKind::Clean(action) => self.storage.execute_action(action),
+            StagedKind::BudgetStep(kind) => self.storage.run_staged_budget_step(kind),
+            StagedKind::Suggestions(batch) => self.storage.run_staged_suggestions(batch),
+            StagedKind::BootOrphans(files) => self.storage.run_staged_boot_clean(files),
+        }
+    }
+
+    /// On quit: what was confirmed still happens
+    /// Put up a note for the staged actions `flush_staged` is about to run;
+    /// false if there are none
+    pub fn show_flush_progress(&mut self) -> bool {
+        let count = self.staged.len();
+        if count == 0 {
+            return false;
+        }
+        let s = i18n::get_strings(self.config.language);
+        self.popup = PopupState::Loading {
+            message: s.staged_flushing.replace("{}", &count.to_string()),
+        };
+        true
+    }
+
+    pub fn flush_staged(&mut self) {
+        self.update_staged();
+        for staged in self.staged.take_all() {
+            // One cleanup after the other, as they would have run
+            self.storage.wait_for_cleanups();
+            self.run_staged(staged);
+        }
+        self.storage.wait_for_cleanups();
+    }
+
+    /// Whether the screen changes without input right now: spinners and
+    /// live output of running jobs, a rebuild, the splash animations
+    pub fn animating(&self) -> bool {
+        !self.jobs.running().is_empty()
+            || self.rebuild.is_running()
+            || self.rebuild.detaching()
+            || self.welcome.active
+            || self.start.active
+    }
+
+    /// How long the main loop waits for input: short while something
+    /// animates, longer when idle so an open nixmate costs next to no CPU
+    pub fn poll_interval(&self) -> Duration {
+        if self.animating() {
+            Duration::from_millis(50)
+        } else {
+            Duration::from_millis(250)
         }
     }
 
     pub fn update_timers(&mut self) -> Result<()> {
-        self.generations.update_undo_timer()?;
+        self.update_staged();
 
         // Poll background loaders (non-blocking)
-        self.services.poll_load();
-        self.storage.poll_load();
-        self.errors.poll_ai();
-        self.config_showcase.poll_scan();
-        self.packages.poll_search();
-        self.health.poll_scan();
-        self.options.poll_load();
-        self.flake_inputs.poll_load();
-        self.rebuild.poll_detect();
-        self.rebuild.poll_build();
+        let active = self.active_tab;
+        for tab in self.tabs() {
+            if let Some(module) = self.module_mut(tab) {
+                module.poll(tab == active);
+            }
+        }
+        self.sync_jobs();
+        self.vitals.tick();
+        self.poll_custom_theme();
+        self.poll_config_file();
+
+        // Auto-refresh only what's visible
+        if let Some((source, loaded_at)) = self.active_data_source() {
+            if self.config.refresh.get(source).refresh_due(loaded_at)
+                && self.degraded_problem(self.active_tab).is_none()
+            {
+                self.refresh_source(source);
+            }
+        }
+
+        if self.quit_after_build && !self.rebuild.is_running() {
+            self.should_quit = true;
+        }
+
+        if let Some(kiosk) = self.kiosk.as_mut() {
+            if kiosk.should_quit() {
+                self.should_quit = true;
+            } else if kiosk.rotation_due() {
+                self.active_tab = kiosk.step(true);
+                self.ensure_active_tab_loaded();
+            }
+        }
 
         // Expire flash messages across all modules
-        expire_flash(&mut self.generations.flash_message);
-        expire_flash(&mut self.errors.flash_message);
-        expire_flash(&mut self.services.flash_message);
-        expire_flash(&mut self.storage.flash_message);
-        expire_flash(&mut self.config_showcase.flash_message);
-        expire_flash(&mut self.packages.flash_message);
-        expire_flash(&mut self.health.flash_message);
-        expire_flash(&mut self.options.flash_message);
-        expire_flash(&mut self.flake_inputs.flash_message);
-        expire_flash(&mut self.rebuild.flash_message);
+        for tab in self.tabs() {
+            if let Some(module) = self.module_mut(tab) {
+                expire_flash(module.flash_message());
+            }
+        }
 
         Ok(())
     }
@@ -661,6 +1507,60 @@ impl App {
         Ok(())
     }
 
+    /// Take the interactive command a module wants to run with the TUI suspended
+    pub fn take_external_command(&mut self) -> Option<ExternalCommand> {
+        let (source, cmd) = if let Some(cmd) = self.rebuild.pending_external.take() {
+            (ModuleTab::Rebuild, cmd)
+        } else if let Some(cmd) = self.errors.pending_external.take() {
+            (ModuleTab::Errors, cmd)
+        } else if let Some(cmd) = self.generations.pending_external.take() {
+            (ModuleTab::Generations, cmd)
+        } else {
+            return None;
+        };
+        self.external_source = Some(source);
+        Some(cmd)
+    }
+
+    /// Report the outcome of a command from `take_external_command`
+    pub fn external_finished(&mut self, success: bool) {
+        let s = i18n::get_strings(self.config.language);
+        match self.external_source.take() {
+            Some(ModuleTab::Errors) => {
+                if !success {
+                    self.errors.show_flash(s.err_editor_failed, true);
+                }
+                return;
+            }
+            Some(ModuleTab::Generations) => {
+                if !success {
+                    self.generations.flash_message =
+                        Some(FlashMessage::new(s.gen_git_diff_failed.to_string(), true));
+                }
+                return;
+            }
+            _ => {}
+        }
+        if let Some(unit) = self.rebuild.pending_detach.take() {
+            self.rebuild.detach_finished(&unit, success);
+            // The build lives on in its unit; nothing keeps nixmate open
+            self.should_quit = success;
+            return;
+        }
+        if !success {
+            self.rebuild.flash_message =
+                Some(FlashMessage::new(s.rb_repl_failed.to_string(), true));
+        }
+    }
+
+    /// The terminal went away (SIGHUP): save a running build if possible
+    /// and run the staged actions, as quitting would
+    pub fn handle_hangup(&mut self) {
+        self.rebuild.detach_on_hangup();
+        self.flush_staged();
+        self.should_quit = true;
+    }
+
     /// Clean up images before exiting (prevents ghost images in terminal)
     /// Called BEFORE LeaveAlternateScreen so the terminal can process the
     /// delete commands while still in the alternate screen buffer.
@@ -675,23 +1575,11 @@ impl App {
 
     /// Check if the module intro page is showing for the current tab
     pub fn is_intro_showing(&self) -> bool {
-        matches!(
-            self.active_tab,
-            ModuleTab::Generations
-                | ModuleTab::Errors
-                | ModuleTab::Services
-                | ModuleTab::Storage
-                | ModuleTab::Config
-                | ModuleTab::Options
-                | ModuleTab::Rebuild
-                | ModuleTab::FlakeInputs
-                | ModuleTab::Packages
-                | ModuleTab::Health
-        ) && !self.intros_dismissed.contains(&self.active_tab.index())
+        self.active_tab.is_module() && !self.intros_dismissed.contains(&self.active_tab.index())
     }
 
     fn handle_settings_key(&mut self, key: KeyEvent) -> Result<()> {
-        let settings_count = 11; // 3 global + 1 pkg search + 1 path + 6 error translator/AI
+        let settings_count = SETTINGS_COUNT;
         match key.code {
             KeyCode::Char('j') | KeyCode::Down => {
                 if self.settings_selected < settings_count - 1 {
@@ -704,8 +1592,7 @@ impl App {
             KeyCode::Enter | KeyCode::Char('l') | KeyCode::Right => {
                 match self.settings_selected {
                     0 => {
-                        self.config.theme = self.config.theme.next();
-                        self.theme = Theme::from_name(self.config.theme);
+                        self.next_theme();
                     }
                     1 => {
                         self.config.language = self.config.language.next();
@@ -771,6 +1658,14 @@ impl App {
                         self.settings_edit_buffer = String::new();
                         return Ok(());
                     }
+                    // Per data source TTL / auto-refresh
+                    11..=15 => {
+                        let source = DataSource::all()[self.settings_selected - 11];
+                        self.settings_editing = true;
+                        self.settings_edit_buffer =
+                            self.config.refresh.get(source).to_edit_string();
+                        return Ok(());
+                    }
                     _ => {}
                 }
                 let s = i18n::get_strings(self.config.language);
@@ -858,6 +1753,22 @@ impl App {
                     10 => {
                         self.config.github_token =
                             if value.is_empty() { None } else { Some(value) };
+                        self.flake_inputs.github_token = self.config.github_token.clone();
+                    }
+                    11..=15 => {
+                        let source = DataSource::all()[self.settings_selected - 11];
+                        match crate::config::SourceTiming::parse(&value) {
+                            Some(timing) => *self.config.refresh.get_mut(source) = timing,
+                            None => {
+                                // Keep editing so the value can be fixed
+                                let s = i18n::get_strings(self.config.language);
+                                self.flash_message = Some(FlashMessage::new(
+                                    s.settings_refresh_invalid.to_string(),
+                                    true,
+                                ));
+                                return Ok(());
+                            }
+                        }
                     }
                     _ => {}
                 }
@@ -904,7 +1815,7 @@ impl App {
         }
 
         let lang_str = match self.config.language {
-            crate::config::Language::English => "en",
+            crate::config::Language::English | crate::config::Language::Locale(_) => "en",
             crate::config::Language::German => "de",
         };
 
@@ -919,6 +1830,20 @@ impl App {
             lang_str,
         );
     }
+
+    /// Start the opt-in "explain with AI" panel next to the translation
+    fn handle_explain_request(&mut self) {
+        if !self.config.ai_explain.available() {
+            let s = i18n::get_strings(self.config.language);
+            self.errors.show_flash(s.err_explain_disabled, true);
+            return;
+        }
+        let lang_str = match self.config.language {
+            crate::config::Language::English | crate::config::Language::Locale(_) => "en",
+            crate::config::Language::German => "de",
+        };
+        self.errors.start_explain(&self.config.ai_explain, lang_str);
+    }
 }
 
 /// Expire a flash message after 3 seconds
@@ -948,10 +1873,26 @@ impl App {
 
     fn sync_config_path_to_modules(&mut self) {
         let cp = self.config.config_path.clone();
-        self.rebuild.config_path = cp.clone();
+        if self.rebuild.config_path != cp {
+            self.rebuild.config_path = cp.clone();
+            self.rebuild.redetect();
+        }
         self.config_showcase.config_path = cp.clone();
+        self.generations.set_config_path(cp.clone());
         self.flake_inputs.config_path = cp.clone();
         self.options.config_path = cp.clone();
+        self.health.config_path = cp.clone();
+        self.errors.config_path = cp.clone();
         self.packages.config_path = cp;
     }
+
+    /// The `nixosConfigurations` attribute every module builds and evaluates
+    fn sync_nixos_host_to_modules(&mut self) {
+        let host = self.config.nixos_host.clone().filter(|h| !h.is_empty());
+        self.rebuild.nixos_host = host.clone();
+        self.options.nixos_host = host.clone();
+        self.health.nixos_host = host.clone();
+        self.config_showcase.nixos_host = host.clone();
+        self.errors.nixos_host = host;
+    }
 }
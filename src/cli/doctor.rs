@@ -0,0 +1,199 @@
+//! `nixmate doctor` — run the Nix Doctor checks without the TUI
+//!
+//! Same checks as the Nix Doctor module. With `--check` the exit code
+//! reflects the result (1 if any check fails), for systemd timers and CI.
+//! Checks that need NixOS are reported as `skipped` on other systems and
+//! count neither for the score nor for the exit code.
+
+use super::output::{Cell, Color, Output, Table};
+use crate::config::Config;
+use crate::i18n::Strings;
+use crate::modules::health::checks::{self, CheckContext, CheckResult, FixAction};
+use crate::modules::health::{trend, Severity};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+
+/// Stable JSON shape; `name`, `detail` and `hint` follow the configured language
+#[derive(Serialize)]
+struct ReportJson<'a> {
+    score: u8,
+    status: &'static str,
+    checks: Vec<CheckJson<'a>>,
+}
+
+#[derive(Serialize)]
+struct CheckJson<'a> {
+    id: &'static str,
+    name: &'a str,
+    category: &'static str,
+    status: &'static str,
+    detail: &'a str,
+    hint: Option<&'a str>,
+    fix_command: Option<&'a str>,
+    fix_snippet: Option<&'a str>,
+}
+
+pub fn run(out: &Output, check: bool) -> Result<i32> {
+    let config = Config::load().context("Failed to load configuration")?;
+    let ctx = Arc::new(CheckContext {
+        lang: config.language,
+        config_path: config.config_path.clone(),
+        nixos_host: config.nixos_host.clone(),
+        flake_watch: config.flake_watch.clone(),
+        accounts: Mutex::new(None),
+        eval_error: Mutex::new(None),
+    });
+    let mut results = checks::run_all(ctx);
+    results.sort_by_key(|c| c.order);
+
+    let worst = results
+        .iter()
+        .filter(|c| !c.skipped)
+        .map(|c| c.severity)
+        .max()
+        .unwrap_or(Severity::Pass);
+    let score = checks::score(&results);
+    // Timer runs feed the trend shown on the TUI start screen
+    trend::record(score);
+
+    if out.is_json() {
+        out.print_json(&ReportJson {
+            score,
+            status: status_word(worst),
+            checks: results.iter().map(to_json).collect(),
+        })?;
+    } else {
+        print_report(
+            out,
+            &results,
+            score,
+            crate::i18n::get_strings(config.language),
+        );
+    }
+
+    Ok(if check && worst == Severity::Fail {
+        1
+    } else {
+        0
+    })
+}
+
+fn print_report(out: &Output, results: &[CheckResult], score: u8, s: &Strings) {
+    let mut table = Table::new(&["STATUS", "CATEGORY", "CHECK", "DETAIL"]);
+    for c in results {
+        let (symbol, color) = match c.severity {
+            _ if c.skipped => ("– skip", Color::Dim),
+            Severity::Pass => ("✓ pass", Color::Green),
+            Severity::Warn => ("⚠ warn", Color::Yellow),
+            Severity::Fail => ("✗ fail", Color::Red),
+        };
+        table.row(vec![
+            Cell::colored(out.mark(symbol, check_status(c)), color),
+            Cell::new(c.category.label(s)),
+            Cell::new(c.name.as_str()),
+            Cell::new(c.detail.as_str()),
+        ]);
+    }
+    out.print_table(&table);
+
+    // What to do about everything that didn't pass
+    let todo: Vec<&CheckResult> = results
+        .iter()
+        .filter(|c| !c.skipped && c.severity != Severity::Pass)
+        .collect();
+    if !todo.is_empty() {
+        println!();
+        for c in todo {
+            println!("{}", out.bold(&c.name));
+            if let Some(hint) = &c.fix_description {
+                println!("  {}", hint);
+            }
+            match &c.fix {
+                Some(FixAction::Run(cmd)) => {
+                    println!("  {}", out.paint(&format!("$ {}", cmd), Color::Dim))
+                }
+                Some(FixAction::Snippet(snippet)) => {
+                    for line in snippet.lines() {
+                        println!("  {}", out.paint(line, Color::Dim));
+                    }
+                }
+                None => {}
+            }
+        }
+    }
+
+    println!();
+    println!("{} {}%", out.bold("Health score:"), score);
+}
+
+fn status_word(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Pass => "pass",
+        Severity::Warn => "warn",
+        Severity::Fail => "fail",
+    }
+}
+
+fn check_status(c: &CheckResult) -> &'static str {
+    if c.skipped {
+        "skipped"
+    } else {
+        status_word(c.severity)
+    }
+}
+
+fn to_json(c: &CheckResult) -> CheckJson<'_> {
+    let (fix_command, fix_snippet) = match &c.fix {
+        Some(FixAction::Run(cmd)) => (Some(cmd.as_str()), None),
+        Some(FixAction::Snippet(snippet)) => (None, Some(snippet.as_str())),
+        None => (None, None),
+    };
+    CheckJson {
+        id: c.id,
+        name: &c.name,
+        category: c.category.id(),
+        status: check_status(c),
+        detail: &c.detail,
+        hint: c.fix_description.as_deref(),
+        fix_command,
+        fix_snippet,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::health::checks::Category;
+
+    fn result(severity: Severity, skipped: bool) -> CheckResult {
+        CheckResult {
+            order: 0,
+            id: "test",
+            name: String::new(),
+            description: String::new(),
+            category: Category::Boot,
+            severity,
+            detail: String::new(),
+            fix: None,
+            fix_description: None,
+            weight: 10,
+            fixed: false,
+            skipped,
+        }
+    }
+
+    #[test]
+    fn test_skipped_checks() {
+        let skipped = result(Severity::Pass, true);
+        assert_eq!(check_status(&skipped), "skipped");
+        assert_eq!(to_json(&skipped).status, "skipped");
+        assert_eq!(check_status(&result(Severity::Pass, false)), "pass");
+        // Only the warning counts, not the skipped check as a pass
+        assert_eq!(
+            checks::score(&[result(Severity::Warn, false), skipped.clone()]),
+            50
+        );
+        assert_eq!(checks::score(&[skipped]), 100);
+    }
+}
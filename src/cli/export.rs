@@ -0,0 +1,242 @@
+//! `nixmate export <kind>` — a module's data as JSON
+//!
+//! Runs the same collectors as the TUI modules, without the TUI, and
+//! prints one JSON document for scripts and monitoring. Field names are
+//! part of the interface, like the other `--json` output.
+
+use super::generations;
+use super::output::{Output, Style};
+use crate::config::Config;
+use crate::modules::flake_inputs;
+use crate::nix::services::{self, EnableState, PortEntry, RunState, ServiceEntry};
+use crate::nix::storage::{self, DiskUsage};
+use anyhow::{anyhow, bail, Context, Result};
+use serde::Serialize;
+
+/// What can be exported, in the order `--help` lists them
+pub const KINDS: &[&str] = &[
+    "generations",
+    "services",
+    "ports",
+    "flake-inputs",
+    "storage",
+];
+
+#[derive(Serialize)]
+struct ServiceJson<'a> {
+    kind: &'static str,
+    name: &'a str,
+    display_name: &'a str,
+    status: &'static str,
+    enabled: &'static str,
+    description: &'a str,
+    pid: Option<u32>,
+    /// Bytes
+    memory: Option<u64>,
+    uptime: Option<&'a str>,
+    ports: &'a [u16],
+    socket: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct PortJson<'a> {
+    protocol: &'a str,
+    port: u16,
+    address: &'a str,
+    process: &'a str,
+    pid: Option<u32>,
+    /// Service or container behind the port, None if unknown
+    owner: Option<&'a str>,
+    owner_kind: Option<&'static str>,
+}
+
+#[derive(Serialize)]
+struct FlakeJson<'a> {
+    flake: &'a str,
+    inputs: Vec<InputJson<'a>>,
+}
+
+#[derive(Serialize)]
+struct InputJson<'a> {
+    name: &'a str,
+    #[serde(rename = "type")]
+    input_type: &'a str,
+    url: &'a str,
+    branch: Option<&'a str>,
+    rev: &'a str,
+    last_modified: String,
+    age_days: u64,
+    follows: &'a [String],
+}
+
+#[derive(Serialize)]
+struct StorageJson<'a> {
+    total_paths: usize,
+    live_paths: usize,
+    dead_paths: usize,
+    /// Byte sizes; null when nix couldn't report them
+    total_size: Option<u64>,
+    live_size: Option<u64>,
+    dead_size: Option<u64>,
+    disks: Vec<DiskJson<'a>>,
+}
+
+#[derive(Serialize)]
+struct DiskJson<'a> {
+    role: &'static str,
+    mount_point: &'a str,
+    filesystem: &'a str,
+    total: u64,
+    used: u64,
+    available: u64,
+    percent: f64,
+}
+
+/// The one kind named in `args`; flags were checked by the dispatcher
+fn kind(args: &[String]) -> Result<&str> {
+    let kinds: Vec<&String> = args.iter().filter(|a| !a.starts_with("--")).collect();
+    match kinds.as_slice() {
+        [kind] if KINDS.contains(&kind.as_str()) => Ok(kind.as_str()),
+        [kind] => bail!("unknown export `{}` (one of: {})", kind, KINDS.join(", ")),
+        [] => bail!("export needs one of: {}", KINDS.join(", ")),
+        _ => bail!("export takes a single kind"),
+    }
+}
+
+pub fn run(args: &[String]) -> Result<i32> {
+    let kind = kind(args)?;
+    let out = Output { style: Style::Json };
+    match kind {
+        "generations" => generations::run(&out),
+        "services" => {
+            let (entries, _, _) = services::load_dashboard()?;
+            let json: Vec<ServiceJson> = entries.iter().map(service_json).collect();
+            out.print_json(&json)?;
+            Ok(0)
+        }
+        "ports" => {
+            let (_, ports, _) = services::load_dashboard()?;
+            let json: Vec<PortJson> = ports.iter().map(port_json).collect();
+            out.print_json(&json)?;
+            Ok(0)
+        }
+        "flake-inputs" => {
+            let config = Config::load().context("Failed to load configuration")?;
+            let (flake, inputs) =
+                flake_inputs::load_inputs(config.language, config.config_path.as_deref())
+                    .map_err(|e| anyhow!(e))?;
+            out.print_json(&FlakeJson {
+                flake: &flake,
+                inputs: inputs.iter().map(input_json).collect(),
+            })?;
+            Ok(0)
+        }
+        "storage" => {
+            let info = storage::load_store_info();
+            let size = |bytes: u64| info.has_sizes.then_some(bytes);
+            let disks = [("store", &info.disk_store), ("root", &info.disk_root)]
+                .into_iter()
+                .filter_map(|(role, disk)| disk.as_ref().map(|d| disk_json(role, d)))
+                .collect();
+            out.print_json(&StorageJson {
+                total_paths: info.total_paths,
+                live_paths: info.live_paths,
+                dead_paths: info.dead_paths,
+                total_size: size(info.total_size),
+                live_size: size(info.live_size),
+                dead_size: size(info.dead_size),
+                disks,
+            })?;
+            Ok(0)
+        }
+        _ => unreachable!("checked by kind()"),
+    }
+}
+
+fn service_json(e: &ServiceEntry) -> ServiceJson<'_> {
+    ServiceJson {
+        kind: e.kind.label(),
+        name: &e.name,
+        display_name: &e.display_name,
+        status: status_word(e.status),
+        enabled: match e.enabled {
+            EnableState::NotApplicable => "n/a",
+            EnableState::Unknown => "unknown",
+            other => other.as_str(),
+        },
+        description: &e.description,
+        pid: e.pid,
+        memory: e.memory,
+        uptime: e.uptime.as_deref(),
+        ports: &e.ports,
+        socket: e.socket.as_deref(),
+    }
+}
+
+fn status_word(state: RunState) -> &'static str {
+    match state {
+        RunState::Running => "running",
+        RunState::Stopped => "stopped",
+        RunState::Failed => "failed",
+        RunState::Restarting => "restarting",
+        RunState::Paused => "paused",
+        RunState::Created => "created",
+        RunState::Exited => "exited",
+        RunState::SocketIdle => "socket-idle",
+        RunState::Unknown => "unknown",
+    }
+}
+
+fn port_json(p: &PortEntry) -> PortJson<'_> {
+    let owned = !p.owner.is_empty();
+    PortJson {
+        protocol: &p.protocol,
+        port: p.port,
+        address: &p.address,
+        process: &p.process_name,
+        pid: p.pid,
+        owner: owned.then_some(p.owner.as_str()),
+        owner_kind: owned.then(|| p.owner_kind.label()),
+    }
+}
+
+fn input_json(i: &flake_inputs::FlakeInput) -> InputJson<'_> {
+    InputJson {
+        name: &i.name,
+        input_type: &i.input_type,
+        url: &i.url,
+        branch: (!i.branch.is_empty()).then_some(i.branch.as_str()),
+        rev: &i.revision,
+        last_modified: chrono::DateTime::from_timestamp(i.last_modified, 0)
+            .map(|d| d.to_rfc3339())
+            .unwrap_or_default(),
+        age_days: i.age_days,
+        follows: &i.follows,
+    }
+}
+
+fn disk_json<'a>(role: &'static str, d: &'a DiskUsage) -> DiskJson<'a> {
+    DiskJson {
+        role,
+        mount_point: &d.mount_point,
+        filesystem: &d.filesystem,
+        total: d.total,
+        used: d.used,
+        available: d.available,
+        percent: d.percent,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kind() {
+        let args = |a: &[&str]| a.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(kind(&args(&["ports", "--json"])).unwrap(), "ports");
+        assert!(kind(&args(&["--json"])).is_err());
+        assert!(kind(&args(&["packages"])).is_err());
+        assert!(kind(&args(&["ports", "storage"])).is_err());
+    }
+}
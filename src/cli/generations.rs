@@ -0,0 +1,104 @@
+//! `nixmate generations` — list system (and home-manager) generations
+
+use super::output::{Cell, Color, Output, Table};
+use crate::config::Config;
+use crate::nix::{self, GenerationSource};
+use crate::types::{Generation, ProfileType};
+use anyhow::Result;
+use serde::Serialize;
+
+/// Stable JSON shape, independent of the internal `Generation` type
+#[derive(Serialize)]
+struct GenerationJson<'a> {
+    profile: &'static str,
+    id: u32,
+    date: String,
+    current: bool,
+    in_bootloader: bool,
+    booted: bool,
+    boot_default: bool,
+    nixos_version: Option<&'a str>,
+    kernel_version: Option<&'a str>,
+    store_path: &'a str,
+}
+
+pub fn run(out: &Output) -> Result<i32> {
+    let info = nix::detect_system(None)?;
+    let mut profiles = vec![(
+        ProfileType::System,
+        nix::list_generations(&GenerationSource {
+            profile_type: ProfileType::System,
+            profile_path: info.system_profile,
+        })?,
+    )];
+    if let Some(hm) = info.home_manager {
+        let source = GenerationSource {
+            profile_type: ProfileType::HomeManager,
+            profile_path: hm.profile_path,
+        };
+        if let Ok(gens) = nix::list_generations(&source) {
+            profiles.push((ProfileType::HomeManager, gens));
+        }
+    }
+
+    if out.is_json() {
+        let json: Vec<GenerationJson> = profiles
+            .iter()
+            .flat_map(|(profile, gens)| gens.iter().map(move |g| to_json(*profile, g)))
+            .collect();
+        out.print_json(&json)?;
+        return Ok(0);
+    }
+
+    // Human output follows the `time_format` setting; JSON stays RFC 3339
+    let config = Config::load().unwrap_or_default();
+    for (i, (profile, gens)) in profiles.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!("{}", out.bold(profile.as_str()));
+        let mut table = Table::new(&["ID", "DATE", "NIXOS", "KERNEL", "BOOT", ""]);
+        for g in gens.iter().rev() {
+            let optional = |value: Option<&str>| match value {
+                Some(v) => Cell::new(v),
+                None => Cell::colored("-", Color::Dim),
+            };
+            table.row(vec![
+                Cell::new(g.id.to_string()),
+                Cell::new(g.formatted_date(config.time_format, config.language)),
+                optional(g.nixos_version.as_deref()),
+                optional(g.kernel_version.as_deref()),
+                optional(g.in_bootloader.then_some("yes")),
+                if g.is_current {
+                    Cell::colored(out.mark("● current", "current"), Color::Green)
+                } else if g.is_booted {
+                    Cell::new(out.mark("◆ booted", "booted"))
+                } else if g.is_boot_default {
+                    Cell::new(out.mark("◆ boot default", "boot-default"))
+                } else {
+                    Cell::new("")
+                },
+            ]);
+        }
+        out.print_table(&table);
+    }
+    Ok(0)
+}
+
+fn to_json(profile: ProfileType, g: &Generation) -> GenerationJson<'_> {
+    GenerationJson {
+        profile: match profile {
+            ProfileType::System => "system",
+            ProfileType::HomeManager => "home-manager",
+        },
+        id: g.id,
+        date: g.date.to_rfc3339(),
+        current: g.is_current,
+        in_bootloader: g.in_bootloader,
+        booted: g.is_booted,
+        boot_default: g.is_boot_default,
+        nixos_version: g.nixos_version.as_deref(),
+        kernel_version: g.kernel_version.as_deref(),
+        store_path: &g.store_path,
+    }
+}
@@ -0,0 +1,75 @@
+//! `nixmate locales` — bundled and own locale files and how complete they are
+
+use super::output::{Cell, Color, Output, Table};
+use crate::i18n;
+use anyhow::Result;
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct LocaleJson<'a> {
+    code: &'a str,
+    name: &'a str,
+    coverage: usize,
+    missing: usize,
+    unknown_keys: &'a [String],
+    bundled: bool,
+}
+
+pub fn run(out: &Output, template: bool) -> Result<i32> {
+    if template {
+        print!("{}", i18n::locale_template()?);
+        return Ok(0);
+    }
+    let locales = i18n::locales();
+
+    if out.is_json() {
+        let json: Vec<LocaleJson> = locales
+            .iter()
+            .map(|l| LocaleJson {
+                code: &l.code,
+                name: &l.name,
+                coverage: l.coverage(),
+                missing: l.missing,
+                unknown_keys: &l.unknown,
+                bundled: l.bundled,
+            })
+            .collect();
+        out.print_json(&json)?;
+        return Ok(0);
+    }
+
+    let mut table = Table::new(&[
+        "CODE",
+        "NAME",
+        "SOURCE",
+        "TRANSLATED",
+        "MISSING",
+        "UNKNOWN KEYS",
+    ]);
+    for l in locales {
+        table.row(vec![
+            Cell::new(&l.code),
+            Cell::new(&l.name),
+            if l.bundled {
+                Cell::colored("bundled", Color::Dim)
+            } else {
+                Cell::new("~/.config/nixmate/locales")
+            },
+            Cell::new(format!("{}%", l.coverage())),
+            Cell::new(l.missing.to_string()),
+            if l.unknown.is_empty() {
+                Cell::colored("-", Color::Dim)
+            } else {
+                Cell::colored(l.unknown.join(", "), Color::Yellow)
+            },
+        ]);
+    }
+    out.print_table(&table);
+    if !locales.iter().any(|l| !l.bundled) {
+        println!();
+        println!(
+            "Add your own with: nixmate locales --template > ~/.config/nixmate/locales/<code>.toml"
+        );
+    }
+    Ok(0)
+}
@@ -0,0 +1,48 @@
+//! Headless subcommands (`nixmate generations`, `nixmate doctor`, …)
+//!
+//! They run without the TUI and print to stdout, for scripts and quick
+//! checks over SSH. All output goes through `output::Output`, so every
+//! subcommand handles `--plain`, `--json` and NO_COLOR the same way.
+
+mod doctor;
+mod export;
+mod generations;
+mod locales;
+pub mod output;
+
+use anyhow::{bail, Result};
+use output::{Output, GLOBAL_FLAGS};
+
+/// Subcommands with the flags each accepts besides `GLOBAL_FLAGS`
+const SUBCOMMANDS: &[(&str, &[&str])] = &[
+    ("generations", &[]),
+    ("doctor", &["--check"]),
+    ("locales", &["--template"]),
+    // Takes the kind as its argument
+    ("export", export::KINDS),
+];
+
+/// Run `args` (without the program name) as a subcommand. Returns the exit
+/// code, or None if the first argument isn't a subcommand (start the TUI).
+pub fn run(args: &[String]) -> Option<Result<i32>> {
+    let name = args.first()?;
+    let (_, flags) = SUBCOMMANDS.iter().find(|(n, _)| n == name)?;
+    let rest = &args[1..];
+
+    Some((|| {
+        if let Some(unknown) = rest
+            .iter()
+            .find(|a| !GLOBAL_FLAGS.contains(&a.as_str()) && !flags.contains(&a.as_str()))
+        {
+            bail!("unknown option for `{}`: {}", name, unknown);
+        }
+        let out = Output::from_args(rest);
+        match name.as_str() {
+            "generations" => generations::run(&out),
+            "doctor" => doctor::run(&out, rest.iter().any(|a| a == "--check")),
+            "locales" => locales::run(&out, rest.iter().any(|a| a == "--template")),
+            "export" => export::run(rest),
+            _ => unreachable!("listed in SUBCOMMANDS"),
+        }
+    })())
+}
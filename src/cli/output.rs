@@ -0,0 +1,190 @@
+//! Output formatting shared by all headless subcommands.
+//!
+//! Three styles, picked once per run from the arguments and environment:
+//!   - pretty: aligned tables with ANSI colors (stdout is a terminal)
+//!   - plain: the same tables without colors or symbols (`--plain`,
+//!     `NO_COLOR`, or stdout is not a terminal)
+//!   - json: one pretty-printed JSON document (`--json`), field names are
+//!     part of the interface and don't change with the language
+//!
+//! Subcommands build a `Table` (or plain lines) and never write escape
+//! codes themselves.
+
+use anyhow::Result;
+use serde::Serialize;
+use std::io::IsTerminal;
+
+/// Flags understood by every subcommand
+pub const GLOBAL_FLAGS: &[&str] = &["--plain", "--json"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    Pretty,
+    Plain,
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Green,
+    Yellow,
+    Red,
+    Dim,
+}
+
+impl Color {
+    fn code(&self) -> &'static str {
+        match self {
+            Color::Green => "32",
+            Color::Yellow => "33",
+            Color::Red => "31",
+            Color::Dim => "2",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Output {
+    pub style: Style,
+}
+
+impl Output {
+    /// `--json` wins over `--plain`; colors also need a terminal and no NO_COLOR
+    pub fn from_args(args: &[String]) -> Self {
+        let style = if args.iter().any(|a| a == "--json") {
+            Style::Json
+        } else if args.iter().any(|a| a == "--plain")
+            || std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty())
+            || !std::io::stdout().is_terminal()
+        {
+            Style::Plain
+        } else {
+            Style::Pretty
+        };
+        Self { style }
+    }
+
+    pub fn is_json(&self) -> bool {
+        self.style == Style::Json
+    }
+
+    /// `text` in `color` (unchanged unless pretty)
+    pub fn paint(&self, text: &str, color: Color) -> String {
+        match self.style {
+            Style::Pretty if !text.is_empty() => {
+                format!("\x1b[{}m{}\x1b[0m", color.code(), text)
+            }
+            _ => text.to_string(),
+        }
+    }
+
+    pub fn bold(&self, text: &str) -> String {
+        match self.style {
+            Style::Pretty if !text.is_empty() => format!("\x1b[1m{}\x1b[0m", text),
+            _ => text.to_string(),
+        }
+    }
+
+    /// Status symbol in pretty mode, a word everywhere else
+    pub fn mark(&self, symbol: &str, word: &str) -> String {
+        match self.style {
+            Style::Pretty => symbol.to_string(),
+            _ => word.to_string(),
+        }
+    }
+
+    pub fn print_json<T: Serialize + ?Sized>(&self, value: &T) -> Result<()> {
+        println!("{}", serde_json::to_string_pretty(value)?);
+        Ok(())
+    }
+
+    pub fn print_table(&self, table: &Table) {
+        print!("{}", table.render(self));
+    }
+}
+
+/// One table cell: text plus an optional color
+#[derive(Debug, Clone)]
+pub struct Cell {
+    pub text: String,
+    pub color: Option<Color>,
+}
+
+impl Cell {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            color: None,
+        }
+    }
+
+    pub fn colored(text: impl Into<String>, color: Color) -> Self {
+        Self {
+            text: text.into(),
+            color: Some(color),
+        }
+    }
+}
+
+/// Column-aligned table; widths are counted in chars so colors and
+/// umlauts don't break the alignment
+#[derive(Debug, Clone, Default)]
+pub struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<Cell>>,
+}
+
+impl Table {
+    pub fn new(headers: &[&str]) -> Self {
+        Self {
+            headers: headers.iter().map(|h| h.to_string()).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn row(&mut self, cells: Vec<Cell>) {
+        self.rows.push(cells);
+    }
+
+    pub fn render(&self, out: &Output) -> String {
+        let columns = self.headers.len();
+        let mut widths: Vec<usize> = self.headers.iter().map(|h| h.chars().count()).collect();
+        for row in &self.rows {
+            for (i, cell) in row.iter().enumerate().take(columns) {
+                widths[i] = widths[i].max(cell.text.chars().count());
+            }
+        }
+
+        let line = |cells: Vec<(String, usize)>| -> String {
+            let mut text = String::new();
+            for (i, (cell, len)) in cells.into_iter().enumerate() {
+                text.push_str(&cell);
+                text.push_str(&" ".repeat(widths[i] - len + 2));
+            }
+            // No trailing spaces (empty last columns included)
+            format!("{}\n", text.trim_end())
+        };
+
+        let mut text = line(
+            self.headers
+                .iter()
+                .map(|h| (out.bold(h), h.chars().count()))
+                .collect(),
+        );
+        for row in &self.rows {
+            text.push_str(&line(
+                row.iter()
+                    .take(columns)
+                    .map(|c| {
+                        let painted = match c.color {
+                            Some(color) => out.paint(&c.text, color),
+                            None => c.text.clone(),
+                        };
+                        (painted, c.text.chars().count())
+                    })
+                    .collect(),
+            ));
+        }
+        text
+    }
+}
@@ -0,0 +1,78 @@
+//! System clipboard through whatever tool is installed
+//!
+//! Inside tmux its paste buffer comes first: copy mode puts the text from
+//! another pane's scrollback there, and a copied text lands there too, so
+//! it survives SSH sessions without a display.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+type Tool = (&'static str, &'static [&'static str]);
+
+/// Clipboard readers, tried in order
+fn read_commands() -> Vec<Tool> {
+    let mut commands: Vec<Tool> = Vec::new();
+    if std::env::var_os("TMUX").is_some() {
+        commands.push(("tmux", &["save-buffer", "-"]));
+    }
+    if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        commands.push(("wl-paste", &["--no-newline"]));
+    }
+    commands.push(("xclip", &["-o", "-selection", "clipboard"]));
+    commands.push(("xsel", &["--clipboard", "--output"]));
+    commands.push(("pbpaste", &[]));
+    commands
+}
+
+/// Clipboard writers, tried in order
+fn write_commands() -> Vec<Tool> {
+    let mut commands: Vec<Tool> = Vec::new();
+    if std::env::var_os("TMUX").is_some() {
+        commands.push(("tmux", &["load-buffer", "-w", "-"]));
+    }
+    if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        commands.push(("wl-copy", &[]));
+    }
+    commands.push(("xclip", &["-selection", "clipboard"]));
+    commands.push(("xsel", &["--clipboard", "--input"]));
+    commands.push(("pbcopy", &[]));
+    commands
+}
+
+/// Clipboard text and the tool it came from. None if no tool is there or
+/// the clipboard is empty.
+pub fn read() -> Option<(String, &'static str)> {
+    read_commands().into_iter().find_map(|(program, args)| {
+        let output = Command::new(program)
+            .args(args)
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()
+            .ok()
+            .filter(|o| o.status.success())?;
+        let text = String::from_utf8_lossy(&output.stdout).into_owned();
+        (!text.trim().is_empty()).then_some((text, program))
+    })
+}
+
+/// Put `text` on the clipboard. Returns the tool that took it, None if
+/// none is installed (or all of them failed, e.g. no display).
+pub fn write(text: &str) -> Option<&'static str> {
+    write_commands().into_iter().find_map(|(program, args)| {
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .ok()?;
+        let written = child
+            .stdin
+            .take()
+            .is_some_and(|mut stdin| stdin.write_all(text.as_bytes()).is_ok());
+        // wl-copy and xclip fork a process that keeps serving the
+        // selection; the one we started exits right away
+        let ok = child.wait().is_ok_and(|s| s.success());
+        (written && ok).then_some(program)
+    })
+}
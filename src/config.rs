@@ -6,10 +6,13 @@
 //!
 //! Config file location: ~/.config/nixmate/config.toml
 
+use crate::i18n;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
+use std::time::SystemTime;
 
 /// Main configuration structure (global for all modules)
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,6 +22,18 @@ pub struct Config {
     pub language: Language,
     pub layout: LayoutMode,
 
+    // Compact layouts (tab strip instead of the sidebar in auto layout,
+    // one-column dashboards, fewer table columns) below this terminal size
+    #[serde(default = "default_compact_width")]
+    pub compact_width: u16,
+    #[serde(default = "default_compact_height")]
+    pub compact_height: u16,
+
+    // Theme file in ~/.config/nixmate/themes/ (name without .toml);
+    // takes precedence over `theme` while set
+    #[serde(default)]
+    pub custom_theme: Option<String>,
+
     // First-run welcome screen flag
     #[serde(default)]
     pub welcome_shown: bool,
@@ -31,7 +46,7 @@ pub struct Config {
     #[serde(default)]
     pub ai_api_key: Option<String>,
     #[serde(default)]
-    /// Reserved for future GitHub API integration (planned for v0.8)
+    /// Used for higher GitHub API rate limits (flake input upstream checks)
     pub github_token: Option<String>,
     #[serde(default = "default_ollama_url")]
     pub ollama_url: Option<String>,
@@ -45,6 +60,78 @@ pub struct Config {
     // Custom NixOS config path (overrides /etc/nixos default)
     #[serde(default)]
     pub config_path: Option<String>,
+
+    // nixosConfigurations attribute to build (default: this host's name)
+    #[serde(default)]
+    pub nixos_host: Option<String>,
+
+    // Rebuild dashboard: one-column layout for narrow terminals
+    #[serde(default)]
+    pub rebuild_dashboard: RebuildDashboard,
+
+    // Timestamps in history views: iso, locale or relative
+    #[serde(default)]
+    pub time_format: TimeFormat,
+
+    // Build backend per host ([rebuild_backend.<hostname>], "default" for
+    // hosts without their own table)
+    #[serde(default)]
+    pub rebuild_backend: BTreeMap<String, BuildBackendConfig>,
+
+    // Services Overview: network mounts and disk health panel
+    #[serde(default)]
+    pub services_mounts: bool,
+
+    // Storage budget mode: free space to aim for, in GB
+    #[serde(default = "default_storage_free_target_gb")]
+    pub storage_free_target_gb: u64,
+
+    // Cache TTL and auto-refresh per data source ([refresh.<source>] tables)
+    #[serde(default)]
+    pub refresh: RefreshConfig,
+
+    // Commit message for flake.lock after an update; {inputs} = updated names
+    #[serde(default = "default_flake_commit_message")]
+    pub flake_commit_message: String,
+
+    // Flake Inputs: the flake shown (last picked with F; unset = the system
+    // flake) and the registered flake projects to switch between
+    #[serde(default)]
+    pub flake_path: Option<String>,
+    #[serde(default)]
+    pub flakes: Vec<String>,
+
+    // Flake Inputs watch mode: staleness thresholds ([flake_watch] table)
+    #[serde(default)]
+    pub flake_watch: FlakeWatchConfig,
+
+    // Error Translator "explain with AI" panel ([ai_explain] table, opt-in)
+    #[serde(default)]
+    pub ai_explain: AiExplainConfig,
+
+    // Paste service the Rebuild log is shared to ([paste] table, opt-in)
+    #[serde(default)]
+    pub paste: PasteConfig,
+
+    // Options Explorer usage examples: dataset download ([option_examples])
+    #[serde(default)]
+    pub option_examples: OptionExamplesConfig,
+
+    // Start screen with the Nix Doctor score and top issues on launch
+    #[serde(default = "default_doctor_splash")]
+    pub doctor_splash: bool,
+
+    // Reopen the module, sub-tabs, searches and filters of the last run
+    #[serde(default)]
+    pub restore_session: bool,
+
+    // Remapped keys per action ([keys] table), e.g. down = ["n", "Down"]
+    #[serde(default)]
+    pub keys: BTreeMap<String, Vec<String>>,
+
+    // Own dashboards: tabs showing a command's output ([[external_modules]])
+    #[serde(default)]
+    pub external_modules: Vec<ExternalModuleConfig>,
 }
 
 fn default_ai_provider() -> String {
@@ -55,6 +142,26 @@ fn default_nixpkgs_channel() -> String {
     "auto".to_string()
 }
 
+fn default_compact_width() -> u16 {
+    100
+}
+
+fn default_compact_height() -> u16 {
+    28
+}
+
+fn default_storage_free_target_gb() -> u64 {
+    50
+}
+
+fn default_flake_commit_message() -> String {
+    "flake: update {inputs}".to_string()
+}
+
+fn default_doctor_splash() -> bool {
+    true
+}
+
 fn default_ollama_url() -> Option<String> {
     Some("http://localhost:11434".to_string())
 }
@@ -69,6 +176,9 @@ impl Default for Config {
             theme: ThemeName::Gruvbox,
             language: Language::English,
             layout: LayoutMode::Auto,
+            compact_width: 100,
+            compact_height: 28,
+            custom_theme: None,
             welcome_shown: false,
             ai_enabled: false,
             ai_provider: "claude".to_string(),
@@ -78,6 +188,24 @@ impl Default for Config {
             ollama_model: Some("llama3".to_string()),
             nixpkgs_channel: "auto".to_string(),
             config_path: None,
+            nixos_host: None,
+            rebuild_dashboard: RebuildDashboard::Auto,
+            time_format: TimeFormat::Locale,
+            rebuild_backend: BTreeMap::new(),
+            services_mounts: false,
+            storage_free_target_gb: 50,
+            refresh: RefreshConfig::default(),
+            flake_commit_message: default_flake_commit_message(),
+            flake_path: None,
+            flakes: Vec::new(),
+            flake_watch: FlakeWatchConfig::default(),
+            ai_explain: AiExplainConfig::default(),
+            paste: PasteConfig::default(),
+            option_examples: OptionExamplesConfig::default(),
+            doctor_splash: true,
+            restore_session: false,
+            keys: BTreeMap::new(),
+            external_modules: Vec::new(),
         }
     }
 }
@@ -91,6 +219,11 @@ impl Config {
         Ok(config_dir.join("config.toml"))
     }
 
+    /// Modification time of the config file, None while there is none
+    pub fn mtime() -> Option<SystemTime> {
+        fs::metadata(Self::path().ok()?).ok()?.modified().ok()
+    }
+
     /// Load config from file, or create default if not exists
     pub fn load() -> Result<Self> {
         let path = Self::path()?;
@@ -150,6 +283,277 @@ impl Config {
     }
 }
 
+/// Data sources with their own cache TTL and auto-refresh interval
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataSource {
+    Services,
+    Ports,
+    FlakeInputs,
+    Options,
+    Storage,
+}
+
+impl DataSource {
+    pub fn all() -> &'static [DataSource] {
+        &[
+            DataSource::Services,
+            DataSource::Ports,
+            DataSource::FlakeInputs,
+            DataSource::Options,
+            DataSource::Storage,
+        ]
+    }
+}
+
+/// How long loaded data counts as fresh, and how often it is reloaded
+/// while visible. Seconds; 0 = never stale / no auto-refresh.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceTiming {
+    pub ttl_secs: u64,
+    #[serde(default)]
+    pub refresh_secs: u64,
+}
+
+impl SourceTiming {
+    const fn ttl(ttl_secs: u64) -> Self {
+        Self {
+            ttl_secs,
+            refresh_secs: 0,
+        }
+    }
+
+    /// Whether data loaded at `loaded_at` is older than the TTL
+    pub fn is_stale(&self, loaded_at: std::time::Instant) -> bool {
+        self.ttl_secs > 0 && loaded_at.elapsed().as_secs() >= self.ttl_secs
+    }
+
+    /// Whether an auto-refresh is due for data loaded at `loaded_at`
+    pub fn refresh_due(&self, loaded_at: std::time::Instant) -> bool {
+        self.refresh_secs > 0 && loaded_at.elapsed().as_secs() >= self.refresh_secs
+    }
+
+    /// "1m 0" style text for the settings editor
+    pub fn to_edit_string(self) -> String {
+        format!(
+            "{} {}",
+            format_secs(self.ttl_secs),
+            format_secs(self.refresh_secs)
+        )
+    }
+
+    /// Parse "<ttl> [refresh]", each a number with optional s/m/h/d suffix
+    pub fn parse(input: &str) -> Option<Self> {
+        let mut parts = input.split_whitespace();
+        let ttl_secs = parse_secs(parts.next()?)?;
+        let refresh_secs = parts.next().map(parse_secs).unwrap_or(Some(0))?;
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(Self {
+            ttl_secs,
+            refresh_secs,
+        })
+    }
+}
+
+/// Seconds as the largest whole unit ("90s", "5m", "2h", "1d"); 0 stays "0"
+pub fn format_secs(secs: u64) -> String {
+    match secs {
+        0 => "0".to_string(),
+        s if s % 86400 == 0 => format!("{}d", s / 86400),
+        s if s % 3600 == 0 => format!("{}h", s / 3600),
+        s if s % 60 == 0 => format!("{}m", s / 60),
+        s => format!("{}s", s),
+    }
+}
+
+fn parse_secs(s: &str) -> Option<u64> {
+    let (num, mult) = match s.char_indices().last()? {
+        (i, 's') => (&s[..i], 1),
+        (i, 'm') => (&s[..i], 60),
+        (i, 'h') => (&s[..i], 3600),
+        (i, 'd') => (&s[..i], 86400),
+        _ => (s, 1),
+    };
+    num.parse::<u64>().ok()?.checked_mul(mult)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RefreshConfig {
+    pub services: SourceTiming,
+    pub ports: SourceTiming,
+    pub flake_inputs: SourceTiming,
+    pub options: SourceTiming,
+    pub storage: SourceTiming,
+}
+
+impl Default for RefreshConfig {
+    fn default() -> Self {
+        Self {
+            services: SourceTiming::ttl(60),
+            ports: SourceTiming::ttl(60),
+            flake_inputs: SourceTiming::ttl(3600),
+            options: SourceTiming::ttl(86400),
+            storage: SourceTiming::ttl(1800),
+        }
+    }
+}
+
+impl RefreshConfig {
+    pub fn get(&self, source: DataSource) -> SourceTiming {
+        match source {
+            DataSource::Services => self.services,
+            DataSource::Ports => self.ports,
+            DataSource::FlakeInputs => self.flake_inputs,
+            DataSource::Options => self.options,
+            DataSource::Storage => self.storage,
+        }
+    }
+
+    pub fn get_mut(&mut self, source: DataSource) -> &mut SourceTiming {
+        match source {
+            DataSource::Services => &mut self.services,
+            DataSource::Ports => &mut self.ports,
+            DataSource::FlakeInputs => &mut self.flake_inputs,
+            DataSource::Options => &mut self.options,
+            DataSource::Storage => &mut self.storage,
+        }
+    }
+}
+
+/// When a flake input counts as out of date. Surfaced as a badge on the
+/// Flake Inputs tab and as a Nix Doctor check.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FlakeWatchConfig {
+    pub enabled: bool,
+    /// Age in days after which any input is flagged (0 = only listed inputs)
+    pub default_days: u64,
+    /// Per-input thresholds in days, overriding the default (0 = never flag)
+    pub inputs: BTreeMap<String, u64>,
+}
+
+impl Default for FlakeWatchConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            default_days: 30,
+            inputs: BTreeMap::from([("nixpkgs".to_string(), 14)]),
+        }
+    }
+}
+
+impl FlakeWatchConfig {
+    /// Threshold in days for `input`, None if it is never flagged
+    pub fn threshold_days(&self, input: &str) -> Option<u64> {
+        let days = self.inputs.get(input).copied().unwrap_or(self.default_days);
+        (self.enabled && days > 0).then_some(days)
+    }
+}
+
+/// Opt-in "explain with AI": the raw error is sent to an OpenAI-compatible
+/// chat completions endpoint and the answer streamed next to the rule-based
+/// translation. Separate from the `ai_*` fallback so it can point at any
+/// server (llama.cpp, vLLM, LM Studio, a hosted API).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AiExplainConfig {
+    pub enabled: bool,
+    /// Base URL; `/chat/completions` is appended
+    pub url: String,
+    pub model: String,
+    /// Sent as a Bearer token; local servers usually need none
+    pub api_key: Option<String>,
+}
+
+impl Default for AiExplainConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            url: "https://api.openai.com/v1".to_string(),
+            model: "gpt-4o-mini".to_string(),
+            api_key: None,
+        }
+    }
+}
+
+impl AiExplainConfig {
+    /// Enabled and complete enough to send a request
+    pub fn available(&self) -> bool {
+        self.enabled && !self.url.trim().is_empty() && !self.model.trim().is_empty()
+    }
+
+    /// Host part of the URL, shown so it's clear where the error goes
+    pub fn host(&self) -> &str {
+        url_host(&self.url)
+    }
+}
+
+/// A tab that shows the output of a shell command, run when the tab is
+/// opened, on `r` and every `refresh_secs` while it's shown (0 = only then)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExternalModuleConfig {
+    pub name: String,
+    pub command: String,
+    #[serde(default)]
+    pub refresh_secs: u64,
+}
+
+/// Paste service for sharing a build log from the Rebuild Log tab. The
+/// log is POSTed as the raw request body and the service answers with the
+/// paste's URL — paste.rs and most self-hosted pastebins work that way.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PasteConfig {
+    pub enabled: bool,
+    pub url: String,
+}
+
+impl Default for PasteConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            url: "https://paste.rs".to_string(),
+        }
+    }
+}
+
+impl PasteConfig {
+    pub fn available(&self) -> bool {
+        self.enabled && !self.url.trim().is_empty()
+    }
+
+    /// Host part of the URL, shown so it's clear where the log goes
+    pub fn host(&self) -> &str {
+        url_host(&self.url)
+    }
+}
+
+/// Where the Options Explorer gets its full usage examples dataset. Empty
+/// `url` = only the small set bundled with nixmate.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OptionExamplesConfig {
+    pub url: String,
+    /// Download again when the copy is older than this (0 = only by hand)
+    pub max_age_days: u32,
+}
+
+impl Default for OptionExamplesConfig {
+    fn default() -> Self {
+        Self {
+            url: String::new(),
+            max_age_days: 30,
+        }
+    }
+}
+
+fn url_host(url: &str) -> &str {
+    let rest = url.split_once("://").map_or(url, |(_, r)| r);
+    rest.split('/').next().unwrap_or(rest)
+}
+
 /// Available theme names
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
@@ -209,12 +613,14 @@ impl ThemeName {
 }
 
 /// Available languages
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
-#[serde(rename_all = "lowercase")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum Language {
     #[default]
     English,
     German,
+    /// Loaded from a locale file, index into `i18n::locales()`. Stored in
+    /// config.toml by its code.
+    Locale(u8),
 }
 
 impl Language {
@@ -222,17 +628,64 @@ impl Language {
         match self {
             Language::English => "English",
             Language::German => "Deutsch",
+            Language::Locale(i) => i18n::locales()
+                .get(*i as usize)
+                .map_or("English", |l| l.name.as_str()),
+        }
+    }
+
+    /// The built-in language for texts that aren't in `Strings` (intro
+    /// pages, error explanations); locale files fall back to English
+    pub fn builtin(&self) -> Self {
+        match self {
+            Language::German => Language::German,
+            _ => Language::English,
         }
     }
 
     pub fn next(&self) -> Self {
+        let count = i18n::locales().len();
         match self {
             Language::English => Language::German,
-            Language::German => Language::English,
+            Language::German if count > 0 => Language::Locale(0),
+            Language::Locale(i) if (*i as usize) + 1 < count => Language::Locale(i + 1),
+            _ => Language::English,
+        }
+    }
+
+    fn code(&self) -> &'static str {
+        match self {
+            Language::English => "english",
+            Language::German => "german",
+            Language::Locale(i) => i18n::locales()
+                .get(*i as usize)
+                .map_or("english", |l| l.code.as_str()),
         }
     }
 }
 
+impl Serialize for Language {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for Language {
+    /// "english", "german" or a locale file's code. A locale that isn't
+    /// there (anymore) falls back to English instead of breaking the config.
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        Ok(match code.as_str() {
+            "english" => Language::English,
+            "german" => Language::German,
+            _ => i18n::locales()
+                .iter()
+                .position(|l| l.code == code)
+                .map_or(Language::English, |i| Language::Locale(i as u8)),
+        })
+    }
+}
+
 /// Layout mode for the UI
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
@@ -246,7 +699,7 @@ pub enum LayoutMode {
 impl LayoutMode {
     pub fn as_str(&self, lang: Language) -> &'static str {
         match lang {
-            Language::English => match self {
+            Language::English | Language::Locale(_) => match self {
                 LayoutMode::Auto => "Auto (responsive)",
                 LayoutMode::SideBySide => "Side-by-side",
                 LayoutMode::TabsOnly => "Tabs only",
@@ -268,6 +721,81 @@ impl LayoutMode {
     }
 }
 
+/// Rebuild dashboard layout while a build runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum RebuildDashboard {
+    /// Compact below `COMPACT_WIDTH` columns, full otherwise
+    #[default]
+    Auto,
+    /// Always the one-column view (phone SSH clients, tmux side panes)
+    Compact,
+    /// Always the phase boxes
+    Full,
+}
+
+impl RebuildDashboard {
+    /// Narrower than this (dashboard area, in columns) is compact in auto mode
+    pub const COMPACT_WIDTH: u16 = 60;
+
+    /// `terminal_compact`: the terminal is below the compact thresholds
+    pub fn is_compact(&self, width: u16, terminal_compact: bool) -> bool {
+        match self {
+            RebuildDashboard::Auto => terminal_compact || width < Self::COMPACT_WIDTH,
+            RebuildDashboard::Compact => true,
+            RebuildDashboard::Full => false,
+        }
+    }
+}
+
+/// How history views (rebuilds, generations, lock dates, cleanups) show timestamps
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeFormat {
+    /// `2026-10-17 14:05`
+    Iso,
+    /// `17 Oct 2026 14:05` / `17.10.2026 14:05`, following the UI language
+    #[default]
+    Locale,
+    /// `2 h ago`, falling back to the locale date after a month
+    Relative,
+}
+
+/// What builds the system before `nixos-rebuild` activates it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum BuildBackend {
+    /// `nixos-rebuild` alone
+    #[default]
+    NixosRebuild,
+    /// `nix-fast-build`: evaluation in parallel workers (nix-eval-jobs)
+    NixFastBuild,
+    /// `nix build` with all attributes at once
+    NixBuild,
+}
+
+impl BuildBackend {
+    pub fn program(&self) -> &'static str {
+        match self {
+            BuildBackend::NixosRebuild => "nixos-rebuild",
+            BuildBackend::NixFastBuild => "nix-fast-build",
+            BuildBackend::NixBuild => "nix",
+        }
+    }
+}
+
+/// One `[rebuild_backend.<host>]` table. Flakes only; the backend builds,
+/// `nixos-rebuild` then activates what's already in the store.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BuildBackendConfig {
+    pub backend: BuildBackend,
+    /// Flake attributes to build; empty = this host's system
+    pub attrs: Vec<String>,
+    /// `nix-fast-build --eval-workers`
+    pub eval_workers: Option<u32>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -297,7 +825,42 @@ mod tests {
     fn test_language_cycle() {
         let lang = Language::English;
         assert_eq!(lang.next(), Language::German);
-        assert_eq!(lang.next().next(), Language::English);
+        // Then the locales (at least the bundled ones), then back to English
+        assert_eq!(lang.next().next(), Language::Locale(0));
+        let mut l = Language::German;
+        for _ in 0..=i18n::locales().len() {
+            l = l.next();
+        }
+        assert_eq!(l, Language::English);
+    }
+
+    #[test]
+    fn test_source_timing_parse() {
+        let t = SourceTiming::parse("5m 30s").unwrap();
+        assert_eq!(t.ttl_secs, 300);
+        assert_eq!(t.refresh_secs, 30);
+        assert_eq!(SourceTiming::parse("90").unwrap().refresh_secs, 0);
+        assert_eq!(t.to_edit_string(), "5m 30s");
+        assert!(SourceTiming::parse("abc").is_none());
+        assert!(SourceTiming::parse("1 2 3").is_none());
+    }
+
+    #[test]
+    fn test_flake_watch_thresholds() {
+        let config: Config = toml::from_str(
+            "[flake_watch]\ndefault_days = 60\n[flake_watch.inputs]\nhome-manager = 0\n",
+        )
+        .unwrap();
+        let watch = &config.flake_watch;
+        assert!(watch.enabled);
+        assert_eq!(watch.threshold_days("nur"), Some(60));
+        assert_eq!(watch.threshold_days("home-manager"), None);
+        // An [inputs] table replaces the default one
+        assert_eq!(watch.threshold_days("nixpkgs"), Some(60));
+        assert_eq!(
+            FlakeWatchConfig::default().threshold_days("nixpkgs"),
+            Some(14)
+        );
     }
 
     #[test]
@@ -305,4 +868,29 @@ mod tests {
         let config = Config::default();
         assert!(!config.ai_available());
     }
+
+    #[test]
+    fn test_ai_explain_opt_in() {
+        let mut config = Config::default();
+        assert!(!config.ai_explain.available());
+
+        config.ai_explain.enabled = true;
+        assert!(config.ai_explain.available());
+        assert_eq!(config.ai_explain.host(), "api.openai.com");
+
+        config.ai_explain.url = "http://localhost:8080/v1".to_string();
+        assert_eq!(config.ai_explain.host(), "localhost:8080");
+    }
+
+    #[test]
+    fn test_rebuild_dashboard_layout() {
+        let config: Config = toml::from_str("rebuild_dashboard = \"compact\"").unwrap();
+        assert_eq!(config.rebuild_dashboard, RebuildDashboard::Compact);
+        assert!(config.rebuild_dashboard.is_compact(200, false));
+
+        assert!(RebuildDashboard::Auto.is_compact(40, false));
+        assert!(!RebuildDashboard::Auto.is_compact(120, false));
+        assert!(RebuildDashboard::Auto.is_compact(120, true));
+        assert!(!RebuildDashboard::Full.is_compact(40, true));
+    }
 }
@@ -2,12 +2,23 @@
 //!
 //! All user-facing strings in English and German.
 //! One central place – no duplicate string definitions per module.
+//!
+//! More languages come from locale files: the ones bundled from
+//! `assets/locales/` and any in `~/.config/nixmate/locales/` (which win
+//! over a bundled file of the same code), read once at startup: `<code>.toml` with a `[locale]` table (`name`) and
+//! a `[strings]` table keyed by the field names below. Missing keys fall
+//! back to English one by one, so a partial translation is usable.
+//! `nixmate locales --template` prints every key with its English text.
 
 use crate::config::Language;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
 
 /// All translatable strings used across the entire application
 // Not all fields used directly — accessed via i18n::get_strings()
 #[allow(dead_code)]
+#[derive(Serialize, Deserialize)]
 pub struct Strings {
     // === App-level ===
     pub app_title: &'static str,
@@ -16,6 +27,163 @@ pub struct Strings {
     pub welcome_body: &'static str,
     pub welcome_continue: &'static str,
     pub welcome_once: &'static str,
+    pub start_top_issues: &'static str,
+    pub start_no_issues: &'static str,
+    pub start_open_doctor: &'static str,
+    pub start_continue: &'static str,
+    pub palette_title: &'static str,
+    pub palette_no_match: &'static str,
+    pub jobs_title: &'static str,
+    pub jobs_running: &'static str,
+    pub jobs_none: &'static str,
+    pub jobs_finished: &'static str,
+    pub jobs_cancelled: &'static str,
+    pub jobs_cancelled_error: &'static str,
+    pub jobs_confirm: &'static str,
+    pub jobs_not_cancellable: &'static str,
+    pub jobs_footer: &'static str,
+    pub staged_countdown: &'static str,
+    pub staged_more: &'static str,
+    pub staged_flushing: &'static str,
+    pub staged_undone: &'static str,
+    pub jobs_badge: &'static str,
+    pub vitals_generation: &'static str,
+    pub vitals_store_free: &'static str,
+    pub vitals_units_ok: &'static str,
+    pub vitals_units_failed: &'static str,
+    pub vitals_stale_inputs: &'static str,
+    pub jobs_cancel_done: &'static str,
+    pub job_svc_load: &'static str,
+    pub job_svc_logs: &'static str,
+    pub job_sto_load: &'static str,
+    pub job_sto_budget: &'static str,
+    pub job_sto_budget_step: &'static str,
+    pub job_cfg_poster: &'static str,
+    pub job_cfg_diagram: &'static str,
+    pub job_cfg_map: &'static str,
+    pub job_opt_load: &'static str,
+    pub job_opt_overrides: &'static str,
+    pub job_opt_changes: &'static str,
+    pub job_pkg_search: &'static str,
+    pub job_pkg_audit: &'static str,
+    pub job_fi_load: &'static str,
+    pub job_fi_update: &'static str,
+    pub job_fi_upstream: &'static str,
+    pub job_fi_relock: &'static str,
+    pub job_fi_impact: &'static str,
+    pub job_health_scan: &'static str,
+    pub job_health_fix: &'static str,
+    pub job_err_ai: &'static str,
+    pub job_err_explain: &'static str,
+    pub job_rebuild: &'static str,
+    pub job_rb_target: &'static str,
+    pub job_rb_share: &'static str,
+    pub job_rb_preview: &'static str,
+    pub job_rb_tree: &'static str,
+    pub key_help_title: &'static str,
+    pub key_help_global: &'static str,
+    pub key_help_none: &'static str,
+    pub key_help_footer: &'static str,
+    pub status_palette: &'static str,
+    pub kiosk_mode: &'static str,
+    pub kiosk_next_in: &'static str,
+    pub kiosk_paused: &'static str,
+    pub kiosk_keys: &'static str,
+    pub keys_issues_flash: &'static str,
+    pub settings_keys_section: &'static str,
+    pub settings_keys_custom: &'static str,
+    pub settings_keys_unknown: &'static str,
+    pub settings_keys_bad_key: &'static str,
+    pub settings_keys_conflict: &'static str,
+    pub settings_keys_hint: &'static str,
+    pub pal_gen_restore: &'static str,
+    pub pal_gen_delete: &'static str,
+    pub pal_gen_pin: &'static str,
+    pub pal_gen_select_all: &'static str,
+    pub pal_gen_filter: &'static str,
+    pub pal_gen_diff: &'static str,
+    pub pal_gen_git_diff: &'static str,
+    pub pal_err_paste: &'static str,
+    pub pal_err_clipboard: &'static str,
+    pub pal_err_file: &'static str,
+    pub pal_err_ai: &'static str,
+    pub pal_err_explain: &'static str,
+    pub pal_err_open: &'static str,
+    pub pal_err_tree: &'static str,
+    pub pal_err_history: &'static str,
+    pub pal_err_submit: &'static str,
+    pub pal_svc_search: &'static str,
+    pub pal_svc_ports: &'static str,
+    pub svc_unit_not_systemd: &'static str,
+    pub svc_unit_failed: &'static str,
+    pub svc_unit_source: &'static str,
+    pub svc_unit_src_nixos: &'static str,
+    pub svc_unit_src_package: &'static str,
+    pub svc_unit_src_local: &'static str,
+    pub svc_unit_triggers: &'static str,
+    pub pal_svc_unit_file: &'static str,
+    pub pal_svc_start: &'static str,
+    pub pal_svc_stop: &'static str,
+    pub pal_svc_restart: &'static str,
+    pub pal_svc_restart_failed: &'static str,
+    pub pal_svc_logs_follow: &'static str,
+    pub pal_svc_enable: &'static str,
+    pub pal_svc_disable: &'static str,
+    pub pal_svc_logs_grep: &'static str,
+    pub pal_svc_logs_since: &'static str,
+    pub pal_svc_logs_range: &'static str,
+    pub pal_svc_logs_errors: &'static str,
+    pub pal_svc_logs_more: &'static str,
+    pub pal_sto_search: &'static str,
+    pub pal_sto_budget_step: &'static str,
+    pub pal_sto_budget_plan: &'static str,
+    pub pal_sto_history: &'static str,
+    pub pal_cfg_export: &'static str,
+    pub pal_cfg_export_path: &'static str,
+    pub pal_cfg_rescan: &'static str,
+    pub pal_cfg_diagram: &'static str,
+    pub pal_cfg_map: &'static str,
+    pub pal_opt_search: &'static str,
+    pub pal_opt_copy_link: &'static str,
+    pub pal_opt_browse: &'static str,
+    pub pal_opt_overrides: &'static str,
+    pub pal_opt_reeval: &'static str,
+    pub pal_opt_changed: &'static str,
+    pub pal_rb_start: &'static str,
+    pub pal_rb_trace: &'static str,
+    pub pal_rb_repl: &'static str,
+    pub pal_rb_targets: &'static str,
+    pub pal_rb_hosts: &'static str,
+    pub pal_rb_tree: &'static str,
+    pub pal_rb_warning_option: &'static str,
+    pub pal_ext_run: &'static str,
+    pub pal_rb_cancel: &'static str,
+    pub pal_rb_log_search: &'static str,
+    pub pal_rb_explain: &'static str,
+    pub pal_rb_smart: &'static str,
+    pub pal_rb_export: &'static str,
+    pub pal_rb_copy_error: &'static str,
+    pub pal_rb_share: &'static str,
+    pub pal_rb_history: &'static str,
+    pub pal_fl_update: &'static str,
+    pub pal_fl_update_all: &'static str,
+    pub pal_fl_add: &'static str,
+    pub pal_fl_edit: &'static str,
+    pub pal_fl_branch: &'static str,
+    pub pal_fl_switch_nixpkgs: &'static str,
+    pub pal_fl_graph: &'static str,
+    pub pal_fl_report: &'static str,
+    pub pal_fl_projects: &'static str,
+    pub pal_pkg_search: &'static str,
+    pub pal_pkg_insecure: &'static str,
+    pub pal_hl_rescan: &'static str,
+    pub pal_hl_fix: &'static str,
+    pub pal_hl_accounts: &'static str,
+    pub pal_set_theme: &'static str,
+    pub pal_set_language: &'static str,
+    pub pal_set_layout: &'static str,
+    pub pal_set_config_path: &'static str,
+    pub pal_set_ai: &'static str,
     pub welcome_language: &'static str,
     pub welcome_lang_hint: &'static str,
     pub quit: &'static str,
@@ -63,12 +231,15 @@ pub struct Strings {
 
     pub cfg_overview: &'static str,
     pub cfg_diagram: &'static str,
+    pub cfg_services: &'static str,
     pub cfg_generate: &'static str,
     pub cfg_scanning: &'static str,
     pub cfg_success: &'static str,
     pub cfg_error: &'static str,
     pub cfg_hint: &'static str,
     pub cfg_tip: &'static str,
+    pub cfg_export_prompt: &'static str,
+    pub cfg_export_formats: &'static str,
     pub cfg_diag_description: &'static str,
     pub cfg_diag_subtitle: &'static str,
     pub cfg_diag_generate: &'static str,
@@ -77,6 +248,13 @@ pub struct Strings {
     pub cfg_diag_hint: &'static str,
     pub cfg_diag_tip: &'static str,
     pub cfg_diag_no_config: &'static str,
+    pub cfg_map_loading: &'static str,
+    pub cfg_map_loading_hint: &'static str,
+    pub cfg_map_failed: &'static str,
+    pub cfg_map_summary: &'static str,
+    pub cfg_map_showing_user: &'static str,
+    pub cfg_map_showing_all: &'static str,
+    pub cfg_map_empty: &'static str,
 
     // === Help / About ===
     pub help_subtitle: &'static str,
@@ -112,11 +290,57 @@ pub struct Strings {
     pub pkg_installed: &'static str,
     pub pkg_not_installed: &'static str,
     pub pkg_install_hint: &'static str,
+    pub pkg_audit_title: &'static str,
+    pub pkg_audit_scanning: &'static str,
+    pub pkg_audit_clean: &'static str,
+    pub pkg_audit_count: &'static str,
+    pub pkg_audit_failed: &'static str,
+    pub pkg_audit_vulnerable: &'static str,
+    pub pkg_audit_reasons: &'static str,
+    pub pkg_audit_upgrade: &'static str,
+    pub pkg_audit_no_upgrade: &'static str,
+    pub pkg_audit_permitted: &'static str,
+    pub pkg_tab_search: &'static str,
+    pub pkg_tab_browse: &'static str,
+    pub pkg_cat_editors: &'static str,
+    pub pkg_cat_terminals: &'static str,
+    pub pkg_cat_shells: &'static str,
+    pub pkg_cat_languages: &'static str,
+    pub pkg_cat_devtools: &'static str,
+    pub pkg_cat_servers: &'static str,
+    pub pkg_cat_browsers: &'static str,
+    pub pkg_sort_popular: &'static str,
+    pub pkg_sort_updated: &'static str,
+    pub pkg_sort_name: &'static str,
+    pub pkg_browse_sort: &'static str,
+    pub pkg_browse_hint: &'static str,
+    pub pkg_browse_building: &'static str,
+    pub pkg_browse_failed: &'static str,
+    pub pkg_browse_empty: &'static str,
+    pub pkg_browse_tracked: &'static str,
+    pub pkg_browse_not_tracked: &'static str,
+    pub job_pkg_index: &'static str,
+    pub pal_pkg_browse: &'static str,
+    pub pal_pkg_rebuild_index: &'static str,
 
     // === Health / Nix Doctor ===
     pub health_dashboard: &'static str,
     pub health_fix: &'static str,
+    pub health_accounts: &'static str,
     pub health_scanning: &'static str,
+    pub health_scan_progress: &'static str,
+    pub health_pending: &'static str,
+    pub health_why: &'static str,
+    pub health_fix_snippet_hint: &'static str,
+    pub health_fix_snippet_flash: &'static str,
+    pub health_cat_storage: &'static str,
+    pub health_cat_updates: &'static str,
+    pub health_cat_security: &'static str,
+    pub health_cat_boot: &'static str,
+    pub health_cat_system: &'static str,
+    pub health_cat_power: &'static str,
+    pub health_cat_hardware: &'static str,
+    pub health_cat_nixmate: &'static str,
     pub health_score_label: &'static str,
     pub health_excellent: &'static str,
     pub health_good: &'static str,
@@ -130,6 +354,27 @@ pub struct Strings {
     pub opt_tab_search: &'static str,
     pub opt_tab_browse: &'static str,
     pub opt_tab_related: &'static str,
+    pub opt_tab_overrides: &'static str,
+    pub opt_ovr_header: &'static str,
+    pub opt_ovr_empty: &'static str,
+    pub opt_ovr_phase_eval: &'static str,
+    pub opt_ovr_scanning: &'static str,
+    pub opt_ovr_loading_hint: &'static str,
+    pub opt_ovr_eval_failed: &'static str,
+    pub opt_ovr_parse_failed: &'static str,
+    pub opt_ovr_retry: &'static str,
+    pub opt_tab_changed: &'static str,
+    pub opt_chg_loading: &'static str,
+    pub opt_chg_no_gens: &'static str,
+    pub opt_chg_same_rev: &'static str,
+    pub opt_chg_no_options: &'static str,
+    pub opt_chg_none: &'static str,
+    pub opt_chg_filter_all: &'static str,
+    pub opt_chg_kind_default: &'static str,
+    pub opt_chg_kind_type: &'static str,
+    pub opt_chg_kind_added: &'static str,
+    pub opt_chg_kind_removed: &'static str,
+    pub opt_chg_kind_moved: &'static str,
     pub opt_loading: &'static str,
     pub opt_loading_hint: &'static str,
     pub opt_empty: &'static str,
@@ -140,6 +385,14 @@ pub struct Strings {
     pub opt_no_results: &'static str,
     pub opt_browse_hint: &'static str,
     pub opt_related_label: &'static str,
+    pub opt_detail_examples: &'static str,
+    pub opt_examples_update: &'static str,
+    pub opt_examples_no_url: &'static str,
+    pub opt_link_copied: &'static str,
+    pub opt_link_none: &'static str,
+    pub opt_examples_updated: &'static str,
+    pub opt_examples_failed: &'static str,
+    pub job_opt_examples: &'static str,
     pub opt_related_empty: &'static str,
     pub opt_detail_type: &'static str,
     pub opt_detail_default: &'static str,
@@ -155,8 +408,20 @@ pub struct Strings {
     pub fi_tab_update: &'static str,
     pub fi_tab_history: &'static str,
     pub fi_tab_details: &'static str,
+    pub fi_tab_graph: &'static str,
+    pub fi_graph_summary: &'static str,
+    pub fi_graph_summary_dupes: &'static str,
+    pub fi_graph_expand_all: &'static str,
+    pub fi_graph_collapse_all: &'static str,
+    pub fi_graph_follows: &'static str,
+    pub fi_graph_copies: &'static str,
     pub fi_loading: &'static str,
     pub fi_empty: &'static str,
+    pub fi_col_name: &'static str,
+    pub fi_col_url: &'static str,
+    pub fi_col_rev: &'static str,
+    pub fi_col_age: &'static str,
+    pub fi_col_status: &'static str,
     pub fi_no_flake_hint: &'static str,
     pub fi_update_hint: &'static str,
     pub fi_updating: &'static str,
@@ -164,6 +429,74 @@ pub struct Strings {
     pub fi_refresh: &'static str,
     pub fi_history_empty: &'static str,
     pub fi_history_hint: &'static str,
+    pub fi_lockdiff_title: &'static str,
+    pub fi_lockdiff_note: &'static str,
+    pub fi_lockdiff_header: &'static str,
+    pub fi_lockdiff_same: &'static str,
+    pub fi_lockdiff_summary: &'static str,
+    pub fi_lockdiff_failed: &'static str,
+    pub fi_rollback_title: &'static str,
+    pub fi_rollback_note: &'static str,
+    pub fi_rollback_unavailable: &'static str,
+    pub fi_report_title: &'static str,
+    pub fi_projects_title: &'static str,
+    pub fi_project_system: &'static str,
+    pub fi_project_gone: &'static str,
+    pub fi_project_open: &'static str,
+    pub fi_project_add: &'static str,
+    pub fi_project_remove: &'static str,
+    pub fi_project_hint: &'static str,
+    pub fi_project_missing: &'static str,
+    pub fi_project_not_flake: &'static str,
+    pub fi_picker_title: &'static str,
+    pub fi_picker_empty: &'static str,
+    pub fi_picker_enter: &'static str,
+    pub fi_picker_up: &'static str,
+    pub fi_picker_select: &'static str,
+    pub fi_report_hint: &'static str,
+    pub fi_report_empty: &'static str,
+    pub fi_report_counting: &'static str,
+    pub fi_report_write: &'static str,
+    pub fi_report_copy: &'static str,
+    pub fi_report_written: &'static str,
+    pub fi_report_copied: &'static str,
+    pub fi_rollback_done: &'static str,
+    pub fi_rollback_failed: &'static str,
+    pub fi_rolled_back: &'static str,
+    pub fi_edit_url_title: &'static str,
+    pub fi_edit_branch_title: &'static str,
+    pub fi_edit_branch_note: &'static str,
+    pub fi_add_name_title: &'static str,
+    pub fi_add_url_title: &'static str,
+    pub fi_remove_title: &'static str,
+    pub fi_remove_note: &'static str,
+    pub fi_edit_followed: &'static str,
+    pub fi_edit_not_declared: &'static str,
+    pub fi_relocking: &'static str,
+    pub fi_edit_done: &'static str,
+    pub fi_edit_failed: &'static str,
+    pub fi_relock_failed: &'static str,
+    pub fi_branch_no_nixpkgs: &'static str,
+    pub fi_branch_title: &'static str,
+    pub fi_branch_current: &'static str,
+    pub fi_branch_notes: &'static str,
+    pub fi_branch_switched: &'static str,
+    pub fi_branch_previewing: &'static str,
+    pub fi_branch_preview_failed: &'static str,
+    pub fi_branch_impact_counts: &'static str,
+    pub fi_branch_impact_updated: &'static str,
+    pub fi_branch_impact_added: &'static str,
+    pub fi_branch_keep: &'static str,
+    pub fi_branch_revert: &'static str,
+    pub fi_commit_title: &'static str,
+    pub fi_commit_message: &'static str,
+    pub fi_commit_confirm: &'static str,
+    pub fi_commit_skip: &'static str,
+    pub fi_all_current: &'static str,
+    pub fi_update_all_done: &'static str,
+    pub fi_update_all_next: &'static str,
+    pub fi_commit_done: &'static str,
+    pub fi_commit_failed: &'static str,
     pub fi_detail_type: &'static str,
     pub fi_detail_url: &'static str,
     pub fi_detail_branch: &'static str,
@@ -171,12 +504,72 @@ pub struct Strings {
     pub fi_detail_narhash: &'static str,
     pub fi_detail_age: &'static str,
     pub fi_detail_locked: &'static str,
+    pub fi_detail_upstream: &'static str,
+    pub fi_detail_watch: &'static str,
+    pub fi_watch_stale_detail: &'static str,
+    pub fi_watch_stale_count: &'static str,
+    pub fi_detail_last_commit: &'static str,
+    pub fi_upstream_checking: &'static str,
+    pub fi_upstream_up_to_date: &'static str,
+    pub fi_upstream_behind: &'static str,
+    pub fi_upstream_rate_limited: &'static str,
+    pub fi_upstream_offline: &'static str,
+    pub fi_upstream_error: &'static str,
+    pub fi_detail_health: &'static str,
+    pub fi_issue_archived: &'static str,
+    pub fi_issue_archived_hint: &'static str,
+    pub fi_issue_moved: &'static str,
+    pub fi_issue_moved_hint: &'static str,
+    pub fi_issue_missing: &'static str,
+    pub fi_issue_missing_hint: &'static str,
+    pub fi_issue_ref_deleted: &'static str,
+    pub fi_issue_ref_deleted_hint: &'static str,
+    pub fi_issue_tag_moved: &'static str,
+    pub fi_issue_tag_moved_hint: &'static str,
     pub fi_detail_follows: &'static str,
 
     // === Storage ===
     pub sto_dashboard: &'static str,
     pub sto_explorer: &'static str,
+    pub sto_ranking: &'static str,
+    pub sto_ranking_title: &'static str,
+    pub sto_ranking_scanning: &'static str,
+    pub sto_ranking_rescanning: &'static str,
+    pub sto_ranking_summary: &'static str,
+    pub sto_ranking_jumped: &'static str,
+    pub sto_ranking_empty: &'static str,
+    pub sto_col_origin: &'static str,
+    pub sto_col_closure: &'static str,
+    pub sto_col_change: &'static str,
+    pub sto_origin_system: &'static str,
+    pub sto_origin_user: &'static str,
+    pub sto_origin_root: &'static str,
+    pub sto_why_title: &'static str,
+    pub sto_why_running: &'static str,
+    pub job_sto_ranking: &'static str,
+    pub job_sto_why: &'static str,
+    pub job_sto_suggestions: &'static str,
+    pub job_sto_sugg_apply: &'static str,
+    pub pal_sto_ranking: &'static str,
+    pub sto_boot: &'static str,
+    pub sto_boot_title: &'static str,
+    pub sto_boot_summary: &'static str,
+    pub sto_boot_unavailable: &'static str,
+    pub sto_col_entry: &'static str,
+    pub sto_col_kernel: &'static str,
+    pub sto_boot_shared: &'static str,
+    pub sto_boot_orphans: &'static str,
+    pub sto_boot_no_orphans: &'static str,
+    pub sto_boot_clean_title: &'static str,
+    pub sto_boot_clean_msg: &'static str,
+    pub sto_boot_cleaned: &'static str,
+    pub sto_boot_clean_failed: &'static str,
+    pub sto_boot_action: &'static str,
+    pub pal_sto_boot: &'static str,
+    pub pal_sto_suggestions: &'static str,
+    pub pal_sto_why: &'static str,
     pub sto_clean: &'static str,
+    pub sto_budget: &'static str,
     pub sto_history: &'static str,
     pub sto_disk_title: &'static str,
     pub sto_breakdown_title: &'static str,
@@ -221,14 +614,58 @@ pub struct Strings {
     pub sto_cleanups: &'static str,
     pub sto_history_log: &'static str,
     pub sto_freed: &'static str,
+    pub sto_budget_target_title: &'static str,
+    pub sto_budget_target: &'static str,
+    pub sto_budget_now: &'static str,
+    pub sto_budget_needed: &'static str,
+    pub sto_budget_planning: &'static str,
+    pub sto_budget_stale: &'static str,
+    pub sto_budget_reached: &'static str,
+    pub sto_budget_plan_title: &'static str,
+    pub sto_budget_step_gc: &'static str,
+    pub sto_budget_step_results: &'static str,
+    pub sto_budget_step_gens: &'static str,
+    pub sto_budget_step_optimise: &'static str,
+    pub sto_budget_running: &'static str,
+    pub sto_budget_predicted: &'static str,
+    pub sto_budget_ok: &'static str,
+    pub sto_budget_short: &'static str,
+    pub sto_budget_hint: &'static str,
+    pub sto_budget_sudo_note: &'static str,
+    pub sto_suggestions: &'static str,
+    pub sto_sugg_scanning: &'static str,
+    pub sto_sugg_nothing: &'static str,
+    pub sto_sugg_results: &'static str,
+    pub sto_sugg_dev_caches: &'static str,
+    pub sto_sugg_home_manager: &'static str,
+    pub sto_sugg_gc: &'static str,
+    pub sto_sugg_selected: &'static str,
+    pub sto_sugg_overlap: &'static str,
+    pub sto_sugg_hint: &'static str,
+    pub sto_sugg_total: &'static str,
+    pub sto_sugg_none_selected: &'static str,
+    pub sto_sugg_history: &'static str,
+    pub sto_sugg_staged: &'static str,
     pub sto_paths_removed: &'static str,
 
     // === Settings ===
     pub settings_theme: &'static str,
+    pub settings_theme_custom: &'static str,
+    pub settings_theme_preview: &'static str,
+    pub settings_theme_sample: &'static str,
+    pub settings_theme_files: &'static str,
+    pub theme_load_failed: &'static str,
+    pub theme_reloaded: &'static str,
+    pub config_reloaded: &'static str,
+    pub config_reload_failed: &'static str,
     pub settings_language: &'static str,
     pub settings_layout: &'static str,
     pub settings_nixpkgs: &'static str,
     pub settings_saved: &'static str,
+    pub time_just_now: &'static str,
+    pub time_min_ago: &'static str,
+    pub time_h_ago: &'static str,
+    pub time_d_ago: &'static str,
     pub settings_config_path: &'static str,
 
     // === Coming Soon ===
@@ -238,6 +675,31 @@ pub struct Strings {
     // === Status bar ===
     pub status_navigate: &'static str,
     pub status_switch_tab: &'static str,
+    pub nix_degraded_title: &'static str,
+    pub too_small_title: &'static str,
+    pub too_small_size: &'static str,
+    pub too_small_hint: &'static str,
+    pub ext_running: &'static str,
+    pub ext_failed: &'static str,
+    pub ext_no_output: &'static str,
+    pub nix_degraded_module: &'static str,
+    pub nix_problem_no_store: &'static str,
+    pub nix_fix_no_store: &'static str,
+    pub nix_problem_no_cli: &'static str,
+    pub nix_fix_no_cli: &'static str,
+    pub nix_problem_daemon_down: &'static str,
+    pub nix_fix_daemon_down: &'static str,
+    pub nix_problem_store_ro: &'static str,
+    pub nix_fix_store_ro: &'static str,
+    pub nix_degraded_still_works: &'static str,
+    pub nix_degraded_retry: &'static str,
+    pub nix_not_nixos_title: &'static str,
+    pub nix_not_nixos_module: &'static str,
+    pub nix_problem_not_nixos: &'static str,
+    pub nix_fix_not_nixos: &'static str,
+    pub nix_not_nixos_still_works: &'static str,
+    pub nix_retry_ok: &'static str,
+    pub nix_retry_failed: &'static str,
     pub status_quit: &'static str,
     pub status_change: &'static str,
 
@@ -250,6 +712,14 @@ pub struct Strings {
     // === Error Translator module ===
     pub err_analyze: &'static str,
     pub err_submit: &'static str,
+    pub err_history: &'static str,
+    pub err_history_empty: &'static str,
+    pub err_history_no_match: &'static str,
+    pub err_history_time: &'static str,
+    pub err_history_source: &'static str,
+    pub err_history_rule: &'static str,
+    pub err_history_open: &'static str,
+    pub err_history_delete: &'static str,
     pub err_paste_hint: &'static str,
     pub err_input: &'static str,
     pub err_found: &'static str,
@@ -257,6 +727,10 @@ pub struct Strings {
     pub err_problem: &'static str,
     pub err_solution: &'static str,
     pub err_understanding: &'static str,
+    pub err_tree_title: &'static str,
+    pub err_tree_frames: &'static str,
+    pub err_tree_hint: &'static str,
+    pub err_tree_summary: &'static str,
     pub err_new_analysis: &'static str,
     pub err_submit_pattern: &'static str,
     pub err_submit_title: &'static str,
@@ -271,6 +745,32 @@ pub struct Strings {
     pub err_idle_hint: &'static str,
     pub err_start_input: &'static str,
     pub err_piped_hint: &'static str,
+    pub pipe_detected: &'static str,
+    pub pipe_kind_rebuild: &'static str,
+    pub pipe_kind_build: &'static str,
+    pub pipe_kind_flake: &'static str,
+    pub pipe_kind_journal: &'static str,
+    pub pipe_kind_error: &'static str,
+    pub err_from_clipboard: &'static str,
+    pub err_from_file: &'static str,
+    pub err_loaded_clipboard: &'static str,
+    pub err_loaded_file: &'static str,
+    pub err_clipboard_empty: &'static str,
+    pub err_file_title: &'static str,
+    pub err_file_prompt: &'static str,
+    pub err_file_hint: &'static str,
+    pub err_open_in_editor: &'static str,
+    pub err_editor_notice: &'static str,
+    pub err_editor_failed: &'static str,
+    pub err_location_missing: &'static str,
+    pub err_in_config: &'static str,
+    pub err_did_you_mean: &'static str,
+    pub err_explain: &'static str,
+    pub err_explain_title: &'static str,
+    pub err_explain_sent: &'static str,
+    pub err_explain_streaming: &'static str,
+    pub err_explain_failed: &'static str,
+    pub err_explain_disabled: &'static str,
     pub err_ai_ask: &'static str,
     pub err_ai_analyzing: &'static str,
     pub err_ai_result: &'static str,
@@ -299,6 +799,17 @@ pub struct Strings {
     pub settings_path_found_config: &'static str,
     pub settings_path_not_found: &'static str,
     pub settings_path_no_config: &'static str,
+    pub settings_refresh_section: &'static str,
+    pub settings_src_services: &'static str,
+    pub settings_src_ports: &'static str,
+    pub settings_src_flake_inputs: &'static str,
+    pub settings_src_options: &'static str,
+    pub settings_src_storage: &'static str,
+    pub settings_refresh_value: &'static str,
+    pub settings_off: &'static str,
+    pub settings_refresh_hint: &'static str,
+    pub settings_refresh_invalid: &'static str,
+    pub stale_badge: &'static str,
 
     // === Services & Ports module ===
     pub svc_overview: &'static str,
@@ -307,9 +818,25 @@ pub struct Strings {
     pub svc_manage: &'static str,
     pub svc_running: &'static str,
     pub svc_failed: &'static str,
+    pub svc_socket_idle: &'static str,
+    pub svc_socket_label: &'static str,
     pub svc_total: &'static str,
     pub svc_total_containers: &'static str,
     pub svc_ports_open: &'static str,
+    pub svc_mounts_title: &'static str,
+    pub svc_mounts_loading: &'static str,
+    pub svc_mounts_none: &'static str,
+    pub svc_mounts_ok: &'static str,
+    pub svc_mounts_problems: &'static str,
+    pub svc_mount_mounted: &'static str,
+    pub svc_mount_automount: &'static str,
+    pub svc_mount_failed: &'static str,
+    pub svc_mount_unmounted: &'static str,
+    pub svc_mount_stale: &'static str,
+    pub svc_disk_passed: &'static str,
+    pub svc_disk_failed: &'static str,
+    pub svc_disk_unknown: &'static str,
+    pub svc_disk_degraded: &'static str,
     pub svc_shown: &'static str,
     pub svc_no_entries: &'static str,
     pub svc_no_ports: &'static str,
@@ -318,8 +845,42 @@ pub struct Strings {
     pub svc_col_address: &'static str,
     pub svc_col_owner: &'static str,
     pub svc_col_process: &'static str,
+    pub svc_col_pid: &'static str,
+    pub svc_col_state: &'static str,
+    pub svc_col_name: &'static str,
+    pub svc_col_enabled: &'static str,
+    pub svc_col_ports: &'static str,
+    pub svc_col_cpu: &'static str,
+    pub svc_col_memory: &'static str,
+    pub svc_sorted_by: &'static str,
+    pub svc_col_description: &'static str,
+    pub svc_proxies: &'static str,
+    pub svc_col_domain: &'static str,
+    pub svc_col_upstream: &'static str,
+    pub svc_proxy_dangling: &'static str,
+    pub svc_proxy_remote: &'static str,
+    pub svc_proxy_dangling_count: &'static str,
+    pub svc_port_warnings: &'static str,
+    pub svc_port_duplicate: &'static str,
+    pub svc_port_not_listening: &'static str,
+    pub svc_port_more: &'static str,
     pub svc_logs_for: &'static str,
     pub svc_no_logs: &'static str,
+    pub svc_logs_tail: &'static str,
+    pub svc_logs_since: &'static str,
+    pub svc_logs_until: &'static str,
+    pub svc_logs_grep: &'static str,
+    pub svc_logs_range_boot: &'static str,
+    pub svc_logs_range_hour: &'static str,
+    pub svc_logs_range_today: &'static str,
+    pub svc_logs_errors_only: &'static str,
+    pub svc_logs_input_since: &'static str,
+    pub svc_logs_input_until: &'static str,
+    pub svc_logs_input_grep: &'static str,
+    pub svc_logs_bad_time: &'static str,
+    pub svc_logs_loading: &'static str,
+    pub svc_logs_no_match: &'static str,
+    pub svc_logs_filters_cleared: &'static str,
     pub svc_no_selection: &'static str,
     pub svc_status_label: &'static str,
     pub svc_enabled_label: &'static str,
@@ -329,11 +890,21 @@ pub struct Strings {
     pub svc_act_start: &'static str,
     pub svc_act_stop: &'static str,
     pub svc_act_restart: &'static str,
+    pub svc_no_failed: &'static str,
+    pub svc_restart_failed_title: &'static str,
+    pub svc_restart_and_follow: &'static str,
+    pub svc_restarted_summary: &'static str,
+    pub svc_logs_following: &'static str,
+    pub job_svc_restart: &'static str,
     pub svc_act_enable: &'static str,
     pub svc_act_disable: &'static str,
     pub svc_confirm_action: &'static str,
     pub svc_action_title: &'static str,
     pub svc_sudo_note: &'static str,
+    pub svc_kill_title: &'static str,
+    pub svc_kill_action: &'static str,
+    pub svc_port_no_pid: &'static str,
+    pub svc_restart_sudo_note: &'static str,
     pub svc_load_error: &'static str,
     pub svc_load_error_hint: &'static str,
     pub svc_filter_all: &'static str,
@@ -349,6 +920,38 @@ pub struct Strings {
     pub rb_history: &'static str,
     pub rb_idle_title: &'static str,
     pub rb_idle_hint: &'static str,
+    pub rb_repl_hint: &'static str,
+    pub rb_targets_hint: &'static str,
+    pub rb_targets_title: &'static str,
+    pub rb_targets_loading: &'static str,
+    pub rb_targets_build: &'static str,
+    pub rb_targets_reload: &'static str,
+    pub rb_hosts_title: &'static str,
+    pub rb_hosts_follow: &'static str,
+    pub rb_hosts_none: &'static str,
+    pub rb_hosts_pick: &'static str,
+    pub rb_hosts_flake: &'static str,
+    pub rb_host: &'static str,
+    pub rb_target_no_detach: &'static str,
+    pub rb_repl_unavailable: &'static str,
+    pub rb_repl_running: &'static str,
+    pub rb_repl_failed: &'static str,
+    pub rb_quit_title: &'static str,
+    pub rb_quit_running: &'static str,
+    pub rb_quit_detach_note: &'static str,
+    pub rb_quit_detach: &'static str,
+    pub rb_quit_stop: &'static str,
+    pub rb_quit_stay: &'static str,
+    pub rb_detach_notice: &'static str,
+    pub rb_detached_to: &'static str,
+    pub rb_detach_failed: &'static str,
+    pub rb_detach_activating: &'static str,
+    pub rb_detach_not_stopped: &'static str,
+    pub rb_detach_stopping: &'static str,
+    pub rb_cancel_activating: &'static str,
+    pub rb_quit_activating: &'static str,
+    pub rb_quit_when_done: &'static str,
+    pub rb_quit_waiting: &'static str,
     pub rb_detecting: &'static str,
     pub rb_config_detected: &'static str,
     pub rb_config_flakes: &'static str,
@@ -360,12 +963,23 @@ pub struct Strings {
     pub rb_current_mode: &'static str,
     pub rb_cycle_mode: &'static str,
     pub rb_last_build: &'static str,
+    pub rb_sys_builds_title: &'static str,
+    pub rb_sys_builds_none: &'static str,
+    pub rb_sys_builds_hint: &'static str,
+    pub rb_sys_builder: &'static str,
+    pub rb_sys_client: &'static str,
+    pub rb_attach_title: &'static str,
+    pub rb_attach_hint: &'static str,
+    pub rb_attach_waiting: &'static str,
+    pub rb_attach_finished: &'static str,
+    pub rb_attach_no_log: &'static str,
     pub rb_estimated_time: &'static str,
     pub rb_mode_switch: &'static str,
     pub rb_mode_boot: &'static str,
     pub rb_mode_test: &'static str,
     pub rb_mode_build: &'static str,
     pub rb_mode_dry: &'static str,
+    pub rb_mode_rollback: &'static str,
     pub rb_phase_idle: &'static str,
     pub rb_phase_preparing: &'static str,
     pub rb_phase_evaluating: &'static str,
@@ -391,18 +1005,80 @@ pub struct Strings {
     pub rb_explain_bootloader: &'static str,
     pub rb_explain_done: &'static str,
     pub rb_explain_failed: &'static str,
+    pub rb_line_build: &'static str,
+    pub rb_line_fetch: &'static str,
+    pub rb_line_build_plan: &'static str,
+    pub rb_line_fetch_plan: &'static str,
+    pub rb_line_evaluating: &'static str,
+    pub rb_line_activating: &'static str,
+    pub rb_line_etc: &'static str,
+    pub rb_line_restart: &'static str,
+    pub rb_line_start: &'static str,
+    pub rb_line_stop: &'static str,
+    pub rb_line_reload: &'static str,
+    pub rb_line_bootloader: &'static str,
+    pub rb_line_warning: &'static str,
+    pub rb_line_error: &'static str,
+    pub rb_line_trace: &'static str,
     pub rb_confirm_title: &'static str,
     pub rb_confirm_mode: &'static str,
+    pub rb_confirm_specialisation: &'static str,
+    pub rb_spec_default: &'static str,
+    pub rb_spec_cycle: &'static str,
     pub rb_confirm_cmd: &'static str,
     pub rb_sudo_note: &'static str,
+    pub rb_net_title: &'static str,
+    pub rb_net_title_slow: &'static str,
+    pub rb_net_metered: &'static str,
+    pub rb_net_slow: &'static str,
+    pub rb_net_size: &'static str,
+    pub rb_net_eta: &'static str,
+    pub rb_net_offline_hint: &'static str,
+    pub rb_net_continue: &'static str,
+    pub rb_net_offline: &'static str,
+    pub rb_confirm_offline: &'static str,
+    pub rb_confirm_rollback_title: &'static str,
+    pub rb_confirm_rollback: &'static str,
+    pub rb_rollback_none: &'static str,
+    pub rb_changes_restored: &'static str,
     pub rb_stat_built: &'static str,
     pub rb_stat_fetched: &'static str,
     pub rb_live_output: &'static str,
+    pub rb_compact_eta: &'static str,
+    pub rb_compact_overdue: &'static str,
+    pub rb_compact_last_error: &'static str,
     pub rb_auto_scroll: &'static str,
     pub rb_resume_scroll: &'static str,
     pub rb_log_empty: &'static str,
     pub rb_log_empty_hint: &'static str,
+    pub rb_busy: &'static str,
+    pub rb_backend_needs_flake: &'static str,
+    pub rb_backend_missing: &'static str,
+    pub rb_smart_fetched: &'static str,
+    pub rb_smart_built: &'static str,
+    pub rb_smart_lines: &'static str,
+    pub rb_tree_title: &'static str,
+    pub rb_tree_counts: &'static str,
+    pub rb_tree_critical: &'static str,
+    pub rb_tree_loading: &'static str,
+    pub rb_tree_no_deps: &'static str,
+    pub rb_tree_empty: &'static str,
+    pub rb_log_nothing: &'static str,
+    pub rb_log_exported: &'static str,
+    pub rb_log_no_error: &'static str,
+    pub rb_log_copied: &'static str,
+    pub rb_log_no_clipboard: &'static str,
+    pub rb_share_disabled: &'static str,
+    pub rb_share_uploading: &'static str,
+    pub rb_share_confirm_title: &'static str,
+    pub rb_share_confirm: &'static str,
+    pub rb_share_confirm_hint: &'static str,
+    pub rb_share_copied: &'static str,
+    pub rb_share_done: &'static str,
     pub rb_changes_summary: &'static str,
+    pub rb_warnings_title: &'static str,
+    pub rb_warnings_hint: &'static str,
+    pub rb_warnings_no_option: &'static str,
     pub rb_changes_added: &'static str,
     pub rb_changes_removed: &'static str,
     pub rb_changes_updated: &'static str,
@@ -413,20 +1089,47 @@ pub struct Strings {
     pub rb_kernel_changed: &'static str,
     pub rb_reboot_needed: &'static str,
     pub rb_services_restarted: &'static str,
+    pub rb_preview_title: &'static str,
+    pub rb_preview_hint: &'static str,
+    pub rb_preview_running: &'static str,
+    pub rb_preview_needs_build: &'static str,
+    pub rb_preview_sudo: &'static str,
+    pub rb_preview_nothing: &'static str,
+    pub rb_preview_systemd: &'static str,
+    pub rb_preview_stop: &'static str,
+    pub rb_preview_restart: &'static str,
+    pub rb_preview_reload: &'static str,
+    pub rb_preview_start: &'static str,
+    pub rb_preview_kept: &'static str,
+    pub rb_preview_switch: &'static str,
     pub rb_history_empty: &'static str,
     pub rb_history_empty_hint: &'static str,
+    pub rb_breakdown_avg: &'static str,
+    pub rb_breakdown_eval: &'static str,
+    pub rb_breakdown_fetch: &'static str,
+    pub rb_breakdown_build: &'static str,
+    pub rb_breakdown_activate: &'static str,
+    pub rb_breakdown_boot: &'static str,
+    pub rb_up_to_date_activation: &'static str,
+    pub rb_up_to_date_nothing: &'static str,
+    pub rb_up_to_date_desc: &'static str,
+    pub rb_up_to_date_short: &'static str,
     pub rb_password_label: &'static str,
     pub rb_password_hint: &'static str,
     pub rb_nopasswd_hint: &'static str,
     pub rb_password_submit: &'static str,
 
     // === Generations (additional) ===
-    pub gen_action_confirmed: &'static str,
-    pub gen_undo_closed: &'static str,
     pub gen_pin_updated: &'static str,
     pub gen_cannot_restore_current: &'static str,
     pub gen_cannot_delete_current: &'static str,
     pub gen_cannot_delete_pinned: &'static str,
+    pub gen_protected_booted: &'static str,
+    pub gen_protected_default: &'static str,
+    pub gen_confirm_protected: &'static str,
+    pub gen_protected_warning: &'static str,
+    pub gen_protected_skipped: &'static str,
+    pub gen_cleanup_refused: &'static str,
     pub gen_no_match_filter: &'static str,
     pub gen_no_found: &'static str,
     pub gen_from: &'static str,
@@ -438,11 +1141,21 @@ pub struct Strings {
     pub gen_no_comparison: &'static str,
     pub gen_diff_hint: &'static str,
     pub gen_command_label: &'static str,
-    pub gen_undo_available: &'static str,
-    pub gen_dismiss: &'static str,
     pub gen_deleted_count: &'static str,
+    pub gen_staged_delete: &'static str,
     pub gen_system_label: &'static str,
     pub gen_hm_label: &'static str,
+    pub gen_col_id: &'static str,
+    pub gen_col_date: &'static str,
+    pub gen_col_version: &'static str,
+    pub gen_col_packages: &'static str,
+    pub gen_col_size: &'static str,
+    pub gen_col_commit: &'static str,
+    pub gen_git_diff_pick: &'static str,
+    pub gen_git_diff_unavailable: &'static str,
+    pub gen_git_diff_unknown_commit: &'static str,
+    pub gen_git_diff_notice: &'static str,
+    pub gen_git_diff_failed: &'static str,
     pub gen_detection_failed: &'static str,
     pub gen_ensure_nixos: &'static str,
 
@@ -516,6 +1229,129 @@ pub struct Strings {
     pub health_detail_fresh_ok: &'static str,
     pub health_detail_fresh_warn: &'static str,
     pub health_detail_fresh_crit: &'static str,
+    pub health_name_flake_watch: &'static str,
+    pub health_desc_flake_watch: &'static str,
+    pub health_fix_flake_watch: &'static str,
+    pub health_detail_flake_watch_ok: &'static str,
+    pub health_detail_flake_watch_stale: &'static str,
+    pub health_name_signatures: &'static str,
+    pub health_desc_signatures: &'static str,
+    pub health_detail_sigs_ok: &'static str,
+    pub health_detail_sigs_untrusted: &'static str,
+    pub health_detail_sigs_no_require: &'static str,
+    pub health_fix_sigs_require: &'static str,
+    pub health_fix_sigs_bad: &'static str,
+    pub health_fix_sigs_unknown_key: &'static str,
+    pub health_fix_sigs_unsigned: &'static str,
+    pub health_name_integrity: &'static str,
+    pub health_desc_integrity: &'static str,
+    pub health_detail_integrity_ok: &'static str,
+    pub health_detail_integrity_bad: &'static str,
+    pub health_detail_skipped_not_nixos: &'static str,
+    pub health_fix_integrity: &'static str,
+    pub health_name_flake_eval: &'static str,
+    pub health_desc_flake_eval: &'static str,
+    pub health_detail_flake_eval_ok: &'static str,
+    pub health_detail_flake_eval_failed: &'static str,
+    pub health_detail_flake_eval_timeout: &'static str,
+    pub health_fix_flake_eval: &'static str,
+    pub health_dep_feat_nixos_option: &'static str,
+    pub health_dep_feat_journalctl: &'static str,
+    pub health_dep_feat_git: &'static str,
+    pub health_dep_feat_containers: &'static str,
+    pub health_dep_feat_busctl: &'static str,
+    pub health_detail_dep_missing: &'static str,
+    pub health_detail_dep_old: &'static str,
+    pub health_fix_dep_install: &'static str,
+    pub health_no_eval_error: &'static str,
+    pub health_name_boot_space: &'static str,
+    pub health_desc_boot_space: &'static str,
+    pub health_detail_boot_space: &'static str,
+    pub health_fix_boot_space_clean: &'static str,
+    pub health_fix_boot_space_limit: &'static str,
+    pub health_name_booted: &'static str,
+    pub health_desc_booted: &'static str,
+    pub health_detail_booted_same: &'static str,
+    pub health_detail_booted_switched: &'static str,
+    pub health_detail_booted_kernel: &'static str,
+    pub health_fix_booted: &'static str,
+    pub health_name_initrd_secrets: &'static str,
+    pub health_desc_initrd_secrets: &'static str,
+    pub health_detail_initrd_ok: &'static str,
+    pub health_detail_initrd_missing: &'static str,
+    pub health_fix_initrd: &'static str,
+    pub health_name_stale_entries: &'static str,
+    pub health_desc_stale_entries: &'static str,
+    pub health_detail_entries_ok: &'static str,
+    pub health_detail_entries_stale: &'static str,
+    pub health_fix_entries: &'static str,
+    pub health_name_power_daemons: &'static str,
+    pub health_desc_power_daemons: &'static str,
+    pub health_detail_power_ok: &'static str,
+    pub health_detail_power_none: &'static str,
+    pub health_detail_power_conflict: &'static str,
+    pub health_fix_power_none: &'static str,
+    pub health_fix_power_conflict: &'static str,
+    pub health_name_thermald: &'static str,
+    pub health_desc_thermald: &'static str,
+    pub health_detail_thermald_ok: &'static str,
+    pub health_detail_thermald_missing: &'static str,
+    pub health_fix_thermald: &'static str,
+    pub health_name_firmware: &'static str,
+    pub health_desc_firmware: &'static str,
+    pub health_detail_firmware_ok: &'static str,
+    pub health_detail_firmware_missing: &'static str,
+    pub health_fix_firmware: &'static str,
+    pub health_name_microcode: &'static str,
+    pub health_desc_microcode: &'static str,
+    pub health_detail_microcode_ok: &'static str,
+    pub health_detail_microcode_missing: &'static str,
+    pub health_fix_microcode: &'static str,
+    pub health_name_swap: &'static str,
+    pub health_desc_swap: &'static str,
+    pub health_detail_swap_none: &'static str,
+    pub health_detail_swap_ok: &'static str,
+    pub health_fix_swap: &'static str,
+    pub health_name_disk_ids: &'static str,
+    pub health_desc_disk_ids: &'static str,
+    pub health_detail_disk_ids_ok: &'static str,
+    pub health_detail_disk_ids_missing: &'static str,
+    pub health_fix_disk_ids: &'static str,
+    pub health_name_features: &'static str,
+    pub health_desc_features: &'static str,
+    pub health_detail_features_ok: &'static str,
+    pub health_detail_features_missing: &'static str,
+    pub health_fix_features: &'static str,
+    pub health_name_parallelism: &'static str,
+    pub health_desc_parallelism: &'static str,
+    pub health_detail_parallelism_ok: &'static str,
+    pub health_detail_parallelism_low: &'static str,
+    pub health_fix_parallelism: &'static str,
+    pub health_name_trusted_users: &'static str,
+    pub health_desc_trusted_users: &'static str,
+    pub health_detail_trusted_ok: &'static str,
+    pub health_detail_trusted_unsafe: &'static str,
+    pub health_fix_trusted_users: &'static str,
+    pub health_name_hibernate: &'static str,
+    pub health_desc_hibernate: &'static str,
+    pub health_detail_hibernate_lid: &'static str,
+    pub health_detail_hibernate_sizes: &'static str,
+    pub health_detail_hibernate_ready: &'static str,
+    pub health_detail_hibernate_swap: &'static str,
+    pub health_detail_hibernate_resume: &'static str,
+    pub health_detail_hibernate_lockdown: &'static str,
+    pub health_fix_hibernate: &'static str,
+    pub health_name_charge_threshold: &'static str,
+    pub health_desc_charge_threshold: &'static str,
+    pub health_detail_charge_set: &'static str,
+    pub health_detail_charge_full: &'static str,
+    pub health_detail_charge_unsupported: &'static str,
+    pub health_fix_charge: &'static str,
+    pub health_name_optimise: &'static str,
+    pub health_desc_optimise: &'static str,
+    pub health_detail_optimise_on: &'static str,
+    pub health_detail_optimise_off: &'static str,
+    pub health_fix_optimise: &'static str,
     pub health_desc_duplicates: &'static str,
     pub health_fix_duplicates: &'static str,
     pub health_detail_dupes_ok: &'static str,
@@ -523,6 +1359,21 @@ pub struct Strings {
     pub health_detail_dupes_crit: &'static str,
     pub health_applying_fix: &'static str,
     pub health_fix_error_detail: &'static str,
+    pub health_name_accounts: &'static str,
+    pub health_desc_accounts: &'static str,
+    pub health_fix_accounts: &'static str,
+    pub health_detail_accounts_ok: &'static str,
+    pub health_detail_accounts_drift: &'static str,
+    pub health_acc_imperative_user: &'static str,
+    pub health_acc_imperative_group: &'static str,
+    pub health_acc_group_drift: &'static str,
+    pub health_acc_extra_key: &'static str,
+    pub health_acc_mutable: &'static str,
+    pub health_acc_unreadable: &'static str,
+    pub health_acc_no_source: &'static str,
+    pub health_acc_summary: &'static str,
+    pub health_acc_clean: &'static str,
+    pub health_acc_remediation: &'static str,
 
     // === Flake Inputs (additional i18n) ===
     pub fi_error_load_failed: &'static str,
@@ -539,6 +1390,13 @@ pub struct Strings {
     // === Generations (additional i18n) ===
     pub gen_restore_msg: &'static str,
     pub gen_delete_msg: &'static str,
+    pub gen_gc_calculating: &'static str,
+    pub gen_gc_unknown: &'static str,
+    pub gen_gc_frees: &'static str,
+    pub gen_gc_only_in: &'static str,
+    pub gen_gc_kept: &'static str,
+    pub job_gen_gc_report: &'static str,
+    pub job_gen_reclaim: &'static str,
 
     // === Packages (additional i18n) ===
     pub pkg_searching_for: &'static str,
@@ -550,6 +1408,9 @@ pub struct Strings {
     pub opt_read_failed: &'static str,
     pub opt_phase_prebuilt: &'static str,
     pub opt_phase_fallback: &'static str,
+    pub opt_phase_cache: &'static str,
+    pub opt_phase_flake_parts: &'static str,
+    pub opt_flake_module: &'static str,
     pub opt_load_error: &'static str,
     pub opt_not_found: &'static str,
 
@@ -563,9 +1424,169 @@ pub fn get_strings(lang: Language) -> &'static Strings {
     match lang {
         Language::English => &EN,
         Language::German => &DE,
+        Language::Locale(i) => locales().get(i as usize).map_or(&EN, |l| l.strings),
+    }
+}
+
+/// A language loaded from a locale file
+pub struct Locale {
+    /// File name without `.toml`, stored as `language` in config.toml
+    pub code: String,
+    /// Shown in Settings
+    pub name: String,
+    pub strings: &'static Strings,
+    /// Keys that fell back to English
+    pub missing: usize,
+    /// Keys nixmate doesn't know (typos, removed strings)
+    pub unknown: Vec<String>,
+    /// Shipped with nixmate rather than read from the config directory
+    pub bundled: bool,
+}
+
+impl Locale {
+    /// Share of keys translated, in percent
+    pub fn coverage(&self) -> usize {
+        let total = key_count();
+        (total - self.missing.min(total)) * 100 / total.max(1)
     }
 }
 
+#[derive(Serialize, Deserialize, Default)]
+struct LocaleMeta {
+    name: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct LocaleFile {
+    #[serde(default)]
+    locale: LocaleMeta,
+    #[serde(default)]
+    strings: toml::Table,
+}
+
+/// Locales shipped in the binary: (code, file)
+const BUNDLED: &[(&str, &str)] = &[("fr", include_str!("../assets/locales/fr.toml"))];
+
+/// Bundled locales and the files found at startup, sorted by code. Files
+/// that fail to parse are skipped.
+pub fn locales() -> &'static [Locale] {
+    static LOCALES: OnceLock<Vec<Locale>> = OnceLock::new();
+    LOCALES.get_or_init(|| {
+        let mut locales: Vec<Locale> = BUNDLED
+            .iter()
+            .filter_map(|(code, text)| parse_locale(code.to_string(), text, true).ok())
+            .collect();
+        let entries = dirs::config_dir()
+            .map(|d| d.join("nixmate").join("locales"))
+            .and_then(|d| std::fs::read_dir(d).ok());
+        let paths = entries
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|e| e == "toml"));
+        for locale in paths.filter_map(|p| load_locale(&p).ok()) {
+            // An own file replaces the bundled one
+            locales.retain(|l| l.code != locale.code);
+            locales.push(locale);
+        }
+        locales.sort_by(|a, b| a.code.cmp(&b.code));
+        // Languages are indexed by u8
+        locales.truncate(u8::MAX as usize);
+        locales
+    })
+}
+
+fn load_locale(path: &std::path::Path) -> Result<Locale> {
+    let code = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .context("bad file name")?
+        .to_string();
+    let text = std::fs::read_to_string(path)?;
+    parse_locale(code, &text, false)
+}
+
+fn parse_locale(code: String, text: &str, bundled: bool) -> Result<Locale> {
+    let file: LocaleFile = toml::from_str(text)?;
+    let (strings, missing, unknown) = with_overrides(&EN, &file.strings)?;
+    Ok(Locale {
+        name: file.locale.name.unwrap_or_else(|| code.clone()),
+        code,
+        strings: Box::leak(Box::new(strings)),
+        missing,
+        unknown,
+        bundled,
+    })
+}
+
+fn key_count() -> usize {
+    match serde_json::to_value(&EN) {
+        Ok(serde_json::Value::Object(map)) => map.len(),
+        _ => 0,
+    }
+}
+
+/// `base` with the string values of `overrides` swapped in. Returns the
+/// strings, how many keys kept the base text, and the keys not in
+/// `Strings`. Translated texts are leaked: a locale lives as long as the
+/// program.
+fn with_overrides(
+    base: &Strings,
+    overrides: &toml::Table,
+) -> Result<(Strings, usize, Vec<String>)> {
+    use serde::de::value::{BorrowedStrDeserializer, Error, MapDeserializer};
+
+    let serde_json::Value::Object(map) = serde_json::to_value(base)? else {
+        anyhow::bail!("strings are not a map");
+    };
+    let mut missing = 0;
+    let mut pairs: Vec<(String, BorrowedStrDeserializer<'static, Error>)> = Vec::new();
+    for (key, value) in map {
+        let text: &'static str = match overrides.get(&key).and_then(|v| v.as_str()) {
+            Some(t) => Box::leak(t.to_string().into_boxed_str()),
+            None => {
+                missing += 1;
+                Box::leak(
+                    value
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string()
+                        .into_boxed_str(),
+                )
+            }
+        };
+        pairs.push((key, BorrowedStrDeserializer::new(text)));
+    }
+    let unknown = overrides
+        .keys()
+        .filter(|k| !pairs.iter().any(|(key, _)| key == *k))
+        .cloned()
+        .collect();
+    let strings = Strings::deserialize(MapDeserializer::<_, Error>::new(pairs.into_iter()))?;
+    Ok((strings, missing, unknown))
+}
+
+/// A locale file with every key and its English text, for translators
+pub fn locale_template() -> Result<String> {
+    #[derive(Serialize)]
+    struct Template<'a> {
+        locale: LocaleMeta,
+        strings: &'a Strings,
+    }
+    let body = toml::to_string(&Template {
+        locale: LocaleMeta {
+            name: Some("English".to_string()),
+        },
+        strings: &EN,
+    })?;
+    Ok(format!(
+        "# nixmate locale — save as ~/.config/nixmate/locales/<code>.toml,\n\
+         # set `name`, translate the strings you want; missing ones stay English\n\n{}",
+        body
+    ))
+}
+
 static EN: Strings = Strings {
     // App-level
     app_title: "nixmate",
@@ -574,6 +1595,163 @@ static EN: Strings = Strings {
     welcome_body: "I'm here to make your NixOS experience smoother. I can help you understand cryptic error messages, manage your generations and storage, keep an eye on your services and ports, and turn your configuration into beautiful diagrams. There's more to come, too.",
     welcome_continue: "Press Enter to get started",
     welcome_once: "I won't bother you with this message again. Have fun!",
+    start_top_issues: "Top issues",
+    start_no_issues: "✓ All checks passed",
+    start_open_doctor: "Enter: open Nix Doctor",
+    start_continue: "any other key: continue",
+    palette_title: "Commands",
+    palette_no_match: "No matching command",
+    jobs_title: "Jobs",
+    jobs_running: "Running",
+    jobs_none: "Nothing running in the background",
+    jobs_finished: "Recently finished",
+    jobs_cancelled: "cancelled",
+    jobs_cancelled_error: "Cancelled — press r to try again",
+    jobs_confirm: "Cancel \"{}\"? [y] Yes  [n] No",
+    jobs_not_cancellable: "Changes the system — can't be stopped halfway, it finishes on its own",
+    jobs_footer: "[j/k] Select  [c] Cancel  [Esc] Close",
+    staged_countdown: "{} in {}s — [u] Undo",
+    staged_more: "+{} more",
+    staged_flushing: "Running {} staged action(s)…",
+    staged_undone: "Undone: {}",
+    jobs_badge: "{} {} running · [J] Jobs",
+    vitals_generation: "Gen {}",
+    vitals_store_free: "Store: {} free",
+    vitals_units_ok: "no failed units",
+    vitals_units_failed: "{} failed units",
+    vitals_stale_inputs: "{} stale flake inputs",
+    jobs_cancel_done: "Cancelled: {}",
+    job_svc_load: "Loading services & ports",
+    job_svc_logs: "Fetching logs",
+    job_sto_load: "Scanning the Nix store",
+    job_sto_budget: "Planning cleanup",
+    job_sto_budget_step: "Running cleanup step",
+    job_cfg_poster: "Exporting poster",
+    job_cfg_diagram: "Scanning config files",
+    job_cfg_map: "Evaluating service map",
+    job_opt_load: "Loading options",
+    job_opt_overrides: "Finding overrides",
+    job_opt_changes: "Comparing nixpkgs revisions",
+    job_pkg_search: "Searching \"{}\"",
+    job_pkg_audit: "Auditing installed packages",
+    job_fi_load: "Reading flake.lock",
+    job_fi_update: "Updating flake inputs",
+    job_fi_upstream: "Checking upstream",
+    job_fi_relock: "Re-locking flake",
+    job_fi_impact: "Previewing nixpkgs switch",
+    job_health_scan: "Running health checks",
+    job_health_fix: "Applying fix",
+    job_err_ai: "AI analysis",
+    job_err_explain: "Explain with AI",
+    job_rebuild: "nixos-rebuild {}",
+    job_rb_target: "nix build {}",
+    job_rb_share: "Sharing build log",
+    job_rb_preview: "Previewing activation",
+    job_rb_tree: "Loading build dependencies",
+    key_help_title: "Keys",
+    key_help_global: "Everywhere",
+    key_help_none: "No module keys here",
+    key_help_footer: "? again: Help / About · any key: close",
+    status_palette: "Ctrl+P: commands",
+    kiosk_mode: "Dashboard mode ·",
+    kiosk_next_in: "next in {}s",
+    kiosk_paused: "paused",
+    kiosk_keys: "[Space] Pause  [←/→] Switch  [q] Quit",
+    keys_issues_flash: "Problems in [keys] — see Settings",
+    settings_keys_section: "Keybindings",
+    settings_keys_custom: "custom",
+    settings_keys_unknown: "Unknown action `{}`",
+    settings_keys_bad_key: "Can't read key `{}` ({})",
+    settings_keys_conflict: "{} is bound to: {}",
+    settings_keys_hint: "Remap in config.toml: [keys] down = [\"n\", \"Down\"]",
+    pal_gen_restore: "Roll back to the selected generation",
+    pal_gen_delete: "Delete selected generations",
+    pal_gen_pin: "Pin / unpin the selected generation",
+    pal_gen_select_all: "Select all deletable generations",
+    pal_gen_filter: "Filter packages of a generation",
+    pal_gen_diff: "Compare generations",
+    pal_gen_git_diff: "git diff of the two generations' configs",
+    pal_err_paste: "Paste an error to analyze",
+    pal_err_clipboard: "Analyze the error in the clipboard",
+    pal_err_file: "Load an error from a log file",
+    pal_err_ai: "Ask the AI about the error",
+    pal_err_explain: "Explain with AI (streamed)",
+    pal_err_open: "Open the error location in the editor",
+    pal_err_tree: "Show the error structure: warnings, notes, trace",
+    pal_err_history: "Browse past analyses",
+    pal_err_submit: "Submit a new error pattern",
+    pal_svc_search: "Search services",
+    pal_svc_ports: "Show listening ports",
+    svc_unit_not_systemd: "Only systemd units have a unit file",
+    svc_unit_failed: "Unit file not readable",
+    svc_unit_source: "Source:",
+    svc_unit_src_nixos: "NixOS configuration — {}",
+    svc_unit_src_package: "package {}",
+    svc_unit_src_local: "written outside Nix (/etc/systemd, runtime)",
+    svc_unit_triggers: "Restarts on change of:",
+    pal_svc_unit_file: "Show unit file",
+    pal_svc_start: "Start the selected service",
+    pal_svc_stop: "Stop the selected service",
+    pal_svc_restart: "Restart the selected service",
+    pal_svc_restart_failed: "Restart all failed units",
+    pal_svc_logs_follow: "Follow the logs (reload every 2s)",
+    pal_svc_enable: "Enable the selected service",
+    pal_svc_disable: "Disable the selected service",
+    pal_svc_logs_grep: "Grep the log",
+    pal_svc_logs_since: "Set the log time range",
+    pal_svc_logs_range: "Log time range: this boot, last hour, today",
+    pal_svc_logs_errors: "Show only errors in the log",
+    pal_svc_logs_more: "Fetch more log lines",
+    pal_sto_search: "Search store paths",
+    pal_sto_budget_step: "Run the next budget step",
+    pal_sto_budget_plan: "Re-plan the budget",
+    pal_sto_history: "Show cleanup history",
+    pal_cfg_export: "Export the poster as SVG",
+    pal_cfg_export_path: "Export the poster to a path (PNG/SVG)",
+    pal_cfg_rescan: "Re-scan the system",
+    pal_cfg_diagram: "Show the config diagram",
+    pal_cfg_map: "Show the service map",
+    pal_opt_search: "Search options",
+    pal_opt_copy_link: "Copy the search.nixos.org link of the selected option",
+    pal_opt_browse: "Browse the option tree",
+    pal_opt_overrides: "Show my overrides",
+    pal_opt_reeval: "Re-evaluate my configuration",
+    pal_opt_changed: "Show options changed from their default",
+    pal_rb_start: "Start {}",
+    pal_rb_trace: "Toggle --show-trace",
+    pal_rb_repl: "Open the eval console",
+    pal_rb_targets: "Build a flake package, dev shell or check",
+    pal_rb_hosts: "Pick the flake and host to rebuild",
+    pal_rb_tree: "Dashboard: dependency tree of the build",
+    pal_rb_warning_option: "Open the selected warning's option",
+    pal_ext_run: "Run the command again",
+    pal_rb_cancel: "Cancel the running build",
+    pal_rb_log_search: "Search the build log",
+    pal_rb_explain: "Explain log lines",
+    pal_rb_smart: "Rebuild: smart log view (group by derivation)",
+    pal_rb_export: "Rebuild: export log to file",
+    pal_rb_copy_error: "Rebuild: copy error block",
+    pal_rb_share: "Rebuild: share log via paste service",
+    pal_rb_history: "Show rebuild history",
+    pal_fl_update: "Update selected inputs",
+    pal_fl_update_all: "Update all inputs and rebuild",
+    pal_fl_add: "Add an input",
+    pal_fl_edit: "Edit the selected input's URL",
+    pal_fl_branch: "Change the selected input's branch",
+    pal_fl_switch_nixpkgs: "Switch nixpkgs release branch",
+    pal_fl_graph: "Show the input graph",
+    pal_fl_report: "Markdown report of the last update",
+    pal_fl_projects: "Switch flake project",
+    pal_pkg_search: "Search nixpkgs",
+    pal_pkg_insecure: "Insecure / EOL packages in the system",
+    pal_hl_rescan: "Run all checks again",
+    pal_hl_fix: "Show fixes",
+    pal_hl_accounts: "Audit users, groups and SSH keys",
+    pal_set_theme: "Change theme",
+    pal_set_language: "Change language",
+    pal_set_layout: "Change layout",
+    pal_set_config_path: "Set the NixOS config path",
+    pal_set_ai: "Toggle AI analysis",
     welcome_language: "Language:",
     welcome_lang_hint: "←/→",
     quit: "Quit",
@@ -620,12 +1798,15 @@ static EN: Strings = Strings {
     cfg_subtitle: "Share on r/unixporn, GitHub READMEs, or anywhere else",
     cfg_overview: "Overview",
     cfg_diagram: "Diagram",
+    cfg_services: "Services",
     cfg_generate: "Generate Poster",
     cfg_scanning: "Scanning system...",
     cfg_success: "Poster saved!",
     cfg_error: "Export failed",
-    cfg_hint: "Press Enter or [g] to generate your system poster",
+    cfg_hint: "Press Enter or [g] to generate your system poster, [e] to export it as PNG/SVG to a path",
     cfg_tip: "Open in browser or drag into Reddit / GitHub",
+    cfg_export_prompt: "Export poster to (Enter to export, Esc to cancel):",
+    cfg_export_formats: ".png renders at 2× with rsvg-convert, ImageMagick or Inkscape; any other extension writes SVG",
     cfg_diag_description: "Generate an architecture diagram of your NixOS config",
     cfg_diag_subtitle: "Visualize file structure, imports, and module dependencies",
     cfg_diag_generate: "Generate Diagram",
@@ -634,6 +1815,13 @@ static EN: Strings = Strings {
     cfg_diag_hint: "Press Enter or [g] to generate your config diagram",
     cfg_diag_tip: "Shows how your NixOS modules connect and depend on each other",
     cfg_diag_no_config: "No NixOS config found at /etc/nixos/",
+    cfg_map_loading: "Evaluating your configuration...",
+    cfg_map_loading_hint: "One nix eval over services, networking, virtualisation, programs, hardware and security",
+    cfg_map_failed: "Evaluation failed",
+    cfg_map_summary: "{} enabled in your config ({} including defaults and implied modules)",
+    cfg_map_showing_user: "[a] show defaults too",
+    cfg_map_showing_all: "[a] only my config",
+    cfg_map_empty: "Nothing enabled under these option roots",
 
     // Help / About
     help_subtitle: "All your NixOS tools in one TUI.",
@@ -669,11 +1857,57 @@ static EN: Strings = Strings {
     pkg_installed: "✓ Installed",
     pkg_not_installed: "Not installed",
     pkg_install_hint: "Install with:",
+    pkg_audit_title: "Insecure / EOL in this system",
+    pkg_audit_scanning: "Checking the system closure against nixpkgs meta (evaluates nixpkgs, takes a moment)…",
+    pkg_audit_clean: "No known-vulnerable or EOL packages ({} packages checked)",
+    pkg_audit_count: "{} of {} packages flagged by nixpkgs",
+    pkg_audit_failed: "Audit failed: {}",
+    pkg_audit_vulnerable: "vulnerable",
+    pkg_audit_reasons: "Reported by nixpkgs (meta.knownVulnerabilities):",
+    pkg_audit_upgrade: "Upgrade path: {} is at {} without known issues",
+    pkg_audit_no_upgrade: "No fixed version in this nixpkgs: update nixpkgs or replace the package",
+    pkg_audit_permitted: "Currently allowed via (remove once upgraded):",
+    pkg_tab_search: "Search",
+    pkg_tab_browse: "Browse",
+    pkg_cat_editors: "Editors",
+    pkg_cat_terminals: "Terminals",
+    pkg_cat_shells: "Shells",
+    pkg_cat_languages: "Languages",
+    pkg_cat_devtools: "Dev tools",
+    pkg_cat_servers: "Server software",
+    pkg_cat_browsers: "Browsers",
+    pkg_sort_popular: "popular",
+    pkg_sort_updated: "recently updated",
+    pkg_sort_name: "name",
+    pkg_browse_sort: "Sort: {}",
+    pkg_browse_hint: "[h/l] category  [s] sort  [Enter] details  [R] rebuild index",
+    pkg_browse_building: "Indexing all packages of {} (takes a minute, once a week)...",
+    pkg_browse_failed: "Package index could not be built: {}",
+    pkg_browse_empty: "No packages in this category",
+    pkg_browse_tracked: "Updates tracked since {}",
+    pkg_browse_not_tracked: "New versions show up here after the next index rebuild",
+    job_pkg_index: "Building package index",
+    pal_pkg_browse: "Browse packages by category",
+    pal_pkg_rebuild_index: "Rebuild package index",
 
     // Health / Nix Doctor
     health_dashboard: "Dashboard",
     health_fix: "Fix",
+    health_accounts: "Accounts",
     health_scanning: "Scanning system health",
+    health_scan_progress: "{}/{} checks done",
+    health_pending: "running…",
+    health_why: "Why:",
+    health_fix_snippet_hint: "Add to your configuration, then rebuild:",
+    health_fix_snippet_flash: "This fix is a config change — copy the snippet below into your configuration",
+    health_cat_storage: "Storage",
+    health_cat_updates: "Updates",
+    health_cat_security: "Security",
+    health_cat_boot: "Boot",
+    health_cat_system: "System",
+    health_cat_power: "Power",
+    health_cat_hardware: "Hardware",
+    health_cat_nixmate: "nixmate",
     health_score_label: "Health Score:",
     health_excellent: "Excellent",
     health_good: "Good",
@@ -687,16 +1921,45 @@ static EN: Strings = Strings {
     opt_tab_search: "Search",
     opt_tab_browse: "Browse",
     opt_tab_related: "Related",
+    opt_tab_overrides: "My Overrides",
+    opt_ovr_header: "Options your configuration sets",
+    opt_ovr_empty: "Your configuration doesn't override any options.",
+    opt_ovr_phase_eval: "Evaluating your configuration",
+    opt_ovr_scanning: "Scanning all options for values set by your config...",
+    opt_ovr_loading_hint: "A single nix eval over the whole configuration — may take up to a minute",
+    opt_ovr_eval_failed: "Could not evaluate your configuration",
+    opt_ovr_parse_failed: "Unexpected output from nix eval",
+    opt_ovr_retry: "Re-evaluate",
+    opt_tab_changed: "Changed",
+    opt_chg_loading: "Comparing options of your last two nixpkgs revisions",
+    opt_chg_no_gens: "No system generations found.",
+    opt_chg_same_rev: "All generations use the same nixpkgs revision — nothing changed.",
+    opt_chg_no_options: "No options.json for generation {} (documentation.nixos.enable = false?)",
+    opt_chg_none: "No changes of this kind.",
+    opt_chg_filter_all: "All changes",
+    opt_chg_kind_default: "Defaults",
+    opt_chg_kind_type: "Types",
+    opt_chg_kind_added: "Added",
+    opt_chg_kind_removed: "Removed",
+    opt_chg_kind_moved: "Moved",
     opt_loading: "Loading NixOS options",
     opt_loading_hint: "This reads the NixOS options database — first run may take 15-30s",
     opt_empty: "No options loaded. Are you on a NixOS system?",
     opt_search_hint: "Type / to search NixOS options...",
     opt_search_label: "Search:",
-    opt_search_empty: "Press / to search all NixOS options — try 'nginx', 'firewall', 'ssh'",
+    opt_search_empty: "Press / to search all NixOS options — try 'nginx', 'firewall', 'ssh' or filters like type:bool in:services.nginx readonly:false has:example",
     opt_results: "results",
     opt_no_results: "No options found.",
     opt_browse_hint: "Enter/→ expand · ←/h collapse · r related options",
     opt_related_label: "Related:",
+    opt_detail_examples: "In public configs:",
+    opt_examples_update: "Update examples",
+    opt_examples_no_url: "No [option_examples] url set in config.toml — only the bundled examples are available",
+    opt_link_copied: "🔗 {} copied ({})",
+    opt_link_none: "flake-parts options have no page on search.nixos.org or in the manual",
+    opt_examples_updated: "Usage examples updated: {} options (as of {})",
+    opt_examples_failed: "Examples download failed: {}",
+    job_opt_examples: "Download option examples",
     opt_related_empty: "Select an option in Search or Browse, then press r to see related options",
     opt_detail_type: "Type:",
     opt_detail_default: "Default:",
@@ -712,8 +1975,20 @@ static EN: Strings = Strings {
     fi_tab_update: "Update",
     fi_tab_history: "History",
     fi_tab_details: "Details",
+    fi_tab_graph: "Graph",
+    fi_graph_summary: "{} nodes in flake.lock",
+    fi_graph_summary_dupes: "{} nodes in flake.lock — {} sources locked in several revisions",
+    fi_graph_expand_all: "Expand all",
+    fi_graph_collapse_all: "Collapse all",
+    fi_graph_follows: "follows",
+    fi_graph_copies: "{} revisions locked",
     fi_loading: "Loading flake inputs",
     fi_empty: "No flake inputs found.",
+    fi_col_name: "Input",
+    fi_col_url: "URL",
+    fi_col_rev: "Rev",
+    fi_col_age: "Age",
+    fi_col_status: "Status",
     fi_no_flake_hint: "This module requires a Flakes-based NixOS configuration with a flake.lock file.",
     fi_update_hint: "Space: toggle · a: all · n: none · Enter: update selected",
     fi_updating: "Updating inputs",
@@ -721,6 +1996,74 @@ static EN: Strings = Strings {
     fi_refresh: "Refresh",
     fi_history_empty: "No update history yet.",
     fi_history_hint: "Update inputs in the Update tab to see changes here.",
+    fi_lockdiff_title: "Compare flake.lock with git revision",
+    fi_lockdiff_note: "A commit, tag or branch, e.g. HEAD~3 or the commit of the last good deploy",
+    fi_lockdiff_header: "flake.lock now vs {}",
+    fi_lockdiff_same: "No input is locked differently.",
+    fi_lockdiff_summary: "{} changed, {} unchanged",
+    fi_lockdiff_failed: "Comparison failed: {}",
+    fi_rollback_title: "Roll back this input to its previous revision?",
+    fi_rollback_note: "flake.lock is rewritten with the previous lock entry.",
+    fi_rollback_unavailable: "This entry cannot be rolled back (no change recorded)",
+    fi_report_title: "Update report",
+    fi_projects_title: "Flake projects",
+    fi_project_system: "System flake",
+    fi_project_gone: "no flake.nix",
+    fi_project_open: "open",
+    fi_project_add: "add directory",
+    fi_project_remove: "forget",
+    fi_project_hint: "F: choose another flake (e.g. ~/src/infra)",
+    fi_project_missing: "No flake.nix in {} (F picks another flake)",
+    fi_project_not_flake: "No flake.nix in {}",
+    fi_picker_title: "Choose a flake directory",
+    fi_picker_empty: "(no subdirectories)",
+    fi_picker_enter: "open",
+    fi_picker_up: "up",
+    fi_picker_select: "use this flake",
+    fi_report_hint: "[m] in History: Markdown report",
+    fi_report_empty: "The last update changed no inputs",
+    fi_report_counting: "Counting commits on GitHub…",
+    fi_report_write: "Write next to flake",
+    fi_report_copy: "Copy",
+    fi_report_written: "Report written: {}",
+    fi_report_copied: "Report copied ({})",
+    fi_rollback_done: "{} rolled back to {}",
+    fi_rollback_failed: "Rollback failed: {}",
+    fi_rolled_back: "rolled back",
+    fi_edit_url_title: "Edit input URL",
+    fi_edit_branch_title: "Change branch / ref",
+    fi_edit_branch_note: "Empty = default branch of the repository",
+    fi_add_name_title: "Add input: name",
+    fi_add_url_title: "Add input: URL",
+    fi_remove_title: "Remove input from flake.nix?",
+    fi_remove_note: "Also removed from the outputs arguments. Previous file kept as flake.nix.bak.",
+    fi_edit_followed: "'{}' is still followed by: {}",
+    fi_edit_not_declared: "'{}' is not declared in flake.nix",
+    fi_relocking: "Re-locking flake",
+    fi_edit_done: "flake.nix updated and re-locked (backup: flake.nix.bak)",
+    fi_edit_failed: "Editing flake.nix failed: {}",
+    fi_relock_failed: "Re-lock failed, flake.nix restored: {}",
+    fi_branch_no_nixpkgs: "No nixpkgs input in this flake",
+    fi_branch_title: "Switch {} to branch",
+    fi_branch_current: "current",
+    fi_branch_notes: "Release notes:",
+    fi_branch_switched: "{} now follows {} (flake.lock updated)",
+    fi_branch_previewing: "Estimating impact (nixos-rebuild dry-build)",
+    fi_branch_preview_failed: "Impact preview failed — the lock is updated, check with a dry-build",
+    fi_branch_impact_counts: "{} derivations to build, {} paths to fetch",
+    fi_branch_impact_updated: "{} packages change version:",
+    fi_branch_impact_added: "{} packages not in the running system",
+    fi_branch_keep: "Keep",
+    fi_branch_revert: "Revert to previous branch",
+    fi_commit_title: "Commit the updated flake.lock?",
+    fi_commit_message: "Message:",
+    fi_commit_confirm: "Commit",
+    fi_commit_skip: "Skip",
+    fi_all_current: "All inputs were already up to date, nothing to rebuild",
+    fi_update_all_done: "{} inputs updated (diff in History)",
+    fi_update_all_next: "[d] Dry-build  [Enter/s] Switch  [Esc] Not now",
+    fi_commit_done: "flake.lock committed ({})",
+    fi_commit_failed: "git commit failed: {}",
     fi_detail_type: "Type:",
     fi_detail_url: "URL:",
     fi_detail_branch: "Branch:",
@@ -728,12 +2071,72 @@ static EN: Strings = Strings {
     fi_detail_narhash: "NAR Hash:",
     fi_detail_age: "Age:",
     fi_detail_locked: "Locked at:",
+    fi_detail_upstream: "Upstream:",
+    fi_detail_watch: "Watch:",
+    fi_watch_stale_detail: "{} days old — threshold is {} days",
+    fi_watch_stale_count: "{} out of date",
+    fi_detail_last_commit: "Last commit:",
+    fi_upstream_checking: "Checking upstream",
+    fi_upstream_up_to_date: "Up to date with upstream",
+    fi_upstream_behind: "{} commits behind",
+    fi_upstream_rate_limited: "GitHub rate limit reached — retry after {} (set github_token for more)",
+    fi_upstream_offline: "Offline — showing lock age only",
+    fi_upstream_error: "Could not compare with upstream",
+    fi_detail_health: "Health:",
+    fi_issue_archived: "Repository archived — it gets no more updates",
+    fi_issue_archived_hint: "Switch to a maintained fork: [e] edit URL",
+    fi_issue_moved: "Repository moved to {}",
+    fi_issue_moved_hint: "Update the URL before the redirect disappears: [e] → github:{}",
+    fi_issue_missing: "Repository not found (deleted, or private without github_token)",
+    fi_issue_missing_hint: "Point the input at its new home [e], or remove it [d]",
+    fi_issue_ref_deleted: "Branch/tag '{}' no longer exists upstream",
+    fi_issue_ref_deleted_hint: "Pick another branch or tag: [b]",
+    fi_issue_tag_moved: "Tag '{}' was force-pushed — it points at another commit now",
+    fi_issue_tag_moved_hint: "Review the new commit before updating; pin the rev in the URL to keep the old one",
     fi_detail_follows: "Follows:",
 
     // Storage
     sto_dashboard: "Dashboard",
     sto_explorer: "Explorer",
+    sto_ranking: "Ranking",
+    sto_ranking_title: "Closure size per package and GC root",
+    sto_ranking_scanning: "Measuring closures… (nix path-info -S)",
+    sto_ranking_rescanning: "rescanning…",
+    sto_ranking_summary: "{} packages and roots · {} sizes asked from nix, the rest cached",
+    sto_ranking_jumped: "{} grew noticeably since the previous scan",
+    sto_ranking_empty: "No profile packages or GC roots found",
+    sto_col_origin: "From",
+    sto_col_closure: "Closure",
+    sto_col_change: "Change",
+    sto_origin_system: "system",
+    sto_origin_user: "user",
+    sto_origin_root: "root",
+    sto_why_title: "Why does the system need {}?",
+    sto_why_running: "Running nix why-depends…",
+    job_sto_ranking: "Closure ranking",
+    job_sto_why: "nix why-depends",
+    job_sto_suggestions: "Looking for cleanup suggestions",
+    job_sto_sugg_apply: "Applying cleanup suggestions",
+    pal_sto_ranking: "Rank packages by closure size",
+    sto_boot: "Boot",
+    sto_boot_title: "/boot — kernels and initrds",
+    sto_boot_summary: "{} entries · {} in kernel files · {} free on /boot",
+    sto_boot_unavailable: "/boot can't be analyzed: {}",
+    sto_col_entry: "Entry",
+    sto_col_kernel: "Kernel",
+    sto_boot_shared: "shared",
+    sto_boot_orphans: "{} orphaned files ({}) — referenced by no boot entry",
+    sto_boot_no_orphans: "No orphaned files. Fewer entries: delete generations, then switch-to-configuration boot.",
+    sto_boot_clean_title: "Delete orphaned /boot files",
+    sto_boot_clean_msg: "Delete {} files ({}) that no boot entry references?",
+    sto_boot_cleaned: "Deleted {} files, {} freed on /boot",
+    sto_boot_clean_failed: "Deleting needs sudo without a password prompt here. Run:\n{}",
+    sto_boot_action: "/boot orphans removed",
+    pal_sto_boot: "Storage: /boot kernels and orphans",
+    pal_sto_suggestions: "Storage: cleanup suggestions",
+    pal_sto_why: "Why does the system depend on this package?",
     sto_clean: "Clean",
+    sto_budget: "Budget",
     sto_history: "History",
     sto_disk_title: "Disk Usage",
     sto_breakdown_title: "Nix Store",
@@ -778,14 +2181,58 @@ static EN: Strings = Strings {
     sto_cleanups: "cleanups",
     sto_history_log: "History",
     sto_freed: "freed",
+    sto_budget_target_title: "Free-space target",
+    sto_budget_target: "Keep free:",
+    sto_budget_now: "Free now:",
+    sto_budget_needed: "To free:",
+    sto_budget_planning: "Planning cleanup (analysing GC roots)...",
+    sto_budget_stale: "Target changed — press [p] to re-plan",
+    sto_budget_reached: "Target already reached — nothing to do",
+    sto_budget_plan_title: "Plan",
+    sto_budget_step_gc: "Collect garbage (dead paths)",
+    sto_budget_step_results: "Remove {} result link(s), then collect garbage",
+    sto_budget_step_gens: "Delete {} generation(s) older than {} days",
+    sto_budget_step_optimise: "Optimise store (hard-link duplicates)",
+    sto_budget_running: "running",
+    sto_budget_predicted: "Predicted free:",
+    sto_budget_ok: "✓ reaches target",
+    sto_budget_short: "⚠ short of target",
+    sto_budget_hint: "[x/Enter] run next step  [p] re-plan  [+/-] target ±5 GB",
+    sto_budget_sudo_note: "Runs sudo -n: needs cached sudo credentials or NOPASSWD",
+    sto_suggestions: "Suggestions",
+    sto_sugg_scanning: "Looking for space to free…",
+    sto_sugg_nothing: "Nothing to clean up",
+    sto_sugg_results: "Remove {} result link(s) of old builds",
+    sto_sugg_dev_caches: "Remove {} direnv/devenv cache root(s)",
+    sto_sugg_home_manager: "Remove {} old Home-Manager generation(s)",
+    sto_sugg_gc: "Collect garbage ({} dead paths now)",
+    sto_sugg_selected: "{} selected, up to",
+    sto_sugg_overlap: "Estimates count only paths nothing else keeps; removed roots free space once garbage is collected.",
+    sto_sugg_hint: "[Space] select  [a] all  [x/Enter] apply  [r] rescan",
+    sto_sugg_total: "Frees up to",
+    sto_sugg_none_selected: "Select suggestions with Space first",
+    sto_sugg_history: "Suggestions: {} applied",
+    sto_sugg_staged: "Apply {} cleanup suggestion(s)",
     sto_paths_removed: "paths removed",
 
     // Settings
     settings_theme: "Theme",
+    settings_theme_custom: "custom",
+    settings_theme_preview: "Theme preview",
+    settings_theme_sample: "Sample text",
+    settings_theme_files: "Own themes: {}/<name>.toml (all fields as #rrggbb, optional base = \"nord\") — saved changes are applied live",
+    theme_load_failed: "Theme {}: {}",
+    theme_reloaded: "Theme {} reloaded",
+    config_reloaded: "Config reloaded",
+    config_reload_failed: "config.toml not applied: {}",
     settings_language: "Language",
     settings_layout: "Layout",
     settings_nixpkgs: "Nixpkgs Channel",
     settings_saved: "Settings saved",
+    time_just_now: "just now",
+    time_min_ago: "{} min ago",
+    time_h_ago: "{} h ago",
+    time_d_ago: "{} d ago",
     settings_config_path: "Config",
 
     // Coming Soon
@@ -795,6 +2242,31 @@ static EN: Strings = Strings {
     // Status bar
     status_navigate: "[j/k] Navigate",
     status_switch_tab: "[1-9,0] Module  [,] Settings  [?] Help",
+    nix_degraded_title: "Nix is not available",
+    too_small_title: "Terminal too small",
+    too_small_size: "{} — needs at least {}",
+    too_small_hint: "Enlarge the window or reduce the font size · q quits",
+    ext_running: "Running…",
+    ext_failed: "Command failed: {}",
+    ext_no_output: "No output",
+    nix_degraded_module: "This module needs a working Nix installation.",
+    nix_problem_no_store: "/nix/store not found — Nix does not seem to be installed on this system.",
+    nix_fix_no_store: "Install Nix (https://nixos.org/download) or run nixmate on a NixOS machine.",
+    nix_problem_no_cli: "The `nix` command was not found in PATH (or did not respond).",
+    nix_fix_no_cli: "Check your PATH — log in again, or source ~/.nix-profile/etc/profile.d/nix.sh.",
+    nix_problem_daemon_down: "The nix-daemon is not reachable (/nix/var/nix/daemon-socket/socket).",
+    nix_fix_daemon_down: "Start it with: sudo systemctl start nix-daemon.socket",
+    nix_problem_store_ro: "The Nix store is mounted read-only and no daemon is running.",
+    nix_fix_store_ro: "Start the nix-daemon, or remount /nix/store read-write.",
+    nix_degraded_still_works: "Still available: Error Translator, Services, Config Showcase, Health, Settings",
+    nix_degraded_retry: "Press [r] to retry detection",
+    nix_not_nixos_title: "Not running NixOS",
+    nix_not_nixos_module: "This module works with a NixOS system and is disabled here.",
+    nix_problem_not_nixos: "Nix is installed, but this system is not NixOS: there are no system generations, services or configuration to read, and nothing for nixos-rebuild to build.",
+    nix_fix_not_nixos: "Use home-manager or nix-darwin for your configuration; the modules below work with any Nix installation.",
+    nix_not_nixos_still_works: "Available: Error Translator, Storage, Flake Inputs, Package Search, Settings",
+    nix_retry_ok: "Nix detected — all modules available",
+    nix_retry_failed: "Nix is still unavailable",
     status_quit: "[q] Quit",
     status_change: "[Enter] Change",
 
@@ -807,6 +2279,14 @@ static EN: Strings = Strings {
     // Error Translator module
     err_analyze: "Analyze",
     err_submit: "Submit Pattern",
+    err_history: "History",
+    err_history_empty: "No analyzed errors yet — every analysis is kept here",
+    err_history_no_match: "no match",
+    err_history_time: "Analyzed:",
+    err_history_source: "Command:",
+    err_history_rule: "Rule:",
+    err_history_open: "Open in Analyze",
+    err_history_delete: "Delete",
     err_paste_hint: "Paste your Nix error message below, then press Enter to analyze:",
     err_input: "Input",
     err_found: "FOUND",
@@ -814,6 +2294,10 @@ static EN: Strings = Strings {
     err_problem: "Problem",
     err_solution: "Solution",
     err_understanding: "Understanding",
+    err_tree_title: "Structure",
+    err_tree_frames: "{} trace frames [Space]",
+    err_tree_hint: "[Space] fold/unfold trace · [j/k] scroll · [t/Esc] back",
+    err_tree_summary: "{} warnings/notes, {} trace frames",
     err_new_analysis: "New analysis",
     err_submit_pattern: "Submit as new pattern",
     err_submit_title: "Submit New Pattern",
@@ -828,6 +2312,32 @@ static EN: Strings = Strings {
     err_idle_hint: "Supports build errors, eval failures, flake issues, and more.",
     err_start_input: "Start typing",
     err_piped_hint: "📎 Piped input — auto-analyzed",
+    pipe_detected: "📎 Piped input read as {} — wrong guess? Pipe again with nixmate --as <rebuild|build|flake|journal|errors>",
+    pipe_kind_rebuild: "nixos-rebuild log",
+    pipe_kind_build: "nix build log",
+    pipe_kind_flake: "nix flake update output",
+    pipe_kind_journal: "journal excerpt",
+    pipe_kind_error: "error message",
+    err_from_clipboard: "from clipboard (tmux buffer first)",
+    err_from_file: "from a log file",
+    err_loaded_clipboard: "📋 Loaded from {}",
+    err_loaded_file: "📄 Loaded {}",
+    err_clipboard_empty: "Clipboard is empty or no clipboard tool found (tmux, wl-paste, xclip, xsel)",
+    err_file_title: "Load from file",
+    err_file_prompt: "Path to a log file (the last 64 KB are read):",
+    err_file_hint: "[Enter] Load  [Esc] Cancel",
+    err_open_in_editor: "Open",
+    err_editor_notice: "opening {} — back to nixmate when the editor exits",
+    err_editor_failed: "Editor exited with an error (check $VISUAL / $EDITOR)",
+    err_location_missing: "{} does not exist on this machine",
+    err_in_config: "In your config",
+    err_did_you_mean: "Did you mean:",
+    err_explain: "Explain with AI",
+    err_explain_title: "AI explanation · external: {}",
+    err_explain_sent: "The error text was sent to {}. AI output can be wrong, check before running anything.",
+    err_explain_streaming: "receiving…",
+    err_explain_failed: "Request failed",
+    err_explain_disabled: "Explain with AI is off: set [ai_explain] enabled = true in config.toml",
     err_ai_ask: "Ask AI",
     err_ai_analyzing: "AI is analyzing...",
     err_ai_result: "AI Analysis",
@@ -856,6 +2366,17 @@ static EN: Strings = Strings {
     settings_path_found_config: "Found configuration.nix",
     settings_path_not_found: "Path does not exist",
     settings_path_no_config: "No flake.nix or configuration.nix found",
+    settings_refresh_section: "Data & refresh",
+    settings_src_services: "Services",
+    settings_src_ports: "Ports",
+    settings_src_flake_inputs: "Flake inputs",
+    settings_src_options: "Options database",
+    settings_src_storage: "Storage snapshot",
+    settings_refresh_value: "TTL {} · auto {}",
+    settings_off: "off",
+    settings_refresh_hint: "Cache TTL and auto-refresh, e.g. \"5m 30s\" (s/m/h/d, 0 = off)",
+    settings_refresh_invalid: "Invalid value, expected e.g. \"5m 30s\"",
+    stale_badge: "data {} old",
 
     // Services & Ports module
     svc_overview: "Overview",
@@ -864,9 +2385,25 @@ static EN: Strings = Strings {
     svc_manage: "Manage",
     svc_running: "running",
     svc_failed: "failed",
+    svc_socket_idle: "socket-activated",
+    svc_socket_label: "Socket",
     svc_total: "services",
     svc_total_containers: "containers",
     svc_ports_open: "ports open",
+    svc_mounts_title: "Mounts & disks",
+    svc_mounts_loading: "Checking mounts and disks…",
+    svc_mounts_none: "No network mounts configured",
+    svc_mounts_ok: "mounts & disks OK",
+    svc_mounts_problems: "{} mount/disk problem(s)",
+    svc_mount_mounted: "mounted",
+    svc_mount_automount: "automount",
+    svc_mount_failed: "failed",
+    svc_mount_unmounted: "not mounted",
+    svc_mount_stale: "not responding",
+    svc_disk_passed: "passed",
+    svc_disk_failed: "FAILING",
+    svc_disk_unknown: "unknown",
+    svc_disk_degraded: "degraded",
     svc_shown: "shown",
     svc_no_entries: "No entries match the current filter.",
     svc_no_ports: "No open ports detected.",
@@ -875,8 +2412,42 @@ static EN: Strings = Strings {
     svc_col_address: "Address",
     svc_col_owner: "Owner",
     svc_col_process: "Process",
+    svc_col_pid: "PID",
+    svc_col_state: "State",
+    svc_col_name: "Name",
+    svc_col_enabled: "Enabled",
+    svc_col_ports: "Ports",
+    svc_col_cpu: "CPU",
+    svc_col_memory: "Memory",
+    svc_sorted_by: "Sorted by {}",
+    svc_col_description: "Description",
+    svc_proxies: "Reverse proxies",
+    svc_col_domain: "Domain",
+    svc_col_upstream: "Upstream",
+    svc_proxy_dangling: "nothing listening",
+    svc_proxy_remote: "remote",
+    svc_proxy_dangling_count: "{} dangling",
+    svc_port_warnings: "{} port warnings",
+    svc_port_duplicate: "several processes listen",
+    svc_port_not_listening: "opened in the NixOS firewall, but nothing listens",
+    svc_port_more: "… {} more",
     svc_logs_for: "Logs:",
     svc_no_logs: "No log entries. Select a service in Overview tab first.",
+    svc_logs_tail: "tail {}",
+    svc_logs_since: "since",
+    svc_logs_until: "until",
+    svc_logs_grep: "grep",
+    svc_logs_range_boot: "this boot",
+    svc_logs_range_hour: "last hour",
+    svc_logs_range_today: "today",
+    svc_logs_errors_only: "errors only",
+    svc_logs_input_since: "Since (30m, 2h, 3d or 2024-05-01 14:00):",
+    svc_logs_input_until: "Until (30m, 2h, 3d or 2024-05-01 14:00):",
+    svc_logs_input_grep: "Grep (filtered by journalctl / while streaming):",
+    svc_logs_bad_time: "Unrecognized time: {}",
+    svc_logs_loading: "Loading logs…",
+    svc_logs_no_match: "No log lines match the filters. [x] clears them.",
+    svc_logs_filters_cleared: "Log filters cleared",
     svc_no_selection: "(none selected)",
     svc_status_label: "Status:",
     svc_enabled_label: "Enabled",
@@ -886,11 +2457,21 @@ static EN: Strings = Strings {
     svc_act_start: "Start",
     svc_act_stop: "Stop",
     svc_act_restart: "Restart",
+    svc_no_failed: "No failed units",
+    svc_restart_failed_title: "Restart {} failed unit(s)",
+    svc_restart_and_follow: "Yes + follow logs",
+    svc_restarted_summary: "{} of {} unit(s) running again",
+    svc_logs_following: "following",
+    job_svc_restart: "Restart failed units",
     svc_act_enable: "Enable (start on boot)",
     svc_act_disable: "Disable (no autostart)",
     svc_confirm_action: "Are you sure?",
     svc_action_title: "Confirm Action",
     svc_sudo_note: "This action requires sudo.",
+    svc_kill_title: "Free port",
+    svc_kill_action: "stop the process (SIGTERM), frees :{}",
+    svc_port_no_pid: "No process visible for this port — it belongs to another user (run as root to see it)",
+    svc_restart_sudo_note: "Runs sudo -n in the background: needs a cached sudo login (run sudo -v first) or NOPASSWD.",
     svc_load_error: "Could not load services",
     svc_load_error_hint: "systemctl may not be available. Are you on NixOS?",
     svc_filter_all: "All",
@@ -906,6 +2487,38 @@ static EN: Strings = Strings {
     rb_history: "History",
     rb_idle_title: "Rebuild Dashboard",
     rb_idle_hint: "Press [Enter] or [r] to start a rebuild",
+    rb_repl_hint: "Press [e] to inspect the configuration in `nixos-rebuild repl`",
+    rb_targets_hint: "Press [f] to build a package, dev shell or check of a flake",
+    rb_targets_title: "Build flake target",
+    rb_targets_loading: "Reading the flake's outputs (nix flake show)…",
+    rb_targets_build: "Build",
+    rb_targets_reload: "Reload",
+    rb_hosts_title: "Flake & host to rebuild",
+    rb_hosts_follow: "This machine's host name ({})",
+    rb_hosts_none: "The flake has no nixosConfigurations",
+    rb_hosts_pick: "use",
+    rb_hosts_flake: "next flake",
+    rb_host: "Host",
+    rb_target_no_detach: "Development builds can't be detached — cancel it or wait",
+    rb_repl_unavailable: "Neither nixos-rebuild nor nix found — cannot open eval console",
+    rb_repl_running: "Opening eval console — exit with :q to return to nixmate",
+    rb_repl_failed: "Eval console exited with an error",
+    rb_quit_title: "Rebuild still running",
+    rb_quit_running: "Quitting now would orphan the running rebuild — it stops halfway.",
+    rb_quit_detach_note: "Detach restarts it as a systemd unit that survives nixmate (already built paths are reused). Reattach later from the Rebuild tab.",
+    rb_quit_detach: "Detach & quit",
+    rb_quit_stop: "Stop & quit",
+    rb_quit_stay: "Stay",
+    rb_detach_notice: "Moving the rebuild into systemd unit {} — follow it with journalctl -fu or reattach in nixmate (7 → a)",
+    rb_detached_to: "⏏ Build detached into {}",
+    rb_detach_failed: "Could not start the systemd unit — the build was stopped, start it again from the Rebuild tab",
+    rb_detach_activating: "Already activating the new system — it can't be moved or stopped now, let it finish",
+    rb_detach_not_stopped: "The local build didn't stop in time — not detached, so it never runs twice",
+    rb_detach_stopping: "Stopping the local build, then detaching…",
+    rb_cancel_activating: "Activation can't be cancelled halfway — it finishes on its own",
+    rb_quit_activating: "The new system is being activated — stopping or moving it now would leave it half switched.",
+    rb_quit_when_done: "Quit when done",
+    rb_quit_waiting: "nixmate quits once the rebuild is done",
     rb_detecting: "Detecting system configuration...",
     rb_config_detected: "Configuration:",
     rb_config_flakes: "Flakes",
@@ -917,12 +2530,23 @@ static EN: Strings = Strings {
     rb_current_mode: "Mode:",
     rb_cycle_mode: "cycle",
     rb_last_build: "Last build:",
+    rb_sys_builds_title: "Builds running on this system",
+    rb_sys_builds_none: "No other builds running",
+    rb_sys_builds_hint: "[j/k] select  [a] attach to log",
+    rb_sys_builder: "builder",
+    rb_sys_client: "command",
+    rb_attach_title: "Attached to",
+    rb_attach_hint: "[Esc] detach",
+    rb_attach_waiting: "Waiting for log output...",
+    rb_attach_finished: "Build finished — log complete",
+    rb_attach_no_log: "No log available for this build (derivation unknown)",
     rb_estimated_time: "Estimated time:",
     rb_mode_switch: "switch",
     rb_mode_boot: "boot",
     rb_mode_test: "test",
     rb_mode_build: "build",
     rb_mode_dry: "dry-build",
+    rb_mode_rollback: "rollback",
     rb_phase_idle: "IDLE",
     rb_phase_preparing: "PREPARING",
     rb_phase_evaluating: "EVALUATING",
@@ -948,18 +2572,80 @@ static EN: Strings = Strings {
     rb_explain_bootloader: "Bootloader entries (GRUB or systemd-boot) are updated so you can select this generation at boot. A new NixOS generation entry is added alongside previous ones for rollback.",
     rb_explain_done: "All phases completed. Your system is now running the new configuration. Previous generations remain available for rollback via boot menu or `nixos-rebuild switch --rollback`.",
     rb_explain_failed: "The build failed. Check the Log tab for the full error output. Common causes: syntax errors in config, missing packages, or network issues during fetch.",
+    rb_line_build: "A .drv (derivation) is Nix's build recipe: exact sources, dependencies and commands. Nobody had this output cached, so it is compiled on your machine.",
+    rb_line_fetch: "A ready-made build result is downloaded from a binary cache (e.g. cache.nixos.org) instead of being compiled — same hash, same bits.",
+    rb_line_build_plan: "Nix compared your config with the store: these derivations exist nowhere yet and must be built locally.",
+    rb_line_fetch_plan: "These store paths already exist in a binary cache; only the download size shown is transferred.",
+    rb_line_evaluating: "Nix reads your .nix files and computes what the system should look like. Nothing is built yet — errors here are config mistakes.",
+    rb_line_activating: "The new system is built; its activation script now makes it live (users, /etc, services). Only switch and test activate.",
+    rb_line_etc: "Files in /etc are symlinks into the new system's store path; they are swapped atomically to the new generation.",
+    rb_line_restart: "These units' definitions changed, so systemd stops and starts them with the new version.",
+    rb_line_start: "These units are new in this configuration (or were not running) and are started now.",
+    rb_line_stop: "These units no longer exist in the new configuration, so they are stopped.",
+    rb_line_reload: "These units support reloading: they re-read their config without a full restart.",
+    rb_line_bootloader: "The boot menu gets an entry for the new generation, so it is also used (or can be picked) at the next boot.",
+    rb_line_warning: "Nix continues, but something in your config is deprecated or suspicious — worth fixing before it becomes an error.",
+    rb_line_error: "The build stopped here. The lines above usually show where; the Error Translator (tab 2) explains common ones.",
+    rb_line_trace: "Output of builtins.trace or --show-trace: Nix shows how evaluation got here, innermost call last.",
     rb_confirm_title: "Confirm Rebuild",
     rb_confirm_mode: "Mode",
+    rb_confirm_specialisation: "Specialisation",
+    rb_spec_default: "default",
+    rb_spec_cycle: "Tab: change",
     rb_confirm_cmd: "Command",
     rb_sudo_note: "⚠ This requires sudo privileges",
+    rb_net_title: "Download on a metered connection",
+    rb_net_title_slow: "Slow download",
+    rb_net_metered: "NetworkManager marks this connection as metered.",
+    rb_net_slow: "Fetching runs at {}/s.",
+    rb_net_size: "nix will download {} MiB from the binary caches.",
+    rb_net_eta: "At the current rate that takes about {}.",
+    rb_net_offline_hint: "Offline stops this build and starts it again without substituters: everything missing is built locally.",
+    rb_net_continue: "Continue",
+    rb_net_offline: "Build offline",
+    rb_confirm_offline: "Offline: no substituters, missing paths are built locally",
+    rb_confirm_rollback_title: "Confirm Rollback",
+    rb_confirm_rollback: "Activates generation {} again. Nothing is evaluated or built; the current generation stays installed.",
+    rb_rollback_none: "No earlier generation to roll back to",
+    rb_changes_restored: "Compared with restored generation {}",
     rb_stat_built: "Built",
     rb_stat_fetched: "Fetched",
     rb_live_output: "Live Output",
+    rb_compact_eta: "~{} left",
+    rb_compact_overdue: "{} over estimate",
+    rb_compact_last_error: "Last error:",
     rb_auto_scroll: "LIVE",
     rb_resume_scroll: "resume live",
     rb_log_empty: "No build log yet",
     rb_log_empty_hint: "Start a rebuild from the Dashboard tab",
+    rb_busy: "A rebuild is already running",
+    rb_backend_needs_flake: "{} needs a flake configuration, building with nixos-rebuild",
+    rb_backend_missing: "{} not found, building with nixos-rebuild",
+    rb_smart_fetched: "{} paths fetched",
+    rb_smart_built: "{} derivations built",
+    rb_smart_lines: "{} lines",
+    rb_tree_title: "Dependency tree",
+    rb_tree_counts: "{} queued · {} building · {} done · {} failed",
+    rb_tree_critical: "critical path: {} left",
+    rb_tree_loading: "Reading dependencies…",
+    rb_tree_no_deps: "Dependencies unknown ({}), derivations listed flat",
+    rb_tree_empty: "Nothing to build in this run (yet)",
+    rb_log_nothing: "The log is empty",
+    rb_log_exported: "Log saved to {}",
+    rb_log_no_error: "No error in the log",
+    rb_log_copied: "Copied {} error lines ({})",
+    rb_log_no_clipboard: "No clipboard tool found (wl-copy, xclip, xsel, pbcopy or tmux)",
+    rb_share_disabled: "Sharing is off: set [paste] enabled = true in config.toml",
+    rb_share_uploading: "Uploading log to {}…",
+    rb_share_confirm_title: "Share the build log?",
+    rb_share_confirm: "Upload {} log lines to {}?",
+    rb_share_confirm_hint: "The paste is public and can't be taken back. Logs can contain hostnames, paths and secrets.",
+    rb_share_copied: "Shared: {} (link copied)",
+    rb_share_done: "Shared: {}",
     rb_changes_summary: "Changes:",
+    rb_warnings_title: "Warnings ({})",
+    rb_warnings_hint: "w/W select · Space raw line · o option",
+    rb_warnings_no_option: "No option recognized in this warning",
     rb_changes_added: "added",
     rb_changes_removed: "removed",
     rb_changes_updated: "updated",
@@ -970,20 +2656,47 @@ static EN: Strings = Strings {
     rb_kernel_changed: "Kernel updated!",
     rb_reboot_needed: "Reboot required to use the new kernel",
     rb_services_restarted: "Services restarted",
+    rb_preview_title: "Before switching (dry-activate)",
+    rb_preview_hint: "[p] Preview which units a switch would stop, restart or reload",
+    rb_preview_running: "Checking what a switch would do…",
+    rb_preview_needs_build: "Preview needs a finished build (mode: build)",
+    rb_preview_sudo: "Runs sudo -n: needs a cached sudo login (run sudo -v first) or NOPASSWD.",
+    rb_preview_nothing: "No running units would change",
+    rb_preview_systemd: "systemd itself would restart",
+    rb_preview_stop: "Would stop",
+    rb_preview_restart: "Would restart",
+    rb_preview_reload: "Would reload",
+    rb_preview_start: "Would start",
+    rb_preview_kept: "Changed, left running",
+    rb_preview_switch: "[s] Switch to this system now",
     rb_history_empty: "No rebuilds in this session yet",
     rb_history_empty_hint: "Your rebuild history will appear here",
+    rb_breakdown_avg: "Average of the last {} builds:",
+    rb_breakdown_eval: "eval",
+    rb_breakdown_fetch: "fetch",
+    rb_breakdown_build: "build",
+    rb_breakdown_activate: "activate",
+    rb_breakdown_boot: "boot",
+    rb_up_to_date_activation: "Already up to date — activation only",
+    rb_up_to_date_nothing: "Already up to date — nothing to build or fetch",
+    rb_up_to_date_desc: "Evaluation found every store path already present; nothing was built or downloaded.",
+    rb_up_to_date_short: "Up to date: nothing built or fetched",
     rb_password_label: "Password:",
     rb_password_hint: "type sudo password...",
     rb_nopasswd_hint: "NOPASSWD? Just press Enter",
     rb_password_submit: "Enter",
 
     // Generations (additional)
-    gen_action_confirmed: "Action confirmed",
-    gen_undo_closed: "Undo window closed",
     gen_pin_updated: "Pin status updated",
     gen_cannot_restore_current: "Cannot restore current generation",
     gen_cannot_delete_current: "Cannot delete current generation",
     gen_cannot_delete_pinned: "Cannot delete pinned generation",
+    gen_protected_booted: "#{} is the generation this machine booted from — your known-good fallback until the next reboot",
+    gen_protected_default: "#{} is the bootloader default — the next boot starts it",
+    gen_confirm_protected: "Delete Protected Generation?",
+    gen_protected_warning: "nix deletes it without complaint. Confirm again only if you know what you will boot next.",
+    gen_protected_skipped: "{} protected generation(s) left out (booted / boot default)",
+    gen_cleanup_refused: "Refused — {}. Reboot into the current generation first, or delete it deliberately in Generations → Manage.",
     gen_no_match_filter: "No packages match filter",
     gen_no_found: "No generations found",
     gen_from: "From",
@@ -993,13 +2706,23 @@ static EN: Strings = Strings {
     gen_command_failed: "Command Failed",
     gen_select_hint: "Select a generation in Overview tab and press Enter",
     gen_no_comparison: "No generations available for comparison",
-    gen_diff_hint: "Select two generations: [Tab] switch, [j/k] navigate, [Enter] select, [c] clear",
+    gen_diff_hint: "Select two generations: [Tab] switch, [j/k] navigate, [Enter] select, [c] clear, [d] git diff of the config",
     gen_command_label: "Command:",
-    gen_undo_available: "Undo Available",
-    gen_dismiss: "Dismiss",
     gen_deleted_count: "Deleted {} generation(s)",
+    gen_staged_delete: "Delete {} generation(s)",
     gen_system_label: "System",
     gen_hm_label: "Home-Manager",
+    gen_col_id: "Gen",
+    gen_col_date: "Date",
+    gen_col_version: "Version",
+    gen_col_packages: "Pkgs",
+    gen_col_size: "Size",
+    gen_col_commit: "Config commit",
+    gen_git_diff_pick: "Select both generations first",
+    gen_git_diff_unavailable: "No config commit known for both generations (flake in a git repo needed)",
+    gen_git_diff_unknown_commit: "A commit isn't in the local config repo (git fetch?)",
+    gen_git_diff_notice: "git diff of the configuration, generation #{} → #{} (quit the pager to return)",
+    gen_git_diff_failed: "git diff exited with an error",
     gen_detection_failed: "System detection failed",
     gen_ensure_nixos: "Make sure you're running on NixOS with nix-env in PATH.",
 
@@ -1073,6 +2796,129 @@ static EN: Strings = Strings {
     health_detail_fresh_ok: "Last rebuild {} days ago",
     health_detail_fresh_warn: "Last rebuild {} days ago — consider updating",
     health_detail_fresh_crit: "Last rebuild {} days ago — very outdated!",
+    health_name_flake_watch: "Flake Input Age",
+    health_desc_flake_watch: "Flake inputs older than their configured threshold",
+    health_fix_flake_watch: "Update them in Flake Inputs (8 → Update)",
+    health_detail_flake_watch_ok: "All inputs within their age thresholds",
+    health_detail_flake_watch_stale: "{} out of date: {}",
+    health_name_signatures: "Cache Signatures",
+    health_desc_signatures: "Sampled closure paths verified against trusted-public-keys",
+    health_detail_sigs_ok: "{} sampled paths trusted ({} signed, {} built locally)",
+    health_detail_sigs_untrusted: "{} of {} sampled paths untrusted: {} bad signature, {} unknown key, {} unsigned",
+    health_detail_sigs_no_require: "require-sigs = false — substituted paths are not checked at all",
+    health_fix_sigs_require: "Set nix.settings.require-sigs = true, otherwise any cache can serve altered paths",
+    health_fix_sigs_bad: "Trusted key but invalid signature: path altered? Repair re-fetches it",
+    health_fix_sigs_unknown_key: "Signed by {}: add its key to nix.settings.trusted-public-keys or drop that cache",
+    health_fix_sigs_unsigned: "Neither built here nor signed (nix copy --no-check-sigs?): rebuild or sign them",
+    health_name_integrity: "Store Contents",
+    health_desc_integrity: "Sampled closure paths still hash to what the Nix database recorded — catches disk corruption and manual edits",
+    health_detail_integrity_ok: "{} sampled paths intact",
+    health_detail_integrity_bad: "{} of {} sampled paths modified or corrupted",
+    health_detail_skipped_not_nixos: "Skipped: needs NixOS",
+    health_fix_integrity: "Repair re-fetches or rebuilds the damaged paths",
+    health_name_flake_eval: "Flake evaluation",
+    health_desc_flake_eval: "Evaluates this host's system from the flake without building anything (nix eval of toplevel.drvPath, or nix flake check --no-build). Catches evaluation errors before a rebuild does.",
+    health_detail_flake_eval_ok: "{} evaluates",
+    health_detail_flake_eval_failed: "Evaluation fails: {}",
+    health_detail_flake_eval_timeout: "Evaluation didn't finish within {}s",
+    health_fix_flake_eval: "Press e to open the full error in the Error Translator",
+    health_dep_feat_nixos_option: "Options Explorer: current values of options (falls back to slower lookups, some values stay empty)",
+    health_dep_feat_journalctl: "Services: unit logs, log search and time filters; Rebuild: logs of failed units",
+    health_dep_feat_git: "Flake Inputs: committing flake.lock after an update",
+    health_dep_feat_containers: "Services: container list, logs and actions",
+    health_dep_feat_busctl: "Rebuild: warning before downloads over a metered connection",
+    health_detail_dep_missing: "Not installed — degraded: {}",
+    health_detail_dep_old: "{} is older than {} — degraded: {}",
+    health_fix_dep_install: "Install pkgs.{}",
+    health_no_eval_error: "No evaluation error to translate",
+    health_name_boot_space: "Boot Partition",
+    health_desc_boot_space: "Every generation keeps a kernel and initrd on /boot — a full EFI partition makes the next rebuild fail halfway",
+    health_detail_boot_space: "{} boot entries, {} free ({}% used)",
+    health_fix_boot_space_clean: "Delete generations older than 14 days and rewrite the boot entries",
+    health_fix_boot_space_limit: "Cap the number of boot entries in your configuration",
+    health_name_booted: "Booted Generation",
+    health_desc_booted: "The running system vs. the one the machine booted — a new kernel only takes effect after a reboot",
+    health_detail_booted_same: "Running the generation the machine booted",
+    health_detail_booted_switched: "Switched since boot, same kernel",
+    health_detail_booted_kernel: "Kernel or initrd changed since boot — reboot pending",
+    health_fix_booted: "Reboot when convenient to run the new kernel",
+    health_name_initrd_secrets: "initrd Secrets",
+    health_desc_initrd_secrets: "Files from boot.initrd.secrets are copied into every initrd — if one is gone, installing the bootloader fails",
+    health_detail_initrd_ok: "{} secrets present",
+    health_detail_initrd_missing: "Missing: {}",
+    health_fix_initrd: "Restore the file (or remove it from boot.initrd.secrets) before the next rebuild",
+    health_name_stale_entries: "Boot Entries",
+    health_desc_stale_entries: "Entries in /boot/loader/entries whose generation was deleted still take space and offer a system that no longer exists",
+    health_detail_entries_ok: "All entries belong to existing generations",
+    health_detail_entries_stale: "{} entries for deleted generations: {}",
+    health_fix_entries: "Rewrite the boot entries from the existing generations",
+    health_name_power_daemons: "Power Management",
+    health_desc_power_daemons: "TLP, power-profiles-daemon and auto-cpufreq all tune the CPU and devices; two at once undo each other's settings, none at all leaves the laptop on default performance",
+    health_detail_power_ok: "Managed by {}",
+    health_detail_power_none: "No power management daemon running on battery",
+    health_detail_power_conflict: "Competing power managers: {}",
+    health_fix_power_none: "Enable one power manager in your configuration",
+    health_fix_power_conflict: "Keep one power manager and disable the others",
+    health_name_thermald: "Intel Thermal Daemon",
+    health_desc_thermald: "On Intel laptops thermald keeps the CPU below its thermal limit without hard throttling, which saves battery and keeps fans quieter",
+    health_detail_thermald_ok: "thermald is running",
+    health_detail_thermald_missing: "Intel CPU without thermald",
+    health_fix_thermald: "Enable thermald",
+    health_name_firmware: "Firmware",
+    health_desc_firmware: "Drivers that can't load their firmware leave Wi-Fi, GPU or network cards half working or missing, often without an obvious error",
+    health_detail_firmware_ok: "No missing firmware in the kernel log",
+    health_detail_firmware_missing: "Firmware not found: {}",
+    health_fix_firmware: "Enable the redistributable firmware",
+    health_name_microcode: "CPU microcode",
+    health_desc_microcode: "Microcode updates fix CPU bugs and security holes (Spectre and friends) that the firmware of the board may never get",
+    health_detail_microcode_ok: "Microcode updated early at boot",
+    health_detail_microcode_missing: "No early microcode update in the kernel log ({} CPU) — not enabled, or the firmware is already current",
+    health_fix_microcode: "Load microcode updates at boot",
+    health_name_swap: "Swap / zram",
+    health_desc_swap: "Without any swap the kernel has nowhere to put idle memory, and a large build or browser can run into the OOM killer",
+    health_detail_swap_none: "No swap and no zram",
+    health_detail_swap_ok: "zram {} · disk swap {}",
+    health_fix_swap: "Enable compressed swap in RAM",
+    health_name_disk_ids: "Disk IDs in hardware-configuration.nix",
+    health_desc_disk_ids: "A file system referenced by a UUID that no longer exists drops the next boot into the emergency shell",
+    health_detail_disk_ids_ok: "Every referenced disk exists",
+    health_detail_disk_ids_missing: "Not present: {}",
+    health_fix_disk_ids: "Point the file systems at the disks that are there now",
+    health_name_features: "Flakes enabled",
+    health_desc_features: "A flake-based system needs the nix-command and flakes features in nix.conf; passing them on the command line works until a tool or another user doesn't",
+    health_detail_features_ok: "nix-command and flakes enabled in nix.conf",
+    health_detail_features_missing: "System uses a flake, but nix.conf doesn't enable: {}",
+    health_fix_features: "Enable flakes in nix.settings",
+    health_name_parallelism: "Build parallelism",
+    health_desc_parallelism: "max-jobs is how many derivations build at once, cores how many CPUs each build may use; either at 1 leaves most of the machine idle during rebuilds",
+    health_detail_parallelism_ok: "max-jobs = {}, cores = {}",
+    health_detail_parallelism_low: "{} on a machine with {} CPUs",
+    health_fix_parallelism: "Use every CPU for builds",
+    health_name_trusted_users: "Trusted Nix users",
+    health_desc_trusted_users: "Trusted users can add substituters and import unsigned store paths, which is as good as root; only admins belong in trusted-users",
+    health_detail_trusted_ok: "trusted-users = {}",
+    health_detail_trusted_unsafe: "Trusted without being an admin: {}",
+    health_fix_trusted_users: "Trust only root and wheel",
+    health_name_hibernate: "Suspend & Hibernate",
+    health_desc_hibernate: "Hibernating writes RAM to swap on disk: it needs swap at least as large as RAM (zram doesn't count), a resume device and no kernel lockdown, or the lid switch silently does nothing",
+    health_detail_hibernate_lid: "Lid: {}",
+    health_detail_hibernate_sizes: "swap {}, RAM {}",
+    health_detail_hibernate_ready: "hibernate ready",
+    health_detail_hibernate_swap: "swap smaller than RAM",
+    health_detail_hibernate_resume: "no resume device",
+    health_detail_hibernate_lockdown: "kernel lockdown blocks hibernation",
+    health_fix_hibernate: "Add enough swap and a resume device, or suspend only",
+    health_name_charge_threshold: "Battery Charge Limit",
+    health_desc_charge_threshold: "Keeping a battery at 100% all day wears it out faster; many laptops can stop charging at e.g. 80%",
+    health_detail_charge_set: "{} stops charging at {}%",
+    health_detail_charge_full: "{} supports a charge limit but charges to 100%",
+    health_detail_charge_unsupported: "{} has no charge limit (not exposed by the firmware or kernel driver)",
+    health_fix_charge: "Set a charge limit in your configuration",
+    health_name_optimise: "Store Optimisation",
+    health_desc_optimise: "Identical files in the store are hard-linked instead of stored twice",
+    health_detail_optimise_on: "Deduplication enabled",
+    health_detail_optimise_off: "auto-optimise-store is off and no nix-optimise timer",
+    health_fix_optimise: "Enable it in your configuration — often saves 20–40%",
     health_desc_duplicates: "Multiple versions of the same package",
     health_fix_duplicates: "Review your configuration for duplicate entries",
     health_detail_dupes_ok: "{} packages, no duplicates",
@@ -1080,6 +2926,21 @@ static EN: Strings = Strings {
     health_detail_dupes_crit: "{} duplicate package names!",
     health_applying_fix: "Applying fix...",
     health_fix_error_detail: "Fix failed: {}",
+    health_name_accounts: "User Accounts",
+    health_desc_accounts: "Users, groups and SSH keys match the declarative config",
+    health_fix_accounts: "Declare them — snippets in the Accounts tab",
+    health_detail_accounts_ok: "{} accounts, all declarative",
+    health_detail_accounts_drift: "{} imperative users, {} group changes, {} extra SSH keys",
+    health_acc_imperative_user: "uid {} — not in your configuration",
+    health_acc_imperative_group: "gid {} — not in your configuration",
+    health_acc_group_drift: "member of {} only imperatively",
+    health_acc_extra_key: "key in {} not declared",
+    health_acc_mutable: "imperative changes survive rebuilds",
+    health_acc_unreadable: "authorized_keys not readable — run as root for a full audit",
+    health_acc_no_source: "Declarative user config not found (is this NixOS?)",
+    health_acc_summary: "{} accounts · {} SSH keys checked ·",
+    health_acc_clean: "No drift — accounts and keys match your configuration",
+    health_acc_remediation: "Remediation",
 
     // Flake Inputs (additional i18n)
     fi_error_load_failed: "Failed to load flake inputs.",
@@ -1096,6 +2957,13 @@ static EN: Strings = Strings {
     // Generations (additional i18n)
     gen_restore_msg: "Restore {} generation #{}?\nDate: {}\nVersion: {}",
     gen_delete_msg: "Delete {} generation(s)?\nIDs: {}",
+    gen_gc_calculating: "Working out what this really frees…",
+    gen_gc_unknown: "Can't tell what this frees: {}",
+    gen_gc_frees: "Frees {} after garbage collection",
+    gen_gc_only_in: "  #{}: {} only it uses",
+    gen_gc_kept: "{} stay, still used by the current system, other generations or GC roots",
+    job_gen_gc_report: "Generation GC report",
+    job_gen_reclaim: "Reclaimable space per generation",
 
     // Packages (additional i18n)
     pkg_searching_for: "Searching for \"{}\"...",
@@ -1107,6 +2975,9 @@ static EN: Strings = Strings {
     opt_read_failed: "Failed to read value",
     opt_phase_prebuilt: "Looking for pre-built options...",
     opt_phase_fallback: "Falling back to nixos-option (this may take a while)...",
+    opt_phase_cache: "Checking cached options index...",
+    opt_phase_flake_parts: "Evaluating flake-parts module options...",
+    opt_flake_module: "flake-parts module option (set in flake.nix, not the system config)",
     opt_load_error: "Could not load NixOS options. Make sure you're on a NixOS system with documentation.nixos.enable = true (default).",
     opt_not_found: "nixos-option not found: {}",
 
@@ -1123,6 +2994,163 @@ static DE: Strings = Strings {
     welcome_body: "Ich bin hier, um dein NixOS-Erlebnis angenehmer zu machen. Ich kann dir helfen, kryptische Fehlermeldungen zu verstehen, deine Generationen und deinen Speicher zu verwalten, deine Services und Ports im Blick zu behalten und deine Konfiguration in schöne Diagramme zu verwandeln. Es kommt noch mehr.",
     welcome_continue: "Drücke Enter zum Starten",
     welcome_once: "Ich werde dich mit dieser Nachricht nicht nochmal nerven. Viel Spaß!",
+    start_top_issues: "Wichtigste Probleme",
+    start_no_issues: "✓ Alle Prüfungen bestanden",
+    start_open_doctor: "Enter: Nix Doctor öffnen",
+    start_continue: "andere Taste: weiter",
+    palette_title: "Befehle",
+    palette_no_match: "Kein passender Befehl",
+    jobs_title: "Jobs",
+    jobs_running: "Laufend",
+    jobs_none: "Im Hintergrund läuft nichts",
+    jobs_finished: "Zuletzt beendet",
+    jobs_cancelled: "abgebrochen",
+    jobs_cancelled_error: "Abgebrochen — r zum erneuten Versuch",
+    jobs_confirm: "\"{}\" abbrechen? [y] Ja  [n] Nein",
+    jobs_not_cancellable: "Ändert das System — nicht mittendrin abbrechbar, läuft von selbst zu Ende",
+    jobs_footer: "[j/k] Auswahl  [c] Abbrechen  [Esc] Schließen",
+    staged_countdown: "{} in {} s — [u] Rückgängig",
+    staged_more: "+{} weitere",
+    staged_flushing: "{} vorgemerkte Aktion(en) werden ausgeführt…",
+    staged_undone: "Rückgängig gemacht: {}",
+    jobs_badge: "{} {} laufen · [J] Jobs",
+    vitals_generation: "Gen {}",
+    vitals_store_free: "Store: {} frei",
+    vitals_units_ok: "keine fehlgeschlagenen Units",
+    vitals_units_failed: "{} fehlgeschlagene Units",
+    vitals_stale_inputs: "{} veraltete Flake-Inputs",
+    jobs_cancel_done: "Abgebrochen: {}",
+    job_svc_load: "Dienste & Ports laden",
+    job_svc_logs: "Logs abrufen",
+    job_sto_load: "Nix-Store scannen",
+    job_sto_budget: "Aufräumen planen",
+    job_sto_budget_step: "Aufräumschritt läuft",
+    job_cfg_poster: "Poster exportieren",
+    job_cfg_diagram: "Konfiguration scannen",
+    job_cfg_map: "Dienste-Karte auswerten",
+    job_opt_load: "Optionen laden",
+    job_opt_overrides: "Überschreibungen suchen",
+    job_opt_changes: "nixpkgs-Revisionen vergleichen",
+    job_pkg_search: "Suche \"{}\"",
+    job_pkg_audit: "Installierte Pakete prüfen",
+    job_fi_load: "flake.lock lesen",
+    job_fi_update: "Flake-Inputs aktualisieren",
+    job_fi_upstream: "Upstream prüfen",
+    job_fi_relock: "Flake neu sperren",
+    job_fi_impact: "Vorschau nixpkgs-Wechsel",
+    job_health_scan: "Health-Checks laufen",
+    job_health_fix: "Fix wird angewendet",
+    job_err_ai: "KI-Analyse",
+    job_err_explain: "Mit KI erklären",
+    job_rebuild: "nixos-rebuild {}",
+    job_rb_target: "nix build {}",
+    job_rb_share: "Build-Log teilen",
+    job_rb_preview: "Vorschau der Aktivierung",
+    job_rb_tree: "Build-Abhängigkeiten laden",
+    key_help_title: "Tasten",
+    key_help_global: "Überall",
+    key_help_none: "Hier keine Modul-Tasten",
+    key_help_footer: "? erneut: Hilfe / Über · beliebige Taste: schließen",
+    status_palette: "Strg+P: Befehle",
+    kiosk_mode: "Dashboard-Modus ·",
+    kiosk_next_in: "weiter in {}s",
+    kiosk_paused: "pausiert",
+    kiosk_keys: "[Leertaste] Pause  [←/→] Wechseln  [q] Beenden",
+    keys_issues_flash: "Probleme in [keys] — siehe Einstellungen",
+    settings_keys_section: "Tastenbelegung",
+    settings_keys_custom: "angepasst",
+    settings_keys_unknown: "Unbekannte Aktion `{}`",
+    settings_keys_bad_key: "Taste `{}` nicht lesbar ({})",
+    settings_keys_conflict: "{} ist belegt mit: {}",
+    settings_keys_hint: "Umbelegen in config.toml: [keys] down = [\"n\", \"Down\"]",
+    pal_gen_restore: "Zur ausgewählten Generation zurückkehren",
+    pal_gen_delete: "Ausgewählte Generationen löschen",
+    pal_gen_pin: "Ausgewählte Generation anheften / lösen",
+    pal_gen_select_all: "Alle löschbaren Generationen auswählen",
+    pal_gen_filter: "Pakete einer Generation filtern",
+    pal_gen_diff: "Generationen vergleichen",
+    pal_gen_git_diff: "git diff der Konfigurationen beider Generationen",
+    pal_err_paste: "Fehler zum Analysieren einfügen",
+    pal_err_clipboard: "Fehler aus der Zwischenablage analysieren",
+    pal_err_file: "Fehler aus einer Logdatei laden",
+    pal_err_ai: "KI zum Fehler befragen",
+    pal_err_explain: "Mit KI erklären (Stream)",
+    pal_err_open: "Fehlerstelle im Editor öffnen",
+    pal_err_tree: "Fehlerstruktur anzeigen: Warnungen, Hinweise, Trace",
+    pal_err_history: "Frühere Analysen durchsehen",
+    pal_err_submit: "Neues Fehlermuster einreichen",
+    pal_svc_search: "Dienste durchsuchen",
+    pal_svc_ports: "Offene Ports anzeigen",
+    svc_unit_not_systemd: "Nur systemd-Units haben eine Unit-Datei",
+    svc_unit_failed: "Unit-Datei nicht lesbar",
+    svc_unit_source: "Quelle:",
+    svc_unit_src_nixos: "NixOS-Konfiguration — {}",
+    svc_unit_src_package: "Paket {}",
+    svc_unit_src_local: "außerhalb von Nix geschrieben (/etc/systemd, Laufzeit)",
+    svc_unit_triggers: "Neustart bei Änderung von:",
+    pal_svc_unit_file: "Unit-Datei anzeigen",
+    pal_svc_start: "Ausgewählten Dienst starten",
+    pal_svc_stop: "Ausgewählten Dienst stoppen",
+    pal_svc_restart: "Ausgewählten Dienst neu starten",
+    pal_svc_restart_failed: "Alle fehlgeschlagenen Units neu starten",
+    pal_svc_logs_follow: "Logs verfolgen (alle 2 s neu laden)",
+    pal_svc_enable: "Ausgewählten Dienst aktivieren",
+    pal_svc_disable: "Ausgewählten Dienst deaktivieren",
+    pal_svc_logs_grep: "Log durchsuchen (grep)",
+    pal_svc_logs_since: "Zeitraum des Logs festlegen",
+    pal_svc_logs_range: "Log-Zeitraum: seit Boot, letzte Stunde, heute",
+    pal_svc_logs_errors: "Nur Fehler im Log anzeigen",
+    pal_svc_logs_more: "Mehr Log-Zeilen laden",
+    pal_sto_search: "Store-Pfade durchsuchen",
+    pal_sto_budget_step: "Nächsten Budget-Schritt ausführen",
+    pal_sto_budget_plan: "Budget neu planen",
+    pal_sto_history: "Aufräum-Verlauf anzeigen",
+    pal_cfg_export: "Poster als SVG exportieren",
+    pal_cfg_export_path: "Poster in einen Pfad exportieren (PNG/SVG)",
+    pal_cfg_rescan: "System neu scannen",
+    pal_cfg_diagram: "Konfigurations-Diagramm anzeigen",
+    pal_cfg_map: "Dienst-Karte anzeigen",
+    pal_opt_search: "Optionen durchsuchen",
+    pal_opt_copy_link: "search.nixos.org-Link der gewählten Option kopieren",
+    pal_opt_browse: "Optionsbaum durchstöbern",
+    pal_opt_overrides: "Meine Überschreibungen anzeigen",
+    pal_opt_reeval: "Konfiguration neu auswerten",
+    pal_opt_changed: "Vom Standard abweichende Optionen anzeigen",
+    pal_rb_start: "{} starten",
+    pal_rb_trace: "--show-trace umschalten",
+    pal_rb_repl: "Eval-Konsole öffnen",
+    pal_rb_targets: "Paket, Dev-Shell oder Check eines Flakes bauen",
+    pal_rb_hosts: "Flake und Host für den Rebuild wählen",
+    pal_rb_tree: "Dashboard: Abhängigkeitsbaum des Builds",
+    pal_rb_warning_option: "Option der gewählten Warnung öffnen",
+    pal_ext_run: "Befehl erneut ausführen",
+    pal_rb_cancel: "Laufenden Build abbrechen",
+    pal_rb_log_search: "Build-Log durchsuchen",
+    pal_rb_explain: "Log-Zeilen erklären",
+    pal_rb_smart: "Rebuild: Smarte Log-Ansicht (nach Derivation gruppiert)",
+    pal_rb_export: "Rebuild: Log in Datei exportieren",
+    pal_rb_copy_error: "Rebuild: Fehlerblock kopieren",
+    pal_rb_share: "Rebuild: Log über Paste-Dienst teilen",
+    pal_rb_history: "Rebuild-Verlauf anzeigen",
+    pal_fl_update: "Ausgewählte Inputs aktualisieren",
+    pal_fl_update_all: "Alle Inputs aktualisieren und neu bauen",
+    pal_fl_add: "Input hinzufügen",
+    pal_fl_edit: "URL des ausgewählten Inputs bearbeiten",
+    pal_fl_branch: "Branch des ausgewählten Inputs ändern",
+    pal_fl_switch_nixpkgs: "nixpkgs-Release-Branch wechseln",
+    pal_fl_graph: "Input-Graph anzeigen",
+    pal_fl_report: "Markdown-Bericht des letzten Updates",
+    pal_fl_projects: "Flake-Projekt wechseln",
+    pal_pkg_search: "nixpkgs durchsuchen",
+    pal_pkg_insecure: "Unsichere / EOL-Pakete im System",
+    pal_hl_rescan: "Alle Prüfungen erneut ausführen",
+    pal_hl_fix: "Korrekturen anzeigen",
+    pal_hl_accounts: "Benutzer, Gruppen und SSH-Schlüssel prüfen",
+    pal_set_theme: "Theme wechseln",
+    pal_set_language: "Sprache wechseln",
+    pal_set_layout: "Layout wechseln",
+    pal_set_config_path: "NixOS-Konfigurationspfad festlegen",
+    pal_set_ai: "KI-Analyse umschalten",
     welcome_language: "Sprache:",
     welcome_lang_hint: "←/→",
     quit: "Beenden",
@@ -1169,12 +3197,15 @@ static DE: Strings = Strings {
     cfg_subtitle: "Teile auf r/unixporn, GitHub READMEs oder anderswo",
     cfg_overview: "Übersicht",
     cfg_diagram: "Diagramm",
+    cfg_services: "Dienste",
     cfg_generate: "Poster generieren",
     cfg_scanning: "System wird gescannt...",
     cfg_success: "Poster gespeichert!",
     cfg_error: "Export fehlgeschlagen",
-    cfg_hint: "Enter oder [g] drücken um System-Poster zu generieren",
+    cfg_hint: "Enter oder [g] drücken um System-Poster zu generieren, [e] exportiert es als PNG/SVG an einen Pfad",
     cfg_tip: "Im Browser öffnen oder auf Reddit / GitHub ziehen",
+    cfg_export_prompt: "Poster exportieren nach (Enter exportiert, Esc bricht ab):",
+    cfg_export_formats: ".png wird in 2× mit rsvg-convert, ImageMagick oder Inkscape gerendert; jede andere Endung schreibt SVG",
     cfg_diag_description: "Architektur-Diagramm deiner NixOS-Konfiguration generieren",
     cfg_diag_subtitle: "Dateistruktur, Imports und Modul-Abhängigkeiten visualisieren",
     cfg_diag_generate: "Diagramm generieren",
@@ -1183,6 +3214,13 @@ static DE: Strings = Strings {
     cfg_diag_hint: "Enter oder [g] drücken um Config-Diagramm zu generieren",
     cfg_diag_tip: "Zeigt wie deine NixOS-Module zusammenhängen",
     cfg_diag_no_config: "Keine NixOS-Konfiguration unter /etc/nixos/ gefunden",
+    cfg_map_loading: "Werte deine Konfiguration aus...",
+    cfg_map_loading_hint: "Ein nix eval über services, networking, virtualisation, programs, hardware und security",
+    cfg_map_failed: "Auswertung fehlgeschlagen",
+    cfg_map_summary: "{} in deiner Config aktiviert ({} inklusive Standards und mitaktivierter Module)",
+    cfg_map_showing_user: "[a] Standards auch zeigen",
+    cfg_map_showing_all: "[a] nur meine Config",
+    cfg_map_empty: "Unter diesen Options-Wurzeln ist nichts aktiviert",
 
     // Help / About
     help_subtitle: "Alle NixOS-Tools in einem TUI.",
@@ -1218,11 +3256,57 @@ static DE: Strings = Strings {
     pkg_installed: "✓ Installiert",
     pkg_not_installed: "Nicht installiert",
     pkg_install_hint: "Installieren mit:",
+    pkg_audit_title: "Unsicher / EOL in diesem System",
+    pkg_audit_scanning: "Prüfe die System-Closure gegen nixpkgs-Metadaten (wertet nixpkgs aus, dauert kurz)…",
+    pkg_audit_clean: "Keine bekannt unsicheren oder EOL-Pakete ({} Pakete geprüft)",
+    pkg_audit_count: "{} von {} Paketen von nixpkgs markiert",
+    pkg_audit_failed: "Prüfung fehlgeschlagen: {}",
+    pkg_audit_vulnerable: "verwundbar",
+    pkg_audit_reasons: "Laut nixpkgs (meta.knownVulnerabilities):",
+    pkg_audit_upgrade: "Upgrade-Pfad: {} ist bei {} ohne bekannte Probleme",
+    pkg_audit_no_upgrade: "Keine behobene Version in diesem nixpkgs: nixpkgs aktualisieren oder Paket ersetzen",
+    pkg_audit_permitted: "Derzeit erlaubt über (nach dem Upgrade entfernen):",
+    pkg_tab_search: "Suche",
+    pkg_tab_browse: "Stöbern",
+    pkg_cat_editors: "Editoren",
+    pkg_cat_terminals: "Terminals",
+    pkg_cat_shells: "Shells",
+    pkg_cat_languages: "Sprachen",
+    pkg_cat_devtools: "Entwicklerwerkzeuge",
+    pkg_cat_servers: "Server-Software",
+    pkg_cat_browsers: "Browser",
+    pkg_sort_popular: "beliebt",
+    pkg_sort_updated: "kürzlich aktualisiert",
+    pkg_sort_name: "Name",
+    pkg_browse_sort: "Sortierung: {}",
+    pkg_browse_hint: "[h/l] Kategorie  [s] Sortierung  [Enter] Details  [R] Index neu aufbauen",
+    pkg_browse_building: "Alle Pakete von {} werden indiziert (dauert eine Minute, einmal pro Woche)...",
+    pkg_browse_failed: "Paketindex konnte nicht erstellt werden: {}",
+    pkg_browse_empty: "Keine Pakete in dieser Kategorie",
+    pkg_browse_tracked: "Updates werden seit {} verfolgt",
+    pkg_browse_not_tracked: "Neue Versionen erscheinen hier nach dem nächsten Index-Neuaufbau",
+    job_pkg_index: "Paketindex aufbauen",
+    pal_pkg_browse: "Pakete nach Kategorie durchstöbern",
+    pal_pkg_rebuild_index: "Paketindex neu aufbauen",
 
     // Health / Nix Doctor
     health_dashboard: "Dashboard",
     health_fix: "Reparieren",
+    health_accounts: "Konten",
     health_scanning: "System-Gesundheit wird geprüft",
+    health_scan_progress: "{}/{} Prüfungen fertig",
+    health_pending: "läuft…",
+    health_why: "Warum:",
+    health_fix_snippet_hint: "In die Konfiguration übernehmen, dann neu bauen:",
+    health_fix_snippet_flash: "Diese Korrektur ist eine Konfigurationsänderung — Snippet unten übernehmen",
+    health_cat_storage: "Speicher",
+    health_cat_updates: "Updates",
+    health_cat_security: "Sicherheit",
+    health_cat_boot: "Boot",
+    health_cat_system: "System",
+    health_cat_power: "Energie",
+    health_cat_hardware: "Hardware",
+    health_cat_nixmate: "nixmate",
     health_score_label: "Gesundheit:",
     health_excellent: "Ausgezeichnet",
     health_good: "Gut",
@@ -1236,16 +3320,45 @@ static DE: Strings = Strings {
     opt_tab_search: "Suche",
     opt_tab_browse: "Durchsuchen",
     opt_tab_related: "Verwandte",
+    opt_tab_overrides: "Meine Änderungen",
+    opt_ovr_header: "Optionen, die deine Konfiguration setzt",
+    opt_ovr_empty: "Deine Konfiguration überschreibt keine Optionen.",
+    opt_ovr_phase_eval: "Werte deine Konfiguration aus",
+    opt_ovr_scanning: "Durchsuche alle Optionen nach Werten aus deiner Config...",
+    opt_ovr_loading_hint: "Ein einziges nix eval über die ganze Konfiguration — kann bis zu einer Minute dauern",
+    opt_ovr_eval_failed: "Konfiguration konnte nicht ausgewertet werden",
+    opt_ovr_parse_failed: "Unerwartete Ausgabe von nix eval",
+    opt_ovr_retry: "Neu auswerten",
+    opt_tab_changed: "Geändert",
+    opt_chg_loading: "Vergleiche Optionen deiner letzten zwei nixpkgs-Revisionen",
+    opt_chg_no_gens: "Keine System-Generationen gefunden.",
+    opt_chg_same_rev: "Alle Generationen nutzen dieselbe nixpkgs-Revision — nichts geändert.",
+    opt_chg_no_options: "Keine options.json für Generation {} (documentation.nixos.enable = false?)",
+    opt_chg_none: "Keine Änderungen dieser Art.",
+    opt_chg_filter_all: "Alle Änderungen",
+    opt_chg_kind_default: "Standardwerte",
+    opt_chg_kind_type: "Typen",
+    opt_chg_kind_added: "Neu",
+    opt_chg_kind_removed: "Entfernt",
+    opt_chg_kind_moved: "Verschoben",
     opt_loading: "NixOS-Optionen werden geladen",
     opt_loading_hint: "Die NixOS-Optionsdatenbank wird gelesen — erster Aufruf kann 15-30s dauern",
     opt_empty: "Keine Optionen geladen. Bist du auf einem NixOS-System?",
     opt_search_hint: "/ drücken um NixOS-Optionen zu durchsuchen...",
     opt_search_label: "Suche:",
-    opt_search_empty: "/ drücken um alle NixOS-Optionen zu durchsuchen — z.B. 'nginx', 'firewall', 'ssh'",
+    opt_search_empty: "/ drücken um alle NixOS-Optionen zu durchsuchen — z.B. 'nginx', 'firewall', 'ssh' oder Filter wie type:bool in:services.nginx readonly:false has:example",
     opt_results: "Ergebnisse",
     opt_no_results: "Keine Optionen gefunden.",
     opt_browse_hint: "Enter/→ aufklappen · ←/h zuklappen · r verwandte Optionen",
     opt_related_label: "Verwandt:",
+    opt_detail_examples: "In öffentlichen Configs:",
+    opt_examples_update: "Beispiele aktualisieren",
+    opt_examples_no_url: "Keine [option_examples] url in config.toml — nur die mitgelieferten Beispiele verfügbar",
+    opt_link_copied: "🔗 {} kopiert ({})",
+    opt_link_none: "flake-parts-Optionen haben keine Seite auf search.nixos.org oder im Handbuch",
+    opt_examples_updated: "Nutzungsbeispiele aktualisiert: {} Optionen (Stand {})",
+    opt_examples_failed: "Download der Beispiele fehlgeschlagen: {}",
+    job_opt_examples: "Options-Beispiele herunterladen",
     opt_related_empty: "Option in Suche oder Browse auswählen, dann r für verwandte Optionen",
     opt_detail_type: "Typ:",
     opt_detail_default: "Standard:",
@@ -1261,8 +3374,20 @@ static DE: Strings = Strings {
     fi_tab_update: "Aktualisieren",
     fi_tab_history: "Verlauf",
     fi_tab_details: "Details",
+    fi_tab_graph: "Graph",
+    fi_graph_summary: "{} Knoten in flake.lock",
+    fi_graph_summary_dupes: "{} Knoten in flake.lock — {} Quellen in mehreren Revisionen gelockt",
+    fi_graph_expand_all: "Alle aufklappen",
+    fi_graph_collapse_all: "Alle zuklappen",
+    fi_graph_follows: "folgt",
+    fi_graph_copies: "{} Revisionen gelockt",
     fi_loading: "Flake-Inputs werden geladen",
     fi_empty: "Keine Flake-Inputs gefunden.",
+    fi_col_name: "Input",
+    fi_col_url: "URL",
+    fi_col_rev: "Rev",
+    fi_col_age: "Alter",
+    fi_col_status: "Status",
     fi_no_flake_hint: "Dieses Modul benötigt eine Flakes-basierte NixOS-Konfiguration mit einer flake.lock-Datei.",
     fi_update_hint: "Leer: umschalten · a: alle · n: keine · Enter: ausgewählte aktualisieren",
     fi_updating: "Inputs werden aktualisiert",
@@ -1270,6 +3395,74 @@ static DE: Strings = Strings {
     fi_refresh: "Aktualisieren",
     fi_history_empty: "Noch kein Update-Verlauf.",
     fi_history_hint: "Inputs im Update-Tab aktualisieren um hier Änderungen zu sehen.",
+    fi_lockdiff_title: "flake.lock mit Git-Revision vergleichen",
+    fi_lockdiff_note: "Ein Commit, Tag oder Branch, z.B. HEAD~3 oder der Commit des letzten guten Deployments",
+    fi_lockdiff_header: "flake.lock jetzt vs {}",
+    fi_lockdiff_same: "Kein Input ist anders gelockt.",
+    fi_lockdiff_summary: "{} geändert, {} unverändert",
+    fi_lockdiff_failed: "Vergleich fehlgeschlagen: {}",
+    fi_rollback_title: "Diesen Input auf die vorherige Revision zurücksetzen?",
+    fi_rollback_note: "flake.lock wird mit dem vorherigen Lock-Eintrag überschrieben.",
+    fi_rollback_unavailable: "Dieser Eintrag kann nicht zurückgesetzt werden (keine Änderung)",
+    fi_report_title: "Update-Bericht",
+    fi_projects_title: "Flake-Projekte",
+    fi_project_system: "System-Flake",
+    fi_project_gone: "keine flake.nix",
+    fi_project_open: "öffnen",
+    fi_project_add: "Verzeichnis hinzufügen",
+    fi_project_remove: "entfernen",
+    fi_project_hint: "F: andere Flake wählen (z. B. ~/src/infra)",
+    fi_project_missing: "Keine flake.nix in {} (F wählt eine andere Flake)",
+    fi_project_not_flake: "Keine flake.nix in {}",
+    fi_picker_title: "Flake-Verzeichnis wählen",
+    fi_picker_empty: "(keine Unterverzeichnisse)",
+    fi_picker_enter: "öffnen",
+    fi_picker_up: "hoch",
+    fi_picker_select: "diese Flake nehmen",
+    fi_report_hint: "[m] im Verlauf: Markdown-Bericht",
+    fi_report_empty: "Das letzte Update hat keine Inputs geändert",
+    fi_report_counting: "Commits auf GitHub werden gezählt…",
+    fi_report_write: "Neben den Flake schreiben",
+    fi_report_copy: "Kopieren",
+    fi_report_written: "Bericht geschrieben: {}",
+    fi_report_copied: "Bericht kopiert ({})",
+    fi_rollback_done: "{} auf {} zurückgesetzt",
+    fi_rollback_failed: "Zurücksetzen fehlgeschlagen: {}",
+    fi_rolled_back: "zurückgesetzt",
+    fi_edit_url_title: "Input-URL bearbeiten",
+    fi_edit_branch_title: "Branch / Ref ändern",
+    fi_edit_branch_note: "Leer = Standard-Branch des Repositorys",
+    fi_add_name_title: "Input hinzufügen: Name",
+    fi_add_url_title: "Input hinzufügen: URL",
+    fi_remove_title: "Input aus flake.nix entfernen?",
+    fi_remove_note: "Wird auch aus den outputs-Argumenten entfernt. Vorherige Datei bleibt als flake.nix.bak.",
+    fi_edit_followed: "'{}' wird noch gefolgt von: {}",
+    fi_edit_not_declared: "'{}' ist nicht in flake.nix deklariert",
+    fi_relocking: "Flake wird neu gelockt",
+    fi_edit_done: "flake.nix aktualisiert und neu gelockt (Backup: flake.nix.bak)",
+    fi_edit_failed: "Bearbeiten von flake.nix fehlgeschlagen: {}",
+    fi_relock_failed: "Neu-Locken fehlgeschlagen, flake.nix wiederhergestellt: {}",
+    fi_branch_no_nixpkgs: "Kein nixpkgs-Input in diesem Flake",
+    fi_branch_title: "{} auf Branch umstellen",
+    fi_branch_current: "aktuell",
+    fi_branch_notes: "Release Notes:",
+    fi_branch_switched: "{} folgt jetzt {} (flake.lock aktualisiert)",
+    fi_branch_previewing: "Schätze Auswirkungen (nixos-rebuild dry-build)",
+    fi_branch_preview_failed: "Vorschau fehlgeschlagen — der Lock ist aktualisiert, mit einem Dry-Build prüfen",
+    fi_branch_impact_counts: "{} Derivations zu bauen, {} Pfade zu laden",
+    fi_branch_impact_updated: "{} Pakete ändern ihre Version:",
+    fi_branch_impact_added: "{} Pakete nicht im laufenden System",
+    fi_branch_keep: "Behalten",
+    fi_branch_revert: "Zurück zum vorherigen Branch",
+    fi_commit_title: "Aktualisierte flake.lock committen?",
+    fi_commit_message: "Nachricht:",
+    fi_commit_confirm: "Committen",
+    fi_commit_skip: "Überspringen",
+    fi_all_current: "Alle Inputs waren schon aktuell, nichts neu zu bauen",
+    fi_update_all_done: "{} Inputs aktualisiert (Diff im Verlauf)",
+    fi_update_all_next: "[d] Dry-Build  [Enter/s] Switch  [Esc] Jetzt nicht",
+    fi_commit_done: "flake.lock committet ({})",
+    fi_commit_failed: "git commit fehlgeschlagen: {}",
     fi_detail_type: "Typ:",
     fi_detail_url: "URL:",
     fi_detail_branch: "Branch:",
@@ -1277,12 +3470,72 @@ static DE: Strings = Strings {
     fi_detail_narhash: "NAR-Hash:",
     fi_detail_age: "Alter:",
     fi_detail_locked: "Gesperrt am:",
+    fi_detail_upstream: "Upstream:",
+    fi_detail_watch: "Beobachtung:",
+    fi_watch_stale_detail: "{} Tage alt — Schwelle ist {} Tage",
+    fi_watch_stale_count: "{} veraltet",
+    fi_detail_last_commit: "Letzter Commit:",
+    fi_upstream_checking: "Prüfe Upstream",
+    fi_upstream_up_to_date: "Auf dem Stand von Upstream",
+    fi_upstream_behind: "{} Commits zurück",
+    fi_upstream_rate_limited: "GitHub-Rate-Limit erreicht — erneut ab {} (github_token setzen für mehr)",
+    fi_upstream_offline: "Offline — nur Lock-Alter verfügbar",
+    fi_upstream_error: "Vergleich mit Upstream fehlgeschlagen",
+    fi_detail_health: "Zustand:",
+    fi_issue_archived: "Repository archiviert — es kommen keine Updates mehr",
+    fi_issue_archived_hint: "Auf einen gepflegten Fork wechseln: [e] URL ändern",
+    fi_issue_moved: "Repository umgezogen nach {}",
+    fi_issue_moved_hint: "URL anpassen, bevor die Weiterleitung verschwindet: [e] → github:{}",
+    fi_issue_missing: "Repository nicht gefunden (gelöscht oder privat ohne github_token)",
+    fi_issue_missing_hint: "Input auf den neuen Ort zeigen lassen [e] oder entfernen [d]",
+    fi_issue_ref_deleted: "Branch/Tag '{}' existiert upstream nicht mehr",
+    fi_issue_ref_deleted_hint: "Anderen Branch oder Tag wählen: [b]",
+    fi_issue_tag_moved: "Tag '{}' wurde force-gepusht — er zeigt jetzt auf einen anderen Commit",
+    fi_issue_tag_moved_hint: "Neuen Commit vor dem Update prüfen; rev in der URL festlegen, um den alten zu behalten",
     fi_detail_follows: "Folgt:",
 
     // Storage
     sto_dashboard: "Dashboard",
     sto_explorer: "Explorer",
+    sto_ranking: "Ranking",
+    sto_ranking_title: "Closure-Größe je Paket und GC-Root",
+    sto_ranking_scanning: "Messe Closures… (nix path-info -S)",
+    sto_ranking_rescanning: "scanne neu…",
+    sto_ranking_summary: "{} Pakete und Roots · {} Größen von nix, der Rest aus dem Cache",
+    sto_ranking_jumped: "{} seit dem letzten Scan deutlich gewachsen",
+    sto_ranking_empty: "Keine Profil-Pakete oder GC-Roots gefunden",
+    sto_col_origin: "Aus",
+    sto_col_closure: "Closure",
+    sto_col_change: "Änderung",
+    sto_origin_system: "System",
+    sto_origin_user: "Nutzer",
+    sto_origin_root: "Root",
+    sto_why_title: "Warum braucht das System {}?",
+    sto_why_running: "nix why-depends läuft…",
+    job_sto_ranking: "Closure-Ranking",
+    job_sto_why: "nix why-depends",
+    job_sto_suggestions: "Suche Aufräum-Vorschläge",
+    job_sto_sugg_apply: "Wende Aufräum-Vorschläge an",
+    pal_sto_ranking: "Pakete nach Closure-Größe ordnen",
+    sto_boot: "Boot",
+    sto_boot_title: "/boot — Kernel und Initrds",
+    sto_boot_summary: "{} Einträge · {} in Kernel-Dateien · {} frei auf /boot",
+    sto_boot_unavailable: "/boot kann nicht analysiert werden: {}",
+    sto_col_entry: "Eintrag",
+    sto_col_kernel: "Kernel",
+    sto_boot_shared: "geteilt",
+    sto_boot_orphans: "{} verwaiste Dateien ({}) — von keinem Booteintrag verwendet",
+    sto_boot_no_orphans: "Keine verwaisten Dateien. Weniger Einträge: Generationen löschen, dann switch-to-configuration boot.",
+    sto_boot_clean_title: "Verwaiste /boot-Dateien löschen",
+    sto_boot_clean_msg: "{} Dateien ({}) löschen, die kein Booteintrag verwendet?",
+    sto_boot_cleaned: "{} Dateien gelöscht, {} auf /boot frei",
+    sto_boot_clean_failed: "Das Löschen braucht hier sudo ohne Passwortabfrage. Ausführen:\n{}",
+    sto_boot_action: "/boot: verwaiste Dateien entfernt",
+    pal_sto_boot: "Speicher: /boot-Kernel und verwaiste Dateien",
+    pal_sto_suggestions: "Speicher: Aufräum-Vorschläge",
+    pal_sto_why: "Warum hängt das System von diesem Paket ab?",
     sto_clean: "Aufräumen",
+    sto_budget: "Budget",
     sto_history: "Verlauf",
     sto_disk_title: "Speicherplatz",
     sto_breakdown_title: "Nix Store",
@@ -1327,14 +3580,58 @@ static DE: Strings = Strings {
     sto_cleanups: "Bereinigungen",
     sto_history_log: "Verlauf",
     sto_freed: "freigegeben",
+    sto_budget_target_title: "Freier-Speicher-Ziel",
+    sto_budget_target: "Frei halten:",
+    sto_budget_now: "Jetzt frei:",
+    sto_budget_needed: "Freizugeben:",
+    sto_budget_planning: "Plane Bereinigung (analysiere GC-Roots)...",
+    sto_budget_stale: "Ziel geändert — [p] drücken um neu zu planen",
+    sto_budget_reached: "Ziel bereits erreicht — nichts zu tun",
+    sto_budget_plan_title: "Plan",
+    sto_budget_step_gc: "Garbage Collection (tote Pfade)",
+    sto_budget_step_results: "{} result-Link(s) entfernen, dann Garbage Collection",
+    sto_budget_step_gens: "{} Generation(en) älter als {} Tage löschen",
+    sto_budget_step_optimise: "Store optimieren (Duplikate verlinken)",
+    sto_budget_running: "läuft",
+    sto_budget_predicted: "Voraussichtlich frei:",
+    sto_budget_ok: "✓ erreicht das Ziel",
+    sto_budget_short: "⚠ verfehlt das Ziel",
+    sto_budget_hint: "[x/Enter] nächsten Schritt ausführen  [p] neu planen  [+/-] Ziel ±5 GB",
+    sto_budget_sudo_note: "Nutzt sudo -n: braucht gecachte sudo-Anmeldung oder NOPASSWD",
+    sto_suggestions: "Vorschläge",
+    sto_sugg_scanning: "Suche nach Platz, der frei werden kann…",
+    sto_sugg_nothing: "Nichts aufzuräumen",
+    sto_sugg_results: "{} result-Link(s) alter Builds entfernen",
+    sto_sugg_dev_caches: "{} direnv/devenv-Cache-Root(s) entfernen",
+    sto_sugg_home_manager: "{} alte Home-Manager-Generation(en) entfernen",
+    sto_sugg_gc: "Garbage Collection ({} tote Pfade derzeit)",
+    sto_sugg_selected: "{} ausgewählt, bis zu",
+    sto_sugg_overlap: "Schätzungen zählen nur Pfade, die sonst nichts hält; entfernte Roots werden erst durch die Garbage Collection frei.",
+    sto_sugg_hint: "[Space] auswählen  [a] alle  [x/Enter] anwenden  [r] neu suchen",
+    sto_sugg_total: "Gibt bis zu frei:",
+    sto_sugg_none_selected: "Zuerst Vorschläge mit Space auswählen",
+    sto_sugg_history: "Vorschläge: {} angewendet",
+    sto_sugg_staged: "{} Aufräum-Vorschläge anwenden",
     sto_paths_removed: "Pfade entfernt",
 
     // Settings
     settings_theme: "Theme",
+    settings_theme_custom: "eigenes",
+    settings_theme_preview: "Theme-Vorschau",
+    settings_theme_sample: "Beispieltext",
+    settings_theme_files: "Eigene Themes: {}/<name>.toml (alle Felder als #rrggbb, optional base = \"nord\") — gespeicherte Änderungen werden sofort übernommen",
+    theme_load_failed: "Theme {}: {}",
+    theme_reloaded: "Theme {} neu geladen",
+    config_reloaded: "Konfiguration neu geladen",
+    config_reload_failed: "config.toml nicht übernommen: {}",
     settings_language: "Sprache",
     settings_layout: "Layout",
     settings_nixpkgs: "Nixpkgs-Kanal",
     settings_saved: "Einstellungen gespeichert",
+    time_just_now: "gerade eben",
+    time_min_ago: "vor {} Min.",
+    time_h_ago: "vor {} Std.",
+    time_d_ago: "vor {} T.",
     settings_config_path: "Konfiguration",
 
     // Coming Soon
@@ -1344,6 +3641,31 @@ static DE: Strings = Strings {
     // Status bar
     status_navigate: "[j/k] Navigieren",
     status_switch_tab: "[1-9,0] Modul  [,] Einstellungen  [?] Hilfe",
+    nix_degraded_title: "Nix ist nicht verfügbar",
+    too_small_title: "Terminal zu klein",
+    too_small_size: "{} — mindestens {} nötig",
+    too_small_hint: "Fenster vergrößern oder Schrift verkleinern · q beendet",
+    ext_running: "Läuft…",
+    ext_failed: "Befehl fehlgeschlagen: {}",
+    ext_no_output: "Keine Ausgabe",
+    nix_degraded_module: "Dieses Modul benötigt eine funktionierende Nix-Installation.",
+    nix_problem_no_store: "/nix/store nicht gefunden — Nix scheint auf diesem System nicht installiert zu sein.",
+    nix_fix_no_store: "Installiere Nix (https://nixos.org/download) oder starte nixmate auf einem NixOS-System.",
+    nix_problem_no_cli: "Der Befehl `nix` wurde nicht im PATH gefunden (oder antwortet nicht).",
+    nix_fix_no_cli: "Prüfe deinen PATH — neu einloggen oder ~/.nix-profile/etc/profile.d/nix.sh einbinden.",
+    nix_problem_daemon_down: "Der nix-daemon ist nicht erreichbar (/nix/var/nix/daemon-socket/socket).",
+    nix_fix_daemon_down: "Starte ihn mit: sudo systemctl start nix-daemon.socket",
+    nix_problem_store_ro: "Der Nix-Store ist schreibgeschützt eingehängt und kein Daemon läuft.",
+    nix_fix_store_ro: "Starte den nix-daemon oder hänge /nix/store beschreibbar ein.",
+    nix_degraded_still_works: "Weiterhin verfügbar: Error Translator, Services, Config Showcase, Health, Einstellungen",
+    nix_degraded_retry: "Drücke [r] um die Erkennung zu wiederholen",
+    nix_not_nixos_title: "Kein NixOS-System",
+    nix_not_nixos_module: "Dieses Modul arbeitet mit einem NixOS-System und ist hier deaktiviert.",
+    nix_problem_not_nixos: "Nix ist installiert, aber dieses System ist kein NixOS: Es gibt keine System-Generationen, Dienste oder Konfiguration zum Lesen und nichts, was nixos-rebuild bauen könnte.",
+    nix_fix_not_nixos: "Verwalte deine Konfiguration mit home-manager oder nix-darwin; die Module unten funktionieren mit jeder Nix-Installation.",
+    nix_not_nixos_still_works: "Verfügbar: Error Translator, Speicher, Flake Inputs, Paketsuche, Einstellungen",
+    nix_retry_ok: "Nix erkannt — alle Module verfügbar",
+    nix_retry_failed: "Nix ist weiterhin nicht verfügbar",
     status_quit: "[q] Beenden",
     status_change: "[Enter] Ändern",
 
@@ -1356,6 +3678,14 @@ static DE: Strings = Strings {
     // Error Translator module
     err_analyze: "Analysieren",
     err_submit: "Pattern einreichen",
+    err_history: "Verlauf",
+    err_history_empty: "Noch keine analysierten Fehler — jede Analyse landet hier",
+    err_history_no_match: "kein Treffer",
+    err_history_time: "Analysiert:",
+    err_history_source: "Befehl:",
+    err_history_rule: "Regel:",
+    err_history_open: "In Analyse öffnen",
+    err_history_delete: "Löschen",
     err_paste_hint: "Füge deine Nix-Fehlermeldung ein und drücke Enter:",
     err_input: "Eingabe",
     err_found: "GEFUNDEN",
@@ -1363,6 +3693,10 @@ static DE: Strings = Strings {
     err_problem: "Problem",
     err_solution: "Lösung",
     err_understanding: "Verstehen",
+    err_tree_title: "Struktur",
+    err_tree_frames: "{} Trace-Frames [Leertaste]",
+    err_tree_hint: "[Leertaste] Trace auf-/zuklappen · [j/k] scrollen · [t/Esc] zurück",
+    err_tree_summary: "{} Warnungen/Hinweise, {} Trace-Frames",
     err_new_analysis: "Neue Analyse",
     err_submit_pattern: "Als neues Pattern einreichen",
     err_submit_title: "Neues Pattern einreichen",
@@ -1377,6 +3711,32 @@ static DE: Strings = Strings {
     err_idle_hint: "Build-Fehler, Eval-Fehler, Flake-Probleme und mehr.",
     err_start_input: "Eingabe starten",
     err_piped_hint: "📎 Pipe-Eingabe — automatisch analysiert",
+    pipe_detected: "📎 Pipe-Eingabe erkannt als {} — falsch? Erneut pipen mit nixmate --as <rebuild|build|flake|journal|errors>",
+    pipe_kind_rebuild: "nixos-rebuild-Log",
+    pipe_kind_build: "nix-build-Log",
+    pipe_kind_flake: "nix-flake-update-Ausgabe",
+    pipe_kind_journal: "Journal-Auszug",
+    pipe_kind_error: "Fehlermeldung",
+    err_from_clipboard: "aus der Zwischenablage (zuerst tmux-Puffer)",
+    err_from_file: "aus einer Logdatei",
+    err_loaded_clipboard: "📋 Geladen aus {}",
+    err_loaded_file: "📄 {} geladen",
+    err_clipboard_empty: "Zwischenablage leer oder kein Werkzeug gefunden (tmux, wl-paste, xclip, xsel)",
+    err_file_title: "Aus Datei laden",
+    err_file_prompt: "Pfad zu einer Logdatei (die letzten 64 KB werden gelesen):",
+    err_file_hint: "[Enter] Laden  [Esc] Abbrechen",
+    err_open_in_editor: "Öffnen",
+    err_editor_notice: "öffne {} — zurück zu nixmate, sobald der Editor beendet ist",
+    err_editor_failed: "Editor mit Fehler beendet ($VISUAL / $EDITOR prüfen)",
+    err_location_missing: "{} existiert auf diesem Rechner nicht",
+    err_in_config: "In deiner Konfiguration",
+    err_did_you_mean: "Meintest du:",
+    err_explain: "Mit KI erklären",
+    err_explain_title: "KI-Erklärung · extern: {}",
+    err_explain_sent: "Der Fehlertext wurde an {} gesendet. KI-Antworten können falsch sein, vor dem Ausführen prüfen.",
+    err_explain_streaming: "empfange…",
+    err_explain_failed: "Anfrage fehlgeschlagen",
+    err_explain_disabled: "Mit KI erklären ist aus: [ai_explain] enabled = true in config.toml setzen",
     err_ai_ask: "KI fragen",
     err_ai_analyzing: "KI analysiert...",
     err_ai_result: "KI-Analyse",
@@ -1405,6 +3765,17 @@ static DE: Strings = Strings {
     settings_path_found_config: "configuration.nix gefunden",
     settings_path_not_found: "Pfad existiert nicht",
     settings_path_no_config: "Keine flake.nix oder configuration.nix gefunden",
+    settings_refresh_section: "Daten & Aktualisierung",
+    settings_src_services: "Dienste",
+    settings_src_ports: "Ports",
+    settings_src_flake_inputs: "Flake-Inputs",
+    settings_src_options: "Options-Datenbank",
+    settings_src_storage: "Storage-Snapshot",
+    settings_refresh_value: "TTL {} · auto {}",
+    settings_off: "aus",
+    settings_refresh_hint: "Cache-TTL und Auto-Aktualisierung, z.B. \"5m 30s\" (s/m/h/d, 0 = aus)",
+    settings_refresh_invalid: "Ungültiger Wert, erwartet z.B. \"5m 30s\"",
+    stale_badge: "Daten {} alt",
 
     // Services & Ports module
     svc_overview: "Übersicht",
@@ -1413,9 +3784,25 @@ static DE: Strings = Strings {
     svc_manage: "Verwalten",
     svc_running: "aktiv",
     svc_failed: "fehlerhaft",
+    svc_socket_idle: "socket-aktiviert",
+    svc_socket_label: "Socket",
     svc_total: "Dienste",
     svc_total_containers: "Container",
     svc_ports_open: "Ports offen",
+    svc_mounts_title: "Mounts & Laufwerke",
+    svc_mounts_loading: "Prüfe Mounts und Laufwerke…",
+    svc_mounts_none: "Keine Netzwerk-Mounts konfiguriert",
+    svc_mounts_ok: "Mounts & Laufwerke OK",
+    svc_mounts_problems: "{} Mount-/Laufwerksproblem(e)",
+    svc_mount_mounted: "eingehängt",
+    svc_mount_automount: "Automount",
+    svc_mount_failed: "fehlgeschlagen",
+    svc_mount_unmounted: "nicht eingehängt",
+    svc_mount_stale: "antwortet nicht",
+    svc_disk_passed: "OK",
+    svc_disk_failed: "FEHLERHAFT",
+    svc_disk_unknown: "unbekannt",
+    svc_disk_degraded: "degradiert",
     svc_shown: "angezeigt",
     svc_no_entries: "Keine Einträge für diesen Filter.",
     svc_no_ports: "Keine offenen Ports erkannt.",
@@ -1424,8 +3811,42 @@ static DE: Strings = Strings {
     svc_col_address: "Adresse",
     svc_col_owner: "Zugehörigkeit",
     svc_col_process: "Prozess",
+    svc_col_pid: "PID",
+    svc_col_state: "Status",
+    svc_col_name: "Name",
+    svc_col_enabled: "Autostart",
+    svc_col_ports: "Ports",
+    svc_col_cpu: "CPU",
+    svc_col_memory: "Speicher",
+    svc_sorted_by: "Sortiert nach {}",
+    svc_col_description: "Beschreibung",
+    svc_proxies: "Reverse-Proxies",
+    svc_col_domain: "Domain",
+    svc_col_upstream: "Upstream",
+    svc_proxy_dangling: "niemand lauscht",
+    svc_proxy_remote: "entfernt",
+    svc_proxy_dangling_count: "{} verwaist",
+    svc_port_warnings: "{} Port-Warnungen",
+    svc_port_duplicate: "mehrere Prozesse lauschen",
+    svc_port_not_listening: "in der NixOS-Firewall geöffnet, aber niemand lauscht",
+    svc_port_more: "… {} weitere",
     svc_logs_for: "Logs:",
     svc_no_logs: "Keine Logs. Wähle zuerst einen Dienst im Übersicht-Tab.",
+    svc_logs_tail: "letzte {}",
+    svc_logs_since: "seit",
+    svc_logs_until: "bis",
+    svc_logs_grep: "grep",
+    svc_logs_range_boot: "seit Boot",
+    svc_logs_range_hour: "letzte Stunde",
+    svc_logs_range_today: "heute",
+    svc_logs_errors_only: "nur Fehler",
+    svc_logs_input_since: "Seit (30m, 2h, 3d oder 2024-05-01 14:00):",
+    svc_logs_input_until: "Bis (30m, 2h, 3d oder 2024-05-01 14:00):",
+    svc_logs_input_grep: "Grep (gefiltert von journalctl / beim Einlesen):",
+    svc_logs_bad_time: "Unbekannte Zeitangabe: {}",
+    svc_logs_loading: "Lade Logs…",
+    svc_logs_no_match: "Keine Log-Zeilen passen zu den Filtern. [x] setzt sie zurück.",
+    svc_logs_filters_cleared: "Log-Filter zurückgesetzt",
     svc_no_selection: "(nicht ausgewählt)",
     svc_status_label: "Status:",
     svc_enabled_label: "Aktiviert",
@@ -1435,11 +3856,21 @@ static DE: Strings = Strings {
     svc_act_start: "Starten",
     svc_act_stop: "Stoppen",
     svc_act_restart: "Neustarten",
+    svc_no_failed: "Keine fehlgeschlagenen Units",
+    svc_restart_failed_title: "{} fehlgeschlagene Unit(s) neu starten",
+    svc_restart_and_follow: "Ja + Logs verfolgen",
+    svc_restarted_summary: "{} von {} Unit(s) laufen wieder",
+    svc_logs_following: "live",
+    job_svc_restart: "Fehlgeschlagene Units neu starten",
     svc_act_enable: "Aktivieren (Autostart)",
     svc_act_disable: "Deaktivieren (kein Autostart)",
     svc_confirm_action: "Bist du sicher?",
     svc_action_title: "Aktion bestätigen",
     svc_sudo_note: "Diese Aktion benötigt sudo.",
+    svc_kill_title: "Port freigeben",
+    svc_kill_action: "Prozess beenden (SIGTERM), gibt :{} frei",
+    svc_port_no_pid: "Kein Prozess zu diesem Port sichtbar — er gehört einem anderen Benutzer (als root ausführen, um ihn zu sehen)",
+    svc_restart_sudo_note: "Läuft im Hintergrund mit sudo -n: braucht eine gültige sudo-Anmeldung (vorher sudo -v) oder NOPASSWD.",
     svc_load_error: "Dienste konnten nicht geladen werden",
     svc_load_error_hint: "systemctl evtl. nicht verfügbar. Läuft NixOS?",
     svc_filter_all: "Alle",
@@ -1455,6 +3886,38 @@ static DE: Strings = Strings {
     rb_history: "Verlauf",
     rb_idle_title: "Rebuild Dashboard",
     rb_idle_hint: "Drücke [Enter] oder [r] um einen Rebuild zu starten",
+    rb_repl_hint: "Drücke [e] um die Konfiguration in `nixos-rebuild repl` zu untersuchen",
+    rb_targets_hint: "Drücke [f] um ein Paket, eine Dev-Shell oder einen Check eines Flakes zu bauen",
+    rb_targets_title: "Flake-Ziel bauen",
+    rb_targets_loading: "Lese die Ausgaben des Flakes (nix flake show)…",
+    rb_targets_build: "Bauen",
+    rb_targets_reload: "Neu laden",
+    rb_hosts_title: "Flake & Host für den Rebuild",
+    rb_hosts_follow: "Host-Name dieses Rechners ({})",
+    rb_hosts_none: "Der Flake hat keine nixosConfigurations",
+    rb_hosts_pick: "verwenden",
+    rb_hosts_flake: "nächster Flake",
+    rb_host: "Host",
+    rb_target_no_detach: "Entwicklungs-Builds können nicht abgekoppelt werden — abbrechen oder warten",
+    rb_repl_unavailable: "Weder nixos-rebuild noch nix gefunden — Eval-Konsole nicht verfügbar",
+    rb_repl_running: "Öffne Eval-Konsole — mit :q zurück zu nixmate",
+    rb_repl_failed: "Eval-Konsole mit Fehler beendet",
+    rb_quit_title: "Rebuild läuft noch",
+    rb_quit_running: "Jetzt beenden würde den laufenden Rebuild abbrechen — er bleibt auf halbem Weg stehen.",
+    rb_quit_detach_note: "Abkoppeln startet ihn als systemd-Unit neu, die nixmate überlebt (Gebautes wird wiederverwendet). Später im Rebuild-Tab wieder anhängen.",
+    rb_quit_detach: "Abkoppeln & beenden",
+    rb_quit_stop: "Stoppen & beenden",
+    rb_quit_stay: "Bleiben",
+    rb_detach_notice: "Verschiebe den Rebuild in die systemd-Unit {} — mit journalctl -fu verfolgen oder in nixmate wieder anhängen (7 → a)",
+    rb_detached_to: "⏏ Build abgekoppelt nach {}",
+    rb_detach_failed: "systemd-Unit konnte nicht gestartet werden — der Build wurde gestoppt, im Rebuild-Tab neu starten",
+    rb_detach_activating: "Das neue System wird schon aktiviert — jetzt nicht verschieb- oder abbrechbar, bitte zu Ende laufen lassen",
+    rb_detach_not_stopped: "Der lokale Build hat nicht rechtzeitig angehalten — nicht abgekoppelt, damit er nie doppelt läuft",
+    rb_detach_stopping: "Lokaler Build wird gestoppt, danach abgekoppelt…",
+    rb_cancel_activating: "Die Aktivierung ist nicht mittendrin abbrechbar — sie läuft von selbst zu Ende",
+    rb_quit_activating: "Das neue System wird gerade aktiviert — jetzt stoppen oder verschieben würde es halb umgeschaltet zurücklassen.",
+    rb_quit_when_done: "Beenden, wenn fertig",
+    rb_quit_waiting: "nixmate beendet sich, sobald der Rebuild fertig ist",
     rb_detecting: "Systemkonfiguration wird erkannt...",
     rb_config_detected: "Konfiguration:",
     rb_config_flakes: "Flakes",
@@ -1466,12 +3929,23 @@ static DE: Strings = Strings {
     rb_current_mode: "Modus:",
     rb_cycle_mode: "wechseln",
     rb_last_build: "Letzter Build:",
+    rb_sys_builds_title: "Laufende Builds auf diesem System",
+    rb_sys_builds_none: "Keine anderen Builds aktiv",
+    rb_sys_builds_hint: "[j/k] auswählen  [a] Log verfolgen",
+    rb_sys_builder: "Builder",
+    rb_sys_client: "Befehl",
+    rb_attach_title: "Verfolge",
+    rb_attach_hint: "[Esc] trennen",
+    rb_attach_waiting: "Warte auf Log-Ausgabe...",
+    rb_attach_finished: "Build beendet — Log vollständig",
+    rb_attach_no_log: "Kein Log für diesen Build verfügbar (Derivation unbekannt)",
     rb_estimated_time: "Geschätzte Zeit:",
     rb_mode_switch: "switch",
     rb_mode_boot: "boot",
     rb_mode_test: "test",
     rb_mode_build: "build",
     rb_mode_dry: "dry-build",
+    rb_mode_rollback: "rollback",
     rb_phase_idle: "BEREIT",
     rb_phase_preparing: "VORBEREITUNG",
     rb_phase_evaluating: "AUSWERTUNG",
@@ -1497,18 +3971,80 @@ static DE: Strings = Strings {
     rb_explain_bootloader: "Bootloader-Einträge (GRUB oder systemd-boot) werden aktualisiert. Eine neue NixOS-Generation wird neben den vorherigen hinzugefügt — du kannst im Boot-Menü jederzeit zurückwechseln.",
     rb_explain_done: "Alle Phasen abgeschlossen. Dein System läuft jetzt mit der neuen Konfiguration. Vorherige Generationen bleiben für Rollback über Boot-Menü oder `nixos-rebuild switch --rollback` verfügbar.",
     rb_explain_failed: "Der Build ist fehlgeschlagen. Prüfe den Log-Tab für die vollständige Fehlerausgabe. Häufige Ursachen: Syntaxfehler in der Config, fehlende Pakete oder Netzwerkprobleme beim Fetch.",
+    rb_line_build: "Eine .drv (Derivation) ist Nix' Bauanleitung: exakte Quellen, Abhängigkeiten und Befehle. Niemand hatte das Ergebnis im Cache, also wird es auf deinem Rechner gebaut.",
+    rb_line_fetch: "Ein fertiges Build-Ergebnis wird aus einem Binary-Cache (z.B. cache.nixos.org) geladen statt gebaut — gleicher Hash, gleiche Bits.",
+    rb_line_build_plan: "Nix hat deine Config mit dem Store verglichen: diese Derivations gibt es noch nirgends und sie müssen lokal gebaut werden.",
+    rb_line_fetch_plan: "Diese Store-Pfade liegen bereits in einem Binary-Cache; übertragen wird nur die angezeigte Downloadgröße.",
+    rb_line_evaluating: "Nix liest deine .nix-Dateien und berechnet, wie das System aussehen soll. Noch wird nichts gebaut — Fehler hier sind Config-Fehler.",
+    rb_line_activating: "Das neue System ist gebaut; sein Aktivierungsskript schaltet es jetzt live (Benutzer, /etc, Dienste). Nur switch und test aktivieren.",
+    rb_line_etc: "Dateien in /etc sind Symlinks in den Store-Pfad des neuen Systems; sie werden atomar auf die neue Generation umgestellt.",
+    rb_line_restart: "Die Definition dieser Units hat sich geändert, daher stoppt und startet systemd sie mit der neuen Version.",
+    rb_line_start: "Diese Units sind neu in dieser Konfiguration (oder liefen nicht) und werden jetzt gestartet.",
+    rb_line_stop: "Diese Units gibt es in der neuen Konfiguration nicht mehr, daher werden sie gestoppt.",
+    rb_line_reload: "Diese Units unterstützen Reload: sie lesen ihre Config neu ein, ohne komplett neu zu starten.",
+    rb_line_bootloader: "Das Bootmenü bekommt einen Eintrag für die neue Generation, damit sie auch beim nächsten Start genutzt (oder gewählt) werden kann.",
+    rb_line_warning: "Nix macht weiter, aber etwas in deiner Config ist veraltet oder verdächtig — besser beheben, bevor es ein Fehler wird.",
+    rb_line_error: "Der Build ist hier abgebrochen. Die Zeilen darüber zeigen meist wo; der Fehler-Übersetzer (Tab 2) erklärt häufige Fehler.",
+    rb_line_trace: "Ausgabe von builtins.trace oder --show-trace: Nix zeigt, wie die Auswertung hierher kam, innerster Aufruf zuletzt.",
     rb_confirm_title: "Rebuild bestätigen",
     rb_confirm_mode: "Modus",
+    rb_confirm_specialisation: "Spezialisierung",
+    rb_spec_default: "Standard",
+    rb_spec_cycle: "Tab: wechseln",
     rb_confirm_cmd: "Befehl",
     rb_sudo_note: "⚠ Erfordert sudo-Berechtigung",
+    rb_net_title: "Download über getaktete Verbindung",
+    rb_net_title_slow: "Langsamer Download",
+    rb_net_metered: "NetworkManager markiert diese Verbindung als getaktet.",
+    rb_net_slow: "Der Download läuft mit {}/s.",
+    rb_net_size: "nix lädt {} MiB aus den Binary Caches.",
+    rb_net_eta: "Beim aktuellen Tempo dauert das etwa {}.",
+    rb_net_offline_hint: "Offline bricht diesen Build ab und startet ihn ohne Substituter neu: alles Fehlende wird lokal gebaut.",
+    rb_net_continue: "Weiter",
+    rb_net_offline: "Offline bauen",
+    rb_confirm_offline: "Offline: keine Substituter, fehlende Pfade werden lokal gebaut",
+    rb_confirm_rollback_title: "Rollback bestätigen",
+    rb_confirm_rollback: "Aktiviert Generation {} erneut. Es wird nichts evaluiert oder gebaut; die aktuelle Generation bleibt installiert.",
+    rb_rollback_none: "Keine ältere Generation für ein Rollback vorhanden",
+    rb_changes_restored: "Verglichen mit wiederhergestellter Generation {}",
     rb_stat_built: "Gebaut",
     rb_stat_fetched: "Geladen",
     rb_live_output: "Live-Ausgabe",
+    rb_compact_eta: "noch ~{}",
+    rb_compact_overdue: "{} über Schätzung",
+    rb_compact_last_error: "Letzter Fehler:",
     rb_auto_scroll: "LIVE",
     rb_resume_scroll: "Live fortsetzen",
     rb_log_empty: "Noch kein Build-Log vorhanden",
     rb_log_empty_hint: "Starte einen Rebuild im Dashboard-Tab",
+    rb_busy: "Ein Rebuild läuft bereits",
+    rb_backend_needs_flake: "{} braucht eine Flake-Konfiguration, baue mit nixos-rebuild",
+    rb_backend_missing: "{} nicht gefunden, baue mit nixos-rebuild",
+    rb_smart_fetched: "{} Pfade geladen",
+    rb_smart_built: "{} Derivationen gebaut",
+    rb_smart_lines: "{} Zeilen",
+    rb_tree_title: "Abhängigkeitsbaum",
+    rb_tree_counts: "{} wartend · {} im Bau · {} fertig · {} fehlgeschlagen",
+    rb_tree_critical: "kritischer Pfad: {} offen",
+    rb_tree_loading: "Abhängigkeiten werden gelesen…",
+    rb_tree_no_deps: "Abhängigkeiten unbekannt ({}), Derivations als Liste",
+    rb_tree_empty: "In diesem Lauf (noch) nichts zu bauen",
+    rb_log_nothing: "Das Log ist leer",
+    rb_log_exported: "Log gespeichert unter {}",
+    rb_log_no_error: "Kein Fehler im Log",
+    rb_log_copied: "{} Fehlerzeilen kopiert ({})",
+    rb_log_no_clipboard: "Kein Zwischenablage-Tool gefunden (wl-copy, xclip, xsel, pbcopy oder tmux)",
+    rb_share_disabled: "Teilen ist aus: [paste] enabled = true in config.toml setzen",
+    rb_share_uploading: "Lade Log zu {} hoch…",
+    rb_share_confirm_title: "Build-Log teilen?",
+    rb_share_confirm: "{} Log-Zeilen zu {} hochladen?",
+    rb_share_confirm_hint: "Der Paste ist öffentlich und lässt sich nicht zurücknehmen. Logs können Hostnamen, Pfade und Geheimnisse enthalten.",
+    rb_share_copied: "Geteilt: {} (Link kopiert)",
+    rb_share_done: "Geteilt: {}",
     rb_changes_summary: "Änderungen:",
+    rb_warnings_title: "Warnungen ({})",
+    rb_warnings_hint: "w/W wählen · Leertaste Originalzeile · o Option",
+    rb_warnings_no_option: "Keine Option in dieser Warnung erkannt",
     rb_changes_added: "hinzugefügt",
     rb_changes_removed: "entfernt",
     rb_changes_updated: "aktualisiert",
@@ -1519,20 +4055,47 @@ static DE: Strings = Strings {
     rb_kernel_changed: "Kernel aktualisiert!",
     rb_reboot_needed: "Neustart erforderlich für den neuen Kernel",
     rb_services_restarted: "Neu gestartete Dienste",
+    rb_preview_title: "Vor dem Umschalten (dry-activate)",
+    rb_preview_hint: "[p] Vorschau: welche Units ein Switch stoppt, neu startet oder neu lädt",
+    rb_preview_running: "Prüfe, was ein Switch tun würde…",
+    rb_preview_needs_build: "Die Vorschau braucht einen fertigen Build (Modus: build)",
+    rb_preview_sudo: "Läuft mit sudo -n: braucht eine gültige sudo-Anmeldung (vorher sudo -v) oder NOPASSWD.",
+    rb_preview_nothing: "Keine laufende Unit würde sich ändern",
+    rb_preview_systemd: "systemd selbst würde neu starten",
+    rb_preview_stop: "Würde stoppen",
+    rb_preview_restart: "Würde neu starten",
+    rb_preview_reload: "Würde neu laden",
+    rb_preview_start: "Würde starten",
+    rb_preview_kept: "Geändert, läuft weiter",
+    rb_preview_switch: "[s] Jetzt auf dieses System umschalten",
     rb_history_empty: "Noch keine Rebuilds in dieser Sitzung",
     rb_history_empty_hint: "Dein Rebuild-Verlauf erscheint hier",
+    rb_breakdown_avg: "Schnitt der letzten {} Builds:",
+    rb_breakdown_eval: "Auswertung",
+    rb_breakdown_fetch: "Download",
+    rb_breakdown_build: "Bauen",
+    rb_breakdown_activate: "Aktivierung",
+    rb_breakdown_boot: "Bootloader",
+    rb_up_to_date_activation: "Schon aktuell — nur Aktivierung",
+    rb_up_to_date_nothing: "Schon aktuell — nichts zu bauen oder zu laden",
+    rb_up_to_date_desc: "Die Auswertung fand alle Store-Pfade schon vor; nichts wurde gebaut oder heruntergeladen.",
+    rb_up_to_date_short: "Aktuell: nichts gebaut oder geladen",
     rb_password_label: "Passwort:",
     rb_password_hint: "sudo-Passwort eingeben...",
     rb_nopasswd_hint: "NOPASSWD? Einfach Enter drücken",
     rb_password_submit: "Enter",
 
     // Generations (additional)
-    gen_action_confirmed: "Aktion bestätigt",
-    gen_undo_closed: "Rückgängig-Fenster geschlossen",
     gen_pin_updated: "Pin-Status aktualisiert",
     gen_cannot_restore_current: "Aktuelle Generation kann nicht wiederhergestellt werden",
     gen_cannot_delete_current: "Aktuelle Generation kann nicht gelöscht werden",
     gen_cannot_delete_pinned: "Gepinnte Generation kann nicht gelöscht werden",
+    gen_protected_booted: "#{} ist die Generation, von der dieses System gebootet hat — dein funktionierender Rückweg bis zum nächsten Neustart",
+    gen_protected_default: "#{} ist der Standard im Bootloader — der nächste Boot startet sie",
+    gen_confirm_protected: "Geschützte Generation löschen?",
+    gen_protected_warning: "nix löscht sie ohne Rückfrage. Nur erneut bestätigen, wenn du weißt, was du als Nächstes bootest.",
+    gen_protected_skipped: "{} geschützte Generation(en) ausgelassen (gebootet / Boot-Standard)",
+    gen_cleanup_refused: "Abgelehnt — {}. Erst in die aktuelle Generation neu starten oder sie bewusst in Generationen → Verwalten löschen.",
     gen_no_match_filter: "Keine Pakete entsprechen dem Filter",
     gen_no_found: "Keine Generationen gefunden",
     gen_from: "Von",
@@ -1542,13 +4105,23 @@ static DE: Strings = Strings {
     gen_command_failed: "Befehl fehlgeschlagen",
     gen_select_hint: "Generation im Übersicht-Tab wählen und Enter drücken",
     gen_no_comparison: "Keine Generationen für Vergleich verfügbar",
-    gen_diff_hint: "Zwei Generationen wählen: [Tab] wechseln, [j/k] navigieren, [Enter] wählen, [c] leeren",
+    gen_diff_hint: "Zwei Generationen wählen: [Tab] wechseln, [j/k] navigieren, [Enter] wählen, [c] leeren, [d] git diff der Konfiguration",
     gen_command_label: "Befehl:",
-    gen_undo_available: "Rückgängig verfügbar",
-    gen_dismiss: "Schließen",
     gen_deleted_count: "{} Generation(en) gelöscht",
+    gen_staged_delete: "{} Generation(en) löschen",
     gen_system_label: "System",
     gen_hm_label: "Home-Manager",
+    gen_col_id: "Gen",
+    gen_col_date: "Datum",
+    gen_col_version: "Version",
+    gen_col_packages: "Pakete",
+    gen_col_size: "Größe",
+    gen_col_commit: "Konfig-Commit",
+    gen_git_diff_pick: "Zuerst beide Generationen wählen",
+    gen_git_diff_unavailable: "Kein Konfig-Commit für beide Generationen bekannt (Flake in einem Git-Repo nötig)",
+    gen_git_diff_unknown_commit: "Ein Commit fehlt im lokalen Konfig-Repo (git fetch?)",
+    gen_git_diff_notice: "git diff der Konfiguration, Generation #{} → #{} (Pager beenden, um zurückzukehren)",
+    gen_git_diff_failed: "git diff mit Fehler beendet",
     gen_detection_failed: "Systemerkennung fehlgeschlagen",
     gen_ensure_nixos: "Stelle sicher, dass du NixOS mit nix-env im PATH verwendest.",
 
@@ -1622,6 +4195,129 @@ static DE: Strings = Strings {
     health_detail_fresh_ok: "Letzter Rebuild vor {} Tagen",
     health_detail_fresh_warn: "Letzter Rebuild vor {} Tagen — Update empfohlen",
     health_detail_fresh_crit: "Letzter Rebuild vor {} Tagen — sehr veraltet!",
+    health_name_flake_watch: "Alter der Flake-Inputs",
+    health_desc_flake_watch: "Flake-Inputs älter als ihre eingestellte Schwelle",
+    health_fix_flake_watch: "In Flake-Inputs aktualisieren (8 → Aktualisieren)",
+    health_detail_flake_watch_ok: "Alle Inputs innerhalb ihrer Altersschwelle",
+    health_detail_flake_watch_stale: "{} veraltet: {}",
+    health_name_signatures: "Cache-Signaturen",
+    health_desc_signatures: "Stichprobe der Closure gegen trusted-public-keys geprüft",
+    health_detail_sigs_ok: "{} geprüfte Pfade vertrauenswürdig ({} signiert, {} lokal gebaut)",
+    health_detail_sigs_untrusted: "{} von {} geprüften Pfaden nicht vertrauenswürdig: {} ungültige Signatur, {} unbekannter Schlüssel, {} unsigniert",
+    health_detail_sigs_no_require: "require-sigs = false — Pfade aus Caches werden gar nicht geprüft",
+    health_fix_sigs_require: "nix.settings.require-sigs = true setzen, sonst kann jeder Cache veränderte Pfade liefern",
+    health_fix_sigs_bad: "Vertrauter Schlüssel, aber ungültige Signatur: Pfad verändert? Reparieren lädt ihn neu",
+    health_fix_sigs_unknown_key: "Signiert von {}: Schlüssel zu nix.settings.trusted-public-keys hinzufügen oder Cache entfernen",
+    health_fix_sigs_unsigned: "Weder hier gebaut noch signiert (nix copy --no-check-sigs?): neu bauen oder signieren",
+    health_name_integrity: "Store-Inhalt",
+    health_desc_integrity: "Stichprobe des Closures hasht noch wie in der Nix-Datenbank vermerkt — erkennt Plattenfehler und manuelle Änderungen",
+    health_detail_integrity_ok: "{} geprüfte Pfade intakt",
+    health_detail_integrity_bad: "{} von {} geprüften Pfaden verändert oder beschädigt",
+    health_detail_skipped_not_nixos: "Übersprungen: benötigt NixOS",
+    health_fix_integrity: "Reparatur lädt die beschädigten Pfade neu oder baut sie neu",
+    health_name_flake_eval: "Flake-Auswertung",
+    health_desc_flake_eval: "Wertet das System dieses Hosts aus dem Flake aus, ohne etwas zu bauen (nix eval von toplevel.drvPath bzw. nix flake check --no-build). Findet Auswertungsfehler, bevor ein Rebuild es tut.",
+    health_detail_flake_eval_ok: "{} lässt sich auswerten",
+    health_detail_flake_eval_failed: "Auswertung schlägt fehl: {}",
+    health_detail_flake_eval_timeout: "Auswertung nicht innerhalb von {}s fertig",
+    health_fix_flake_eval: "e öffnet den vollständigen Fehler im Error Translator",
+    health_dep_feat_nixos_option: "Options-Explorer: aktuelle Werte von Optionen (langsamere Ersatzwege, manche Werte bleiben leer)",
+    health_dep_feat_journalctl: "Services: Unit-Logs, Log-Suche und Zeitfilter; Rebuild: Logs fehlgeschlagener Units",
+    health_dep_feat_git: "Flake-Inputs: flake.lock nach einem Update committen",
+    health_dep_feat_containers: "Services: Container-Liste, Logs und Aktionen",
+    health_dep_feat_busctl: "Rebuild: Warnung vor Downloads über eine getaktete Verbindung",
+    health_detail_dep_missing: "Nicht installiert — eingeschränkt: {}",
+    health_detail_dep_old: "{} ist älter als {} — eingeschränkt: {}",
+    health_fix_dep_install: "pkgs.{} installieren",
+    health_no_eval_error: "Kein Auswertungsfehler zum Übersetzen",
+    health_name_boot_space: "Boot-Partition",
+    health_desc_boot_space: "Jede Generation legt Kernel und initrd auf /boot ab — eine volle EFI-Partition lässt den nächsten Rebuild mittendrin scheitern",
+    health_detail_boot_space: "{} Boot-Einträge, {} frei ({}% belegt)",
+    health_fix_boot_space_clean: "Generationen älter als 14 Tage löschen und Boot-Einträge neu schreiben",
+    health_fix_boot_space_limit: "Anzahl der Boot-Einträge in der Konfiguration begrenzen",
+    health_name_booted: "Gebootete Generation",
+    health_desc_booted: "Laufendes System vs. das gebootete — ein neuer Kernel wirkt erst nach einem Neustart",
+    health_detail_booted_same: "Läuft die Generation, die gebootet wurde",
+    health_detail_booted_switched: "Seit dem Boot umgeschaltet, gleicher Kernel",
+    health_detail_booted_kernel: "Kernel oder initrd seit dem Boot geändert — Neustart ausstehend",
+    health_fix_booted: "Bei Gelegenheit neu starten, um den neuen Kernel zu nutzen",
+    health_name_initrd_secrets: "initrd-Secrets",
+    health_desc_initrd_secrets: "Dateien aus boot.initrd.secrets werden in jede initrd kopiert — fehlt eine, scheitert die Bootloader-Installation",
+    health_detail_initrd_ok: "{} Secrets vorhanden",
+    health_detail_initrd_missing: "Fehlt: {}",
+    health_fix_initrd: "Datei wiederherstellen (oder aus boot.initrd.secrets entfernen) vor dem nächsten Rebuild",
+    health_name_stale_entries: "Boot-Einträge",
+    health_desc_stale_entries: "Einträge in /boot/loader/entries, deren Generation gelöscht wurde, belegen Platz und bieten ein System an, das es nicht mehr gibt",
+    health_detail_entries_ok: "Alle Einträge gehören zu vorhandenen Generationen",
+    health_detail_entries_stale: "{} Einträge für gelöschte Generationen: {}",
+    health_fix_entries: "Boot-Einträge aus den vorhandenen Generationen neu schreiben",
+    health_name_power_daemons: "Energieverwaltung",
+    health_desc_power_daemons: "TLP, power-profiles-daemon und auto-cpufreq stellen alle CPU und Geräte ein; zwei gleichzeitig überschreiben sich gegenseitig, keiner lässt den Laptop auf Standard-Leistung",
+    health_detail_power_ok: "Verwaltet von {}",
+    health_detail_power_none: "Kein Energieverwaltungs-Dienst im Akkubetrieb aktiv",
+    health_detail_power_conflict: "Konkurrierende Energieverwalter: {}",
+    health_fix_power_none: "Einen Energieverwalter in der Konfiguration aktivieren",
+    health_fix_power_conflict: "Einen Energieverwalter behalten, die anderen deaktivieren",
+    health_name_thermald: "Intel Thermal Daemon",
+    health_desc_thermald: "Auf Intel-Laptops hält thermald die CPU unter ihrem Temperaturlimit, ohne hart zu drosseln – spart Akku und hält die Lüfter leiser",
+    health_detail_thermald_ok: "thermald läuft",
+    health_detail_thermald_missing: "Intel-CPU ohne thermald",
+    health_fix_thermald: "thermald aktivieren",
+    health_name_firmware: "Firmware",
+    health_desc_firmware: "Treiber, die ihre Firmware nicht laden können, lassen WLAN, GPU oder Netzwerkkarten halb funktionierend oder ganz weg – oft ohne deutliche Fehlermeldung",
+    health_detail_firmware_ok: "Keine fehlende Firmware im Kernel-Log",
+    health_detail_firmware_missing: "Firmware nicht gefunden: {}",
+    health_fix_firmware: "Weiterverteilbare Firmware aktivieren",
+    health_name_microcode: "CPU-Microcode",
+    health_desc_microcode: "Microcode-Updates beheben CPU-Fehler und Sicherheitslücken (Spectre & Co.), die die Firmware des Boards vielleicht nie bekommt",
+    health_detail_microcode_ok: "Microcode beim Booten früh aktualisiert",
+    health_detail_microcode_missing: "Kein früher Microcode-Update im Kernel-Log ({}-CPU) – nicht aktiviert oder die Firmware ist schon aktuell",
+    health_fix_microcode: "Microcode-Updates beim Booten laden",
+    health_name_swap: "Swap / zram",
+    health_desc_swap: "Ohne Swap kann der Kernel ungenutzten Speicher nirgends auslagern, und ein großer Build oder Browser läuft in den OOM-Killer",
+    health_detail_swap_none: "Kein Swap und kein zram",
+    health_detail_swap_ok: "zram {} · Swap auf Disk {}",
+    health_fix_swap: "Komprimierten Swap im RAM aktivieren",
+    health_name_disk_ids: "Disk-IDs in hardware-configuration.nix",
+    health_desc_disk_ids: "Ein Dateisystem mit einer UUID, die es nicht mehr gibt, lässt den nächsten Boot in der Notfall-Shell enden",
+    health_detail_disk_ids_ok: "Alle referenzierten Disks existieren",
+    health_detail_disk_ids_missing: "Nicht vorhanden: {}",
+    health_fix_disk_ids: "Dateisysteme auf die jetzt vorhandenen Disks zeigen lassen",
+    health_name_features: "Flakes aktiviert",
+    health_desc_features: "Ein Flake-basiertes System braucht die Features nix-command und flakes in nix.conf; sie auf der Kommandozeile anzugeben klappt nur, bis ein Tool oder ein anderer Benutzer es nicht tut",
+    health_detail_features_ok: "nix-command und flakes in nix.conf aktiviert",
+    health_detail_features_missing: "System nutzt einen Flake, aber nix.conf aktiviert nicht: {}",
+    health_fix_features: "Flakes in nix.settings aktivieren",
+    health_name_parallelism: "Build-Parallelität",
+    health_desc_parallelism: "max-jobs bestimmt, wie viele Derivations gleichzeitig bauen, cores wie viele CPUs jeder Build nutzen darf; steht eins davon auf 1, bleibt der Rechner beim Rebuild größtenteils untätig",
+    health_detail_parallelism_ok: "max-jobs = {}, cores = {}",
+    health_detail_parallelism_low: "{} auf einem Rechner mit {} CPUs",
+    health_fix_parallelism: "Alle CPUs für Builds nutzen",
+    health_name_trusted_users: "Vertrauenswürdige Nix-Benutzer",
+    health_desc_trusted_users: "Vertrauenswürdige Benutzer können Substituter hinzufügen und unsignierte Store-Pfade importieren, das kommt root gleich; in trusted-users gehören nur Admins",
+    health_detail_trusted_ok: "trusted-users = {}",
+    health_detail_trusted_unsafe: "Vertrauenswürdig ohne Admin zu sein: {}",
+    health_fix_trusted_users: "Nur root und wheel vertrauen",
+    health_name_hibernate: "Standby & Ruhezustand",
+    health_desc_hibernate: "Der Ruhezustand schreibt den RAM in Swap auf der Platte: dafür braucht es Swap mindestens so groß wie der RAM (zram zählt nicht), ein Resume-Gerät und keinen Kernel-Lockdown, sonst passiert beim Zuklappen still nichts",
+    health_detail_hibernate_lid: "Deckel: {}",
+    health_detail_hibernate_sizes: "Swap {}, RAM {}",
+    health_detail_hibernate_ready: "Ruhezustand bereit",
+    health_detail_hibernate_swap: "Swap kleiner als RAM",
+    health_detail_hibernate_resume: "kein Resume-Gerät",
+    health_detail_hibernate_lockdown: "Kernel-Lockdown blockiert den Ruhezustand",
+    health_fix_hibernate: "Genug Swap und ein Resume-Gerät einrichten oder nur Standby nutzen",
+    health_name_charge_threshold: "Akku-Ladegrenze",
+    health_desc_charge_threshold: "Ein Akku, der den ganzen Tag auf 100 % steht, altert schneller; viele Laptops können z. B. bei 80 % aufhören zu laden",
+    health_detail_charge_set: "{} hört bei {} % auf zu laden",
+    health_detail_charge_full: "{} unterstützt eine Ladegrenze, lädt aber bis 100 %",
+    health_detail_charge_unsupported: "{} hat keine Ladegrenze (von Firmware oder Kernel-Treiber nicht angeboten)",
+    health_fix_charge: "Ladegrenze in der Konfiguration setzen",
+    health_name_optimise: "Store-Optimierung",
+    health_desc_optimise: "Identische Dateien im Store werden hart verlinkt statt doppelt gespeichert",
+    health_detail_optimise_on: "Deduplizierung aktiv",
+    health_detail_optimise_off: "auto-optimise-store ist aus, kein nix-optimise-Timer",
+    health_fix_optimise: "In der Konfiguration aktivieren — spart oft 20–40%",
     health_desc_duplicates: "Mehrere Versionen desselben Pakets",
     health_fix_duplicates: "Konfiguration auf doppelte Einträge prüfen",
     health_detail_dupes_ok: "{} Pakete, keine Duplikate",
@@ -1629,6 +4325,21 @@ static DE: Strings = Strings {
     health_detail_dupes_crit: "{} doppelte Paketnamen!",
     health_applying_fix: "Fix wird angewendet...",
     health_fix_error_detail: "Fix fehlgeschlagen: {}",
+    health_name_accounts: "Benutzerkonten",
+    health_desc_accounts: "Benutzer, Gruppen und SSH-Keys entsprechen der deklarativen Config",
+    health_fix_accounts: "Deklarieren — Snippets im Konten-Tab",
+    health_detail_accounts_ok: "{} Konten, alle deklarativ",
+    health_detail_accounts_drift: "{} imperative Benutzer, {} Gruppenänderungen, {} zusätzliche SSH-Keys",
+    health_acc_imperative_user: "uid {} — nicht in deiner Konfiguration",
+    health_acc_imperative_group: "gid {} — nicht in deiner Konfiguration",
+    health_acc_group_drift: "nur imperativ Mitglied von {}",
+    health_acc_extra_key: "Key in {} nicht deklariert",
+    health_acc_mutable: "imperative Änderungen überleben Rebuilds",
+    health_acc_unreadable: "authorized_keys nicht lesbar — als root starten für vollständiges Audit",
+    health_acc_no_source: "Deklarative Benutzer-Config nicht gefunden (ist das NixOS?)",
+    health_acc_summary: "{} Konten · {} SSH-Keys geprüft ·",
+    health_acc_clean: "Keine Abweichung — Konten und Keys entsprechen deiner Konfiguration",
+    health_acc_remediation: "Behebung",
 
     // Flake Inputs (additional i18n)
     fi_error_load_failed: "Flake-Inputs konnten nicht geladen werden.",
@@ -1645,6 +4356,13 @@ static DE: Strings = Strings {
     // Generations (additional i18n)
     gen_restore_msg: "{}-Generation #{} wiederherstellen?\nDatum: {}\nVersion: {}",
     gen_delete_msg: "{} Generation(en) löschen?\nIDs: {}",
+    gen_gc_calculating: "Ermittle, was das wirklich freigibt…",
+    gen_gc_unknown: "Kann nicht ermitteln, was das freigibt: {}",
+    gen_gc_frees: "Gibt nach der Garbage Collection {} frei",
+    gen_gc_only_in: "  #{}: {} nur von ihr genutzt",
+    gen_gc_kept: "{} bleiben, weiter genutzt vom aktuellen System, anderen Generationen oder GC-Roots",
+    job_gen_gc_report: "GC-Bericht Generationen",
+    job_gen_reclaim: "Freiwerdender Platz je Generation",
 
     // Packages (additional i18n)
     pkg_searching_for: "Suche nach \"{}\"...",
@@ -1656,6 +4374,9 @@ static DE: Strings = Strings {
     opt_read_failed: "Wert konnte nicht gelesen werden",
     opt_phase_prebuilt: "Suche vorbereitete Optionen...",
     opt_phase_fallback: "Verwende nixos-option als Fallback (kann etwas dauern)...",
+    opt_phase_cache: "Prüfe zwischengespeicherten Options-Index...",
+    opt_phase_flake_parts: "Optionen der flake-parts-Module werden ausgewertet...",
+    opt_flake_module: "Option eines flake-parts-Moduls (in flake.nix gesetzt, nicht in der Systemkonfiguration)",
     opt_load_error: "NixOS-Optionen konnten nicht geladen werden. Stelle sicher, dass du auf einem NixOS-System bist und documentation.nixos.enable = true (Standard) gesetzt ist.",
     opt_not_found: "nixos-option nicht gefunden: {}",
 
@@ -1678,6 +4399,26 @@ mod tests {
         assert!(!s.gen_confirm_delete.is_empty());
     }
     #[test]
+    fn test_locale_overrides_fall_back_per_key() {
+        let overrides: toml::Table =
+            toml::from_str("quit = \"Quitter\"\napp_title = \"nixmate\"\nnot_a_key = \"x\"")
+                .unwrap();
+        let (strings, missing, unknown) = with_overrides(&EN, &overrides).unwrap();
+        assert_eq!(strings.quit, "Quitter");
+        assert_eq!(strings.rb_phase_done, EN.rb_phase_done);
+        assert_eq!(missing, key_count() - 2);
+        assert_eq!(unknown, vec!["not_a_key".to_string()]);
+    }
+    #[test]
+    fn test_bundled_locales_parse() {
+        for (code, text) in BUNDLED {
+            let locale = parse_locale(code.to_string(), text, true).unwrap();
+            assert!(locale.unknown.is_empty(), "{}: {:?}", code, locale.unknown);
+            assert!(locale.missing < key_count());
+            assert_ne!(locale.strings.quit, EN.quit);
+        }
+    }
+    #[test]
     fn test_german_strings_no_empty() {
         let s = get_strings(crate::config::Language::German);
         assert!(!s.app_title.is_empty());
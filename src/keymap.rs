@@ -0,0 +1,303 @@
+//! User keybindings (`[keys]` in config.toml)
+//!
+//! Modules match on fixed keys (`j`, `Enter`, `]`, …). Instead of teaching
+//! every module about remapping, `App` runs each key through this layer
+//! first: a key the user bound to an action becomes that action's
+//! canonical key, so modules keep seeing what they always did.
+//!
+//! Bindings for an action replace its defaults. A default key that is no
+//! longer bound to anything is swallowed, so `down = ["n"]` really frees
+//! `j`. Without a `[keys]` table nothing is translated at all.
+//!
+//! Text fields, module popups and the command palette are not remapped —
+//! there a key is just a key.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::BTreeMap;
+
+/// A bindable action: config name, key modules expect, default keys
+pub struct Action {
+    pub name: &'static str,
+    canonical: (KeyCode, bool),
+    defaults: &'static [&'static str],
+}
+
+const fn action(
+    name: &'static str,
+    canonical: KeyCode,
+    defaults: &'static [&'static str],
+) -> Action {
+    Action {
+        name,
+        canonical: (canonical, false),
+        defaults,
+    }
+}
+
+pub const ACTIONS: &[Action] = &[
+    action("up", KeyCode::Up, &["k", "Up"]),
+    action("down", KeyCode::Down, &["j", "Down"]),
+    action("left", KeyCode::Left, &["h", "Left"]),
+    action("right", KeyCode::Right, &["l", "Right"]),
+    action("top", KeyCode::Char('g'), &["g"]),
+    action("bottom", KeyCode::Char('G'), &["G"]),
+    action("select", KeyCode::Enter, &["Enter"]),
+    action("back", KeyCode::Esc, &["Esc"]),
+    action("search", KeyCode::Char('/'), &["/"]),
+    action("refresh", KeyCode::Char('r'), &["r"]),
+    action("prev_subtab", KeyCode::Char('['), &["["]),
+    action("next_subtab", KeyCode::Char(']'), &["]"]),
+    Action {
+        name: "palette",
+        canonical: (KeyCode::Char('p'), true),
+        defaults: &["Ctrl+p"],
+    },
+    action("quit", KeyCode::Char('q'), &["q"]),
+    action("jobs", KeyCode::Char('J'), &["J"]),
+    action("generations", KeyCode::Char('1'), &["1"]),
+    action("errors", KeyCode::Char('2'), &["2"]),
+    action("services", KeyCode::Char('3'), &["3"]),
+    action("storage", KeyCode::Char('4'), &["4"]),
+    action("config", KeyCode::Char('5'), &["5"]),
+    action("options", KeyCode::Char('6'), &["6"]),
+    action("rebuild", KeyCode::Char('7'), &["7"]),
+    action("flake_inputs", KeyCode::Char('8'), &["8"]),
+    action("packages", KeyCode::Char('9'), &["9"]),
+    action("doctor", KeyCode::Char('0'), &["0"]),
+    action("settings", KeyCode::Char(','), &[","]),
+    action("help", KeyCode::Char('?'), &["?"]),
+];
+
+/// Key + whether Ctrl is held (Shift is already in the char)
+type Key = (KeyCode, bool);
+
+/// Problems with the `[keys]` table, shown in Settings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeymapIssue {
+    UnknownAction(String),
+    BadKey {
+        action: String,
+        key: String,
+    },
+    /// Same key bound to several actions; a customized one wins over
+    /// defaults, otherwise the first
+    Conflict {
+        key: String,
+        actions: Vec<String>,
+    },
+}
+
+/// Effective bindings of one action
+pub struct Binding {
+    pub action: &'static Action,
+    pub keys: Vec<String>,
+    pub custom: bool,
+}
+
+#[derive(Default)]
+pub struct Keymap {
+    /// Keys of customized actions → canonical key
+    remap: Vec<(Key, Key)>,
+    /// Defaults of customized actions that nothing is bound to anymore
+    swallowed: Vec<Key>,
+    pub bindings: Vec<Binding>,
+    pub issues: Vec<KeymapIssue>,
+}
+
+impl Keymap {
+    pub fn from_config(keys: &BTreeMap<String, Vec<String>>) -> Self {
+        let mut issues = Vec::new();
+        for name in keys.keys() {
+            if !ACTIONS.iter().any(|a| a.name == name) {
+                issues.push(KeymapIssue::UnknownAction(name.clone()));
+            }
+        }
+
+        let mut bindings = Vec::new();
+        let mut bound: Vec<(Key, &'static Action)> = Vec::new();
+        for a in ACTIONS {
+            let (list, custom) = match keys.get(a.name) {
+                Some(list) => (list.clone(), true),
+                None => (a.defaults.iter().map(|k| k.to_string()).collect(), false),
+            };
+            for k in &list {
+                match parse_key(k) {
+                    Some(key) => bound.push((key, a)),
+                    None => issues.push(KeymapIssue::BadKey {
+                        action: a.name.to_string(),
+                        key: k.clone(),
+                    }),
+                }
+            }
+            bindings.push(Binding {
+                action: a,
+                keys: list,
+                custom,
+            });
+        }
+
+        // Conflicts, in the order the keys first appear
+        let mut seen: Vec<Key> = Vec::new();
+        for (key, _) in &bound {
+            if seen.contains(key) {
+                continue;
+            }
+            seen.push(*key);
+            let mut actions: Vec<String> = bound
+                .iter()
+                .filter(|(k, _)| k == key)
+                .map(|(_, a)| a.name.to_string())
+                .collect();
+            actions.dedup();
+            if actions.len() > 1 {
+                issues.push(KeymapIssue::Conflict {
+                    key: format_key(*key),
+                    actions,
+                });
+            }
+        }
+
+        // A key's owner: the first customized action binding it, else the
+        // first default. Keys owned by untouched actions pass through as-is.
+        let customized = |a: &Action| keys.contains_key(a.name);
+        let mut remap: Vec<(Key, Key)> = Vec::new();
+        for (key, a) in bound.iter().filter(|(_, a)| customized(a)) {
+            if !remap.iter().any(|(k, _)| k == key) {
+                remap.push((*key, a.canonical));
+            }
+        }
+        let swallowed = ACTIONS
+            .iter()
+            .filter(|a| customized(a))
+            .flat_map(|a| a.defaults.iter().filter_map(|k| parse_key(k)))
+            .filter(|key| !bound.iter().any(|(k, _)| k == key))
+            .collect();
+
+        Self {
+            remap,
+            swallowed,
+            bindings,
+            issues,
+        }
+    }
+
+    /// The key modules should see, None if it's an unbound default
+    pub fn translate(&self, key: KeyEvent) -> Option<KeyEvent> {
+        let pressed = (key.code, key.modifiers.contains(KeyModifiers::CONTROL));
+        if let Some((_, (code, ctrl))) = self.remap.iter().find(|(k, _)| *k == pressed) {
+            let modifiers = if *ctrl {
+                KeyModifiers::CONTROL
+            } else {
+                KeyModifiers::NONE
+            };
+            return Some(KeyEvent::new(*code, modifiers));
+        }
+        if self.swallowed.contains(&pressed) {
+            return None;
+        }
+        Some(key)
+    }
+}
+
+/// "j", "G", "Enter", "Space", "PageDown", "F5", "Ctrl+p", …
+fn parse_key(s: &str) -> Option<Key> {
+    let s = s.trim();
+    let (ctrl, name) = match s
+        .strip_prefix("Ctrl+")
+        .or_else(|| s.strip_prefix("ctrl+"))
+        .or_else(|| s.strip_prefix("C-"))
+    {
+        Some(rest) if !rest.is_empty() => (true, rest),
+        _ => (false, s),
+    };
+    if name.chars().count() == 1 {
+        return Some((KeyCode::Char(name.chars().next()?), ctrl));
+    }
+    let code = match name.to_lowercase().as_str() {
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "enter" | "return" => KeyCode::Enter,
+        "esc" | "escape" => KeyCode::Esc,
+        "tab" => KeyCode::Tab,
+        "backtab" => KeyCode::BackTab,
+        "space" => KeyCode::Char(' '),
+        "backspace" => KeyCode::Backspace,
+        "delete" | "del" => KeyCode::Delete,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        f => KeyCode::F(f.strip_prefix('f')?.parse().ok()?),
+    };
+    Some((code, ctrl))
+}
+
+fn format_key((code, ctrl): Key) -> String {
+    let name = match code {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::F(n) => format!("F{}", n),
+        other => format!("{:?}", other),
+    };
+    if ctrl {
+        format!("Ctrl+{}", name)
+    } else {
+        name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(c: char) -> Option<KeyCode> {
+        let keymap = Keymap::from_config(&BTreeMap::from([
+            (
+                "down".to_string(),
+                vec!["n".to_string(), "Down".to_string()],
+            ),
+            ("up".to_string(), vec!["e".to_string()]),
+        ]));
+        keymap
+            .translate(KeyEvent::from(KeyCode::Char(c)))
+            .map(|k| k.code)
+    }
+
+    #[test]
+    fn test_remap_replaces_defaults() {
+        assert_eq!(press('n'), Some(KeyCode::Down));
+        assert_eq!(press('e'), Some(KeyCode::Up));
+        // Freed defaults are swallowed, untouched keys pass through
+        assert_eq!(press('j'), None);
+        assert_eq!(press('k'), None);
+        assert_eq!(press('l'), Some(KeyCode::Char('l')));
+
+        // No [keys] table: nothing changes
+        let keymap = Keymap::from_config(&BTreeMap::new());
+        let key = KeyEvent::from(KeyCode::Char('j'));
+        assert_eq!(keymap.translate(key), Some(key));
+        assert!(keymap.issues.is_empty());
+    }
+
+    #[test]
+    fn test_keymap_issues() {
+        let keymap = Keymap::from_config(&BTreeMap::from([
+            ("down".to_string(), vec!["q".to_string()]),
+            ("jump".to_string(), vec!["x".to_string()]),
+            ("top".to_string(), vec!["Hyper+x".to_string()]),
+        ]));
+        assert!(keymap
+            .issues
+            .contains(&KeymapIssue::UnknownAction("jump".to_string())));
+        assert!(keymap
+            .issues
+            .iter()
+            .any(|i| matches!(i, KeymapIssue::BadKey { action, .. } if action == "top")));
+        assert!(keymap.issues.contains(&KeymapIssue::Conflict {
+            key: "q".to_string(),
+            actions: vec!["down".to_string(), "quit".to_string()],
+        }));
+    }
+}
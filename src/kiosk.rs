@@ -0,0 +1,171 @@
+//! Dashboard (kiosk) mode for wall-mounted monitors
+//!
+//! `nixmate --dashboard services,storage --rotate 30s [--quit-after 8h]`
+//! cycles the overview screens of the listed modules. Keys are limited to
+//! pause, manual switching and quit, so nothing destructive can be
+//! triggered from an unattended terminal.
+
+use crate::ui::ModuleTab;
+use anyhow::{bail, Context, Result};
+use std::time::{Duration, Instant};
+
+const DEFAULT_ROTATE: Duration = Duration::from_secs(30);
+
+/// `--dashboard` module names
+const MODULE_NAMES: &[(&str, ModuleTab)] = &[
+    ("generations", ModuleTab::Generations),
+    ("errors", ModuleTab::Errors),
+    ("services", ModuleTab::Services),
+    ("storage", ModuleTab::Storage),
+    ("config", ModuleTab::Config),
+    ("options", ModuleTab::Options),
+    ("rebuild", ModuleTab::Rebuild),
+    ("flake-inputs", ModuleTab::FlakeInputs),
+    ("packages", ModuleTab::Packages),
+    ("doctor", ModuleTab::Health),
+];
+
+pub struct Kiosk {
+    pub tabs: Vec<ModuleTab>,
+    pub rotate: Duration,
+    pub quit_after: Option<Duration>,
+    pub paused: bool,
+    current: usize,
+    started: Instant,
+    switched_at: Instant,
+}
+
+impl Kiosk {
+    /// Parse `--dashboard`, `--rotate` and `--quit-after` from the command
+    /// line. None if `--dashboard` isn't given.
+    pub fn from_args(args: &[String]) -> Result<Option<Self>> {
+        let value = |flag: &str| -> Result<Option<&String>> {
+            match args.iter().position(|a| a == flag) {
+                Some(i) => args
+                    .get(i + 1)
+                    .filter(|v| !v.starts_with("--"))
+                    .map(Some)
+                    .with_context(|| format!("{} needs a value", flag)),
+                None => Ok(None),
+            }
+        };
+
+        let Some(modules) = value("--dashboard")? else {
+            if args.iter().any(|a| a == "--rotate" || a == "--quit-after") {
+                bail!("--rotate and --quit-after only work with --dashboard");
+            }
+            return Ok(None);
+        };
+
+        let mut tabs = Vec::new();
+        for name in modules.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+            let Some((_, tab)) = MODULE_NAMES.iter().find(|(n, _)| *n == name) else {
+                let known: Vec<&str> = MODULE_NAMES.iter().map(|(n, _)| *n).collect();
+                bail!("unknown module `{}` (one of: {})", name, known.join(", "));
+            };
+            tabs.push(*tab);
+        }
+        if tabs.is_empty() {
+            bail!("--dashboard needs at least one module");
+        }
+
+        let rotate = match value("--rotate")? {
+            Some(v) => parse_duration(v).with_context(|| format!("invalid --rotate: {}", v))?,
+            None => DEFAULT_ROTATE,
+        };
+        let quit_after = value("--quit-after")?
+            .map(|v| parse_duration(v).with_context(|| format!("invalid --quit-after: {}", v)))
+            .transpose()?;
+
+        let now = Instant::now();
+        Ok(Some(Self {
+            tabs,
+            rotate,
+            quit_after,
+            paused: false,
+            current: 0,
+            started: now,
+            switched_at: now,
+        }))
+    }
+
+    pub fn current_tab(&self) -> ModuleTab {
+        self.tabs[self.current]
+    }
+
+    /// Step forward (or back) through the tabs, restarting the timer
+    pub fn step(&mut self, forward: bool) -> ModuleTab {
+        let len = self.tabs.len();
+        self.current = if forward {
+            (self.current + 1) % len
+        } else {
+            (self.current + len - 1) % len
+        };
+        self.switched_at = Instant::now();
+        self.current_tab()
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+        self.switched_at = Instant::now();
+    }
+
+    /// Whether the current screen has been shown long enough
+    pub fn rotation_due(&self) -> bool {
+        !self.paused && self.tabs.len() > 1 && self.switched_at.elapsed() >= self.rotate
+    }
+
+    /// Seconds until the next screen
+    pub fn remaining_secs(&self) -> u64 {
+        self.rotate
+            .saturating_sub(self.switched_at.elapsed())
+            .as_secs()
+    }
+
+    pub fn should_quit(&self) -> bool {
+        self.quit_after
+            .is_some_and(|limit| self.started.elapsed() >= limit)
+    }
+}
+
+/// "30s", "5m", "2h", "1d" or plain seconds
+fn parse_duration(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    let (num, unit) = match value.find(|c: char| !c.is_ascii_digit()) {
+        Some(i) => value.split_at(i),
+        None => (value, "s"),
+    };
+    let n: u64 = num.parse().ok()?;
+    let secs = match unit {
+        "s" => n,
+        "m" => n * 60,
+        "h" => n * 3600,
+        "d" => n * 86400,
+        _ => return None,
+    };
+    (secs > 0).then(|| Duration::from_secs(secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_dashboard_args() {
+        let kiosk = Kiosk::from_args(&args("--dashboard services,storage --rotate 2m"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(kiosk.tabs, vec![ModuleTab::Services, ModuleTab::Storage]);
+        assert_eq!(kiosk.rotate, Duration::from_secs(120));
+        assert_eq!(kiosk.quit_after, None);
+
+        assert!(Kiosk::from_args(&args("")).unwrap().is_none());
+        assert!(Kiosk::from_args(&args("--dashboard nope")).is_err());
+        assert!(Kiosk::from_args(&args("--dashboard services --rotate 0s")).is_err());
+        assert!(Kiosk::from_args(&args("--rotate 30s")).is_err());
+    }
+}
@@ -8,26 +8,51 @@
 //! - And more to come
 //!
 //! Usage: nixmate [--help] [--version]
+//! Kiosk: nixmate --dashboard services,storage --rotate 30s
 //! Pipe:  nixos-rebuild switch 2>&1 | nixmate
 
 mod app;
+mod cli;
+mod clipboard;
 mod config;
 mod i18n;
+mod keymap;
+mod kiosk;
 mod modules;
 mod nix;
+mod pipe;
+mod session;
+mod staged;
 mod types;
 mod ui;
 
 use anyhow::{Context, Result};
 use app::App;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyEventKind},
+    event::{
+        self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+        Event, KeyEventKind,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::prelude::*;
 use std::io::{self, stdout, IsTerminal, Read, Write};
-use std::time::Duration;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use types::PipedInput;
+
+/// Set by the SIGHUP handler; the main loop notices and shuts down
+static HANGUP: AtomicBool = AtomicBool::new(false);
+
+/// Idle screens are still redrawn this often (clocks, elapsed times,
+/// flash expiry, the undo countdown)
+const IDLE_REDRAW: Duration = Duration::from_secs(1);
+
+#[cfg(unix)]
+extern "C" fn on_sighup(_: libc::c_int) {
+    HANGUP.store(true, Ordering::SeqCst);
+}
 
 fn main() -> Result<()> {
     let args: Vec<String> = std::env::args().collect();
@@ -42,8 +67,42 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
-    // Check for piped input BEFORE starting TUI
-    let piped_input = read_piped_input();
+    // Headless subcommands never start the TUI
+    if let Some(result) = cli::run(&args[1..]) {
+        match result {
+            Ok(code) => std::process::exit(code),
+            Err(e) => {
+                eprintln!("Error: {:#}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let kiosk = match kiosk::Kiosk::from_args(&args[1..]) {
+        Ok(kiosk) => kiosk,
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let pipe_kind = match pipe::PipeKind::from_args(&args[1..]) {
+        Ok(kind) => kind,
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            std::process::exit(1);
+        }
+    };
+
+    // Check for piped input BEFORE starting TUI (not in dashboard mode)
+    let piped_input = if kiosk.is_none() {
+        read_piped_input().map(|input| PipedInput {
+            kind: pipe_kind,
+            ..input
+        })
+    } else {
+        None
+    };
 
     // If stdin was a pipe, reattach to /dev/tty so crossterm can read key events
     if piped_input.is_some() {
@@ -51,7 +110,7 @@ fn main() -> Result<()> {
             .context("Failed to reattach stdin to terminal. Are you running in a TTY?")?;
     }
 
-    let result = run_app(piped_input);
+    let result = run_app(piped_input, kiosk);
 
     if let Err(e) = result {
         eprintln!("Error: {:#}", e);
@@ -64,11 +123,14 @@ fn main() -> Result<()> {
 /// Read all of stdin if it's a pipe (not a terminal).
 /// Returns None if stdin is a terminal (normal interactive mode).
 /// Limits input to 1 MB to prevent excessive memory usage.
-fn read_piped_input() -> Option<String> {
+fn read_piped_input() -> Option<PipedInput> {
     if io::stdin().is_terminal() {
         return None;
     }
 
+    // Look up the writer now, it may exit once we've read everything
+    let source = pipe_writer_command();
+
     const MAX_PIPE_SIZE: usize = 1024 * 1024; // 1 MB — more than enough for any build log
 
     let mut input = String::new();
@@ -85,7 +147,51 @@ fn read_piped_input() -> Option<String> {
         return None;
     }
 
-    Some(trimmed.to_string())
+    Some(PipedInput {
+        text: trimmed.to_string(),
+        source,
+        kind: None,
+    })
+}
+
+/// Command line of the process writing into our stdin pipe: the one whose
+/// stdout or stderr is the same pipe. Processes of other users (sudo) can't
+/// be inspected, so this is best effort.
+#[cfg(target_os = "linux")]
+fn pipe_writer_command() -> Option<String> {
+    let pipe = std::fs::read_link("/proc/self/fd/0").ok()?;
+    if !pipe.to_string_lossy().starts_with("pipe:") {
+        return None;
+    }
+    let me = std::process::id().to_string();
+
+    std::fs::read_dir("/proc")
+        .ok()?
+        .flatten()
+        .filter(|e| {
+            e.file_name()
+                .to_str()
+                .is_some_and(|n| n.parse::<u32>().is_ok() && n != me)
+        })
+        .filter(|e| {
+            ["1", "2"]
+                .iter()
+                .any(|fd| std::fs::read_link(e.path().join("fd").join(fd)).is_ok_and(|l| l == pipe))
+        })
+        .find_map(|e| {
+            let cmdline = std::fs::read(e.path().join("cmdline")).ok()?;
+            let args: Vec<String> = cmdline
+                .split(|b| *b == 0)
+                .filter(|a| !a.is_empty())
+                .map(|a| String::from_utf8_lossy(a).to_string())
+                .collect();
+            (!args.is_empty()).then(|| args.join(" "))
+        })
+}
+
+#[cfg(not(target_os = "linux"))]
+fn pipe_writer_command() -> Option<String> {
+    None
 }
 
 /// After reading piped stdin, reopen /dev/tty as fd 0 so crossterm
@@ -131,12 +237,40 @@ Made with ♥ by daskladas
 
 USAGE:
     nixmate [OPTIONS]
-    nixos-rebuild switch 2>&1 | nixmate     # pipe errors directly
+    nixmate <COMMAND> [--plain] [--json]
+    nixmate --dashboard <MODULES> [--rotate 30s] [--quit-after 8h]
+    nixos-rebuild switch 2>&1 | nixmate     # pipe a log in (see PIPE MODE)
 
 OPTIONS:
     -h, --help       Print help information
     -v, --version    Print version information
 
+PIPE MODE:
+    Piped text opens in the module that fits it: nixos-rebuild runs in the
+    Rebuild log, nix flake update output in Flake Inputs history, nix build
+    logs, journalctl dumps and anything else in the Error Translator.
+    --as             Skip detection: rebuild, build, flake, journal, errors
+
+DASHBOARD MODE (read-only, for wall-mounted monitors):
+    --dashboard      Comma-separated modules to cycle: generations, errors,
+                     services, storage, config, options, rebuild,
+                     flake-inputs, packages, doctor
+    --rotate         Time per module (30s, 5m, …; default 30s)
+    --quit-after     Exit after this long (e.g. 8h)
+    Only Space (pause), ←/→ (switch) and q (quit) are accepted.
+
+COMMANDS (headless, no TUI):
+    generations      List system and home-manager generations
+    doctor           Run the Nix Doctor health checks
+      --check        Exit with status 1 if any check fails (systemd timers, CI)
+    locales          List locale files and how much of each is translated
+      --template     Print all strings in English as a starting point
+    export <KIND>    Print a module's data as JSON: generations, services,
+                     ports, flake-inputs, storage
+
+    --plain          No colors or symbols (also: NO_COLOR, output not a terminal)
+    --json           Machine-readable JSON with stable field names
+
 KEYBINDINGS:
     1-9,0            Switch modules
     j/k              Navigate up/down
@@ -158,29 +292,33 @@ MODULES:
     [,] Settings          Theme, language, layout
     [?] Help / About      What nixmate does
 
-PIPE MODE:
-    Pipe build output into nixmate to auto-analyze errors:
-      nixos-rebuild switch 2>&1 | nixmate
-      nix build .#foo 2>&1 | nixmate
-
 CONFIG:
     ~/.config/nixmate/config.toml
 "#
     );
 }
 
-fn run_app(piped_input: Option<String>) -> Result<()> {
+fn run_app(piped_input: Option<PipedInput>, kiosk: Option<kiosk::Kiosk>) -> Result<()> {
     // Load configuration
     let config = config::Config::load().context("Failed to load configuration")?;
 
     // Create application state (with optional piped input)
     let mut app = App::new(config, piped_input).context("Failed to initialize application")?;
+    match kiosk {
+        Some(kiosk) => app.start_kiosk(kiosk),
+        None => app.restore_session(),
+    }
 
     // Setup terminal
     enable_raw_mode().context("Failed to enable raw mode")?;
     let mut stdout = stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)
-        .context("Failed to setup terminal")?;
+    execute!(
+        stdout,
+        EnterAlternateScreen,
+        EnableMouseCapture,
+        EnableBracketedPaste
+    )
+    .context("Failed to setup terminal")?;
 
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend).context("Failed to create terminal")?;
@@ -197,14 +335,36 @@ fn run_app(piped_input: Option<String>) -> Result<()> {
             let _ = std::io::stdout().flush();
         }
         let _ = disable_raw_mode();
-        let _ = execute!(std::io::stdout(), LeaveAlternateScreen, DisableMouseCapture);
+        let _ = execute!(
+            std::io::stdout(),
+            LeaveAlternateScreen,
+            DisableMouseCapture,
+            DisableBracketedPaste
+        );
         let _ = execute!(std::io::stdout(), crossterm::cursor::Show);
         original_hook(info);
     }));
 
+    // A closed terminal window must not silently kill a running rebuild:
+    // catch SIGHUP so the main loop can detach it first
+    #[cfg(unix)]
+    unsafe {
+        libc::signal(
+            libc::SIGHUP,
+            on_sighup as extern "C" fn(libc::c_int) as libc::sighandler_t,
+        );
+    }
+
     // Run main loop
     let result = main_loop(&mut terminal, &mut app);
 
+    // Drawing fails once the terminal is gone, so the loop may have
+    // bailed out with an error before seeing the flag
+    if HANGUP.load(Ordering::SeqCst) {
+        app.handle_hangup();
+        return Ok(());
+    }
+
     // IMPORTANT: Clean up terminal images BEFORE leaving the alternate screen.
     // The Kitty Graphics Protocol stores images in the terminal's GPU memory.
     // We must send the delete command while still in the alternate screen,
@@ -224,7 +384,8 @@ fn run_app(piped_input: Option<String>) -> Result<()> {
     execute!(
         terminal.backend_mut(),
         LeaveAlternateScreen,
-        DisableMouseCapture
+        DisableMouseCapture,
+        DisableBracketedPaste
     )
     .context("Failed to restore terminal")?;
     terminal.show_cursor().context("Failed to show cursor")?;
@@ -233,36 +394,123 @@ fn run_app(piped_input: Option<String>) -> Result<()> {
 }
 
 fn main_loop<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Result<()> {
+    // Redraw after input, while something animates (and once more after it
+    // stopped, for the final state) and otherwise only every IDLE_REDRAW
+    let mut dirty = true;
+    let mut last_draw = Instant::now();
+    let size = terminal.size().context("Failed to read terminal size")?;
+    app.handle_resize(size.width, size.height);
     loop {
-        terminal.draw(|frame| {
-            ui::render(frame, app);
-        })?;
+        if HANGUP.load(Ordering::SeqCst) {
+            break;
+        }
 
-        // Display terminal images AFTER ratatui has flushed its frame buffer.
-        // This uses native protocols (Kitty/iTerm2) to overlay the real PNG
-        // on top of the blank area reserved by the render functions.
-        app.handle_image()?;
+        let animating = app.animating();
+        if dirty || animating || last_draw.elapsed() >= IDLE_REDRAW {
+            terminal.draw(|frame| {
+                ui::render(frame, app);
+            })?;
+
+            // Display terminal images AFTER ratatui has flushed its frame buffer.
+            // This uses native protocols (Kitty/iTerm2) to overlay the real PNG
+            // on top of the blank area reserved by the render functions.
+            app.handle_image()?;
+            last_draw = Instant::now();
+        }
+        dirty = animating;
 
         // Update module timers (undo countdown etc.)
         app.update_timers()?;
 
         // Poll for events with timeout (for flash message expiry etc.)
-        if event::poll(Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
-                if key.kind == KeyEventKind::Press {
-                    app.handle_key(key)?;
-                }
+        if event::poll(app.poll_interval())? {
+            match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => app.handle_key(key)?,
+                Event::Paste(text) => app.handle_paste(&text)?,
+                Event::Resize(width, height) => app.handle_resize(width, height),
+                _ => {}
             }
+            dirty = true;
         }
 
         if app.should_quit {
+            if app.show_flush_progress() {
+                terminal.draw(|frame| {
+                    ui::render(frame, app);
+                })?;
+            }
+            app.flush_staged();
+            app.save_session();
             break;
         }
+
+        if let Some(cmd) = app.take_external_command() {
+            let success = run_external(terminal, app, &cmd)?;
+            app.external_finished(success);
+            dirty = true;
+        }
     }
 
     Ok(())
 }
 
+/// Suspend the TUI, run an interactive program in the foreground and
+/// restore the TUI afterwards. Returns whether the program exited successfully.
+fn run_external<B: Backend>(
+    terminal: &mut Terminal<B>,
+    app: &mut App,
+    cmd: &types::ExternalCommand,
+) -> Result<bool> {
+    // Same order as on exit: images first, while still in the alternate screen
+    app.cleanup_images();
+    disable_raw_mode().context("Failed to disable raw mode")?;
+    execute!(
+        stdout(),
+        LeaveAlternateScreen,
+        DisableMouseCapture,
+        DisableBracketedPaste
+    )
+    .context("Failed to restore terminal")?;
+    terminal.show_cursor().context("Failed to show cursor")?;
+
+    println!("nixmate: {}", cmd.notice);
+    println!("$ {}\n", cmd.display());
+
+    let mut command = std::process::Command::new(&cmd.program);
+    command.args(&cmd.args);
+    if let Some(dir) = &cmd.cwd {
+        command.current_dir(dir);
+    }
+    // Ctrl+C inside the program reaches our process group too — ignore it
+    // here while the child runs (with default handling restored in the child)
+    #[cfg(unix)]
+    unsafe {
+        use std::os::unix::process::CommandExt;
+        command.pre_exec(|| {
+            libc::signal(libc::SIGINT, libc::SIG_DFL);
+            Ok(())
+        });
+        libc::signal(libc::SIGINT, libc::SIG_IGN);
+    }
+    let success = command.status().map(|s| s.success()).unwrap_or(false);
+    #[cfg(unix)]
+    unsafe {
+        libc::signal(libc::SIGINT, libc::SIG_DFL);
+    }
+
+    enable_raw_mode().context("Failed to enable raw mode")?;
+    execute!(
+        stdout(),
+        EnterAlternateScreen,
+        EnableMouseCapture,
+        EnableBracketedPaste
+    )
+    .context("Failed to setup terminal")?;
+    terminal.clear().context("Failed to clear terminal")?;
+
+    Ok(success)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
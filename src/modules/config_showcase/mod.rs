@@ -1,16 +1,23 @@
 //! Config Showcase module — generate system overview posters and config diagrams.
 //!
-//! Two modes:
+//! Three modes:
 //!   F1 — Overview: beautiful system poster (existing)
 //!   F2 — Diagram:  architecture diagram of NixOS config structure
+//!   F3 — Services: what the evaluated config enables, grouped by option
+//!        root with systemd dependencies (Enter opens the Options Explorer)
 //!
-//! Both export as SVG for GitHub READMEs, r/unixporn, or anywhere else.
+//! Both export as SVG for GitHub READMEs, r/unixporn, or anywhere else;
+//! the poster can also go to a chosen path as PNG (`e`).
 
 pub mod diagram;
 pub mod poster;
+pub mod service_map;
 
 use crate::config::Language;
 use crate::i18n;
+use crate::modules::jobs::Job;
+use crate::modules::module::{Module, RenderCtx};
+use crate::modules::palette::PaletteCommand;
 use crate::nix::sysinfo::{self, PosterInfo};
 use crate::types::FlashMessage;
 use crate::ui::theme::Theme;
@@ -33,17 +40,19 @@ pub enum CfgSubTab {
     #[default]
     Overview,
     Diagram,
+    Services,
 }
 
 impl CfgSubTab {
     pub fn all() -> &'static [CfgSubTab] {
-        &[CfgSubTab::Overview, CfgSubTab::Diagram]
+        &[CfgSubTab::Overview, CfgSubTab::Diagram, CfgSubTab::Services]
     }
 
     pub fn index(&self) -> usize {
         match self {
             CfgSubTab::Overview => 0,
             CfgSubTab::Diagram => 1,
+            CfgSubTab::Services => 2,
         }
     }
 
@@ -52,6 +61,7 @@ impl CfgSubTab {
         match self {
             CfgSubTab::Overview => s.cfg_overview,
             CfgSubTab::Diagram => s.cfg_diagram,
+            CfgSubTab::Services => s.cfg_services,
         }
     }
 
@@ -82,7 +92,11 @@ pub struct ConfigShowcaseState {
     pub scan_result: Option<PosterInfo>,
     pub export_path: Option<String>,
     pub export_error: Option<String>,
-    scan_rx: Option<mpsc::Receiver<PosterInfo>>,
+    /// Export path being typed after `e`
+    pub export_input: Option<String>,
+    /// Active nixmate theme, drawn as swatches on the poster
+    pub poster_theme: poster::PosterTheme,
+    scan_rx: Option<mpsc::Receiver<(PosterInfo, Result<String, String>)>>,
 
     // Diagram state
     pub diagram_scanning: bool,
@@ -91,9 +105,21 @@ pub struct ConfigShowcaseState {
     pub diagram_export_error: Option<String>,
     diagram_rx: Option<mpsc::Receiver<diagram::DiagramInfo>>,
 
+    // Services state (evaluated config)
+    pub map_loading: bool,
+    pub map: Option<Result<service_map::ServiceMap, String>>,
+    pub map_selected: usize,
+    /// Also show sets enabled by defaults or other modules
+    pub map_show_all: bool,
+    map_rx: Option<mpsc::Receiver<Result<service_map::ServiceMap, String>>>,
+    /// Option path to open in the Options Explorer (taken by App)
+    pub pending_option: Option<String>,
+
     // Common
     pub lang: Language,
     pub config_path: Option<String>,
+    /// `nixosConfigurations` attribute from the config (None = host name)
+    pub nixos_host: Option<String>,
     pub flash_message: Option<FlashMessage>,
 }
 
@@ -105,32 +131,114 @@ impl ConfigShowcaseState {
             scan_result: None,
             export_path: None,
             export_error: None,
+            export_input: None,
+            poster_theme: poster::PosterTheme::new(
+                crate::config::ThemeName::Gruvbox.as_str(),
+                &crate::ui::Theme::gruvbox(),
+            ),
             scan_rx: None,
             diagram_scanning: false,
             diagram_result: None,
             diagram_export_path: None,
             diagram_export_error: None,
             diagram_rx: None,
+            map_loading: false,
+            map: None,
+            map_selected: 0,
+            map_show_all: false,
+            map_rx: None,
+            pending_option: None,
             lang: Language::English,
             config_path: None,
+            nixos_host: None,
             flash_message: None,
         }
     }
 
+    /// Actions offered in the command palette
+    /// Running background work, for the jobs overlay
+    pub fn jobs(&self, s: &i18n::Strings) -> Vec<Job> {
+        let mut jobs = Vec::new();
+        if self.scanning {
+            jobs.push(Job::new("poster", s.job_cfg_poster, true));
+        }
+        if self.diagram_scanning {
+            jobs.push(Job::new("diagram", s.job_cfg_diagram, true));
+        }
+        if self.map_loading {
+            jobs.push(Job::new("map", s.job_cfg_map, true));
+        }
+        jobs
+    }
+
+    /// Stop waiting for a job from `jobs()`; its result is discarded
+    pub fn cancel_job(&mut self, id: &str) {
+        let s = i18n::get_strings(self.lang);
+        match id {
+            "poster" => {
+                self.scan_rx = None;
+                self.scanning = false;
+            }
+            "diagram" => {
+                self.diagram_rx = None;
+                self.diagram_scanning = false;
+            }
+            "map" => {
+                self.map_rx = None;
+                self.map_loading = false;
+                self.map = Some(Err(s.jobs_cancelled_error.to_string()));
+            }
+            _ => {}
+        }
+    }
+
+    pub fn palette_commands(&self, s: &i18n::Strings) -> Vec<PaletteCommand> {
+        let overview = Some(CfgSubTab::Overview.index());
+        vec![
+            PaletteCommand::new(s.pal_cfg_export, overview, vec![KeyCode::Enter]),
+            PaletteCommand::new(s.pal_cfg_export_path, overview, vec![KeyCode::Char('e')]),
+            PaletteCommand::new(s.pal_cfg_rescan, overview, vec![KeyCode::Char('r')]),
+            PaletteCommand::new(s.pal_cfg_diagram, Some(CfgSubTab::Diagram.index()), vec![]),
+            PaletteCommand::new(s.pal_cfg_map, Some(CfgSubTab::Services.index()), vec![]),
+        ]
+    }
+
     pub fn handle_key(&mut self, key: KeyEvent) -> Result<()> {
         // Sub-tab switching with [ / ]
         match key.code {
             KeyCode::Char('[') => {
                 self.active_sub_tab = self.active_sub_tab.prev();
+                self.ensure_map_loaded();
                 return Ok(());
             }
             KeyCode::Char(']') => {
                 self.active_sub_tab = self.active_sub_tab.next();
+                self.ensure_map_loaded();
                 return Ok(());
             }
             _ => {}
         }
 
+        // Export path input
+        if let Some(input) = &mut self.export_input {
+            match key.code {
+                KeyCode::Esc => self.export_input = None,
+                KeyCode::Enter => {
+                    let target = std::mem::take(input);
+                    self.export_input = None;
+                    if !target.trim().is_empty() {
+                        self.start_overview_scan(Some(target));
+                    }
+                }
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Char(c) => input.push(c),
+                _ => {}
+            }
+            return Ok(());
+        }
+
         // Tab-specific keys
         match self.active_sub_tab {
             CfgSubTab::Overview => {
@@ -139,7 +247,23 @@ impl ConfigShowcaseState {
                 }
                 match key.code {
                     KeyCode::Enter | KeyCode::Char('g') => {
-                        self.start_overview_scan();
+                        self.start_overview_scan(None);
+                    }
+                    KeyCode::Char('e') => {
+                        let hostname = match &self.scan_result {
+                            Some(info) => info.hostname.clone(),
+                            None => std::fs::read_to_string("/proc/sys/kernel/hostname")
+                                .map(|h| h.trim().to_string())
+                                .unwrap_or_else(|_| "nixos".into()),
+                        };
+                        let path = poster::default_path(&hostname, "png");
+                        let path = match dirs::home_dir()
+                            .and_then(|home| path.strip_prefix(home).ok().map(|p| p.to_owned()))
+                        {
+                            Some(rel) => format!("~/{}", rel.display()),
+                            None => path.display().to_string(),
+                        };
+                        self.export_input = Some(path);
                     }
                     _ => {}
                 }
@@ -155,11 +279,80 @@ impl ConfigShowcaseState {
                     _ => {}
                 }
             }
+            CfgSubTab::Services => self.handle_map_key(key),
         }
         Ok(())
     }
 
-    fn start_overview_scan(&mut self) {
+    fn handle_map_key(&mut self, key: KeyEvent) {
+        if self.map_loading {
+            return;
+        }
+        let nodes = self.visible_nodes();
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down if self.map_selected + 1 < nodes.len() => {
+                self.map_selected += 1;
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.map_selected = self.map_selected.saturating_sub(1);
+            }
+            KeyCode::Char('g') => self.map_selected = 0,
+            KeyCode::Char('G') => self.map_selected = nodes.len().saturating_sub(1),
+            KeyCode::Char('a') => {
+                // Keep the selected node selected across the filter change
+                let path = nodes.get(self.map_selected).map(|n| n.path.clone());
+                self.map_show_all = !self.map_show_all;
+                let nodes = self.visible_nodes();
+                self.map_selected = path
+                    .and_then(|p| nodes.iter().position(|n| n.path == p))
+                    .unwrap_or(0);
+            }
+            KeyCode::Char('r') => self.start_map_eval(),
+            KeyCode::Enter => {
+                if let Some(node) = nodes.get(self.map_selected) {
+                    self.pending_option = Some(format!("{}.enable", node.path));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Nodes of the Services tab in display order
+    fn visible_nodes(&self) -> Vec<&service_map::ServiceNode> {
+        match &self.map {
+            Some(Ok(map)) => map
+                .visible(self.map_show_all)
+                .into_iter()
+                .flat_map(|(_, nodes)| nodes)
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Evaluate the config on first visit of the Services tab
+    pub fn ensure_map_loaded(&mut self) {
+        if self.active_sub_tab == CfgSubTab::Services && self.map.is_none() {
+            self.start_map_eval();
+        }
+    }
+
+    fn start_map_eval(&mut self) {
+        if self.map_loading {
+            return;
+        }
+        self.map_loading = true;
+        let (tx, rx) = mpsc::channel();
+        self.map_rx = Some(rx);
+        let cp = self.config_path.clone();
+        let host = self.nixos_host.clone();
+        std::thread::spawn(move || {
+            let _ = tx.send(service_map::evaluate(cp.as_deref(), host.as_deref()));
+        });
+    }
+
+    /// Scan the system and export the poster — to `target` (SVG or PNG by
+    /// extension) or the default SVG path
+    fn start_overview_scan(&mut self, target: Option<String>) {
         if self.scanning {
             return;
         }
@@ -170,9 +363,14 @@ impl ConfigShowcaseState {
         let (tx, rx) = mpsc::channel();
         self.scan_rx = Some(rx);
 
+        let theme = self.poster_theme.clone();
         std::thread::spawn(move || {
             let info = sysinfo::gather();
-            let _ = tx.send(info);
+            let result = match &target {
+                Some(path) => poster::export(&info, &theme, path).map_err(|e| format!("{:#}", e)),
+                None => poster::save_svg(&info, &theme).map_err(|e| e.to_string()),
+            };
+            let _ = tx.send((info, result.map(|p| p.display().to_string())));
         });
     }
 
@@ -196,14 +394,45 @@ impl ConfigShowcaseState {
 
     /// Poll for scan completion. Call from update_timers.
     pub fn poll_scan(&mut self) {
+        // Poll service map evaluation
+        if let Some(ref rx) = self.map_rx {
+            match rx.try_recv() {
+                Ok(result) => {
+                    self.map = Some(result);
+                    self.map_loading = false;
+                    self.map_rx = None;
+                    self.map_selected = self
+                        .map_selected
+                        .min(self.visible_nodes().len().saturating_sub(1));
+                }
+                Err(mpsc::TryRecvError::Empty) => {}
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.map_loading = false;
+                    self.map_rx = None;
+                    self.map = Some(Err(crate::i18n::get_strings(self.lang)
+                        .thread_crashed
+                        .to_string()));
+                }
+            }
+        }
+
         // Poll overview scan
         if let Some(ref rx) = self.scan_rx {
             match rx.try_recv() {
-                Ok(info) => {
+                Ok((info, result)) => {
                     self.scan_result = Some(info);
                     self.scanning = false;
                     self.scan_rx = None;
-                    self.do_overview_export();
+                    match result {
+                        Ok(path) => {
+                            self.export_path = Some(path);
+                            self.export_error = None;
+                        }
+                        Err(e) => {
+                            self.export_error = Some(e);
+                            self.export_path = None;
+                        }
+                    }
                 }
                 Err(mpsc::TryRecvError::Empty) => {}
                 Err(mpsc::TryRecvError::Disconnected) => {
@@ -239,22 +468,6 @@ impl ConfigShowcaseState {
         }
     }
 
-    fn do_overview_export(&mut self) {
-        let Some(info) = &self.scan_result else {
-            return;
-        };
-        match poster::save_svg(info) {
-            Ok(path) => {
-                self.export_path = Some(path.display().to_string());
-                self.export_error = None;
-            }
-            Err(e) => {
-                self.export_error = Some(e.to_string());
-                self.export_path = None;
-            }
-        }
-    }
-
     fn do_diagram_export(&mut self) {
         let Some(info) = &self.diagram_result else {
             return;
@@ -276,6 +489,52 @@ impl ConfigShowcaseState {
 //  Rendering
 // ═══════════════════════════════════════
 
+impl Module for ConfigShowcaseState {
+    fn handle_key(&mut self, key: KeyEvent, _lang: Language) -> Result<()> {
+        self.handle_key(key).map(|_| ())
+    }
+
+    fn captures_input(&self) -> bool {
+        self.export_input.is_some()
+    }
+
+    fn render(&mut self, frame: &mut Frame, ctx: &RenderCtx, area: Rect) {
+        render(frame, self, ctx.theme, ctx.lang, area);
+    }
+
+    fn activate(&mut self) {
+        self.ensure_map_loaded();
+    }
+
+    fn poll(&mut self, _active: bool) {
+        self.poll_scan();
+    }
+
+    fn commands(&self, s: &i18n::Strings) -> Vec<PaletteCommand> {
+        self.palette_commands(s)
+    }
+
+    fn jobs(&self, s: &i18n::Strings) -> Vec<Job> {
+        self.jobs(s)
+    }
+
+    fn cancel_job(&mut self, id: &str) {
+        self.cancel_job(id)
+    }
+
+    fn sub_tab(&self) -> Option<(usize, usize)> {
+        Some((self.active_sub_tab.index(), CfgSubTab::all().len()))
+    }
+
+    fn sub_tab_label(&self, lang: Language) -> Option<&'static str> {
+        Some(self.active_sub_tab.label(lang))
+    }
+
+    fn flash_message(&mut self) -> &mut Option<FlashMessage> {
+        &mut self.flash_message
+    }
+}
+
 pub fn render(
     frame: &mut Frame,
     state: &ConfigShowcaseState,
@@ -312,6 +571,7 @@ pub fn render(
     match state.active_sub_tab {
         CfgSubTab::Overview => render_overview(frame, state, theme, lang, layout[1]),
         CfgSubTab::Diagram => render_diagram(frame, state, theme, lang, layout[1]),
+        CfgSubTab::Services => render_service_map(frame, state, theme, lang, layout[1]),
     }
 
     // Flash
@@ -412,7 +672,22 @@ fn render_overview(
     // Status area
     let mut status_lines: Vec<Line> = Vec::new();
 
-    if let Some(ref path) = state.export_path {
+    if let Some(ref input) = state.export_input {
+        status_lines.push(Line::raw(""));
+        status_lines.push(Line::styled(
+            format!("  {}", s.cfg_export_prompt),
+            theme.text_dim(),
+        ));
+        status_lines.push(Line::from(vec![
+            Span::raw("  "),
+            Span::styled(format!("{}█", input), Style::default().fg(theme.accent)),
+        ]));
+        status_lines.push(Line::raw(""));
+        status_lines.push(Line::styled(
+            format!("  ℹ {}", s.cfg_export_formats),
+            theme.text_dim(),
+        ));
+    } else if let Some(ref path) = state.export_path {
         status_lines.push(Line::raw(""));
         status_lines.push(Line::styled(
             format!("  ✅ {}", s.cfg_success),
@@ -557,3 +832,146 @@ fn render_diagram(
     let status = Paragraph::new(status_lines).wrap(Wrap { trim: false });
     frame.render_widget(status, chunks[3]);
 }
+
+fn render_service_map(
+    frame: &mut Frame,
+    state: &ConfigShowcaseState,
+    theme: &Theme,
+    lang: Language,
+    area: Rect,
+) {
+    let s = i18n::get_strings(lang);
+
+    let map = match &state.map {
+        _ if state.map_loading => {
+            let msg = Paragraph::new(vec![
+                Line::raw(""),
+                Line::styled(
+                    format!("⏳ {}", s.cfg_map_loading),
+                    Style::default().fg(theme.accent),
+                ),
+                Line::styled(s.cfg_map_loading_hint, theme.text_dim()),
+            ])
+            .alignment(Alignment::Center);
+            frame.render_widget(msg, area);
+            return;
+        }
+        Some(Ok(map)) => map,
+        Some(Err(err)) => {
+            let mut lines = vec![
+                Line::raw(""),
+                Line::styled(format!("  ❌ {}", s.cfg_map_failed), theme.error()),
+            ];
+            for line in err.lines() {
+                lines.push(Line::styled(format!("  {}", line), theme.text_dim()));
+            }
+            frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), area);
+            return;
+        }
+        None => return,
+    };
+
+    let chunks = Layout::vertical([Constraint::Length(2), Constraint::Min(1)]).split(area);
+
+    let summary = s
+        .cfg_map_summary
+        .replacen("{}", &map.count(false).to_string(), 1)
+        .replacen("{}", &map.count(true).to_string(), 1);
+    let toggle = if state.map_show_all {
+        s.cfg_map_showing_all
+    } else {
+        s.cfg_map_showing_user
+    };
+    frame.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled(format!(" {}", summary), theme.text()),
+            Span::styled(format!("  ·  {}", toggle), theme.text_dim()),
+        ])),
+        chunks[0],
+    );
+
+    let groups = map.visible(state.map_show_all);
+    if groups.is_empty() {
+        frame.render_widget(
+            Paragraph::new(Line::styled(
+                format!("  {}", s.cfg_map_empty),
+                theme.text_dim(),
+            )),
+            chunks[1],
+        );
+        return;
+    }
+
+    // One row per group header and per node; the selection counts nodes only
+    let name_width = groups
+        .iter()
+        .flat_map(|(_, nodes)| nodes)
+        .map(|n| n.name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .min(32);
+    let mut rows: Vec<Line> = Vec::new();
+    let mut selected_row = 0;
+    let mut node_idx = 0;
+    for (root, nodes) in &groups {
+        if !rows.is_empty() {
+            rows.push(Line::raw(""));
+        }
+        rows.push(Line::from(vec![
+            Span::styled(
+                format!(" {}", root),
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(format!(" ({})", nodes.len()), theme.text_dim()),
+        ]));
+        for (i, node) in nodes.iter().enumerate() {
+            let selected = node_idx == state.map_selected;
+            if selected {
+                selected_row = rows.len();
+            }
+            node_idx += 1;
+
+            let branch = if i + 1 == nodes.len() {
+                "└─"
+            } else {
+                "├─"
+            };
+            let name_style = if selected {
+                Style::default()
+                    .fg(theme.selection_fg)
+                    .bg(theme.selection_bg)
+                    .add_modifier(Modifier::BOLD)
+            } else if node.user {
+                theme.text()
+            } else {
+                theme.text_dim()
+            };
+            let mut spans = vec![
+                Span::styled(format!("   {} ", branch), Style::default().fg(theme.border)),
+                Span::styled(
+                    format!("{:<width$}", node.name, width = name_width),
+                    name_style,
+                ),
+            ];
+            if !node.deps.is_empty() {
+                spans.push(Span::styled(
+                    format!("  → {}", node.deps.join(", ")),
+                    Style::default().fg(theme.success),
+                ));
+            }
+            if !node.used_by.is_empty() {
+                spans.push(Span::styled(
+                    format!("  ← {}", node.used_by.join(", ")),
+                    Style::default().fg(theme.warning),
+                ));
+            }
+            rows.push(Line::from(spans));
+        }
+    }
+
+    let visible = chunks[1].height as usize;
+    let scroll = selected_row.saturating_sub(visible.saturating_sub(2));
+    frame.render_widget(Paragraph::new(rows).scroll((scroll as u16, 0)), chunks[1]);
+}
@@ -2,11 +2,38 @@
 //!
 //! Generates a dark-themed system overview infographic.
 //! Designed for r/unixporn, GitHub READMEs, and flex posts.
+//!
+//! PNG is rasterized from the SVG by an external renderer (rsvg-convert,
+//! ImageMagick or Inkscape — whichever is installed) at 2× size and then
+//! checked with the `image` crate that also loads the splash icon.
 
 #![allow(clippy::write_with_newline)]
 use crate::nix::sysinfo::PosterInfo;
+use crate::ui::theme::Theme;
+use anyhow::{bail, Context, Result};
 use std::fmt::Write;
-use std::path::PathBuf;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Rasterization scale for PNG (2400×1640 for the default layout)
+const PNG_SCALE: f64 = 2.0;
+
+/// The nixmate theme shown as swatches on the poster
+#[derive(Debug, Clone)]
+pub struct PosterTheme {
+    pub name: String,
+    pub colors: Vec<String>,
+}
+
+impl PosterTheme {
+    pub fn new(name: &str, theme: &Theme) -> Self {
+        Self {
+            name: name.to_string(),
+            colors: theme.palette_hex(),
+        }
+    }
+}
 
 // ── Colors ──
 const BG: &str = "#0d1117";
@@ -51,7 +78,7 @@ const ROW1: f64 = 255.0;
 const ROW2: f64 = ROW1 + CARD_H + CARD_GAP;
 
 /// Generate the complete SVG string.
-pub fn generate_svg(info: &PosterInfo) -> String {
+pub fn generate_svg(info: &PosterInfo, theme: &PosterTheme) -> String {
     let mut s = String::with_capacity(16384);
 
     // SVG header + embedded font import
@@ -69,6 +96,7 @@ text {{ font-family: 'JetBrains Mono', 'Fira Code', 'Cascadia Code', Consolas, m
 
     background(&mut s);
     header(&mut s, info);
+    theme_swatches(&mut s, theme);
     badges(&mut s, info);
     pills(&mut s, info);
     card_hardware(&mut s, info);
@@ -83,18 +111,103 @@ text {{ font-family: 'JetBrains Mono', 'Fira Code', 'Cascadia Code', Consolas, m
     s
 }
 
-/// Save SVG file. Returns file path.
-pub fn save_svg(info: &PosterInfo) -> std::io::Result<PathBuf> {
-    let svg = generate_svg(info);
-    let dir = dirs::home_dir()
+/// Default export location: ~/nixmate-poster/<hostname>-nixos.<ext>
+pub fn default_path(hostname: &str, ext: &str) -> PathBuf {
+    dirs::home_dir()
         .unwrap_or_else(|| PathBuf::from("/tmp"))
-        .join("nixmate-poster");
-    std::fs::create_dir_all(&dir)?;
-    let path = dir.join(format!("{}-nixos.svg", info.hostname));
-    std::fs::write(&path, &svg)?;
+        .join("nixmate-poster")
+        .join(format!("{}-nixos.{}", hostname, ext))
+}
+
+/// Save SVG file. Returns file path.
+pub fn save_svg(info: &PosterInfo, theme: &PosterTheme) -> std::io::Result<PathBuf> {
+    let path = default_path(&info.hostname, "svg");
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(&path, generate_svg(info, theme))?;
+    Ok(path)
+}
+
+/// Export to `path`; a `.png` extension rasterizes, anything else is SVG.
+/// A leading `~/` is expanded. Returns the written path.
+pub fn export(info: &PosterInfo, theme: &PosterTheme, path: &str) -> Result<PathBuf> {
+    let path = match path.trim().strip_prefix("~/") {
+        Some(rest) => dirs::home_dir().context("no home directory")?.join(rest),
+        None => PathBuf::from(path.trim()),
+    };
+    if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir).with_context(|| format!("cannot create {}", dir.display()))?;
+    }
+
+    let svg = generate_svg(info, theme);
+    let is_png = path
+        .extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case("png"));
+    if is_png {
+        rasterize(&svg, &path)?;
+        let img = image::open(&path).context("renderer wrote an unreadable PNG")?;
+        if img.width() < W as u32 {
+            bail!("renderer produced a {}×{} image", img.width(), img.height());
+        }
+    } else {
+        std::fs::write(&path, svg).with_context(|| format!("cannot write {}", path.display()))?;
+    }
     Ok(path)
 }
 
+/// Render `svg` to a PNG at `out` with the first renderer that works
+fn rasterize(svg: &str, out: &Path) -> Result<()> {
+    let out_str = out.to_string_lossy();
+    let zoom = PNG_SCALE.to_string();
+    let dpi = (96.0 * PNG_SCALE).to_string();
+    let renderers: [(&str, Vec<String>); 3] = [
+        (
+            "rsvg-convert",
+            vec!["-z".into(), zoom, "-o".into(), out_str.to_string()],
+        ),
+        (
+            "magick",
+            vec![
+                "-background".into(),
+                "none".into(),
+                "-density".into(),
+                dpi.clone(),
+                "svg:-".into(),
+                format!("png:{}", out_str),
+            ],
+        ),
+        (
+            "inkscape",
+            vec![
+                "--pipe".into(),
+                "--export-type=png".into(),
+                format!("--export-dpi={}", dpi),
+                format!("--export-filename={}", out_str),
+            ],
+        ),
+    ];
+
+    for (cmd, args) in renderers {
+        let Ok(mut child) = Command::new(cmd)
+            .args(&args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        else {
+            continue;
+        };
+        if let Some(mut stdin) = child.stdin.take() {
+            let _ = stdin.write_all(svg.as_bytes());
+        }
+        if child.wait().is_ok_and(|s| s.success()) && out.exists() {
+            return Ok(());
+        }
+    }
+    bail!("PNG export needs rsvg-convert, ImageMagick or Inkscape (e.g. `nix shell nixpkgs#librsvg`); use a .svg path instead")
+}
+
 // ═══════════════════════════════════════
 //  Background + Header
 // ═══════════════════════════════════════
@@ -146,6 +259,28 @@ fn header(s: &mut String, info: &PosterInfo) {
     );
 }
 
+/// nixmate theme colors, top right
+fn theme_swatches(s: &mut String, theme: &PosterTheme) {
+    let r = 9.0;
+    let step = 24.0;
+    let right = W - PAD - r;
+    for (i, color) in theme.colors.iter().rev().enumerate() {
+        let _ = write!(
+            s,
+            r#"<circle cx="{cx}" cy="72" r="{r}" fill="{color}" stroke="{CARD_BORDER}" stroke-width="1"/>
+"#,
+            cx = right - i as f64 * step,
+        );
+    }
+    let _ = write!(
+        s,
+        r#"<text x="{x}" y="104" font-size="11" fill="{DIM}" text-anchor="end" letter-spacing="1">theme  ·  {}</text>
+"#,
+        esc(&theme.name),
+        x = W - PAD
+    );
+}
+
 // ═══════════════════════════════════════
 //  Badges
 // ═══════════════════════════════════════
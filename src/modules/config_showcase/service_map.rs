@@ -0,0 +1,227 @@
+//! Service map — what the evaluated configuration actually enables.
+//!
+//! One `nix eval` walks `services`, `networking`, `virtualisation`,
+//! `programs`, `hardware` and `security` of the host's config and returns
+//! every attribute set whose `enable` is true (up to five levels deep, so
+//! `services.xserver.desktopManager.gnome` is found). Groups are the option
+//! roots the nodes live under, not a hard-coded category list.
+//!
+//! Edges come from the generated systemd units: if `nginx.service` has
+//! `after`/`wants`/`requires` on `phpfpm-nextcloud.service` and both are
+//! enabled nodes, nginx depends on it.
+
+use crate::modules::options::system_eval_command;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+
+/// Option namespaces that are walked for `enable = true`
+const ROOTS: &[&str] = &[
+    "services",
+    "networking",
+    "virtualisation",
+    "programs",
+    "hardware",
+    "security",
+];
+
+/// One enabled option set, e.g. `services.nginx`
+#[derive(Debug, Clone)]
+pub struct ServiceNode {
+    /// Option path of the set (its `.enable` is what was found)
+    pub path: String,
+    /// Last path segment, matched against systemd unit names
+    pub name: String,
+    /// Set by the user's own files (false: enabled by a default or another module)
+    pub user: bool,
+    /// Names of other nodes this one's unit orders after / requires
+    pub deps: Vec<String>,
+    /// Names of other nodes depending on this one
+    pub used_by: Vec<String>,
+}
+
+/// Nodes under the same option root, e.g. `services.xserver.desktopManager`
+#[derive(Debug, Clone)]
+pub struct ServiceGroup {
+    pub root: String,
+    pub nodes: Vec<ServiceNode>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ServiceMap {
+    pub groups: Vec<ServiceGroup>,
+}
+
+impl ServiceMap {
+    /// Groups with only the nodes `show_all` admits (user-set ones otherwise)
+    pub fn visible(&self, show_all: bool) -> Vec<(&str, Vec<&ServiceNode>)> {
+        self.groups
+            .iter()
+            .map(|g| {
+                let nodes: Vec<&ServiceNode> =
+                    g.nodes.iter().filter(|n| show_all || n.user).collect();
+                (g.root.as_str(), nodes)
+            })
+            .filter(|(_, nodes)| !nodes.is_empty())
+            .collect()
+    }
+
+    pub fn count(&self, show_all: bool) -> usize {
+        self.groups
+            .iter()
+            .flat_map(|g| &g.nodes)
+            .filter(|n| show_all || n.user)
+            .count()
+    }
+}
+
+#[derive(Deserialize)]
+struct RawEnabled {
+    loc: Vec<String>,
+    path: String,
+    #[serde(default)]
+    user: bool,
+}
+
+#[derive(Deserialize)]
+struct RawMap {
+    enabled: Vec<RawEnabled>,
+    #[serde(default)]
+    units: HashMap<String, Vec<String>>,
+}
+
+/// Evaluate the config. Blocking — run in background thread!
+pub fn evaluate(config_path: Option<&str>, nixos_host: Option<&str>) -> Result<ServiceMap, String> {
+    let output = system_eval_command(config_path, nixos_host, service_map_expr)
+        .stdin(std::process::Stdio::null())
+        .output()
+        .map_err(|e| format!("Failed to run nix eval: {}", e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(stderr
+            .lines()
+            .find(|l| l.trim_start().starts_with("error:"))
+            .unwrap_or("nix eval failed")
+            .trim()
+            .to_string());
+    }
+    let raw: RawMap = serde_json::from_slice(&output.stdout).map_err(|e| e.to_string())?;
+    Ok(build_map(raw))
+}
+
+fn build_map(raw: RawMap) -> ServiceMap {
+    let names: Vec<&str> = raw
+        .enabled
+        .iter()
+        .filter_map(|e| e.loc.last().map(|s| s.as_str()))
+        .collect();
+
+    let deps_of = |name: &str| -> Vec<String> {
+        let mut deps: Vec<String> = raw
+            .units
+            .get(name)
+            .into_iter()
+            .flatten()
+            .filter_map(|unit| unit.strip_suffix(".service"))
+            .filter(|dep| *dep != name && names.contains(dep))
+            .map(|dep| dep.to_string())
+            .collect();
+        deps.sort();
+        deps.dedup();
+        deps
+    };
+
+    let mut groups: BTreeMap<String, Vec<ServiceNode>> = BTreeMap::new();
+    for e in &raw.enabled {
+        let Some(name) = e.loc.last() else {
+            continue;
+        };
+        let root = e
+            .path
+            .strip_suffix(name.as_str())
+            .map(|r| r.trim_end_matches('.'))
+            .filter(|r| !r.is_empty())
+            .unwrap_or(e.loc[0].as_str())
+            .to_string();
+        groups.entry(root).or_default().push(ServiceNode {
+            path: e.path.clone(),
+            name: name.clone(),
+            user: e.user,
+            deps: deps_of(name),
+            used_by: Vec::new(),
+        });
+    }
+
+    // Reverse edges
+    let edges: Vec<(String, String)> = groups
+        .values()
+        .flatten()
+        .flat_map(|n| n.deps.iter().map(move |d| (d.clone(), n.name.clone())))
+        .collect();
+    for node in groups.values_mut().flatten() {
+        node.used_by = edges
+            .iter()
+            .filter(|(dep, _)| *dep == node.name)
+            .map(|(_, by)| by.clone())
+            .collect();
+        node.used_by.sort();
+        node.used_by.dedup();
+    }
+
+    let mut groups: Vec<ServiceGroup> = groups
+        .into_iter()
+        .map(|(root, mut nodes)| {
+            nodes.sort_by(|a, b| a.name.cmp(&b.name));
+            ServiceGroup { root, nodes }
+        })
+        .collect();
+    // Keep the namespaces in ROOTS order, sub-roots right after their root
+    groups.sort_by_key(|g| {
+        let top = g.root.split('.').next().unwrap_or_default();
+        (
+            ROOTS.iter().position(|r| *r == top).unwrap_or(ROOTS.len()),
+            g.root.clone(),
+        )
+    });
+    ServiceMap { groups }
+}
+
+/// Nix expression listing enabled option sets of `system` plus the
+/// systemd dependencies of their units
+fn service_map_expr(system: &str) -> String {
+    let roots: Vec<String> = ROOTS.iter().map(|r| format!("\"{}\"", r)).collect();
+    format!(
+        r#"
+let
+  sys = {system};
+  lib = sys.pkgs.lib;
+  cfg = sys.config;
+  try = v: d: let r = builtins.tryEval (builtins.deepSeq v v); in if r.success then r.value else d;
+  fromUser = f: builtins.match ".*/nixos/modules/.*" (toString f) == null;
+  walk = loc: depth: v:
+    let
+      isSet = try (builtins.isAttrs v && !(lib.isDerivation v)) false;
+      en = if isSet then try (v.enable or null) null else null;
+      here = if en == true then [ loc ] else [ ];
+      deeper =
+        if isSet && depth > 0 && en != false
+        then lib.concatLists (lib.mapAttrsToList (n: walk (loc ++ [ n ]) (depth - 1)) v)
+        else [ ];
+    in here ++ deeper;
+  found = lib.concatMap (r: walk [ r ] 4 (cfg.${{r}} or {{ }})) [ {roots} ];
+  userSet = loc:
+    let o = lib.attrByPath (loc ++ [ "enable" ]) null sys.options; in
+    try (o != null && builtins.any fromUser (o.files or [ ])) false;
+  names = lib.unique (map lib.last found);
+  unitDeps = n:
+    let u = cfg.systemd.services.${{n}} or {{ }}; in
+    try ((u.requires or [ ]) ++ (u.wants or [ ]) ++ (u.bindsTo or [ ]) ++ (u.after or [ ])) [ ];
+in
+{{
+  enabled = map (loc: {{ inherit loc; path = lib.showOption loc; user = userSet loc; }}) found;
+  units = lib.genAttrs names unitDeps;
+}}
+"#,
+        system = system,
+        roots = roots.join(" "),
+    )
+}
@@ -3,8 +3,13 @@
 //! Supports Claude, OpenAI, and Ollama (local).
 //! All calls are blocking — ALWAYS run in a background thread!
 //! Uses `ureq` for HTTP with timeouts on every request.
+//!
+//! The opt-in "explain with AI" panel streams from any OpenAI-compatible
+//! endpoint instead, see `stream_explain`.
 
 use anyhow::{Context, Result};
+use std::io::BufRead;
+use std::sync::mpsc;
 use std::time::Duration;
 
 const TIMEOUT_SECS: u64 = 60;
@@ -141,6 +146,96 @@ fn call_openai(agent: &ureq::Agent, api_key: &str, prompt: &str) -> Result<Strin
     }
 }
 
+// ═══════════════════════════════════════
+//  OPENAI-COMPATIBLE (streaming)
+// ═══════════════════════════════════════
+
+/// A piece of a streamed explanation
+pub enum ExplainChunk {
+    Text(String),
+    Done,
+    Failed(String),
+}
+
+/// Stream an explanation of `error_text` from an OpenAI-compatible
+/// `/chat/completions` endpoint, sending chunks as they arrive.
+/// This blocks — caller MUST run in a background thread. Stops early
+/// when the receiver is dropped (panel closed).
+pub fn stream_explain(
+    base_url: &str,
+    api_key: Option<&str>,
+    model: &str,
+    error_text: &str,
+    lang: &str,
+    tx: &mpsc::Sender<ExplainChunk>,
+) {
+    let chunk = match stream_openai_compatible(base_url, api_key, model, error_text, lang, tx) {
+        Ok(()) => ExplainChunk::Done,
+        Err(e) => ExplainChunk::Failed(format!("{:#}", e)),
+    };
+    let _ = tx.send(chunk);
+}
+
+fn stream_openai_compatible(
+    base_url: &str,
+    api_key: Option<&str>,
+    model: &str,
+    error_text: &str,
+    lang: &str,
+    tx: &mpsc::Sender<ExplainChunk>,
+) -> Result<()> {
+    // Per-read timeout: a long answer may stream for longer than TIMEOUT_SECS
+    let agent = ureq::AgentBuilder::new()
+        .timeout_connect(Duration::from_secs(10))
+        .timeout_read(Duration::from_secs(TIMEOUT_SECS))
+        .build();
+
+    let url = format!("{}/chat/completions", base_url.trim_end_matches('/'));
+    let body = serde_json::json!({
+        "model": model,
+        "messages": [{"role": "user", "content": build_prompt(error_text, lang)}],
+        "max_tokens": 2048,
+        "stream": true
+    });
+
+    let mut req = agent.post(&url).set("content-type", "application/json");
+    if let Some(key) = api_key.filter(|k| !k.is_empty()) {
+        req = req.set("Authorization", &format!("Bearer {}", key));
+    }
+
+    let resp = match req.send_string(&serde_json::to_string(&body)?) {
+        Ok(resp) => resp,
+        Err(ureq::Error::Status(code, resp)) => {
+            let body = resp.into_string().unwrap_or_default();
+            let msg: String = body.chars().take(200).collect();
+            anyhow::bail!("API error {} ({}): {}", code, url, msg)
+        }
+        Err(ureq::Error::Transport(e)) => {
+            anyhow::bail!("Network error ({}): {}", url, e)
+        }
+    };
+
+    // Server-sent events: `data: {json}` lines, terminated by `data: [DONE]`
+    let reader = std::io::BufReader::new(resp.into_reader());
+    for line in reader.lines() {
+        let line = line.context("Stream interrupted")?;
+        let Some(data) = line.strip_prefix("data:").map(|d| d.trim()) else {
+            continue;
+        };
+        if data == "[DONE]" {
+            break;
+        }
+        let json: serde_json::Value =
+            serde_json::from_str(data).context("Unexpected stream format")?;
+        if let Some(text) = json["choices"][0]["delta"]["content"].as_str() {
+            if !text.is_empty() && tx.send(ExplainChunk::Text(text.to_string())).is_err() {
+                return Ok(());
+            }
+        }
+    }
+    Ok(())
+}
+
 // ═══════════════════════════════════════
 //  OLLAMA (local)
 // ═══════════════════════════════════════
@@ -0,0 +1,111 @@
+//! Ecosystem detection for matched errors.
+//!
+//! The pattern set is written for NixOS. The same errors also show up in
+//! home-manager and nix-darwin, where the fix uses different options and
+//! commands (`home.stateVersion`, `home-manager switch`, `darwin-rebuild`).
+//! Detection looks at the pattern id first, then at markers in the error
+//! text; `adapt` rewrites the suggested fix for the detected ecosystem.
+
+use super::matcher::MatchResult;
+use crate::nix::availability::Platform;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// Where an error comes from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Ecosystem {
+    #[default]
+    NixOS,
+    HomeManager,
+    Darwin,
+}
+
+impl Ecosystem {
+    pub fn emoji(&self) -> &'static str {
+        match self {
+            Self::NixOS => "🐧",
+            Self::HomeManager => "🏠",
+            Self::Darwin => "🍎",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::NixOS => "NIXOS",
+            Self::HomeManager => "HOME-MANAGER",
+            Self::Darwin => "NIX-DARWIN",
+        }
+    }
+}
+
+static DARWIN_MARKERS: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"darwin-rebuild|nix-darwin|darwinConfigurations|darwin-system|system\.primaryUser")
+        .unwrap()
+});
+
+static HOME_MANAGER_MARKERS: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"home-manager|homeConfigurations|hm-activate|home\.stateVersion|HOME_MANAGER_|homeManagerConfiguration")
+        .unwrap()
+});
+
+/// Detect the ecosystem of an error matched by `pattern_id`.
+///
+/// Patterns that only exist in one ecosystem decide on their own. Otherwise
+/// darwin markers win over home-manager ones: with home-manager used as a
+/// nix-darwin module the fix is still run through `darwin-rebuild`.
+pub fn detect(pattern_id: &str, error_text: &str) -> Ecosystem {
+    if pattern_id.starts_with("darwin-") {
+        Ecosystem::Darwin
+    } else if pattern_id.starts_with("home-") || pattern_id.starts_with("hm-") {
+        Ecosystem::HomeManager
+    } else if DARWIN_MARKERS.is_match(error_text) {
+        Ecosystem::Darwin
+    } else if HOME_MANAGER_MARKERS.is_match(error_text) {
+        Ecosystem::HomeManager
+    } else {
+        Ecosystem::NixOS
+    }
+}
+
+/// Errors that look like NixOS ones come from home-manager or nix-darwin
+/// on a system that isn't NixOS
+pub fn for_platform(ecosystem: Ecosystem, platform: Platform) -> Ecosystem {
+    match (ecosystem, platform) {
+        (Ecosystem::NixOS, Platform::Darwin) => Ecosystem::Darwin,
+        (Ecosystem::NixOS, Platform::Linux) => Ecosystem::HomeManager,
+        (ecosystem, _) => ecosystem,
+    }
+}
+
+/// NixOS spelling → ecosystem spelling, longest first so that
+/// `nixos-rebuild boot` is rewritten before plain `nixos-rebuild`.
+fn replacements(ecosystem: Ecosystem) -> &'static [(&'static str, &'static str)] {
+    match ecosystem {
+        Ecosystem::NixOS => &[],
+        Ecosystem::HomeManager => &[
+            (
+                "sudo nixos-rebuild switch --flake .#<hostname>",
+                "home-manager switch --flake .#<user>",
+            ),
+            ("sudo nixos-rebuild switch", "home-manager switch"),
+            ("system.stateVersion", "home.stateVersion"),
+            ("environment.systemPackages", "home.packages"),
+            ("nixosConfigurations", "homeConfigurations"),
+        ],
+        Ecosystem::Darwin => &[
+            ("nixos-rebuild boot", "darwin-rebuild switch"),
+            ("nixos-rebuild", "darwin-rebuild"),
+            ("nixosConfigurations", "darwinConfigurations"),
+        ],
+    }
+}
+
+/// Rewrite the suggested fix of a (translated) result for its ecosystem.
+pub fn adapt(mut result: MatchResult) -> MatchResult {
+    for (from, to) in replacements(result.ecosystem) {
+        result.solution = result.solution.replace(from, to);
+        result.deep_dive = result.deep_dive.replace(from, to);
+        result.tip = result.tip.map(|t| t.replace(from, to));
+    }
+    result
+}
@@ -0,0 +1,73 @@
+//! History of analyzed errors.
+//!
+//! Every analysis is appended to `~/.local/state/nixmate/errors.json`
+//! (newest first), so an error from yesterday can still be looked at after
+//! the terminal scrollback is gone. Re-analyzing the same text moves the
+//! existing entry to the top instead of adding a duplicate.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Oldest entries are dropped beyond this
+const MAX_ENTRIES: usize = 200;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// Local time, `%Y-%m-%d %H:%M`
+    pub timestamp: String,
+    /// Command that produced the error (piped input only)
+    pub source: Option<String>,
+    pub text: String,
+    /// Matched pattern id, None if no rule matched
+    pub rule: Option<String>,
+}
+
+impl HistoryEntry {
+    /// First `error:` line, else the first non-empty one
+    pub fn summary(&self) -> &str {
+        let mut lines = self.text.lines().map(str::trim).filter(|l| !l.is_empty());
+        let first = lines.clone().next();
+        lines
+            .find(|l| l.starts_with("error:"))
+            .or(first)
+            .unwrap_or("")
+    }
+}
+
+fn history_path() -> Option<PathBuf> {
+    dirs::state_dir()
+        .or_else(|| dirs::home_dir().map(|h| h.join(".local").join("state")))
+        .map(|p| p.join("nixmate").join("errors.json"))
+}
+
+/// Load the history from disk, newest first
+pub fn load() -> Vec<HistoryEntry> {
+    let Some(path) = history_path() else {
+        return Vec::new();
+    };
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Write `entries` back to disk
+pub fn save(entries: &[HistoryEntry]) -> Result<()> {
+    let path = history_path().context("No state directory")?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let json = serde_json::to_string_pretty(entries)?;
+    std::fs::write(&path, json)?;
+    Ok(())
+}
+
+/// Put `entry` on top of `entries` (replacing an entry with the same text)
+/// and persist the result
+pub fn record(entries: &mut Vec<HistoryEntry>, entry: HistoryEntry) -> Result<()> {
+    entries.retain(|e| e.text != entry.text);
+    entries.insert(0, entry);
+    entries.truncate(MAX_ENTRIES);
+    save(entries)
+}
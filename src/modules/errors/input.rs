@@ -0,0 +1,61 @@
+//! Getting an error into Analyze from a log file while nixmate is already
+//! running (the clipboard is in `crate::clipboard`).
+
+use anyhow::{bail, Context, Result};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::PathBuf;
+
+/// A build log can be huge; the error is at its end
+const MAX_FILE_BYTES: u64 = 64 * 1024;
+
+/// The end of a log file (at most `MAX_FILE_BYTES`, starting at a line).
+/// A leading `~/` is expanded. Returns the text and the resolved path.
+pub fn read_file(path: &str) -> Result<(String, PathBuf)> {
+    let path = match path.trim().strip_prefix("~/") {
+        Some(rest) => dirs::home_dir().context("no home directory")?.join(rest),
+        None => PathBuf::from(path.trim()),
+    };
+    let mut file =
+        std::fs::File::open(&path).with_context(|| format!("cannot open {}", path.display()))?;
+    let len = file.metadata()?.len();
+    let cut = len > MAX_FILE_BYTES;
+    if cut {
+        file.seek(SeekFrom::Start(len - MAX_FILE_BYTES))?;
+    }
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+    let text = String::from_utf8_lossy(&bytes);
+    let text = if cut { from_next_line(&text) } else { &text };
+    if text.trim().is_empty() {
+        bail!("{} is empty", path.display());
+    }
+    Ok((text.to_string(), path))
+}
+
+/// Drop the partial first line of a text that was cut at the front
+fn from_next_line(text: &str) -> &str {
+    text.split_once('\n').map_or(text, |(_, rest)| rest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_file_keeps_the_end() {
+        let path = std::env::temp_dir().join(format!("nixmate-errlog-{}", std::process::id()));
+        let filler = "building '/nix/store/…-foo.drv'...\n".repeat(4000);
+        std::fs::write(
+            &path,
+            format!("{}error: undefined variable 'pkgs'\n", filler),
+        )
+        .unwrap();
+        let (text, _) = read_file(path.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(text.len() as u64 <= MAX_FILE_BYTES);
+        assert!(text.starts_with("building"));
+        assert!(text.ends_with("error: undefined variable 'pkgs'\n"));
+        assert!(read_file("/nonexistent/nixmate.log").is_err());
+    }
+}
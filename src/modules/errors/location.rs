@@ -0,0 +1,122 @@
+//! Source locations in error text and opening them in $EDITOR.
+//!
+//! Nix reports positions as `at /etc/nixos/foo.nix:42:7`. Traces list many
+//! of them, mostly inside nixpkgs; the interesting one is the first that
+//! points at the user's own files. Flakes are evaluated from a store copy
+//! (`/nix/store/<hash>-source/…`, nixpkgs too), so those are mapped back to
+//! the flake directory where that file exists.
+
+use crate::types::ExternalCommand;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::path::Path;
+
+static LOCATION: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(/[^\s:'`\x22«»]+\.nix):(\d+)(?::(\d+))?").unwrap());
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceLocation {
+    pub file: String,
+    pub line: u32,
+    pub column: Option<u32>,
+}
+
+impl SourceLocation {
+    /// `foo.nix:42` for titles and hints
+    pub fn short(&self) -> String {
+        let name = self.file.rsplit('/').next().unwrap_or(&self.file);
+        format!("{}:{}", name, self.line)
+    }
+}
+
+/// Best location to open, already mapped into `flake_dir`: the user's own
+/// files first, then anything else outside the store, then the first one.
+pub fn find(text: &str, flake_dir: Option<&str>) -> Option<SourceLocation> {
+    let all: Vec<SourceLocation> = LOCATION
+        .captures_iter(text)
+        .filter_map(|c| {
+            Some(SourceLocation {
+                file: c.get(1)?.as_str().to_string(),
+                line: c.get(2)?.as_str().parse().ok()?,
+                column: c.get(3).and_then(|m| m.as_str().parse().ok()),
+            })
+        })
+        .map(|loc| resolve(&loc, flake_dir))
+        .collect();
+
+    let rank = |loc: &SourceLocation| {
+        let in_store = loc.file.starts_with("/nix/store/");
+        match (in_store, Path::new(&loc.file).exists()) {
+            (false, true) => 0,
+            (false, false) => 1,
+            (true, _) => 2,
+        }
+    };
+    all.iter().min_by_key(|loc| rank(loc)).cloned()
+}
+
+/// `nixos/foo.nix` for `/nix/store/<hash>-source/nixos/foo.nix`
+fn store_relative(file: &str) -> Option<&str> {
+    let rest = file.strip_prefix("/nix/store/")?;
+    let (dir, rel) = rest.split_once('/')?;
+    dir.ends_with("-source").then_some(rel)
+}
+
+/// The file to actually edit: store copies of a flake map back to the
+/// same file in `flake_dir` when it exists there.
+fn resolve(loc: &SourceLocation, flake_dir: Option<&str>) -> SourceLocation {
+    if let (Some(rel), Some(dir)) = (store_relative(&loc.file), flake_dir) {
+        let local = Path::new(dir).join(rel);
+        if local.exists() {
+            return SourceLocation {
+                file: local.to_string_lossy().to_string(),
+                ..loc.clone()
+            };
+        }
+    }
+    loc.clone()
+}
+
+/// `$VISUAL` / `$EDITOR` invocation that opens `loc` at its line
+pub fn editor_command(loc: &SourceLocation, notice: String) -> ExternalCommand {
+    let editor = std::env::var("VISUAL")
+        .ok()
+        .filter(|e| !e.trim().is_empty())
+        .or_else(|| std::env::var("EDITOR").ok())
+        .filter(|e| !e.trim().is_empty())
+        .unwrap_or_else(|| "nano".to_string());
+
+    // $EDITOR may carry its own flags ("code --wait")
+    let mut parts = editor.split_whitespace().map(|p| p.to_string());
+    let program = parts.next().unwrap_or_else(|| "nano".to_string());
+    let mut args: Vec<String> = parts.collect();
+
+    let col = loc.column.unwrap_or(1);
+    let name = program.rsplit('/').next().unwrap_or(&program);
+    match name {
+        "code" | "codium" | "code-insiders" => {
+            if !args.iter().any(|a| a == "--wait" || a == "-w") {
+                args.push("--wait".into());
+            }
+            args.push("-g".into());
+            args.push(format!("{}:{}:{}", loc.file, loc.line, col));
+        }
+        "hx" | "helix" => args.push(format!("{}:{}:{}", loc.file, loc.line, col)),
+        "nano" => {
+            args.push(format!("+{},{}", loc.line, col));
+            args.push(loc.file.clone());
+        }
+        // vi, vim, nvim, emacs, kak, micro, … all take +LINE
+        _ => {
+            args.push(format!("+{}", loc.line));
+            args.push(loc.file.clone());
+        }
+    }
+
+    ExternalCommand {
+        program,
+        args,
+        cwd: None,
+        notice,
+    }
+}
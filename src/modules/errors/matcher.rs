@@ -3,6 +3,7 @@
 //! Takes raw error text and finds matching patterns,
 //! extracting captured groups for templating.
 
+use super::ecosystem::{self, Ecosystem};
 use super::patterns::{library_to_package, Category, Pattern, PATTERNS};
 
 /// Result of matching an error against patterns.
@@ -10,6 +11,8 @@ use super::patterns::{library_to_package, Category, Pattern, PATTERNS};
 pub struct MatchResult {
     pub pattern_id: String,
     pub category: Category,
+    /// NixOS, home-manager or nix-darwin, see `ecosystem::detect`
+    pub ecosystem: Ecosystem,
     pub title: String,
     pub explanation: String,
     pub solution: String,
@@ -26,7 +29,9 @@ pub fn analyze(error_text: &str) -> Option<MatchResult> {
     for pattern in PATTERNS {
         let re = pattern.regex();
         if let Some(captures) = re.captures(error_text) {
-            return Some(build_result(pattern, &captures));
+            let mut result = build_result(pattern, &captures);
+            result.ecosystem = ecosystem::detect(pattern.id, error_text);
+            return Some(result);
         }
     }
     None
@@ -61,6 +66,7 @@ fn build_result(pattern: &Pattern, captures: &regex::Captures) -> MatchResult {
     MatchResult {
         pattern_id: pattern.id.to_string(),
         category: pattern.category,
+        ecosystem: Ecosystem::default(),
         title,
         explanation,
         solution,
@@ -129,6 +135,30 @@ collect2: error: ld returned 1 exit status
         );
     }
 
+    #[test]
+    fn test_home_manager_state_version_adapted() {
+        let error = "error: The option `home.stateVersion' is used but not defined.";
+        let result = ecosystem::adapt(analyze(error).expect("Should match"));
+        assert_eq!(result.pattern_id, "state-version-missing");
+        assert_eq!(result.ecosystem, Ecosystem::HomeManager);
+        assert!(result.solution.contains("home.stateVersion"));
+        assert!(result.solution.contains("home-manager switch"));
+        assert!(!result.solution.contains("nixos-rebuild"));
+    }
+
+    #[test]
+    fn test_darwin_detected_from_context() {
+        let error = "error: Unexpected files in /etc, aborting activation";
+        let result = analyze(error).expect("Should match");
+        assert_eq!(result.pattern_id, "darwin-unexpected-etc-files");
+        assert_eq!(result.ecosystem, Ecosystem::Darwin);
+
+        let error = "$ darwin-rebuild switch\nerror: infinite recursion encountered";
+        let result = ecosystem::adapt(analyze(error).expect("Should match"));
+        assert_eq!(result.ecosystem, Ecosystem::Darwin);
+        assert!(!result.deep_dive.contains("nixos-rebuild"));
+    }
+
     #[test]
     fn test_library_mapping_in_solution() {
         let error = "ld: cannot find -lz";
@@ -1,21 +1,35 @@
 //! Error Translator module (formerly nixplain)
 //!
 //! Integrated into nixmate as an inline module.
-//! Has sub-tabs: Analyze, Submit Pattern.
+//! Has sub-tabs: Analyze, History, Submit Pattern.
 //! Uses nixmate's global theme, i18n, and config.
+//! Input is split into errors, warnings, notes and trace frames (see
+//! `tree.rs`); only the errors are translated, `t` shows the whole tree.
 
 pub mod ai;
+pub mod ecosystem;
+pub mod history;
+mod input;
+pub mod location;
 pub mod matcher;
 pub mod patterns;
 pub mod patterns_i18n;
+mod suggest;
+pub mod tree;
 
-use crate::config::Language;
+use crate::config::{AiExplainConfig, Language};
 use crate::i18n;
-use crate::types::FlashMessage;
+use crate::modules::jobs::Job;
+use crate::modules::module::{Module, RenderCtx};
+use crate::modules::palette::PaletteCommand;
+use crate::nix::availability::Platform;
+use crate::types::{ExternalCommand, FlashMessage, PipedInput};
 use crate::ui::theme::Theme;
 use crate::ui::widgets;
 use anyhow::Result;
 use crossterm::event::{KeyCode, KeyEvent};
+use history::HistoryEntry;
+use location::SourceLocation;
 use matcher::MatchResult;
 use ratatui::{
     layout::{Alignment, Constraint, Layout, Rect},
@@ -32,18 +46,20 @@ use std::sync::mpsc;
 pub enum ErrSubTab {
     #[default]
     Analyze,
+    History,
     Submit,
 }
 
 impl ErrSubTab {
     pub fn all() -> &'static [ErrSubTab] {
-        &[ErrSubTab::Analyze, ErrSubTab::Submit]
+        &[ErrSubTab::Analyze, ErrSubTab::History, ErrSubTab::Submit]
     }
 
     pub fn index(&self) -> usize {
         match self {
             ErrSubTab::Analyze => 0,
-            ErrSubTab::Submit => 1,
+            ErrSubTab::History => 1,
+            ErrSubTab::Submit => 2,
         }
     }
 
@@ -51,6 +67,7 @@ impl ErrSubTab {
         let s = i18n::get_strings(lang);
         match self {
             ErrSubTab::Analyze => s.err_analyze,
+            ErrSubTab::History => s.err_history,
             ErrSubTab::Submit => s.err_submit,
         }
     }
@@ -141,6 +158,19 @@ impl SubmitForm {
     }
 }
 
+// ── Explain with AI ──
+
+/// Streamed answer from the `[ai_explain]` endpoint, shown next to the
+/// rule-based translation
+pub struct ExplainPanel {
+    /// Where the error text was sent (shown in the panel title)
+    pub host: String,
+    pub text: String,
+    pub streaming: bool,
+    pub error: Option<String>,
+    pub scroll: usize,
+}
+
 // ── Module state ──
 
 pub struct ErrorsState {
@@ -150,9 +180,18 @@ pub struct ErrorsState {
     // Analyze
     pub input_buffer: String,
     pub input_mode: bool,
+    /// Path being typed for `f` (load the error from a file)
+    pub path_prompt: Option<String>,
     pub result: Option<MatchResult>,
     pub scroll_offset: usize,
 
+    // Structure of the input: errors, warnings, notes, trace frames
+    pub tree: Vec<tree::Entry>,
+    pub tree_open: bool,
+    pub tree_scroll: usize,
+    /// Trace frames unfolded
+    pub tree_traces: bool,
+
     // Pipe mode
     #[allow(dead_code)] // Set during init, reserved for future pipe-specific UI
     pub piped: bool,
@@ -165,9 +204,31 @@ pub struct ErrorsState {
     pub ai_requested: bool,
     ai_rx: Option<mpsc::Receiver<Result<String, String>>>,
 
+    // History (persisted, newest first)
+    pub history: Vec<HistoryEntry>,
+    pub history_selected: usize,
+    /// Command that produced the piped input, recorded with its analysis
+    input_source: Option<String>,
+
     // Submit
     pub submit_form: SubmitForm,
 
+    // Open in $EDITOR (run by the main loop with the TUI suspended)
+    pub location: Option<SourceLocation>,
+    pub config_path: Option<String>,
+    /// `nixosConfigurations` attribute from the config (None = host name)
+    pub nixos_host: Option<String>,
+    pub pending_external: Option<ExternalCommand>,
+
+    // Where the option or package the error names is set in the config
+    pub suggestion: Option<suggest::Suggestion>,
+    suggest_rx: Option<mpsc::Receiver<suggest::Suggestion>>,
+
+    // Explain with AI (opt-in, started by app.rs which has the config)
+    pub explain: Option<ExplainPanel>,
+    pub explain_requested: bool,
+    explain_rx: Option<mpsc::Receiver<ai::ExplainChunk>>,
+
     // Flash
     pub lang: Language,
     pub flash_message: Option<FlashMessage>,
@@ -180,8 +241,13 @@ impl ErrorsState {
             active_sub_tab: ErrSubTab::Analyze,
             input_buffer: String::new(),
             input_mode: false,
+            path_prompt: None,
             result: None,
             scroll_offset: 0,
+            tree: Vec::new(),
+            tree_open: false,
+            tree_scroll: 0,
+            tree_traces: false,
             piped: false,
             ai_loading: false,
             ai_result: None,
@@ -189,21 +255,38 @@ impl ErrorsState {
             ai_scroll: 0,
             ai_requested: false,
             ai_rx: None,
+            history: history::load(),
+            history_selected: 0,
+            input_source: None,
             submit_form: SubmitForm::default(),
+            location: None,
+            config_path: None,
+            nixos_host: None,
+            suggestion: None,
+            suggest_rx: None,
+            explain: None,
+            explain_requested: false,
+            explain_rx: None,
+            pending_external: None,
             lang: Language::English,
             flash_message: None,
         }
     }
 
     /// Initialize with piped input and auto-analyze.
-    pub fn new_with_input(input: String, lang: Language) -> Self {
+    pub fn new_with_input(input: PipedInput, lang: Language) -> Self {
         let s = i18n::get_strings(lang);
         let mut state = Self {
             active_sub_tab: ErrSubTab::Analyze,
-            input_buffer: input,
+            input_buffer: input.text,
             input_mode: false,
+            path_prompt: None,
             result: None,
             scroll_offset: 0,
+            tree: Vec::new(),
+            tree_open: false,
+            tree_scroll: 0,
+            tree_traces: false,
             piped: true,
             ai_loading: false,
             ai_result: None,
@@ -211,7 +294,19 @@ impl ErrorsState {
             ai_scroll: 0,
             ai_requested: false,
             ai_rx: None,
+            history: history::load(),
+            history_selected: 0,
+            input_source: input.source,
             submit_form: SubmitForm::default(),
+            location: None,
+            config_path: None,
+            nixos_host: None,
+            suggestion: None,
+            suggest_rx: None,
+            explain: None,
+            explain_requested: false,
+            explain_rx: None,
+            pending_external: None,
             lang,
             flash_message: Some(FlashMessage::new(s.err_piped_hint.to_string(), false)),
         };
@@ -219,21 +314,149 @@ impl ErrorsState {
         state
     }
 
-    /// Perform analysis on the current input buffer
+    /// Perform analysis on the current input buffer and add it to the history
     fn analyze_input(&mut self, lang: Language) {
         if self.input_buffer.trim().is_empty() {
             return;
         }
+        self.run_analysis(lang);
+
+        let entry = HistoryEntry {
+            timestamp: chrono::Local::now().format("%Y-%m-%d %H:%M").to_string(),
+            source: self.input_source.take(),
+            text: self.input_buffer.trim().to_string(),
+            rule: self.result.as_ref().map(|r| r.pattern_id.clone()),
+        };
+        if let Err(e) = history::record(&mut self.history, entry) {
+            self.show_flash(&e.to_string(), true);
+        }
+        self.history_selected = 0;
+    }
 
+    fn run_analysis(&mut self, lang: Language) {
         let lang_str = match lang {
-            Language::English => "en",
+            Language::English | Language::Locale(_) => "en",
             Language::German => "de",
         };
 
-        self.result =
-            matcher::analyze(&self.input_buffer).map(|r| patterns_i18n::translate(&r, lang_str));
+        let flake_dir = crate::modules::flake_inputs::find_flake_dir(self.config_path.as_deref());
+        self.location = location::find(&self.input_buffer, flake_dir.as_deref());
+        self.start_suggest(flake_dir);
+
+        // The errors first: a warning above them mustn't decide the match
+        self.tree = tree::parse(&self.input_buffer);
+        let errors = tree::error_text(&self.tree);
+        let input = &self.input_buffer;
+        self.result = errors
+            .as_deref()
+            .and_then(matcher::analyze)
+            .or_else(|| matcher::analyze(input))
+            .map(|mut r| {
+                r.ecosystem = ecosystem::for_platform(
+                    ecosystem::detect(&r.pattern_id, input),
+                    Platform::current(),
+                );
+                ecosystem::adapt(patterns_i18n::translate(&r, lang_str))
+            });
         self.input_mode = false;
         self.scroll_offset = 0;
+        self.tree_open = false;
+        self.tree_scroll = 0;
+        self.tree_traces = false;
+    }
+
+    /// Keys of the structure view (`t`)
+    fn handle_tree_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('t') => self.tree_open = false,
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.tree_scroll = self.tree_scroll.saturating_add(1);
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.tree_scroll = self.tree_scroll.saturating_sub(1);
+            }
+            KeyCode::Char('g') => self.tree_scroll = 0,
+            KeyCode::Char('G') => self.tree_scroll = usize::MAX,
+            KeyCode::Char(' ') | KeyCode::Enter => self.tree_traces = !self.tree_traces,
+            _ => {}
+        }
+    }
+
+    /// Forget the current analysis and analyze `text` instead
+    pub fn load_text(&mut self, text: String, source: Option<String>, lang: Language) {
+        self.input_buffer = text;
+        self.input_source = source;
+        self.location = None;
+        self.suggestion = None;
+        self.close_explain();
+        self.result = None;
+        self.ai_result = None;
+        self.ai_scroll = 0;
+        self.active_sub_tab = ErrSubTab::Analyze;
+        self.analyze_input(lang);
+    }
+
+    fn load_clipboard(&mut self, lang: Language) {
+        let s = i18n::get_strings(lang);
+        match crate::clipboard::read() {
+            Some((text, tool)) => {
+                self.load_text(text, None, lang);
+                self.show_flash(&s.err_loaded_clipboard.replace("{}", tool), false);
+            }
+            None => self.show_flash(s.err_clipboard_empty, true),
+        }
+    }
+
+    fn load_file(&mut self, path: &str, lang: Language) {
+        match input::read_file(path) {
+            Ok((text, path)) => {
+                let path = path.display().to_string();
+                self.load_text(text, Some(path.clone()), lang);
+                let s = i18n::get_strings(lang);
+                self.show_flash(&s.err_loaded_file.replace("{}", &path), false);
+            }
+            Err(e) => self.show_flash(&e.to_string(), true),
+        }
+    }
+
+    /// Bracketed paste: the whole text, newlines included, goes into the
+    /// input (starting a new one unless it's already open). False if
+    /// Analyze isn't taking text right now.
+    pub fn paste(&mut self, text: &str) -> bool {
+        if self.active_sub_tab != ErrSubTab::Analyze || self.ai_loading {
+            return false;
+        }
+        if let Some(path) = &mut self.path_prompt {
+            path.push_str(text.lines().next().unwrap_or_default());
+            return true;
+        }
+        if !self.input_mode {
+            self.input_buffer.clear();
+            self.location = None;
+            self.suggestion = None;
+            self.close_explain();
+            self.result = None;
+            self.ai_result = None;
+            self.input_mode = true;
+        }
+        self.input_buffer.push_str(&text.replace("\r\n", "\n"));
+        true
+    }
+
+    /// Queue opening the error's location in $EDITOR
+    fn open_location(&mut self, lang: Language) {
+        let Some(loc) = self.location.clone() else {
+            return;
+        };
+        let s = i18n::get_strings(lang);
+        if !std::path::Path::new(&loc.file).exists() {
+            self.show_flash(&s.err_location_missing.replace("{}", &loc.file), true);
+            return;
+        }
+        let notice = s
+            .err_editor_notice
+            .replace("{}", &format!("{}:{}", loc.file, loc.line));
+        self.pending_external = Some(location::editor_command(&loc, notice));
     }
 
     pub fn show_flash(&mut self, msg: &str, is_error: bool) {
@@ -285,8 +508,125 @@ impl ErrorsState {
         });
     }
 
+    /// Stream an explanation of the current error into the side panel
+    pub fn start_explain(&mut self, cfg: &AiExplainConfig, lang: &str) {
+        if self.input_buffer.trim().is_empty() {
+            return;
+        }
+        self.explain = Some(ExplainPanel {
+            host: cfg.host().to_string(),
+            text: String::new(),
+            streaming: true,
+            error: None,
+            scroll: 0,
+        });
+
+        let (tx, rx) = mpsc::channel();
+        self.explain_rx = Some(rx);
+
+        let cfg = cfg.clone();
+        let error_text = self.input_buffer.clone();
+        let lang = lang.to_string();
+        std::thread::spawn(move || {
+            ai::stream_explain(
+                &cfg.url,
+                cfg.api_key.as_deref(),
+                &cfg.model,
+                &error_text,
+                &lang,
+                &tx,
+            );
+        });
+    }
+
+    /// Close the panel; dropping the receiver stops the stream
+    fn close_explain(&mut self) {
+        self.explain = None;
+        self.explain_rx = None;
+    }
+
+    fn poll_explain(&mut self) {
+        let (Some(rx), Some(panel)) = (&self.explain_rx, &mut self.explain) else {
+            return;
+        };
+        loop {
+            match rx.try_recv() {
+                Ok(ai::ExplainChunk::Text(text)) => panel.text.push_str(&text),
+                Ok(ai::ExplainChunk::Done) => {
+                    panel.streaming = false;
+                    break;
+                }
+                Ok(ai::ExplainChunk::Failed(err)) => {
+                    panel.streaming = false;
+                    panel.error = Some(err);
+                    break;
+                }
+                Err(mpsc::TryRecvError::Empty) => return,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    panel.streaming = false;
+                    break;
+                }
+            }
+        }
+        self.explain_rx = None;
+    }
+
+    /// Look up what the error names in the configuration, in the background
+    fn start_suggest(&mut self, flake_dir: Option<String>) {
+        self.suggestion = None;
+        self.suggest_rx = None;
+        let Some(reference) = suggest::reference(&self.input_buffer) else {
+            return;
+        };
+        let root = flake_dir
+            .or_else(|| self.config_path.clone())
+            .unwrap_or_else(|| "/etc/nixos".to_string());
+        let config_path = self.config_path.clone();
+        let nixos_host = self.nixos_host.clone();
+        let (tx, rx) = mpsc::channel();
+        self.suggest_rx = Some(rx);
+        std::thread::spawn(move || {
+            let options = match reference {
+                suggest::Reference::Option(_) => crate::modules::options::cached_paths(
+                    config_path.as_deref(),
+                    nixos_host.as_deref(),
+                ),
+                suggest::Reference::Name(_) => None,
+            };
+            let _ = tx.send(suggest::suggest(
+                reference,
+                std::path::Path::new(&root),
+                options.as_deref(),
+            ));
+        });
+    }
+
+    fn poll_suggest(&mut self) {
+        let Some(ref rx) = self.suggest_rx else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(suggestion) => {
+                // Errors without a position of their own open the config line
+                if self.location.is_none() {
+                    self.location = suggestion.hits.first().map(|hit| SourceLocation {
+                        file: hit.file.clone(),
+                        line: hit.line,
+                        column: None,
+                    });
+                }
+                self.suggestion = Some(suggestion);
+                self.suggest_rx = None;
+            }
+            Err(mpsc::TryRecvError::Empty) => {}
+            Err(mpsc::TryRecvError::Disconnected) => self.suggest_rx = None,
+        }
+    }
+
     /// Poll for AI analysis results. Called from update_timers (non-blocking).
     pub fn poll_ai(&mut self) {
+        self.poll_explain();
+        self.poll_suggest();
         if let Some(ref rx) = self.ai_rx {
             match rx.try_recv() {
                 Ok(Ok(text)) => {
@@ -313,6 +653,51 @@ impl ErrorsState {
     }
 
     /// Handle key events
+    /// Actions offered in the command palette
+    /// Running background work, for the jobs overlay
+    pub fn jobs(&self, s: &i18n::Strings) -> Vec<Job> {
+        let mut jobs = Vec::new();
+        if self.ai_loading {
+            jobs.push(Job::new("ai", s.job_err_ai, true).detail(self.ai_provider_name.clone()));
+        }
+        if let Some(panel) = self.explain.as_ref().filter(|p| p.streaming) {
+            jobs.push(Job::new("explain", s.job_err_explain, true).detail(panel.host.clone()));
+        }
+        jobs
+    }
+
+    /// Stop waiting for a job from `jobs()`; its result is discarded
+    pub fn cancel_job(&mut self, id: &str) {
+        match id {
+            "ai" => {
+                self.ai_loading = false;
+                self.ai_rx = None;
+            }
+            "explain" => {
+                self.explain_rx = None;
+                if let Some(panel) = &mut self.explain {
+                    panel.streaming = false;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn palette_commands(&self, s: &i18n::Strings) -> Vec<PaletteCommand> {
+        let analyze = Some(ErrSubTab::Analyze.index());
+        vec![
+            PaletteCommand::new(s.pal_err_paste, analyze, vec![KeyCode::Char('i')]),
+            PaletteCommand::new(s.pal_err_clipboard, analyze, vec![KeyCode::Char('c')]),
+            PaletteCommand::new(s.pal_err_file, analyze, vec![KeyCode::Char('f')]),
+            PaletteCommand::new(s.pal_err_ai, analyze, vec![KeyCode::Char('a')]),
+            PaletteCommand::new(s.pal_err_explain, analyze, vec![KeyCode::Char('e')]),
+            PaletteCommand::new(s.pal_err_open, analyze, vec![KeyCode::Char('o')]),
+            PaletteCommand::new(s.pal_err_tree, analyze, vec![KeyCode::Char('t')]),
+            PaletteCommand::new(s.pal_err_history, Some(ErrSubTab::History.index()), vec![]),
+            PaletteCommand::new(s.pal_err_submit, Some(ErrSubTab::Submit.index()), vec![]),
+        ]
+    }
+
     pub fn handle_key(&mut self, key: KeyEvent, lang: Language) -> Result<()> {
         // Clear expired flash
         if let Some(msg) = &self.flash_message {
@@ -336,11 +721,100 @@ impl ErrorsState {
 
         match self.active_sub_tab {
             ErrSubTab::Analyze => self.handle_analyze_key(key, lang),
+            ErrSubTab::History => {
+                self.handle_history_key(key, lang);
+                Ok(())
+            }
             ErrSubTab::Submit => self.handle_submit_key(key, lang),
         }
     }
 
+    fn handle_history_key(&mut self, key: KeyEvent, lang: Language) {
+        let len = self.history.len();
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.history_selected = (self.history_selected + 1).min(len.saturating_sub(1));
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.history_selected = self.history_selected.saturating_sub(1);
+            }
+            KeyCode::Char('g') => self.history_selected = 0,
+            KeyCode::Char('G') => self.history_selected = len.saturating_sub(1),
+            KeyCode::Enter => self.open_history_entry(lang),
+            KeyCode::Char('d') | KeyCode::Delete if self.history_selected < len => {
+                self.history.remove(self.history_selected);
+                self.history_selected = self
+                    .history_selected
+                    .min(self.history.len().saturating_sub(1));
+                if let Err(e) = history::save(&self.history) {
+                    self.show_flash(&e.to_string(), true);
+                }
+            }
+            KeyCode::Esc => self.active_sub_tab = ErrSubTab::Analyze,
+            _ => {}
+        }
+    }
+
+    /// Load the selected history entry into Analyze (without re-recording it)
+    fn open_history_entry(&mut self, lang: Language) {
+        let Some(entry) = self.history.get(self.history_selected) else {
+            return;
+        };
+        self.input_buffer = entry.text.clone();
+        self.close_explain();
+        self.ai_result = None;
+        self.ai_scroll = 0;
+        self.run_analysis(lang);
+        self.active_sub_tab = ErrSubTab::Analyze;
+    }
+
     fn handle_analyze_key(&mut self, key: KeyEvent, lang: Language) -> Result<()> {
+        if let Some(path) = &mut self.path_prompt {
+            match key.code {
+                KeyCode::Esc => self.path_prompt = None,
+                KeyCode::Enter => {
+                    let path = std::mem::take(path);
+                    self.path_prompt = None;
+                    if !path.trim().is_empty() {
+                        self.load_file(&path, lang);
+                    }
+                }
+                KeyCode::Backspace => {
+                    path.pop();
+                }
+                KeyCode::Char(c) => path.push(c),
+                _ => {}
+            }
+            return Ok(());
+        }
+        // Clipboard / file work everywhere in Analyze except while typing
+        if !self.input_mode && !self.ai_loading {
+            match key.code {
+                KeyCode::Char('c') => {
+                    self.load_clipboard(lang);
+                    return Ok(());
+                }
+                KeyCode::Char('f') => {
+                    self.path_prompt = Some(String::new());
+                    return Ok(());
+                }
+                _ => {}
+            }
+        }
+
+        if self.tree_open && !self.input_mode && !self.ai_loading {
+            self.handle_tree_key(key);
+            return Ok(());
+        }
+        if key.code == KeyCode::Char('t')
+            && !self.input_mode
+            && !self.ai_loading
+            && !self.tree.is_empty()
+        {
+            self.tree_open = true;
+            return Ok(());
+        }
+
         if self.input_mode {
             match key.code {
                 KeyCode::Esc => {
@@ -370,6 +844,9 @@ impl ErrorsState {
                 KeyCode::Char('i') | KeyCode::Char('n') | KeyCode::Enter => {
                     self.input_mode = true;
                     self.input_buffer.clear();
+                    self.location = None;
+                    self.suggestion = None;
+                    self.close_explain();
                     self.result = None;
                     self.ai_result = None;
                     self.ai_scroll = 0;
@@ -381,6 +858,7 @@ impl ErrorsState {
                 KeyCode::Char('k') | KeyCode::Up => {
                     self.ai_scroll = self.ai_scroll.saturating_sub(1);
                 }
+                KeyCode::Char('o') => self.open_location(lang),
                 _ => {}
             }
         } else if self.result.is_some() {
@@ -390,21 +868,36 @@ impl ErrorsState {
                     // New analysis
                     self.input_mode = true;
                     self.input_buffer.clear();
+                    self.location = None;
+                    self.suggestion = None;
+                    self.close_explain();
                     self.result = None;
                     self.scroll_offset = 0;
                 }
-                KeyCode::Char('j') | KeyCode::Down => {
-                    self.scroll_offset = self.scroll_offset.saturating_add(1);
-                }
-                KeyCode::Char('k') | KeyCode::Up => {
-                    self.scroll_offset = self.scroll_offset.saturating_sub(1);
+                // With the AI panel open, j/k scroll the panel
+                KeyCode::Char('j') | KeyCode::Down => match &mut self.explain {
+                    Some(panel) => panel.scroll = panel.scroll.saturating_add(1),
+                    None => self.scroll_offset = self.scroll_offset.saturating_add(1),
+                },
+                KeyCode::Char('k') | KeyCode::Up => match &mut self.explain {
+                    Some(panel) => panel.scroll = panel.scroll.saturating_sub(1),
+                    None => self.scroll_offset = self.scroll_offset.saturating_sub(1),
+                },
+                KeyCode::Char('e') => {
+                    if self.explain.is_some() {
+                        self.close_explain();
+                    } else {
+                        self.explain_requested = true;
+                    }
                 }
+                KeyCode::Esc => self.close_explain(),
                 KeyCode::Char('s') => {
                     // Switch to submit tab
                     self.submit_form = SubmitForm::default();
                     self.submit_form.error_message = self.input_buffer.clone();
                     self.active_sub_tab = ErrSubTab::Submit;
                 }
+                KeyCode::Char('o') => self.open_location(lang),
                 _ => {}
             }
         } else {
@@ -416,6 +909,9 @@ impl ErrorsState {
                 KeyCode::Char('n') => {
                     self.input_mode = true;
                     self.input_buffer.clear();
+                    self.location = None;
+                    self.suggestion = None;
+                    self.close_explain();
                     self.result = None;
                     self.ai_result = None;
                 }
@@ -430,6 +926,7 @@ impl ErrorsState {
                     self.submit_form.error_message = self.input_buffer.clone();
                     self.active_sub_tab = ErrSubTab::Submit;
                 }
+                KeyCode::Char('o') => self.open_location(lang),
                 _ => {}
             }
         }
@@ -508,6 +1005,51 @@ impl ErrorsState {
 // RENDERING
 // ════════════════════════════════════════════════════════════════════
 
+impl Module for ErrorsState {
+    fn handle_key(&mut self, key: KeyEvent, lang: Language) -> Result<()> {
+        self.handle_key(key, lang)
+    }
+
+    fn captures_input(&self) -> bool {
+        self.input_mode
+            || self.path_prompt.is_some()
+            || self.ai_loading
+            || self.active_sub_tab == ErrSubTab::Submit
+    }
+
+    fn render(&mut self, frame: &mut Frame, ctx: &RenderCtx, area: Rect) {
+        render(frame, self, ctx.theme, ctx.lang, area, ctx.ai_available);
+    }
+
+    fn poll(&mut self, _active: bool) {
+        self.poll_ai();
+    }
+
+    fn commands(&self, s: &i18n::Strings) -> Vec<PaletteCommand> {
+        self.palette_commands(s)
+    }
+
+    fn jobs(&self, s: &i18n::Strings) -> Vec<Job> {
+        self.jobs(s)
+    }
+
+    fn cancel_job(&mut self, id: &str) {
+        self.cancel_job(id)
+    }
+
+    fn sub_tab(&self) -> Option<(usize, usize)> {
+        Some((self.active_sub_tab.index(), ErrSubTab::all().len()))
+    }
+
+    fn sub_tab_label(&self, lang: Language) -> Option<&'static str> {
+        Some(self.active_sub_tab.label(lang))
+    }
+
+    fn flash_message(&mut self) -> &mut Option<FlashMessage> {
+        &mut self.flash_message
+    }
+}
+
 /// Main render function for the errors module
 pub fn render(
     frame: &mut Frame,
@@ -532,9 +1074,22 @@ pub fn render(
     // Content based on active sub-tab
     match state.active_sub_tab {
         ErrSubTab::Analyze => render_analyze(frame, state, theme, lang, layout[1], ai_available),
+        ErrSubTab::History => render_history(frame, state, theme, lang, layout[1]),
         ErrSubTab::Submit => render_submit(frame, state, theme, lang, layout[1]),
     }
 
+    if let Some(path) = &state.path_prompt {
+        let s = i18n::get_strings(lang);
+        let content = vec![
+            Line::styled(s.err_file_prompt, theme.text_dim()),
+            Line::raw(""),
+            Line::styled(format!("{}█", path), Style::default().fg(theme.accent)),
+            Line::raw(""),
+            Line::styled(s.err_file_hint, theme.text_dim()),
+        ];
+        widgets::render_popup(frame, s.err_file_title, content, &[], theme, area);
+    }
+
     // Flash message
     if let Some(msg) = &state.flash_message {
         widgets::render_flash_message(frame, &msg.text, msg.is_error, theme, area);
@@ -582,6 +1137,8 @@ fn render_analyze(
 ) {
     if state.input_mode {
         render_input(frame, state, theme, lang, area);
+    } else if state.tree_open && !state.ai_loading {
+        render_tree(frame, state, theme, lang, area);
     } else if state.ai_loading {
         render_ai_loading(frame, state, theme, lang, area);
     } else if state.ai_result.is_some() {
@@ -621,6 +1178,10 @@ fn render_idle(frame: &mut Frame, theme: &Theme, lang: Language, area: Rect) {
             format!("[i] / [Enter] → {}", s.err_start_input),
             Style::default().fg(theme.accent),
         ),
+        Line::styled(
+            format!("[c] → {}   [f] → {}", s.err_from_clipboard, s.err_from_file),
+            Style::default().fg(theme.accent),
+        ),
     ];
 
     frame.render_widget(
@@ -697,6 +1258,51 @@ fn render_input(frame: &mut Frame, state: &ErrorsState, theme: &Theme, lang: Lan
     }
 }
 
+fn render_explain_panel(
+    frame: &mut Frame,
+    panel: &ExplainPanel,
+    theme: &Theme,
+    lang: Language,
+    area: Rect,
+) {
+    let s = i18n::get_strings(lang);
+
+    let mut title = format!(" 🤖 {} ", s.err_explain_title.replace("{}", &panel.host));
+    if panel.streaming {
+        title.push_str(&format!("⏳ {} ", s.err_explain_streaming));
+    }
+
+    // Always marked: external service, may be wrong
+    let mut lines = vec![
+        Line::styled(
+            format!("⚠ {}", s.err_explain_sent.replace("{}", &panel.host)),
+            Style::default().fg(theme.warning),
+        ),
+        Line::raw(""),
+    ];
+    lines.extend(panel.text.lines().map(|l| Line::raw(l.to_string())));
+    if let Some(err) = &panel.error {
+        lines.push(Line::raw(""));
+        lines.push(Line::styled(
+            format!("✗ {}: {}", s.err_explain_failed, err),
+            Style::default().fg(theme.error),
+        ));
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .style(theme.block_style())
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.warning))
+                .title(title)
+                .title_style(Style::default().fg(theme.warning)),
+        )
+        .wrap(Wrap { trim: false })
+        .scroll((panel.scroll.min(u16::MAX as usize) as u16, 0));
+    frame.render_widget(paragraph, area);
+}
+
 fn render_result_found(
     frame: &mut Frame,
     state: &ErrorsState,
@@ -707,20 +1313,48 @@ fn render_result_found(
 ) {
     let s = i18n::get_strings(lang);
 
+    // AI panel to the right of the translation when open
+    let area = match &state.explain {
+        Some(panel) => {
+            let cols = Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)])
+                .split(area);
+            render_explain_panel(frame, panel, theme, lang, cols[1]);
+            cols[0]
+        }
+        None => area,
+    };
+
+    let config_lines = state
+        .suggestion
+        .as_ref()
+        .map(|sg| suggestion_lines(sg, theme, lang))
+        .unwrap_or_default();
+    let config_height = if config_lines.is_empty() {
+        0
+    } else {
+        (config_lines.len() as u16 + 2).min(10)
+    };
     let chunks = Layout::vertical([
-        Constraint::Length(3), // Status header
-        Constraint::Length(4), // Problem
-        Constraint::Length(6), // Solution
-        Constraint::Min(6),    // Deep dive (scrollable)
+        Constraint::Length(3),             // Status header
+        Constraint::Length(4),             // Problem
+        Constraint::Length(6),             // Solution
+        Constraint::Length(config_height), // In your config
+        Constraint::Min(6),                // Deep dive (scrollable)
     ])
     .split(area);
 
     // 1. Status header
+    // Only tag results that aren't plain NixOS, the common case
+    let ecosystem_tag = match result.ecosystem {
+        ecosystem::Ecosystem::NixOS => String::new(),
+        eco => format!(" · {} {}", eco.emoji(), eco.name()),
+    };
     let status_title = format!(
-        " ✅ {} · {} {}: {}",
+        " ✅ {} · {} {}{}: {}",
         s.err_found,
         result.category.emoji(),
         result.category.name(),
+        ecosystem_tag,
         result.title
     );
     let title = Paragraph::new(status_title).style(theme.success()).block(
@@ -732,13 +1366,17 @@ fn render_result_found(
     frame.render_widget(title, chunks[0]);
 
     // 2. Problem
+    let problem_title = match &state.location {
+        Some(loc) => format!(" 📋 {} · 📍 {} [o] ", s.err_problem, loc.short()),
+        None => format!(" 📋 {} ", s.err_problem),
+    };
     let explanation = Paragraph::new(result.explanation.as_str())
         .block(
             Block::default()
                 .style(theme.block_style())
                 .borders(Borders::ALL)
                 .border_style(theme.border())
-                .title(format!(" 📋 {} ", s.err_problem))
+                .title(problem_title)
                 .title_style(theme.text_dim()),
         )
         .wrap(Wrap { trim: true });
@@ -761,9 +1399,29 @@ fn render_result_found(
         .wrap(Wrap { trim: true });
     frame.render_widget(solution, chunks[2]);
 
-    // 4. Deep dive (scrollable)
+    // 4. Where the option or package is set in the configuration
+    if !config_lines.is_empty() {
+        let title = state
+            .suggestion
+            .as_ref()
+            .map(|sg| format!(" 🔎 {}: {} ", s.err_in_config, sg.reference.text()))
+            .unwrap_or_default();
+        frame.render_widget(
+            Paragraph::new(config_lines).block(
+                Block::default()
+                    .style(theme.block_style())
+                    .borders(Borders::ALL)
+                    .border_style(theme.border())
+                    .title(title)
+                    .title_style(theme.text_dim()),
+            ),
+            chunks[3],
+        );
+    }
+
+    // 5. Deep dive (scrollable)
     let deep_lines: Vec<&str> = result.deep_dive.lines().collect();
-    let visible_height = chunks[3].height.saturating_sub(2) as usize;
+    let visible_height = chunks[4].height.saturating_sub(2) as usize;
     let max_scroll = deep_lines.len().saturating_sub(visible_height);
     let scroll = state.scroll_offset.min(max_scroll);
 
@@ -781,7 +1439,21 @@ fn render_result_found(
         String::new()
     };
 
-    let deep_title = format!(" 📚 {} (j/k){} ", s.err_understanding, scroll_indicator);
+    let (others, frames) = tree_extras(state);
+    let tree_hint = if others + frames > 0 {
+        format!(
+            " · [t] {}",
+            s.err_tree_summary
+                .replacen("{}", &others.to_string(), 1)
+                .replacen("{}", &frames.to_string(), 1)
+        )
+    } else {
+        String::new()
+    };
+    let deep_title = format!(
+        " 📚 {} (j/k){}{} ",
+        s.err_understanding, scroll_indicator, tree_hint
+    );
 
     let deep_dive = Paragraph::new(visible_text)
         .block(
@@ -793,12 +1465,136 @@ fn render_result_found(
                 .title_style(Style::default().fg(theme.accent)),
         )
         .wrap(Wrap { trim: false });
-    frame.render_widget(deep_dive, chunks[3]);
+    frame.render_widget(deep_dive, chunks[4]);
+}
+
+/// The input as errors, warnings and notes; trace frames folded unless
+/// `tree_traces`
+fn render_tree(frame: &mut Frame, state: &ErrorsState, theme: &Theme, lang: Language, area: Rect) {
+    let s = i18n::get_strings(lang);
+    let tag = |level: tree::Level| match level {
+        tree::Level::Error => ("error", theme.error()),
+        tree::Level::Warning => ("warning", theme.warning()),
+        tree::Level::Note => ("note", Style::default().fg(theme.accent)),
+        tree::Level::Trace => ("…", theme.text_dim()),
+    };
+    let entry_lines = |entry: &tree::Entry, indent: &str, lines: &mut Vec<Line<'static>>| {
+        let (label, style) = tag(entry.level);
+        let headline_style = match entry.level {
+            tree::Level::Trace => theme.text_dim(),
+            _ => theme.text().add_modifier(Modifier::BOLD),
+        };
+        lines.push(Line::from(vec![
+            Span::raw(indent.to_string()),
+            Span::styled(format!("{} ", label), style.add_modifier(Modifier::BOLD)),
+            Span::styled(entry.headline().to_string(), headline_style),
+        ]));
+        for detail in entry.details() {
+            lines.push(Line::styled(
+                format!("{}    {}", indent, detail),
+                theme.text_dim(),
+            ));
+        }
+    };
+
+    let mut lines: Vec<Line> = Vec::new();
+    for entry in &state.tree {
+        entry_lines(entry, "", &mut lines);
+        let frames = entry.frames();
+        if frames > 0 {
+            let marker = if state.tree_traces { "▾" } else { "▸" };
+            lines.push(Line::styled(
+                format!(
+                    "  {} {}",
+                    marker,
+                    s.err_tree_frames.replace("{}", &frames.to_string())
+                ),
+                Style::default().fg(theme.accent),
+            ));
+        }
+        for child in &entry.children {
+            if child.level != tree::Level::Trace || state.tree_traces {
+                entry_lines(child, "    ", &mut lines);
+            }
+        }
+        lines.push(Line::raw(""));
+    }
+
+    let block = Block::default()
+        .style(theme.block_style())
+        .title(format!(" 🌳 {} ", s.err_tree_title))
+        .title_style(theme.title())
+        .title_bottom(Line::styled(
+            format!(" {} ", s.err_tree_hint),
+            theme.text_dim(),
+        ))
+        .borders(Borders::ALL)
+        .border_style(theme.border_focused());
+    let visible = block.inner(area).height as usize;
+    let scroll = state.tree_scroll.min(lines.len().saturating_sub(visible));
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(block)
+            .scroll((scroll.min(u16::MAX as usize) as u16, 0)),
+        area,
+    );
+}
+
+/// What `t` shows beyond the error itself, for hints: (warnings and
+/// notes, trace frames)
+fn tree_extras(state: &ErrorsState) -> (usize, usize) {
+    let others = state
+        .tree
+        .iter()
+        .filter(|e| e.level != tree::Level::Error)
+        .count();
+    let frames = state.tree.iter().map(tree::Entry::frames).sum();
+    (others, frames)
+}
+
+/// Config lines that set what the error names, and the nearest existing
+/// option names
+fn suggestion_lines<'a>(
+    suggestion: &'a suggest::Suggestion,
+    theme: &Theme,
+    lang: Language,
+) -> Vec<Line<'a>> {
+    let s = i18n::get_strings(lang);
+    let mut lines = Vec::new();
+    if !suggestion.nearest.is_empty() {
+        lines.push(Line::from(vec![
+            Span::styled(format!("💡 {} ", s.err_did_you_mean), theme.text()),
+            Span::styled(
+                suggestion.nearest.join(", "),
+                Style::default()
+                    .fg(theme.success)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]));
+    }
+    for hit in &suggestion.hits {
+        let short = SourceLocation {
+            file: hit.file.clone(),
+            line: hit.line,
+            column: None,
+        }
+        .short();
+        lines.push(Line::styled(format!("📍 {}", short), theme.text_dim()));
+        for (n, text) in &hit.snippet {
+            let style = if *n == hit.line {
+                Style::default().fg(theme.accent)
+            } else {
+                theme.text_dim()
+            };
+            lines.push(Line::styled(format!("{:>5} │ {}", n, text), style));
+        }
+    }
+    lines
 }
 
 fn render_result_not_found(
     frame: &mut Frame,
-    _state: &ErrorsState,
+    state: &ErrorsState,
     theme: &Theme,
     lang: Language,
     area: Rect,
@@ -857,6 +1653,23 @@ fn render_result_not_found(
         ),
         Span::styled(s.err_submit_pattern, theme.text()),
     ]));
+    let (others, frames) = tree_extras(state);
+    if others + frames > 0 {
+        content.push(Line::from(vec![
+            Span::styled(
+                "  [t] ",
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                s.err_tree_summary
+                    .replacen("{}", &others.to_string(), 1)
+                    .replacen("{}", &frames.to_string(), 1),
+                theme.text(),
+            ),
+        ]));
+    }
 
     let paragraph = Paragraph::new(content)
         .alignment(Alignment::Center)
@@ -980,6 +1793,111 @@ fn render_ai_result(
     frame.render_widget(response, chunks[1]);
 }
 
+// ── History ──
+
+fn render_history(
+    frame: &mut Frame,
+    state: &ErrorsState,
+    theme: &Theme,
+    lang: Language,
+    area: Rect,
+) {
+    let s = i18n::get_strings(lang);
+
+    if state.history.is_empty() {
+        let msg = Paragraph::new(vec![
+            Line::raw(""),
+            Line::styled(s.err_history_empty, theme.text_dim()),
+        ])
+        .alignment(Alignment::Center)
+        .style(theme.block_style());
+        frame.render_widget(msg, area);
+        return;
+    }
+
+    let chunks =
+        Layout::horizontal([Constraint::Percentage(45), Constraint::Percentage(55)]).split(area);
+
+    // 1. Entry list
+    let visible = chunks[0].height.saturating_sub(2) as usize;
+    let scroll = (state.history_selected + 1).saturating_sub(visible);
+    let lines: Vec<Line> = state
+        .history
+        .iter()
+        .enumerate()
+        .skip(scroll)
+        .take(visible)
+        .map(|(i, entry)| {
+            let (marker, rule, color) = match &entry.rule {
+                Some(rule) => ("✓", rule.as_str(), theme.success),
+                None => ("✗", s.err_history_no_match, theme.fg_dim),
+            };
+            let style = if i == state.history_selected {
+                theme.selected()
+            } else {
+                theme.text()
+            };
+            Line::from(vec![
+                Span::styled(format!(" {} ", entry.timestamp), theme.text_dim()),
+                Span::styled(format!("{} ", marker), Style::default().fg(color)),
+                Span::styled(rule.to_string(), style),
+            ])
+        })
+        .collect();
+    let list = Paragraph::new(lines).block(
+        Block::default()
+            .style(theme.block_style())
+            .borders(Borders::ALL)
+            .border_style(theme.border_focused())
+            .title(format!(" {} ({}) ", s.err_history, state.history.len()))
+            .title_style(theme.title()),
+    );
+    frame.render_widget(list, chunks[0]);
+
+    // 2. Selected entry
+    let Some(entry) = state.history.get(state.history_selected) else {
+        return;
+    };
+    let label = |text: &'static str| Span::styled(format!("{} ", text), theme.text_dim());
+    let mut lines = vec![
+        Line::from(vec![
+            label(s.err_history_time),
+            Span::styled(entry.timestamp.clone(), theme.text()),
+        ]),
+        Line::from(vec![
+            label(s.err_history_rule),
+            Span::styled(
+                entry.rule.as_deref().unwrap_or(s.err_history_no_match),
+                Style::default().fg(theme.accent),
+            ),
+        ]),
+    ];
+    if let Some(source) = &entry.source {
+        lines.push(Line::from(vec![
+            label(s.err_history_source),
+            Span::styled(source.clone(), theme.text()),
+        ]));
+    }
+    lines.push(Line::raw(""));
+    lines.extend(
+        entry
+            .text
+            .lines()
+            .map(|l| Line::styled(l.to_string(), theme.text())),
+    );
+    let detail = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .style(theme.block_style())
+                .borders(Borders::ALL)
+                .border_style(theme.border())
+                .title(format!(" {} ", entry.summary()))
+                .title_style(theme.text_dim()),
+        )
+        .wrap(Wrap { trim: false });
+    frame.render_widget(detail, chunks[1]);
+}
+
 // ── Submit ──
 
 fn render_submit(
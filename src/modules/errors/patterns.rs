@@ -1705,6 +1705,196 @@ configs into your home.nix rather than managing them manually.",
         tip: Some("Backup the file first, then let HM manage it"),
     },
 
+    Pattern {
+        id: "state-version-missing",
+        category: Category::NixOS,
+        regex_str: r#"The option ['`"]((?:home|system)\.stateVersion)['`"] is used but not defined"#,
+        title: "$1 is not set",
+        explanation: "The configuration doesn't say which release its stateful defaults come from.",
+        solution: "\
+# Set it to the release you FIRST installed with:
+system.stateVersion = \"24.05\";
+
+# Then rebuild:
+sudo nixos-rebuild switch",
+        deep_dive: "\
+WHY THIS HAPPENS:
+stateVersion pins defaults that affect data on disk (database formats,
+state directories, file locations). Modules read it, so it must be set.
+
+WHAT VALUE TO USE:
+The release your system (or home) was first installed with, NOT the
+current one. The installer writes it into the generated config.
+
+NEVER BUMP IT JUST TO UPGRADE:
+Changing stateVersion doesn't upgrade anything. It only changes
+defaults, which can break existing state (e.g. PostgreSQL data dirs).
+
+FINDING THE ORIGINAL VALUE:
+  grep -r stateVersion /etc/nixos/
+  git log -S stateVersion  # in your config repo",
+        tip: Some("Use the release you first installed with, never bump it to upgrade"),
+    },
+
+    Pattern {
+        id: "hm-backup-clobbered",
+        category: Category::NixOS,
+        regex_str: r#"Existing file ['`"]([^'`"]+)['`"] would be clobbered by backing up"#,
+        title: "Home-Manager backup already exists: $1",
+        explanation: "Home-Manager wants to back up a file, but the backup '$1' already exists.",
+        solution: "\
+# Remove or move the old backup:
+mv '$1' '$1.old'
+
+# Then activate again:
+home-manager switch",
+        deep_dive: "\
+WHY THIS HAPPENS:
+With backupFileExtension set, Home-Manager moves files in its way to
+<file>.<ext> before linking its own. If that backup already exists
+from an earlier activation, it refuses to overwrite it.
+
+SOLUTIONS:
+
+1. CLEAN UP THE OLD BACKUP:
+   Check what's in it, then remove or rename it.
+
+2. FIND ALL LEFTOVER BACKUPS:
+   find ~ -name '*.backup' -newer ~/.nix-profile
+
+3. AS NIXOS/DARWIN MODULE:
+   home-manager.backupFileExtension = \"backup\";
+
+WHY IT KEEPS HAPPENING:
+Some programs rewrite their config file, so Home-Manager finds a
+regular file in place of its symlink on every activation.",
+        tip: Some("Check the old backup before deleting it"),
+    },
+
+    Pattern {
+        id: "hm-activation-failed",
+        category: Category::NixOS,
+        regex_str: r"home-manager-([\w.@-]+?)\.service.*(?:[Ff]ailed|FAILURE)",
+        title: "Home-Manager activation failed for $1",
+        explanation: "The system switched, but activating the home configuration of '$1' failed.",
+        solution: "\
+# See why activation failed:
+journalctl -u home-manager-$1.service -b
+
+# Fix the cause, then retry just this user:
+sudo systemctl restart home-manager-$1.service",
+        deep_dive: "\
+WHY THIS HAPPENS:
+With Home-Manager as a NixOS module, each user's home is activated by
+a systemd unit home-manager-<user>.service. The system switch itself
+succeeds; only that user's activation failed.
+
+COMMON CAUSES:
+1. A file in the way of a Home-Manager link (see backupFileExtension)
+2. A home.activation script that exits non-zero
+3. A user service failing to (re)start
+
+READING THE LOG:
+  journalctl -u home-manager-<user>.service -b
+  The first 'Existing file' or 'error' line is the real cause.
+
+RETRY WITHOUT A FULL REBUILD:
+  sudo systemctl restart home-manager-<user>.service",
+        tip: Some("The unit's journal has the real error"),
+    },
+
+    // =========================================================================
+    // NIX-DARWIN ERRORS
+    // =========================================================================
+    Pattern {
+        id: "darwin-unexpected-etc-files",
+        category: Category::NixOS,
+        regex_str: r"Unexpected files in /etc, aborting activation",
+        title: "nix-darwin refuses to overwrite files in /etc",
+        explanation: "Files in /etc that nix-darwin wants to manage already exist with unknown content.",
+        solution: "\
+# Rename each listed file, e.g.:
+sudo mv /etc/nix/nix.conf /etc/nix/nix.conf.before-nix-darwin
+
+# Then activate again:
+sudo darwin-rebuild switch --flake .",
+        deep_dive: "\
+WHY THIS HAPPENS:
+nix-darwin links its managed files into /etc. When a file exists that
+it didn't create (e.g. written by the Nix installer), it stops instead
+of silently overwriting it.
+
+SOLUTION:
+The error lists the files. Check there's nothing important in them,
+then rename each with the suffix .before-nix-darwin, which nix-darwin
+recognizes.
+
+COMMON FILES:
+  /etc/nix/nix.conf        (from the Nix installer)
+  /etc/bashrc, /etc/zshrc  (shell hooks from the installer)
+
+MOVE YOUR SETTINGS OVER:
+Settings from the old nix.conf belong in nix.settings in your
+nix-darwin config.",
+        tip: Some("Rename the files with the .before-nix-darwin suffix"),
+    },
+
+    Pattern {
+        id: "darwin-primary-user",
+        category: Category::NixOS,
+        regex_str: r"system\.primaryUser",
+        title: "nix-darwin needs system.primaryUser",
+        explanation: "Some options apply to a user, but activation now runs as root and doesn't know which user.",
+        solution: "\
+# In your darwin configuration:
+system.primaryUser = \"yourname\";
+
+# Activation runs as root now:
+sudo darwin-rebuild switch --flake .",
+        deep_dive: "\
+WHY THIS HAPPENS:
+nix-darwin moved all activation to root. Options that used to apply
+to whoever ran darwin-rebuild (homebrew, system.defaults, launchd user
+agents) now need to know their user explicitly.
+
+SOLUTION:
+Set system.primaryUser to your macOS user name. The error message
+lists the options that triggered the requirement.
+
+LONGER TERM:
+Per-user settings are moving to Home-Manager or user-scoped options;
+system.primaryUser is the bridge until then.",
+        tip: Some("Activation runs as root now: use sudo darwin-rebuild"),
+    },
+
+    Pattern {
+        id: "darwin-rebuild-not-found",
+        category: Category::NixOS,
+        regex_str: r"darwin-rebuild: (?:command )?not found|command not found: darwin-rebuild",
+        title: "darwin-rebuild not found",
+        explanation: "nix-darwin isn't installed yet, or its profile isn't on PATH.",
+        solution: "\
+# First activation, bootstraps darwin-rebuild:
+sudo nix run nix-darwin -- switch --flake .
+
+# Afterwards:
+sudo darwin-rebuild switch --flake .",
+        deep_dive: "\
+WHY THIS HAPPENS:
+darwin-rebuild comes from nix-darwin itself. Before the first
+activation it doesn't exist anywhere on PATH.
+
+FIRST INSTALL:
+  sudo nix run nix-darwin -- switch --flake .#<hostname>
+
+ALREADY INSTALLED?
+Open a new terminal: /run/current-system/sw/bin is added to PATH by
+the shell init files nix-darwin manages (/etc/zshrc, /etc/bashrc).
+If those were replaced (e.g. by a macOS update), activate once via
+nix run again.",
+        tip: Some("Bootstrap once with: nix run nix-darwin -- switch"),
+    },
+
     // =========================================================================
     // FUNCTION / ARGUMENT ERRORS
     // =========================================================================
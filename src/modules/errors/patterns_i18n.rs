@@ -935,6 +935,182 @@ LÖSUNGEN:
         },
     );
 
+    m.insert(
+        "state-version-missing",
+        PatternTranslation {
+            title: "$1 ist nicht gesetzt",
+            explanation: "Die Konfiguration legt nicht fest, aus welchem Release ihre zustandsbehafteten Defaults stammen.",
+            solution: "\
+# Auf das Release setzen, mit dem du ZUERST installiert hast:
+system.stateVersion = \"24.05\";
+
+# Dann neu bauen:
+sudo nixos-rebuild switch",
+            deep_dive: "\
+WARUM PASSIERT DAS:
+stateVersion legt Defaults fest, die Daten auf der Platte betreffen
+(Datenbankformate, State-Verzeichnisse). Module lesen den Wert, daher
+muss er gesetzt sein.
+
+WELCHER WERT:
+Das Release der Erstinstallation, NICHT das aktuelle. Der Installer
+schreibt ihn in die generierte Konfiguration.
+
+NIE ZUM UPGRADEN ERHÖHEN:
+stateVersion zu ändern aktualisiert nichts, sondern ändert nur
+Defaults und kann bestehenden State kaputt machen.
+
+URSPRÜNGLICHEN WERT FINDEN:
+  grep -r stateVersion /etc/nixos/
+  git log -S stateVersion  # im Config-Repo",
+            tip: Some("Release der Erstinstallation nutzen, nie zum Upgraden erhöhen"),
+        },
+    );
+
+    m.insert(
+        "hm-backup-clobbered",
+        PatternTranslation {
+            title: "Home-Manager Backup existiert bereits: $1",
+            explanation:
+                "Home-Manager will eine Datei sichern, aber das Backup '$1' existiert schon.",
+            solution: "\
+# Altes Backup entfernen oder verschieben:
+mv '$1' '$1.old'
+
+# Dann erneut aktivieren:
+home-manager switch",
+            deep_dive: "\
+WARUM PASSIERT DAS:
+Mit backupFileExtension verschiebt Home-Manager Dateien, die im Weg
+sind, nach <datei>.<ext>. Existiert dieses Backup schon von einer
+früheren Aktivierung, überschreibt HM es nicht.
+
+LÖSUNGEN:
+1. Altes Backup prüfen, dann löschen oder umbenennen
+2. Übrige Backups finden:
+   find ~ -name '*.backup' -newer ~/.nix-profile
+
+WARUM ES IMMER WIEDER PASSIERT:
+Manche Programme schreiben ihre Config neu, dann findet HM bei jeder
+Aktivierung eine echte Datei statt seines Symlinks.",
+            tip: Some("Altes Backup vor dem Löschen prüfen"),
+        },
+    );
+
+    m.insert(
+        "hm-activation-failed",
+        PatternTranslation {
+            title: "Home-Manager Aktivierung für $1 fehlgeschlagen",
+            explanation: "Das System wurde umgeschaltet, aber die Home-Konfiguration von '$1' ließ sich nicht aktivieren.",
+            solution: "\
+# Ursache ansehen:
+journalctl -u home-manager-$1.service -b
+
+# Ursache beheben, dann nur diesen Benutzer neu aktivieren:
+sudo systemctl restart home-manager-$1.service",
+            deep_dive: "\
+WARUM PASSIERT DAS:
+Mit Home-Manager als NixOS-Modul aktiviert je Benutzer eine
+systemd-Unit home-manager-<user>.service dessen Home. Der
+System-Switch selbst klappt, nur diese Aktivierung schlug fehl.
+
+HÄUFIGE URSACHEN:
+1. Eine Datei steht einem HM-Link im Weg (siehe backupFileExtension)
+2. Ein home.activation Skript endet mit Fehler
+3. Ein User-Service startet nicht
+
+LOG LESEN:
+  journalctl -u home-manager-<user>.service -b
+  Die erste 'Existing file' oder 'error' Zeile ist die Ursache.",
+            tip: Some("Das Journal der Unit enthält den eigentlichen Fehler"),
+        },
+    );
+
+    // =========================================================================
+    // NIX-DARWIN
+    // =========================================================================
+    m.insert(
+        "darwin-unexpected-etc-files",
+        PatternTranslation {
+            title: "nix-darwin überschreibt keine Dateien in /etc",
+            explanation: "Dateien in /etc, die nix-darwin verwalten will, existieren bereits mit unbekanntem Inhalt.",
+            solution: "\
+# Jede aufgelistete Datei umbenennen, z.B.:
+sudo mv /etc/nix/nix.conf /etc/nix/nix.conf.before-nix-darwin
+
+# Dann erneut aktivieren:
+sudo darwin-rebuild switch --flake .",
+            deep_dive: "\
+WARUM PASSIERT DAS:
+nix-darwin verlinkt seine Dateien nach /etc. Existiert dort eine
+Datei, die es nicht angelegt hat (z.B. vom Nix-Installer), bricht
+es ab statt sie still zu überschreiben.
+
+LÖSUNG:
+Die Fehlermeldung listet die Dateien. Prüfen, dann jede mit der
+Endung .before-nix-darwin umbenennen.
+
+HÄUFIGE DATEIEN:
+  /etc/nix/nix.conf        (vom Nix-Installer)
+  /etc/bashrc, /etc/zshrc  (Shell-Hooks vom Installer)
+
+Einstellungen aus der alten nix.conf gehören nach nix.settings.",
+            tip: Some("Dateien mit der Endung .before-nix-darwin umbenennen"),
+        },
+    );
+
+    m.insert(
+        "darwin-primary-user",
+        PatternTranslation {
+            title: "nix-darwin braucht system.primaryUser",
+            explanation: "Einige Optionen gelten für einen Benutzer, aber die Aktivierung läuft jetzt als root.",
+            solution: "\
+# In deiner darwin-Konfiguration:
+system.primaryUser = \"deinname\";
+
+# Aktivierung läuft jetzt als root:
+sudo darwin-rebuild switch --flake .",
+            deep_dive: "\
+WARUM PASSIERT DAS:
+nix-darwin aktiviert alles als root. Optionen, die früher für den
+Aufrufer von darwin-rebuild galten (homebrew, system.defaults,
+launchd User-Agents), brauchen jetzt ihren Benutzer explizit.
+
+LÖSUNG:
+system.primaryUser auf deinen macOS-Benutzernamen setzen. Die
+Fehlermeldung listet die auslösenden Optionen.",
+            tip: Some("Aktivierung läuft als root: sudo darwin-rebuild nutzen"),
+        },
+    );
+
+    m.insert(
+        "darwin-rebuild-not-found",
+        PatternTranslation {
+            title: "darwin-rebuild nicht gefunden",
+            explanation: "nix-darwin ist noch nicht installiert oder sein Profil nicht im PATH.",
+            solution: "\
+# Erste Aktivierung, installiert darwin-rebuild:
+sudo nix run nix-darwin -- switch --flake .
+
+# Danach:
+sudo darwin-rebuild switch --flake .",
+            deep_dive: "\
+WARUM PASSIERT DAS:
+darwin-rebuild kommt von nix-darwin selbst. Vor der ersten
+Aktivierung existiert es nirgends im PATH.
+
+ERSTINSTALLATION:
+  sudo nix run nix-darwin -- switch --flake .#<hostname>
+
+SCHON INSTALLIERT?
+Neues Terminal öffnen: /run/current-system/sw/bin kommt über die von
+nix-darwin verwalteten Shell-Init-Dateien in den PATH. Wurden die
+(z.B. durch ein macOS-Update) ersetzt, einmal erneut per nix run
+aktivieren.",
+            tip: Some("Einmalig mit: nix run nix-darwin -- switch"),
+        },
+    );
+
     // =========================================================================
     // FUNCTION / ARGUMENT ERRORS
     // =========================================================================
@@ -3176,12 +3352,14 @@ pub fn translate(result: &MatchResult, lang: &str) -> MatchResult {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::modules::errors::ecosystem::Ecosystem;
     use crate::modules::errors::patterns::Category;
 
     fn make_test_result() -> MatchResult {
         MatchResult {
             pattern_id: "linker-missing-lib".to_string(),
             category: Category::Build,
+            ecosystem: Ecosystem::NixOS,
             title: "Linker cannot find library: ssl".to_string(),
             explanation: "The linker needs the 'ssl' library.".to_string(),
             solution: "buildInputs = [ ssl ];".to_string(),
@@ -0,0 +1,256 @@
+//! Suggestions that read the user's configuration.
+//!
+//! When an error names an option ("The option `services.foo.bar' does not
+//! exist") or a package ("undefined variable 'firefx'"), the configuration
+//! tree is searched for where it is set and those lines are shown next to
+//! the translation. For options the cached Options Explorer index gives
+//! the nearest existing names, so a typo or a renamed option comes with
+//! its correction.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::path::{Path, PathBuf};
+
+static OPTION: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"[Tt]he option [`'‘"]([A-Za-z0-9_.\-"<>*]+)['’`"]"#).unwrap());
+static VARIABLE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"undefined variable '([A-Za-z0-9_\-]+)'").unwrap());
+static ATTRIBUTE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"attribute '([A-Za-z0-9_\-]+)' missing").unwrap());
+
+/// Files searched at most, so a huge home directory flake stays quick
+const MAX_FILES: usize = 500;
+const MAX_HITS: usize = 3;
+const MAX_NEAREST: usize = 3;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reference {
+    Option(String),
+    /// A package or other attribute name
+    Name(String),
+}
+
+impl Reference {
+    pub fn text(&self) -> &str {
+        match self {
+            Reference::Option(path) | Reference::Name(path) => path,
+        }
+    }
+}
+
+/// A place in the configuration, with the lines around it
+#[derive(Debug, Clone)]
+pub struct ConfigHit {
+    pub file: String,
+    pub line: u32,
+    pub snippet: Vec<(u32, String)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Suggestion {
+    pub reference: Reference,
+    pub hits: Vec<ConfigHit>,
+    /// Existing option names closest to a missing one
+    pub nearest: Vec<String>,
+}
+
+/// The option or name the error is about
+pub fn reference(text: &str) -> Option<Reference> {
+    if let Some(c) = OPTION.captures(text) {
+        return Some(Reference::Option(c[1].to_string()));
+    }
+    VARIABLE
+        .captures(text)
+        .or_else(|| ATTRIBUTE.captures(text))
+        .map(|c| Reference::Name(c[1].to_string()))
+}
+
+/// Where `reference` is set below `root`, and for options the nearest
+/// names in `options` (the cached index; None when it was never built)
+pub fn suggest(reference: Reference, root: &Path, options: Option<&[String]>) -> Suggestion {
+    let mut files = Vec::new();
+    collect_nix_files(root, 6, &mut files);
+    let hits = match &reference {
+        Reference::Option(path) => find_option(&files, path),
+        Reference::Name(name) => find_lines(&files, |line| contains_word(line, name)),
+    };
+    let nearest = match (&reference, options) {
+        (Reference::Option(path), Some(options)) => nearest(path, options),
+        _ => Vec::new(),
+    };
+    Suggestion {
+        reference,
+        hits,
+        nearest,
+    }
+}
+
+fn collect_nix_files(dir: &Path, depth: usize, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let mut entries: Vec<_> = entries.flatten().map(|e| e.path()).collect();
+    entries.sort();
+    for path in entries {
+        if files.len() >= MAX_FILES {
+            return;
+        }
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        if name.starts_with('.') || name == "result" {
+            continue;
+        }
+        if path.is_dir() {
+            if depth > 0 {
+                collect_nix_files(&path, depth - 1, files);
+            }
+        } else if name.ends_with(".nix") {
+            files.push(path);
+        }
+    }
+}
+
+/// The full path when it's written out, else its last part assigned in a
+/// file that also mentions the part above it (`services.foo = { bar = …`)
+fn find_option(files: &[PathBuf], path: &str) -> Vec<ConfigHit> {
+    let hits = find_lines(files, |line| line.contains(path));
+    if !hits.is_empty() {
+        return hits;
+    }
+    let mut parts = path.rsplit('.');
+    let (Some(last), Some(parent)) = (parts.next(), parts.next()) else {
+        return hits;
+    };
+    let nested: Vec<PathBuf> = files
+        .iter()
+        .filter(|f| std::fs::read_to_string(f).is_ok_and(|t| contains_word(&t, parent)))
+        .cloned()
+        .collect();
+    find_lines(&nested, |line| {
+        line.trim_start()
+            .strip_prefix(last)
+            .is_some_and(|rest| rest.trim_start().starts_with('='))
+    })
+}
+
+fn find_lines(files: &[PathBuf], matches: impl Fn(&str) -> bool) -> Vec<ConfigHit> {
+    let mut hits = Vec::new();
+    for file in files {
+        let Ok(text) = std::fs::read_to_string(file) else {
+            continue;
+        };
+        let lines: Vec<&str> = text.lines().collect();
+        for (i, line) in lines.iter().enumerate() {
+            if line.trim_start().starts_with('#') || !matches(line) {
+                continue;
+            }
+            let from = i.saturating_sub(1);
+            let to = (i + 2).min(lines.len());
+            hits.push(ConfigHit {
+                file: file.to_string_lossy().to_string(),
+                line: i as u32 + 1,
+                snippet: (from..to)
+                    .map(|n| (n as u32 + 1, lines[n].to_string()))
+                    .collect(),
+            });
+            if hits.len() >= MAX_HITS {
+                return hits;
+            }
+        }
+    }
+    hits
+}
+
+/// `name` as a whole identifier (not inside `firefox-esr` for `firefox`)
+fn contains_word(text: &str, name: &str) -> bool {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_' || c == '-';
+    text.match_indices(name).any(|(i, _)| {
+        let before = text[..i].chars().next_back();
+        let after = text[i + name.len()..].chars().next();
+        !before.is_some_and(is_ident) && !after.is_some_and(is_ident)
+    })
+}
+
+/// Existing option names within a few edits of `path`, closest first;
+/// an option of the same module ending in the same name (moved into
+/// `settings`, say) counts as just within reach
+pub fn nearest(path: &str, options: &[String]) -> Vec<String> {
+    let limit = (path.len() / 6).max(2);
+    let leaf = |p: &str| p.rsplit('.').next().unwrap_or_default().to_lowercase();
+    let module = |p: &str| p.split('.').take(2).collect::<Vec<_>>().join(".");
+    let (path_leaf, path_module) = (leaf(path), module(path));
+    let mut scored: Vec<(usize, &String)> = options
+        .iter()
+        .filter(|o| o.as_str() != path)
+        .filter_map(|o| {
+            if o.len().abs_diff(path.len()) <= limit {
+                let d = distance(path, o);
+                if d <= limit {
+                    return Some((d, o));
+                }
+            }
+            (leaf(o) == path_leaf && module(o) == path_module).then_some((limit, o))
+        })
+        .collect();
+    scored.sort();
+    scored
+        .into_iter()
+        .take(MAX_NEAREST)
+        .map(|(_, o)| o.clone())
+        .collect()
+}
+
+/// Levenshtein distance
+fn distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cur = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                prev
+            } else {
+                1 + prev.min(cur).min(row[j])
+            };
+            prev = cur;
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reference_and_nearest() {
+        assert_eq!(
+            reference("error: The option `services.openssh.permitRootLogin' does not exist."),
+            Some(Reference::Option("services.openssh.permitRootLogin".into()))
+        );
+        assert_eq!(
+            reference("error: undefined variable 'firefx'"),
+            Some(Reference::Name("firefx".into()))
+        );
+
+        let options: Vec<String> = [
+            "services.openssh.settings.PermitRootLogin",
+            "services.openssh.enable",
+            "services.nginx.enable",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        assert_eq!(
+            nearest("services.opensh.enable", &options),
+            vec!["services.openssh.enable".to_string()]
+        );
+        assert_eq!(
+            nearest("services.openssh.permitRootLogin", &options),
+            vec!["services.openssh.settings.PermitRootLogin".to_string()]
+        );
+        assert!(contains_word("with pkgs; [ firefx git ]", "firefx"));
+        assert!(!contains_word("firefox-esr", "firefox"));
+    }
+}
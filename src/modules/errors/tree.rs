@@ -0,0 +1,183 @@
+//! Structure of nix's error output.
+//!
+//! What gets pasted is rarely one error: evaluation warnings come first,
+//! then `error:` with a trace of `… while evaluating` frames and, indented
+//! below them, the error that actually happened with its position and code
+//! excerpt. `parse` splits that into top-level entries (error, warning,
+//! note) whose nested messages and trace frames are children; indentation
+//! tells a nested `error:` from the next top-level one. Only the errors are
+//! matched against the patterns, the rest stays browsable (`t`).
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Error,
+    Warning,
+    Note,
+    /// `… while evaluating` frame of an error's trace
+    Trace,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub level: Level,
+    /// Headline first (without its `error:` / `warning:` prefix; may be
+    /// empty), then detail lines as printed
+    pub lines: Vec<String>,
+    pub children: Vec<Entry>,
+}
+
+impl Entry {
+    fn new(level: Level, headline: &str) -> Self {
+        Self {
+            level,
+            lines: vec![headline.trim().to_string()],
+            children: Vec::new(),
+        }
+    }
+
+    pub fn headline(&self) -> &str {
+        self.lines.first().map_or("", String::as_str)
+    }
+
+    pub fn details(&self) -> &[String] {
+        self.lines.get(1..).unwrap_or_default()
+    }
+
+    pub fn frames(&self) -> usize {
+        self.children
+            .iter()
+            .filter(|c| c.level == Level::Trace)
+            .count()
+    }
+}
+
+/// `(level, rest)` of a line that starts an entry
+fn tag(line: &str, in_error: bool) -> Option<(Level, &str)> {
+    let plain = line.strip_prefix("trace:").map(str::trim_start);
+    let rest = plain.unwrap_or(line);
+    for (prefix, level) in [
+        ("evaluation warning:", Level::Warning),
+        ("warning:", Level::Warning),
+        ("error:", Level::Error),
+        ("note:", Level::Note),
+    ] {
+        if let Some(rest) = rest.strip_prefix(prefix) {
+            return Some((level, rest));
+        }
+    }
+    // Output of builtins.trace
+    if let Some(rest) = plain {
+        return Some((Level::Note, rest));
+    }
+    if in_error {
+        if let Some(rest) = line.strip_prefix('…').or_else(|| line.strip_prefix("...")) {
+            return Some((Level::Trace, rest));
+        }
+        if line.starts_with("(stack trace truncated") {
+            return Some((Level::Note, line));
+        }
+    }
+    None
+}
+
+/// Split error output into entries. Text without any `error:` / `warning:`
+/// is one error.
+pub fn parse(text: &str) -> Vec<Entry> {
+    let mut entries: Vec<Entry> = Vec::new();
+    for raw in text.lines() {
+        let line = raw.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let indented = raw.starts_with(char::is_whitespace);
+        let in_error = indented && entries.last().is_some_and(|e| e.level == Level::Error);
+        match (tag(line, in_error), entries.last_mut()) {
+            (Some((level, rest)), Some(error)) if in_error => {
+                error.children.push(Entry::new(level, rest));
+            }
+            (Some((level, rest)), _) => entries.push(Entry::new(level, rest)),
+            // Build output and the like before the first entry
+            (None, None) => {}
+            (None, Some(last)) => {
+                let target = match last.children.last_mut() {
+                    Some(child) if in_error => child,
+                    _ => last,
+                };
+                target.lines.push(line.to_string());
+            }
+        }
+    }
+    if entries.is_empty() && !text.trim().is_empty() {
+        let mut lines = text.lines().map(str::trim).filter(|l| !l.is_empty());
+        let mut entry = Entry::new(Level::Error, lines.next().unwrap_or(""));
+        entry.lines.extend(lines.map(String::from));
+        entries.push(entry);
+    }
+    entries
+}
+
+/// The errors with their nested messages, without warnings and trace
+/// frames: what gets translated. None when the input has only warnings
+/// and notes.
+pub fn error_text(entries: &[Entry]) -> Option<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for error in entries.iter().filter(|e| e.level == Level::Error) {
+        lines.push(format!("error: {}", error.headline()));
+        lines.extend(error.details().iter().cloned());
+        for child in error.children.iter().filter(|c| c.level == Level::Error) {
+            lines.push(format!("error: {}", child.headline()));
+            lines.extend(child.details().iter().cloned());
+        }
+    }
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_error_tree() {
+        let text = "\
+building the system configuration...
+trace: evaluation warning: The option `services.xserver.layout' has been renamed
+error:
+       … while calling the 'derivationStrict' builtin
+         at /builtin/derivation.nix:9:12: (source not available)
+
+       … while evaluating attribute 'buildCommand' of derivation 'system-path'
+
+       (stack trace truncated; use '--show-trace' to show the full, detailed trace)
+
+       error: undefined variable 'foo'
+       at /etc/nixos/configuration.nix:10:5:
+            9|
+           10|   foo
+";
+        let entries = parse(text);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].level, Level::Warning);
+        assert!(entries[0].headline().starts_with("The option"));
+
+        let error = &entries[1];
+        assert_eq!(error.level, Level::Error);
+        assert_eq!(error.headline(), "");
+        assert_eq!(error.frames(), 2);
+        assert_eq!(error.children[0].details().len(), 1);
+        assert_eq!(error.children[2].level, Level::Note);
+        let nested = &error.children[3];
+        assert_eq!(nested.level, Level::Error);
+        assert_eq!(nested.headline(), "undefined variable 'foo'");
+        assert_eq!(nested.details()[0], "at /etc/nixos/configuration.nix:10:5:");
+
+        let translated = error_text(&entries).unwrap();
+        assert!(translated.contains("undefined variable 'foo'"));
+        assert!(!translated.contains("derivationStrict"));
+        assert!(!translated.contains("renamed"));
+
+        assert_eq!(
+            parse("infinite recursion encountered")[0].level,
+            Level::Error
+        );
+    }
+}
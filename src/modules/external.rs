@@ -0,0 +1,237 @@
+//! External modules: user-defined tabs showing a command's output
+//!
+//! Each `[[external_modules]]` entry in config.toml becomes a tab after
+//! the built-in ones, reached with F1–F12 in order, the sidebar or the
+//! palette. The command runs through `sh -c` in the background when the
+//! tab is opened, on `r`, and every `refresh_secs` while the tab is shown;
+//! its output (colors stripped) scrolls with j/k/g/G. That is enough for
+//! your own dashboards (backups, VPN peers, a homelab health script)
+//! without forking nixmate.
+
+use crate::config::{ExternalModuleConfig, Language};
+use crate::i18n::{self, Strings};
+use crate::modules::jobs::Job;
+use crate::modules::module::{Module, RenderCtx};
+use crate::modules::palette::PaletteCommand;
+use crate::types::FlashMessage;
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use once_cell::sync::Lazy;
+use ratatui::{
+    layout::Rect,
+    style::Style,
+    text::Line,
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+use regex::Regex;
+use std::sync::mpsc;
+use std::time::Instant;
+
+/// Longest a command may run before it's given up on
+const TIMEOUT_SECS: u64 = 60;
+
+static ANSI: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\x1b\[[0-9;?]*[A-Za-z]|\x1b\][^\x07]*\x07").unwrap());
+
+/// Output lines, or why the command failed (with what it printed)
+type RunResult = Result<Vec<String>, (String, Vec<String>)>;
+
+pub struct ExternalModule {
+    pub config: ExternalModuleConfig,
+    pub output: Option<RunResult>,
+    pub loaded_at: Option<Instant>,
+    rx: Option<mpsc::Receiver<RunResult>>,
+    scroll: usize,
+    pub flash_message: Option<FlashMessage>,
+}
+
+impl ExternalModule {
+    pub fn new(config: ExternalModuleConfig) -> Self {
+        Self {
+            config,
+            output: None,
+            loaded_at: None,
+            rx: None,
+            scroll: 0,
+            flash_message: None,
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.rx.is_some()
+    }
+
+    fn run(&mut self) {
+        if self.is_running() {
+            return;
+        }
+        let (tx, rx) = mpsc::channel();
+        self.rx = Some(rx);
+        let command = self.config.command.clone();
+        std::thread::spawn(move || {
+            let _ = tx.send(run_command(&command));
+        });
+    }
+
+    fn lines(&self) -> &[String] {
+        match &self.output {
+            Some(Ok(lines)) | Some(Err((_, lines))) => lines,
+            None => &[],
+        }
+    }
+}
+
+/// Blocking — run in background thread!
+fn run_command(command: &str) -> RunResult {
+    let Some(output) =
+        crate::nix::storage::output_with_timeout("sh", &["-c", command], TIMEOUT_SECS)
+    else {
+        return Err((format!("timeout after {}s", TIMEOUT_SECS), Vec::new()));
+    };
+    let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+    text.push_str(&String::from_utf8_lossy(&output.stderr));
+    let lines = clean_lines(&text);
+    if output.status.success() {
+        Ok(lines)
+    } else {
+        let status = match output.status.code() {
+            Some(code) => format!("exit status {}", code),
+            None => "killed".to_string(),
+        };
+        Err((status, lines))
+    }
+}
+
+/// Output split into lines, escape sequences and trailing blank lines removed
+fn clean_lines(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = ANSI
+        .replace_all(text, "")
+        .lines()
+        .map(|l| l.replace('\t', "    ").trim_end().to_string())
+        .collect();
+    while lines.last().is_some_and(|l| l.is_empty()) {
+        lines.pop();
+    }
+    lines
+}
+
+impl Module for ExternalModule {
+    fn handle_key(&mut self, key: KeyEvent, _lang: Language) -> Result<()> {
+        let last = self.lines().len().saturating_sub(1);
+        match key.code {
+            KeyCode::Char('r') => self.run(),
+            KeyCode::Char('j') | KeyCode::Down => self.scroll = (self.scroll + 1).min(last),
+            KeyCode::Char('k') | KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::PageDown => self.scroll = (self.scroll + 20).min(last),
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(20),
+            KeyCode::Char('g') => self.scroll = 0,
+            KeyCode::Char('G') => self.scroll = last,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn render(&mut self, frame: &mut Frame, ctx: &RenderCtx, area: Rect) {
+        let theme = ctx.theme;
+        let s = i18n::get_strings(ctx.lang);
+
+        let block = Block::default()
+            .style(theme.block_style())
+            .title(format!(" {} ", self.config.name))
+            .title_style(theme.title())
+            .borders(Borders::ALL)
+            .border_style(theme.border_focused());
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let mut lines = vec![Line::styled(
+            format!("$ {}", self.config.command),
+            theme.text_dim(),
+        )];
+        if self.is_running() {
+            lines.push(Line::styled(
+                format!("{} {}", crate::modules::jobs::spinner(), s.ext_running),
+                Style::default().fg(theme.accent),
+            ));
+        }
+        if let Some(Err((why, _))) = &self.output {
+            lines.push(Line::styled(
+                s.ext_failed.replace("{}", why),
+                Style::default().fg(theme.error),
+            ));
+        }
+        if matches!(&self.output, Some(Ok(out)) if out.is_empty()) {
+            lines.push(Line::styled(s.ext_no_output, theme.text_dim()));
+        }
+        lines.push(Line::raw(""));
+
+        let room = (inner.height as usize).saturating_sub(lines.len());
+        let start = self.scroll.min(self.lines().len().saturating_sub(room));
+        lines.extend(
+            self.lines()
+                .iter()
+                .skip(start)
+                .take(room)
+                .map(|l| Line::styled(l.clone(), theme.text())),
+        );
+        frame.render_widget(Paragraph::new(lines), inner);
+    }
+
+    fn activate(&mut self) {
+        if self.output.is_none() {
+            self.run();
+        }
+    }
+
+    fn poll(&mut self, active: bool) {
+        if let Some(rx) = &self.rx {
+            match rx.try_recv() {
+                Ok(result) => {
+                    self.output = Some(result);
+                    self.loaded_at = Some(Instant::now());
+                    self.rx = None;
+                }
+                Err(mpsc::TryRecvError::Empty) => {}
+                Err(mpsc::TryRecvError::Disconnected) => self.rx = None,
+            }
+        }
+        let due = self.config.refresh_secs > 0
+            && self
+                .loaded_at
+                .is_some_and(|at| at.elapsed().as_secs() >= self.config.refresh_secs);
+        if active && due {
+            self.run();
+        }
+    }
+
+    fn commands(&self, s: &Strings) -> Vec<PaletteCommand> {
+        vec![PaletteCommand::new(
+            s.pal_ext_run,
+            None,
+            vec![KeyCode::Char('r')],
+        )]
+    }
+
+    fn jobs(&self, s: &Strings) -> Vec<Job> {
+        if !self.is_running() {
+            return Vec::new();
+        }
+        vec![Job::new("external", s.ext_running, false).detail(self.config.name.clone())]
+    }
+
+    fn flash_message(&mut self) -> &mut Option<FlashMessage> {
+        &mut self.flash_message
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_lines() {
+        let text = "\x1b[1;32mok\x1b[0m backups\n\tlast: 2h ago   \n\n\n";
+        assert_eq!(clean_lines(text), vec!["ok backups", "    last: 2h ago"]);
+    }
+}
@@ -0,0 +1,251 @@
+//! Switching the nixpkgs input between release branches.
+//!
+//! Offers the last few releases around the current ref plus unstable,
+//! rewrites the ref through the usual flake.nix edit (and re-lock), then
+//! estimates the impact with `nixos-rebuild dry-build`: what would be built
+//! or fetched, and which packages change version against the running system.
+
+use super::FlakeInput;
+use crate::nix::availability::Platform;
+use chrono::{Datelike, NaiveDate};
+use std::collections::HashMap;
+use std::process::Command;
+
+/// Releases offered besides unstable
+const RELEASES_SHOWN: usize = 3;
+
+/// Whether `input` is nixpkgs (any fork named nixpkgs counts)
+pub fn is_nixpkgs(input: &FlakeInput) -> bool {
+    input.repo == "nixpkgs" || input.url.contains("nixpkgs")
+}
+
+/// Branches to offer for a nixpkgs input currently on `current`, oldest
+/// first and unstable last. Keeps the naming of the current ref
+/// (`nixos-24.05`, `nixpkgs-24.05-darwin`, `release-24.05`).
+pub fn choices(current: &str, today: NaiveDate) -> Vec<String> {
+    let (prefix, suffix) = naming(current);
+    let mut releases: Vec<(i32, u32)> = Vec::new();
+    for year in 2020..=today.year() {
+        for month in [5, 11] {
+            // Releases land at the end of their month
+            if NaiveDate::from_ymd_opt(year, month, 28).is_some_and(|d| d <= today) {
+                releases.push((year % 100, month));
+            }
+        }
+    }
+    let shown = releases.len().saturating_sub(RELEASES_SHOWN);
+    let mut branches: Vec<String> = releases[shown..]
+        .iter()
+        .map(|(y, m)| format!("{}-{:02}.{:02}{}", prefix, y, m, suffix))
+        .collect();
+    // Keep an older current release visible, so it can be seen and kept
+    if release_of(current).is_some() && !branches.iter().any(|b| b == current) {
+        branches.insert(0, current.to_string());
+    }
+    branches.push(match prefix {
+        "release" => "master".to_string(),
+        "nixpkgs" => format!("nixpkgs-unstable{}", suffix),
+        _ => "nixos-unstable".to_string(),
+    });
+    branches
+}
+
+/// (prefix, suffix) of a branch name, defaulting to `nixos-…`
+fn naming(branch: &str) -> (&'static str, &'static str) {
+    let prefix = if branch.starts_with("nixpkgs-") {
+        "nixpkgs"
+    } else if branch.starts_with("release-") {
+        "release"
+    } else {
+        "nixos"
+    };
+    let suffix = if branch.ends_with("-darwin") {
+        "-darwin"
+    } else {
+        ""
+    };
+    (prefix, suffix)
+}
+
+/// "24.11" for nixos-24.11 / nixpkgs-24.11-darwin / release-24.11
+pub fn release_of(branch: &str) -> Option<&str> {
+    let version = branch.split('-').nth(1)?;
+    let (year, month) = version.split_once('.')?;
+    (year.len() == 2
+        && month.len() == 2
+        && year
+            .bytes()
+            .chain(month.bytes())
+            .all(|b| b.is_ascii_digit()))
+    .then_some(version)
+}
+
+/// Release notes for the target branch
+pub fn release_notes_url(branch: &str) -> String {
+    match release_of(branch) {
+        Some(version) => format!(
+            "https://nixos.org/manual/nixos/stable/release-notes#sec-release-{}",
+            version
+        ),
+        None => "https://nixos.org/manual/nixos/unstable/release-notes".to_string(),
+    }
+}
+
+/// What switching would do to the running system
+#[derive(Debug, Clone, Default)]
+pub struct Impact {
+    /// (name, running version, new version)
+    pub updated: Vec<(String, String, String)>,
+    /// Packages not in the running system
+    pub added: Vec<(String, String)>,
+    pub to_build: usize,
+    pub to_fetch: usize,
+    /// "(123 MiB download, 456 MiB unpacked)" as nix reports it
+    pub download: Option<String>,
+}
+
+/// Dry-build the re-locked flake and compare with /run/current-system.
+/// Blocking — run in background thread!
+pub fn preview(flake_dir: &str) -> Result<Impact, String> {
+    if !Platform::current().is_nixos() {
+        return Err("no impact preview: there is no NixOS system to dry-build".to_string());
+    }
+    let output = Command::new("nixos-rebuild")
+        .args(["dry-build", "--flake", &format!("{}#", flake_dir)])
+        .stdin(std::process::Stdio::null())
+        .output()
+        .map_err(|e| format!("nixos-rebuild: {}", e))?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() {
+        return Err(stderr
+            .lines()
+            .map(str::trim)
+            .find(|l| l.starts_with("error:"))
+            .unwrap_or("nixos-rebuild dry-build failed")
+            .to_string());
+    }
+
+    let running: HashMap<String, String> = Command::new("nix")
+        .args([
+            "--extra-experimental-features",
+            "nix-command",
+            "path-info",
+            "-r",
+            "/run/current-system",
+        ])
+        .output()
+        .map(|o| {
+            String::from_utf8_lossy(&o.stdout)
+                .lines()
+                .filter_map(crate::modules::rebuild::parse_store_path_name)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(impact(&stderr, &running))
+}
+
+/// Parse dry-build output ("these N derivations will be built:", "these N
+/// paths will be fetched (… download, … unpacked):", one path per line)
+fn impact(output: &str, running: &HashMap<String, String>) -> Impact {
+    let mut result = Impact::default();
+    let mut new: Vec<(String, String)> = Vec::new();
+    let mut section = None;
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if trimmed.contains("will be built") {
+            section = Some(true);
+        } else if trimmed.contains("will be fetched") {
+            section = Some(false);
+            result.download = trimmed
+                .split_once('(')
+                .map(|(_, rest)| format!("({}", rest.trim_end_matches(':')));
+        } else if trimmed.starts_with("/nix/store/") {
+            match section {
+                Some(true) => result.to_build += 1,
+                Some(false) => result.to_fetch += 1,
+                None => continue,
+            }
+            let path = trimmed.trim_end_matches(".drv");
+            if let Some((name, version)) = crate::modules::rebuild::parse_store_path_name(path) {
+                if !version.is_empty() && !crate::modules::rebuild::should_skip_pkg(&name) {
+                    new.push((name, version));
+                }
+            }
+        } else {
+            section = None;
+        }
+    }
+
+    new.sort();
+    new.dedup_by(|a, b| a.0 == b.0);
+    for (name, version) in new {
+        match running.get(&name) {
+            Some(old) if *old != version => result.updated.push((name, old.clone(), version)),
+            Some(_) => {}
+            None => result.added.push((name, version)),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_branch_choices() {
+        let today = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        assert_eq!(
+            choices("nixos-24.05", today),
+            vec![
+                "nixos-23.11",
+                "nixos-24.05",
+                "nixos-24.11",
+                "nixos-unstable"
+            ]
+        );
+        assert_eq!(
+            choices("nixpkgs-23.05-darwin", today),
+            vec![
+                "nixpkgs-23.05-darwin",
+                "nixpkgs-23.11-darwin",
+                "nixpkgs-24.05-darwin",
+                "nixpkgs-24.11-darwin",
+                "nixpkgs-unstable-darwin",
+            ]
+        );
+        assert_eq!(
+            release_notes_url("nixos-unstable"),
+            "https://nixos.org/manual/nixos/unstable/release-notes"
+        );
+    }
+
+    #[test]
+    fn test_dry_build_impact() {
+        let output = "\
+these 2 derivations will be built:
+  /nix/store/aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa-firefox-128.0.drv
+  /nix/store/bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb-nixos-system-host-24.11.drv
+these 2 paths will be fetched (12.50 MiB download, 40.00 MiB unpacked):
+  /nix/store/cccccccccccccccccccccccccccccccc-git-2.47.0
+  /nix/store/dddddddddddddddddddddddddddddddd-ripgrep-14.1.1
+";
+        let running = HashMap::from([
+            ("firefox".to_string(), "127.0".to_string()),
+            ("ripgrep".to_string(), "14.1.1".to_string()),
+        ]);
+        let impact = impact(output, &running);
+        assert_eq!(impact.to_build, 2);
+        assert_eq!(impact.to_fetch, 2);
+        assert_eq!(
+            impact.download.as_deref(),
+            Some("(12.50 MiB download, 40.00 MiB unpacked)")
+        );
+        assert_eq!(
+            impact.updated,
+            vec![("firefox".into(), "127.0".into(), "128.0".into())]
+        );
+        assert!(impact.added.iter().any(|(n, _)| n == "git"));
+    }
+}
@@ -0,0 +1,46 @@
+//! Committing flake.lock after a selective update.
+//!
+//! Only offered when the flake lives in a git repo and flake.lock actually
+//! changed there. The popup shows `git status` and the diff stat first;
+//! the message comes from `flake_commit_message` in config.toml.
+
+use crate::nix::git;
+use std::path::Path;
+
+const LOCK_FILE: &str = "flake.lock";
+
+/// A prepared flake.lock commit, shown in the popup before committing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockCommit {
+    pub message: String,
+    pub status: Vec<String>,
+    pub diff_stat: Vec<String>,
+}
+
+/// Fill `{inputs}` in the message template
+pub fn message(template: &str, inputs: &[String]) -> String {
+    template.replace("{inputs}", &inputs.join(", "))
+}
+
+/// Commit to offer for `inputs`, None if there is nothing to commit
+pub fn prepare(flake_dir: &str, inputs: &[String], template: &str) -> Option<LockCommit> {
+    let dir = Path::new(flake_dir);
+    if inputs.is_empty() || !git::is_repo(dir) {
+        return None;
+    }
+    let status = git::status_short(dir, &[LOCK_FILE]).ok()?;
+    if status.is_empty() {
+        return None;
+    }
+    Some(LockCommit {
+        message: message(template, inputs),
+        status,
+        // Fails without a first commit; the status alone says enough then
+        diff_stat: git::diff_stat(dir, &[LOCK_FILE]).unwrap_or_default(),
+    })
+}
+
+/// Commit flake.lock (and nothing else); returns the short hash
+pub fn run(flake_dir: &str, commit: &LockCommit) -> Result<String, String> {
+    git::commit_paths(Path::new(flake_dir), &[LOCK_FILE], &commit.message)
+}
@@ -0,0 +1,94 @@
+//! Editing inputs in flake.nix: change URL/branch, add, remove.
+//!
+//! The rewrite itself lives in `nix::flake_nix`; this applies it to the
+//! flake directory (keeping `flake.nix.bak`) and re-locks. If re-locking
+//! fails the backup is put back, so a typo never leaves a broken flake.
+
+use crate::config::Language;
+use crate::nix::flake_nix::{self, FlakeNix};
+use std::path::Path;
+use std::process::Command;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Edit {
+    SetUrl { name: String, url: String },
+    SetBranch { name: String, branch: String },
+    Add { name: String, url: String },
+    Remove(String),
+}
+
+/// Parse `<flake_dir>/flake.nix`
+pub fn load(flake_dir: &str) -> Result<FlakeNix, String> {
+    let path = Path::new(flake_dir).join("flake.nix");
+    let source =
+        std::fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    FlakeNix::parse(&source).map_err(|e| e.to_string())
+}
+
+/// Rewrite flake.nix for `edit`, keeping the old file as flake.nix.bak
+pub fn apply(flake_dir: &str, edit: &Edit, lang: Language) -> Result<(), String> {
+    let s = crate::i18n::get_strings(lang);
+    let flake = load(flake_dir)?;
+    let declared = |name: &str| {
+        flake
+            .input(name)
+            .ok_or_else(|| s.fi_edit_not_declared.replace("{}", name))
+    };
+
+    let new_source = match edit {
+        Edit::SetUrl { name, url } => {
+            declared(name)?;
+            flake.with_url(name, url.trim())
+        }
+        Edit::SetBranch { name, branch } => {
+            let url = declared(name)?.url.clone().unwrap_or_default();
+            flake.with_url(name, &flake_nix::with_ref(&url, branch))
+        }
+        Edit::Add { name, url } => flake.with_added(name.trim(), url.trim()),
+        Edit::Remove(name) => {
+            declared(name)?;
+            let followers = flake.followers_of(name);
+            if !followers.is_empty() {
+                return Err(s.fi_edit_followed.replacen("{}", name, 1).replacen(
+                    "{}",
+                    &followers.join(", "),
+                    1,
+                ));
+            }
+            flake.without(name)
+        }
+    }
+    .map_err(|e| e.to_string())?;
+
+    flake_nix::write_with_backup(&Path::new(flake_dir).join("flake.nix"), &new_source)
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// `nix flake lock` after an edit; restores flake.nix.bak on failure.
+/// Blocking — run in background thread!
+pub fn relock(flake_dir: &str) -> Result<(), String> {
+    let result = Command::new("nix")
+        .args(["flake", "lock"])
+        .current_dir(flake_dir)
+        .output();
+
+    let error = match result {
+        Ok(output) if output.status.success() => return Ok(()),
+        Ok(output) => {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            stderr
+                .lines()
+                .map(|l| l.trim())
+                .find(|l| l.starts_with("error:"))
+                .or_else(|| stderr.lines().next())
+                .unwrap_or("nix flake lock failed")
+                .to_string()
+        }
+        Err(e) => format!("Failed to run nix: {}", e),
+    };
+
+    let dir = Path::new(flake_dir);
+    let _ = std::fs::copy(dir.join("flake.nix.bak"), dir.join("flake.nix"));
+    Err(error)
+}
@@ -0,0 +1,315 @@
+//! Full flake.lock node graph for the Graph sub-tab.
+//!
+//! The Overview only lists the root's direct inputs. Here every node is
+//! reachable, including transitive inputs and `follows` edges, so it's
+//! visible which input drags in a second copy of nixpkgs.
+
+use std::collections::{HashMap, HashSet};
+
+/// Where an input edge points
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Target {
+    /// A node in the lock file
+    Node(String),
+    /// `follows` path from the root, e.g. ["home-manager", "nixpkgs"]
+    Follows(Vec<String>),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LockNode {
+    /// Short source description ("github:NixOS/nixpkgs/nixos-unstable")
+    pub source: String,
+    pub rev_short: String,
+    /// Identity of the upstream source, ignoring the revision
+    pub identity: String,
+    /// (input name, target), sorted by name
+    pub inputs: Vec<(String, Target)>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LockGraph {
+    pub root: String,
+    pub nodes: HashMap<String, LockNode>,
+    /// identity → number of distinct revisions locked for that source
+    copies: HashMap<String, usize>,
+}
+
+/// One visible line of the tree
+#[derive(Debug, Clone)]
+pub struct GraphRow {
+    /// Unique path of input names from the root ("home-manager/nixpkgs")
+    pub key: String,
+    pub depth: usize,
+    pub name: String,
+    /// Node this row resolves to (None for dangling references)
+    pub node: Option<String>,
+    /// Set for follows edges: the path that is followed
+    pub follows: Option<String>,
+    pub has_children: bool,
+    pub expanded: bool,
+    /// How many revisions of the same source are locked (>1 = duplicate)
+    pub copies: usize,
+}
+
+impl LockGraph {
+    pub fn parse(lock: &serde_json::Value) -> Self {
+        let root = lock
+            .get("root")
+            .and_then(|r| r.as_str())
+            .unwrap_or("root")
+            .to_string();
+
+        let nodes: HashMap<String, LockNode> = lock
+            .get("nodes")
+            .and_then(|n| n.as_object())
+            .map(|obj| {
+                obj.iter()
+                    .map(|(name, node)| (name.clone(), parse_node(node)))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut revs: HashMap<String, HashSet<&str>> = HashMap::new();
+        for (name, node) in &nodes {
+            if *name != root && !node.identity.is_empty() {
+                revs.entry(node.identity.clone())
+                    .or_default()
+                    .insert(node.rev_short.as_str());
+            }
+        }
+        let copies = revs.into_iter().map(|(id, r)| (id, r.len())).collect();
+
+        Self {
+            root,
+            nodes,
+            copies,
+        }
+    }
+
+    /// Resolve a follows path (input names from the root) to a node name.
+    /// None for dangling or cyclic follows (a hand-edited lock file).
+    pub fn resolve_follows(&self, path: &[String]) -> Option<String> {
+        self.resolve_follows_within(path, self.nodes.len())
+    }
+
+    /// A follows chain longer than the node count must loop
+    fn resolve_follows_within(&self, path: &[String], hops: usize) -> Option<String> {
+        let mut current = self.root.clone();
+        for name in path {
+            let node = self.nodes.get(&current)?;
+            let (_, target) = node.inputs.iter().find(|(n, _)| n == name)?;
+            current = match target {
+                Target::Node(n) => n.clone(),
+                Target::Follows(p) => self.resolve_follows_within(p, hops.checked_sub(1)?)?,
+            };
+        }
+        Some(current)
+    }
+
+    /// Number of distinct revisions locked for the source of `node`
+    pub fn copies_of(&self, node: &str) -> usize {
+        self.nodes
+            .get(node)
+            .and_then(|n| self.copies.get(&n.identity))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Number of sources locked in more than one revision
+    pub fn duplicate_sources(&self) -> usize {
+        self.copies.values().filter(|&&c| c > 1).count()
+    }
+
+    /// Flatten the tree into visible rows. Children of a row are shown if
+    /// its key is in `expanded`; follows edges are leaves.
+    pub fn rows(&self, expanded: &HashSet<String>) -> Vec<GraphRow> {
+        let mut rows = Vec::new();
+        let mut on_path = HashSet::new();
+        on_path.insert(self.root.clone());
+        self.push_children(&self.root, "", 0, expanded, &mut on_path, &mut rows);
+        rows
+    }
+
+    fn push_children(
+        &self,
+        node_name: &str,
+        prefix: &str,
+        depth: usize,
+        expanded: &HashSet<String>,
+        on_path: &mut HashSet<String>,
+        rows: &mut Vec<GraphRow>,
+    ) {
+        let Some(node) = self.nodes.get(node_name) else {
+            return;
+        };
+        for (input, target) in &node.inputs {
+            let key = if prefix.is_empty() {
+                input.clone()
+            } else {
+                format!("{}/{}", prefix, input)
+            };
+            match target {
+                Target::Node(child) => {
+                    let has_children = self.nodes.get(child).is_some_and(|n| !n.inputs.is_empty());
+                    let is_expanded = has_children && expanded.contains(&key);
+                    rows.push(GraphRow {
+                        key: key.clone(),
+                        depth,
+                        name: input.clone(),
+                        node: self.nodes.contains_key(child).then(|| child.clone()),
+                        follows: None,
+                        has_children,
+                        expanded: is_expanded,
+                        copies: self.copies_of(child),
+                    });
+                    // Cycle guard: lock files shouldn't have cycles, but don't hang if one does
+                    if is_expanded && on_path.insert(child.clone()) {
+                        self.push_children(child, &key, depth + 1, expanded, on_path, rows);
+                        on_path.remove(child);
+                    }
+                }
+                Target::Follows(path) => {
+                    let resolved = self.resolve_follows(path);
+                    rows.push(GraphRow {
+                        key,
+                        depth,
+                        name: input.clone(),
+                        copies: resolved.as_deref().map(|n| self.copies_of(n)).unwrap_or(0),
+                        node: resolved,
+                        follows: Some(path.join("/")),
+                        has_children: false,
+                        expanded: false,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Keys of every expandable row (for "expand all")
+    pub fn all_keys(&self) -> HashSet<String> {
+        let mut keys = HashSet::new();
+        let mut stack: Vec<(String, String, usize)> = vec![(self.root.clone(), String::new(), 0)];
+        while let Some((node_name, prefix, depth)) = stack.pop() {
+            // Depth limit guards against cycles
+            if depth > 32 {
+                continue;
+            }
+            let Some(node) = self.nodes.get(&node_name) else {
+                continue;
+            };
+            for (input, target) in &node.inputs {
+                if let Target::Node(child) = target {
+                    let key = if prefix.is_empty() {
+                        input.clone()
+                    } else {
+                        format!("{}/{}", prefix, input)
+                    };
+                    keys.insert(key.clone());
+                    stack.push((child.clone(), key, depth + 1));
+                }
+            }
+        }
+        keys
+    }
+}
+
+fn parse_node(node: &serde_json::Value) -> LockNode {
+    let mut inputs: Vec<(String, Target)> = node
+        .get("inputs")
+        .and_then(|i| i.as_object())
+        .map(|obj| {
+            obj.iter()
+                .filter_map(|(name, target)| {
+                    let target = if let Some(s) = target.as_str() {
+                        Target::Node(s.to_string())
+                    } else {
+                        let path = target
+                            .as_array()?
+                            .iter()
+                            .filter_map(|p| p.as_str().map(|s| s.to_string()))
+                            .collect();
+                        Target::Follows(path)
+                    };
+                    Some((name.clone(), target))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    inputs.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let Some(locked) = node.get("locked") else {
+        return LockNode {
+            inputs,
+            ..Default::default()
+        };
+    };
+    let field = |k: &str| locked.get(k).and_then(|v| v.as_str()).unwrap_or("");
+
+    let input_type = field("type");
+    let identity = match input_type {
+        "github" | "gitlab" | "sourcehut" => format!(
+            "{}:{}/{}",
+            input_type,
+            field("owner").to_lowercase(),
+            field("repo").to_lowercase()
+        ),
+        "git" | "tarball" | "file" | "mercurial" => format!("{}:{}", input_type, field("url")),
+        "path" => format!("path:{}", field("path")),
+        _ => String::new(),
+    };
+
+    let branch = node
+        .get("original")
+        .and_then(|o| o.get("ref"))
+        .and_then(|r| r.as_str())
+        .map(|r| format!("/{}", r))
+        .unwrap_or_default();
+    let source = if identity.is_empty() {
+        input_type.to_string()
+    } else {
+        format!("{}{}", identity, branch)
+    };
+
+    let rev = field("rev");
+    LockNode {
+        source,
+        rev_short: rev.chars().take(7).collect(),
+        identity,
+        inputs,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_resolve_follows() {
+        let graph = LockGraph::parse(&json!({
+            "root": "root",
+            "nodes": {
+                "root": { "inputs": { "hm": "hm", "nixpkgs": "nixpkgs" } },
+                "hm": { "inputs": { "nixpkgs": ["nixpkgs"] } },
+                "nixpkgs": {},
+            }
+        }));
+        let path = |p: &[&str]| p.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            graph.resolve_follows(&path(&["hm", "nixpkgs"])).as_deref(),
+            Some("nixpkgs")
+        );
+        assert_eq!(graph.resolve_follows(&path(&["missing"])), None);
+    }
+
+    #[test]
+    fn test_resolve_cyclic_follows() {
+        let graph = LockGraph::parse(&json!({
+            "root": "root",
+            "nodes": {
+                "root": { "inputs": { "a": ["b"], "b": ["a"] } },
+            }
+        }));
+        assert_eq!(graph.resolve_follows(&["a".to_string()]), None);
+    }
+}
@@ -0,0 +1,132 @@
+//! flake.lock against any git revision of it.
+//!
+//! `c` in History asks for a revision (`HEAD~3`, a tag, the commit of the
+//! last good deploy) and lists the direct inputs whose lock differs, with
+//! both revisions, their ages and the URL — including updates that never
+//! went through nixmate.
+
+use super::{parse_flake_lock, FlakeInput};
+use crate::nix::git;
+use std::path::Path;
+
+const LOCK_FILE: &str = "flake.lock";
+
+/// One direct input whose lock differs; None on a side = not an input there
+#[derive(Debug, Clone)]
+pub struct LockChange {
+    pub name: String,
+    pub old: Option<FlakeInput>,
+    pub new: Option<FlakeInput>,
+}
+
+#[derive(Debug, Clone)]
+pub struct LockDiff {
+    /// The revision as entered
+    pub rev: String,
+    /// Short hash, date and subject of that commit
+    pub commit: String,
+    pub changes: Vec<LockChange>,
+    pub unchanged: usize,
+}
+
+/// Compare the working tree's flake.lock with the one at `rev`
+pub fn load(flake_dir: &str, rev: &str) -> Result<LockDiff, String> {
+    let dir = Path::new(flake_dir);
+    if !git::is_repo(dir) {
+        return Err(format!("{} is not in a git repository", flake_dir));
+    }
+    let old = git::show_file(dir, rev, LOCK_FILE)?;
+    let new = std::fs::read_to_string(dir.join(LOCK_FILE)).map_err(|e| e.to_string())?;
+    let parse = |text: &str| {
+        serde_json::from_str::<serde_json::Value>(text)
+            .map(|lock| parse_flake_lock(&lock))
+            .map_err(|e| format!("{}: {}", LOCK_FILE, e))
+    };
+    let (changes, unchanged) = compare(&parse(&old)?, &parse(&new)?);
+    Ok(LockDiff {
+        rev: rev.to_string(),
+        commit: git::describe_commit(dir, rev).unwrap_or_default(),
+        changes,
+        unchanged,
+    })
+}
+
+/// Inputs added, removed or locked to another revision or URL, by name;
+/// plus how many stayed the same
+pub fn compare(old: &[FlakeInput], new: &[FlakeInput]) -> (Vec<LockChange>, usize) {
+    let mut names: Vec<&str> = old.iter().chain(new).map(|i| i.name.as_str()).collect();
+    names.sort_unstable();
+    names.dedup();
+
+    let mut unchanged = 0;
+    let changes = names
+        .into_iter()
+        .filter_map(|name| {
+            let before = old.iter().find(|i| i.name == name);
+            let after = new.iter().find(|i| i.name == name);
+            if let (Some(b), Some(a)) = (before, after) {
+                if b.revision == a.revision && b.url == a.url && b.nar_hash == a.nar_hash {
+                    unchanged += 1;
+                    return None;
+                }
+            }
+            Some(LockChange {
+                name: name.to_string(),
+                old: before.cloned(),
+                new: after.cloned(),
+            })
+        })
+        .collect();
+    (changes, unchanged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lock(inputs: &[(&str, &str)]) -> Vec<FlakeInput> {
+        let mut nodes = serde_json::Map::new();
+        let mut root = serde_json::Map::new();
+        for (name, rev) in inputs {
+            root.insert(name.to_string(), serde_json::json!(name));
+            nodes.insert(
+                name.to_string(),
+                serde_json::json!({
+                    "locked": {"type": "github", "owner": "o", "repo": name, "rev": rev},
+                    "original": {"type": "github", "owner": "o", "repo": name}
+                }),
+            );
+        }
+        nodes.insert("root".into(), serde_json::json!({ "inputs": root }));
+        parse_flake_lock(&serde_json::json!({ "nodes": nodes, "root": "root" }))
+    }
+
+    #[test]
+    fn test_compare_locks() {
+        let old = lock(&[
+            ("nixpkgs", "aaaaaaa1"),
+            ("home-manager", "bbbbbbb1"),
+            ("old", "c"),
+        ]);
+        let new = lock(&[
+            ("nixpkgs", "aaaaaaa2"),
+            ("home-manager", "bbbbbbb1"),
+            ("new", "d"),
+        ]);
+        let (changes, unchanged) = compare(&old, &new);
+        assert_eq!(unchanged, 1);
+        let summary: Vec<(&str, bool, bool)> = changes
+            .iter()
+            .map(|c| (c.name.as_str(), c.old.is_some(), c.new.is_some()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("new", false, true),
+                ("nixpkgs", true, true),
+                ("old", true, false)
+            ]
+        );
+        assert_eq!(changes[1].new.as_ref().unwrap().rev_short, "aaaaaaa");
+    }
+}
@@ -2,17 +2,52 @@
 //!
 //! Manage, update, and inspect flake inputs individually.
 //! Sub-tabs:
-//!   Overview — all inputs with revision, age, status
+//!   Overview — all inputs with revision, age, status; `e`/`b`/`a`/`d` edit flake.nix
 //!   Update   — selective per-input updates with checkboxes
-//!   History  — diff of last update (old vs new revisions)
+//!   History  — diff of last update (old vs new revisions), `u` rolls an input back
 //!   Details  — full info for the selected input
+//!   Graph    — whole flake.lock node graph incl. transitive inputs
 //!
 //! Data source: flake.lock (JSON) + flake.nix parsing.
 //! Updates via `nix flake lock --update-input <name>`.
-
-use crate::config::Language;
+//! Edits rewrite flake.nix (backup: flake.nix.bak), then `nix flake lock`.
+//! After an update, flake.lock can be committed if the flake is in git.
+//! Upstream "commits behind" for GitHub inputs via the compare API.
+//! Watch mode flags inputs older than their `[flake_watch]` threshold.
+//! `s` switches nixpkgs between release branches and previews the impact.
+//! `U` updates every input, shows the revision diff and hands a dry-build
+//! or switch over to the Rebuild module (`pending_rebuild`).
+//! `m` in History turns the last update into a Markdown report (see
+//! `report.rs`), written next to the flake or copied to the clipboard.
+//! `c` in History compares flake.lock with any git revision (see `lockdiff.rs`).
+//! `F` switches between the system flake and registered flake projects,
+//! `o` there picks a new one from the file system (see `projects.rs`).
+
+mod branch;
+mod commit;
+mod edit;
+mod graph;
+mod lockdiff;
+mod projects;
+mod report;
+mod rollback;
+mod upstream;
+pub mod watch;
+
+use commit::LockCommit;
+use edit::Edit;
+use graph::LockGraph;
+pub use upstream::{InputIssue, UpstreamStatus};
+use watch::StaleInput;
+
+use crate::config::{FlakeWatchConfig, Language, TimeFormat};
 use crate::i18n;
-use crate::types::FlashMessage;
+use crate::modules::jobs::Job;
+use crate::modules::module::{Module, RenderCtx};
+use crate::modules::palette::PaletteCommand;
+use crate::modules::rebuild::RebuildMode;
+use crate::types::{format_time, FlashMessage};
+use crate::ui::table::{Cell, Column, SortKey, Table, TableState};
 use crate::ui::theme::Theme;
 use crate::ui::widgets;
 use anyhow::Result;
@@ -24,8 +59,12 @@ use ratatui::{
     widgets::{Block, Borders, List, ListItem, Paragraph, Tabs, Wrap},
     Frame,
 };
-use std::collections::HashMap;
-use std::sync::mpsc;
+use std::collections::{HashMap, HashSet};
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
+
+/// How often watch mode re-reads flake.lock while the module is closed
+const WATCH_INTERVAL: Duration = Duration::from_secs(3600);
 
 // ── Sub-tabs ──
 
@@ -36,6 +75,7 @@ pub enum FlakeSubTab {
     Update,
     History,
     Details,
+    Graph,
 }
 
 impl FlakeSubTab {
@@ -45,6 +85,7 @@ impl FlakeSubTab {
             FlakeSubTab::Update,
             FlakeSubTab::History,
             FlakeSubTab::Details,
+            FlakeSubTab::Graph,
         ]
     }
 
@@ -54,6 +95,18 @@ impl FlakeSubTab {
             FlakeSubTab::Update => 1,
             FlakeSubTab::History => 2,
             FlakeSubTab::Details => 3,
+            FlakeSubTab::Graph => 4,
+        }
+    }
+
+    pub fn label(&self, lang: Language) -> &'static str {
+        let s = i18n::get_strings(lang);
+        match self {
+            FlakeSubTab::Overview => s.fi_tab_overview,
+            FlakeSubTab::Update => s.fi_tab_update,
+            FlakeSubTab::History => s.fi_tab_history,
+            FlakeSubTab::Details => s.fi_tab_details,
+            FlakeSubTab::Graph => s.fi_tab_graph,
         }
     }
 
@@ -81,9 +134,7 @@ pub struct FlakeInput {
     pub name: String,
     pub input_type: String, // github, git, path, indirect, etc.
     pub url: String,        // display URL (e.g. "github:NixOS/nixpkgs")
-    #[allow(dead_code)] // Parsed from flake.lock, reserved for detail view
     pub owner: String,
-    #[allow(dead_code)] // Parsed from flake.lock, reserved for detail view
     pub repo: String,
     pub branch: String,    // ref/branch if set
     pub revision: String,  // full rev hash
@@ -99,13 +150,22 @@ pub struct FlakeInput {
 
 // ── Update result ──
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct UpdateResult {
     pub input_name: String,
     pub old_rev: String,
     pub new_rev: String,
     pub success: bool,
     pub message: String,
+    /// flake.lock as it was before this change (for rollback)
+    pub old_lock: Option<Arc<serde_json::Value>>,
+}
+
+impl UpdateResult {
+    /// Whether this entry changed the lock and can be undone
+    pub fn can_rollback(&self) -> bool {
+        self.success && self.old_rev != self.new_rev && self.old_lock.is_some()
+    }
 }
 
 #[derive(Debug)]
@@ -124,6 +184,57 @@ pub enum FlakePopup {
     None,
     ConfirmUpdate,
     Updating,
+    /// Index into history
+    ConfirmRollback(usize),
+    /// Text prompt, buffer in `edit_buffer`
+    EditInput(EditField),
+    /// Input name
+    ConfirmRemove(String),
+    Relocking,
+    /// Commit flake.lock after an update, prepared in `pending_commit`
+    ConfirmCommit,
+    /// Pick the nixpkgs branch, state in `branch_switch`
+    SwitchBranch,
+    /// Dry-build impact of the switched branch
+    BranchImpact,
+    /// End of "update all and rebuild": dry-build, switch or stop
+    ConfirmRebuild,
+    /// Markdown report of the last update, text in `report`
+    Report,
+    /// System flake and registered projects (`F`)
+    Projects,
+    /// Directory picker for a new project, state in `picker`
+    PickDir,
+}
+
+/// A nixpkgs branch switch in progress
+pub struct BranchSwitch {
+    pub input: String,
+    /// Ref before the switch (empty = default branch)
+    pub from: String,
+    pub choices: Vec<String>,
+    pub selected: usize,
+    /// Set once flake.nix was rewritten
+    pub applied: bool,
+    pub impact: Option<Result<branch::Impact, String>>,
+}
+
+impl BranchSwitch {
+    pub fn target(&self) -> &str {
+        &self.choices[self.selected]
+    }
+}
+
+/// What the edit prompt is asking for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EditField {
+    Url(String),
+    Branch(String),
+    AddName,
+    /// Name entered in the first step
+    AddUrl(String),
+    /// Git revision to compare flake.lock with
+    LockRev,
 }
 
 // ── Module state ──
@@ -137,11 +248,16 @@ pub struct FlakeInputsState {
     pub loaded: bool,
     pub loading: bool,
     pub error_message: Option<String>,
+    /// When flake.lock was read (for TTL / auto-refresh)
+    pub loaded_at: Option<Instant>,
     load_rx: Option<mpsc::Receiver<LoadResult>>,
+    /// Current load is a background refresh (keeps selections, no upstream check)
+    refreshing: bool,
 
     // Overview tab
+    /// Index into `inputs`, stepped through in table order
     pub selected: usize,
-    pub scroll_offset: usize,
+    pub overview_table: TableState,
 
     // Update tab
     pub update_checked: Vec<bool>,
@@ -160,8 +276,61 @@ pub struct FlakeInputsState {
     pub history_selected: usize,
     pub history_scroll: usize,
 
+    // Graph tab
+    pub graph: LockGraph,
+    pub graph_expanded: HashSet<String>,
+    pub graph_selected: usize,
+
+    // Upstream comparison (GitHub inputs only), keyed by input name
+    pub upstream: HashMap<String, UpstreamStatus>,
+    /// Upstream problems found by the same check (archived, moved, …)
+    pub input_issues: HashMap<String, Vec<InputIssue>>,
+    upstream_rx: Option<mpsc::Receiver<(String, UpstreamStatus, Vec<InputIssue>)>>,
+
+    // flake.nix editing
+    pub edit_buffer: String,
+    relock_rx: Option<mpsc::Receiver<Result<(), String>>>,
+
+    // nixpkgs branch switch
+    pub branch_switch: Option<BranchSwitch>,
+    impact_rx: Option<mpsc::Receiver<Result<branch::Impact, String>>>,
+
+    // git commit of flake.lock after an update
+    pub commit_template: String,
+    pub pending_commit: Option<LockCommit>,
+
+    // "Update all and rebuild": running, and the rebuild App hands over
+    update_all: bool,
+    pub pending_rebuild: Option<RebuildMode>,
+
+    // flake.lock against a git revision (`c` in History), shown in place
+    // of the update list until Esc
+    pub lock_diff: Option<lockdiff::LockDiff>,
+    pub lock_diff_scroll: usize,
+
+    // Markdown report of the last update (None while counting commits)
+    pub report: Option<String>,
+    report_rx: Option<mpsc::Receiver<String>>,
+
+    // Watch mode (staleness thresholds from config)
+    pub watch: FlakeWatchConfig,
+    /// Inputs past their threshold; drives the sidebar badge
+    pub stale: Vec<StaleInput>,
+    watched_at: Option<Instant>,
+    watch_rx: Option<mpsc::Receiver<Option<Vec<StaleInput>>>>,
+
     pub lang: Language,
+    pub time_format: TimeFormat,
+    /// Terminal below the compact thresholds (set on resize)
+    pub compact: bool,
     pub config_path: Option<String>,
+    /// Flake shown instead of the system flake (`flake_path`; None = system)
+    pub flake_dir: Option<String>,
+    /// Registered flake projects (`flakes`)
+    pub flakes: Vec<String>,
+    pub projects_selected: usize,
+    pub picker: Option<projects::DirPicker>,
+    pub github_token: Option<String>,
     pub flash_message: Option<FlashMessage>,
 }
 
@@ -169,6 +338,7 @@ pub struct FlakeInputsState {
 enum LoadResult {
     Done {
         inputs: Vec<FlakeInput>,
+        graph: LockGraph,
         flake_path: String,
     },
     Error(String),
@@ -183,9 +353,11 @@ impl FlakeInputsState {
             loaded: false,
             loading: false,
             error_message: None,
+            loaded_at: None,
             load_rx: None,
+            refreshing: false,
             selected: 0,
-            scroll_offset: 0,
+            overview_table: TableState::default(),
             update_checked: Vec::new(),
             update_selected: 0,
             update_scroll: 0,
@@ -197,12 +369,46 @@ impl FlakeInputsState {
             history: Vec::new(),
             history_selected: 0,
             history_scroll: 0,
+            graph: LockGraph::default(),
+            graph_expanded: HashSet::new(),
+            graph_selected: 0,
+            upstream: HashMap::new(),
+            input_issues: HashMap::new(),
+            upstream_rx: None,
+            edit_buffer: String::new(),
+            relock_rx: None,
+            branch_switch: None,
+            impact_rx: None,
+            commit_template: String::new(),
+            pending_commit: None,
+            update_all: false,
+            pending_rebuild: None,
+            lock_diff: None,
+            lock_diff_scroll: 0,
+            report: None,
+            report_rx: None,
+            watch: FlakeWatchConfig::default(),
+            stale: Vec::new(),
+            watched_at: None,
+            watch_rx: None,
             lang: Language::English,
+            time_format: TimeFormat::Locale,
+            compact: false,
             config_path: None,
+            flake_dir: None,
+            flakes: Vec::new(),
+            projects_selected: 0,
+            picker: None,
+            github_token: None,
             flash_message: None,
         }
     }
 
+    /// The picked flake project's directory (None = the system flake)
+    fn project(&self) -> Option<String> {
+        self.flake_dir.as_deref().map(projects::expand_home)
+    }
+
     /// Lazy load on first tab visit
     pub fn ensure_loaded(&mut self) {
         if self.loaded || self.loading {
@@ -214,37 +420,155 @@ impl FlakeInputsState {
         let (tx, rx) = mpsc::channel();
         self.load_rx = Some(rx);
         let lang = self.lang;
+        let project = self.project();
+        let cp = self.config_path.clone();
+
+        std::thread::spawn(move || {
+            let result = load_source(lang, project.as_deref(), cp.as_deref());
+            let _ = tx.send(result);
+        });
+    }
+
+    /// Re-read flake.lock in the background, keeping the current view
+    pub fn refresh_in_background(&mut self) {
+        if self.load_rx.is_some() || !self.loaded || self.popup != FlakePopup::None {
+            return;
+        }
+        self.refreshing = true;
+
+        let (tx, rx) = mpsc::channel();
+        self.load_rx = Some(rx);
+        let lang = self.lang;
+        let project = self.project();
         let cp = self.config_path.clone();
 
         std::thread::spawn(move || {
-            let result = load_flake_inputs(lang, cp.as_deref());
+            let result = load_source(lang, project.as_deref(), cp.as_deref());
             let _ = tx.send(result);
         });
     }
 
+    /// Watch mode: re-check flake.lock against the staleness thresholds
+    /// every `WATCH_INTERVAL`, even if the module was never opened
+    pub fn tick_watch(&mut self) {
+        if let Some(rx) = &self.watch_rx {
+            match rx.try_recv() {
+                Ok(stale) => {
+                    self.stale = stale.unwrap_or_default();
+                    self.watch_rx = None;
+                }
+                Err(mpsc::TryRecvError::Empty) => {}
+                Err(mpsc::TryRecvError::Disconnected) => self.watch_rx = None,
+            }
+            return;
+        }
+
+        if !self.watch.enabled {
+            self.stale.clear();
+            return;
+        }
+        if self
+            .watched_at
+            .is_some_and(|t| t.elapsed() < WATCH_INTERVAL)
+        {
+            return;
+        }
+        self.watched_at = Some(Instant::now());
+
+        let (tx, rx) = mpsc::channel();
+        self.watch_rx = Some(rx);
+        let cp = self.project().or_else(|| self.config_path.clone());
+        let policy = self.watch.clone();
+
+        std::thread::spawn(move || {
+            let _ = tx.send(watch::scan(cp.as_deref(), &policy));
+        });
+    }
+
+    /// Watch-mode entry for `name`, if it is past its threshold
+    fn stale_entry(&self, name: &str) -> Option<&StaleInput> {
+        self.stale.iter().find(|st| st.name == name)
+    }
+
+    /// Indices into `inputs` in overview table order
+    pub fn overview_order(&self) -> Vec<usize> {
+        self.overview_table
+            .order(&self.inputs, |input, col| self.sort_key(input, col))
+    }
+
+    fn sort_key(&self, input: &FlakeInput, col: usize) -> SortKey {
+        match col {
+            0 => SortKey::text(&input.name),
+            1 => SortKey::text(&input.url),
+            2 => SortKey::text(&input.rev_short),
+            3 => SortKey::Num(input.last_modified),
+            // How much attention it needs: issues, staleness, commits behind
+            _ => {
+                let issues = match self.input_issues.get(&input.name) {
+                    Some(issues) if has_dead_issue(issues) => 2_000_000,
+                    Some(_) => 1_000_000,
+                    None => 0,
+                };
+                let stale = if self.stale_entry(&input.name).is_some() {
+                    100_000
+                } else {
+                    0
+                };
+                let behind = match self.upstream.get(&input.name) {
+                    Some(UpstreamStatus::Behind { commits, .. }) => *commits as i64,
+                    _ => 0,
+                };
+                SortKey::Num(issues + stale + behind)
+            }
+        }
+    }
+
     /// Poll background loaders
     pub fn poll_load(&mut self) {
         // Poll initial load
         if let Some(rx) = &self.load_rx {
             match rx.try_recv() {
-                Ok(LoadResult::Done { inputs, flake_path }) => {
-                    self.update_checked = vec![false; inputs.len()];
+                Ok(LoadResult::Done {
+                    inputs,
+                    graph,
+                    flake_path,
+                }) => {
+                    if self.update_checked.len() != inputs.len() {
+                        self.update_checked = vec![false; inputs.len()];
+                    }
+                    self.selected = self.selected.min(inputs.len().saturating_sub(1));
                     self.inputs = inputs;
+                    self.graph = graph;
+                    self.graph_selected = self.graph_selected.min(
+                        self.graph
+                            .rows(&self.graph_expanded)
+                            .len()
+                            .saturating_sub(1),
+                    );
                     self.flake_path = Some(flake_path);
                     self.loaded = true;
                     self.loading = false;
                     self.load_rx = None;
+                    self.loaded_at = Some(Instant::now());
+                    self.stale = watch::stale_inputs(&self.inputs, &self.watch);
+                    self.watched_at = self.loaded_at;
+                    // Upstream comparison hits the GitHub API; not on every refresh
+                    if !std::mem::take(&mut self.refreshing) {
+                        self.start_upstream_check();
+                    }
                 }
                 Ok(LoadResult::Error(msg)) => {
                     self.error_message = Some(msg);
                     self.loaded = true;
                     self.loading = false;
+                    self.refreshing = false;
                     self.load_rx = None;
                 }
                 Err(mpsc::TryRecvError::Empty) => {}
                 Err(mpsc::TryRecvError::Disconnected) => {
                     self.loading = false;
                     self.loaded = true;
+                    self.refreshing = false;
                     self.load_rx = None;
                     if self.inputs.is_empty() && self.error_message.is_none() {
                         self.error_message = Some(
@@ -257,6 +581,73 @@ impl FlakeInputsState {
             }
         }
 
+        // Poll upstream comparison
+        if let Some(rx) = &self.upstream_rx {
+            loop {
+                match rx.try_recv() {
+                    Ok((name, status, issues)) => {
+                        if !issues.is_empty() {
+                            self.input_issues.insert(name.clone(), issues);
+                        }
+                        self.upstream.insert(name, status);
+                    }
+                    Err(mpsc::TryRecvError::Empty) => break,
+                    Err(mpsc::TryRecvError::Disconnected) => {
+                        self.upstream_rx = None;
+                        break;
+                    }
+                }
+            }
+        }
+
+        // Poll re-lock after a flake.nix edit
+        if let Some(rx) = &self.relock_rx {
+            let result = match rx.try_recv() {
+                Ok(result) => Some(result),
+                Err(mpsc::TryRecvError::Empty) => None,
+                Err(mpsc::TryRecvError::Disconnected) => Some(Err(String::new())),
+            };
+            if let Some(result) = result {
+                let s = crate::i18n::get_strings(self.lang);
+                self.relock_rx = None;
+                self.popup = FlakePopup::None;
+                let switched =
+                    result.is_ok() && self.branch_switch.as_ref().is_some_and(|b| b.applied);
+                if switched {
+                    self.start_impact_preview();
+                } else {
+                    self.branch_switch = None;
+                    self.flash_message = Some(match result {
+                        Ok(()) => FlashMessage::new(s.fi_edit_done.to_string(), false),
+                        Err(e) => FlashMessage::new(s.fi_relock_failed.replace("{}", &e), true),
+                    });
+                }
+                self.reload();
+            }
+        }
+
+        // Poll the dry-build after a branch switch
+        if let Some(rx) = &self.impact_rx {
+            let result = match rx.try_recv() {
+                Ok(result) => Some(result),
+                Err(mpsc::TryRecvError::Empty) => None,
+                Err(mpsc::TryRecvError::Disconnected) => Some(Err(String::new())),
+            };
+            if let Some(result) = result {
+                self.impact_rx = None;
+                if let Some(switch) = self.branch_switch.as_mut() {
+                    switch.impact = Some(result);
+                }
+            }
+        }
+
+        if let Some(rx) = &self.report_rx {
+            if let Ok(text) = rx.try_recv() {
+                self.report = Some(text);
+                self.report_rx = None;
+            }
+        }
+
         // Poll update process
         if let Some(rx) = &self.update_rx {
             loop {
@@ -275,6 +666,7 @@ impl FlakeInputsState {
                             new_rev: result.new_rev.clone(),
                             success: result.success,
                             message: result.message.clone(),
+                            old_lock: result.old_lock.clone(),
                         });
                         self.update_results.push(result);
                     }
@@ -288,12 +680,28 @@ impl FlakeInputsState {
                         self.ensure_loaded();
                         let s = crate::i18n::get_strings(self.lang);
                         self.flash_message = Some(FlashMessage::new(
-                            format!("{} {}", self.update_results.len(), s.flk_inputs_updated),
+                            format!(
+                                "{} {} · {}",
+                                self.update_results.len(),
+                                s.flk_inputs_updated,
+                                s.fi_report_hint
+                            ),
                             true,
                         ));
+                        self.offer_commit();
+                        if self.update_all {
+                            // The combined diff is the History tab
+                            self.sub_tab = FlakeSubTab::History;
+                            self.history_selected =
+                                self.history.len().saturating_sub(self.update_results.len());
+                            if self.popup == FlakePopup::None {
+                                self.offer_rebuild();
+                            }
+                        }
                         return;
                     }
                     Ok(UpdateStatus::Error(msg)) => {
+                        self.update_all = false;
                         self.updating = false;
                         self.popup = FlakePopup::None;
                         self.update_rx = None;
@@ -312,6 +720,143 @@ impl FlakeInputsState {
         }
     }
 
+    /// Compare GitHub inputs with their upstream branch in the background
+    fn start_upstream_check(&mut self) {
+        let comparable: Vec<FlakeInput> = self
+            .inputs
+            .iter()
+            .filter(|i| upstream::is_comparable(i))
+            .cloned()
+            .collect();
+        self.upstream.clear();
+        self.input_issues.clear();
+        if comparable.is_empty() {
+            return;
+        }
+        for input in &comparable {
+            self.upstream
+                .insert(input.name.clone(), UpstreamStatus::Checking);
+        }
+
+        let (tx, rx) = mpsc::channel();
+        self.upstream_rx = Some(rx);
+        let token = self.github_token.clone();
+
+        std::thread::spawn(move || {
+            upstream::check_inputs(comparable, token, tx);
+        });
+    }
+
+    /// Markdown report of the last update; commit counts come from GitHub
+    /// in the background
+    fn start_report(&mut self) {
+        let mut entries = report::entries(&self.update_results, &self.inputs);
+        if entries.is_empty() {
+            let s = crate::i18n::get_strings(self.lang);
+            self.flash_message = Some(FlashMessage::new(s.fi_report_empty.to_string(), true));
+            return;
+        }
+        self.report = None;
+        self.popup = FlakePopup::Report;
+        let (tx, rx) = mpsc::channel();
+        self.report_rx = Some(rx);
+        let token = self.github_token.clone();
+        std::thread::spawn(move || {
+            report::count_commits(&mut entries, token.as_deref());
+            let generated = chrono::Local::now().format("%Y-%m-%d %H:%M").to_string();
+            let _ = tx.send(report::markdown(&entries, &generated));
+        });
+    }
+
+    /// Write the report next to the flake as flake-update-<date>.md
+    fn write_report(&mut self) {
+        let (Some(text), Some(dir)) = (&self.report, &self.flake_path) else {
+            return;
+        };
+        let s = crate::i18n::get_strings(self.lang);
+        let path = std::path::Path::new(dir).join(format!(
+            "flake-update-{}.md",
+            chrono::Local::now().format("%Y-%m-%d")
+        ));
+        self.flash_message = Some(match std::fs::write(&path, text) {
+            Ok(()) => FlashMessage::new(
+                s.fi_report_written
+                    .replace("{}", &path.display().to_string()),
+                true,
+            ),
+            Err(e) => FlashMessage::new(format!("{}: {}", path.display(), e), false),
+        });
+        self.popup = FlakePopup::None;
+    }
+
+    fn copy_report(&mut self) {
+        let Some(text) = &self.report else {
+            return;
+        };
+        let s = crate::i18n::get_strings(self.lang);
+        self.flash_message = Some(match crate::clipboard::write(text) {
+            Some(tool) => FlashMessage::new(s.fi_report_copied.replace("{}", tool), true),
+            None => FlashMessage::new(s.rb_log_no_clipboard.to_string(), false),
+        });
+        self.popup = FlakePopup::None;
+    }
+
+    /// After an update: ask to commit flake.lock if it changed in git
+    fn offer_commit(&mut self) {
+        let Some(flake_path) = &self.flake_path else {
+            return;
+        };
+        let changed: Vec<String> = self
+            .update_results
+            .iter()
+            .filter(|r| r.success && r.old_rev != r.new_rev)
+            .map(|r| r.input_name.clone())
+            .collect();
+        self.pending_commit = commit::prepare(flake_path, &changed, &self.commit_template);
+        if self.pending_commit.is_some() {
+            self.popup = FlakePopup::ConfirmCommit;
+        }
+    }
+
+    /// "Update all and rebuild": every input checked, then the update
+    fn start_update_all(&mut self) {
+        if self.updating || self.flake_path.is_none() || self.inputs.is_empty() {
+            return;
+        }
+        self.update_checked = vec![true; self.inputs.len()];
+        self.update_all = true;
+        self.start_update();
+    }
+
+    /// Last step of "update all and rebuild", after the commit question
+    fn offer_rebuild(&mut self) {
+        self.update_all = false;
+        let changed = self
+            .update_results
+            .iter()
+            .filter(|r| r.success && r.old_rev != r.new_rev)
+            .count();
+        if changed == 0 {
+            let s = crate::i18n::get_strings(self.lang);
+            self.flash_message = Some(FlashMessage::new(s.fi_all_current.to_string(), false));
+        } else {
+            self.popup = FlakePopup::ConfirmRebuild;
+        }
+    }
+
+    fn commit_lock(&mut self) {
+        self.popup = FlakePopup::None;
+        let (Some(flake_path), Some(pending)) = (&self.flake_path, self.pending_commit.take())
+        else {
+            return;
+        };
+        let s = crate::i18n::get_strings(self.lang);
+        self.flash_message = Some(match commit::run(flake_path, &pending) {
+            Ok(hash) => FlashMessage::new(s.fi_commit_done.replace("{}", &hash), false),
+            Err(e) => FlashMessage::new(s.fi_commit_failed.replace("{}", &e), true),
+        });
+    }
+
     /// Start updating selected inputs
     fn start_update(&mut self) {
         let flake_path = match &self.flake_path {
@@ -346,102 +891,785 @@ impl FlakeInputsState {
         });
     }
 
-    /// Reload flake data
-    fn reload(&mut self) {
-        self.loaded = false;
-        self.loading = false;
-        self.load_rx = None;
-        self.inputs.clear();
-        self.update_checked.clear();
-        self.error_message = None;
-        self.ensure_loaded();
+    /// Show `nix flake update` output piped into nixmate as history; the
+    /// lock before the update isn't known, so these can't be rolled back
+    pub fn load_update_output(&mut self, text: &str) {
+        let s = crate::i18n::get_strings(self.lang);
+        self.update_results = crate::pipe::flake_updates(text)
+            .into_iter()
+            .map(|(input_name, old_rev, new_rev)| UpdateResult {
+                message: s
+                    .fi_updated_input
+                    .replacen("{}", &old_rev, 1)
+                    .replacen("{}", &new_rev, 1),
+                input_name,
+                old_rev,
+                new_rev,
+                success: true,
+                old_lock: None,
+            })
+            .collect();
+        self.history.extend(self.update_results.iter().cloned());
+        self.history_selected = self.history.len().saturating_sub(self.update_results.len());
+        self.sub_tab = FlakeSubTab::History;
     }
 
-    pub fn handle_key(&mut self, key: KeyEvent) -> Result<bool> {
-        // Popup handling
-        match &self.popup {
-            FlakePopup::ConfirmUpdate => {
-                match key.code {
-                    KeyCode::Enter | KeyCode::Char('y') => {
-                        self.start_update();
-                    }
-                    KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('q') => {
-                        self.popup = FlakePopup::None;
-                    }
-                    _ => {}
-                }
-                return Ok(true);
+    /// Restore the lock entry a history item replaced, then reload
+    fn rollback(&mut self, idx: usize) {
+        let s = crate::i18n::get_strings(self.lang);
+        let (Some(flake_path), Some(entry)) = (self.flake_path.clone(), self.history.get(idx))
+        else {
+            return;
+        };
+        let Some(old_lock) = entry.old_lock.clone() else {
+            return;
+        };
+        let name = entry.input_name.clone();
+        let current_rev = self
+            .inputs
+            .iter()
+            .find(|i| i.name == name)
+            .map(|i| i.rev_short.clone())
+            .unwrap_or_else(|| entry.new_rev.clone());
+        let lock_before = std::fs::read_to_string(format!("{}/flake.lock", flake_path))
+            .ok()
+            .and_then(|c| serde_json::from_str(&c).ok())
+            .map(Arc::new);
+
+        match rollback::restore_input(&flake_path, &name, &old_lock) {
+            Ok(restored) => {
+                self.history.push(UpdateResult {
+                    input_name: name.clone(),
+                    old_rev: current_rev.clone(),
+                    new_rev: restored.clone(),
+                    success: true,
+                    message: s.fi_rolled_back.to_string(),
+                    old_lock: lock_before,
+                });
+                self.history_selected = self.history.len() - 1;
+                self.flash_message = Some(FlashMessage::new(
+                    s.fi_rollback_done
+                        .replacen("{}", &name, 1)
+                        .replacen("{}", &restored, 1),
+                    false,
+                ));
+                self.reload();
             }
-            FlakePopup::Updating => {
-                // Absorb all keys while updating
-                return Ok(true);
+            Err(e) => {
+                self.flash_message = Some(FlashMessage::new(
+                    s.fi_rollback_failed.replace("{}", &e),
+                    true,
+                ));
             }
-            FlakePopup::None => {}
         }
+    }
 
-        // Sub-tab switching with [ / ]
-        match key.code {
-            KeyCode::Char('[') => {
-                self.sub_tab = self.sub_tab.prev();
-                return Ok(true);
-            }
-            KeyCode::Char(']') => {
-                self.sub_tab = self.sub_tab.next();
-                return Ok(true);
+    /// Open the edit prompt for the selected input, prefilled from flake.nix
+    fn open_edit(&mut self, branch: bool) {
+        let s = crate::i18n::get_strings(self.lang);
+        let (Some(flake_path), Some(input)) = (&self.flake_path, self.inputs.get(self.selected))
+        else {
+            return;
+        };
+        let name = input.name.clone();
+        let url = match edit::load(flake_path) {
+            Ok(flake) => match flake.input(&name) {
+                Some(declared) => declared.url.clone().unwrap_or_default(),
+                None => {
+                    self.flash_message = Some(FlashMessage::new(
+                        s.fi_edit_not_declared.replace("{}", &name),
+                        true,
+                    ));
+                    return;
+                }
+            },
+            Err(e) => {
+                self.flash_message =
+                    Some(FlashMessage::new(s.fi_edit_failed.replace("{}", &e), true));
+                return;
             }
-            _ => {}
-        }
-
-        match self.sub_tab {
-            FlakeSubTab::Overview => self.handle_overview_key(key),
-            FlakeSubTab::Update => self.handle_update_key(key),
-            FlakeSubTab::History => self.handle_history_key(key),
-            FlakeSubTab::Details => self.handle_details_key(key),
+        };
+        if branch {
+            self.edit_buffer = crate::nix::flake_nix::url_ref(&url).unwrap_or_default();
+            self.popup = FlakePopup::EditInput(EditField::Branch(name));
+        } else {
+            self.edit_buffer = url;
+            self.popup = FlakePopup::EditInput(EditField::Url(name));
         }
     }
 
-    fn handle_overview_key(&mut self, key: KeyEvent) -> Result<bool> {
-        match key.code {
-            KeyCode::Char('j') | KeyCode::Down => {
-                if !self.inputs.is_empty() {
-                    self.selected = (self.selected + 1).min(self.inputs.len() - 1);
-                }
-            }
-            KeyCode::Char('k') | KeyCode::Up => {
-                if self.selected > 0 {
-                    self.selected -= 1;
-                }
+    /// Submit the edit prompt
+    fn submit_edit(&mut self, field: EditField) {
+        let value = self.edit_buffer.trim().to_string();
+        let edit = match field {
+            EditField::Url(name) if !value.is_empty() => Edit::SetUrl { name, url: value },
+            EditField::Branch(name) => Edit::SetBranch {
+                name,
+                branch: value,
+            },
+            EditField::AddName if !value.is_empty() => {
+                // Second step: ask for the URL
+                self.edit_buffer = "github:".to_string();
+                self.popup = FlakePopup::EditInput(EditField::AddUrl(value));
+                return;
             }
-            KeyCode::Char('g') => self.selected = 0,
-            KeyCode::Char('G') => {
-                if !self.inputs.is_empty() {
-                    self.selected = self.inputs.len() - 1;
-                }
+            EditField::AddUrl(name) if !value.is_empty() => Edit::Add { name, url: value },
+            EditField::LockRev if !value.is_empty() => {
+                self.popup = FlakePopup::None;
+                self.open_lock_diff(&value);
+                return;
             }
-            KeyCode::Enter => {
-                // Switch to details for selected input
-                self.sub_tab = FlakeSubTab::Details;
+            _ => return,
+        };
+        self.edit_buffer.clear();
+        self.apply_edit(edit);
+    }
+
+    /// Compare flake.lock with its version at `rev`
+    fn open_lock_diff(&mut self, rev: &str) {
+        let s = crate::i18n::get_strings(self.lang);
+        let Some(flake_path) = self.flake_path.clone() else {
+            return;
+        };
+        match lockdiff::load(&flake_path, rev) {
+            Ok(diff) => {
+                self.lock_diff = Some(diff);
+                self.lock_diff_scroll = 0;
             }
-            KeyCode::Char('r') => {
-                self.reload();
+            Err(e) => {
+                self.flash_message = Some(FlashMessage::new(
+                    s.fi_lockdiff_failed.replace("{}", &e),
+                    true,
+                ));
             }
-            _ => return Ok(false),
         }
-        Ok(true)
     }
 
-    fn handle_update_key(&mut self, key: KeyEvent) -> Result<bool> {
-        match key.code {
-            KeyCode::Char('j') | KeyCode::Down => {
-                if !self.inputs.is_empty() {
-                    self.update_selected = (self.update_selected + 1).min(self.inputs.len() - 1);
-                }
-            }
-            KeyCode::Char('k') | KeyCode::Up => {
-                if self.update_selected > 0 {
-                    self.update_selected -= 1;
-                }
-            }
+    /// Rewrite flake.nix, then re-lock in the background
+    fn apply_edit(&mut self, edit: Edit) {
+        let s = crate::i18n::get_strings(self.lang);
+        let Some(flake_path) = self.flake_path.clone() else {
+            return;
+        };
+        if let Err(e) = edit::apply(&flake_path, &edit, self.lang) {
+            self.popup = FlakePopup::None;
+            self.flash_message = Some(FlashMessage::new(s.fi_edit_failed.replace("{}", &e), true));
+            return;
+        }
+
+        self.popup = FlakePopup::Relocking;
+        let (tx, rx) = mpsc::channel();
+        self.relock_rx = Some(rx);
+        std::thread::spawn(move || {
+            let _ = tx.send(edit::relock(&flake_path));
+        });
+    }
+
+    /// Offer the release branches for the selected input if it's nixpkgs,
+    /// else for the input called nixpkgs
+    fn open_branch_switch(&mut self) {
+        let s = crate::i18n::get_strings(self.lang);
+        let Some(flake_path) = &self.flake_path else {
+            return;
+        };
+        let input = self
+            .inputs
+            .get(self.selected)
+            .filter(|i| branch::is_nixpkgs(i))
+            .or_else(|| self.inputs.iter().find(|i| i.name == "nixpkgs"));
+        let Some(input) = input else {
+            self.flash_message = Some(FlashMessage::new(s.fi_branch_no_nixpkgs.to_string(), true));
+            return;
+        };
+        let name = input.name.clone();
+        let from = match edit::load(flake_path) {
+            Ok(flake) => match flake.input(&name) {
+                Some(declared) => {
+                    crate::nix::flake_nix::url_ref(declared.url.as_deref().unwrap_or_default())
+                        .unwrap_or_default()
+                }
+                None => {
+                    self.flash_message = Some(FlashMessage::new(
+                        s.fi_edit_not_declared.replace("{}", &name),
+                        true,
+                    ));
+                    return;
+                }
+            },
+            Err(e) => {
+                self.flash_message =
+                    Some(FlashMessage::new(s.fi_edit_failed.replace("{}", &e), true));
+                return;
+            }
+        };
+        let choices = branch::choices(&from, chrono::Local::now().date_naive());
+        let selected = choices.iter().position(|b| *b == from).unwrap_or(0);
+        self.branch_switch = Some(BranchSwitch {
+            input: name,
+            from,
+            choices,
+            selected,
+            applied: false,
+            impact: None,
+        });
+        self.popup = FlakePopup::SwitchBranch;
+    }
+
+    /// Rewrite the ref to the picked branch; the preview follows the re-lock
+    fn confirm_branch_switch(&mut self) {
+        let Some(switch) = self.branch_switch.as_mut() else {
+            return;
+        };
+        if switch.target() == switch.from {
+            self.branch_switch = None;
+            self.popup = FlakePopup::None;
+            return;
+        }
+        switch.applied = true;
+        let edit = Edit::SetBranch {
+            name: switch.input.clone(),
+            branch: switch.target().to_string(),
+        };
+        self.apply_edit(edit);
+        if self.popup != FlakePopup::Relocking {
+            self.branch_switch = None;
+        }
+    }
+
+    /// Put the previous branch back (and re-lock)
+    fn revert_branch_switch(&mut self) {
+        let Some(switch) = self.branch_switch.take() else {
+            return;
+        };
+        self.impact_rx = None;
+        self.apply_edit(Edit::SetBranch {
+            name: switch.input,
+            branch: switch.from,
+        });
+    }
+
+    fn start_impact_preview(&mut self) {
+        let Some(flake_path) = self.flake_path.clone() else {
+            return;
+        };
+        self.popup = FlakePopup::BranchImpact;
+        let (tx, rx) = mpsc::channel();
+        self.impact_rx = Some(rx);
+        std::thread::spawn(move || {
+            let _ = tx.send(branch::preview(&flake_path));
+        });
+    }
+
+    /// Keys shared by Overview and Details that edit flake.nix
+    fn handle_edit_key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Char('e') => self.open_edit(false),
+            KeyCode::Char('b') => self.open_edit(true),
+            KeyCode::Char('s') => self.open_branch_switch(),
+            KeyCode::Char('a') if self.flake_path.is_some() => {
+                self.edit_buffer.clear();
+                self.popup = FlakePopup::EditInput(EditField::AddName);
+            }
+            KeyCode::Char('d') => {
+                if let Some(input) = self.inputs.get(self.selected) {
+                    self.popup = FlakePopup::ConfirmRemove(input.name.clone());
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Reload flake data
+    /// Rows of the `F` list: the system flake, then the registered projects
+    pub fn project_rows(&self) -> Vec<Option<String>> {
+        std::iter::once(None)
+            .chain(self.flakes.iter().cloned().map(Some))
+            .collect()
+    }
+
+    fn open_projects(&mut self) {
+        self.projects_selected = self
+            .project_rows()
+            .iter()
+            .position(|r| *r == self.flake_dir)
+            .unwrap_or(0);
+        self.popup = FlakePopup::Projects;
+    }
+
+    /// Show the flake in `dir` (None = the system flake)
+    pub fn switch_project(&mut self, dir: Option<String>) {
+        if dir == self.flake_dir {
+            return;
+        }
+        self.flake_dir = dir;
+
+        // Nothing of the previous flake carries over
+        self.flake_path = None;
+        self.selected = 0;
+        self.update_selected = 0;
+        self.update_results.clear();
+        self.history.clear();
+        self.history_selected = 0;
+        self.lock_diff = None;
+        self.graph = LockGraph::default();
+        self.graph_expanded.clear();
+        self.graph_selected = 0;
+        self.upstream.clear();
+        self.input_issues.clear();
+        self.upstream_rx = None;
+        self.stale.clear();
+        self.watched_at = None;
+        if self.loaded || self.loading {
+            self.reload();
+        }
+    }
+
+    fn open_picker(&mut self) {
+        let start = self
+            .flake_path
+            .as_deref()
+            .and_then(|p| std::path::Path::new(p).parent())
+            .map(|p| p.to_path_buf())
+            .unwrap_or_else(|| std::env::var("HOME").unwrap_or_else(|_| "/".into()).into());
+        self.picker = Some(projects::DirPicker::open(&start));
+        self.popup = FlakePopup::PickDir;
+    }
+
+    /// Register the highlighted directory of the picker and switch to it
+    fn pick_project(&mut self) {
+        let Some(path) = self.picker.as_ref().and_then(|p| p.selected_path()) else {
+            return;
+        };
+        let dir = path.to_string_lossy().to_string();
+        if !projects::is_flake(&path) {
+            let s = i18n::get_strings(self.lang);
+            self.flash_message = Some(FlashMessage::new(
+                s.fi_project_not_flake
+                    .replace("{}", &projects::shorten_home(&dir)),
+                true,
+            ));
+            return;
+        }
+        projects::register(&mut self.flakes, &dir);
+        self.picker = None;
+        self.popup = FlakePopup::None;
+        self.switch_project(Some(projects::shorten_home(&dir)));
+    }
+
+    /// `d` in the list: forget the highlighted project (not the system flake)
+    fn unregister_project(&mut self) {
+        let Some(Some(dir)) = self.project_rows().get(self.projects_selected).cloned() else {
+            return;
+        };
+        self.flakes.retain(|f| *f != dir);
+        self.projects_selected = self.projects_selected.min(self.flakes.len());
+        if self.flake_dir.as_ref() == Some(&dir) {
+            self.switch_project(None);
+        }
+    }
+
+    fn reload(&mut self) {
+        self.loaded = false;
+        self.loading = false;
+        self.load_rx = None;
+        self.refreshing = false;
+        self.inputs.clear();
+        self.update_checked.clear();
+        self.error_message = None;
+        self.ensure_loaded();
+    }
+
+    /// Actions offered in the command palette
+    /// Running background work, for the jobs overlay
+    pub fn jobs(&self, s: &i18n::Strings) -> Vec<Job> {
+        let mut jobs = Vec::new();
+        if self.loading {
+            jobs.push(Job::new("load", s.job_fi_load, true));
+        }
+        if self.updating {
+            // `nix flake update` rewrites flake.lock; let it finish
+            let total = self.update_checked.iter().filter(|c| **c).count();
+            jobs.push(
+                Job::new("update", s.job_fi_update, false)
+                    .detail(self.update_log.last().cloned().unwrap_or_default())
+                    .progress(self.update_results.len(), total),
+            );
+        }
+        if self.upstream_rx.is_some() {
+            let checking = self
+                .upstream
+                .values()
+                .filter(|u| matches!(u, UpstreamStatus::Checking))
+                .count();
+            jobs.push(
+                Job::new("upstream", s.job_fi_upstream, true)
+                    .progress(self.upstream.len() - checking, self.upstream.len()),
+            );
+        }
+        if self.relock_rx.is_some() {
+            jobs.push(Job::new("relock", s.job_fi_relock, false));
+        }
+        if self.impact_rx.is_some() {
+            jobs.push(Job::new("impact", s.job_fi_impact, true));
+        }
+        jobs
+    }
+
+    /// Stop waiting for a job from `jobs()`; its result is discarded
+    pub fn cancel_job(&mut self, id: &str) {
+        let s = i18n::get_strings(self.lang);
+        match id {
+            "load" => {
+                self.load_rx = None;
+                self.loading = false;
+                if !self.loaded {
+                    self.loaded = true;
+                    self.error_message = Some(s.jobs_cancelled_error.to_string());
+                }
+            }
+            "impact" => {
+                self.impact_rx = None;
+                if let Some(switch) = self.branch_switch.as_mut() {
+                    switch.impact = Some(Err(s.jobs_cancelled_error.to_string()));
+                }
+            }
+            "upstream" => {
+                self.upstream_rx = None;
+                self.upstream
+                    .retain(|_, u| !matches!(u, UpstreamStatus::Checking));
+            }
+            _ => {}
+        }
+    }
+
+    pub fn palette_commands(&self, s: &i18n::Strings) -> Vec<PaletteCommand> {
+        let overview = Some(FlakeSubTab::Overview.index());
+        vec![
+            PaletteCommand::new(
+                s.pal_fl_update,
+                Some(FlakeSubTab::Update.index()),
+                vec![KeyCode::Char('u')],
+            ),
+            PaletteCommand::new(s.pal_fl_update_all, None, vec![KeyCode::Char('U')]),
+            PaletteCommand::new(s.pal_fl_add, overview, vec![KeyCode::Char('a')]),
+            PaletteCommand::new(s.pal_fl_edit, overview, vec![KeyCode::Char('e')]),
+            PaletteCommand::new(s.pal_fl_branch, overview, vec![KeyCode::Char('b')]),
+            PaletteCommand::new(s.pal_fl_switch_nixpkgs, overview, vec![KeyCode::Char('s')]),
+            PaletteCommand::new(s.pal_fl_graph, Some(FlakeSubTab::Graph.index()), vec![]),
+            PaletteCommand::new(
+                s.pal_fl_report,
+                Some(FlakeSubTab::History.index()),
+                vec![KeyCode::Char('m')],
+            ),
+            PaletteCommand::new(s.pal_fl_projects, None, vec![KeyCode::Char('F')]),
+        ]
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> Result<bool> {
+        // Popup handling
+        match &self.popup {
+            FlakePopup::ConfirmUpdate => {
+                match key.code {
+                    KeyCode::Enter | KeyCode::Char('y') => {
+                        self.start_update();
+                    }
+                    KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('q') => {
+                        self.popup = FlakePopup::None;
+                    }
+                    _ => {}
+                }
+                return Ok(true);
+            }
+            FlakePopup::Updating => {
+                // Absorb all keys while updating
+                return Ok(true);
+            }
+            FlakePopup::ConfirmRollback(idx) => {
+                let idx = *idx;
+                match key.code {
+                    KeyCode::Enter | KeyCode::Char('y') => {
+                        self.popup = FlakePopup::None;
+                        self.rollback(idx);
+                    }
+                    KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('q') => {
+                        self.popup = FlakePopup::None;
+                    }
+                    _ => {}
+                }
+                return Ok(true);
+            }
+            FlakePopup::EditInput(field) => {
+                let field = field.clone();
+                match key.code {
+                    KeyCode::Enter => self.submit_edit(field),
+                    KeyCode::Esc => {
+                        self.edit_buffer.clear();
+                        self.popup = FlakePopup::None;
+                    }
+                    KeyCode::Backspace => {
+                        self.edit_buffer.pop();
+                    }
+                    KeyCode::Char(c) => self.edit_buffer.push(c),
+                    _ => {}
+                }
+                return Ok(true);
+            }
+            FlakePopup::ConfirmRemove(name) => {
+                let name = name.clone();
+                match key.code {
+                    KeyCode::Enter | KeyCode::Char('y') => self.apply_edit(Edit::Remove(name)),
+                    KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('q') => {
+                        self.popup = FlakePopup::None;
+                    }
+                    _ => {}
+                }
+                return Ok(true);
+            }
+            FlakePopup::Relocking => {
+                // Absorb all keys until the lock is written
+                return Ok(true);
+            }
+            FlakePopup::SwitchBranch => {
+                match key.code {
+                    KeyCode::Char('j') | KeyCode::Down => {
+                        if let Some(switch) = self.branch_switch.as_mut() {
+                            switch.selected = (switch.selected + 1).min(switch.choices.len() - 1);
+                        }
+                    }
+                    KeyCode::Char('k') | KeyCode::Up => {
+                        if let Some(switch) = self.branch_switch.as_mut() {
+                            switch.selected = switch.selected.saturating_sub(1);
+                        }
+                    }
+                    KeyCode::Enter => self.confirm_branch_switch(),
+                    KeyCode::Esc | KeyCode::Char('q') => {
+                        self.branch_switch = None;
+                        self.popup = FlakePopup::None;
+                    }
+                    _ => {}
+                }
+                return Ok(true);
+            }
+            FlakePopup::BranchImpact => {
+                match key.code {
+                    KeyCode::Char('u') => self.revert_branch_switch(),
+                    KeyCode::Enter | KeyCode::Esc | KeyCode::Char('q') => {
+                        self.impact_rx = None;
+                        self.branch_switch = None;
+                        self.popup = FlakePopup::None;
+                    }
+                    _ => {}
+                }
+                return Ok(true);
+            }
+            FlakePopup::ConfirmCommit => {
+                match key.code {
+                    KeyCode::Enter | KeyCode::Char('y') => self.commit_lock(),
+                    KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('q') => {
+                        self.pending_commit = None;
+                        self.popup = FlakePopup::None;
+                    }
+                    _ => return Ok(true),
+                }
+                if self.update_all {
+                    self.offer_rebuild();
+                }
+                return Ok(true);
+            }
+            FlakePopup::ConfirmRebuild => {
+                match key.code {
+                    KeyCode::Char('d') => self.pending_rebuild = Some(RebuildMode::DryBuild),
+                    KeyCode::Enter | KeyCode::Char('s') => {
+                        self.pending_rebuild = Some(RebuildMode::Switch)
+                    }
+                    KeyCode::Esc | KeyCode::Char('q') => {}
+                    _ => return Ok(true),
+                }
+                self.popup = FlakePopup::None;
+                return Ok(true);
+            }
+            FlakePopup::Report => {
+                match key.code {
+                    KeyCode::Char('w') => self.write_report(),
+                    KeyCode::Char('c') => self.copy_report(),
+                    KeyCode::Esc | KeyCode::Char('q') => {
+                        self.popup = FlakePopup::None;
+                        self.report_rx = None;
+                    }
+                    _ => {}
+                }
+                return Ok(true);
+            }
+            FlakePopup::Projects => {
+                let rows = self.project_rows();
+                match key.code {
+                    KeyCode::Char('j') | KeyCode::Down => {
+                        self.projects_selected = (self.projects_selected + 1).min(rows.len() - 1);
+                    }
+                    KeyCode::Char('k') | KeyCode::Up => {
+                        self.projects_selected = self.projects_selected.saturating_sub(1);
+                    }
+                    KeyCode::Enter => {
+                        let dir = rows[self.projects_selected.min(rows.len() - 1)].clone();
+                        self.popup = FlakePopup::None;
+                        self.switch_project(dir);
+                    }
+                    KeyCode::Char('o') => self.open_picker(),
+                    KeyCode::Char('d') => self.unregister_project(),
+                    KeyCode::Esc | KeyCode::Char('q') => self.popup = FlakePopup::None,
+                    _ => {}
+                }
+                return Ok(true);
+            }
+            FlakePopup::PickDir => {
+                if let Some(picker) = self.picker.as_mut() {
+                    match key.code {
+                        KeyCode::Char('j') | KeyCode::Down => {
+                            picker.selected =
+                                (picker.selected + 1).min(picker.entries.len().saturating_sub(1));
+                        }
+                        KeyCode::Char('k') | KeyCode::Up => {
+                            picker.selected = picker.selected.saturating_sub(1);
+                        }
+                        KeyCode::Enter | KeyCode::Char('l') | KeyCode::Right => picker.enter(),
+                        KeyCode::Char('h') | KeyCode::Left | KeyCode::Backspace => picker.up(),
+                        KeyCode::Char('s') | KeyCode::Char(' ') => self.pick_project(),
+                        KeyCode::Esc | KeyCode::Char('q') => {
+                            self.picker = None;
+                            self.popup = FlakePopup::Projects;
+                        }
+                        _ => {}
+                    }
+                }
+                return Ok(true);
+            }
+            FlakePopup::None => {}
+        }
+
+        // Sub-tab switching with [ / ]
+        match key.code {
+            KeyCode::Char('[') => {
+                self.sub_tab = self.sub_tab.prev();
+                return Ok(true);
+            }
+            KeyCode::Char(']') => {
+                self.sub_tab = self.sub_tab.next();
+                return Ok(true);
+            }
+            KeyCode::Char('U') => {
+                self.start_update_all();
+                return Ok(true);
+            }
+            KeyCode::Char('F') if !self.updating => {
+                self.open_projects();
+                return Ok(true);
+            }
+            _ => {}
+        }
+
+        match self.sub_tab {
+            FlakeSubTab::Overview => self.handle_overview_key(key),
+            FlakeSubTab::Update => self.handle_update_key(key),
+            FlakeSubTab::History => self.handle_history_key(key),
+            FlakeSubTab::Details => self.handle_details_key(key),
+            FlakeSubTab::Graph => self.handle_graph_key(key),
+        }
+    }
+
+    fn handle_graph_key(&mut self, key: KeyEvent) -> Result<bool> {
+        let rows = self.graph.rows(&self.graph_expanded);
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                if !rows.is_empty() {
+                    self.graph_selected = (self.graph_selected + 1).min(rows.len() - 1);
+                }
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.graph_selected = self.graph_selected.saturating_sub(1);
+            }
+            KeyCode::Char('g') => self.graph_selected = 0,
+            KeyCode::Char('G') => self.graph_selected = rows.len().saturating_sub(1),
+            KeyCode::Enter | KeyCode::Char(' ') | KeyCode::Char('l') | KeyCode::Right => {
+                if let Some(row) = rows.get(self.graph_selected) {
+                    if row.has_children && !self.graph_expanded.remove(&row.key) {
+                        self.graph_expanded.insert(row.key.clone());
+                    }
+                }
+            }
+            KeyCode::Char('h') | KeyCode::Left => {
+                // Collapse current row, or jump to its parent
+                if let Some(row) = rows.get(self.graph_selected) {
+                    if row.expanded {
+                        self.graph_expanded.remove(&row.key);
+                    } else if let Some((parent, _)) = row.key.rsplit_once('/') {
+                        if let Some(idx) = rows.iter().position(|r| r.key == parent) {
+                            self.graph_selected = idx;
+                        }
+                    }
+                }
+            }
+            KeyCode::Char('e') => {
+                self.graph_expanded = self.graph.all_keys();
+            }
+            KeyCode::Char('c') => {
+                self.graph_expanded.clear();
+                self.graph_selected = 0;
+            }
+            KeyCode::Char('r') => {
+                self.reload();
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn handle_overview_key(&mut self, key: KeyEvent) -> Result<bool> {
+        if self
+            .overview_table
+            .handle_key(key, &overview_columns(i18n::get_strings(self.lang)))
+        {
+            return Ok(true);
+        }
+        let order = self.overview_order();
+        let pos = order.iter().position(|&i| i == self.selected).unwrap_or(0);
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                if let Some(&next) = order.get(pos + 1) {
+                    self.selected = next;
+                }
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                if pos > 0 {
+                    self.selected = order[pos - 1];
+                }
+            }
+            KeyCode::Char('g') => self.selected = order.first().copied().unwrap_or(0),
+            KeyCode::Char('G') => self.selected = order.last().copied().unwrap_or(0),
+            KeyCode::Enter => {
+                // Switch to details for selected input
+                self.sub_tab = FlakeSubTab::Details;
+            }
+            KeyCode::Char('r') => {
+                self.reload();
+            }
+            _ => return Ok(self.handle_edit_key(key)),
+        }
+        Ok(true)
+    }
+
+    fn handle_update_key(&mut self, key: KeyEvent) -> Result<bool> {
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                if !self.inputs.is_empty() {
+                    self.update_selected = (self.update_selected + 1).min(self.inputs.len() - 1);
+                }
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                if self.update_selected > 0 {
+                    self.update_selected -= 1;
+                }
+            }
             KeyCode::Char(' ') => {
                 // Toggle checkbox
                 if self.update_selected < self.update_checked.len() {
@@ -479,7 +1707,30 @@ impl FlakeInputsState {
     }
 
     fn handle_history_key(&mut self, key: KeyEvent) -> Result<bool> {
+        if let Some(ref diff) = self.lock_diff {
+            match key.code {
+                KeyCode::Char('j') | KeyCode::Down => {
+                    if self.lock_diff_scroll + 1 < diff.changes.len() {
+                        self.lock_diff_scroll += 1;
+                    }
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    self.lock_diff_scroll = self.lock_diff_scroll.saturating_sub(1);
+                }
+                KeyCode::Char('c') => {
+                    self.edit_buffer = diff.rev.clone();
+                    self.popup = FlakePopup::EditInput(EditField::LockRev);
+                }
+                KeyCode::Esc => self.lock_diff = None,
+                _ => return Ok(false),
+            }
+            return Ok(true);
+        }
         match key.code {
+            KeyCode::Char('c') => {
+                self.edit_buffer = "HEAD".to_string();
+                self.popup = FlakePopup::EditInput(EditField::LockRev);
+            }
             KeyCode::Char('j') | KeyCode::Down => {
                 if !self.history.is_empty() {
                     self.history_selected = (self.history_selected + 1).min(self.history.len() - 1);
@@ -496,6 +1747,20 @@ impl FlakeInputsState {
                     self.history_selected = self.history.len() - 1;
                 }
             }
+            KeyCode::Char('m') => self.start_report(),
+            KeyCode::Char('u') => {
+                if let Some(entry) = self.history.get(self.history_selected) {
+                    if entry.can_rollback() {
+                        self.popup = FlakePopup::ConfirmRollback(self.history_selected);
+                    } else {
+                        let s = crate::i18n::get_strings(self.lang);
+                        self.flash_message = Some(FlashMessage::new(
+                            s.fi_rollback_unavailable.to_string(),
+                            true,
+                        ));
+                    }
+                }
+            }
             _ => return Ok(false),
         }
         Ok(true)
@@ -516,7 +1781,7 @@ impl FlakeInputsState {
             KeyCode::Char('r') => {
                 self.reload();
             }
-            _ => return Ok(false),
+            _ => return Ok(self.handle_edit_key(key)),
         }
         Ok(true)
     }
@@ -524,18 +1789,8 @@ impl FlakeInputsState {
 
 // ── Data loading ──
 
-fn find_flake_dir(custom_path: Option<&str>) -> Option<String> {
-    let home = std::env::var("HOME").unwrap_or_default();
-    let mut candidates = Vec::new();
-    if let Some(p) = custom_path {
-        candidates.push(p.to_string());
-    }
-    candidates.extend([
-        "/etc/nixos".to_string(),
-        format!("{}/.config/nixos", home),
-        format!("{}/nixos", home),
-        format!("{}/.nixos", home),
-    ]);
+pub(crate) fn find_flake_dir(custom_path: Option<&str>) -> Option<String> {
+    let candidates = crate::nix::detect::flake_candidates(custom_path);
 
     for dir in &candidates {
         let flake_nix = format!("{}/flake.nix", dir);
@@ -556,6 +1811,33 @@ fn find_flake_dir(custom_path: Option<&str>) -> Option<String> {
     None
 }
 
+/// The flake directory and its direct inputs, for `nixmate export`
+pub fn load_inputs(
+    lang: Language,
+    config_path: Option<&str>,
+) -> Result<(String, Vec<FlakeInput>), String> {
+    match load_flake_inputs(lang, config_path) {
+        LoadResult::Done {
+            inputs, flake_path, ..
+        } => Ok((flake_path, inputs)),
+        LoadResult::Error(e) => Err(e),
+    }
+}
+
+/// A picked project is used as it is (a vanished one is an error, not a
+/// silent fall back to the system flake); otherwise the system flake
+fn load_source(lang: Language, project: Option<&str>, config_path: Option<&str>) -> LoadResult {
+    match project {
+        Some(dir) if !projects::is_flake(std::path::Path::new(dir)) => LoadResult::Error(
+            crate::i18n::get_strings(lang)
+                .fi_project_missing
+                .replace("{}", dir),
+        ),
+        Some(dir) => load_flake_inputs(lang, Some(dir)),
+        None => load_flake_inputs(lang, config_path),
+    }
+}
+
 fn load_flake_inputs(lang: Language, config_path: Option<&str>) -> LoadResult {
     let s = crate::i18n::get_strings(lang);
     let flake_dir = match find_flake_dir(config_path) {
@@ -588,6 +1870,7 @@ fn load_flake_inputs(lang: Language, config_path: Option<&str>) -> LoadResult {
 
     LoadResult::Done {
         inputs,
+        graph: LockGraph::parse(&lock_json),
         flake_path: flake_dir,
     }
 }
@@ -801,7 +2084,10 @@ fn run_selective_update(
 
     // Read current lock before update for diffing
     let lock_path = format!("{}/flake.lock", flake_dir);
-    let _old_lock = std::fs::read_to_string(&lock_path).ok();
+    let old_lock: Option<Arc<serde_json::Value>> = std::fs::read_to_string(&lock_path)
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .map(Arc::new);
 
     for (name, old_rev) in inputs {
         let _ = tx.send(UpdateStatus::Progress(
@@ -839,6 +2125,7 @@ fn run_selective_update(
                     new_rev: new_rev_short,
                     success: true,
                     message,
+                    old_lock: old_lock.clone(),
                 }));
             }
             Ok(output) => {
@@ -854,6 +2141,7 @@ fn run_selective_update(
                     new_rev: old_rev.clone(),
                     success: false,
                     message: msg,
+                    old_lock: None,
                 }));
             }
             Err(e) => {
@@ -863,6 +2151,7 @@ fn run_selective_update(
                     new_rev: old_rev.clone(),
                     success: false,
                     message: format!("Failed to run nix: {}", e),
+                    old_lock: None,
                 }));
             }
         }
@@ -900,6 +2189,57 @@ fn age_color(days: u64, theme: &Theme) -> ratatui::style::Color {
 
 // ── Rendering ──
 
+impl Module for FlakeInputsState {
+    fn handle_key(&mut self, key: KeyEvent, _lang: Language) -> Result<()> {
+        self.handle_key(key).map(|_| ())
+    }
+
+    fn captures_input(&self) -> bool {
+        self.popup != FlakePopup::None
+    }
+
+    fn render(&mut self, frame: &mut Frame, ctx: &RenderCtx, area: Rect) {
+        render(frame, self, ctx.theme, ctx.lang, area);
+    }
+
+    fn activate(&mut self) {
+        self.ensure_loaded();
+    }
+
+    fn poll(&mut self, _active: bool) {
+        self.poll_load();
+        self.tick_watch();
+    }
+
+    fn badge(&self) -> Option<usize> {
+        (!self.stale.is_empty()).then_some(self.stale.len())
+    }
+
+    fn commands(&self, s: &i18n::Strings) -> Vec<PaletteCommand> {
+        self.palette_commands(s)
+    }
+
+    fn jobs(&self, s: &i18n::Strings) -> Vec<Job> {
+        self.jobs(s)
+    }
+
+    fn cancel_job(&mut self, id: &str) {
+        self.cancel_job(id)
+    }
+
+    fn sub_tab(&self) -> Option<(usize, usize)> {
+        Some((self.sub_tab.index(), FlakeSubTab::all().len()))
+    }
+
+    fn sub_tab_label(&self, lang: Language) -> Option<&'static str> {
+        Some(self.sub_tab.label(lang))
+    }
+
+    fn flash_message(&mut self) -> &mut Option<FlashMessage> {
+        &mut self.flash_message
+    }
+}
+
 pub fn render(
     frame: &mut Frame,
     state: &FlakeInputsState,
@@ -950,6 +2290,11 @@ pub fn render(
                 format!("  {}", s.fi_no_flake_hint),
                 Style::default().fg(theme.fg_dim),
             ),
+            Line::raw(""),
+            Line::styled(
+                format!("  {}", s.fi_project_hint),
+                Style::default().fg(theme.accent),
+            ),
         ];
         frame.render_widget(
             Paragraph::new(lines)
@@ -957,6 +2302,9 @@ pub fn render(
                 .wrap(Wrap { trim: false }),
             inner,
         );
+        if state.popup != FlakePopup::None {
+            render_popup(frame, state, theme, lang, area);
+        }
         return;
     }
 
@@ -990,6 +2338,18 @@ pub fn render(
                     format!("  ({} inputs)", state.inputs.len()),
                     Style::default().fg(theme.fg_dim),
                 ),
+                Span::styled(
+                    if state.stale.is_empty() {
+                        String::new()
+                    } else {
+                        format!(
+                            "  ⌛ {}",
+                            s.fi_watch_stale_count
+                                .replace("{}", &state.stale.len().to_string())
+                        )
+                    },
+                    Style::default().fg(theme.warning),
+                ),
             ]))
             .style(theme.block_style()),
             chunks[0],
@@ -997,12 +2357,10 @@ pub fn render(
     }
 
     // Tab bar
-    let tabs = vec![
-        s.fi_tab_overview.to_string(),
-        s.fi_tab_update.to_string(),
-        s.fi_tab_history.to_string(),
-        s.fi_tab_details.to_string(),
-    ];
+    let tabs: Vec<String> = FlakeSubTab::all()
+        .iter()
+        .map(|t| t.label(lang).to_string())
+        .collect();
     let tab_selected = state.sub_tab.index();
     let tab_titles: Vec<Line> = tabs.into_iter().map(Line::from).collect();
     let tabs_widget = Tabs::new(tab_titles)
@@ -1023,6 +2381,7 @@ pub fn render(
         FlakeSubTab::Update => render_update(frame, state, theme, lang, chunks[2]),
         FlakeSubTab::History => render_history(frame, state, theme, lang, chunks[2]),
         FlakeSubTab::Details => render_details(frame, state, theme, lang, chunks[2]),
+        FlakeSubTab::Graph => render_graph(frame, state, theme, lang, chunks[2]),
     }
 
     // Popup overlay
@@ -1039,77 +2398,8 @@ fn render_overview(
     area: Rect,
 ) {
     let s = i18n::get_strings(lang);
-    let visible_height = area.height as usize;
 
-    let mut scroll = state.scroll_offset;
-    if state.selected >= scroll + visible_height {
-        scroll = state.selected + 1 - visible_height;
-    }
-    if state.selected < scroll {
-        scroll = state.selected;
-    }
-
-    let name_w = 20usize.min(area.width as usize / 4);
-    let url_w = 30usize.min(area.width as usize / 3);
-    let rev_w = 9;
-
-    let items: Vec<ListItem> = state
-        .inputs
-        .iter()
-        .enumerate()
-        .skip(scroll)
-        .take(visible_height)
-        .map(|(i, input)| {
-            let is_selected = i == state.selected;
-            let style = if is_selected {
-                theme.selected()
-            } else {
-                theme.text()
-            };
-
-            let name_display = if input.name.len() > name_w {
-                let t = safe_truncate(&input.name, name_w.saturating_sub(1));
-                format!("{}…", t)
-            } else {
-                format!("{:<width$}", input.name, width = name_w)
-            };
-
-            let url_display = if input.url.len() > url_w {
-                let t = safe_truncate(&input.url, url_w.saturating_sub(1));
-                format!("{}…", t)
-            } else {
-                format!("{:<width$}", input.url, width = url_w)
-            };
-
-            let age_c = age_color(input.age_days, theme);
-
-            ListItem::new(Line::from(vec![
-                Span::styled(
-                    format!("  {}", name_display),
-                    if is_selected {
-                        style.add_modifier(Modifier::BOLD)
-                    } else {
-                        Style::default().fg(theme.accent)
-                    },
-                ),
-                Span::styled(
-                    format!(" {} ", url_display),
-                    if is_selected {
-                        style
-                    } else {
-                        Style::default().fg(theme.fg_dim)
-                    },
-                ),
-                Span::styled(
-                    format!("{:<width$}", input.rev_short, width = rev_w),
-                    if is_selected { style } else { theme.text() },
-                ),
-                Span::styled(format!(" {}", input.age_text), Style::default().fg(age_c)),
-            ]))
-        })
-        .collect();
-
-    if items.is_empty() {
+    if state.inputs.is_empty() {
         frame.render_widget(
             Paragraph::new(vec![
                 Line::raw(""),
@@ -1121,9 +2411,70 @@ fn render_overview(
             .style(theme.block_style()),
             area,
         );
-    } else {
-        frame.render_widget(List::new(items).style(theme.block_style()), area);
+        return;
     }
+
+    let order = state.overview_order();
+    let columns = overview_columns(s);
+    Table::new(&columns, &state.overview_table, order.len(), |i| {
+        let input = &state.inputs[order[i]];
+        let upstream = match state.upstream.get(&input.name) {
+            Some(UpstreamStatus::Behind { commits, .. }) => {
+                Some((format!("↓{}", commits), theme.warning))
+            }
+            Some(UpstreamStatus::UpToDate) => Some(("✓".to_string(), theme.success)),
+            Some(UpstreamStatus::Checking) => Some(("…".to_string(), theme.fg_dim)),
+            Some(UpstreamStatus::RateLimited(_)) | Some(UpstreamStatus::Offline) => {
+                Some(("?".to_string(), theme.fg_dim))
+            }
+            Some(UpstreamStatus::Error(_)) => Some(("⚠".to_string(), theme.fg_dim)),
+            None => None,
+        };
+        // Status: the most pressing of issue, staleness and upstream
+        let mut status: Vec<&str> = Vec::new();
+        let mut status_color = theme.fg_dim;
+        if let Some(issues) = state.input_issues.get(&input.name) {
+            let (badge, color) = issue_badge(issues, theme);
+            status.push(badge);
+            status_color = color;
+        }
+        if state.stale_entry(&input.name).is_some() {
+            status.push("⌛");
+            if status.len() == 1 {
+                status_color = theme.warning;
+            }
+        }
+        if let Some((text, color)) = &upstream {
+            status.push(text);
+            if status.len() == 1 {
+                status_color = *color;
+            }
+        }
+        vec![
+            Cell::new(input.name.clone(), Style::default().fg(theme.accent)),
+            Cell::new(input.url.clone(), Style::default().fg(theme.fg_dim)),
+            Cell::new(input.rev_short.clone(), theme.text()),
+            Cell::new(
+                input.age_text.clone(),
+                Style::default().fg(age_color(input.age_days, theme)),
+            ),
+            Cell::new(status.join(" "), Style::default().fg(status_color)),
+        ]
+    })
+    .selected(order.iter().position(|&i| i == state.selected))
+    .compact(state.compact)
+    .render(frame, theme, area);
+}
+
+/// Name, URL, revision, age, status (issues, staleness, upstream)
+fn overview_columns(s: &i18n::Strings) -> [Column; 5] {
+    [
+        Column::fill(s.fi_col_name, 14),
+        Column::fill(s.fi_col_url, 20).secondary(),
+        Column::fixed(s.fi_col_rev, 8).secondary(),
+        Column::fixed(s.fi_col_age, 16),
+        Column::fixed(s.fi_col_status, 10),
+    ]
 }
 
 fn render_update(
@@ -1222,6 +2573,11 @@ fn render_history(
 ) {
     let s = i18n::get_strings(lang);
 
+    if let Some(ref diff) = state.lock_diff {
+        render_lock_diff(frame, diff, state.lock_diff_scroll, theme, lang, area);
+        return;
+    }
+
     if state.history.is_empty() {
         frame.render_widget(
             Paragraph::new(vec![
@@ -1311,6 +2667,87 @@ fn render_history(
     frame.render_widget(List::new(items).style(theme.block_style()), area);
 }
 
+/// flake.lock now against a git revision, one row per changed input
+fn render_lock_diff(
+    frame: &mut Frame,
+    diff: &lockdiff::LockDiff,
+    scroll: usize,
+    theme: &Theme,
+    lang: Language,
+    area: Rect,
+) {
+    let s = i18n::get_strings(lang);
+    let mut lines = vec![
+        Line::raw(""),
+        Line::from(vec![
+            Span::styled(
+                format!("  {} ", s.fi_lockdiff_header.replace("{}", &diff.rev)),
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(diff.commit.clone(), Style::default().fg(theme.fg_dim)),
+        ]),
+        Line::raw(""),
+    ];
+    if diff.changes.is_empty() {
+        lines.push(Line::styled(
+            format!("  {}", s.fi_lockdiff_same),
+            Style::default().fg(theme.fg_dim),
+        ));
+    }
+
+    let side = |input: &Option<FlakeInput>| match input {
+        Some(i) => format!("{} ({})", i.rev_short, i.age_text),
+        None => "—".to_string(),
+    };
+    let visible = (area.height as usize).saturating_sub(lines.len() + 2) / 2;
+    for change in diff.changes.iter().skip(scroll).take(visible.max(1)) {
+        let (mark, color) = match (&change.old, &change.new) {
+            (None, _) => ("+", theme.success),
+            (_, None) => ("-", theme.error),
+            _ => ("~", theme.warning),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {} ", mark), Style::default().fg(color)),
+            Span::styled(
+                format!("{:<20}", change.name),
+                Style::default().fg(theme.accent),
+            ),
+            Span::styled(
+                format!(" {} → {}", side(&change.old), side(&change.new)),
+                theme.text(),
+            ),
+        ]));
+        let url = change
+            .new
+            .as_ref()
+            .or(change.old.as_ref())
+            .map(|i| i.url.clone())
+            .unwrap_or_default();
+        let moved = match (&change.old, &change.new) {
+            (Some(old), Some(new)) if old.url != new.url => format!("{} → ", old.url),
+            _ => String::new(),
+        };
+        lines.push(Line::styled(
+            format!("      {}{}", moved, url),
+            Style::default().fg(theme.fg_dim),
+        ));
+    }
+
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        format!(
+            "  {}",
+            s.fi_lockdiff_summary
+                .replacen("{}", &diff.changes.len().to_string(), 1)
+                .replacen("{}", &diff.unchanged.to_string(), 1)
+        ),
+        Style::default().fg(theme.fg_dim),
+    ));
+    frame.render_widget(Paragraph::new(lines).style(theme.block_style()), area);
+}
+
 fn render_details(
     frame: &mut Frame,
     state: &FlakeInputsState,
@@ -1383,13 +2820,80 @@ fn render_details(
                     Style::default().fg(theme.fg_dim),
                 ),
                 Span::styled(
-                    local.format("%Y-%m-%d %H:%M:%S").to_string(),
+                    format_time(&local, state.time_format, lang),
                     Style::default().fg(theme.fg),
                 ),
             ]));
         }
     }
 
+    // Watch mode
+    if let Some(st) = state.stale_entry(&input.name) {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {:<14}", s.fi_detail_watch),
+                Style::default().fg(theme.fg_dim),
+            ),
+            Span::styled(
+                s.fi_watch_stale_detail
+                    .replacen("{}", &st.age_days.to_string(), 1)
+                    .replacen("{}", &st.threshold_days.to_string(), 1),
+                Style::default().fg(theme.warning),
+            ),
+        ]));
+    }
+
+    // Upstream comparison
+    if let Some(status) = state.upstream.get(&input.name) {
+        let (text, color) = upstream_summary(status, s, theme);
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {:<14}", s.fi_detail_upstream),
+                Style::default().fg(theme.fg_dim),
+            ),
+            Span::styled(text, Style::default().fg(color)),
+        ]));
+        if let UpstreamStatus::Behind {
+            last_subject,
+            last_date,
+            ..
+        } = status
+        {
+            let date = last_date
+                .and_then(|t| chrono::DateTime::from_timestamp(t, 0))
+                .map(|dt| {
+                    let local: chrono::DateTime<chrono::Local> = dt.into();
+                    format!(" ({})", local.format("%Y-%m-%d"))
+                })
+                .unwrap_or_default();
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("  {:<14}", s.fi_detail_last_commit),
+                    Style::default().fg(theme.fg_dim),
+                ),
+                Span::styled(last_subject.clone(), theme.text()),
+                Span::styled(date, Style::default().fg(theme.fg_dim)),
+            ]));
+        }
+    }
+
+    // Upstream health, with what to do about it
+    for issue in state.input_issues.get(&input.name).into_iter().flatten() {
+        let (text, hint) = issue_text(issue, s);
+        let (badge, color) = issue_badge(std::slice::from_ref(issue), theme);
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {:<14}", s.fi_detail_health),
+                Style::default().fg(theme.fg_dim),
+            ),
+            Span::styled(format!("{} {}", badge, text), Style::default().fg(color)),
+        ]));
+        lines.push(Line::styled(
+            format!("  {:<14}→ {}", "", hint),
+            Style::default().fg(theme.accent_dim),
+        ));
+    }
+
     // Follows
     if !input.follows.is_empty() {
         lines.push(Line::raw(""));
@@ -1418,6 +2922,349 @@ fn render_details(
     frame.render_widget(Paragraph::new(lines).style(theme.block_style()), area);
 }
 
+fn render_graph(
+    frame: &mut Frame,
+    state: &FlakeInputsState,
+    theme: &Theme,
+    lang: Language,
+    area: Rect,
+) {
+    let s = i18n::get_strings(lang);
+
+    let chunks = Layout::vertical([
+        Constraint::Length(1), // Summary / hint
+        Constraint::Min(3),    // Tree
+    ])
+    .split(area);
+
+    let duplicates = state.graph.duplicate_sources();
+    let summary = if duplicates > 0 {
+        Span::styled(
+            format!(
+                "  {}",
+                s.fi_graph_summary_dupes
+                    .replacen("{}", &(state.graph.nodes.len() - 1).to_string(), 1)
+                    .replacen("{}", &duplicates.to_string(), 1)
+            ),
+            Style::default().fg(theme.warning),
+        )
+    } else {
+        Span::styled(
+            format!(
+                "  {}",
+                s.fi_graph_summary
+                    .replace("{}", &(state.graph.nodes.len() - 1).to_string())
+            ),
+            Style::default().fg(theme.fg_dim),
+        )
+    };
+    frame.render_widget(
+        Paragraph::new(Line::from(vec![
+            summary,
+            Span::styled(
+                format!(
+                    "   [e] {}  [c] {}",
+                    s.fi_graph_expand_all, s.fi_graph_collapse_all
+                ),
+                Style::default().fg(theme.fg_dim),
+            ),
+        ]))
+        .style(theme.block_style()),
+        chunks[0],
+    );
+
+    let rows = state.graph.rows(&state.graph_expanded);
+    if rows.is_empty() {
+        frame.render_widget(
+            Paragraph::new(Line::styled(
+                format!("  {}", s.fi_empty),
+                Style::default().fg(theme.fg_dim),
+            ))
+            .style(theme.block_style()),
+            chunks[1],
+        );
+        return;
+    }
+
+    let height = chunks[1].height as usize;
+    let scroll = state
+        .graph_selected
+        .saturating_sub(height.saturating_sub(1));
+
+    let items: Vec<ListItem> = rows
+        .iter()
+        .enumerate()
+        .skip(scroll)
+        .take(height)
+        .map(|(i, row)| {
+            let is_selected = i == state.graph_selected;
+            let style = if is_selected {
+                theme.selected()
+            } else {
+                theme.text()
+            };
+            let marker = if !row.has_children {
+                "  "
+            } else if row.expanded {
+                "▼ "
+            } else {
+                "▶ "
+            };
+
+            let mut spans = vec![
+                Span::styled(
+                    format!("  {}{}", "  ".repeat(row.depth), marker),
+                    Style::default().fg(theme.accent),
+                ),
+                Span::styled(
+                    row.name.clone(),
+                    if is_selected {
+                        style.add_modifier(Modifier::BOLD)
+                    } else if row.follows.is_some() {
+                        Style::default().fg(theme.fg_dim)
+                    } else {
+                        Style::default().fg(theme.accent)
+                    },
+                ),
+            ];
+
+            if let Some(ref path) = row.follows {
+                spans.push(Span::styled(
+                    format!("  → {} {}", s.fi_graph_follows, path),
+                    Style::default().fg(theme.fg_dim),
+                ));
+            } else if let Some(node) = row.node.as_ref().and_then(|n| state.graph.nodes.get(n)) {
+                spans.push(Span::styled(
+                    format!("  {}", node.source),
+                    if is_selected {
+                        style
+                    } else {
+                        Style::default().fg(theme.fg_dim)
+                    },
+                ));
+                if !node.rev_short.is_empty() {
+                    spans.push(Span::styled(
+                        format!("  {}", node.rev_short),
+                        if is_selected { style } else { theme.text() },
+                    ));
+                }
+                if row.copies > 1 {
+                    spans.push(Span::styled(
+                        format!(
+                            "  ⚠ {}",
+                            s.fi_graph_copies.replace("{}", &row.copies.to_string())
+                        ),
+                        Style::default().fg(theme.warning),
+                    ));
+                }
+            }
+
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    frame.render_widget(List::new(items).style(theme.block_style()), chunks[1]);
+}
+
+/// Badge for the worst of an input's issues: a dead input blocks
+/// updates, a moved one still works through the redirect
+fn issue_badge(issues: &[InputIssue], theme: &Theme) -> (&'static str, ratatui::style::Color) {
+    if has_dead_issue(issues) {
+        ("⛔", theme.error)
+    } else {
+        ("⚠", theme.warning)
+    }
+}
+
+/// The input is gone or frozen upstream, not just outdated
+fn has_dead_issue(issues: &[InputIssue]) -> bool {
+    issues.iter().any(|i| {
+        matches!(
+            i,
+            InputIssue::Archived | InputIssue::Missing | InputIssue::RefDeleted(_)
+        )
+    })
+}
+
+/// Problem and guidance for one issue
+fn issue_text(issue: &InputIssue, s: &i18n::Strings) -> (String, String) {
+    match issue {
+        InputIssue::Archived => (
+            s.fi_issue_archived.to_string(),
+            s.fi_issue_archived_hint.to_string(),
+        ),
+        InputIssue::Moved { to } => (
+            s.fi_issue_moved.replace("{}", to),
+            s.fi_issue_moved_hint.replace("{}", to),
+        ),
+        InputIssue::Missing => (
+            s.fi_issue_missing.to_string(),
+            s.fi_issue_missing_hint.to_string(),
+        ),
+        InputIssue::RefDeleted(r) => (
+            s.fi_issue_ref_deleted.replace("{}", r),
+            s.fi_issue_ref_deleted_hint.to_string(),
+        ),
+        InputIssue::TagMoved(t) => (
+            s.fi_issue_tag_moved.replace("{}", t),
+            s.fi_issue_tag_moved_hint.to_string(),
+        ),
+    }
+}
+
+/// One-line description of an upstream status
+fn upstream_summary(
+    status: &UpstreamStatus,
+    s: &i18n::Strings,
+    theme: &Theme,
+) -> (String, ratatui::style::Color) {
+    match status {
+        UpstreamStatus::Checking => (format!("{}...", s.fi_upstream_checking), theme.fg_dim),
+        UpstreamStatus::UpToDate => (s.fi_upstream_up_to_date.to_string(), theme.success),
+        UpstreamStatus::Behind { commits, .. } => (
+            s.fi_upstream_behind.replace("{}", &commits.to_string()),
+            theme.warning,
+        ),
+        UpstreamStatus::RateLimited(reset) => {
+            let until = reset
+                .and_then(|t| chrono::DateTime::from_timestamp(t, 0))
+                .map(|dt| {
+                    let local: chrono::DateTime<chrono::Local> = dt.into();
+                    local.format("%H:%M").to_string()
+                })
+                .unwrap_or_else(|| "?".to_string());
+            (
+                s.fi_upstream_rate_limited.replace("{}", &until),
+                theme.warning,
+            )
+        }
+        UpstreamStatus::Offline => (s.fi_upstream_offline.to_string(), theme.fg_dim),
+        UpstreamStatus::Error(e) => (format!("{} ({})", s.fi_upstream_error, e), theme.fg_dim),
+    }
+}
+
+/// `F`: the system flake and the registered projects
+fn render_projects(
+    frame: &mut Frame,
+    state: &FlakeInputsState,
+    theme: &Theme,
+    lang: Language,
+    area: Rect,
+) {
+    let s = i18n::get_strings(lang);
+    let mut lines = vec![Line::raw("")];
+    for (i, row) in state.project_rows().iter().enumerate() {
+        let selected = i == state.projects_selected;
+        let (name, style) = match row {
+            None => (s.fi_project_system.to_string(), theme.text()),
+            Some(dir) if projects::is_flake(std::path::Path::new(&projects::expand_home(dir))) => {
+                (dir.clone(), theme.text())
+            }
+            Some(dir) => (
+                format!("{}  ({})", dir, s.fi_project_gone),
+                theme.text_dim(),
+            ),
+        };
+        let active = if *row == state.flake_dir { " ●" } else { "" };
+        lines.push(Line::from(vec![
+            Span::styled(
+                if selected { "  ▸ " } else { "    " },
+                Style::default().fg(theme.accent),
+            ),
+            Span::styled(name, if selected { theme.selected() } else { style }),
+            Span::styled(active, Style::default().fg(theme.success)),
+        ]));
+    }
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        format!(
+            "  [Enter] {}  [o] {}  [d] {}  [Esc] {}",
+            s.fi_project_open, s.fi_project_add, s.fi_project_remove, s.cancel
+        ),
+        Style::default().fg(theme.fg_dim),
+    ));
+
+    let block = Block::default()
+        .title(format!(" {} ", s.fi_projects_title))
+        .title_style(theme.title())
+        .borders(Borders::ALL)
+        .border_style(theme.border_focused())
+        .style(theme.block_style());
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// `o` in the project list: directories, flakes marked with ❄
+fn render_picker(
+    frame: &mut Frame,
+    state: &FlakeInputsState,
+    theme: &Theme,
+    lang: Language,
+    area: Rect,
+) {
+    let Some(picker) = &state.picker else {
+        return;
+    };
+    let s = i18n::get_strings(lang);
+    let mut lines = vec![
+        Line::styled(
+            format!(
+                "  📁 {}",
+                projects::shorten_home(&picker.dir.to_string_lossy())
+            ),
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Line::raw(""),
+    ];
+
+    let room = area.height.saturating_sub(6) as usize;
+    let scroll = (picker.selected + 1).saturating_sub(room);
+    if picker.entries.is_empty() {
+        lines.push(Line::styled(
+            format!("    {}", s.fi_picker_empty),
+            Style::default().fg(theme.fg_dim),
+        ));
+    }
+    for (i, entry) in picker.entries.iter().enumerate().skip(scroll).take(room) {
+        let selected = i == picker.selected;
+        let style = if selected {
+            theme.selected()
+        } else if entry.flake {
+            Style::default().fg(theme.accent)
+        } else {
+            theme.text()
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                if selected { "  ▸ " } else { "    " },
+                Style::default().fg(theme.accent),
+            ),
+            Span::styled(format!("{}/", entry.name), style),
+            Span::styled(
+                if entry.flake { "  ❄ flake" } else { "" },
+                Style::default().fg(theme.success),
+            ),
+        ]));
+    }
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        format!(
+            "  [Enter/l] {}  [h] {}  [s] {}  [Esc] {}",
+            s.fi_picker_enter, s.fi_picker_up, s.fi_picker_select, s.back
+        ),
+        Style::default().fg(theme.fg_dim),
+    ));
+
+    let block = Block::default()
+        .title(format!(" {} ", s.fi_picker_title))
+        .title_style(theme.title())
+        .borders(Borders::ALL)
+        .border_style(theme.border_focused())
+        .style(theme.block_style());
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
 fn render_popup(
     frame: &mut Frame,
     state: &FlakeInputsState,
@@ -1427,9 +3274,36 @@ fn render_popup(
 ) {
     let s = i18n::get_strings(lang);
 
-    // Center popup
-    let popup_w = 50u16.min(area.width.saturating_sub(4));
-    let popup_h = 12u16.min(area.height.saturating_sub(4));
+    // Center popup (URLs need the room)
+    let width = if matches!(
+        state.popup,
+        FlakePopup::EditInput(_)
+            | FlakePopup::ConfirmCommit
+            | FlakePopup::SwitchBranch
+            | FlakePopup::BranchImpact
+            | FlakePopup::ConfirmRebuild
+            | FlakePopup::Report
+            | FlakePopup::Projects
+            | FlakePopup::PickDir
+    ) {
+        72
+    } else {
+        50
+    };
+    let height = match (&state.popup, &state.pending_commit, &state.branch_switch) {
+        (FlakePopup::ConfirmCommit, Some(c), _) => {
+            (10 + c.status.len() + c.diff_stat.len()).min(20) as u16
+        }
+        (FlakePopup::SwitchBranch, _, Some(b)) => (11 + b.choices.len()) as u16,
+        (FlakePopup::BranchImpact, _, _) => 24,
+        (FlakePopup::ConfirmRebuild, _, _) => 13,
+        (FlakePopup::Report, _, _) => 24,
+        (FlakePopup::Projects, _, _) => (9 + state.flakes.len()) as u16,
+        (FlakePopup::PickDir, _, _) => 24,
+        _ => 12,
+    };
+    let popup_w = width.min(area.width.saturating_sub(4));
+    let popup_h = height.min(area.height.saturating_sub(4));
     let popup_x = area.x + (area.width.saturating_sub(popup_w)) / 2;
     let popup_y = area.y + (area.height.saturating_sub(popup_h)) / 2;
     let popup_area = Rect::new(popup_x, popup_y, popup_w, popup_h);
@@ -1506,18 +3380,490 @@ fn render_popup(
 
             frame.render_widget(Paragraph::new(lines).block(block), popup_area);
         }
-        FlakePopup::None => {}
-    }
-}
+        FlakePopup::ConfirmRollback(idx) => {
+            let Some(entry) = state.history.get(*idx) else {
+                return;
+            };
+            let lines = vec![
+                Line::raw(""),
+                Line::styled(
+                    format!("  {}", s.fi_rollback_title),
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Line::raw(""),
+                Line::from(vec![
+                    Span::styled(format!("    {}  ", entry.input_name), theme.text()),
+                    Span::styled(entry.new_rev.clone(), Style::default().fg(theme.fg_dim)),
+                    Span::styled(" → ", Style::default().fg(theme.fg_dim)),
+                    Span::styled(entry.old_rev.clone(), Style::default().fg(theme.warning)),
+                ]),
+                Line::raw(""),
+                Line::styled(
+                    format!("  {}", s.fi_rollback_note),
+                    Style::default().fg(theme.fg_dim),
+                ),
+                Line::raw(""),
+                Line::styled(
+                    format!("  [Enter/y] {}  [Esc/n] {}", s.confirm, s.cancel),
+                    Style::default().fg(theme.fg_dim),
+                ),
+            ];
 
-/// Safely truncate a string to at most `max_bytes`
-fn safe_truncate(s: &str, max_bytes: usize) -> &str {
-    if s.len() <= max_bytes {
-        return s;
-    }
-    let mut end = max_bytes;
-    while end > 0 && !s.is_char_boundary(end) {
-        end -= 1;
+            let block = Block::default()
+                .title(format!(" {} ", s.fi_tab_history))
+                .title_style(theme.title())
+                .borders(Borders::ALL)
+                .border_style(theme.border_focused())
+                .style(theme.block_style());
+
+            frame.render_widget(
+                Paragraph::new(lines)
+                    .block(block)
+                    .wrap(Wrap { trim: false }),
+                popup_area,
+            );
+        }
+        FlakePopup::EditInput(field) => {
+            let (title, name, note) = match field {
+                EditField::Url(name) => (s.fi_edit_url_title, name.as_str(), None),
+                EditField::Branch(name) => (
+                    s.fi_edit_branch_title,
+                    name.as_str(),
+                    Some(s.fi_edit_branch_note),
+                ),
+                EditField::AddName => (s.fi_add_name_title, "", None),
+                EditField::AddUrl(name) => (s.fi_add_url_title, name.as_str(), None),
+                EditField::LockRev => (s.fi_lockdiff_title, "", Some(s.fi_lockdiff_note)),
+            };
+            let mut lines = vec![
+                Line::raw(""),
+                Line::styled(
+                    format!("  {}", title),
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Line::raw(""),
+            ];
+            if !name.is_empty() {
+                lines.push(Line::styled(format!("  {}", name), theme.text()));
+            }
+            lines.push(Line::from(vec![
+                Span::styled("  > ", Style::default().fg(theme.accent)),
+                Span::styled(state.edit_buffer.clone(), theme.text()),
+                Span::styled("│", Style::default().fg(theme.accent)),
+            ]));
+            if let Some(note) = note {
+                lines.push(Line::raw(""));
+                lines.push(Line::styled(
+                    format!("  {}", note),
+                    Style::default().fg(theme.fg_dim),
+                ));
+            }
+            lines.push(Line::raw(""));
+            lines.push(Line::styled(
+                format!("  [Enter] {}  [Esc] {}", s.confirm, s.cancel),
+                Style::default().fg(theme.fg_dim),
+            ));
+
+            let block = Block::default()
+                .title(" flake.nix ")
+                .title_style(theme.title())
+                .borders(Borders::ALL)
+                .border_style(theme.border_focused())
+                .style(theme.block_style());
+
+            frame.render_widget(
+                Paragraph::new(lines)
+                    .block(block)
+                    .wrap(Wrap { trim: false }),
+                popup_area,
+            );
+        }
+        FlakePopup::ConfirmRemove(name) => {
+            let lines = vec![
+                Line::raw(""),
+                Line::styled(
+                    format!("  {}", s.fi_remove_title),
+                    Style::default()
+                        .fg(theme.warning)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Line::raw(""),
+                Line::styled(format!("    • {}", name), theme.text()),
+                Line::raw(""),
+                Line::styled(
+                    format!("  {}", s.fi_remove_note),
+                    Style::default().fg(theme.fg_dim),
+                ),
+                Line::raw(""),
+                Line::styled(
+                    format!("  [Enter/y] {}  [Esc/n] {}", s.confirm, s.cancel),
+                    Style::default().fg(theme.fg_dim),
+                ),
+            ];
+
+            let block = Block::default()
+                .title(" flake.nix ")
+                .title_style(theme.title())
+                .borders(Borders::ALL)
+                .border_style(theme.border_focused())
+                .style(theme.block_style());
+
+            frame.render_widget(
+                Paragraph::new(lines)
+                    .block(block)
+                    .wrap(Wrap { trim: false }),
+                popup_area,
+            );
+        }
+        FlakePopup::Relocking => {
+            let lines = vec![
+                Line::raw(""),
+                Line::styled(
+                    format!("  ⏳ {}...", s.fi_relocking),
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            ];
+
+            let block = Block::default()
+                .title(" flake.nix ")
+                .title_style(theme.title())
+                .borders(Borders::ALL)
+                .border_style(theme.border_focused())
+                .style(theme.block_style());
+
+            frame.render_widget(Paragraph::new(lines).block(block), popup_area);
+        }
+        FlakePopup::ConfirmCommit => {
+            let Some(pending) = &state.pending_commit else {
+                return;
+            };
+            let mut lines = vec![
+                Line::raw(""),
+                Line::styled(
+                    format!("  {}", s.fi_commit_title),
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Line::raw(""),
+            ];
+            for status in &pending.status {
+                lines.push(Line::styled(
+                    format!("    {}", status),
+                    Style::default().fg(theme.warning),
+                ));
+            }
+            for stat in &pending.diff_stat {
+                lines.push(Line::styled(
+                    format!("    {}", stat.trim()),
+                    Style::default().fg(theme.fg_dim),
+                ));
+            }
+            lines.push(Line::raw(""));
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("  {} ", s.fi_commit_message),
+                    Style::default().fg(theme.fg_dim),
+                ),
+                Span::styled(pending.message.clone(), theme.text()),
+            ]));
+            lines.push(Line::raw(""));
+            lines.push(Line::styled(
+                format!(
+                    "  [Enter/y] {}  [Esc/n] {}",
+                    s.fi_commit_confirm, s.fi_commit_skip
+                ),
+                Style::default().fg(theme.fg_dim),
+            ));
+
+            let block = Block::default()
+                .title(" git ")
+                .title_style(theme.title())
+                .borders(Borders::ALL)
+                .border_style(theme.border_focused())
+                .style(theme.block_style());
+
+            frame.render_widget(
+                Paragraph::new(lines)
+                    .block(block)
+                    .wrap(Wrap { trim: false }),
+                popup_area,
+            );
+        }
+        FlakePopup::Report => {
+            let mut lines = vec![Line::raw("")];
+            match &state.report {
+                None => lines.push(Line::styled(
+                    format!("  ⏳ {}", s.fi_report_counting),
+                    Style::default().fg(theme.fg_dim),
+                )),
+                Some(text) => {
+                    let room = popup_area.height.saturating_sub(6) as usize;
+                    lines.extend(
+                        text.lines()
+                            .take(room)
+                            .map(|l| Line::styled(format!("  {}", l), theme.text())),
+                    );
+                    if text.lines().count() > room {
+                        lines.push(Line::styled("  …", Style::default().fg(theme.fg_dim)));
+                    }
+                }
+            }
+            lines.push(Line::raw(""));
+            lines.push(Line::styled(
+                format!(
+                    "  [w] {}  [c] {}  [Esc] {}",
+                    s.fi_report_write, s.fi_report_copy, s.cancel
+                ),
+                Style::default().fg(theme.fg_dim),
+            ));
+
+            let block = Block::default()
+                .title(format!(" {} ", s.fi_report_title))
+                .title_style(theme.title())
+                .borders(Borders::ALL)
+                .border_style(theme.border_focused())
+                .style(theme.block_style());
+            frame.render_widget(Paragraph::new(lines).block(block), popup_area);
+        }
+        FlakePopup::ConfirmRebuild => {
+            let changed: Vec<&UpdateResult> = state
+                .update_results
+                .iter()
+                .filter(|r| r.success && r.old_rev != r.new_rev)
+                .collect();
+            let mut lines = vec![
+                Line::raw(""),
+                Line::styled(
+                    format!(
+                        "  {}",
+                        s.fi_update_all_done
+                            .replace("{}", &changed.len().to_string())
+                    ),
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Line::raw(""),
+            ];
+            for r in changed.iter().take(5) {
+                lines.push(Line::from(vec![
+                    Span::styled(format!("    {:<16}", r.input_name), theme.text()),
+                    Span::styled(
+                        format!("{} → {}", r.old_rev, r.new_rev),
+                        Style::default().fg(theme.fg_dim),
+                    ),
+                ]));
+            }
+            if changed.len() > 5 {
+                lines.push(Line::styled(
+                    format!("    … +{}", changed.len() - 5),
+                    Style::default().fg(theme.fg_dim),
+                ));
+            }
+            lines.push(Line::raw(""));
+            lines.push(Line::styled(
+                format!("  {}", s.fi_update_all_next),
+                Style::default().fg(theme.fg_dim),
+            ));
+
+            let block = Block::default()
+                .title(format!(" {} ", s.tab_rebuild))
+                .title_style(theme.title())
+                .borders(Borders::ALL)
+                .border_style(theme.border_focused())
+                .style(theme.block_style());
+            frame.render_widget(
+                Paragraph::new(lines)
+                    .block(block)
+                    .wrap(Wrap { trim: false }),
+                popup_area,
+            );
+        }
+        FlakePopup::Projects => render_projects(frame, state, theme, lang, popup_area),
+        FlakePopup::PickDir => render_picker(frame, state, theme, lang, popup_area),
+        FlakePopup::SwitchBranch => {
+            let Some(switch) = &state.branch_switch else {
+                return;
+            };
+            let mut lines = vec![
+                Line::raw(""),
+                Line::styled(
+                    format!("  {}", s.fi_branch_title.replace("{}", &switch.input)),
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Line::raw(""),
+            ];
+            for (i, choice) in switch.choices.iter().enumerate() {
+                let current = if *choice == switch.from {
+                    format!("  ({})", s.fi_branch_current)
+                } else {
+                    String::new()
+                };
+                let style = if i == switch.selected {
+                    theme.selected()
+                } else {
+                    theme.text()
+                };
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        if i == switch.selected {
+                            "  ▸ "
+                        } else {
+                            "    "
+                        },
+                        Style::default().fg(theme.accent),
+                    ),
+                    Span::styled(choice.clone(), style),
+                    Span::styled(current, Style::default().fg(theme.fg_dim)),
+                ]));
+            }
+            lines.push(Line::raw(""));
+            lines.push(Line::styled(
+                format!("  {}", s.fi_branch_notes),
+                Style::default().fg(theme.fg_dim),
+            ));
+            lines.push(Line::styled(
+                format!("  {}", branch::release_notes_url(switch.target())),
+                Style::default().fg(theme.accent),
+            ));
+            lines.push(Line::raw(""));
+            lines.push(Line::styled(
+                format!(
+                    "  [j/k] {}  [Enter] {}  [Esc] {}",
+                    s.navigate, s.confirm, s.cancel
+                ),
+                Style::default().fg(theme.fg_dim),
+            ));
+
+            let block = Block::default()
+                .title(" flake.nix ")
+                .title_style(theme.title())
+                .borders(Borders::ALL)
+                .border_style(theme.border_focused())
+                .style(theme.block_style());
+
+            frame.render_widget(Paragraph::new(lines).block(block), popup_area);
+        }
+        FlakePopup::BranchImpact => {
+            let Some(switch) = &state.branch_switch else {
+                return;
+            };
+            let mut lines = vec![
+                Line::raw(""),
+                Line::styled(
+                    format!(
+                        "  {}",
+                        s.fi_branch_switched
+                            .replacen("{}", &switch.input, 1)
+                            .replacen("{}", switch.target(), 1)
+                    ),
+                    Style::default()
+                        .fg(theme.success)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Line::raw(""),
+            ];
+            match &switch.impact {
+                None => lines.push(Line::styled(
+                    format!("  ⏳ {}...", s.fi_branch_previewing),
+                    Style::default().fg(theme.accent),
+                )),
+                Some(Err(e)) => {
+                    lines.push(Line::styled(
+                        format!("  {}", s.fi_branch_preview_failed),
+                        Style::default().fg(theme.error),
+                    ));
+                    if !e.is_empty() {
+                        lines.push(Line::styled(format!("  {}", e), theme.text_dim()));
+                    }
+                }
+                Some(Ok(impact)) => {
+                    lines.push(Line::styled(
+                        format!(
+                            "  {}",
+                            s.fi_branch_impact_counts
+                                .replacen("{}", &impact.to_build.to_string(), 1)
+                                .replacen("{}", &impact.to_fetch.to_string(), 1)
+                        ),
+                        theme.text(),
+                    ));
+                    if let Some(download) = &impact.download {
+                        lines.push(Line::styled(format!("  {}", download), theme.text_dim()));
+                    }
+                    lines.push(Line::raw(""));
+                    lines.push(Line::styled(
+                        format!(
+                            "  {}",
+                            s.fi_branch_impact_updated
+                                .replace("{}", &impact.updated.len().to_string())
+                        ),
+                        Style::default().fg(theme.accent),
+                    ));
+                    const SHOWN: usize = 8;
+                    for (name, old, new) in impact.updated.iter().take(SHOWN) {
+                        lines.push(Line::from(vec![
+                            Span::styled(format!("    {} ", name), theme.text()),
+                            Span::styled(old.clone(), Style::default().fg(theme.fg_dim)),
+                            Span::styled(" → ", Style::default().fg(theme.fg_dim)),
+                            Span::styled(new.clone(), Style::default().fg(theme.success)),
+                        ]));
+                    }
+                    if impact.updated.len() > SHOWN {
+                        lines.push(Line::styled(
+                            format!("    … +{}", impact.updated.len() - SHOWN),
+                            theme.text_dim(),
+                        ));
+                    }
+                    if !impact.added.is_empty() {
+                        lines.push(Line::styled(
+                            format!(
+                                "  {}",
+                                s.fi_branch_impact_added
+                                    .replace("{}", &impact.added.len().to_string())
+                            ),
+                            theme.text_dim(),
+                        ));
+                    }
+                }
+            }
+            lines.push(Line::raw(""));
+            lines.push(Line::styled(
+                format!("  {}", s.fi_branch_notes),
+                Style::default().fg(theme.fg_dim),
+            ));
+            lines.push(Line::styled(
+                format!("  {}", branch::release_notes_url(switch.target())),
+                Style::default().fg(theme.accent),
+            ));
+            lines.push(Line::raw(""));
+            lines.push(Line::styled(
+                format!("  [Enter] {}  [u] {}", s.fi_branch_keep, s.fi_branch_revert),
+                Style::default().fg(theme.fg_dim),
+            ));
+
+            let block = Block::default()
+                .title(" nixpkgs ")
+                .title_style(theme.title())
+                .borders(Borders::ALL)
+                .border_style(theme.border_focused())
+                .style(theme.block_style());
+
+            frame.render_widget(
+                Paragraph::new(lines)
+                    .block(block)
+                    .wrap(Wrap { trim: false }),
+                popup_area,
+            );
+        }
+        FlakePopup::None => {}
     }
-    &s[..end]
 }
@@ -0,0 +1,142 @@
+//! Flake projects: flakes outside the usual locations
+//!
+//! Directories registered in `flakes` (config) are listed by `F` next to
+//! the system flake; the one picked is remembered as `flake_path`. `o` in
+//! that list opens a directory picker that marks directories holding a
+//! flake.nix, so a flake in `~/src/infra` is two keystrokes away.
+
+pub use crate::nix::detect::expand_home;
+use std::path::{Path, PathBuf};
+
+pub fn is_flake(dir: &Path) -> bool {
+    dir.join("flake.nix").is_file()
+}
+
+/// An absolute path with the home directory shortened to `~`
+pub fn shorten_home(path: &str) -> String {
+    let home = std::env::var("HOME").unwrap_or_default();
+    match path.strip_prefix(&home) {
+        Some(rest) if !home.is_empty() && (rest.is_empty() || rest.starts_with('/')) => {
+            format!("~{}", rest)
+        }
+        _ => path.to_string(),
+    }
+}
+
+/// Add `dir` to the registered flakes unless it's there already
+pub fn register(flakes: &mut Vec<String>, dir: &str) {
+    if !flakes.iter().any(|f| expand_home(f) == dir) {
+        flakes.push(shorten_home(dir));
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirEntry {
+    pub name: String,
+    pub flake: bool,
+}
+
+/// Directory browser for the `o` picker
+#[derive(Debug, Clone)]
+pub struct DirPicker {
+    pub dir: PathBuf,
+    pub entries: Vec<DirEntry>,
+    pub selected: usize,
+}
+
+impl DirPicker {
+    pub fn open(dir: &Path) -> Self {
+        Self {
+            dir: dir.to_path_buf(),
+            entries: list_dirs(dir),
+            selected: 0,
+        }
+    }
+
+    pub fn selected_path(&self) -> Option<PathBuf> {
+        self.entries
+            .get(self.selected)
+            .map(|e| self.dir.join(&e.name))
+    }
+
+    /// Into the highlighted directory
+    pub fn enter(&mut self) {
+        if let Some(path) = self.selected_path() {
+            *self = Self::open(&path);
+        }
+    }
+
+    /// Up to the parent, with the directory we came from highlighted
+    pub fn up(&mut self) {
+        let Some(parent) = self.dir.parent().map(Path::to_path_buf) else {
+            return;
+        };
+        let from = self
+            .dir
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string());
+        *self = Self::open(&parent);
+        if let Some(pos) = self
+            .entries
+            .iter()
+            .position(|e| Some(&e.name) == from.as_ref())
+        {
+            self.selected = pos;
+        }
+    }
+}
+
+/// Subdirectories of `dir`, visible ones first, each sorted by name
+fn list_dirs(dir: &Path) -> Vec<DirEntry> {
+    let Ok(read) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut entries: Vec<DirEntry> = read
+        .flatten()
+        .filter(|e| e.path().is_dir())
+        .map(|e| DirEntry {
+            name: e.file_name().to_string_lossy().to_string(),
+            flake: is_flake(&e.path()),
+        })
+        .collect();
+    entries.sort_by_key(|e| (e.name.starts_with('.'), e.name.to_lowercase()));
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_picker_marks_flakes() {
+        let root = std::env::temp_dir().join(format!("nixmate-projects-{}", std::process::id()));
+        for dir in ["infra", ".dotfiles", "Notes"] {
+            std::fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        std::fs::write(root.join("infra/flake.nix"), "{ }").unwrap();
+        std::fs::write(root.join("README"), "").unwrap();
+
+        let mut picker = DirPicker::open(&root);
+        let names: Vec<(&str, bool)> = picker
+            .entries
+            .iter()
+            .map(|e| (e.name.as_str(), e.flake))
+            .collect();
+        assert_eq!(
+            names,
+            vec![("infra", true), ("Notes", false), (".dotfiles", false)]
+        );
+
+        picker.enter();
+        assert_eq!(picker.dir, root.join("infra"));
+        picker.up();
+        assert_eq!((picker.dir.clone(), picker.selected), (root.clone(), 0));
+
+        let mut flakes = vec!["/srv/flake".to_string()];
+        register(&mut flakes, "/srv/flake");
+        register(&mut flakes, "/opt/other");
+        assert_eq!(flakes.len(), 2);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
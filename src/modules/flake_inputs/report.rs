@@ -0,0 +1,141 @@
+//! Markdown report of the last update, for a PR description or changelog.
+//!
+//! One table row per changed input with the old and new revision; GitHub
+//! inputs link both commits and the compare view, and get the number of
+//! commits in between from the compare API (blocking — background thread).
+//! The report is plain English Markdown whatever the UI language, like the
+//! commit messages it usually ends up next to.
+
+use super::{upstream, FlakeInput, UpdateResult};
+
+/// One updated (or failed) input
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub name: String,
+    pub old_rev: String,
+    pub new_rev: String,
+    /// `owner/repo` for GitHub inputs
+    pub github: Option<String>,
+    pub commits: Option<u64>,
+    /// Error of a failed update
+    pub error: Option<String>,
+}
+
+/// Entries for the inputs an update changed or failed on
+pub fn entries(results: &[UpdateResult], inputs: &[FlakeInput]) -> Vec<Entry> {
+    results
+        .iter()
+        .filter(|r| !r.success || r.old_rev != r.new_rev)
+        .map(|r| {
+            let input = inputs.iter().find(|i| i.name == r.input_name);
+            Entry {
+                name: r.input_name.clone(),
+                old_rev: r.old_rev.clone(),
+                new_rev: r.new_rev.clone(),
+                github: input
+                    .filter(|i| i.input_type == "github" && !i.owner.is_empty())
+                    .map(|i| format!("{}/{}", i.owner, i.repo)),
+                commits: None,
+                error: (!r.success).then(|| r.message.clone()),
+            }
+        })
+        .collect()
+}
+
+/// Fill in commit counts of GitHub inputs (network)
+pub fn count_commits(entries: &mut [Entry], token: Option<&str>) {
+    for entry in entries.iter_mut().filter(|e| e.error.is_none()) {
+        if let Some(repo) = &entry.github {
+            entry.commits = upstream::commits_between(repo, &entry.old_rev, &entry.new_rev, token);
+        }
+    }
+}
+
+/// The report; `generated` is the formatted timestamp
+pub fn markdown(entries: &[Entry], generated: &str) -> String {
+    let updated: Vec<&Entry> = entries.iter().filter(|e| e.error.is_none()).collect();
+    let mut out = String::from("## flake.lock update\n\n");
+    out.push_str(&format!(
+        "Generated {} · {} input{} updated\n\n",
+        generated,
+        updated.len(),
+        if updated.len() == 1 { "" } else { "s" }
+    ));
+
+    if !updated.is_empty() {
+        out.push_str("| Input | Old | New | Commits |\n|---|---|---|---|\n");
+        for e in &updated {
+            let (old, new, commits) = match &e.github {
+                Some(repo) => {
+                    let base = format!("https://github.com/{}", repo);
+                    let count = e.commits.map_or("?".to_string(), |c| c.to_string());
+                    (
+                        format!("[`{}`]({}/commit/{})", e.old_rev, base, e.old_rev),
+                        format!("[`{}`]({}/commit/{})", e.new_rev, base, e.new_rev),
+                        format!(
+                            "[{}]({}/compare/{}...{})",
+                            count, base, e.old_rev, e.new_rev
+                        ),
+                    )
+                }
+                None => (
+                    format!("`{}`", e.old_rev),
+                    format!("`{}`", e.new_rev),
+                    "–".to_string(),
+                ),
+            };
+            out.push_str(&format!(
+                "| {} | {} | {} | {} |\n",
+                e.name, old, new, commits
+            ));
+        }
+    }
+
+    let failed: Vec<&Entry> = entries.iter().filter(|e| e.error.is_some()).collect();
+    if !failed.is_empty() {
+        out.push_str("\nFailed:\n\n");
+        for e in failed {
+            out.push_str(&format!(
+                "- {}: {}\n",
+                e.name,
+                e.error.as_deref().unwrap_or_default()
+            ));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_report() {
+        let entries = vec![
+            Entry {
+                name: "nixpkgs".into(),
+                old_rev: "abc1234".into(),
+                new_rev: "def5678".into(),
+                github: Some("NixOS/nixpkgs".into()),
+                commits: Some(412),
+                error: None,
+            },
+            Entry {
+                name: "private".into(),
+                old_rev: "1111111".into(),
+                new_rev: "1111111".into(),
+                github: None,
+                commits: None,
+                error: Some("access denied".into()),
+            },
+        ];
+        let md = markdown(&entries, "2024-05-01 12:00");
+        assert!(md.contains("1 input updated"));
+        assert!(md.contains(
+            "| nixpkgs | [`abc1234`](https://github.com/NixOS/nixpkgs/commit/abc1234) | \
+             [`def5678`](https://github.com/NixOS/nixpkgs/commit/def5678) | \
+             [412](https://github.com/NixOS/nixpkgs/compare/abc1234...def5678) |"
+        ));
+        assert!(md.contains("- private: access denied"));
+    }
+}
@@ -0,0 +1,244 @@
+//! Roll back a single input to the lock entry it had before an update.
+//!
+//! `nix flake lock --override-input` would record the override as the
+//! input's `original`, and the next `nix flake lock` would silently re-lock
+//! it from flake.nix. Instead we put the previous node (and the nodes below
+//! it) back into flake.lock, exactly as they were before the update.
+
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+
+/// Restore `input` in `<flake_dir>/flake.lock` from `old_lock`.
+/// Returns the short revision that is locked afterwards.
+pub fn restore_input(flake_dir: &str, input: &str, old_lock: &Value) -> Result<String, String> {
+    let lock_path = format!("{}/flake.lock", flake_dir);
+    let content = std::fs::read_to_string(&lock_path).map_err(|e| e.to_string())?;
+    let mut lock: Value = serde_json::from_str(&content).map_err(|e| e.to_string())?;
+
+    let restored = restore_in(&mut lock, input, old_lock)?;
+
+    let mut out = serde_json::to_string_pretty(&lock).map_err(|e| e.to_string())?;
+    out.push('\n');
+    // Via a temp file: a half-written flake.lock breaks every nix command
+    let tmp_path = format!("{}.tmp", lock_path);
+    std::fs::write(&tmp_path, out).map_err(|e| format!("{}: {}", tmp_path, e))?;
+    std::fs::rename(&tmp_path, &lock_path).map_err(|e| format!("{}: {}", lock_path, e))?;
+    Ok(restored)
+}
+
+fn restore_in(lock: &mut Value, input: &str, old_lock: &Value) -> Result<String, String> {
+    let old_nodes = old_lock
+        .get("nodes")
+        .and_then(|n| n.as_object())
+        .ok_or("previous flake.lock has no nodes")?;
+    let old_root = root_name(old_lock);
+    let old_target = old_nodes
+        .get(&old_root)
+        .and_then(|r| r.get("inputs"))
+        .and_then(|i| i.get(input))
+        .and_then(|t| t.as_str())
+        .ok_or_else(|| format!("'{}' was not a locked input before the update", input))?
+        .to_string();
+
+    let root = root_name(lock);
+    let nodes = lock
+        .get_mut("nodes")
+        .and_then(|n| n.as_object_mut())
+        .ok_or("flake.lock has no nodes")?;
+
+    // Nodes still used by the rest of the graph must not be overwritten
+    let current_target = nodes
+        .get(&root)
+        .and_then(|r| r.get("inputs"))
+        .and_then(|i| i.get(input))
+        .and_then(|t| t.as_str())
+        .map(|t| t.to_string());
+    let in_use = reachable(nodes, &root, current_target.as_deref());
+
+    // Old subtree, renamed where a name is taken by a different node
+    let mut rename: HashMap<String, String> = HashMap::new();
+    for name in reachable(old_nodes, &old_target, None) {
+        let old_node = old_nodes
+            .get(&name)
+            .ok_or_else(|| format!("previous flake.lock refers to missing node '{}'", name))?;
+        let taken = in_use.contains(&name) && nodes.get(&name) != Some(old_node);
+        let new_name = if taken {
+            (1..)
+                .map(|i| format!("{}_rollback{}", name, i))
+                .find(|n| !nodes.contains_key(n) && !old_nodes.contains_key(n))
+                .unwrap_or_default()
+        } else {
+            name.clone()
+        };
+        rename.insert(name, new_name);
+    }
+    for (old_name, new_name) in &rename {
+        let mut node = old_nodes[old_name.as_str()].clone();
+        if let Some(inputs) = node.get_mut("inputs").and_then(|i| i.as_object_mut()) {
+            for target in inputs.values_mut() {
+                if let Some(t) = target.as_str().and_then(|t| rename.get(t)) {
+                    *target = Value::String(t.clone());
+                }
+            }
+        }
+        nodes.insert(new_name.clone(), node);
+    }
+
+    let new_target = rename[&old_target].clone();
+    nodes
+        .get_mut(&root)
+        .and_then(|r| r.get_mut("inputs"))
+        .and_then(|i| i.as_object_mut())
+        .ok_or("flake.lock root has no inputs")?
+        .insert(input.to_string(), Value::String(new_target.clone()));
+
+    // Drop nodes nothing points to anymore (the updated revision)
+    let keep = reachable(nodes, &root, None);
+    nodes.retain(|name, _| keep.contains(name));
+
+    Ok(nodes
+        .get(&new_target)
+        .and_then(|n| n.get("locked"))
+        .and_then(|l| l.get("rev"))
+        .and_then(|r| r.as_str())
+        .map(|r| r.chars().take(7).collect())
+        .unwrap_or_default())
+}
+
+fn root_name(lock: &Value) -> String {
+    lock.get("root")
+        .and_then(|r| r.as_str())
+        .unwrap_or("root")
+        .to_string()
+}
+
+/// Node names reachable from `start` via direct references. `follows`
+/// edges are paths from the root and end at nodes the root reaches anyway.
+/// If `skip_root_input` is set, that edge out of `start` is not followed.
+fn reachable(
+    nodes: &Map<String, Value>,
+    start: &str,
+    skip_root_input: Option<&str>,
+) -> HashSet<String> {
+    let mut seen = HashSet::new();
+    let mut stack = vec![start.to_string()];
+    while let Some(name) = stack.pop() {
+        if !seen.insert(name.clone()) {
+            continue;
+        }
+        let Some(inputs) = nodes
+            .get(&name)
+            .and_then(|n| n.get("inputs"))
+            .and_then(|i| i.as_object())
+        else {
+            continue;
+        };
+        for target in inputs.values().filter_map(|t| t.as_str()) {
+            if name == start && Some(target) == skip_root_input {
+                continue;
+            }
+            stack.push(target.to_string());
+        }
+    }
+    seen
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn node(rev: &str, inputs: Value) -> Value {
+        json!({ "inputs": inputs, "locked": { "rev": rev } })
+    }
+
+    #[test]
+    fn test_plain_restore() {
+        let old = json!({
+            "root": "root",
+            "nodes": {
+                "root": { "inputs": { "nixpkgs": "nixpkgs" } },
+                "nixpkgs": node("aaaaaaaaaa", json!({})),
+            },
+            "version": 7
+        });
+        let mut lock = json!({
+            "root": "root",
+            "nodes": {
+                "root": { "inputs": { "nixpkgs": "nixpkgs" } },
+                "nixpkgs": node("bbbbbbbbbb", json!({})),
+            },
+            "version": 7
+        });
+        assert_eq!(restore_in(&mut lock, "nixpkgs", &old).unwrap(), "aaaaaaa");
+        assert_eq!(lock, old);
+    }
+
+    #[test]
+    fn test_restore_renames_shared_node() {
+        let old = json!({
+            "root": "root",
+            "nodes": {
+                "root": { "inputs": { "hm": "hm" } },
+                "hm": node("h1", json!({ "utils": "utils" })),
+                "utils": node("u1", json!({})),
+            }
+        });
+        // `utils` moved on and is now also used by `other`
+        let mut lock = json!({
+            "root": "root",
+            "nodes": {
+                "root": { "inputs": { "hm": "hm", "other": "other" } },
+                "hm": node("h2", json!({ "utils": "utils" })),
+                "other": node("o1", json!({ "utils": "utils" })),
+                "utils": node("u2", json!({})),
+            }
+        });
+        assert_eq!(restore_in(&mut lock, "hm", &old).unwrap(), "h1");
+        let nodes = lock["nodes"].as_object().unwrap();
+        assert_eq!(
+            nodes["hm"],
+            node("h1", json!({ "utils": "utils_rollback1" }))
+        );
+        assert_eq!(nodes["utils_rollback1"], node("u1", json!({})));
+        assert_eq!(nodes["utils"], node("u2", json!({})));
+        assert_eq!(nodes["other"]["inputs"]["utils"], "utils");
+    }
+
+    #[test]
+    fn test_restore_prunes_updated_nodes() {
+        let old = json!({
+            "root": "root",
+            "nodes": {
+                "root": { "inputs": { "a": "a" } },
+                "a": node("a1", json!({})),
+            }
+        });
+        let mut lock = json!({
+            "root": "root",
+            "nodes": {
+                "root": { "inputs": { "a": "a" } },
+                "a": node("a2", json!({ "b": "b" })),
+                "b": node("b1", json!({})),
+            }
+        });
+        restore_in(&mut lock, "a", &old).unwrap();
+        let nodes = lock["nodes"].as_object().unwrap();
+        assert!(!nodes.contains_key("b"));
+        assert_eq!(nodes["a"], node("a1", json!({})));
+    }
+
+    #[test]
+    fn test_restore_dangling_reference() {
+        let old = json!({
+            "root": "root",
+            "nodes": {
+                "root": { "inputs": { "a": "a" } },
+                "a": node("a1", json!({ "b": "missing" })),
+            }
+        });
+        let mut lock = old.clone();
+        assert!(restore_in(&mut lock, "a", &old).is_err());
+        assert!(restore_in(&mut lock, "nope", &old).is_err());
+    }
+}
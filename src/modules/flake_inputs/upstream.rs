@@ -0,0 +1,283 @@
+//! Upstream comparison for GitHub inputs — "what would an update pull in?"
+//!
+//! Uses the GitHub compare API (`<locked rev>...<branch>`). Unauthenticated
+//! requests are limited to 60/hour, so `github_token` from the config is
+//! sent when set. All calls are blocking — run in a background thread!
+//!
+//! The same pass looks for upstream problems that would leave an input
+//! pinned forever: archived or moved repositories, deleted branches and
+//! tags that were force-pushed to another commit.
+
+use super::FlakeInput;
+use std::sync::mpsc;
+use std::time::Duration;
+
+const TIMEOUT_SECS: u64 = 10;
+const API_BASE: &str = "https://api.github.com";
+
+/// Result of comparing a locked revision with its upstream branch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UpstreamStatus {
+    /// Request in flight
+    Checking,
+    UpToDate,
+    Behind {
+        commits: u64,
+        /// Subject line of the newest upstream commit
+        last_subject: String,
+        /// Commit date (unix timestamp) of the newest upstream commit
+        last_date: Option<i64>,
+    },
+    /// API rate limit hit; reset time (unix timestamp) if known
+    RateLimited(Option<i64>),
+    /// No network connection
+    Offline,
+    Error(String),
+}
+
+/// Upstream problem with an input, shown as a badge with guidance
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputIssue {
+    /// Read-only upstream, no more updates
+    Archived,
+    /// Renamed or transferred; GitHub redirects, for now
+    Moved { to: String },
+    /// Deleted, or private and no token configured
+    Missing,
+    /// The pinned branch (or tag) no longer exists
+    RefDeleted(String),
+    /// The pinned tag points at a different commit than the lock
+    TagMoved(String),
+}
+
+/// Whether an input can be compared (GitHub type with a locked rev)
+pub fn is_comparable(input: &FlakeInput) -> bool {
+    input.input_type == "github"
+        && !input.owner.is_empty()
+        && !input.repo.is_empty()
+        && !input.revision.is_empty()
+}
+
+/// Compare all given inputs one by one, sending results as they arrive.
+/// Stops querying after a rate limit or network error — the remaining
+/// inputs get the same status instead of failing one timeout at a time.
+pub fn check_inputs(
+    inputs: Vec<FlakeInput>,
+    token: Option<String>,
+    tx: mpsc::Sender<(String, UpstreamStatus, Vec<InputIssue>)>,
+) {
+    let agent = ureq::AgentBuilder::new()
+        .timeout(Duration::from_secs(TIMEOUT_SECS))
+        .build();
+
+    let mut sticky: Option<UpstreamStatus> = None;
+    for input in inputs {
+        let (status, issues) = match &sticky {
+            Some(s) => (s.clone(), Vec::new()),
+            None => inspect(&agent, &input, token.as_deref()),
+        };
+        if matches!(
+            status,
+            UpstreamStatus::RateLimited(_) | UpstreamStatus::Offline
+        ) {
+            sticky = Some(status.clone());
+        }
+        if tx.send((input.name.clone(), status, issues)).is_err() {
+            return;
+        }
+    }
+}
+
+/// Repository health first, then the comparison against the pinned ref
+/// (or the default branch)
+fn inspect(
+    agent: &ureq::Agent,
+    input: &FlakeInput,
+    token: Option<&str>,
+) -> (UpstreamStatus, Vec<InputIssue>) {
+    let url = format!("{}/repos/{}/{}", API_BASE, input.owner, input.repo);
+    let repo = match get_json(agent, &url, token) {
+        Ok(j) => j,
+        Err(UpstreamStatus::Error(e)) if e == NOT_FOUND => {
+            return (UpstreamStatus::Error(e), vec![InputIssue::Missing]);
+        }
+        Err(status) => return (status, Vec::new()),
+    };
+
+    let mut issues = Vec::new();
+    if repo["archived"].as_bool().unwrap_or(false) {
+        issues.push(InputIssue::Archived);
+    }
+    // Redirects are followed, so a moved repo answers under its new name
+    if let Some(full_name) = repo["full_name"].as_str() {
+        let pinned = format!("{}/{}", input.owner, input.repo);
+        if !full_name.eq_ignore_ascii_case(&pinned) {
+            issues.push(InputIssue::Moved {
+                to: full_name.to_string(),
+            });
+        }
+    }
+
+    let git_ref = input
+        .branch
+        .trim_start_matches("refs/heads/")
+        .trim_start_matches("refs/tags/");
+    let target = if git_ref.is_empty() {
+        match repo["default_branch"].as_str() {
+            Some(b) => b.to_string(),
+            None => {
+                return (
+                    UpstreamStatus::Error("no default branch".to_string()),
+                    issues,
+                )
+            }
+        }
+    } else {
+        match ref_kind(agent, input, git_ref, token) {
+            Ok(RefKind::Branch) => git_ref.to_string(),
+            Ok(RefKind::Tag(sha)) => {
+                if sha != input.revision {
+                    issues.push(InputIssue::TagMoved(git_ref.to_string()));
+                }
+                git_ref.to_string()
+            }
+            Ok(RefKind::Gone) => {
+                issues.push(InputIssue::RefDeleted(git_ref.to_string()));
+                return (UpstreamStatus::Error(NOT_FOUND.to_string()), issues);
+            }
+            Err(status) => return (status, issues),
+        }
+    };
+
+    (compare(agent, input, &target, token), issues)
+}
+
+const NOT_FOUND: &str = "404 not found";
+
+enum RefKind {
+    Branch,
+    /// Commit the tag points at now
+    Tag(String),
+    Gone,
+}
+
+fn ref_kind(
+    agent: &ureq::Agent,
+    input: &FlakeInput,
+    git_ref: &str,
+    token: Option<&str>,
+) -> Result<RefKind, UpstreamStatus> {
+    let base = format!("{}/repos/{}/{}", API_BASE, input.owner, input.repo);
+    match get_json(agent, &format!("{}/branches/{}", base, git_ref), token) {
+        Ok(_) => return Ok(RefKind::Branch),
+        Err(UpstreamStatus::Error(e)) if e == NOT_FOUND => {}
+        Err(status) => return Err(status),
+    }
+    let tag = match get_json(agent, &format!("{}/git/ref/tags/{}", base, git_ref), token) {
+        Ok(j) => j,
+        Err(UpstreamStatus::Error(e)) if e == NOT_FOUND => return Ok(RefKind::Gone),
+        Err(status) => return Err(status),
+    };
+    // Annotated tags point at a tag object, which points at the commit
+    let object = &tag["object"];
+    let sha = if object["type"].as_str() == Some("tag") {
+        let url = object["url"].as_str().unwrap_or_default();
+        get_json(agent, url, token)?["object"]["sha"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string()
+    } else {
+        object["sha"].as_str().unwrap_or_default().to_string()
+    };
+    Ok(RefKind::Tag(sha))
+}
+
+fn compare(
+    agent: &ureq::Agent,
+    input: &FlakeInput,
+    branch: &str,
+    token: Option<&str>,
+) -> UpstreamStatus {
+    let url = format!(
+        "{}/repos/{}/{}/compare/{}...{}",
+        API_BASE, input.owner, input.repo, input.revision, branch
+    );
+    let json = match get_json(agent, &url, token) {
+        Ok(j) => j,
+        Err(status) => return status,
+    };
+
+    let behind = json.get("ahead_by").and_then(|v| v.as_u64()).unwrap_or(0);
+    if behind == 0 {
+        return UpstreamStatus::UpToDate;
+    }
+
+    // The compare list stops at 250 commits, so its last entry isn't
+    // necessarily the branch head: ask for the head itself
+    let url = format!(
+        "{}/repos/{}/{}/commits/{}",
+        API_BASE, input.owner, input.repo, branch
+    );
+    let head = match get_json(agent, &url, token) {
+        Ok(j) => j,
+        Err(status) => return status,
+    };
+    let last_subject = head["commit"]["message"]
+        .as_str()
+        .and_then(|m| m.lines().next())
+        .unwrap_or("")
+        .to_string();
+    let last_date = head["commit"]["committer"]["date"]
+        .as_str()
+        .and_then(|d| chrono::DateTime::parse_from_rfc3339(d).ok())
+        .map(|d| d.timestamp());
+
+    UpstreamStatus::Behind {
+        commits: behind,
+        last_subject,
+        last_date,
+    }
+}
+
+/// Commits from `old` to `new` in `owner/repo`, None if GitHub can't tell
+pub fn commits_between(repo: &str, old: &str, new: &str, token: Option<&str>) -> Option<u64> {
+    let agent = ureq::AgentBuilder::new()
+        .timeout(Duration::from_secs(TIMEOUT_SECS))
+        .build();
+    let url = format!("{}/repos/{}/compare/{}...{}", API_BASE, repo, old, new);
+    get_json(&agent, &url, token).ok()?["ahead_by"].as_u64()
+}
+
+fn get_json(
+    agent: &ureq::Agent,
+    url: &str,
+    token: Option<&str>,
+) -> Result<serde_json::Value, UpstreamStatus> {
+    let mut req = agent
+        .get(url)
+        .set("Accept", "application/vnd.github+json")
+        .set("User-Agent", "nixmate");
+    if let Some(t) = token.filter(|t| !t.is_empty()) {
+        req = req.set("Authorization", &format!("Bearer {}", t));
+    }
+
+    match req.call() {
+        Ok(resp) => serde_json::from_reader(resp.into_reader())
+            .map_err(|e| UpstreamStatus::Error(e.to_string())),
+        Err(ureq::Error::Status(code, resp)) => {
+            let remaining = resp.header("x-ratelimit-remaining").map(|r| r.to_string());
+            let reset = resp
+                .header("x-ratelimit-reset")
+                .and_then(|r| r.parse::<i64>().ok());
+            if (code == 403 || code == 429) && remaining.as_deref() == Some("0") {
+                Err(UpstreamStatus::RateLimited(reset))
+            } else if code == 404 {
+                // Private repo without token, or rev/branch no longer exists
+                Err(UpstreamStatus::Error(NOT_FOUND.to_string()))
+            } else {
+                Err(UpstreamStatus::Error(format!("HTTP {}", code)))
+            }
+        }
+        Err(ureq::Error::Transport(_)) => Err(UpstreamStatus::Offline),
+    }
+}
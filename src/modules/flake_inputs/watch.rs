@@ -0,0 +1,43 @@
+//! Watch mode: inputs older than their staleness threshold.
+//!
+//! Thresholds come from `[flake_watch]` in config.toml. The check only
+//! reads flake.lock, so it runs at startup and then periodically without
+//! the module ever being opened; the result feeds the sidebar badge and
+//! the Nix Doctor check.
+
+use super::{find_flake_dir, parse_flake_lock, FlakeInput};
+use crate::config::FlakeWatchConfig;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaleInput {
+    pub name: String,
+    pub age_days: u64,
+    pub threshold_days: u64,
+}
+
+/// Inputs past their threshold, oldest first
+pub fn stale_inputs(inputs: &[FlakeInput], policy: &FlakeWatchConfig) -> Vec<StaleInput> {
+    let mut stale: Vec<StaleInput> = inputs
+        .iter()
+        .filter(|i| i.last_modified > 0)
+        .filter_map(|i| {
+            let threshold_days = policy.threshold_days(&i.name)?;
+            (i.age_days > threshold_days).then(|| StaleInput {
+                name: i.name.clone(),
+                age_days: i.age_days,
+                threshold_days,
+            })
+        })
+        .collect();
+    stale.sort_by_key(|st| std::cmp::Reverse(st.age_days));
+    stale
+}
+
+/// Read flake.lock and check it against `policy`.
+/// None if there is no flake (or no readable lock) to check.
+pub fn scan(config_path: Option<&str>, policy: &FlakeWatchConfig) -> Option<Vec<StaleInput>> {
+    let flake_dir = find_flake_dir(config_path)?;
+    let content = std::fs::read_to_string(format!("{}/flake.lock", flake_dir)).ok()?;
+    let lock: serde_json::Value = serde_json::from_str(&content).ok()?;
+    Some(stale_inputs(&parse_flake_lock(&lock), policy))
+}
@@ -3,12 +3,20 @@
 //! Integrated into nixmate as an inline module.
 //! Has sub-tabs: Overview, Packages, Diff, Manage.
 //! Uses nixmate's global theme, i18n, and config.
+//! System generations built from a flake show the config commit they came
+//! from (`configurationRevision`); `d` in Diff opens `git diff` between two.
 
-use crate::config::Language;
+use crate::config::{Language, TimeFormat};
 use crate::i18n;
+use crate::modules::jobs::Job;
+use crate::modules::module::{Module, RenderCtx};
+use crate::modules::palette::PaletteCommand;
+use crate::nix::gc_report::{self, GcReport, Reclaimable};
 use crate::nix::{self, CommandResult, GenerationSource};
-use crate::types::FlashMessage;
-use crate::types::{Generation, GenerationDiff, Package, ProfileType};
+use crate::staged::StagedKind;
+use crate::types::{format_bytes, ExternalCommand, FlashMessage};
+use crate::types::{BootRole, Generation, GenerationDiff, Package, ProfileType};
+use crate::ui::table::{self, Column, SortKey, TableState};
 use crate::ui::theme::Theme;
 use crate::ui::widgets;
 use anyhow::Result;
@@ -20,8 +28,36 @@ use ratatui::{
     widgets::{Block, Borders, Cell, List, ListItem, Paragraph, Row, Table, Tabs, Wrap},
     Frame,
 };
-use std::collections::HashSet;
-use std::time::Instant;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::mpsc;
+
+/// Config commit a system generation was built from
+#[derive(Debug, Clone)]
+pub struct ConfigRevision {
+    /// Commit hash, without the `-dirty` suffix
+    pub rev: String,
+    /// Built from uncommitted changes on top of `rev`
+    pub dirty: bool,
+    /// Branch-relative name (`main~2`)
+    pub branch: Option<String>,
+    /// None: the commit isn't in the local repo
+    pub subject: Option<String>,
+}
+
+impl ConfigRevision {
+    /// "3d213d6* · main~2 · subject"
+    pub fn label(&self) -> String {
+        let short: String = self.rev.chars().take(7).collect();
+        let mut parts = vec![format!("{}{}", short, if self.dirty { "*" } else { "" })];
+        parts.extend(self.branch.clone());
+        parts.extend(self.subject.clone());
+        parts.join(" · ")
+    }
+}
+
+/// Config repo (if it is a git repo) and the revision of each generation
+type Revisions = (Option<String>, HashMap<u32, ConfigRevision>);
 
 // ── Sub-tabs ──
 
@@ -94,25 +130,6 @@ pub enum GenPopupState {
         title: String,
         message: String,
     },
-    Undo {
-        message: String,
-        seconds_remaining: u8,
-    },
-}
-
-#[derive(Debug, Clone)]
-pub struct PendingUndo {
-    #[allow(dead_code)] // Stored for future undo implementation
-    pub action: UndoAction,
-    pub started_at: Instant,
-}
-
-#[derive(Debug, Clone)]
-pub enum UndoAction {
-    Delete {
-        _profile: ProfileType,
-        _generation_ids: Vec<u32>,
-    },
 }
 
 // ── Module state ──
@@ -144,6 +161,8 @@ pub struct GenerationsState {
     pub overview_focus: usize, // 0 = system, 1 = HM
     pub overview_system_selected: usize,
     pub overview_hm_selected: usize,
+    /// Sort and columns, shared by the system and Home-Manager lists
+    pub overview_table: TableState,
 
     // Packages
     pub packages_list: Vec<Package>,
@@ -175,11 +194,32 @@ pub struct GenerationsState {
 
     // Popup
     pub popup: GenPopupState,
-    pub pending_undo: Option<PendingUndo>,
+    /// A confirmed deletion for App's undo window (see `staged`)
+    pub pending_staged: Option<(String, StagedKind)>,
+    /// What the deletion being confirmed really frees
+    pub gc_report: Option<Result<GcReport, String>>,
+    gc_report_rx: Option<mpsc::Receiver<Result<GcReport, String>>>,
+    /// What each generation of a profile frees on its own (Manage list)
+    pub reclaim: Option<(ProfileType, Result<Reclaimable, String>)>,
+    reclaim_rx: Option<mpsc::Receiver<(ProfileType, Result<Reclaimable, String>)>>,
 
     // Flash
     pub lang: Language,
+    pub time_format: TimeFormat,
+    /// Terminal below the compact thresholds (set on resize)
+    pub compact: bool,
     pub flash_message: Option<FlashMessage>,
+
+    // Config commits per system generation
+    pub config_path: Option<String>,
+    pub revisions: HashMap<u32, ConfigRevision>,
+    /// Git repo the revisions were looked up in
+    revisions_repo: Option<String>,
+    revisions_loaded: bool,
+    revisions_rx: Option<mpsc::Receiver<Revisions>>,
+    /// `git diff` between two generations' commits, run by App with the
+    /// TUI suspended
+    pub pending_external: Option<ExternalCommand>,
 }
 
 impl GenerationsState {
@@ -261,6 +301,7 @@ impl GenerationsState {
             overview_focus: 0,
             overview_system_selected: 0,
             overview_hm_selected: 0,
+            overview_table: TableState::default(),
 
             packages_list: Vec::new(),
             packages_gen_id: None,
@@ -286,13 +327,48 @@ impl GenerationsState {
             pinned_hm: HashSet::new(),
 
             popup: GenPopupState::None,
-            pending_undo: None,
+            pending_staged: None,
+            gc_report: None,
+            gc_report_rx: None,
+            reclaim: None,
+            reclaim_rx: None,
             lang: Language::English,
+            time_format: TimeFormat::Locale,
+            compact: false,
             flash_message: None,
+
+            config_path: None,
+            revisions: HashMap::new(),
+            revisions_repo: None,
+            revisions_loaded: false,
+            revisions_rx: None,
+            pending_external: None,
         }
     }
 
     /// Handle key events
+    /// Actions offered in the command palette
+    pub fn palette_commands(&self, s: &i18n::Strings) -> Vec<PaletteCommand> {
+        let manage = Some(GenSubTab::Manage.index());
+        vec![
+            PaletteCommand::new(s.pal_gen_restore, manage, vec![KeyCode::Char('r')]),
+            PaletteCommand::new(s.pal_gen_delete, manage, vec![KeyCode::Char('d')]),
+            PaletteCommand::new(s.pal_gen_pin, manage, vec![KeyCode::Char('p')]),
+            PaletteCommand::new(s.pal_gen_select_all, manage, vec![KeyCode::Char('a')]),
+            PaletteCommand::new(
+                s.pal_gen_filter,
+                Some(GenSubTab::Packages.index()),
+                vec![KeyCode::Char('/')],
+            ),
+            PaletteCommand::new(s.pal_gen_diff, Some(GenSubTab::Diff.index()), vec![]),
+            PaletteCommand::new(
+                s.pal_gen_git_diff,
+                Some(GenSubTab::Diff.index()),
+                vec![KeyCode::Char('d')],
+            ),
+        ]
+    }
+
     pub fn handle_key(&mut self, key: KeyEvent) -> Result<()> {
         // Clear expired flash
         if let Some(msg) = &self.flash_message {
@@ -305,7 +381,6 @@ impl GenerationsState {
         match &self.popup {
             GenPopupState::Confirm { .. } => return self.handle_confirm_key(key),
             GenPopupState::Error { .. } => return self.handle_error_key(key),
-            GenPopupState::Undo { .. } => return self.handle_undo_key(key),
             GenPopupState::None => {}
         }
 
@@ -330,32 +405,46 @@ impl GenerationsState {
         }
     }
 
-    /// Update undo timer
-    pub fn update_undo_timer(&mut self) -> Result<()> {
-        if let Some(pending) = &self.pending_undo {
-            let elapsed = pending.started_at.elapsed().as_secs() as u8;
-            let remaining = 10u8.saturating_sub(elapsed);
+    // ── Key handlers ──
 
-            if remaining == 0 {
-                self.pending_undo = None;
-                self.popup = GenPopupState::None;
-                let s = crate::i18n::get_strings(self.lang);
-                self.show_flash(s.gen_action_confirmed, false);
-            } else if let GenPopupState::Undo { message, .. } = &self.popup {
-                self.popup = GenPopupState::Undo {
-                    message: message.clone(),
-                    seconds_remaining: remaining,
-                };
-            }
-        }
-        Ok(())
+    /// System (or Home-Manager) generations in table order
+    pub fn overview_generations(&self, home_manager: bool) -> Vec<&Generation> {
+        let no_revisions = HashMap::new();
+        let (gens, revisions) = if home_manager {
+            (&self.home_manager_generations, &no_revisions)
+        } else {
+            (&self.system_generations, &self.revisions)
+        };
+        self.overview_table.sorted(gens.iter().collect(), |g, col| {
+            gen_sort_key(g, col, revisions)
+        })
     }
 
-    // ── Key handlers ──
-
     fn handle_overview_key(&mut self, key: KeyEvent) -> Result<()> {
         let has_hm = !self.home_manager_generations.is_empty();
 
+        let columns = gen_columns(i18n::get_strings(self.lang));
+        let ids = |state: &Self, hm: bool, sel: usize| {
+            state.overview_generations(hm).get(sel).map(|g| g.id)
+        };
+        let current = (
+            ids(self, false, self.overview_system_selected),
+            ids(self, true, self.overview_hm_selected),
+        );
+        if self.overview_table.handle_key(key, &columns) {
+            // Keep the same generations selected in the new order
+            let find = |state: &Self, hm: bool, id: Option<u32>| {
+                state
+                    .overview_generations(hm)
+                    .iter()
+                    .position(|g| Some(g.id) == id)
+                    .unwrap_or(0)
+            };
+            self.overview_system_selected = find(self, false, current.0);
+            self.overview_hm_selected = find(self, true, current.1);
+            return Ok(());
+        }
+
         match key.code {
             KeyCode::Char('j') | KeyCode::Down => {
                 if self.overview_focus == 0 {
@@ -398,20 +487,13 @@ impl GenerationsState {
                 }
             }
             KeyCode::Enter => {
-                let (gen, profile) = if self.overview_focus == 0 {
-                    (
-                        self.system_generations.get(self.overview_system_selected),
-                        ProfileType::System,
-                    )
+                let (gen_id, profile) = if self.overview_focus == 0 {
+                    (current.0, ProfileType::System)
                 } else {
-                    (
-                        self.home_manager_generations.get(self.overview_hm_selected),
-                        ProfileType::HomeManager,
-                    )
+                    (current.1, ProfileType::HomeManager)
                 };
 
-                if let Some(gen) = gen {
-                    let gen_id = gen.id;
+                if let Some(gen_id) = gen_id {
                     self.load_packages(gen_id, profile)?;
                     self.active_sub_tab = GenSubTab::Packages;
                 }
@@ -525,11 +607,88 @@ impl GenerationsState {
                 self.current_diff = None;
                 self.diff_scroll = 0;
             }
+            KeyCode::Char('d') => self.open_git_diff(),
             _ => {}
         }
         Ok(())
     }
 
+    /// Map system generations to config commits in the background (once,
+    /// again after the list changed)
+    pub fn ensure_revisions_loaded(&mut self) {
+        if self.revisions_loaded || self.revisions_rx.is_some() {
+            return;
+        }
+        self.revisions_loaded = true;
+        let generations: Vec<(u32, String)> = self
+            .system_generations
+            .iter()
+            .map(|g| (g.id, g.store_path.clone()))
+            .collect();
+        let config_path = self.config_path.clone();
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(load_revisions(config_path.as_deref(), &generations));
+        });
+        self.revisions_rx = Some(rx);
+    }
+
+    pub fn poll_revisions(&mut self) {
+        let Some(rx) = &self.revisions_rx else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok((repo, revisions)) => {
+                self.revisions_repo = repo;
+                self.revisions = revisions;
+                self.revisions_rx = None;
+            }
+            Err(mpsc::TryRecvError::Empty) => {}
+            Err(mpsc::TryRecvError::Disconnected) => self.revisions_rx = None,
+        }
+    }
+
+    /// The config path changed: look the commits up in the new repo
+    pub fn set_config_path(&mut self, path: Option<String>) {
+        if self.config_path != path {
+            self.config_path = path;
+            self.revisions_loaded = false;
+        }
+    }
+
+    /// Queue `git diff` between the commits of the two compared generations
+    fn open_git_diff(&mut self) {
+        let s = i18n::get_strings(self.lang);
+        let (Some(from), Some(to)) = (self.diff_from_gen, self.diff_to_gen) else {
+            self.show_flash(s.gen_git_diff_pick, true);
+            return;
+        };
+        let revs = (self.revisions.get(&from), self.revisions.get(&to));
+        let (Some(repo), (Some(from_rev), Some(to_rev))) = (&self.revisions_repo, revs) else {
+            self.show_flash(s.gen_git_diff_unavailable, true);
+            return;
+        };
+        if from_rev.subject.is_none() || to_rev.subject.is_none() {
+            self.show_flash(s.gen_git_diff_unknown_commit, true);
+            return;
+        }
+        self.pending_external = Some(ExternalCommand {
+            program: "git".into(),
+            args: vec![
+                "-C".into(),
+                repo.clone(),
+                "diff".into(),
+                from_rev.rev.clone(),
+                to_rev.rev.clone(),
+            ],
+            cwd: None,
+            notice: s
+                .gen_git_diff_notice
+                .replacen("{}", &from.to_string(), 1)
+                .replacen("{}", &to.to_string(), 1),
+        });
+    }
+
     fn handle_manage_key(&mut self, key: KeyEvent) -> Result<()> {
         let generations = self.get_manage_generations();
         let gen_count = generations.len();
@@ -569,11 +728,25 @@ impl GenerationsState {
                 }
             }
             KeyCode::Char('a') | KeyCode::Char('A') => {
+                // Booted / boot-default ones only go by explicit selection
+                let mut skipped = 0;
                 for gen in &generations {
-                    if !gen.is_current && !gen.is_pinned {
+                    if gen.is_current || gen.is_pinned {
+                        continue;
+                    }
+                    if gen.boot_role().is_some() {
+                        skipped += 1;
+                    } else {
                         self.manage_selected.insert(gen.id);
                     }
                 }
+                if skipped > 0 {
+                    let s = crate::i18n::get_strings(self.lang);
+                    self.show_flash(
+                        &s.gen_protected_skipped.replace("{}", &skipped.to_string()),
+                        false,
+                    );
+                }
             }
             KeyCode::Char('c') | KeyCode::Char('C') => {
                 self.manage_selected.clear();
@@ -602,6 +775,7 @@ impl GenerationsState {
             }
             KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
                 self.popup = GenPopupState::None;
+                self.cancel_job("gc_report");
             }
             _ => {}
         }
@@ -618,19 +792,6 @@ impl GenerationsState {
         Ok(())
     }
 
-    fn handle_undo_key(&mut self, key: KeyEvent) -> Result<()> {
-        match key.code {
-            KeyCode::Char('u') | KeyCode::Char('U') | KeyCode::Esc => {
-                self.pending_undo = None;
-                self.popup = GenPopupState::None;
-                let s = crate::i18n::get_strings(self.lang);
-                self.show_flash(s.gen_undo_closed, false);
-            }
-            _ => {}
-        }
-        Ok(())
-    }
-
     // ── Helpers ──
 
     fn get_manage_generations(&self) -> Vec<Generation> {
@@ -785,7 +946,7 @@ impl GenerationsState {
                 .gen_restore_msg
                 .replacen("{}", self.manage_profile.as_str(), 1)
                 .replacen("{}", &gen.id.to_string(), 1)
-                .replacen("{}", &gen.formatted_date(), 1)
+                .replacen("{}", &gen.formatted_date(self.time_format, self.lang), 1)
                 .replacen("{}", gen.nixos_version.as_deref().unwrap_or("?"), 1),
             command,
         };
@@ -833,20 +994,140 @@ impl GenerationsState {
             &ids,
             self.manage_profile,
         );
+        self.start_gc_report(source.profile_path.clone(), ids.clone());
 
         let s = crate::i18n::get_strings(self.lang);
+        let mut message = s
+            .gen_delete_msg
+            .replacen("{}", &ids.len().to_string(), 1)
+            .replacen("{}", &format!("{:?}", ids), 1);
+        for reason in self.protected_reasons(&ids) {
+            message.push_str(&format!("\n⚠ {}", reason));
+        }
         self.popup = GenPopupState::Confirm {
             title: s.gen_confirm_delete.into(),
-            message: s
-                .gen_delete_msg
-                .replacen("{}", &ids.len().to_string(), 1)
-                .replacen("{}", &format!("{:?}", ids), 1),
+            message,
             command,
         };
 
         Ok(())
     }
 
+    /// Work out in the background what deleting `ids` frees; shown in the
+    /// confirmation popup once it's there
+    fn start_gc_report(&mut self, profile_path: std::path::PathBuf, ids: Vec<u32>) {
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let report = gc_report::gc_report(&profile_path, &ids).map_err(|e| e.to_string());
+            let _ = tx.send(report);
+        });
+        self.gc_report = None;
+        self.gc_report_rx = Some(rx);
+    }
+
+    /// Index the Manage profile's closures in the background, once per
+    /// profile and generation list
+    fn ensure_reclaim(&mut self) {
+        let profile = self.manage_profile;
+        if self.reclaim_rx.is_some() || self.reclaim.as_ref().is_some_and(|(p, _)| *p == profile) {
+            return;
+        }
+        let ids: Vec<u32> = self.get_manage_generations().iter().map(|g| g.id).collect();
+        if ids.is_empty() {
+            return;
+        }
+        let profile_path = if profile == ProfileType::System {
+            &self.system_source
+        } else {
+            self.home_manager_source
+                .as_ref()
+                .unwrap_or(&self.system_source)
+        }
+        .profile_path
+        .clone();
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let index = gc_report::reclaimable(&profile_path, &ids).map_err(|e| e.to_string());
+            let _ = tx.send((profile, index));
+        });
+        self.reclaim = None;
+        self.reclaim_rx = Some(rx);
+    }
+
+    /// Space the marked generations free together; None until indexed
+    pub fn reclaim_selected(&self) -> Option<u64> {
+        match &self.reclaim {
+            Some((profile, Ok(index))) if *profile == self.manage_profile => {
+                let ids: Vec<u32> = self.manage_selected.iter().copied().collect();
+                index.freed(&ids)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn poll_gc_report(&mut self) {
+        if self.active_sub_tab == GenSubTab::Manage {
+            self.ensure_reclaim();
+        }
+        if let Some(rx) = &self.reclaim_rx {
+            match rx.try_recv() {
+                Ok(result) => {
+                    self.reclaim = Some(result);
+                    self.reclaim_rx = None;
+                }
+                Err(mpsc::TryRecvError::Empty) => {}
+                Err(mpsc::TryRecvError::Disconnected) => self.reclaim_rx = None,
+            }
+        }
+
+        let Some(rx) = &self.gc_report_rx else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(report) => {
+                self.gc_report = Some(report);
+                self.gc_report_rx = None;
+            }
+            Err(mpsc::TryRecvError::Empty) => {}
+            Err(mpsc::TryRecvError::Disconnected) => self.gc_report_rx = None,
+        }
+    }
+
+    /// Running background work, for the jobs overlay
+    pub fn jobs(&self, s: &i18n::Strings) -> Vec<Job> {
+        let mut jobs = Vec::new();
+        if self.gc_report_rx.is_some() {
+            jobs.push(Job::new("gc_report", s.job_gen_gc_report, true));
+        }
+        if self.reclaim_rx.is_some() {
+            jobs.push(Job::new("reclaim", s.job_gen_reclaim, true));
+        }
+        jobs
+    }
+
+    /// Stop waiting for a job from `jobs()`; its result is discarded
+    pub fn cancel_job(&mut self, id: &str) {
+        if id == "gc_report" {
+            self.gc_report_rx = None;
+            self.gc_report = None;
+        }
+        if id == "reclaim" {
+            // Keep the (empty) result so it isn't started again right away
+            self.reclaim_rx = None;
+            let s = crate::i18n::get_strings(self.lang);
+            self.reclaim = Some((self.manage_profile, Err(s.jobs_cancelled.to_string())));
+        }
+    }
+
+    /// Why any of `ids` must not go, one line per protected generation
+    fn protected_reasons(&self, ids: &[u32]) -> Vec<String> {
+        self.get_manage_generations()
+            .iter()
+            .filter(|g| ids.contains(&g.id))
+            .filter_map(|g| g.boot_role().map(|r| boot_role_reason(g.id, r, self.lang)))
+            .collect()
+    }
+
     fn execute_pending_action(&mut self) -> Result<()> {
         let title = match &self.popup {
             GenPopupState::Confirm { title, .. } => title.clone(),
@@ -857,11 +1138,32 @@ impl GenerationsState {
         let result = if title == s.gen_confirm_restore {
             self.execute_restore()
         } else if title == s.gen_confirm_delete {
-            self.execute_delete()
+            // nix would delete the booted / boot-default generation without
+            // a word, so that takes a second, explicit confirmation
+            let reasons = self.protected_reasons(&self.delete_ids());
+            if !reasons.is_empty() {
+                if let GenPopupState::Confirm { command, .. } = &self.popup {
+                    self.popup = GenPopupState::Confirm {
+                        title: s.gen_confirm_protected.into(),
+                        message: format!("{}\n\n{}", reasons.join("\n"), s.gen_protected_warning),
+                        command: command.clone(),
+                    };
+                }
+                return Ok(());
+            }
+            return self.stage_delete();
+        } else if title == s.gen_confirm_protected {
+            return self.stage_delete();
         } else {
             return Ok(());
         };
 
+        self.show_result(result);
+        Ok(())
+    }
+
+    fn show_result(&mut self, result: Result<CommandResult>) {
+        let s = crate::i18n::get_strings(self.lang);
         match result {
             Ok(cmd_result) if cmd_result.success => {
                 self.popup = GenPopupState::None;
@@ -881,8 +1183,6 @@ impl GenerationsState {
                 };
             }
         }
-
-        Ok(())
     }
 
     fn execute_restore(&self) -> Result<CommandResult> {
@@ -907,54 +1207,68 @@ impl GenerationsState {
         )
     }
 
-    fn execute_delete(&mut self) -> Result<CommandResult> {
-        let generations = self.get_manage_generations();
-        let ids: Vec<u32> = if self.manage_selected.is_empty() {
-            generations
+    /// Selected generations, or the one under the cursor
+    fn delete_ids(&self) -> Vec<u32> {
+        if self.manage_selected.is_empty() {
+            self.get_manage_generations()
                 .get(self.manage_cursor)
                 .map(|g| vec![g.id])
                 .unwrap_or_default()
         } else {
             self.manage_selected.iter().copied().collect()
-        };
+        }
+    }
 
-        let source = if self.manage_profile == ProfileType::System {
+    /// Hand the deletion to App's undo window; a dry run reports right away
+    fn stage_delete(&mut self) -> Result<()> {
+        let ids = self.delete_ids();
+        self.manage_selected.clear();
+        self.popup = GenPopupState::None;
+        self.cancel_job("gc_report");
+        if self.dry_run {
+            let result = self.execute_delete(self.manage_profile, &ids);
+            self.show_result(result);
+            return Ok(());
+        }
+        let s = crate::i18n::get_strings(self.lang);
+        self.pending_staged = Some((
+            s.gen_staged_delete.replace("{}", &ids.len().to_string()),
+            StagedKind::DeleteGenerations {
+                profile: self.manage_profile,
+                ids,
+            },
+        ));
+        Ok(())
+    }
+
+    /// Run a staged deletion once its undo window has passed
+    pub fn run_staged_delete(&mut self, profile: ProfileType, ids: &[u32]) {
+        let result = self.execute_delete(profile, ids).map(|mut r| {
+            if r.success {
+                let s = crate::i18n::get_strings(self.lang);
+                r.message = s.gen_deleted_count.replace("{}", &ids.len().to_string());
+            }
+            r
+        });
+        self.show_result(result);
+    }
+
+    fn execute_delete(&self, profile: ProfileType, ids: &[u32]) -> Result<CommandResult> {
+        let source = if profile == ProfileType::System {
             &self.system_source
         } else {
             self.home_manager_source
                 .as_ref()
                 .unwrap_or(&self.system_source)
         };
-
-        let result = nix::delete_generations(
-            &source.profile_path,
-            &ids,
-            self.manage_profile,
-            self.dry_run,
-        )?;
-
-        if result.success && !self.dry_run {
-            self.pending_undo = Some(PendingUndo {
-                action: UndoAction::Delete {
-                    _profile: self.manage_profile,
-                    _generation_ids: ids.clone(),
-                },
-                started_at: Instant::now(),
-            });
-            self.popup = GenPopupState::Undo {
-                message: {
-                    let s = crate::i18n::get_strings(self.lang);
-                    s.gen_deleted_count.replace("{}", &ids.len().to_string())
-                },
-                seconds_remaining: 10,
-            };
-        }
-
-        self.manage_selected.clear();
-        Ok(result)
+        nix::delete_generations(&source.profile_path, ids, profile, self.dry_run)
     }
 
-    fn refresh_generations(&mut self) -> Result<()> {
+    pub fn refresh_generations(&mut self) -> Result<()> {
+        // Closures changed with the generation list
+        self.reclaim = None;
+        self.reclaim_rx = None;
+        self.revisions_loaded = false;
         self.system_generations = nix::list_generations(&self.system_source).unwrap_or_default();
         for gen in &mut self.system_generations {
             gen.is_pinned = self.pinned_system.contains(&gen.id);
@@ -977,10 +1291,97 @@ impl GenerationsState {
     }
 }
 
+/// Blocking — run in background thread!
+fn load_revisions(config_path: Option<&str>, generations: &[(u32, String)]) -> Revisions {
+    let repo = nix::detect::flake_candidates(config_path)
+        .into_iter()
+        .find(|dir| Path::new(dir).is_dir() && nix::git::is_repo(Path::new(dir)));
+
+    // Several generations usually come from the same commit
+    let mut summaries: HashMap<String, (Option<String>, Option<String>)> = HashMap::new();
+    let mut revisions = HashMap::new();
+    for (id, store_path) in generations {
+        let Some(full) = nix::generations::configuration_revision(store_path) else {
+            continue;
+        };
+        let (rev, dirty) = match full.strip_suffix("-dirty") {
+            Some(rev) => (rev.to_string(), true),
+            None => (full, false),
+        };
+        let (branch, subject) = match &repo {
+            Some(dir) => summaries
+                .entry(rev.clone())
+                .or_insert_with(|| match nix::git::revision_summary(Path::new(dir), &rev) {
+                    Ok((branch, subject)) => (branch, Some(subject)),
+                    Err(_) => (None, None),
+                })
+                .clone(),
+            None => (None, None),
+        };
+        revisions.insert(
+            *id,
+            ConfigRevision {
+                rev,
+                dirty,
+                branch,
+                subject,
+            },
+        );
+    }
+    (repo, revisions)
+}
+
 // ══════════════════════════════════════════════════════════════
 //  RENDERING
 // ══════════════════════════════════════════════════════════════
 
+impl Module for GenerationsState {
+    fn handle_key(&mut self, key: KeyEvent, _lang: Language) -> Result<()> {
+        self.handle_key(key).map(|_| ())
+    }
+
+    fn captures_input(&self) -> bool {
+        !matches!(self.popup, GenPopupState::None) || self.packages_filter_active
+    }
+
+    fn render(&mut self, frame: &mut Frame, ctx: &RenderCtx, area: Rect) {
+        render(frame, self, ctx.theme, ctx.lang, area);
+    }
+
+    fn activate(&mut self) {
+        self.ensure_revisions_loaded();
+    }
+
+    fn poll(&mut self, _active: bool) {
+        self.poll_gc_report();
+        self.poll_revisions();
+    }
+
+    fn commands(&self, s: &i18n::Strings) -> Vec<PaletteCommand> {
+        self.palette_commands(s)
+    }
+
+    fn jobs(&self, s: &i18n::Strings) -> Vec<Job> {
+        self.jobs(s)
+    }
+
+    fn cancel_job(&mut self, id: &str) {
+        self.cancel_job(id)
+    }
+
+    fn sub_tab(&self) -> Option<(usize, usize)> {
+        Some((self.active_sub_tab.index(), GenSubTab::all().len()))
+    }
+
+    fn sub_tab_label(&self, lang: Language) -> Option<&'static str> {
+        Some(self.active_sub_tab.label(lang))
+    }
+
+    fn flash_message(&mut self) -> &mut Option<FlashMessage> {
+        &mut self.flash_message
+    }
+}
+
 /// Render the generations module
 pub fn render(
     frame: &mut Frame,
@@ -1052,9 +1453,8 @@ fn render_sub_tab_bar(
 // ── Overview ──
 
 fn render_overview(frame: &mut Frame, state: &GenerationsState, theme: &Theme, area: Rect) {
-    let s = crate::i18n::get_strings(state.lang);
     let has_hm = !state.home_manager_generations.is_empty();
-    let use_side_by_side = has_hm && area.width >= 100;
+    let use_side_by_side = has_hm && area.width >= 100 && !state.compact;
 
     // Show init errors if any
     if !state.init_errors.is_empty() && state.system_generations.is_empty() {
@@ -1097,62 +1497,44 @@ fn render_overview(frame: &mut Frame, state: &GenerationsState, theme: &Theme, a
 
         render_gen_list(
             frame,
-            s.gen_system_label,
-            &state.system_generations,
-            state.overview_system_selected,
+            state,
+            false,
             state.overview_focus == 0,
             theme,
             panels[0],
         );
-
         render_gen_list(
             frame,
-            s.gen_hm_label,
-            &state.home_manager_generations,
-            state.overview_hm_selected,
+            state,
+            true,
             state.overview_focus == 1,
             theme,
             panels[1],
         );
     } else if has_hm {
         // Stacked: show active panel only, with Tab hint
-        let (title, gens, selected) = if state.overview_focus == 0 {
-            (
-                s.gen_system_label,
-                &state.system_generations,
-                state.overview_system_selected,
-            )
-        } else {
-            (
-                s.gen_hm_label,
-                &state.home_manager_generations,
-                state.overview_hm_selected,
-            )
-        };
-        render_gen_list(frame, title, gens, selected, true, theme, area);
+        render_gen_list(frame, state, state.overview_focus == 1, true, theme, area);
     } else {
         // System only
-        render_gen_list(
-            frame,
-            s.gen_system_label,
-            &state.system_generations,
-            state.overview_system_selected,
-            true,
-            theme,
-            area,
-        );
+        render_gen_list(frame, state, false, true, theme, area);
     }
 }
 
 fn render_gen_list(
     frame: &mut Frame,
-    title: &str,
-    generations: &[Generation],
-    selected: usize,
+    state: &GenerationsState,
+    home_manager: bool,
     is_focused: bool,
     theme: &Theme,
     area: Rect,
 ) {
+    let s = i18n::get_strings(state.lang);
+    let (title, selected) = if home_manager {
+        (s.gen_hm_label, state.overview_hm_selected)
+    } else {
+        (s.gen_system_label, state.overview_system_selected)
+    };
+    let generations = state.overview_generations(home_manager);
     let border_style = if is_focused {
         theme.border_focused()
     } else {
@@ -1191,13 +1573,6 @@ fn render_gen_list(
         return;
     }
 
-    // Calculate scroll offset to keep selected visible
-    let scroll_offset = if selected >= list_height {
-        selected - list_height + 1
-    } else {
-        0
-    };
-
     let list_area = Rect {
         x: inner.x,
         y: inner.y,
@@ -1205,45 +1580,50 @@ fn render_gen_list(
         height: list_height as u16,
     };
 
-    let items: Vec<ListItem> = generations
-        .iter()
-        .enumerate()
-        .skip(scroll_offset)
-        .take(list_height)
-        .map(|(i, gen)| {
-            let marker = if gen.is_current {
-                "● "
-            } else if gen.is_pinned {
-                "★ "
-            } else if gen.in_bootloader {
-                "⚡"
-            } else {
-                "  "
-            };
-
-            let version_str = gen.nixos_version.as_deref().unwrap_or("-");
-            let line_text = format!(
-                "{}#{:<4} {}  {}  {} pkgs  {}",
-                marker,
-                gen.id,
-                gen.formatted_date(),
-                version_str,
-                gen.package_count,
-                gen.formatted_size(),
-            );
-
-            let style = if i == selected {
-                theme.selected()
-            } else {
-                theme.text()
-            };
-
-            ListItem::new(Line::styled(line_text, style))
-        })
-        .collect();
-
-    let list = List::new(items).style(theme.block_style());
-    frame.render_widget(list, list_area);
+    let columns = gen_columns(s);
+    table::Table::new(&columns, &state.overview_table, generations.len(), |i| {
+        let gen = generations[i];
+        let marker = if gen.is_current {
+            "●"
+        } else if gen.is_pinned {
+            "★"
+        } else if gen.boot_role().is_some() {
+            "◆"
+        } else if gen.in_bootloader {
+            "⚡"
+        } else {
+            ""
+        };
+        vec![
+            table::Cell::new(marker, Style::default().fg(theme.accent)),
+            table::Cell::new(format!("#{}", gen.id), theme.text()),
+            table::Cell::new(
+                gen.formatted_date(state.time_format, state.lang),
+                theme.text(),
+            ),
+            table::Cell::new(
+                gen.nixos_version.as_deref().unwrap_or("-"),
+                theme.text_dim(),
+            ),
+            table::Cell::new(gen.package_count.to_string(), theme.text()),
+            table::Cell::new(gen.formatted_size(), theme.text()),
+            table::Cell::new(
+                if home_manager {
+                    String::new()
+                } else {
+                    state
+                        .revisions
+                        .get(&gen.id)
+                        .map(ConfigRevision::label)
+                        .unwrap_or_default()
+                },
+                theme.text_dim(),
+            ),
+        ]
+    })
+    .selected(Some(selected))
+    .compact(state.compact)
+    .render(frame, theme, list_area);
 
     // Detail line at bottom
     if let Some(gen) = generations.get(selected) {
@@ -1274,6 +1654,47 @@ fn render_gen_list(
     }
 }
 
+/// Marker, id, date, NixOS version, package count, closure size, config
+/// commit
+fn gen_columns(s: &i18n::Strings) -> [Column; 7] {
+    [
+        Column::fixed("", 1),
+        Column::fixed(s.gen_col_id, 5),
+        Column::fixed(s.gen_col_date, 14),
+        Column::fill(s.gen_col_version, 8),
+        Column::fixed(s.gen_col_packages, 6).right().secondary(),
+        Column::fixed(s.gen_col_size, 9).right(),
+        Column::fill(s.gen_col_commit, 12).secondary(),
+    ]
+}
+
+fn gen_sort_key(g: &Generation, col: usize, revisions: &HashMap<u32, ConfigRevision>) -> SortKey {
+    match col {
+        // Current, pinned, protected, in the bootloader, the rest
+        0 => SortKey::Num(if g.is_current {
+            0
+        } else if g.is_pinned {
+            1
+        } else if g.boot_role().is_some() {
+            2
+        } else if g.in_bootloader {
+            3
+        } else {
+            4
+        }),
+        1 => SortKey::Num(g.id as i64),
+        2 => SortKey::Num(g.date.timestamp()),
+        3 => SortKey::text(g.nixos_version.as_deref().unwrap_or("")),
+        4 => SortKey::Num(g.package_count as i64),
+        5 => SortKey::Num(g.closure_size as i64),
+        _ => SortKey::text(
+            &revisions
+                .get(&g.id)
+                .map_or(String::new(), ConfigRevision::label),
+        ),
+    }
+}
+
 // ── Packages ──
 
 fn render_packages(frame: &mut Frame, state: &GenerationsState, theme: &Theme, area: Rect) {
@@ -1449,8 +1870,8 @@ fn render_diff(frame: &mut Frame, state: &GenerationsState, theme: &Theme, area:
 
     render_diff_selector(
         frame,
+        state,
         s.gen_from,
-        &state.system_generations,
         state.diff_from_cursor,
         state.diff_from_gen,
         state.diff_focus == 0,
@@ -1460,8 +1881,8 @@ fn render_diff(frame: &mut Frame, state: &GenerationsState, theme: &Theme, area:
 
     render_diff_selector(
         frame,
+        state,
         s.gen_to,
-        &state.system_generations,
         state.diff_to_cursor,
         state.diff_to_gen,
         state.diff_focus == 1,
@@ -1500,8 +1921,8 @@ fn render_diff(frame: &mut Frame, state: &GenerationsState, theme: &Theme, area:
 #[allow(clippy::too_many_arguments)]
 fn render_diff_selector(
     frame: &mut Frame,
+    state: &GenerationsState,
     title: &str,
-    generations: &[Generation],
     cursor: usize,
     selected_id: Option<u32>,
     is_focused: bool,
@@ -1512,6 +1933,7 @@ fn render_diff_selector(
         Some(id) => format!(" {} (#{}) ", title, id),
         None => format!(" {} ", title),
     };
+    let generations = &state.system_generations;
 
     let block = Block::default()
         .style(theme.block_style())
@@ -1545,7 +1967,15 @@ fn render_diff_selector(
                 "  "
             };
 
-            let text = format!("{}#{:<4} {}", check, gen.id, gen.formatted_date());
+            let mut text = format!(
+                "{}#{:<4} {}",
+                check,
+                gen.id,
+                gen.formatted_date(state.time_format, state.lang)
+            );
+            if let Some(rev) = state.revisions.get(&gen.id) {
+                text.push_str(&format!("  {}", rev.label()));
+            }
 
             let style = if i == cursor && is_focused {
                 theme.selected()
@@ -1729,6 +2159,7 @@ fn render_manage(frame: &mut Frame, state: &GenerationsState, theme: &Theme, are
         Cell::from(" GEN").style(theme.title()),
         Cell::from("DATE").style(theme.title()),
         Cell::from("SIZE").style(theme.title()),
+        Cell::from("FREES").style(theme.title()),
         Cell::from("STATUS").style(theme.title()),
     ])
     .style(theme.block_style());
@@ -1747,6 +2178,10 @@ fn render_manage(frame: &mut Frame, state: &GenerationsState, theme: &Theme, are
                 "● current"
             } else if gen.is_pinned {
                 "★ pinned"
+            } else if gen.is_booted {
+                "◆ booted"
+            } else if gen.is_boot_default {
+                "◆ boot default"
             } else if gen.in_bootloader {
                 "⚡ boot"
             } else {
@@ -1759,11 +2194,22 @@ fn render_manage(frame: &mut Frame, state: &GenerationsState, theme: &Theme, are
                 theme.text()
             };
 
+            // Space removing only this generation gives back
+            let frees = match &state.reclaim {
+                Some((profile, Ok(index))) if *profile == state.manage_profile => {
+                    index.alone(gen.id).map_or("?".to_string(), format_bytes)
+                }
+                Some((profile, Err(_))) if *profile == state.manage_profile => "?".to_string(),
+                _ if gen.is_current => "–".to_string(),
+                _ => "…".to_string(),
+            };
+
             Row::new(vec![
                 Cell::from(sel_marker),
                 Cell::from(format!(" #{}", gen.id)),
-                Cell::from(gen.formatted_date()),
+                Cell::from(gen.formatted_date(state.time_format, state.lang)),
                 Cell::from(gen.formatted_size()),
+                Cell::from(frees),
                 Cell::from(status),
             ])
             .style(style)
@@ -1775,6 +2221,7 @@ fn render_manage(frame: &mut Frame, state: &GenerationsState, theme: &Theme, are
         Constraint::Length(8),
         Constraint::Length(16),
         Constraint::Length(12),
+        Constraint::Length(11),
         Constraint::Min(10),
     ];
 
@@ -1794,9 +2241,13 @@ fn render_manage(frame: &mut Frame, state: &GenerationsState, theme: &Theme, are
 
     let sel_count = state.manage_selected.len();
     let actions = if sel_count > 0 {
+        let frees = state
+            .reclaim_selected()
+            .map(|b| format!(" · frees {}", format_bytes(b)))
+            .unwrap_or_default();
         format!(
-            " {} selected · [R] Restore  [D] Delete  [P] Pin  [C] Clear",
-            sel_count
+            " {} selected{} · [R] Restore  [D] Delete  [P] Pin  [C] Clear",
+            sel_count, frees
         )
     } else {
         " [Space] Select  [A] Select All  [R] Restore  [D] Delete  [P] Pin".to_string()
@@ -1808,6 +2259,17 @@ fn render_manage(frame: &mut Frame, state: &GenerationsState, theme: &Theme, are
     );
 }
 
+/// Why generation `id` must survive cleanups; shown here and wherever a
+/// cleanup is refused
+pub fn boot_role_reason(id: u32, role: BootRole, lang: Language) -> String {
+    let s = crate::i18n::get_strings(lang);
+    let template = match role {
+        BootRole::Booted => s.gen_protected_booted,
+        BootRole::BootDefault => s.gen_protected_default,
+    };
+    template.replace("{}", &id.to_string())
+}
+
 // ── Popups ──
 
 fn render_gen_popups(frame: &mut Frame, state: &GenerationsState, theme: &Theme, area: Rect) {
@@ -1819,14 +2281,17 @@ fn render_gen_popups(frame: &mut Frame, state: &GenerationsState, theme: &Theme,
             message,
             command,
         } => {
-            let content = vec![
+            let mut content = vec![
                 Line::raw(""),
                 Line::styled(message.as_str(), theme.text()),
                 Line::raw(""),
+            ];
+            content.extend(gc_report_lines(state, theme));
+            content.extend([
                 Line::styled(s.gen_command_label, theme.text_dim()),
                 Line::styled(command.as_str(), Style::default().fg(theme.fg_dim)),
                 Line::raw(""),
-            ];
+            ]);
             widgets::render_popup(
                 frame,
                 title,
@@ -1839,33 +2304,47 @@ fn render_gen_popups(frame: &mut Frame, state: &GenerationsState, theme: &Theme,
         GenPopupState::Error { title, message } => {
             widgets::render_error_popup(frame, title, message, theme, area);
         }
-        GenPopupState::Undo {
-            message,
-            seconds_remaining,
-        } => {
-            let bar_width = 30;
-            let filled = (*seconds_remaining as usize * bar_width / 10).min(bar_width);
-            let empty = bar_width - filled;
-            let bar = format!("{}{}", "█".repeat(filled), "░".repeat(empty));
+    }
+}
 
-            let content = vec![
-                Line::raw(""),
-                Line::styled(message.as_str(), theme.text()),
-                Line::raw(""),
-                Line::from(vec![
-                    Span::styled(bar, theme.warning()),
-                    Span::raw(format!("  {}s", seconds_remaining)),
-                ]),
+/// The GC safety report in the delete confirmation, empty for other popups
+fn gc_report_lines(state: &GenerationsState, theme: &Theme) -> Vec<Line<'static>> {
+    let s = crate::i18n::get_strings(state.lang);
+    if state.gc_report_rx.is_some() {
+        return vec![
+            Line::styled(s.gen_gc_calculating, theme.text_dim()),
+            Line::raw(""),
+        ];
+    }
+    let report = match &state.gc_report {
+        None => return Vec::new(),
+        Some(Err(e)) => {
+            return vec![
+                Line::styled(s.gen_gc_unknown.replace("{}", e), theme.warning()),
                 Line::raw(""),
-            ];
-            widgets::render_popup(
-                frame,
-                s.gen_undo_available,
-                content,
-                &[(s.gen_dismiss, 'u')],
-                theme,
-                area,
-            );
+            ]
+        }
+        Some(Ok(report)) => report,
+    };
+    let mut lines = vec![Line::styled(
+        s.gen_gc_frees.replace("{}", &format_bytes(report.total)),
+        theme.success().add_modifier(Modifier::BOLD),
+    )];
+    if report.generations.len() > 1 {
+        for (id, bytes) in &report.generations {
+            lines.push(Line::styled(
+                s.gen_gc_only_in
+                    .replacen("{}", &id.to_string(), 1)
+                    .replacen("{}", &format_bytes(*bytes), 1),
+                theme.text(),
+            ));
         }
     }
+    lines.push(Line::styled(
+        s.gen_gc_kept
+            .replace("{}", &format_bytes(report.kept_alive)),
+        theme.text_dim(),
+    ));
+    lines.push(Line::raw(""));
+    lines
 }
@@ -0,0 +1,436 @@
+//! Accounts audit — users, groups and SSH keys vs. the declarative config.
+//!
+//! The declared state comes from the `users-groups.json` that the current
+//! system's activation script feeds to `update-users-groups.pl` (falls back
+//! to /var/lib/nixos/declarative-{users,groups}, names only). Declared SSH
+//! keys live in /etc/ssh/authorized_keys.d/<user>; everything in a user's
+//! ~/.ssh/authorized_keys was added by hand.
+//!
+//! No `nix eval` needed — the audit only reads files and is fast.
+
+use super::Severity;
+use crate::config::Language;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FindingKind {
+    ImperativeUser,
+    ImperativeGroup,
+    GroupDrift,
+    ExtraKey,
+    MutableUsers,
+    Unreadable,
+}
+
+/// One difference between the running system and the config
+#[derive(Debug, Clone)]
+pub struct AccountFinding {
+    pub kind: FindingKind,
+    pub severity: Severity,
+    /// User, group or key the finding is about
+    pub subject: String,
+    pub detail: String,
+    /// Nix snippet (or shell command) that resolves the drift
+    pub remediation: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AccountAudit {
+    pub findings: Vec<AccountFinding>,
+    /// Where the declared state was read from (None = not a NixOS system)
+    pub source: Option<String>,
+    pub mutable_users: Option<bool>,
+    pub users_checked: usize,
+    pub keys_checked: usize,
+}
+
+impl AccountAudit {
+    pub fn worst_severity(&self) -> Severity {
+        if self.findings.iter().any(|f| f.severity == Severity::Fail) {
+            Severity::Fail
+        } else if self.findings.iter().any(|f| f.severity == Severity::Warn) {
+            Severity::Warn
+        } else {
+            Severity::Pass
+        }
+    }
+
+    pub fn count(&self, kind: FindingKind) -> usize {
+        self.findings.iter().filter(|f| f.kind == kind).count()
+    }
+}
+
+// ── Declared state ──
+
+#[derive(Debug, Default)]
+struct Declared {
+    users: HashSet<String>,
+    groups: HashSet<String>,
+    /// group → declared members (empty map if only names are known)
+    members: HashMap<String, HashSet<String>>,
+    mutable_users: Option<bool>,
+    source: String,
+}
+
+fn load_declared() -> Option<Declared> {
+    load_users_groups_json().or_else(load_declarative_lists)
+}
+
+/// Locate users-groups.json via the activation script of the current system
+fn load_users_groups_json() -> Option<Declared> {
+    let activate = std::fs::read_to_string("/run/current-system/activate").ok()?;
+    let path = activate.split_whitespace().find(|w| {
+        w.starts_with("/nix/store/") && w.trim_matches('\'').ends_with("-users-groups.json")
+    })?;
+    let path = path.trim_matches('\'');
+    let content = std::fs::read_to_string(path).ok()?;
+    let data: serde_json::Value = serde_json::from_str(&content).ok()?;
+
+    let names = |key: &str| -> HashSet<String> {
+        data.get(key)
+            .and_then(|v| v.as_array())
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|u| u.get("name")?.as_str().map(|s| s.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    let members = data
+        .get("groups")
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|g| {
+                    let name = g.get("name")?.as_str()?.to_string();
+                    let members = g
+                        .get("members")
+                        .and_then(|m| m.as_array())
+                        .map(|m| {
+                            m.iter()
+                                .filter_map(|u| u.as_str().map(|s| s.to_string()))
+                                .collect()
+                        })
+                        .unwrap_or_default();
+                    Some((name, members))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Some(Declared {
+        users: names("users"),
+        groups: names("groups"),
+        members,
+        mutable_users: data.get("mutableUsers").and_then(|v| v.as_bool()),
+        source: path.to_string(),
+    })
+}
+
+fn load_declarative_lists() -> Option<Declared> {
+    let read = |path: &str| -> Option<HashSet<String>> {
+        let content = std::fs::read_to_string(path).ok()?;
+        Some(content.split_whitespace().map(|s| s.to_string()).collect())
+    };
+    let users = read("/var/lib/nixos/declarative-users")?;
+    let groups = read("/var/lib/nixos/declarative-groups").unwrap_or_default();
+    Some(Declared {
+        users,
+        groups,
+        members: HashMap::new(),
+        mutable_users: None,
+        source: "/var/lib/nixos/declarative-users".to_string(),
+    })
+}
+
+// ── Actual state ──
+
+#[derive(Debug, Clone)]
+struct PasswdEntry {
+    name: String,
+    uid: u32,
+    home: String,
+    shell: String,
+}
+
+fn read_passwd() -> Vec<PasswdEntry> {
+    std::fs::read_to_string("/etc/passwd")
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.split(':').collect();
+            if parts.len() < 7 {
+                return None;
+            }
+            Some(PasswdEntry {
+                name: parts[0].to_string(),
+                uid: parts[2].parse().ok()?,
+                home: parts[5].to_string(),
+                shell: parts[6].to_string(),
+            })
+        })
+        .collect()
+}
+
+/// group name → (gid, members)
+fn read_group() -> Vec<(String, u32, Vec<String>)> {
+    std::fs::read_to_string("/etc/group")
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.split(':').collect();
+            if parts.len() < 4 {
+                return None;
+            }
+            let members = parts[3]
+                .split(',')
+                .filter(|m| !m.is_empty())
+                .map(|m| m.to_string())
+                .collect();
+            Some((parts[0].to_string(), parts[2].parse().ok()?, members))
+        })
+        .collect()
+}
+
+/// Key identity: "<type> <base64>" without options and comment
+fn key_identity(line: &str) -> Option<(String, String)> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    let words: Vec<&str> = line.split_whitespace().collect();
+    // Options come first if present: find the key type token
+    let pos = words
+        .iter()
+        .position(|w| w.starts_with("ssh-") || w.starts_with("ecdsa-") || w.starts_with("sk-"))?;
+    let blob = words.get(pos + 1)?;
+    let comment = words[pos + 2..].join(" ");
+    Some((format!("{} {}", words[pos], blob), comment))
+}
+
+/// (identity, comment, raw line) for every key in an authorized_keys file
+fn read_keys(path: &Path) -> std::io::Result<Vec<(String, String, String)>> {
+    let content = std::fs::read_to_string(path)?;
+    Ok(content
+        .lines()
+        .filter_map(|l| key_identity(l).map(|(id, comment)| (id, comment, l.trim().to_string())))
+        .collect())
+}
+
+fn has_login_shell(shell: &str) -> bool {
+    !(shell.ends_with("/nologin") || shell.ends_with("/false") || shell.is_empty())
+}
+
+fn short_key(id: &str, comment: &str) -> String {
+    let mut parts = id.split_whitespace();
+    let kind = parts.next().unwrap_or("");
+    let blob = parts.next().unwrap_or("");
+    let tail: String = blob
+        .chars()
+        .rev()
+        .take(8)
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .collect();
+    if comment.is_empty() {
+        format!("{} …{}", kind, tail)
+    } else {
+        format!("{} …{} ({})", kind, tail, comment)
+    }
+}
+
+// ── Audit ──
+
+/// Compare /etc/passwd, /etc/group and authorized_keys with the config.
+/// Blocking (file reads only) — called from the health scan thread.
+pub fn run_audit(lang: Language) -> AccountAudit {
+    let s = crate::i18n::get_strings(lang);
+    let Some(declared) = load_declared() else {
+        return AccountAudit::default();
+    };
+
+    let passwd = read_passwd();
+    let groups = read_group();
+    let mut findings = Vec::new();
+    let mut keys_checked = 0;
+    let mut unreadable = Vec::new();
+
+    let wheel_members: HashSet<&str> = groups
+        .iter()
+        .find(|(name, _, _)| name == "wheel")
+        .map(|(_, _, m)| m.iter().map(|s| s.as_str()).collect())
+        .unwrap_or_default();
+
+    // Users not declared anywhere in the config
+    for user in &passwd {
+        if declared.users.contains(&user.name) {
+            continue;
+        }
+        let in_wheel = wheel_members.contains(user.name.as_str());
+        let severity = if in_wheel || user.uid == 0 {
+            Severity::Fail
+        } else {
+            Severity::Warn
+        };
+        let extra_groups: Vec<String> = groups
+            .iter()
+            .filter(|(_, _, m)| m.contains(&user.name))
+            .map(|(g, _, _)| format!("\"{}\"", g))
+            .collect();
+        let kind_line = if user.uid >= 1000 && has_login_shell(&user.shell) {
+            "isNormalUser = true;"
+        } else {
+            "isSystemUser = true;"
+        };
+        findings.push(AccountFinding {
+            kind: FindingKind::ImperativeUser,
+            severity,
+            subject: user.name.clone(),
+            detail: s
+                .health_acc_imperative_user
+                .replacen("{}", &user.uid.to_string(), 1),
+            remediation: format!(
+                "# Declare the user in your configuration:\nusers.users.{name} = {{\n  {kind}\n  uid = {uid};\n  extraGroups = [ {groups} ];\n}};\n\n# …or remove it:\nsudo userdel {name}",
+                name = user.name,
+                kind = kind_line,
+                uid = user.uid,
+                groups = extra_groups.join(" "),
+            ),
+        });
+    }
+
+    // Groups not declared, and members that only exist imperatively
+    for (name, gid, members) in &groups {
+        if !declared.groups.contains(name) {
+            findings.push(AccountFinding {
+                kind: FindingKind::ImperativeGroup,
+                severity: Severity::Warn,
+                subject: name.clone(),
+                detail: s
+                    .health_acc_imperative_group
+                    .replacen("{}", &gid.to_string(), 1),
+                remediation: format!(
+                    "# Declare the group in your configuration:\nusers.groups.{name}.gid = {gid};\n\n# …or remove it:\nsudo groupdel {name}",
+                    name = name,
+                    gid = gid,
+                ),
+            });
+            continue;
+        }
+
+        let Some(declared_members) = declared.members.get(name) else {
+            continue;
+        };
+        for member in members {
+            if declared_members.contains(member) {
+                continue;
+            }
+            let severity = if name == "wheel" {
+                Severity::Fail
+            } else {
+                Severity::Warn
+            };
+            findings.push(AccountFinding {
+                kind: FindingKind::GroupDrift,
+                severity,
+                subject: format!("{} ∈ {}", member, name),
+                detail: s.health_acc_group_drift.replacen("{}", name, 1),
+                remediation: format!(
+                    "# Make the membership declarative:\nusers.users.{member}.extraGroups = [ \"{group}\" ];\n\n# …or revoke it:\nsudo gpasswd -d {member} {group}",
+                    member = member,
+                    group = name,
+                ),
+            });
+        }
+    }
+
+    // SSH keys added by hand to ~/.ssh/authorized_keys
+    for user in &passwd {
+        if !has_login_shell(&user.shell) && user.uid != 0 {
+            continue;
+        }
+        let home_keys = Path::new(&user.home).join(".ssh/authorized_keys");
+        let declared_keys: HashSet<String> =
+            read_keys(&Path::new("/etc/ssh/authorized_keys.d").join(&user.name))
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(id, _, _)| id)
+                .collect();
+        keys_checked += declared_keys.len();
+
+        let keys = match read_keys(&home_keys) {
+            Ok(keys) => keys,
+            Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+                unreadable.push(user.name.clone());
+                continue;
+            }
+            Err(_) => continue,
+        };
+        keys_checked += keys.len();
+
+        for (id, comment, raw) in keys {
+            if declared_keys.contains(&id) {
+                continue;
+            }
+            let severity = if user.uid == 0 {
+                Severity::Fail
+            } else {
+                Severity::Warn
+            };
+            findings.push(AccountFinding {
+                kind: FindingKind::ExtraKey,
+                severity,
+                subject: format!("{}: {}", user.name, short_key(&id, &comment)),
+                detail: s
+                    .health_acc_extra_key
+                    .replacen("{}", &home_keys.display().to_string(), 1),
+                remediation: format!(
+                    "# Declare the key in your configuration:\nusers.users.{name}.openssh.authorizedKeys.keys = [\n  \"{raw}\"\n];\n\n# …then remove it from {file}",
+                    name = user.name,
+                    raw = raw.replace('"', "\\\""),
+                    file = home_keys.display(),
+                ),
+            });
+        }
+    }
+
+    // mutableUsers lets all of the above survive rebuilds
+    let has_drift = !findings.is_empty();
+    if declared.mutable_users == Some(true) && has_drift {
+        findings.push(AccountFinding {
+            kind: FindingKind::MutableUsers,
+            severity: Severity::Warn,
+            subject: "users.mutableUsers".to_string(),
+            detail: s.health_acc_mutable.to_string(),
+            remediation: "# After declaring everything above:\nusers.mutableUsers = false;\n\n# Passwords must then be set declaratively too:\nusers.users.<name>.hashedPasswordFile = \"/run/secrets/<name>-password\";".to_string(),
+        });
+    }
+
+    if !unreadable.is_empty() {
+        findings.push(AccountFinding {
+            kind: FindingKind::Unreadable,
+            severity: Severity::Pass,
+            subject: unreadable.join(", "),
+            detail: s.health_acc_unreadable.to_string(),
+            remediation: "sudo nixmate".to_string(),
+        });
+    }
+
+    // Most severe first, stable within a severity
+    findings.sort_by_key(|f| match f.severity {
+        Severity::Fail => 0,
+        Severity::Warn => 1,
+        Severity::Pass => 2,
+    });
+
+    AccountAudit {
+        findings,
+        source: Some(declared.source),
+        mutable_users: declared.mutable_users,
+        users_checked: passwd.len(),
+        keys_checked,
+    }
+}
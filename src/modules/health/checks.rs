@@ -0,0 +1,1779 @@
+//! Health check framework and the built-in checks.
+//!
+//! Every check implements `HealthCheck` and is listed in `registry()`.
+//! The Doctor runs each one in its own thread and shows results as they
+//! come in. A check reports Pass/Warn/Fail with a one-line detail and may
+//! offer a `FixAction`: a command that is safe to run with one key, or a
+//! config snippet when the fix belongs in configuration.nix.
+
+use super::accounts::{self, AccountAudit, FindingKind};
+use super::signatures::{self, PathTrust};
+use super::Severity;
+use crate::config::{FlakeWatchConfig, Language};
+use crate::i18n::{self, Strings};
+use crate::modules::flake_inputs::{find_flake_dir, watch};
+use crate::modules::generations::boot_role_reason;
+use crate::nix::availability::Platform;
+use crate::nix::generations::endangered_by_cleanup;
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+
+// ── Framework ──
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    Storage,
+    Updates,
+    Security,
+    Boot,
+    System,
+    Power,
+    Hardware,
+    /// nixmate's own dependencies
+    Nixmate,
+}
+
+impl Category {
+    /// Stable name for `nixmate doctor --json`
+    pub fn id(&self) -> &'static str {
+        match self {
+            Category::Storage => "storage",
+            Category::Updates => "updates",
+            Category::Security => "security",
+            Category::Boot => "boot",
+            Category::System => "system",
+            Category::Power => "power",
+            Category::Hardware => "hardware",
+            Category::Nixmate => "nixmate",
+        }
+    }
+
+    pub fn label(&self, s: &Strings) -> &'static str {
+        match self {
+            Category::Storage => s.health_cat_storage,
+            Category::Updates => s.health_cat_updates,
+            Category::Security => s.health_cat_security,
+            Category::Boot => s.health_cat_boot,
+            Category::System => s.health_cat_system,
+            Category::Power => s.health_cat_power,
+            Category::Hardware => s.health_cat_hardware,
+            Category::Nixmate => s.health_cat_nixmate,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FixAction {
+    /// Shell command that is safe to run from the Fix tab (Enter)
+    Run(String),
+    /// Nix snippet for the system configuration; shown, never applied
+    Snippet(String),
+}
+
+/// What a check found
+#[derive(Debug, Clone)]
+pub struct Finding {
+    pub severity: Severity,
+    pub detail: String,
+    pub fix: Option<FixAction>,
+    pub fix_description: Option<String>,
+}
+
+/// Input shared by all checks of one scan
+pub struct CheckContext {
+    pub lang: Language,
+    pub config_path: Option<String>,
+    /// `nixosConfigurations` attribute from the config (None = host name)
+    pub nixos_host: Option<String>,
+    pub flake_watch: FlakeWatchConfig,
+    /// Filled by the accounts check for the Accounts sub-tab
+    pub accounts: Mutex<Option<AccountAudit>>,
+    /// Full output of a failed flake evaluation, for the Error Translator
+    pub eval_error: Mutex<Option<String>>,
+}
+
+pub trait HealthCheck: Send + Sync {
+    /// Stable identifier for `nixmate doctor --json`
+    fn id(&self) -> &'static str;
+    fn name(&self, s: &Strings) -> &'static str;
+    /// Why the check matters, shown for the selected check
+    fn description(&self, s: &Strings) -> &'static str;
+    fn category(&self) -> Category;
+    /// Share of the health score
+    fn weight(&self) -> u8;
+    /// Reads the NixOS system (profile, boot entries, configuration);
+    /// skipped elsewhere
+    fn nixos_only(&self) -> bool {
+        false
+    }
+    /// None if the check doesn't apply to this system
+    fn run(&self, ctx: &CheckContext) -> Option<Finding>;
+}
+
+/// A finished check as shown in the Doctor
+#[derive(Debug, Clone)]
+pub struct CheckResult {
+    /// Position in `registry()`, keeps the list order stable
+    pub order: usize,
+    pub id: &'static str,
+    pub name: String,
+    pub description: String,
+    pub category: Category,
+    pub severity: Severity,
+    pub detail: String,
+    pub fix: Option<FixAction>,
+    pub fix_description: Option<String>,
+    pub weight: u8,
+    /// Whether this check has been fixed in current session
+    pub fixed: bool,
+    /// Not run: needs NixOS and this system isn't. Counts neither for the
+    /// score nor as passed.
+    pub skipped: bool,
+}
+
+/// All checks, in display order
+pub fn registry() -> Vec<Arc<dyn HealthCheck>> {
+    let mut checks: Vec<Arc<dyn HealthCheck>> = vec![
+        Arc::new(OldGenerations),
+        Arc::new(StoreSize),
+        Arc::new(DiskUsage),
+        Arc::new(AutoOptimise),
+        Arc::new(Freshness),
+        Arc::new(FlakeWatch),
+        Arc::new(FlakeEval),
+        Arc::new(Duplicates),
+        Arc::new(Signatures),
+        Arc::new(StoreIntegrity),
+        Arc::new(Accounts),
+        Arc::new(BootSpace),
+        Arc::new(BootedMismatch),
+        Arc::new(InitrdSecrets),
+        Arc::new(StaleBootEntries),
+        Arc::new(PowerDaemons),
+        Arc::new(Thermald),
+        Arc::new(Hibernate),
+        Arc::new(ChargeThreshold),
+    ];
+    checks.extend(super::nixconf::checks());
+    checks.extend(super::hardware::checks());
+    checks.extend(super::selfcheck::checks());
+    checks
+}
+
+/// Run one check and wrap its finding for display
+pub fn run_check(order: usize, check: &dyn HealthCheck, ctx: &CheckContext) -> Option<CheckResult> {
+    let s = i18n::get_strings(ctx.lang);
+    let skipped = check.nixos_only() && !Platform::current().is_nixos();
+    let finding = if skipped {
+        Finding {
+            severity: Severity::Pass,
+            detail: s.health_detail_skipped_not_nixos.to_string(),
+            fix: None,
+            fix_description: None,
+        }
+    } else {
+        check.run(ctx)?
+    };
+    Some(CheckResult {
+        order,
+        id: check.id(),
+        name: check.name(s).to_string(),
+        description: check.description(s).to_string(),
+        category: check.category(),
+        severity: finding.severity,
+        detail: finding.detail,
+        fix: finding.fix,
+        fix_description: finding.fix_description,
+        weight: check.weight(),
+        fixed: false,
+        skipped,
+    })
+}
+
+/// Run every check in its own thread and wait for all of them
+pub fn run_all(ctx: Arc<CheckContext>) -> Vec<CheckResult> {
+    let handles: Vec<_> = registry()
+        .into_iter()
+        .enumerate()
+        .map(|(i, check)| {
+            let ctx = Arc::clone(&ctx);
+            std::thread::spawn(move || run_check(i, check.as_ref(), &ctx))
+        })
+        .collect();
+    handles
+        .into_iter()
+        .filter_map(|h| h.join().ok().flatten())
+        .collect()
+}
+
+/// Health score 0-100: failures cost a check's full weight, warnings half
+pub fn score(checks: &[CheckResult]) -> u8 {
+    let checks: Vec<&CheckResult> = checks.iter().filter(|c| !c.skipped).collect();
+    let total_weight: u16 = checks.iter().map(|c| c.weight as u16).sum();
+    if total_weight == 0 {
+        return 100;
+    }
+    let lost: u16 = checks
+        .iter()
+        .map(|c| match c.severity {
+            Severity::Pass => 0,
+            Severity::Warn => (c.weight as u16) / 2,
+            Severity::Fail => c.weight as u16,
+        })
+        .sum();
+    100u16.saturating_sub((lost * 100) / total_weight) as u8
+}
+
+// ── Old generations ──
+
+struct OldGenerations;
+
+impl HealthCheck for OldGenerations {
+    fn id(&self) -> &'static str {
+        "old-generations"
+    }
+    fn name(&self, s: &Strings) -> &'static str {
+        s.health_name_old_gens
+    }
+    fn description(&self, s: &Strings) -> &'static str {
+        s.health_desc_old_gens
+    }
+    fn category(&self) -> Category {
+        Category::Storage
+    }
+    fn weight(&self) -> u8 {
+        15
+    }
+    fn nixos_only(&self) -> bool {
+        true
+    }
+
+    fn run(&self, ctx: &CheckContext) -> Option<Finding> {
+        let s = i18n::get_strings(ctx.lang);
+
+        let output = Command::new("sh")
+            .args([
+                "-c",
+                "nixos-rebuild list-generations 2>/dev/null | head -50",
+            ])
+            .output();
+
+        let mut old_count = 0u32;
+        let mut total_count = 0u32;
+
+        if let Ok(o) = output {
+            let stdout = String::from_utf8_lossy(&o.stdout);
+            let now = chrono_now_days();
+
+            for line in stdout.lines() {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with("Generation") {
+                    continue;
+                }
+                total_count += 1;
+
+                // Try to parse date from generation line
+                // Format varies: "  42   2024-01-15 10:30:00" or similar
+                if let Some(days) = extract_generation_age_days(line, now) {
+                    if days > 30 {
+                        old_count += 1;
+                    }
+                }
+            }
+        }
+
+        // Fallback: count profiles
+        if total_count == 0 {
+            let profile_path = std::path::Path::new("/nix/var/nix/profiles");
+            if let Ok(entries) = std::fs::read_dir(profile_path) {
+                for entry in entries.flatten() {
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    if name.starts_with("system-") && name.ends_with("-link") {
+                        total_count += 1;
+                        // Check age via mtime
+                        if let Ok(meta) = entry.metadata() {
+                            if let Ok(modified) = meta.modified() {
+                                if let Ok(elapsed) = modified.elapsed() {
+                                    if elapsed.as_secs() > 30 * 86400 {
+                                        old_count += 1;
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        let (severity, detail) = if old_count == 0 {
+            (
+                Severity::Pass,
+                s.health_detail_gens_all_recent
+                    .replace("{}", &total_count.to_string()),
+            )
+        } else if old_count > 10 {
+            (
+                Severity::Fail,
+                s.health_detail_gens_older
+                    .replace("{}", &old_count.to_string()),
+            )
+        } else {
+            (
+                Severity::Warn,
+                s.health_detail_gens_older
+                    .replace("{}", &old_count.to_string()),
+            )
+        };
+
+        let (fix, fix_description) = if old_count > 0 {
+            guarded_cleanup(
+                "sudo nix-collect-garbage --delete-older-than 30d",
+                Some(30),
+                s.health_fix_old_gens,
+                ctx.lang,
+            )
+        } else {
+            (None, s.health_fix_old_gens.to_string())
+        };
+        Some(Finding {
+            severity,
+            detail,
+            fix,
+            fix_description: Some(fix_description),
+        })
+    }
+}
+
+// ── Store size ──
+
+struct StoreSize;
+
+impl HealthCheck for StoreSize {
+    fn id(&self) -> &'static str {
+        "store-size"
+    }
+    fn name(&self, s: &Strings) -> &'static str {
+        s.health_name_store_size
+    }
+    fn description(&self, s: &Strings) -> &'static str {
+        s.health_desc_store_size
+    }
+    fn category(&self) -> Category {
+        Category::Storage
+    }
+    fn weight(&self) -> u8 {
+        20
+    }
+
+    fn run(&self, ctx: &CheckContext) -> Option<Finding> {
+        let s = i18n::get_strings(ctx.lang);
+        let store_path = std::path::Path::new("/nix/store");
+        let mut size_gb = 0.0f64;
+
+        // Fast method: use df on /nix/store
+        if let Ok(output) = Command::new("df").args(["-B1", "/nix/store"]).output() {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            if let Some(line) = stdout.lines().nth(1) {
+                let parts: Vec<&str> = line.split_whitespace().collect();
+                if parts.len() >= 3 {
+                    if let Ok(used) = parts[2].parse::<u64>() {
+                        size_gb = used as f64 / 1_073_741_824.0;
+                    }
+                }
+            }
+        }
+
+        // Also count store paths
+        let path_count = if store_path.exists() {
+            std::fs::read_dir(store_path)
+                .map(|entries| entries.count())
+                .unwrap_or(0)
+        } else {
+            0
+        };
+
+        let size_str = format!("{:.1}", size_gb);
+        let (severity, detail) = if size_gb < 20.0 {
+            (
+                Severity::Pass,
+                s.health_detail_store_ok
+                    .replacen("{}", &size_str, 1)
+                    .replacen("{}", &path_count.to_string(), 1),
+            )
+        } else if size_gb < 50.0 {
+            (
+                Severity::Warn,
+                s.health_detail_store_warn.replace("{}", &size_str),
+            )
+        } else {
+            (
+                Severity::Fail,
+                s.health_detail_store_crit.replace("{}", &size_str),
+            )
+        };
+
+        let (fix, fix_description) = guarded_cleanup(
+            "sudo nix-collect-garbage -d",
+            None,
+            s.health_fix_store_size,
+            ctx.lang,
+        );
+        Some(Finding {
+            severity,
+            detail,
+            fix,
+            fix_description: Some(fix_description),
+        })
+    }
+}
+
+// ── Disk usage ──
+
+struct DiskUsage;
+
+impl HealthCheck for DiskUsage {
+    fn id(&self) -> &'static str {
+        "disk-usage"
+    }
+    fn name(&self, s: &Strings) -> &'static str {
+        s.health_name_disk_usage
+    }
+    fn description(&self, s: &Strings) -> &'static str {
+        s.health_desc_disk
+    }
+    fn category(&self) -> Category {
+        Category::Storage
+    }
+    fn weight(&self) -> u8 {
+        25
+    }
+
+    fn run(&self, ctx: &CheckContext) -> Option<Finding> {
+        let s = i18n::get_strings(ctx.lang);
+        let mut usage_pct = 0u8;
+
+        if let Ok(output) = Command::new("df").args(["--output=pcent", "/"]).output() {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            if let Some(line) = stdout.lines().nth(1) {
+                if let Ok(pct) = line.trim().trim_end_matches('%').parse::<u8>() {
+                    usage_pct = pct;
+                }
+            }
+        }
+
+        let pct_str = usage_pct.to_string();
+        let (severity, detail) = if usage_pct < 80 {
+            (
+                Severity::Pass,
+                s.health_detail_disk_ok.replace("{}", &pct_str),
+            )
+        } else if usage_pct < 90 {
+            (
+                Severity::Warn,
+                s.health_detail_disk_warn.replace("{}", &pct_str),
+            )
+        } else {
+            (
+                Severity::Fail,
+                s.health_detail_disk_crit.replace("{}", &pct_str),
+            )
+        };
+
+        let (fix, fix_description) = guarded_cleanup(
+            "sudo nix-collect-garbage --delete-older-than 7d",
+            Some(7),
+            s.health_fix_disk,
+            ctx.lang,
+        );
+        Some(Finding {
+            severity,
+            detail,
+            fix,
+            fix_description: Some(fix_description),
+        })
+    }
+}
+
+// ── Store optimisation ──
+
+struct AutoOptimise;
+
+impl HealthCheck for AutoOptimise {
+    fn id(&self) -> &'static str {
+        "store-optimise"
+    }
+    fn name(&self, s: &Strings) -> &'static str {
+        s.health_name_optimise
+    }
+    fn description(&self, s: &Strings) -> &'static str {
+        s.health_desc_optimise
+    }
+    fn category(&self) -> Category {
+        Category::Storage
+    }
+    fn weight(&self) -> u8 {
+        5
+    }
+
+    fn run(&self, ctx: &CheckContext) -> Option<Finding> {
+        let s = i18n::get_strings(ctx.lang);
+        let config = signatures::nix_config()?;
+        let enabled = config
+            .get("auto-optimise-store")
+            .is_some_and(|v| v == "true");
+        // A periodic `nix-store --optimise` timer does the same job
+        let timer = std::path::Path::new("/etc/systemd/system/nix-optimise.timer").exists();
+
+        if enabled || timer {
+            return Some(Finding {
+                severity: Severity::Pass,
+                detail: s.health_detail_optimise_on.to_string(),
+                fix: None,
+                fix_description: None,
+            });
+        }
+        Some(Finding {
+            severity: Severity::Warn,
+            detail: s.health_detail_optimise_off.to_string(),
+            fix: Some(FixAction::Snippet(
+                "nix.settings.auto-optimise-store = true;\n\
+                 # or periodically instead of on every build:\n\
+                 # nix.optimise.automatic = true;"
+                    .to_string(),
+            )),
+            fix_description: Some(s.health_fix_optimise.to_string()),
+        })
+    }
+}
+
+// ── Channel / flake freshness ──
+
+struct Freshness;
+
+impl HealthCheck for Freshness {
+    fn id(&self) -> &'static str {
+        "freshness"
+    }
+    fn name(&self, s: &Strings) -> &'static str {
+        s.health_name_freshness
+    }
+    fn description(&self, s: &Strings) -> &'static str {
+        s.health_desc_freshness
+    }
+    fn category(&self) -> Category {
+        Category::Updates
+    }
+    fn weight(&self) -> u8 {
+        20
+    }
+    fn nixos_only(&self) -> bool {
+        true
+    }
+
+    fn run(&self, ctx: &CheckContext) -> Option<Finding> {
+        let s = i18n::get_strings(ctx.lang);
+        // Check when the current system was last built
+        let system_path = std::path::Path::new("/run/current-system");
+        let mut days_old = 0u64;
+
+        if let Ok(meta) = std::fs::symlink_metadata(system_path) {
+            if let Ok(modified) = meta.modified() {
+                if let Ok(elapsed) = modified.elapsed() {
+                    days_old = elapsed.as_secs() / 86400;
+                }
+            }
+        }
+
+        let days_str = days_old.to_string();
+        let (severity, detail) = if days_old <= 14 {
+            (
+                Severity::Pass,
+                s.health_detail_fresh_ok.replace("{}", &days_str),
+            )
+        } else if days_old <= 30 {
+            (
+                Severity::Warn,
+                s.health_detail_fresh_warn.replace("{}", &days_str),
+            )
+        } else {
+            (
+                Severity::Fail,
+                s.health_detail_fresh_crit.replace("{}", &days_str),
+            )
+        };
+
+        // Detect if flakes or channels for fix command
+        let uses_flakes = crate::nix::detect::detect_system(None)
+            .map(|s| s.uses_flakes)
+            .unwrap_or(false);
+
+        let fix_cmd = if uses_flakes {
+            "cd /etc/nixos && sudo nix flake update && sudo nixos-rebuild switch".to_string()
+        } else {
+            "sudo nix-channel --update && sudo nixos-rebuild switch".to_string()
+        };
+
+        Some(Finding {
+            severity,
+            detail,
+            fix: Some(FixAction::Run(fix_cmd)),
+            fix_description: Some(s.health_fix_freshness.to_string()),
+        })
+    }
+}
+
+// ── Flake input age ──
+
+struct FlakeWatch;
+
+impl HealthCheck for FlakeWatch {
+    fn id(&self) -> &'static str {
+        "flake-watch"
+    }
+    fn name(&self, s: &Strings) -> &'static str {
+        s.health_name_flake_watch
+    }
+    fn description(&self, s: &Strings) -> &'static str {
+        s.health_desc_flake_watch
+    }
+    fn category(&self) -> Category {
+        Category::Updates
+    }
+    fn weight(&self) -> u8 {
+        10
+    }
+
+    fn run(&self, ctx: &CheckContext) -> Option<Finding> {
+        if !ctx.flake_watch.enabled {
+            return None;
+        }
+        let stale = watch::scan(ctx.config_path.as_deref(), &ctx.flake_watch)?;
+        let s = i18n::get_strings(ctx.lang);
+
+        // Twice the threshold is no longer "a bit behind"
+        let severity = if stale.is_empty() {
+            Severity::Pass
+        } else if stale.iter().any(|st| st.age_days > st.threshold_days * 2) {
+            Severity::Fail
+        } else {
+            Severity::Warn
+        };
+        let detail = if stale.is_empty() {
+            s.health_detail_flake_watch_ok.to_string()
+        } else {
+            let list: Vec<String> = stale
+                .iter()
+                .map(|st| format!("{} ({}d)", st.name, st.age_days))
+                .collect();
+            s.health_detail_flake_watch_stale
+                .replacen("{}", &stale.len().to_string(), 1)
+                .replacen("{}", &list.join(", "), 1)
+        };
+
+        Some(Finding {
+            severity,
+            detail,
+            fix: None, // Selective update lives in Flake Inputs
+            fix_description: Some(s.health_fix_flake_watch.to_string()),
+        })
+    }
+}
+
+// ── Flake evaluation smoke test ──
+
+/// Evaluating a large configuration takes a while, but not this long
+const EVAL_TIMEOUT_SECS: u64 = 300;
+
+struct FlakeEval;
+
+impl HealthCheck for FlakeEval {
+    fn id(&self) -> &'static str {
+        "flake-eval"
+    }
+    fn name(&self, s: &Strings) -> &'static str {
+        s.health_name_flake_eval
+    }
+    fn description(&self, s: &Strings) -> &'static str {
+        s.health_desc_flake_eval
+    }
+    fn category(&self) -> Category {
+        Category::System
+    }
+    fn weight(&self) -> u8 {
+        15
+    }
+    fn nixos_only(&self) -> bool {
+        true
+    }
+
+    fn run(&self, ctx: &CheckContext) -> Option<Finding> {
+        let flake_dir = find_flake_dir(ctx.config_path.as_deref())?;
+        let s = i18n::get_strings(ctx.lang);
+        let nix = |args: &[&str]| {
+            let mut full = vec!["--extra-experimental-features", "nix-command flakes"];
+            full.extend_from_slice(args);
+            crate::nix::storage::output_with_timeout("nix", &full, EVAL_TIMEOUT_SECS)
+        };
+
+        // This host's toplevel is what a rebuild evaluates; flakes without
+        // it (host named differently) get the whole `flake check` instead
+        let host = crate::nix::detect::nixos_host(ctx.nixos_host.as_deref());
+        let attr = format!(
+            "{}#nixosConfigurations.\"{}\".config.system.build.toplevel.drvPath",
+            flake_dir, host
+        );
+        let mut target = format!("nixosConfigurations.{}", host);
+        let mut output = nix(&["eval", "--raw", &attr]);
+        let missing_host = output.as_ref().is_some_and(|o| {
+            !o.status.success()
+                && String::from_utf8_lossy(&o.stderr).contains("does not provide attribute")
+        });
+        if missing_host || host.is_empty() {
+            target = flake_dir.clone();
+            output = nix(&["flake", "check", "--no-build", &flake_dir]);
+        }
+
+        let Some(output) = output else {
+            return Some(Finding {
+                severity: Severity::Warn,
+                detail: s
+                    .health_detail_flake_eval_timeout
+                    .replace("{}", &EVAL_TIMEOUT_SECS.to_string()),
+                fix: None,
+                fix_description: None,
+            });
+        };
+        if output.status.success() {
+            return Some(Finding {
+                severity: Severity::Pass,
+                detail: s.health_detail_flake_eval_ok.replace("{}", &target),
+                fix: None,
+                fix_description: None,
+            });
+        }
+
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        let detail = s
+            .health_detail_flake_eval_failed
+            .replace("{}", &first_error_line(&stderr));
+        if let Ok(mut e) = ctx.eval_error.lock() {
+            *e = Some(stderr);
+        }
+        Some(Finding {
+            severity: Severity::Fail,
+            detail,
+            fix: None, // The fix is in the config; the Error Translator explains it
+            fix_description: Some(s.health_fix_flake_eval.to_string()),
+        })
+    }
+}
+
+/// The `error: …` line of nix output (the trace before it is noise here)
+fn first_error_line(stderr: &str) -> String {
+    let line = stderr
+        .lines()
+        .map(str::trim)
+        .find(|l| l.starts_with("error:"))
+        .or_else(|| stderr.lines().map(str::trim).find(|l| !l.is_empty()))
+        .unwrap_or("?");
+    let line = line.trim_start_matches("error:").trim();
+    if line.chars().count() > 100 {
+        format!("{}…", line.chars().take(99).collect::<String>())
+    } else {
+        line.to_string()
+    }
+}
+
+// ── Duplicate packages ──
+
+struct Duplicates;
+
+impl HealthCheck for Duplicates {
+    fn id(&self) -> &'static str {
+        "duplicates"
+    }
+    fn name(&self, s: &Strings) -> &'static str {
+        s.health_name_duplicates
+    }
+    fn description(&self, s: &Strings) -> &'static str {
+        s.health_desc_duplicates
+    }
+    fn category(&self) -> Category {
+        Category::System
+    }
+    fn weight(&self) -> u8 {
+        20
+    }
+
+    fn run(&self, ctx: &CheckContext) -> Option<Finding> {
+        use std::collections::HashMap;
+        let s = i18n::get_strings(ctx.lang);
+
+        let system_path = std::path::Path::new("/run/current-system");
+        let mut duplicates = 0u32;
+        let mut total = 0u32;
+
+        if system_path.exists() {
+            if let Ok(pkgs) = crate::nix::packages::get_packages(system_path) {
+                total = pkgs.len() as u32;
+                let mut name_counts: HashMap<String, u32> = HashMap::new();
+                for pkg in &pkgs {
+                    *name_counts.entry(pkg.name.clone()).or_insert(0) += 1;
+                }
+                duplicates = name_counts.values().filter(|&&v| v > 1).count() as u32;
+            }
+        }
+
+        let (severity, detail) = if duplicates == 0 {
+            (
+                Severity::Pass,
+                s.health_detail_dupes_ok.replace("{}", &total.to_string()),
+            )
+        } else if duplicates < 5 {
+            (
+                Severity::Warn,
+                s.health_detail_dupes_warn
+                    .replace("{}", &duplicates.to_string()),
+            )
+        } else {
+            (
+                Severity::Fail,
+                s.health_detail_dupes_crit
+                    .replace("{}", &duplicates.to_string()),
+            )
+        };
+
+        Some(Finding {
+            severity,
+            detail,
+            fix: None, // Can't auto-fix this easily
+            fix_description: Some(s.health_fix_duplicates.to_string()),
+        })
+    }
+}
+
+// ── Binary cache signatures ──
+
+struct Signatures;
+
+impl HealthCheck for Signatures {
+    fn id(&self) -> &'static str {
+        "signatures"
+    }
+    fn name(&self, s: &Strings) -> &'static str {
+        s.health_name_signatures
+    }
+    fn description(&self, s: &Strings) -> &'static str {
+        s.health_desc_signatures
+    }
+    fn category(&self) -> Category {
+        Category::Security
+    }
+    fn weight(&self) -> u8 {
+        15
+    }
+
+    fn run(&self, ctx: &CheckContext) -> Option<Finding> {
+        let audit = signatures::run_audit()?;
+        let s = i18n::get_strings(ctx.lang);
+
+        let bad: Vec<&str> = audit
+            .untrusted()
+            .filter(|p| p.trust == PathTrust::BadSignature)
+            .map(|p| p.path.as_str())
+            .collect();
+        let unknown = audit.count(PathTrust::UnknownKey);
+        let unsigned = audit.count(PathTrust::Unsigned);
+        let untrusted = bad.len() + unknown + unsigned;
+
+        // Without require-sigs nothing below matters: nix never checks
+        let severity = if !audit.require_sigs || !bad.is_empty() {
+            Severity::Fail
+        } else if untrusted > 0 {
+            Severity::Warn
+        } else {
+            Severity::Pass
+        };
+
+        let detail = if !audit.require_sigs {
+            s.health_detail_sigs_no_require.to_string()
+        } else if untrusted > 0 {
+            s.health_detail_sigs_untrusted
+                .replacen("{}", &untrusted.to_string(), 1)
+                .replacen("{}", &audit.paths.len().to_string(), 1)
+                .replacen("{}", &bad.len().to_string(), 1)
+                .replacen("{}", &unknown.to_string(), 1)
+                .replacen("{}", &unsigned.to_string(), 1)
+        } else {
+            s.health_detail_sigs_ok
+                .replacen("{}", &audit.paths.len().to_string(), 1)
+                .replacen("{}", &audit.count(PathTrust::Signed).to_string(), 1)
+                .replacen("{}", &audit.count(PathTrust::LocalBuild).to_string(), 1)
+        };
+
+        // The most serious finding decides what to tell the user
+        let fix_description = if !audit.require_sigs {
+            s.health_fix_sigs_require.to_string()
+        } else if !bad.is_empty() {
+            s.health_fix_sigs_bad.to_string()
+        } else if unknown > 0 {
+            s.health_fix_sigs_unknown_key
+                .replace("{}", &audit.unknown_signers().join(", "))
+        } else {
+            s.health_fix_sigs_unsigned.to_string()
+        };
+        // Re-substituting is the only fix that doesn't need a config change
+        let fix = if !audit.require_sigs {
+            Some(FixAction::Snippet(
+                "nix.settings.require-sigs = true;".to_string(),
+            ))
+        } else if !bad.is_empty() {
+            Some(FixAction::Run(format!(
+                "sudo nix-store --repair-path {}",
+                bad.join(" ")
+            )))
+        } else {
+            None
+        };
+
+        Some(Finding {
+            severity,
+            detail,
+            fix,
+            fix_description: Some(fix_description),
+        })
+    }
+}
+
+// ── Store contents ──
+
+/// Paths hashed per scan; verifying contents reads every file
+const INTEGRITY_SAMPLE: usize = 20;
+
+struct StoreIntegrity;
+
+impl HealthCheck for StoreIntegrity {
+    fn id(&self) -> &'static str {
+        "store-integrity"
+    }
+    fn name(&self, s: &Strings) -> &'static str {
+        s.health_name_integrity
+    }
+    fn description(&self, s: &Strings) -> &'static str {
+        s.health_desc_integrity
+    }
+    fn category(&self) -> Category {
+        Category::Security
+    }
+    fn weight(&self) -> u8 {
+        15
+    }
+
+    fn run(&self, ctx: &CheckContext) -> Option<Finding> {
+        let sample = signatures::closure_sample(INTEGRITY_SAMPLE)?;
+        let s = i18n::get_strings(ctx.lang);
+
+        let mut args = vec![
+            "--extra-experimental-features",
+            "nix-command",
+            "store",
+            "verify",
+            "--no-trust",
+        ];
+        args.extend(sample.iter().map(|p| p.as_str()));
+        let output = crate::nix::storage::output_with_timeout("nix", &args, 120)?;
+        // "path '/nix/store/…' was modified! expected hash …"
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let mut modified: Vec<&str> = stderr
+            .lines()
+            .filter(|l| l.contains("was modified") || l.contains("is corrupted"))
+            .filter_map(|l| l.split('\'').nth(1))
+            .collect();
+        modified.dedup();
+
+        if modified.is_empty() {
+            return Some(Finding {
+                severity: Severity::Pass,
+                detail: s
+                    .health_detail_integrity_ok
+                    .replace("{}", &sample.len().to_string()),
+                fix: None,
+                fix_description: None,
+            });
+        }
+        Some(Finding {
+            severity: Severity::Fail,
+            detail: s
+                .health_detail_integrity_bad
+                .replacen("{}", &modified.len().to_string(), 1)
+                .replacen("{}", &sample.len().to_string(), 1),
+            fix: Some(FixAction::Run(format!(
+                "sudo nix --extra-experimental-features nix-command store verify --repair {}",
+                modified.join(" ")
+            ))),
+            fix_description: Some(s.health_fix_integrity.to_string()),
+        })
+    }
+}
+
+// ── Accounts ──
+
+struct Accounts;
+
+impl HealthCheck for Accounts {
+    fn id(&self) -> &'static str {
+        "accounts"
+    }
+    fn name(&self, s: &Strings) -> &'static str {
+        s.health_name_accounts
+    }
+    fn description(&self, s: &Strings) -> &'static str {
+        s.health_desc_accounts
+    }
+    fn category(&self) -> Category {
+        Category::Security
+    }
+    fn weight(&self) -> u8 {
+        15
+    }
+    fn nixos_only(&self) -> bool {
+        true
+    }
+
+    fn run(&self, ctx: &CheckContext) -> Option<Finding> {
+        let audit = accounts::run_audit(ctx.lang);
+        let s = i18n::get_strings(ctx.lang);
+
+        let users = audit.count(FindingKind::ImperativeUser);
+        let groups =
+            audit.count(FindingKind::ImperativeGroup) + audit.count(FindingKind::GroupDrift);
+        let keys = audit.count(FindingKind::ExtraKey);
+
+        let severity = audit.worst_severity();
+        let detail = if severity == Severity::Pass {
+            s.health_detail_accounts_ok
+                .replace("{}", &audit.users_checked.to_string())
+        } else {
+            s.health_detail_accounts_drift
+                .replacen("{}", &users.to_string(), 1)
+                .replacen("{}", &groups.to_string(), 1)
+                .replacen("{}", &keys.to_string(), 1)
+        };
+        let applies = audit.source.is_some();
+        if let Ok(mut slot) = ctx.accounts.lock() {
+            *slot = Some(audit);
+        }
+        if !applies {
+            return None;
+        }
+
+        Some(Finding {
+            severity,
+            detail,
+            fix: None, // Needs config changes, see Accounts tab
+            fix_description: Some(s.health_fix_accounts.to_string()),
+        })
+    }
+}
+
+// ── Boot partition space ──
+
+const LOADER_ENTRIES: &str = "/boot/loader/entries";
+
+struct BootSpace;
+
+impl HealthCheck for BootSpace {
+    fn id(&self) -> &'static str {
+        "boot-space"
+    }
+    fn name(&self, s: &Strings) -> &'static str {
+        s.health_name_boot_space
+    }
+    fn description(&self, s: &Strings) -> &'static str {
+        s.health_desc_boot_space
+    }
+    fn category(&self) -> Category {
+        Category::Boot
+    }
+    fn weight(&self) -> u8 {
+        20
+    }
+    fn nixos_only(&self) -> bool {
+        true
+    }
+
+    fn run(&self, ctx: &CheckContext) -> Option<Finding> {
+        // /boot on the root filesystem is covered by the disk check
+        let usage =
+            crate::nix::storage::parse_disk_usage("/boot").filter(|u| u.mount_point == "/boot")?;
+        let s = i18n::get_strings(ctx.lang);
+        let entries = loader_entry_ids().map(|ids| ids.len()).unwrap_or(0);
+
+        // A rebuild with a new kernel copies one more kernel + initrd
+        let kernel_size: u64 = ["kernel", "initrd"]
+            .iter()
+            .filter_map(|f| std::fs::metadata(format!("/run/current-system/{}", f)).ok())
+            .map(|m| m.len())
+            .sum();
+        let free = crate::types::format_bytes(usage.available);
+        let detail = s
+            .health_detail_boot_space
+            .replacen("{}", &entries.to_string(), 1)
+            .replacen("{}", &free, 1)
+            .replacen("{}", &format!("{:.0}", usage.percent), 1);
+
+        if usage.available < kernel_size * 2 {
+            let (fix, fix_description) = guarded_cleanup(
+                "sudo nix-collect-garbage --delete-older-than 14d && \
+                 sudo /run/current-system/bin/switch-to-configuration boot",
+                Some(14),
+                s.health_fix_boot_space_clean,
+                ctx.lang,
+            );
+            Some(Finding {
+                severity: Severity::Fail,
+                detail,
+                fix,
+                fix_description: Some(fix_description),
+            })
+        } else if usage.percent >= 80.0 {
+            Some(Finding {
+                severity: Severity::Warn,
+                detail,
+                fix: Some(FixAction::Snippet(
+                    "boot.loader.systemd-boot.configurationLimit = 10;\n\
+                     # GRUB: boot.loader.grub.configurationLimit = 10;"
+                        .to_string(),
+                )),
+                fix_description: Some(s.health_fix_boot_space_limit.to_string()),
+            })
+        } else {
+            Some(Finding {
+                severity: Severity::Pass,
+                detail,
+                fix: None,
+                fix_description: None,
+            })
+        }
+    }
+}
+
+// ── Booted vs. running system ──
+
+struct BootedMismatch;
+
+impl HealthCheck for BootedMismatch {
+    fn id(&self) -> &'static str {
+        "booted-generation"
+    }
+    fn name(&self, s: &Strings) -> &'static str {
+        s.health_name_booted
+    }
+    fn description(&self, s: &Strings) -> &'static str {
+        s.health_desc_booted
+    }
+    fn category(&self) -> Category {
+        Category::Boot
+    }
+    fn weight(&self) -> u8 {
+        10
+    }
+    fn nixos_only(&self) -> bool {
+        true
+    }
+
+    fn run(&self, ctx: &CheckContext) -> Option<Finding> {
+        let booted = std::fs::read_link("/run/booted-system").ok()?;
+        let current = std::fs::read_link("/run/current-system").ok()?;
+        let s = i18n::get_strings(ctx.lang);
+
+        if booted == current {
+            return Some(Finding {
+                severity: Severity::Pass,
+                detail: s.health_detail_booted_same.to_string(),
+                fix: None,
+                fix_description: None,
+            });
+        }
+        // Only a kernel / initrd change needs the reboot to take effect
+        let kernel = |system: &std::path::Path| {
+            ["kernel", "initrd", "kernel-modules"].map(|f| std::fs::read_link(system.join(f)).ok())
+        };
+        let kernel_changed = kernel(&booted) != kernel(&current);
+        let (severity, detail) = if kernel_changed {
+            (Severity::Warn, s.health_detail_booted_kernel.to_string())
+        } else {
+            (Severity::Pass, s.health_detail_booted_switched.to_string())
+        };
+        Some(Finding {
+            severity,
+            detail,
+            fix: None, // Rebooting is the user's call
+            fix_description: Some(s.health_fix_booted.to_string()),
+        })
+    }
+}
+
+// ── initrd secrets ──
+
+struct InitrdSecrets;
+
+impl HealthCheck for InitrdSecrets {
+    fn id(&self) -> &'static str {
+        "initrd-secrets"
+    }
+    fn name(&self, s: &Strings) -> &'static str {
+        s.health_name_initrd_secrets
+    }
+    fn description(&self, s: &Strings) -> &'static str {
+        s.health_desc_initrd_secrets
+    }
+    fn category(&self) -> Category {
+        Category::Boot
+    }
+    fn weight(&self) -> u8 {
+        15
+    }
+    fn nixos_only(&self) -> bool {
+        true
+    }
+
+    fn run(&self, ctx: &CheckContext) -> Option<Finding> {
+        // Only exists when boot.initrd.secrets is set; copies each secret
+        // with `cp -a <source> "$tmp/.initrd-secrets/<dest>"`
+        let script = std::fs::read_to_string("/run/current-system/append-initrd-secrets").ok()?;
+        let s = i18n::get_strings(ctx.lang);
+        let sources: Vec<&str> = script
+            .lines()
+            .filter_map(|l| l.trim().strip_prefix("cp -a "))
+            .filter_map(|rest| rest.split_whitespace().next())
+            .map(|src| src.trim_matches(|c| c == '"' || c == '\''))
+            .collect();
+        let missing: Vec<&str> = sources
+            .iter()
+            .copied()
+            .filter(|src| !std::path::Path::new(src).exists())
+            .collect();
+
+        if missing.is_empty() {
+            return Some(Finding {
+                severity: Severity::Pass,
+                detail: s
+                    .health_detail_initrd_ok
+                    .replace("{}", &sources.len().to_string()),
+                fix: None,
+                fix_description: None,
+            });
+        }
+        Some(Finding {
+            severity: Severity::Fail,
+            detail: s
+                .health_detail_initrd_missing
+                .replace("{}", &missing.join(", ")),
+            fix: None, // Restoring the secret is up to the user
+            fix_description: Some(s.health_fix_initrd.to_string()),
+        })
+    }
+}
+
+// ── Stale boot entries ──
+
+struct StaleBootEntries;
+
+impl HealthCheck for StaleBootEntries {
+    fn id(&self) -> &'static str {
+        "boot-entries"
+    }
+    fn name(&self, s: &Strings) -> &'static str {
+        s.health_name_stale_entries
+    }
+    fn description(&self, s: &Strings) -> &'static str {
+        s.health_desc_stale_entries
+    }
+    fn category(&self) -> Category {
+        Category::Boot
+    }
+    fn weight(&self) -> u8 {
+        10
+    }
+    fn nixos_only(&self) -> bool {
+        true
+    }
+
+    fn run(&self, ctx: &CheckContext) -> Option<Finding> {
+        let ids = loader_entry_ids()?;
+        let s = i18n::get_strings(ctx.lang);
+        let mut stale: Vec<u32> = ids
+            .into_iter()
+            .filter(|id| {
+                !std::path::Path::new(&format!("/nix/var/nix/profiles/system-{}-link", id)).exists()
+            })
+            .collect();
+        stale.sort_unstable();
+        stale.dedup();
+
+        if stale.is_empty() {
+            return Some(Finding {
+                severity: Severity::Pass,
+                detail: s.health_detail_entries_ok.to_string(),
+                fix: None,
+                fix_description: None,
+            });
+        }
+        let list: Vec<String> = stale.iter().map(|id| format!("#{}", id)).collect();
+        Some(Finding {
+            severity: Severity::Warn,
+            detail: s
+                .health_detail_entries_stale
+                .replacen("{}", &stale.len().to_string(), 1)
+                .replacen("{}", &list.join(", "), 1),
+            // Reinstalling the bootloader writes entries for existing
+            // generations only and removes the rest
+            fix: Some(FixAction::Run(
+                "sudo /run/current-system/bin/switch-to-configuration boot".to_string(),
+            )),
+            fix_description: Some(s.health_fix_entries.to_string()),
+        })
+    }
+}
+
+/// Generation ids of systemd-boot entries (specialisations included).
+/// None without systemd-boot or when /boot isn't readable.
+fn loader_entry_ids() -> Option<Vec<u32>> {
+    let entries = std::fs::read_dir(LOADER_ENTRIES).ok()?;
+    Some(
+        entries
+            .flatten()
+            .filter_map(|e| {
+                let name = e.file_name().to_string_lossy().to_string();
+                let rest = name.strip_prefix("nixos-generation-")?;
+                let id: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+                id.parse().ok()
+            })
+            .collect(),
+    )
+}
+
+// ── Laptop power management ──
+//
+// These only apply with a built-in battery. Enabled NixOS services show up
+// as units in /etc/systemd/system, which is all they need to know.
+
+const POWER_DAEMONS: [(&str, &str); 3] = [
+    ("tlp.service", "services.tlp"),
+    (
+        "power-profiles-daemon.service",
+        "services.power-profiles-daemon",
+    ),
+    ("auto-cpufreq.service", "services.auto-cpufreq"),
+];
+
+/// /sys/class/power_supply entries of built-in batteries (not the ones in
+/// mice or headsets)
+fn batteries() -> Vec<std::path::PathBuf> {
+    let Ok(entries) = std::fs::read_dir("/sys/class/power_supply") else {
+        return Vec::new();
+    };
+    let read = |p: &std::path::Path, f: &str| {
+        std::fs::read_to_string(p.join(f))
+            .map(|v| v.trim().to_string())
+            .unwrap_or_default()
+    };
+    let mut found: Vec<_> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| read(p, "type") == "Battery" && read(p, "scope") != "Device")
+        .collect();
+    found.sort();
+    found
+}
+
+fn unit_installed(unit: &str) -> bool {
+    std::path::Path::new("/etc/systemd/system")
+        .join(unit)
+        .exists()
+}
+
+/// A `Key: value kB` line of /proc/meminfo, in bytes
+fn meminfo_bytes(meminfo: &str, key: &str) -> Option<u64> {
+    meminfo
+        .lines()
+        .find_map(|l| l.strip_prefix(key)?.strip_prefix(':'))
+        .and_then(|v| v.split_whitespace().next()?.parse::<u64>().ok())
+        .map(|kb| kb * 1024)
+}
+
+/// Swap that can hold a hibernation image, in bytes (zram lives in RAM)
+fn disk_swap_bytes(swaps: &str) -> u64 {
+    swaps
+        .lines()
+        .skip(1)
+        .filter(|l| !l.starts_with("/dev/zram"))
+        .filter_map(|l| l.split_whitespace().nth(2)?.parse::<u64>().ok())
+        .map(|kb| kb * 1024)
+        .sum()
+}
+
+/// `HandleLidSwitch` from logind.conf, systemd's default if unset
+fn lid_switch_action() -> String {
+    std::fs::read_to_string("/etc/systemd/logind.conf")
+        .ok()
+        .and_then(|conf| {
+            conf.lines()
+                .find_map(|l| l.trim().strip_prefix("HandleLidSwitch="))
+                .map(|v| v.trim().to_string())
+        })
+        .unwrap_or_else(|| "suspend".to_string())
+}
+
+struct PowerDaemons;
+
+impl HealthCheck for PowerDaemons {
+    fn id(&self) -> &'static str {
+        "power-daemons"
+    }
+    fn name(&self, s: &Strings) -> &'static str {
+        s.health_name_power_daemons
+    }
+    fn description(&self, s: &Strings) -> &'static str {
+        s.health_desc_power_daemons
+    }
+    fn category(&self) -> Category {
+        Category::Power
+    }
+    fn weight(&self) -> u8 {
+        15
+    }
+    fn nixos_only(&self) -> bool {
+        true
+    }
+
+    fn run(&self, ctx: &CheckContext) -> Option<Finding> {
+        if batteries().is_empty() {
+            return None;
+        }
+        let s = i18n::get_strings(ctx.lang);
+        let active: Vec<(&str, &str)> = POWER_DAEMONS
+            .iter()
+            .copied()
+            .filter(|(unit, _)| unit_installed(unit))
+            .collect();
+        let names: Vec<&str> = active
+            .iter()
+            .map(|(unit, _)| unit.trim_end_matches(".service"))
+            .collect();
+
+        match active.as_slice() {
+            [] => Some(Finding {
+                severity: Severity::Warn,
+                detail: s.health_detail_power_none.to_string(),
+                fix: Some(FixAction::Snippet(
+                    "services.power-profiles-daemon.enable = true;\n\
+                     # or, for finer control: services.tlp.enable = true;"
+                        .to_string(),
+                )),
+                fix_description: Some(s.health_fix_power_none.to_string()),
+            }),
+            [_] => Some(Finding {
+                severity: Severity::Pass,
+                detail: s.health_detail_power_ok.replace("{}", names[0]),
+                fix: None,
+                fix_description: None,
+            }),
+            [(_, keep), rest @ ..] => {
+                // Keep the first in POWER_DAEMONS order (TLP if it's there:
+                // it's the one people enable on purpose, the others often
+                // come with a desktop)
+                let snippet: Vec<String> = rest
+                    .iter()
+                    .map(|(_, option)| format!("{}.enable = false;", option))
+                    .chain(std::iter::once(format!("# keeps {}", keep)))
+                    .collect();
+                Some(Finding {
+                    severity: Severity::Fail,
+                    detail: s
+                        .health_detail_power_conflict
+                        .replace("{}", &names.join(", ")),
+                    fix: Some(FixAction::Snippet(snippet.join("\n"))),
+                    fix_description: Some(s.health_fix_power_conflict.to_string()),
+                })
+            }
+        }
+    }
+}
+
+struct Thermald;
+
+impl HealthCheck for Thermald {
+    fn id(&self) -> &'static str {
+        "thermald"
+    }
+    fn name(&self, s: &Strings) -> &'static str {
+        s.health_name_thermald
+    }
+    fn description(&self, s: &Strings) -> &'static str {
+        s.health_desc_thermald
+    }
+    fn category(&self) -> Category {
+        Category::Power
+    }
+    fn weight(&self) -> u8 {
+        5
+    }
+    fn nixos_only(&self) -> bool {
+        true
+    }
+
+    fn run(&self, ctx: &CheckContext) -> Option<Finding> {
+        let cpuinfo = std::fs::read_to_string("/proc/cpuinfo").ok()?;
+        if !cpuinfo.contains("GenuineIntel") || batteries().is_empty() {
+            return None;
+        }
+        let s = i18n::get_strings(ctx.lang);
+        if unit_installed("thermald.service") {
+            return Some(Finding {
+                severity: Severity::Pass,
+                detail: s.health_detail_thermald_ok.to_string(),
+                fix: None,
+                fix_description: None,
+            });
+        }
+        Some(Finding {
+            severity: Severity::Warn,
+            detail: s.health_detail_thermald_missing.to_string(),
+            fix: Some(FixAction::Snippet(
+                "services.thermald.enable = true;".to_string(),
+            )),
+            fix_description: Some(s.health_fix_thermald.to_string()),
+        })
+    }
+}
+
+struct Hibernate;
+
+impl HealthCheck for Hibernate {
+    fn id(&self) -> &'static str {
+        "hibernate"
+    }
+    fn name(&self, s: &Strings) -> &'static str {
+        s.health_name_hibernate
+    }
+    fn description(&self, s: &Strings) -> &'static str {
+        s.health_desc_hibernate
+    }
+    fn category(&self) -> Category {
+        Category::Power
+    }
+    fn weight(&self) -> u8 {
+        10
+    }
+    fn nixos_only(&self) -> bool {
+        true
+    }
+
+    fn run(&self, ctx: &CheckContext) -> Option<Finding> {
+        if batteries().is_empty() {
+            return None;
+        }
+        let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+        let ram = meminfo_bytes(&meminfo, "MemTotal")?;
+        let s = i18n::get_strings(ctx.lang);
+        let swap = disk_swap_bytes(&std::fs::read_to_string("/proc/swaps").unwrap_or_default());
+        let resume = std::fs::read_to_string("/sys/power/resume")
+            .is_ok_and(|r| !matches!(r.trim(), "" | "0:0"))
+            || std::fs::read_to_string("/proc/cmdline")
+                .is_ok_and(|c| c.split_whitespace().any(|p| p.starts_with("resume=")));
+        // Kernel lockdown (e.g. with Secure Boot) disables hibernation
+        let locked_down = std::fs::read_to_string("/sys/kernel/security/lockdown")
+            .is_ok_and(|l| !l.contains("[none]"));
+        let lid = lid_switch_action();
+        let wants_hibernate = matches!(
+            lid.as_str(),
+            "hibernate" | "suspend-then-hibernate" | "hybrid-sleep"
+        );
+
+        let sizes = s
+            .health_detail_hibernate_sizes
+            .replacen("{}", &crate::types::format_bytes(swap), 1)
+            .replacen("{}", &crate::types::format_bytes(ram), 1);
+        let problem = if locked_down {
+            Some(s.health_detail_hibernate_lockdown.to_string())
+        } else if swap < ram {
+            Some(s.health_detail_hibernate_swap.to_string())
+        } else if !resume {
+            Some(s.health_detail_hibernate_resume.to_string())
+        } else {
+            None
+        };
+
+        match problem {
+            Some(problem) if wants_hibernate => {
+                let ram_mib = ram.div_ceil(1024 * 1024);
+                Some(Finding {
+                    severity: Severity::Fail,
+                    detail: format!(
+                        "{} · {} ({})",
+                        s.health_detail_hibernate_lid.replace("{}", &lid),
+                        problem,
+                        sizes
+                    ),
+                    fix: Some(FixAction::Snippet(format!(
+                        "# Swap on disk at least as large as RAM\n\
+                         swapDevices = [ {{ device = \"/var/lib/swapfile\"; size = {}; }} ];\n\
+                         boot.resumeDevice = \"/dev/disk/by-uuid/<partition holding the swap>\";\n\
+                         # for a swap file also: boot.kernelParams = [ \"resume_offset=<offset>\" ];\n\
+                         # or keep it simple and only suspend:\n\
+                         # services.logind.lidSwitch = \"suspend\";",
+                        ram_mib
+                    ))),
+                    fix_description: Some(s.health_fix_hibernate.to_string()),
+                })
+            }
+            Some(problem) => Some(Finding {
+                severity: Severity::Pass,
+                detail: format!(
+                    "{} · {} ({})",
+                    s.health_detail_hibernate_lid.replace("{}", &lid),
+                    problem,
+                    sizes
+                ),
+                fix: None,
+                fix_description: None,
+            }),
+            None => Some(Finding {
+                severity: Severity::Pass,
+                detail: format!(
+                    "{} · {} ({})",
+                    s.health_detail_hibernate_lid.replace("{}", &lid),
+                    s.health_detail_hibernate_ready,
+                    sizes
+                ),
+                fix: None,
+                fix_description: None,
+            }),
+        }
+    }
+}
+
+struct ChargeThreshold;
+
+impl HealthCheck for ChargeThreshold {
+    fn id(&self) -> &'static str {
+        "charge-threshold"
+    }
+    fn name(&self, s: &Strings) -> &'static str {
+        s.health_name_charge_threshold
+    }
+    fn description(&self, s: &Strings) -> &'static str {
+        s.health_desc_charge_threshold
+    }
+    fn category(&self) -> Category {
+        Category::Power
+    }
+    fn weight(&self) -> u8 {
+        5
+    }
+    fn nixos_only(&self) -> bool {
+        true
+    }
+
+    fn run(&self, ctx: &CheckContext) -> Option<Finding> {
+        let battery = batteries().into_iter().next()?;
+        let s = i18n::get_strings(ctx.lang);
+        let name = battery.file_name()?.to_string_lossy().to_string();
+        // Older ThinkPad drivers use charge_stop_threshold
+        let threshold = ["charge_control_end_threshold", "charge_stop_threshold"]
+            .iter()
+            .find_map(|f| std::fs::read_to_string(battery.join(f)).ok())
+            .and_then(|v| v.trim().parse::<u8>().ok());
+
+        let Some(threshold) = threshold else {
+            return Some(Finding {
+                severity: Severity::Pass,
+                detail: s.health_detail_charge_unsupported.replace("{}", &name),
+                fix: None,
+                fix_description: None,
+            });
+        };
+        if threshold < 100 {
+            return Some(Finding {
+                severity: Severity::Pass,
+                detail: s
+                    .health_detail_charge_set
+                    .replacen("{}", &name, 1)
+                    .replacen("{}", &threshold.to_string(), 1),
+                fix: None,
+                fix_description: None,
+            });
+        }
+        let snippet = if unit_installed("tlp.service") {
+            format!(
+                "services.tlp.settings = {{\n  START_CHARGE_THRESH_{0} = 75;\n  STOP_CHARGE_THRESH_{0} = 80;\n}};",
+                name
+            )
+        } else {
+            format!(
+                "systemd.tmpfiles.rules = [\n  \"w /sys/class/power_supply/{}/charge_control_end_threshold - - - - 80\"\n];",
+                name
+            )
+        };
+        Some(Finding {
+            severity: Severity::Warn,
+            detail: s.health_detail_charge_full.replace("{}", &name),
+            fix: Some(FixAction::Snippet(snippet)),
+            fix_description: Some(s.health_fix_charge.to_string()),
+        })
+    }
+}
+
+// ── Helpers ──
+
+/// A generation-deleting fix, unless it would take the booted or
+/// boot-default generation along; then no fix and the reason instead
+fn guarded_cleanup(
+    cmd: &str,
+    older_than_days: Option<u32>,
+    description: &str,
+    lang: Language,
+) -> (Option<FixAction>, String) {
+    match endangered_by_cleanup(older_than_days) {
+        Some((id, role)) => {
+            let s = i18n::get_strings(lang);
+            (
+                None,
+                s.gen_cleanup_refused
+                    .replace("{}", &boot_role_reason(id, role, lang)),
+            )
+        }
+        None => (
+            Some(FixAction::Run(cmd.to_string())),
+            description.to_string(),
+        ),
+    }
+}
+
+fn chrono_now_days() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() / 86400)
+        .unwrap_or(0)
+}
+
+fn extract_generation_age_days(line: &str, now_days: u64) -> Option<u64> {
+    // Try to find a date pattern YYYY-MM-DD in the line
+    for word in line.split_whitespace() {
+        if word.len() == 10 && word.chars().nth(4) == Some('-') && word.chars().nth(7) == Some('-')
+        {
+            let parts: Vec<&str> = word.split('-').collect();
+            if parts.len() == 3 {
+                let y: u64 = parts[0].parse().ok()?;
+                let m: u64 = parts[1].parse().ok()?;
+                let d: u64 = parts[2].parse().ok()?;
+                // Rough day count since epoch
+                let gen_days = (y - 1970) * 365 + (y - 1969) / 4 + (m - 1) * 30 + d;
+                return Some(now_days.saturating_sub(gen_days));
+            }
+        }
+    }
+    None
+}
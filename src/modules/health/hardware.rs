@@ -0,0 +1,416 @@
+//! Hardware and firmware checks.
+//!
+//! Things that hardware-configuration.nix and a few enable flags decide:
+//! firmware the kernel asked for and didn't get, CPU microcode, swap/zram,
+//! and file systems referenced by a UUID that no longer exists (a disk
+//! swapped or reformatted after `nixos-generate-config`). Each finding
+//! comes with the snippet that fixes it.
+
+use super::checks::{Category, CheckContext, Finding, FixAction, HealthCheck};
+use super::Severity;
+use crate::i18n::{self, Strings};
+use crate::nix::storage::output_with_timeout;
+use crate::types::format_bytes;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Appended to the registry, in the "hardware" category
+pub fn checks() -> Vec<Arc<dyn HealthCheck>> {
+    vec![
+        Arc::new(Firmware),
+        Arc::new(Microcode),
+        Arc::new(Swap),
+        Arc::new(DiskIds),
+    ]
+}
+
+/// Kernel messages of this boot: `dmesg`, or the journal when
+/// `kernel.dmesg_restrict` keeps dmesg from non-root users
+fn kernel_log() -> Option<String> {
+    let read = |cmd: &str, args: &[&str]| {
+        output_with_timeout(cmd, args, 5)
+            .filter(|o| o.status.success() && !o.stdout.is_empty())
+            .map(|o| String::from_utf8_lossy(&o.stdout).to_string())
+    };
+    read("dmesg", &[])
+        .or_else(|| read("journalctl", &["-k", "-b", "-q", "--no-pager", "-o", "cat"]))
+}
+
+/// Firmware files the kernel failed to load, deduplicated
+fn missing_firmware(log: &str) -> Vec<String> {
+    let mut files: Vec<String> = log
+        .lines()
+        .filter_map(|line| {
+            // "Direct firmware load for iwlwifi-ty-a0-gf-a0-83.ucode failed with error -2"
+            // "firmware: failed to load rtl_nic/rtl8168h-2.fw (-2)"
+            let rest = line
+                .split_once("Direct firmware load for ")
+                .or_else(|| line.split_once("firmware: failed to load "))?
+                .1;
+            let file = rest.split_whitespace().next()?;
+            // -2 is ENOENT; other errors aren't a missing file
+            (line.contains("error -2") || line.contains("(-2)")).then(|| file.to_string())
+        })
+        .collect();
+    files.sort();
+    files.dedup();
+    files
+}
+
+struct Firmware;
+
+impl HealthCheck for Firmware {
+    fn id(&self) -> &'static str {
+        "firmware"
+    }
+    fn name(&self, s: &Strings) -> &'static str {
+        s.health_name_firmware
+    }
+    fn description(&self, s: &Strings) -> &'static str {
+        s.health_desc_firmware
+    }
+    fn category(&self) -> Category {
+        Category::Hardware
+    }
+    fn weight(&self) -> u8 {
+        10
+    }
+
+    fn run(&self, ctx: &CheckContext) -> Option<Finding> {
+        let s = i18n::get_strings(ctx.lang);
+        let missing = missing_firmware(&kernel_log()?);
+        if missing.is_empty() {
+            return Some(Finding {
+                severity: Severity::Pass,
+                detail: s.health_detail_firmware_ok.to_string(),
+                fix: None,
+                fix_description: None,
+            });
+        }
+        Some(Finding {
+            severity: Severity::Warn,
+            detail: s
+                .health_detail_firmware_missing
+                .replace("{}", &missing.join(", ")),
+            fix: Some(FixAction::Snippet(
+                "hardware.enableRedistributableFirmware = true;\n\
+                 # if that is already on, the file is in the unfree set:\n\
+                 # nixpkgs.config.allowUnfree = true;\n\
+                 # hardware.enableAllFirmware = true;"
+                    .to_string(),
+            )),
+            fix_description: Some(s.health_fix_firmware.to_string()),
+        })
+    }
+}
+
+struct Microcode;
+
+impl HealthCheck for Microcode {
+    fn id(&self) -> &'static str {
+        "microcode"
+    }
+    fn name(&self, s: &Strings) -> &'static str {
+        s.health_name_microcode
+    }
+    fn description(&self, s: &Strings) -> &'static str {
+        s.health_desc_microcode
+    }
+    fn category(&self) -> Category {
+        Category::Hardware
+    }
+    fn weight(&self) -> u8 {
+        10
+    }
+
+    fn run(&self, ctx: &CheckContext) -> Option<Finding> {
+        let cpuinfo = std::fs::read_to_string("/proc/cpuinfo").ok()?;
+        let vendor = if cpuinfo.contains("GenuineIntel") {
+            "intel"
+        } else if cpuinfo.contains("AuthenticAMD") {
+            "amd"
+        } else {
+            return None;
+        };
+        // VMs report the host CPU too, but the host loads the microcode
+        if cpuinfo.contains(" hypervisor") {
+            return None;
+        }
+        let log = kernel_log()?;
+        let s = i18n::get_strings(ctx.lang);
+        // Intel and AMD both log "updated early" when the initrd carried
+        // a newer revision than the firmware
+        if log.to_lowercase().contains("updated early") {
+            return Some(Finding {
+                severity: Severity::Pass,
+                detail: s.health_detail_microcode_ok.to_string(),
+                fix: None,
+                fix_description: None,
+            });
+        }
+        Some(Finding {
+            severity: Severity::Warn,
+            detail: s.health_detail_microcode_missing.replace("{}", vendor),
+            fix: Some(FixAction::Snippet(format!(
+                "hardware.cpu.{}.updateMicrocode = true;",
+                vendor
+            ))),
+            fix_description: Some(s.health_fix_microcode.to_string()),
+        })
+    }
+}
+
+/// (zram bytes, other swap bytes) from /proc/swaps
+fn swap_sizes(swaps: &str) -> (u64, u64) {
+    swaps
+        .lines()
+        .skip(1)
+        .filter_map(|l| {
+            let mut fields = l.split_whitespace();
+            let name = fields.next()?;
+            let kb = fields.nth(1)?.parse::<u64>().ok()?;
+            Some((name.starts_with("/dev/zram"), kb * 1024))
+        })
+        .fold((0, 0), |(zram, disk), (is_zram, bytes)| {
+            if is_zram {
+                (zram + bytes, disk)
+            } else {
+                (zram, disk + bytes)
+            }
+        })
+}
+
+struct Swap;
+
+impl HealthCheck for Swap {
+    fn id(&self) -> &'static str {
+        "swap"
+    }
+    fn name(&self, s: &Strings) -> &'static str {
+        s.health_name_swap
+    }
+    fn description(&self, s: &Strings) -> &'static str {
+        s.health_desc_swap
+    }
+    fn category(&self) -> Category {
+        Category::Hardware
+    }
+    fn weight(&self) -> u8 {
+        5
+    }
+
+    fn run(&self, ctx: &CheckContext) -> Option<Finding> {
+        let s = i18n::get_strings(ctx.lang);
+        let (zram, disk) = swap_sizes(&std::fs::read_to_string("/proc/swaps").ok()?);
+        if zram + disk == 0 {
+            return Some(Finding {
+                severity: Severity::Warn,
+                detail: s.health_detail_swap_none.to_string(),
+                fix: Some(FixAction::Snippet(
+                    "zramSwap.enable = true;\n\
+                     # compressed swap in RAM; for hibernation a disk swap is needed:\n\
+                     # swapDevices = [ { device = \"/var/lib/swapfile\"; size = 8192; } ];"
+                        .to_string(),
+                )),
+                fix_description: Some(s.health_fix_swap.to_string()),
+            });
+        }
+        Some(Finding {
+            severity: Severity::Pass,
+            detail: s
+                .health_detail_swap_ok
+                .replacen("{}", &format_bytes(zram), 1)
+                .replacen("{}", &format_bytes(disk), 1),
+            fix: None,
+            fix_description: None,
+        })
+    }
+}
+
+/// hardware-configuration.nix files of the configuration
+fn hardware_configs(config_path: Option<&str>) -> Vec<PathBuf> {
+    let mut roots: Vec<PathBuf> = config_path.map(PathBuf::from).into_iter().collect();
+    roots.push(PathBuf::from("/etc/nixos"));
+    let mut found = Vec::new();
+    for root in roots {
+        let root = if root.is_file() {
+            root.parent().map(Path::to_path_buf).unwrap_or(root)
+        } else {
+            root
+        };
+        collect_named(&root, "hardware-configuration.nix", 3, &mut found);
+    }
+    found.sort();
+    found.dedup();
+    found
+}
+
+fn collect_named(dir: &Path, name: &str, depth: usize, found: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let file_name = entry.file_name();
+        if file_name.to_string_lossy().starts_with('.') {
+            continue;
+        }
+        if path.is_dir() && depth > 0 {
+            collect_named(&path, name, depth - 1, found);
+        } else if file_name == name {
+            found.push(path);
+        }
+    }
+}
+
+/// `/dev/disk/by-uuid/…` (and by-label, by-partuuid) references with the
+/// mount point or "swap" they belong to
+fn disk_refs(nix: &str) -> Vec<(String, String)> {
+    let mut owner = String::new();
+    let mut refs = Vec::new();
+    for line in nix.lines() {
+        if let Some(rest) = line.split_once("fileSystems.\"").map(|(_, r)| r) {
+            owner = rest.split('"').next().unwrap_or_default().to_string();
+        } else if line.contains("swapDevices") {
+            owner = "swap".to_string();
+        }
+        let mut rest = line;
+        while let Some(start) = rest.find("/dev/disk/by-") {
+            let path: String = rest[start..]
+                .chars()
+                .take_while(|c| !matches!(c, '"' | ';' | ' ' | '}'))
+                .collect();
+            rest = &rest[start + path.len()..];
+            refs.push((owner.clone(), path));
+        }
+    }
+    refs
+}
+
+/// The by-uuid path of what is mounted at `mount_point` right now
+fn current_uuid(mount_point: &str) -> Option<String> {
+    let mounts = std::fs::read_to_string("/proc/self/mounts").ok()?;
+    let source = mounts.lines().find_map(|l| {
+        let mut fields = l.split_whitespace();
+        let source = fields.next()?;
+        (fields.next()? == mount_point).then(|| source.to_string())
+    })?;
+    let device = std::fs::canonicalize(source).ok()?;
+    std::fs::read_dir("/dev/disk/by-uuid")
+        .ok()?
+        .flatten()
+        .find(|e| std::fs::canonicalize(e.path()).is_ok_and(|p| p == device))
+        .map(|e| e.path().to_string_lossy().to_string())
+}
+
+struct DiskIds;
+
+impl HealthCheck for DiskIds {
+    fn id(&self) -> &'static str {
+        "disk-ids"
+    }
+    fn name(&self, s: &Strings) -> &'static str {
+        s.health_name_disk_ids
+    }
+    fn description(&self, s: &Strings) -> &'static str {
+        s.health_desc_disk_ids
+    }
+    fn category(&self) -> Category {
+        Category::Hardware
+    }
+    fn weight(&self) -> u8 {
+        20
+    }
+    fn nixos_only(&self) -> bool {
+        true
+    }
+
+    fn run(&self, ctx: &CheckContext) -> Option<Finding> {
+        let files = hardware_configs(ctx.config_path.as_deref());
+        if files.is_empty() || !Path::new("/dev/disk").exists() {
+            return None;
+        }
+        let s = i18n::get_strings(ctx.lang);
+        let mut missing = Vec::new();
+        let mut snippet = Vec::new();
+        for file in &files {
+            let Ok(nix) = std::fs::read_to_string(file) else {
+                continue;
+            };
+            for (owner, path) in disk_refs(&nix) {
+                if Path::new(&path).exists() {
+                    continue;
+                }
+                missing.push(format!("{} ({})", path, owner));
+                match current_uuid(&owner) {
+                    Some(uuid) => {
+                        snippet.push(format!("fileSystems.\"{}\".device = \"{}\";", owner, uuid))
+                    }
+                    None => snippet.push(format!("# {}: {} is gone", owner, path)),
+                }
+            }
+        }
+        if missing.is_empty() {
+            return Some(Finding {
+                severity: Severity::Pass,
+                detail: s.health_detail_disk_ids_ok.to_string(),
+                fix: None,
+                fix_description: None,
+            });
+        }
+        snippet.push("# or compare with: nixos-generate-config --show-hardware-config".to_string());
+        Some(Finding {
+            severity: Severity::Fail,
+            detail: s
+                .health_detail_disk_ids_missing
+                .replace("{}", &missing.join(", ")),
+            fix: Some(FixAction::Snippet(snippet.join("\n"))),
+            fix_description: Some(s.health_fix_disk_ids.to_string()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_firmware_swap_and_disk_refs() {
+        let log = "iwlwifi 0000:00:14.3: Direct firmware load for iwlwifi-so-a0-gf-a0-86.ucode failed with error -2\n\
+                   r8169 0000:02:00.0: firmware: failed to load rtl_nic/rtl8168h-2.fw (-2)\n\
+                   iwlwifi 0000:00:14.3: Direct firmware load for iwlwifi-so-a0-gf-a0-86.ucode failed with error -2\n\
+                   i915: Direct firmware load for i915/x.bin failed with error -110";
+        assert_eq!(
+            missing_firmware(log),
+            vec!["iwlwifi-so-a0-gf-a0-86.ucode", "rtl_nic/rtl8168h-2.fw"]
+        );
+
+        let swaps = "Filename Type Size Used Priority\n\
+                     /dev/zram0 partition 4194300 0 5\n\
+                     /dev/nvme0n1p3 partition 8388604 0 -2\n";
+        assert_eq!(swap_sizes(swaps), (4194300 * 1024, 8388604 * 1024));
+
+        let nix = r#"
+  fileSystems."/" =
+    { device = "/dev/disk/by-uuid/1111-aaaa";
+      fsType = "ext4";
+    };
+  fileSystems."/boot" = { device = "/dev/disk/by-uuid/ABCD-1234"; fsType = "vfat"; };
+  swapDevices = [ { device = "/dev/disk/by-uuid/2222-bbbb"; } ];
+"#;
+        assert_eq!(
+            disk_refs(nix),
+            vec![
+                ("/".to_string(), "/dev/disk/by-uuid/1111-aaaa".to_string()),
+                (
+                    "/boot".to_string(),
+                    "/dev/disk/by-uuid/ABCD-1234".to_string()
+                ),
+                (
+                    "swap".to_string(),
+                    "/dev/disk/by-uuid/2222-bbbb".to_string()
+                ),
+            ]
+        );
+    }
+}
@@ -2,29 +2,59 @@
 //!
 //! Dashboard: health score + check list (read-only)
 //! Fix: actionable items to heal your system
+//! Accounts: users, groups and SSH keys vs. the declarative config
 //!
-//! Checks:
+//! Checks (see `checks.rs`, each runs in its own thread):
 //! - Old generations (>30 days)
+//! - Nix store size and root disk usage
+//! - Store optimisation (auto-optimise-store)
 //! - Channel/flake freshness
-//! - Nix store size
+//! - Flake inputs past their `[flake_watch]` age threshold
+//! - Flake evaluation smoke test
 //! - Duplicate packages
-//! - Root disk usage
-
-use crate::config::Language;
+//! - Binary cache signatures of a closure sample (see `signatures.rs`)
+//! - Store contents of a closure sample (`nix store verify`)
+//! - Imperative users/groups/SSH keys (see `accounts.rs`)
+//! - /boot free space, booted vs. running generation, initrd secrets,
+//!   boot entries of deleted generations
+//! - nix.conf: experimental features for flakes, build parallelism,
+//!   trusted-users (see `nixconf.rs`)
+//! - Missing firmware, CPU microcode, swap/zram, file systems whose
+//!   UUID is gone (see `hardware.rs`)
+//! - nixmate's own optional tools (see `selfcheck.rs`, not scored)
+//!
+//! Every finished scan is appended to the score history (`trend.rs`); the
+//! start screen shows the score, its trend and the top issues.
+
+mod accounts;
+pub mod checks;
+mod hardware;
+mod nixconf;
+mod selfcheck;
+mod signatures;
+pub mod trend;
+
+use crate::config::{FlakeWatchConfig, Language};
 use crate::i18n;
+use crate::modules::jobs::Job;
+use crate::modules::module::{Module, RenderCtx};
+use crate::modules::palette::PaletteCommand;
 use crate::types::FlashMessage;
 use crate::ui::theme::Theme;
 use crate::ui::widgets;
+use accounts::AccountAudit;
 use anyhow::Result;
+use checks::{CheckContext, CheckResult, FixAction};
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
     layout::{Constraint, Layout, Rect},
-    style::{Modifier, Style},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, Paragraph, Tabs},
     Frame,
 };
-use std::sync::mpsc;
+use std::sync::{mpsc, Arc, Mutex};
+use trend::Trend;
 
 // ── Sub-tabs ──
 
@@ -33,17 +63,32 @@ pub enum HealthSubTab {
     #[default]
     Dashboard,
     Fix,
+    Accounts,
 }
 
 impl HealthSubTab {
     pub fn all() -> &'static [HealthSubTab] {
-        &[HealthSubTab::Dashboard, HealthSubTab::Fix]
+        &[
+            HealthSubTab::Dashboard,
+            HealthSubTab::Fix,
+            HealthSubTab::Accounts,
+        ]
     }
 
     pub fn index(&self) -> usize {
         match self {
             HealthSubTab::Dashboard => 0,
             HealthSubTab::Fix => 1,
+            HealthSubTab::Accounts => 2,
+        }
+    }
+
+    pub fn label(&self, lang: Language) -> &'static str {
+        let s = i18n::get_strings(lang);
+        match self {
+            HealthSubTab::Dashboard => s.health_dashboard,
+            HealthSubTab::Fix => s.health_fix,
+            HealthSubTab::Accounts => s.health_accounts,
         }
     }
 
@@ -66,39 +111,41 @@ impl HealthSubTab {
 
 // ── Health check severity ──
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Ordered: the worst of several results is the maximum
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Severity {
-    Ok,
-    Warning,
-    Critical,
-}
-
-// ── Individual health check ──
-
-#[derive(Debug, Clone)]
-pub struct HealthCheck {
-    pub name: String,
-    #[allow(dead_code)] // Populated by checks, reserved for detail view
-    pub description: String,
-    pub severity: Severity,
-    pub detail: String,
-    pub fix_command: Option<String>,
-    pub fix_description: Option<String>,
-    /// Weight for score calculation (0-20)
-    pub weight: u8,
-    /// Whether this check has been fixed in current session
-    pub fixed: bool,
+    Pass,
+    Warn,
+    Fail,
 }
 
 // ── Module state ──
 
 pub struct HealthState {
     pub sub_tab: HealthSubTab,
-    pub checks: Vec<HealthCheck>,
+    /// Finished checks in registry order
+    pub checks: Vec<CheckResult>,
     pub selected: usize,
     pub scanning: bool,
     pub scanned: bool,
-    scan_rx: Option<mpsc::Receiver<Vec<HealthCheck>>>,
+    /// Score of the scan recorded before this one (for the trend arrow)
+    pub previous_score: Option<u8>,
+    /// Select the worst finding once the running scan finishes
+    focus_worst_pending: bool,
+    /// (registry index, name) of checks still running
+    pending: Vec<(usize, String)>,
+    scan_total: usize,
+    scan_ctx: Option<Arc<CheckContext>>,
+    scan_rx: Option<mpsc::Receiver<(usize, Option<CheckResult>)>>,
+
+    // Accounts audit (filled by the same scan)
+    pub accounts: AccountAudit,
+    pub accounts_selected: usize,
+
+    /// Output of the failed flake evaluation check (same scan)
+    pub eval_error: Option<String>,
+    /// Set by `e`: the App opens this in the Error Translator
+    pub pending_error: Option<String>,
 
     // Fix action state
     pub fix_running: bool,
@@ -106,6 +153,10 @@ pub struct HealthState {
     fix_rx: Option<mpsc::Receiver<(usize, bool, String)>>,
 
     pub lang: Language,
+    pub config_path: Option<String>,
+    /// `nixosConfigurations` attribute from the config (None = host name)
+    pub nixos_host: Option<String>,
+    pub flake_watch: FlakeWatchConfig,
     pub flash_message: Option<FlashMessage>,
 }
 
@@ -117,11 +168,23 @@ impl HealthState {
             selected: 0,
             scanning: false,
             scanned: false,
+            previous_score: None,
+            focus_worst_pending: false,
+            pending: Vec::new(),
+            scan_total: 0,
+            scan_ctx: None,
             scan_rx: None,
+            accounts: AccountAudit::default(),
+            accounts_selected: 0,
+            eval_error: None,
+            pending_error: None,
             fix_running: false,
             fix_message: None,
             fix_rx: None,
             lang: Language::English,
+            config_path: None,
+            nixos_host: None,
+            flake_watch: FlakeWatchConfig::default(),
             flash_message: None,
         }
     }
@@ -133,49 +196,83 @@ impl HealthState {
         self.scanning = true;
         let (tx, rx) = mpsc::channel();
         self.scan_rx = Some(rx);
-        let lang = self.lang;
-
-        std::thread::spawn(move || {
-            let checks = run_health_checks(lang);
-            let _ = tx.send(checks);
+        let ctx = Arc::new(CheckContext {
+            lang: self.lang,
+            config_path: self.config_path.clone(),
+            nixos_host: self.nixos_host.clone(),
+            flake_watch: self.flake_watch.clone(),
+            accounts: Mutex::new(None),
+            eval_error: Mutex::new(None),
         });
+        self.scan_ctx = Some(Arc::clone(&ctx));
+
+        // One thread per check: a slow one (store verify) doesn't hold up the rest
+        let s = i18n::get_strings(self.lang);
+        let registry = checks::registry();
+        self.scan_total = registry.len();
+        self.pending = registry
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (i, c.name(s).to_string()))
+            .collect();
+        for (i, check) in registry.into_iter().enumerate() {
+            let tx = tx.clone();
+            let ctx = Arc::clone(&ctx);
+            std::thread::spawn(move || {
+                let _ = tx.send((i, checks::run_check(i, check.as_ref(), &ctx)));
+            });
+        }
     }
 
     pub fn rescan(&mut self) {
         self.scanned = false;
         self.scanning = false;
         self.scan_rx = None;
+        self.scan_ctx = None;
+        self.pending.clear();
         self.checks.clear();
+        self.accounts = AccountAudit::default();
+        self.accounts_selected = 0;
+        self.eval_error = None;
         self.ensure_scanned();
     }
 
     pub fn poll_scan(&mut self) {
         if let Some(rx) = &self.scan_rx {
-            match rx.try_recv() {
-                Ok(checks) => {
-                    self.checks = checks;
-                    self.scanning = false;
-                    self.scanned = true;
-                    self.scan_rx = None;
-                }
-                Err(mpsc::TryRecvError::Empty) => {}
-                Err(mpsc::TryRecvError::Disconnected) => {
-                    self.scanning = false;
-                    self.scanned = true;
-                    self.scan_rx = None;
+            let mut done = false;
+            loop {
+                match rx.try_recv() {
+                    Ok((order, result)) => {
+                        self.pending.retain(|(i, _)| *i != order);
+                        if let Some(result) = result {
+                            let pos = self.checks.partition_point(|c| c.order < order);
+                            self.checks.insert(pos, result);
+                        }
+                        done = self.pending.is_empty();
+                    }
+                    Err(mpsc::TryRecvError::Empty) => break,
+                    Err(mpsc::TryRecvError::Disconnected) => {
+                        done = true;
+                        break;
+                    }
                 }
             }
+            if done {
+                self.finish_scan();
+            }
         }
 
         // Poll fix result
         if let Some(rx) = &self.fix_rx {
             match rx.try_recv() {
-                Ok((idx, success, msg)) => {
+                Ok((order, success, msg)) => {
                     self.fix_running = false;
                     self.fix_rx = None;
-                    if success && idx < self.checks.len() {
-                        self.checks[idx].fixed = true;
-                        self.checks[idx].severity = Severity::Ok;
+                    if let Some(check) =
+                        self.checks.iter_mut().find(|c| success && c.order == order)
+                    {
+                        check.fixed = true;
+                        check.severity = Severity::Pass;
                     }
                     self.fix_message = Some(FlashMessage::new(msg, !success));
                 }
@@ -195,25 +292,72 @@ impl HealthState {
         }
     }
 
-    pub fn health_score(&self) -> u8 {
-        if self.checks.is_empty() {
-            return 100;
+    fn finish_scan(&mut self) {
+        self.scanning = false;
+        self.scanned = true;
+        self.scan_rx = None;
+        self.pending.clear();
+        if let Some(ctx) = self.scan_ctx.take() {
+            if let Some(audit) = ctx.accounts.lock().ok().and_then(|mut a| a.take()) {
+                self.accounts = audit;
+            }
+            self.eval_error = ctx.eval_error.lock().ok().and_then(|mut e| e.take());
+        }
+        self.previous_score = trend::record(self.health_score());
+        if std::mem::take(&mut self.focus_worst_pending) {
+            self.focus_worst();
         }
-        let total_weight: u16 = self.checks.iter().map(|c| c.weight as u16).sum();
-        if total_weight == 0 {
-            return 100;
+    }
+
+    pub fn health_score(&self) -> u8 {
+        checks::score(&self.checks)
+    }
+
+    /// None until the scan is done or without an earlier recorded score
+    pub fn trend(&self) -> Option<Trend> {
+        if !self.scanned {
+            return None;
         }
-        let lost: u16 = self
+        Trend::between(self.previous_score, self.health_score())
+    }
+
+    /// (finished, total) of the running scan
+    pub fn scan_progress(&self) -> (usize, usize) {
+        (self.scan_total - self.pending.len(), self.scan_total)
+    }
+
+    /// Failing and warning checks, worst first (severity, then weight)
+    pub fn top_issues(&self, n: usize) -> Vec<&CheckResult> {
+        let mut issues: Vec<&CheckResult> = self
             .checks
             .iter()
-            .map(|c| match c.severity {
-                Severity::Ok => 0,
-                Severity::Warning => (c.weight as u16) / 2,
-                Severity::Critical => c.weight as u16,
-            })
-            .sum();
-        let score = 100u16.saturating_sub((lost * 100) / total_weight);
-        score as u8
+            .filter(|c| c.severity != Severity::Pass)
+            .collect();
+        issues.sort_by(|a, b| {
+            b.severity
+                .cmp(&a.severity)
+                .then(b.weight.cmp(&a.weight))
+                .then(a.order.cmp(&b.order))
+        });
+        issues.truncate(n);
+        issues
+    }
+
+    /// Dashboard with the worst finding selected (deferred while scanning)
+    pub fn focus_worst(&mut self) {
+        self.sub_tab = HealthSubTab::Dashboard;
+        if !self.scanned {
+            self.focus_worst_pending = true;
+            return;
+        }
+        let worst = self.top_issues(1).first().map(|c| c.order);
+        if let Some(order) = worst {
+            self.selected = self
+                .checks
+                .iter()
+                .position(|c| c.order == order)
+                .unwrap_or(0);
+        }
     }
 
     fn start_fix(&mut self) {
@@ -221,14 +365,24 @@ impl HealthState {
             return;
         }
         let check = &self.checks[self.selected];
-        if check.severity == Severity::Ok || check.fix_command.is_none() {
+        if check.severity == Severity::Pass {
             return;
         }
-
-        let Some(cmd) = check.fix_command.clone() else {
-            return;
+        let s = i18n::get_strings(self.lang);
+
+        let cmd = match &check.fix {
+            Some(FixAction::Run(cmd)) => cmd.clone(),
+            // Config changes are the user's to make; point at the snippet
+            Some(FixAction::Snippet(_)) => {
+                self.fix_message = Some(FlashMessage::new(
+                    s.health_fix_snippet_flash.to_string(),
+                    false,
+                ));
+                return;
+            }
+            None => return,
         };
-        let idx = self.selected;
+        let idx = check.order;
         self.fix_running = true;
 
         let (tx, rx) = mpsc::channel();
@@ -259,6 +413,49 @@ impl HealthState {
         });
     }
 
+    /// Actions offered in the command palette
+    /// Running background work, for the jobs overlay
+    pub fn jobs(&self, s: &i18n::Strings) -> Vec<Job> {
+        let mut jobs = Vec::new();
+        if self.scanning {
+            let (done, total) = self.scan_progress();
+            let running: Vec<&str> = self.pending.iter().map(|(_, n)| n.as_str()).collect();
+            jobs.push(
+                Job::new("scan", s.job_health_scan, true)
+                    .detail(running.join(", "))
+                    .progress(done, total),
+            );
+        }
+        if self.fix_running {
+            jobs.push(Job::new("fix", s.job_health_fix, false));
+        }
+        jobs
+    }
+
+    /// Stop waiting for a job from `jobs()`; its result is discarded.
+    /// A cancelled scan keeps the checks that finished and records no score.
+    pub fn cancel_job(&mut self, id: &str) {
+        if id == "scan" {
+            self.scan_rx = None;
+            self.scan_ctx = None;
+            self.pending.clear();
+            self.scanning = false;
+            self.scanned = true;
+        }
+    }
+
+    pub fn palette_commands(&self, s: &i18n::Strings) -> Vec<PaletteCommand> {
+        vec![
+            PaletteCommand::new(s.pal_hl_rescan, None, vec![KeyCode::Char('r')]),
+            PaletteCommand::new(s.pal_hl_fix, Some(HealthSubTab::Fix.index()), vec![]),
+            PaletteCommand::new(
+                s.pal_hl_accounts,
+                Some(HealthSubTab::Accounts.index()),
+                vec![],
+            ),
+        ]
+    }
+
     pub fn handle_key(&mut self, key: KeyEvent) -> Result<bool> {
         match key.code {
             KeyCode::Char('[') => {
@@ -276,6 +473,10 @@ impl HealthState {
             _ => {}
         }
 
+        if self.sub_tab == HealthSubTab::Accounts {
+            return self.handle_accounts_key(key);
+        }
+
         match key.code {
             KeyCode::Char('j') | KeyCode::Down => {
                 if !self.checks.is_empty() {
@@ -292,380 +493,88 @@ impl HealthState {
                     self.start_fix();
                 }
             }
+            KeyCode::Char('e') => {
+                let s = i18n::get_strings(self.lang);
+                let on_eval = self
+                    .checks
+                    .get(self.selected)
+                    .is_some_and(|c| c.id == "flake-eval");
+                match &self.eval_error {
+                    Some(text) if on_eval => self.pending_error = Some(text.clone()),
+                    _ => {
+                        self.flash_message =
+                            Some(FlashMessage::new(s.health_no_eval_error.into(), true))
+                    }
+                }
+            }
             _ => return Ok(false),
         }
         Ok(true)
     }
-}
-
-// ── Health checks implementation ──
-
-fn run_health_checks(lang: Language) -> Vec<HealthCheck> {
-    let s = crate::i18n::get_strings(lang);
-    let mut checks = Vec::new();
-
-    let mut c = check_old_generations(lang);
-    c.name = s.health_name_old_gens.to_string();
-    checks.push(c);
 
-    let mut c = check_store_size(lang);
-    c.name = s.health_name_store_size.to_string();
-    checks.push(c);
-
-    let mut c = check_disk_usage(lang);
-    c.name = s.health_name_disk_usage.to_string();
-    checks.push(c);
-
-    let mut c = check_channel_freshness(lang);
-    c.name = s.health_name_freshness.to_string();
-    checks.push(c);
-
-    let mut c = check_duplicate_packages(lang);
-    c.name = s.health_name_duplicates.to_string();
-    checks.push(c);
-
-    checks
-}
-
-fn check_old_generations(lang: Language) -> HealthCheck {
-    use std::process::Command;
-    let s = crate::i18n::get_strings(lang);
-
-    let output = Command::new("sh")
-        .args([
-            "-c",
-            "nixos-rebuild list-generations 2>/dev/null | head -50",
-        ])
-        .output();
-
-    let mut old_count = 0u32;
-    let mut total_count = 0u32;
-
-    if let Ok(o) = output {
-        let stdout = String::from_utf8_lossy(&o.stdout);
-        let now = chrono_now_days();
-
-        for line in stdout.lines() {
-            let line = line.trim();
-            if line.is_empty() || line.starts_with("Generation") {
-                continue;
-            }
-            total_count += 1;
-
-            // Try to parse date from generation line
-            // Format varies: "  42   2024-01-15 10:30:00" or similar
-            if let Some(days) = extract_generation_age_days(line, now) {
-                if days > 30 {
-                    old_count += 1;
+    fn handle_accounts_key(&mut self, key: KeyEvent) -> Result<bool> {
+        let len = self.accounts.findings.len();
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                if len > 0 {
+                    self.accounts_selected = (self.accounts_selected + 1).min(len - 1);
                 }
             }
-        }
-    }
-
-    // Fallback: count profiles
-    if total_count == 0 {
-        let profile_path = std::path::Path::new("/nix/var/nix/profiles");
-        if let Ok(entries) = std::fs::read_dir(profile_path) {
-            for entry in entries.flatten() {
-                let name = entry.file_name().to_string_lossy().to_string();
-                if name.starts_with("system-") && name.ends_with("-link") {
-                    total_count += 1;
-                    // Check age via mtime
-                    if let Ok(meta) = entry.metadata() {
-                        if let Ok(modified) = meta.modified() {
-                            if let Ok(elapsed) = modified.elapsed() {
-                                if elapsed.as_secs() > 30 * 86400 {
-                                    old_count += 1;
-                                }
-                            }
-                        }
-                    }
-                }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.accounts_selected = self.accounts_selected.saturating_sub(1);
             }
+            KeyCode::Char('g') => self.accounts_selected = 0,
+            KeyCode::Char('G') => self.accounts_selected = len.saturating_sub(1),
+            _ => return Ok(false),
         }
+        Ok(true)
     }
+}
 
-    let (severity, detail) = if old_count == 0 {
-        (
-            Severity::Ok,
-            s.health_detail_gens_all_recent
-                .replace("{}", &total_count.to_string()),
-        )
-    } else if old_count > 10 {
-        (
-            Severity::Critical,
-            s.health_detail_gens_older
-                .replace("{}", &old_count.to_string()),
-        )
-    } else {
-        (
-            Severity::Warning,
-            s.health_detail_gens_older
-                .replace("{}", &old_count.to_string()),
-        )
-    };
-
-    let fix_cmd = if old_count > 0 {
-        Some("sudo nix-collect-garbage --delete-older-than 30d".to_string())
-    } else {
-        None
-    };
+// ── Rendering ──
 
-    HealthCheck {
-        name: s.health_name_old_gens.to_string(),
-        description: s.health_desc_old_gens.to_string(),
-        severity,
-        detail,
-        fix_command: fix_cmd,
-        fix_description: Some(s.health_fix_old_gens.to_string()),
-        weight: 15,
-        fixed: false,
+impl Module for HealthState {
+    fn handle_key(&mut self, key: KeyEvent, _lang: Language) -> Result<()> {
+        self.handle_key(key).map(|_| ())
     }
-}
 
-fn check_store_size(lang: Language) -> HealthCheck {
-    let s = crate::i18n::get_strings(lang);
-    let store_path = std::path::Path::new("/nix/store");
-    let mut size_gb = 0.0f64;
-
-    // Fast method: use df on /nix/store
-    if let Ok(output) = std::process::Command::new("df")
-        .args(["-B1", "/nix/store"])
-        .output()
-    {
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        if let Some(line) = stdout.lines().nth(1) {
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            if parts.len() >= 3 {
-                if let Ok(used) = parts[2].parse::<u64>() {
-                    size_gb = used as f64 / 1_073_741_824.0;
-                }
-            }
-        }
+    fn render(&mut self, frame: &mut Frame, ctx: &RenderCtx, area: Rect) {
+        render(frame, self, ctx.theme, ctx.lang, area);
     }
 
-    // Also count store paths
-    let path_count = if store_path.exists() {
-        std::fs::read_dir(store_path)
-            .map(|entries| entries.count())
-            .unwrap_or(0)
-    } else {
-        0
-    };
-
-    let size_str = format!("{:.1}", size_gb);
-    let (severity, detail) = if size_gb < 20.0 {
-        (
-            Severity::Ok,
-            s.health_detail_store_ok
-                .replacen("{}", &size_str, 1)
-                .replacen("{}", &path_count.to_string(), 1),
-        )
-    } else if size_gb < 50.0 {
-        (
-            Severity::Warning,
-            s.health_detail_store_warn.replace("{}", &size_str),
-        )
-    } else {
-        (
-            Severity::Critical,
-            s.health_detail_store_crit.replace("{}", &size_str),
-        )
-    };
-
-    HealthCheck {
-        name: s.health_name_store_size.to_string(),
-        description: s.health_desc_store_size.to_string(),
-        severity,
-        detail,
-        fix_command: Some("sudo nix-collect-garbage -d".to_string()),
-        fix_description: Some(s.health_fix_store_size.to_string()),
-        weight: 20,
-        fixed: false,
+    fn activate(&mut self) {
+        self.ensure_scanned();
     }
-}
 
-fn check_disk_usage(lang: Language) -> HealthCheck {
-    let s = crate::i18n::get_strings(lang);
-    let mut usage_pct = 0u8;
-
-    if let Ok(output) = std::process::Command::new("df")
-        .args(["--output=pcent", "/"])
-        .output()
-    {
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        if let Some(line) = stdout.lines().nth(1) {
-            if let Ok(pct) = line.trim().trim_end_matches('%').parse::<u8>() {
-                usage_pct = pct;
-            }
-        }
+    fn poll(&mut self, _active: bool) {
+        self.poll_scan();
     }
 
-    let pct_str = usage_pct.to_string();
-    let (severity, detail) = if usage_pct < 80 {
-        (
-            Severity::Ok,
-            s.health_detail_disk_ok.replace("{}", &pct_str),
-        )
-    } else if usage_pct < 90 {
-        (
-            Severity::Warning,
-            s.health_detail_disk_warn.replace("{}", &pct_str),
-        )
-    } else {
-        (
-            Severity::Critical,
-            s.health_detail_disk_crit.replace("{}", &pct_str),
-        )
-    };
-
-    HealthCheck {
-        name: s.health_name_disk_usage.to_string(),
-        description: s.health_desc_disk.to_string(),
-        severity,
-        detail,
-        fix_command: Some("sudo nix-collect-garbage --delete-older-than 7d".to_string()),
-        fix_description: Some(s.health_fix_disk.to_string()),
-        weight: 25,
-        fixed: false,
+    fn commands(&self, s: &i18n::Strings) -> Vec<PaletteCommand> {
+        self.palette_commands(s)
     }
-}
-
-fn check_channel_freshness(lang: Language) -> HealthCheck {
-    let s = crate::i18n::get_strings(lang);
-    // Check when the current system was last built
-    let system_path = std::path::Path::new("/run/current-system");
-    let mut days_old = 0u64;
 
-    if let Ok(meta) = std::fs::symlink_metadata(system_path) {
-        if let Ok(modified) = meta.modified() {
-            if let Ok(elapsed) = modified.elapsed() {
-                days_old = elapsed.as_secs() / 86400;
-            }
-        }
+    fn jobs(&self, s: &i18n::Strings) -> Vec<Job> {
+        self.jobs(s)
     }
 
-    let days_str = days_old.to_string();
-    let (severity, detail) = if days_old <= 14 {
-        (
-            Severity::Ok,
-            s.health_detail_fresh_ok.replace("{}", &days_str),
-        )
-    } else if days_old <= 30 {
-        (
-            Severity::Warning,
-            s.health_detail_fresh_warn.replace("{}", &days_str),
-        )
-    } else {
-        (
-            Severity::Critical,
-            s.health_detail_fresh_crit.replace("{}", &days_str),
-        )
-    };
-
-    // Detect if flakes or channels for fix command
-    let uses_flakes = crate::nix::detect::detect_system(None)
-        .map(|s| s.uses_flakes)
-        .unwrap_or(false);
-
-    let fix_cmd = if uses_flakes {
-        "cd /etc/nixos && sudo nix flake update && sudo nixos-rebuild switch".to_string()
-    } else {
-        "sudo nix-channel --update && sudo nixos-rebuild switch".to_string()
-    };
-
-    HealthCheck {
-        name: s.health_name_freshness.to_string(),
-        description: s.health_desc_freshness.to_string(),
-        severity,
-        detail,
-        fix_command: Some(fix_cmd),
-        fix_description: Some(s.health_fix_freshness.to_string()),
-        weight: 20,
-        fixed: false,
+    fn cancel_job(&mut self, id: &str) {
+        self.cancel_job(id)
     }
-}
-
-fn check_duplicate_packages(lang: Language) -> HealthCheck {
-    use std::collections::HashMap;
-    let s = crate::i18n::get_strings(lang);
-
-    let system_path = std::path::Path::new("/run/current-system");
-    let mut duplicates = 0u32;
-    let mut total = 0u32;
 
-    if system_path.exists() {
-        if let Ok(pkgs) = crate::nix::packages::get_packages(system_path) {
-            total = pkgs.len() as u32;
-            let mut name_counts: HashMap<String, u32> = HashMap::new();
-            for pkg in &pkgs {
-                *name_counts.entry(pkg.name.clone()).or_insert(0) += 1;
-            }
-            duplicates = name_counts.values().filter(|&&v| v > 1).count() as u32;
-        }
+    fn sub_tab(&self) -> Option<(usize, usize)> {
+        Some((self.sub_tab.index(), HealthSubTab::all().len()))
     }
 
-    let (severity, detail) = if duplicates == 0 {
-        (
-            Severity::Ok,
-            s.health_detail_dupes_ok.replace("{}", &total.to_string()),
-        )
-    } else if duplicates < 5 {
-        (
-            Severity::Warning,
-            s.health_detail_dupes_warn
-                .replace("{}", &duplicates.to_string()),
-        )
-    } else {
-        (
-            Severity::Critical,
-            s.health_detail_dupes_crit
-                .replace("{}", &duplicates.to_string()),
-        )
-    };
-
-    HealthCheck {
-        name: s.health_name_duplicates.to_string(),
-        description: s.health_desc_duplicates.to_string(),
-        severity,
-        detail,
-        fix_command: None, // Can't auto-fix this easily
-        fix_description: Some(s.health_fix_duplicates.to_string()),
-        weight: 20,
-        fixed: false,
+    fn sub_tab_label(&self, lang: Language) -> Option<&'static str> {
+        Some(self.sub_tab.label(lang))
     }
-}
-
-// ── Time helpers ──
-
-fn chrono_now_days() -> u64 {
-    std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .map(|d| d.as_secs() / 86400)
-        .unwrap_or(0)
-}
 
-fn extract_generation_age_days(line: &str, now_days: u64) -> Option<u64> {
-    // Try to find a date pattern YYYY-MM-DD in the line
-    for word in line.split_whitespace() {
-        if word.len() == 10 && word.chars().nth(4) == Some('-') && word.chars().nth(7) == Some('-')
-        {
-            let parts: Vec<&str> = word.split('-').collect();
-            if parts.len() == 3 {
-                let y: u64 = parts[0].parse().ok()?;
-                let m: u64 = parts[1].parse().ok()?;
-                let d: u64 = parts[2].parse().ok()?;
-                // Rough day count since epoch
-                let gen_days = (y - 1970) * 365 + (y - 1969) / 4 + (m - 1) * 30 + d;
-                return Some(now_days.saturating_sub(gen_days));
-            }
-        }
+    fn flash_message(&mut self) -> &mut Option<FlashMessage> {
+        &mut self.flash_message
     }
-    None
 }
 
-// ── Rendering ──
-
 pub fn render(frame: &mut Frame, state: &HealthState, theme: &Theme, lang: Language, area: Rect) {
     let s = i18n::get_strings(lang);
 
@@ -691,14 +600,11 @@ pub fn render(frame: &mut Frame, state: &HealthState, theme: &Theme, lang: Langu
     .split(inner);
 
     // Render tab bar
-    let tab_titles: Vec<Line> = vec![
-        Line::from(format!(" {} ", s.health_dashboard)),
-        Line::from(format!(" {} ", s.health_fix)),
-    ];
-    let tab_idx = match state.sub_tab {
-        HealthSubTab::Dashboard => 0,
-        HealthSubTab::Fix => 1,
-    };
+    let tab_titles: Vec<Line> = HealthSubTab::all()
+        .iter()
+        .map(|t| Line::from(format!(" {} ", t.label(lang))))
+        .collect();
+    let tab_idx = state.sub_tab.index();
     let tabs = Tabs::new(tab_titles)
         .select(tab_idx)
         .style(theme.tab_inactive())
@@ -707,7 +613,8 @@ pub fn render(frame: &mut Frame, state: &HealthState, theme: &Theme, lang: Langu
     let tabs_area = widgets::render_sub_tab_nav(frame, theme, chunks[0]);
     frame.render_widget(tabs, tabs_area);
 
-    if state.scanning {
+    // Checks show up as they finish; the accounts audit arrives with its check
+    if state.scanning && state.sub_tab == HealthSubTab::Accounts {
         let lines = vec![
             Line::raw(""),
             Line::raw(""),
@@ -723,9 +630,35 @@ pub fn render(frame: &mut Frame, state: &HealthState, theme: &Theme, lang: Langu
     match state.sub_tab {
         HealthSubTab::Dashboard => render_dashboard(frame, state, theme, lang, chunks[1]),
         HealthSubTab::Fix => render_fix(frame, state, theme, lang, chunks[1]),
+        HealthSubTab::Accounts => render_accounts(frame, state, theme, lang, chunks[1]),
+    }
+
+    if let Some(msg) = &state.flash_message {
+        widgets::render_flash_message(frame, &msg.text, msg.is_error, theme, area);
     }
 }
 
+/// Color and word for a health score, shared with the start screen
+pub fn score_rating(score: u8, theme: &Theme, s: &i18n::Strings) -> (Color, &'static str) {
+    let color = if score >= 90 {
+        theme.success
+    } else if score >= 60 {
+        theme.warning
+    } else {
+        theme.error
+    };
+    let label = if score >= 90 {
+        s.health_excellent
+    } else if score >= 75 {
+        s.health_good
+    } else if score >= 60 {
+        s.health_fair
+    } else {
+        s.health_poor
+    };
+    (color, label)
+}
+
 fn render_dashboard(
     frame: &mut Frame,
     state: &HealthState,
@@ -743,23 +676,7 @@ fn render_dashboard(
     .split(area);
 
     // Score display
-    let score_color = if score >= 90 {
-        theme.success
-    } else if score >= 60 {
-        theme.warning
-    } else {
-        theme.error
-    };
-
-    let score_label = if score >= 90 {
-        s.health_excellent
-    } else if score >= 75 {
-        s.health_good
-    } else if score >= 60 {
-        s.health_fair
-    } else {
-        s.health_poor
-    };
+    let (score_color, score_label) = score_rating(score, theme, s);
 
     // Score bar visualization
     let bar_width = (area.width as usize).saturating_sub(6).min(40);
@@ -784,6 +701,30 @@ fn render_dashboard(
                 format!("  — {}", score_label),
                 Style::default().fg(score_color),
             ),
+            Span::styled(
+                state
+                    .trend()
+                    .map(|t| format!("   {}", t.label()))
+                    .unwrap_or_default(),
+                Style::default().fg(theme.fg_dim),
+            ),
+            Span::styled(
+                if state.scanning {
+                    format!(
+                        "   ⏳ {}",
+                        s.health_scan_progress
+                            .replacen(
+                                "{}",
+                                &(state.scan_total - state.pending.len()).to_string(),
+                                1
+                            )
+                            .replacen("{}", &state.scan_total.to_string(), 1)
+                    )
+                } else {
+                    String::new()
+                },
+                Style::default().fg(theme.accent),
+            ),
         ]),
         Line::from(vec![
             Span::raw("  "),
@@ -799,7 +740,7 @@ fn render_dashboard(
     );
 
     // Check list
-    render_check_list(frame, state, theme, chunks[1], false);
+    render_check_list(frame, state, theme, lang, chunks[1], false);
 }
 
 fn render_fix(frame: &mut Frame, state: &HealthState, theme: &Theme, lang: Language, area: Rect) {
@@ -808,11 +749,12 @@ fn render_fix(frame: &mut Frame, state: &HealthState, theme: &Theme, lang: Langu
     let has_fixable = state
         .checks
         .iter()
-        .any(|c| c.severity != Severity::Ok && c.fix_command.is_some());
+        .any(|c| c.severity != Severity::Pass && c.fix.is_some());
 
     let chunks = Layout::vertical([
         Constraint::Length(2), // Fix header
         Constraint::Min(3),    // Check list with fix info
+        Constraint::Length(7), // Selected check: why + fix
         Constraint::Length(2), // Fix message
     ])
     .split(area);
@@ -831,7 +773,10 @@ fn render_fix(frame: &mut Frame, state: &HealthState, theme: &Theme, lang: Langu
     );
 
     // Check list with fix details
-    render_check_list(frame, state, theme, chunks[1], true);
+    render_check_list(frame, state, theme, lang, chunks[1], true);
+    if let Some(check) = state.checks.get(state.selected) {
+        render_fix_detail(frame, check, theme, lang, chunks[2]);
+    }
 
     // Fix message
     if let Some(msg) = &state.fix_message {
@@ -846,7 +791,7 @@ fn render_fix(frame: &mut Frame, state: &HealthState, theme: &Theme, lang: Langu
                 Style::default().fg(color),
             ))
             .style(theme.block_style()),
-            chunks[2],
+            chunks[3],
         );
     } else if state.fix_running {
         frame.render_widget(
@@ -855,23 +800,207 @@ fn render_fix(frame: &mut Frame, state: &HealthState, theme: &Theme, lang: Langu
                 Style::default().fg(theme.accent),
             ))
             .style(theme.block_style()),
-            chunks[2],
+            chunks[3],
         );
     }
 }
 
-fn render_check_list(
+/// Why the selected check matters and what its fix does
+fn render_fix_detail(
+    frame: &mut Frame,
+    check: &CheckResult,
+    theme: &Theme,
+    lang: Language,
+    area: Rect,
+) {
+    let s = i18n::get_strings(lang);
+    let block = Block::default()
+        .title(format!(" {} · {} ", check.category.label(s), check.name))
+        .title_style(Style::default().fg(theme.accent))
+        .borders(Borders::TOP)
+        .border_style(theme.border())
+        .style(theme.block_style());
+
+    let mut lines = vec![Line::from(vec![
+        Span::styled(
+            format!("  {} ", s.health_why),
+            Style::default().fg(theme.fg_dim),
+        ),
+        Span::styled(check.description.clone(), theme.text()),
+    ])];
+    if check.severity != Severity::Pass {
+        match &check.fix {
+            Some(FixAction::Run(cmd)) => lines.push(Line::from(vec![
+                Span::styled("  [Enter] ", Style::default().fg(theme.fg_dim)),
+                Span::styled(format!("$ {}", cmd), Style::default().fg(theme.success)),
+            ])),
+            Some(FixAction::Snippet(snippet)) => {
+                lines.push(Line::styled(
+                    format!("  {}", s.health_fix_snippet_hint),
+                    Style::default().fg(theme.fg_dim),
+                ));
+                lines.extend(snippet.lines().map(|l| {
+                    let style = if l.starts_with('#') {
+                        Style::default().fg(theme.fg_dim)
+                    } else {
+                        Style::default().fg(theme.success)
+                    };
+                    Line::styled(format!("    {}", l), style)
+                }));
+            }
+            None => {}
+        }
+    }
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn render_accounts(
     frame: &mut Frame,
     state: &HealthState,
     theme: &Theme,
+    lang: Language,
     area: Rect,
-    show_fix_info: bool,
 ) {
-    if state.checks.is_empty() {
+    let s = i18n::get_strings(lang);
+    let audit = &state.accounts;
+
+    let chunks = Layout::vertical([
+        Constraint::Length(2),      // Summary
+        Constraint::Percentage(50), // Findings
+        Constraint::Min(4),         // Remediation
+    ])
+    .split(area);
+
+    // Summary
+    let summary = match &audit.source {
+        None => Line::styled(
+            format!("  {}", s.health_acc_no_source),
+            Style::default().fg(theme.fg_dim),
+        ),
+        Some(_) => {
+            let mutable = match audit.mutable_users {
+                Some(true) => "users.mutableUsers = true",
+                Some(false) => "users.mutableUsers = false",
+                None => "users.mutableUsers = ?",
+            };
+            Line::from(vec![
+                Span::styled(
+                    format!(
+                        "  {} ",
+                        s.health_acc_summary
+                            .replacen("{}", &audit.users_checked.to_string(), 1)
+                            .replacen("{}", &audit.keys_checked.to_string(), 1)
+                    ),
+                    Style::default().fg(theme.fg_dim),
+                ),
+                Span::styled(
+                    format!(" {}", mutable),
+                    Style::default().fg(theme.accent_dim),
+                ),
+            ])
+        }
+    };
+    frame.render_widget(
+        Paragraph::new(summary).style(theme.block_style()),
+        chunks[0],
+    );
+
+    if audit.source.is_none() {
+        return;
+    }
+
+    if audit.findings.is_empty() {
+        frame.render_widget(
+            Paragraph::new(Line::styled(
+                format!("  ✓ {}", s.health_acc_clean),
+                Style::default().fg(theme.success),
+            ))
+            .style(theme.block_style()),
+            chunks[1],
+        );
         return;
     }
 
-    let items: Vec<ListItem> = state
+    // Findings list
+    let visible = chunks[1].height as usize;
+    let scroll = state
+        .accounts_selected
+        .saturating_sub(visible.saturating_sub(1));
+    let items: Vec<ListItem> = audit
+        .findings
+        .iter()
+        .enumerate()
+        .skip(scroll)
+        .take(visible)
+        .map(|(i, f)| {
+            let is_selected = i == state.accounts_selected;
+            let (icon, color) = match f.severity {
+                Severity::Pass => ("ℹ", theme.fg_dim),
+                Severity::Warn => ("⚠", theme.warning),
+                Severity::Fail => ("✗", theme.error),
+            };
+            let style = if is_selected {
+                theme.selected()
+            } else {
+                theme.text()
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("  {} ", icon), Style::default().fg(color)),
+                Span::styled(
+                    format!("{:<28}", f.subject),
+                    if is_selected {
+                        style.add_modifier(Modifier::BOLD)
+                    } else {
+                        style
+                    },
+                ),
+                Span::styled(
+                    f.detail.clone(),
+                    if is_selected {
+                        style
+                    } else {
+                        Style::default().fg(theme.fg_dim)
+                    },
+                ),
+            ]))
+        })
+        .collect();
+    frame.render_widget(List::new(items).style(theme.block_style()), chunks[1]);
+
+    // Remediation snippet for the selected finding
+    if let Some(f) = audit.findings.get(state.accounts_selected) {
+        let block = Block::default()
+            .title(format!(" {} ", s.health_acc_remediation))
+            .title_style(Style::default().fg(theme.accent))
+            .borders(Borders::TOP)
+            .border_style(theme.border())
+            .style(theme.block_style());
+        let lines: Vec<Line> = f
+            .remediation
+            .lines()
+            .map(|l| {
+                let style = if l.starts_with('#') {
+                    Style::default().fg(theme.fg_dim)
+                } else {
+                    Style::default().fg(theme.success)
+                };
+                Line::styled(format!("  {}", l), style)
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(lines).block(block), chunks[2]);
+    }
+}
+
+fn render_check_list(
+    frame: &mut Frame,
+    state: &HealthState,
+    theme: &Theme,
+    lang: Language,
+    area: Rect,
+    show_fix_info: bool,
+) {
+    let s = i18n::get_strings(lang);
+    let mut items: Vec<ListItem> = state
         .checks
         .iter()
         .enumerate()
@@ -879,14 +1008,14 @@ fn render_check_list(
             let is_selected = i == state.selected;
 
             let icon = match check.severity {
-                Severity::Ok => "✓",
-                Severity::Warning => "⚠",
-                Severity::Critical => "✗",
+                Severity::Pass => "✓",
+                Severity::Warn => "⚠",
+                Severity::Fail => "✗",
             };
             let icon_color = match check.severity {
-                Severity::Ok => theme.success,
-                Severity::Warning => theme.warning,
-                Severity::Critical => theme.error,
+                Severity::Pass => theme.success,
+                Severity::Warn => theme.warning,
+                Severity::Fail => theme.error,
             };
 
             let name_style = if is_selected {
@@ -915,7 +1044,7 @@ fn render_check_list(
                 ),
             ];
 
-            if show_fix_info && check.severity != Severity::Ok {
+            if show_fix_info && check.severity != Severity::Pass {
                 if let Some(fix_desc) = &check.fix_description {
                     spans.push(Span::styled(
                         format!("  → {}", fix_desc),
@@ -928,6 +1057,15 @@ fn render_check_list(
         })
         .collect();
 
+    // Still running, below the finished ones
+    items.extend(state.pending.iter().map(|(_, name)| {
+        ListItem::new(Line::from(vec![
+            Span::styled("  ⏳ ", Style::default().fg(theme.accent)),
+            Span::styled(format!("{:<22}", name), Style::default().fg(theme.fg_dim)),
+            Span::styled(s.health_pending, Style::default().fg(theme.fg_dim)),
+        ]))
+    }));
+
     let list = List::new(items).style(theme.block_style());
     frame.render_widget(list, area);
 }
@@ -0,0 +1,304 @@
+//! nix.conf checks.
+//!
+//! Settings that NixOS writes to /etc/nix/nix.conf from `nix.settings`:
+//! the experimental features a flake system needs, build parallelism left
+//! at a single job or core, and `trusted-users` that hand root-equivalent
+//! access to more than the admins. Store optimisation has its own check.
+//! Each finding comes with the `nix.settings.*` line that fixes it.
+
+use super::checks::{Category, CheckContext, Finding, FixAction, HealthCheck};
+use super::signatures::nix_config;
+use super::Severity;
+use crate::i18n::{self, Strings};
+use std::sync::Arc;
+
+/// Appended to the registry, before the hardware checks
+pub fn checks() -> Vec<Arc<dyn HealthCheck>> {
+    vec![
+        Arc::new(ExperimentalFeatures),
+        Arc::new(BuildParallelism),
+        Arc::new(TrustedUsers),
+    ]
+}
+
+/// Experimental features as configured, applied in the order nix reads
+/// them. `nix config show` can't tell: nixmate runs it with
+/// `--extra-experimental-features nix-command`, which shows up there.
+fn configured_features() -> Vec<String> {
+    let mut sources: Vec<String> = Vec::new();
+    sources.extend(std::fs::read_to_string("/etc/nix/nix.conf").ok());
+    if let Some(dir) = dirs::config_dir() {
+        sources.extend(std::fs::read_to_string(dir.join("nix").join("nix.conf")).ok());
+    }
+    sources.extend(std::env::var("NIX_CONFIG").ok());
+
+    let mut features = Vec::new();
+    for conf in &sources {
+        apply_features(conf, &mut features);
+    }
+    features
+}
+
+/// `experimental-features` replaces the list, `extra-…` adds to it
+fn apply_features(conf: &str, features: &mut Vec<String>) {
+    for line in conf.lines() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let values = value.split_whitespace().map(String::from);
+        match key.trim() {
+            "experimental-features" => *features = values.collect(),
+            "extra-experimental-features" => features.extend(values),
+            _ => {}
+        }
+    }
+}
+
+struct ExperimentalFeatures;
+
+impl HealthCheck for ExperimentalFeatures {
+    fn id(&self) -> &'static str {
+        "experimental-features"
+    }
+    fn name(&self, s: &Strings) -> &'static str {
+        s.health_name_features
+    }
+    fn description(&self, s: &Strings) -> &'static str {
+        s.health_desc_features
+    }
+    fn category(&self) -> Category {
+        Category::System
+    }
+    fn weight(&self) -> u8 {
+        10
+    }
+
+    fn run(&self, ctx: &CheckContext) -> Option<Finding> {
+        if !crate::nix::detect::detect_flakes(ctx.config_path.as_deref()) {
+            return None;
+        }
+        let s = i18n::get_strings(ctx.lang);
+        let features = configured_features();
+        let missing: Vec<&str> = ["nix-command", "flakes"]
+            .into_iter()
+            .filter(|f| !features.iter().any(|have| have == f))
+            .collect();
+        if missing.is_empty() {
+            return Some(Finding {
+                severity: Severity::Pass,
+                detail: s.health_detail_features_ok.to_string(),
+                fix: None,
+                fix_description: None,
+            });
+        }
+        Some(Finding {
+            severity: Severity::Warn,
+            detail: s
+                .health_detail_features_missing
+                .replace("{}", &missing.join(", ")),
+            fix: Some(FixAction::Snippet(
+                "nix.settings.experimental-features = [ \"nix-command\" \"flakes\" ];".to_string(),
+            )),
+            fix_description: Some(s.health_fix_features.to_string()),
+        })
+    }
+}
+
+/// Settings that leave CPUs idle during builds, as (setting, value)
+fn parallelism_problems(max_jobs: &str, cores: &str, cpus: usize) -> Vec<(&'static str, String)> {
+    // One CPU or two: a single job or core costs little
+    if cpus < 4 {
+        return Vec::new();
+    }
+    let mut problems = Vec::new();
+    if max_jobs.trim() == "1" {
+        problems.push(("max-jobs", max_jobs.trim().to_string()));
+    }
+    if cores.trim() == "1" {
+        problems.push(("cores", cores.trim().to_string()));
+    }
+    problems
+}
+
+struct BuildParallelism;
+
+impl HealthCheck for BuildParallelism {
+    fn id(&self) -> &'static str {
+        "build-parallelism"
+    }
+    fn name(&self, s: &Strings) -> &'static str {
+        s.health_name_parallelism
+    }
+    fn description(&self, s: &Strings) -> &'static str {
+        s.health_desc_parallelism
+    }
+    fn category(&self) -> Category {
+        Category::System
+    }
+    fn weight(&self) -> u8 {
+        5
+    }
+
+    fn run(&self, ctx: &CheckContext) -> Option<Finding> {
+        let config = nix_config()?;
+        let cpus = std::thread::available_parallelism().ok()?.get();
+        let s = i18n::get_strings(ctx.lang);
+        let max_jobs = config.get("max-jobs").map_or("", String::as_str);
+        let cores = config.get("cores").map_or("", String::as_str);
+        let problems = parallelism_problems(max_jobs, cores, cpus);
+        if problems.is_empty() {
+            return Some(Finding {
+                severity: Severity::Pass,
+                detail: s
+                    .health_detail_parallelism_ok
+                    .replacen("{}", max_jobs, 1)
+                    .replacen("{}", cores, 1),
+                fix: None,
+                fix_description: None,
+            });
+        }
+        let settings: Vec<String> = problems
+            .iter()
+            .map(|(name, value)| format!("{} = {}", name, value))
+            .collect();
+        Some(Finding {
+            severity: Severity::Warn,
+            detail: s
+                .health_detail_parallelism_low
+                .replacen("{}", &settings.join(", "), 1)
+                .replacen("{}", &cpus.to_string(), 1),
+            fix: Some(FixAction::Snippet(format!(
+                "nix.settings.max-jobs = \"auto\";  # one build per CPU ({})\n\
+                 nix.settings.cores = 0;           # every CPU for each build",
+                cpus
+            ))),
+            fix_description: Some(s.health_fix_parallelism.to_string()),
+        })
+    }
+}
+
+/// Members of the `wheel` group from /etc/group content
+fn wheel_members(group: &str) -> Vec<String> {
+    group
+        .lines()
+        .find_map(|line| line.strip_prefix("wheel:"))
+        .and_then(|rest| rest.rsplit(':').next())
+        .map(|members| {
+            members
+                .split(',')
+                .filter(|m| !m.is_empty())
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Trusted users that shouldn't be: everyone, a broad group, or a user who
+/// isn't an admin. Severity is Fail when anyone at all is trusted.
+fn untrusted_entries(trusted: &str, wheel: &[String]) -> (Severity, Vec<String>) {
+    let mut severity = Severity::Pass;
+    let mut entries = Vec::new();
+    for entry in trusted.split_whitespace() {
+        match entry {
+            "root" | "@wheel" => {}
+            "*" | "@users" => {
+                severity = Severity::Fail;
+                entries.push(entry.to_string());
+            }
+            group if group.starts_with('@') => {
+                severity = severity.max(Severity::Warn);
+                entries.push(entry.to_string());
+            }
+            user if !wheel.iter().any(|w| w == user) => {
+                severity = severity.max(Severity::Warn);
+                entries.push(entry.to_string());
+            }
+            _ => {}
+        }
+    }
+    (severity, entries)
+}
+
+struct TrustedUsers;
+
+impl HealthCheck for TrustedUsers {
+    fn id(&self) -> &'static str {
+        "trusted-users"
+    }
+    fn name(&self, s: &Strings) -> &'static str {
+        s.health_name_trusted_users
+    }
+    fn description(&self, s: &Strings) -> &'static str {
+        s.health_desc_trusted_users
+    }
+    fn category(&self) -> Category {
+        Category::Security
+    }
+    fn weight(&self) -> u8 {
+        15
+    }
+
+    fn run(&self, ctx: &CheckContext) -> Option<Finding> {
+        let config = nix_config()?;
+        let trusted = config.get("trusted-users").map_or("", String::as_str);
+        let group = std::fs::read_to_string("/etc/group").unwrap_or_default();
+        let (severity, entries) = untrusted_entries(trusted, &wheel_members(&group));
+        let s = i18n::get_strings(ctx.lang);
+        if entries.is_empty() {
+            return Some(Finding {
+                severity: Severity::Pass,
+                detail: s.health_detail_trusted_ok.replace("{}", trusted),
+                fix: None,
+                fix_description: None,
+            });
+        }
+        Some(Finding {
+            severity,
+            detail: s
+                .health_detail_trusted_unsafe
+                .replace("{}", &entries.join(" ")),
+            fix: Some(FixAction::Snippet(
+                "nix.settings.trusted-users = [ \"root\" \"@wheel\" ];\n\
+                 # other users can still build; to let them use a cache:\n\
+                 # nix.settings.extra-substituters / trusted-public-keys"
+                    .to_string(),
+            )),
+            fix_description: Some(s.health_fix_trusted_users.to_string()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nix_conf_settings() {
+        let mut features = Vec::new();
+        apply_features(
+            "experimental-features = nix-command # flakes later\nsubstituters = https://cache.nixos.org",
+            &mut features,
+        );
+        apply_features("extra-experimental-features = flakes", &mut features);
+        assert_eq!(features, vec!["nix-command", "flakes"]);
+
+        assert!(parallelism_problems("auto", "0", 16).is_empty());
+        assert!(parallelism_problems("1", "1", 2).is_empty());
+        assert_eq!(
+            parallelism_problems("1", "0", 8),
+            vec![("max-jobs", "1".to_string())]
+        );
+
+        let wheel = wheel_members("users:x:100:\nwheel:x:1:alice,bob\n");
+        assert_eq!(wheel, vec!["alice", "bob"]);
+        assert_eq!(
+            untrusted_entries("root @wheel alice", &wheel),
+            (Severity::Pass, vec![])
+        );
+        assert_eq!(
+            untrusted_entries("root carol *", &wheel),
+            (Severity::Fail, vec!["carol".to_string(), "*".to_string()])
+        );
+    }
+}
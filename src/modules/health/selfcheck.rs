@@ -0,0 +1,206 @@
+//! Self-check: the optional tools nixmate shells out to.
+//!
+//! nixmate runs without any of them, but features quietly degrade: no
+//! current values in the Options Explorer without `nixos-option`, no
+//! container view without docker/podman, and so on. Each tool becomes
+//! one check in the "nixmate" category with weight 0, so a missing tool
+//! is listed (with the package to install) without lowering the health
+//! score of the system itself.
+
+use super::checks::{Category, CheckContext, Finding, FixAction, HealthCheck};
+use super::Severity;
+use crate::i18n::{self, Strings};
+use std::path::Path;
+use std::sync::Arc;
+
+/// An external program and what depends on it
+struct Tool {
+    id: &'static str,
+    /// Shown as the check name
+    label: &'static str,
+    /// Any of these binaries will do
+    bins: &'static [&'static str],
+    /// Prints the version; None if the tool has no usable version flag
+    version_args: Option<&'static [&'static str]>,
+    /// Oldest version with every flag nixmate passes
+    min_version: Option<(u32, u32)>,
+    /// nixpkgs attribute to install
+    package: &'static str,
+    /// Features that don't work without the tool
+    features: fn(&Strings) -> &'static str,
+}
+
+const TOOLS: &[Tool] = &[
+    Tool {
+        id: "dep-nixos-option",
+        label: "nixos-option",
+        bins: &["nixos-option"],
+        version_args: None,
+        min_version: None,
+        package: "nixos-option",
+        features: |s| s.health_dep_feat_nixos_option,
+    },
+    Tool {
+        id: "dep-journalctl",
+        label: "journalctl",
+        bins: &["journalctl"],
+        version_args: Some(&["--version"]),
+        // --grep
+        min_version: Some((237, 0)),
+        package: "systemd",
+        features: |s| s.health_dep_feat_journalctl,
+    },
+    Tool {
+        id: "dep-git",
+        label: "git",
+        bins: &["git"],
+        version_args: Some(&["--version"]),
+        // git -C
+        min_version: Some((1, 9)),
+        package: "git",
+        features: |s| s.health_dep_feat_git,
+    },
+    Tool {
+        id: "dep-containers",
+        label: "docker / podman",
+        bins: &["docker", "podman"],
+        version_args: Some(&["--version"]),
+        min_version: None,
+        package: "podman",
+        features: |s| s.health_dep_feat_containers,
+    },
+    Tool {
+        id: "dep-busctl",
+        label: "busctl",
+        bins: &["busctl"],
+        version_args: None,
+        min_version: None,
+        package: "systemd",
+        features: |s| s.health_dep_feat_busctl,
+    },
+];
+
+/// One check per tool, appended to the registry
+pub fn checks() -> Vec<Arc<dyn HealthCheck>> {
+    TOOLS
+        .iter()
+        .map(|t| Arc::new(Dependency(t)) as Arc<dyn HealthCheck>)
+        .collect()
+}
+
+struct Dependency(&'static Tool);
+
+/// First binary of `bins` on $PATH
+fn find_binary(bins: &[&str]) -> Option<(String, std::path::PathBuf)> {
+    let path = std::env::var_os("PATH")?;
+    bins.iter().find_map(|bin| {
+        std::env::split_paths(&path)
+            .map(|dir| dir.join(bin))
+            .find(|p| is_executable(p))
+            .map(|p| (bin.to_string(), p))
+    })
+}
+
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path).is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+}
+
+/// (major, minor) of the first version-looking word:
+/// "git version 2.44.0" → (2, 44), "systemd 254 (254.6)" → (254, 0)
+fn parse_version(text: &str) -> Option<(u32, u32)> {
+    let word = text
+        .split_whitespace()
+        .find(|w| w.starts_with(|c: char| c.is_ascii_digit()))?;
+    let mut parts = word
+        .split('.')
+        .map(|p| p.trim_end_matches(|c: char| !c.is_ascii_digit()));
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next().and_then(|p| p.parse().ok()).unwrap_or(0);
+    Some((major, minor))
+}
+
+impl HealthCheck for Dependency {
+    fn id(&self) -> &'static str {
+        self.0.id
+    }
+    fn name(&self, _s: &Strings) -> &'static str {
+        self.0.label
+    }
+    fn description(&self, s: &Strings) -> &'static str {
+        (self.0.features)(s)
+    }
+    fn category(&self) -> Category {
+        Category::Nixmate
+    }
+    fn weight(&self) -> u8 {
+        0
+    }
+
+    fn run(&self, ctx: &CheckContext) -> Option<Finding> {
+        let s = i18n::get_strings(ctx.lang);
+        let tool = self.0;
+        let Some((bin, path)) = find_binary(tool.bins) else {
+            return Some(Finding {
+                severity: Severity::Warn,
+                detail: s
+                    .health_detail_dep_missing
+                    .replace("{}", (tool.features)(s)),
+                fix: Some(FixAction::Snippet(format!(
+                    "environment.systemPackages = [ pkgs.{} ];",
+                    tool.package
+                ))),
+                fix_description: Some(s.health_fix_dep_install.replace("{}", tool.package)),
+            });
+        };
+
+        let version = tool.version_args.and_then(|args| {
+            let path = path.to_string_lossy();
+            let output = crate::nix::storage::output_with_timeout(&path, args, 5)?;
+            let text = String::from_utf8_lossy(&output.stdout);
+            Some((
+                text.lines().next()?.trim().to_string(),
+                parse_version(&text)?,
+            ))
+        });
+        let found = match &version {
+            Some((line, _)) => line.clone(),
+            None => bin,
+        };
+
+        match (tool.min_version, version) {
+            (Some(min), Some((_, v))) if v < min => Some(Finding {
+                severity: Severity::Warn,
+                detail: s
+                    .health_detail_dep_old
+                    .replacen("{}", &found, 1)
+                    .replacen("{}", &format!("{}.{}", min.0, min.1), 1)
+                    .replacen("{}", (tool.features)(s), 1),
+                fix: None,
+                fix_description: Some(s.health_fix_dep_install.replace("{}", tool.package)),
+            }),
+            _ => Some(Finding {
+                severity: Severity::Pass,
+                detail: format!("{} · {}", found, path.display()),
+                fix: None,
+                fix_description: None,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(parse_version("git version 2.44.0"), Some((2, 44)));
+        assert_eq!(parse_version("systemd 254 (254.6)\n+PAM"), Some((254, 0)));
+        assert_eq!(
+            parse_version("Docker version 24.0.7, build afdd53b"),
+            Some((24, 0))
+        );
+        assert_eq!(parse_version("no version here"), None);
+    }
+}
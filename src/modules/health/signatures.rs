@@ -0,0 +1,260 @@
+//! Binary cache signature audit — a sample of the current system closure
+//! checked against `trusted-public-keys`.
+//!
+//! `nix store verify --no-contents` does the actual signature check (no
+//! hashing, so it stays fast). For every path it rejects, `nix path-info
+//! --json` tells why: signed by a key we don't trust, signed by a trusted
+//! key but still rejected (path or signature altered), or not signed at
+//! all. Locally built paths are "ultimate" and trusted without signatures.
+
+use crate::nix::storage::output_with_timeout;
+use std::collections::{HashMap, HashSet};
+use std::process::Command;
+
+/// Paths sampled from the closure; enough to spot a pattern, quick to verify
+const SAMPLE_SIZE: usize = 40;
+const SYSTEM_PATH: &str = "/run/current-system";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathTrust {
+    /// Valid signature from a trusted key
+    Signed,
+    /// Built on this machine
+    LocalBuild,
+    /// Signed, but by no key in trusted-public-keys
+    UnknownKey,
+    /// Claims a trusted key, but the signature doesn't verify
+    BadSignature,
+    /// Neither built here nor signed
+    Unsigned,
+}
+
+#[derive(Debug, Clone)]
+pub struct SampledPath {
+    pub path: String,
+    pub trust: PathTrust,
+    /// Key names from the path's signatures (`cache.nixos.org-1`, …)
+    pub signers: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SignatureAudit {
+    pub paths: Vec<SampledPath>,
+    pub require_sigs: bool,
+}
+
+impl SignatureAudit {
+    pub fn count(&self, trust: PathTrust) -> usize {
+        self.paths.iter().filter(|p| p.trust == trust).count()
+    }
+
+    pub fn untrusted(&self) -> impl Iterator<Item = &SampledPath> {
+        self.paths
+            .iter()
+            .filter(|p| !matches!(p.trust, PathTrust::Signed | PathTrust::LocalBuild))
+    }
+
+    /// Signers of untrusted paths that aren't in trusted-public-keys
+    pub fn unknown_signers(&self) -> Vec<String> {
+        let mut keys: Vec<String> = self
+            .untrusted()
+            .filter(|p| p.trust == PathTrust::UnknownKey)
+            .flat_map(|p| p.signers.iter().cloned())
+            .collect();
+        keys.sort();
+        keys.dedup();
+        keys
+    }
+}
+
+/// Run the audit. None if nix (or the current system) isn't available.
+pub fn run_audit() -> Option<SignatureAudit> {
+    let (require_sigs, trusted_keys) = read_nix_config()?;
+    let sample = closure_sample(SAMPLE_SIZE)?;
+
+    let rejected = verify(&sample)?;
+    let infos = path_infos(&sample);
+
+    let paths = sample
+        .into_iter()
+        .map(|path| {
+            let (ultimate, signers) = infos
+                .iter()
+                .find(|(p, _, _)| *p == path)
+                .map(|(_, u, s)| (*u, s.clone()))
+                .unwrap_or_default();
+            let trust = classify(rejected.contains(&path), ultimate, &signers, &trusted_keys);
+            SampledPath {
+                path,
+                trust,
+                signers,
+            }
+        })
+        .collect();
+
+    Some(SignatureAudit {
+        paths,
+        require_sigs,
+    })
+}
+
+fn classify(rejected: bool, ultimate: bool, signers: &[String], trusted: &[String]) -> PathTrust {
+    if !rejected {
+        if ultimate {
+            PathTrust::LocalBuild
+        } else {
+            PathTrust::Signed
+        }
+    } else if signers.is_empty() {
+        PathTrust::Unsigned
+    } else if signers.iter().any(|k| trusted.contains(k)) {
+        PathTrust::BadSignature
+    } else {
+        PathTrust::UnknownKey
+    }
+}
+
+/// Up to `n` paths spread over the current system's closure
+pub(super) fn closure_sample(n: usize) -> Option<Vec<String>> {
+    // Plain output(): the closure listing is too big for a polled pipe
+    let closure = Command::new("nix-store")
+        .args(["-qR", SYSTEM_PATH])
+        .output()
+        .ok()?;
+    if !closure.status.success() {
+        return None;
+    }
+    let all: Vec<String> = String::from_utf8_lossy(&closure.stdout)
+        .lines()
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty())
+        .collect();
+    let sample = sample_evenly(&all, n);
+    (!sample.is_empty()).then_some(sample)
+}
+
+/// Every n-th path, so the sample covers the whole closure
+fn sample_evenly(all: &[String], n: usize) -> Vec<String> {
+    if all.len() <= n {
+        return all.to_vec();
+    }
+    let step = all.len() / n;
+    all.iter().step_by(step).take(n).cloned().collect()
+}
+
+/// The effective nix settings as name → value
+pub(super) fn nix_config() -> Option<HashMap<String, String>> {
+    // `nix config show` since Nix 2.19, `nix show-config` before
+    let output = output_with_timeout(
+        "nix",
+        &[
+            "--extra-experimental-features",
+            "nix-command",
+            "config",
+            "show",
+        ],
+        15,
+    )
+    .filter(|o| o.status.success())
+    .or_else(|| {
+        output_with_timeout(
+            "nix",
+            &[
+                "--extra-experimental-features",
+                "nix-command",
+                "show-config",
+            ],
+            15,
+        )
+        .filter(|o| o.status.success())
+    })?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Some(
+        stdout
+            .lines()
+            .filter_map(|line| line.split_once('='))
+            .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+            .collect(),
+    )
+}
+
+/// `require-sigs` and the names of `trusted-public-keys`
+fn read_nix_config() -> Option<(bool, Vec<String>)> {
+    let config = nix_config()?;
+    let require_sigs = config.get("require-sigs").is_none_or(|v| v != "false");
+    let keys = config
+        .get("trusted-public-keys")
+        .map(|v| {
+            v.split_whitespace()
+                .filter_map(|k| k.split_once(':').map(|(n, _)| n.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+    Some((require_sigs, keys))
+}
+
+/// Paths `nix store verify` rejects as untrusted
+fn verify(paths: &[String]) -> Option<HashSet<String>> {
+    let mut args = vec![
+        "--extra-experimental-features",
+        "nix-command",
+        "store",
+        "verify",
+        "--no-contents",
+    ];
+    args.extend(paths.iter().map(|p| p.as_str()));
+    // Exits non-zero when anything is untrusted; stderr says which
+    let output = output_with_timeout("nix", &args, 60)?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    Some(
+        stderr
+            .lines()
+            .filter(|l| l.contains("is untrusted"))
+            .filter_map(|l| l.split('\'').nth(1))
+            .map(|p| p.to_string())
+            .collect(),
+    )
+}
+
+/// (path, ultimate, signer key names) from `nix path-info --json`
+fn path_infos(paths: &[String]) -> Vec<(String, bool, Vec<String>)> {
+    let mut args = vec![
+        "--extra-experimental-features",
+        "nix-command",
+        "path-info",
+        "--json",
+        "--sigs",
+    ];
+    args.extend(paths.iter().map(|p| p.as_str()));
+    let Ok(output) = Command::new("nix").args(&args).output() else {
+        return Vec::new();
+    };
+    let Ok(json) = serde_json::from_slice::<serde_json::Value>(&output.stdout) else {
+        return Vec::new();
+    };
+
+    let info = |path: &str, v: &serde_json::Value| {
+        let ultimate = v.get("ultimate").and_then(|u| u.as_bool()).unwrap_or(false);
+        let signers = v
+            .get("signatures")
+            .and_then(|s| s.as_array())
+            .map(|sigs| {
+                sigs.iter()
+                    .filter_map(|s| s.as_str()?.split_once(':').map(|(k, _)| k.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        (path.to_string(), ultimate, signers)
+    };
+
+    // Object keyed by path since Nix 2.19, array of objects before
+    match json {
+        serde_json::Value::Object(map) => map.iter().map(|(p, v)| info(p, v)).collect(),
+        serde_json::Value::Array(list) => list
+            .iter()
+            .filter_map(|v| Some(info(v.get("path")?.as_str()?, v)))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
@@ -0,0 +1,77 @@
+//! Health score history — one entry per finished scan (TUI or `nixmate doctor`),
+//! so the start screen can show whether things got better or worse.
+
+use serde::{Deserialize, Serialize};
+
+/// Entries kept on disk
+const MAX_ENTRIES: usize = 50;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoreEntry {
+    pub timestamp: String,
+    pub score: u8,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trend {
+    Up(u8),
+    Down(u8),
+    Same,
+}
+
+impl Trend {
+    /// None without an earlier score to compare against
+    pub fn between(previous: Option<u8>, current: u8) -> Option<Self> {
+        let previous = previous?;
+        Some(if current > previous {
+            Trend::Up(current - previous)
+        } else if current < previous {
+            Trend::Down(previous - current)
+        } else {
+            Trend::Same
+        })
+    }
+
+    /// Arrow plus delta, e.g. "↑ +5"
+    pub fn label(&self) -> String {
+        match self {
+            Trend::Up(d) => format!("↑ +{}", d),
+            Trend::Down(d) => format!("↓ -{}", d),
+            Trend::Same => "→".to_string(),
+        }
+    }
+}
+
+fn history_path() -> Option<std::path::PathBuf> {
+    dirs::data_dir().map(|p| p.join("nixmate").join("doctor-history.json"))
+}
+
+/// Oldest first
+pub fn load_history() -> Vec<ScoreEntry> {
+    history_path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Append `score` and return the score recorded before it, if any
+pub fn record(score: u8) -> Option<u8> {
+    let mut entries = load_history();
+    let previous = entries.last().map(|e| e.score);
+    entries.push(ScoreEntry {
+        timestamp: chrono::Local::now().format("%Y-%m-%d %H:%M").to_string(),
+        score,
+    });
+    if entries.len() > MAX_ENTRIES {
+        entries.drain(..entries.len() - MAX_ENTRIES);
+    }
+    if let Some(path) = history_path() {
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        if let Ok(json) = serde_json::to_string_pretty(&entries) {
+            let _ = std::fs::write(path, json);
+        }
+    }
+    previous
+}
@@ -0,0 +1,279 @@
+//! Background jobs — one place to see and cancel what's running
+//!
+//! Modules keep their own threads and channels; each reports what it is
+//! currently waiting on via `jobs()` and knows how to stop it via
+//! `cancel_job(id)`. `JobManager` collects those reports every tick, so a
+//! job exists exactly as long as its module thinks it does: start times,
+//! the status bar badge and the "recently finished" list come from here.
+//!
+//! Cancelling a load or search drops the module's receiver (the result is
+//! discarded). Jobs that change the system — a cleanup step, a flake
+//! update, a fix — are reported as not cancellable: stopping to wait
+//! wouldn't stop them. A rebuild is cancelled by killing its process.
+
+use crate::config::Language;
+use crate::i18n;
+use crate::ui::{ModuleTab, Theme};
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Finished jobs kept for the overlay
+const MAX_FINISHED: usize = 8;
+
+/// A running job as a module reports it
+#[derive(Debug, Clone)]
+pub struct Job {
+    /// Stable within the module, handed back to `cancel_job`
+    pub id: &'static str,
+    pub name: String,
+    /// Current phase or step, if the job reports one
+    pub detail: Option<String>,
+    /// (done, total)
+    pub progress: Option<(usize, usize)>,
+    pub cancellable: bool,
+}
+
+impl Job {
+    pub fn new(id: &'static str, name: impl Into<String>, cancellable: bool) -> Self {
+        Self {
+            id,
+            name: name.into(),
+            detail: None,
+            progress: None,
+            cancellable,
+        }
+    }
+
+    pub fn detail(mut self, detail: impl Into<String>) -> Self {
+        let detail = detail.into();
+        self.detail = (!detail.is_empty()).then_some(detail);
+        self
+    }
+
+    pub fn progress(mut self, done: usize, total: usize) -> Self {
+        self.progress = (total > 0).then_some((done, total));
+        self
+    }
+}
+
+pub struct Tracked {
+    pub tab: ModuleTab,
+    pub job: Job,
+    pub started: Instant,
+}
+
+pub struct Finished {
+    pub tab: ModuleTab,
+    pub name: String,
+    pub took: Duration,
+    pub cancelled: bool,
+}
+
+#[derive(Default)]
+pub struct JobManager {
+    running: Vec<Tracked>,
+    finished: VecDeque<Finished>,
+    /// Jobs cancelled from the overlay, labelled as such once they're gone
+    cancelled: Vec<(ModuleTab, &'static str)>,
+    pub overlay: bool,
+    pub selected: usize,
+    /// Waiting for y/n before cancelling the selected job
+    pub confirm: bool,
+}
+
+impl JobManager {
+    /// Replace the running set with what the modules report now
+    pub fn sync(&mut self, current: Vec<(ModuleTab, Job)>) {
+        let now = Instant::now();
+        let mut next: Vec<Tracked> = Vec::with_capacity(current.len());
+        for (tab, job) in current {
+            let started = self
+                .running
+                .iter()
+                .find(|t| t.tab == tab && t.job.id == job.id)
+                .map_or(now, |t| t.started);
+            next.push(Tracked { tab, job, started });
+        }
+        for gone in &self.running {
+            if next
+                .iter()
+                .any(|t| t.tab == gone.tab && t.job.id == gone.job.id)
+            {
+                continue;
+            }
+            let key = (gone.tab, gone.job.id);
+            let cancelled = self.cancelled.contains(&key);
+            self.cancelled.retain(|k| *k != key);
+            self.finished.push_front(Finished {
+                tab: gone.tab,
+                name: gone.job.name.clone(),
+                took: now.duration_since(gone.started),
+                cancelled,
+            });
+            self.finished.truncate(MAX_FINISHED);
+        }
+        self.running = next;
+        self.selected = self.selected.min(self.running.len().saturating_sub(1));
+        if self.running.is_empty() {
+            self.confirm = false;
+        }
+    }
+
+    pub fn running(&self) -> &[Tracked] {
+        &self.running
+    }
+
+    pub fn selected_job(&self) -> Option<&Tracked> {
+        self.running.get(self.selected)
+    }
+
+    /// Remember that the selected job was cancelled (for the finished list)
+    pub fn mark_cancelled(&mut self, tab: ModuleTab, id: &'static str) {
+        self.cancelled.push((tab, id));
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.running.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+}
+
+fn format_duration(d: Duration) -> String {
+    let secs = d.as_secs();
+    if secs >= 60 {
+        format!("{}m {:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+/// Braille spinner frame for the current time
+pub fn spinner() -> &'static str {
+    const FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+    let ms = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    FRAMES[(ms / 100) as usize % FRAMES.len()]
+}
+
+/// The jobs overlay. `label` gives a tab's module name.
+pub fn render<'a>(
+    frame: &mut Frame,
+    jobs: &JobManager,
+    label: impl Fn(ModuleTab) -> &'a str,
+    theme: &Theme,
+    lang: Language,
+    area: Rect,
+) {
+    let s = i18n::get_strings(lang);
+    let mut lines = vec![Line::styled(format!(" {}", s.jobs_running), theme.title())];
+
+    if jobs.running.is_empty() {
+        lines.push(Line::styled(
+            format!("   {}", s.jobs_none),
+            theme.text_dim(),
+        ));
+    }
+    for (i, t) in jobs.running.iter().enumerate() {
+        let style = if i == jobs.selected {
+            theme.selected()
+        } else {
+            theme.text()
+        };
+        let progress = t
+            .job
+            .progress
+            .map(|(done, total)| format!(" {}/{}", done, total))
+            .unwrap_or_default();
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(" {} ", spinner()),
+                Style::default().fg(theme.accent),
+            ),
+            Span::styled(
+                format!("{}: {}{}", label(t.tab), t.job.name, progress),
+                style,
+            ),
+            Span::styled(
+                format!("  {}", format_duration(t.started.elapsed())),
+                theme.text_dim(),
+            ),
+        ]));
+        if let Some(detail) = &t.job.detail {
+            lines.push(Line::styled(format!("     {}", detail), theme.text_dim()));
+        }
+    }
+
+    if let Some(t) = jobs.selected_job() {
+        lines.push(Line::raw(""));
+        if jobs.confirm {
+            lines.push(Line::styled(
+                format!(" {}", s.jobs_confirm.replace("{}", &t.job.name)),
+                Style::default()
+                    .fg(theme.warning)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        } else if !t.job.cancellable {
+            lines.push(Line::styled(
+                format!(" {}", s.jobs_not_cancellable),
+                theme.text_dim(),
+            ));
+        }
+    }
+
+    if !jobs.finished.is_empty() {
+        lines.push(Line::raw(""));
+        lines.push(Line::styled(format!(" {}", s.jobs_finished), theme.title()));
+        for f in &jobs.finished {
+            let mut spans = vec![
+                Span::styled(format!("   {}: {}", label(f.tab), f.name), theme.text_dim()),
+                Span::styled(format!("  {}", format_duration(f.took)), theme.text_dim()),
+            ];
+            if f.cancelled {
+                spans.push(Span::styled(
+                    format!("  ({})", s.jobs_cancelled),
+                    Style::default().fg(theme.warning),
+                ));
+            }
+            lines.push(Line::from(spans));
+        }
+    }
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        format!(" {}", s.jobs_footer),
+        theme.text_dim(),
+    ));
+
+    let width = 72.min(area.width.saturating_sub(4));
+    let height = (lines.len() as u16 + 2).min(area.height.saturating_sub(2));
+    if width < 20 || height < 5 {
+        return;
+    }
+    let popup = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    };
+    frame.render_widget(Clear, popup);
+    let block = Block::default()
+        .style(theme.block_style())
+        .title(format!(" {} ", s.jobs_title))
+        .title_style(theme.title())
+        .borders(Borders::ALL)
+        .border_style(theme.border_focused());
+    frame.render_widget(Paragraph::new(lines).block(block), popup);
+}
@@ -0,0 +1,134 @@
+//! `?` overlay — the keys that work right here
+//!
+//! Built from the same data as the command palette: the active module's
+//! single-key `palette_commands()` for the current sub-tab, plus the global
+//! actions from the keymap (so remapped keys show as remapped). Multi-key
+//! palette commands are macros, not keybindings, and are left out.
+
+use crate::config::Language;
+use crate::i18n;
+use crate::keymap::Keymap;
+use crate::modules::palette::PaletteCommand;
+use crate::ui::Theme;
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Global actions listed in the overlay, in this order
+const GLOBAL_ACTIONS: &[&str] = &[
+    "up",
+    "down",
+    "select",
+    "back",
+    "prev_subtab",
+    "next_subtab",
+    "palette",
+    "jobs",
+    "quit",
+];
+
+pub struct KeyHelp {
+    /// "Module" or "Module › Sub-tab"
+    pub title: String,
+    /// (key, what it does)
+    pub module: Vec<(String, String)>,
+    pub global: Vec<(String, String)>,
+}
+
+impl KeyHelp {
+    pub fn new(
+        title: String,
+        commands: Vec<PaletteCommand>,
+        sub_tab: Option<usize>,
+        keymap: &Keymap,
+    ) -> Self {
+        let mut module: Vec<(String, String)> = Vec::new();
+        for c in commands {
+            if c.keys.len() != 1 || (c.sub_tab.is_some() && c.sub_tab != sub_tab) {
+                continue;
+            }
+            let key = c.hint();
+            if !module.iter().any(|(k, _)| *k == key) {
+                module.push((key, c.label));
+            }
+        }
+        let global = GLOBAL_ACTIONS
+            .iter()
+            .filter_map(|name| keymap.bindings.iter().find(|b| b.action.name == *name))
+            .map(|b| (b.keys.join(", "), b.action.name.replace('_', " ")))
+            .collect();
+        Self {
+            title,
+            module,
+            global,
+        }
+    }
+}
+
+pub fn render(frame: &mut Frame, help: &KeyHelp, theme: &Theme, lang: Language, area: Rect) {
+    let s = i18n::get_strings(lang);
+    let key_width = help
+        .module
+        .iter()
+        .chain(&help.global)
+        .map(|(k, _)| k.chars().count())
+        .max()
+        .unwrap_or(0)
+        .clamp(5, 16);
+
+    let row = |(key, label): &(String, String)| {
+        Line::from(vec![
+            Span::styled(
+                format!("  {:>width$}  ", key, width = key_width),
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(label.clone(), theme.text()),
+        ])
+    };
+
+    let mut lines = vec![Line::styled(format!(" {}", help.title), theme.title())];
+    if help.module.is_empty() {
+        lines.push(Line::styled(
+            format!("  {}", s.key_help_none),
+            theme.text_dim(),
+        ));
+    }
+    lines.extend(help.module.iter().map(row));
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        format!(" {}", s.key_help_global),
+        theme.title(),
+    ));
+    lines.extend(help.global.iter().map(row));
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        format!(" {}", s.key_help_footer),
+        theme.text_dim(),
+    ));
+
+    let width = 64.min(area.width.saturating_sub(4));
+    let height = (lines.len() as u16 + 2).min(area.height.saturating_sub(2));
+    if width < 20 || height < 5 {
+        return;
+    }
+    let popup = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    };
+    frame.render_widget(Clear, popup);
+    let block = Block::default()
+        .style(theme.block_style())
+        .title(format!(" {} ", s.key_help_title))
+        .title_style(theme.title())
+        .borders(Borders::ALL)
+        .border_style(theme.border_focused());
+    frame.render_widget(Paragraph::new(lines).block(block), popup);
+}
@@ -2,12 +2,18 @@
 
 pub mod config_showcase;
 pub mod errors;
+pub mod external;
 pub mod flake_inputs;
 pub mod generations;
 pub mod health;
+pub mod jobs;
+pub mod key_help;
+pub mod module;
 pub mod options;
 pub mod packages;
+pub mod palette;
 pub mod rebuild;
 pub mod services;
 pub mod splash;
 pub mod storage;
+pub mod vitals;
@@ -0,0 +1,72 @@
+//! The `Module` trait: what App needs from a tab
+//!
+//! Every built-in module's state implements it, and so do the external
+//! modules from `[[external_modules]]` (see `external`). App reaches them
+//! through `App::module` / `module_mut` for rendering, keys, polling,
+//! sidebar badges, palette commands and jobs, and keeps only what crosses
+//! modules (jumping to another tab, persisting settings) per module.
+
+use crate::config::Language;
+use crate::i18n::Strings;
+use crate::modules::jobs::Job;
+use crate::modules::palette::PaletteCommand;
+use crate::types::FlashMessage;
+use crate::ui::Theme;
+use anyhow::Result;
+use crossterm::event::KeyEvent;
+use ratatui::{layout::Rect, Frame};
+
+/// What every module's render gets besides its own state
+pub struct RenderCtx<'a> {
+    pub theme: &'a Theme,
+    pub lang: Language,
+    /// An AI provider is configured (Error Translator)
+    pub ai_available: bool,
+}
+
+pub trait Module {
+    /// A key App didn't keep for itself
+    fn handle_key(&mut self, key: KeyEvent, lang: Language) -> Result<()>;
+
+    /// Takes every key right now (text input, popup, form), including the
+    /// tab switching ones
+    fn captures_input(&self) -> bool {
+        false
+    }
+
+    fn render(&mut self, frame: &mut Frame, ctx: &RenderCtx, area: Rect);
+
+    /// Entering the tab: start loading what it shows
+    fn activate(&mut self) {}
+
+    /// Pick up background results, every tick; `active` = the tab is shown
+    fn poll(&mut self, _active: bool) {}
+
+    /// Attention count next to the module in the sidebar
+    fn badge(&self) -> Option<usize> {
+        None
+    }
+
+    /// Actions for the palette and the `?` overlay
+    fn commands(&self, _s: &Strings) -> Vec<PaletteCommand> {
+        Vec::new()
+    }
+
+    fn jobs(&self, _s: &Strings) -> Vec<Job> {
+        Vec::new()
+    }
+
+    fn cancel_job(&mut self, _id: &str) {}
+
+    /// (current sub-tab index, sub-tab count)
+    fn sub_tab(&self) -> Option<(usize, usize)> {
+        None
+    }
+
+    fn sub_tab_label(&self, _lang: Language) -> Option<&'static str> {
+        None
+    }
+
+    /// The module's flash message, expired by App
+    fn flash_message(&mut self) -> &mut Option<FlashMessage>;
+}
@@ -0,0 +1,151 @@
+//! Persistent on-disk cache for the parsed options index
+//!
+//! Parsing 20k+ options (or worse, building options.json first) is slow.
+//! After a successful load the parsed `Vec<NixOption>` is written to
+//! `~/.cache/nixmate/options-<nixos-version>.bin` as compact JSON.
+//!
+//! The file name is keyed on the running NixOS version; the file content
+//! additionally records the locked nixpkgs (and flake-parts) revision of
+//! the user's flake, so a `nix flake update` invalidates the cache even
+//! before a rebuild, and the `nixosConfigurations` host picked in Rebuild.
+
+use super::NixOption;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Bump when the on-disk layout of `NixOption` changes
+const CACHE_FORMAT: u32 = 2;
+
+/// Identifies the option set a cache file belongs to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheKey {
+    pub nixos_version: String,
+    pub flake_rev: Option<String>,
+    #[serde(default)]
+    pub flake_parts_rev: Option<String>,
+    #[serde(default)]
+    pub nixos_host: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheFile {
+    format: u32,
+    key: CacheKey,
+    options: Vec<NixOption>,
+}
+
+impl CacheKey {
+    /// Determine the key for the currently running system
+    pub fn current(config_path: Option<&str>, nixos_host: Option<&str>) -> Self {
+        let nixos_version = std::fs::read_to_string("/run/current-system/nixos-version")
+            .map(|v| v.trim().to_string())
+            .ok()
+            .filter(|v| !v.is_empty())
+            .unwrap_or_else(|| "unknown".to_string());
+
+        Self {
+            nixos_version,
+            flake_rev: locked_rev(config_path, "nixpkgs"),
+            flake_parts_rev: locked_rev(config_path, "flake-parts"),
+            nixos_host: nixos_host.filter(|h| !h.is_empty()).map(str::to_string),
+        }
+    }
+
+    fn file_path(&self) -> Option<PathBuf> {
+        file_path_for_version(&self.nixos_version)
+    }
+}
+
+fn file_path_for_version(nixos_version: &str) -> Option<PathBuf> {
+    let safe: String = nixos_version
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    dirs::cache_dir().map(|p| p.join("nixmate").join(format!("options-{}.bin", safe)))
+}
+
+/// Load cached options if a cache file exists and matches `key`
+pub fn load(key: &CacheKey) -> Option<Vec<NixOption>> {
+    let path = key.file_path()?;
+    let content = std::fs::read(&path).ok()?;
+    let file: CacheFile = serde_json::from_slice(&content).ok()?;
+
+    if file.format != CACHE_FORMAT || &file.key != key || file.options.is_empty() {
+        return None;
+    }
+    Some(file.options)
+}
+
+/// Load whatever was cached for a NixOS version, ignoring the flake rev.
+/// Used for older generations whose options.json may be gone.
+pub fn load_for_version(nixos_version: &str) -> Option<Vec<NixOption>> {
+    let path = file_path_for_version(nixos_version)?;
+    let content = std::fs::read(&path).ok()?;
+    let file: CacheFile = serde_json::from_slice(&content).ok()?;
+
+    if file.format != CACHE_FORMAT || file.options.is_empty() {
+        return None;
+    }
+    Some(file.options)
+}
+
+/// Write the parsed options to the cache (best effort, caller may ignore errors)
+pub fn store(key: &CacheKey, options: &[NixOption]) -> Result<()> {
+    let path = key.file_path().context("No cache directory")?;
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let file = CacheFile {
+        format: CACHE_FORMAT,
+        key: key.clone(),
+        options: options.to_vec(),
+    };
+    let json = serde_json::to_vec(&file)?;
+
+    // Write to a temp file first so a crash never leaves a truncated cache
+    let tmp = path.with_extension("bin.tmp");
+    std::fs::write(&tmp, json)?;
+    std::fs::rename(&tmp, &path)?;
+
+    Ok(())
+}
+
+/// Locked revision of a root input from the user's flake.lock (if any)
+fn locked_rev(config_path: Option<&str>, input: &str) -> Option<String> {
+    for dir in &super::flake_dirs(config_path) {
+        let lock_path = format!("{}/flake.lock", dir);
+        let content = match std::fs::read_to_string(&lock_path) {
+            Ok(c) => c,
+            Err(_) => continue,
+        };
+        let lock: serde_json::Value = serde_json::from_str(&content).ok()?;
+        let nodes = lock.get("nodes")?;
+        let root_name = lock.get("root").and_then(|r| r.as_str()).unwrap_or("root");
+
+        // Resolve the root's input to its node name
+        let node_name = nodes
+            .get(root_name)
+            .and_then(|r| r.get("inputs"))
+            .and_then(|i| i.get(input))
+            .and_then(|n| n.as_str())
+            .unwrap_or(input);
+
+        return nodes
+            .get(node_name)
+            .and_then(|n| n.get("locked"))
+            .and_then(|l| l.get("rev"))
+            .and_then(|r| r.as_str())
+            .map(|s| s.to_string());
+    }
+
+    None
+}
@@ -0,0 +1,283 @@
+//! "Options that changed under you" — diff of the option set between the
+//! nixpkgs revisions of the two most recent rebuilds.
+//!
+//! Each system generation records its nixpkgs revision in `nixos-version`
+//! (e.g. `24.11.20241105.3f8b5a2`). Starting from the newest generation,
+//! we pick the most recent older generation built from a *different*
+//! revision, then compare their options.json files (from the generation's
+//! documentation, or the on-disk options cache for that version).
+
+use super::{cache, NixOption};
+use crate::config::Language;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+const PROFILES_DIR: &str = "/nix/var/nix/profiles";
+
+/// A system generation and the nixpkgs revision it was built from
+#[derive(Debug, Clone)]
+pub struct GenRev {
+    pub id: u32,
+    pub version: String,
+    path: PathBuf,
+}
+
+impl GenRev {
+    /// Short nixpkgs revision (last component of the version string)
+    pub fn short_rev(&self) -> &str {
+        self.version.rsplit('.').next().unwrap_or(&self.version)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Default,
+    Type,
+    /// Declared in a different module file
+    Moved,
+}
+
+impl ChangeKind {
+    pub fn icon(&self) -> &'static str {
+        match self {
+            ChangeKind::Added => "+",
+            ChangeKind::Removed => "−",
+            ChangeKind::Default => "~",
+            ChangeKind::Type => "T",
+            ChangeKind::Moved => "↪",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct OptionChange {
+    pub path: String,
+    pub kind: ChangeKind,
+    /// Old → new value for Default/Type/Moved changes
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ChangesReport {
+    pub old: GenRev,
+    pub new: GenRev,
+    pub changes: Vec<OptionChange>,
+}
+
+/// Compute the report. Blocking — run in background thread!
+pub fn compute_changes(lang: Language) -> Result<ChangesReport, String> {
+    let s = crate::i18n::get_strings(lang);
+
+    let gens = system_generations();
+    let newest = gens.first().ok_or_else(|| s.opt_chg_no_gens.to_string())?;
+    let older = gens
+        .iter()
+        .skip(1)
+        .find(|g| g.version != newest.version)
+        .ok_or_else(|| s.opt_chg_same_rev.to_string())?;
+
+    let new_opts = options_for(newest)
+        .ok_or_else(|| s.opt_chg_no_options.replace("{}", &newest.id.to_string()))?;
+    let old_opts = options_for(older)
+        .ok_or_else(|| s.opt_chg_no_options.replace("{}", &older.id.to_string()))?;
+
+    Ok(ChangesReport {
+        old: older.clone(),
+        new: newest.clone(),
+        changes: diff_options(&old_opts, &new_opts),
+    })
+}
+
+/// System generations, newest first
+fn system_generations() -> Vec<GenRev> {
+    let Ok(entries) = std::fs::read_dir(PROFILES_DIR) else {
+        return Vec::new();
+    };
+    let mut gens: Vec<GenRev> = entries
+        .flatten()
+        .filter_map(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            let id = name
+                .strip_prefix("system-")?
+                .strip_suffix("-link")?
+                .parse()
+                .ok()?;
+            let path = e.path();
+            let version = std::fs::read_to_string(path.join("nixos-version"))
+                .ok()?
+                .trim()
+                .to_string();
+            Some(GenRev { id, version, path })
+        })
+        .collect();
+    gens.sort_by_key(|g| std::cmp::Reverse(g.id));
+    gens
+}
+
+fn options_for(gen: &GenRev) -> Option<Vec<NixOption>> {
+    let doc = gen.path.join("sw/share/doc/nixos/options.json");
+    super::try_load_options_json(&doc.to_string_lossy())
+        .or_else(|| cache::load_for_version(&gen.version))
+}
+
+/// Declarations point into a store path; compare only the part below it
+fn normalize_decl(decl: &str) -> String {
+    match decl.find("nixos/modules/") {
+        Some(pos) => decl[pos..].to_string(),
+        None => Path::new(decl)
+            .file_name()
+            .map(|f| f.to_string_lossy().to_string())
+            .unwrap_or_else(|| decl.to_string()),
+    }
+}
+
+fn diff_options(old: &[NixOption], new: &[NixOption]) -> Vec<OptionChange> {
+    let old_by_path: HashMap<&str, &NixOption> = old.iter().map(|o| (o.path.as_str(), o)).collect();
+    let new_by_path: HashMap<&str, &NixOption> = new.iter().map(|o| (o.path.as_str(), o)).collect();
+
+    let mut changes = Vec::new();
+
+    for n in new {
+        let Some(o) = old_by_path.get(n.path.as_str()) else {
+            changes.push(OptionChange {
+                path: n.path.clone(),
+                kind: ChangeKind::Added,
+                old: None,
+                new: None,
+            });
+            continue;
+        };
+
+        if o.default_str != n.default_str {
+            changes.push(OptionChange {
+                path: n.path.clone(),
+                kind: ChangeKind::Default,
+                old: o.default_str.clone(),
+                new: n.default_str.clone(),
+            });
+        }
+        if o.type_str != n.type_str {
+            changes.push(OptionChange {
+                path: n.path.clone(),
+                kind: ChangeKind::Type,
+                old: Some(o.type_str.clone()),
+                new: Some(n.type_str.clone()),
+            });
+        }
+        let old_decl: Vec<String> = o.declared_in.iter().map(|d| normalize_decl(d)).collect();
+        let new_decl: Vec<String> = n.declared_in.iter().map(|d| normalize_decl(d)).collect();
+        if old_decl != new_decl {
+            changes.push(OptionChange {
+                path: n.path.clone(),
+                kind: ChangeKind::Moved,
+                old: Some(old_decl.join(", ")),
+                new: Some(new_decl.join(", ")),
+            });
+        }
+    }
+
+    for o in old {
+        if !new_by_path.contains_key(o.path.as_str()) {
+            changes.push(OptionChange {
+                path: o.path.clone(),
+                kind: ChangeKind::Removed,
+                old: None,
+                new: None,
+            });
+        }
+    }
+
+    changes.sort_by(|a, b| a.path.cmp(&b.path));
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn option(path: &str, type_str: &str, default: &str, decl: &str) -> NixOption {
+        NixOption {
+            path: path.to_string(),
+            type_str: type_str.to_string(),
+            description: String::new(),
+            default_str: Some(default.to_string()),
+            example_str: None,
+            declared_in: vec![decl.to_string()],
+            read_only: false,
+            flake_module: false,
+        }
+    }
+
+    #[test]
+    fn test_normalize_decl() {
+        assert_eq!(
+            normalize_decl("/nix/store/abc-source/nixos/modules/services/web.nix"),
+            "nixos/modules/services/web.nix"
+        );
+        assert_eq!(normalize_decl("/some/flake/module.nix"), "module.nix");
+    }
+
+    #[test]
+    fn test_diff_options() {
+        let old = vec![
+            option(
+                "a.same",
+                "bool",
+                "false",
+                "/nix/store/x-source/nixos/modules/a.nix",
+            ),
+            option(
+                "b.changed",
+                "int",
+                "1",
+                "/nix/store/x-source/nixos/modules/b.nix",
+            ),
+            option(
+                "c.removed",
+                "bool",
+                "false",
+                "/nix/store/x-source/nixos/modules/c.nix",
+            ),
+        ];
+        let new = vec![
+            // Another store path, same module file: unchanged
+            option(
+                "a.same",
+                "bool",
+                "false",
+                "/nix/store/y-source/nixos/modules/a.nix",
+            ),
+            option(
+                "b.changed",
+                "str",
+                "2",
+                "/nix/store/y-source/nixos/modules/b2.nix",
+            ),
+            option(
+                "d.added",
+                "bool",
+                "true",
+                "/nix/store/y-source/nixos/modules/d.nix",
+            ),
+        ];
+        let changes = diff_options(&old, &new);
+        let kinds: Vec<(&str, ChangeKind)> =
+            changes.iter().map(|c| (c.path.as_str(), c.kind)).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("b.changed", ChangeKind::Default),
+                ("b.changed", ChangeKind::Type),
+                ("b.changed", ChangeKind::Moved),
+                ("c.removed", ChangeKind::Removed),
+                ("d.added", ChangeKind::Added),
+            ]
+        );
+        assert_eq!(changes[0].old.as_deref(), Some("1"));
+        assert_eq!(changes[0].new.as_deref(), Some("2"));
+        assert_eq!(changes[2].new.as_deref(), Some("nixos/modules/b2.nix"));
+    }
+}
@@ -0,0 +1,123 @@
+//! Usage examples from public NixOS configurations
+//!
+//! The manual's `example` is often a single terse value. This dataset has
+//! how real configurations set an option, a few snippets per option. A
+//! small set ships with nixmate (`assets/option-examples.json`);
+//! `[option_examples] url` points at a full, regularly regenerated set
+//! that is downloaded to `~/.cache/nixmate/option-examples.json`.
+//! Whichever of the two is newer is used.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+const BUNDLED: &str = include_str!("../../../assets/option-examples.json");
+
+const DOWNLOAD_TIMEOUT_SECS: u64 = 60;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Dataset {
+    /// When the dataset was harvested (YYYY-MM-DD)
+    pub updated: String,
+    /// Option path (`<name>` for attribute names) → snippets
+    pub options: HashMap<String, Vec<Example>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Example {
+    /// Configuration the snippet comes from (`owner/repo`)
+    #[serde(default)]
+    pub source: Option<String>,
+    pub code: String,
+}
+
+impl Dataset {
+    pub fn for_option(&self, path: &str) -> &[Example] {
+        self.options.get(path).map_or(&[], Vec::as_slice)
+    }
+}
+
+fn cache_path() -> Option<PathBuf> {
+    dirs::cache_dir().map(|d| d.join("nixmate").join("option-examples.json"))
+}
+
+/// The newer of the bundled and the downloaded dataset
+pub fn load() -> Dataset {
+    let bundled: Dataset = serde_json::from_str(BUNDLED).unwrap_or_default();
+    let cached = cache_path()
+        .and_then(|p| std::fs::read_to_string(p).ok())
+        .and_then(|text| serde_json::from_str::<Dataset>(&text).ok());
+    newest(bundled, cached)
+}
+
+fn newest(bundled: Dataset, cached: Option<Dataset>) -> Dataset {
+    match cached {
+        // Dates are YYYY-MM-DD, so they compare as strings
+        Some(c) if c.updated >= bundled.updated => c,
+        _ => bundled,
+    }
+}
+
+/// The downloaded copy is missing or older than `max_age_days` (0 = never
+/// refresh on its own)
+pub fn is_stale(max_age_days: u32) -> bool {
+    if max_age_days == 0 {
+        return false;
+    }
+    let max_age = Duration::from_secs(max_age_days as u64 * 86400);
+    cache_path()
+        .and_then(|p| std::fs::metadata(p).ok())
+        .and_then(|m| m.modified().ok())
+        .and_then(|t| SystemTime::now().duration_since(t).ok())
+        .is_none_or(|age| age > max_age)
+}
+
+/// Fetch the dataset from `url` and keep it in the cache. Blocks — run in
+/// a background thread.
+pub fn download(url: &str) -> Result<Dataset> {
+    let agent = ureq::AgentBuilder::new()
+        .timeout(Duration::from_secs(DOWNLOAD_TIMEOUT_SECS))
+        .build();
+    let text = agent
+        .get(url)
+        .call()
+        .with_context(|| format!("download from {} failed", url))?
+        .into_string()?;
+    let dataset: Dataset =
+        serde_json::from_str(&text).with_context(|| format!("{} is no examples dataset", url))?;
+    if dataset.options.is_empty() {
+        bail!("{} has no examples", url);
+    }
+    let path = cache_path().context("no cache directory")?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(&path, text).with_context(|| format!("cannot write {}", path.display()))?;
+    Ok(dataset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundled_dataset() {
+        let bundled: Dataset = serde_json::from_str(BUNDLED).expect("bundled dataset parses");
+        assert!(!bundled.for_option("services.openssh.enable").is_empty());
+        assert!(bundled.for_option("no.such.option").is_empty());
+
+        // An older download doesn't replace the bundled set
+        let old = Dataset {
+            updated: "2020-01-01".into(),
+            options: HashMap::new(),
+        };
+        assert_eq!(newest(bundled.clone(), Some(old)).updated, bundled.updated);
+        let new = Dataset {
+            updated: "2099-01-01".into(),
+            options: HashMap::new(),
+        };
+        assert_eq!(newest(bundled, Some(new)).updated, "2099-01-01");
+    }
+}
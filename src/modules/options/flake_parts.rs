@@ -0,0 +1,105 @@
+//! flake-parts options — merged into the index when the user's flake uses it
+//!
+//! Detection: the flake's root inputs include `flake-parts` (flake.lock) or
+//! flake.nix calls `mkFlake`. The options are evaluated with
+//! `flake-parts.lib.evalFlakeModule`: once with the core modules, then once
+//! per input (and the flake itself) with its `flakeModule(s)` imported. A
+//! framework that fails to evaluate on its own only drops its own options.
+//!
+//! `perSystem` is a deferred module, so its sub-options (treefmt, devenv,
+//! pre-commit hooks, …) come out as `perSystem.<name>` paths.
+
+use super::{parse_options_json, NixOption};
+use std::process::Command;
+
+/// Flake directory using flake-parts, if any
+pub fn detect(flake_dirs: &[String]) -> Option<String> {
+    flake_dirs
+        .iter()
+        .find(|dir| {
+            let lock = std::fs::read_to_string(format!("{}/flake.lock", dir)).unwrap_or_default();
+            let nix = std::fs::read_to_string(format!("{}/flake.nix", dir)).unwrap_or_default();
+            root_inputs_include(&lock, "flake-parts") || nix.contains("mkFlake")
+        })
+        .cloned()
+}
+
+fn root_inputs_include(lock: &str, input: &str) -> bool {
+    let Ok(lock) = serde_json::from_str::<serde_json::Value>(lock) else {
+        return false;
+    };
+    let root = lock.get("root").and_then(|r| r.as_str()).unwrap_or("root");
+    lock.get("nodes")
+        .and_then(|n| n.get(root))
+        .and_then(|r| r.get("inputs"))
+        .and_then(|i| i.get(input))
+        .is_some()
+}
+
+/// Evaluate the flake-parts options of `flake_dir`. Blocking — run in
+/// background thread!
+pub fn load(flake_dir: &str) -> Option<Vec<NixOption>> {
+    let output = Command::new("nix")
+        .args([
+            "eval",
+            "--impure",
+            "--json",
+            "--extra-experimental-features",
+            "nix-command flakes",
+            "--expr",
+            &options_expr(flake_dir),
+        ])
+        .stdin(std::process::Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let mut options = parse_options_json(&String::from_utf8_lossy(&output.stdout))?;
+    for o in &mut options {
+        o.flake_module = true;
+    }
+    Some(options)
+}
+
+/// Option docs of every flake module as an options.json-shaped attrset
+fn options_expr(flake_dir: &str) -> String {
+    format!(
+        r#"
+let
+  f = builtins.getFlake (toString {dir});
+  fp = f.inputs.flake-parts;
+  lib = fp.inputs.nixpkgs-lib.lib or f.inputs.nixpkgs.lib;
+  modulesOf = i:
+    let r = builtins.tryEval ((builtins.attrValues (i.flakeModules or {{ }}))
+      ++ lib.optional (i ? flakeModule) i.flakeModule);
+    in if r.success then r.value else [ ];
+  docsFor = mods:
+    let
+      ev = fp.lib.evalFlakeModule {{ inputs = f.inputs // {{ self = f; }}; }}
+        {{ imports = mods; systems = [ ]; }};
+      docs = builtins.filter (d: (d.visible or true) != false && !(d.internal or false))
+        (lib.optionAttrSetToDocList ev.options);
+      r = builtins.tryEval (builtins.deepSeq (map (d: d.name) docs) docs);
+    in if r.success then r.value else [ ];
+  text = v: if builtins.isAttrs v && v ? text then v.text else v;
+  safe = v: let r = builtins.tryEval (builtins.deepSeq v v); in if r.success then r.value else null;
+  entry = d: {{
+    name = d.name;
+    value = {{
+      type = d.type or "unknown";
+      description = safe (text (d.description or ""));
+      declarations = safe (map (x: x.name or (toString x)) (d.declarations or [ ]));
+      readOnly = d.readOnly or false;
+    }} // lib.optionalAttrs (d ? default) {{ default = safe (text d.default); }}
+      // lib.optionalAttrs (d ? example) {{ example = safe (text d.example); }};
+  }};
+  # Core first, then one evaluation per input that ships flake modules
+  mods = builtins.filter (m: builtins.length m > 0)
+    (map modulesOf (builtins.attrValues f.inputs ++ [ f ]));
+in
+builtins.listToAttrs (map entry (lib.concatMap docsFor ([ [ ] ] ++ mods)))
+"#,
+        dir = serde_json::to_string(flake_dir).unwrap_or_default(),
+    )
+}
@@ -0,0 +1,136 @@
+//! Full-text search over option paths and descriptions.
+//!
+//! Text is split into words on anything that isn't a letter or digit, and
+//! every query word has to match a word of the path or the description
+//! (AND). Words match by prefix after a common English ending is cut off,
+//! so "sharing" finds "share" and "portal" finds "portals". A hit in the
+//! path counts more than one in the description; repeated description
+//! hits (up to three per word) rank an option above a passing mention.
+
+use ratatui::{style::Style, text::Span};
+use std::ops::Range;
+
+/// Endings cut off before comparing, longest first
+const SUFFIXES: &[&str] = &["ing", "ed", "es", "s", "e"];
+
+/// Query words, stemmed and without duplicates
+pub fn terms(query: &str) -> Vec<String> {
+    let mut terms: Vec<String> = Vec::new();
+    for (_, word) in words(query) {
+        let term = stem(&word.to_lowercase()).to_string();
+        if !terms.contains(&term) {
+            terms.push(term);
+        }
+    }
+    terms
+}
+
+/// Score of an option whose lowercased path and description contain every
+/// term; None if one is missing
+pub fn score(terms: &[String], path_lower: &str, desc_lower: &str) -> Option<i32> {
+    if terms.is_empty() {
+        return None;
+    }
+    let path_words: Vec<&str> = words(path_lower).into_iter().map(|(_, w)| w).collect();
+    let desc_words: Vec<&str> = words(desc_lower).into_iter().map(|(_, w)| w).collect();
+    let mut score = 0;
+    for term in terms {
+        let in_path = path_words.iter().any(|w| matches(w, term));
+        let in_desc = desc_words
+            .iter()
+            .filter(|w| matches(w, term))
+            .count()
+            .min(3) as i32;
+        if !in_path && in_desc == 0 {
+            return None;
+        }
+        score += if in_path { 40 } else { 0 } + 10 * in_desc;
+    }
+    Some(score)
+}
+
+/// Byte ranges of the words in `text` that match a term
+pub fn match_ranges(text: &str, terms: &[String]) -> Vec<Range<usize>> {
+    if terms.is_empty() {
+        return Vec::new();
+    }
+    words(text)
+        .into_iter()
+        .filter(|(_, w)| {
+            let lower = w.to_lowercase();
+            terms.iter().any(|t| matches(&lower, t))
+        })
+        .map(|(start, w)| start..start + w.len())
+        .collect()
+}
+
+/// `text` as spans with the matched words in `hit`
+pub fn highlight(text: &str, terms: &[String], base: Style, hit: Style) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut pos = 0;
+    for range in match_ranges(text, terms) {
+        if range.start > pos {
+            spans.push(Span::styled(text[pos..range.start].to_string(), base));
+        }
+        spans.push(Span::styled(text[range.clone()].to_string(), hit));
+        pos = range.end;
+    }
+    if pos < text.len() || spans.is_empty() {
+        spans.push(Span::styled(text[pos..].to_string(), base));
+    }
+    spans
+}
+
+/// Words with their byte offset
+fn words(text: &str) -> Vec<(usize, &str)> {
+    let mut words = Vec::new();
+    let mut start = None;
+    for (i, c) in text.char_indices() {
+        if c.is_alphanumeric() {
+            start.get_or_insert(i);
+        } else if let Some(s) = start.take() {
+            words.push((s, &text[s..i]));
+        }
+    }
+    if let Some(s) = start {
+        words.push((s, &text[s..]));
+    }
+    words
+}
+
+/// "sharing" → "shar"; short words stay as they are
+fn stem(word: &str) -> &str {
+    SUFFIXES
+        .iter()
+        .find_map(|s| word.strip_suffix(s).filter(|rest| rest.len() >= 3))
+        .unwrap_or(word)
+}
+
+/// Whether a lowercased word matches a query term
+fn matches(word: &str, term: &str) -> bool {
+    word.starts_with(term) || stem(word).starts_with(term)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fulltext_score_and_ranges() {
+        let terms = terms("Wayland screen sharing");
+        assert_eq!(terms, vec!["wayland", "screen", "shar"]);
+
+        let desc = "whether to enable xdg desktop portals, needed for screen sharing \
+                    and file pickers under wayland compositors.";
+        assert!(score(&terms, "xdg.portal.enable", desc).is_some());
+        assert!(score(&terms, "programs.sway.enable", "tiling wayland compositor").is_none());
+
+        let more = "screen sharing on wayland: share a screen, share a window";
+        assert!(score(&terms, "a", more) > score(&terms, "a", desc));
+
+        let text = "Enables Screen sharing.";
+        let ranges = match_ranges(text, &terms);
+        assert_eq!(ranges, vec![8..14, 15..22]);
+        assert_eq!(&text[ranges[1].clone()], "sharing");
+    }
+}
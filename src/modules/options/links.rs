@@ -0,0 +1,128 @@
+//! Shareable links to an option on search.nixos.org and in the NixOS manual.
+//!
+//! The channel comes from `/run/current-system/nixos-version`. Unstable
+//! systems carry the number of the *next* release ("25.11.20250612.abc"
+//! in June 2025), so a release that lies in the future means unstable.
+//! The manual only exists as stable and unstable, search.nixos.org has
+//! every supported release.
+
+use chrono::Datelike;
+
+/// Release channel of the running system
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Channel {
+    Release(String),
+    Unstable,
+}
+
+impl Channel {
+    /// Channel of the running system (unstable if unknown)
+    pub fn current() -> Self {
+        let today = chrono::Local::now().date_naive();
+        std::fs::read_to_string("/run/current-system/nixos-version")
+            .map(|v| Self::from_version(&v, today.year(), today.month()))
+            .unwrap_or(Channel::Unstable)
+    }
+
+    /// "24.05.20240501.abcdef (Uakari)" → 24.05, given today's year and month
+    fn from_version(version: &str, year: i32, month: u32) -> Self {
+        let mut parts = version.trim().split('.');
+        let (Some(yy), Some(mm)) = (parts.next(), parts.next()) else {
+            return Channel::Unstable;
+        };
+        let (Ok(y), Ok(m)) = (yy.parse::<i32>(), mm.get(..2).unwrap_or(mm).parse::<u32>()) else {
+            return Channel::Unstable;
+        };
+        if mm.contains("pre") || (2000 + y, m) > (year, month) {
+            Channel::Unstable
+        } else {
+            Channel::Release(format!("{}.{:02}", yy, m))
+        }
+    }
+
+    fn search_name(&self) -> &str {
+        match self {
+            Channel::Release(r) => r,
+            Channel::Unstable => "unstable",
+        }
+    }
+}
+
+/// search.nixos.org page showing the option
+pub fn search_url(path: &str, channel: &Channel) -> String {
+    let path = encode(path);
+    format!(
+        "https://search.nixos.org/options?channel={}&show={}&query={}",
+        channel.search_name(),
+        path,
+        path
+    )
+}
+
+/// The option's entry in the NixOS manual's option appendix
+pub fn manual_url(path: &str, channel: &Channel) -> String {
+    let manual = match channel {
+        Channel::Release(_) => "stable",
+        Channel::Unstable => "unstable",
+    };
+    // The manual's anchors turn `<name>` into `_name_`
+    let anchor: String = path
+        .chars()
+        .map(|c| {
+            if matches!(c, '<' | '>' | '*' | ' ') {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect();
+    format!(
+        "https://nixos.org/manual/nixos/{}/options#opt-{}",
+        manual,
+        encode(&anchor)
+    )
+}
+
+/// Percent-encode everything but unreserved URL characters
+fn encode(text: &str) -> String {
+    text.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'.' | b'-' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_channel_and_urls() {
+        let stable = Channel::from_version("24.05.20240501.abcdef (Uakari)", 2024, 8);
+        assert_eq!(stable, Channel::Release("24.05".into()));
+        assert_eq!(
+            Channel::from_version("25.11.20250612.abcdef (Xantusia)", 2025, 6),
+            Channel::Unstable
+        );
+        assert_eq!(
+            Channel::from_version("23.11pre-git", 2023, 12),
+            Channel::Unstable
+        );
+
+        let path = "services.nginx.virtualHosts.<name>.root";
+        assert_eq!(
+            search_url(path, &stable),
+            "https://search.nixos.org/options?channel=24.05\
+             &show=services.nginx.virtualHosts.%3Cname%3E.root\
+             &query=services.nginx.virtualHosts.%3Cname%3E.root"
+        );
+        assert_eq!(
+            manual_url(path, &Channel::Unstable),
+            "https://nixos.org/manual/nixos/unstable/options\
+             #opt-services.nginx.virtualHosts._name_.root"
+        );
+    }
+}
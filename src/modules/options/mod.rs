@@ -1,16 +1,25 @@
 //! Options Explorer module
 //!
 //! Search, browse, and discover all 20,000+ NixOS options.
-//! Three sub-tabs:
-//!   Search  — fuzzy search with detail view + current values
-//!   Browse  — tree navigation through the option hierarchy
-//!   Related — sibling options for the selected option
+//! Five sub-tabs:
+//!   Search    — fuzzy search with detail view + current values
+//!   Browse    — tree navigation through the option hierarchy
+//!   Related   — sibling options for the selected option
+//!   Overrides — everything the user's config sets (see `overrides.rs`)
+//!   Changed   — options that changed between the last two nixpkgs revs
 //!
-//! Data source: options.json from NixOS manual (pre-built or generated).
+//! Data source: options.json from NixOS manual (pre-built or generated),
+//! plus flake-parts module options when the flake uses it (see
+//! `flake_parts.rs`), cached on disk after the first successful load
+//! (see `cache.rs`).
 //! Current values loaded on-demand via nixos-option.
 
-use crate::config::Language;
+use crate::config::{Language, OptionExamplesConfig};
 use crate::i18n;
+use crate::modules::jobs::Job;
+use crate::modules::module::{Module, RenderCtx};
+use crate::modules::palette::PaletteCommand;
+use crate::session::ModuleSession;
 use crate::types::FlashMessage;
 use crate::ui::theme::Theme;
 use crate::ui::widgets;
@@ -23,10 +32,26 @@ use ratatui::{
     widgets::{Block, Borders, List, ListItem, Paragraph, Tabs, Wrap},
     Frame,
 };
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::mpsc;
 use std::time::Instant;
 
+mod cache;
+mod changes;
+mod examples;
+mod flake_parts;
+mod fulltext;
+mod links;
+mod overrides;
+mod query;
+
+pub(crate) use overrides::system_eval_command;
+
+use changes::{ChangeKind, ChangesReport};
+use overrides::{OverrideMsg, RawOverride};
+use query::{SearchFilter, SearchQuery};
+
 // ── Sub-tabs ──
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -35,11 +60,19 @@ pub enum OptSubTab {
     Search,
     Browse,
     Related,
+    Overrides,
+    Changed,
 }
 
 impl OptSubTab {
     pub fn all() -> &'static [OptSubTab] {
-        &[OptSubTab::Search, OptSubTab::Browse, OptSubTab::Related]
+        &[
+            OptSubTab::Search,
+            OptSubTab::Browse,
+            OptSubTab::Related,
+            OptSubTab::Overrides,
+            OptSubTab::Changed,
+        ]
     }
 
     pub fn index(&self) -> usize {
@@ -47,6 +80,19 @@ impl OptSubTab {
             OptSubTab::Search => 0,
             OptSubTab::Browse => 1,
             OptSubTab::Related => 2,
+            OptSubTab::Overrides => 3,
+            OptSubTab::Changed => 4,
+        }
+    }
+
+    pub fn label(&self, lang: Language) -> &'static str {
+        let s = i18n::get_strings(lang);
+        match self {
+            OptSubTab::Search => s.opt_tab_search,
+            OptSubTab::Browse => s.opt_tab_browse,
+            OptSubTab::Related => s.opt_tab_related,
+            OptSubTab::Overrides => s.opt_tab_overrides,
+            OptSubTab::Changed => s.opt_tab_changed,
         }
     }
 
@@ -69,7 +115,7 @@ impl OptSubTab {
 
 // ── NixOS option data ──
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NixOption {
     pub path: String,
     pub type_str: String,
@@ -78,6 +124,9 @@ pub struct NixOption {
     pub example_str: Option<String>,
     pub declared_in: Vec<String>,
     pub read_only: bool,
+    /// Declared by a flake-parts module, not NixOS (no current value)
+    #[serde(default)]
+    pub flake_module: bool,
 }
 
 // ── Tree node for Browse tab ──
@@ -111,6 +160,17 @@ pub struct CurrentValue {
     pub error: Option<String>,
 }
 
+// ── Option set by the user's config ──
+
+#[derive(Debug, Clone)]
+pub struct OptOverride {
+    pub path: String,
+    pub value: Option<String>,
+    pub files: Vec<String>,
+    /// Index into `options` (None if the option isn't in options.json)
+    pub option_idx: Option<usize>,
+}
+
 // ── Module state ──
 
 pub struct OptionsState {
@@ -122,6 +182,8 @@ pub struct OptionsState {
     pub loading: bool,
     pub loading_phase: String,
     pub loading_start: Option<Instant>,
+    /// When the option set was loaded (for TTL / auto-refresh)
+    pub loaded_at: Option<Instant>,
     pub error_message: Option<String>,
     load_rx: Option<mpsc::Receiver<LoadStatus>>,
 
@@ -129,6 +191,8 @@ pub struct OptionsState {
     pub search_active: bool,
     pub search_query: String,
     pub search_results: Vec<usize>, // indices into options vec
+    /// Full-text terms of the last search, highlighted in results and detail
+    pub search_terms: Vec<String>,
     pub search_selected: usize,
     pub search_scroll: usize,
 
@@ -141,6 +205,12 @@ pub struct OptionsState {
     current_value_rx: Option<mpsc::Receiver<CurrentValue>>,
     current_value_path: String,
 
+    // Usage examples (dataset loaded with the first detail view)
+    pub examples: Option<examples::Dataset>,
+    pub examples_config: OptionExamplesConfig,
+    pub examples_downloading: bool,
+    examples_rx: Option<mpsc::Receiver<Result<examples::Dataset, String>>>,
+
     // Browse tab
     pub tree_rows: Vec<TreeRow>,
     pub tree_selected: usize,
@@ -155,9 +225,35 @@ pub struct OptionsState {
     pub related_scroll: usize,
     pub related_for_path: String,
 
+    // Overrides tab (evaluated lazily, one `nix eval` for the whole config)
+    pub overrides: Vec<OptOverride>,
+    pub overrides_loaded: bool,
+    pub overrides_loading: bool,
+    pub overrides_phase: String,
+    pub overrides_progress: Option<(usize, usize)>,
+    pub overrides_start: Option<Instant>,
+    pub overrides_error: Option<String>,
+    pub overrides_selected: usize,
+    overrides_rx: Option<mpsc::Receiver<OverrideMsg>>,
+
+    // Changed tab (diff between the nixpkgs revs of the last two rebuilds)
+    pub changes: Option<ChangesReport>,
+    pub changes_loading: bool,
+    pub changes_error: Option<String>,
+    pub changes_selected: usize,
+    /// Show only one kind of change (None = all)
+    pub changes_filter: Option<ChangeKind>,
+    changes_rx: Option<mpsc::Receiver<Result<ChangesReport, String>>>,
+
+    /// Option to open once the option set is loaded (drill-down from
+    /// other modules)
+    pending_reveal: Option<String>,
+
     pub lang: Language,
     pub flash_message: Option<FlashMessage>,
     pub config_path: Option<String>,
+    /// `nixosConfigurations` attribute from the config (None = host name)
+    pub nixos_host: Option<String>,
 }
 
 impl OptionsState {
@@ -169,11 +265,13 @@ impl OptionsState {
             loading: false,
             loading_phase: String::new(),
             loading_start: None,
+            loaded_at: None,
             error_message: None,
             load_rx: None,
             search_active: false,
             search_query: String::new(),
             search_results: Vec::new(),
+            search_terms: Vec::new(),
             search_selected: 0,
             search_scroll: 0,
             detail_open: false,
@@ -183,6 +281,10 @@ impl OptionsState {
             current_value_loading: false,
             current_value_rx: None,
             current_value_path: String::new(),
+            examples: None,
+            examples_config: OptionExamplesConfig::default(),
+            examples_downloading: false,
+            examples_rx: None,
             tree_rows: Vec::new(),
             tree_selected: 0,
             tree_scroll: 0,
@@ -192,9 +294,61 @@ impl OptionsState {
             related_selected: 0,
             related_scroll: 0,
             related_for_path: String::new(),
+            overrides: Vec::new(),
+            overrides_loaded: false,
+            overrides_loading: false,
+            overrides_phase: String::new(),
+            overrides_progress: None,
+            overrides_start: None,
+            overrides_error: None,
+            overrides_selected: 0,
+            overrides_rx: None,
+            changes: None,
+            changes_loading: false,
+            changes_error: None,
+            changes_selected: 0,
+            changes_filter: None,
+            changes_rx: None,
+            pending_reveal: None,
             lang: Language::English,
             flash_message: None,
             config_path: None,
+            nixos_host: None,
+        }
+    }
+
+    /// First of `namespaces` that has options (the first one while the
+    /// option set isn't loaded yet)
+    pub fn pick_namespace<'a>(&self, namespaces: &'a [String]) -> Option<&'a String> {
+        if !self.loaded {
+            return namespaces.first();
+        }
+        namespaces.iter().find(|ns| {
+            self.options.iter().any(|o| {
+                o.path
+                    .strip_prefix(ns.as_str())
+                    .is_some_and(|r| r.starts_with('.'))
+            })
+        })
+    }
+
+    /// Show `path` in the Search tab with its detail open (loads the
+    /// option set first if needed)
+    pub fn reveal_option(&mut self, path: &str) {
+        self.sub_tab = OptSubTab::Search;
+        self.search_active = false;
+        self.search_query = path.to_string();
+        if self.options.is_empty() {
+            self.pending_reveal = Some(path.to_string());
+            self.ensure_loaded();
+            return;
+        }
+        self.run_search();
+        if let Some(idx) = self.options.iter().position(|o| o.path == path) {
+            if let Some(pos) = self.search_results.iter().position(|&i| i == idx) {
+                self.search_selected = pos;
+            }
+            self.open_detail(idx);
         }
     }
 
@@ -212,12 +366,79 @@ impl OptionsState {
         self.load_rx = Some(rx);
         let lang = self.lang;
         let config_path = self.config_path.clone();
+        let nixos_host = self.nixos_host.clone();
+
+        std::thread::spawn(move || {
+            load_options_background(tx, lang, config_path.as_deref(), nixos_host.as_deref());
+        });
+    }
+
+    /// Reload in the background, keeping the current options on screen
+    pub fn refresh_in_background(&mut self) {
+        if self.load_rx.is_some() || !self.loaded {
+            return;
+        }
+        let (tx, rx) = mpsc::channel();
+        self.load_rx = Some(rx);
+        let lang = self.lang;
+        let config_path = self.config_path.clone();
+        let nixos_host = self.nixos_host.clone();
 
         std::thread::spawn(move || {
-            load_options_background(tx, lang, config_path.as_deref());
+            load_options_background(tx, lang, config_path.as_deref(), nixos_host.as_deref());
         });
     }
 
+    /// Swap in a freshly loaded option set; indices into the old one are
+    /// remapped by path
+    fn replace_options(&mut self, options: Vec<NixOption>) {
+        let same = self.options.len() == options.len()
+            && self
+                .options
+                .iter()
+                .zip(&options)
+                .all(|(a, b)| a.path == b.path);
+        let detail_path = self
+            .detail_option_idx
+            .and_then(|i| self.options.get(i))
+            .map(|o| o.path.clone());
+        self.options = options;
+        self.loaded_at = Some(Instant::now());
+        if same {
+            return;
+        }
+
+        let index: HashMap<&str, usize> = self
+            .options
+            .iter()
+            .enumerate()
+            .map(|(i, o)| (o.path.as_str(), i))
+            .collect();
+        for o in &mut self.overrides {
+            o.option_idx = index.get(o.path.as_str()).copied();
+        }
+        self.detail_option_idx = detail_path.and_then(|p| index.get(p.as_str()).copied());
+        if self.detail_option_idx.is_none() {
+            self.detail_open = false;
+        }
+        let related = index.get(self.related_for_path.as_str()).copied();
+
+        if self.tree_built {
+            self.rebuild_tree_rows();
+            self.tree_selected = self
+                .tree_selected
+                .min(self.tree_rows.len().saturating_sub(1));
+        }
+        self.run_search();
+        match related {
+            Some(idx) => self.build_related(idx),
+            None => {
+                self.related_options.clear();
+                self.related_for_path.clear();
+            }
+        }
+    }
+
     /// Poll background loader
     pub fn poll_load(&mut self) {
         if let Some(rx) = &self.load_rx {
@@ -228,11 +449,21 @@ impl OptionsState {
                     }
                     Ok(LoadStatus::Done(options)) => {
                         let count = options.len();
-                        self.options = options;
+                        // Searching again resets the selection; keep it for a
+                        // search restored from the last session
+                        let selected = self.search_selected;
+                        self.replace_options(options);
+                        self.search_selected =
+                            selected.min(self.search_results.len().saturating_sub(1));
                         self.loaded = true;
                         self.loading = false;
                         self.load_rx = None;
                         self.loading_phase = format!("{} options loaded", count);
+                        // A sub-tab restored from the last session
+                        self.ensure_sub_tab_loaded();
+                        if let Some(path) = self.pending_reveal.take() {
+                            self.reveal_option(&path);
+                        }
                         return;
                     }
                     Ok(LoadStatus::Error(msg)) => {
@@ -260,6 +491,9 @@ impl OptionsState {
             }
         }
 
+        self.poll_overrides();
+        self.poll_changes();
+
         // Poll current value
         if let Some(rx) = &self.current_value_rx {
             match rx.try_recv() {
@@ -279,27 +513,190 @@ impl OptionsState {
         }
     }
 
-    /// Run fuzzy search over loaded options
+    /// Start evaluating the user's overrides (first visit or manual refresh)
+    fn start_overrides(&mut self) {
+        if self.overrides_loading {
+            return;
+        }
+        self.overrides_loading = true;
+        self.overrides_error = None;
+        self.overrides_progress = None;
+        self.overrides_phase = String::new();
+        self.overrides_start = Some(Instant::now());
+
+        let (tx, rx) = mpsc::channel();
+        self.overrides_rx = Some(rx);
+        let lang = self.lang;
+        let config_path = self.config_path.clone();
+        let nixos_host = self.nixos_host.clone();
+
+        std::thread::spawn(move || {
+            overrides::evaluate_overrides(tx, lang, config_path.as_deref(), nixos_host.as_deref());
+        });
+    }
+
+    /// Lazy-load what the current sub-tab shows
+    fn ensure_sub_tab_loaded(&mut self) {
+        match self.sub_tab {
+            OptSubTab::Browse => self.ensure_tree_built(),
+            OptSubTab::Overrides => self.ensure_overrides_loaded(),
+            OptSubTab::Changed => self.ensure_changes_loaded(),
+            OptSubTab::Search | OptSubTab::Related => {}
+        }
+    }
+
+    fn ensure_overrides_loaded(&mut self) {
+        if !self.overrides_loaded {
+            self.start_overrides();
+        }
+    }
+
+    fn poll_overrides(&mut self) {
+        let Some(rx) = &self.overrides_rx else {
+            return;
+        };
+        loop {
+            match rx.try_recv() {
+                Ok(OverrideMsg::Phase(msg)) => self.overrides_phase = msg,
+                Ok(OverrideMsg::Progress(done, total)) => {
+                    self.overrides_progress = Some((done, total));
+                }
+                Ok(OverrideMsg::Done(raw)) => {
+                    self.overrides = self.resolve_overrides(raw);
+                    self.overrides_selected = 0;
+                    self.finish_overrides();
+                    return;
+                }
+                Ok(OverrideMsg::Error(msg)) => {
+                    self.overrides_error = Some(msg);
+                    self.finish_overrides();
+                    return;
+                }
+                Err(mpsc::TryRecvError::Empty) => return,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.finish_overrides();
+                    return;
+                }
+            }
+        }
+    }
+
+    fn finish_overrides(&mut self) {
+        self.overrides_loading = false;
+        self.overrides_loaded = true;
+        self.overrides_rx = None;
+    }
+
+    /// Link evaluated overrides to options.json entries and drop values
+    /// that are explicitly set but equal to the default
+    fn resolve_overrides(&self, raw: Vec<RawOverride>) -> Vec<OptOverride> {
+        let index: HashMap<&str, usize> = self
+            .options
+            .iter()
+            .enumerate()
+            .map(|(i, o)| (o.path.as_str(), i))
+            .collect();
+
+        raw.into_iter()
+            .filter_map(|r| {
+                let option_idx = index.get(r.path.as_str()).copied();
+                let default = option_idx.and_then(|i| self.options[i].default_str.as_deref());
+                if r.value.is_some() && r.value.as_deref() == default {
+                    return None;
+                }
+                Some(OptOverride {
+                    path: r.path,
+                    value: r.value,
+                    files: r.files,
+                    option_idx,
+                })
+            })
+            .collect()
+    }
+
+    fn start_changes(&mut self) {
+        if self.changes_loading {
+            return;
+        }
+        self.changes_loading = true;
+        self.changes_error = None;
+
+        let (tx, rx) = mpsc::channel();
+        self.changes_rx = Some(rx);
+        let lang = self.lang;
+
+        std::thread::spawn(move || {
+            let _ = tx.send(changes::compute_changes(lang));
+        });
+    }
+
+    fn ensure_changes_loaded(&mut self) {
+        if self.changes.is_none() && self.changes_error.is_none() {
+            self.start_changes();
+        }
+    }
+
+    fn poll_changes(&mut self) {
+        let Some(rx) = &self.changes_rx else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(Ok(report)) => {
+                self.changes = Some(report);
+                self.changes_selected = 0;
+            }
+            Ok(Err(msg)) => self.changes_error = Some(msg),
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => {}
+        }
+        self.changes_loading = false;
+        self.changes_rx = None;
+    }
+
+    /// Indices into `changes.changes` matching the active filter
+    pub fn visible_changes(&self) -> Vec<usize> {
+        let Some(report) = &self.changes else {
+            return Vec::new();
+        };
+        report
+            .changes
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| self.changes_filter.is_none_or(|k| c.kind == k))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Run fuzzy and full-text search over loaded options
     fn run_search(&mut self) {
-        let query = self.search_query.trim().to_lowercase();
-        if query.is_empty() {
+        let parsed = SearchQuery::parse(&self.search_query);
+        self.search_terms = fulltext::terms(&parsed.text);
+        if parsed.is_empty() {
             self.search_results.clear();
             return;
         }
+        let query = parsed.text.as_str();
+        let terms = &self.search_terms;
 
         let mut scored: Vec<(usize, i32)> = self
             .options
             .iter()
             .enumerate()
+            .filter(|(_, opt)| parsed.matches_filters(opt))
             .filter_map(|(i, opt)| {
+                // Filters only: list everything that passes, by path
+                if query.is_empty() {
+                    return Some((i, 0));
+                }
+
                 let path_lower = opt.path.to_lowercase();
                 let desc_lower = opt.description.to_lowercase();
 
                 // Exact substring match in path (highest priority)
-                if path_lower.contains(&query) {
+                if path_lower.contains(query) {
                     let score = if path_lower == query {
                         1000
-                    } else if path_lower.starts_with(&query) {
+                    } else if path_lower.starts_with(query) {
                         900
                     } else {
                         // Bonus for shorter paths (more specific matches)
@@ -308,13 +705,18 @@ impl OptionsState {
                     return Some((i, score));
                 }
 
+                // Every query word in path or description ("wayland screen sharing")
+                if let Some(hits) = fulltext::score(terms, &path_lower, &desc_lower) {
+                    return Some((i, 300 + hits.min(400) - (opt.path.len() as i32).min(100)));
+                }
+
                 // Match in description
-                if desc_lower.contains(&query) {
+                if desc_lower.contains(query) {
                     return Some((i, 200 - (opt.path.len() as i32).min(100)));
                 }
 
                 // Fuzzy: all query chars appear in order in path
-                if fuzzy_match(&query, &path_lower) {
+                if fuzzy_match(query, &path_lower) {
                     return Some((i, 100 - (opt.path.len() as i32).min(50)));
                 }
 
@@ -478,9 +880,10 @@ impl OptionsState {
         self.detail_scroll = 0;
         self.current_value = None;
         self.current_value_loading = false;
+        self.ensure_examples();
 
-        // Start loading current value
-        if option_idx < self.options.len() {
+        // Start loading current value (flake-parts options aren't in the system config)
+        if option_idx < self.options.len() && !self.options[option_idx].flake_module {
             let path = self.options[option_idx].path.clone();
             self.current_value_path = path.clone();
             self.current_value_loading = true;
@@ -496,6 +899,61 @@ impl OptionsState {
         }
     }
 
+    fn ensure_examples(&mut self) {
+        if self.examples.is_none() {
+            self.examples = Some(examples::load());
+        }
+        if !self.examples_config.url.trim().is_empty()
+            && examples::is_stale(self.examples_config.max_age_days)
+        {
+            self.download_examples();
+        }
+    }
+
+    /// Fetch the full examples dataset from `[option_examples] url`
+    fn download_examples(&mut self) {
+        let s = i18n::get_strings(self.lang);
+        let url = self.examples_config.url.trim().to_string();
+        if url.is_empty() {
+            self.flash_message = Some(FlashMessage::new(s.opt_examples_no_url.into(), true));
+            return;
+        }
+        if self.examples_downloading {
+            return;
+        }
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(examples::download(&url).map_err(|e| e.to_string()));
+        });
+        self.examples_rx = Some(rx);
+        self.examples_downloading = true;
+    }
+
+    pub fn poll_examples(&mut self) {
+        let Some(rx) = &self.examples_rx else {
+            return;
+        };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => Err("download thread died".into()),
+        };
+        self.examples_rx = None;
+        self.examples_downloading = false;
+        let s = i18n::get_strings(self.lang);
+        self.flash_message = Some(match result {
+            Ok(dataset) => {
+                let msg = s
+                    .opt_examples_updated
+                    .replacen("{}", &dataset.options.len().to_string(), 1)
+                    .replacen("{}", &dataset.updated, 1);
+                self.examples = Some(dataset);
+                FlashMessage::new(msg, false)
+            }
+            Err(e) => FlashMessage::new(s.opt_examples_failed.replace("{}", &e), true),
+        });
+    }
+
     /// Build related options for the Related tab
     fn build_related(&mut self, option_idx: usize) {
         if option_idx >= self.options.len() {
@@ -541,6 +999,124 @@ impl OptionsState {
         self.sub_tab = OptSubTab::Related;
     }
 
+    /// Search and its selection for the session file
+    pub fn session(&self) -> ModuleSession {
+        ModuleSession {
+            query: self.search_query.clone(),
+            selected: self.search_selected,
+            ..Default::default()
+        }
+    }
+
+    /// The search runs again once the options are loaded
+    pub fn restore_session(&mut self, saved: &ModuleSession) {
+        self.search_query = saved.query.clone();
+        self.search_selected = saved.selected;
+    }
+
+    /// Actions offered in the command palette
+    /// Running background work, for the jobs overlay
+    pub fn jobs(&self, s: &i18n::Strings) -> Vec<Job> {
+        let mut jobs = Vec::new();
+        if self.loading {
+            jobs.push(Job::new("load", s.job_opt_load, true).detail(self.loading_phase.clone()));
+        }
+        if self.overrides_loading {
+            let mut job = Job::new("overrides", s.job_opt_overrides, true)
+                .detail(self.overrides_phase.clone());
+            if let Some((done, total)) = self.overrides_progress {
+                job = job.progress(done, total);
+            }
+            jobs.push(job);
+        }
+        if self.changes_loading {
+            jobs.push(Job::new("changes", s.job_opt_changes, true));
+        }
+        if self.examples_downloading {
+            jobs.push(Job::new("examples", s.job_opt_examples, true));
+        }
+        jobs
+    }
+
+    /// Stop waiting for a job from `jobs()`; its result is discarded
+    pub fn cancel_job(&mut self, id: &str) {
+        let s = i18n::get_strings(self.lang);
+        match id {
+            "load" => {
+                self.load_rx = None;
+                self.loading = false;
+                if !self.loaded {
+                    self.loaded = true;
+                    self.error_message = Some(s.jobs_cancelled_error.to_string());
+                }
+            }
+            "overrides" => {
+                self.overrides_rx = None;
+                self.overrides_loading = false;
+                self.overrides_loaded = true;
+                self.overrides_error = Some(s.jobs_cancelled_error.to_string());
+            }
+            "changes" => {
+                self.changes_rx = None;
+                self.changes_loading = false;
+                self.changes_error = Some(s.jobs_cancelled_error.to_string());
+            }
+            "examples" => {
+                self.examples_rx = None;
+                self.examples_downloading = false;
+            }
+            _ => {}
+        }
+    }
+
+    pub fn palette_commands(&self, s: &i18n::Strings) -> Vec<PaletteCommand> {
+        let overrides = Some(OptSubTab::Overrides.index());
+        vec![
+            PaletteCommand::new(
+                s.pal_opt_search,
+                Some(OptSubTab::Search.index()),
+                vec![KeyCode::Char('/')],
+            ),
+            PaletteCommand::new(
+                s.pal_opt_copy_link,
+                Some(OptSubTab::Search.index()),
+                vec![KeyCode::Char('y')],
+            ),
+            PaletteCommand::new(s.pal_opt_browse, Some(OptSubTab::Browse.index()), vec![]),
+            PaletteCommand::new(s.pal_opt_overrides, overrides, vec![]),
+            PaletteCommand::new(s.pal_opt_reeval, overrides, vec![KeyCode::Char('R')]),
+            PaletteCommand::new(s.pal_opt_changed, Some(OptSubTab::Changed.index()), vec![]),
+        ]
+    }
+
+    /// Copy the search.nixos.org (or, with `manual`, NixOS manual) link of
+    /// an option for the release the system runs
+    fn copy_link(&mut self, opt_idx: usize, manual: bool) {
+        let Some(opt) = self.options.get(opt_idx) else {
+            return;
+        };
+        let s = i18n::get_strings(self.lang);
+        if opt.flake_module {
+            self.flash_message = Some(FlashMessage::new(s.opt_link_none.to_string(), false));
+            return;
+        }
+        let channel = links::Channel::current();
+        let url = if manual {
+            links::manual_url(&opt.path, &channel)
+        } else {
+            links::search_url(&opt.path, &channel)
+        };
+        self.flash_message = Some(match crate::clipboard::write(&url) {
+            Some(tool) => FlashMessage::new(
+                s.opt_link_copied
+                    .replacen("{}", &url, 1)
+                    .replacen("{}", tool, 1),
+                true,
+            ),
+            None => FlashMessage::new(s.rb_log_no_clipboard.to_string(), false),
+        });
+    }
+
     pub fn handle_key(&mut self, key: KeyEvent) -> Result<bool> {
         // Detail overlay captures all keys
         if self.detail_open {
@@ -558,6 +1134,12 @@ impl OptionsState {
                         self.build_related(idx);
                     }
                 }
+                KeyCode::Char('U') => self.download_examples(),
+                KeyCode::Char('y') | KeyCode::Char('Y') => {
+                    if let Some(idx) = self.detail_option_idx {
+                        self.copy_link(idx, key.code == KeyCode::Char('Y'));
+                    }
+                }
                 KeyCode::Char('j') | KeyCode::Down => {
                     self.detail_scroll = self.detail_scroll.saturating_add(1);
                 }
@@ -573,16 +1155,12 @@ impl OptionsState {
         match key.code {
             KeyCode::Char('[') => {
                 self.sub_tab = self.sub_tab.prev();
-                if self.sub_tab == OptSubTab::Browse {
-                    self.ensure_tree_built();
-                }
+                self.ensure_sub_tab_loaded();
                 return Ok(true);
             }
             KeyCode::Char(']') => {
                 self.sub_tab = self.sub_tab.next();
-                if self.sub_tab == OptSubTab::Browse {
-                    self.ensure_tree_built();
-                }
+                self.ensure_sub_tab_loaded();
                 return Ok(true);
             }
             _ => {}
@@ -592,7 +1170,97 @@ impl OptionsState {
             OptSubTab::Search => self.handle_search_key(key),
             OptSubTab::Browse => self.handle_browse_key(key),
             OptSubTab::Related => self.handle_related_key(key),
+            OptSubTab::Overrides => self.handle_overrides_key(key),
+            OptSubTab::Changed => self.handle_changes_key(key),
+        }
+    }
+
+    fn handle_changes_key(&mut self, key: KeyEvent) -> Result<bool> {
+        let visible = self.visible_changes();
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                if !visible.is_empty() {
+                    self.changes_selected = (self.changes_selected + 1).min(visible.len() - 1);
+                }
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.changes_selected = self.changes_selected.saturating_sub(1);
+            }
+            KeyCode::Char('g') => self.changes_selected = 0,
+            KeyCode::Char('G') => self.changes_selected = visible.len().saturating_sub(1),
+            KeyCode::Char('f') => {
+                self.changes_filter = match self.changes_filter {
+                    None => Some(ChangeKind::Default),
+                    Some(ChangeKind::Default) => Some(ChangeKind::Type),
+                    Some(ChangeKind::Type) => Some(ChangeKind::Added),
+                    Some(ChangeKind::Added) => Some(ChangeKind::Removed),
+                    Some(ChangeKind::Removed) => Some(ChangeKind::Moved),
+                    Some(ChangeKind::Moved) => None,
+                };
+                self.changes_selected = 0;
+            }
+            KeyCode::Enter => {
+                let path = self.changes.as_ref().and_then(|r| {
+                    visible
+                        .get(self.changes_selected)
+                        .map(|&i| r.changes[i].path.clone())
+                });
+                if let Some(idx) = path.and_then(|p| self.options.iter().position(|o| o.path == p))
+                {
+                    self.open_detail(idx);
+                }
+            }
+            KeyCode::Char('R') => {
+                self.changes = None;
+                self.changes_error = None;
+                self.start_changes();
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn handle_overrides_key(&mut self, key: KeyEvent) -> Result<bool> {
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                if !self.overrides.is_empty() {
+                    self.overrides_selected =
+                        (self.overrides_selected + 1).min(self.overrides.len() - 1);
+                }
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.overrides_selected = self.overrides_selected.saturating_sub(1);
+            }
+            KeyCode::Char('g') => {
+                self.overrides_selected = 0;
+            }
+            KeyCode::Char('G') => {
+                self.overrides_selected = self.overrides.len().saturating_sub(1);
+            }
+            KeyCode::Enter => {
+                if let Some(idx) = self
+                    .overrides
+                    .get(self.overrides_selected)
+                    .and_then(|o| o.option_idx)
+                {
+                    self.open_detail(idx);
+                }
+            }
+            KeyCode::Char('r') => {
+                if let Some(idx) = self
+                    .overrides
+                    .get(self.overrides_selected)
+                    .and_then(|o| o.option_idx)
+                {
+                    self.build_related(idx);
+                }
+            }
+            KeyCode::Char('R') => {
+                self.start_overrides();
+            }
+            _ => return Ok(false),
         }
+        Ok(true)
     }
 
     fn handle_search_key(&mut self, key: KeyEvent) -> Result<bool> {
@@ -651,6 +1319,11 @@ impl OptionsState {
                     self.build_related(opt_idx);
                 }
             }
+            KeyCode::Char('y') | KeyCode::Char('Y') => {
+                if let Some(&opt_idx) = self.search_results.get(self.search_selected) {
+                    self.copy_link(opt_idx, key.code == KeyCode::Char('Y'));
+                }
+            }
             KeyCode::Char('n') => {
                 self.search_query.clear();
                 self.search_results.clear();
@@ -721,6 +1394,16 @@ impl OptionsState {
                     }
                 }
             }
+            KeyCode::Char('y') | KeyCode::Char('Y') => {
+                if let Some(idx) = self
+                    .tree_rows
+                    .get(self.tree_selected)
+                    .filter(|row| row.is_leaf)
+                    .and_then(|row| row.option_idx)
+                {
+                    self.copy_link(idx, key.code == KeyCode::Char('Y'));
+                }
+            }
             _ => return Ok(false),
         }
         Ok(true)
@@ -753,6 +1436,11 @@ impl OptionsState {
                     self.open_detail(opt_idx);
                 }
             }
+            KeyCode::Char('y') | KeyCode::Char('Y') => {
+                if let Some(&opt_idx) = self.related_options.get(self.related_selected) {
+                    self.copy_link(opt_idx, key.code == KeyCode::Char('Y'));
+                }
+            }
             _ => return Ok(false),
         }
         Ok(true)
@@ -787,44 +1475,115 @@ fn safe_truncate(s: &str, max_bytes: usize) -> &str {
 
 // ── Background loading ──
 
+/// Option paths of the on-disk index for other modules; None until the
+/// Options Explorer has loaded once for this system
+pub fn cached_paths(config_path: Option<&str>, nixos_host: Option<&str>) -> Option<Vec<String>> {
+    let options = cache::load(&cache::CacheKey::current(config_path, nixos_host))?;
+    Some(options.into_iter().map(|o| o.path).collect())
+}
+
 fn load_options_background(
     tx: mpsc::Sender<LoadStatus>,
     lang: Language,
     config_path: Option<&str>,
+    nixos_host: Option<&str>,
 ) {
     let s = crate::i18n::get_strings(lang);
-    use std::process::Command;
-
-    // Phase 1: Try pre-built options.json (fast path)
-    let _ = tx.send(LoadStatus::Phase(s.opt_phase_prebuilt.to_string()));
 
-    // Try standard NixOS documentation path
-    let doc_path = "/run/current-system/sw/share/doc/nixos/options.json";
-    if let Some(options) = try_load_options_json(doc_path) {
+    // Phase 0: On-disk cache from a previous launch (instant)
+    let _ = tx.send(LoadStatus::Phase(s.opt_phase_cache.to_string()));
+    let key = cache::CacheKey::current(config_path, nixos_host);
+    if let Some(options) = cache::load(&key) {
         let _ = tx.send(LoadStatus::Done(options));
         return;
     }
 
-    // Phase 2: Try building options.json
-    let _ = tx.send(LoadStatus::Phase(s.opt_building_db.to_string()));
+    match load_options_uncached(&tx, lang, config_path, nixos_host) {
+        Some(options) => {
+            // Best effort — a failed cache write only costs speed next launch
+            let _ = cache::store(&key, &options);
+            let _ = tx.send(LoadStatus::Done(options));
+        }
+        None => {
+            let _ = tx.send(LoadStatus::Error(s.opt_load_error.to_string()));
+        }
+    }
+}
 
-    // Try nix-build for channels
-    let result = Command::new("nix-build")
-        .args([
-            "<nixpkgs/nixos/release.nix>",
-            "-A",
-            "options",
-            "--no-out-link",
-        ])
-        .output();
+/// Where the user's flake may live, custom config path first
+fn flake_dirs(config_path: Option<&str>) -> Vec<String> {
+    crate::nix::detect::flake_candidates(config_path)
+}
+
+/// NixOS options plus flake-parts options when the flake uses flake-parts
+fn load_options_uncached(
+    tx: &mpsc::Sender<LoadStatus>,
+    lang: Language,
+    config_path: Option<&str>,
+    nixos_host: Option<&str>,
+) -> Option<Vec<NixOption>> {
+    let s = crate::i18n::get_strings(lang);
+    let nixos = load_nixos_options(tx, lang, config_path, nixos_host);
+
+    let Some(dir) = flake_parts::detect(&flake_dirs(config_path)) else {
+        return nixos;
+    };
+    let _ = tx.send(LoadStatus::Phase(s.opt_phase_flake_parts.to_string()));
+    let extra = flake_parts::load(&dir);
+
+    let mut options = match (nixos, extra) {
+        (None, None) => return None,
+        (Some(options), None) | (None, Some(options)) => return Some(options),
+        (Some(options), Some(extra)) => {
+            let known: std::collections::HashSet<String> =
+                options.iter().map(|o| o.path.clone()).collect();
+            let mut options = options;
+            options.extend(extra.into_iter().filter(|o| !known.contains(&o.path)));
+            options
+        }
+    };
+    options.sort_by(|a, b| a.path.cmp(&b.path));
+    Some(options)
+}
+
+/// Load NixOS options from the slow sources, reporting phases along the way
+fn load_nixos_options(
+    tx: &mpsc::Sender<LoadStatus>,
+    lang: Language,
+    config_path: Option<&str>,
+    nixos_host: Option<&str>,
+) -> Option<Vec<NixOption>> {
+    let s = crate::i18n::get_strings(lang);
+    use std::process::Command;
+
+    // Phase 1: Try pre-built options.json (fast path)
+    let _ = tx.send(LoadStatus::Phase(s.opt_phase_prebuilt.to_string()));
+
+    // Try standard NixOS documentation path
+    let doc_path = "/run/current-system/sw/share/doc/nixos/options.json";
+    if let Some(options) = try_load_options_json(doc_path) {
+        return Some(options);
+    }
+
+    // Phase 2: Try building options.json
+    let _ = tx.send(LoadStatus::Phase(s.opt_building_db.to_string()));
+
+    // Try nix-build for channels
+    let result = Command::new("nix-build")
+        .args([
+            "<nixpkgs/nixos/release.nix>",
+            "-A",
+            "options",
+            "--no-out-link",
+        ])
+        .output();
 
     if let Ok(output) = result {
         if output.status.success() {
             let store_path = String::from_utf8_lossy(&output.stdout).trim().to_string();
             let json_path = format!("{}/share/doc/nixos/options.json", store_path);
             if let Some(options) = try_load_options_json(&json_path) {
-                let _ = tx.send(LoadStatus::Done(options));
-                return;
+                return Some(options);
             }
         }
     }
@@ -832,19 +1591,7 @@ fn load_options_background(
     // Phase 3: Try flakes-based build
     let _ = tx.send(LoadStatus::Phase(s.opt_trying_flakes.to_string()));
 
-    let home = std::env::var("HOME").unwrap_or_default();
-    let mut flake_dirs: Vec<String> = Vec::new();
-    if let Some(p) = config_path {
-        flake_dirs.push(p.to_string());
-    }
-    flake_dirs.extend([
-        "/etc/nixos".to_string(),
-        format!("{}/.config/nixos", home),
-        format!("{}/nixos", home),
-        format!("{}/.nixos", home),
-    ]);
-
-    for flake_dir in &flake_dirs {
+    for flake_dir in &flake_dirs(config_path) {
         let flake_nix = format!("{}/flake.nix", flake_dir);
         if !std::path::Path::new(&flake_nix).exists() {
             continue;
@@ -856,7 +1603,7 @@ fn load_options_background(
                 &format!(
                     "{}#nixosConfigurations.{}.config.system.build.manual.optionsJSON",
                     flake_dir,
-                    get_hostname()
+                    crate::nix::detect::nixos_host(nixos_host)
                 ),
                 "--no-link",
                 "--print-out-paths",
@@ -870,8 +1617,7 @@ fn load_options_background(
                 for suffix in &["/share/doc/nixos/options.json", ""] {
                     let json_path = format!("{}{}", store_path, suffix);
                     if let Some(options) = try_load_options_json(&json_path) {
-                        let _ = tx.send(LoadStatus::Done(options));
-                        return;
+                        return Some(options);
                     }
                 }
             }
@@ -883,12 +1629,11 @@ fn load_options_background(
 
     if let Some(options) = try_nixos_option_fallback() {
         if !options.is_empty() {
-            let _ = tx.send(LoadStatus::Done(options));
-            return;
+            return Some(options);
         }
     }
 
-    let _ = tx.send(LoadStatus::Error(s.opt_load_error.to_string()));
+    None
 }
 
 fn try_load_options_json(path: &str) -> Option<Vec<NixOption>> {
@@ -964,6 +1709,7 @@ fn parse_options_json(content: &str) -> Option<Vec<NixOption>> {
                 example_str,
                 declared_in,
                 read_only,
+                flake_module: false,
             })
         })
         .collect();
@@ -1006,13 +1752,6 @@ fn format_nix_value(v: &serde_json::Value) -> String {
     }
 }
 
-fn get_hostname() -> String {
-    std::fs::read_to_string("/etc/hostname")
-        .unwrap_or_default()
-        .trim()
-        .to_string()
-}
-
 fn try_nixos_option_fallback() -> Option<Vec<NixOption>> {
     use std::process::Command;
 
@@ -1035,6 +1774,7 @@ fn try_nixos_option_fallback() -> Option<Vec<NixOption>> {
             example_str: None,
             declared_in: Vec::new(),
             read_only: false,
+            flake_module: false,
         })
         .collect();
 
@@ -1134,6 +1874,53 @@ fn type_color(type_str: &str, theme: &Theme) -> ratatui::style::Color {
 
 // ── Rendering ──
 
+impl Module for OptionsState {
+    fn handle_key(&mut self, key: KeyEvent, _lang: Language) -> Result<()> {
+        self.handle_key(key).map(|_| ())
+    }
+
+    fn captures_input(&self) -> bool {
+        self.search_active || self.detail_open
+    }
+
+    fn render(&mut self, frame: &mut Frame, ctx: &RenderCtx, area: Rect) {
+        render(frame, self, ctx.theme, ctx.lang, area);
+    }
+
+    fn activate(&mut self) {
+        self.ensure_loaded();
+    }
+
+    fn poll(&mut self, _active: bool) {
+        self.poll_load();
+        self.poll_examples();
+    }
+
+    fn commands(&self, s: &i18n::Strings) -> Vec<PaletteCommand> {
+        self.palette_commands(s)
+    }
+
+    fn jobs(&self, s: &i18n::Strings) -> Vec<Job> {
+        self.jobs(s)
+    }
+
+    fn cancel_job(&mut self, id: &str) {
+        self.cancel_job(id)
+    }
+
+    fn sub_tab(&self) -> Option<(usize, usize)> {
+        Some((self.sub_tab.index(), OptSubTab::all().len()))
+    }
+
+    fn sub_tab_label(&self, lang: Language) -> Option<&'static str> {
+        Some(self.sub_tab.label(lang))
+    }
+
+    fn flash_message(&mut self) -> &mut Option<FlashMessage> {
+        &mut self.flash_message
+    }
+}
+
 pub fn render(frame: &mut Frame, state: &OptionsState, theme: &Theme, lang: Language, area: Rect) {
     let s = i18n::get_strings(lang);
 
@@ -1186,6 +1973,8 @@ pub fn render(frame: &mut Frame, state: &OptionsState, theme: &Theme, lang: Lang
             OptSubTab::Search => render_search(frame, state, theme, lang, chunks[1]),
             OptSubTab::Browse => render_browse(frame, state, theme, lang, chunks[1]),
             OptSubTab::Related => render_related(frame, state, theme, lang, chunks[1]),
+            OptSubTab::Overrides => render_overrides(frame, state, theme, lang, chunks[1]),
+            OptSubTab::Changed => render_changes(frame, state, theme, lang, chunks[1]),
         }
     }
 }
@@ -1197,18 +1986,12 @@ fn render_tab_bar(
     lang: Language,
     area: Rect,
 ) {
-    let s = i18n::get_strings(lang);
-    let tabs = vec![
-        s.opt_tab_search.to_string(),
-        s.opt_tab_browse.to_string(),
-        s.opt_tab_related.to_string(),
-    ];
+    let tabs: Vec<String> = OptSubTab::all()
+        .iter()
+        .map(|t| t.label(lang).to_string())
+        .collect();
 
-    let selected = match state.sub_tab {
-        OptSubTab::Search => 0,
-        OptSubTab::Browse => 1,
-        OptSubTab::Related => 2,
-    };
+    let selected = state.sub_tab.index();
 
     let tab_titles: Vec<Line> = tabs.into_iter().map(Line::from).collect();
 
@@ -1353,11 +2136,6 @@ fn render_search(
 
     // Search bar
     let cursor_char = if state.search_active { "│" } else { "" };
-    let query_display = if state.search_query.is_empty() && !state.search_active {
-        s.opt_search_hint.to_string()
-    } else {
-        format!("{}{}", state.search_query, cursor_char)
-    };
 
     let search_style = if state.search_active {
         Style::default().fg(theme.accent)
@@ -1367,16 +2145,38 @@ fn render_search(
         theme.text()
     };
 
-    let line = Line::from(vec![
-        Span::styled(
-            format!("  {} ", s.opt_search_label),
-            Style::default()
-                .fg(theme.accent)
-                .add_modifier(Modifier::BOLD),
-        ),
-        Span::styled(query_display, search_style),
-    ]);
-    frame.render_widget(Paragraph::new(line).style(theme.block_style()), chunks[0]);
+    let mut spans = vec![Span::styled(
+        format!("  {} ", s.opt_search_label),
+        Style::default()
+            .fg(theme.accent)
+            .add_modifier(Modifier::BOLD),
+    )];
+    if state.search_query.is_empty() && !state.search_active {
+        spans.push(Span::styled(s.opt_search_hint, search_style));
+    } else {
+        // Highlight recognized filters as chips, keep typed spacing intact
+        for (i, token) in state.search_query.split(' ').enumerate() {
+            if i > 0 {
+                spans.push(Span::raw(" "));
+            }
+            if SearchFilter::parse(token).is_some() {
+                spans.push(Span::styled(
+                    token.to_string(),
+                    Style::default()
+                        .fg(theme.bg)
+                        .bg(theme.accent_dim)
+                        .add_modifier(Modifier::BOLD),
+                ));
+            } else {
+                spans.push(Span::styled(token.to_string(), search_style));
+            }
+        }
+        spans.push(Span::styled(cursor_char, search_style));
+    }
+    frame.render_widget(
+        Paragraph::new(Line::from(spans)).style(theme.block_style()),
+        chunks[0],
+    );
 
     // Result count
     if !state.search_results.is_empty() {
@@ -1606,6 +2406,374 @@ fn render_related(
     );
 }
 
+fn render_overrides(
+    frame: &mut Frame,
+    state: &OptionsState,
+    theme: &Theme,
+    lang: Language,
+    area: Rect,
+) {
+    let s = i18n::get_strings(lang);
+
+    if state.overrides_loading {
+        let elapsed = state
+            .overrides_start
+            .map(|t| t.elapsed().as_secs())
+            .unwrap_or(0);
+        let mut lines = vec![
+            Line::raw(""),
+            Line::styled(
+                format!("  ⏳ {} ({}s)", state.overrides_phase, elapsed),
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Line::raw(""),
+        ];
+        match state.overrides_progress {
+            Some((done, total)) if total > 0 => {
+                let bar_width = (area.width as usize).saturating_sub(8).min(40);
+                let fill = done * bar_width / total;
+                lines.push(Line::styled(
+                    format!(
+                        "  [{}{}] {}/{}",
+                        "█".repeat(fill),
+                        "░".repeat(bar_width - fill),
+                        done,
+                        total
+                    ),
+                    Style::default().fg(theme.accent_dim),
+                ));
+            }
+            Some(_) => {}
+            None => lines.push(Line::styled(
+                format!("  {}", s.opt_ovr_scanning),
+                Style::default().fg(theme.fg_dim),
+            )),
+        }
+        lines.push(Line::raw(""));
+        lines.push(Line::styled(
+            format!("  {}", s.opt_ovr_loading_hint),
+            Style::default().fg(theme.fg_dim),
+        ));
+        frame.render_widget(
+            Paragraph::new(lines)
+                .style(theme.block_style())
+                .wrap(Wrap { trim: false }),
+            area,
+        );
+        return;
+    }
+
+    if let Some(ref err) = state.overrides_error {
+        let lines = vec![
+            Line::raw(""),
+            Line::styled(
+                format!("  ✗ {}", s.opt_ovr_eval_failed),
+                Style::default()
+                    .fg(theme.error)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Line::raw(""),
+            Line::styled(format!("  {}", err), theme.text()),
+            Line::raw(""),
+            Line::styled(
+                format!("  [R] {}", s.opt_ovr_retry),
+                Style::default().fg(theme.fg_dim),
+            ),
+        ];
+        frame.render_widget(
+            Paragraph::new(lines)
+                .style(theme.block_style())
+                .wrap(Wrap { trim: false }),
+            area,
+        );
+        return;
+    }
+
+    if state.overrides.is_empty() {
+        frame.render_widget(
+            Paragraph::new(vec![
+                Line::raw(""),
+                Line::raw(""),
+                Line::styled(s.opt_ovr_empty, Style::default().fg(theme.fg_dim)),
+            ])
+            .alignment(Alignment::Center)
+            .style(theme.block_style()),
+            area,
+        );
+        return;
+    }
+
+    let chunks = Layout::vertical([
+        Constraint::Length(2), // Header
+        Constraint::Min(3),    // List
+    ])
+    .split(area);
+
+    frame.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled(
+                format!("  {} ", s.opt_ovr_header),
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("({})", state.overrides.len()),
+                Style::default().fg(theme.fg_dim),
+            ),
+            Span::styled(
+                format!("  [R] {}", s.opt_ovr_retry),
+                Style::default().fg(theme.fg_dim),
+            ),
+        ]))
+        .style(theme.block_style()),
+        chunks[0],
+    );
+
+    let visible_height = chunks[1].height as usize;
+    let scroll = state
+        .overrides_selected
+        .saturating_sub(visible_height.saturating_sub(1));
+    let path_width = (chunks[1].width as usize * 2 / 5).clamp(20, 60);
+
+    let items: Vec<ListItem> = state
+        .overrides
+        .iter()
+        .enumerate()
+        .skip(scroll)
+        .take(visible_height)
+        .map(|(i, ovr)| {
+            let is_selected = i == state.overrides_selected;
+            let path_display = if ovr.path.chars().count() > path_width {
+                let tail: String = ovr
+                    .path
+                    .chars()
+                    .skip(ovr.path.chars().count() - path_width + 1)
+                    .collect();
+                format!("…{}", tail)
+            } else {
+                format!("{:<width$}", ovr.path, width = path_width)
+            };
+
+            let value = ovr
+                .value
+                .as_deref()
+                .map(|v| v.lines().next().unwrap_or("").to_string())
+                .unwrap_or_else(|| "?".to_string());
+            let file = ovr
+                .files
+                .first()
+                .map(|f| f.rsplit('/').next().unwrap_or(f).to_string())
+                .unwrap_or_default();
+
+            let value_width = (chunks[1].width as usize)
+                .saturating_sub(path_width + file.chars().count() + 8)
+                .max(8);
+            let value_display = if value.chars().count() > value_width {
+                let trunc: String = value.chars().take(value_width - 1).collect();
+                format!("{}…", trunc)
+            } else {
+                value
+            };
+
+            let style = if is_selected {
+                theme.selected()
+            } else {
+                theme.text()
+            };
+
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("  {}", path_display),
+                    if is_selected {
+                        style.add_modifier(Modifier::BOLD)
+                    } else {
+                        Style::default().fg(theme.accent)
+                    },
+                ),
+                Span::styled(
+                    format!(" {} ", value_display),
+                    if is_selected {
+                        style
+                    } else {
+                        Style::default().fg(theme.success)
+                    },
+                ),
+                Span::styled(file, Style::default().fg(theme.fg_dim)),
+            ]))
+        })
+        .collect();
+
+    frame.render_widget(List::new(items).style(theme.block_style()), chunks[1]);
+}
+
+fn render_changes(
+    frame: &mut Frame,
+    state: &OptionsState,
+    theme: &Theme,
+    lang: Language,
+    area: Rect,
+) {
+    let s = i18n::get_strings(lang);
+
+    let message = if state.changes_loading {
+        Some((format!("⏳ {}...", s.opt_chg_loading), theme.accent))
+    } else if let Some(ref err) = state.changes_error {
+        Some((format!("{}  [R] {}", err, s.opt_ovr_retry), theme.fg_dim))
+    } else if state.changes.is_none() {
+        Some((s.opt_chg_loading.to_string(), theme.fg_dim))
+    } else {
+        None
+    };
+    if let Some((text, color)) = message {
+        frame.render_widget(
+            Paragraph::new(vec![
+                Line::raw(""),
+                Line::raw(""),
+                Line::styled(text, Style::default().fg(color)),
+            ])
+            .alignment(Alignment::Center)
+            .style(theme.block_style())
+            .wrap(Wrap { trim: false }),
+            area,
+        );
+        return;
+    }
+    let Some(report) = &state.changes else {
+        return;
+    };
+
+    let chunks = Layout::vertical([
+        Constraint::Length(2), // Header
+        Constraint::Min(3),    // List
+    ])
+    .split(area);
+
+    let count = |k: ChangeKind| report.changes.iter().filter(|c| c.kind == k).count();
+    let filter_label = match state.changes_filter {
+        None => s.opt_chg_filter_all,
+        Some(ChangeKind::Default) => s.opt_chg_kind_default,
+        Some(ChangeKind::Type) => s.opt_chg_kind_type,
+        Some(ChangeKind::Added) => s.opt_chg_kind_added,
+        Some(ChangeKind::Removed) => s.opt_chg_kind_removed,
+        Some(ChangeKind::Moved) => s.opt_chg_kind_moved,
+    };
+    frame.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled(
+                format!(
+                    "  #{} {} → #{} {}",
+                    report.old.id,
+                    report.old.short_rev(),
+                    report.new.id,
+                    report.new.short_rev()
+                ),
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!(
+                    "   ~{} T{} +{} −{} ↪{}",
+                    count(ChangeKind::Default),
+                    count(ChangeKind::Type),
+                    count(ChangeKind::Added),
+                    count(ChangeKind::Removed),
+                    count(ChangeKind::Moved)
+                ),
+                Style::default().fg(theme.fg_dim),
+            ),
+            Span::styled(
+                format!("   [f] {}", filter_label),
+                Style::default().fg(theme.accent_dim),
+            ),
+        ]))
+        .style(theme.block_style()),
+        chunks[0],
+    );
+
+    let visible = state.visible_changes();
+    if visible.is_empty() {
+        frame.render_widget(
+            Paragraph::new(Line::styled(
+                format!("  {}", s.opt_chg_none),
+                Style::default().fg(theme.fg_dim),
+            ))
+            .style(theme.block_style()),
+            chunks[1],
+        );
+        return;
+    }
+
+    let height = chunks[1].height as usize;
+    let scroll = state
+        .changes_selected
+        .saturating_sub(height.saturating_sub(1));
+    let path_width = (chunks[1].width as usize / 2).clamp(20, 60);
+
+    let items: Vec<ListItem> = visible
+        .iter()
+        .enumerate()
+        .skip(scroll)
+        .take(height)
+        .map(|(row, &i)| {
+            let change = &report.changes[i];
+            let is_selected = row == state.changes_selected;
+            let style = if is_selected {
+                theme.selected()
+            } else {
+                theme.text()
+            };
+            let icon_color = match change.kind {
+                ChangeKind::Added => theme.success,
+                ChangeKind::Removed => theme.error,
+                ChangeKind::Default | ChangeKind::Type => theme.warning,
+                ChangeKind::Moved => theme.fg_dim,
+            };
+            let detail = match (&change.old, &change.new) {
+                (Some(o), Some(n)) => format!("{} → {}", o, n),
+                (None, Some(n)) => format!("∅ → {}", n),
+                (Some(o), None) => format!("{} → ∅", o),
+                (None, None) => String::new(),
+            };
+            let detail: String = detail
+                .lines()
+                .next()
+                .unwrap_or("")
+                .chars()
+                .take(80)
+                .collect();
+
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("  {} ", change.kind.icon()),
+                    Style::default().fg(icon_color),
+                ),
+                Span::styled(
+                    format!("{:<width$}", change.path, width = path_width),
+                    if is_selected {
+                        style.add_modifier(Modifier::BOLD)
+                    } else {
+                        style
+                    },
+                ),
+                Span::styled(
+                    format!(" {}", detail),
+                    if is_selected {
+                        style
+                    } else {
+                        Style::default().fg(theme.fg_dim)
+                    },
+                ),
+            ]))
+        })
+        .collect();
+
+    frame.render_widget(List::new(items).style(theme.block_style()), chunks[1]);
+}
+
 /// Shared list renderer for search results and related options
 fn render_option_list(
     frame: &mut Frame,
@@ -1616,6 +2784,7 @@ fn render_option_list(
     scroll_offset: usize,
     area: Rect,
 ) {
+    let terms = highlight_terms(state);
     let visible_height = area.height as usize;
     let mut scroll = scroll_offset;
     if selected >= scroll + visible_height {
@@ -1657,15 +2826,28 @@ fn render_option_list(
             };
 
             let desc_width = (area.width as usize).saturating_sub(path_width + type_width + 6);
-            let desc: String = if opt.description.len() > desc_width {
-                if desc_width > 1 {
-                    let trunc = safe_truncate(&opt.description, desc_width.saturating_sub(1));
-                    format!("{}…", trunc)
+            // Start the description near the first matched word if it would be cut off
+            let first_match = fulltext::match_ranges(&opt.description, terms)
+                .first()
+                .map(|r| r.start);
+            let (lead, text) = match first_match {
+                Some(start) if start + 12 > desc_width && desc_width > 20 => {
+                    let from = (start.saturating_sub(12)..=start)
+                        .find(|&i| opt.description.is_char_boundary(i))
+                        .unwrap_or(start);
+                    ("…", &opt.description[from..])
+                }
+                _ => ("", opt.description.as_str()),
+            };
+            let room = desc_width.saturating_sub(lead.chars().count());
+            let desc: String = if text.len() > room {
+                if room > 1 {
+                    format!("{}{}…", lead, safe_truncate(text, room.saturating_sub(1)))
                 } else {
                     String::new()
                 }
             } else {
-                opt.description.clone()
+                format!("{}{}", lead, text)
             };
 
             let style = if is_selected {
@@ -1676,25 +2858,31 @@ fn render_option_list(
 
             let tc = type_color(&opt.type_str, theme);
 
-            ListItem::new(Line::from(vec![
-                Span::styled(
-                    format!("  {}", path_display),
-                    if is_selected {
-                        style.add_modifier(Modifier::BOLD)
-                    } else {
-                        Style::default().fg(theme.accent)
-                    },
-                ),
-                Span::styled(format!(" {} ", type_display), Style::default().fg(tc)),
-                Span::styled(
-                    desc,
+            ListItem::new(Line::from(
+                vec![
+                    Span::styled(
+                        format!("  {}", path_display),
+                        if is_selected {
+                            style.add_modifier(Modifier::BOLD)
+                        } else {
+                            Style::default().fg(theme.accent)
+                        },
+                    ),
+                    Span::styled(format!(" {} ", type_display), Style::default().fg(tc)),
+                ]
+                .into_iter()
+                .chain(fulltext::highlight(
+                    &desc,
+                    terms,
                     if is_selected {
                         style
                     } else {
                         Style::default().fg(theme.fg_dim)
                     },
-                ),
-            ]))
+                    match_style(theme),
+                ))
+                .collect::<Vec<_>>(),
+            ))
         })
         .collect();
 
@@ -1796,6 +2984,13 @@ fn render_detail(
         ]));
     }
 
+    if opt.flake_module {
+        lines.push(Line::styled(
+            format!("  ◆ {}", s.opt_flake_module),
+            Style::default().fg(theme.accent),
+        ));
+    }
+
     // Read-only marker
     if opt.read_only {
         lines.push(Line::styled(
@@ -1816,8 +3011,47 @@ fn render_detail(
 
     // Word-wrap description
     let wrap_width = (area.width as usize).saturating_sub(6).max(10);
+    let terms = highlight_terms(state);
     for wrapped_line in word_wrap(&opt.description, wrap_width) {
-        lines.push(Line::styled(format!("    {}", wrapped_line), theme.text()));
+        let mut spans = vec![Span::raw("    ")];
+        spans.extend(fulltext::highlight(
+            &wrapped_line,
+            terms,
+            theme.text(),
+            match_style(theme),
+        ));
+        lines.push(Line::from(spans));
+    }
+
+    // Usage examples from public configs
+    let found = state
+        .examples
+        .as_ref()
+        .map_or(&[][..], |d| d.for_option(&opt.path));
+    if !found.is_empty() {
+        lines.push(Line::raw(""));
+        lines.push(Line::styled(
+            format!("  {}", s.opt_detail_examples),
+            Style::default()
+                .fg(theme.fg_dim)
+                .add_modifier(Modifier::BOLD),
+        ));
+        for (i, ex) in found.iter().enumerate() {
+            let source = match &ex.source {
+                Some(src) => src.clone(),
+                None => format!("#{}", i + 1),
+            };
+            lines.push(Line::styled(
+                format!("    ── {}", source),
+                Style::default().fg(theme.fg_dim),
+            ));
+            for code in ex.code.lines() {
+                lines.push(Line::styled(
+                    format!("    {}", code),
+                    Style::default().fg(theme.warning),
+                ));
+            }
+        }
     }
 
     // Declared in
@@ -1844,8 +3078,8 @@ fn render_detail(
     lines.push(Line::raw(""));
     lines.push(Line::styled(
         format!(
-            "  [Esc] {}  [r] {}  [j/k] {}",
-            s.back, s.opt_related_label, s.navigate
+            "  [Esc] {}  [r] {}  [U] {}  [j/k] {}",
+            s.back, s.opt_related_label, s.opt_examples_update, s.navigate
         ),
         Style::default().fg(theme.fg_dim),
     ));
@@ -1871,6 +3105,22 @@ fn truncate_value(s: &str, max_width: usize) -> String {
     }
 }
 
+/// Search terms to highlight: only results of (and details opened from) Search
+fn highlight_terms(state: &OptionsState) -> &[String] {
+    if state.sub_tab == OptSubTab::Search {
+        &state.search_terms
+    } else {
+        &[]
+    }
+}
+
+/// Matched search words in results and detail
+fn match_style(theme: &Theme) -> Style {
+    Style::default()
+        .fg(theme.warning)
+        .add_modifier(Modifier::BOLD)
+}
+
 fn word_wrap(text: &str, width: usize) -> Vec<String> {
     if width == 0 {
         return vec![text.to_string()];
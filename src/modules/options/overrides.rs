@@ -0,0 +1,234 @@
+//! "My Overrides" — every option the user's configuration sets.
+//!
+//! Instead of calling `nixos-option` once per option (minutes for 20k
+//! options), the whole configuration is evaluated in a single `nix eval`.
+//! The Nix expression walks `options`, keeps those defined in files
+//! outside nixpkgs' `nixos/modules/`, and serializes their values.
+//! Each serialized option emits a `builtins.trace` line on stderr, which
+//! is read live to report progress.
+
+use crate::config::Language;
+use std::io::{BufRead, BufReader};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+
+/// An option the user's config defines, as returned by the evaluation
+#[derive(Debug, Clone)]
+pub struct RawOverride {
+    pub path: String,
+    /// Nix-style rendering of the value (None if it couldn't be evaluated)
+    pub value: Option<String>,
+    /// Files from the user's config that define the option
+    pub files: Vec<String>,
+}
+
+/// Messages from the evaluation thread
+#[derive(Debug)]
+pub enum OverrideMsg {
+    Phase(String),
+    Progress(usize, usize),
+    Done(Vec<RawOverride>),
+    Error(String),
+}
+
+const PROGRESS_MARKER: &str = "nixmate-progress ";
+
+/// Evaluate all user-defined options. Blocking — run in background thread!
+pub fn evaluate_overrides(
+    tx: mpsc::Sender<OverrideMsg>,
+    lang: Language,
+    config_path: Option<&str>,
+    nixos_host: Option<&str>,
+) {
+    let s = crate::i18n::get_strings(lang);
+
+    let mut cmd = system_eval_command(config_path, nixos_host, overrides_expr);
+
+    let _ = tx.send(OverrideMsg::Phase(s.opt_ovr_phase_eval.to_string()));
+
+    let mut child = match cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+    {
+        Ok(c) => c,
+        Err(e) => {
+            let _ = tx.send(OverrideMsg::Error(e.to_string()));
+            return;
+        }
+    };
+
+    // stdout is one JSON document at the end; read it on a separate thread
+    // so a full pipe never blocks the evaluator while we follow stderr
+    let stdout = child.stdout.take();
+    let stdout_handle = std::thread::spawn(move || {
+        let mut buf = String::new();
+        if let Some(mut out) = stdout {
+            let _ = std::io::Read::read_to_string(&mut out, &mut buf);
+        }
+        buf
+    });
+
+    // Follow trace lines for progress, remember the last error line
+    let mut last_error: Option<String> = None;
+    if let Some(stderr) = child.stderr.take() {
+        for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+            if let Some((done, total)) = parse_progress(&line) {
+                let _ = tx.send(OverrideMsg::Progress(done, total));
+            } else if line.trim_start().starts_with("error:") {
+                last_error = Some(line.trim().to_string());
+            }
+        }
+    }
+
+    let status = child.wait();
+    let stdout = stdout_handle.join().unwrap_or_default();
+
+    match status {
+        Ok(st) if st.success() => match parse_result(&stdout) {
+            Some(overrides) => {
+                let _ = tx.send(OverrideMsg::Done(overrides));
+            }
+            None => {
+                let _ = tx.send(OverrideMsg::Error(s.opt_ovr_parse_failed.to_string()));
+            }
+        },
+        _ => {
+            let msg = last_error.unwrap_or_else(|| s.opt_ovr_eval_failed.to_string());
+            let _ = tx.send(OverrideMsg::Error(msg));
+        }
+    }
+}
+
+/// `nix eval --json` of `expr(system)`, where `system` is a Nix expression
+/// for this host's NixOS system (flake `nixosConfigurations.<host>`, or
+/// `<nixpkgs/nixos>` for channel setups).
+pub(crate) fn system_eval_command(
+    config_path: Option<&str>,
+    nixos_host: Option<&str>,
+    expr: impl Fn(&str) -> String,
+) -> Command {
+    match crate::nix::detect::find_flake_path(config_path) {
+        Some(dir) => {
+            let host = crate::nix::detect::nixos_host(nixos_host);
+            let system = format!(
+                "(builtins.getFlake \"{}\").nixosConfigurations.\"{}\"",
+                escape_nix(&dir),
+                escape_nix(&host)
+            );
+            let mut c = Command::new("nix");
+            c.args([
+                "--extra-experimental-features",
+                "nix-command flakes",
+                "eval",
+                "--impure",
+                "--json",
+                "--expr",
+                &expr(&system),
+            ]);
+            c
+        }
+        None => {
+            let mut c = Command::new("nix-instantiate");
+            c.args([
+                "--eval",
+                "--strict",
+                "--json",
+                "-E",
+                &expr("(import <nixpkgs/nixos> { })"),
+            ]);
+            c
+        }
+    }
+}
+
+/// Nix expression evaluating every user-defined option of `system`.
+///
+/// Values are sanitized before `toJSON` (functions, derivations and deep
+/// nesting can't or shouldn't be serialized) since `tryEval` can't catch
+/// those errors.
+fn overrides_expr(system: &str) -> String {
+    format!(
+        r#"
+let
+  sys = {system};
+  lib = sys.pkgs.lib;
+  fromUser = f: builtins.match ".*/nixos/modules/.*" (toString f) == null;
+  isOverride = o:
+    let r = builtins.tryEval ((o.isDefined or false)
+      && (o.highestPrio or 1500) < 1500
+      && builtins.any fromUser (o.files or [ ]));
+    in r.success && r.value;
+  sanitize = depth: v:
+    if builtins.isFunction v then "<function>"
+    else if lib.isDerivation v then
+      (let n = builtins.tryEval (v.pname or v.name or "derivation"); in
+        "<${{if n.success then n.value else "derivation"}}>")
+    else if builtins.isAttrs v then
+      (if depth > 3 then "{{ ... }}" else builtins.mapAttrs (_: sanitize (depth + 1)) v)
+    else if builtins.isList v then
+      (if depth > 3 then "[ ... ]" else map (sanitize (depth + 1)) v)
+    else if builtins.isPath v then toString v
+    else v;
+  overridden = builtins.filter isOverride (lib.collect lib.isOption sys.options);
+  total = toString (builtins.length overridden);
+  show = i: o:
+    let v = builtins.tryEval (builtins.toJSON (sanitize 0 o.value)); in
+    builtins.trace "{marker}${{toString (i + 1)}}/${{total}}" {{
+      path = lib.showOption o.loc;
+      value = if v.success then v.value else null;
+      files = map toString (builtins.filter fromUser (o.files or [ ]));
+    }};
+in
+builtins.trace "{marker}0/${{total}}" (lib.imap0 show overridden)
+"#,
+        system = system,
+        marker = PROGRESS_MARKER,
+    )
+}
+
+fn parse_progress(line: &str) -> Option<(usize, usize)> {
+    let rest = line.split(PROGRESS_MARKER).nth(1)?;
+    let (done, total) = rest.trim().split_once('/')?;
+    Some((done.parse().ok()?, total.parse().ok()?))
+}
+
+fn parse_result(stdout: &str) -> Option<Vec<RawOverride>> {
+    let data: serde_json::Value = serde_json::from_str(stdout.trim()).ok()?;
+    let list = data.as_array()?;
+
+    let mut overrides: Vec<RawOverride> = list
+        .iter()
+        .filter_map(|item| {
+            let path = item.get("path")?.as_str()?.to_string();
+            let value = item
+                .get("value")
+                .and_then(|v| v.as_str())
+                .and_then(|json| serde_json::from_str::<serde_json::Value>(json).ok())
+                .map(|v| match v {
+                    serde_json::Value::String(s) => s,
+                    other => super::format_nix_value(&other),
+                });
+            let files = item
+                .get("files")
+                .and_then(|f| f.as_array())
+                .map(|arr| {
+                    arr.iter()
+                        .filter_map(|f| f.as_str().map(|s| s.to_string()))
+                        .collect()
+                })
+                .unwrap_or_default();
+            Some(RawOverride { path, value, files })
+        })
+        .collect();
+
+    overrides.sort_by(|a, b| a.path.cmp(&b.path));
+    Some(overrides)
+}
+
+fn escape_nix(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace("${", "\\${")
+}
@@ -0,0 +1,165 @@
+//! Search query parsing: free text plus `key:value` filters.
+//!
+//!   type:bool          — option type contains "bool" (matches "boolean")
+//!   in:services.nginx  — option lives below this namespace
+//!   readonly:false     — read-only (true) or settable (false) options
+//!   has:example        — option has an example / default / description
+//!
+//! Filters are applied before fuzzy scoring; unknown `key:` prefixes are
+//! treated as plain text so searching for e.g. "foo:bar" still works.
+
+use super::NixOption;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HasField {
+    Example,
+    Default,
+    Description,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SearchFilter {
+    Type(String),
+    In(String),
+    ReadOnly(bool),
+    Has(HasField),
+}
+
+impl SearchFilter {
+    /// Parse a single `key:value` token (None = not a filter)
+    pub fn parse(token: &str) -> Option<Self> {
+        let (key, value) = token.split_once(':')?;
+        let value = value.trim();
+        if value.is_empty() {
+            return None;
+        }
+        match key.to_lowercase().as_str() {
+            "type" => Some(SearchFilter::Type(value.to_lowercase())),
+            "in" => Some(SearchFilter::In(value.trim_end_matches('.').to_string())),
+            "readonly" | "ro" => match value.to_lowercase().as_str() {
+                "true" | "yes" | "1" => Some(SearchFilter::ReadOnly(true)),
+                "false" | "no" | "0" => Some(SearchFilter::ReadOnly(false)),
+                _ => None,
+            },
+            "has" => match value.to_lowercase().as_str() {
+                "example" => Some(SearchFilter::Has(HasField::Example)),
+                "default" => Some(SearchFilter::Has(HasField::Default)),
+                "description" | "desc" => Some(SearchFilter::Has(HasField::Description)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    pub fn matches(&self, opt: &NixOption) -> bool {
+        match self {
+            SearchFilter::Type(t) => opt.type_str.to_lowercase().contains(t.as_str()),
+            SearchFilter::In(ns) => {
+                opt.path == *ns
+                    || (opt.path.starts_with(ns.as_str()) && opt.path[ns.len()..].starts_with('.'))
+            }
+            SearchFilter::ReadOnly(ro) => opt.read_only == *ro,
+            SearchFilter::Has(HasField::Example) => opt.example_str.is_some(),
+            SearchFilter::Has(HasField::Default) => opt.default_str.is_some(),
+            SearchFilter::Has(HasField::Description) => !opt.description.trim().is_empty(),
+        }
+    }
+}
+
+/// A search query split into free text and filters
+#[derive(Debug, Clone, Default)]
+pub struct SearchQuery {
+    /// Lowercased free text (filters removed)
+    pub text: String,
+    pub filters: Vec<SearchFilter>,
+}
+
+impl SearchQuery {
+    pub fn parse(input: &str) -> Self {
+        let mut text = Vec::new();
+        let mut filters = Vec::new();
+        for token in input.split_whitespace() {
+            match SearchFilter::parse(token) {
+                Some(f) => filters.push(f),
+                None => text.push(token.to_lowercase()),
+            }
+        }
+        Self {
+            text: text.join(" "),
+            filters,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty() && self.filters.is_empty()
+    }
+
+    pub fn matches_filters(&self, opt: &NixOption) -> bool {
+        self.filters.iter().all(|f| f.matches(opt))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn option(path: &str, type_str: &str) -> NixOption {
+        NixOption {
+            path: path.to_string(),
+            type_str: type_str.to_string(),
+            description: "Whether to enable it.".to_string(),
+            default_str: Some("false".to_string()),
+            example_str: None,
+            declared_in: Vec::new(),
+            read_only: false,
+            flake_module: false,
+        }
+    }
+
+    #[test]
+    fn test_filters() {
+        assert_eq!(
+            SearchFilter::parse("TYPE:Bool"),
+            Some(SearchFilter::Type("bool".to_string()))
+        );
+        assert_eq!(
+            SearchFilter::parse("in:services.nginx."),
+            Some(SearchFilter::In("services.nginx".to_string()))
+        );
+        assert_eq!(
+            SearchFilter::parse("ro:yes"),
+            Some(SearchFilter::ReadOnly(true))
+        );
+        assert_eq!(
+            SearchFilter::parse("has:desc"),
+            Some(SearchFilter::Has(HasField::Description))
+        );
+        assert_eq!(SearchFilter::parse("readonly:maybe"), None);
+        assert_eq!(SearchFilter::parse("type:"), None);
+        assert_eq!(SearchFilter::parse("nginx"), None);
+
+        let opt = option("services.nginx.enable", "boolean");
+        assert!(SearchFilter::Type("bool".to_string()).matches(&opt));
+        assert!(SearchFilter::In("services.nginx".to_string()).matches(&opt));
+        assert!(!SearchFilter::In("services.ngin".to_string()).matches(&opt));
+        assert!(SearchFilter::ReadOnly(false).matches(&opt));
+        assert!(!SearchFilter::Has(HasField::Example).matches(&opt));
+    }
+
+    #[test]
+    fn test_query() {
+        let query = SearchQuery::parse("  Enable type:bool  in:services.nginx ");
+        assert_eq!(query.text, "enable");
+        assert_eq!(query.filters.len(), 2);
+        assert!(query.matches_filters(&option("services.nginx.enable", "boolean")));
+        assert!(!query.matches_filters(&option("services.caddy.enable", "boolean")));
+        assert!(SearchQuery::parse("   ").is_empty());
+    }
+
+    #[test]
+    fn test_unknown_prefix_and_quotes_are_text() {
+        let query = SearchQuery::parse("foo:bar \"type:bool\"");
+        assert_eq!(query.text, "foo:bar \"type:bool\"");
+        assert!(query.filters.is_empty());
+    }
+}
@@ -0,0 +1,367 @@
+//! Browse: packages by category, without knowing a name
+//!
+//! nixpkgs has no categories in `nix search` output, so they're inferred
+//! from the attribute path and name (`python312` is a language, anything
+//! ending in `-language-server` a dev tool) and, failing that, from a few
+//! telling phrases in the description. Each category has a curated list
+//! of the packages people usually look for, in that order; "Popular"
+//! sorting puts them first.
+
+use super::index::IndexedPackage;
+use crate::i18n::Strings;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    Editors,
+    Terminals,
+    Shells,
+    Languages,
+    DevTools,
+    Servers,
+    Browsers,
+}
+
+impl Category {
+    pub const ALL: [Category; 7] = [
+        Category::Editors,
+        Category::Terminals,
+        Category::Shells,
+        Category::Languages,
+        Category::DevTools,
+        Category::Servers,
+        Category::Browsers,
+    ];
+
+    pub fn label(self, s: &Strings) -> &'static str {
+        match self {
+            Category::Editors => s.pkg_cat_editors,
+            Category::Terminals => s.pkg_cat_terminals,
+            Category::Shells => s.pkg_cat_shells,
+            Category::Languages => s.pkg_cat_languages,
+            Category::DevTools => s.pkg_cat_devtools,
+            Category::Servers => s.pkg_cat_servers,
+            Category::Browsers => s.pkg_cat_browsers,
+        }
+    }
+
+    /// Curated picks, most wanted first (attribute names)
+    fn popular(self) -> &'static [&'static str] {
+        match self {
+            Category::Editors => &[
+                "neovim",
+                "vim",
+                "emacs",
+                "helix",
+                "vscode",
+                "vscodium",
+                "zed-editor",
+                "kakoune",
+                "micro",
+                "nano",
+                "sublime4",
+                "kdePackages.kate",
+                "gnome-text-editor",
+                "lapce",
+            ],
+            Category::Terminals => &[
+                "kitty",
+                "alacritty",
+                "wezterm",
+                "ghostty",
+                "foot",
+                "tmux",
+                "zellij",
+                "konsole",
+                "gnome-terminal",
+                "xterm",
+                "st",
+                "screen",
+                "rio",
+            ],
+            Category::Shells => &[
+                "zsh",
+                "fish",
+                "bash",
+                "nushell",
+                "starship",
+                "oh-my-zsh",
+                "xonsh",
+                "elvish",
+                "dash",
+                "oil",
+            ],
+            Category::Languages => &[
+                "python3",
+                "nodejs",
+                "rustc",
+                "cargo",
+                "go",
+                "gcc",
+                "clang",
+                "jdk",
+                "ghc",
+                "ruby",
+                "php",
+                "lua",
+                "zig",
+                "deno",
+                "bun",
+                "elixir",
+                "erlang",
+                "dotnet-sdk",
+                "julia",
+                "R",
+                "ocaml",
+                "nim",
+                "kotlin",
+                "scala",
+            ],
+            Category::DevTools => &[
+                "git",
+                "gh",
+                "lazygit",
+                "direnv",
+                "just",
+                "gnumake",
+                "cmake",
+                "meson",
+                "ninja",
+                "gdb",
+                "jq",
+                "ripgrep",
+                "fd",
+                "fzf",
+                "docker",
+                "podman",
+                "nil",
+                "nixd",
+                "shellcheck",
+                "pre-commit",
+            ],
+            Category::Servers => &[
+                "nginx",
+                "caddy",
+                "apacheHttpd",
+                "traefik",
+                "haproxy",
+                "postgresql",
+                "mariadb",
+                "redis",
+                "valkey",
+                "jellyfin",
+                "nextcloud",
+                "grafana",
+                "prometheus",
+                "forgejo",
+                "gitea",
+                "vaultwarden",
+                "home-assistant",
+                "syncthing",
+                "minio",
+                "mosquitto",
+            ],
+            Category::Browsers => &[
+                "firefox",
+                "chromium",
+                "google-chrome",
+                "brave",
+                "librewolf",
+                "vivaldi",
+                "qutebrowser",
+                "tor-browser",
+                "epiphany",
+                "w3m",
+                "lynx",
+            ],
+        }
+    }
+
+    /// Attribute name prefixes, for versioned attributes (`python312`,
+    /// `postgresql_16`, `nodejs_22`)
+    fn attr_prefixes(self) -> &'static [&'static str] {
+        match self {
+            Category::Languages => &[
+                "python3",
+                "nodejs_",
+                "jdk",
+                "openjdk",
+                "go_1",
+                "ghc",
+                "ruby_",
+                "php8",
+                "lua5",
+                "luajit",
+                "zig_",
+                "erlang_",
+                "elixir_",
+                "dotnet-sdk",
+                "rustc",
+                "gcc1",
+                "clang_",
+                "llvm_",
+            ],
+            Category::Servers => &["postgresql_", "mariadb_", "mysql", "redis", "nginx"],
+            Category::Editors => &["vscode", "emacs", "neovim"],
+            _ => &[],
+        }
+    }
+
+    /// Attribute name suffixes
+    fn attr_suffixes(self) -> &'static [&'static str] {
+        match self {
+            Category::DevTools => &["-language-server", "-lsp", "-ls", "-fmt", "-lint"],
+            Category::Servers => &["-server", "d-server"],
+            _ => &[],
+        }
+    }
+
+    /// Phrases in the description that settle it
+    fn description_phrases(self) -> &'static [&'static str] {
+        match self {
+            Category::Editors => &["text editor", "code editor", "integrated development"],
+            Category::Terminals => &["terminal emulator", "terminal multiplexer"],
+            Category::Shells => &["command-line shell", "interactive shell", "unix shell"],
+            Category::Languages => &["programming language", "compiler for", "interpreter for"],
+            Category::DevTools => &[
+                "language server",
+                "version control",
+                "build system",
+                "debugger",
+                "linter",
+                "code formatter",
+            ],
+            Category::Servers => &[
+                "web server",
+                "http server",
+                "database server",
+                "reverse proxy",
+                "self-hosted",
+                "media server",
+            ],
+            Category::Browsers => &["web browser"],
+        }
+    }
+}
+
+/// The category a package belongs to, if any
+pub fn categorize(package: &IndexedPackage) -> Option<Category> {
+    let attr = package.attr.as_str();
+    let description = package.description.to_lowercase();
+    Category::ALL
+        .into_iter()
+        .find(|c| c.popular().contains(&attr))
+        .or_else(|| {
+            Category::ALL.into_iter().find(|c| {
+                c.attr_prefixes().iter().any(|p| attr.starts_with(p))
+                    || c.attr_suffixes().iter().any(|p| attr.ends_with(p))
+            })
+        })
+        .or_else(|| {
+            Category::ALL.into_iter().find(|c| {
+                c.description_phrases()
+                    .iter()
+                    .any(|p| description.contains(p))
+            })
+        })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BrowseSort {
+    /// Curated picks first, then by name
+    #[default]
+    Popular,
+    /// Most recently changed version first
+    Updated,
+    Name,
+}
+
+impl BrowseSort {
+    pub fn next(self) -> Self {
+        match self {
+            BrowseSort::Popular => BrowseSort::Updated,
+            BrowseSort::Updated => BrowseSort::Name,
+            BrowseSort::Name => BrowseSort::Popular,
+        }
+    }
+
+    pub fn label(self, s: &Strings) -> &'static str {
+        match self {
+            BrowseSort::Popular => s.pkg_sort_popular,
+            BrowseSort::Updated => s.pkg_sort_updated,
+            BrowseSort::Name => s.pkg_sort_name,
+        }
+    }
+}
+
+/// Packages of `category`, sorted
+pub fn list(
+    packages: &[IndexedPackage],
+    category: Category,
+    sort: BrowseSort,
+) -> Vec<&IndexedPackage> {
+    let mut list: Vec<&IndexedPackage> = packages
+        .iter()
+        .filter(|p| categorize(p) == Some(category))
+        .collect();
+    let popular = category.popular();
+    let rank = |p: &IndexedPackage| {
+        popular
+            .iter()
+            .position(|a| *a == p.attr)
+            .unwrap_or(popular.len())
+    };
+    match sort {
+        BrowseSort::Popular => list.sort_by(|a, b| rank(a).cmp(&rank(b)).then(a.attr.cmp(&b.attr))),
+        BrowseSort::Updated => {
+            list.sort_by(|a, b| b.updated_at.cmp(&a.updated_at).then(a.attr.cmp(&b.attr)))
+        }
+        BrowseSort::Name => list.sort_by_key(|p| p.attr.to_lowercase()),
+    }
+    list
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn package(attr: &str, description: &str, updated_at: Option<i64>) -> IndexedPackage {
+        IndexedPackage {
+            attr: attr.to_string(),
+            pname: attr.to_string(),
+            version: "1.0".to_string(),
+            description: description.to_string(),
+            updated_at,
+        }
+    }
+
+    #[test]
+    fn test_browse_categories() {
+        let packages = vec![
+            package("aaa-edit", "A small text editor", Some(20)),
+            package(
+                "python312",
+                "High-level dynamically-typed programming language",
+                None,
+            ),
+            package("nil", "Yet another language server for Nix", None),
+            package("neovim", "Vim text editor fork", Some(10)),
+            package(
+                "hello",
+                "A program that produces a familiar, friendly greeting",
+                None,
+            ),
+        ];
+        assert_eq!(categorize(&packages[1]), Some(Category::Languages));
+        assert_eq!(categorize(&packages[2]), Some(Category::DevTools));
+        assert_eq!(categorize(&packages[4]), None);
+
+        let attrs = |sort| -> Vec<&str> {
+            list(&packages, Category::Editors, sort)
+                .iter()
+                .map(|p| p.attr.as_str())
+                .collect()
+        };
+        assert_eq!(attrs(BrowseSort::Popular), vec!["neovim", "aaa-edit"]);
+        assert_eq!(attrs(BrowseSort::Updated), vec!["aaa-edit", "neovim"]);
+    }
+}
@@ -0,0 +1,203 @@
+//! Local index of every package in the nixpkgs source, for Browse
+//!
+//! Searching runs `nix search` per query; browsing needs all packages at
+//! once. The index is built once in the background (`nix search <source>
+//! ^ --json`, or `nix-env -qaP --description` for channels) and kept in
+//! `~/.cache/nixmate/package-index.json`, rebuilt after `MAX_AGE_DAYS` or
+//! on `R`. nixpkgs doesn't record when a package changed, so each rebuild
+//! compares versions with the previous index: `updated_at` is when nixmate
+//! first saw the current version, counted from `tracked_since`.
+
+use super::NixpkgsSource;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Rebuild an index older than this when Browse is opened
+pub const MAX_AGE_DAYS: i64 = 7;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexedPackage {
+    /// Top-level attribute (`neovim`, `python312`)
+    pub attr: String,
+    pub pname: String,
+    pub version: String,
+    pub description: String,
+    /// Unix time the current version first showed up (None = already there
+    /// when tracking started)
+    pub updated_at: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackageIndex {
+    /// `NixpkgsSource::channel` it was built from
+    pub source: String,
+    pub built_at: i64,
+    /// First build for this source; version changes are known from here on
+    pub tracked_since: i64,
+    pub packages: Vec<IndexedPackage>,
+}
+
+impl PackageIndex {
+    pub fn is_stale(&self) -> bool {
+        chrono::Local::now().timestamp() - self.built_at > MAX_AGE_DAYS * 86_400
+    }
+}
+
+fn file_path() -> Option<PathBuf> {
+    dirs::cache_dir().map(|d| d.join("nixmate").join("package-index.json"))
+}
+
+/// The saved index, if it was built from `source`
+pub fn load(source: &str) -> Option<PackageIndex> {
+    let content = std::fs::read(file_path()?).ok()?;
+    let index: PackageIndex = serde_json::from_slice(&content).ok()?;
+    (index.source == source).then_some(index)
+}
+
+fn save(index: &PackageIndex) {
+    let Some(path) = file_path() else {
+        return;
+    };
+    if let Some(dir) = path.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    if let Ok(json) = serde_json::to_vec(index) {
+        let _ = std::fs::write(path, json);
+    }
+}
+
+/// Query every package and save the index. Blocking — run in background
+/// thread!
+pub fn build(
+    source: &NixpkgsSource,
+    previous: Option<PackageIndex>,
+) -> Result<PackageIndex, String> {
+    let mut packages = if source.is_flakes {
+        query_flake(&source.channel)?
+    } else {
+        query_channel()?
+    };
+    packages.sort_by(|a, b| a.attr.cmp(&b.attr));
+
+    let now = chrono::Local::now().timestamp();
+    let tracked_since = match &previous {
+        Some(previous) => {
+            mark_updates(&mut packages, previous, now);
+            previous.tracked_since
+        }
+        None => now,
+    };
+    let index = PackageIndex {
+        source: source.channel.clone(),
+        built_at: now,
+        tracked_since,
+        packages,
+    };
+    save(&index);
+    Ok(index)
+}
+
+/// New packages and changed versions get `now`, the rest keep their date
+fn mark_updates(packages: &mut [IndexedPackage], previous: &PackageIndex, now: i64) {
+    let before: HashMap<&str, &IndexedPackage> = previous
+        .packages
+        .iter()
+        .map(|p| (p.attr.as_str(), p))
+        .collect();
+    for package in packages {
+        package.updated_at = match before.get(package.attr.as_str()) {
+            Some(old) if old.version == package.version => old.updated_at,
+            _ => Some(now),
+        };
+    }
+}
+
+fn query_flake(channel: &str) -> Result<Vec<IndexedPackage>, String> {
+    let output = Command::new("nix")
+        .args(["search", channel, "^", "--json"])
+        .env("NIX_CONFIG", "warn-dirty = false")
+        .output()
+        .map_err(|e| format!("nix search: {}", e))?;
+    if !output.status.success() {
+        return Err(first_line(&output.stderr));
+    }
+    let data: serde_json::Value =
+        serde_json::from_slice(&output.stdout).map_err(|e| format!("nix search: {}", e))?;
+    let field = |info: &serde_json::Value, key: &str| {
+        info.get(key)
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string()
+    };
+    Ok(data
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter_map(|(path, info)| {
+            // legacyPackages.<system>.<attr>; nested package sets are left out
+            let attr = path.splitn(3, '.').nth(2)?;
+            (!attr.contains('.')).then(|| IndexedPackage {
+                attr: attr.to_string(),
+                pname: field(info, "pname"),
+                version: field(info, "version"),
+                description: field(info, "description"),
+                updated_at: None,
+            })
+        })
+        .collect())
+}
+
+fn query_channel() -> Result<Vec<IndexedPackage>, String> {
+    let output = Command::new("nix-env")
+        .args(["-qaP", "--description"])
+        .output()
+        .map_err(|e| format!("nix-env: {}", e))?;
+    if !output.status.success() {
+        return Err(first_line(&output.stderr));
+    }
+    Ok(parse_nix_env(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// `nixos.neovim  neovim-0.10.2  Vim text editor fork …`
+fn parse_nix_env(output: &str) -> Vec<IndexedPackage> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let path = parts.next()?;
+            let name = parts.next()?;
+            let description = parts.collect::<Vec<_>>().join(" ");
+            let attr = path.split_once('.').map_or(path, |(_, attr)| attr);
+            if attr.contains('.') {
+                return None;
+            }
+            let (pname, version) = match name.rfind('-') {
+                Some(pos) if name[pos + 1..].starts_with(|c: char| c.is_ascii_digit()) => {
+                    (&name[..pos], &name[pos + 1..])
+                }
+                _ => (name, ""),
+            };
+            Some(IndexedPackage {
+                attr: attr.to_string(),
+                pname: pname.to_string(),
+                version: version.to_string(),
+                description,
+                updated_at: None,
+            })
+        })
+        .collect()
+}
+
+/// The `error:` line of a failed command, else its first line
+fn first_line(stderr: &[u8]) -> String {
+    let text = String::from_utf8_lossy(stderr);
+    let mut lines = text.lines().map(str::trim).filter(|l| !l.is_empty());
+    let first = lines.clone().next();
+    lines
+        .find(|l| l.starts_with("error:"))
+        .or(first)
+        .unwrap_or("failed")
+        .to_string()
+}
@@ -0,0 +1,256 @@
+//! Insecure / EOL packages in the current system closure.
+//!
+//! Store paths carry no meta, so the closure's `name-version`s are looked
+//! up in the nixpkgs the system is built from: one `nix eval` reads
+//! `meta.knownVulnerabilities` for a few candidate attributes per package
+//! (`openssl`, `openssl_1_1`, …). A package counts only when the attribute
+//! evaluates to exactly the name in the closure, so a newer version in
+//! nixpkgs doesn't hide (or fake) a finding. The unversioned attribute's
+//! version is offered as the upgrade path.
+//!
+//! Insecure packages only fail evaluation when not permitted; anything
+//! found here was let through by `permittedInsecurePackages` (or an
+//! `allowInsecurePredicate`), which nix mentions once and then never again.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::process::Command;
+
+const SYSTEM_PATH: &str = "/run/current-system";
+/// Output suffixes of multi-output store paths (`openssl-3.0.13-bin`)
+const OUTPUT_SUFFIXES: &[&str] = &[
+    "-bin", "-lib", "-dev", "-out", "-man", "-doc", "-info", "-debug",
+];
+
+#[derive(Debug, Clone)]
+pub struct InsecurePackage {
+    /// `name-version` as in the closure, also the permittedInsecurePackages entry
+    pub name: String,
+    pub attr: String,
+    pub reasons: Vec<String>,
+    /// Marked end-of-life rather than (only) vulnerable
+    pub eol: bool,
+    /// (attribute, version) of the same package without known issues
+    pub upgrade: Option<(String, String)>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct InsecureAudit {
+    pub packages: Vec<InsecurePackage>,
+    /// Distinct package names checked
+    pub checked: usize,
+}
+
+/// Run the audit (blocking, evaluates nixpkgs — run in a thread)
+pub fn scan(config_path: Option<&str>) -> Result<InsecureAudit, String> {
+    // Plain output(): the closure listing is too big for a polled pipe
+    let closure = Command::new("nix-store")
+        .args(["-qR", SYSTEM_PATH])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .ok_or_else(|| format!("nix-store -qR {} failed", SYSTEM_PATH))?;
+    let names: BTreeSet<(String, String)> = String::from_utf8_lossy(&closure.stdout)
+        .lines()
+        .filter_map(|l| split_store_name(l.trim()))
+        .collect();
+
+    let candidates: BTreeMap<String, Vec<String>> = names
+        .iter()
+        .map(|(pname, version)| {
+            (
+                format!("{}-{}", pname, version),
+                candidate_attrs(pname, version),
+            )
+        })
+        .collect();
+    let attrs: BTreeSet<&str> = candidates.values().flatten().map(|a| a.as_str()).collect();
+
+    let nixpkgs = nixpkgs_expr(config_path);
+    let meta = eval_meta(&nixpkgs, &attrs)?;
+
+    let mut packages: Vec<InsecurePackage> = candidates
+        .iter()
+        .filter_map(|(name, attrs)| {
+            let (attr, info) = attrs
+                .iter()
+                .filter_map(|a| Some((a, meta.get(a)?)))
+                .find(|(_, info)| info.name == *name && !info.vulns.is_empty())?;
+            let eol = info.vulns.iter().any(|r| is_eol_reason(r));
+            // The plain attribute, if it's a different version without issues
+            let upgrade = attrs.first().and_then(|plain| {
+                let fixed = meta.get(plain)?;
+                (fixed.name != *name && fixed.vulns.is_empty())
+                    .then(|| (plain.clone(), fixed.version.clone()))
+            });
+            Some(InsecurePackage {
+                name: name.clone(),
+                attr: attr.clone(),
+                reasons: info.vulns.clone(),
+                eol,
+                upgrade,
+            })
+        })
+        .collect();
+    packages.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(InsecureAudit {
+        packages,
+        checked: names.len(),
+    })
+}
+
+/// `/nix/store/<hash>-openssl-1.1.1w-bin` → ("openssl", "1.1.1w").
+/// Like `builtins.parseDrvName`: the version starts at the first dash
+/// followed by a digit.
+fn split_store_name(path: &str) -> Option<(String, String)> {
+    let base = path.rsplit('/').next()?;
+    let (_hash, name) = base.split_once('-')?;
+    if name.ends_with(".drv") {
+        return None;
+    }
+    let name = OUTPUT_SUFFIXES
+        .iter()
+        .find_map(|sfx| name.strip_suffix(sfx))
+        .unwrap_or(name);
+    let pos = name
+        .char_indices()
+        .find(|&(i, c)| c == '-' && name[i + 1..].starts_with(|d: char| d.is_ascii_digit()))
+        .map(|(i, _)| i)?;
+    Some((name[..pos].to_string(), name[pos + 1..].to_string()))
+}
+
+/// Attribute names that may hold `pname` at `version`, unversioned first
+fn candidate_attrs(pname: &str, version: &str) -> Vec<String> {
+    let mut attrs = vec![pname.to_string()];
+    let parts: Vec<&str> = version
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .take_while(|p| p.chars().all(|c| c.is_ascii_digit()) && !p.is_empty())
+        .collect();
+    for n in 1..=parts.len().min(3) {
+        attrs.push(format!("{}_{}", pname, parts[..n].join("_")));
+    }
+    // Only plain identifiers make it into the Nix expression
+    attrs.retain(|a| {
+        a.chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+            && a.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+    });
+    attrs
+}
+
+fn is_eol_reason(reason: &str) -> bool {
+    let r = reason.to_lowercase();
+    r.contains("end of life")
+        || r.contains("end-of-life")
+        || r.contains("no longer maintained")
+        || r.contains("unmaintained")
+        || r.split(|c: char| !c.is_ascii_alphanumeric())
+            .any(|w| w == "eol")
+}
+
+/// Nix expression for the nixpkgs the system is built from: the flake's
+/// locked `nixpkgs` input, else `<nixpkgs>` from NIX_PATH
+fn nixpkgs_expr(config_path: Option<&str>) -> String {
+    let locked = crate::modules::flake_inputs::find_flake_dir(config_path).and_then(|dir| {
+        let output = Command::new("nix")
+            .args([
+                "--extra-experimental-features",
+                "nix-command flakes",
+                "flake",
+                "archive",
+                "--json",
+                "--dry-run",
+            ])
+            .current_dir(dir)
+            .output()
+            .ok()?;
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).ok()?;
+        let inputs = json.get("inputs")?.as_object()?;
+        let input = inputs.get("nixpkgs").or_else(|| {
+            inputs
+                .iter()
+                .find(|(k, _)| k.starts_with("nixpkgs"))
+                .map(|(_, v)| v)
+        })?;
+        input.get("path")?.as_str().map(|p| p.to_string())
+    });
+    match locked {
+        Some(path) if path.starts_with("/nix/store/") && !path.contains(char::is_whitespace) => {
+            path
+        }
+        _ => "<nixpkgs>".to_string(),
+    }
+}
+
+struct MetaInfo {
+    name: String,
+    version: String,
+    vulns: Vec<String>,
+}
+
+/// name, version and knownVulnerabilities of `attrs` in one evaluation.
+/// Attributes that are missing or throw (removed aliases) are skipped.
+fn eval_meta(nixpkgs: &str, attrs: &BTreeSet<&str>) -> Result<BTreeMap<String, MetaInfo>, String> {
+    let list: Vec<String> = attrs.iter().map(|a| format!("\"{}\"", a)).collect();
+    let expr = format!(
+        r#"let
+  pkgs = import {} {{ config = {{ }}; overlays = [ ]; }};
+  info = a:
+    let
+      r = builtins.tryEval (
+        let p = pkgs.${{a}} or null; in
+        if builtins.isAttrs p && p ? name then
+          let v = {{
+            name = p.name;
+            version = p.version or "";
+            vulns = p.meta.knownVulnerabilities or [ ];
+          }}; in builtins.deepSeq v v
+        else null);
+    in if r.success then r.value else null;
+in builtins.listToAttrs (map (a: {{ name = a; value = info a; }}) [ {} ])"#,
+        nixpkgs,
+        list.join(" ")
+    );
+
+    let output = Command::new("nix")
+        .args([
+            "--extra-experimental-features",
+            "nix-command",
+            "eval",
+            "--impure",
+            "--json",
+            "--expr",
+            &expr,
+        ])
+        .output()
+        .map_err(|e| format!("Failed to run nix eval: {}", e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(stderr
+            .lines()
+            .find(|l| l.trim_start().starts_with("error:"))
+            .unwrap_or("nix eval failed")
+            .trim()
+            .to_string());
+    }
+
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).map_err(|e| e.to_string())?;
+    let obj = json.as_object().ok_or("unexpected nix eval output")?;
+    Ok(obj
+        .iter()
+        .filter_map(|(attr, v)| {
+            let info = MetaInfo {
+                name: v.get("name")?.as_str()?.to_string(),
+                version: v.get("version")?.as_str().unwrap_or("").to_string(),
+                vulns: v
+                    .get("vulns")?
+                    .as_array()?
+                    .iter()
+                    .filter_map(|r| r.as_str().map(|s| s.to_string()))
+                    .collect(),
+            };
+            Some((attr.clone(), info))
+        })
+        .collect())
+}
@@ -4,18 +4,33 @@
 //! Auto-detects flakes vs channels, configurable in Settings.
 //! Shows package name, version, description, and installed status.
 //! Fun loading messages while nix search runs.
+//! `v` audits the installed system for insecure / EOL packages.
+//! Browse lists packages by category, popular or recently updated first.
+
+mod browse;
+mod index;
+mod insecure;
 
 use crate::config::Language;
 use crate::i18n;
+use crate::modules::jobs::Job;
+use crate::modules::module::{Module, RenderCtx};
+use crate::modules::palette::PaletteCommand;
+use crate::nix::availability::Platform;
+use crate::session::ModuleSession;
 use crate::types::FlashMessage;
 use crate::ui::theme::Theme;
+use crate::ui::widgets;
 use anyhow::Result;
+use browse::{BrowseSort, Category};
 use crossterm::event::{KeyCode, KeyEvent};
+use index::PackageIndex;
+use insecure::InsecureAudit;
 use ratatui::{
     layout::{Alignment, Constraint, Layout, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Tabs},
     Frame,
 };
 use std::sync::mpsc;
@@ -48,9 +63,51 @@ pub enum SearchStatus {
     Error(String),
 }
 
+// ── Sub-tabs ──
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PkgSubTab {
+    #[default]
+    Search,
+    Browse,
+}
+
+impl PkgSubTab {
+    pub fn all() -> &'static [PkgSubTab] {
+        &[PkgSubTab::Search, PkgSubTab::Browse]
+    }
+
+    pub fn index(&self) -> usize {
+        match self {
+            PkgSubTab::Search => 0,
+            PkgSubTab::Browse => 1,
+        }
+    }
+
+    pub fn label(&self, lang: Language) -> &'static str {
+        let s = i18n::get_strings(lang);
+        match self {
+            PkgSubTab::Search => s.pkg_tab_search,
+            PkgSubTab::Browse => s.pkg_tab_browse,
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        let tabs = Self::all();
+        tabs[(self.index() + 1) % tabs.len()]
+    }
+
+    pub fn prev(&self) -> Self {
+        let tabs = Self::all();
+        tabs[(self.index() + tabs.len() - 1) % tabs.len()]
+    }
+}
+
 // ── Module state ──
 
 pub struct PackagesState {
+    pub sub_tab: PkgSubTab,
+
     // Search
     pub search_active: bool,
     pub search_query: String,
@@ -64,6 +121,24 @@ pub struct PackagesState {
     // Detail view
     pub detail_open: bool,
 
+    // Browse: the package index, listed by category
+    pub index: Option<PackageIndex>,
+    pub index_loading: bool,
+    pub index_error: Option<String>,
+    index_rx: Option<mpsc::Receiver<Result<PackageIndex, String>>>,
+    pub browse_category: usize,
+    pub browse_sort: BrowseSort,
+    pub browse_selected: usize,
+    /// Packages of the category with when their version last changed
+    pub browse_items: Vec<(SearchResult, Option<i64>)>,
+
+    // Insecure / EOL audit of the current system
+    pub audit_open: bool,
+    pub audit: Option<Result<InsecureAudit, String>>,
+    pub audit_loading: bool,
+    pub audit_selected: usize,
+    audit_rx: Option<mpsc::Receiver<Result<InsecureAudit, String>>>,
+
     // Background search
     pub loading: bool,
     pub loading_start: Option<Instant>,
@@ -90,6 +165,7 @@ pub struct PackagesState {
 impl PackagesState {
     pub fn new() -> Self {
         Self {
+            sub_tab: PkgSubTab::Search,
             search_active: false,
             search_query: String::new(),
             last_query: String::new(),
@@ -97,6 +173,19 @@ impl PackagesState {
             selected: 0,
             scroll_offset: 0,
             detail_open: false,
+            index: None,
+            index_loading: false,
+            index_error: None,
+            index_rx: None,
+            browse_category: 0,
+            browse_sort: BrowseSort::default(),
+            browse_selected: 0,
+            browse_items: Vec::new(),
+            audit_open: false,
+            audit: None,
+            audit_loading: false,
+            audit_selected: 0,
+            audit_rx: None,
             loading: false,
             loading_start: None,
             loading_phase: String::new(),
@@ -146,6 +235,117 @@ impl PackagesState {
     pub fn reset_source(&mut self) {
         self.source_detected = false;
         self.source = None;
+        self.index = None;
+        self.index_rx = None;
+        self.index_loading = false;
+        self.index_error = None;
+        self.browse_items.clear();
+    }
+
+    /// Load what the current sub-tab shows
+    fn ensure_sub_tab_loaded(&mut self) {
+        if self.sub_tab == PkgSubTab::Browse {
+            self.ensure_index_loaded();
+        }
+    }
+
+    /// Load the saved package index; build it in the background when
+    /// there's none for this source, or rebuild it when it's stale (the old
+    /// one is shown meanwhile)
+    fn ensure_index_loaded(&mut self) {
+        if self.index.is_some() || self.index_loading || self.index_error.is_some() {
+            return;
+        }
+        let Some(source) = self.source.clone() else {
+            return;
+        };
+        let saved = index::load(&source.channel);
+        if let Some(saved) = &saved {
+            self.index = Some(saved.clone());
+            self.refresh_browse();
+        }
+        if saved.as_ref().is_none_or(PackageIndex::is_stale) {
+            self.start_index_build(source, saved);
+        }
+    }
+
+    /// `R`: build the index again, comparing versions with the current one
+    fn rebuild_index(&mut self) {
+        if self.index_loading {
+            return;
+        }
+        let Some(source) = self.source.clone() else {
+            return;
+        };
+        let previous = self.index.clone().or_else(|| index::load(&source.channel));
+        self.start_index_build(source, previous);
+    }
+
+    fn start_index_build(&mut self, source: NixpkgsSource, previous: Option<PackageIndex>) {
+        self.index_loading = true;
+        self.index_error = None;
+        let (tx, rx) = mpsc::channel();
+        self.index_rx = Some(rx);
+        std::thread::spawn(move || {
+            let _ = tx.send(index::build(&source, previous));
+        });
+    }
+
+    fn poll_index(&mut self) {
+        let Some(rx) = &self.index_rx else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(result) => {
+                self.index_loading = false;
+                self.index_rx = None;
+                match result {
+                    Ok(index) => {
+                        self.index = Some(index);
+                        self.refresh_browse();
+                    }
+                    Err(e) => self.index_error = Some(e),
+                }
+            }
+            Err(mpsc::TryRecvError::Empty) => {}
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.index_loading = false;
+                self.index_rx = None;
+            }
+        }
+    }
+
+    /// List the selected category again, sorted
+    fn refresh_browse(&mut self) {
+        let Some(index) = &self.index else {
+            self.browse_items.clear();
+            return;
+        };
+        let category = Category::ALL[self.browse_category];
+        let installed = &self.installed_packages;
+        let items = browse::list(&index.packages, category, self.browse_sort)
+            .into_iter()
+            .map(|p| {
+                let result = SearchResult {
+                    attr: p.attr.clone(),
+                    pname: p.pname.clone(),
+                    version: p.version.clone(),
+                    description: p.description.clone(),
+                    installed: installed.iter().any(|i| *i == p.pname || *i == p.attr),
+                };
+                (result, p.updated_at)
+            })
+            .collect();
+        self.browse_items = items;
+        self.browse_selected = 0;
+    }
+
+    /// Package shown by the detail view
+    fn detail_package(&self) -> Option<&SearchResult> {
+        match self.sub_tab {
+            PkgSubTab::Search => self.results.get(self.selected),
+            PkgSubTab::Browse => self.browse_items.get(self.browse_selected).map(|(p, _)| p),
+        }
     }
 
     /// Start a background search
@@ -179,8 +379,68 @@ impl PackagesState {
         });
     }
 
+    /// Scan the system closure for insecure / EOL packages in the background
+    fn start_audit(&mut self) {
+        if self.audit_loading {
+            return;
+        }
+        self.audit_loading = true;
+        self.audit_selected = 0;
+        let config_path = self.config_path.clone();
+        let (tx, rx) = mpsc::channel();
+        self.audit_rx = Some(rx);
+        std::thread::spawn(move || {
+            let _ = tx.send(insecure::scan(config_path.as_deref()));
+        });
+    }
+
+    fn poll_audit(&mut self) {
+        let Some(rx) = &self.audit_rx else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(result) => {
+                self.audit = Some(result);
+                self.audit_loading = false;
+                self.audit_rx = None;
+            }
+            Err(mpsc::TryRecvError::Empty) => {}
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.audit_loading = false;
+                self.audit_rx = None;
+            }
+        }
+    }
+
+    fn audit_len(&self) -> usize {
+        match &self.audit {
+            Some(Ok(audit)) => audit.packages.len(),
+            _ => 0,
+        }
+    }
+
+    fn handle_audit_key(&mut self, key: KeyEvent) {
+        let len = self.audit_len();
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('v') => self.audit_open = false,
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.audit_selected = (self.audit_selected + 1).min(len.saturating_sub(1));
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.audit_selected = self.audit_selected.saturating_sub(1);
+            }
+            KeyCode::Char('g') => self.audit_selected = 0,
+            KeyCode::Char('G') => self.audit_selected = len.saturating_sub(1),
+            KeyCode::Char('r') => self.start_audit(),
+            _ => {}
+        }
+    }
+
     /// Poll for search results (non-blocking)
     pub fn poll_search(&mut self) {
+        self.poll_audit();
+        self.poll_index();
+
         if self.loading {
             if let Some(last) = self.last_joke_change {
                 if last.elapsed().as_secs() >= 8 {
@@ -232,6 +492,84 @@ impl PackagesState {
         }
     }
 
+    /// Last search for the session file
+    pub fn session(&self) -> ModuleSession {
+        ModuleSession {
+            query: self.last_query.clone(),
+            ..Default::default()
+        }
+    }
+
+    /// The search runs again when the tab is opened (see `resume_search`)
+    pub fn restore_session(&mut self, saved: &ModuleSession) {
+        self.search_query = saved.query.clone();
+    }
+
+    /// Run a restored search that hasn't run yet
+    pub fn resume_search(&mut self) {
+        if self.last_query.is_empty() && !self.loading {
+            self.start_search();
+        }
+    }
+
+    /// Actions offered in the command palette
+    /// Running background work, for the jobs overlay
+    pub fn jobs(&self, s: &i18n::Strings) -> Vec<Job> {
+        let mut jobs = Vec::new();
+        if self.loading {
+            jobs.push(
+                Job::new(
+                    "search",
+                    s.job_pkg_search.replace("{}", &self.last_query),
+                    true,
+                )
+                .detail(self.loading_phase.clone()),
+            );
+        }
+        if self.audit_loading {
+            jobs.push(Job::new("audit", s.job_pkg_audit, true));
+        }
+        if self.index_loading {
+            let source = self.source.as_ref().map(|s| s.display_name.clone());
+            jobs.push(Job::new("index", s.job_pkg_index, true).detail(source.unwrap_or_default()));
+        }
+        jobs
+    }
+
+    /// Stop waiting for a job from `jobs()`; its result is discarded
+    pub fn cancel_job(&mut self, id: &str) {
+        let s = i18n::get_strings(self.lang);
+        match id {
+            "search" => {
+                self.search_rx = None;
+                self.loading = false;
+                self.error_message = Some(s.jobs_cancelled_error.to_string());
+            }
+            "audit" => {
+                self.audit_rx = None;
+                self.audit_loading = false;
+                self.audit = Some(Err(s.jobs_cancelled_error.to_string()));
+            }
+            "index" => {
+                self.index_rx = None;
+                self.index_loading = false;
+                self.index_error = Some(s.jobs_cancelled_error.to_string());
+            }
+            _ => {}
+        }
+    }
+
+    pub fn palette_commands(&self, s: &i18n::Strings) -> Vec<PaletteCommand> {
+        let search = Some(PkgSubTab::Search.index());
+        let browse = Some(PkgSubTab::Browse.index());
+        vec![
+            PaletteCommand::new(s.pal_pkg_search, search, vec![KeyCode::Char('/')]),
+            PaletteCommand::new(s.pal_pkg_insecure, None, vec![KeyCode::Char('v')]),
+            PaletteCommand::new(s.pal_pkg_browse, browse, vec![]),
+            PaletteCommand::new(s.pal_pkg_rebuild_index, browse, vec![KeyCode::Char('R')]),
+        ]
+    }
+
     pub fn handle_key(&mut self, key: KeyEvent) -> Result<bool> {
         if self.detail_open {
             match key.code {
@@ -243,6 +581,11 @@ impl PackagesState {
             return Ok(true);
         }
 
+        if self.audit_open {
+            self.handle_audit_key(key);
+            return Ok(true);
+        }
+
         if self.search_active {
             match key.code {
                 KeyCode::Enter => {
@@ -263,6 +606,34 @@ impl PackagesState {
             return Ok(true);
         }
 
+        match key.code {
+            KeyCode::Char('[') => {
+                self.sub_tab = self.sub_tab.prev();
+                self.ensure_sub_tab_loaded();
+                return Ok(true);
+            }
+            KeyCode::Char(']') => {
+                self.sub_tab = self.sub_tab.next();
+                self.ensure_sub_tab_loaded();
+                return Ok(true);
+            }
+            KeyCode::Char('v') => {
+                self.audit_open = true;
+                if self.audit.is_none() {
+                    self.start_audit();
+                }
+                return Ok(true);
+            }
+            _ => {}
+        }
+
+        match self.sub_tab {
+            PkgSubTab::Search => Ok(self.handle_search_key(key)),
+            PkgSubTab::Browse => Ok(self.handle_browse_key(key)),
+        }
+    }
+
+    fn handle_search_key(&mut self, key: KeyEvent) -> bool {
         match key.code {
             KeyCode::Char('/') | KeyCode::Char('i') => {
                 self.search_active = true;
@@ -295,9 +666,44 @@ impl PackagesState {
                 self.last_query.clear();
                 self.search_active = true;
             }
-            _ => return Ok(false),
+            _ => return false,
         }
-        Ok(true)
+        true
+    }
+
+    fn handle_browse_key(&mut self, key: KeyEvent) -> bool {
+        let len = self.browse_items.len();
+        let categories = Category::ALL.len();
+        match key.code {
+            KeyCode::Char('l') | KeyCode::Right => {
+                self.browse_category = (self.browse_category + 1) % categories;
+                self.refresh_browse();
+            }
+            KeyCode::Char('h') | KeyCode::Left => {
+                self.browse_category = (self.browse_category + categories - 1) % categories;
+                self.refresh_browse();
+            }
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.browse_selected = (self.browse_selected + 1).min(len.saturating_sub(1));
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.browse_selected = self.browse_selected.saturating_sub(1);
+            }
+            KeyCode::Char('g') => self.browse_selected = 0,
+            KeyCode::Char('G') => self.browse_selected = len.saturating_sub(1),
+            KeyCode::Char('s') => {
+                self.browse_sort = self.browse_sort.next();
+                self.refresh_browse();
+            }
+            KeyCode::Enter if len > 0 => self.detail_open = true,
+            KeyCode::Char('R') => self.rebuild_index(),
+            KeyCode::Char('/') => {
+                self.sub_tab = PkgSubTab::Search;
+                self.search_active = true;
+            }
+            _ => return false,
+        }
+        true
     }
 }
 
@@ -409,7 +815,10 @@ fn detect_channel_name() -> Option<String> {
         }
     }
 
-    // Fallback: /etc/os-release VERSION_ID
+    // Fallback on NixOS: /etc/os-release VERSION_ID
+    if !Platform::current().is_nixos() {
+        return None;
+    }
     if let Ok(content) = std::fs::read_to_string("/etc/os-release") {
         for line in content.lines() {
             if let Some(version) = line.strip_prefix("VERSION_ID=") {
@@ -659,6 +1068,54 @@ fn load_installed_packages() -> Vec<String> {
 
 // ── Rendering ──
 
+impl Module for PackagesState {
+    fn handle_key(&mut self, key: KeyEvent, _lang: Language) -> Result<()> {
+        self.handle_key(key).map(|_| ())
+    }
+
+    fn captures_input(&self) -> bool {
+        self.search_active || self.detail_open || self.audit_open
+    }
+
+    fn render(&mut self, frame: &mut Frame, ctx: &RenderCtx, area: Rect) {
+        render(frame, self, ctx.theme, ctx.lang, area);
+    }
+
+    fn activate(&mut self) {
+        self.ensure_installed_loaded();
+        self.resume_search();
+        self.ensure_sub_tab_loaded();
+    }
+
+    fn poll(&mut self, _active: bool) {
+        self.poll_search();
+    }
+
+    fn commands(&self, s: &i18n::Strings) -> Vec<PaletteCommand> {
+        self.palette_commands(s)
+    }
+
+    fn jobs(&self, s: &i18n::Strings) -> Vec<Job> {
+        self.jobs(s)
+    }
+
+    fn cancel_job(&mut self, id: &str) {
+        self.cancel_job(id)
+    }
+
+    fn sub_tab(&self) -> Option<(usize, usize)> {
+        Some((self.sub_tab.index(), PkgSubTab::all().len()))
+    }
+
+    fn sub_tab_label(&self, lang: Language) -> Option<&'static str> {
+        Some(self.sub_tab.label(lang))
+    }
+
+    fn flash_message(&mut self) -> &mut Option<FlashMessage> {
+        &mut self.flash_message
+    }
+}
+
 pub fn render(frame: &mut Frame, state: &PackagesState, theme: &Theme, lang: Language, area: Rect) {
     let s = i18n::get_strings(lang);
 
@@ -677,22 +1134,65 @@ pub fn render(frame: &mut Frame, state: &PackagesState, theme: &Theme, lang: Lan
     }
 
     let chunks = Layout::vertical([
+        Constraint::Length(1), // Sub-tabs
         Constraint::Length(1), // Source
-        Constraint::Length(2), // Search bar
-        Constraint::Min(4),    // Results or loading
+        Constraint::Min(4),    // Search or Browse
     ])
     .split(inner);
 
-    render_source_line(frame, state, theme, chunks[0]);
-    render_search_bar(frame, state, theme, lang, chunks[1]);
+    render_tab_bar(frame, state, theme, lang, chunks[0]);
+    render_source_line(frame, state, theme, chunks[1]);
 
-    if state.loading {
-        render_loading(frame, state, theme, chunks[2]);
-    } else if state.detail_open && !state.results.is_empty() {
-        render_detail(frame, state, theme, lang, chunks[2]);
-    } else {
-        render_results(frame, state, theme, lang, chunks[2]);
+    if state.audit_open {
+        render_audit(frame, state, theme, lang, chunks[2]);
+        return;
     }
+    if state.detail_open {
+        if let Some(pkg) = state.detail_package() {
+            render_detail(frame, pkg, theme, lang, chunks[2]);
+            return;
+        }
+    }
+    match state.sub_tab {
+        PkgSubTab::Search => {
+            let chunks = Layout::vertical([
+                Constraint::Length(2), // Search bar
+                Constraint::Min(2),    // Results or loading
+            ])
+            .split(chunks[2]);
+            render_search_bar(frame, state, theme, lang, chunks[0]);
+            if state.loading {
+                render_loading(frame, state, theme, chunks[1]);
+            } else {
+                render_results(frame, state, theme, lang, chunks[1]);
+            }
+        }
+        PkgSubTab::Browse => render_browse(frame, state, theme, lang, chunks[2]),
+    }
+}
+
+fn render_tab_bar(
+    frame: &mut Frame,
+    state: &PackagesState,
+    theme: &Theme,
+    lang: Language,
+    area: Rect,
+) {
+    let titles: Vec<Line> = PkgSubTab::all()
+        .iter()
+        .map(|t| Line::from(t.label(lang)))
+        .collect();
+    let tabs = Tabs::new(titles)
+        .select(state.sub_tab.index())
+        .style(theme.text_dim())
+        .highlight_style(
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )
+        .divider(" │ ");
+    let tabs_area = widgets::render_sub_tab_nav(frame, theme, area);
+    frame.render_widget(tabs, tabs_area);
 }
 
 fn render_source_line(frame: &mut Frame, state: &PackagesState, theme: &Theme, area: Rect) {
@@ -859,70 +1359,85 @@ fn render_results(
         scroll = state.selected;
     }
 
-    let name_width = 28usize.min(area.width as usize / 3);
-    let version_width = 14usize.min(area.width as usize / 5);
-
     let items: Vec<ListItem> = state
         .results
         .iter()
         .enumerate()
         .skip(scroll)
         .take(visible_height)
-        .map(|(i, pkg)| {
-            let is_selected = i == state.selected;
-            let installed_marker = if pkg.installed { "✓ " } else { "  " };
-            let name = if pkg.attr != pkg.pname && !pkg.attr.is_empty() {
-                format!("{:<width$}", pkg.attr, width = name_width)
-            } else {
-                format!("{:<width$}", pkg.pname, width = name_width)
-            };
-            let version = format!("{:<width$}", pkg.version, width = version_width);
+        .map(|(i, pkg)| package_row(pkg, None, i == state.selected, theme, area.width))
+        .collect();
 
-            let desc_width = (area.width as usize).saturating_sub(name_width + version_width + 6);
-            let description: String = if pkg.description.len() > desc_width {
-                format!("{}…", &pkg.description[..desc_width.saturating_sub(1)])
-            } else {
-                pkg.description.clone()
-            };
+    frame.render_widget(List::new(items).style(theme.block_style()), area);
+}
 
-            let style = if is_selected {
-                theme.selected()
-            } else {
-                theme.text()
-            };
-            let installed_style = if pkg.installed {
-                Style::default().fg(theme.success)
-            } else {
-                Style::default().fg(theme.fg_dim)
-            };
+/// One package in a list: installed mark, attribute, version, an optional
+/// extra column (Browse: date of the last update), description
+fn package_row(
+    pkg: &SearchResult,
+    extra: Option<String>,
+    is_selected: bool,
+    theme: &Theme,
+    width: u16,
+) -> ListItem<'static> {
+    let name_width = 28usize.min(width as usize / 3);
+    let version_width = 14usize.min(width as usize / 5);
+
+    let installed_marker = if pkg.installed { "✓ " } else { "  " };
+    let name = if pkg.attr != pkg.pname && !pkg.attr.is_empty() {
+        format!("{:<width$}", pkg.attr, width = name_width)
+    } else {
+        format!("{:<width$}", pkg.pname, width = name_width)
+    };
+    let version = format!("{:<width$}", pkg.version, width = version_width);
+    let extra = extra.map(|e| format!("{} ", e)).unwrap_or_default();
+
+    let desc_width =
+        (width as usize).saturating_sub(name_width + version_width + extra.chars().count() + 6);
+    let description: String = if pkg.description.chars().count() > desc_width {
+        let cut: String = pkg
+            .description
+            .chars()
+            .take(desc_width.saturating_sub(1))
+            .collect();
+        format!("{}…", cut)
+    } else {
+        pkg.description.clone()
+    };
 
-            ListItem::new(Line::from(vec![
-                Span::styled(installed_marker.to_string(), installed_style),
-                Span::styled(
-                    name,
-                    if is_selected {
-                        style.add_modifier(Modifier::BOLD)
-                    } else {
-                        Style::default().fg(theme.accent)
-                    },
-                ),
-                Span::styled(format!(" {} ", version), style),
-                Span::styled(
-                    description,
-                    if is_selected {
-                        style
-                    } else {
-                        Style::default().fg(theme.fg_dim)
-                    },
-                ),
-            ]))
-        })
-        .collect();
+    let style = if is_selected {
+        theme.selected()
+    } else {
+        theme.text()
+    };
+    let installed_style = if pkg.installed {
+        Style::default().fg(theme.success)
+    } else {
+        Style::default().fg(theme.fg_dim)
+    };
+    let dim = if is_selected {
+        style
+    } else {
+        Style::default().fg(theme.fg_dim)
+    };
 
-    frame.render_widget(List::new(items).style(theme.block_style()), area);
+    ListItem::new(Line::from(vec![
+        Span::styled(installed_marker.to_string(), installed_style),
+        Span::styled(
+            name,
+            if is_selected {
+                style.add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.accent)
+            },
+        ),
+        Span::styled(format!(" {} ", version), style),
+        Span::styled(extra, dim),
+        Span::styled(description, dim),
+    ]))
 }
 
-fn render_detail(
+fn render_browse(
     frame: &mut Frame,
     state: &PackagesState,
     theme: &Theme,
@@ -930,10 +1445,137 @@ fn render_detail(
     area: Rect,
 ) {
     let s = i18n::get_strings(lang);
-    let Some(pkg) = state.results.get(state.selected) else {
+
+    let message = |text: String, color| {
+        Paragraph::new(vec![
+            Line::raw(""),
+            Line::raw(""),
+            Line::styled(text, Style::default().fg(color)),
+        ])
+        .alignment(Alignment::Center)
+        .style(theme.block_style())
+    };
+    let Some(index) = &state.index else {
+        let source = state.source.as_ref().map_or("nixpkgs", |s| &s.display_name);
+        let msg = match &state.index_error {
+            Some(e) => message(s.pkg_browse_failed.replace("{}", e), theme.error),
+            None => message(s.pkg_browse_building.replace("{}", source), theme.accent),
+        };
+        frame.render_widget(msg, area);
         return;
     };
 
+    let chunks = Layout::vertical([
+        Constraint::Length(2), // Sort, index state
+        Constraint::Min(2),    // Categories | packages
+        Constraint::Length(1), // Hint
+    ])
+    .split(area);
+
+    let date = |t: i64| {
+        chrono::DateTime::from_timestamp(t, 0)
+            .map(|d| {
+                d.with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d")
+                    .to_string()
+            })
+            .unwrap_or_default()
+    };
+    let mut header = vec![Span::styled(
+        format!(
+            "  {}",
+            s.pkg_browse_sort.replace("{}", state.browse_sort.label(s))
+        ),
+        Style::default()
+            .fg(theme.accent)
+            .add_modifier(Modifier::BOLD),
+    )];
+    if state.browse_sort == BrowseSort::Updated {
+        let tracked = if index.tracked_since == index.built_at {
+            s.pkg_browse_not_tracked.to_string()
+        } else {
+            s.pkg_browse_tracked
+                .replace("{}", &date(index.tracked_since))
+        };
+        header.push(Span::styled(
+            format!("  · {}", tracked),
+            Style::default().fg(theme.fg_dim),
+        ));
+    }
+    if state.index_loading {
+        header.push(Span::styled(
+            format!("  ⟳ {}", s.job_pkg_index),
+            Style::default().fg(theme.warning),
+        ));
+    } else if let Some(e) = &state.index_error {
+        header.push(Span::styled(
+            format!("  {}", s.pkg_browse_failed.replace("{}", e)),
+            Style::default().fg(theme.error),
+        ));
+    }
+    frame.render_widget(
+        Paragraph::new(Line::from(header)).style(theme.block_style()),
+        chunks[0],
+    );
+
+    let panes = Layout::horizontal([Constraint::Length(22), Constraint::Min(20)]).split(chunks[1]);
+
+    let categories: Vec<ListItem> = Category::ALL
+        .iter()
+        .enumerate()
+        .map(|(i, category)| {
+            let style = if i == state.browse_category {
+                theme.selected()
+            } else {
+                theme.text()
+            };
+            ListItem::new(Line::styled(format!("  {}", category.label(s)), style))
+        })
+        .collect();
+    frame.render_widget(List::new(categories).style(theme.block_style()), panes[0]);
+
+    if state.browse_items.is_empty() {
+        frame.render_widget(
+            message(s.pkg_browse_empty.to_string(), theme.fg_dim),
+            panes[1],
+        );
+    } else {
+        let visible = panes[1].height as usize;
+        let scroll = (state.browse_selected + 1).saturating_sub(visible);
+        let items: Vec<ListItem> = state
+            .browse_items
+            .iter()
+            .enumerate()
+            .skip(scroll)
+            .take(visible)
+            .map(|(i, (pkg, updated_at))| {
+                let extra = (state.browse_sort == BrowseSort::Updated)
+                    .then(|| format!("{:<10}", updated_at.map(date).unwrap_or_else(|| "—".into())));
+                package_row(
+                    pkg,
+                    extra,
+                    i == state.browse_selected,
+                    theme,
+                    panes[1].width,
+                )
+            })
+            .collect();
+        frame.render_widget(List::new(items).style(theme.block_style()), panes[1]);
+    }
+
+    frame.render_widget(
+        Paragraph::new(Line::styled(
+            format!("  {}", s.pkg_browse_hint),
+            Style::default().fg(theme.fg_dim),
+        ))
+        .style(theme.block_style()),
+        chunks[2],
+    );
+}
+
+fn render_detail(frame: &mut Frame, pkg: &SearchResult, theme: &Theme, lang: Language, area: Rect) {
+    let s = i18n::get_strings(lang);
+
     let mut lines: Vec<Line> = Vec::new();
     lines.push(Line::raw(""));
 
@@ -1005,3 +1647,172 @@ fn render_detail(
 
     frame.render_widget(Paragraph::new(lines).style(theme.block_style()), area);
 }
+
+fn render_audit(
+    frame: &mut Frame,
+    state: &PackagesState,
+    theme: &Theme,
+    lang: Language,
+    area: Rect,
+) {
+    let s = i18n::get_strings(lang);
+
+    let message = |text: String, color| {
+        Paragraph::new(vec![
+            Line::raw(""),
+            Line::raw(""),
+            Line::styled(text, Style::default().fg(color)),
+        ])
+        .alignment(Alignment::Center)
+        .style(theme.block_style())
+    };
+
+    let audit = match (&state.audit, state.audit_loading) {
+        (_, true) | (None, false) => {
+            frame.render_widget(
+                message(s.pkg_audit_scanning.to_string(), theme.accent),
+                area,
+            );
+            return;
+        }
+        (Some(Err(e)), false) => {
+            frame.render_widget(
+                message(s.pkg_audit_failed.replace("{}", e), theme.error),
+                area,
+            );
+            return;
+        }
+        (Some(Ok(audit)), false) => audit,
+    };
+
+    let chunks = Layout::vertical([Constraint::Length(2), Constraint::Min(2)]).split(area);
+
+    let (summary, color) = if audit.packages.is_empty() {
+        (
+            s.pkg_audit_clean.replace("{}", &audit.checked.to_string()),
+            theme.success,
+        )
+    } else {
+        (
+            s.pkg_audit_count
+                .replacen("{}", &audit.packages.len().to_string(), 1)
+                .replacen("{}", &audit.checked.to_string(), 1),
+            theme.warning,
+        )
+    };
+    frame.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled(
+                format!("  {}  ", s.pkg_audit_title),
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(summary, Style::default().fg(color)),
+        ]))
+        .style(theme.block_style()),
+        chunks[0],
+    );
+
+    if audit.packages.is_empty() {
+        return;
+    }
+
+    let panes = Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(chunks[1]);
+
+    // List
+    let visible = panes[0].height as usize;
+    let scroll = (state.audit_selected + 1).saturating_sub(visible);
+    let items: Vec<ListItem> = audit
+        .packages
+        .iter()
+        .enumerate()
+        .skip(scroll)
+        .take(visible)
+        .map(|(i, pkg)| {
+            let (tag, color) = if pkg.eol {
+                ("EOL ", theme.warning)
+            } else {
+                ("⚠ ", theme.error)
+            };
+            let name_style = if i == state.audit_selected {
+                theme.selected()
+            } else {
+                theme.text()
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("  {}", tag), Style::default().fg(color)),
+                Span::styled(pkg.name.clone(), name_style),
+            ]))
+        })
+        .collect();
+    frame.render_widget(List::new(items).style(theme.block_style()), panes[0]);
+
+    // Detail
+    let Some(pkg) = audit.packages.get(state.audit_selected) else {
+        return;
+    };
+    let mut lines: Vec<Line> = vec![
+        Line::from(vec![
+            Span::styled(
+                pkg.name.clone(),
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!(
+                    "  ({})",
+                    if pkg.eol {
+                        "EOL"
+                    } else {
+                        s.pkg_audit_vulnerable
+                    }
+                ),
+                Style::default().fg(if pkg.eol { theme.warning } else { theme.error }),
+            ),
+        ]),
+        Line::styled(
+            format!("{} {}", s.pkg_detail_attr, pkg.attr),
+            Style::default().fg(theme.fg_dim),
+        ),
+        Line::raw(""),
+        Line::styled(s.pkg_audit_reasons, Style::default().fg(theme.fg_dim)),
+    ];
+    for reason in &pkg.reasons {
+        lines.push(Line::styled(format!("  • {}", reason), theme.text()));
+    }
+    lines.push(Line::raw(""));
+    match &pkg.upgrade {
+        Some((attr, version)) => lines.push(Line::styled(
+            s.pkg_audit_upgrade
+                .replacen("{}", attr, 1)
+                .replacen("{}", version, 1),
+            Style::default().fg(theme.success),
+        )),
+        None => lines.push(Line::styled(
+            s.pkg_audit_no_upgrade,
+            Style::default().fg(theme.warning),
+        )),
+    }
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        s.pkg_audit_permitted,
+        Style::default().fg(theme.fg_dim),
+    ));
+    lines.push(Line::styled(
+        format!(
+            "  nixpkgs.config.permittedInsecurePackages = [ \"{}\" ];",
+            pkg.name
+        ),
+        Style::default().fg(theme.accent),
+    ));
+
+    frame.render_widget(
+        Paragraph::new(lines)
+            .wrap(ratatui::widgets::Wrap { trim: false })
+            .style(theme.block_style()),
+        panes[1],
+    );
+}
@@ -0,0 +1,235 @@
+//! Command palette (Ctrl+P) — every module's actions, fuzzy-searchable
+//!
+//! Modules list what they can do via `palette_commands()`. A command is the
+//! module's own key sequence, optionally after switching to a sub-tab;
+//! `App` replays it as if typed, so the palette goes through the same
+//! confirmation popups as the keybindings it teaches.
+
+use crate::config::Language;
+use crate::i18n;
+use crate::ui::{ModuleTab, Theme};
+use crossterm::event::KeyCode;
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+    Frame,
+};
+
+/// One action as a module registers it
+#[derive(Debug, Clone)]
+pub struct PaletteCommand {
+    pub label: String,
+    /// Sub-tab (index into the module's `all()`) the keys apply to
+    pub sub_tab: Option<usize>,
+    pub keys: Vec<KeyCode>,
+}
+
+impl PaletteCommand {
+    pub fn new(label: impl Into<String>, sub_tab: Option<usize>, keys: Vec<KeyCode>) -> Self {
+        Self {
+            label: label.into(),
+            sub_tab,
+            keys,
+        }
+    }
+
+    /// Key shown next to the entry: the one that triggers the action
+    pub fn hint(&self) -> String {
+        match self.keys.last() {
+            Some(KeyCode::Char(' ')) => "Space".to_string(),
+            Some(KeyCode::Char(c)) => c.to_string(),
+            Some(KeyCode::Enter) => "Enter".to_string(),
+            Some(KeyCode::Tab) => "Tab".to_string(),
+            _ => String::new(),
+        }
+    }
+}
+
+/// A command with the module it belongs to
+#[derive(Debug, Clone)]
+pub struct PaletteEntry {
+    pub tab: ModuleTab,
+    /// "Module: action"
+    pub title: String,
+    pub command: PaletteCommand,
+}
+
+#[derive(Default)]
+pub struct PaletteState {
+    pub active: bool,
+    pub query: String,
+    pub selected: usize,
+    entries: Vec<PaletteEntry>,
+}
+
+impl PaletteState {
+    pub fn open(&mut self, entries: Vec<PaletteEntry>) {
+        self.active = true;
+        self.query.clear();
+        self.selected = 0;
+        self.entries = entries;
+    }
+
+    pub fn close(&mut self) {
+        self.active = false;
+        self.entries.clear();
+    }
+
+    /// Entries matching the query, best first
+    pub fn matches(&self) -> Vec<&PaletteEntry> {
+        let query = self.query.to_lowercase();
+        let mut scored: Vec<(i32, usize, &PaletteEntry)> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(i, e)| fuzzy_score(&query, &e.title.to_lowercase()).map(|s| (s, i, e)))
+            .collect();
+        scored.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+        scored.into_iter().map(|(_, _, e)| e).collect()
+    }
+
+    /// Take the selected entry and close the palette
+    pub fn take_selected(&mut self) -> Option<PaletteEntry> {
+        let entry = self.matches().get(self.selected).map(|e| (*e).clone());
+        self.close();
+        entry
+    }
+
+    pub fn select_next(&mut self) {
+        let count = self.matches().len();
+        if self.selected + 1 < count {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn push_char(&mut self, c: char) {
+        self.query.push(c);
+        self.selected = 0;
+    }
+
+    pub fn pop_char(&mut self) {
+        self.query.pop();
+        self.selected = 0;
+    }
+}
+
+/// Substring matches first (earlier is better), then subsequence matches
+/// where consecutive characters and word starts score higher.
+/// None if not all query characters appear in order.
+fn fuzzy_score(query: &str, target: &str) -> Option<i32> {
+    if query.is_empty() {
+        return Some(0);
+    }
+    if let Some(pos) = target.find(query) {
+        return Some(1000 - pos as i32);
+    }
+    let mut score = 0;
+    let mut prev_match: Option<usize> = None;
+    let mut query_chars = query.chars().filter(|c| !c.is_whitespace()).peekable();
+    let mut prev_char = ' ';
+    for (i, tc) in target.chars().enumerate() {
+        let Some(&qc) = query_chars.peek() else {
+            break;
+        };
+        if qc == tc {
+            score += 1;
+            if prev_match == Some(i.wrapping_sub(1)) {
+                score += 3;
+            }
+            if !prev_char.is_alphanumeric() {
+                score += 2;
+            }
+            prev_match = Some(i);
+            query_chars.next();
+        }
+        prev_char = tc;
+    }
+    query_chars.peek().is_none().then_some(score)
+}
+
+pub fn render(frame: &mut Frame, state: &PaletteState, theme: &Theme, lang: Language, area: Rect) {
+    let s = i18n::get_strings(lang);
+    let width = 72.min(area.width.saturating_sub(4));
+    let height = 20.min(area.height.saturating_sub(2));
+    if width < 20 || height < 5 {
+        return;
+    }
+    let popup = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + 1,
+        width,
+        height,
+    };
+    frame.render_widget(Clear, popup);
+
+    let block = Block::default()
+        .style(theme.block_style())
+        .title(format!(" {} ", s.palette_title))
+        .title_style(theme.title())
+        .borders(Borders::ALL)
+        .border_style(theme.border_focused());
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    let input = Line::from(vec![
+        Span::styled("> ", Style::default().fg(theme.accent)),
+        Span::styled(state.query.clone(), Style::default().fg(theme.fg)),
+        Span::styled("▏", Style::default().fg(theme.accent)),
+    ]);
+    frame.render_widget(Paragraph::new(input), Rect { height: 1, ..inner });
+
+    let list_area = Rect {
+        y: inner.y + 2,
+        height: inner.height.saturating_sub(2),
+        ..inner
+    };
+    let matches = state.matches();
+    if matches.is_empty() {
+        frame.render_widget(
+            Paragraph::new(Line::styled(s.palette_no_match, theme.text_dim())),
+            list_area,
+        );
+        return;
+    }
+
+    // Keep the selection in view
+    let visible = list_area.height as usize;
+    let offset = state.selected.saturating_sub(visible.saturating_sub(1));
+    let items: Vec<ListItem> = matches
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(visible)
+        .map(|(i, e)| {
+            let hint = e.command.hint();
+            let pad = (list_area.width as usize)
+                .saturating_sub(e.title.chars().count() + hint.chars().count() + 2);
+            let style = if i == state.selected {
+                theme.selected()
+            } else {
+                theme.text()
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!(" {}", e.title), style),
+                Span::styled(" ".repeat(pad), style),
+                Span::styled(
+                    format!("{} ", hint),
+                    if i == state.selected {
+                        style
+                    } else {
+                        Style::default()
+                            .fg(theme.accent)
+                            .add_modifier(Modifier::BOLD)
+                    },
+                ),
+            ]))
+        })
+        .collect();
+    frame.render_widget(List::new(items), list_area);
+}
@@ -0,0 +1,160 @@
+//! Build backends (`[rebuild_backend.<host>]` in config.toml)
+//!
+//! `nix-fast-build` or a multi-target `nix build` builds the configured
+//! flake attributes first — evaluated in parallel, several systems at
+//! once — and `nixos-rebuild` then only activates what is already in the
+//! store. Each attribute's progress shows on the dashboard.
+
+use crate::config::{BuildBackend, BuildBackendConfig};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttrState {
+    Queued,
+    Building,
+    Done,
+    Failed,
+}
+
+#[derive(Debug, Clone)]
+pub struct AttrProgress {
+    pub attr: String,
+    /// Host name for `nixosConfigurations.<host>…`, else the last segment
+    pub label: String,
+    pub state: AttrState,
+}
+
+/// One backend invocation and the attributes (indices) it builds
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub attrs: Vec<usize>,
+    pub program: &'static str,
+    pub args: Vec<String>,
+}
+
+impl Step {
+    pub fn command_line(&self) -> String {
+        format!("{} {}", self.program, self.args.join(" "))
+    }
+}
+
+/// This machine's host name, as `nixosConfigurations` keys use it
+pub fn hostname() -> String {
+    std::fs::read_to_string("/proc/sys/kernel/hostname")
+        .map(|h| h.trim().to_string())
+        .unwrap_or_default()
+}
+
+/// The attributes to build: the configured ones, or this host's system
+pub fn attrs(config: &BuildBackendConfig, host: &str) -> Vec<AttrProgress> {
+    let attrs = if config.attrs.is_empty() {
+        vec![format!(
+            "nixosConfigurations.{}.config.system.build.toplevel",
+            host
+        )]
+    } else {
+        config.attrs.clone()
+    };
+    attrs
+        .into_iter()
+        .map(|attr| AttrProgress {
+            label: label(&attr),
+            attr,
+            state: AttrState::Queued,
+        })
+        .collect()
+}
+
+fn label(attr: &str) -> String {
+    let parts: Vec<&str> = attr.split('.').collect();
+    match parts.as_slice() {
+        ["nixosConfigurations", host, ..] => host.trim_matches('"').to_string(),
+        _ => parts.last().unwrap_or(&attr).trim_matches('"').to_string(),
+    }
+}
+
+/// Invocations building `attrs` from `flake`. `nix build` takes them all
+/// at once; `nix-fast-build` takes one flake attribute per run and
+/// parallelizes inside it.
+pub fn steps(config: &BuildBackendConfig, flake: &str, attrs: &[AttrProgress]) -> Vec<Step> {
+    let target = |a: &AttrProgress| format!("{}#{}", flake, a.attr);
+    match config.backend {
+        BuildBackend::NixosRebuild => Vec::new(),
+        BuildBackend::NixBuild => {
+            let mut args: Vec<String> = [
+                "--extra-experimental-features",
+                "nix-command flakes",
+                "build",
+                "--no-link",
+                "--keep-going",
+            ]
+            .map(String::from)
+            .to_vec();
+            args.extend(attrs.iter().map(target));
+            vec![Step {
+                attrs: (0..attrs.len()).collect(),
+                program: config.backend.program(),
+                args,
+            }]
+        }
+        BuildBackend::NixFastBuild => attrs
+            .iter()
+            .enumerate()
+            .map(|(i, a)| {
+                let mut args: Vec<String> = ["--no-nom", "--skip-cached", "--no-link", "--flake"]
+                    .map(String::from)
+                    .to_vec();
+                args.push(target(a));
+                if let Some(n) = config.eval_workers {
+                    args.push("--eval-workers".into());
+                    args.push(n.to_string());
+                }
+                Step {
+                    attrs: vec![i],
+                    program: config.backend.program(),
+                    args,
+                }
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backend_steps() {
+        let config = BuildBackendConfig {
+            backend: BuildBackend::NixBuild,
+            attrs: vec![
+                "nixosConfigurations.server.config.system.build.toplevel".into(),
+                "packages.x86_64-linux.tool".into(),
+            ],
+            eval_workers: None,
+        };
+        let list = attrs(&config, "laptop");
+        let labels: Vec<&str> = list.iter().map(|a| a.label.as_str()).collect();
+        assert_eq!(labels, vec!["server", "tool"]);
+
+        let plan = steps(&config, "/etc/nixos", &list);
+        assert_eq!(plan.len(), 1);
+        assert_eq!(plan[0].attrs, vec![0, 1]);
+        assert!(plan[0]
+            .args
+            .ends_with(&["/etc/nixos#packages.x86_64-linux.tool".to_string()]));
+
+        // Default: this host's system, one nix-fast-build run per attribute
+        let config = BuildBackendConfig {
+            backend: BuildBackend::NixFastBuild,
+            attrs: Vec::new(),
+            eval_workers: Some(4),
+        };
+        let list = attrs(&config, "laptop");
+        assert_eq!(list[0].label, "laptop");
+        let plan = steps(&config, "/etc/nixos", &list);
+        assert_eq!(plan[0].program, "nix-fast-build");
+        assert!(plan[0]
+            .args
+            .ends_with(&["--eval-workers".into(), "4".into()]));
+    }
+}
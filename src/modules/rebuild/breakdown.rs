@@ -0,0 +1,122 @@
+//! Where the rebuild time goes: seconds per pipeline phase (eval, fetch,
+//! build, activate, bootloader) are stored with every history entry and
+//! drawn in History as a stacked bar per row, under the average of the
+//! recent builds and whether each phase is getting slower or faster.
+
+use super::HistoryEntry;
+use std::time::Instant;
+
+/// Breakdowns averaged for the summary line
+const AVERAGE_OF: usize = 10;
+/// A phase counts as slower/faster when the newer half of the averaged
+/// builds differs by this share and at least `TREND_MIN_SECS`
+const TREND_SHARE: f64 = 0.15;
+const TREND_MIN_SECS: u64 = 5;
+
+/// Seconds spent in each pipeline phase; phases never entered count 0
+pub fn phase_secs(times: &[Option<(Instant, Option<Instant>)>; 5]) -> Vec<u64> {
+    let now = Instant::now();
+    times
+        .iter()
+        .map(|t| match t {
+            Some((start, end)) => end.unwrap_or(now).duration_since(*start).as_secs(),
+            None => 0,
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trend {
+    Slower,
+    Faster,
+    Steady,
+}
+
+impl Trend {
+    pub fn arrow(self) -> &'static str {
+        match self {
+            Trend::Slower => "↑",
+            Trend::Faster => "↓",
+            Trend::Steady => "",
+        }
+    }
+}
+
+/// Average per phase of the newest successful builds with a breakdown,
+/// with the trend of the newer half against the older half. None until
+/// there is such a build.
+pub fn summary(history: &[HistoryEntry]) -> Option<(usize, Vec<(u64, Trend)>)> {
+    let recent: Vec<&[u64]> = history
+        .iter()
+        .rev()
+        .filter(|h| h.success && h.phase_secs.len() == 5)
+        .map(|h| h.phase_secs.as_slice())
+        .take(AVERAGE_OF)
+        .collect();
+    if recent.is_empty() {
+        return None;
+    }
+    let half = recent.len() / 2;
+    let phases = (0..5)
+        .map(|i| {
+            let avg = |rows: &[&[u64]]| rows.iter().map(|r| r[i]).sum::<u64>() / rows.len() as u64;
+            let trend = if half == 0 {
+                Trend::Steady
+            } else {
+                // `recent` is newest first
+                trend(avg(&recent[half..]), avg(&recent[..half]))
+            };
+            (avg(&recent), trend)
+        })
+        .collect();
+    Some((recent.len(), phases))
+}
+
+fn trend(older: u64, newer: u64) -> Trend {
+    let diff = newer.abs_diff(older);
+    if diff < TREND_MIN_SECS || (diff as f64) < older as f64 * TREND_SHARE {
+        Trend::Steady
+    } else if newer > older {
+        Trend::Slower
+    } else {
+        Trend::Faster
+    }
+}
+
+/// Cells per phase of a stacked bar where `max` seconds fill `width`
+/// cells; rounded on the running total so the segments add up
+pub fn bar_widths(secs: &[u64], max: u64, width: usize) -> Vec<usize> {
+    let max = max.max(1);
+    let mut done = 0;
+    let mut total = 0;
+    secs.iter()
+        .map(|s| {
+            total += s;
+            let end = ((total.min(max) * width as u64 + max / 2) / max) as usize;
+            let cells = end.saturating_sub(done);
+            done = end.max(done);
+            cells
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bar_widths_and_trend() {
+        assert_eq!(bar_widths(&[10, 0, 30, 5, 5], 50, 20), vec![4, 0, 12, 2, 2]);
+        // Half of the longest build fills half the bar
+        assert_eq!(
+            bar_widths(&[25, 0, 0, 0, 0], 50, 20).iter().sum::<usize>(),
+            10
+        );
+        assert_eq!(bar_widths(&[0; 5], 0, 20), vec![0; 5]);
+
+        assert_eq!(trend(60, 90), Trend::Slower);
+        assert_eq!(trend(60, 40), Trend::Faster);
+        assert_eq!(trend(60, 63), Trend::Steady);
+        assert_eq!(trend(2, 6), Trend::Steady);
+    }
+}
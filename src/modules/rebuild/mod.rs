@@ -4,28 +4,71 @@
 //! Tracks build phases, derivation counts, warnings, errors.
 //! Post-rebuild diff: packages added/removed/updated, services restarted.
 //! Supports Flakes, Channels, and Home-Manager configurations.
-
-use crate::config::Language;
+//! Idle screen lists builds running elsewhere on the system (attachable via `nix log`).
+//! `e` suspends the TUI and opens `nixos-rebuild repl` with the configuration loaded.
+//! Quitting during a build offers to detach it into a `systemd-run` transient
+//! unit; such units show up among the system builds and can be reattached.
+//! The Log tab exports the raw log (`w`), copies the error block (`y`) and
+//! shares the log through a paste service (`s`, `[paste]` in config.toml).
+//! `v` switches it to a smart view grouped by derivation (see `smart`).
+//! A per-host build backend can build before `nixos-rebuild` (see `backend`).
+//! Big downloads over a metered or slow connection are flagged while
+//! fetching, with a switch to a build without substituters (see `network`).
+//! Tab in the confirm popup picks a specialisation (see `specialisation`).
+//! `f` builds a package, dev shell or check of a flake instead (see `targets`).
+//! `H` picks the flake and `nixosConfigurations` host that gets rebuilt.
+//! History shows where each build spent its time, per phase (see `breakdown`).
+//! After a `build`, `p` in Changes previews the unit restarts of a switch
+//! (see `preview`).
+//! A run whose evaluation finds nothing to build or fetch shows a short
+//! "already up to date" summary instead of the phase pipeline.
+//! `v` on the Dashboard swaps the live output for the dependency tree of
+//! the derivations being built, with the critical path marked (see `tree`).
+
+pub(crate) mod backend;
+mod breakdown;
+mod network;
+mod preview;
+mod share;
+mod smart;
+mod specialisation;
+mod targets;
+mod tree;
+mod warnings;
+
+use crate::config::{
+    BuildBackend, BuildBackendConfig, Language, PasteConfig, RebuildDashboard, TimeFormat,
+};
 use crate::i18n;
+use crate::modules::jobs::Job;
+use crate::modules::module::{Module, RenderCtx};
+use crate::modules::palette::PaletteCommand;
+use crate::nix::builds::{scan_running_builds, RunningBuild, DETACHED_UNIT_PREFIX};
+use crate::nix::commands::command_exists;
 use crate::nix::detect::{detect_flakes, find_flake_path};
-use crate::types::FlashMessage;
+use crate::types::{format_stored_time, ExternalCommand, FlashMessage};
 use crate::ui::theme::Theme;
 use crate::ui::widgets;
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
     layout::{Alignment, Constraint, Layout, Rect},
-    style::{Modifier, Style},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Tabs},
     Frame,
 };
 use std::sync::mpsc;
 use std::sync::{
-    atomic::{AtomicU32, Ordering},
+    atomic::{AtomicBool, AtomicU32, Ordering},
     Arc,
 };
 use std::time::{Duration, Instant};
 
+/// How long a stopped build may take to exit before detaching gives up
+const DETACH_STOP_TIMEOUT: Duration = Duration::from_secs(15);
+/// Upper bound for waiting out an activation after a hangup
+const ACTIVATION_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
 // ── Sub-tabs ──
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -93,9 +136,22 @@ pub enum RebuildMode {
     Test,
     Build,
     DryBuild,
+    /// `switch --rollback`: activate the previous generation again
+    Rollback,
 }
 
 impl RebuildMode {
+    pub const ALL: [RebuildMode; 6] = [
+        RebuildMode::Switch,
+        RebuildMode::Boot,
+        RebuildMode::Test,
+        RebuildMode::Build,
+        RebuildMode::DryBuild,
+        RebuildMode::Rollback,
+    ];
+
+    /// Name in history and job labels; the nixos-rebuild arguments come
+    /// from `build_rebuild_command`
     pub fn as_arg(&self) -> &'static str {
         match self {
             RebuildMode::Switch => "switch",
@@ -103,6 +159,7 @@ impl RebuildMode {
             RebuildMode::Test => "test",
             RebuildMode::Build => "build",
             RebuildMode::DryBuild => "dry-build",
+            RebuildMode::Rollback => "rollback",
         }
     }
 
@@ -114,6 +171,7 @@ impl RebuildMode {
             RebuildMode::Test => s.rb_mode_test,
             RebuildMode::Build => s.rb_mode_build,
             RebuildMode::DryBuild => s.rb_mode_dry,
+            RebuildMode::Rollback => s.rb_mode_rollback,
         }
     }
 
@@ -123,9 +181,16 @@ impl RebuildMode {
             RebuildMode::Boot => RebuildMode::Test,
             RebuildMode::Test => RebuildMode::Build,
             RebuildMode::Build => RebuildMode::DryBuild,
-            RebuildMode::DryBuild => RebuildMode::Switch,
+            RebuildMode::DryBuild => RebuildMode::Rollback,
+            RebuildMode::Rollback => RebuildMode::Switch,
         }
     }
+
+    /// Whether the mode evaluates and builds the configuration. A rollback
+    /// only activates a generation that already exists.
+    pub fn builds(&self) -> bool {
+        *self != RebuildMode::Rollback
+    }
 }
 
 // ── Build phase ──
@@ -228,6 +293,8 @@ pub struct RebuildDiff {
     pub reboot_needed: bool,
     pub services_restarted: Vec<String>,
     pub nixos_version: Option<(String, String)>, // (old, new)
+    /// Set after a rollback: the generation that is active again
+    pub restored_generation: Option<u32>,
 }
 
 // ── History entry ──
@@ -242,6 +309,15 @@ pub struct HistoryEntry {
     pub success: bool,
     pub error_preview: Option<String>,
     pub command: String,
+    #[serde(default)]
+    pub specialisation: Option<String>,
+    /// Flake target of a development build ("package tool"); None for rebuilds
+    #[serde(default)]
+    pub target: Option<String>,
+    /// Seconds per pipeline phase (eval, fetch, build, activate,
+    /// bootloader); empty for entries from older versions
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub phase_secs: Vec<u64>,
 }
 
 mod rebuild_mode_serde {
@@ -259,6 +335,7 @@ mod rebuild_mode_serde {
             "test" => RebuildMode::Test,
             "build" => RebuildMode::Build,
             "dry-build" => RebuildMode::DryBuild,
+            "rollback" => RebuildMode::Rollback,
             _ => RebuildMode::Switch,
         })
     }
@@ -290,6 +367,10 @@ pub enum RebuildMsg {
     ServiceRestart(String),
     Finished(bool, Option<String>), // (success, error_message)
     CommandInfo(String),
+    /// Build backend progress of one attribute
+    Attr(usize, backend::AttrState),
+    /// Store path of the system a `build` produced
+    BuiltSystem(String),
 }
 
 #[derive(Debug, Clone, Default)]
@@ -297,18 +378,58 @@ pub struct BuildStats {
     pub derivations_built: u32,
     pub derivations_total: Option<u32>,
     pub fetched: u32,
+    /// From "these N paths will be fetched"
+    pub fetch_total: Option<u32>,
     pub warnings: u32,
     pub errors: u32,
 }
 
+// ── Attached external build log ──
+
+/// Messages from the `nix log` follower thread
+pub enum AttachMsg {
+    Lines(Vec<String>),
+    Finished,
+}
+
+/// Log of a build started outside nixmate that the user attached to
+pub struct AttachedLog {
+    pub name: String,
+    /// .drv path (`nix log`) or systemd unit (`journalctl`) being followed
+    pub source: String,
+    pub lines: Vec<String>,
+    pub finished: bool,
+    stop: Arc<AtomicBool>,
+    rx: mpsc::Receiver<AttachMsg>,
+}
+
 // ── Popup state ──
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum RebuildPopup {
     None,
     ConfirmRebuild,
+    /// Fetching on a metered or slow connection: go on or build offline
+    NetworkWarning(network::Reason),
+    /// Picker of flake packages, dev shells and checks to build
+    Targets,
+    /// Picker of the flake and `nixosConfigurations` host to rebuild
+    Hosts,
+    /// Uploading the log to the paste service: public and for good
+    ConfirmShare,
 }
 
+/// Flake directory and its targets (or why `nix flake show` failed)
+type TargetList = (String, Result<Vec<targets::FlakeTarget>, String>);
+/// Flake directory and its `nixosConfigurations` names
+type HostList = (String, Result<Vec<String>, String>);
+/// Planned derivations and the ones among them they need (or why
+/// `nix derivation show` failed)
+type TreeInputs = Result<std::collections::HashMap<String, Vec<String>>, String>;
+/// Unit and `systemd-run` arguments of a detach, and whether the local
+/// build has exited (sent once it stopped or gave up)
+type DetachStop = (String, Vec<String>, mpsc::Receiver<bool>);
+
 // ── Module state ──
 
 pub struct RebuildState {
@@ -326,10 +447,30 @@ pub struct RebuildState {
     pub log_auto_scroll: bool,
     pub log_search_active: bool,
     pub log_search_query: String,
+    /// Explain mode: annotate recognized line types in the Log tab
+    pub log_explain: bool,
+    /// Smart view: sections per derivation, progress runs collapsed
+    pub log_smart: bool,
+    /// Selected row in the smart view (follows the end while auto-scrolling)
+    smart_selected: usize,
+    /// Sections expanded by hand
+    smart_expanded: Vec<String>,
+
+    // Dependency tree instead of the live output on the Dashboard, the
+    // inputs of each planned derivation (loaded once per run) and scroll
+    pub dashboard_tree: bool,
+    tree_inputs: Option<TreeInputs>,
+    tree_rx: Option<mpsc::Receiver<TreeInputs>>,
+    tree_scroll: usize,
 
     // Current build line (shown in dashboard)
     pub current_activity: String,
 
+    // Dashboard layout while building (from config)
+    pub dashboard: RebuildDashboard,
+    /// Terminal below the compact thresholds (set on resize)
+    pub compact: bool,
+
     // Last phase that had an explanation (for "linger" display on fast phases)
     pub last_explanation_phase: BuildPhase,
 
@@ -337,6 +478,8 @@ pub struct RebuildState {
     pub phase_times: [Option<(Instant, Option<Instant>)>; 5],
     pub phase_skipped: [bool; 5],
     pub failed_phase_idx: Option<usize>, // which pipeline phase the build failed in
+    /// Generation a rollback restores (looked up when it's confirmed)
+    pub rollback_target: Option<u32>,
 
     // Pre/post snapshot for diff
     pre_packages: Vec<(String, String)>,
@@ -347,6 +490,19 @@ pub struct RebuildState {
     pub diff: Option<RebuildDiff>,
     pub changes_scroll: usize,
 
+    // Warnings of the finished build, deduplicated (Done dashboard, Changes)
+    pub warnings: Vec<warnings::BuildWarning>,
+    warnings_selected: usize,
+    /// The selected warning shows its raw line
+    warnings_expanded: bool,
+    /// Option a warning is about, for App to open in the Options Explorer
+    pub pending_option: Option<String>,
+
+    // System of the last `build` and what activating it would do
+    built_system: Option<String>,
+    preview: Option<Result<preview::DryActivation, String>>,
+    preview_rx: Option<mpsc::Receiver<Result<preview::DryActivation, String>>>,
+
     // History
     pub history: Vec<HistoryEntry>,
     pub history_selected: usize,
@@ -360,6 +516,7 @@ pub struct RebuildState {
 
     // Flash message
     pub lang: Language,
+    pub time_format: TimeFormat,
     pub flash_message: Option<FlashMessage>,
 
     // Password for sudo
@@ -368,18 +525,70 @@ pub struct RebuildState {
     // Show --show-trace flag
     pub show_trace: bool,
 
+    // Build without substituters (`--option substitute false`)
+    pub offline: bool,
+    network: network::NetworkWatch,
+
     // Run `nix flake update` before rebuild
     pub update_flake_inputs: bool,
 
     // Custom NixOS config path
     pub config_path: Option<String>,
+    /// `nixosConfigurations` attribute from the config (None = host name)
+    pub nixos_host: Option<String>,
 
     // Child process PID for cancellation
     child_pid: Arc<AtomicU32>,
 
+    // Builds running elsewhere on the system (shown on idle screen)
+    pub system_builds: Vec<RunningBuild>,
+    pub system_builds_selected: usize,
+    pub attached: Option<AttachedLog>,
+    last_system_scan: Option<Instant>,
+
+    // Eval console queued for the main loop (TUI is suspended while it runs)
+    pub pending_external: Option<ExternalCommand>,
+    /// Unit the queued external command moves the running build into
+    pub pending_detach: Option<String>,
+    /// Detach waiting for the local build to exit
+    detach_stop: Option<DetachStop>,
+
+    // Build backends per host (from config) and the attributes the
+    // current build's backend works on
+    pub backends: std::collections::BTreeMap<String, BuildBackendConfig>,
+    pub backend: BuildBackend,
+    pub attrs: Vec<backend::AttrProgress>,
+
+    // Paste service for sharing the log (from config)
+    pub paste: PasteConfig,
+    share_rx: Option<mpsc::Receiver<Result<String, String>>>,
+
     // mpsc channels
     build_rx: Option<mpsc::Receiver<RebuildMsg>>,
     _detect_rx: Option<mpsc::Receiver<(bool, Option<String>)>>,
+
+    // Specialisations of the configuration; the chosen one (None = default)
+    pub specialisations: Vec<String>,
+    pub specialisation: Option<String>,
+    spec_rx: Option<mpsc::Receiver<Vec<String>>>,
+    system_scan_rx: Option<mpsc::Receiver<Vec<RunningBuild>>>,
+
+    // Development build: the flake target being built (None = rebuild),
+    // the targets of the flake and the picker's selection
+    pub target: Option<targets::FlakeTarget>,
+    targets: Option<TargetList>,
+    targets_rx: Option<mpsc::Receiver<TargetList>>,
+    targets_selected: usize,
+
+    // Host picker: flakes registered in Flake Inputs (offered besides the
+    // detected one), the flake shown, its hosts and the selection (0 =
+    // follow the host name). `picked` is taken by App to persist.
+    pub flakes: Vec<String>,
+    host_flake: usize,
+    hosts: Option<HostList>,
+    hosts_rx: Option<mpsc::Receiver<HostList>>,
+    hosts_selected: usize,
+    pub picked: Option<(String, Option<String>)>,
 }
 
 impl RebuildState {
@@ -397,17 +606,35 @@ impl RebuildState {
             log_scroll: 0,
             log_auto_scroll: true,
             log_search_active: false,
+            log_explain: false,
+            log_smart: false,
+            smart_selected: 0,
+            smart_expanded: Vec::new(),
+            dashboard_tree: false,
+            tree_inputs: None,
+            tree_rx: None,
+            tree_scroll: 0,
             log_search_query: String::new(),
             current_activity: String::new(),
+            dashboard: RebuildDashboard::Auto,
+            compact: false,
             last_explanation_phase: BuildPhase::Idle,
             phase_times: [None; 5],
             phase_skipped: [false; 5],
+            rollback_target: None,
             failed_phase_idx: None,
             pre_packages: Vec::new(),
             pre_kernel: None,
             pre_nixos_ver: None,
             diff: None,
             changes_scroll: 0,
+            warnings: Vec::new(),
+            warnings_selected: 0,
+            warnings_expanded: false,
+            pending_option: None,
+            built_system: None,
+            preview: None,
+            preview_rx: None,
             history,
             history_selected: 0,
             detected_command: None,
@@ -416,14 +643,44 @@ impl RebuildState {
             detected: false,
             detecting: false,
             lang: Language::English,
+            time_format: TimeFormat::Locale,
             flash_message: None,
             password_buffer: String::new(),
             show_trace: false,
+            offline: false,
+            network: network::NetworkWatch::default(),
             update_flake_inputs: false,
             config_path: None,
+            nixos_host: None,
             child_pid: Arc::new(AtomicU32::new(0)),
+            system_builds: Vec::new(),
+            system_builds_selected: 0,
+            attached: None,
+            last_system_scan: None,
+            pending_external: None,
+            pending_detach: None,
+            detach_stop: None,
+            backends: std::collections::BTreeMap::new(),
+            backend: BuildBackend::NixosRebuild,
+            attrs: Vec::new(),
+            paste: PasteConfig::default(),
+            share_rx: None,
             build_rx: None,
             _detect_rx: None,
+            specialisations: Vec::new(),
+            specialisation: None,
+            spec_rx: None,
+            system_scan_rx: None,
+            target: None,
+            targets: None,
+            targets_rx: None,
+            targets_selected: 0,
+            flakes: Vec::new(),
+            host_flake: 0,
+            hosts: None,
+            hosts_rx: None,
+            hosts_selected: 0,
+            picked: None,
         }
     }
 
@@ -439,6 +696,30 @@ impl RebuildState {
         )
     }
 
+    /// Switching to the new system or installing the bootloader: stopping
+    /// or restarting it now would leave the system half switched
+    pub fn activating(&self) -> bool {
+        matches!(self.phase, BuildPhase::Activating | BuildPhase::Bootloader)
+    }
+
+    /// Evaluation is over and found nothing to build or fetch: the run is
+    /// activation only (or, for build and dry-build, nothing at all)
+    pub fn up_to_date(&self) -> bool {
+        let evaluated = matches!(
+            self.phase,
+            BuildPhase::Activating | BuildPhase::Bootloader | BuildPhase::Done
+        );
+        let st = &self.stats;
+        evaluated
+            && self.mode.builds()
+            && self.target.is_none()
+            && self.attrs.is_empty()
+            && st.derivations_total.is_none()
+            && st.fetch_total.is_none()
+            && st.derivations_built == 0
+            && st.fetched == 0
+    }
+
     pub fn elapsed(&self) -> Duration {
         self.final_duration.unwrap_or_else(|| {
             self.start_time
@@ -460,7 +741,11 @@ impl RebuildState {
             .history
             .iter()
             .rev()
-            .filter(|h| h.success)
+            .filter(|h| {
+                h.success
+                    && h.target == self.target_label()
+                    && (h.target.is_some() || h.mode.builds() == self.mode.builds())
+            })
             .take(5)
             .collect();
         if successes.is_empty() {
@@ -488,10 +773,14 @@ impl RebuildState {
     /// Get the rebuild command for the current mode (dynamically computed)
     pub fn current_command(&self) -> String {
         let uses_flakes = self.uses_flakes.unwrap_or(false);
-        let (program, args) =
-            build_rebuild_command(self.mode.as_arg(), uses_flakes, self.flake_path.as_deref());
+        let (program, args) = build_rebuild_command(
+            self.mode,
+            uses_flakes,
+            self.flake_path.as_deref(),
+            self.nixos_host.as_deref(),
+        );
         let mut cmd = String::new();
-        if uses_flakes && self.update_flake_inputs {
+        if uses_flakes && self.update_flake_inputs && self.mode.builds() {
             let path = self.flake_path.as_deref().unwrap_or("/etc/nixos");
             if path.starts_with("/etc/") {
                 cmd.push_str(&format!("sudo nix flake update --flake {} && ", path));
@@ -500,14 +789,79 @@ impl RebuildState {
             }
         }
         cmd.push_str(&format!("{} {}", program, args.join(" ")));
-        if self.show_trace {
-            cmd.push_str(" --show-trace");
+        for arg in self.extra_args() {
+            cmd.push(' ');
+            cmd.push_str(&arg);
         }
         cmd
     }
 
-    /// Cancel a running build by killing the child process.
+    /// Flags appended to `nixos-rebuild` (building modes only)
+    fn extra_args(&self) -> Vec<String> {
+        let mut args = specialisation::args(self.mode, self.specialisation.as_deref());
+        if self.mode.builds() {
+            if self.show_trace {
+                args.push("--show-trace".into());
+            }
+            if self.offline {
+                args.extend(["--option".into(), "substitute".into(), "false".into()]);
+            }
+        }
+        args
+    }
+
+    /// Queue an interactive eval console with the system configuration loaded.
+    pub fn open_repl(&mut self) {
+        let s = crate::i18n::get_strings(self.lang);
+        let Some(uses_flakes) = self.uses_flakes else {
+            self.flash_message = Some(FlashMessage::new(s.rb_detecting.to_string(), false));
+            return;
+        };
+        match build_repl_command(
+            uses_flakes,
+            self.flake_path.as_deref(),
+            self.config_path.as_deref(),
+            self.nixos_host.as_deref(),
+            s.rb_repl_running,
+        ) {
+            Some(cmd) => self.pending_external = Some(cmd),
+            None => {
+                self.flash_message =
+                    Some(FlashMessage::new(s.rb_repl_unavailable.to_string(), true))
+            }
+        }
+    }
+
+    /// Warn once per build when the fetch runs over a metered or slow link
+    fn check_network(&mut self) {
+        if !self.is_running() {
+            return;
+        }
+        self.network.tick();
+        if self.popup != RebuildPopup::None {
+            return;
+        }
+        if let Some(reason) = self.network.warning() {
+            self.network.warned = true;
+            self.popup = RebuildPopup::NetworkWarning(reason);
+        }
+    }
+
+    /// Stop the running fetch and offer the same rebuild without substituters
+    fn restart_offline(&mut self) {
+        self.cancel_build();
+        self.offline = true;
+        self.popup = RebuildPopup::ConfirmRebuild;
+    }
+
+    /// Cancel a running build by killing the child process. Not once
+    /// activation has started.
     pub fn cancel_build(&mut self) {
+        if self.activating() {
+            let s = crate::i18n::get_strings(self.lang);
+            self.flash_message = Some(FlashMessage::new(s.rb_cancel_activating.to_string(), true));
+            return;
+        }
         let pid = self.child_pid.load(Ordering::SeqCst);
         if pid != 0 && self.is_running() {
             // Track which phase was cancelled
@@ -542,6 +896,167 @@ impl RebuildState {
         }
     }
 
+    /// The running rebuild as a `systemd-run` transient unit (unit name, argv).
+    /// Restarting it there is cheap: everything already built stays in the store.
+    fn detach_command(&self) -> (String, Vec<String>) {
+        let unit = format!(
+            "{}{}",
+            DETACHED_UNIT_PREFIX,
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        );
+        let (_, mut rebuild) = build_rebuild_command(
+            self.mode,
+            self.uses_flakes.unwrap_or(false),
+            self.flake_path.as_deref(),
+            self.nixos_host.as_deref(),
+        );
+        rebuild.extend(self.extra_args());
+
+        let mut args = vec![
+            "systemd-run".to_string(),
+            format!("--unit={}", unit),
+            format!(
+                "--description=nixmate: nixos-rebuild {}",
+                self.mode.as_arg()
+            ),
+            "--collect".into(),
+        ];
+        // The service manager's environment lacks the user's PATH / NIX_PATH
+        for var in ["PATH", "NIX_PATH"] {
+            if let Ok(value) = std::env::var(var) {
+                args.push(format!("--setenv={}={}", var, value));
+            }
+        }
+        args.extend(rebuild);
+        (unit, args)
+    }
+
+    /// Stop the local build and wait in the background until its processes
+    /// are gone, so the detached unit never runs next to it. Sends false if
+    /// they didn't exit.
+    fn stop_for_detach(&mut self) -> mpsc::Receiver<bool> {
+        let pid = self.child_pid.load(Ordering::SeqCst);
+        self.cancel_build();
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(pid == 0 || wait_for_group_exit(pid, DETACH_STOP_TIMEOUT));
+        });
+        rx
+    }
+
+    /// A detach is waiting for the local build to exit
+    pub fn detaching(&self) -> bool {
+        self.detach_stop.is_some()
+    }
+
+    /// Queue moving the running build into a transient unit. The local
+    /// build is stopped first (`poll_detach_stop`); the main loop then
+    /// starts the unit with the TUI suspended (sudo may ask for a password
+    /// there) and reports back through `detach_finished`. Only up to the
+    /// end of building: an activation is never cut off or run a second time.
+    pub fn queue_detach(&mut self) {
+        if !self.is_running() {
+            return;
+        }
+        let s = crate::i18n::get_strings(self.lang);
+        if self.target.is_some() {
+            self.flash_message = Some(FlashMessage::new(s.rb_target_no_detach.to_string(), true));
+            return;
+        }
+        if self.activating() {
+            self.flash_message = Some(FlashMessage::new(s.rb_detach_activating.to_string(), true));
+            return;
+        }
+        let (unit, args) = self.detach_command();
+        let rx = self.stop_for_detach();
+        self.detach_stop = Some((unit, args, rx));
+        self.flash_message = Some(FlashMessage::new(s.rb_detach_stopping.to_string(), false));
+    }
+
+    /// The local build exited (or didn't in time): hand the unit to the
+    /// main loop, or give up
+    pub fn poll_detach_stop(&mut self) {
+        let Some((_, _, rx)) = &self.detach_stop else {
+            return;
+        };
+        let stopped = match rx.try_recv() {
+            Ok(stopped) => stopped,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => false,
+        };
+        let Some((unit, args, _)) = self.detach_stop.take() else {
+            return;
+        };
+        let s = crate::i18n::get_strings(self.lang);
+        if !stopped {
+            self.flash_message = Some(FlashMessage::new(s.rb_detach_not_stopped.to_string(), true));
+            return;
+        }
+        self.pending_external = Some(ExternalCommand {
+            program: "sudo".into(),
+            args,
+            cwd: None,
+            notice: s.rb_detach_notice.replace("{}", &unit),
+        });
+        self.pending_detach = Some(unit);
+    }
+
+    /// The transient unit took over (the local build is stopped already)
+    pub fn detach_finished(&mut self, unit: &str, success: bool) {
+        let s = crate::i18n::get_strings(self.lang);
+        if success {
+            self.log_lines.push(LogLine {
+                text: s.rb_detached_to.replace("{}", unit),
+                raw: unit.to_string(),
+                level: LogLevel::Info,
+            });
+        } else {
+            self.flash_message = Some(FlashMessage::new(s.rb_detach_failed.to_string(), true));
+        }
+    }
+
+    /// Terminal hung up mid-build: no way to ask, so detach if sudo allows
+    /// it without a password (cached credentials); otherwise the build
+    /// goes down with nixmate. An activation is waited for instead.
+    /// Returns whether the build was detached.
+    pub fn detach_on_hangup(&mut self) -> bool {
+        // Already stopping for a detach: finish it without asking
+        if let Some((unit, args, rx)) = self.detach_stop.take() {
+            return rx.recv().unwrap_or(false) && self.start_unit_unattended(&unit, &args);
+        }
+        if !self.is_running() || self.target.is_some() {
+            return false;
+        }
+        if self.activating() {
+            let pid = self.child_pid.load(Ordering::SeqCst);
+            if pid != 0 {
+                wait_for_group_exit(pid, ACTIVATION_TIMEOUT);
+            }
+            return false;
+        }
+        let (unit, args) = self.detach_command();
+        if !self.stop_for_detach().recv().unwrap_or(false) {
+            return false;
+        }
+        self.start_unit_unattended(&unit, &args)
+    }
+
+    /// `sudo -n systemd-run …` with no terminal to ask for a password
+    fn start_unit_unattended(&mut self, unit: &str, args: &[String]) -> bool {
+        let detached = std::process::Command::new("sudo")
+            .arg("-n")
+            .args(args)
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()
+            .is_ok_and(|st| st.success());
+        if detached {
+            self.detach_finished(unit, true);
+        }
+        detached
+    }
+
     /// Detect system config (flakes vs channels, flake path)
     pub fn ensure_detected(&mut self) {
         if self.detected || self.detecting {
@@ -564,30 +1079,211 @@ impl RebuildState {
         self._detect_rx = Some(rx);
     }
 
+    /// Detect again after the config path changed (not mid-build)
+    pub fn redetect(&mut self) {
+        if self.is_running() {
+            return;
+        }
+        self.detected = false;
+        self.detecting = false;
+        self._detect_rx = None;
+        self.specialisations.clear();
+        self.specialisation = None;
+        self.ensure_detected();
+    }
+
     /// Poll detection result
     pub fn poll_detect(&mut self) {
         if let Some(rx) = &self._detect_rx {
             if let Ok((uses_flakes, flake_path)) = rx.try_recv() {
                 self.uses_flakes = Some(uses_flakes);
-                self.flake_path = flake_path;
+                self.flake_path = flake_path.clone();
                 self.detected = true;
                 self.detecting = false;
                 self._detect_rx = None;
+
+                let (tx, rx) = mpsc::channel();
+                let host = self.nixos_host.clone();
+                std::thread::spawn(move || {
+                    let _ = tx.send(specialisation::detect(
+                        uses_flakes,
+                        flake_path.as_deref(),
+                        host.as_deref(),
+                    ));
+                });
+                self.spec_rx = Some(rx);
+            }
+        }
+        if let Some(rx) = &self.spec_rx {
+            if let Ok(names) = rx.try_recv() {
+                self.specialisations = names;
+                self.spec_rx = None;
             }
         }
     }
 
-    /// Start rebuild in background
-    pub fn start_rebuild(&mut self, password: Option<String>) {
+    /// Rescan /proc for running builds every few seconds while idle.
+    /// Called only while the Rebuild tab is visible.
+    pub fn tick_system_builds(&mut self) {
+        if self.phase != BuildPhase::Idle || self.system_scan_rx.is_some() {
+            return;
+        }
+        if self
+            .last_system_scan
+            .is_some_and(|t| t.elapsed() < Duration::from_secs(2))
+        {
+            return;
+        }
+        self.last_system_scan = Some(Instant::now());
+
+        let (tx, rx) = mpsc::channel();
+        let own_child = self.child_pid.load(Ordering::SeqCst);
+        std::thread::spawn(move || {
+            let _ = tx.send(scan_running_builds(Some(own_child)));
+        });
+        self.system_scan_rx = Some(rx);
+    }
+
+    /// Poll system build scan results and the attached log follower
+    pub fn poll_system_builds(&mut self) {
+        if let Some(rx) = &self.system_scan_rx {
+            if let Ok(builds) = rx.try_recv() {
+                self.system_builds = builds;
+                self.system_builds_selected = self
+                    .system_builds_selected
+                    .min(self.system_builds.len().saturating_sub(1));
+                self.system_scan_rx = None;
+            }
+        }
+
+        if let Some(ref mut att) = self.attached {
+            while let Ok(msg) = att.rx.try_recv() {
+                match msg {
+                    AttachMsg::Lines(lines) => att.lines.extend(lines),
+                    AttachMsg::Finished => att.finished = true,
+                }
+            }
+        }
+    }
+
+    /// Attach to the log of the selected system build
+    fn attach_selected(&mut self) {
+        let Some(build) = self.system_builds.get(self.system_builds_selected).cloned() else {
+            return;
+        };
+        let source = match (&build.unit, &build.drv_path) {
+            (Some(unit), _) => unit.clone(),
+            (None, Some(drv)) => drv.clone(),
+            (None, None) => {
+                let s = crate::i18n::get_strings(self.lang);
+                self.flash_message = Some(FlashMessage::new(s.rb_attach_no_log.to_string(), true));
+                return;
+            }
+        };
+
+        self.detach();
+
+        let (tx, rx) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_ref = stop.clone();
+        let pid = build.pid;
+        let source_clone = source.clone();
+        let is_unit = build.unit.is_some();
+        std::thread::spawn(move || {
+            if is_unit {
+                follow_unit_log(tx, &source_clone, stop_ref);
+            } else {
+                follow_build_log(tx, &source_clone, pid, stop_ref);
+            }
+        });
+
+        self.attached = Some(AttachedLog {
+            name: build.name,
+            source,
+            lines: Vec::new(),
+            finished: false,
+            stop,
+            rx,
+        });
+    }
+
+    /// Stop following an attached build log
+    fn detach(&mut self) {
+        if let Some(att) = self.attached.take() {
+            att.stop.store(true, Ordering::SeqCst);
+        }
+    }
+
+    /// Open the confirmation for `mode`, handed over from another module
+    /// (Flake Inputs' "update all and rebuild")
+    pub fn prefill(&mut self, mode: RebuildMode) {
         if self.is_running() {
+            let s = crate::i18n::get_strings(self.lang);
+            self.flash_message = Some(FlashMessage::new(s.rb_busy.to_string(), true));
             return;
         }
+        self.mode = mode;
+        self.sub_tab = RebuildSubTab::Dashboard;
+        if matches!(self.phase, BuildPhase::Done | BuildPhase::Failed) {
+            self.phase = BuildPhase::Idle;
+        }
+        self.ensure_detected();
+        self.open_confirm();
+    }
 
-        let uses_flakes = self.uses_flakes.unwrap_or(false);
-        let flake_path = self.flake_path.clone();
-        let mode = self.mode;
+    /// Show a log piped into nixmate (`nixos-rebuild switch 2>&1 | nixmate`)
+    /// in the Log tab, with the same phase markers as a live build
+    pub fn load_piped_log(&mut self, text: &str) {
+        let mut phase = BuildPhase::Preparing;
+        self.log_lines.clear();
+        for line in text.lines() {
+            let new_phase = detect_phase(line, phase);
+            if new_phase != phase {
+                phase = new_phase;
+                let marker = format!("── {} ──", phase_label(phase, self.lang));
+                self.log_lines.push(LogLine {
+                    text: marker.clone(),
+                    raw: marker,
+                    level: LogLevel::Phase,
+                });
+            }
+            self.log_lines.push(LogLine {
+                text: beautify_store_path(line),
+                raw: line.to_string(),
+                level: classify_line(line),
+            });
+        }
+        self.log_scroll = 0;
+        self.log_auto_scroll = false;
+        self.sub_tab = RebuildSubTab::Log;
+    }
+
+    /// Ask before starting. A rollback first needs a generation to go back to.
+    fn open_confirm(&mut self) {
+        if self.mode == RebuildMode::Rollback {
+            self.rollback_target = crate::nix::generations::rollback_target();
+            if self.rollback_target.is_none() {
+                let s = crate::i18n::get_strings(self.lang);
+                self.flash_message = Some(FlashMessage::new(s.rb_rollback_none.to_string(), true));
+                return;
+            }
+        }
+        self.offline = false;
+        self.popup = RebuildPopup::ConfirmRebuild;
+    }
 
-        // Reset state
+    /// After a resize: no log offset past the last full page of `visible`
+    /// lines (following the output keeps following)
+    pub fn fit_scroll(&mut self, visible: usize) {
+        if !self.log_auto_scroll {
+            self.log_scroll = self
+                .log_scroll
+                .min(self.log_lines.len().saturating_sub(visible));
+        }
+    }
+
+    /// Clear the previous run's log, timings and results for a new one
+    fn reset_run_state(&mut self) {
         self.phase = BuildPhase::Preparing;
         self.stats = BuildStats::default();
         self.start_time = Some(Instant::now());
@@ -597,91 +1293,350 @@ impl RebuildState {
         self.log_auto_scroll = true;
         self.log_search_active = false;
         self.log_search_query.clear();
+        self.smart_selected = 0;
+        self.smart_expanded.clear();
+        self.tree_inputs = None;
+        self.tree_rx = None;
+        self.tree_scroll = 0;
         self.current_activity.clear();
         self.last_explanation_phase = BuildPhase::Idle;
         self.diff = None;
         self.changes_scroll = 0;
+        self.warnings.clear();
+        self.warnings_selected = 0;
+        self.warnings_expanded = false;
+        self.built_system = None;
+        self.preview = None;
         self.phase_times = [None; 5];
-        self.phase_skipped = [false; 5];
         self.failed_phase_idx = None;
         self.sub_tab = RebuildSubTab::Dashboard;
+        self.child_pid.store(0, Ordering::SeqCst);
+    }
+
+    /// Open the flake target picker, loading the targets the first time
+    fn open_targets(&mut self) {
+        let flake = targets::flake_dir(self.flake_path.as_deref());
+        let cached = matches!(&self.targets, Some((dir, Ok(_))) if *dir == flake);
+        if !cached && self.targets_rx.is_none() {
+            self.load_targets(flake);
+        }
+        self.popup = RebuildPopup::Targets;
+    }
 
+    /// `nix flake show` in the background (evaluates the flake)
+    fn load_targets(&mut self, flake: String) {
         let (tx, rx) = mpsc::channel();
-        self.build_rx = Some(rx);
-        self.child_pid.store(0, Ordering::SeqCst);
+        self.targets_rx = Some(rx);
+        self.targets = None;
+        self.targets_selected = 0;
+        std::thread::spawn(move || {
+            let result = targets::load(&flake);
+            let _ = tx.send((flake, result));
+        });
+    }
 
-        let (prog, args) = build_rebuild_command(mode.as_arg(), uses_flakes, flake_path.as_deref());
-        let mut command = String::new();
-        let update_flake = uses_flakes && self.update_flake_inputs;
-        if update_flake {
-            let path = flake_path.as_deref().unwrap_or("/etc/nixos");
-            if path.starts_with("/etc/") {
-                command.push_str(&format!("sudo nix flake update --flake {} && ", path));
-            } else {
-                command.push_str(&format!("nix flake update --flake {} && ", path));
+    pub fn poll_targets(&mut self) {
+        let Some(rx) = &self.targets_rx else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(list) => {
+                self.targets = Some(list);
+                self.targets_rx = None;
             }
+            Err(mpsc::TryRecvError::Empty) => {}
+            Err(mpsc::TryRecvError::Disconnected) => self.targets_rx = None,
         }
-        command.push_str(&format!("{} {}", prog, args.join(" ")));
-        let show_trace = self.show_trace;
-        if show_trace {
-            command.push_str(" --show-trace");
+    }
+
+    /// Flakes the host picker offers: the detected system flake, then the
+    /// projects registered in Flake Inputs
+    fn host_flakes(&self) -> Vec<String> {
+        let mut dirs: Vec<String> = self.flake_path.iter().cloned().collect();
+        for dir in self
+            .flakes
+            .iter()
+            .map(|f| crate::nix::detect::expand_home(f))
+        {
+            if !dirs.contains(&dir) {
+                dirs.push(dir);
+            }
         }
-        self.detected_command = Some(command.clone());
-        let _ = tx.send(RebuildMsg::CommandInfo(command));
+        dirs
+    }
 
-        let s = crate::i18n::get_strings(self.lang);
-        let auth_msg = s.rb_authenticating.to_string();
-        let updating_flake_msg = s.rb_updating_flake.to_string();
-        let flake_update_failed_msg = s.rb_flake_update_failed.to_string();
-        let pid_ref = Arc::clone(&self.child_pid);
+    /// Open the flake / host picker on the detected flake
+    fn open_hosts(&mut self) {
+        if self.host_flakes().is_empty() {
+            return;
+        }
+        self.host_flake = 0;
+        self.popup = RebuildPopup::Hosts;
+        self.load_hosts(false);
+    }
+
+    /// `nix flake show` of the flake shown in the host picker, unless its
+    /// hosts are already there
+    fn load_hosts(&mut self, force: bool) {
+        let Some(flake) = self.host_flakes().get(self.host_flake).cloned() else {
+            return;
+        };
+        if !force && matches!(&self.hosts, Some((dir, Ok(_))) if *dir == flake) {
+            return;
+        }
+        let (tx, rx) = mpsc::channel();
+        self.hosts_rx = Some(rx);
+        self.hosts = None;
+        self.hosts_selected = 0;
         std::thread::spawn(move || {
-            run_rebuild(
-                tx,
-                mode,
-                uses_flakes,
-                flake_path.as_deref(),
-                password,
-                show_trace,
-                pid_ref,
-                auth_msg,
-                update_flake,
-                updating_flake_msg,
-                flake_update_failed_msg,
-            );
+            let result = targets::load_hosts(&flake);
+            let _ = tx.send((flake, result));
         });
     }
 
-    /// Poll build progress messages
-    pub fn poll_build(&mut self) {
-        let rx = match &self.build_rx {
-            Some(rx) => rx,
-            None => return,
+    pub fn poll_hosts(&mut self) {
+        let Some(rx) = &self.hosts_rx else {
+            return;
         };
+        match rx.try_recv() {
+            Ok(list) => {
+                // Start on the configured host
+                if let (Ok(hosts), Some(host)) = (&list.1, &self.nixos_host) {
+                    self.hosts_selected = hosts.iter().position(|h| h == host).map_or(0, |i| i + 1);
+                }
+                self.hosts = Some(list);
+                self.hosts_rx = None;
+            }
+            Err(mpsc::TryRecvError::Empty) => {}
+            Err(mpsc::TryRecvError::Disconnected) => self.hosts_rx = None,
+        }
+    }
 
-        // Drain all available messages (non-blocking)
-        let mut finished = false;
-        for _ in 0..100 {
-            match rx.try_recv() {
-                Ok(msg) => match msg {
-                    RebuildMsg::OutputLine(line) => {
-                        let level = classify_line(&line);
-                        let display_text = beautify_store_path(&line);
-                        self.current_activity = display_text.clone();
-                        self.log_lines.push(LogLine {
-                            text: display_text,
-                            raw: line,
-                            level,
-                        });
-                        // Cap log lines to prevent unbounded memory growth
-                        if self.log_lines.len() > 50_000 {
-                            self.log_lines.drain(..10_000);
-                            if self.log_scroll > 10_000 {
+    /// Rebuild the selected host of the flake shown from now on
+    fn pick_host(&mut self) {
+        let Some((flake, Ok(hosts))) = &self.hosts else {
+            return;
+        };
+        let host = self
+            .hosts_selected
+            .checked_sub(1)
+            .and_then(|i| hosts.get(i))
+            .cloned();
+        self.picked = Some((flake.clone(), host));
+        self.popup = RebuildPopup::None;
+    }
+
+    /// "package tool" for the target being built
+    fn target_label(&self) -> Option<String> {
+        self.target.as_ref().map(|t| match t.kind {
+            targets::TargetKind::FlakeCheck => t.name.clone(),
+            kind => format!("{} {}", kind.label(), t.name),
+        })
+    }
+
+    /// Build the selected flake target: one backend step, no sudo, no
+    /// activation
+    fn start_target_build(&mut self) {
+        if self.is_running() || self.detaching() {
+            return;
+        }
+        let Some((flake, Ok(list))) = &self.targets else {
+            return;
+        };
+        let Some(target) = list.get(self.targets_selected).cloned() else {
+            return;
+        };
+        let step = targets::step(flake, &target);
+        self.detach();
+        self.popup = RebuildPopup::None;
+        self.rollback_target = None;
+        self.reset_run_state();
+        // Nothing is activated or installed as bootloader entry
+        self.phase_skipped = [false, false, false, true, true];
+        self.network = network::NetworkWatch::start();
+        self.backend = BuildBackend::NixBuild;
+        self.attrs = vec![backend::AttrProgress {
+            attr: target.attr.clone(),
+            label: target.name.clone(),
+            state: backend::AttrState::Queued,
+        }];
+        self.target = Some(target);
+
+        let (tx, rx) = mpsc::channel();
+        self.build_rx = Some(rx);
+        let command = step.command_line();
+        self.detected_command = Some(command.clone());
+        let _ = tx.send(RebuildMsg::CommandInfo(command));
+        let pid_ref = Arc::clone(&self.child_pid);
+        std::thread::spawn(move || {
+            let result = run_backend_step(&tx, &step, &pid_ref);
+            let _ = tx.send(RebuildMsg::Finished(result.is_ok(), result.err()));
+        });
+    }
+
+    /// Start rebuild in background
+    pub fn start_rebuild(&mut self, password: Option<String>) {
+        // A build on its way into a detached unit must not run twice
+        if self.is_running() || self.detaching() {
+            return;
+        }
+        self.detach();
+
+        let uses_flakes = self.uses_flakes.unwrap_or(false);
+        let flake_path = self.flake_path.clone();
+        let mode = self.mode;
+        let s = crate::i18n::get_strings(self.lang);
+
+        let rollback_to = if mode == RebuildMode::Rollback {
+            let Some(target) = crate::nix::generations::rollback_target() else {
+                self.flash_message = Some(FlashMessage::new(s.rb_rollback_none.to_string(), true));
+                return;
+            };
+            Some(target)
+        } else {
+            None
+        };
+        self.rollback_target = rollback_to;
+        self.target = None;
+        self.reset_run_state();
+        // A rollback has nothing to evaluate, fetch or build
+        self.phase_skipped = [!mode.builds(), !mode.builds(), !mode.builds(), false, false];
+
+        let (tx, rx) = mpsc::channel();
+        self.build_rx = Some(rx);
+
+        // The host's backend builds first; nixos-rebuild then activates
+        let host = backend::hostname();
+        let backend_config = self
+            .backends
+            .get(&host)
+            .or_else(|| self.backends.get("default"))
+            .filter(|c| c.backend != BuildBackend::NixosRebuild && mode.builds());
+        let mut notices = Vec::new();
+        self.backend = BuildBackend::NixosRebuild;
+        self.attrs.clear();
+        let mut steps = Vec::new();
+        if let Some(config) = backend_config {
+            let program = config.backend.program();
+            if !uses_flakes {
+                notices.push(s.rb_backend_needs_flake.replace("{}", program));
+            } else if !command_exists(program) {
+                notices.push(s.rb_backend_missing.replace("{}", program));
+            } else {
+                let flake = flake_path.as_deref().unwrap_or("/etc/nixos");
+                self.backend = config.backend;
+                self.attrs = backend::attrs(
+                    config,
+                    &crate::nix::detect::nixos_host(self.nixos_host.as_deref()),
+                );
+                steps = backend::steps(config, flake, &self.attrs);
+            }
+        }
+
+        let rebuild_cmd = build_rebuild_command(
+            mode,
+            uses_flakes,
+            flake_path.as_deref(),
+            self.nixos_host.as_deref(),
+        );
+        let (prog, args) = &rebuild_cmd;
+        let mut command = String::new();
+        let update_flake = uses_flakes && self.update_flake_inputs && mode.builds();
+        if update_flake {
+            let path = flake_path.as_deref().unwrap_or("/etc/nixos");
+            if path.starts_with("/etc/") {
+                command.push_str(&format!("sudo nix flake update --flake {} && ", path));
+            } else {
+                command.push_str(&format!("nix flake update --flake {} && ", path));
+            }
+        }
+        for step in &steps {
+            command.push_str(&format!("{} && ", step.command_line()));
+        }
+        command.push_str(&format!("{} {}", prog, args.join(" ")));
+        let extra_args = self.extra_args();
+        for arg in &extra_args {
+            command.push(' ');
+            command.push_str(arg);
+        }
+        self.network = if mode.builds() && !self.offline {
+            network::NetworkWatch::start()
+        } else {
+            network::NetworkWatch::default()
+        };
+        self.detected_command = Some(command.clone());
+        let _ = tx.send(RebuildMsg::CommandInfo(command));
+        for notice in notices {
+            let _ = tx.send(RebuildMsg::OutputLine(notice));
+        }
+
+        let auth_msg = s.rb_authenticating.to_string();
+        let updating_flake_msg = s.rb_updating_flake.to_string();
+        let flake_update_failed_msg = s.rb_flake_update_failed.to_string();
+        let pid_ref = Arc::clone(&self.child_pid);
+        std::thread::spawn(move || {
+            run_rebuild(
+                tx,
+                mode,
+                rollback_to,
+                rebuild_cmd,
+                flake_path.as_deref(),
+                password,
+                extra_args,
+                pid_ref,
+                auth_msg,
+                update_flake,
+                updating_flake_msg,
+                flake_update_failed_msg,
+                steps,
+            );
+        });
+    }
+
+    /// Poll build progress messages
+    pub fn poll_build(&mut self) {
+        self.check_network();
+        let rx = match &self.build_rx {
+            Some(rx) => rx,
+            None => return,
+        };
+
+        // Drain all available messages (non-blocking)
+        let mut finished = false;
+        // The plan of derivations is complete once the first build starts
+        let mut plan_complete = false;
+        for _ in 0..100 {
+            match rx.try_recv() {
+                Ok(msg) => match msg {
+                    RebuildMsg::OutputLine(line) => {
+                        if matches!(line_kind(&line), Some(LineKind::FetchPlan)) {
+                            self.network.on_fetch_plan(&line);
+                        }
+                        let started = line.trim_start().starts_with("building '");
+                        let level = classify_line(&line);
+                        let display_text = beautify_store_path(&line);
+                        self.current_activity = display_text.clone();
+                        self.log_lines.push(LogLine {
+                            text: display_text,
+                            raw: line,
+                            level,
+                        });
+                        plan_complete |= started;
+                        // Cap log lines to prevent unbounded memory growth
+                        if self.log_lines.len() > 50_000 {
+                            self.log_lines.drain(..10_000);
+                            if self.log_scroll > 10_000 {
                                 self.log_scroll -= 10_000;
                             } else {
                                 self.log_scroll = 0;
                             }
                         }
                     }
+                    // Phases a rollback doesn't have stay skipped
+                    RebuildMsg::Phase(phase)
+                        if phase
+                            .pipeline_index()
+                            .is_some_and(|i| self.phase_skipped[i]) => {}
                     RebuildMsg::Phase(phase) => {
                         // Close timing for old phase
                         if let Some(old_idx) = self.phase.pipeline_index() {
@@ -713,6 +1668,11 @@ impl RebuildState {
                     RebuildMsg::Stats(stats) => {
                         self.stats = stats;
                     }
+                    RebuildMsg::Attr(i, state) => {
+                        if let Some(attr) = self.attrs.get_mut(i) {
+                            attr.state = state;
+                        }
+                    }
                     RebuildMsg::PreSnapshot(pkgs, kernel, ver) => {
                         self.pre_packages = pkgs;
                         self.pre_kernel = kernel;
@@ -728,7 +1688,13 @@ impl RebuildState {
                             &self.pre_nixos_ver,
                             &ver,
                         );
-                        self.diff = Some(diff);
+                        self.diff = Some(RebuildDiff {
+                            restored_generation: self.rollback_target,
+                            ..diff
+                        });
+                    }
+                    RebuildMsg::BuiltSystem(path) => {
+                        self.built_system = Some(path);
                     }
                     RebuildMsg::ServiceRestart(svc) => {
                         if let Some(ref mut diff) = self.diff {
@@ -765,6 +1731,7 @@ impl RebuildState {
                         } else {
                             BuildPhase::Failed
                         };
+                        self.warnings = warnings::collect(&self.log_lines);
 
                         // Mark phases that were never entered as skipped
                         for i in 0..5 {
@@ -802,6 +1769,13 @@ impl RebuildState {
                             success,
                             error_preview,
                             command: self.detected_command.clone().unwrap_or_default(),
+                            specialisation: self
+                                .specialisation
+                                .clone()
+                                .filter(|_| self.target.is_none())
+                                .filter(|_| specialisation::applies(self.mode)),
+                            target: self.target_label(),
+                            phase_secs: breakdown::phase_secs(&self.phase_times),
                         };
                         self.history.push(entry);
                         // Cap history to prevent unbounded memory growth
@@ -858,6 +1832,104 @@ impl RebuildState {
         if finished {
             self.build_rx = None;
         }
+        if plan_complete {
+            self.ensure_tree_inputs();
+        }
+    }
+
+    /// History selection for the session file
+    pub fn session(&self) -> crate::session::ModuleSession {
+        crate::session::ModuleSession {
+            selected: self.history_selected,
+            ..Default::default()
+        }
+    }
+
+    pub fn restore_session(&mut self, saved: &crate::session::ModuleSession) {
+        self.history_selected = saved.selected.min(self.history.len().saturating_sub(1));
+    }
+
+    /// Actions offered in the command palette
+    /// Running background work, for the jobs overlay
+    pub fn jobs(&self, s: &i18n::Strings) -> Vec<Job> {
+        let mut jobs = Vec::new();
+        if self.is_running() {
+            let title = match self.target_label() {
+                Some(target) => s.job_rb_target.replace("{}", &target),
+                None => s.job_rebuild.replace("{}", self.mode.as_arg()),
+            };
+            let cancellable = self.child_pid.load(Ordering::SeqCst) != 0 && !self.activating();
+            let mut job = Job::new("build", title, cancellable).detail(self.phase.label(self.lang));
+            if let Some(idx) = self.phase.pipeline_index() {
+                job = job.progress(idx, BuildPhase::pipeline_phases().len());
+            }
+            jobs.push(job);
+        }
+        if self.preview_rx.is_some() {
+            jobs.push(Job::new("preview", s.job_rb_preview, false).detail("dry-activate"));
+        }
+        if self.tree_rx.is_some() {
+            jobs.push(Job::new("tree", s.job_rb_tree, false).detail("nix derivation show"));
+        }
+        if self.share_rx.is_some() {
+            // Already on its way; cancelling wouldn't take it back
+            jobs.push(Job::new("share", s.job_rb_share, false).detail(self.paste.host()));
+        }
+        jobs
+    }
+
+    /// Kill the running rebuild
+    pub fn cancel_job(&mut self, id: &str) {
+        if id == "build" {
+            self.cancel_build();
+        }
+    }
+
+    pub fn palette_commands(&self, s: &i18n::Strings) -> Vec<PaletteCommand> {
+        let dashboard = Some(RebuildSubTab::Dashboard.index());
+        let log = Some(RebuildSubTab::Log.index());
+
+        // One "start" per mode: cycle `m` from the current mode, then `r`
+        let mut commands = Vec::new();
+        let mut mode = self.mode;
+        for steps in 0..RebuildMode::ALL.len() {
+            let mut keys = vec![KeyCode::Char('m'); steps];
+            keys.push(KeyCode::Char('r'));
+            commands.push(PaletteCommand::new(
+                s.pal_rb_start.replace("{}", mode.as_arg()),
+                dashboard,
+                keys,
+            ));
+            mode = mode.next();
+        }
+
+        commands.extend([
+            PaletteCommand::new(s.pal_rb_trace, dashboard, vec![KeyCode::Char('t')]),
+            PaletteCommand::new(s.pal_rb_repl, dashboard, vec![KeyCode::Char('e')]),
+            PaletteCommand::new(s.pal_rb_targets, dashboard, vec![KeyCode::Char('f')]),
+            PaletteCommand::new(s.pal_rb_hosts, dashboard, vec![KeyCode::Char('H')]),
+            PaletteCommand::new(s.pal_rb_tree, dashboard, vec![KeyCode::Char('v')]),
+            PaletteCommand::new(s.pal_rb_cancel, None, vec![KeyCode::Char('c')]),
+            PaletteCommand::new(s.pal_rb_log_search, log, vec![KeyCode::Char('/')]),
+            PaletteCommand::new(s.pal_rb_explain, log, vec![KeyCode::Char('x')]),
+            PaletteCommand::new(s.pal_rb_smart, log, vec![KeyCode::Char('v')]),
+            PaletteCommand::new(s.pal_rb_export, log, vec![KeyCode::Char('w')]),
+            PaletteCommand::new(s.pal_rb_copy_error, log, vec![KeyCode::Char('y')]),
+            PaletteCommand::new(s.pal_rb_share, log, vec![KeyCode::Char('s')]),
+            PaletteCommand::new(
+                s.pal_rb_history,
+                Some(RebuildSubTab::History.index()),
+                vec![],
+            ),
+        ]);
+        if !self.warnings.is_empty() {
+            commands.push(PaletteCommand::new(
+                s.pal_rb_warning_option,
+                Some(RebuildSubTab::Changes.index()),
+                vec![KeyCode::Char('o')],
+            ));
+        }
+        commands
     }
 
     pub fn handle_key(&mut self, key: KeyEvent) -> anyhow::Result<bool> {
@@ -880,6 +1952,13 @@ impl RebuildState {
                     self.popup = RebuildPopup::None;
                     return Ok(true);
                 }
+                KeyCode::Tab if specialisation::applies(self.mode) => {
+                    self.specialisation = specialisation::cycle(
+                        &self.specialisations,
+                        self.specialisation.as_deref(),
+                    );
+                    return Ok(true);
+                }
                 KeyCode::Backspace => {
                     self.password_buffer.pop();
                     return Ok(true);
@@ -892,6 +1971,78 @@ impl RebuildState {
             }
         }
 
+        if self.popup == RebuildPopup::ConfirmShare {
+            match key.code {
+                KeyCode::Char('y') | KeyCode::Enter => {
+                    self.popup = RebuildPopup::None;
+                    self.upload_log();
+                }
+                KeyCode::Char('n') | KeyCode::Esc => self.popup = RebuildPopup::None,
+                _ => {}
+            }
+            return Ok(true);
+        }
+
+        if let RebuildPopup::NetworkWarning(_) = self.popup {
+            match key.code {
+                KeyCode::Char('o') => self.restart_offline(),
+                KeyCode::Char('c') | KeyCode::Enter | KeyCode::Esc => {
+                    self.popup = RebuildPopup::None
+                }
+                _ => {}
+            }
+            return Ok(true);
+        }
+
+        if self.popup == RebuildPopup::Hosts {
+            let count = match &self.hosts {
+                Some((_, Ok(list))) => list.len() + 1,
+                _ => 0,
+            };
+            match key.code {
+                KeyCode::Char('j') | KeyCode::Down if self.hosts_selected + 1 < count => {
+                    self.hosts_selected += 1;
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    self.hosts_selected = self.hosts_selected.saturating_sub(1);
+                }
+                KeyCode::Char('f') | KeyCode::Tab => {
+                    let flakes = self.host_flakes().len();
+                    if flakes > 1 {
+                        self.host_flake = (self.host_flake + 1) % flakes;
+                        self.load_hosts(false);
+                    }
+                }
+                KeyCode::Enter => self.pick_host(),
+                KeyCode::Char('R') if self.hosts_rx.is_none() => self.load_hosts(true),
+                KeyCode::Esc => self.popup = RebuildPopup::None,
+                _ => {}
+            }
+            return Ok(true);
+        }
+
+        if self.popup == RebuildPopup::Targets {
+            let count = match &self.targets {
+                Some((_, Ok(list))) => list.len(),
+                _ => 0,
+            };
+            match key.code {
+                KeyCode::Char('j') | KeyCode::Down if self.targets_selected + 1 < count => {
+                    self.targets_selected += 1;
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    self.targets_selected = self.targets_selected.saturating_sub(1);
+                }
+                KeyCode::Enter => self.start_target_build(),
+                KeyCode::Char('R') if self.targets_rx.is_none() => {
+                    self.load_targets(targets::flake_dir(self.flake_path.as_deref()));
+                }
+                KeyCode::Esc => self.popup = RebuildPopup::None,
+                _ => {}
+            }
+            return Ok(true);
+        }
+
         // Log search mode
         if self.log_search_active {
             match key.code {
@@ -944,6 +2095,38 @@ impl RebuildState {
     }
 
     fn handle_dashboard_key(&mut self, key: KeyEvent) -> anyhow::Result<bool> {
+        // Idle screen: navigate builds running elsewhere on the system
+        if self.phase == BuildPhase::Idle {
+            match key.code {
+                KeyCode::Esc if self.attached.is_some() => {
+                    self.detach();
+                    return Ok(true);
+                }
+                KeyCode::Char('j') | KeyCode::Down => {
+                    if self.system_builds_selected + 1 < self.system_builds.len() {
+                        self.system_builds_selected += 1;
+                    }
+                    return Ok(true);
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    self.system_builds_selected = self.system_builds_selected.saturating_sub(1);
+                    return Ok(true);
+                }
+                KeyCode::Char('a') => {
+                    self.attach_selected();
+                    return Ok(true);
+                }
+                _ => {}
+            }
+        }
+
+        if self.phase == BuildPhase::Done && self.handle_warnings_key(key) {
+            return Ok(true);
+        }
+        if self.dashboard_tree && self.phase != BuildPhase::Idle && self.handle_tree_key(key) {
+            return Ok(true);
+        }
+
         match key.code {
             // Dismiss build results and return to idle
             KeyCode::Esc => {
@@ -954,7 +2137,7 @@ impl RebuildState {
             }
             KeyCode::Enter | KeyCode::Char('r') => {
                 if !self.is_running() {
-                    self.popup = RebuildPopup::ConfirmRebuild;
+                    self.open_confirm();
                 }
                 Ok(true)
             }
@@ -964,6 +2147,24 @@ impl RebuildState {
                 }
                 Ok(true)
             }
+            KeyCode::Char('e') => {
+                if !self.is_running() {
+                    self.open_repl();
+                }
+                Ok(true)
+            }
+            KeyCode::Char('f') => {
+                if !self.is_running() {
+                    self.open_targets();
+                }
+                Ok(true)
+            }
+            KeyCode::Char('H') => {
+                if !self.is_running() && self.uses_flakes == Some(true) {
+                    self.open_hosts();
+                }
+                Ok(true)
+            }
             KeyCode::Char('t') => {
                 if !self.is_running() {
                     self.show_trace = !self.show_trace;
@@ -976,6 +2177,11 @@ impl RebuildState {
                 }
                 Ok(true)
             }
+            KeyCode::Char('v') => {
+                self.dashboard_tree = !self.dashboard_tree;
+                self.ensure_tree_inputs();
+                Ok(true)
+            }
             KeyCode::Char('j') | KeyCode::Down => {
                 // Scroll live output
                 if !self.log_lines.is_empty() {
@@ -1005,6 +2211,9 @@ impl RebuildState {
     }
 
     fn handle_log_key(&mut self, key: KeyEvent) -> anyhow::Result<bool> {
+        if self.log_smart && self.handle_smart_key(key) {
+            return Ok(true);
+        }
         match key.code {
             KeyCode::Char('j') | KeyCode::Down => {
                 self.log_auto_scroll = false;
@@ -1031,54 +2240,411 @@ impl RebuildState {
                 self.log_search_query.clear();
                 Ok(true)
             }
-            _ => Ok(false),
-        }
-    }
-
-    fn handle_changes_key(&mut self, key: KeyEvent) -> anyhow::Result<bool> {
-        match key.code {
-            KeyCode::Char('j') | KeyCode::Down => {
-                self.changes_scroll += 1;
+            KeyCode::Char('x') => {
+                self.log_explain = !self.log_explain;
                 Ok(true)
             }
-            KeyCode::Char('k') | KeyCode::Up => {
-                self.changes_scroll = self.changes_scroll.saturating_sub(1);
+            KeyCode::Char('v') => {
+                self.log_smart = !self.log_smart;
                 Ok(true)
             }
-            KeyCode::Char('g') => {
-                self.changes_scroll = 0;
+            KeyCode::Char('w') => {
+                self.export_log();
+                Ok(true)
+            }
+            KeyCode::Char('y') => {
+                self.copy_error_block();
+                Ok(true)
+            }
+            KeyCode::Char('s') => {
+                self.share_log();
                 Ok(true)
             }
             _ => Ok(false),
         }
     }
 
-    fn handle_history_key(&mut self, key: KeyEvent) -> anyhow::Result<bool> {
+    /// Moving through the smart view's rows and expanding sections
+    fn handle_smart_key(&mut self, key: KeyEvent) -> bool {
+        let grouping = smart::group(&self.log_lines);
+        let rows = smart::rows(&self.log_lines, &grouping, &self.smart_expanded);
+        let last = rows.len().saturating_sub(1);
+        let selected = if self.log_auto_scroll {
+            last
+        } else {
+            self.smart_selected.min(last)
+        };
         match key.code {
             KeyCode::Char('j') | KeyCode::Down => {
-                if !self.history.is_empty() {
-                    self.history_selected =
-                        (self.history_selected + 1).min(self.history.len().saturating_sub(1));
-                }
-                Ok(true)
+                self.smart_selected = (selected + 1).min(last);
+                self.log_auto_scroll = self.smart_selected == last;
             }
             KeyCode::Char('k') | KeyCode::Up => {
-                self.history_selected = self.history_selected.saturating_sub(1);
-                Ok(true)
+                self.smart_selected = selected.saturating_sub(1);
+                self.log_auto_scroll = false;
             }
-            _ => Ok(false),
+            KeyCode::Char('g') => {
+                self.smart_selected = 0;
+                self.log_auto_scroll = false;
+            }
+            KeyCode::Char('G') => self.log_auto_scroll = true,
+            KeyCode::Enter | KeyCode::Char(' ') => {
+                // On a section or one of its lines: fold the section
+                let section = rows
+                    .iter()
+                    .take(selected + 1)
+                    .rposition(|r| !matches!(r, smart::Row::Member(_)));
+                let Some(header) = section else {
+                    return true;
+                };
+                let smart::Row::Section {
+                    name,
+                    pinned: false,
+                    ..
+                } = &rows[header]
+                else {
+                    return true;
+                };
+                if let Some(pos) = self.smart_expanded.iter().position(|n| n == name) {
+                    self.smart_expanded.remove(pos);
+                    self.smart_selected = header;
+                } else {
+                    self.smart_expanded.push(name.clone());
+                    self.smart_selected = selected;
+                }
+                self.log_auto_scroll = false;
+            }
+            _ => return false,
         }
+        true
     }
-}
 
-// ── Rendering ──
+    fn export_log(&mut self) {
+        let s = i18n::get_strings(self.lang);
+        self.flash_message = Some(if self.log_lines.is_empty() {
+            FlashMessage::new(s.rb_log_nothing.to_string(), true)
+        } else {
+            match share::export(&self.log_lines, self.mode.as_arg()) {
+                Ok(path) => FlashMessage::new(
+                    s.rb_log_exported.replace("{}", &path.display().to_string()),
+                    false,
+                ),
+                Err(e) => FlashMessage::new(format!("{:#}", e), true),
+            }
+        });
+    }
 
-pub fn render(frame: &mut Frame, state: &RebuildState, theme: &Theme, lang: Language, area: Rect) {
-    let s = i18n::get_strings(lang);
+    /// Copy the error block at the top of the view — the last one while
+    /// following the output
+    fn copy_error_block(&mut self) {
+        let s = i18n::get_strings(self.lang);
+        let from = if self.log_auto_scroll {
+            self.log_lines.len()
+        } else if self.log_smart {
+            let grouping = smart::group(&self.log_lines);
+            let rows = smart::rows(&self.log_lines, &grouping, &self.smart_expanded);
+            rows.get(self.smart_selected)
+                .and_then(|r| r.line(&grouping))
+                .unwrap_or(0)
+        } else {
+            self.log_scroll
+        };
+        let Some(block) = share::error_block(&self.log_lines, from) else {
+            self.flash_message = Some(FlashMessage::new(s.rb_log_no_error.to_string(), true));
+            return;
+        };
+        let count = block.len();
+        let text = share::raw_text(&self.log_lines[block]);
+        self.flash_message = Some(match crate::clipboard::write(&text) {
+            Some(tool) => FlashMessage::new(
+                s.rb_log_copied
+                    .replacen("{}", &count.to_string(), 1)
+                    .replacen("{}", tool, 1),
+                false,
+            ),
+            None => FlashMessage::new(s.rb_log_no_clipboard.to_string(), true),
+        });
+    }
 
-    let block = Block::default()
-        .style(theme.block_style())
-        .title(format!(" {} ", s.tab_rebuild))
+    /// Ask before uploading the whole log to the `[paste]` service
+    fn share_log(&mut self) {
+        let s = i18n::get_strings(self.lang);
+        if !self.paste.available() {
+            self.flash_message = Some(FlashMessage::new(s.rb_share_disabled.to_string(), true));
+            return;
+        }
+        if self.log_lines.is_empty() {
+            self.flash_message = Some(FlashMessage::new(s.rb_log_nothing.to_string(), true));
+            return;
+        }
+        if self.share_rx.is_some() {
+            return;
+        }
+        self.popup = RebuildPopup::ConfirmShare;
+    }
+
+    /// Upload the whole log to the `[paste]` service in the background
+    fn upload_log(&mut self) {
+        let s = i18n::get_strings(self.lang);
+        let url = self.paste.url.trim().to_string();
+        let text = share::raw_text(&self.log_lines);
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(share::upload(&url, &text).map_err(|e| format!("{:#}", e)));
+        });
+        self.share_rx = Some(rx);
+        self.flash_message = Some(FlashMessage::new(
+            s.rb_share_uploading.replace("{}", self.paste.host()),
+            false,
+        ));
+    }
+
+    /// Paste service answer: flash the link and put it on the clipboard
+    pub fn poll_share(&mut self) {
+        let Some(rx) = &self.share_rx else {
+            return;
+        };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => Err("upload thread died".to_string()),
+        };
+        self.share_rx = None;
+        let s = i18n::get_strings(self.lang);
+        self.flash_message = Some(match result {
+            Ok(link) => {
+                let text = if crate::clipboard::write(&link).is_some() {
+                    s.rb_share_copied
+                } else {
+                    s.rb_share_done
+                };
+                FlashMessage::new(text.replace("{}", &link), false)
+            }
+            Err(e) => FlashMessage::new(e, true),
+        });
+    }
+
+    /// Warnings panel (Done dashboard, Changes): w / W select, Space shows
+    /// the raw line, o opens the option in the Options Explorer
+    fn handle_warnings_key(&mut self, key: KeyEvent) -> bool {
+        let count = self.warnings.len();
+        if count == 0 {
+            return false;
+        }
+        match key.code {
+            KeyCode::Char('w') => self.warnings_selected = (self.warnings_selected + 1) % count,
+            KeyCode::Char('W') => {
+                self.warnings_selected = (self.warnings_selected + count - 1) % count
+            }
+            KeyCode::Char(' ') => self.warnings_expanded = !self.warnings_expanded,
+            KeyCode::Char('o') => {
+                let warning = &self.warnings[self.warnings_selected.min(count - 1)];
+                match &warning.option {
+                    Some(option) => self.pending_option = Some(option.clone()),
+                    None => {
+                        let s = i18n::get_strings(self.lang);
+                        self.flash_message =
+                            Some(FlashMessage::new(s.rb_warnings_no_option.to_string(), true));
+                    }
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    fn handle_changes_key(&mut self, key: KeyEvent) -> anyhow::Result<bool> {
+        if self.handle_warnings_key(key) {
+            return Ok(true);
+        }
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.changes_scroll += 1;
+                Ok(true)
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.changes_scroll = self.changes_scroll.saturating_sub(1);
+                Ok(true)
+            }
+            KeyCode::Char('g') => {
+                self.changes_scroll = 0;
+                Ok(true)
+            }
+            KeyCode::Char('p') => {
+                self.start_preview();
+                Ok(true)
+            }
+            // Go ahead with the switch the preview describes
+            KeyCode::Char('s') if matches!(self.preview, Some(Ok(_))) && !self.is_running() => {
+                self.mode = RebuildMode::Switch;
+                self.open_confirm();
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// dry-activate the built system in the background
+    fn start_preview(&mut self) {
+        let s = i18n::get_strings(self.lang);
+        let Some(system) = self.built_system.clone() else {
+            self.flash_message = Some(FlashMessage::new(
+                s.rb_preview_needs_build.to_string(),
+                true,
+            ));
+            return;
+        };
+        if self.preview_rx.is_some() {
+            return;
+        }
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(preview::dry_activate(&system));
+        });
+        self.preview_rx = Some(rx);
+        self.preview = None;
+    }
+
+    /// Load which planned derivation needs which, once per run, while the
+    /// tree is shown
+    fn ensure_tree_inputs(&mut self) {
+        if !self.dashboard_tree || self.tree_inputs.is_some() || self.tree_rx.is_some() {
+            return;
+        }
+        let drvs = tree::planned(&self.log_lines);
+        if drvs.is_empty() {
+            return;
+        }
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(tree::load_inputs(&drvs));
+        });
+        self.tree_rx = Some(rx);
+    }
+
+    pub fn poll_tree(&mut self) {
+        let Some(rx) = &self.tree_rx else {
+            return;
+        };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => Err("tree thread died".to_string()),
+        };
+        self.tree_rx = None;
+        self.tree_inputs = Some(result);
+    }
+
+    /// Scrolling the dependency tree; `v` switches back to the output
+    fn handle_tree_key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => self.tree_scroll += 1,
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.tree_scroll = self.tree_scroll.saturating_sub(1)
+            }
+            KeyCode::Char('g') => self.tree_scroll = 0,
+            _ => return false,
+        }
+        true
+    }
+
+    pub fn poll_preview(&mut self) {
+        let Some(rx) = &self.preview_rx else {
+            return;
+        };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => Err("dry-activate thread died".to_string()),
+        };
+        self.preview_rx = None;
+        self.preview = Some(result);
+        self.changes_scroll = 0;
+    }
+
+    fn handle_history_key(&mut self, key: KeyEvent) -> anyhow::Result<bool> {
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                if !self.history.is_empty() {
+                    self.history_selected =
+                        (self.history_selected + 1).min(self.history.len().saturating_sub(1));
+                }
+                Ok(true)
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.history_selected = self.history_selected.saturating_sub(1);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+}
+
+// ── Rendering ──
+
+impl Module for RebuildState {
+    fn handle_key(&mut self, key: KeyEvent, _lang: Language) -> anyhow::Result<()> {
+        self.handle_key(key).map(|_| ())
+    }
+
+    fn captures_input(&self) -> bool {
+        self.popup != RebuildPopup::None || self.log_search_active
+    }
+
+    fn render(&mut self, frame: &mut Frame, ctx: &RenderCtx, area: Rect) {
+        render(frame, self, ctx.theme, ctx.lang, area);
+    }
+
+    fn activate(&mut self) {
+        self.ensure_detected();
+    }
+
+    fn poll(&mut self, active: bool) {
+        self.poll_detect();
+        self.poll_build();
+        self.poll_detach_stop();
+        self.poll_targets();
+        self.poll_hosts();
+        self.poll_system_builds();
+        self.poll_share();
+        self.poll_preview();
+        self.poll_tree();
+        if active {
+            self.tick_system_builds();
+        }
+    }
+
+    fn commands(&self, s: &i18n::Strings) -> Vec<PaletteCommand> {
+        self.palette_commands(s)
+    }
+
+    fn jobs(&self, s: &i18n::Strings) -> Vec<Job> {
+        self.jobs(s)
+    }
+
+    fn cancel_job(&mut self, id: &str) {
+        self.cancel_job(id)
+    }
+
+    fn sub_tab(&self) -> Option<(usize, usize)> {
+        Some((self.sub_tab.index(), RebuildSubTab::all().len()))
+    }
+
+    fn sub_tab_label(&self, lang: Language) -> Option<&'static str> {
+        Some(self.sub_tab.label(lang))
+    }
+
+    fn flash_message(&mut self) -> &mut Option<FlashMessage> {
+        &mut self.flash_message
+    }
+}
+
+pub fn render(frame: &mut Frame, state: &RebuildState, theme: &Theme, lang: Language, area: Rect) {
+    let s = i18n::get_strings(lang);
+
+    let block = Block::default()
+        .style(theme.block_style())
+        .title(format!(" {} ", s.tab_rebuild))
         .title_style(theme.title())
         .borders(Borders::ALL)
         .border_style(theme.border_focused());
@@ -1110,6 +2676,255 @@ pub fn render(frame: &mut Frame, state: &RebuildState, theme: &Theme, lang: Lang
     if state.popup == RebuildPopup::ConfirmRebuild {
         render_confirm_popup(frame, state, theme, lang, area);
     }
+    if let RebuildPopup::NetworkWarning(reason) = state.popup {
+        render_network_popup(frame, state, reason, theme, lang, area);
+    }
+    if state.popup == RebuildPopup::Targets {
+        render_targets_popup(frame, state, theme, lang, area);
+    }
+    if state.popup == RebuildPopup::Hosts {
+        render_hosts_popup(frame, state, theme, lang, area);
+    }
+    if state.popup == RebuildPopup::ConfirmShare {
+        render_share_popup(frame, state, theme, lang, area);
+    }
+}
+
+/// Flake targets, scrolled to keep the selection in view
+fn render_targets_popup(
+    frame: &mut Frame,
+    state: &RebuildState,
+    theme: &Theme,
+    lang: Language,
+    area: Rect,
+) {
+    const VISIBLE: usize = 12;
+    let s = i18n::get_strings(lang);
+    let flake = targets::flake_dir(state.flake_path.as_deref());
+    let mut content = vec![
+        Line::styled(format!("{}: {}", s.rb_flake_path, flake), theme.text_dim()),
+        Line::raw(""),
+    ];
+    match &state.targets {
+        _ if state.targets_rx.is_some() => {
+            content.push(Line::styled(
+                format!(
+                    "{} {}",
+                    crate::modules::jobs::spinner(),
+                    s.rb_targets_loading
+                ),
+                Style::default().fg(theme.accent),
+            ));
+        }
+        Some((_, Err(e))) => {
+            content.push(Line::styled(e.clone(), Style::default().fg(theme.error)));
+        }
+        Some((_, Ok(list))) => {
+            let start = state
+                .targets_selected
+                .saturating_sub(VISIBLE - 1)
+                .min(list.len().saturating_sub(VISIBLE));
+            for (i, target) in list.iter().enumerate().skip(start).take(VISIBLE) {
+                let selected = i == state.targets_selected;
+                let name_style = if selected {
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme.fg)
+                };
+                let mut spans = vec![
+                    Span::styled(
+                        if selected { "▸ " } else { "  " },
+                        Style::default().fg(theme.accent),
+                    ),
+                    Span::styled(format!("{:<12}", target.kind.label()), theme.text_dim()),
+                    Span::styled(target.name.clone(), name_style),
+                ];
+                // One line per target: the description gets what's left
+                let room = 50usize.saturating_sub(16 + target.name.chars().count());
+                if let Some(desc) = target.description.as_ref().filter(|_| room > 8) {
+                    let desc: String = desc.chars().take(room).collect();
+                    spans.push(Span::styled(format!("  {}", desc), theme.text_dim()));
+                }
+                content.push(Line::from(spans));
+            }
+        }
+        None => {}
+    }
+    widgets::render_popup(
+        frame,
+        s.rb_targets_title,
+        content,
+        &[(s.rb_targets_build, '⏎'), (s.rb_targets_reload, 'R')],
+        theme,
+        area,
+    );
+}
+
+/// Flake and host picker: row 0 follows the host name, then the flake's
+/// `nixosConfigurations`
+fn render_hosts_popup(
+    frame: &mut Frame,
+    state: &RebuildState,
+    theme: &Theme,
+    lang: Language,
+    area: Rect,
+) {
+    const VISIBLE: usize = 12;
+    let s = i18n::get_strings(lang);
+    let flakes = state.host_flakes();
+    let flake = flakes.get(state.host_flake).cloned().unwrap_or_default();
+    let mut content = vec![
+        Line::styled(
+            format!(
+                "{}: {} ({}/{})",
+                s.rb_flake_path,
+                flake,
+                state.host_flake + 1,
+                flakes.len()
+            ),
+            theme.text_dim(),
+        ),
+        Line::raw(""),
+    ];
+    match &state.hosts {
+        _ if state.hosts_rx.is_some() => {
+            content.push(Line::styled(
+                format!(
+                    "{} {}",
+                    crate::modules::jobs::spinner(),
+                    s.rb_targets_loading
+                ),
+                Style::default().fg(theme.accent),
+            ));
+        }
+        Some((_, Err(e))) => {
+            content.push(Line::styled(e.clone(), Style::default().fg(theme.error)));
+        }
+        Some((_, Ok(hosts))) => {
+            let follow = s
+                .rb_hosts_follow
+                .replace("{}", &crate::nix::detect::machine_hostname());
+            let rows: Vec<String> = std::iter::once(follow)
+                .chain(hosts.iter().cloned())
+                .collect();
+            let configured = state.nixos_host.clone();
+            let start = state
+                .hosts_selected
+                .saturating_sub(VISIBLE - 1)
+                .min(rows.len().saturating_sub(VISIBLE));
+            for (i, row) in rows.iter().enumerate().skip(start).take(VISIBLE) {
+                let selected = i == state.hosts_selected;
+                let current = match i {
+                    0 => configured.is_none(),
+                    _ => configured.as_deref() == Some(row.as_str()),
+                };
+                let style = if selected {
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme.fg)
+                };
+                content.push(Line::from(vec![
+                    Span::styled(
+                        if selected { "▸ " } else { "  " },
+                        Style::default().fg(theme.accent),
+                    ),
+                    Span::styled(row.clone(), style),
+                    Span::styled(if current { "  ●" } else { "" }, theme.text_dim()),
+                ]));
+            }
+            if hosts.is_empty() {
+                content.push(Line::raw(""));
+                content.push(Line::styled(s.rb_hosts_none, theme.text_dim()));
+            }
+        }
+        None => {}
+    }
+    widgets::render_popup(
+        frame,
+        s.rb_hosts_title,
+        content,
+        &[
+            (s.rb_hosts_pick, '⏎'),
+            (s.rb_hosts_flake, 'f'),
+            (s.rb_targets_reload, 'R'),
+        ],
+        theme,
+        area,
+    );
+}
+
+fn render_share_popup(
+    frame: &mut Frame,
+    state: &RebuildState,
+    theme: &Theme,
+    lang: Language,
+    area: Rect,
+) {
+    let s = i18n::get_strings(lang);
+    let content = vec![
+        Line::raw(
+            s.rb_share_confirm
+                .replacen("{}", &state.log_lines.len().to_string(), 1)
+                .replacen("{}", state.paste.host(), 1),
+        ),
+        Line::raw(""),
+        Line::styled(s.rb_share_confirm_hint, Style::default().fg(theme.warning)),
+    ];
+    widgets::render_popup(
+        frame,
+        s.rb_share_confirm_title,
+        content,
+        &[(s.yes, 'y'), (s.no, 'n')],
+        theme,
+        area,
+    );
+}
+
+fn render_network_popup(
+    frame: &mut Frame,
+    state: &RebuildState,
+    reason: network::Reason,
+    theme: &Theme,
+    lang: Language,
+    area: Rect,
+) {
+    let s = i18n::get_strings(lang);
+    let net = &state.network;
+    let (title, cause) = match reason {
+        network::Reason::Metered => (s.rb_net_title, s.rb_net_metered.to_string()),
+        network::Reason::Slow => (
+            s.rb_net_title_slow,
+            s.rb_net_slow.replace(
+                "{}",
+                &crate::types::format_bytes(net.rate.unwrap_or(0.0) as u64),
+            ),
+        ),
+    };
+    let mut content = vec![
+        Line::styled(cause, Style::default().fg(theme.warning)),
+        Line::raw(""),
+        Line::raw(
+            s.rb_net_size
+                .replace("{}", &format!("{:.0}", net.download_mib.unwrap_or(0.0))),
+        ),
+    ];
+    if let Some(eta) = net.eta() {
+        content.push(Line::raw(s.rb_net_eta.replace("{}", &format_duration(eta))));
+    }
+    content.push(Line::raw(""));
+    content.push(Line::styled(s.rb_net_offline_hint, theme.text_dim()));
+    widgets::render_popup(
+        frame,
+        title,
+        content,
+        &[(s.rb_net_continue, 'c'), (s.rb_net_offline, 'o')],
+        theme,
+        area,
+    );
 }
 
 fn render_sub_tabs(
@@ -1146,34 +2961,311 @@ fn render_dashboard(
         return;
     }
 
+    if state.dashboard.is_compact(area.width, state.compact) {
+        render_compact_dashboard(frame, state, theme, lang, area);
+        return;
+    }
+
+    if state.up_to_date() {
+        render_up_to_date(frame, state, theme, lang, area);
+        return;
+    }
+
     // Running/finished layout
+    let attrs = attrs_line(state, theme);
     let layout = Layout::vertical([
         Constraint::Length(5), // phase boxes (compact: border+1 content line)
         Constraint::Length(5), // active phase explanation (enough for wrapped text)
         Constraint::Length(1), // stats row
+        Constraint::Length(u16::from(attrs.is_some())), // build backend attributes
         Constraint::Length(1), // separator
         Constraint::Min(4),    // live output
     ])
     .split(area);
 
-    // Phase boxes
-    render_phase_boxes(frame, state, theme, lang, layout[0]);
+    // Phase boxes
+    render_phase_boxes(frame, state, theme, lang, layout[0]);
+
+    // Active phase explanation
+    render_phase_explanation(frame, state, theme, lang, layout[1]);
+
+    // Stats row
+    render_stats_row(frame, state, theme, lang, layout[2]);
+    if let Some(attrs) = attrs {
+        frame.render_widget(Paragraph::new(attrs), layout[3]);
+    }
+
+    // Separator
+    let sep_line = "─".repeat(area.width as usize);
+    frame.render_widget(
+        Paragraph::new(sep_line).style(Style::default().fg(theme.border)),
+        layout[4],
+    );
+
+    // Live output, or the dependency tree (`v`)
+    if state.dashboard_tree {
+        render_build_tree(frame, state, theme, lang, layout[5]);
+    } else {
+        render_output_with_warnings(frame, state, theme, lang, layout[5]);
+    }
+}
+
+/// Nothing to build or fetch: one summary instead of the five phase boxes,
+/// so a quick iteration doesn't look like a full rebuild
+fn render_up_to_date(
+    frame: &mut Frame,
+    state: &RebuildState,
+    theme: &Theme,
+    lang: Language,
+    area: Rect,
+) {
+    let s = i18n::get_strings(lang);
+    let activates = !matches!(state.mode, RebuildMode::Build | RebuildMode::DryBuild);
+    let title = if activates {
+        s.rb_up_to_date_activation
+    } else {
+        s.rb_up_to_date_nothing
+    };
+    let (icon, color) = match state.phase {
+        BuildPhase::Done => ("✓", theme.success),
+        _ => (crate::modules::jobs::spinner(), theme.accent),
+    };
+
+    // Time of the phases that ran
+    let secs = breakdown::phase_secs(&state.phase_times);
+    let phase_names = [
+        s.rb_breakdown_eval,
+        "",
+        "",
+        s.rb_breakdown_activate,
+        s.rb_breakdown_boot,
+    ];
+    let mut times: Vec<String> = state
+        .phase_times
+        .iter()
+        .enumerate()
+        .filter(|(i, t)| t.is_some() && !phase_names[*i].is_empty())
+        .map(|(i, _)| format!("{} {}s", phase_names[i], secs[i]))
+        .collect();
+    times.push(format!("⏱ {}", state.elapsed_str()));
+
+    let mut lines = vec![
+        Line::raw(""),
+        Line::from(vec![
+            Span::styled(format!("  {} ", icon), Style::default().fg(color)),
+            Span::styled(
+                title,
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::styled(format!("    {}", s.rb_up_to_date_desc), theme.text_dim()),
+        Line::styled(format!("    {}", times.join(" · ")), theme.text()),
+    ];
+    if state.is_running() {
+        lines.push(Line::styled(
+            format!("    {} …", state.phase.label(lang)),
+            Style::default().fg(theme.accent),
+        ));
+    }
+    if state.stats.warnings > 0 {
+        lines.push(Line::styled(
+            format!("    ⚠ {}", state.stats.warnings),
+            Style::default().fg(theme.warning),
+        ));
+    }
+
+    let layout = Layout::vertical([
+        Constraint::Length(lines.len() as u16 + 1),
+        Constraint::Length(1),
+        Constraint::Min(4),
+    ])
+    .split(area);
+    frame.render_widget(Paragraph::new(lines), layout[0]);
+    frame.render_widget(
+        Paragraph::new("─".repeat(area.width as usize)).style(Style::default().fg(theme.border)),
+        layout[1],
+    );
+    render_output_with_warnings(frame, state, theme, lang, layout[2]);
+}
+
+/// `nix-fast-build: ✓ laptop  ⠋ server  · desktop` while a backend builds
+fn attrs_line(state: &RebuildState, theme: &Theme) -> Option<Line<'static>> {
+    if state.attrs.is_empty() {
+        return None;
+    }
+    let mut spans = vec![Span::styled(
+        format!("  {}:", state.backend.program()),
+        Style::default().fg(theme.fg_dim),
+    )];
+    for attr in &state.attrs {
+        let (icon, color) = match attr.state {
+            backend::AttrState::Queued => ("·", theme.fg_dim),
+            backend::AttrState::Building => (crate::modules::jobs::spinner(), theme.accent),
+            backend::AttrState::Done => ("✓", theme.success),
+            backend::AttrState::Failed => ("✗", theme.error),
+        };
+        spans.push(Span::styled(
+            format!("  {} {}", icon, attr.label),
+            Style::default().fg(color),
+        ));
+    }
+    Some(Line::from(spans))
+}
+
+/// One-column dashboard for narrow terminals (phone SSH, tmux side panes):
+/// phase, time/ETA, counters, current activity and the last error, then as
+/// much live output as fits.
+fn render_compact_dashboard(
+    frame: &mut Frame,
+    state: &RebuildState,
+    theme: &Theme,
+    lang: Language,
+    area: Rect,
+) {
+    let s = i18n::get_strings(lang);
+    let width = area.width.saturating_sub(2) as usize;
+    let clip = |text: &str| -> String { text.chars().take(width).collect() };
+
+    // Phase
+    let (icon, color) = match state.phase {
+        BuildPhase::Done => ("✓", theme.success),
+        BuildPhase::Failed => ("✗", theme.error),
+        _ => ("◉", theme.accent),
+    };
+    let step = state
+        .phase
+        .pipeline_index()
+        .or(state.failed_phase_idx)
+        .map(|i| format!(" ({}/5)", i + 1))
+        .unwrap_or_default();
+    let mut lines = vec![Line::from(vec![
+        Span::styled(
+            format!(" {} ", icon),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!("{}{}", state.phase.label(lang), step),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        ),
+    ])];
+    if state.up_to_date() {
+        lines.push(Line::styled(
+            format!(" {}", clip(s.rb_up_to_date_short)),
+            Style::default().fg(theme.success),
+        ));
+    }
+
+    // Time and ETA
+    let mut time = vec![Span::styled(
+        format!(" ⏱ {}", state.elapsed_str()),
+        Style::default().fg(theme.accent),
+    )];
+    if let (true, Some(est)) = (state.is_running(), state.estimated_time()) {
+        let elapsed = state.elapsed();
+        let eta = if est > elapsed {
+            s.rb_compact_eta
+                .replace("{}", &format_duration(est - elapsed))
+        } else {
+            s.rb_compact_overdue
+                .replace("{}", &format_duration(elapsed - est))
+        };
+        time.push(Span::styled(
+            format!("  {}", eta),
+            Style::default().fg(theme.fg_dim),
+        ));
+    }
+    lines.push(Line::from(time));
+
+    // Counters
+    lines.push(Line::from(vec![
+        Span::styled(
+            format!(
+                " {}:{} {}:{} ",
+                s.rb_stat_built,
+                state.stats.derivations_built,
+                s.rb_stat_fetched,
+                state.stats.fetched
+            ),
+            Style::default().fg(theme.fg),
+        ),
+        Span::styled(
+            format!("⚠:{} ", state.stats.warnings),
+            Style::default().fg(if state.stats.warnings > 0 {
+                theme.warning
+            } else {
+                theme.fg_dim
+            }),
+        ),
+        Span::styled(
+            format!("✗:{}", state.stats.errors),
+            Style::default().fg(if state.stats.errors > 0 {
+                theme.error
+            } else {
+                theme.fg_dim
+            }),
+        ),
+    ]));
+
+    if let Some(attrs) = attrs_line(state, theme) {
+        lines.push(attrs);
+    }
+
+    // Current activity
+    if state.is_running() && !state.current_activity.is_empty() {
+        lines.push(Line::styled(
+            format!(" {}", clip(&state.current_activity)),
+            Style::default().fg(theme.fg_dim),
+        ));
+    }
 
-    // Active phase explanation
-    render_phase_explanation(frame, state, theme, lang, layout[1]);
+    // Last error (wrapped, it's usually the one line that matters)
+    if let Some(err) = state
+        .log_lines
+        .iter()
+        .rev()
+        .find(|l| l.level == LogLevel::Error)
+    {
+        lines.push(Line::raw(""));
+        lines.push(Line::styled(
+            format!(" {}", s.rb_compact_last_error),
+            Style::default()
+                .fg(theme.error)
+                .add_modifier(Modifier::BOLD),
+        ));
+        let chars: Vec<char> = err.text.chars().collect();
+        for chunk in chars.chunks(width.max(1)).take(3) {
+            lines.push(Line::styled(
+                format!(" {}", chunk.iter().collect::<String>()),
+                Style::default().fg(theme.error),
+            ));
+        }
+    }
 
-    // Stats row
-    render_stats_row(frame, state, theme, lang, layout[2]);
+    let hint = if state.is_running() {
+        "[c] cancel"
+    } else {
+        "[Esc] back  [r] rebuild"
+    };
+    lines.push(Line::styled(
+        format!(" {}", hint),
+        Style::default().fg(theme.fg_dim),
+    ));
+    lines.push(Line::styled(
+        "─".repeat(area.width as usize),
+        Style::default().fg(theme.border),
+    ));
 
-    // Separator
-    let sep_line = "─".repeat(area.width as usize);
-    frame.render_widget(
-        Paragraph::new(sep_line).style(Style::default().fg(theme.border)),
-        layout[3],
-    );
+    let header_height = (lines.len() as u16).min(area.height);
+    let layout =
+        Layout::vertical([Constraint::Length(header_height), Constraint::Min(0)]).split(area);
+    frame.render_widget(Paragraph::new(lines), layout[0]);
 
-    // Live output
-    render_live_output(frame, state, theme, lang, layout[4]);
+    // Live output (or the dependency tree) with whatever height is left
+    if state.dashboard_tree {
+        render_build_tree(frame, state, theme, lang, layout[1]);
+    } else {
+        render_live_output(frame, state, theme, lang, layout[1]);
+    }
 }
 
 fn render_phase_boxes(
@@ -1255,7 +3347,7 @@ fn render_single_phase_box(
     let (is_active, is_done, is_failed) = if state.is_running() {
         // Build in progress
         let active = current_idx == Some(idx);
-        let done = current_idx.is_some_and(|ci| idx < ci);
+        let done = !is_skipped && current_idx.is_some_and(|ci| idx < ci);
         (active, done, false)
     } else if matches!(state.phase, BuildPhase::Done) {
         // Build succeeded: all non-skipped phases are done
@@ -1478,6 +3570,18 @@ fn render_idle_dashboard(
                     Style::default().fg(theme.fg_dim),
                 ),
             ]));
+            lines.push(Line::from(vec![
+                Span::styled("     ", Style::default()),
+                Span::styled(
+                    format!(
+                        "{}: {}",
+                        s.rb_host,
+                        crate::nix::detect::nixos_host(state.nixos_host.as_deref())
+                    ),
+                    Style::default().fg(theme.fg_dim),
+                ),
+                Span::styled("  [H]", Style::default().fg(theme.accent)),
+            ]));
         }
 
         lines.push(Line::raw(""));
@@ -1550,6 +3654,14 @@ fn render_idle_dashboard(
         Span::styled("  ", Style::default()),
         Span::styled(s.rb_idle_hint, Style::default().fg(theme.fg_dim)),
     ]));
+    lines.push(Line::from(vec![
+        Span::styled("  ", Style::default()),
+        Span::styled(s.rb_repl_hint, Style::default().fg(theme.fg_dim)),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("  ", Style::default()),
+        Span::styled(s.rb_targets_hint, Style::default().fg(theme.fg_dim)),
+    ]));
 
     lines.push(Line::raw(""));
 
@@ -1583,9 +3695,9 @@ fn render_idle_dashboard(
             Span::styled(
                 format!(
                     "{} ({}) — {}",
-                    last.mode.as_arg(),
+                    last.target.as_deref().unwrap_or(last.mode.as_arg()),
                     duration_str,
-                    last.timestamp
+                    format_stored_time(&last.timestamp, state.time_format, lang)
                 ),
                 Style::default().fg(theme.fg_dim),
             ),
@@ -1599,7 +3711,154 @@ fn render_idle_dashboard(
         }
     }
 
-    frame.render_widget(Paragraph::new(lines), area);
+    // Builds running elsewhere on the system
+    lines.push(Line::raw(""));
+    push_system_builds(&mut lines, state, theme, lang);
+
+    match state.attached {
+        Some(ref att) => {
+            let chunks = Layout::default()
+                .direction(ratatui::layout::Direction::Vertical)
+                .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .split(area);
+            frame.render_widget(Paragraph::new(lines), chunks[0]);
+            render_attached_log(frame, att, theme, lang, chunks[1]);
+        }
+        None => frame.render_widget(Paragraph::new(lines), area),
+    }
+}
+
+fn push_system_builds<'a>(
+    lines: &mut Vec<Line<'a>>,
+    state: &'a RebuildState,
+    theme: &Theme,
+    lang: Language,
+) {
+    let s = i18n::get_strings(lang);
+
+    lines.push(Line::from(vec![
+        Span::styled("  🔨 ", Style::default()),
+        Span::styled(
+            format!("{} ({})", s.rb_sys_builds_title, state.system_builds.len()),
+            Style::default().fg(theme.fg).add_modifier(Modifier::BOLD),
+        ),
+    ]));
+
+    if state.system_builds.is_empty() {
+        lines.push(Line::styled(
+            format!("     {}", s.rb_sys_builds_none),
+            Style::default().fg(theme.fg_dim),
+        ));
+        return;
+    }
+
+    for (i, build) in state.system_builds.iter().enumerate() {
+        let is_selected = i == state.system_builds_selected;
+        let kind = if build.is_builder {
+            s.rb_sys_builder
+        } else {
+            s.rb_sys_client
+        };
+        let name_style = if is_selected {
+            Style::default()
+                .fg(theme.selection_fg)
+                .bg(theme.selection_bg)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.fg)
+        };
+
+        lines.push(Line::from(vec![
+            Span::styled(
+                if is_selected { "   ▸ " } else { "     " },
+                Style::default().fg(theme.accent),
+            ),
+            Span::styled(build.name.as_str(), name_style),
+            Span::styled(
+                format!(
+                    "  {} · {} · ⏱ {}",
+                    kind,
+                    build.owner,
+                    format_duration(build.elapsed)
+                ),
+                Style::default().fg(theme.fg_dim),
+            ),
+            if let Some(unit) = &build.unit {
+                Span::styled(format!("  [{}]", unit), Style::default().fg(theme.success))
+            } else if build.drv_path.is_some() {
+                Span::styled("  [log]", Style::default().fg(theme.success))
+            } else {
+                Span::raw("")
+            },
+        ]));
+    }
+
+    lines.push(Line::styled(
+        format!("     {}", s.rb_sys_builds_hint),
+        Style::default().fg(theme.fg_dim),
+    ));
+}
+
+fn render_attached_log(
+    frame: &mut Frame,
+    att: &AttachedLog,
+    theme: &Theme,
+    lang: Language,
+    area: Rect,
+) {
+    let s = i18n::get_strings(lang);
+
+    let block = Block::default()
+        .title(format!(" {} {} ", s.rb_attach_title, att.name))
+        .title_style(theme.title())
+        .title_bottom(Line::styled(
+            format!(" {} ", s.rb_attach_hint),
+            Style::default().fg(theme.fg_dim),
+        ))
+        .borders(Borders::ALL)
+        .border_style(theme.border_focused());
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let visible = inner.height as usize;
+    let mut lines: Vec<Line> = Vec::new();
+
+    if att.lines.is_empty() && !att.finished {
+        lines.push(Line::styled(
+            format!(" {}", s.rb_attach_waiting),
+            Style::default().fg(theme.fg_dim),
+        ));
+        lines.push(Line::styled(
+            format!(" {}", att.source),
+            Style::default().fg(theme.fg_dim),
+        ));
+    } else {
+        // Always follow the tail
+        let status_rows = usize::from(att.finished);
+        let skip = att
+            .lines
+            .len()
+            .saturating_sub(visible.saturating_sub(status_rows));
+        for line in att.lines.iter().skip(skip) {
+            lines.push(Line::styled(
+                format!(" {}", line),
+                Style::default().fg(match classify_line(line) {
+                    LogLevel::Error => theme.error,
+                    LogLevel::Warning => theme.warning,
+                    LogLevel::Info => theme.accent,
+                    _ => theme.fg,
+                }),
+            ));
+        }
+        if att.finished {
+            lines.push(Line::styled(
+                format!(" ✓ {}", s.rb_attach_finished),
+                Style::default().fg(theme.success),
+            ));
+        }
+    }
+
+    frame.render_widget(Paragraph::new(lines), inner);
 }
 
 // (Phase banner replaced by render_phase_boxes above)
@@ -1671,6 +3930,101 @@ fn render_stats_row(
     frame.render_widget(Paragraph::new(stats), area);
 }
 
+/// Live output, below the warnings panel once the build is done
+fn render_output_with_warnings(
+    frame: &mut Frame,
+    state: &RebuildState,
+    theme: &Theme,
+    lang: Language,
+    area: Rect,
+) {
+    if state.phase != BuildPhase::Done || state.warnings.is_empty() {
+        render_live_output(frame, state, theme, lang, area);
+        return;
+    }
+    let s = i18n::get_strings(lang);
+    let (lines, selected_row) = warning_lines(state, theme, s);
+    let height = (lines.len() as u16 + 1).min(area.height / 2);
+    let layout = Layout::vertical([Constraint::Length(height), Constraint::Min(2)]).split(area);
+
+    // Header stays, rows scroll to keep the selection visible
+    let room = (height as usize).saturating_sub(2);
+    let last_row = selected_row + usize::from(state.warnings_expanded);
+    let skip = (last_row + 1).saturating_sub(room);
+    let mut lines = lines.into_iter();
+    let visible: Vec<Line> = lines
+        .next()
+        .into_iter()
+        .chain(lines.skip(skip).take(room))
+        .collect();
+    frame.render_widget(Paragraph::new(visible), layout[0]);
+    render_live_output(frame, state, theme, lang, layout[1]);
+}
+
+/// "⚠ Warnings (N)" and one row per warning; also the row of the selected
+/// one (counting from the first warning)
+fn warning_lines(
+    state: &RebuildState,
+    theme: &Theme,
+    s: &i18n::Strings,
+) -> (Vec<Line<'static>>, usize) {
+    let mut lines = vec![Line::from(vec![
+        Span::styled(
+            format!(
+                "  ⚠ {}",
+                s.rb_warnings_title
+                    .replace("{}", &state.warnings.len().to_string())
+            ),
+            Style::default()
+                .fg(theme.warning)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(format!("   {}", s.rb_warnings_hint), theme.text_dim()),
+    ])];
+    let selected = state.warnings_selected.min(state.warnings.len() - 1);
+    let mut selected_row = 0;
+    for (i, warning) in state.warnings.iter().enumerate() {
+        let is_selected = i == selected;
+        if is_selected {
+            selected_row = lines.len() - 1;
+        }
+        let mut spans = vec![
+            Span::styled(
+                if is_selected { "  ▸ " } else { "    " },
+                Style::default().fg(theme.accent),
+            ),
+            Span::styled(
+                warning.message.clone(),
+                if is_selected {
+                    theme.selected()
+                } else {
+                    theme.text()
+                },
+            ),
+        ];
+        if warning.count > 1 {
+            spans.push(Span::styled(
+                format!("  ×{}", warning.count),
+                theme.text_dim(),
+            ));
+        }
+        if let Some(option) = &warning.option {
+            spans.push(Span::styled(
+                format!("  → {}", option),
+                Style::default().fg(theme.accent),
+            ));
+        }
+        lines.push(Line::from(spans));
+        if is_selected && state.warnings_expanded {
+            lines.push(Line::styled(
+                format!("      {}", warning.raw),
+                theme.text_dim(),
+            ));
+        }
+    }
+    (lines, selected_row)
+}
+
 fn render_live_output(
     frame: &mut Frame,
     state: &RebuildState,
@@ -1765,6 +4119,126 @@ fn render_live_output(
     frame.render_widget(list, lines_area);
 }
 
+/// Derivations of the run as a dependency tree, the critical path marked
+fn render_build_tree(
+    frame: &mut Frame,
+    state: &RebuildState,
+    theme: &Theme,
+    lang: Language,
+    area: Rect,
+) {
+    let s = i18n::get_strings(lang);
+    if area.height < 2 {
+        return;
+    }
+    let empty = std::collections::HashMap::new();
+    let inputs = match &state.tree_inputs {
+        Some(Ok(inputs)) => inputs,
+        _ => &empty,
+    };
+    let tree = tree::Tree::build(&state.log_lines, inputs, state.phase == BuildPhase::Done);
+    let dim = Style::default().fg(theme.fg_dim);
+
+    let critical = tree.critical_path().len();
+    let mut header = vec![
+        Span::styled(
+            format!("  {} ", s.rb_tree_title),
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            s.rb_tree_counts
+                .replacen("{}", &tree.count(tree::State::Queued).to_string(), 1)
+                .replacen("{}", &tree.count(tree::State::Building).to_string(), 1)
+                .replacen("{}", &tree.count(tree::State::Done).to_string(), 1)
+                .replacen("{}", &tree.count(tree::State::Failed).to_string(), 1),
+            dim,
+        ),
+    ];
+    if critical > 0 {
+        header.push(Span::styled(
+            format!(
+                "  ◆ {}",
+                s.rb_tree_critical.replace("{}", &critical.to_string())
+            ),
+            Style::default().fg(theme.warning),
+        ));
+    }
+    let note = match &state.tree_inputs {
+        _ if state.tree_rx.is_some() => Some(s.rb_tree_loading.to_string()),
+        Some(Err(e)) => Some(s.rb_tree_no_deps.replace("{}", e)),
+        _ => None,
+    };
+    header.push(Span::styled("  [v] ", dim));
+    header.push(Span::styled(s.rb_live_output, dim));
+
+    let mut lines = vec![Line::from(header)];
+    if let Some(note) = note {
+        lines.push(Line::styled(format!("  {}", note), dim));
+    }
+    let rows = tree.rows();
+    if rows.is_empty() {
+        lines.push(Line::styled(format!("  {}", s.rb_tree_empty), dim));
+    }
+    let room = (area.height as usize).saturating_sub(lines.len());
+    let scroll = state.tree_scroll.min(rows.len().saturating_sub(room));
+    for row in rows.iter().skip(scroll).take(room) {
+        let node = &tree.nodes[row.node];
+        let (icon, color) = match node.state {
+            tree::State::Queued => ("○", theme.fg_dim),
+            tree::State::Building => ("◉", theme.accent),
+            tree::State::Done => ("✓", theme.success),
+            tree::State::Failed => ("✗", theme.error),
+        };
+        let mut name = Style::default().fg(match node.state {
+            tree::State::Queued | tree::State::Done => theme.fg,
+            _ => color,
+        });
+        if row.critical {
+            name = name.add_modifier(Modifier::BOLD);
+        }
+        let mut spans = vec![
+            Span::styled(
+                format!(
+                    " {}{}",
+                    "  ".repeat(row.depth),
+                    if row.depth > 0 { "└ " } else { "" }
+                ),
+                Style::default().fg(theme.border),
+            ),
+            Span::styled(format!("{} ", icon), Style::default().fg(color)),
+            Span::styled(node.name.clone(), name),
+        ];
+        if row.critical {
+            spans.push(Span::styled(" ◆", Style::default().fg(theme.warning)));
+        }
+        lines.push(Line::from(spans));
+    }
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+/// Color by level; search matches highlighted
+fn log_line_style(line: &LogLine, theme: &Theme, search_query: Option<&str>) -> Style {
+    if search_query
+        .is_some_and(|q| !q.is_empty() && line.raw.to_lowercase().contains(&q.to_lowercase()))
+    {
+        return Style::default()
+            .fg(theme.selection_fg)
+            .bg(theme.selection_bg)
+            .add_modifier(Modifier::BOLD);
+    }
+    match line.level {
+        LogLevel::Normal => Style::default().fg(theme.fg),
+        LogLevel::Info => Style::default().fg(theme.accent),
+        LogLevel::Warning => Style::default().fg(theme.warning),
+        LogLevel::Error => Style::default().fg(theme.error),
+        LogLevel::Phase => Style::default()
+            .fg(theme.accent)
+            .add_modifier(Modifier::BOLD),
+    }
+}
+
 fn render_log(frame: &mut Frame, state: &RebuildState, theme: &Theme, lang: Language, area: Rect) {
     let s = i18n::get_strings(lang);
 
@@ -1779,9 +4253,40 @@ fn render_log(frame: &mut Frame, state: &RebuildState, theme: &Theme, lang: Lang
         return;
     }
 
+    if state.log_smart {
+        render_smart_log(frame, state, theme, lang, area);
+        render_log_search(frame, state, theme, area);
+        return;
+    }
+
     let visible_lines = area.height as usize;
     let total = state.log_lines.len();
-    let scroll_pos = if state.log_auto_scroll {
+    let kinds: Vec<Option<LineKind>> = if state.log_explain {
+        state.log_lines.iter().map(|l| line_kind(&l.raw)).collect()
+    } else {
+        vec![None; total]
+    };
+    let scroll_pos = if state.log_auto_scroll && state.log_explain {
+        // Explanations take rows too: walk back until the screen is full
+        let mut seen = Vec::new();
+        let mut rows = 0;
+        let mut start = total;
+        while start > 0 {
+            let kind = kinds[start - 1];
+            let extra = usize::from(kind.is_some_and(|k| !seen.contains(&k)));
+            if rows + 1 + extra > visible_lines {
+                break;
+            }
+            if let Some(k) = kind {
+                if !seen.contains(&k) {
+                    seen.push(k);
+                }
+            }
+            rows += 1 + extra;
+            start -= 1;
+        }
+        start
+    } else if state.log_auto_scroll {
         total.saturating_sub(visible_lines)
     } else {
         state.log_scroll.min(total.saturating_sub(visible_lines))
@@ -1793,48 +4298,145 @@ fn render_log(frame: &mut Frame, state: &RebuildState, theme: &Theme, lang: Lang
         None
     };
 
-    let lines: Vec<ListItem> = state
+    // Each line type is explained once per screen, below its first line
+    let mut explained: Vec<LineKind> = Vec::new();
+    let mut lines: Vec<ListItem> = Vec::new();
+    for (line, kind) in state
         .log_lines
         .iter()
+        .zip(&kinds)
         .skip(scroll_pos)
         .take(visible_lines)
-        .map(|line| {
-            let style = match line.level {
-                LogLevel::Normal => Style::default().fg(theme.fg),
-                LogLevel::Info => Style::default().fg(theme.accent),
-                LogLevel::Warning => Style::default().fg(theme.warning),
-                LogLevel::Error => Style::default().fg(theme.error),
-                LogLevel::Phase => Style::default()
-                    .fg(theme.accent)
-                    .add_modifier(Modifier::BOLD),
-            };
-
-            // Log tab shows RAW output (full nix paths, unmodified)
-            let raw = &line.raw;
-
-            // Highlight search matches
-            let highlighted = if let Some(query) = search_query {
-                if !query.is_empty() && raw.to_lowercase().contains(&query.to_lowercase()) {
-                    Style::default()
-                        .fg(theme.selection_fg)
-                        .bg(theme.selection_bg)
-                        .add_modifier(Modifier::BOLD)
-                } else {
-                    style
-                }
-            } else {
-                style
-            };
-
-            let display = format!(" {}", raw);
-            ListItem::new(Line::styled(display, highlighted))
-        })
-        .collect();
+    {
+        if lines.len() >= visible_lines {
+            break;
+        }
+        // Log tab shows RAW output (full nix paths, unmodified)
+        lines.push(ListItem::new(Line::styled(
+            format!(" {}", line.raw),
+            log_line_style(line, theme, search_query),
+        )));
+        if let Some(k) = kind.filter(|k| !explained.contains(k)) {
+            explained.push(k);
+            lines.push(ListItem::new(Line::styled(
+                format!("   ↳ {}", k.explanation(lang)),
+                Style::default()
+                    .fg(theme.fg_dim)
+                    .add_modifier(Modifier::ITALIC),
+            )));
+        }
+    }
 
     let list = List::new(lines);
     frame.render_widget(list, area);
+    render_log_search(frame, state, theme, area);
+}
+
+/// Sections per derivation with a cursor; see `smart`
+fn render_smart_log(
+    frame: &mut Frame,
+    state: &RebuildState,
+    theme: &Theme,
+    lang: Language,
+    area: Rect,
+) {
+    let s = i18n::get_strings(lang);
+    let grouping = smart::group(&state.log_lines);
+    let rows = smart::rows(&state.log_lines, &grouping, &state.smart_expanded);
+    let height = area.height as usize;
+    let last = rows.len().saturating_sub(1);
+    let selected = if state.log_auto_scroll {
+        last
+    } else {
+        state.smart_selected.min(last)
+    };
+    let scroll = (selected + 1).saturating_sub(height);
+    let search_query = Some(state.log_search_query.as_str()).filter(|q| !q.is_empty());
+    let dim = Style::default().fg(theme.fg_dim);
+
+    let mut items: Vec<ListItem> = Vec::new();
+    for (i, row) in rows.iter().enumerate().skip(scroll).take(height) {
+        let line = match row {
+            smart::Row::Line(l) => {
+                let line = &state.log_lines[*l];
+                Line::styled(
+                    format!(" {}", line.raw),
+                    log_line_style(line, theme, search_query),
+                )
+            }
+            smart::Row::Progress { kind, count, last } => {
+                let (icon, label) = match kind {
+                    smart::Progress::Fetch => ("⇣", s.rb_smart_fetched),
+                    smart::Progress::Build => ("⚙", s.rb_smart_built),
+                };
+                Line::from(vec![
+                    Span::styled(format!(" {} ", icon), Style::default().fg(theme.accent)),
+                    Span::styled(label.replace("{}", &count.to_string()), theme.text()),
+                    Span::styled(format!("  {}", state.log_lines[*last].text), dim),
+                ])
+            }
+            smart::Row::Section {
+                name,
+                lines,
+                phase,
+                expanded,
+                pinned,
+            } => {
+                let color = if *pinned {
+                    let error = grouping.index.get(name).is_some_and(|&sec| {
+                        grouping.sections[sec]
+                            .iter()
+                            .any(|&m| state.log_lines[m].level == LogLevel::Error)
+                    });
+                    if error {
+                        theme.error
+                    } else {
+                        theme.warning
+                    }
+                } else {
+                    theme.accent
+                };
+                let mut spans = vec![
+                    Span::styled(
+                        format!(" {} {}", if *expanded { "▾" } else { "▸" }, name),
+                        Style::default().fg(color).add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        format!("  ({})", s.rb_smart_lines.replace("{}", &lines.to_string())),
+                        dim,
+                    ),
+                ];
+                if let Some(p) = phase {
+                    let raw = &state.log_lines[*p].raw;
+                    let text = smart::prefix(raw)
+                        .and_then(|n| raw.get(n.len() + 2..))
+                        .unwrap_or(raw);
+                    spans.push(Span::styled(format!("  · {}", text), theme.text()));
+                }
+                Line::from(spans)
+            }
+            smart::Row::Member(l) => {
+                let line = &state.log_lines[*l];
+                let text = smart::prefix(&line.raw)
+                    .and_then(|n| line.raw.get(n.len() + 1..))
+                    .unwrap_or(&line.raw);
+                Line::from(vec![
+                    Span::styled("   │", dim),
+                    Span::styled(text.to_string(), log_line_style(line, theme, search_query)),
+                ])
+            }
+        };
+        let line = if i == selected && !state.log_auto_scroll {
+            line.patch_style(theme.selected())
+        } else {
+            line
+        };
+        items.push(ListItem::new(line));
+    }
+    frame.render_widget(List::new(items), area);
+}
 
-    // Search bar overlay at bottom if active
+fn render_log_search(frame: &mut Frame, state: &RebuildState, theme: &Theme, area: Rect) {
     if state.log_search_active {
         let search_area = Rect {
             x: area.x,
@@ -1863,9 +4465,18 @@ fn render_changes(
     area: Rect,
 ) {
     let s = i18n::get_strings(lang);
+    let mut lines = preview_lines(state, theme, s);
+    if !state.warnings.is_empty() {
+        lines.extend(warning_lines(state, theme, s).0);
+        lines.push(Line::raw(""));
+    }
 
     let diff = match &state.diff {
         Some(d) => d,
+        None if !lines.is_empty() => {
+            render_scrolled(frame, lines, state.changes_scroll, area);
+            return;
+        }
         None => {
             // No diff available yet
             let msg = if state.is_running() {
@@ -1886,8 +4497,6 @@ fn render_changes(
         }
     };
 
-    let mut lines: Vec<Line> = Vec::new();
-
     // Summary header
     let total_changes = diff.added.len() + diff.removed.len() + diff.updated.len();
     lines.push(Line::from(vec![
@@ -1910,6 +4519,15 @@ fn render_changes(
             Style::default().fg(theme.fg),
         ),
     ]));
+    if let Some(id) = diff.restored_generation {
+        lines.push(Line::styled(
+            format!(
+                "  ↶ {}",
+                s.rb_changes_restored.replace("{}", &id.to_string())
+            ),
+            Style::default().fg(theme.warning),
+        ));
+    }
     lines.push(Line::raw(""));
 
     // Kernel change warning
@@ -2041,16 +4659,89 @@ fn render_changes(
         ));
     }
 
-    // Apply scroll
+    render_scrolled(frame, lines, state.changes_scroll, area);
+}
+
+fn render_scrolled(frame: &mut Frame, lines: Vec<Line>, scroll: usize, area: Rect) {
     let visible = area.height as usize;
     let max_scroll = lines.len().saturating_sub(visible);
-    let scroll = state.changes_scroll.min(max_scroll);
+    let scroll = scroll.min(max_scroll);
 
     let display_lines: Vec<Line> = lines.into_iter().skip(scroll).take(visible).collect();
 
     frame.render_widget(Paragraph::new(display_lines), area);
 }
 
+/// Pre-activation section of the Changes tab: what switching to the built
+/// system would do to running units (empty when nothing was built)
+fn preview_lines<'a>(
+    state: &'a RebuildState,
+    theme: &Theme,
+    s: &'a i18n::Strings,
+) -> Vec<Line<'a>> {
+    if state.built_system.is_none() || state.is_running() {
+        return Vec::new();
+    }
+    let mut lines = vec![Line::styled(
+        format!("  ▶ {}", s.rb_preview_title),
+        Style::default()
+            .fg(theme.accent)
+            .add_modifier(Modifier::BOLD),
+    )];
+    let dim = Style::default().fg(theme.fg_dim);
+    match &state.preview {
+        _ if state.preview_rx.is_some() => {
+            lines.push(Line::styled(format!("    {}", s.rb_preview_running), dim));
+        }
+        None => lines.push(Line::styled(format!("    {}", s.rb_preview_hint), dim)),
+        Some(Err(e)) => {
+            lines.push(Line::styled(
+                format!("    ✖ {}", e),
+                Style::default().fg(theme.error),
+            ));
+            if e.contains("password") {
+                lines.push(Line::styled(format!("    {}", s.rb_preview_sudo), dim));
+            }
+        }
+        Some(Ok(dry)) if dry.is_empty() => {
+            lines.push(Line::styled(format!("    {}", s.rb_preview_nothing), dim));
+        }
+        Some(Ok(dry)) => {
+            if dry.restart_systemd {
+                lines.push(Line::styled(
+                    format!("    ⟳ {}", s.rb_preview_systemd),
+                    Style::default().fg(theme.warning),
+                ));
+            }
+            let groups = [
+                ("■", s.rb_preview_stop, &dry.stop, theme.error),
+                ("⟳", s.rb_preview_restart, &dry.restart, theme.warning),
+                ("↻", s.rb_preview_reload, &dry.reload, theme.accent),
+                ("▶", s.rb_preview_start, &dry.start, theme.diff_added),
+                ("·", s.rb_preview_kept, &dry.kept, theme.fg_dim),
+            ];
+            for (icon, label, units, color) in groups {
+                if units.is_empty() {
+                    continue;
+                }
+                lines.push(Line::styled(
+                    format!("    {} {} ({})", icon, label, units.len()),
+                    Style::default().fg(color).add_modifier(Modifier::BOLD),
+                ));
+                for unit in units {
+                    lines.push(Line::styled(
+                        format!("      {}", unit),
+                        Style::default().fg(theme.fg),
+                    ));
+                }
+            }
+            lines.push(Line::styled(format!("    {}", s.rb_preview_switch), dim));
+        }
+    }
+    lines.push(Line::raw(""));
+    lines
+}
+
 fn render_history(
     frame: &mut Frame,
     state: &RebuildState,
@@ -2071,6 +4762,53 @@ fn render_history(
         return;
     }
 
+    let phase_names = [
+        s.rb_breakdown_eval,
+        s.rb_breakdown_fetch,
+        s.rb_breakdown_build,
+        s.rb_breakdown_activate,
+        s.rb_breakdown_boot,
+    ];
+    let phase_colors = breakdown_colors(theme);
+    let area = match breakdown::summary(&state.history) {
+        Some((count, phases)) => {
+            let mut spans = vec![Span::styled(
+                format!(" {} ", s.rb_breakdown_avg.replace("{}", &count.to_string())),
+                Style::default().fg(theme.fg_dim),
+            )];
+            for (i, (secs, trend)) in phases.iter().enumerate() {
+                spans.push(Span::styled("■ ", Style::default().fg(phase_colors[i])));
+                spans.push(Span::styled(
+                    format!(
+                        "{} {}",
+                        phase_names[i],
+                        format_duration(Duration::from_secs(*secs))
+                    ),
+                    Style::default().fg(theme.fg),
+                ));
+                let trend_color = match trend {
+                    breakdown::Trend::Slower => theme.warning,
+                    _ => theme.success,
+                };
+                spans.push(Span::styled(
+                    format!("{}  ", trend.arrow()),
+                    Style::default().fg(trend_color),
+                ));
+            }
+            let rows = Layout::vertical([Constraint::Length(2), Constraint::Min(0)]).split(area);
+            frame.render_widget(Paragraph::new(Line::from(spans)), rows[0]);
+            rows[1]
+        }
+        None => area,
+    };
+    let longest = state
+        .history
+        .iter()
+        .filter(|h| !h.phase_secs.is_empty())
+        .map(|h| h.phase_secs.iter().sum::<u64>())
+        .max()
+        .unwrap_or(0);
+
     let items: Vec<ListItem> = state
         .history
         .iter()
@@ -2089,7 +4827,7 @@ fn render_history(
 
             let duration_str = format_duration(entry.duration);
 
-            let spans = vec![
+            let mut spans = vec![
                 Span::styled(
                     if is_selected { " ▸ " } else { "   " },
                     Style::default().fg(theme.accent),
@@ -2099,11 +4837,24 @@ fn render_history(
                     Style::default().fg(status_color),
                 ),
                 Span::styled(
-                    format!("{} ", entry.timestamp),
+                    format!(
+                        "{} ",
+                        format_stored_time(&entry.timestamp, state.time_format, lang)
+                    ),
                     Style::default().fg(theme.fg_dim),
                 ),
+                match entry.target {
+                    Some(ref target) => {
+                        Span::styled(format!("⚒ {} ", target), Style::default().fg(theme.accent))
+                    }
+                    None => history_mode_span(entry.mode, theme),
+                },
                 Span::styled(
-                    format!("{} ", entry.mode.as_arg()),
+                    entry
+                        .specialisation
+                        .as_ref()
+                        .map(|name| format!("[{}] ", name))
+                        .unwrap_or_default(),
                     Style::default().fg(theme.accent),
                 ),
                 Span::styled(
@@ -2111,6 +4862,13 @@ fn render_history(
                     Style::default().fg(theme.fg_dim),
                 ),
             ];
+            if !entry.phase_secs.is_empty() {
+                spans.push(Span::raw("  "));
+                let widths = breakdown::bar_widths(&entry.phase_secs, longest, HISTORY_BAR_WIDTH);
+                for (cells, color) in widths.into_iter().zip(phase_colors) {
+                    spans.push(Span::styled("█".repeat(cells), Style::default().fg(color)));
+                }
+            }
 
             let mut lines = vec![Line::from(spans)];
 
@@ -2132,6 +4890,20 @@ fn render_history(
     frame.render_widget(list, area);
 }
 
+/// Width of the per-phase bar of the longest build in History
+const HISTORY_BAR_WIDTH: usize = 24;
+
+/// Eval, fetch, build, activate, bootloader
+fn breakdown_colors(theme: &Theme) -> [Color; 5] {
+    [
+        theme.accent,
+        theme.diff_updated,
+        theme.warning,
+        theme.success,
+        theme.accent_dim,
+    ]
+}
+
 fn render_confirm_popup(
     frame: &mut Frame,
     state: &RebuildState,
@@ -2143,8 +4915,9 @@ fn render_confirm_popup(
 
     let cmd = state.current_command();
     let mode_label = state.mode.label(lang);
+    let rollback = state.mode == RebuildMode::Rollback;
 
-    let content = vec![
+    let mut content = vec![
         Line::raw(""),
         Line::from(vec![
             Span::styled(
@@ -2158,6 +4931,42 @@ fn render_confirm_popup(
                     .add_modifier(Modifier::BOLD),
             ),
         ]),
+    ];
+    if specialisation::applies(state.mode) && !state.specialisations.is_empty() {
+        content.push(Line::from(vec![
+            Span::styled(
+                format!("  {}: ", s.rb_confirm_specialisation),
+                Style::default().fg(theme.fg),
+            ),
+            Span::styled(
+                state.specialisation.as_deref().unwrap_or(s.rb_spec_default),
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("  ({})", s.rb_spec_cycle),
+                Style::default().fg(theme.fg_dim),
+            ),
+        ]));
+    }
+    if state.offline && state.mode.builds() {
+        content.push(Line::styled(
+            format!("  {}", s.rb_confirm_offline),
+            Style::default().fg(theme.warning),
+        ));
+    }
+    if rollback {
+        let target = state
+            .rollback_target
+            .map(|id| id.to_string())
+            .unwrap_or_else(|| "?".into());
+        content.push(Line::styled(
+            format!("  {}", s.rb_confirm_rollback.replace("{}", &target)),
+            Style::default().fg(theme.warning),
+        ));
+    }
+    content.extend([
         Line::raw(""),
         Line::from(vec![Span::styled(
             format!("  {}: ", s.rb_confirm_cmd),
@@ -2195,7 +5004,7 @@ fn render_confirm_popup(
             Span::styled("  ", Style::default()),
             Span::styled(s.rb_nopasswd_hint, Style::default().fg(theme.fg)),
         ]),
-    ];
+    ]);
 
     // Use custom popup rendering for wider width
     let popup_width = 66.min(area.width.saturating_sub(4));
@@ -2206,7 +5015,14 @@ fn render_confirm_popup(
 
     let block = Block::default()
         .style(theme.block_style())
-        .title(format!(" {} ", s.rb_confirm_title))
+        .title(format!(
+            " {} ",
+            if rollback {
+                s.rb_confirm_rollback_title
+            } else {
+                s.rb_confirm_title
+            }
+        ))
         .title_style(theme.title())
         .borders(Borders::ALL)
         .border_style(theme.border_focused());
@@ -2260,28 +5076,45 @@ fn render_confirm_popup(
     );
 }
 
+/// Mode column of a history entry; rollbacks stand out
+fn history_mode_span(mode: RebuildMode, theme: &Theme) -> Span<'static> {
+    if mode == RebuildMode::Rollback {
+        Span::styled(
+            format!("↶ {} ", mode.as_arg()),
+            Style::default().fg(theme.warning),
+        )
+    } else {
+        Span::styled(
+            format!("{} ", mode.as_arg()),
+            Style::default().fg(theme.accent),
+        )
+    }
+}
+
 // ── Background rebuild logic ──
 
 #[allow(clippy::too_many_arguments)]
 fn run_rebuild(
     tx: mpsc::Sender<RebuildMsg>,
     mode: RebuildMode,
-    uses_flakes: bool,
+    rollback_to: Option<u32>,
+    rebuild_cmd: (String, Vec<String>),
     flake_path: Option<&str>,
     password: Option<String>,
-    show_trace: bool,
+    extra_args: Vec<String>,
     child_pid: Arc<AtomicU32>,
     auth_msg: String,
     update_flake: bool,
     updating_flake_msg: String,
     flake_update_failed_msg: String,
+    backend_steps: Vec<backend::Step>,
 ) {
     use std::io::{BufRead, BufReader, Write};
     use std::process::{Command, Stdio};
 
     // Phase 1: Take pre-rebuild snapshot
     let _ = tx.send(RebuildMsg::Phase(BuildPhase::Preparing));
-    let pre_snapshot = take_package_snapshot();
+    let pre_snapshot = take_package_snapshot(CURRENT_SYSTEM);
     let _ = tx.send(RebuildMsg::PreSnapshot(
         pre_snapshot.0.clone(),
         pre_snapshot.1.clone(),
@@ -2371,13 +5204,25 @@ fn run_rebuild(
         }
     }
 
-    // Phase 2: Build the command
-    let _ = tx.send(RebuildMsg::Phase(BuildPhase::Evaluating));
+    // Build with the backend first; a failed attribute stops here
+    for step in &backend_steps {
+        if let Err(e) = run_backend_step(&tx, step, &child_pid) {
+            let _ = tx.send(RebuildMsg::OutputLine(e.clone()));
+            let _ = tx.send(RebuildMsg::Finished(false, Some(e)));
+            return;
+        }
+    }
 
-    let cmd_str = build_rebuild_command(mode.as_arg(), uses_flakes, flake_path);
+    // Phase 2: Build the command. A rollback goes straight to activation.
+    let first_phase = if mode.builds() {
+        BuildPhase::Evaluating
+    } else {
+        BuildPhase::Activating
+    };
+    let _ = tx.send(RebuildMsg::Phase(first_phase));
 
     // Build the command args
-    let (program, base_args) = cmd_str;
+    let (program, base_args) = rebuild_cmd;
     let has_sudo = program == "sudo";
     let mut args: Vec<String> = if has_sudo && password.is_some() {
         // Insert -S flag after "sudo" to read password from stdin
@@ -2388,15 +5233,14 @@ fn run_rebuild(
         base_args
     };
 
-    if show_trace {
-        args.push("--show-trace".into());
-    }
+    args.extend(extra_args);
 
     if password.is_some() {
         let _ = tx.send(RebuildMsg::OutputLine(auth_msg));
     }
 
-    let mut child = match Command::new(&program)
+    let mut command = Command::new(&program);
+    command
         .args(&args)
         .stdin(if password.is_some() {
             Stdio::piped()
@@ -2404,9 +5248,16 @@ fn run_rebuild(
             Stdio::null()
         })
         .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
+        .stderr(Stdio::piped());
+    // Own process group: `cancel_build` signals the whole group, and a
+    // terminal hangup doesn't reach the build before we can detach it
+    #[cfg(unix)]
     {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+
+    let mut child = match command.spawn() {
         Ok(c) => c,
         Err(e) => {
             let _ = tx.send(RebuildMsg::OutputLine(format!("Failed to start: {}", e)));
@@ -2434,7 +5285,7 @@ fn run_rebuild(
         if let Some(stderr) = stderr {
             let reader = BufReader::new(stderr);
             let mut stats = BuildStats::default();
-            let mut current_phase = BuildPhase::Evaluating;
+            let mut current_phase = first_phase;
 
             for line in reader.lines().map_while(Result::ok) {
                 // Phase detection
@@ -2492,7 +5343,20 @@ fn run_rebuild(
         // from the build output. Just take the snapshot silently.
         std::thread::sleep(std::time::Duration::from_millis(500));
 
-        let post_snapshot = take_package_snapshot();
+        // After a rollback, compare against the generation it restored,
+        // after a build against the system it built
+        let built = (mode == RebuildMode::Build)
+            .then(preview::built_system)
+            .flatten();
+        let post_path = match (rollback_to, built) {
+            (Some(id), _) => format!("/nix/var/nix/profiles/system-{}-link", id),
+            (None, Some(system)) => {
+                let _ = tx.send(RebuildMsg::BuiltSystem(system.clone()));
+                system
+            }
+            (None, None) => CURRENT_SYSTEM.to_string(),
+        };
+        let post_snapshot = take_package_snapshot(&post_path);
         let _ = tx.send(RebuildMsg::PostSnapshot(
             post_snapshot.0,
             post_snapshot.1,
@@ -2503,13 +5367,201 @@ fn run_rebuild(
     let _ = tx.send(RebuildMsg::Finished(success, err_msg));
 }
 
+/// Run one build backend invocation, streaming its output like the
+/// rebuild's own. No sudo: building goes through the nix daemon.
+fn run_backend_step(
+    tx: &mpsc::Sender<RebuildMsg>,
+    step: &backend::Step,
+    child_pid: &AtomicU32,
+) -> Result<(), String> {
+    use std::io::{BufRead, BufReader};
+    use std::process::{Command, Stdio};
+
+    let _ = tx.send(RebuildMsg::Phase(BuildPhase::Evaluating));
+    for &a in &step.attrs {
+        let _ = tx.send(RebuildMsg::Attr(a, backend::AttrState::Building));
+    }
+    let _ = tx.send(RebuildMsg::OutputLine(format!("$ {}", step.command_line())));
+
+    let mut command = Command::new(step.program);
+    command
+        .args(&step.args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+    let mut child = command
+        .spawn()
+        .map_err(|e| format!("{}: {}", step.program, e))?;
+    child_pid.store(child.id(), Ordering::SeqCst);
+
+    let stderr = child.stderr.take();
+    let tx_stderr = tx.clone();
+    let stderr_handle = std::thread::spawn(move || {
+        let Some(stderr) = stderr else {
+            return;
+        };
+        let mut current_phase = BuildPhase::Evaluating;
+        for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+            let new_phase = detect_phase(&line, current_phase);
+            if new_phase != current_phase {
+                current_phase = new_phase;
+                let _ = tx_stderr.send(RebuildMsg::Phase(new_phase));
+            }
+            let _ = tx_stderr.send(RebuildMsg::OutputLine(line));
+        }
+    });
+    if let Some(stdout) = child.stdout.take() {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            let _ = tx.send(RebuildMsg::OutputLine(line));
+        }
+    }
+    let status = child.wait();
+    let _ = stderr_handle.join();
+    child_pid.store(0, Ordering::SeqCst);
+
+    let ok = status.as_ref().is_ok_and(|s| s.success());
+    let state = if ok {
+        backend::AttrState::Done
+    } else {
+        backend::AttrState::Failed
+    };
+    for &a in &step.attrs {
+        let _ = tx.send(RebuildMsg::Attr(a, state));
+    }
+    match status {
+        Ok(s) if s.success() => Ok(()),
+        Ok(s) => Err(format!("{}: exit code {:?}", step.program, s.code())),
+        Err(e) => Err(format!("{}: {}", step.program, e)),
+    }
+}
+
+/// Follow the log of a build running outside nixmate.
+///
+/// `nix log` has no follow mode, so re-read it periodically and forward
+/// only new lines until the builder process exits.
+fn follow_build_log(tx: mpsc::Sender<AttachMsg>, drv: &str, pid: u32, stop: Arc<AtomicBool>) {
+    use std::process::{Command, Stdio};
+
+    let mut seen = 0;
+    loop {
+        let alive = std::path::Path::new(&format!("/proc/{}", pid)).exists();
+
+        if let Ok(output) = Command::new("nix")
+            .args(["--extra-experimental-features", "nix-command", "log", drv])
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()
+        {
+            let text = String::from_utf8_lossy(&output.stdout);
+            let lines: Vec<String> = text.lines().map(|l| l.to_string()).collect();
+            if lines.len() > seen {
+                let new = lines[seen..].to_vec();
+                seen = lines.len();
+                if tx.send(AttachMsg::Lines(new)).is_err() {
+                    return;
+                }
+            }
+        }
+
+        if !alive {
+            let _ = tx.send(AttachMsg::Finished);
+            return;
+        }
+
+        // Sleep in small steps so detaching is responsive
+        for _ in 0..20 {
+            if stop.load(Ordering::SeqCst) {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(100));
+        }
+    }
+}
+
+/// Follow the journal of a detached rebuild unit until it stops.
+///
+/// Polls like `follow_build_log`; `journalctl -f` would need killing on detach.
+fn follow_unit_log(tx: mpsc::Sender<AttachMsg>, unit: &str, stop: Arc<AtomicBool>) {
+    use std::process::{Command, Stdio};
+
+    let mut seen = 0;
+    loop {
+        let active = Command::new("systemctl")
+            .args(["is-active", "--quiet", unit])
+            .stdin(Stdio::null())
+            .status()
+            .is_ok_and(|st| st.success());
+
+        if let Ok(output) = Command::new("journalctl")
+            .args(["-u", unit, "-o", "cat", "--no-pager"])
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()
+        {
+            let text = String::from_utf8_lossy(&output.stdout);
+            let lines: Vec<String> = text.lines().map(|l| l.to_string()).collect();
+            if lines.len() > seen {
+                let new = lines[seen..].to_vec();
+                seen = lines.len();
+                if tx.send(AttachMsg::Lines(new)).is_err() {
+                    return;
+                }
+            }
+        }
+
+        if !active {
+            let _ = tx.send(AttachMsg::Finished);
+            return;
+        }
+
+        for _ in 0..20 {
+            if stop.load(Ordering::SeqCst) {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(100));
+        }
+    }
+}
+
+/// Wait until no process of the group is left. EPERM still means alive:
+/// the root processes under sudo can't be signalled, only probed.
+fn wait_for_group_exit(pgid: u32, timeout: Duration) -> bool {
+    let start = Instant::now();
+    loop {
+        // SAFETY: signal 0 only checks whether the group exists
+        let gone = unsafe { libc::kill(-(pgid as i32), 0) } == -1
+            && std::io::Error::last_os_error().raw_os_error() == Some(libc::ESRCH);
+        if gone {
+            return true;
+        }
+        if start.elapsed() >= timeout {
+            return false;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+}
+
 // ── System detection helpers ──
 
 fn build_rebuild_command(
-    mode: &str,
+    mode: RebuildMode,
     uses_flakes: bool,
     flake_path: Option<&str>,
+    nixos_host: Option<&str>,
 ) -> (String, Vec<String>) {
+    // Rolling back activates an existing generation: no flake to evaluate
+    if mode == RebuildMode::Rollback {
+        return (
+            "sudo".into(),
+            vec!["nixos-rebuild".into(), "switch".into(), "--rollback".into()],
+        );
+    }
+    let mode = mode.as_arg();
     if uses_flakes {
         let path = flake_path.unwrap_or("/etc/nixos");
         (
@@ -2518,7 +5570,7 @@ fn build_rebuild_command(
                 "nixos-rebuild".into(),
                 mode.into(),
                 "--flake".into(),
-                format!("{}#", path),
+                flake_ref(path, nixos_host),
             ],
         )
     } else {
@@ -2526,6 +5578,58 @@ fn build_rebuild_command(
     }
 }
 
+/// `path#attr` for `nixos-rebuild --flake`: the configuration picked with
+/// `H`, else a bare `path#` (nixos-rebuild picks the host name itself)
+fn flake_ref(path: &str, nixos_host: Option<&str>) -> String {
+    format!("{}#{}", path, nixos_host.unwrap_or_default())
+}
+
+/// `nixos-rebuild repl` where available, otherwise a plain `nix repl` on the
+/// configuration. No sudo: evaluation only needs read access.
+fn build_repl_command(
+    uses_flakes: bool,
+    flake_path: Option<&str>,
+    config_path: Option<&str>,
+    nixos_host: Option<&str>,
+    notice: &str,
+) -> Option<ExternalCommand> {
+    let path = flake_path.unwrap_or("/etc/nixos");
+    let (program, args): (&str, Vec<String>) = if command_exists("nixos-rebuild") {
+        if uses_flakes {
+            (
+                "nixos-rebuild",
+                vec!["repl".into(), "--flake".into(), flake_ref(path, nixos_host)],
+            )
+        } else {
+            let mut args = vec!["repl".to_string()];
+            if let Some(cp) = config_path {
+                args.push("-I".into());
+                args.push(format!("nixos-config={}/configuration.nix", cp));
+            }
+            ("nixos-rebuild", args)
+        }
+    } else if command_exists("nix") && uses_flakes {
+        let host = crate::nix::detect::nixos_host(nixos_host);
+        (
+            "nix",
+            vec![
+                "--extra-experimental-features".into(),
+                "nix-command flakes".into(),
+                "repl".into(),
+                format!("{}#nixosConfigurations.{}", path, host),
+            ],
+        )
+    } else {
+        return None;
+    };
+    Some(ExternalCommand {
+        program: program.into(),
+        args,
+        cwd: uses_flakes.then(|| path.to_string()),
+        notice: notice.to_string(),
+    })
+}
+
 // ── Line parsing ──
 
 fn detect_phase(line: &str, current: BuildPhase) -> BuildPhase {
@@ -2596,6 +5700,11 @@ fn update_stats(line: &str, stats: &mut BuildStats) {
         }
     }
 
+    // "these 12 paths will be fetched" / "this path will be fetched"
+    if lower.contains("will be fetched") {
+        stats.fetch_total = Some(extract_number(line).unwrap_or(1));
+    }
+
     // Fetched paths
     if lower.contains("copying path") || lower.contains("fetching path") {
         stats.fetched += 1;
@@ -2685,13 +5794,14 @@ fn phase_label(phase: BuildPhase, lang: crate::config::Language) -> &'static str
 
 // ── Package snapshot for diff ──
 
-fn take_package_snapshot() -> (Vec<(String, String)>, Option<String>, Option<String>) {
+const CURRENT_SYSTEM: &str = "/run/current-system";
+
+fn take_package_snapshot(system: &str) -> (Vec<(String, String)>, Option<String>, Option<String>) {
     let mut packages = Vec::new();
     let mut kernel = None;
     let mut nixos_ver = None;
 
-    // Get current system profile path
-    let system_path = std::path::Path::new("/run/current-system");
+    let system_path = std::path::Path::new(system);
 
     // Try to get NixOS version
     let ver_path = system_path.join("nixos-version");
@@ -2794,7 +5904,7 @@ fn parse_path_info_for_snapshot(json_str: &str) -> Vec<(String, String)> {
     packages
 }
 
-fn parse_store_path_name(path: &str) -> Option<(String, String)> {
+pub(crate) fn parse_store_path_name(path: &str) -> Option<(String, String)> {
     // Format: /nix/store/hash-name-version
     let basename = path.rsplit('/').next()?;
     // Skip the hash prefix (32 chars + dash)
@@ -2813,7 +5923,7 @@ fn parse_store_path_name(path: &str) -> Option<(String, String)> {
     Some((rest.to_string(), String::new()))
 }
 
-fn should_skip_pkg(name: &str) -> bool {
+pub(crate) fn should_skip_pkg(name: &str) -> bool {
     // Skip infrastructure packages that aren't meaningful for users
     let skip_prefixes = [
         "hook",
@@ -2894,6 +6004,7 @@ fn calculate_diff(
         reboot_needed,
         services_restarted: Vec::new(),
         nixos_version,
+        restored_generation: None,
     }
 }
 
@@ -2912,133 +6023,177 @@ fn format_duration(d: Duration) -> String {
 
 /// Beautify Nix store paths and common output into human-readable messages.
 /// This is the "intelligent log" feature — the core UX improvement over raw terminal output.
-fn beautify_store_path(line: &str) -> String {
+/// Line types the beautifier (and explain mode) recognizes in nix output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LineKind {
+    Build,
+    Fetch,
+    BuildPlan,
+    FetchPlan,
+    Evaluating,
+    Activating,
+    Etc,
+    Restart,
+    Start,
+    Stop,
+    Reload,
+    Grub,
+    SystemdBoot,
+    Warning,
+    Error,
+    Trace,
+}
+
+impl LineKind {
+    /// What this kind of line means, for explain mode in the Log tab
+    fn explanation(&self, lang: Language) -> &'static str {
+        let s = i18n::get_strings(lang);
+        match self {
+            LineKind::Build => s.rb_line_build,
+            LineKind::Fetch => s.rb_line_fetch,
+            LineKind::BuildPlan => s.rb_line_build_plan,
+            LineKind::FetchPlan => s.rb_line_fetch_plan,
+            LineKind::Evaluating => s.rb_line_evaluating,
+            LineKind::Activating => s.rb_line_activating,
+            LineKind::Etc => s.rb_line_etc,
+            LineKind::Restart => s.rb_line_restart,
+            LineKind::Start => s.rb_line_start,
+            LineKind::Stop => s.rb_line_stop,
+            LineKind::Reload => s.rb_line_reload,
+            LineKind::Grub | LineKind::SystemdBoot => s.rb_line_bootloader,
+            LineKind::Warning => s.rb_line_warning,
+            LineKind::Error => s.rb_line_error,
+            LineKind::Trace => s.rb_line_trace,
+        }
+    }
+}
+
+/// Recognize a raw output line (order matters: "restarting" contains "starting")
+fn line_kind(line: &str) -> Option<LineKind> {
     let lower = line.to_lowercase();
+    let kind = if lower.contains("building '") {
+        LineKind::Build
+    } else if lower.contains("copying path") || lower.contains("fetching path") {
+        LineKind::Fetch
+    } else if lower.contains("derivations will be built")
+        || lower.contains("derivation(s) will be built")
+    {
+        LineKind::BuildPlan
+    } else if lower.contains("paths will be fetched") {
+        LineKind::FetchPlan
+    } else if lower.starts_with("evaluating") {
+        LineKind::Evaluating
+    } else if lower.contains("activating the configuration") {
+        LineKind::Activating
+    } else if lower.contains("setting up /etc") {
+        LineKind::Etc
+    } else if lower.contains("restarting the following units:") {
+        LineKind::Restart
+    } else if lower.contains("starting the following units:") {
+        LineKind::Start
+    } else if lower.contains("stopping the following units:") {
+        LineKind::Stop
+    } else if lower.contains("reloading the following units:") {
+        LineKind::Reload
+    } else if lower.contains("updating grub") || lower.contains("installing grub") {
+        LineKind::Grub
+    } else if lower.contains("updating systemd-boot") || lower.contains("installing systemd-boot") {
+        LineKind::SystemdBoot
+    } else if lower.starts_with("warning:") {
+        LineKind::Warning
+    } else if lower.starts_with("error:") {
+        LineKind::Error
+    } else if lower.starts_with("trace:") {
+        LineKind::Trace
+    } else {
+        return None;
+    };
+    Some(kind)
+}
 
-    // Pattern: building '/nix/store/hash-name-version.drv'
-    if lower.contains("building '") {
-        if let Some(start) = line.find("/nix/store/") {
-            if let Some(end) = line[start..].find('\'') {
-                let store_path = &line[start..start + end];
-                if let Some((name, version)) = parse_store_path_name(store_path) {
-                    let clean_name = name.trim_end_matches(".drv");
-                    if version.is_empty() || version.ends_with(".drv") {
-                        let clean_ver = version.trim_end_matches(".drv");
-                        if clean_ver.is_empty() {
-                            return format!("🔨 Building {}", clean_name);
+fn beautify_store_path(line: &str) -> String {
+    let units = || line.split("units:").nth(1).unwrap_or("").trim();
+
+    match line_kind(line) {
+        // Pattern: building '/nix/store/hash-name-version.drv'
+        Some(LineKind::Build) => {
+            if let Some(start) = line.find("/nix/store/") {
+                if let Some(end) = line[start..].find('\'') {
+                    let store_path = &line[start..start + end];
+                    if let Some((name, version)) = parse_store_path_name(store_path) {
+                        let clean_name = name.trim_end_matches(".drv");
+                        if version.is_empty() || version.ends_with(".drv") {
+                            let clean_ver = version.trim_end_matches(".drv");
+                            if clean_ver.is_empty() {
+                                return format!("🔨 Building {}", clean_name);
+                            }
+                            return format!("🔨 Building {} {}", clean_name, clean_ver);
                         }
-                        return format!("🔨 Building {} {}", clean_name, clean_ver);
+                        return format!("🔨 Building {} {}", clean_name, version);
                     }
-                    return format!("🔨 Building {} {}", clean_name, version);
                 }
             }
         }
-    }
 
-    // Pattern: copying path '/nix/store/hash-name-version' ...
-    if lower.contains("copying path") || lower.contains("fetching path") {
-        if let Some(start) = line.find("/nix/store/") {
-            let rest = &line[start..];
-            let end = rest
-                .find('\'')
-                .or_else(|| rest.find(' '))
-                .unwrap_or(rest.len());
-            let store_path = &rest[..end];
-            if let Some((name, version)) = parse_store_path_name(store_path) {
-                if version.is_empty() {
-                    return format!("📦 Fetching {}", name);
+        // Pattern: copying path '/nix/store/hash-name-version' ...
+        Some(LineKind::Fetch) => {
+            if let Some(start) = line.find("/nix/store/") {
+                let rest = &line[start..];
+                let end = rest
+                    .find('\'')
+                    .or_else(|| rest.find(' '))
+                    .unwrap_or(rest.len());
+                let store_path = &rest[..end];
+                if let Some((name, version)) = parse_store_path_name(store_path) {
+                    if version.is_empty() {
+                        return format!("📦 Fetching {}", name);
+                    }
+                    return format!("📦 Fetching {} {}", name, version);
                 }
-                return format!("📦 Fetching {} {}", name, version);
             }
         }
-    }
 
-    // Pattern: "these N derivations will be built:"
-    if lower.contains("derivations will be built") || lower.contains("derivation(s) will be built")
-    {
-        if let Some(n) = extract_number(line) {
-            return format!("📋 {} derivations to build", n);
+        // Pattern: "these N derivations will be built:"
+        Some(LineKind::BuildPlan) => {
+            if let Some(n) = extract_number(line) {
+                return format!("📋 {} derivations to build", n);
+            }
         }
-    }
 
-    // Pattern: "these N paths will be fetched (M MiB download, ..."
-    if lower.contains("paths will be fetched") {
-        if let Some(n) = extract_number(line) {
-            // Try to extract size info
-            if let Some(size_start) = line.find('(') {
-                if let Some(size_end) = line.find(')') {
-                    let size_info = &line[size_start + 1..size_end];
-                    return format!("📋 {} paths to fetch ({})", n, size_info);
+        // Pattern: "these N paths will be fetched (M MiB download, ..."
+        Some(LineKind::FetchPlan) => {
+            if let Some(n) = extract_number(line) {
+                // Try to extract size info
+                if let Some(size_start) = line.find('(') {
+                    if let Some(size_end) = line.find(')') {
+                        let size_info = &line[size_start + 1..size_end];
+                        return format!("📋 {} paths to fetch ({})", n, size_info);
+                    }
                 }
+                return format!("📋 {} paths to fetch from cache", n);
             }
-            return format!("📋 {} paths to fetch from cache", n);
         }
-    }
-
-    // Pattern: "evaluating derivation ..."
-    if lower.starts_with("evaluating") {
-        return format!("⚙ {}", line);
-    }
-
-    // Pattern: "activating the configuration..."
-    if lower.contains("activating the configuration") {
-        return "⚡ Activating new system configuration".to_string();
-    }
-
-    // Pattern: "setting up /etc..."
-    if lower.contains("setting up /etc") {
-        return "📁 Updating /etc configuration files".to_string();
-    }
 
-    // Pattern: "restarting the following units: ..."
-    if lower.contains("restarting the following units:") {
-        let units = line.split("units:").nth(1).unwrap_or("").trim();
-        return format!("🔄 Restarting: {}", units);
-    }
-
-    // Pattern: "starting the following units: ..."
-    if lower.contains("starting the following units:") {
-        let units = line.split("units:").nth(1).unwrap_or("").trim();
-        return format!("▶ Starting: {}", units);
-    }
-
-    // Pattern: "stopping the following units: ..."
-    if lower.contains("stopping the following units:") {
-        let units = line.split("units:").nth(1).unwrap_or("").trim();
-        return format!("⏹ Stopping: {}", units);
-    }
-
-    // Pattern: "reloading the following units: ..."
-    if lower.contains("reloading the following units:") {
-        let units = line.split("units:").nth(1).unwrap_or("").trim();
-        return format!("🔃 Reloading: {}", units);
-    }
-
-    // Pattern: "updating GRUB" / "updating systemd-boot"
-    if lower.contains("updating grub") || lower.contains("installing grub") {
-        return "🥾 Updating GRUB bootloader".to_string();
-    }
-    if lower.contains("updating systemd-boot") || lower.contains("installing systemd-boot") {
-        return "🥾 Updating systemd-boot".to_string();
-    }
-
-    // Pattern: warning: ...
-    if lower.starts_with("warning:") {
-        return format!("⚠ {}", line);
-    }
-
-    // Pattern: error: ...
-    if lower.starts_with("error:") {
-        return format!("✗ {}", line);
-    }
-
-    // Pattern: trace: ... (Nix evaluation trace)
-    if lower.starts_with("trace:") {
-        // Shorten long traces
-        if line.chars().count() > 100 {
+        Some(LineKind::Evaluating) => return format!("⚙ {}", line),
+        Some(LineKind::Activating) => {
+            return "⚡ Activating new system configuration".to_string();
+        }
+        Some(LineKind::Etc) => return "📁 Updating /etc configuration files".to_string(),
+        Some(LineKind::Restart) => return format!("🔄 Restarting: {}", units()),
+        Some(LineKind::Start) => return format!("▶ Starting: {}", units()),
+        Some(LineKind::Stop) => return format!("⏹ Stopping: {}", units()),
+        Some(LineKind::Reload) => return format!("🔃 Reloading: {}", units()),
+        Some(LineKind::Grub) => return "🥾 Updating GRUB bootloader".to_string(),
+        Some(LineKind::SystemdBoot) => return "🥾 Updating systemd-boot".to_string(),
+        Some(LineKind::Warning) => return format!("⚠ {}", line),
+        Some(LineKind::Error) => return format!("✗ {}", line),
+
+        // Nix evaluation trace: shorten long ones
+        Some(LineKind::Trace) if line.chars().count() > 100 => {
             let truncated: String = line.chars().take(97).collect();
             return format!("… {}", truncated);
         }
+        Some(LineKind::Trace) | None => {}
     }
 
     line.to_string()
@@ -0,0 +1,179 @@
+//! Connection check for the Fetching phase.
+//!
+//! NetworkManager's `Metered` property says whether the primary connection
+//! costs money per byte; the receive counters in `/proc/net/dev` give the
+//! actual download rate a few seconds into fetching. Together with the
+//! download size nix announces ("these N paths will be fetched (X MiB
+//! download, …)") that is enough to warn before a big download runs over
+//! a phone hotspot or a slow link.
+
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// How long to measure the download rate before judging it
+const SAMPLE_SECS: u64 = 5;
+/// A fetch projected to take longer than this is "slow"
+const SLOW_ETA_SECS: f64 = 600.0;
+
+const MIB: f64 = 1024.0 * 1024.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reason {
+    Metered,
+    Slow,
+}
+
+/// What the running build knows about the connection
+#[derive(Default)]
+pub struct NetworkWatch {
+    metered: Option<bool>,
+    metered_rx: Option<mpsc::Receiver<bool>>,
+    /// Announced download size
+    pub download_mib: Option<f64>,
+    /// (taken at, received bytes) when fetching started
+    sample: Option<(Instant, u64)>,
+    /// Measured download rate in bytes per second
+    pub rate: Option<f64>,
+    /// Warned once for this build already
+    pub warned: bool,
+}
+
+impl NetworkWatch {
+    /// Ask NetworkManager in the background; the build doesn't wait for it
+    pub fn start() -> Self {
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(is_metered());
+        });
+        Self {
+            metered_rx: Some(rx),
+            ..Self::default()
+        }
+    }
+
+    /// Called for every "paths will be fetched" line
+    pub fn on_fetch_plan(&mut self, line: &str) {
+        if let Some(mib) = parse_download_mib(line) {
+            *self.download_mib.get_or_insert(0.0) += mib;
+        }
+        if self.sample.is_none() {
+            self.sample = rx_bytes().map(|b| (Instant::now(), b));
+        }
+    }
+
+    /// Pick up the metered answer and finish the rate sample
+    pub fn tick(&mut self) {
+        if let Some(rx) = &self.metered_rx {
+            if let Ok(metered) = rx.try_recv() {
+                self.metered = Some(metered);
+                self.metered_rx = None;
+            }
+        }
+        if let (Some((at, start)), None) = (self.sample, self.rate) {
+            let elapsed = at.elapsed();
+            if elapsed >= Duration::from_secs(SAMPLE_SECS) {
+                if let Some(now) = rx_bytes() {
+                    self.rate = Some(now.saturating_sub(start) as f64 / elapsed.as_secs_f64());
+                }
+            }
+        }
+    }
+
+    /// Projected time for the whole download at the measured rate
+    pub fn eta(&self) -> Option<Duration> {
+        let (mib, rate) = (self.download_mib?, self.rate?);
+        (rate > 0.0).then(|| Duration::from_secs_f64(mib * MIB / rate))
+    }
+
+    /// Why to warn now, if at all (once per build)
+    pub fn warning(&self) -> Option<Reason> {
+        if self.warned || self.download_mib.is_none_or(|mib| mib <= 0.0) {
+            return None;
+        }
+        if self.metered == Some(true) {
+            return Some(Reason::Metered);
+        }
+        if self.rate.is_some_and(|r| r <= 0.0)
+            || self
+                .eta()
+                .is_some_and(|eta| eta.as_secs_f64() > SLOW_ETA_SECS)
+        {
+            return Some(Reason::Slow);
+        }
+        None
+    }
+}
+
+/// NetworkManager's global `Metered`: 1 = yes, 3 = guessed yes
+fn is_metered() -> bool {
+    crate::nix::storage::output_with_timeout(
+        "busctl",
+        &[
+            "--system",
+            "get-property",
+            "org.freedesktop.NetworkManager",
+            "/org/freedesktop/NetworkManager",
+            "org.freedesktop.NetworkManager",
+            "Metered",
+        ],
+        3,
+    )
+    .filter(|o| o.status.success())
+    .is_some_and(|o| {
+        // "u 1"
+        let text = String::from_utf8_lossy(&o.stdout);
+        matches!(text.split_whitespace().nth(1), Some("1") | Some("3"))
+    })
+}
+
+/// Bytes received on all interfaces but loopback
+fn rx_bytes() -> Option<u64> {
+    let content = std::fs::read_to_string("/proc/net/dev").ok()?;
+    Some(sum_rx_bytes(&content))
+}
+
+fn sum_rx_bytes(proc_net_dev: &str) -> u64 {
+    proc_net_dev
+        .lines()
+        .filter_map(|l| l.split_once(':'))
+        .filter(|(iface, _)| iface.trim() != "lo")
+        .filter_map(|(_, counters)| counters.split_whitespace().next()?.parse::<u64>().ok())
+        .sum()
+}
+
+/// "these 12 paths will be fetched (45.67 MiB download, 200.10 MiB unpacked):"
+pub fn parse_download_mib(line: &str) -> Option<f64> {
+    let before = &line[..line.find(" download")?];
+    let mut words = before.rsplit([' ', '(']);
+    let unit = words.next()?;
+    let value: f64 = words.next()?.parse().ok()?;
+    let factor = match unit {
+        "KiB" => 1.0 / 1024.0,
+        "MiB" => 1.0,
+        "GiB" => 1024.0,
+        _ => return None,
+    };
+    Some(value * factor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_download_size_and_rx_counters() {
+        let line = "these 12 paths will be fetched (45.50 MiB download, 200.10 MiB unpacked):";
+        assert_eq!(parse_download_mib(line), Some(45.5));
+        assert_eq!(
+            parse_download_mib("these 3 paths will be fetched (1.5 GiB download, 4 GiB unpacked):"),
+            Some(1536.0)
+        );
+        assert_eq!(
+            parse_download_mib("these 3 derivations will be built:"),
+            None
+        );
+
+        let dev = "Inter-|   Receive\n face |bytes    packets\n    lo: 900 1 0\n  eth0: 1000 10 0\n wlan0: 24 2 0\n";
+        assert_eq!(sum_rx_bytes(dev), 1024);
+    }
+}
@@ -0,0 +1,118 @@
+//! Pre-activation preview: after a `build`, `switch-to-configuration
+//! dry-activate` of the built system lists the units a switch would stop,
+//! restart, reload or start, shown on top of the Changes tab.
+
+use std::path::Path;
+
+/// Timeout for dry-activate (it compares unit files, no building)
+const TIMEOUT_SECS: u64 = 60;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DryActivation {
+    pub stop: Vec<String>,
+    pub restart: Vec<String>,
+    pub reload: Vec<String>,
+    pub start: Vec<String>,
+    /// Changed units that are left running (`X-StopIfChanged=false` etc.)
+    pub kept: Vec<String>,
+    pub restart_systemd: bool,
+}
+
+impl DryActivation {
+    pub fn is_empty(&self) -> bool {
+        self.stop.is_empty()
+            && self.restart.is_empty()
+            && self.reload.is_empty()
+            && self.start.is_empty()
+            && self.kept.is_empty()
+            && !self.restart_systemd
+    }
+}
+
+/// The system `./result` points to after `nixos-rebuild build`
+pub fn built_system() -> Option<String> {
+    let path = std::fs::canonicalize("result").ok()?;
+    path.join("bin/switch-to-configuration")
+        .exists()
+        .then(|| path.to_string_lossy().to_string())
+}
+
+/// Run dry-activate of `system` (`sudo -n`: runs in the background, so it
+/// can't prompt). Errors are the first line of its output.
+pub fn dry_activate(system: &str) -> Result<DryActivation, String> {
+    let program = Path::new(system).join("bin/switch-to-configuration");
+    let program = program.to_string_lossy();
+    let output = crate::nix::storage::output_with_timeout(
+        "sudo",
+        &["-n", &program, "dry-activate"],
+        TIMEOUT_SECS,
+    )
+    .ok_or_else(|| "switch-to-configuration timed out".to_string())?;
+    let text = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    if !output.status.success() {
+        return Err(text
+            .lines()
+            .map(str::trim)
+            .find(|l| !l.is_empty())
+            .unwrap_or("dry-activate failed")
+            .to_string());
+    }
+    Ok(parse(&text))
+}
+
+/// "would <verb> the following units: a.service, b.socket" lines
+pub fn parse(text: &str) -> DryActivation {
+    let mut result = DryActivation::default();
+    for line in text.lines().map(str::trim) {
+        if line == "would restart systemd" {
+            result.restart_systemd = true;
+            continue;
+        }
+        let Some((head, units)) = line.split_once(" units: ") else {
+            continue;
+        };
+        let list = match head {
+            "would stop the following" => &mut result.stop,
+            "would NOT stop the following changed" => &mut result.kept,
+            "would restart the following" => &mut result.restart,
+            "would reload the following" => &mut result.reload,
+            "would start the following" => &mut result.start,
+            _ => continue,
+        };
+        list.extend(
+            units
+                .split(',')
+                .map(str::trim)
+                .filter(|u| !u.is_empty())
+                .map(String::from),
+        );
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let out = "would stop the following units: old.service\n\
+                   would NOT stop the following changed units: sshd.service\n\
+                   would activate the configuration...\n\
+                   would restart systemd\n\
+                   would restart the following units: nginx.service, postgresql.service\n\
+                   would start the following units: new.service, new.socket\n";
+        let dry = parse(out);
+        assert_eq!(dry.stop, vec!["old.service"]);
+        assert_eq!(dry.kept, vec!["sshd.service"]);
+        assert_eq!(dry.restart, vec!["nginx.service", "postgresql.service"]);
+        assert!(dry.reload.is_empty());
+        assert_eq!(dry.start, vec!["new.service", "new.socket"]);
+        assert!(dry.restart_systemd);
+        assert!(parse("would activate the configuration...\n").is_empty());
+    }
+}
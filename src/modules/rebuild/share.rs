@@ -0,0 +1,114 @@
+//! Getting a build log out of nixmate: a file, the error block on the
+//! clipboard, or a paste service link for chat and issue trackers.
+
+use super::{LogLevel, LogLine};
+use anyhow::{bail, Context, Result};
+use std::ops::Range;
+use std::path::PathBuf;
+use std::time::Duration;
+
+const UPLOAD_TIMEOUT_SECS: u64 = 30;
+
+/// Write the raw log to `~/nixmate-rebuild-<mode>-<timestamp>.log`
+pub fn export(lines: &[LogLine], mode: &str) -> Result<PathBuf> {
+    let name = format!(
+        "nixmate-rebuild-{}-{}.log",
+        mode,
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    );
+    let path = dirs::home_dir().context("no home directory")?.join(name);
+    std::fs::write(&path, raw_text(lines))
+        .with_context(|| format!("cannot write {}", path.display()))?;
+    Ok(path)
+}
+
+/// The unmodified output, one line each
+pub fn raw_text(lines: &[LogLine]) -> String {
+    let mut text = String::new();
+    for line in lines {
+        text.push_str(&line.raw);
+        text.push('\n');
+    }
+    text
+}
+
+/// The error block to copy: the one at `from` (the top of the view) or
+/// the first below it, else the last one above it. An error runs on
+/// through the indented lines nix prints below it ("last 10 log lines:",
+/// the builder output, "For full logs, run …") and any errors right after.
+pub fn error_block(lines: &[LogLine], from: usize) -> Option<Range<usize>> {
+    let mut blocks: Vec<Range<usize>> = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        if lines[i].level != LogLevel::Error {
+            i += 1;
+            continue;
+        }
+        let len = lines[i + 1..].iter().take_while(|l| continues(l)).count();
+        blocks.push(i..i + 1 + len);
+        i += 1 + len;
+    }
+    blocks
+        .iter()
+        .find(|b| b.end > from)
+        .or(blocks.last())
+        .cloned()
+}
+
+fn continues(line: &LogLine) -> bool {
+    line.level == LogLevel::Error || line.raw.starts_with([' ', '\t', '>'])
+}
+
+/// POST `text` as the request body; the service answers with the paste's
+/// URL. Blocks — run in a background thread.
+pub fn upload(url: &str, text: &str) -> Result<String> {
+    let agent = ureq::AgentBuilder::new()
+        .timeout(Duration::from_secs(UPLOAD_TIMEOUT_SECS))
+        .build();
+    let body = agent
+        .post(url)
+        .set("Content-Type", "text/plain; charset=utf-8")
+        .send_string(text)
+        .with_context(|| format!("upload to {} failed", url))?
+        .into_string()?;
+    let link = body.trim();
+    if !link.starts_with("http") {
+        bail!("unexpected answer from {}: {}", url, link);
+    }
+    Ok(link.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log(text: &str) -> Vec<LogLine> {
+        text.lines()
+            .map(|l| LogLine {
+                text: l.to_string(),
+                raw: l.to_string(),
+                level: super::super::classify_line(l),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_error_block() {
+        let lines = log("building '/nix/store/aaa-foo.drv'...\n\
+             error: builder for '/nix/store/aaa-foo.drv' failed with exit code 1;\n       \
+             last 2 log lines:\n       \
+             > make: *** No rule to make target\n       \
+             For full logs, run 'nix log /nix/store/aaa-foo.drv'.\n\
+             error: 1 dependencies of derivation '/nix/store/bbb-system.drv' failed to build\n\
+             building '/nix/store/ccc-bar.drv'...\n\
+             error: cannot connect to socket");
+
+        assert_eq!(error_block(&lines, 0), Some(1..6));
+        // Scrolled into the middle of the first block: still all of it
+        assert_eq!(error_block(&lines, 3), Some(1..6));
+        assert_eq!(error_block(&lines, 6), Some(7..8));
+        // Nothing below the view: the last block above it
+        assert_eq!(error_block(&lines, 8), Some(7..8));
+        assert_eq!(error_block(&log("building\ndone"), 0), None);
+    }
+}
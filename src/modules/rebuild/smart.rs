@@ -0,0 +1,277 @@
+//! Smart view of the build log (`v` in the Log tab)
+//!
+//! Lines are grouped into one section per derivation: its `building '…'`
+//! line plus everything printed with its `name> ` prefix (`-L` /
+//! `print-build-logs`), wherever parallel builds interleaved them. A
+//! section is one row showing its latest build phase until it's expanded;
+//! sections with a warning or error are always expanded. Runs of progress
+//! lines — cache downloads, derivations that printed nothing — become a
+//! single row that keeps updating.
+
+use super::{LogLevel, LogLine};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Progress {
+    /// `copying path …` / `downloading …`
+    Fetch,
+    /// `building '…'` without output of its own
+    Build,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Row {
+    /// A line outside any section
+    Line(usize),
+    /// `count` progress lines, shown as the newest (`last`)
+    Progress {
+        kind: Progress,
+        count: usize,
+        last: usize,
+    },
+    /// A derivation's section header
+    Section {
+        name: String,
+        /// Lines in the section
+        lines: usize,
+        /// Latest phase line (`unpacking sources`, `installing`, …)
+        phase: Option<usize>,
+        expanded: bool,
+        /// Has a warning or error, can't be collapsed
+        pinned: bool,
+    },
+    /// A line inside an expanded section
+    Member(usize),
+}
+
+impl Row {
+    /// The log line this row stands for (the first one for a section)
+    pub fn line(&self, grouping: &Grouping) -> Option<usize> {
+        match self {
+            Row::Line(i) | Row::Member(i) => Some(*i),
+            Row::Progress { last, .. } => Some(*last),
+            Row::Section { name, .. } => grouping
+                .index
+                .get(name)
+                .and_then(|&s| grouping.sections[s].first().copied()),
+        }
+    }
+}
+
+/// Sections (line indices per derivation) and each name's position
+pub struct Grouping {
+    pub sections: Vec<Vec<usize>>,
+    pub index: HashMap<String, usize>,
+}
+
+/// Derivation name of a `building '/nix/store/<hash>-<name>.drv'...` line,
+/// as `-L` prints it: without hash, `.drv` and version
+fn building(raw: &str) -> Option<&str> {
+    let rest = raw.trim_start().strip_prefix("building '/nix/store/")?;
+    let drv = rest.split('\'').next()?.strip_suffix(".drv")?;
+    let name = drv.split_once('-').map_or(drv, |(_, n)| n);
+    Some(pname(name))
+}
+
+/// `hello-2.12.1` → `hello`: the name ends at the first `-` that is
+/// followed by something other than a letter
+fn pname(name: &str) -> &str {
+    let bytes = name.as_bytes();
+    (0..bytes.len())
+        .find(|&i| bytes[i] == b'-' && bytes.get(i + 1).is_some_and(|b| !b.is_ascii_alphabetic()))
+        .map_or(name, |i| &name[..i])
+}
+
+/// The `name` of a `name> output` line
+pub fn prefix(raw: &str) -> Option<&str> {
+    let (name, _) = raw.split_once("> ").or_else(|| {
+        // Empty output lines come as `name>`
+        raw.strip_suffix('>').map(|n| (n, ""))
+    })?;
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_.+".contains(c));
+    valid.then_some(name)
+}
+
+fn is_fetch(raw: &str) -> bool {
+    let lower = raw.trim_start();
+    lower.starts_with("copying path '")
+        || lower.starts_with("fetching path '")
+        || lower.starts_with("downloading '")
+}
+
+fn is_phase(text: &str) -> bool {
+    const PHASES: &[&str] = &[
+        "unpacking sources",
+        "patching sources",
+        "updateAutotoolsGnuConfigScriptsPhase",
+        "configuring",
+        "building",
+        "running tests",
+        "checking",
+        "installing",
+        "post-installation fixup",
+        "shrinking RPATHs",
+        "stripping",
+        "patching script interpreter",
+    ];
+    PHASES.iter().any(|p| text.starts_with(p))
+}
+
+fn is_problem(line: &LogLine) -> bool {
+    matches!(line.level, LogLevel::Error | LogLevel::Warning)
+}
+
+pub fn group(lines: &[LogLine]) -> Grouping {
+    let mut grouping = Grouping {
+        sections: Vec::new(),
+        index: HashMap::new(),
+    };
+    for (i, line) in lines.iter().enumerate() {
+        let Some(name) = building(&line.raw).or_else(|| prefix(&line.raw)) else {
+            continue;
+        };
+        let s = *grouping.index.entry(name.to_string()).or_insert_with(|| {
+            grouping.sections.push(Vec::new());
+            grouping.sections.len() - 1
+        });
+        grouping.sections[s].push(i);
+    }
+    grouping
+}
+
+/// Rows to show. `toggled` are the sections the user expanded.
+pub fn rows(lines: &[LogLine], grouping: &Grouping, toggled: &[String]) -> Vec<Row> {
+    let mut rows: Vec<Row> = Vec::new();
+    let mut placed: Vec<bool> = vec![false; grouping.sections.len()];
+
+    for (i, line) in lines.iter().enumerate() {
+        let name = building(&line.raw).or_else(|| prefix(&line.raw));
+        let section = name.and_then(|n| grouping.index.get(n).map(|&s| (n, s)));
+
+        let progress = match section {
+            // A derivation that printed nothing is just build progress
+            Some((_, s)) if grouping.sections[s].len() == 1 => Some(Progress::Build),
+            Some(_) => None,
+            None if is_fetch(&line.raw) => Some(Progress::Fetch),
+            None => None,
+        };
+        if let Some(kind) = progress {
+            match rows.last_mut() {
+                Some(Row::Progress {
+                    kind: k,
+                    count,
+                    last,
+                }) if *k == kind => {
+                    *count += 1;
+                    *last = i;
+                }
+                _ => rows.push(Row::Progress {
+                    kind,
+                    count: 1,
+                    last: i,
+                }),
+            }
+            continue;
+        }
+
+        let Some((name, s)) = section else {
+            rows.push(Row::Line(i));
+            continue;
+        };
+        if placed[s] {
+            continue;
+        }
+        placed[s] = true;
+        let members = &grouping.sections[s];
+        let pinned = members.iter().any(|&m| is_problem(&lines[m]));
+        let expanded = pinned || toggled.iter().any(|t| t == name);
+        let phase = members.iter().rev().copied().find(|&m| {
+            prefix(&lines[m].raw)
+                .and_then(|p| lines[m].raw.get(p.len() + 2..))
+                .is_some_and(is_phase)
+        });
+        rows.push(Row::Section {
+            name: name.to_string(),
+            lines: members.len(),
+            phase,
+            expanded,
+            pinned,
+        });
+        if expanded {
+            rows.extend(members.iter().map(|&m| Row::Member(m)));
+        }
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log(text: &str) -> Vec<LogLine> {
+        text.lines()
+            .map(|l| LogLine {
+                text: l.to_string(),
+                raw: l.to_string(),
+                level: super::super::classify_line(l),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_smart_rows() {
+        let lines = log(
+            "copying path '/nix/store/aaa-glibc-2.39' from 'https://cache.nixos.org'...\n\
+             copying path '/nix/store/bbb-bash-5.2' from 'https://cache.nixos.org'...\n\
+             building '/nix/store/ccc-hello-2.12.1.drv'...\n\
+             building '/nix/store/ddd-etc.drv'...\n\
+             building '/nix/store/eee-unit-foo.service.drv'...\n\
+             hello> unpacking sources\n\
+             hello> installing\n\
+             building '/nix/store/fff-broken-1.0.drv'...\n\
+             hello> done\n\
+             broken> warning: deprecated option\n\
+             activating the configuration...",
+        );
+        let grouping = group(&lines);
+        assert_eq!(pname("hello-2.12.1"), "hello");
+        assert_eq!(pname("unit-foo.service"), "unit-foo.service");
+
+        let rows = rows(&lines, &grouping, &[]);
+        assert_eq!(
+            rows,
+            vec![
+                Row::Progress {
+                    kind: Progress::Fetch,
+                    count: 2,
+                    last: 1
+                },
+                Row::Section {
+                    name: "hello".into(),
+                    lines: 4,
+                    phase: Some(6),
+                    expanded: false,
+                    pinned: false
+                },
+                Row::Progress {
+                    kind: Progress::Build,
+                    count: 2,
+                    last: 4
+                },
+                Row::Section {
+                    name: "broken".into(),
+                    lines: 2,
+                    phase: None,
+                    expanded: true,
+                    pinned: true
+                },
+                Row::Member(7),
+                Row::Member(9),
+                Row::Line(10),
+            ]
+        );
+    }
+}
@@ -0,0 +1,112 @@
+//! Specialisations (`specialisation.<name>` in the configuration).
+//!
+//! The names come from the configuration itself (evaluated, so a new
+//! specialisation shows up before its first build) and from
+//! `/run/current-system/specialisation/`, which also covers setups the
+//! evaluation doesn't reach. `nixos-rebuild switch|test --specialisation`
+//! then activates the chosen one.
+
+use super::RebuildMode;
+use crate::nix::storage::output_with_timeout;
+
+const SPECIALISATION_DIR: &str = "/run/current-system/specialisation";
+
+/// Evaluation reads the whole configuration; don't wait forever
+const EVAL_TIMEOUT_SECS: u64 = 60;
+
+/// Names of all specialisations, sorted
+pub fn detect(
+    uses_flakes: bool,
+    flake_path: Option<&str>,
+    nixos_host: Option<&str>,
+) -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(SPECIALISATION_DIR)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|e| e.file_name().to_string_lossy().into_owned())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let output = if uses_flakes {
+        let attr = format!(
+            "{}#nixosConfigurations.\"{}\".config.specialisation",
+            flake_path.unwrap_or("/etc/nixos"),
+            crate::nix::detect::nixos_host(nixos_host)
+        );
+        output_with_timeout(
+            "nix",
+            &[
+                "--extra-experimental-features",
+                "nix-command flakes",
+                "eval",
+                "--json",
+                &attr,
+                "--apply",
+                "builtins.attrNames",
+            ],
+            EVAL_TIMEOUT_SECS,
+        )
+    } else {
+        output_with_timeout(
+            "nix-instantiate",
+            &[
+                "--eval",
+                "--json",
+                "--strict",
+                "-E",
+                "builtins.attrNames (import <nixpkgs/nixos> {}).config.specialisation",
+            ],
+            EVAL_TIMEOUT_SECS,
+        )
+    };
+    if let Some(o) = output.filter(|o| o.status.success()) {
+        names.extend(serde_json::from_slice::<Vec<String>>(&o.stdout).unwrap_or_default());
+    }
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Only activating modes can switch into a specialisation
+pub fn applies(mode: RebuildMode) -> bool {
+    matches!(mode, RebuildMode::Switch | RebuildMode::Test)
+}
+
+/// `nixos-rebuild` flags for `name` in `mode`
+pub fn args(mode: RebuildMode, name: Option<&str>) -> Vec<String> {
+    match name {
+        Some(name) if applies(mode) => vec!["--specialisation".into(), name.into()],
+        _ => Vec::new(),
+    }
+}
+
+/// Next choice for Tab in the confirm popup: default, then each name
+pub fn cycle(names: &[String], current: Option<&str>) -> Option<String> {
+    let next = match current.and_then(|c| names.iter().position(|n| n == c)) {
+        Some(i) => i + 1,
+        None => 0,
+    };
+    names.get(next).cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_specialisation_args_and_cycle() {
+        assert_eq!(
+            args(RebuildMode::Switch, Some("work")),
+            vec!["--specialisation", "work"]
+        );
+        assert!(args(RebuildMode::Boot, Some("work")).is_empty());
+        assert!(args(RebuildMode::Test, None).is_empty());
+
+        let names = vec!["gaming".to_string(), "work".to_string()];
+        assert_eq!(cycle(&names, None).as_deref(), Some("gaming"));
+        assert_eq!(cycle(&names, Some("gaming")).as_deref(), Some("work"));
+        assert_eq!(cycle(&names, Some("work")), None);
+    }
+}
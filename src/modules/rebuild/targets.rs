@@ -0,0 +1,216 @@
+//! Development builds: a package, dev shell or check of a flake, or the
+//! whole `nix flake check`, supervised by the same dashboard as a rebuild.
+//!
+//! The attributes come from `nix flake show --json` for this machine's
+//! system. The flake is the one in the working directory if there is one
+//! (the project being worked on), else the system flake. Builds run as
+//! one backend step (`nix build --no-link --print-out-paths`), so they get
+//! the phase boxes, log and attribute progress of a backend build, without
+//! sudo and without activation.
+//!
+//! The same `nix flake show` lists the flake's `nixosConfigurations` for
+//! the host picker (`H`), so multi-host flakes build the chosen machine.
+
+use super::backend::Step;
+use serde_json::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TargetKind {
+    /// `nix flake check`: every check, plus evaluating all outputs
+    FlakeCheck,
+    Check,
+    Package,
+    DevShell,
+}
+
+impl TargetKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TargetKind::FlakeCheck => "flake check",
+            TargetKind::Check => "check",
+            TargetKind::Package => "package",
+            TargetKind::DevShell => "devShell",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlakeTarget {
+    pub kind: TargetKind,
+    /// Full attribute path ("packages.x86_64-linux.default"), empty for
+    /// `nix flake check`
+    pub attr: String,
+    /// Short name shown in the picker and the dashboard
+    pub name: String,
+    pub description: Option<String>,
+}
+
+/// Flake to offer targets of: the working directory's, else the system's
+pub fn flake_dir(system_flake: Option<&str>) -> String {
+    std::env::current_dir()
+        .ok()
+        .filter(|d| d.join("flake.nix").is_file())
+        .map(|d| d.to_string_lossy().into_owned())
+        .or_else(|| system_flake.map(str::to_string))
+        .unwrap_or_else(|| "/etc/nixos".to_string())
+}
+
+/// Nix system name of this machine ("x86_64-linux")
+pub fn current_system() -> String {
+    format!("{}-{}", std::env::consts::ARCH, std::env::consts::OS)
+}
+
+/// `nix flake show --json` (evaluates the flake — background thread)
+pub fn load(flake: &str) -> Result<Vec<FlakeTarget>, String> {
+    Ok(parse_show(&show_json(flake)?, &current_system()))
+}
+
+/// The flake's `nixosConfigurations` names (background thread)
+pub fn load_hosts(flake: &str) -> Result<Vec<String>, String> {
+    Ok(parse_hosts(&show_json(flake)?))
+}
+
+fn show_json(flake: &str) -> Result<Value, String> {
+    let output = crate::nix::storage::output_with_timeout(
+        "nix",
+        &[
+            "--extra-experimental-features",
+            "nix-command flakes",
+            "flake",
+            "show",
+            "--json",
+            flake,
+        ],
+        120,
+    )
+    .ok_or_else(|| "nix flake show: timeout".to_string())?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(stderr
+            .lines()
+            .rfind(|l| l.contains("error"))
+            .unwrap_or("nix flake show failed")
+            .trim()
+            .to_string());
+    }
+    serde_json::from_slice(&output.stdout).map_err(|e| e.to_string())
+}
+
+/// Keys of `nixosConfigurations`, sorted
+fn parse_hosts(json: &Value) -> Vec<String> {
+    let mut hosts: Vec<String> = json
+        .get("nixosConfigurations")
+        .and_then(Value::as_object)
+        .map(|o| o.keys().cloned().collect())
+        .unwrap_or_default();
+    hosts.sort();
+    hosts
+}
+
+/// Targets of `system` in `nix flake show --json` output, `nix flake check`
+/// first
+fn parse_show(json: &Value, system: &str) -> Vec<FlakeTarget> {
+    let mut targets = vec![FlakeTarget {
+        kind: TargetKind::FlakeCheck,
+        attr: String::new(),
+        name: "nix flake check".to_string(),
+        description: None,
+    }];
+    for (output, kind) in [
+        ("checks", TargetKind::Check),
+        ("packages", TargetKind::Package),
+        ("devShells", TargetKind::DevShell),
+    ] {
+        let Some(attrs) = json
+            .get(output)
+            .and_then(|o| o.get(system))
+            .and_then(Value::as_object)
+        else {
+            continue;
+        };
+        for (name, info) in attrs {
+            if info.get("type").and_then(Value::as_str) != Some("derivation") {
+                continue;
+            }
+            targets.push(FlakeTarget {
+                kind,
+                attr: format!("{}.{}.{}", output, system, name),
+                name: name.clone(),
+                description: info
+                    .get("description")
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+            });
+        }
+    }
+    targets
+}
+
+/// The build of `target` in `flake` as a backend step
+pub fn step(flake: &str, target: &FlakeTarget) -> Step {
+    let mut args: Vec<String> = ["--extra-experimental-features", "nix-command flakes"]
+        .map(String::from)
+        .to_vec();
+    match target.kind {
+        TargetKind::FlakeCheck => {
+            args.extend(["flake", "check", "--keep-going", flake].map(String::from));
+        }
+        _ => {
+            args.extend(["build", "--no-link", "--print-out-paths"].map(String::from));
+            args.push(format!("{}#{}", flake, target.attr));
+        }
+    }
+    Step {
+        attrs: vec![0],
+        program: "nix",
+        args,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_flake_show() {
+        let json: Value = serde_json::from_str(
+            r#"{
+                "checks": {"x86_64-linux": {"fmt": {"name": "fmt-check", "type": "derivation"}}},
+                "devShells": {"x86_64-linux": {"default": {"name": "nix-shell", "type": "derivation"}}},
+                "packages": {
+                    "aarch64-linux": {"default": {"type": "derivation"}},
+                    "x86_64-linux": {"tool": {"description": "A tool", "name": "tool-1.0", "type": "derivation"}}
+                },
+                "nixosConfigurations": {
+                    "laptop": {"type": "nixos-configuration"},
+                    "build-box": {"type": "nixos-configuration"}
+                }
+            }"#,
+        )
+        .unwrap();
+        let targets = parse_show(&json, "x86_64-linux");
+        let attrs: Vec<&str> = targets.iter().map(|t| t.attr.as_str()).collect();
+        assert_eq!(
+            attrs,
+            vec![
+                "",
+                "checks.x86_64-linux.fmt",
+                "packages.x86_64-linux.tool",
+                "devShells.x86_64-linux.default"
+            ]
+        );
+        assert_eq!(targets[2].description.as_deref(), Some("A tool"));
+        assert_eq!(parse_hosts(&json), vec!["build-box", "laptop"]);
+
+        let build = step("/src/tool", &targets[2]);
+        assert_eq!(
+            build.args.last().map(String::as_str),
+            Some("/src/tool#packages.x86_64-linux.tool")
+        );
+        assert!(step("/src/tool", &targets[0]).args.ends_with(&[
+            "check".into(),
+            "--keep-going".into(),
+            "/src/tool".into()
+        ]));
+    }
+}
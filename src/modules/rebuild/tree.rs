@@ -0,0 +1,350 @@
+//! Dependency tree of the build (`v` on the Dashboard), like
+//! nix-output-monitor draws it
+//!
+//! The derivations come from the log: "these N derivations will be built"
+//! lists them, `building '…'` starts one, `builder for '…' failed` and
+//! "dependencies of derivation '…' failed" fail it. Which one needs which
+//! is read once from `nix derivation show` of the planned derivations.
+//! Without `-L` nix doesn't print when a build finishes, so a derivation
+//! counts as done once something depending on it starts building (and all
+//! of them once the run succeeded). The critical path is the longest chain
+//! of unfinished derivations down from the top: what the build waits for.
+
+use super::LogLine;
+use std::collections::{HashMap, HashSet};
+use std::process::Command;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum State {
+    Queued,
+    Building,
+    Done,
+    Failed,
+}
+
+#[derive(Debug, Clone)]
+pub struct Node {
+    /// `hello-2.12.1` (store path without hash and `.drv`)
+    pub name: String,
+    /// Planned derivations this one needs
+    pub inputs: Vec<usize>,
+    pub state: State,
+}
+
+/// One derivation per row, below the first derivation that needs it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    pub node: usize,
+    pub depth: usize,
+    /// On the critical path
+    pub critical: bool,
+}
+
+pub struct Tree {
+    pub nodes: Vec<Node>,
+    /// Unfinished derivations on the longest chain below each node
+    remaining: Vec<usize>,
+}
+
+/// Basename (`<hash>-<name>.drv`) of the first `.drv` store path in a line
+fn drv(line: &str) -> Option<&str> {
+    let start = line.find("/nix/store/")? + "/nix/store/".len();
+    let rest = &line[start..];
+    let end = rest.find(".drv")? + ".drv".len();
+    let name = &rest[..end];
+    (!name.contains(['\'', ' ', '/'])).then_some(name)
+}
+
+fn display_name(basename: &str) -> String {
+    let name = basename.strip_suffix(".drv").unwrap_or(basename);
+    name.split_once('-').map_or(name, |(_, n)| n).to_string()
+}
+
+/// Derivations listed under "these N derivations will be built:", in order
+pub fn planned(lines: &[LogLine]) -> Vec<String> {
+    let mut drvs: Vec<String> = Vec::new();
+    let mut in_plan = false;
+    for line in lines {
+        let raw = line.raw.trim();
+        if raw.contains("will be built") {
+            in_plan = true;
+            continue;
+        }
+        match drv(raw) {
+            Some(name) if in_plan && raw.starts_with("/nix/store/") => {
+                if !drvs.iter().any(|d| d == name) {
+                    drvs.push(name.to_string());
+                }
+            }
+            _ => in_plan = false,
+        }
+    }
+    drvs
+}
+
+/// Inputs per derivation from `nix derivation show` JSON, as basenames.
+/// Newer nix nests the derivations under `derivations` and leaves out the
+/// store directory.
+pub fn parse_inputs(json: &str) -> Result<HashMap<String, Vec<String>>, String> {
+    let value: serde_json::Value = serde_json::from_str(json).map_err(|e| e.to_string())?;
+    let derivations = value
+        .get("derivations")
+        .unwrap_or(&value)
+        .as_object()
+        .ok_or("unexpected `nix derivation show` output")?;
+    let basename = |path: &str| path.rsplit('/').next().unwrap_or(path).to_string();
+    Ok(derivations
+        .iter()
+        .map(|(path, info)| {
+            let inputs = info
+                .get("inputDrvs")
+                .and_then(|i| i.as_object())
+                .map(|i| i.keys().map(|k| basename(k)).collect())
+                .unwrap_or_default();
+            (basename(path), inputs)
+        })
+        .collect())
+}
+
+/// Which planned derivation needs which. Blocking — run in background
+/// thread!
+pub fn load_inputs(drvs: &[String]) -> Result<HashMap<String, Vec<String>>, String> {
+    let output = Command::new("nix")
+        .args(["--extra-experimental-features", "nix-command"])
+        .args(["derivation", "show"])
+        .args(drvs.iter().map(|d| format!("/nix/store/{}", d)))
+        .stdin(std::process::Stdio::null())
+        .output()
+        .map_err(|e| format!("nix derivation show: {}", e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(stderr
+            .lines()
+            .map(str::trim)
+            .find(|l| l.starts_with("error:"))
+            .unwrap_or("nix derivation show failed")
+            .to_string());
+    }
+    parse_inputs(&String::from_utf8_lossy(&output.stdout))
+}
+
+impl Tree {
+    /// The tree as far as the log got. `succeeded`: the run finished
+    /// without error, everything was built.
+    pub fn build(
+        lines: &[LogLine],
+        inputs: &HashMap<String, Vec<String>>,
+        succeeded: bool,
+    ) -> Self {
+        let mut drvs = planned(lines);
+        // Builds the plan didn't list (a log piped in from the middle)
+        for line in lines {
+            if let Some(name) = line
+                .raw
+                .trim_start()
+                .strip_prefix("building '")
+                .and_then(drv)
+            {
+                if !drvs.iter().any(|d| d == name) {
+                    drvs.push(name.to_string());
+                }
+            }
+        }
+        let index: HashMap<&str, usize> = drvs
+            .iter()
+            .enumerate()
+            .map(|(i, d)| (d.as_str(), i))
+            .collect();
+        let mut nodes: Vec<Node> = drvs
+            .iter()
+            .map(|d| Node {
+                name: display_name(d),
+                inputs: inputs
+                    .get(d)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|i| index.get(i.as_str()).copied())
+                    .collect(),
+                state: if succeeded {
+                    State::Done
+                } else {
+                    State::Queued
+                },
+            })
+            .collect();
+
+        if !succeeded {
+            for line in lines {
+                let raw = line.raw.trim_start();
+                let Some(&i) = drv(raw).and_then(|d| index.get(d)) else {
+                    continue;
+                };
+                if raw.starts_with("building '") {
+                    nodes[i].state = State::Building;
+                    for input in nodes[i].inputs.clone() {
+                        if nodes[input].state != State::Failed {
+                            nodes[input].state = State::Done;
+                        }
+                    }
+                } else if raw.contains("error:")
+                    && (raw.contains("failed") || raw.contains("Cannot build"))
+                {
+                    nodes[i].state = State::Failed;
+                }
+            }
+        }
+
+        let mut tree = Self {
+            remaining: vec![0; nodes.len()],
+            nodes,
+        };
+        let mut known = vec![false; tree.nodes.len()];
+        for i in 0..tree.nodes.len() {
+            tree.measure(i, &mut known);
+        }
+        tree
+    }
+
+    fn measure(&mut self, i: usize, known: &mut [bool]) -> usize {
+        if known[i] {
+            return self.remaining[i];
+        }
+        // Marked first: a cycle (which nix wouldn't build) ends here
+        known[i] = true;
+        let below = self.nodes[i]
+            .inputs
+            .clone()
+            .into_iter()
+            .map(|input| self.measure(input, known))
+            .max()
+            .unwrap_or(0);
+        self.remaining[i] = match self.nodes[i].state {
+            State::Done => 0,
+            _ => below + 1,
+        };
+        self.remaining[i]
+    }
+
+    pub fn count(&self, state: State) -> usize {
+        self.nodes.iter().filter(|n| n.state == state).count()
+    }
+
+    /// Derivations nothing else in the build needs (the system itself)
+    fn roots(&self) -> Vec<usize> {
+        let needed: HashSet<usize> = self
+            .nodes
+            .iter()
+            .flat_map(|n| n.inputs.iter().copied())
+            .collect();
+        (0..self.nodes.len())
+            .filter(|i| !needed.contains(i))
+            .collect()
+    }
+
+    /// Longest chain of unfinished derivations, from the top down
+    pub fn critical_path(&self) -> Vec<usize> {
+        let mut path = Vec::new();
+        let mut next = self.roots().into_iter().max_by_key(|&i| self.remaining[i]);
+        while let Some(i) = next.filter(|&i| self.remaining[i] > 0) {
+            path.push(i);
+            next = self.nodes[i]
+                .inputs
+                .iter()
+                .copied()
+                .max_by_key(|&input| self.remaining[input]);
+        }
+        path
+    }
+
+    /// Rows to show: longest remaining chains first, finished derivations
+    /// without what's below them (all finished too)
+    pub fn rows(&self) -> Vec<Row> {
+        let critical: HashSet<usize> = self.critical_path().into_iter().collect();
+        let mut rows = Vec::new();
+        let mut shown = vec![false; self.nodes.len()];
+        let mut roots = self.roots();
+        roots.sort_by_key(|&i| std::cmp::Reverse(self.remaining[i]));
+        let mut stack: Vec<(usize, usize)> = roots.into_iter().rev().map(|i| (i, 0)).collect();
+        while let Some((i, depth)) = stack.pop() {
+            if shown[i] {
+                continue;
+            }
+            shown[i] = true;
+            rows.push(Row {
+                node: i,
+                depth,
+                critical: critical.contains(&i),
+            });
+            if self.nodes[i].state == State::Done {
+                continue;
+            }
+            let mut inputs = self.nodes[i].inputs.clone();
+            inputs.sort_by_key(|&input| std::cmp::Reverse(self.remaining[input]));
+            stack.extend(inputs.into_iter().rev().map(|input| (input, depth + 1)));
+        }
+        rows
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log(text: &str) -> Vec<LogLine> {
+        text.lines()
+            .map(|l| LogLine {
+                text: l.to_string(),
+                raw: l.to_string(),
+                level: super::super::classify_line(l),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_build_tree() {
+        let lines = log("these 4 derivations will be built:\n  \
+             /nix/store/aaa-nixos-system-host.drv\n  \
+             /nix/store/bbb-etc.drv\n  \
+             /nix/store/ccc-hello-2.12.1.drv\n  \
+             /nix/store/ddd-lib-1.0.drv\n\
+             these 2 paths will be fetched (1.2 MiB download):\n  \
+             /nix/store/eee-bash-5.2\n\
+             building '/nix/store/ddd-lib-1.0.drv'...\n\
+             building '/nix/store/ccc-hello-2.12.1.drv'...");
+        assert_eq!(planned(&lines).len(), 4);
+
+        let inputs = parse_inputs(
+            r#"{
+                "/nix/store/aaa-nixos-system-host.drv": {"inputDrvs": {
+                    "/nix/store/bbb-etc.drv": {}, "/nix/store/zzz-glibc.drv": {}}},
+                "/nix/store/bbb-etc.drv": {"inputDrvs": {"/nix/store/ccc-hello-2.12.1.drv": {}}},
+                "/nix/store/ccc-hello-2.12.1.drv": {"inputDrvs": {"/nix/store/ddd-lib-1.0.drv": {}}},
+                "/nix/store/ddd-lib-1.0.drv": {"inputDrvs": {}}
+            }"#,
+        )
+        .unwrap();
+        let tree = Tree::build(&lines, &inputs, false);
+        let states: Vec<State> = tree.nodes.iter().map(|n| n.state).collect();
+        assert_eq!(
+            states,
+            vec![State::Queued, State::Queued, State::Building, State::Done]
+        );
+        assert_eq!(tree.nodes[2].name, "hello-2.12.1");
+        assert_eq!(tree.critical_path(), vec![0, 1, 2]);
+
+        let rows = tree.rows();
+        let order: Vec<(usize, usize)> = rows.iter().map(|r| (r.node, r.depth)).collect();
+        assert_eq!(order, vec![(0, 0), (1, 1), (2, 2), (3, 3)]);
+        assert!(!rows[3].critical);
+
+        let mut failed = lines.clone();
+        failed.extend(log(
+            "error: builder for '/nix/store/ccc-hello-2.12.1.drv' failed with exit code 1",
+        ));
+        assert_eq!(
+            Tree::build(&failed, &inputs, false).nodes[2].state,
+            State::Failed
+        );
+        assert_eq!(Tree::build(&lines, &inputs, true).count(State::Done), 4);
+    }
+}
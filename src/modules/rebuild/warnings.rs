@@ -0,0 +1,107 @@
+//! Warnings of a build, collected once it's finished
+//!
+//! Evaluation warnings scroll by long before the build is done, and a
+//! deprecated option warns once per evaluation of every module that sets
+//! it. The Done dashboard and the Changes tab show each message once, with
+//! how often it came up; renamed or obsolete options are linked to the
+//! Options Explorer.
+
+use super::{LogLevel, LogLine};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+static OPTION: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"option (?:definition )?[`‘]([^'’`\s]+)['’]").unwrap());
+static RENAMED_TO: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"renamed to [`‘]([^'’`\s]+)['’]").unwrap());
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildWarning {
+    /// The message without `trace:` / `warning:` prefixes
+    pub message: String,
+    /// First line it appeared as
+    pub raw: String,
+    /// How many times it came up
+    pub count: usize,
+    /// Option to look at: the new name of a renamed option, else the one
+    /// the message is about
+    pub option: Option<String>,
+}
+
+/// Warning lines of a log, deduplicated, in the order they first appeared
+pub fn collect(lines: &[LogLine]) -> Vec<BuildWarning> {
+    let mut warnings: Vec<BuildWarning> = Vec::new();
+    for line in lines.iter().filter(|l| l.level == LogLevel::Warning) {
+        let message = message(&line.raw);
+        if let Some(existing) = warnings.iter_mut().find(|w| w.message == message) {
+            existing.count += 1;
+            continue;
+        }
+        warnings.push(BuildWarning {
+            option: option(&message),
+            message,
+            raw: line.raw.clone(),
+            count: 1,
+        });
+    }
+    warnings
+}
+
+/// `trace: evaluation warning: text` → `text`
+fn message(raw: &str) -> String {
+    let mut rest = raw.trim();
+    loop {
+        let lower = rest.to_lowercase();
+        let prefix = ["trace:", "evaluation warning:", "warning:"]
+            .into_iter()
+            .find(|p| lower.starts_with(p));
+        match prefix {
+            Some(p) => rest = rest[p.len()..].trim_start(),
+            None => return rest.to_string(),
+        }
+    }
+}
+
+fn option(message: &str) -> Option<String> {
+    RENAMED_TO
+        .captures(message)
+        .or_else(|| OPTION.captures(message))
+        .map(|cap| cap[1].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(raw: &str, level: LogLevel) -> LogLine {
+        LogLine {
+            text: raw.to_string(),
+            raw: raw.to_string(),
+            level,
+        }
+    }
+
+    #[test]
+    fn test_collect_warnings() {
+        let renamed = "trace: warning: The option `services.xserver.layout' defined in `/etc/nixos/configuration.nix' has been renamed to `services.xserver.xkb.layout'.";
+        let lines = vec![
+            line("warning: Git tree '/etc/nixos' is dirty", LogLevel::Warning),
+            line(renamed, LogLevel::Warning),
+            line("building '/nix/store/abc-foo.drv'...", LogLevel::Normal),
+            line(
+                &renamed.replace("trace: warning:", "evaluation warning:"),
+                LogLevel::Warning,
+            ),
+        ];
+        let warnings = collect(&lines);
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(warnings[0].message, "Git tree '/etc/nixos' is dirty");
+        assert_eq!(warnings[0].option, None);
+        assert_eq!(warnings[1].count, 2);
+        assert_eq!(warnings[1].raw, renamed);
+        assert_eq!(
+            warnings[1].option.as_deref(),
+            Some("services.xserver.xkb.layout")
+        );
+    }
+}
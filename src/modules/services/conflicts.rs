@@ -0,0 +1,132 @@
+//! Port warnings for the strip at the top of the Ports tab
+//!
+//! Two kinds of mismatch between intended and actual state:
+//! - a port several processes listen on (on different addresses — the
+//!   kernel won't hand the same address to two sockets without
+//!   SO_REUSEPORT), which usually means one of them isn't reachable the way
+//!   it's meant to be
+//! - a port the NixOS firewall opens (see `nix::firewall`) that nothing
+//!   listens on: a stopped service, or a leftover `allowedTCPPorts` entry
+
+use crate::nix::firewall::DeclaredPort;
+use crate::nix::services::PortEntry;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PortWarning {
+    /// Different processes on the same port, with the addresses they use
+    Duplicate {
+        protocol: String,
+        port: u16,
+        listeners: Vec<String>,
+    },
+    /// Opened in the firewall, no listener
+    NotListening { protocol: String, port: u16 },
+}
+
+impl PortWarning {
+    pub fn port(&self) -> u16 {
+        match self {
+            PortWarning::Duplicate { port, .. } | PortWarning::NotListening { port, .. } => *port,
+        }
+    }
+}
+
+/// All warnings, duplicates first, each kind in port order
+pub fn find(ports: &[PortEntry], declared: &[DeclaredPort]) -> Vec<PortWarning> {
+    let mut warnings = Vec::new();
+
+    let mut keys: Vec<(&str, u16)> = ports
+        .iter()
+        .map(|p| (p.protocol.as_str(), p.port))
+        .collect();
+    keys.sort();
+    keys.dedup();
+    for (protocol, port) in keys {
+        // One process on v4 and v6 (or several addresses) is one listener
+        let mut listeners: Vec<(&str, Option<u32>, &str)> = ports
+            .iter()
+            .filter(|p| p.protocol == protocol && p.port == port)
+            .map(|p| (p.process_name.as_str(), p.pid, p.address.as_str()))
+            .collect();
+        listeners.sort();
+        listeners.dedup_by(|a, b| (a.0, a.1) == (b.0, b.1));
+        if listeners.len() > 1 {
+            warnings.push(PortWarning::Duplicate {
+                protocol: protocol.to_string(),
+                port,
+                listeners: listeners
+                    .iter()
+                    .map(|(name, _, address)| format!("{} ({})", name, address))
+                    .collect(),
+            });
+        }
+    }
+
+    for d in declared {
+        let listening = ports
+            .iter()
+            .any(|p| p.protocol == d.protocol && p.port == d.port);
+        if !listening {
+            warnings.push(PortWarning::NotListening {
+                protocol: d.protocol.clone(),
+                port: d.port,
+            });
+        }
+    }
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nix::services::EntryKind;
+
+    fn entry(port: u16, address: &str, process: &str, pid: u32) -> PortEntry {
+        PortEntry {
+            protocol: "tcp".to_string(),
+            port,
+            address: address.to_string(),
+            process_name: process.to_string(),
+            pid: Some(pid),
+            owner: String::new(),
+            owner_kind: EntryKind::Systemd,
+        }
+    }
+
+    #[test]
+    fn test_port_warnings() {
+        let ports = vec![
+            entry(22, "*", "sshd", 10),
+            entry(22, "[::]", "sshd", 10),
+            entry(53, "127.0.0.53", "systemd-resolve", 20),
+            entry(53, "192.168.1.2", "dnsmasq", 30),
+        ];
+        let declared = vec![
+            DeclaredPort {
+                protocol: "tcp".to_string(),
+                port: 22,
+            },
+            DeclaredPort {
+                protocol: "tcp".to_string(),
+                port: 443,
+            },
+        ];
+        assert_eq!(
+            find(&ports, &declared),
+            vec![
+                PortWarning::Duplicate {
+                    protocol: "tcp".to_string(),
+                    port: 53,
+                    listeners: vec![
+                        "dnsmasq (192.168.1.2)".to_string(),
+                        "systemd-resolve (127.0.0.53)".to_string()
+                    ],
+                },
+                PortWarning::NotListening {
+                    protocol: "tcp".to_string(),
+                    port: 443,
+                },
+            ]
+        );
+    }
+}
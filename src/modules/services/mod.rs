@@ -3,14 +3,40 @@
 //! Integrated into nixmate as an inline module.
 //! Sub-tabs: Overview, Ports, Manage, Logs.
 //! Shows systemd services, Docker/Podman containers, and open ports in one view.
+//! The Ports tab also maps reverse proxy domains to the port and owner behind them.
+//! A warnings strip above the port list flags ports several processes listen
+//! on and firewall ports nothing listens on (see `conflicts.rs`).
+//! `x` there frees a port: stops its service or container, or kills the process.
+//! `u` shows the selected unit's file and the NixOS module behind it
+//! (see `unit_file.rs`).
+//! The Overview lists CPU and memory per unit and container; `s` sorts by
+//! them (see `usage.rs`).
+//! With `services_mounts = true` the Overview also lists network mounts and
+//! disks with their health (see `nix::mounts`).
+//! The Logs tab narrows the journal by grep (matches highlighted), time
+//! range presets (`t`: this boot, last hour, today) and priority (`e`:
+//! errors only), all passed on to `journalctl`.
 //! Uses nixmate's global theme, i18n, and config.
 
+mod conflicts;
+mod unit_file;
+mod usage;
+
 use crate::config::Language;
 use crate::i18n;
+use crate::modules::jobs::Job;
+use crate::modules::module::{Module, RenderCtx};
+use crate::modules::palette::PaletteCommand;
+use crate::nix::firewall::{self, DeclaredPort};
+use crate::nix::mounts::{self, Health, MountReport, MountState};
+use crate::nix::proxies::{self, ProxyRoute};
 use crate::nix::services::{
-    self, DashboardStats, EnableState, EntryKind, PortEntry, RunState, ServiceAction, ServiceEntry,
+    self, DashboardStats, EnableState, EntryKind, LogQuery, LogRange, PortEntry, RunState,
+    ServiceAction, ServiceEntry, LOG_TAIL_SIZES,
 };
-use crate::types::FlashMessage;
+use crate::session::{self, ModuleSession};
+use crate::types::{format_bytes, FlashMessage};
+use crate::ui::table::{Cell, Column, SortKey, Table, TableState};
 use crate::ui::theme::Theme;
 use crate::ui::widgets;
 use anyhow::Result;
@@ -23,9 +49,22 @@ use ratatui::{
     Frame,
 };
 use std::sync::mpsc;
+use std::time::{Duration, Instant};
+use unit_file::{Source, UnitView};
+
+/// Seconds between reloads while following the logs
+const LOG_FOLLOW_SECS: u64 = 2;
 
 // ── Sub-tabs ──
 
+/// Log filter being typed in the Logs tab
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogField {
+    Since,
+    Until,
+    Grep,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum SvcSubTab {
     #[default]
@@ -92,6 +131,22 @@ pub enum SvcPopupState {
         entry_kind: EntryKind,
         action: ServiceAction,
     },
+    /// Restart every failed unit: the list to confirm, then each result
+    RestartFailed {
+        units: Vec<String>,
+        /// Per unit once done: the state it settled in, or the error
+        results: Vec<Option<Result<String, String>>>,
+        started: bool,
+        /// Open their logs, following, once all are restarted
+        follow: bool,
+    },
+    /// Free a port held by a process without a service or container behind it
+    KillProcess {
+        pid: u32,
+        process: String,
+        port: u16,
+        sudo: bool,
+    },
 }
 
 // ── Filter mode ──
@@ -131,40 +186,82 @@ impl FilterKind {
 // ── Module state ──
 
 /// Result type for background loading
-type SvcLoadResult = Result<(Vec<ServiceEntry>, Vec<PortEntry>, DashboardStats)>;
+type SvcLoadResult = Result<(
+    Vec<ServiceEntry>,
+    Vec<PortEntry>,
+    DashboardStats,
+    Vec<ProxyRoute>,
+    Option<MountReport>,
+    Vec<DeclaredPort>,
+)>;
 
 pub struct ServicesState {
     // Data
     pub entries: Vec<ServiceEntry>,
     pub ports: Vec<PortEntry>,
     pub stats: DashboardStats,
+    /// Reverse proxy domain → upstream mappings
+    pub proxies: Vec<ProxyRoute>,
+    /// Ports the NixOS firewall opens
+    pub declared_ports: Vec<DeclaredPort>,
+    /// Network mounts and disks (`services_mounts`, opt-in)
+    pub mounts: Option<MountReport>,
+    pub mounts_enabled: bool,
+    /// Terminal below the compact thresholds (set on resize)
+    pub compact: bool,
     pub logs: Vec<String>,
     pub load_error: Option<String>,
     pub loaded: bool,
     pub loading: bool,
+    /// When the shown data was loaded (for TTL / auto-refresh)
+    pub loaded_at: Option<Instant>,
     load_rx: Option<mpsc::Receiver<SvcLoadResult>>,
+    /// CPU time per entry at the last load, for the CPU column
+    cpu_sample: Option<usage::CpuSample>,
 
     // Navigation
     pub active_sub_tab: SvcSubTab,
 
     // Overview
     pub overview_selected: usize,
+    pub overview_table: TableState,
     pub filter_kind: FilterKind,
     pub search_text: String,
     pub search_active: bool,
 
     // Ports
     pub ports_selected: usize,
+    pub ports_table: TableState,
 
     // Manage
     pub manage_action_idx: usize,
 
     // Logs
     pub logs_scroll: usize,
+    pub logs_query: LogQuery,
+    /// Filter currently being edited, with its input buffer
+    pub logs_input: Option<(LogField, String)>,
+    pub logs_loading: bool,
+    logs_rx: Option<mpsc::Receiver<Result<Vec<String>, String>>>,
+    /// Units shown together instead of the selected entry (after
+    /// restarting the failed ones)
+    pub logs_units: Vec<String>,
+    /// Reload the logs every few seconds
+    pub logs_follow: bool,
+    logs_loaded_at: Option<Instant>,
+
+    // Restart of all failed units: (unit index, result)
+    restart_rx: Option<mpsc::Receiver<(usize, Result<String, String>)>>,
 
     // Popup
     pub popup: SvcPopupState,
 
+    /// Unit file shown over the tab (`u`)
+    pub unit_view: Option<UnitView>,
+    /// Option namespaces to open in the Options Explorer, most specific
+    /// first (taken by App)
+    pub pending_option: Option<Vec<String>>,
+
     // Flash
     pub lang: Language,
     pub flash_message: Option<FlashMessage>,
@@ -177,20 +274,39 @@ impl ServicesState {
             entries: Vec::new(),
             ports: Vec::new(),
             stats: DashboardStats::default(),
+            proxies: Vec::new(),
+            declared_ports: Vec::new(),
+            mounts: None,
+            mounts_enabled: false,
+            compact: false,
             logs: Vec::new(),
             load_error: None,
             loaded: false,
             loading: false,
+            loaded_at: None,
             load_rx: None,
+            cpu_sample: None,
             active_sub_tab: SvcSubTab::Overview,
             overview_selected: 0,
+            overview_table: TableState::default(),
             filter_kind: FilterKind::Active,
             search_text: String::new(),
             search_active: false,
             ports_selected: 0,
+            ports_table: TableState::default(),
             manage_action_idx: 0,
             logs_scroll: 0,
+            logs_query: LogQuery::default(),
+            logs_input: None,
+            logs_loading: false,
+            logs_rx: None,
+            logs_units: Vec::new(),
+            logs_follow: false,
+            logs_loaded_at: None,
+            restart_rx: None,
             popup: SvcPopupState::None,
+            unit_view: None,
+            pending_option: None,
             lang: Language::English,
             flash_message: None,
         }
@@ -201,11 +317,33 @@ impl ServicesState {
         if self.loaded || self.loading {
             return;
         }
+        self.spawn_load();
+    }
+
+    /// Reload in the background, keeping the current data on screen
+    pub fn refresh_in_background(&mut self) {
+        if !self.loading {
+            self.spawn_load();
+        }
+    }
+
+    fn spawn_load(&mut self) {
         self.loading = true;
         let (tx, rx) = mpsc::channel();
         self.load_rx = Some(rx);
+        let with_mounts = self.mounts_enabled;
         std::thread::spawn(move || {
-            let result = services::load_dashboard();
+            let result = services::load_dashboard().map(|(e, p, s)| {
+                let mounts = with_mounts.then(mounts::load);
+                (
+                    e,
+                    p,
+                    s,
+                    proxies::load_routes(),
+                    mounts,
+                    firewall::declared_ports(),
+                )
+            });
             let _ = tx.send(result);
         });
     }
@@ -214,11 +352,16 @@ impl ServicesState {
     pub fn poll_load(&mut self) {
         if let Some(ref rx) = self.load_rx {
             match rx.try_recv() {
-                Ok(Ok((e, p, s))) => {
+                Ok(Ok((mut e, p, s, r, m, d))) => {
+                    self.cpu_sample = Some(usage::apply(&mut e, self.cpu_sample.as_ref()));
                     self.entries = e;
                     self.ports = p;
                     self.stats = s;
+                    self.proxies = r;
+                    self.mounts = m;
+                    self.declared_ports = d;
                     self.load_error = None;
+                    self.loaded_at = Some(Instant::now());
                     self.loaded = true;
                     self.loading = false;
                     self.load_rx = None;
@@ -253,11 +396,15 @@ impl ServicesState {
         self.loading = false;
 
         match services::load_dashboard() {
-            Ok((e, p, s)) => {
+            Ok((mut e, p, s)) => {
+                self.cpu_sample = Some(usage::apply(&mut e, self.cpu_sample.as_ref()));
                 self.entries = e;
                 self.ports = p;
                 self.stats = s;
+                self.proxies = proxies::load_routes();
+                self.declared_ports = firewall::declared_ports();
                 self.load_error = None;
+                self.loaded_at = Some(Instant::now());
             }
             Err(e) => {
                 self.load_error = Some(e.to_string());
@@ -266,14 +413,37 @@ impl ServicesState {
         self.loaded = true;
     }
 
-    /// Filtered entry list based on current filter + search
+    /// Listening port a proxy route forwards to (None = remote or not listening)
+    pub fn proxy_target(&self, route: &ProxyRoute) -> Option<&PortEntry> {
+        let port = route.port?;
+        if !route.local {
+            return None;
+        }
+        self.ports.iter().find(|p| p.port == port)
+    }
+
+    /// Local proxy routes whose upstream port nothing listens on
+    pub fn dangling_proxies(&self) -> usize {
+        self.proxies
+            .iter()
+            .filter(|r| r.local && r.port.is_some() && self.proxy_target(r).is_none())
+            .count()
+    }
+
+    /// Duplicate listeners and firewall ports nothing listens on
+    pub fn port_warnings(&self) -> Vec<conflicts::PortWarning> {
+        conflicts::find(&self.ports, &self.declared_ports)
+    }
+
+    /// Filtered entry list based on current filter + search, in table order
     pub fn filtered_entries(&self) -> Vec<&ServiceEntry> {
-        self.entries
+        let filtered = self
+            .entries
             .iter()
             .filter(|e| match self.filter_kind {
                 FilterKind::All => true,
-                FilterKind::Active => e.status.is_active(),
-                FilterKind::Systemd => e.kind == EntryKind::Systemd && e.status.is_active(),
+                FilterKind::Active => e.status.is_up(),
+                FilterKind::Systemd => e.kind == EntryKind::Systemd && e.status.is_up(),
                 FilterKind::Containers => {
                     matches!(e.kind, EntryKind::Docker | EntryKind::Podman)
                 }
@@ -287,7 +457,15 @@ impl ServicesState {
                 e.display_name.to_lowercase().contains(&needle)
                     || e.description.to_lowercase().contains(&needle)
             })
-            .collect()
+            .collect();
+        self.overview_table
+            .sorted(filtered, |e, col| overview_sort_key(e, col))
+    }
+
+    /// Open ports in table order
+    pub fn sorted_ports(&self) -> Vec<&PortEntry> {
+        self.ports_table
+            .sorted(self.ports.iter().collect(), |p, col| port_sort_key(p, col))
     }
 
     /// Currently selected entry (if any)
@@ -296,29 +474,230 @@ impl ServicesState {
         filtered.get(self.overview_selected).copied()
     }
 
-    /// Load logs for the selected entry
+    /// Load logs for the selected entry in the background
     fn load_logs(&mut self) {
-        if let Some(entry) = self.selected_entry().cloned() {
-            match services::get_logs(&entry, 200) {
-                Ok(lines) => {
-                    self.logs = lines;
-                    self.logs_scroll = if self.logs.len() > 10 {
-                        self.logs.len().saturating_sub(10)
-                    } else {
-                        0
-                    };
-                }
-                Err(e) => {
-                    self.logs = vec![format!("Error: {}", e)];
-                    self.logs_scroll = 0;
-                }
-            }
+        self.logs_loaded_at = Some(Instant::now());
+        if !self.logs_units.is_empty() {
+            let units = self.logs_units.clone();
+            let query = self.logs_query.clone();
+            let (tx, rx) = mpsc::channel();
+            self.logs_rx = Some(rx);
+            self.logs_loading = true;
+            std::thread::spawn(move || {
+                let _ =
+                    tx.send(services::get_units_logs(&units, &query).map_err(|e| e.to_string()));
+            });
+        } else if let Some(entry) = self.selected_entry().cloned() {
+            let query = self.logs_query.clone();
+            let (tx, rx) = mpsc::channel();
+            self.logs_rx = Some(rx);
+            self.logs_loading = true;
+            std::thread::spawn(move || {
+                let _ = tx.send(services::get_logs(&entry, &query).map_err(|e| e.to_string()));
+            });
         } else {
+            self.logs_rx = None;
+            self.logs_loading = false;
             self.logs.clear();
             self.logs_scroll = 0;
         }
     }
 
+    /// Reload the logs while following. Called from update_timers while
+    /// the tab is visible.
+    pub fn tick_logs_follow(&mut self) {
+        let due = self
+            .logs_loaded_at
+            .is_none_or(|t| t.elapsed() >= Duration::from_secs(LOG_FOLLOW_SECS));
+        if self.logs_follow && self.active_sub_tab == SvcSubTab::Logs && !self.logs_loading && due {
+            self.load_logs();
+        }
+    }
+
+    /// Ask to restart all failed systemd units
+    fn prompt_restart_failed(&mut self) {
+        let units: Vec<String> = self
+            .entries
+            .iter()
+            .filter(|e| e.kind == EntryKind::Systemd && e.status == RunState::Failed)
+            .map(|e| e.name.clone())
+            .collect();
+        if units.is_empty() {
+            let s = i18n::get_strings(self.lang);
+            self.show_flash(s.svc_no_failed, false);
+            return;
+        }
+        self.popup = SvcPopupState::RestartFailed {
+            results: vec![None; units.len()],
+            units,
+            started: false,
+            follow: false,
+        };
+    }
+
+    fn start_restart_failed(&mut self, follow_logs: bool) {
+        let SvcPopupState::RestartFailed {
+            units,
+            started,
+            follow,
+            ..
+        } = &mut self.popup
+        else {
+            return;
+        };
+        *started = true;
+        *follow = follow_logs;
+        let units = units.clone();
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            for (i, unit) in units.iter().enumerate() {
+                let result = services::restart_unit(unit).map_err(|e| e.to_string());
+                if tx.send((i, result)).is_err() {
+                    return;
+                }
+            }
+        });
+        self.restart_rx = Some(rx);
+    }
+
+    /// Collect restart results. Called from update_timers.
+    pub fn poll_restart(&mut self) {
+        let Some(rx) = &self.restart_rx else {
+            return;
+        };
+        let SvcPopupState::RestartFailed {
+            units,
+            results,
+            follow,
+            ..
+        } = &mut self.popup
+        else {
+            return;
+        };
+        loop {
+            match rx.try_recv() {
+                Ok((i, result)) => results[i] = Some(result),
+                Err(mpsc::TryRecvError::Empty) => return,
+                Err(mpsc::TryRecvError::Disconnected) => break,
+            }
+        }
+        self.restart_rx = None;
+        let units = units.clone();
+        let failed = results.iter().filter(|r| !matches!(r, Some(Ok(_)))).count();
+        let follow = *follow;
+        self.refresh_in_background();
+
+        let s = i18n::get_strings(self.lang);
+        let summary = s
+            .svc_restarted_summary
+            .replacen("{}", &(units.len() - failed).to_string(), 1)
+            .replacen("{}", &units.len().to_string(), 1);
+        if follow {
+            self.popup = SvcPopupState::None;
+            self.logs_units = units;
+            self.logs_follow = true;
+            self.logs_scroll = 0;
+            self.active_sub_tab = SvcSubTab::Logs;
+            self.load_logs();
+            self.show_flash(&summary, failed > 0);
+        }
+    }
+
+    /// Poll for background log results. Called from update_timers.
+    pub fn poll_logs(&mut self) {
+        let Some(rx) = &self.logs_rx else {
+            return;
+        };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => Err(crate::i18n::get_strings(self.lang)
+                .thread_crashed
+                .to_string()),
+        };
+        self.logs_rx = None;
+        self.logs_loading = false;
+        match result {
+            Ok(lines) => {
+                self.logs = lines;
+                self.logs_scroll = if self.logs.len() > 10 {
+                    self.logs.len().saturating_sub(10)
+                } else {
+                    0
+                };
+            }
+            Err(e) => {
+                self.logs = vec![format!("Error: {}", e)];
+                self.logs_scroll = 0;
+            }
+        }
+    }
+
+    /// After a resize: no log offset past the last full page of `visible` lines
+    pub fn fit_scroll(&mut self, visible: usize) {
+        self.logs_scroll = self
+            .logs_scroll
+            .min(self.logs.len().saturating_sub(visible));
+    }
+
+    /// Apply the typed filter value and reload
+    fn commit_log_input(&mut self, field: LogField, value: String) {
+        let s = crate::i18n::get_strings(self.lang);
+        let value = Some(value.trim().to_string()).filter(|v| !v.is_empty());
+        if let (LogField::Since | LogField::Until, Some(v)) = (field, &value) {
+            if services::parse_log_time(v).is_none() {
+                self.show_flash(&s.svc_logs_bad_time.replace("{}", v), true);
+                return;
+            }
+        }
+        match field {
+            LogField::Since => {
+                self.logs_query.range = LogRange::All;
+                self.logs_query.since = value;
+            }
+            LogField::Until => self.logs_query.until = value,
+            LogField::Grep => self.logs_query.grep = value,
+        }
+        self.load_logs();
+    }
+
+    /// `systemctl cat` of the selected systemd unit
+    fn open_unit_file(&mut self) {
+        let s = i18n::get_strings(self.lang);
+        let Some(entry) = self.selected_entry() else {
+            return;
+        };
+        if entry.kind != EntryKind::Systemd {
+            self.show_flash(s.svc_unit_not_systemd, false);
+            return;
+        }
+        match unit_file::load(&entry.name.clone()) {
+            Ok(view) => self.unit_view = Some(view),
+            Err(e) => self.show_flash(&format!("{}: {}", s.svc_unit_failed, e), true),
+        }
+    }
+
+    fn handle_unit_view_key(&mut self, key: KeyEvent) {
+        let Some(view) = &mut self.unit_view else {
+            return;
+        };
+        let last = view.lines.len().saturating_sub(1);
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => view.scroll = (view.scroll + 1).min(last),
+            KeyCode::Char('k') | KeyCode::Up => view.scroll = view.scroll.saturating_sub(1),
+            KeyCode::PageDown => view.scroll = (view.scroll + 20).min(last),
+            KeyCode::PageUp => view.scroll = view.scroll.saturating_sub(20),
+            KeyCode::Char('g') => view.scroll = 0,
+            KeyCode::Char('G') => view.scroll = last,
+            KeyCode::Char('o') => {
+                self.pending_option = Some(view.namespaces.clone());
+                self.unit_view = None;
+            }
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('u') => self.unit_view = None,
+            _ => {}
+        }
+    }
+
     fn show_flash(&mut self, msg: &str, is_error: bool) {
         self.flash_message = Some(FlashMessage::new(msg.to_string(), is_error));
     }
@@ -336,6 +715,115 @@ impl ServicesState {
     //  KEY HANDLING
     // ═══════════════════════════════════════
 
+    /// Filter and search for the session file
+    pub fn session(&self) -> ModuleSession {
+        ModuleSession {
+            query: self.search_text.clone(),
+            filter: session::cycle_index(FilterKind::All, self.filter_kind, FilterKind::next),
+            ..Default::default()
+        }
+    }
+
+    pub fn restore_session(&mut self, saved: &ModuleSession) {
+        self.search_text = saved.query.clone();
+        self.filter_kind = session::cycle_nth(FilterKind::All, saved.filter, FilterKind::next);
+    }
+
+    /// Actions offered in the command palette
+    /// Running background work, for the jobs overlay
+    pub fn jobs(&self, s: &i18n::Strings) -> Vec<Job> {
+        let mut jobs = Vec::new();
+        if self.loading {
+            jobs.push(Job::new("load", s.job_svc_load, true));
+        }
+        if self.logs_loading {
+            jobs.push(Job::new("logs", s.job_svc_logs, true));
+        }
+        if let (Some(_), SvcPopupState::RestartFailed { results, .. }) =
+            (&self.restart_rx, &self.popup)
+        {
+            let done = results.iter().filter(|r| r.is_some()).count();
+            jobs.push(Job::new("restart", s.job_svc_restart, false).progress(done, results.len()));
+        }
+        jobs
+    }
+
+    /// Stop waiting for a job from `jobs()`; its result is discarded
+    pub fn cancel_job(&mut self, id: &str) {
+        let s = i18n::get_strings(self.lang);
+        match id {
+            "load" => {
+                self.load_rx = None;
+                self.loading = false;
+                if !self.loaded {
+                    self.loaded = true;
+                    self.load_error = Some(s.jobs_cancelled_error.to_string());
+                }
+            }
+            "logs" => {
+                self.logs_rx = None;
+                self.logs_loading = false;
+            }
+            _ => {}
+        }
+    }
+
+    pub fn palette_commands(&self, s: &i18n::Strings) -> Vec<PaletteCommand> {
+        let logs = Some(SvcSubTab::Logs.index());
+        let mut commands = vec![
+            PaletteCommand::new(
+                s.pal_svc_search,
+                Some(SvcSubTab::Overview.index()),
+                vec![KeyCode::Char('/')],
+            ),
+            PaletteCommand::new(s.pal_svc_ports, Some(SvcSubTab::Ports.index()), vec![]),
+            PaletteCommand::new(
+                s.pal_svc_unit_file,
+                Some(SvcSubTab::Overview.index()),
+                vec![KeyCode::Char('u')],
+            ),
+            PaletteCommand::new(
+                s.pal_svc_restart_failed,
+                Some(SvcSubTab::Overview.index()),
+                vec![KeyCode::Char('F')],
+            ),
+        ];
+
+        // Manage: move to the action in the list, then confirm
+        let selected = self.selected_entry().map(|e| e.display_name.clone());
+        let actions = self.available_actions();
+        for (i, action) in actions.iter().enumerate() {
+            let label = match action {
+                ServiceAction::Start => s.pal_svc_start,
+                ServiceAction::Stop => s.pal_svc_stop,
+                ServiceAction::Restart => s.pal_svc_restart,
+                ServiceAction::Enable => s.pal_svc_enable,
+                ServiceAction::Disable => s.pal_svc_disable,
+            };
+            let mut keys = vec![KeyCode::Up; actions.len()];
+            keys.extend(vec![KeyCode::Down; i]);
+            keys.push(KeyCode::Enter);
+            commands.push(PaletteCommand::new(
+                match &selected {
+                    Some(name) => format!("{} ({})", label, name),
+                    None => label.to_string(),
+                },
+                Some(SvcSubTab::Manage.index()),
+                keys,
+            ));
+        }
+
+        commands.extend([
+            PaletteCommand::new(s.pal_svc_logs_grep, logs, vec![KeyCode::Char('/')]),
+            PaletteCommand::new(s.pal_svc_logs_since, logs, vec![KeyCode::Char('s')]),
+            PaletteCommand::new(s.pal_svc_logs_range, logs, vec![KeyCode::Char('t')]),
+            PaletteCommand::new(s.pal_svc_logs_errors, logs, vec![KeyCode::Char('e')]),
+            PaletteCommand::new(s.pal_svc_logs_more, logs, vec![KeyCode::Char('+')]),
+            PaletteCommand::new(s.pal_svc_logs_follow, logs, vec![KeyCode::Char('f')]),
+        ]);
+        commands
+    }
+
     pub fn handle_key(&mut self, key: KeyEvent) -> Result<()> {
         // Clear expired flash
         if let Some(msg) = &self.flash_message {
@@ -344,7 +832,52 @@ impl ServicesState {
             }
         }
 
+        if self.unit_view.is_some() {
+            self.handle_unit_view_key(key);
+            return Ok(());
+        }
+
         // Handle popup first
+        if let SvcPopupState::RestartFailed { started, .. } = &self.popup {
+            let running = self.restart_rx.is_some();
+            match key.code {
+                KeyCode::Char('y') | KeyCode::Enter if !started => self.start_restart_failed(false),
+                KeyCode::Char('l') if !started => self.start_restart_failed(true),
+                KeyCode::Char('n') | KeyCode::Esc if !started => {
+                    self.popup = SvcPopupState::None;
+                }
+                KeyCode::Char('o') | KeyCode::Enter | KeyCode::Esc if *started && !running => {
+                    self.popup = SvcPopupState::None;
+                }
+                _ => {}
+            }
+            return Ok(());
+        }
+        if let SvcPopupState::KillProcess {
+            pid,
+            ref process,
+            sudo,
+            ..
+        } = self.popup.clone()
+        {
+            match key.code {
+                KeyCode::Char('y') | KeyCode::Enter => {
+                    self.popup = SvcPopupState::None;
+                    match services::kill_process(pid, process, sudo) {
+                        Ok(msg) => {
+                            self.show_flash(&msg, false);
+                            self.refresh();
+                        }
+                        Err(e) => self.show_flash(&e.to_string(), true),
+                    }
+                }
+                KeyCode::Char('n') | KeyCode::Esc => {
+                    self.popup = SvcPopupState::None;
+                }
+                _ => {}
+            }
+            return Ok(());
+        }
         if let SvcPopupState::ConfirmAction {
             ref entry_name,
             ref entry_display,
@@ -365,8 +898,11 @@ impl ServicesState {
                         description: String::new(),
                         pid: None,
                         memory: None,
+                        cpu_usec: None,
+                        cpu_percent: None,
                         uptime: None,
                         ports: Vec::new(),
+                        socket: None,
                     };
                     match services::execute_action(&tmp, action) {
                         Ok(msg) => {
@@ -439,6 +975,19 @@ impl ServicesState {
             return Ok(());
         }
 
+        let s = i18n::get_strings(self.lang);
+        let columns = overview_columns(s);
+        let current = self.selected_entry().map(|e| e.name.clone());
+        if self.overview_table.handle_key(key, &columns) {
+            // Keep the same entry selected in the new order
+            self.overview_selected = self
+                .filtered_entries()
+                .iter()
+                .position(|e| Some(&e.name) == current.as_ref())
+                .unwrap_or(0);
+            return Ok(());
+        }
+
         let count = self.filtered_entries().len();
         match key.code {
             KeyCode::Char('j') | KeyCode::Down => {
@@ -465,6 +1014,8 @@ impl ServicesState {
             KeyCode::Enter => {
                 // Jump to Logs for selected
                 self.active_sub_tab = SvcSubTab::Logs;
+                self.logs_units.clear();
+                self.logs_follow = false;
                 self.load_logs();
             }
             KeyCode::Char('m') => {
@@ -472,6 +1023,21 @@ impl ServicesState {
                 self.active_sub_tab = SvcSubTab::Manage;
                 self.manage_action_idx = 0;
             }
+            KeyCode::Char('s') => {
+                let table = &mut self.overview_table;
+                let (col, descending) = usage::next_sort((table.sort, table.descending));
+                table.sort = Some(col);
+                table.descending = descending;
+                table.hidden.retain(|&c| c != col);
+                self.overview_selected = self
+                    .filtered_entries()
+                    .iter()
+                    .position(|e| Some(&e.name) == current.as_ref())
+                    .unwrap_or(0);
+                self.show_flash(&s.svc_sorted_by.replace("{}", columns[col].title), false);
+            }
+            KeyCode::Char('F') => self.prompt_restart_failed(),
+            KeyCode::Char('u') => self.open_unit_file(),
             KeyCode::Char('g') => {
                 self.overview_selected = 0;
             }
@@ -493,6 +1059,20 @@ impl ServicesState {
     }
 
     fn handle_ports_key(&mut self, key: KeyEvent) -> Result<()> {
+        let s = i18n::get_strings(self.lang);
+        let current = self
+            .sorted_ports()
+            .get(self.ports_selected)
+            .map(|p| (p.protocol.clone(), p.port, p.address.clone()));
+        if self.ports_table.handle_key(key, &port_columns(s)) {
+            self.ports_selected = self
+                .sorted_ports()
+                .iter()
+                .position(|p| current == Some((p.protocol.clone(), p.port, p.address.clone())))
+                .unwrap_or(0);
+            return Ok(());
+        }
+
         let count = self.ports.len();
         match key.code {
             KeyCode::Char('j') | KeyCode::Down => {
@@ -508,6 +1088,7 @@ impl ServicesState {
                 let s = crate::i18n::get_strings(self.lang);
                 self.show_flash(s.svc_refreshed, false);
             }
+            KeyCode::Char('x') => self.prompt_free_port(),
             KeyCode::Char('g') => {
                 self.ports_selected = 0;
             }
@@ -521,6 +1102,39 @@ impl ServicesState {
         Ok(())
     }
 
+    /// `x` in Ports: stop what holds the selected port — its service or
+    /// container when one was matched, else the process itself
+    fn prompt_free_port(&mut self) {
+        let Some(port) = self
+            .sorted_ports()
+            .get(self.ports_selected)
+            .map(|p| (*p).clone())
+        else {
+            return;
+        };
+        let owner = self.entries.iter().find(|e| {
+            !port.owner.is_empty() && e.kind == port.owner_kind && e.display_name == port.owner
+        });
+        if let Some(entry) = owner {
+            self.popup = SvcPopupState::ConfirmAction {
+                entry_name: entry.name.clone(),
+                entry_display: entry.display_name.clone(),
+                entry_kind: entry.kind,
+                action: ServiceAction::Stop,
+            };
+        } else if let Some(pid) = port.pid {
+            self.popup = SvcPopupState::KillProcess {
+                pid,
+                process: port.process_name,
+                port: port.port,
+                sudo: services::kill_needs_sudo(pid),
+            };
+        } else {
+            let s = i18n::get_strings(self.lang);
+            self.show_flash(s.svc_port_no_pid, true);
+        }
+    }
+
     fn handle_manage_key(&mut self, key: KeyEvent) -> Result<()> {
         let entry = self.selected_entry().cloned();
         let actions = self.available_actions();
@@ -553,7 +1167,71 @@ impl ServicesState {
     }
 
     fn handle_logs_key(&mut self, key: KeyEvent) -> Result<()> {
+        // Filter input mode
+        if let Some((field, buf)) = &mut self.logs_input {
+            match key.code {
+                KeyCode::Esc => self.logs_input = None,
+                KeyCode::Enter => {
+                    let (field, value) = (*field, std::mem::take(buf));
+                    self.logs_input = None;
+                    self.commit_log_input(field, value);
+                }
+                KeyCode::Backspace => {
+                    buf.pop();
+                }
+                KeyCode::Char(c) => buf.push(c),
+                _ => {}
+            }
+            return Ok(());
+        }
+
+        let start_input = |field: LogField, current: &Option<String>| {
+            Some((field, current.clone().unwrap_or_default()))
+        };
         match key.code {
+            KeyCode::Char('/') => {
+                self.logs_input = start_input(LogField::Grep, &self.logs_query.grep);
+            }
+            KeyCode::Char('s') => {
+                self.logs_input = start_input(LogField::Since, &self.logs_query.since);
+            }
+            KeyCode::Char('u') => {
+                self.logs_input = start_input(LogField::Until, &self.logs_query.until);
+            }
+            KeyCode::Char('t') => {
+                // A preset replaces the typed start
+                self.logs_query.range = self.logs_query.range.next();
+                self.logs_query.since = None;
+                self.load_logs();
+            }
+            KeyCode::Char('e') => {
+                self.logs_query.errors_only = !self.logs_query.errors_only;
+                self.load_logs();
+            }
+            KeyCode::Char('+') | KeyCode::Char('-') => {
+                let pos = LOG_TAIL_SIZES
+                    .iter()
+                    .position(|&n| n == self.logs_query.tail)
+                    .unwrap_or(1);
+                let pos = if key.code == KeyCode::Char('+') {
+                    (pos + 1).min(LOG_TAIL_SIZES.len() - 1)
+                } else {
+                    pos.saturating_sub(1)
+                };
+                if LOG_TAIL_SIZES[pos] != self.logs_query.tail {
+                    self.logs_query.tail = LOG_TAIL_SIZES[pos];
+                    self.load_logs();
+                }
+            }
+            KeyCode::Char('x') if self.logs_query.is_filtered() => {
+                self.logs_query = LogQuery {
+                    tail: self.logs_query.tail,
+                    ..LogQuery::default()
+                };
+                self.load_logs();
+                let s = crate::i18n::get_strings(self.lang);
+                self.show_flash(s.svc_logs_filters_cleared, false);
+            }
             KeyCode::Char('j') | KeyCode::Down => {
                 self.logs_scroll = self.logs_scroll.saturating_add(1);
             }
@@ -565,6 +1243,15 @@ impl ServicesState {
                 let s = crate::i18n::get_strings(self.lang);
                 self.show_flash(s.svc_logs_refreshed, false);
             }
+            KeyCode::Char('f') => {
+                self.logs_follow = !self.logs_follow;
+            }
+            KeyCode::Esc if !self.logs_units.is_empty() => {
+                // Back to the selected entry's logs
+                self.logs_units.clear();
+                self.logs_follow = false;
+                self.load_logs();
+            }
             KeyCode::Char('g') => {
                 self.logs_scroll = 0;
             }
@@ -599,6 +1286,60 @@ impl ServicesState {
 //  RENDERING
 // ═══════════════════════════════════════════════════════════════
 
+impl Module for ServicesState {
+    fn handle_key(&mut self, key: KeyEvent, _lang: Language) -> Result<()> {
+        self.handle_key(key).map(|_| ())
+    }
+
+    fn captures_input(&self) -> bool {
+        !matches!(self.popup, SvcPopupState::None)
+            || self.unit_view.is_some()
+            || self.search_active
+            || self.logs_input.is_some()
+    }
+
+    fn render(&mut self, frame: &mut Frame, ctx: &RenderCtx, area: Rect) {
+        render(frame, self, ctx.theme, ctx.lang, area);
+    }
+
+    fn activate(&mut self) {
+        self.start_loading();
+    }
+
+    fn poll(&mut self, active: bool) {
+        self.poll_load();
+        self.poll_logs();
+        self.poll_restart();
+        if active {
+            self.tick_logs_follow();
+        }
+    }
+
+    fn commands(&self, s: &i18n::Strings) -> Vec<PaletteCommand> {
+        self.palette_commands(s)
+    }
+
+    fn jobs(&self, s: &i18n::Strings) -> Vec<Job> {
+        self.jobs(s)
+    }
+
+    fn cancel_job(&mut self, id: &str) {
+        self.cancel_job(id)
+    }
+
+    fn sub_tab(&self) -> Option<(usize, usize)> {
+        Some((self.active_sub_tab.index(), SvcSubTab::all().len()))
+    }
+
+    fn sub_tab_label(&self, lang: Language) -> Option<&'static str> {
+        Some(self.active_sub_tab.label(lang))
+    }
+
+    fn flash_message(&mut self) -> &mut Option<FlashMessage> {
+        &mut self.flash_message
+    }
+}
+
 pub fn render(
     frame: &mut Frame,
     state: &mut ServicesState,
@@ -655,6 +1396,9 @@ pub fn render(
             SvcSubTab::Logs => render_logs(frame, state, theme, lang, chunks[1]),
         }
     }
+    if let Some(view) = &state.unit_view {
+        render_unit_view(frame, view, theme, lang, chunks[1]);
+    }
 
     // Popup overlay
     render_popups(frame, state, theme, lang, area);
@@ -748,13 +1492,32 @@ fn render_overview(
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    // Layout: dashboard stats → filter bar → list
+    // Layout: dashboard stats → filter bar → list → mounts and disks
+    let mount_lines = if state.mounts_enabled {
+        mount_lines(state, theme, lang)
+    } else {
+        Vec::new()
+    };
+    let mounts_height = if mount_lines.is_empty() {
+        0
+    } else {
+        (mount_lines.len() as u16 + 2).min(inner.height / 2)
+    };
     let layout = Layout::vertical([
-        Constraint::Length(3), // Stats dashboard
-        Constraint::Length(1), // Filter + search
-        Constraint::Min(3),    // Entry list
+        Constraint::Length(3),             // Stats dashboard
+        Constraint::Length(1),             // Filter + search
+        Constraint::Min(3),                // Entry list
+        Constraint::Length(mounts_height), // Mounts and disks (opt-in)
     ])
     .split(inner);
+    if mounts_height > 0 {
+        let block = Block::default()
+            .title(format!(" {} ", s.svc_mounts_title))
+            .title_style(theme.title())
+            .borders(Borders::TOP)
+            .border_style(Style::default().fg(theme.border));
+        frame.render_widget(Paragraph::new(mount_lines).block(block), layout[3]);
+    }
 
     // ── Dashboard stats ──
     let mut stat_spans: Vec<Span> = vec![
@@ -766,6 +1529,13 @@ fn render_overview(
         Span::styled(format!(" {} ", s.svc_running), theme.text_dim()),
     ];
 
+    if st.services_socket_idle > 0 {
+        stat_spans.push(Span::styled(
+            format!("  {} {} ", st.services_socket_idle, s.svc_socket_idle),
+            Style::default().fg(theme.accent),
+        ));
+    }
+
     if st.services_failed > 0 {
         stat_spans.push(Span::styled(
             format!("  {} {} ", st.services_failed, s.svc_failed),
@@ -818,14 +1588,26 @@ fn render_overview(
     };
 
     // Port stats
-    let stats_line3 = Line::from(vec![
+    let mut port_spans = vec![
         Span::styled("  🔌 ", theme.text_dim()),
         Span::styled(
             format!("{}", st.ports_open),
             Style::default().fg(theme.accent),
         ),
         Span::styled(format!(" {}", s.svc_ports_open), theme.text_dim()),
-    ]);
+    ];
+    if let Some(problems) = state.mounts.as_ref().map(MountReport::problems) {
+        port_spans.push(Span::styled("    💾 ", theme.text_dim()));
+        port_spans.push(if problems > 0 {
+            Span::styled(
+                s.svc_mounts_problems.replace("{}", &problems.to_string()),
+                Style::default().fg(theme.error),
+            )
+        } else {
+            Span::styled(s.svc_mounts_ok, Style::default().fg(theme.success))
+        });
+    }
+    let stats_line3 = Line::from(port_spans);
 
     let stats_widget = Paragraph::new(vec![stats_line1, stats_line2, stats_line3]);
     frame.render_widget(stats_widget, layout[0]);
@@ -865,7 +1647,6 @@ fn render_overview(
 
     // ── Entry list ──
     let list_area = layout[2];
-    let visible_height = list_area.height as usize;
 
     if filtered.is_empty() {
         let msg = Paragraph::new(Line::styled(
@@ -876,75 +1657,190 @@ fn render_overview(
         return;
     }
 
-    // Scroll to keep selection visible
-    let scroll = if state.overview_selected >= visible_height {
-        state.overview_selected - visible_height + 1
-    } else {
-        0
-    };
-
-    let name_width = (list_area.width as usize / 3).clamp(15, 35);
-
-    let items: Vec<ListItem> = filtered
-        .iter()
-        .enumerate()
-        .skip(scroll)
-        .take(visible_height)
-        .map(|(i, entry)| {
-            let is_sel = i == state.overview_selected;
-
-            let status_style = match entry.status {
-                RunState::Running => Style::default().fg(theme.success),
-                RunState::Failed => Style::default().fg(theme.error),
-                RunState::Restarting => Style::default().fg(theme.warning),
-                _ => theme.text_dim(),
-            };
-
-            let line_style = if is_sel {
-                theme.selected()
-            } else {
-                theme.text()
-            };
-
-            let kind_icon = entry.kind.icon();
-            let padded_name = format!("{:<width$}", entry.display_name, width = name_width);
+    let columns = overview_columns(s);
+    Table::new(&columns, &state.overview_table, filtered.len(), |i| {
+        let entry = filtered[i];
+        let status_style = match entry.status {
+            RunState::Running => Style::default().fg(theme.success),
+            RunState::Failed => Style::default().fg(theme.error),
+            RunState::Restarting => Style::default().fg(theme.warning),
+            RunState::SocketIdle => Style::default().fg(theme.accent),
+            _ => theme.text_dim(),
+        };
+        let enabled = match entry.enabled {
+            EnableState::Enabled => "✓",
+            EnableState::Disabled => "✗",
+            _ => "",
+        };
+        let ports: Vec<String> = entry.ports.iter().map(|p| p.to_string()).collect();
+        vec![
+            Cell::new(
+                format!("{} {}", entry.status.symbol(), entry.kind.icon()),
+                status_style,
+            ),
+            Cell::new(entry.display_name.clone(), theme.text()),
+            Cell::new(enabled, theme.text_dim()),
+            Cell::new(
+                if ports.is_empty() {
+                    String::new()
+                } else {
+                    format!(":{}", ports.join(","))
+                },
+                Style::default().fg(theme.accent),
+            ),
+            Cell::new(
+                entry
+                    .cpu_percent
+                    .map(|p| format!("{:.1}%", p))
+                    .unwrap_or_default(),
+                theme.text(),
+            ),
+            Cell::new(
+                entry.memory.map(format_bytes).unwrap_or_default(),
+                theme.text(),
+            ),
+            Cell::new(entry.description.clone(), theme.text_dim()),
+        ]
+    })
+    .selected(Some(state.overview_selected))
+    .compact(state.compact)
+    .render(frame, theme, list_area);
+}
 
-            // Show ports inline if any
-            let port_str = if entry.ports.is_empty() {
-                String::new()
-            } else {
-                let port_list: Vec<String> = entry.ports.iter().map(|p| p.to_string()).collect();
-                format!(" :{}", port_list.join(","))
-            };
+/// Network mounts, disks and degraded arrays for the Overview panel
+fn mount_lines(state: &ServicesState, theme: &Theme, lang: Language) -> Vec<Line<'static>> {
+    let s = i18n::get_strings(lang);
+    let Some(report) = &state.mounts else {
+        return vec![Line::styled(
+            format!("  {}", s.svc_mounts_loading),
+            theme.text_dim(),
+        )];
+    };
+    let usage = |used: u64, size: u64| {
+        format!(
+            "{} / {} ({}%)",
+            format_bytes(used),
+            format_bytes(size),
+            used * 100 / size.max(1)
+        )
+    };
+    let mut lines = Vec::new();
+    if report.mounts.is_empty() {
+        lines.push(Line::styled(
+            format!("  {}", s.svc_mounts_none),
+            theme.text_dim(),
+        ));
+    }
+    for m in &report.mounts {
+        let (icon, label, color) = match m.state {
+            _ if m.stale => ("◌", s.svc_mount_stale, theme.warning),
+            MountState::Mounted => ("●", s.svc_mount_mounted, theme.success),
+            MountState::Automount => ("○", s.svc_mount_automount, theme.accent),
+            MountState::Failed => ("✗", s.svc_mount_failed, theme.error),
+            MountState::Unmounted => ("○", s.svc_mount_unmounted, theme.fg_dim),
+        };
+        let mut spans = vec![
+            Span::styled(format!("  {} ", icon), Style::default().fg(color)),
+            Span::styled(format!("{:<20}", m.target), theme.text()),
+            Span::styled(format!(" {:<6}", m.fstype), theme.text_dim()),
+            Span::styled(format!(" {:<12}", label), Style::default().fg(color)),
+        ];
+        if let Some((used, size)) = m.usage {
+            spans.push(Span::styled(
+                format!(" {}", usage(used, size)),
+                theme.text_dim(),
+            ));
+        }
+        spans.push(Span::styled(format!("  {}", m.source), theme.text_dim()));
+        lines.push(Line::from(spans));
+    }
+    for d in &report.disks {
+        let (health, color) = match d.health {
+            Health::Passed => (s.svc_disk_passed, theme.success),
+            Health::Failed => (s.svc_disk_failed, theme.error),
+            Health::Unknown => (s.svc_disk_unknown, theme.fg_dim),
+        };
+        let mut spans = vec![
+            Span::styled("  ▪ ", Style::default().fg(color)),
+            Span::styled(format!("{:<20}", d.name), theme.text()),
+            Span::styled(format!(" {:>8}", format_bytes(d.size)), theme.text_dim()),
+            Span::styled(format!(" SMART {:<10}", health), Style::default().fg(color)),
+        ];
+        if let Some((used, size)) = d.usage {
+            spans.push(Span::styled(
+                format!(" {}", usage(used, size)),
+                theme.text_dim(),
+            ));
+        }
+        if !d.model.is_empty() {
+            spans.push(Span::styled(format!("  {}", d.model), theme.text_dim()));
+        }
+        lines.push(Line::from(spans));
+    }
+    for array in &report.degraded {
+        lines.push(Line::from(vec![
+            Span::styled("  ✗ ", Style::default().fg(theme.error)),
+            Span::styled(format!("{:<20}", array.name), theme.text()),
+            Span::styled(
+                format!(" {} {}", s.svc_disk_degraded, array.detail),
+                Style::default().fg(theme.error),
+            ),
+        ]));
+    }
+    lines
+}
 
-            let enabled_str = match entry.enabled {
-                EnableState::Enabled => " ✓",
-                EnableState::Disabled => " ✗",
-                EnableState::NotApplicable => "",
-                _ => "",
-            };
+/// Status, name, enabled, ports, CPU, memory, description
+fn overview_columns(s: &i18n::Strings) -> [Column; 7] {
+    [
+        Column::fixed(s.svc_col_state, 5),
+        Column::fill(s.svc_col_name, 15),
+        Column::fixed(s.svc_col_enabled, 9).secondary(),
+        Column::fixed(s.svc_col_ports, 12),
+        Column::fixed(s.svc_col_cpu, 6).right().secondary(),
+        Column::fixed(s.svc_col_memory, 9).right().secondary(),
+        Column::fill(s.svc_col_description, 10),
+    ]
+}
 
-            // Truncate description to fit
-            let desc_width =
-                list_area.width as usize - name_width - 12 - port_str.len() - enabled_str.len();
-            let desc = truncate(&entry.description, desc_width);
+fn overview_sort_key(e: &ServiceEntry, col: usize) -> SortKey {
+    match col {
+        0 => SortKey::Num(e.status.sort_rank() as i64),
+        1 => SortKey::text(&e.display_name),
+        2 => SortKey::Num(match e.enabled {
+            EnableState::Enabled => 0,
+            EnableState::Disabled => 2,
+            _ => 1,
+        }),
+        3 => SortKey::Num(e.ports.first().map_or(i64::MAX, |&p| p as i64)),
+        // Unknown below zero, so it ends up last when sorted descending
+        4 => SortKey::Num(e.cpu_percent.map_or(-1, |p| (p * 10.0) as i64)),
+        5 => SortKey::Num(e.memory.map_or(-1, |m| m as i64)),
+        _ => SortKey::text(&e.description),
+    }
+}
 
-            ListItem::new(Line::from(vec![
-                Span::styled(
-                    if is_sel { " ▸" } else { "  " },
-                    Style::default().fg(theme.accent),
-                ),
-                Span::styled(format!("{} ", entry.status.symbol()), status_style),
-                Span::styled(format!("{} ", kind_icon), theme.text_dim()),
-                Span::styled(padded_name, line_style),
-                Span::styled(enabled_str, theme.text_dim()),
-                Span::styled(port_str, Style::default().fg(theme.accent)),
-                Span::styled(format!("  {}", desc), theme.text_dim()),
-            ]))
-        })
-        .collect();
+/// Protocol, port, address, owner, process, PID
+fn port_columns(s: &i18n::Strings) -> [Column; 6] {
+    [
+        Column::fixed(s.svc_col_proto, 6),
+        Column::fixed(s.svc_col_port, 6).right(),
+        Column::fixed(s.svc_col_address, 20).secondary(),
+        Column::fill(s.svc_col_owner, 20),
+        Column::fixed(s.svc_col_process, 14),
+        Column::fixed(s.svc_col_pid, 8).right().secondary(),
+    ]
+}
 
-    frame.render_widget(List::new(items), list_area);
+fn port_sort_key(p: &PortEntry, col: usize) -> SortKey {
+    match col {
+        0 => SortKey::text(&p.protocol),
+        1 => SortKey::Num(p.port as i64),
+        2 => SortKey::text(&p.address),
+        3 => SortKey::text(&p.owner),
+        4 => SortKey::text(&p.process_name),
+        _ => SortKey::Num(p.pid.map_or(i64::MAX, |pid| pid as i64)),
+    }
 }
 
 // ── Ports ──
@@ -968,6 +1864,34 @@ fn render_ports(
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
+    // Reverse proxy mappings below the port list
+    let inner = if state.proxies.is_empty() {
+        inner
+    } else {
+        let proxy_h = (state.proxies.len() as u16 + 3).min(inner.height / 2);
+        let split =
+            Layout::vertical([Constraint::Min(3), Constraint::Length(proxy_h)]).split(inner);
+        render_proxies(frame, state, theme, lang, split[1]);
+        split[0]
+    };
+
+    // Mismatches between the firewall and what listens, above the list
+    let warnings = state.port_warnings();
+    let inner = if warnings.is_empty() {
+        inner
+    } else {
+        let strip_h = (warnings.len().min(PORT_WARNINGS_SHOWN) as u16 + 2).min(inner.height / 2);
+        let split =
+            Layout::vertical([Constraint::Length(strip_h), Constraint::Min(3)]).split(inner);
+        render_port_warnings(frame, &warnings, theme, lang, split[0]);
+        split[1]
+    };
+    let duplicates: Vec<u16> = warnings
+        .iter()
+        .filter(|w| matches!(w, conflicts::PortWarning::Duplicate { .. }))
+        .map(|w| w.port())
+        .collect();
+
     if state.ports.is_empty() {
         let msg = Paragraph::new(vec![
             Line::raw(""),
@@ -978,86 +1902,171 @@ fn render_ports(
         return;
     }
 
-    // Header + list
-    let layout = Layout::vertical([
-        Constraint::Length(2), // Header row
-        Constraint::Min(3),    // Port list
-    ])
-    .split(inner);
+    let ports = state.sorted_ports();
+    let columns = port_columns(s);
+    Table::new(&columns, &state.ports_table, ports.len(), |i| {
+        let port = ports[i];
+        let proto_style = if port.protocol == "tcp" {
+            Style::default().fg(theme.success)
+        } else {
+            Style::default().fg(theme.warning)
+        };
+        let owner = if port.owner.is_empty() {
+            "-".to_string()
+        } else {
+            format!("{} {}", port.owner_kind.icon(), port.owner)
+        };
+        vec![
+            Cell::new(port.protocol.clone(), proto_style),
+            Cell::new(
+                port.port.to_string(),
+                Style::default()
+                    .fg(if duplicates.contains(&port.port) {
+                        theme.warning
+                    } else {
+                        theme.accent
+                    })
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Cell::new(port.address.clone(), theme.text()),
+            Cell::new(owner, theme.text()),
+            Cell::new(port.process_name.clone(), theme.text_dim()),
+            Cell::new(
+                port.pid.map(|p| p.to_string()).unwrap_or_default(),
+                theme.text_dim(),
+            ),
+        ]
+    })
+    .selected(Some(state.ports_selected))
+    .compact(state.compact)
+    .render(frame, theme, inner);
+}
 
-    let header = Line::from(vec![Span::styled(
+/// Port warnings listed before "… N more"
+const PORT_WARNINGS_SHOWN: usize = 4;
+
+fn render_port_warnings(
+    frame: &mut Frame,
+    warnings: &[conflicts::PortWarning],
+    theme: &Theme,
+    lang: Language,
+    area: Rect,
+) {
+    let s = i18n::get_strings(lang);
+    let warn = Style::default().fg(theme.warning);
+    let mut lines = vec![Line::styled(
         format!(
-            "  {:<7} {:<7} {:<20} {:<24} {}",
-            s.svc_col_proto, s.svc_col_port, s.svc_col_address, s.svc_col_owner, s.svc_col_process,
+            "  ⚠ {}",
+            s.svc_port_warnings
+                .replace("{}", &warnings.len().to_string())
         ),
+        warn.add_modifier(Modifier::BOLD),
+    )];
+    for warning in warnings.iter().take(PORT_WARNINGS_SHOWN) {
+        let (label, detail) = match warning {
+            conflicts::PortWarning::Duplicate {
+                protocol,
+                port,
+                listeners,
+            } => (
+                format!(":{}/{}", port, protocol),
+                format!("{}: {}", s.svc_port_duplicate, listeners.join(", ")),
+            ),
+            conflicts::PortWarning::NotListening { protocol, port } => (
+                format!(":{}/{}", port, protocol),
+                s.svc_port_not_listening.to_string(),
+            ),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("    {:<12} ", label), warn),
+            Span::styled(detail, theme.text()),
+        ]));
+    }
+    if warnings.len() > PORT_WARNINGS_SHOWN {
+        lines.push(Line::styled(
+            format!(
+                "    {}",
+                s.svc_port_more
+                    .replace("{}", &(warnings.len() - PORT_WARNINGS_SHOWN).to_string())
+            ),
+            theme.text_dim(),
+        ));
+    }
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+/// Domain → upstream port → owner, flagging proxies to ports nobody listens on
+fn render_proxies(
+    frame: &mut Frame,
+    state: &ServicesState,
+    theme: &Theme,
+    lang: Language,
+    area: Rect,
+) {
+    let s = i18n::get_strings(lang);
+
+    let dangling = state.dangling_proxies();
+    let mut title = vec![Span::styled(
+        format!("  {} ({})", s.svc_proxies, state.proxies.len()),
         Style::default()
             .fg(theme.accent)
             .add_modifier(Modifier::BOLD),
-    )]);
-    let sep = Line::styled(
-        format!("  {}", "─".repeat(inner.width.saturating_sub(4) as usize)),
-        theme.text_dim(),
-    );
-    frame.render_widget(Paragraph::new(vec![header, sep]), layout[0]);
-
-    // Port list
-    let visible = layout[1].height as usize;
-    let scroll = if state.ports_selected >= visible {
-        state.ports_selected - visible + 1
-    } else {
-        0
-    };
-
-    let items: Vec<ListItem> = state
-        .ports
-        .iter()
-        .enumerate()
-        .skip(scroll)
-        .take(visible)
-        .map(|(i, port)| {
-            let is_sel = i == state.ports_selected;
-            let style = if is_sel {
-                theme.selected()
-            } else {
-                theme.text()
-            };
-
-            let proto_style = if port.protocol == "tcp" {
-                Style::default().fg(theme.success)
-            } else {
-                Style::default().fg(theme.warning)
-            };
-
-            let owner_display = if port.owner.is_empty() {
-                "-".to_string()
-            } else {
-                let icon = port.owner_kind.icon();
-                format!("{} {}", icon, port.owner)
-            };
+    )];
+    if dangling > 0 {
+        title.push(Span::styled(
+            format!(
+                "  ⚠ {}",
+                s.svc_proxy_dangling_count
+                    .replace("{}", &dangling.to_string())
+            ),
+            Style::default().fg(theme.warning),
+        ));
+    }
 
-            let pid_str = port.pid.map(|p| p.to_string()).unwrap_or_default();
+    let mut lines = vec![
+        Line::styled(
+            format!("  {}", "─".repeat(area.width.saturating_sub(4) as usize)),
+            theme.text_dim(),
+        ),
+        Line::from(title),
+        Line::styled(
+            format!(
+                "  {:<8} {:<30} {:<28} {}",
+                "", s.svc_col_domain, s.svc_col_upstream, s.svc_col_owner
+            ),
+            theme.text_dim(),
+        ),
+    ];
 
-            ListItem::new(Line::from(vec![
-                Span::styled(
-                    if is_sel { " ▸" } else { "  " },
-                    Style::default().fg(theme.accent),
-                ),
-                Span::styled(format!("{:<7}", port.protocol), proto_style),
-                Span::styled(
-                    format!("{:<7}", port.port),
-                    Style::default()
-                        .fg(theme.accent)
-                        .add_modifier(Modifier::BOLD),
-                ),
-                Span::styled(format!("{:<20}", port.address), style),
-                Span::styled(format!("{:<24}", truncate(&owner_display, 23)), style),
-                Span::styled(format!("{:<12}", port.process_name), theme.text_dim()),
-                Span::styled(pid_str, theme.text_dim()),
-            ]))
-        })
-        .collect();
+    let visible = (area.height as usize).saturating_sub(lines.len());
+    for route in state.proxies.iter().take(visible) {
+        let (owner, owner_style) = match (state.proxy_target(route), route.port) {
+            (Some(port), _) if !port.owner.is_empty() => (
+                format!("{} {}", port.owner_kind.icon(), port.owner),
+                theme.text(),
+            ),
+            (Some(port), _) => (port.process_name.clone(), theme.text()),
+            (None, Some(_)) if route.local => (
+                format!("⚠ {}", s.svc_proxy_dangling),
+                Style::default().fg(theme.warning),
+            ),
+            _ => (s.svc_proxy_remote.to_string(), theme.text_dim()),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {:<8} ", route.proxy.label()), theme.text_dim()),
+            Span::styled(
+                format!("{:<30} ", truncate(&route.domain, 29)),
+                theme.text(),
+            ),
+            Span::styled(
+                format!("{:<28} ", truncate(&route.upstream, 27)),
+                Style::default().fg(theme.accent),
+            ),
+            Span::styled(owner, owner_style),
+        ]));
+    }
 
-    frame.render_widget(List::new(items), layout[1]);
+    frame.render_widget(Paragraph::new(lines), area);
 }
 
 // ── Manage ──
@@ -1094,6 +2103,7 @@ fn render_manage(
         let status_style = match entry.status {
             RunState::Running => Style::default().fg(theme.success),
             RunState::Failed => Style::default().fg(theme.error),
+            RunState::SocketIdle => Style::default().fg(theme.accent),
             _ => theme.text_dim(),
         };
 
@@ -1106,8 +2116,12 @@ fn render_manage(
 
         let mem_str = entry
             .memory
+            .map(|m| format!("  Mem: {}", format_bytes(m)))
+            .unwrap_or_default();
+        let socket_str = entry
+            .socket
             .as_deref()
-            .map(|m| format!("  Mem: {}", m))
+            .map(|sock| format!("  {}: {}", s.svc_socket_label, sock))
             .unwrap_or_default();
 
         let detail = Paragraph::new(vec![
@@ -1137,7 +2151,7 @@ fn render_manage(
                 theme.text_dim(),
             )]),
             Line::from(vec![Span::styled(
-                format!("{}{}", port_str, mem_str),
+                format!("{}{}{}", port_str, mem_str, socket_str),
                 theme.text_dim(),
             )]),
         ]);
@@ -1217,26 +2231,88 @@ fn render_logs(
     let s = i18n::get_strings(lang);
     let entry = state.selected_entry();
 
-    let entry_label = entry
-        .map(|e| format!("{} {} ", e.kind.icon(), e.display_name))
-        .unwrap_or_else(|| s.svc_no_selection.to_string());
+    let entry_label = if !state.logs_units.is_empty() {
+        state.logs_units.join(", ")
+    } else {
+        entry
+            .map(|e| format!("{} {} ", e.kind.icon(), e.display_name))
+            .unwrap_or_else(|| s.svc_no_selection.to_string())
+    };
+
+    // Active query, e.g. "tail 200 · since 2h · grep 'timeout'"
+    let q = &state.logs_query;
+    let mut query = vec![s.svc_logs_tail.replace("{}", &q.tail.to_string())];
+    match q.range {
+        LogRange::All => {}
+        LogRange::Boot => query.push(s.svc_logs_range_boot.to_string()),
+        LogRange::LastHour => query.push(s.svc_logs_range_hour.to_string()),
+        LogRange::Today => query.push(s.svc_logs_range_today.to_string()),
+    }
+    if let Some(v) = &q.since {
+        query.push(format!("{} {}", s.svc_logs_since, v));
+    }
+    if let Some(v) = &q.until {
+        query.push(format!("{} {}", s.svc_logs_until, v));
+    }
+    if let Some(v) = &q.grep {
+        query.push(format!("{} '{}'", s.svc_logs_grep, v));
+    }
+    if q.errors_only {
+        query.push(s.svc_logs_errors_only.to_string());
+    }
+    if state.logs_follow {
+        query.push(s.svc_logs_following.to_string());
+    }
 
     let block = Block::default()
         .style(theme.block_style())
-        .title(format!(" {} {} ", s.svc_logs_for, entry_label))
+        .title(format!(
+            " {} {} ({}) ",
+            s.svc_logs_for,
+            entry_label,
+            query.join(" · ")
+        ))
         .title_style(theme.title())
         .borders(Borders::ALL)
         .border_style(theme.border_focused());
 
-    let inner = block.inner(area);
+    let mut inner = block.inner(area);
     frame.render_widget(block, area);
 
+    if let Some((field, buf)) = &state.logs_input {
+        let prompt = match field {
+            LogField::Since => s.svc_logs_input_since,
+            LogField::Until => s.svc_logs_input_until,
+            LogField::Grep => s.svc_logs_input_grep,
+        };
+        frame.render_widget(
+            Paragraph::new(Line::from(vec![
+                Span::styled(format!("{} ", prompt), theme.text_dim()),
+                Span::styled(format!("{}█", buf), theme.text()),
+            ])),
+            Rect { height: 1, ..inner },
+        );
+        inner.y += 1;
+        inner.height = inner.height.saturating_sub(1);
+    }
+
+    if state.logs_loading && state.logs.is_empty() {
+        frame.render_widget(
+            Paragraph::new(Line::styled(s.svc_logs_loading, theme.text_dim()))
+                .alignment(Alignment::Center),
+            inner,
+        );
+        return;
+    }
+
     if state.logs.is_empty() {
-        let msg = Paragraph::new(vec![
-            Line::raw(""),
-            Line::styled(s.svc_no_logs, theme.text_dim()),
-        ])
-        .alignment(Alignment::Center);
+        let empty = if q.is_filtered() {
+            s.svc_logs_no_match
+        } else {
+            s.svc_no_logs
+        };
+        let msg = Paragraph::new(vec![Line::raw(""), Line::styled(empty, theme.text_dim())])
+            .alignment(Alignment::Center);
         frame.render_widget(msg, inner);
         return;
     }
@@ -1259,15 +2335,99 @@ fn render_logs(
                 } else {
                     theme.text()
                 };
-            Line::styled(line.as_str(), style)
+            let hit = Style::default()
+                .fg(theme.selection_fg)
+                .bg(theme.selection_bg)
+                .add_modifier(Modifier::BOLD);
+            let mut spans = Vec::new();
+            let mut pos = 0;
+            for range in match_ranges(line, q.grep.as_deref().unwrap_or("")) {
+                spans.push(Span::styled(&line[pos..range.start], style));
+                spans.push(Span::styled(&line[range.clone()], hit));
+                pos = range.end;
+            }
+            spans.push(Span::styled(&line[pos..], style));
+            Line::from(spans)
         })
         .collect();
 
     frame.render_widget(Paragraph::new(log_lines), inner);
 }
 
+/// Byte ranges of `needle` in `line`, ignoring ASCII case (journalctl's
+/// smart case and regexes aside, what the grep matched)
+fn match_ranges(line: &str, needle: &str) -> Vec<std::ops::Range<usize>> {
+    if needle.is_empty() {
+        return Vec::new();
+    }
+    let hay = line.to_ascii_lowercase();
+    let needle = needle.to_ascii_lowercase();
+    hay.match_indices(&needle)
+        .map(|(i, m)| i..i + m.len())
+        .collect()
+}
+
 // ── Popups ──
 
+fn render_unit_view(frame: &mut Frame, view: &UnitView, theme: &Theme, lang: Language, area: Rect) {
+    let s = i18n::get_strings(lang);
+    let block = Block::default()
+        .style(theme.block_style())
+        .title(format!(" {} ", view.unit))
+        .title_style(theme.title())
+        .borders(Borders::ALL)
+        .border_style(theme.border_focused());
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    // Header: where the file comes from and what restarts it
+    let source = match &view.source {
+        Source::Nixos => s
+            .svc_unit_src_nixos
+            .replace("{}", &view.namespaces.join(" · ")),
+        Source::Package(pkg) => s.svc_unit_src_package.replace("{}", pkg),
+        Source::Local => s.svc_unit_src_local.to_string(),
+    };
+    let mut header = vec![Line::from(vec![
+        Span::styled(format!(" {} ", s.svc_unit_source), theme.text_dim()),
+        Span::styled(source, Style::default().fg(theme.accent)),
+    ])];
+    if let Some(path) = &view.resolved {
+        header.push(Line::styled(format!(" → {}", path), theme.text_dim()));
+    }
+    if !view.triggers.is_empty() {
+        header.push(Line::from(vec![
+            Span::styled(format!(" {} ", s.svc_unit_triggers), theme.text_dim()),
+            Span::styled(view.triggers.join(", "), theme.text()),
+        ]));
+    }
+    let header_height = header.len() as u16 + 1;
+    if inner.height <= header_height {
+        return;
+    }
+    frame.render_widget(
+        Paragraph::new(header),
+        Rect {
+            height: header_height,
+            ..inner
+        },
+    );
+
+    let body = Rect {
+        y: inner.y + header_height,
+        height: inner.height - header_height,
+        ..inner
+    };
+    let lines: Vec<Line> = view
+        .lines
+        .iter()
+        .skip(view.scroll)
+        .take(body.height as usize)
+        .map(|l| unit_file::highlight(l, theme))
+        .collect();
+    frame.render_widget(Paragraph::new(lines), body);
+}
+
 fn render_popups(
     frame: &mut Frame,
     state: &ServicesState,
@@ -1318,6 +2478,83 @@ fn render_popups(
                 area,
             );
         }
+        SvcPopupState::RestartFailed {
+            units,
+            results,
+            started,
+            ..
+        } => {
+            let mut content = vec![Line::raw("")];
+            for (unit, result) in units.iter().zip(results) {
+                let (mark, detail, style) = match result {
+                    None if *started => ("…", String::new(), theme.text_dim()),
+                    None => ("↻", String::new(), theme.text()),
+                    Some(Ok(state)) => ("✓", state.clone(), theme.success()),
+                    Some(Err(e)) => ("✗", e.clone(), theme.error()),
+                };
+                content.push(Line::from(vec![
+                    Span::styled(format!("{} ", mark), style),
+                    Span::styled(unit.as_str(), theme.text()),
+                    Span::styled(format!("  {}", detail), style),
+                ]));
+            }
+            content.push(Line::raw(""));
+            let running = state.restart_rx.is_some();
+            let buttons: &[(&str, char)] = if !started {
+                content.push(Line::styled(s.svc_restart_sudo_note, theme.text_dim()));
+                &[(s.yes, 'y'), (s.svc_restart_and_follow, 'l'), (s.no, 'n')]
+            } else if running {
+                &[]
+            } else {
+                &[(s.ok, 'o')]
+            };
+            widgets::render_popup(
+                frame,
+                &s.svc_restart_failed_title
+                    .replace("{}", &units.len().to_string()),
+                content,
+                buttons,
+                theme,
+                area,
+            );
+        }
+        SvcPopupState::KillProcess {
+            pid,
+            process,
+            port,
+            sudo,
+        } => {
+            let mut content = vec![
+                Line::raw(""),
+                Line::from(vec![
+                    Span::styled(
+                        process.as_str(),
+                        Style::default()
+                            .fg(theme.accent)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(format!("  PID {}", pid), theme.text_dim()),
+                ]),
+                Line::raw(""),
+                Line::styled(
+                    format!("→ {}", s.svc_kill_action.replace("{}", &port.to_string())),
+                    theme.text(),
+                ),
+                Line::raw(""),
+                Line::styled(s.svc_confirm_action, theme.text()),
+            ];
+            if *sudo {
+                content.push(Line::styled(s.svc_sudo_note, theme.text_dim()));
+            }
+            widgets::render_popup(
+                frame,
+                s.svc_kill_title,
+                content,
+                &[(s.yes, 'y'), (s.no, 'n')],
+                theme,
+                area,
+            );
+        }
     }
 }
 
@@ -1345,6 +2582,7 @@ fn status_str(state: &RunState) -> &'static str {
         RunState::Paused => "paused",
         RunState::Created => "created",
         RunState::Exited => "exited",
+        RunState::SocketIdle => "socket-activated (idle)",
         RunState::Unknown => "unknown",
     }
 }
@@ -0,0 +1,173 @@
+//! Unit file viewer: `systemctl cat` plus where the unit came from.
+//!
+//! On NixOS `/etc/systemd/system/<unit>` resolves to
+//! `/nix/store/<hash>-unit-<unit>/<unit>`, written by `systemd.services`
+//! and usually by a `services.<name>` module on top of it. Units shipped
+//! by a package resolve into that package's store path instead. The
+//! `X-Restart-Triggers` entries NixOS adds name the store files (configs,
+//! scripts) whose change restarts the unit.
+
+use crate::ui::theme::Theme;
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+};
+
+/// Where a unit file was written
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    /// Generated from the NixOS configuration
+    Nixos,
+    /// Shipped by a package (name without hash)
+    Package(String),
+    /// Written outside Nix
+    Local,
+}
+
+/// A loaded unit file, shown over the Services tab
+#[derive(Debug, Clone)]
+pub struct UnitView {
+    pub unit: String,
+    pub lines: Vec<String>,
+    /// The file after following symlinks
+    pub resolved: Option<String>,
+    pub source: Source,
+    /// Store files named by `X-Restart-Triggers`
+    pub triggers: Vec<String>,
+    /// Option namespaces that may define the unit, most specific first
+    pub namespaces: Vec<String>,
+    pub scroll: usize,
+}
+
+/// `systemctl cat <unit>`
+pub fn load(unit: &str) -> Result<UnitView, String> {
+    let output = crate::nix::storage::output_with_timeout("systemctl", &["cat", "--", unit], 5)
+        .ok_or_else(|| "systemctl cat: timeout".to_string())?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    let text = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<String> = text.lines().map(str::to_string).collect();
+
+    // First line: "# /etc/systemd/system/nginx.service"
+    let resolved = lines
+        .first()
+        .and_then(|l| l.strip_prefix("# "))
+        .and_then(|p| std::fs::canonicalize(p.trim()).ok())
+        .map(|p| p.to_string_lossy().into_owned());
+    let source = resolved.as_deref().map_or(Source::Local, classify);
+
+    Ok(UnitView {
+        unit: unit.to_string(),
+        triggers: restart_triggers(&lines),
+        namespaces: namespaces(unit),
+        resolved,
+        source,
+        lines,
+        scroll: 0,
+    })
+}
+
+/// Kind of source from the resolved path of the unit file
+fn classify(path: &str) -> Source {
+    let Some(entry) = path
+        .strip_prefix("/nix/store/")
+        .and_then(|rest| rest.split('/').next())
+    else {
+        return Source::Local;
+    };
+    let name = entry.split_once('-').map_or(entry, |(_, name)| name);
+    if name.starts_with("unit-") || name == "system-units" {
+        Source::Nixos
+    } else {
+        Source::Package(name.to_string())
+    }
+}
+
+/// Store names from `X-Restart-Triggers=` lines (hash stripped)
+fn restart_triggers(lines: &[String]) -> Vec<String> {
+    lines
+        .iter()
+        .filter_map(|l| l.trim().strip_prefix("X-Restart-Triggers="))
+        .flat_map(str::split_whitespace)
+        .filter_map(|p| p.strip_prefix("/nix/store/"))
+        .map(|p| p.split_once('-').map_or(p, |(_, name)| name).to_string())
+        .collect()
+}
+
+/// "phpfpm-nextcloud.service" → services.phpfpm-nextcloud, services.phpfpm,
+/// systemd.services
+fn namespaces(unit: &str) -> Vec<String> {
+    let stem = unit.rsplit_once('.').map_or(unit, |(stem, _)| stem);
+    let stem = stem.split('@').next().unwrap_or(stem);
+    let mut result = vec![format!("services.{}", stem)];
+    if let Some((prefix, _)) = stem.split_once('-') {
+        result.push(format!("services.{}", prefix));
+    }
+    result.push("systemd.services".to_string());
+    result
+}
+
+/// One line of unit file syntax: sections, keys, comments
+pub fn highlight<'a>(line: &'a str, theme: &Theme) -> Line<'a> {
+    let trimmed = line.trim_start();
+    if trimmed.starts_with('#') || trimmed.starts_with(';') {
+        return Line::styled(line, theme.text_dim());
+    }
+    if trimmed.starts_with('[') {
+        return Line::styled(
+            line,
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        );
+    }
+    match line.split_once('=') {
+        Some((key, value)) => {
+            let value_style = if value.contains("/nix/store/") {
+                theme.text_dim()
+            } else {
+                theme.text()
+            };
+            Line::from(vec![
+                Span::styled(key, Style::default().fg(theme.accent_dim)),
+                Span::styled("=", theme.text_dim()),
+                Span::styled(value, value_style),
+            ])
+        }
+        None => Line::styled(line, theme.text()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unit_source_and_namespaces() {
+        assert_eq!(
+            classify("/nix/store/abc-unit-nginx.service/nginx.service"),
+            Source::Nixos
+        );
+        assert_eq!(
+            classify("/nix/store/abc-systemd-255.6/example/systemd/system/foo.service"),
+            Source::Package("systemd-255.6".into())
+        );
+        assert_eq!(classify("/etc/systemd/system/foo.service"), Source::Local);
+
+        assert_eq!(
+            namespaces("phpfpm-nextcloud.service"),
+            vec![
+                "services.phpfpm-nextcloud",
+                "services.phpfpm",
+                "systemd.services"
+            ]
+        );
+        assert_eq!(namespaces("getty@tty1.service")[0], "services.getty");
+
+        let lines = vec![
+            "X-Restart-Triggers=/nix/store/abc-nginx.conf /nix/store/def-unit-script".to_string(),
+        ];
+        assert_eq!(restart_triggers(&lines), vec!["nginx.conf", "unit-script"]);
+    }
+}
@@ -0,0 +1,75 @@
+//! Resource use in the Overview: CPU share between two loads (the
+//! auto-refresh tick or `r`) and the sort orders `s` steps through.
+
+use crate::nix::services::ServiceEntry;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// CPU time per entry at one load
+pub struct CpuSample {
+    at: Instant,
+    usec: HashMap<String, u64>,
+}
+
+/// Fill in `cpu_percent` from the CPU time used since `prev` and return
+/// this load's sample for the next one. The first load has no share yet.
+pub fn apply(entries: &mut [ServiceEntry], prev: Option<&CpuSample>) -> CpuSample {
+    let now = Instant::now();
+    for e in entries.iter_mut() {
+        e.cpu_percent = match (prev, e.cpu_usec) {
+            (Some(prev), Some(cur)) => prev
+                .usec
+                .get(&e.name)
+                .map(|&old| percent(old, cur, now.duration_since(prev.at))),
+            _ => None,
+        };
+    }
+    CpuSample {
+        at: now,
+        usec: entries
+            .iter()
+            .filter_map(|e| Some((e.name.clone(), e.cpu_usec?)))
+            .collect(),
+    }
+}
+
+/// Share of one core, like `top` (over 100 on several cores); a restart
+/// in between resets the counter and counts as 0
+fn percent(old_usec: u64, cur_usec: u64, elapsed: Duration) -> f64 {
+    let elapsed = elapsed.as_micros() as f64;
+    if elapsed <= 0.0 {
+        return 0.0;
+    }
+    cur_usec.saturating_sub(old_usec) as f64 * 100.0 / elapsed
+}
+
+/// Sort orders of `s`: (Overview column, descending) — name, memory, CPU,
+/// status
+pub const SORTS: [(usize, bool); 4] = [(1, false), (5, true), (4, true), (0, false)];
+
+/// The sort after `current`; from any other order the first one
+pub fn next_sort(current: (Option<usize>, bool)) -> (usize, bool) {
+    let pos = SORTS
+        .iter()
+        .position(|&(col, desc)| (Some(col), desc) == current);
+    match pos {
+        Some(i) => SORTS[(i + 1) % SORTS.len()],
+        None => SORTS[0],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percent_and_sorts() {
+        assert_eq!(percent(1_000_000, 1_500_000, Duration::from_secs(1)), 50.0);
+        assert_eq!(percent(2_000_000, 6_000_000, Duration::from_secs(2)), 200.0);
+        assert_eq!(percent(5_000, 1_000, Duration::from_secs(1)), 0.0);
+
+        assert_eq!(next_sort((None, false)), SORTS[0]);
+        assert_eq!(next_sort((Some(1), false)), (5, true));
+        assert_eq!(next_sort((Some(0), false)), (1, false));
+    }
+}
@@ -5,9 +5,13 @@
 //! - Kitty Graphics Protocol (Kitty, WezTerm, Ghostty)
 //! - iTerm2 Inline Images (iTerm2, WezTerm)
 //! - Fallback: no image for unsupported terminals
+//!
+//! On later launches a start screen shows the Nix Doctor score, its trend
+//! and the top issues while the checks run in the background.
 
 use crate::config::Language;
 use crate::i18n;
+use crate::modules::health::{self, HealthState, Severity};
 use crate::ui::Theme;
 use ratatui::{
     layout::Alignment,
@@ -348,6 +352,144 @@ pub fn render_welcome(
     })
 }
 
+// ─── Start Screen (Doctor summary) ──────────────────────────────────
+
+pub struct StartState {
+    pub active: bool,
+    pub created_at: Instant,
+}
+
+impl StartState {
+    pub fn new(show: bool) -> Self {
+        Self {
+            active: show,
+            created_at: Instant::now(),
+        }
+    }
+
+    pub fn dismiss(&mut self) {
+        self.active = false;
+    }
+
+    pub fn ready_for_input(&self) -> bool {
+        self.created_at.elapsed().as_millis() >= 400
+    }
+}
+
+/// Issues listed on the start screen
+const START_TOP_ISSUES: usize = 3;
+
+pub fn render_start(frame: &mut Frame, health: &HealthState, theme: &Theme, lang: Language) {
+    let area = frame.area();
+    let s = i18n::get_strings(lang);
+
+    frame.render_widget(Block::default().style(theme.block_style()), area);
+
+    let mut lines: Vec<Line<'static>> = vec![
+        Line::from(vec![
+            Span::styled(
+                "nixmate",
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("  v{}", env!("CARGO_PKG_VERSION")),
+                Style::default().fg(theme.fg_dim),
+            ),
+        ]),
+        Line::raw(""),
+    ];
+
+    if !health.scanned {
+        let (done, total) = health.scan_progress();
+        lines.push(Line::styled(
+            format!("⏳ {}", s.health_scanning),
+            Style::default().fg(theme.fg),
+        ));
+        lines.push(Line::styled(
+            s.health_scan_progress
+                .replacen("{}", &done.to_string(), 1)
+                .replacen("{}", &total.to_string(), 1),
+            Style::default().fg(theme.fg_dim),
+        ));
+    } else {
+        let score = health.health_score();
+        let (color, label) = health::score_rating(score, theme, s);
+        let mut score_line = vec![
+            Span::styled(
+                format!("{} ", s.health_score_label),
+                Style::default().fg(theme.fg_dim),
+            ),
+            Span::styled(
+                format!("{}%", score),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(format!("  — {}", label), Style::default().fg(color)),
+        ];
+        if let Some(trend) = health.trend() {
+            score_line.push(Span::styled(
+                format!("   {}", trend.label()),
+                Style::default().fg(theme.fg_dim),
+            ));
+        }
+        lines.push(Line::from(score_line));
+        lines.push(Line::raw(""));
+
+        let issues = health.top_issues(START_TOP_ISSUES);
+        if issues.is_empty() {
+            lines.push(Line::styled(
+                s.start_no_issues,
+                Style::default().fg(theme.success),
+            ));
+        } else {
+            lines.push(Line::styled(
+                s.start_top_issues,
+                Style::default().fg(theme.fg).add_modifier(Modifier::BOLD),
+            ));
+            for c in issues {
+                let (icon, color) = match c.severity {
+                    Severity::Fail => ("✗", theme.error),
+                    _ => ("⚠", theme.warning),
+                };
+                let detail = truncate(&c.detail, 50);
+                lines.push(Line::from(vec![
+                    Span::styled(format!("{} ", icon), Style::default().fg(color)),
+                    Span::styled(c.name.clone(), Style::default().fg(theme.fg)),
+                    Span::styled(format!("  {}", detail), Style::default().fg(theme.fg_dim)),
+                ]));
+            }
+        }
+    }
+    lines.push(Line::raw(""));
+
+    lines.push(Line::from(vec![
+        Span::styled("── ", Style::default().fg(theme.fg_dim)),
+        Span::styled(
+            s.start_open_doctor,
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!("  ·  {}", s.start_continue),
+            Style::default().fg(theme.fg_dim),
+        ),
+        Span::styled(" ──", Style::default().fg(theme.fg_dim)),
+    ]));
+
+    let top_pad = area.height.saturating_sub(lines.len() as u16) / 2;
+    let mut padded: Vec<Line<'static>> = vec![Line::raw(""); top_pad as usize];
+    padded.extend(lines);
+
+    frame.render_widget(
+        Paragraph::new(padded)
+            .alignment(Alignment::Center)
+            .style(theme.block_style()),
+        area,
+    );
+}
+
 // ─── Help Tab: Image Area ───────────────────────────────────────────
 
 pub fn help_image_area(
@@ -370,6 +512,15 @@ pub fn help_image_area(
 
 // ─── Helpers ────────────────────────────────────────────────────────
 
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        text.to_string()
+    } else {
+        let cut: String = text.chars().take(max_chars.saturating_sub(1)).collect();
+        format!("{}…", cut)
+    }
+}
+
 fn word_wrap(text: &str, max_width: usize) -> Vec<String> {
     let mut lines = Vec::new();
     let mut current = String::new();
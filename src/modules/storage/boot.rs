@@ -0,0 +1,364 @@
+//! /boot analysis — kernels and initrds per boot entry
+//!
+//! NixOS copies every generation's kernel and initrd to /boot: systemd-boot
+//! to `EFI/nixos/` with one `loader/entries/nixos-generation-N.conf` per
+//! entry, GRUB to `kernels/` referenced from `grub/grub.cfg`. Store GC
+//! doesn't touch them, and a full /boot breaks the next rebuild. Files in
+//! those directories no entry references are left over (a bootloader
+//! install that failed halfway, entries removed by hand) and safe to
+//! delete. Every path in an entry counts (`devicetree`, `efi`, … too), and
+//! an entry that can't be read or has no kernel line stops the analysis:
+//! whatever it references would otherwise show up as orphaned.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+const BOOT: &str = "/boot";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Loader {
+    SystemdBoot,
+    Grub,
+}
+
+/// One boot menu entry
+#[derive(Debug, Clone)]
+pub struct BootEntry {
+    /// None for GRUB's "NixOS - Default"
+    pub generation: Option<u32>,
+    pub specialisation: Option<String>,
+    /// Kernel file name (without directory)
+    pub kernel: String,
+    /// Kernel + initrd(s)
+    pub size: u64,
+    /// Kernel or initrd also used by other entries (deleting this entry
+    /// doesn't free them)
+    pub shared: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BootFile {
+    pub path: String,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct BootAnalysis {
+    pub loader: Loader,
+    /// Newest generation first
+    pub entries: Vec<BootEntry>,
+    /// Kernel/initrd files no entry references
+    pub orphans: Vec<BootFile>,
+    /// Everything in the kernel directory
+    pub total: u64,
+    /// Free space on the /boot filesystem
+    pub free: Option<u64>,
+}
+
+impl BootAnalysis {
+    pub fn orphan_bytes(&self) -> u64 {
+        self.orphans.iter().map(|f| f.size).sum()
+    }
+}
+
+/// Read /boot. Err with a reason when there is nothing to analyze.
+pub fn analyze() -> Result<BootAnalysis, String> {
+    analyze_at(Path::new(BOOT))
+}
+
+fn analyze_at(boot: &Path) -> Result<BootAnalysis, String> {
+    let (loader, kernel_dir, refs, others) = if boot.join("loader/entries").is_dir() {
+        let (refs, others) = read_systemd_entries(&boot.join("loader/entries"))?;
+        (Loader::SystemdBoot, boot.join("EFI/nixos"), refs, others)
+    } else if boot.join("grub/grub.cfg").is_file() {
+        let cfg = boot.join("grub/grub.cfg");
+        let text =
+            std::fs::read_to_string(&cfg).map_err(|e| format!("{}: {}", cfg.display(), e))?;
+        (
+            Loader::Grub,
+            boot.join("kernels"),
+            parse_grub_cfg(&text),
+            Vec::new(),
+        )
+    } else {
+        return Err(format!("{}: no systemd-boot or GRUB files", boot.display()));
+    };
+
+    let files: HashMap<String, u64> = std::fs::read_dir(&kernel_dir)
+        .map_err(|e| format!("{}: {}", kernel_dir.display(), e))?
+        .flatten()
+        .filter_map(|e| {
+            let size = e.metadata().ok().filter(|m| m.is_file())?.len();
+            Some((e.file_name().to_string_lossy().to_string(), size))
+        })
+        .collect();
+
+    let mut analysis = build(loader, &kernel_dir.to_string_lossy(), refs, &others, &files);
+    analysis.free =
+        crate::nix::storage::parse_disk_usage(&boot.to_string_lossy()).map(|u| u.available);
+    Ok(analysis)
+}
+
+/// NixOS entries, and the paths of other entries (memtest, another
+/// distribution), which are kept but not listed
+fn read_systemd_entries(dir: &Path) -> Result<(Vec<EntryRefs>, Vec<String>), String> {
+    let mut refs = Vec::new();
+    let mut others = Vec::new();
+    let entries = std::fs::read_dir(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("{}: {}", dir.display(), e))?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !name.ends_with(".conf") {
+            continue;
+        }
+        let path = entry.path();
+        let text =
+            std::fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let paths = parse_systemd_entry(&text);
+        if paths.is_empty() {
+            return Err(format!("{}: no linux or efi line", path.display()));
+        }
+        match parse_entry_name(&name) {
+            Some((generation, specialisation)) => refs.push((generation, specialisation, paths)),
+            None => others.extend(paths),
+        }
+    }
+    Ok((refs, others))
+}
+
+/// Delete orphaned files (`sudo -n`: no password prompt inside the TUI).
+/// Returns the bytes freed.
+pub fn remove_orphans(orphans: &[BootFile]) -> Result<u64, String> {
+    if orphans.is_empty() {
+        return Ok(0);
+    }
+    let output = std::process::Command::new("sudo")
+        .args(["-n", "rm", "-f", "--"])
+        .args(orphans.iter().map(|f| f.path.as_str()))
+        .output()
+        .map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(orphans.iter().map(|f| f.size).sum())
+}
+
+type EntryRefs = (Option<u32>, Option<String>, Vec<String>);
+
+fn build(
+    loader: Loader,
+    kernel_dir: &str,
+    refs: Vec<EntryRefs>,
+    others: &[String],
+    files: &HashMap<String, u64>,
+) -> BootAnalysis {
+    let mut uses: HashMap<&str, usize> = HashMap::new();
+    for (_, _, paths) in &refs {
+        for p in paths {
+            *uses.entry(base_name(p)).or_default() += 1;
+        }
+    }
+    for p in others {
+        *uses.entry(base_name(p)).or_default() += 1;
+    }
+
+    let mut entries: Vec<BootEntry> = refs
+        .iter()
+        .map(|(generation, specialisation, paths)| {
+            let names: Vec<&str> = paths.iter().map(|p| base_name(p)).collect();
+            BootEntry {
+                generation: *generation,
+                specialisation: specialisation.clone(),
+                kernel: names.first().unwrap_or(&"?").to_string(),
+                size: names.iter().filter_map(|n| files.get(*n)).sum(),
+                shared: names.iter().any(|n| uses.get(n).copied().unwrap_or(0) > 1),
+            }
+        })
+        .collect();
+    entries.sort_by(|a, b| {
+        b.generation
+            .cmp(&a.generation)
+            .then_with(|| a.specialisation.cmp(&b.specialisation))
+    });
+
+    let mut orphans: Vec<BootFile> = files
+        .iter()
+        .filter(|(name, _)| !uses.contains_key(name.as_str()))
+        .map(|(name, size)| BootFile {
+            path: format!("{}/{}", kernel_dir, name),
+            size: *size,
+        })
+        .collect();
+    orphans.sort_by(|a, b| a.path.cmp(&b.path));
+
+    BootAnalysis {
+        loader,
+        entries,
+        orphans,
+        total: files.values().sum(),
+        free: None,
+    }
+}
+
+fn base_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+/// "nixos-generation-42-specialisation-work.conf" → (42, Some("work"))
+fn parse_entry_name(name: &str) -> Option<(Option<u32>, Option<String>)> {
+    let rest = name
+        .strip_prefix("nixos-generation-")?
+        .strip_suffix(".conf")?;
+    let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+    let specialisation = rest[digits.len()..]
+        .strip_prefix("-specialisation-")
+        .map(str::to_string);
+    Some((Some(digits.parse().ok()?), specialisation))
+}
+
+/// Every path of a systemd-boot entry (`linux`, `initrd`, `efi`,
+/// `devicetree`, `devicetree-overlay`, …), kernel first. Empty without a
+/// `linux` or `efi` line.
+fn parse_systemd_entry(text: &str) -> Vec<String> {
+    let mut kernel = Vec::new();
+    let mut others = Vec::new();
+    for line in text.lines().map(str::trim) {
+        let Some((key, value)) = line.split_once(char::is_whitespace) else {
+            continue;
+        };
+        // `options` are kernel parameters (init=/nix/store/…), not files
+        if line.starts_with('#') || key == "options" {
+            continue;
+        }
+        let paths = value
+            .split_whitespace()
+            .filter(|w| w.starts_with('/'))
+            .map(str::to_string);
+        if matches!(key, "linux" | "efi") {
+            kernel.extend(paths);
+        } else {
+            others.extend(paths);
+        }
+    }
+    if kernel.is_empty() {
+        return Vec::new();
+    }
+    kernel.extend(others);
+    kernel
+}
+
+/// Paths into `/kernels/` (kernel, initrd, device tree) per `menuentry`
+/// of a NixOS grub.cfg
+fn parse_grub_cfg(text: &str) -> Vec<EntryRefs> {
+    let mut result: Vec<EntryRefs> = Vec::new();
+    for line in text.lines().map(str::trim) {
+        if let Some(title) = line.strip_prefix("menuentry ") {
+            // "NixOS - Configuration 42 (2024-05-01 - 24.05…)"
+            let generation = title
+                .split("Configuration ")
+                .nth(1)
+                .and_then(|r| r.split_whitespace().next())
+                .and_then(|n| n.parse().ok());
+            result.push((generation, None, Vec::new()));
+        } else if let Some((_, _, paths)) = result.last_mut() {
+            paths.extend(
+                line.split_whitespace()
+                    .filter(|w| w.contains("/kernels/"))
+                    .map(str::to_string),
+            );
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entries_and_orphans() {
+        let entry = "title NixOS\nversion Generation 42\nlinux /EFI/nixos/aaa-linux-6.6-bzImage.efi\ninitrd /EFI/nixos/bbb-initrd-linux-6.6-initrd.efi\noptions init=/nix/store/x/init\n";
+        let paths = parse_systemd_entry(entry);
+        assert_eq!(paths.len(), 2);
+        assert_eq!(
+            parse_entry_name("nixos-generation-42-specialisation-work.conf"),
+            Some((Some(42), Some("work".to_string())))
+        );
+
+        let refs = vec![(Some(42), None, paths.clone()), (Some(41), None, paths)];
+        let files: HashMap<String, u64> = [
+            ("aaa-linux-6.6-bzImage.efi", 10),
+            ("bbb-initrd-linux-6.6-initrd.efi", 20),
+            ("old-linux-6.1-bzImage.efi", 5),
+        ]
+        .into_iter()
+        .map(|(n, s)| (n.to_string(), s))
+        .collect();
+        let analysis = build(Loader::SystemdBoot, "/boot/EFI/nixos", refs, &[], &files);
+        assert_eq!(analysis.entries[0].generation, Some(42));
+        assert_eq!(analysis.entries[0].size, 30);
+        assert!(analysis.entries[0].shared);
+        assert_eq!(analysis.orphans.len(), 1);
+        assert_eq!(analysis.orphan_bytes(), 5);
+
+        let dtb = "linux /EFI/nixos/k.efi\ninitrd /EFI/nixos/i.efi\ndevicetree /EFI/nixos/x-dtbs/board.dtb\noptions init=/nix/store/x/init root=/dev/sda1\n";
+        assert_eq!(
+            parse_systemd_entry(dtb),
+            vec![
+                "/EFI/nixos/k.efi",
+                "/EFI/nixos/i.efi",
+                "/EFI/nixos/x-dtbs/board.dtb"
+            ]
+        );
+        assert!(parse_systemd_entry("title NixOS\ninitrd /EFI/nixos/i.efi\n").is_empty());
+
+        let grub = "menuentry \"NixOS - Configuration 7 (2024-05-01)\" {\n  linux /kernels/k-bzImage init=/x\n  initrd /kernels/i-initrd\n}\n";
+        assert_eq!(parse_grub_cfg(grub)[0].0, Some(7));
+        assert_eq!(parse_grub_cfg(grub)[0].2.len(), 2);
+    }
+
+    #[test]
+    fn test_analyze_boot_dir() {
+        let boot = std::env::temp_dir().join(format!("nixmate-boot-{}", std::process::id()));
+        let entries = boot.join("loader/entries");
+        let kernels = boot.join("EFI/nixos");
+        std::fs::create_dir_all(&entries).unwrap();
+        std::fs::create_dir_all(&kernels).unwrap();
+        for file in ["k.efi", "i.efi", "board.dtb", "memtest.efi", "old.efi"] {
+            std::fs::write(kernels.join(file), "x").unwrap();
+        }
+        std::fs::write(
+            entries.join("nixos-generation-3.conf"),
+            "linux /EFI/nixos/k.efi
+initrd /EFI/nixos/i.efi
+devicetree /EFI/nixos/board.dtb
+",
+        )
+        .unwrap();
+        std::fs::write(
+            entries.join("memtest86.conf"),
+            "efi /EFI/nixos/memtest.efi
+",
+        )
+        .unwrap();
+
+        let analysis = analyze_at(&boot).unwrap();
+        assert_eq!(analysis.entries.len(), 1);
+        let orphans: Vec<&str> = analysis
+            .orphans
+            .iter()
+            .map(|f| base_name(&f.path))
+            .collect();
+        assert_eq!(orphans, vec!["old.efi"]);
+
+        // An entry that can't be understood stops the analysis
+        std::fs::write(
+            entries.join("nixos-generation-2.conf"),
+            "title broken
+",
+        )
+        .unwrap();
+        assert!(analyze_at(&boot).is_err());
+        let _ = std::fs::remove_dir_all(&boot);
+    }
+}
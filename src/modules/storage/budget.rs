@@ -0,0 +1,360 @@
+//! Budget mode — "keep N GB free".
+//!
+//! Builds an ordered cleanup plan predicted to reach the free-space target:
+//!   1. collect garbage (dead paths)
+//!   2. remove `result` symlinks that pin builds as GC roots
+//!   3. delete system generations older than N days
+//!   4. optimise the store (savings can't be predicted)
+//!
+//! Steps are only added until the prediction reaches the target.
+//! Estimates for 2 and 3 are the sizes of store paths that no other GC root
+//! keeps alive. Blocking — run in background thread!
+
+use crate::nix::generations::endangered_by_cleanup;
+use crate::nix::storage::{self, StoreInfo};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+pub(super) const PROFILES_DIR: &str = "/nix/var/nix/profiles";
+
+/// Age thresholds tried for generation deletion, most conservative first
+const AGE_STEPS: &[u32] = &[90, 60, 30, 14, 7, 3];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StepKind {
+    CollectGarbage,
+    /// Symlinks to remove before collecting garbage
+    RemoveResults(Vec<PathBuf>),
+    /// Delete generations older than N days (sudo)
+    DeleteOlderThan {
+        days: u32,
+        generations: usize,
+    },
+    Optimise,
+}
+
+impl StepKind {
+    pub fn needs_sudo(&self) -> bool {
+        matches!(self, StepKind::DeleteOlderThan { .. })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StepStatus {
+    Pending,
+    Running,
+    /// Bytes actually freed
+    Done(u64),
+    Failed(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct PlanStep {
+    pub kind: StepKind,
+    /// Predicted bytes freed (None = can't be predicted)
+    pub estimate: Option<u64>,
+    pub status: StepStatus,
+}
+
+#[derive(Debug, Clone)]
+pub struct BudgetPlan {
+    /// Free bytes when the plan was made
+    pub available: u64,
+    pub target: u64,
+    pub steps: Vec<PlanStep>,
+}
+
+impl BudgetPlan {
+    /// Bytes that still have to be freed
+    pub fn needed(&self) -> u64 {
+        self.target.saturating_sub(self.available)
+    }
+
+    pub fn predicted(&self) -> u64 {
+        self.steps.iter().filter_map(|s| s.estimate).sum()
+    }
+
+    pub fn reaches_target(&self) -> bool {
+        self.predicted() >= self.needed()
+    }
+
+    /// Index of the next step to run
+    pub fn next_pending(&self) -> Option<usize> {
+        self.steps
+            .iter()
+            .position(|s| s.status == StepStatus::Pending)
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.steps.iter().any(|s| s.status == StepStatus::Running)
+    }
+}
+
+/// Build a plan for `target` free bytes from already loaded store info
+pub fn build_plan(info: &StoreInfo, target: u64) -> BudgetPlan {
+    let available = storage::free_space()
+        .or(info.disk_root.as_ref().map(|d| d.available))
+        .unwrap_or(0);
+    plan_for(info, target, available)
+}
+
+fn plan_for(info: &StoreInfo, target: u64, available: u64) -> BudgetPlan {
+    let mut plan = BudgetPlan {
+        available,
+        target,
+        steps: Vec::new(),
+    };
+    if plan.needed() == 0 {
+        return plan;
+    }
+
+    let sizes: HashMap<&str, u64> = info
+        .paths
+        .iter()
+        .map(|p| (p.path.as_str(), p.size))
+        .collect();
+    let size_of = |paths: &HashSet<String>| -> u64 {
+        paths
+            .iter()
+            .filter_map(|p| sizes.get(p.as_str()))
+            .sum::<u64>()
+    };
+
+    // 1. Dead paths
+    if info.dead_paths > 0 {
+        plan.steps.push(step(
+            StepKind::CollectGarbage,
+            info.has_sizes.then_some(info.dead_size),
+        ));
+        if plan.reaches_target() {
+            return plan;
+        }
+    }
+
+    let roots = storage::gc_roots();
+
+    // 2. result symlinks
+    let results: Vec<&(PathBuf, String)> = roots.iter().filter(|(l, _)| is_result(l)).collect();
+    if !results.is_empty() {
+        let kept: Vec<&str> = roots
+            .iter()
+            .filter(|(l, _)| !is_result(l))
+            .map(|(_, t)| t.as_str())
+            .collect();
+        let freed = storage::closure(results.iter().map(|(_, t)| t.as_str()))
+            .difference(&storage::closure(kept))
+            .cloned()
+            .collect();
+        plan.steps.push(step(
+            StepKind::RemoveResults(results.iter().map(|(l, _)| l.clone()).collect()),
+            info.has_sizes.then(|| size_of(&freed)),
+        ));
+        if plan.reaches_target() {
+            return plan;
+        }
+    }
+
+    // 3. Old generations: the most conservative age that reaches the target
+    let gens = system_generations();
+    let now = std::time::SystemTime::now();
+    let candidates = AGE_STEPS
+        .iter()
+        // Shorter ages would delete the booted / boot-default generation too
+        .take_while(|&&days| endangered_by_cleanup(Some(days)).is_none())
+        .filter_map(|&days| {
+            let cutoff = now - std::time::Duration::from_secs(days as u64 * 86400);
+            let old: Vec<&Generation> = gens
+                .iter()
+                .filter(|g| !g.is_current && g.modified < cutoff)
+                .collect();
+            if old.is_empty() {
+                return None;
+            }
+            let old_links: HashSet<&Path> = old.iter().map(|g| g.link.as_path()).collect();
+            let kept: Vec<&str> = roots
+                .iter()
+                .filter(|(l, _)| !is_result(l) && !old_links.contains(l.as_path()))
+                .map(|(_, t)| t.as_str())
+                .collect();
+            let freed = storage::closure(old.iter().map(|g| g.target.as_str()))
+                .difference(&storage::closure(kept))
+                .cloned()
+                .collect();
+            Some(step(
+                StepKind::DeleteOlderThan {
+                    days,
+                    generations: old.len(),
+                },
+                info.has_sizes.then(|| size_of(&freed)),
+            ))
+        });
+    if let Some(s) = pick_generation_step(candidates, plan.predicted(), plan.needed()) {
+        plan.steps.push(s);
+        if plan.reaches_target() {
+            return plan;
+        }
+    }
+
+    // 4. Optimise — whatever it frees is a bonus
+    plan.steps.push(step(StepKind::Optimise, None));
+    plan
+}
+
+/// The first (most conservative) candidate that reaches `needed` on top of
+/// `predicted`, else the last one, which frees the most
+fn pick_generation_step(
+    candidates: impl Iterator<Item = PlanStep>,
+    predicted: u64,
+    needed: u64,
+) -> Option<PlanStep> {
+    let mut best = None;
+    for candidate in candidates {
+        let reaches = predicted + candidate.estimate.unwrap_or(0) >= needed;
+        best = Some(candidate);
+        if reaches {
+            break;
+        }
+    }
+    best
+}
+
+/// A `result` symlink of a build (not a profile)
+pub(super) fn is_result(link: &Path) -> bool {
+    !link.starts_with(PROFILES_DIR)
+        && link
+            .file_name()
+            .is_some_and(|n| n.to_string_lossy().starts_with("result"))
+}
+
+/// Remove the symlinks among `links` that point into the store
+pub(super) fn remove_store_links(links: &[PathBuf]) -> Result<(), String> {
+    for link in links {
+        let is_store_link = std::fs::read_link(link).is_ok_and(|t| t.starts_with("/nix/store"));
+        if is_store_link {
+            std::fs::remove_file(link).map_err(|e| format!("{}: {}", link.display(), e))?;
+        }
+    }
+    Ok(())
+}
+
+/// Execute one step, returning the bytes freed
+pub fn run_step(kind: &StepKind) -> Result<u64, String> {
+    match kind {
+        StepKind::CollectGarbage => storage::run_gc()
+            .map(|r| r.bytes_freed)
+            .map_err(|e| e.to_string()),
+        StepKind::RemoveResults(links) => {
+            remove_store_links(links)?;
+            storage::run_gc()
+                .map(|r| r.bytes_freed)
+                .map_err(|e| e.to_string())
+        }
+        StepKind::DeleteOlderThan { days, .. } => storage::run_delete_system_older_than(*days)
+            .map(|r| r.bytes_freed)
+            .map_err(|e| e.to_string()),
+        StepKind::Optimise => storage::run_optimise()
+            .map(|r| r.bytes_saved)
+            .map_err(|e| e.to_string()),
+    }
+}
+
+fn step(kind: StepKind, estimate: Option<u64>) -> PlanStep {
+    PlanStep {
+        kind,
+        estimate,
+        status: StepStatus::Pending,
+    }
+}
+
+pub(super) struct Generation {
+    pub link: PathBuf,
+    pub target: String,
+    pub modified: std::time::SystemTime,
+    pub is_current: bool,
+}
+
+/// System profile generations. Age is the link's mtime, as used by
+/// `nix-collect-garbage --delete-older-than`.
+pub(super) fn system_generations() -> Vec<Generation> {
+    let current = std::fs::read_link(format!("{}/system", PROFILES_DIR))
+        .ok()
+        .and_then(|t| t.file_name().map(|f| f.to_string_lossy().to_string()));
+    let Ok(entries) = std::fs::read_dir(PROFILES_DIR) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter_map(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            name.strip_prefix("system-")?.strip_suffix("-link")?;
+            let link = e.path();
+            let target = std::fs::read_link(&link).ok()?;
+            let modified = std::fs::symlink_metadata(&link).ok()?.modified().ok()?;
+            Some(Generation {
+                is_current: current.as_deref() == Some(name.as_str()),
+                target: target.to_string_lossy().to_string(),
+                link,
+                modified,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GB: u64 = 1 << 30;
+
+    fn delete_step(days: u32, estimate: u64) -> PlanStep {
+        step(
+            StepKind::DeleteOlderThan {
+                days,
+                generations: 1,
+            },
+            Some(estimate),
+        )
+    }
+
+    #[test]
+    fn test_plan_target_already_met() {
+        let plan = plan_for(&StoreInfo::default(), 10 * GB, 20 * GB);
+        assert_eq!(plan.needed(), 0);
+        assert!(plan.steps.is_empty());
+        assert!(plan.reaches_target());
+    }
+
+    #[test]
+    fn test_plan_garbage_is_enough() {
+        let info = StoreInfo {
+            dead_paths: 100,
+            dead_size: 6 * GB,
+            has_sizes: true,
+            ..Default::default()
+        };
+        let plan = plan_for(&info, 10 * GB, 5 * GB);
+        assert_eq!(plan.steps.len(), 1);
+        assert_eq!(plan.steps[0].kind, StepKind::CollectGarbage);
+        assert!(plan.reaches_target());
+        assert_eq!(plan.next_pending(), Some(0));
+    }
+
+    #[test]
+    fn test_pick_generation_step() {
+        let candidates = || {
+            vec![
+                delete_step(90, GB),
+                delete_step(60, 3 * GB),
+                delete_step(30, 8 * GB),
+            ]
+            .into_iter()
+        };
+        // The oldest cutoff that reaches the target
+        let picked = pick_generation_step(candidates(), GB, 4 * GB).unwrap();
+        assert_eq!(picked.estimate, Some(3 * GB));
+        // None reaches it: the one that frees the most
+        let picked = pick_generation_step(candidates(), 0, 20 * GB).unwrap();
+        assert_eq!(picked.estimate, Some(8 * GB));
+        assert!(pick_generation_step(std::iter::empty(), 0, GB).is_none());
+    }
+}
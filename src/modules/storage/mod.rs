@@ -1,17 +1,36 @@
 //! Storage module — Nix Store Analysis & Cleanup
 //!
-//! Sub-tabs: Dashboard, Explorer, Clean, History.
+//! Sub-tabs: Dashboard, Explorer, Clean, Budget, Suggestions, History.
 //! Shows disk usage, store path analysis, cleanup tools, and history.
+//! Budget plans cleanup steps to reach a free-space target.
+//! Suggestions lists what can be cleaned up, each with the space it frees,
+//! and applies a selection in one go (see `suggest`).
 
-use crate::config::Language;
+pub(crate) mod boot;
+pub(crate) mod budget;
+mod ranking;
+pub(crate) mod suggest;
+
+use crate::config::{Language, TimeFormat};
 use crate::i18n;
+use crate::modules::generations::boot_role_reason;
+use crate::modules::jobs::Job;
+use crate::modules::module::{Module, RenderCtx};
+use crate::modules::palette::PaletteCommand;
+use crate::nix::generations;
 use crate::nix::storage::{self, CleanAction, DiskUsage, HistoryEntry, StoreInfo, StorePath};
-use crate::types::format_bytes;
+use crate::session::{self, ModuleSession};
+use crate::staged::StagedKind;
 use crate::types::FlashMessage;
+use crate::types::{format_bytes, format_stored_time};
+use crate::ui::table::{Cell, Column, SortKey, Table, TableState};
 use crate::ui::theme::Theme;
 use crate::ui::widgets;
 use anyhow::Result;
+use boot::{BootAnalysis, BootEntry};
+use budget::{BudgetPlan, StepKind, StepStatus};
 use crossterm::event::{KeyCode, KeyEvent};
+use ranking::{Origin, Ranked, Ranking};
 use ratatui::{
     layout::{Alignment, Constraint, Layout, Rect},
     style::{Modifier, Style},
@@ -20,6 +39,8 @@ use ratatui::{
     Frame,
 };
 use std::sync::mpsc;
+use std::time::Instant;
+use suggest::{Suggestion, SuggestionKind};
 
 // ── Sub-tabs ──
 
@@ -28,7 +49,11 @@ pub enum StoSubTab {
     #[default]
     Dashboard,
     Explorer,
+    Ranking,
     Clean,
+    Boot,
+    Budget,
+    Suggestions,
     History,
 }
 
@@ -37,7 +62,11 @@ impl StoSubTab {
         &[
             StoSubTab::Dashboard,
             StoSubTab::Explorer,
+            StoSubTab::Ranking,
             StoSubTab::Clean,
+            StoSubTab::Boot,
+            StoSubTab::Budget,
+            StoSubTab::Suggestions,
             StoSubTab::History,
         ]
     }
@@ -46,8 +75,12 @@ impl StoSubTab {
         match self {
             StoSubTab::Dashboard => 0,
             StoSubTab::Explorer => 1,
-            StoSubTab::Clean => 2,
-            StoSubTab::History => 3,
+            StoSubTab::Ranking => 2,
+            StoSubTab::Clean => 3,
+            StoSubTab::Boot => 4,
+            StoSubTab::Budget => 5,
+            StoSubTab::Suggestions => 6,
+            StoSubTab::History => 7,
         }
     }
 
@@ -56,7 +89,11 @@ impl StoSubTab {
         match self {
             StoSubTab::Dashboard => s.sto_dashboard,
             StoSubTab::Explorer => s.sto_explorer,
+            StoSubTab::Ranking => s.sto_ranking,
             StoSubTab::Clean => s.sto_clean,
+            StoSubTab::Boot => s.sto_boot,
+            StoSubTab::Budget => s.sto_budget,
+            StoSubTab::Suggestions => s.sto_suggestions,
             StoSubTab::History => s.sto_history,
         }
     }
@@ -83,8 +120,19 @@ impl StoSubTab {
 #[derive(Debug, Clone)]
 pub enum StoPopupState {
     None,
-    ConfirmAction { action: CleanAction },
-    ActionResult { title: String, message: String },
+    ConfirmAction {
+        action: CleanAction,
+    },
+    /// Index into the budget plan
+    ConfirmBudgetStep(usize),
+    /// Apply the selected suggestions
+    ConfirmSuggestions,
+    /// Delete the orphaned files in /boot
+    ConfirmBootClean,
+    ActionResult {
+        title: String,
+        message: String,
+    },
 }
 
 // ── Explorer filter ──
@@ -127,6 +175,8 @@ pub struct StorageState {
     pub load_error: Option<String>,
     pub loaded: bool,
     pub loading: bool,
+    /// When the shown snapshot was taken (for TTL / auto-refresh)
+    pub loaded_at: Option<Instant>,
     load_rx: Option<mpsc::Receiver<StoreInfo>>,
 
     // Explorer
@@ -135,15 +185,47 @@ pub struct StorageState {
     pub explorer_search: String,
     pub explorer_search_active: bool,
 
+    // Ranking (closure size per package / GC root)
+    pub ranking: Option<Ranking>,
+    pub ranking_scanning: bool,
+    pub ranking_selected: usize,
+    pub ranking_table: TableState,
+    ranking_rx: Option<mpsc::Receiver<Ranking>>,
+
+    // /boot analysis (read on first view; cheap enough to do inline)
+    pub boot: Option<Result<BootAnalysis, String>>,
+    pub boot_selected: usize,
+    pub boot_table: TableState,
+    why_rx: Option<mpsc::Receiver<(String, Result<String, String>)>>,
+
     // Clean
     pub clean_selected: usize,
 
+    // Budget
+    pub free_target_gb: u64,
+    pub budget: Option<BudgetPlan>,
+    pub budget_planning: bool,
+    budget_rx: Option<mpsc::Receiver<BudgetPlan>>,
+    budget_step_rx: Option<mpsc::Receiver<(usize, Result<u64, String>)>>,
+
+    // Suggestions; applying sends each one's result as it finishes
+    pub suggestions: Option<Vec<Suggestion>>,
+    pub suggestions_scanning: bool,
+    pub suggestion_selected: usize,
+    suggestions_rx: Option<mpsc::Receiver<Vec<Suggestion>>>,
+    suggestions_apply_rx: Option<mpsc::Receiver<(SuggestionKind, Result<u64, String>)>>,
+
     // History
     pub history_scroll: usize,
 
     // Popup & flash
     pub popup: StoPopupState,
+    /// A confirmed cleanup for App's undo window (see `staged`)
+    pub pending_staged: Option<(String, StagedKind)>,
     pub lang: Language,
+    pub time_format: TimeFormat,
+    /// Terminal below the compact thresholds (set on resize)
+    pub compact: bool,
     pub flash_message: Option<FlashMessage>,
 }
 
@@ -158,15 +240,38 @@ impl StorageState {
             load_error: None,
             loaded: false,
             loading: false,
+            loaded_at: None,
             load_rx: None,
             explorer_selected: 0,
             explorer_filter: ExplorerFilter::default(),
             explorer_search: String::new(),
             explorer_search_active: false,
+            ranking: None,
+            ranking_scanning: false,
+            ranking_selected: 0,
+            ranking_table: TableState::default(),
+            ranking_rx: None,
+            boot: None,
+            boot_selected: 0,
+            boot_table: TableState::default(),
+            why_rx: None,
             clean_selected: 0,
+            free_target_gb: 50,
+            budget: None,
+            budget_planning: false,
+            budget_rx: None,
+            budget_step_rx: None,
+            suggestions: None,
+            suggestions_scanning: false,
+            suggestion_selected: 0,
+            suggestions_rx: None,
+            suggestions_apply_rx: None,
             history_scroll: 0,
             popup: StoPopupState::None,
             lang: Language::English,
+            time_format: TimeFormat::Locale,
+            compact: false,
+            pending_staged: None,
             flash_message: None,
         }
     }
@@ -176,6 +281,17 @@ impl StorageState {
         if self.loaded || self.loading {
             return;
         }
+        self.spawn_load();
+    }
+
+    /// Reload in the background, keeping the current data on screen
+    pub fn refresh_in_background(&mut self) {
+        if !self.loading {
+            self.spawn_load();
+        }
+    }
+
+    fn spawn_load(&mut self) {
         self.loading = true;
         let (tx, rx) = mpsc::channel();
         self.load_rx = Some(rx);
@@ -191,6 +307,7 @@ impl StorageState {
             match rx.try_recv() {
                 Ok(info) => {
                     self.info = info;
+                    self.loaded_at = Some(Instant::now());
                     self.loaded = true;
                     self.loading = false;
                     self.load_rx = None;
@@ -210,6 +327,480 @@ impl StorageState {
                 }
             }
         }
+
+        if let Some(ref rx) = self.budget_rx {
+            match rx.try_recv() {
+                Ok(plan) => {
+                    self.budget = Some(plan);
+                    self.budget_planning = false;
+                    self.budget_rx = None;
+                }
+                Err(mpsc::TryRecvError::Empty) => {}
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.budget_planning = false;
+                    self.budget_rx = None;
+                }
+            }
+        }
+
+        self.poll_budget_step();
+
+        if let Some(ref rx) = self.suggestions_rx {
+            match rx.try_recv() {
+                Ok(list) => {
+                    self.suggestions = Some(list);
+                    self.suggestion_selected = 0;
+                    self.suggestions_scanning = false;
+                    self.suggestions_rx = None;
+                }
+                Err(mpsc::TryRecvError::Empty) => {}
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.suggestions_scanning = false;
+                    self.suggestions_rx = None;
+                }
+            }
+        }
+        self.poll_suggestions_apply();
+    }
+
+    /// Rank closures in the background the first time the tab is shown
+    pub fn ensure_ranking(&mut self) {
+        if self.ranking.is_none() && !self.ranking_scanning {
+            self.start_ranking();
+        }
+    }
+
+    fn start_ranking(&mut self) {
+        self.ranking_scanning = true;
+        let (tx, rx) = mpsc::channel();
+        self.ranking_rx = Some(rx);
+        std::thread::spawn(move || {
+            let _ = tx.send(ranking::scan());
+        });
+    }
+
+    /// Collect the ranking and why-depends results. Called from
+    /// update_timers.
+    pub fn poll_ranking(&mut self) {
+        if let Some(rx) = &self.ranking_rx {
+            match rx.try_recv() {
+                Ok(ranking) => {
+                    self.ranking = Some(ranking);
+                    self.ranking_scanning = false;
+                    self.ranking_rx = None;
+                }
+                Err(mpsc::TryRecvError::Empty) => {}
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.ranking_scanning = false;
+                    self.ranking_rx = None;
+                }
+            }
+        }
+
+        if let Some(rx) = &self.why_rx {
+            let (name, result) = match rx.try_recv() {
+                Ok(answer) => answer,
+                Err(mpsc::TryRecvError::Empty) => return,
+                Err(mpsc::TryRecvError::Disconnected) => (
+                    String::new(),
+                    Err(crate::i18n::get_strings(self.lang)
+                        .thread_crashed
+                        .to_string()),
+                ),
+            };
+            self.why_rx = None;
+            let s = crate::i18n::get_strings(self.lang);
+            // Only if the popup is still waiting for it
+            if let StoPopupState::ActionResult { title, message } = &mut self.popup {
+                if title.as_str() == s.sto_why_title.replace("{}", &name) {
+                    *message = result.unwrap_or_else(|e| e);
+                }
+            }
+        }
+    }
+
+    /// Ranking entries in display order
+    fn sorted_ranking(&self) -> Vec<&Ranked> {
+        let entries = self
+            .ranking
+            .as_ref()
+            .map(|r| r.entries.iter().collect())
+            .unwrap_or_default();
+        self.ranking_table.sorted(entries, ranking_sort_key)
+    }
+
+    /// Trace why the running system pulls in the selected entry
+    fn start_why_depends(&mut self) {
+        let Some(entry) = self.sorted_ranking().get(self.ranking_selected).copied() else {
+            return;
+        };
+        let (name, path) = (entry.name.clone(), entry.path.clone());
+        let s = crate::i18n::get_strings(self.lang);
+        self.popup = StoPopupState::ActionResult {
+            title: s.sto_why_title.replace("{}", &name),
+            message: s.sto_why_running.to_string(),
+        };
+        let (tx, rx) = mpsc::channel();
+        self.why_rx = Some(rx);
+        std::thread::spawn(move || {
+            let _ = tx.send((name, ranking::why_depends(&path)));
+        });
+    }
+
+    /// Read /boot the first time the tab is shown
+    pub fn ensure_boot(&mut self) {
+        if self.boot.is_none() {
+            self.boot = Some(boot::analyze());
+        }
+    }
+
+    fn boot_orphans(&self) -> &[boot::BootFile] {
+        match &self.boot {
+            Some(Ok(a)) => &a.orphans,
+            _ => &[],
+        }
+    }
+
+    /// Hand the confirmed orphans to App's undo window
+    fn clean_boot(&mut self) {
+        let orphans = self.boot_orphans().to_vec();
+        if orphans.is_empty() {
+            return;
+        }
+        let s = crate::i18n::get_strings(self.lang);
+        self.pending_staged = Some((
+            s.sto_boot_clean_title.to_string(),
+            StagedKind::BootOrphans(orphans),
+        ));
+    }
+
+    /// Staged orphans whose undo window has passed. /boot is read again
+    /// and only files that are still orphaned are deleted: a rebuild in
+    /// the meantime may reference them now.
+    pub fn run_staged_boot_clean(&mut self, staged: Vec<boot::BootFile>) {
+        let s = crate::i18n::get_strings(self.lang);
+        let orphans: Vec<boot::BootFile> = match boot::analyze() {
+            Ok(analysis) => staged
+                .into_iter()
+                .filter(|f| analysis.orphans.contains(f))
+                .collect(),
+            Err(e) => {
+                self.boot = Some(Err(e.clone()));
+                self.popup = StoPopupState::ActionResult {
+                    title: s.sto_boot_clean_title.to_string(),
+                    message: s.sto_boot_unavailable.replace("{}", &e),
+                };
+                return;
+            }
+        };
+        let message = match boot::remove_orphans(&orphans) {
+            Ok(freed) => {
+                let _ = storage::save_history_entry(HistoryEntry {
+                    timestamp: chrono::Local::now().format("%Y-%m-%d %H:%M").to_string(),
+                    action: s.sto_boot_action.to_string(),
+                    freed_bytes: freed,
+                    paths_removed: orphans.len(),
+                });
+                self.history = storage::load_history();
+                s.sto_boot_cleaned
+                    .replacen("{}", &orphans.len().to_string(), 1)
+                    .replacen("{}", &format_bytes(freed), 1)
+            }
+            Err(e) => {
+                let paths: Vec<&str> = orphans.iter().map(|f| f.path.as_str()).collect();
+                format!(
+                    "{}\n\n{}",
+                    e,
+                    s.sto_boot_clean_failed
+                        .replace("{}", &format!("sudo rm -- {}", paths.join(" ")))
+                )
+            }
+        };
+        self.popup = StoPopupState::ActionResult {
+            title: s.sto_boot_clean_title.to_string(),
+            message,
+        };
+        self.boot = Some(boot::analyze());
+        self.boot_selected = 0;
+    }
+
+    /// Plan the budget steps in the background once store info is loaded
+    pub fn ensure_budget_plan(&mut self) {
+        if !self.loaded || self.budget.is_some() || self.budget_planning {
+            return;
+        }
+        self.start_budget_plan();
+    }
+
+    fn start_budget_plan(&mut self) {
+        self.budget_planning = true;
+        let (tx, rx) = mpsc::channel();
+        self.budget_rx = Some(rx);
+        let info = self.info.clone();
+        let target = self.free_target_gb * 1024 * 1024 * 1024;
+        std::thread::spawn(move || {
+            let _ = tx.send(budget::build_plan(&info, target));
+        });
+    }
+
+    /// Look for cleanup suggestions in the background once store info is loaded
+    pub fn ensure_suggestions(&mut self) {
+        if !self.loaded || self.suggestions.is_some() || self.suggestions_scanning {
+            return;
+        }
+        self.start_suggestions();
+    }
+
+    fn start_suggestions(&mut self) {
+        self.suggestions_scanning = true;
+        let (tx, rx) = mpsc::channel();
+        self.suggestions_rx = Some(rx);
+        let info = self.info.clone();
+        std::thread::spawn(move || {
+            let _ = tx.send(suggest::build(&info));
+        });
+    }
+
+    fn suggestions_applying(&self) -> bool {
+        self.suggestions_apply_rx.is_some()
+    }
+
+    /// Hand the selected suggestions to App's undo window
+    fn apply_suggestions(&mut self) {
+        let batch: Vec<SuggestionKind> = self
+            .suggestions
+            .iter()
+            .flatten()
+            .filter(|s| s.selected && s.status == StepStatus::Pending)
+            .map(|s| s.kind.clone())
+            .collect();
+        if batch.is_empty() {
+            return;
+        }
+        let s = crate::i18n::get_strings(self.lang);
+        let label = s.sto_sugg_staged.replace("{}", &batch.len().to_string());
+        self.pending_staged = Some((label, StagedKind::Suggestions(batch)));
+    }
+
+    /// A staged batch whose undo window has passed, applied one after
+    /// another in the background. Suggestions that already ran (staged
+    /// twice) are skipped.
+    pub fn run_staged_suggestions(&mut self, batch: Vec<SuggestionKind>) {
+        let list = self.suggestions.as_deref_mut().unwrap_or_default();
+        let batch: Vec<SuggestionKind> = batch
+            .into_iter()
+            .filter(|k| {
+                !list
+                    .iter()
+                    .any(|s| &s.kind == k && s.status != StepStatus::Pending)
+            })
+            .collect();
+        if batch.is_empty() || self.suggestions_apply_rx.is_some() {
+            return;
+        }
+        if let Some(first) = list.iter_mut().find(|s| s.kind == batch[0]) {
+            first.status = StepStatus::Running;
+        }
+        let lang = self.lang;
+        let (tx, rx) = mpsc::channel();
+        self.suggestions_apply_rx = Some(rx);
+        std::thread::spawn(move || {
+            for kind in batch {
+                let result = suggest::run(&kind, lang);
+                if tx.send((kind, result)).is_err() {
+                    return;
+                }
+            }
+        });
+    }
+
+    /// Progress of the batch; once it's through, log it and reload
+    fn poll_suggestions_apply(&mut self) {
+        let Some(rx) = &self.suggestions_apply_rx else {
+            return;
+        };
+        let mut finished = false;
+        loop {
+            match rx.try_recv() {
+                Ok((kind, result)) => {
+                    let Some(list) = self.suggestions.as_mut() else {
+                        continue;
+                    };
+                    if let Some(item) = list.iter_mut().find(|s| s.kind == kind) {
+                        item.status = match result {
+                            Ok(freed) => StepStatus::Done(freed),
+                            Err(e) => StepStatus::Failed(e),
+                        };
+                    }
+                    if let Some(next) = list
+                        .iter_mut()
+                        .find(|s| s.selected && s.status == StepStatus::Pending)
+                    {
+                        next.status = StepStatus::Running;
+                    }
+                }
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    finished = true;
+                    break;
+                }
+            }
+        }
+        if !finished {
+            return;
+        }
+        self.suggestions_apply_rx = None;
+        let s = crate::i18n::get_strings(self.lang);
+        let list = self.suggestions.as_deref_mut().unwrap_or_default();
+        for item in list.iter_mut().filter(|i| i.status == StepStatus::Running) {
+            item.status = StepStatus::Failed(s.thread_crashed.to_string());
+        }
+        let done: Vec<u64> = list
+            .iter()
+            .filter_map(|i| match i.status {
+                StepStatus::Done(freed) if i.selected => Some(freed),
+                _ => None,
+            })
+            .collect();
+        if !done.is_empty() {
+            let _ = storage::save_history_entry(HistoryEntry {
+                timestamp: chrono::Local::now().format("%Y-%m-%d %H:%M").to_string(),
+                action: s.sto_sugg_history.replace("{}", &done.len().to_string()),
+                freed_bytes: done.iter().sum(),
+                paths_removed: 0,
+            });
+            self.history = storage::load_history();
+            // Sizes and plans from before the cleanup are stale now
+            self.budget = None;
+            self.refresh_in_background();
+        }
+    }
+
+    /// A confirmed plan step: deleting ones wait out App's undo window,
+    /// optimising (nothing is lost) starts right away
+    fn run_budget_step(&mut self, idx: usize) {
+        let Some(kind) = self
+            .budget
+            .as_ref()
+            .and_then(|p| p.steps.get(idx))
+            .map(|s| s.kind.clone())
+        else {
+            return;
+        };
+        if kind == StepKind::Optimise {
+            return self.start_budget_step(idx);
+        }
+        if self.refuse_budget_step(idx) {
+            return;
+        }
+        let s = crate::i18n::get_strings(self.lang);
+        let label = format!("{}: {}", s.sto_budget, budget_step_title(&kind, s));
+        self.pending_staged = Some((label, StagedKind::BudgetStep(kind)));
+    }
+
+    /// A staged step whose undo window has passed. Skipped if it already
+    /// ran (staged twice) or the plan was made again in the meantime.
+    pub fn run_staged_budget_step(&mut self, kind: StepKind) {
+        let idx = self.budget.as_ref().and_then(|p| {
+            p.steps
+                .iter()
+                .position(|s| s.kind == kind && s.status == StepStatus::Pending)
+        });
+        if let Some(idx) = idx {
+            self.start_budget_step(idx);
+        }
+    }
+
+    /// Whether deleting generations would now hit the booted or default
+    /// one (the plan was safe when made; a reboot or rebuild may change
+    /// that). Marks the step failed if so.
+    fn refuse_budget_step(&mut self, idx: usize) -> bool {
+        let Some(step) = self.budget.as_mut().and_then(|p| p.steps.get_mut(idx)) else {
+            return true;
+        };
+        let StepKind::DeleteOlderThan { days, .. } = step.kind else {
+            return false;
+        };
+        let Some((id, role)) = generations::endangered_by_cleanup(Some(days)) else {
+            return false;
+        };
+        let s = crate::i18n::get_strings(self.lang);
+        step.status = StepStatus::Failed(
+            s.gen_cleanup_refused
+                .replace("{}", &boot_role_reason(id, role, self.lang)),
+        );
+        true
+    }
+
+    /// Run a plan step in the background
+    fn start_budget_step(&mut self, idx: usize) {
+        if self.budget_step_rx.is_some() || self.refuse_budget_step(idx) {
+            return;
+        }
+        let Some(step) = self.budget.as_mut().and_then(|p| p.steps.get_mut(idx)) else {
+            return;
+        };
+        step.status = StepStatus::Running;
+        let kind = step.kind.clone();
+        let (tx, rx) = mpsc::channel();
+        self.budget_step_rx = Some(rx);
+        std::thread::spawn(move || {
+            let _ = tx.send((idx, budget::run_step(&kind)));
+        });
+    }
+
+    fn poll_budget_step(&mut self) {
+        let Some(ref rx) = self.budget_step_rx else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok((idx, result)) => {
+                self.budget_step_rx = None;
+                self.finish_budget_step(idx, result);
+            }
+            Err(mpsc::TryRecvError::Empty) => {}
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.budget_step_rx = None;
+                let msg = crate::i18n::get_strings(self.lang).thread_crashed;
+                if let Some(step) = self
+                    .budget
+                    .as_mut()
+                    .and_then(|p| p.steps.iter_mut().find(|s| s.status == StepStatus::Running))
+                {
+                    step.status = StepStatus::Failed(msg.to_string());
+                }
+            }
+        }
+    }
+
+    /// On quit: let cleanups that already started finish instead of
+    /// ending them with the process
+    pub fn wait_for_cleanups(&mut self) {
+        while self.budget_step_rx.is_some() || self.suggestions_applying() {
+            self.poll_budget_step();
+            self.poll_suggestions_apply();
+            std::thread::sleep(std::time::Duration::from_millis(50));
+        }
+    }
+
+    fn finish_budget_step(&mut self, idx: usize, result: Result<u64, String>) {
+        let s = crate::i18n::get_strings(self.lang);
+        let Some(step) = self.budget.as_mut().and_then(|p| p.steps.get_mut(idx)) else {
+            return;
+        };
+        match result {
+            Ok(freed) => {
+                step.status = StepStatus::Done(freed);
+                let _ = storage::save_history_entry(HistoryEntry {
+                    timestamp: chrono::Local::now().format("%Y-%m-%d %H:%M").to_string(),
+                    action: format!("{}: {}", s.sto_budget, budget_step_title(&step.kind, s)),
+                    freed_bytes: freed,
+                    paths_removed: 0,
+                });
+                self.history = storage::load_history();
+            }
+            Err(e) => step.status = StepStatus::Failed(e),
+        }
     }
 
     pub fn refresh(&mut self) {
@@ -218,6 +809,7 @@ impl StorageState {
 
         self.info = storage::load_store_info();
         self.history = storage::load_history();
+        self.loaded_at = Some(Instant::now());
         self.loaded = true;
         self.explorer_selected = 0;
     }
@@ -246,10 +838,38 @@ impl StorageState {
         self.flash_message = Some(FlashMessage::new(msg.to_string(), is_error));
     }
 
-    fn execute_action(&mut self, action: CleanAction) {
+    /// Deleting actions wait out App's undo window; optimising (nothing is
+    /// lost) and a refused full clean happen right away
+    fn stage_action(&mut self, action: CleanAction) {
+        let s = crate::i18n::get_strings(self.lang);
+        let label = match action {
+            CleanAction::GarbageCollect => s.sto_gc_title,
+            CleanAction::FullClean if generations::endangered_by_cleanup(None).is_none() => {
+                s.sto_full_title
+            }
+            _ => return self.execute_action(action),
+        };
+        self.pending_staged = Some((label.to_string(), StagedKind::Clean(action)));
+    }
+
+    /// Run a cleanup action (staged ones once their undo window has passed)
+    pub fn execute_action(&mut self, action: CleanAction) {
         let s = crate::i18n::get_strings(self.lang);
         let now = chrono::Local::now().format("%Y-%m-%d %H:%M").to_string();
 
+        // `-d` spares only the profile's current generation
+        if matches!(action, CleanAction::FullClean) {
+            if let Some((id, role)) = generations::endangered_by_cleanup(None) {
+                self.popup = StoPopupState::ActionResult {
+                    title: s.stor_fullclean_title.to_string(),
+                    message: s
+                        .gen_cleanup_refused
+                        .replace("{}", &boot_role_reason(id, role, self.lang)),
+                };
+                return;
+            }
+        }
+
         match action {
             CleanAction::GarbageCollect => match storage::run_gc() {
                 Ok(result) => {
@@ -321,6 +941,139 @@ impl StorageState {
         self.history = storage::load_history();
     }
 
+    /// Explorer filter and search for the session file
+    pub fn session(&self) -> ModuleSession {
+        ModuleSession {
+            query: self.explorer_search.clone(),
+            filter: session::cycle_index(
+                ExplorerFilter::All,
+                self.explorer_filter,
+                ExplorerFilter::next,
+            ),
+            ..Default::default()
+        }
+    }
+
+    pub fn restore_session(&mut self, saved: &ModuleSession) {
+        self.explorer_search = saved.query.clone();
+        self.explorer_filter =
+            session::cycle_nth(ExplorerFilter::All, saved.filter, ExplorerFilter::next);
+    }
+
+    /// Actions offered in the command palette
+    /// Running background work, for the jobs overlay
+    pub fn jobs(&self, s: &i18n::Strings) -> Vec<Job> {
+        let mut jobs = Vec::new();
+        if self.loading {
+            jobs.push(Job::new("load", s.job_sto_load, true));
+        }
+        if self.budget_planning {
+            jobs.push(Job::new("budget", s.job_sto_budget, true));
+        }
+        if self.ranking_scanning {
+            jobs.push(Job::new("ranking", s.job_sto_ranking, true));
+        }
+        if self.why_rx.is_some() {
+            jobs.push(Job::new("why", s.job_sto_why, true));
+        }
+        if self.budget_step_rx.is_some() {
+            // Deleting generations / collecting garbage goes on regardless
+            jobs.push(Job::new("budget_step", s.job_sto_budget_step, false));
+        }
+        if self.suggestions_scanning {
+            jobs.push(Job::new("suggestions", s.job_sto_suggestions, true));
+        }
+        if let Some(list) = self
+            .suggestions
+            .as_ref()
+            .filter(|_| self.suggestions_applying())
+        {
+            let batch: Vec<&Suggestion> = list.iter().filter(|i| i.selected).collect();
+            let done = batch
+                .iter()
+                .filter(|i| matches!(i.status, StepStatus::Done(_) | StepStatus::Failed(_)))
+                .count();
+            jobs.push(
+                Job::new("suggestions_apply", s.job_sto_sugg_apply, false)
+                    .progress(done, batch.len()),
+            );
+        }
+        jobs
+    }
+
+    /// Stop waiting for a job from `jobs()`; its result is discarded
+    pub fn cancel_job(&mut self, id: &str) {
+        let s = i18n::get_strings(self.lang);
+        match id {
+            "load" => {
+                self.load_rx = None;
+                self.loading = false;
+                if !self.loaded {
+                    self.loaded = true;
+                    self.load_error = Some(s.jobs_cancelled_error.to_string());
+                }
+            }
+            "budget" => {
+                self.budget_rx = None;
+                self.budget_planning = false;
+            }
+            "ranking" => {
+                self.ranking_rx = None;
+                self.ranking_scanning = false;
+            }
+            "why" => self.why_rx = None,
+            "suggestions" => {
+                self.suggestions_rx = None;
+                self.suggestions_scanning = false;
+            }
+            _ => {}
+        }
+    }
+
+    pub fn palette_commands(&self, s: &i18n::Strings) -> Vec<PaletteCommand> {
+        let budget = Some(StoSubTab::Budget.index());
+        let actions = CleanAction::all();
+        let mut commands: Vec<PaletteCommand> = actions
+            .iter()
+            .enumerate()
+            .map(|(i, action)| {
+                let label = match action {
+                    CleanAction::GarbageCollect => s.sto_gc_title,
+                    CleanAction::Optimise => s.sto_optimise_title,
+                    CleanAction::FullClean => s.sto_full_title,
+                };
+                // Select the action in the Clean list, then confirm
+                let mut keys = vec![KeyCode::Up; actions.len()];
+                keys.extend(vec![KeyCode::Down; i]);
+                keys.push(KeyCode::Enter);
+                PaletteCommand::new(label, Some(StoSubTab::Clean.index()), keys)
+            })
+            .collect();
+        commands.extend([
+            PaletteCommand::new(
+                s.pal_sto_search,
+                Some(StoSubTab::Explorer.index()),
+                vec![KeyCode::Char('/')],
+            ),
+            PaletteCommand::new(s.pal_sto_ranking, Some(StoSubTab::Ranking.index()), vec![]),
+            PaletteCommand::new(
+                s.pal_sto_why,
+                Some(StoSubTab::Ranking.index()),
+                vec![KeyCode::Char('w')],
+            ),
+            PaletteCommand::new(s.pal_sto_boot, Some(StoSubTab::Boot.index()), vec![]),
+            PaletteCommand::new(s.pal_sto_budget_step, budget, vec![KeyCode::Char('x')]),
+            PaletteCommand::new(s.pal_sto_budget_plan, budget, vec![KeyCode::Char('p')]),
+            PaletteCommand::new(
+                s.pal_sto_suggestions,
+                Some(StoSubTab::Suggestions.index()),
+                vec![],
+            ),
+            PaletteCommand::new(s.pal_sto_history, Some(StoSubTab::History.index()), vec![]),
+        ]);
+        commands
+    }
+
     pub fn handle_key(&mut self, key: KeyEvent) -> Result<()> {
         // Flash expiry
         if let Some(msg) = &self.flash_message {
@@ -336,7 +1089,7 @@ impl StorageState {
                 match key.code {
                     KeyCode::Char('y') | KeyCode::Enter => {
                         self.popup = StoPopupState::None;
-                        self.execute_action(action);
+                        self.stage_action(action);
                     }
                     KeyCode::Char('n') | KeyCode::Esc => {
                         self.popup = StoPopupState::None;
@@ -345,39 +1098,154 @@ impl StorageState {
                 }
                 return Ok(());
             }
-            StoPopupState::ActionResult { .. } => {
+            StoPopupState::ConfirmBootClean => {
                 match key.code {
-                    KeyCode::Enter | KeyCode::Esc | KeyCode::Char('o') => {
+                    KeyCode::Char('y') | KeyCode::Enter => {
+                        self.popup = StoPopupState::None;
+                        self.clean_boot();
+                    }
+                    KeyCode::Char('n') | KeyCode::Esc => {
                         self.popup = StoPopupState::None;
                     }
                     _ => {}
                 }
                 return Ok(());
             }
-            StoPopupState::None => {}
-        }
-
-        // Sub-tab switching with [ / ]
-        match key.code {
-            KeyCode::Char('[') => {
-                self.active_sub_tab = self.active_sub_tab.prev();
-                return Ok(());
-            }
-            KeyCode::Char(']') => {
-                self.active_sub_tab = self.active_sub_tab.next();
+            StoPopupState::ConfirmBudgetStep(idx) => {
+                let idx = *idx;
+                match key.code {
+                    KeyCode::Char('y') | KeyCode::Enter => {
+                        self.popup = StoPopupState::None;
+                        self.run_budget_step(idx);
+                    }
+                    KeyCode::Char('n') | KeyCode::Esc => {
+                        self.popup = StoPopupState::None;
+                    }
+                    _ => {}
+                }
                 return Ok(());
             }
-            _ => {}
-        }
-
-        match self.active_sub_tab {
-            StoSubTab::Dashboard => self.handle_dashboard_key(key),
+            StoPopupState::ConfirmSuggestions => {
+                match key.code {
+                    KeyCode::Char('y') | KeyCode::Enter => {
+                        self.popup = StoPopupState::None;
+                        self.apply_suggestions();
+                    }
+                    KeyCode::Char('n') | KeyCode::Esc => {
+                        self.popup = StoPopupState::None;
+                    }
+                    _ => {}
+                }
+                return Ok(());
+            }
+            StoPopupState::ActionResult { .. } => {
+                match key.code {
+                    KeyCode::Enter | KeyCode::Esc | KeyCode::Char('o') => {
+                        self.popup = StoPopupState::None;
+                    }
+                    _ => {}
+                }
+                return Ok(());
+            }
+            StoPopupState::None => {}
+        }
+
+        // Sub-tab switching with [ / ]
+        match key.code {
+            KeyCode::Char('[') => {
+                self.active_sub_tab = self.active_sub_tab.prev();
+                return Ok(());
+            }
+            KeyCode::Char(']') => {
+                self.active_sub_tab = self.active_sub_tab.next();
+                return Ok(());
+            }
+            _ => {}
+        }
+
+        match self.active_sub_tab {
+            StoSubTab::Dashboard => self.handle_dashboard_key(key),
             StoSubTab::Explorer => self.handle_explorer_key(key),
+            StoSubTab::Ranking => self.handle_ranking_key(key),
             StoSubTab::Clean => self.handle_clean_key(key),
+            StoSubTab::Boot => self.handle_boot_key(key),
+            StoSubTab::Budget => self.handle_budget_key(key),
+            StoSubTab::Suggestions => self.handle_suggestions_key(key),
             StoSubTab::History => self.handle_history_key(key),
         }
     }
 
+    fn handle_suggestions_key(&mut self, key: KeyEvent) -> Result<()> {
+        if self.suggestions_applying() {
+            return Ok(());
+        }
+        let count = self.suggestions.as_ref().map_or(0, Vec::len);
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.suggestion_selected =
+                    (self.suggestion_selected + 1).min(count.saturating_sub(1));
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.suggestion_selected = self.suggestion_selected.saturating_sub(1);
+            }
+            KeyCode::Char(' ') => {
+                if let Some(list) = self.suggestions.as_mut() {
+                    suggest::toggle(list, self.suggestion_selected);
+                }
+            }
+            KeyCode::Char('a') => {
+                if let Some(list) = self.suggestions.as_mut() {
+                    let all = list.iter().all(|i| i.selected);
+                    for item in list.iter_mut() {
+                        item.selected = !all;
+                    }
+                }
+            }
+            KeyCode::Char('r') if !self.suggestions_scanning => {
+                self.start_suggestions();
+            }
+            KeyCode::Enter | KeyCode::Char('x') => {
+                let any = self.suggestions.as_ref().is_some_and(|l| {
+                    l.iter()
+                        .any(|i| i.selected && i.status == StepStatus::Pending)
+                });
+                if any {
+                    self.popup = StoPopupState::ConfirmSuggestions;
+                } else {
+                    let s = i18n::get_strings(self.lang);
+                    self.flash_message = Some(FlashMessage::new(
+                        s.sto_sugg_none_selected.to_string(),
+                        true,
+                    ));
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn handle_budget_key(&mut self, key: KeyEvent) -> Result<()> {
+        let running = self.budget.as_ref().is_some_and(|p| p.is_running());
+        match key.code {
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                self.free_target_gb += 5;
+            }
+            KeyCode::Char('-') => {
+                self.free_target_gb = self.free_target_gb.saturating_sub(5).max(5);
+            }
+            KeyCode::Char('p') if !running && !self.budget_planning => {
+                self.start_budget_plan();
+            }
+            KeyCode::Enter | KeyCode::Char('x') if !running => {
+                if let Some(idx) = self.budget.as_ref().and_then(|p| p.next_pending()) {
+                    self.popup = StoPopupState::ConfirmBudgetStep(idx);
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
     fn handle_dashboard_key(&mut self, key: KeyEvent) -> Result<()> {
         if let KeyCode::Char('r') = key.code {
             self.refresh()
@@ -433,6 +1301,64 @@ impl StorageState {
         Ok(())
     }
 
+    fn handle_ranking_key(&mut self, key: KeyEvent) -> Result<()> {
+        let s = i18n::get_strings(self.lang);
+        let current = self
+            .sorted_ranking()
+            .get(self.ranking_selected)
+            .map(|e| e.path.clone());
+        if self.ranking_table.handle_key(key, &ranking_columns(s)) {
+            self.ranking_selected = self
+                .sorted_ranking()
+                .iter()
+                .position(|e| Some(&e.path) == current.as_ref())
+                .unwrap_or(0);
+            return Ok(());
+        }
+
+        let count = self.sorted_ranking().len();
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down if self.ranking_selected + 1 < count => {
+                self.ranking_selected += 1;
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.ranking_selected = self.ranking_selected.saturating_sub(1);
+            }
+            KeyCode::Char('g') => self.ranking_selected = 0,
+            KeyCode::Char('G') => self.ranking_selected = count.saturating_sub(1),
+            KeyCode::Char('r') if !self.ranking_scanning => self.start_ranking(),
+            KeyCode::Char('w') | KeyCode::Enter => self.start_why_depends(),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn handle_boot_key(&mut self, key: KeyEvent) -> Result<()> {
+        let count = match &self.boot {
+            Some(Ok(a)) => a.entries.len(),
+            _ => 0,
+        };
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down if self.boot_selected + 1 < count => {
+                self.boot_selected += 1;
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.boot_selected = self.boot_selected.saturating_sub(1);
+            }
+            KeyCode::Char('g') => self.boot_selected = 0,
+            KeyCode::Char('G') => self.boot_selected = count.saturating_sub(1),
+            KeyCode::Char('r') => {
+                self.boot = Some(boot::analyze());
+                self.boot_selected = 0;
+            }
+            KeyCode::Char('x') if !self.boot_orphans().is_empty() => {
+                self.popup = StoPopupState::ConfirmBootClean;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
     fn handle_clean_key(&mut self, key: KeyEvent) -> Result<()> {
         let action_count = CleanAction::all().len();
         match key.code {
@@ -481,6 +1407,53 @@ impl StorageState {
 // RENDERING
 // ════════════════════════════════════════════════════════════════════
 
+impl Module for StorageState {
+    fn handle_key(&mut self, key: KeyEvent, _lang: Language) -> Result<()> {
+        self.handle_key(key).map(|_| ())
+    }
+
+    fn captures_input(&self) -> bool {
+        !matches!(self.popup, StoPopupState::None) || self.explorer_search_active
+    }
+
+    fn render(&mut self, frame: &mut Frame, ctx: &RenderCtx, area: Rect) {
+        render(frame, self, ctx.theme, ctx.lang, area);
+    }
+
+    fn activate(&mut self) {
+        self.start_loading();
+    }
+
+    fn poll(&mut self, _active: bool) {
+        self.poll_load();
+        self.poll_ranking();
+    }
+
+    fn commands(&self, s: &i18n::Strings) -> Vec<PaletteCommand> {
+        self.palette_commands(s)
+    }
+
+    fn jobs(&self, s: &i18n::Strings) -> Vec<Job> {
+        self.jobs(s)
+    }
+
+    fn cancel_job(&mut self, id: &str) {
+        self.cancel_job(id)
+    }
+
+    fn sub_tab(&self) -> Option<(usize, usize)> {
+        Some((self.active_sub_tab.index(), StoSubTab::all().len()))
+    }
+
+    fn sub_tab_label(&self, lang: Language) -> Option<&'static str> {
+        Some(self.active_sub_tab.label(lang))
+    }
+
+    fn flash_message(&mut self) -> &mut Option<FlashMessage> {
+        &mut self.flash_message
+    }
+}
+
 pub fn render(
     frame: &mut Frame,
     state: &mut StorageState,
@@ -520,7 +1493,23 @@ pub fn render(
     match state.active_sub_tab {
         StoSubTab::Dashboard => render_dashboard(frame, state, theme, lang, layout[1]),
         StoSubTab::Explorer => render_explorer(frame, state, theme, lang, layout[1]),
+        StoSubTab::Ranking => {
+            state.ensure_ranking();
+            render_ranking(frame, state, theme, lang, layout[1]);
+        }
         StoSubTab::Clean => render_clean(frame, state, theme, lang, layout[1]),
+        StoSubTab::Boot => {
+            state.ensure_boot();
+            render_boot(frame, state, theme, lang, layout[1]);
+        }
+        StoSubTab::Budget => {
+            state.ensure_budget_plan();
+            render_budget(frame, state, theme, lang, layout[1]);
+        }
+        StoSubTab::Suggestions => {
+            state.ensure_suggestions();
+            render_suggestions(frame, state, theme, lang, layout[1]);
+        }
         StoSubTab::History => render_history(frame, state, theme, lang, layout[1]),
     }
 
@@ -529,6 +1518,51 @@ pub fn render(
         StoPopupState::ConfirmAction { action } => {
             render_confirm_popup(frame, *action, theme, lang, area);
         }
+        StoPopupState::ConfirmBudgetStep(idx) => {
+            if let Some(step) = state.budget.as_ref().and_then(|p| p.steps.get(*idx)) {
+                render_budget_confirm(frame, &step.kind, theme, lang, area);
+            }
+        }
+        StoPopupState::ConfirmSuggestions => {
+            if let Some(list) = &state.suggestions {
+                render_suggestions_confirm(frame, list, theme, lang, area);
+            }
+        }
+        StoPopupState::ConfirmBootClean => {
+            let s = i18n::get_strings(lang);
+            let orphans = state.boot_orphans();
+            let size: u64 = orphans.iter().map(|f| f.size).sum();
+            let mut content = vec![
+                Line::raw(""),
+                Line::styled(
+                    s.sto_boot_clean_msg
+                        .replacen("{}", &orphans.len().to_string(), 1)
+                        .replacen("{}", &format_bytes(size), 1),
+                    theme.text(),
+                ),
+                Line::raw(""),
+            ];
+            content.extend(
+                orphans
+                    .iter()
+                    .take(6)
+                    .map(|f| Line::styled(format!("  {}", f.path), theme.text_dim())),
+            );
+            if orphans.len() > 6 {
+                content.push(Line::styled(
+                    format!("  … +{}", orphans.len() - 6),
+                    theme.text_dim(),
+                ));
+            }
+            widgets::render_popup(
+                frame,
+                s.sto_boot_clean_title,
+                content,
+                &[(s.yes, 'y'), (s.no, 'n')],
+                theme,
+                area,
+            );
+        }
         StoPopupState::ActionResult { title, message } => {
             let content = vec![
                 Line::raw(""),
@@ -1055,6 +2089,266 @@ fn render_explorer(
     frame.render_widget(Paragraph::new(lines), list_area);
 }
 
+// ── Ranking ──
+
+fn ranking_columns(s: &i18n::Strings) -> [Column; 4] {
+    [
+        Column::fill(s.sto_col_name, 20),
+        Column::fixed(s.sto_col_origin, 8).secondary(),
+        Column::fixed(s.sto_col_closure, 10).right(),
+        Column::fixed(s.sto_col_change, 11).right(),
+    ]
+}
+
+fn ranking_sort_key(e: &&Ranked, col: usize) -> SortKey {
+    match col {
+        0 => SortKey::text(&e.name),
+        1 => SortKey::Num(e.origin as i64),
+        2 => SortKey::Num(e.closure as i64),
+        _ => SortKey::Num(e.closure as i64 - e.previous.unwrap_or(e.closure) as i64),
+    }
+}
+
+fn origin_label(origin: Origin, s: &i18n::Strings) -> &'static str {
+    match origin {
+        Origin::System => s.sto_origin_system,
+        Origin::User => s.sto_origin_user,
+        Origin::HomeManager => "HM",
+        Origin::Root => s.sto_origin_root,
+    }
+}
+
+fn render_ranking(
+    frame: &mut Frame,
+    state: &StorageState,
+    theme: &Theme,
+    lang: Language,
+    area: Rect,
+) {
+    let s = i18n::get_strings(lang);
+    let block = Block::default()
+        .style(theme.block_style())
+        .title(format!(" {} ", s.sto_ranking_title))
+        .title_style(theme.title())
+        .borders(Borders::ALL)
+        .border_style(theme.border_focused());
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let Some(ranking) = &state.ranking else {
+        frame.render_widget(
+            Paragraph::new(Line::styled(
+                format!("  {}", s.sto_ranking_scanning),
+                theme.text_dim(),
+            )),
+            inner,
+        );
+        return;
+    };
+    if inner.height < 3 {
+        return;
+    }
+
+    let entries = state.sorted_ranking();
+    let jumped = entries.iter().filter(|e| e.jumped()).count();
+    let mut summary = s
+        .sto_ranking_summary
+        .replacen("{}", &entries.len().to_string(), 1)
+        .replacen("{}", &ranking.queried.to_string(), 1);
+    if state.ranking_scanning {
+        summary = format!("{}  ·  {}", summary, s.sto_ranking_rescanning);
+    }
+    let mut header = vec![Span::styled(format!("  {}", summary), theme.text_dim())];
+    if jumped > 0 {
+        header.push(Span::styled(
+            format!(
+                "  ·  ▲ {}",
+                s.sto_ranking_jumped.replace("{}", &jumped.to_string())
+            ),
+            theme.warning(),
+        ));
+    }
+    frame.render_widget(
+        Paragraph::new(Line::from(header)),
+        Rect { height: 1, ..inner },
+    );
+    let table_area = Rect {
+        y: inner.y + 2,
+        height: inner.height - 2,
+        ..inner
+    };
+
+    if entries.is_empty() {
+        frame.render_widget(
+            Paragraph::new(Line::styled(
+                format!("  {}", s.sto_ranking_empty),
+                theme.text_dim(),
+            )),
+            table_area,
+        );
+        return;
+    }
+
+    let columns = ranking_columns(s);
+    Table::new(&columns, &state.ranking_table, entries.len(), |i| {
+        let e = entries[i];
+        let change = match e.previous {
+            Some(prev) if e.closure > prev => format!("+{}", format_bytes(e.closure - prev)),
+            Some(prev) if e.closure < prev => format!("-{}", format_bytes(prev - e.closure)),
+            _ => String::new(),
+        };
+        let (name_style, change_style) = if e.jumped() {
+            (
+                theme.warning(),
+                theme.warning().add_modifier(Modifier::BOLD),
+            )
+        } else {
+            (theme.text(), theme.text_dim())
+        };
+        vec![
+            Cell::new(e.name.clone(), name_style),
+            Cell::new(origin_label(e.origin, s), theme.text_dim()),
+            Cell::new(format_bytes(e.closure), Style::default().fg(theme.accent)),
+            Cell::new(change, change_style),
+        ]
+    })
+    .selected(Some(state.ranking_selected.min(entries.len() - 1)))
+    .compact(state.compact)
+    .render(frame, theme, table_area);
+}
+
+// ── Boot ──
+
+fn boot_columns(s: &i18n::Strings) -> [Column; 4] {
+    [
+        Column::fixed(s.sto_col_entry, 16),
+        Column::fill(s.sto_col_kernel, 24),
+        Column::fixed(s.sto_col_size, 10).right(),
+        Column::fixed("", 8),
+    ]
+}
+
+fn boot_entry_label(e: &BootEntry) -> String {
+    match (e.generation, &e.specialisation) {
+        (Some(g), Some(spec)) => format!("#{} {}", g, spec),
+        (Some(g), None) => format!("#{}", g),
+        (None, _) => "default".to_string(),
+    }
+}
+
+fn render_boot(frame: &mut Frame, state: &StorageState, theme: &Theme, lang: Language, area: Rect) {
+    let s = i18n::get_strings(lang);
+    let block = Block::default()
+        .style(theme.block_style())
+        .title(format!(" {} ", s.sto_boot_title))
+        .title_style(theme.title())
+        .borders(Borders::ALL)
+        .border_style(theme.border_focused());
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let analysis = match &state.boot {
+        Some(Ok(a)) => a,
+        Some(Err(e)) => {
+            frame.render_widget(
+                Paragraph::new(Line::styled(
+                    format!("  {}", s.sto_boot_unavailable.replace("{}", e)),
+                    theme.text_dim(),
+                ))
+                .wrap(Wrap { trim: false }),
+                inner,
+            );
+            return;
+        }
+        None => return,
+    };
+    if inner.height < 6 {
+        return;
+    }
+
+    let loader = match analysis.loader {
+        boot::Loader::SystemdBoot => "systemd-boot",
+        boot::Loader::Grub => "GRUB",
+    };
+    let summary = s
+        .sto_boot_summary
+        .replacen("{}", &analysis.entries.len().to_string(), 1)
+        .replacen("{}", &format_bytes(analysis.total), 1)
+        .replacen(
+            "{}",
+            &analysis
+                .free
+                .map(format_bytes)
+                .unwrap_or_else(|| "?".into()),
+            1,
+        );
+    frame.render_widget(
+        Paragraph::new(Line::styled(
+            format!("  {} · {}", loader, summary),
+            theme.text_dim(),
+        )),
+        Rect { height: 1, ..inner },
+    );
+
+    // Orphans below the table: header plus up to four paths
+    let orphan_lines: Vec<Line> = if analysis.orphans.is_empty() {
+        vec![Line::styled(
+            format!("  ✓ {}", s.sto_boot_no_orphans),
+            theme.success(),
+        )]
+    } else {
+        let mut lines = vec![Line::styled(
+            format!(
+                "  ⚠ {}",
+                s.sto_boot_orphans
+                    .replacen("{}", &analysis.orphans.len().to_string(), 1)
+                    .replacen("{}", &format_bytes(analysis.orphan_bytes()), 1)
+            ),
+            theme.warning(),
+        )];
+        lines.extend(analysis.orphans.iter().take(4).map(|f| {
+            Line::styled(
+                format!("    {}  ({})", f.path, format_bytes(f.size)),
+                theme.text_dim(),
+            )
+        }));
+        lines
+    };
+    let orphan_height = (orphan_lines.len() as u16).min(inner.height - 4);
+    let table_area = Rect {
+        y: inner.y + 2,
+        height: inner.height - 3 - orphan_height,
+        ..inner
+    };
+    frame.render_widget(
+        Paragraph::new(orphan_lines),
+        Rect {
+            y: inner.y + inner.height - orphan_height,
+            height: orphan_height,
+            ..inner
+        },
+    );
+
+    if analysis.entries.is_empty() {
+        return;
+    }
+    let columns = boot_columns(s);
+    Table::new(&columns, &state.boot_table, analysis.entries.len(), |i| {
+        let e = &analysis.entries[i];
+        vec![
+            Cell::new(boot_entry_label(e), theme.text()),
+            Cell::new(e.kernel.clone(), theme.text_dim()),
+            Cell::new(format_bytes(e.size), Style::default().fg(theme.accent)),
+            Cell::new(
+                if e.shared { s.sto_boot_shared } else { "" },
+                theme.text_dim(),
+            ),
+        ]
+    })
+    .selected(Some(state.boot_selected.min(analysis.entries.len() - 1)))
+    .render(frame, theme, table_area);
+}
+
 // ── Clean ──
 
 fn render_clean(
@@ -1160,6 +2454,459 @@ fn render_clean(
     frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), inner);
 }
 
+// ── Budget ──
+
+fn budget_step_title(kind: &StepKind, s: &i18n::Strings) -> String {
+    match kind {
+        StepKind::CollectGarbage => s.sto_budget_step_gc.to_string(),
+        StepKind::RemoveResults(links) => s
+            .sto_budget_step_results
+            .replace("{}", &links.len().to_string()),
+        StepKind::DeleteOlderThan { days, generations } => s
+            .sto_budget_step_gens
+            .replacen("{}", &generations.to_string(), 1)
+            .replacen("{}", &days.to_string(), 1),
+        StepKind::Optimise => s.sto_budget_step_optimise.to_string(),
+    }
+}
+
+fn render_budget(
+    frame: &mut Frame,
+    state: &StorageState,
+    theme: &Theme,
+    lang: Language,
+    area: Rect,
+) {
+    let s = i18n::get_strings(lang);
+
+    let block = Block::default()
+        .style(theme.block_style())
+        .title(format!(" {} ", s.sto_budget))
+        .title_style(theme.title())
+        .borders(Borders::ALL)
+        .border_style(theme.border_focused());
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let heading = |text: &str| {
+        Line::styled(
+            format!("  ── {} ──", text),
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )
+    };
+
+    let mut lines: Vec<Line> = vec![heading(s.sto_budget_target_title), Line::raw("")];
+    lines.push(Line::from(vec![
+        Span::styled(format!("    {:<14}", s.sto_budget_target), theme.text_dim()),
+        Span::styled(
+            format!("{} GB", state.free_target_gb),
+            Style::default().fg(theme.fg).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled("   [+/-]", theme.text_dim()),
+    ]));
+
+    let Some(plan) = &state.budget else {
+        lines.push(Line::raw(""));
+        lines.push(Line::styled(
+            format!("  ⏳ {}", s.sto_budget_planning),
+            Style::default().fg(theme.accent),
+        ));
+        frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), inner);
+        return;
+    };
+
+    lines.push(Line::from(vec![
+        Span::styled(format!("    {:<14}", s.sto_budget_now), theme.text_dim()),
+        Span::styled(format_bytes(plan.available), Style::default().fg(theme.fg)),
+    ]));
+    let target_bytes = state.free_target_gb * 1024 * 1024 * 1024;
+    if plan.target != target_bytes {
+        lines.push(Line::styled(
+            format!("    {}", s.sto_budget_stale),
+            Style::default().fg(theme.warning),
+        ));
+    }
+    lines.push(Line::raw(""));
+
+    if plan.needed() == 0 {
+        lines.push(Line::styled(
+            format!("  ✓ {}", s.sto_budget_reached),
+            Style::default().fg(theme.success),
+        ));
+        frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), inner);
+        return;
+    }
+
+    lines.push(Line::from(vec![
+        Span::styled(format!("    {:<14}", s.sto_budget_needed), theme.text_dim()),
+        Span::styled(
+            format_bytes(plan.needed()),
+            Style::default().fg(theme.warning),
+        ),
+    ]));
+    lines.push(Line::raw(""));
+    lines.push(heading(s.sto_budget_plan_title));
+    lines.push(Line::raw(""));
+
+    for (i, step) in plan.steps.iter().enumerate() {
+        let (icon, color) = match &step.status {
+            StepStatus::Pending => ("·", theme.fg_dim),
+            StepStatus::Running => ("▶", theme.accent),
+            StepStatus::Done(_) => ("✓", theme.success),
+            StepStatus::Failed(_) => ("✗", theme.error),
+        };
+        let estimate = match step.estimate {
+            Some(b) => format!("≈ {}", format_bytes(b)),
+            None => "≈ ?".to_string(),
+        };
+        let mut spans = vec![
+            Span::styled(
+                format!("  {} {}. ", icon, i + 1),
+                Style::default().fg(color),
+            ),
+            Span::styled(budget_step_title(&step.kind, s), theme.text()),
+        ];
+        if step.kind.needs_sudo() {
+            spans.push(Span::styled(" (sudo)", Style::default().fg(theme.warning)));
+        }
+        spans.push(Span::styled(
+            format!("   {}", estimate),
+            Style::default().fg(theme.accent),
+        ));
+        match &step.status {
+            StepStatus::Done(freed) => spans.push(Span::styled(
+                format!("   {} {}", s.sto_freed, format_bytes(*freed)),
+                Style::default().fg(theme.success),
+            )),
+            StepStatus::Running => spans.push(Span::styled(
+                format!("   {}…", s.sto_budget_running),
+                Style::default().fg(theme.accent),
+            )),
+            _ => {}
+        }
+        lines.push(Line::from(spans));
+
+        if let StepStatus::Failed(e) = &step.status {
+            lines.push(Line::styled(
+                format!("       {}", e),
+                Style::default().fg(theme.error),
+            ));
+        }
+        if let StepKind::RemoveResults(links) = &step.kind {
+            for link in links.iter().take(5) {
+                lines.push(Line::styled(
+                    format!("       {}", link.display()),
+                    theme.text_dim(),
+                ));
+            }
+            if links.len() > 5 {
+                lines.push(Line::styled(
+                    format!("       … +{}", links.len() - 5),
+                    theme.text_dim(),
+                ));
+            }
+        }
+    }
+
+    lines.push(Line::raw(""));
+    let (verdict, color) = if plan.reaches_target() {
+        (s.sto_budget_ok, theme.success)
+    } else {
+        (s.sto_budget_short, theme.warning)
+    };
+    lines.push(Line::from(vec![
+        Span::styled(format!("  {} ", s.sto_budget_predicted), theme.text_dim()),
+        Span::styled(
+            format_bytes(plan.available + plan.predicted()),
+            Style::default().fg(theme.fg).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(format!("  {}", verdict), Style::default().fg(color)),
+    ]));
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        format!("  {}", s.sto_budget_hint),
+        theme.text_dim(),
+    ));
+
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), inner);
+}
+
+fn render_budget_confirm(
+    frame: &mut Frame,
+    kind: &StepKind,
+    theme: &Theme,
+    lang: Language,
+    area: Rect,
+) {
+    let s = i18n::get_strings(lang);
+
+    let mut content = vec![
+        Line::raw(""),
+        Line::styled(
+            budget_step_title(kind, s),
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Line::raw(""),
+    ];
+    if kind.needs_sudo() {
+        content.push(Line::styled(
+            s.sto_budget_sudo_note,
+            Style::default().fg(theme.warning),
+        ));
+        content.push(Line::raw(""));
+    }
+    content.push(Line::styled(s.sto_confirm_question, theme.text()));
+
+    widgets::render_popup(
+        frame,
+        s.sto_confirm_title,
+        content,
+        &[(s.yes, 'y'), (s.no, 'n')],
+        theme,
+        area,
+    );
+}
+
+// ── Suggestions ──
+
+fn suggestion_title(kind: &SuggestionKind, s: &i18n::Strings) -> String {
+    match kind {
+        SuggestionKind::OldGenerations { days, generations } => s
+            .sto_budget_step_gens
+            .replacen("{}", &generations.to_string(), 1)
+            .replacen("{}", &days.to_string(), 1),
+        SuggestionKind::ResultLinks(links) => {
+            s.sto_sugg_results.replace("{}", &links.len().to_string())
+        }
+        SuggestionKind::DevCaches(links) => s
+            .sto_sugg_dev_caches
+            .replace("{}", &links.len().to_string()),
+        SuggestionKind::HomeManager(links) => s
+            .sto_sugg_home_manager
+            .replace("{}", &links.len().to_string()),
+        SuggestionKind::CollectGarbage { dead, .. } => {
+            s.sto_sugg_gc.replace("{}", &dead.to_string())
+        }
+    }
+}
+
+fn estimate_text(estimate: Option<u64>) -> String {
+    match estimate {
+        Some(b) => format!("≈ {}", format_bytes(b)),
+        None => "≈ ?".to_string(),
+    }
+}
+
+fn render_suggestions(
+    frame: &mut Frame,
+    state: &StorageState,
+    theme: &Theme,
+    lang: Language,
+    area: Rect,
+) {
+    let s = i18n::get_strings(lang);
+
+    let block = Block::default()
+        .style(theme.block_style())
+        .title(format!(" {} ", s.sto_suggestions))
+        .title_style(theme.title())
+        .borders(Borders::ALL)
+        .border_style(theme.border_focused());
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let list = match &state.suggestions {
+        Some(list) if !state.suggestions_scanning => list,
+        _ => {
+            let lines = vec![
+                Line::raw(""),
+                Line::styled(
+                    format!("  ⏳ {}", s.sto_sugg_scanning),
+                    Style::default().fg(theme.accent),
+                ),
+            ];
+            frame.render_widget(Paragraph::new(lines), inner);
+            return;
+        }
+    };
+    if list.is_empty() {
+        let lines = vec![
+            Line::raw(""),
+            Line::styled(
+                format!("  ✓ {}", s.sto_sugg_nothing),
+                Style::default().fg(theme.success),
+            ),
+            Line::raw(""),
+            Line::styled(format!("  {}", s.sto_sugg_hint), theme.text_dim()),
+        ];
+        frame.render_widget(Paragraph::new(lines), inner);
+        return;
+    }
+
+    let mut lines: Vec<Line> = vec![Line::raw("")];
+    for (i, item) in list.iter().enumerate() {
+        let is_sel = i == state.suggestion_selected;
+        let (icon, color) = match &item.status {
+            StepStatus::Pending => (if item.selected { "☑" } else { "☐" }, theme.fg),
+            StepStatus::Running => ("▶", theme.accent),
+            StepStatus::Done(_) => ("✓", theme.success),
+            StepStatus::Failed(_) => ("✗", theme.error),
+        };
+        let title_style = if is_sel {
+            theme.selected()
+        } else {
+            theme.text()
+        };
+        let mut spans = vec![
+            Span::styled(if is_sel { "  ▸ " } else { "    " }, theme.text_dim()),
+            Span::styled(format!("{} ", icon), Style::default().fg(color)),
+            Span::styled(suggestion_title(&item.kind, s), title_style),
+        ];
+        if item.kind.needs_sudo() {
+            spans.push(Span::styled(" (sudo)", Style::default().fg(theme.warning)));
+        }
+        spans.push(Span::styled(
+            format!("   {}", estimate_text(item.estimate)),
+            Style::default().fg(theme.accent),
+        ));
+        match &item.status {
+            StepStatus::Done(freed) if *freed > 0 => spans.push(Span::styled(
+                format!("   {} {}", s.sto_freed, format_bytes(*freed)),
+                Style::default().fg(theme.success),
+            )),
+            StepStatus::Running => spans.push(Span::styled(
+                format!("   {}…", s.sto_budget_running),
+                Style::default().fg(theme.accent),
+            )),
+            _ => {}
+        }
+        lines.push(Line::from(spans));
+        if let StepStatus::Failed(e) = &item.status {
+            lines.push(Line::styled(
+                format!("         {}", e),
+                Style::default().fg(theme.error),
+            ));
+        }
+
+        // What the selected one would remove
+        if !is_sel {
+            continue;
+        }
+        let details: Vec<String> = match &item.kind {
+            SuggestionKind::CollectGarbage { largest, .. } => largest
+                .iter()
+                .map(|(name, size)| format!("{:>10}  {}", format_bytes(*size), name))
+                .collect(),
+            kind => kind
+                .links()
+                .iter()
+                .map(|l| l.display().to_string())
+                .collect(),
+        };
+        for detail in details.iter().take(5) {
+            lines.push(Line::styled(
+                format!("         {}", detail),
+                theme.text_dim(),
+            ));
+        }
+        if details.len() > 5 {
+            lines.push(Line::styled(
+                format!("         … +{}", details.len() - 5),
+                theme.text_dim(),
+            ));
+        }
+    }
+
+    let selected: Vec<&Suggestion> = list.iter().filter(|i| i.selected).collect();
+    lines.push(Line::raw(""));
+    lines.push(Line::from(vec![
+        Span::styled(
+            format!(
+                "  {} ",
+                s.sto_sugg_selected
+                    .replace("{}", &selected.len().to_string())
+            ),
+            theme.text_dim(),
+        ),
+        Span::styled(
+            format_bytes(selected.iter().filter_map(|i| i.estimate).sum()),
+            Style::default().fg(theme.fg).add_modifier(Modifier::BOLD),
+        ),
+    ]));
+    lines.push(Line::styled(
+        format!("  {}", s.sto_sugg_overlap),
+        theme.text_dim(),
+    ));
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        format!("  {}", s.sto_sugg_hint),
+        theme.text_dim(),
+    ));
+
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), inner);
+}
+
+fn render_suggestions_confirm(
+    frame: &mut Frame,
+    list: &[Suggestion],
+    theme: &Theme,
+    lang: Language,
+    area: Rect,
+) {
+    let s = i18n::get_strings(lang);
+    let batch: Vec<&Suggestion> = list
+        .iter()
+        .filter(|i| i.selected && i.status == StepStatus::Pending)
+        .collect();
+
+    let mut content = vec![Line::raw("")];
+    for item in &batch {
+        content.push(Line::from(vec![
+            Span::styled("  • ", theme.text_dim()),
+            Span::styled(suggestion_title(&item.kind, s), theme.text()),
+            Span::styled(
+                format!("  {}", estimate_text(item.estimate)),
+                Style::default().fg(theme.accent),
+            ),
+        ]));
+    }
+    content.push(Line::raw(""));
+    content.push(Line::styled(
+        format!(
+            "{} {}",
+            s.sto_sugg_total,
+            format_bytes(batch.iter().filter_map(|i| i.estimate).sum())
+        ),
+        Style::default()
+            .fg(theme.accent)
+            .add_modifier(Modifier::BOLD),
+    ));
+    content.push(Line::raw(""));
+    if batch.iter().any(|i| i.kind.needs_sudo()) {
+        content.push(Line::styled(
+            s.sto_budget_sudo_note,
+            Style::default().fg(theme.warning),
+        ));
+        content.push(Line::raw(""));
+    }
+    content.push(Line::styled(s.sto_confirm_question, theme.text()));
+
+    widgets::render_popup(
+        frame,
+        s.sto_confirm_title,
+        content,
+        &[(s.yes, 'y'), (s.no, 'n')],
+        theme,
+        area,
+    );
+}
+
 // ── History ──
 
 fn render_history(
@@ -1208,7 +2955,10 @@ fn render_history(
     if let Some(last) = &last_cleanup {
         lines.push(Line::from(vec![
             Span::styled(format!("  {} ", s.sto_last_cleanup), theme.text_dim()),
-            Span::styled(last.as_str(), Style::default().fg(theme.fg)),
+            Span::styled(
+                format_stored_time(last, state.time_format, lang),
+                Style::default().fg(theme.fg),
+            ),
         ]));
     }
     lines.push(Line::from(vec![
@@ -1258,7 +3008,10 @@ fn render_history(
 
         lines.push(Line::from(vec![
             Span::styled(
-                format!("  {}  ", entry.timestamp),
+                format!(
+                    "  {}  ",
+                    format_stored_time(&entry.timestamp, state.time_format, lang)
+                ),
                 Style::default().fg(theme.fg_dim),
             ),
             Span::styled(
@@ -1329,3 +3082,64 @@ fn render_confirm_popup(
         area,
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use budget::PlanStep;
+
+    #[test]
+    fn test_budget_step_waits_for_undo_window() {
+        let mut state = StorageState::new();
+        let step = |kind| PlanStep {
+            kind,
+            estimate: None,
+            status: StepStatus::Pending,
+        };
+        state.budget = Some(BudgetPlan {
+            available: 0,
+            target: 1,
+            steps: vec![step(StepKind::CollectGarbage)],
+        });
+
+        // Confirmed: handed to App's undo queue, nothing started yet
+        state.run_budget_step(0);
+        assert!(state.budget_step_rx.is_none());
+        let (_, kind) = state.pending_staged.take().unwrap();
+        assert_eq!(kind, StagedKind::BudgetStep(StepKind::CollectGarbage));
+        assert_eq!(
+            state.budget.as_ref().unwrap().steps[0].status,
+            StepStatus::Pending
+        );
+
+        // A step the plan no longer has pending isn't run
+        state.budget.as_mut().unwrap().steps[0].status = StepStatus::Done(0);
+        state.run_staged_budget_step(StepKind::CollectGarbage);
+        assert!(state.budget_step_rx.is_none());
+    }
+
+    #[test]
+    fn test_suggestions_wait_for_undo_window() {
+        let mut state = StorageState::new();
+        let gc = SuggestionKind::CollectGarbage {
+            dead: 3,
+            largest: Vec::new(),
+        };
+        state.suggestions = Some(vec![Suggestion {
+            kind: gc.clone(),
+            estimate: None,
+            selected: true,
+            status: StepStatus::Pending,
+        }]);
+
+        state.apply_suggestions();
+        assert!(!state.suggestions_applying());
+        let (_, kind) = state.pending_staged.take().unwrap();
+        assert_eq!(kind, StagedKind::Suggestions(vec![gc.clone()]));
+
+        // Staged twice: the second batch finds it done and does nothing
+        state.suggestions.as_mut().unwrap()[0].status = StepStatus::Done(0);
+        state.run_staged_suggestions(vec![gc]);
+        assert!(!state.suggestions_applying());
+    }
+}
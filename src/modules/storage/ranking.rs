@@ -0,0 +1,297 @@
+//! Closure-size ranking — "what's eating my store"
+//!
+//! Every package of the system profile (`environment.systemPackages`),
+//! the user's profiles and Home-Manager, plus every other GC root
+//! (`result` links, direnv, …), ranked by the size of its whole closure.
+//! Store paths never change, so closure sizes are cached per path in
+//! `~/.cache/nixmate/closure-ranking.json` and a rescan only asks nix
+//! about new ones. The scan before the last one that changed anything is
+//! kept, to point out what grew. Blocking — run in background thread!
+
+use crate::nix::storage;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// A closure counts as jumped when it grew by this much…
+const JUMP_MIN_BYTES: u64 = 50 * 1024 * 1024;
+/// …and by at least 1/JUMP_FRACTION of its old size
+const JUMP_FRACTION: u64 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Origin {
+    System,
+    User,
+    HomeManager,
+    /// Another GC root; its link is the entry's name
+    Root,
+}
+
+impl Origin {
+    fn key(&self) -> &'static str {
+        match self {
+            Origin::System => "system",
+            Origin::User => "user",
+            Origin::HomeManager => "hm",
+            Origin::Root => "root",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Ranked {
+    pub name: String,
+    pub path: String,
+    pub origin: Origin,
+    pub closure: u64,
+    /// Closure size in the previous scan (matched by name without version)
+    pub previous: Option<u64>,
+}
+
+impl Ranked {
+    pub fn jumped(&self) -> bool {
+        self.previous
+            .is_some_and(|prev| self.closure >= prev + JUMP_MIN_BYTES.max(prev / JUMP_FRACTION))
+    }
+
+    fn key(&self) -> String {
+        format!("{}:{}", self.origin.key(), pname(&self.name))
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Ranking {
+    /// Largest closure first
+    pub entries: Vec<Ranked>,
+    /// Sizes nix was asked for (the rest came from the cache)
+    pub queried: usize,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct Cache {
+    /// Closure size per store path
+    sizes: HashMap<String, u64>,
+    /// Closure size per `origin:name` in the last scan and the one before
+    latest: HashMap<String, u64>,
+    previous: HashMap<String, u64>,
+}
+
+fn cache_path() -> Option<PathBuf> {
+    dirs::cache_dir().map(|d| d.join("nixmate").join("closure-ranking.json"))
+}
+
+pub fn scan() -> Ranking {
+    let mut cache: Cache = cache_path()
+        .and_then(|p| std::fs::read_to_string(p).ok())
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default();
+
+    let candidates = candidates();
+    let missing: Vec<&str> = candidates
+        .iter()
+        .map(|(_, path, _)| path.as_str())
+        .filter(|p| !cache.sizes.contains_key(*p))
+        .collect();
+    let queried = missing.len();
+    cache.sizes.extend(closure_sizes(&missing));
+
+    let mut entries: Vec<Ranked> = candidates
+        .into_iter()
+        .filter_map(|(name, path, origin)| {
+            let closure = *cache.sizes.get(&path)?;
+            Some(Ranked {
+                name,
+                path,
+                origin,
+                closure,
+                previous: None,
+            })
+        })
+        .collect();
+    entries.sort_by_key(|e| std::cmp::Reverse(e.closure));
+
+    rotate(&mut cache, &entries);
+    for e in &mut entries {
+        e.previous = cache.previous.get(&e.key()).copied();
+    }
+
+    let live: HashSet<&str> = entries.iter().map(|e| e.path.as_str()).collect();
+    cache.sizes.retain(|p, _| live.contains(p.as_str()));
+    if let (Some(path), Ok(text)) = (cache_path(), serde_json::to_string(&cache)) {
+        if let Some(dir) = path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        let _ = std::fs::write(path, text);
+    }
+
+    Ranking { entries, queried }
+}
+
+/// Make this scan the latest. An unchanged rescan keeps the older scan as
+/// `previous`, so what grew stays marked until something changes again.
+fn rotate(cache: &mut Cache, entries: &[Ranked]) {
+    let now: HashMap<String, u64> = entries.iter().map(|e| (e.key(), e.closure)).collect();
+    if now != cache.latest {
+        cache.previous = std::mem::replace(&mut cache.latest, now);
+    }
+}
+
+/// (name, store path, origin) of everything to rank
+fn candidates() -> Vec<(String, String, Origin)> {
+    let home = dirs::home_dir().unwrap_or_default();
+    let user = std::env::var("USER").unwrap_or_default();
+    let profiles: [(PathBuf, Origin); 4] = [
+        (PathBuf::from("/run/current-system/sw"), Origin::System),
+        (home.join(".nix-profile"), Origin::User),
+        (
+            PathBuf::from("/etc/profiles/per-user").join(&user),
+            Origin::User,
+        ),
+        (
+            home.join(".local/state/nix/profiles/home-manager/home-path"),
+            Origin::HomeManager,
+        ),
+    ];
+
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut list = Vec::new();
+    for (profile, origin) in &profiles {
+        let Ok(target) = std::fs::canonicalize(profile) else {
+            continue;
+        };
+        for path in references(&target) {
+            if seen.insert(path.clone()) {
+                list.push((storage::path_to_name(&path), path, *origin));
+            }
+        }
+    }
+
+    for (link, target) in storage::gc_roots() {
+        if is_profile_root(&link) || !seen.insert(target.clone()) {
+            continue;
+        }
+        let name = match link.strip_prefix(&home) {
+            Ok(rel) => format!("~/{}", rel.display()),
+            Err(_) => link.display().to_string(),
+        };
+        list.push((name, target, Origin::Root));
+    }
+    list
+}
+
+/// Generations, the running system and in-memory roots aren't packages
+fn is_profile_root(link: &Path) -> bool {
+    let text = link.to_string_lossy();
+    text.starts_with("/nix/var/nix/profiles")
+        || text.contains("/.local/state/nix/profiles")
+        || text.starts_with("/run/")
+        || text.starts_with("/proc/")
+        || text.contains("{censored}")
+}
+
+/// Direct references of a store path (a profile's packages)
+fn references(path: &Path) -> Vec<String> {
+    let path = path.to_string_lossy();
+    storage::output_with_timeout("nix-store", &["-q", "--references", &path], 15)
+        .filter(|o| o.status.success())
+        .map(|o| {
+            String::from_utf8_lossy(&o.stdout)
+                .lines()
+                .map(|l| l.trim().to_string())
+                .filter(|l| l.starts_with("/nix/store/") && *l != path)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Closure sizes via `nix path-info -S`, in batches
+fn closure_sizes(paths: &[&str]) -> HashMap<String, u64> {
+    let mut sizes = HashMap::new();
+    for batch in paths.chunks(200) {
+        let mut args = vec!["path-info", "-S"];
+        args.extend(batch);
+        let Some(out) = storage::output_with_timeout("nix", &args, 60) else {
+            continue;
+        };
+        for line in String::from_utf8_lossy(&out.stdout).lines() {
+            let mut parts = line.split_whitespace();
+            if let (Some(path), Some(size)) = (parts.next(), parts.next()) {
+                if let Ok(size) = size.parse() {
+                    sizes.insert(path.to_string(), size);
+                }
+            }
+        }
+    }
+    sizes
+}
+
+/// `firefox-131.0` → `firefox`: versions change with every update
+fn pname(name: &str) -> &str {
+    let bytes = name.as_bytes();
+    (0..bytes.len())
+        .find(|&i| bytes[i] == b'-' && bytes.get(i + 1).is_some_and(u8::is_ascii_digit))
+        .map_or(name, |i| &name[..i])
+}
+
+/// `nix why-depends` from the running system to `path`. Blocking.
+pub fn why_depends(path: &str) -> Result<String, String> {
+    let out = storage::output_with_timeout(
+        "nix",
+        &[
+            "--extra-experimental-features",
+            "nix-command",
+            "why-depends",
+            "/run/current-system",
+            path,
+        ],
+        60,
+    )
+    .ok_or_else(|| "nix why-depends timed out".to_string())?;
+    let stdout = String::from_utf8_lossy(&out.stdout).trim().to_string();
+    let stderr = String::from_utf8_lossy(&out.stderr).trim().to_string();
+    if out.status.success() && !stdout.is_empty() {
+        Ok(stdout)
+    } else if out.status.success() || !stderr.is_empty() {
+        // "… does not depend on …" comes on stderr
+        Err(stderr.lines().last().unwrap_or_default().to_string())
+    } else {
+        Err("nix why-depends failed".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIB: u64 = 1024 * 1024;
+
+    fn ranked(name: &str, closure: u64) -> Ranked {
+        Ranked {
+            name: name.into(),
+            path: format!("/nix/store/aaa-{}", name),
+            origin: Origin::System,
+            closure,
+            previous: None,
+        }
+    }
+
+    #[test]
+    fn test_rotate_and_jump() {
+        let mut cache = Cache::default();
+        rotate(&mut cache, &[ranked("firefox-130.0", 400 * MIB)]);
+        rotate(&mut cache, &[ranked("firefox-131.0", 600 * MIB)]);
+        assert_eq!(cache.previous.get("system:firefox"), Some(&(400 * MIB)));
+
+        // Rescanning without changes keeps the comparison
+        rotate(&mut cache, &[ranked("firefox-131.0", 600 * MIB)]);
+        let mut e = ranked("firefox-131.0", 600 * MIB);
+        e.previous = cache.previous.get(&e.key()).copied();
+        assert!(e.jumped());
+
+        // Small growth isn't a jump
+        e.previous = Some(580 * MIB);
+        assert!(!e.jumped());
+        assert_eq!(pname("python3-3.12.5-env"), "python3");
+        assert_eq!(pname("unit-script-foo"), "unit-script-foo");
+    }
+}
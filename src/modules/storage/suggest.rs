@@ -0,0 +1,285 @@
+//! Suggestions — cleanup recommendations.
+//!
+//! Looks through the GC roots and the store for space to give back:
+//!   - system generations older than `GENERATION_AGE_DAYS`
+//!   - `result` symlinks of old builds
+//!   - direnv / devenv caches (`.direnv`, `.devenv` roots in projects)
+//!   - Home-Manager generations other than the current one
+//!   - garbage: dead paths, plus whatever the others stop pinning
+//!
+//! Each estimate is the size of the store paths that only the suggestion's
+//! roots keep alive, so two suggestions sharing paths both leave them out.
+//! Blocking — run in background thread!
+
+use super::budget::{self, StepStatus};
+use crate::config::Language;
+use crate::modules::generations::boot_role_reason;
+use crate::nix::generations::endangered_by_cleanup;
+use crate::nix::storage::{self, StoreInfo};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Generations older than this are suggested for deletion
+const GENERATION_AGE_DAYS: u32 = 30;
+/// Largest dead paths listed with the garbage suggestion
+const LARGEST_DEAD: usize = 5;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SuggestionKind {
+    /// Delete system generations older than N days (sudo)
+    OldGenerations {
+        days: u32,
+        generations: usize,
+    },
+    ResultLinks(Vec<PathBuf>),
+    DevCaches(Vec<PathBuf>),
+    /// Links of the old Home-Manager generations
+    HomeManager(Vec<PathBuf>),
+    /// Dead paths, largest first (name, size)
+    CollectGarbage {
+        dead: usize,
+        largest: Vec<(String, u64)>,
+    },
+}
+
+impl SuggestionKind {
+    pub fn needs_sudo(&self) -> bool {
+        matches!(self, SuggestionKind::OldGenerations { .. })
+    }
+
+    /// Symlinks the suggestion removes
+    pub fn links(&self) -> &[PathBuf] {
+        match self {
+            SuggestionKind::ResultLinks(links)
+            | SuggestionKind::DevCaches(links)
+            | SuggestionKind::HomeManager(links) => links,
+            _ => &[],
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Suggestion {
+    pub kind: SuggestionKind,
+    /// Predicted bytes freed (None = sizes unknown)
+    pub estimate: Option<u64>,
+    pub selected: bool,
+    pub status: StepStatus,
+}
+
+/// What kind of cleanup a GC root belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Root {
+    Result,
+    DevCache,
+    OldHomeManager,
+}
+
+fn classify(link: &Path, hm_current: &HashMap<PathBuf, String>) -> Option<Root> {
+    let text = link.to_string_lossy();
+    if text.contains("/.direnv/") || text.contains("/.devenv/") {
+        return Some(Root::DevCache);
+    }
+    if budget::is_result(link) {
+        return Some(Root::Result);
+    }
+    let name = link.file_name()?.to_string_lossy();
+    let generation = name.strip_prefix("home-manager-")?.strip_suffix("-link")?;
+    let current = hm_current.get(link.parent()?);
+    (generation.parse::<u32>().is_ok() && current.map(String::as_str) != Some(&*name))
+        .then_some(Root::OldHomeManager)
+}
+
+/// Suggestions for the current store, the garbage one last: it is what
+/// frees the space the others unpin
+pub fn build(info: &StoreInfo) -> Vec<Suggestion> {
+    let sizes: HashMap<&str, u64> = info
+        .paths
+        .iter()
+        .map(|p| (p.path.as_str(), p.size))
+        .collect();
+    let roots = storage::gc_roots();
+    // Only paths no other root keeps alive count
+    let exclusive = |removed: &HashSet<&Path>| -> Option<u64> {
+        if !info.has_sizes {
+            return None;
+        }
+        let (gone, kept): (Vec<_>, Vec<_>) = roots
+            .iter()
+            .partition(|(l, _)| removed.contains(l.as_path()));
+        let freed = storage::closure(gone.iter().map(|(_, t)| t.as_str()));
+        let kept = storage::closure(kept.iter().map(|(_, t)| t.as_str()));
+        Some(
+            freed
+                .difference(&kept)
+                .filter_map(|p| sizes.get(p.as_str()))
+                .sum(),
+        )
+    };
+    let suggest = |kind: SuggestionKind, estimate: Option<u64>| Suggestion {
+        kind,
+        estimate,
+        selected: false,
+        status: StepStatus::Pending,
+    };
+    let mut suggestions = Vec::new();
+
+    // Old system generations, unless that would hit the booted / default one
+    let cutoff = std::time::SystemTime::now()
+        - std::time::Duration::from_secs(GENERATION_AGE_DAYS as u64 * 86400);
+    let old: Vec<budget::Generation> = budget::system_generations()
+        .into_iter()
+        .filter(|g| !g.is_current && g.modified < cutoff)
+        .collect();
+    if !old.is_empty() && endangered_by_cleanup(Some(GENERATION_AGE_DAYS)).is_none() {
+        let links: HashSet<&Path> = old.iter().map(|g| g.link.as_path()).collect();
+        suggestions.push(suggest(
+            SuggestionKind::OldGenerations {
+                days: GENERATION_AGE_DAYS,
+                generations: old.len(),
+            },
+            exclusive(&links),
+        ));
+    }
+
+    // Roots of builds, dev shells and Home-Manager
+    let hm_current: HashMap<PathBuf, String> = roots
+        .iter()
+        .filter_map(|(l, _)| l.parent())
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .filter_map(|dir| {
+            let current = std::fs::read_link(dir.join("home-manager")).ok()?;
+            Some((
+                dir.to_path_buf(),
+                current.file_name()?.to_string_lossy().to_string(),
+            ))
+        })
+        .collect();
+    let of = |root: Root| -> Vec<PathBuf> {
+        roots
+            .iter()
+            .filter(|(l, _)| classify(l, &hm_current) == Some(root))
+            .map(|(l, _)| l.clone())
+            .collect()
+    };
+    for (root, kind) in [
+        (Root::Result, SuggestionKind::ResultLinks as fn(_) -> _),
+        (Root::DevCache, SuggestionKind::DevCaches),
+        (Root::OldHomeManager, SuggestionKind::HomeManager),
+    ] {
+        let links = of(root);
+        if links.is_empty() {
+            continue;
+        }
+        let estimate = exclusive(&links.iter().map(PathBuf::as_path).collect());
+        suggestions.push(suggest(kind(links), estimate));
+    }
+
+    if info.dead_paths > 0 || !suggestions.is_empty() {
+        let mut dead: Vec<(String, u64)> = info
+            .paths
+            .iter()
+            .filter(|p| p.is_dead)
+            .map(|p| (p.name.clone(), p.size))
+            .collect();
+        dead.sort_by_key(|d| std::cmp::Reverse(d.1));
+        dead.truncate(LARGEST_DEAD);
+        suggestions.push(suggest(
+            SuggestionKind::CollectGarbage {
+                dead: info.dead_paths,
+                largest: dead,
+            },
+            info.has_sizes.then_some(info.dead_size),
+        ));
+    }
+    suggestions
+}
+
+/// Apply one suggestion, returning the bytes freed. Removing roots frees
+/// nothing by itself; the garbage collection after them does.
+pub fn run(kind: &SuggestionKind, lang: Language) -> Result<u64, String> {
+    match kind {
+        SuggestionKind::OldGenerations { days, .. } => {
+            // Checked again: a reboot or rebuild since the scan may have
+            // made an old generation the booted or default one
+            if let Some((id, role)) = endangered_by_cleanup(Some(*days)) {
+                return Err(crate::i18n::get_strings(lang)
+                    .gen_cleanup_refused
+                    .replace("{}", &boot_role_reason(id, role, lang)));
+            }
+            storage::run_delete_system_older_than(*days)
+                .map(|r| r.bytes_freed)
+                .map_err(|e| e.to_string())
+        }
+        SuggestionKind::CollectGarbage { .. } => storage::run_gc()
+            .map(|r| r.bytes_freed)
+            .map_err(|e| e.to_string()),
+        other => budget::remove_store_links(other.links()).map(|_| 0),
+    }
+}
+
+/// Select `idx`'s suggestion and, for one that removes roots, the garbage
+/// collection that turns it into free space
+pub fn toggle(suggestions: &mut [Suggestion], idx: usize) {
+    let Some(s) = suggestions.get_mut(idx) else {
+        return;
+    };
+    s.selected = !s.selected;
+    if s.selected && !s.kind.links().is_empty() {
+        if let Some(gc) = suggestions
+            .iter_mut()
+            .find(|s| matches!(s.kind, SuggestionKind::CollectGarbage { .. }))
+        {
+            gc.selected = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_and_toggle() {
+        let dir = PathBuf::from("/home/me/.local/state/nix/profiles");
+        let hm: HashMap<PathBuf, String> =
+            [(dir.clone(), "home-manager-12-link".to_string())].into();
+        let root = |p: &str| classify(Path::new(p), &hm);
+        assert_eq!(root("/home/me/src/app/result"), Some(Root::Result));
+        assert_eq!(
+            root("/home/me/src/app/.direnv/flake-profile-a5d5b61aa8a6-link"),
+            Some(Root::DevCache)
+        );
+        assert_eq!(
+            root("/home/me/src/app/.devenv/gc/shell"),
+            Some(Root::DevCache)
+        );
+        assert_eq!(
+            classify(&dir.join("home-manager-11-link"), &hm),
+            Some(Root::OldHomeManager)
+        );
+        assert_eq!(classify(&dir.join("home-manager-12-link"), &hm), None);
+        assert_eq!(root("/nix/var/nix/profiles/system-40-link"), None);
+
+        let item = |kind| Suggestion {
+            kind,
+            estimate: None,
+            selected: false,
+            status: StepStatus::Pending,
+        };
+        let mut list = vec![
+            item(SuggestionKind::ResultLinks(vec![PathBuf::from(
+                "/x/result",
+            )])),
+            item(SuggestionKind::CollectGarbage {
+                dead: 0,
+                largest: Vec::new(),
+            }),
+        ];
+        toggle(&mut list, 0);
+        assert!(list[0].selected && list[1].selected);
+        toggle(&mut list, 0);
+        assert!(!list[0].selected && list[1].selected);
+    }
+}
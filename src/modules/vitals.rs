@@ -0,0 +1,63 @@
+//! System vitals for the status bar
+//!
+//! Current generation, free store space and failed units are gathered by a
+//! background thread once a minute; rendering only reads the last result.
+//! Flake staleness comes from the Flake Inputs watch, which already runs in
+//! the background.
+
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+const REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Default)]
+pub struct VitalsSnapshot {
+    pub generation: Option<u32>,
+    /// Free bytes on the filesystem holding /nix/store
+    pub store_free: Option<u64>,
+    pub failed_units: Option<usize>,
+}
+
+#[derive(Default)]
+pub struct Vitals {
+    pub current: VitalsSnapshot,
+    rx: Option<mpsc::Receiver<VitalsSnapshot>>,
+    refreshed_at: Option<Instant>,
+}
+
+impl Vitals {
+    /// Pick up a finished collection and start the next one when due
+    pub fn tick(&mut self) {
+        if let Some(rx) = &self.rx {
+            match rx.try_recv() {
+                Ok(snapshot) => {
+                    self.current = snapshot;
+                    self.rx = None;
+                }
+                Err(mpsc::TryRecvError::Disconnected) => self.rx = None,
+                Err(mpsc::TryRecvError::Empty) => return,
+            }
+        }
+        if self
+            .refreshed_at
+            .is_some_and(|at| at.elapsed() < REFRESH_INTERVAL)
+        {
+            return;
+        }
+        self.refreshed_at = Some(Instant::now());
+        let (tx, rx) = mpsc::channel();
+        self.rx = Some(rx);
+        std::thread::spawn(move || {
+            let _ = tx.send(collect());
+        });
+    }
+}
+
+/// Blocking — run in background thread!
+fn collect() -> VitalsSnapshot {
+    VitalsSnapshot {
+        generation: crate::nix::generations::current_system_generation(),
+        store_free: crate::nix::storage::free_space(),
+        failed_units: crate::nix::services::failed_unit_count(),
+    }
+}
@@ -0,0 +1,211 @@
+//! Startup check whether Nix itself is usable.
+//!
+//! Probes the store, the `nix` CLI and the daemon once at startup (and on
+//! retry) so modules can show an explanation instead of each one failing
+//! with its own cryptic error after a long timeout.
+//!
+//! The platform is detected alongside: on Nix installed on another Linux
+//! distribution or macOS, the modules that read or build a NixOS system
+//! are disabled and the rest keeps working.
+
+use once_cell::sync::Lazy;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+const STORE_DIR: &str = "/nix/store";
+const DAEMON_SOCKET: &str = "/nix/var/nix/daemon-socket/socket";
+
+/// The system nixmate runs on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Platform {
+    NixOS,
+    /// macOS, with or without nix-darwin
+    Darwin,
+    /// Nix on another Linux distribution
+    Linux,
+}
+
+static PLATFORM: Lazy<Platform> = Lazy::new(|| {
+    if cfg!(target_os = "macos") {
+        return Platform::Darwin;
+    }
+    let os_release = std::fs::read_to_string("/etc/os-release").unwrap_or_default();
+    linux_platform(Path::new("/etc/NIXOS").exists(), &os_release)
+});
+
+impl Platform {
+    /// Detected once per run
+    pub fn current() -> Self {
+        *PLATFORM
+    }
+
+    pub fn is_nixos(self) -> bool {
+        self == Platform::NixOS
+    }
+}
+
+/// `/etc/NIXOS` marks a NixOS root; os-release covers containers built
+/// without it
+fn linux_platform(nixos_marker: bool, os_release: &str) -> Platform {
+    let nixos = nixos_marker
+        || os_release
+            .lines()
+            .any(|l| matches!(l.strip_prefix("ID="), Some("nixos" | "\"nixos\"")));
+    if nixos {
+        Platform::NixOS
+    } else {
+        Platform::Linux
+    }
+}
+
+/// What is missing for Nix to work
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NixProblem {
+    /// /nix/store does not exist
+    NoStore,
+    /// `nix` binary not found in PATH or not responding
+    NoCli,
+    /// Multi-user install but the daemon socket is unreachable
+    DaemonDown,
+    /// No daemon and the store is mounted read-only
+    StoreReadOnly,
+    /// Nix works, but the module needs a NixOS system
+    NotNixOS,
+}
+
+/// What a module needs from Nix to function
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NixNeed {
+    /// Read files below /nix (profiles, store paths)
+    Store,
+    /// Run `nix` commands that query the store
+    Query,
+    /// Build or modify the store
+    Build,
+}
+
+/// Result of probing the Nix installation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NixAvailability {
+    pub store: bool,
+    pub cli: bool,
+    /// Daemon reachable, or not needed (root / single-user install)
+    pub daemon: bool,
+    pub platform: Platform,
+}
+
+impl NixAvailability {
+    /// Probe the system. Blocking, but bounded by a short timeout.
+    pub fn probe() -> Self {
+        let store = Path::new(STORE_DIR).is_dir();
+        let cli = store && nix_cli_responds(Duration::from_secs(3));
+        let is_root = unsafe { libc::geteuid() } == 0;
+        let socket_ok = std::os::unix::net::UnixStream::connect(DAEMON_SOCKET).is_ok();
+        let direct_write = store && (is_root || store_dir_writable());
+
+        Self {
+            store,
+            cli,
+            daemon: socket_ok || direct_write,
+            platform: Platform::current(),
+        }
+    }
+
+    /// All detected problems, most fundamental first
+    pub fn problems(&self) -> Vec<NixProblem> {
+        let mut problems = Vec::new();
+        if !self.store {
+            problems.push(NixProblem::NoStore);
+            return problems;
+        }
+        if !self.cli {
+            problems.push(NixProblem::NoCli);
+        }
+        if !self.daemon {
+            if store_mounted_read_only() && !Path::new(DAEMON_SOCKET).exists() {
+                problems.push(NixProblem::StoreReadOnly);
+            } else {
+                problems.push(NixProblem::DaemonDown);
+            }
+        }
+        problems
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.problems().is_empty()
+    }
+
+    /// The problem that prevents something needing `need`, if any
+    pub fn blocking_problem(&self, need: NixNeed) -> Option<NixProblem> {
+        let problems = self.problems();
+        problems.into_iter().find(|p| match (need, p) {
+            (_, NixProblem::NoStore) => true,
+            (NixNeed::Store, _) => false,
+            (_, NixProblem::NoCli) | (_, NixProblem::DaemonDown) => true,
+            (NixNeed::Build, NixProblem::StoreReadOnly) => true,
+            (NixNeed::Query, NixProblem::StoreReadOnly) => false,
+            (_, NixProblem::NotNixOS) => false,
+        })
+    }
+}
+
+fn nix_cli_responds(timeout: Duration) -> bool {
+    let mut child = match Command::new("nix")
+        .arg("--version")
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+    {
+        Ok(c) => c,
+        Err(_) => return false,
+    };
+
+    let start = Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => return status.success(),
+            Ok(None) if start.elapsed() < timeout => {
+                std::thread::sleep(Duration::from_millis(20));
+            }
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return false;
+            }
+        }
+    }
+}
+
+fn store_dir_writable() -> bool {
+    let path = std::ffi::CString::new(STORE_DIR).expect("no NUL in path");
+    // SAFETY: path is a valid NUL-terminated string
+    unsafe { libc::access(path.as_ptr(), libc::W_OK) == 0 }
+}
+
+fn store_mounted_read_only() -> bool {
+    let path = std::ffi::CString::new(STORE_DIR).expect("no NUL in path");
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    // SAFETY: path is valid, stat is a properly sized out-parameter
+    let rc = unsafe { libc::statvfs(path.as_ptr(), &mut stat) };
+    rc == 0 && (stat.f_flag & libc::ST_RDONLY) != 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_linux_platform() {
+        assert_eq!(linux_platform(true, ""), Platform::NixOS);
+        assert_eq!(
+            linux_platform(false, "NAME=NixOS\nID=nixos\nVERSION_ID=\"24.11\""),
+            Platform::NixOS
+        );
+        assert_eq!(
+            linux_platform(false, "NAME=\"Ubuntu\"\nID=ubuntu\nID_LIKE=debian"),
+            Platform::Linux
+        );
+    }
+}
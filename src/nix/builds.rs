@@ -0,0 +1,381 @@
+//! System-wide detection of running Nix builds.
+//!
+//! Scans /proc for builder processes (children of nix-daemon running as
+//! nixbld users, or builds started without a daemon) and for client
+//! commands like `nixos-rebuild` or `nix build`. Works without root;
+//! with root more details (derivation names) can be resolved.
+//! Rebuilds nixmate detached into a systemd unit are recognized by their
+//! cgroup, so their journal can be followed later.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+/// Name prefix of the transient units detached rebuilds run in
+pub const DETACHED_UNIT_PREFIX: &str = "nixmate-rebuild-";
+
+/// A build currently running somewhere on this system
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunningBuild {
+    pub pid: u32,
+    /// Derivation name (e.g. "firefox-128.0"), or the command line if unknown
+    pub name: String,
+    /// Full .drv store path if it could be resolved (needed for `nix log`)
+    pub drv_path: Option<String>,
+    /// User who requested the build (the client), or the build user
+    pub owner: String,
+    pub elapsed: Duration,
+    /// true = builder process, false = client command (nixos-rebuild, nix build...)
+    pub is_builder: bool,
+    /// Transient unit of a rebuild detached from nixmate (log via journalctl)
+    pub unit: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct ProcInfo {
+    pid: u32,
+    ppid: u32,
+    uid: u32,
+    comm: String,
+    cmdline: Vec<String>,
+    start_ticks: u64,
+}
+
+/// Client commands that trigger builds
+const CLIENT_COMMANDS: &[&str] = &[
+    "nixos-rebuild",
+    "nixos-rebuild-ng",
+    "darwin-rebuild",
+    "home-manager",
+    "nix-build",
+    "nix-shell",
+    "nom",
+    "colmena",
+    "deploy",
+];
+
+/// `nix <subcommand>` invocations that can trigger builds
+const NIX_BUILD_SUBCOMMANDS: &[&str] = &["build", "develop", "shell", "run", "flake", "profile"];
+
+/// Scan /proc for running builds. Blocking — run in background thread!
+///
+/// `exclude_pid` hides a process tree (nixmate's own rebuild child).
+pub fn scan_running_builds(exclude_pid: Option<u32>) -> Vec<RunningBuild> {
+    let procs = read_all_procs();
+    if procs.is_empty() {
+        return Vec::new();
+    }
+
+    let by_pid: HashMap<u32, &ProcInfo> = procs.iter().map(|p| (p.pid, p)).collect();
+    let users = read_users();
+    let uptime = read_uptime();
+    let ticks = clock_ticks();
+    let own_pid = std::process::id();
+
+    let elapsed_of = |p: &ProcInfo| -> Duration {
+        let started = p.start_ticks as f64 / ticks;
+        Duration::from_secs_f64((uptime - started).max(0.0))
+    };
+    let owner_of =
+        |uid: u32| -> String { users.get(&uid).cloned().unwrap_or_else(|| uid.to_string()) };
+    let excluded = |p: &ProcInfo| -> bool {
+        has_ancestor(p, own_pid, &by_pid)
+            || exclude_pid.is_some_and(|ex| ex != 0 && has_ancestor(p, ex, &by_pid))
+    };
+
+    let is_build_user = |uid: u32| -> bool {
+        users
+            .get(&uid)
+            .is_some_and(|name| name.starts_with("nixbld"))
+            // auto-allocate-uids range
+            || (872_415_232..872_415_232 + 65_536 * 128).contains(&uid)
+    };
+
+    let mut builds = Vec::new();
+
+    // Builders: top-most process of each nixbld process tree
+    for p in &procs {
+        if !is_build_user(p.uid) || excluded(p) {
+            continue;
+        }
+        let parent_is_builder = by_pid.get(&p.ppid).is_some_and(|pp| is_build_user(pp.uid));
+        if parent_is_builder {
+            continue;
+        }
+
+        let (name, drv_path) = match derivation_of(p.pid) {
+            Some((name, drv)) => (name, drv),
+            None => (short_cmdline(p), None),
+        };
+
+        builds.push(RunningBuild {
+            pid: p.pid,
+            name,
+            drv_path,
+            owner: owner_of(p.uid),
+            elapsed: elapsed_of(p),
+            is_builder: true,
+            unit: None,
+        });
+    }
+
+    // Clients: nixos-rebuild, nix build, ... (skip nested invocations)
+    for p in &procs {
+        if !is_client(p) || excluded(p) {
+            continue;
+        }
+        let nested = ancestors(p, &by_pid).any(is_client);
+        if nested {
+            continue;
+        }
+
+        builds.push(RunningBuild {
+            pid: p.pid,
+            name: short_cmdline(p),
+            drv_path: None,
+            owner: owner_of(p.uid),
+            elapsed: elapsed_of(p),
+            is_builder: false,
+            unit: detached_unit_of(p.pid),
+        });
+    }
+
+    // Longest-running first
+    builds.sort_by_key(|b| std::cmp::Reverse(b.elapsed));
+    builds
+}
+
+fn is_client(p: &ProcInfo) -> bool {
+    let argv0 = p
+        .cmdline
+        .first()
+        .map(|a| a.rsplit('/').next().unwrap_or(a).to_string())
+        .unwrap_or_else(|| p.comm.clone());
+    // Wrapped binaries show up as ".nixos-rebuild-wrapped"
+    let argv0 = argv0
+        .trim_start_matches('.')
+        .trim_end_matches("-wrapped")
+        .to_string();
+
+    if CLIENT_COMMANDS.contains(&argv0.as_str()) {
+        return true;
+    }
+
+    if argv0 == "nix" {
+        return p
+            .cmdline
+            .iter()
+            .skip(1)
+            .find(|a| !a.starts_with('-'))
+            .is_some_and(|sub| NIX_BUILD_SUBCOMMANDS.contains(&sub.as_str()));
+    }
+
+    false
+}
+
+fn has_ancestor(p: &ProcInfo, pid: u32, by_pid: &HashMap<u32, &ProcInfo>) -> bool {
+    p.pid == pid || ancestors(p, by_pid).any(|a| a.pid == pid)
+}
+
+fn ancestors<'a>(
+    p: &ProcInfo,
+    by_pid: &'a HashMap<u32, &'a ProcInfo>,
+) -> impl Iterator<Item = &'a ProcInfo> + 'a {
+    let mut next = p.ppid;
+    let mut depth = 0;
+    std::iter::from_fn(move || {
+        // Depth limit guards against pid reuse loops
+        if next <= 1 || depth > 64 {
+            return None;
+        }
+        depth += 1;
+        let parent = *by_pid.get(&next)?;
+        next = parent.ppid;
+        Some(parent)
+    })
+}
+
+/// Resolve the derivation a builder is working on.
+///
+/// The build directory is named `nix-build-<name>.drv-<n>`; inside the
+/// sandbox it's `/build`, so fall back to the `name` environment variable
+/// (only readable as root). The .drv path comes from the build log file.
+fn derivation_of(pid: u32) -> Option<(String, Option<String>)> {
+    let name = std::fs::read_link(format!("/proc/{}/cwd", pid))
+        .ok()
+        .and_then(|cwd| {
+            cwd.components().find_map(|c| {
+                let part = c.as_os_str().to_string_lossy();
+                let rest = part.strip_prefix("nix-build-")?;
+                let end = rest.rfind(".drv-")?;
+                Some(rest[..end].to_string())
+            })
+        })
+        .or_else(|| {
+            let environ = std::fs::read(format!("/proc/{}/environ", pid)).ok()?;
+            environ
+                .split(|b| *b == 0)
+                .filter_map(|kv| std::str::from_utf8(kv).ok())
+                .find_map(|kv| kv.strip_prefix("name=").map(|s| s.to_string()))
+        })?;
+
+    Some((name.clone(), find_active_log_drv(&name)))
+}
+
+/// Find the .drv path of an in-progress build via its log file in
+/// /nix/var/log/nix/drvs (most recently modified file matching the name).
+fn find_active_log_drv(name: &str) -> Option<String> {
+    let suffix = format!("-{}.drv.bz2", name);
+    let base = Path::new("/nix/var/log/nix/drvs");
+    let mut best: Option<(std::time::SystemTime, String)> = None;
+
+    for bucket in std::fs::read_dir(base).ok()?.flatten() {
+        let bucket_name = bucket.file_name().to_string_lossy().to_string();
+        let Ok(entries) = std::fs::read_dir(bucket.path()) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let file = entry.file_name().to_string_lossy().to_string();
+            if !file.ends_with(&suffix) {
+                continue;
+            }
+            let Ok(modified) = entry.metadata().and_then(|m| m.modified()) else {
+                continue;
+            };
+            if best.as_ref().is_none_or(|(t, _)| modified > *t) {
+                let drv = format!(
+                    "/nix/store/{}{}",
+                    bucket_name,
+                    file.trim_end_matches(".bz2")
+                );
+                best = Some((modified, drv));
+            }
+        }
+    }
+
+    best.map(|(_, drv)| drv)
+}
+
+/// Detached nixmate rebuild unit the process runs in, from its cgroup
+/// ("0::/system.slice/nixmate-rebuild-20250101-120000.service")
+fn detached_unit_of(pid: u32) -> Option<String> {
+    let cgroup = std::fs::read_to_string(format!("/proc/{}/cgroup", pid)).ok()?;
+    cgroup.lines().find_map(|line| {
+        let unit = line.rsplit('/').next()?;
+        (unit.starts_with(DETACHED_UNIT_PREFIX) && unit.ends_with(".service"))
+            .then(|| unit.to_string())
+    })
+}
+
+fn short_cmdline(p: &ProcInfo) -> String {
+    if p.cmdline.is_empty() {
+        return p.comm.clone();
+    }
+    let parts: Vec<String> = p
+        .cmdline
+        .iter()
+        .map(|a| {
+            // Shorten store paths: /nix/store/<hash>-bash-5.2/bin/bash → bash
+            if a.starts_with("/nix/store/") {
+                a.rsplit('/').next().unwrap_or(a).to_string()
+            } else {
+                a.clone()
+            }
+        })
+        .collect();
+    let joined = parts.join(" ");
+    if joined.chars().count() > 80 {
+        let truncated: String = joined.chars().take(77).collect();
+        format!("{}...", truncated)
+    } else {
+        joined
+    }
+}
+
+// ── /proc readers ──
+
+fn read_all_procs() -> Vec<ProcInfo> {
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter_map(|e| e.file_name().to_str()?.parse::<u32>().ok())
+        .filter_map(read_proc)
+        .collect()
+}
+
+fn read_proc(pid: u32) -> Option<ProcInfo> {
+    let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    let (comm, ppid, start_ticks) = parse_stat(&stat)?;
+
+    let status = std::fs::read_to_string(format!("/proc/{}/status", pid)).ok()?;
+    let uid = status
+        .lines()
+        .find_map(|l| l.strip_prefix("Uid:"))
+        .and_then(|rest| rest.split_whitespace().next())
+        .and_then(|u| u.parse().ok())?;
+
+    let cmdline = std::fs::read(format!("/proc/{}/cmdline", pid))
+        .map(|raw| {
+            raw.split(|b| *b == 0)
+                .filter(|a| !a.is_empty())
+                .map(|a| String::from_utf8_lossy(a).to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Some(ProcInfo {
+        pid,
+        ppid,
+        uid,
+        comm,
+        cmdline,
+        start_ticks,
+    })
+}
+
+/// Parse /proc/<pid>/stat → (comm, ppid, starttime in clock ticks)
+fn parse_stat(stat: &str) -> Option<(String, u32, u64)> {
+    // comm may contain spaces and parens: "123 (my (weird) cmd) S 1 ..."
+    let open = stat.find('(')?;
+    let close = stat.rfind(')')?;
+    let comm = stat[open + 1..close].to_string();
+    let fields: Vec<&str> = stat[close + 1..].split_whitespace().collect();
+    // fields[0] = state (field 3), ppid = field 4, starttime = field 22
+    let ppid = fields.get(1)?.parse().ok()?;
+    let start_ticks = fields.get(19)?.parse().ok()?;
+    Some((comm, ppid, start_ticks))
+}
+
+fn read_uptime() -> f64 {
+    std::fs::read_to_string("/proc/uptime")
+        .ok()
+        .and_then(|s| s.split_whitespace().next()?.parse().ok())
+        .unwrap_or(0.0)
+}
+
+fn clock_ticks() -> f64 {
+    // SAFETY: sysconf has no preconditions
+    let ticks = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+    if ticks > 0 {
+        ticks as f64
+    } else {
+        100.0
+    }
+}
+
+fn read_users() -> HashMap<u32, String> {
+    std::fs::read_to_string("/etc/passwd")
+        .map(|content| {
+            content
+                .lines()
+                .filter_map(|line| {
+                    let parts: Vec<&str> = line.split(':').collect();
+                    let uid = parts.get(2)?.parse().ok()?;
+                    Some((uid, parts[0].to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
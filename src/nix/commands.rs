@@ -147,7 +147,7 @@ fn build_delete_command(
     }
 }
 
-fn command_exists(cmd: &str) -> bool {
+pub fn command_exists(cmd: &str) -> bool {
     Command::new("which")
         .arg(cmd)
         .stdout(Stdio::null())
@@ -66,42 +66,57 @@ fn get_username() -> Result<String> {
         .context("Could not determine username from USER or LOGNAME environment variable")
 }
 
-pub fn detect_flakes(custom_path: Option<&str>) -> bool {
+/// Where the system configuration may live: the custom config path (`~/`
+/// expanded) first, then the usual locations
+pub fn flake_candidates(custom_path: Option<&str>) -> Vec<String> {
     let home = env::var("HOME").unwrap_or_default();
-    let mut flake_paths = Vec::new();
-    if let Some(p) = custom_path {
-        flake_paths.push(PathBuf::from(p).join("flake.nix"));
+    custom_path
+        .map(expand_home)
+        .into_iter()
+        .chain([
+            "/etc/nixos".to_string(),
+            format!("{}/.config/nixos", home),
+            format!("{}/nixos", home),
+            format!("{}/.nixos", home),
+        ])
+        .collect()
+}
+
+/// `~/…` to an absolute path
+pub fn expand_home(path: &str) -> String {
+    match path.strip_prefix("~/") {
+        Some(rest) => format!("{}/{}", env::var("HOME").unwrap_or_default(), rest),
+        None => path.to_string(),
     }
-    flake_paths.extend([
-        PathBuf::from("/etc/nixos/flake.nix"),
-        PathBuf::from(&home).join(".config/nixos/flake.nix"),
-        PathBuf::from(&home).join("nixos/flake.nix"),
-        PathBuf::from(&home).join(".nixos/flake.nix"),
-    ]);
-    flake_paths.iter().any(|p| p.exists())
+}
+
+pub fn detect_flakes(custom_path: Option<&str>) -> bool {
+    find_flake_path(custom_path).is_some()
 }
 
 /// Find the directory containing flake.nix (checks common locations)
 pub fn find_flake_path(custom_path: Option<&str>) -> Option<String> {
-    let home = env::var("HOME").unwrap_or_default();
-    let mut candidates = Vec::new();
-    if let Some(p) = custom_path {
-        candidates.push(format!("{}/flake.nix", p));
-    }
-    candidates.extend([
-        "/etc/nixos/flake.nix".to_string(),
-        format!("{}/.config/nixos/flake.nix", home),
-        format!("{}/nixos/flake.nix", home),
-        format!("{}/.nixos/flake.nix", home),
-    ]);
-
-    for candidate in &candidates {
-        let path = Path::new(candidate);
-        if path.exists() {
-            return path.parent().map(|p| p.to_string_lossy().to_string());
-        }
-    }
-    None
+    flake_candidates(custom_path)
+        .into_iter()
+        .find(|dir| Path::new(dir).join("flake.nix").exists())
+}
+
+/// The `nixosConfigurations` attribute the system is built from: the one
+/// picked in Rebuild (`nixos_host` in config.toml), else this machine's
+/// host name (what `nixos-rebuild` uses for a bare `flake#`)
+pub fn nixos_host(configured: Option<&str>) -> String {
+    configured
+        .filter(|h| !h.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(machine_hostname)
+}
+
+/// This machine's host name, as `nixos-rebuild` reads it
+pub fn machine_hostname() -> String {
+    std::fs::read_to_string("/proc/sys/kernel/hostname")
+        .or_else(|_| std::fs::read_to_string("/etc/hostname"))
+        .map(|h| h.trim().to_string())
+        .unwrap_or_default()
 }
 
 fn detect_home_manager(username: &str) -> Option<HomeManagerInfo> {
@@ -0,0 +1,122 @@
+//! Ports opened in the NixOS firewall
+//!
+//! `networking.firewall.allowedTCPPorts` / `allowedUDPPorts` (and the
+//! per-interface variants) end up as rules in the script `firewall.service`
+//! runs, or in the ruleset `nftables.service` loads. Like `proxies`, this
+//! reads the generated files instead of evaluating the configuration.
+//! Port ranges are skipped: nobody expects every port of a range to listen.
+
+use super::services::run_with_timeout;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+static IPTABLES: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"-p (tcp|udp) --dport (\d+)(:\d+)? -j nixos-fw-accept").unwrap());
+static NFT: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(tcp|udp) dport (\{[^}]*\}|[\d-]+) accept").unwrap());
+
+/// A port the configuration opens
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DeclaredPort {
+    /// "tcp" or "udp", as in `PortEntry`
+    pub protocol: String,
+    pub port: u16,
+}
+
+/// Ports opened by the active firewall. Blocking — run in background thread!
+pub fn declared_ports() -> Vec<DeclaredPort> {
+    let mut ports = Vec::new();
+    for unit in ["firewall.service", "nftables.service"] {
+        for script in unit_scripts(unit) {
+            ports.extend(parse_rules(&script));
+        }
+    }
+    ports.sort();
+    ports.dedup();
+    ports
+}
+
+/// The program a unit starts, plus the store files it references whose name
+/// mentions rules (nftables loads its ruleset from a separate file)
+fn unit_scripts(unit: &str) -> Vec<String> {
+    let exec = run_with_timeout(
+        "systemctl",
+        &["show", "-p", "ExecStart", "--value", unit],
+        3,
+    )
+    .unwrap_or_default();
+    let Some(program) = exec
+        .split(" ; ")
+        .find_map(|part| part.trim().strip_prefix("path="))
+        .map(str::trim)
+    else {
+        return Vec::new();
+    };
+    let Ok(script) = std::fs::read_to_string(program) else {
+        return Vec::new();
+    };
+
+    let mut scripts: Vec<String> = script
+        .split(|c: char| c.is_whitespace() || c == '\'' || c == '"')
+        .filter(|w| w.starts_with("/nix/store/") && w.contains("rules"))
+        .filter_map(|w| std::fs::read_to_string(w).ok())
+        .collect();
+    scripts.push(script);
+    scripts
+}
+
+/// Single ports accepted by iptables (`--dport 22`) or nftables
+/// (`tcp dport { 22, 80 } accept`) rules
+fn parse_rules(script: &str) -> Vec<DeclaredPort> {
+    let mut ports = Vec::new();
+    for cap in IPTABLES.captures_iter(script) {
+        if cap.get(3).is_some() {
+            continue;
+        }
+        if let Ok(port) = cap[2].parse() {
+            ports.push(DeclaredPort {
+                protocol: cap[1].to_string(),
+                port,
+            });
+        }
+    }
+    for cap in NFT.captures_iter(script) {
+        let list = cap[2].trim_matches(|c| c == '{' || c == '}');
+        for item in list.split(',').map(str::trim) {
+            if let Ok(port) = item.parse() {
+                ports.push(DeclaredPort {
+                    protocol: cap[1].to_string(),
+                    port,
+                });
+            }
+        }
+    }
+    ports
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_firewall_rules() {
+        let iptables = "\
+ip46tables -A nixos-fw -p tcp --dport 22 -j nixos-fw-accept
+ip46tables -A nixos-fw -p tcp --dport 8000:8010 -j nixos-fw-accept
+ip46tables -A nixos-fw -p udp --dport 51820 -j nixos-fw-accept -i wg0
+";
+        let nft = "chain input-allow {\n  tcp dport { 80, 443 } accept\n  udp dport 5353 accept\n  tcp dport 6000-6010 accept\n}";
+        let port = |protocol: &str, port| DeclaredPort {
+            protocol: protocol.to_string(),
+            port,
+        };
+        assert_eq!(
+            parse_rules(iptables),
+            vec![port("tcp", 22), port("udp", 51820)]
+        );
+        assert_eq!(
+            parse_rules(nft),
+            vec![port("tcp", 80), port("tcp", 443), port("udp", 5353)]
+        );
+    }
+}
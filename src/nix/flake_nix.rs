@@ -0,0 +1,802 @@
+//! Minimal flake.nix `inputs` parser and rewriter
+//!
+//! Not a Nix parser — a tokenizer that understands just enough structure
+//! (attribute paths, strings, comments, nesting) to find the `inputs`
+//! declarations and the `outputs` argument pattern. Edits are done by
+//! splicing the original text, so formatting and comments elsewhere in
+//! the file are preserved. Handles the common forms:
+//!
+//! ```nix
+//! inputs.nixpkgs.url = "github:NixOS/nixpkgs/nixos-unstable";
+//! inputs = {
+//!   home-manager = { url = "github:nix-community/home-manager"; inputs.nixpkgs.follows = "nixpkgs"; };
+//!   foo.url = "git+https://example.org/foo?ref=main";
+//! };
+//! ```
+
+use anyhow::{bail, Context, Result};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+/// An input as declared in flake.nix
+#[derive(Debug, Clone)]
+pub struct DeclaredInput {
+    pub name: String,
+    pub url: Option<String>,
+    /// Byte range of the url string contents (without quotes)
+    url_span: Option<Range<usize>>,
+    /// Statements declaring this input (removed together)
+    statements: Vec<Range<usize>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FlakeNix {
+    source: String,
+    pub inputs: Vec<DeclaredInput>,
+    /// `inputs = { ... }` block: positions of the braces
+    inputs_block: Option<(usize, usize)>,
+    /// Last top-level `inputs.<name>...` statement (insert point for that style)
+    last_flat_statement: Option<Range<usize>>,
+    /// `outputs = { ... }:` pattern: positions of the braces, has `...`
+    outputs_pattern: Option<(usize, usize, bool)>,
+    /// (follower path, followed input) for every `follows = "x"`
+    follows: Vec<(String, String)>,
+}
+
+impl FlakeNix {
+    pub fn parse(source: &str) -> Result<Self> {
+        let tokens = tokenize(source)?;
+        let mut flake = FlakeNix {
+            source: source.to_string(),
+            inputs: Vec::new(),
+            inputs_block: None,
+            last_flat_statement: None,
+            outputs_pattern: None,
+            follows: Vec::new(),
+        };
+
+        // The flake is one attribute set; its statements start after the first `{`
+        let start = tokens
+            .iter()
+            .position(|t| t.kind == Tok::LBrace)
+            .context("flake.nix: no top-level attribute set")?;
+        let mut i = start + 1;
+        while i < tokens.len() && tokens[i].kind != Tok::RBrace {
+            let stmt = Statement::parse(source, &tokens, i)?;
+            let value_is_set = tokens[stmt.value_start].kind == Tok::LBrace;
+            match stmt.path.first().map(|s| s.as_str()) {
+                Some("inputs") if stmt.path.len() == 1 && value_is_set => {
+                    let close = matching_brace(&tokens, stmt.value_start)?;
+                    flake.inputs_block = Some((
+                        tokens[stmt.value_start].span.start,
+                        tokens[close].span.start,
+                    ));
+                    let mut j = stmt.value_start + 1;
+                    while j < close {
+                        let inner = Statement::parse(source, &tokens, j)?;
+                        flake.record(&tokens, &inner.path, &inner, source);
+                        j = inner.end + 1;
+                    }
+                }
+                Some("inputs") if stmt.path.len() > 1 => {
+                    flake.record(&tokens, &stmt.path[1..], &stmt, source);
+                    flake.last_flat_statement = Some(stmt.span(&tokens));
+                }
+                Some("outputs") if stmt.path.len() == 1 && value_is_set => {
+                    let close = matching_brace(&tokens, stmt.value_start)?;
+                    // `{ self, ... }:` is a function argument pattern, not a set
+                    if tokens.get(close + 1).map(|t| t.kind) == Some(Tok::Colon) {
+                        let ellipsis = source
+                            [tokens[stmt.value_start].span.start..tokens[close].span.end]
+                            .contains("...");
+                        flake.outputs_pattern = Some((
+                            tokens[stmt.value_start].span.start,
+                            tokens[close].span.start,
+                            ellipsis,
+                        ));
+                    }
+                }
+                _ => {}
+            }
+            i = stmt.end + 1;
+        }
+        Ok(flake)
+    }
+
+    /// Record a statement below `inputs`; `path` starts with the input name
+    fn record(&mut self, tokens: &[Token], path: &[String], stmt: &Statement, source: &str) {
+        let Some(name) = path.first() else {
+            return;
+        };
+        let idx = match self.inputs.iter().position(|i| &i.name == name) {
+            Some(idx) => idx,
+            None => {
+                self.inputs.push(DeclaredInput {
+                    name: name.clone(),
+                    url: None,
+                    url_span: None,
+                    statements: Vec::new(),
+                });
+                self.inputs.len() - 1
+            }
+        };
+        self.inputs[idx].statements.push(stmt.span(tokens));
+
+        let rest = &path[1..];
+        let value = &tokens[stmt.value_start];
+        if rest == ["url"] && value.kind == Tok::Str {
+            self.set_found_url(idx, value.span.clone(), source);
+        } else if rest.last().map(|s| s.as_str()) == Some("follows") && value.kind == Tok::Str {
+            self.follows.push((
+                path[..path.len() - 1].join("."),
+                source[value.span.clone()].to_string(),
+            ));
+        } else if rest.is_empty() && value.kind == Tok::LBrace {
+            // name = { url = "..."; inputs.x.follows = "y"; ... };
+            let Ok(close) = matching_brace(tokens, stmt.value_start) else {
+                return;
+            };
+            let mut j = stmt.value_start + 1;
+            while j < close {
+                let Ok(inner) = Statement::parse(source, tokens, j) else {
+                    return;
+                };
+                let v = &tokens[inner.value_start];
+                if inner.path == ["url"] && v.kind == Tok::Str {
+                    self.set_found_url(idx, v.span.clone(), source);
+                } else if inner.path.last().map(|s| s.as_str()) == Some("follows")
+                    && v.kind == Tok::Str
+                {
+                    self.follows.push((
+                        format!("{}.{}", name, inner.path[..inner.path.len() - 1].join(".")),
+                        source[v.span.clone()].to_string(),
+                    ));
+                }
+                j = inner.end + 1;
+            }
+        }
+    }
+
+    fn set_found_url(&mut self, idx: usize, span: Range<usize>, source: &str) {
+        self.inputs[idx].url = Some(source[span.clone()].to_string());
+        self.inputs[idx].url_span = Some(span);
+    }
+
+    pub fn input(&self, name: &str) -> Option<&DeclaredInput> {
+        self.inputs.iter().find(|i| i.name == name)
+    }
+
+    /// Inputs that `follows` the given one (e.g. "home-manager.inputs.nixpkgs")
+    pub fn followers_of(&self, name: &str) -> Vec<String> {
+        self.follows
+            .iter()
+            .filter(|(_, target)| target == name || target.starts_with(&format!("{}/", name)))
+            .filter(|(path, _)| path.split('.').next() != Some(name))
+            .map(|(path, _)| path.clone())
+            .collect()
+    }
+
+    /// Source with the url of `name` replaced
+    pub fn with_url(&self, name: &str, url: &str) -> Result<String> {
+        let input = self
+            .input(name)
+            .with_context(|| format!("input '{}' is not declared in flake.nix", name))?;
+        let span = input
+            .url_span
+            .clone()
+            .with_context(|| format!("input '{}' has no url attribute", name))?;
+        let mut out = self.source.clone();
+        let url = if self.source[..span.start].ends_with("''") {
+            escape_indented(url)
+        } else {
+            escape(url)
+        };
+        out.replace_range(span, &url);
+        Ok(out)
+    }
+
+    /// Source with a new input added (and named in the outputs pattern)
+    pub fn with_added(&self, name: &str, url: &str) -> Result<String> {
+        if !is_valid_name(name) {
+            bail!("'{}' is not a valid input name", name);
+        }
+        if self.input(name).is_some() {
+            bail!("input '{}' already exists", name);
+        }
+
+        let mut edits: Vec<(Range<usize>, String)> = Vec::new();
+        if let Some((_, close)) = self.inputs_block {
+            // Indent like the block's other entries
+            let indent = self
+                .inputs
+                .iter()
+                .filter_map(|i| i.statements.first())
+                .map(|s| line_indent(&self.source, s.start))
+                .next_back()
+                .unwrap_or_else(|| format!("{}  ", line_indent(&self.source, close)));
+            let insert_at = line_start(&self.source, close);
+            let text = format!("{}{}.url = \"{}\";\n", indent, name, escape(url));
+            if self.source[insert_at..close].trim().is_empty() {
+                edits.push((insert_at..insert_at, text));
+            } else {
+                // `inputs = { a.url = "..."; };` on one line
+                edits.push((
+                    close..close,
+                    format!(" {}.url = \"{}\"; ", name, escape(url)),
+                ));
+            }
+        } else if let Some(last) = &self.last_flat_statement {
+            let indent = line_indent(&self.source, last.start);
+            edits.push((
+                last.end..last.end,
+                format!("\n{}inputs.{}.url = \"{}\";", indent, name, escape(url)),
+            ));
+        } else {
+            bail!("flake.nix has no inputs section");
+        }
+
+        if let Some((open, close, ellipsis)) = self.outputs_pattern {
+            if !ellipsis {
+                let inner = self.source[open + 1..close].trim_end();
+                let sep = if inner.trim().is_empty() || inner.ends_with(',') {
+                    " "
+                } else {
+                    ", "
+                };
+                let pos = open + 1 + inner.len();
+                edits.push((pos..pos, format!("{}{}", sep, name)));
+            }
+        }
+        Ok(apply(&self.source, edits))
+    }
+
+    /// Source with an input and its statements removed
+    pub fn without(&self, name: &str) -> Result<String> {
+        let input = self
+            .input(name)
+            .with_context(|| format!("input '{}' is not declared in flake.nix", name))?;
+        let mut edits: Vec<(Range<usize>, String)> = input
+            .statements
+            .iter()
+            .map(|s| (whole_lines(&self.source, s.clone()), String::new()))
+            .collect();
+
+        // Drop the name from `outputs = { self, nixpkgs, <name>, ... }:`
+        if let Some((open, close, _)) = self.outputs_pattern {
+            if let Some(span) = pattern_entry(&self.source, open + 1, close, name) {
+                edits.push((span, String::new()));
+            }
+        }
+        Ok(apply(&self.source, edits))
+    }
+}
+
+/// Current ref of a flake URL ("github:o/r/ref", "...?ref=x")
+pub fn url_ref(url: &str) -> Option<String> {
+    if let Some((_, query)) = url.split_once('?') {
+        return query
+            .split('&')
+            .find_map(|kv| kv.strip_prefix("ref="))
+            .map(|r| r.to_string());
+    }
+    let (scheme, rest) = url.split_once(':')?;
+    if matches!(scheme, "github" | "gitlab" | "sourcehut") {
+        let parts: Vec<&str> = rest.splitn(3, '/').collect();
+        return parts.get(2).map(|r| r.to_string());
+    }
+    None
+}
+
+/// Flake URL with its ref replaced (empty ref = default branch)
+pub fn with_ref(url: &str, new_ref: &str) -> String {
+    let new_ref = new_ref.trim();
+    let (base, query) = match url.split_once('?') {
+        Some((b, q)) => (b, Some(q)),
+        None => (url, None),
+    };
+    if let Some((scheme, rest)) = base.split_once(':') {
+        if matches!(scheme, "github" | "gitlab" | "sourcehut") {
+            let parts: Vec<&str> = rest.splitn(3, '/').collect();
+            let mut out = format!("{}:{}", scheme, parts[..parts.len().min(2)].join("/"));
+            if !new_ref.is_empty() {
+                out.push('/');
+                out.push_str(new_ref);
+            }
+            if let Some(q) = query {
+                out.push('?');
+                out.push_str(q);
+            }
+            return out;
+        }
+    }
+    // git+https://…, tarballs etc.: ?ref=
+    let mut params: Vec<String> = query
+        .map(|q| {
+            q.split('&')
+                .filter(|kv| !kv.starts_with("ref=") && !kv.is_empty())
+                .map(|kv| kv.to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+    if !new_ref.is_empty() {
+        params.insert(0, format!("ref={}", new_ref));
+    }
+    if params.is_empty() {
+        base.to_string()
+    } else {
+        format!("{}?{}", base, params.join("&"))
+    }
+}
+
+/// Write `content` to `path`, keeping the previous file as `<path>.bak`
+pub fn write_with_backup(path: &Path, content: &str) -> Result<PathBuf> {
+    let backup = path.with_extension("nix.bak");
+    std::fs::copy(path, &backup)
+        .with_context(|| format!("Failed to back up {}", path.display()))?;
+    std::fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(backup)
+}
+
+// ── Tokenizer ──
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tok {
+    Ident,
+    /// String literal; span covers the contents only
+    Str,
+    Dot,
+    Eq,
+    Semi,
+    Colon,
+    LBrace,
+    RBrace,
+    /// ( [ and ) ] — only tracked for nesting
+    Open,
+    Close,
+    Other,
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: Tok,
+    span: Range<usize>,
+    /// Start of the token including quotes
+    outer_start: usize,
+    /// End of the token including quotes
+    outer_end: usize,
+}
+
+fn tokenize(src: &str) -> Result<Vec<Token>> {
+    let bytes = src.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    let push = |tokens: &mut Vec<Token>, kind, span: Range<usize>, outer: Range<usize>| {
+        tokens.push(Token {
+            kind,
+            span,
+            outer_start: outer.start,
+            outer_end: outer.end,
+        })
+    };
+    while i < bytes.len() {
+        let c = bytes[i];
+        match c {
+            b' ' | b'\t' | b'\n' | b'\r' => i += 1,
+            b'#' => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                let end = src[i + 2..]
+                    .find("*/")
+                    .context("flake.nix: unterminated comment")?;
+                i += end + 4;
+            }
+            b'"' => {
+                let start = i;
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    if bytes[i] == b'\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+                if i >= bytes.len() {
+                    bail!("flake.nix: unterminated string");
+                }
+                push(&mut tokens, Tok::Str, start + 1..i, start..i + 1);
+                i += 1;
+            }
+            b'\'' if bytes.get(i + 1) == Some(&b'\'') => {
+                let start = i;
+                i += 2;
+                loop {
+                    let end = src[i..]
+                        .find("''")
+                        .context("flake.nix: unterminated string")?;
+                    i += end;
+                    // `'''` is a literal `''`, `''$` a literal `$`, `''\x` an escaped x
+                    match bytes.get(i + 2) {
+                        Some(b'\'' | b'$') => i += 3,
+                        Some(b'\\') => {
+                            i += 3 + src[i + 3..].chars().next().map_or(0, char::len_utf8)
+                        }
+                        _ => break,
+                    }
+                }
+                push(&mut tokens, Tok::Str, start + 2..i, start..i + 2);
+                i += 2;
+            }
+            b'.' if bytes.get(i + 1) == Some(&b'.') => {
+                // `...` in argument patterns
+                push(&mut tokens, Tok::Other, i..i + 3, i..i + 3);
+                i += 3;
+            }
+            _ if c.is_ascii_alphabetic() || c == b'_' => {
+                let start = i;
+                while i < bytes.len()
+                    && (bytes[i].is_ascii_alphanumeric() || matches!(bytes[i], b'_' | b'-' | b'\''))
+                {
+                    i += 1;
+                }
+                push(&mut tokens, Tok::Ident, start..i, start..i);
+            }
+            _ => {
+                let kind = match c {
+                    b'.' => Tok::Dot,
+                    b'=' if bytes.get(i + 1) == Some(&b'=') => Tok::Other,
+                    b'=' => Tok::Eq,
+                    b';' => Tok::Semi,
+                    b':' => Tok::Colon,
+                    b'{' => Tok::LBrace,
+                    b'}' => Tok::RBrace,
+                    b'(' | b'[' => Tok::Open,
+                    b')' | b']' => Tok::Close,
+                    _ => Tok::Other,
+                };
+                // Skip `==` as a unit; other multi-byte chars one at a time
+                let len = if kind == Tok::Other && c == b'=' {
+                    2
+                } else {
+                    src[i..].chars().next().map(|ch| ch.len_utf8()).unwrap_or(1)
+                };
+                push(&mut tokens, kind, i..i + len, i..i + len);
+                i += len;
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+/// `a.b."c" = <value>;` — `end` is the index of the `;`
+struct Statement {
+    path: Vec<String>,
+    first: usize,
+    value_start: usize,
+    end: usize,
+}
+
+impl Statement {
+    fn parse(src: &str, tokens: &[Token], start: usize) -> Result<Self> {
+        let mut path = Vec::new();
+        let mut i = start;
+        loop {
+            let t = tokens.get(i).context("flake.nix: unexpected end of file")?;
+            match t.kind {
+                Tok::Ident | Tok::Str => path.push(src[t.span.clone()].to_string()),
+                Tok::Dot => {}
+                Tok::Eq => break,
+                _ => {
+                    // `inherit ...;` and anything else we don't understand: skip
+                    let end = skip_to_semi(tokens, i)?;
+                    return Ok(Statement {
+                        path: Vec::new(),
+                        first: start,
+                        value_start: end,
+                        end,
+                    });
+                }
+            }
+            i += 1;
+        }
+        let value_start = i + 1;
+        if value_start >= tokens.len() {
+            bail!("flake.nix: unexpected end of file");
+        }
+        let end = skip_to_semi(tokens, value_start)?;
+        Ok(Statement {
+            path,
+            first: start,
+            value_start,
+            end,
+        })
+    }
+
+    /// Byte range from the first path token to the `;`
+    fn span(&self, tokens: &[Token]) -> Range<usize> {
+        tokens[self.first].outer_start..tokens[self.end].outer_end
+    }
+}
+
+fn skip_to_semi(tokens: &[Token], from: usize) -> Result<usize> {
+    let mut depth = 0i32;
+    for (i, t) in tokens.iter().enumerate().skip(from) {
+        match t.kind {
+            Tok::LBrace | Tok::Open => depth += 1,
+            Tok::RBrace | Tok::Close => depth -= 1,
+            Tok::Semi if depth == 0 => return Ok(i),
+            _ => {}
+        }
+        if depth < 0 {
+            bail!("flake.nix: missing ';'");
+        }
+    }
+    bail!("flake.nix: missing ';'")
+}
+
+fn matching_brace(tokens: &[Token], open: usize) -> Result<usize> {
+    let mut depth = 0i32;
+    for (i, t) in tokens.iter().enumerate().skip(open) {
+        match t.kind {
+            Tok::LBrace | Tok::Open => depth += 1,
+            Tok::RBrace | Tok::Close => {
+                depth -= 1;
+                if depth == 0 {
+                    return Ok(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    bail!("flake.nix: unbalanced braces")
+}
+
+// ── Text helpers ──
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace("${", "\\${")
+}
+
+/// For `''…''` strings
+fn escape_indented(s: &str) -> String {
+    s.replace("''", "'''").replace("${", "''${")
+}
+
+fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '\''))
+}
+
+fn line_start(src: &str, pos: usize) -> usize {
+    src[..pos].rfind('\n').map(|p| p + 1).unwrap_or(0)
+}
+
+fn line_indent(src: &str, pos: usize) -> String {
+    let start = line_start(src, pos);
+    src[start..pos]
+        .chars()
+        .take_while(|c| c.is_whitespace())
+        .collect()
+}
+
+/// Extend a range to whole lines if nothing else is on them (a trailing
+/// `#` comment goes with the line)
+fn whole_lines(src: &str, range: Range<usize>) -> Range<usize> {
+    let start = line_start(src, range.start);
+    let end = src[range.end..]
+        .find('\n')
+        .map(|p| range.end + p + 1)
+        .unwrap_or(src.len());
+    let before_blank = src[start..range.start].trim().is_empty();
+    let after = src[range.end..end].trim();
+    let after_blank = after.is_empty() || after.starts_with('#');
+    if before_blank && after_blank {
+        start..end
+    } else {
+        range
+    }
+}
+
+/// Range of `name` (with its separating comma) in an argument pattern
+fn pattern_entry(src: &str, from: usize, to: usize, name: &str) -> Option<Range<usize>> {
+    let inner = &src[from..to];
+    let mut offset = 0;
+    for part in inner.split(',') {
+        let trimmed = part.trim();
+        // `name ? default` is also an entry for `name`
+        let entry = trimmed
+            .split(|c: char| c.is_whitespace() || c == '?')
+            .next();
+        if entry == Some(name) {
+            let start = from + offset;
+            let end = start + part.len();
+            if end < to {
+                // with its trailing comma
+                return Some(start..end + 1);
+            }
+            // last entry: eat the preceding comma instead, keep the padding
+            let name_end = start + part.trim_end().len();
+            return Some(start.saturating_sub(1).max(from)..name_end);
+        }
+        offset += part.len() + 1;
+    }
+    None
+}
+
+fn apply(src: &str, mut edits: Vec<(Range<usize>, String)>) -> String {
+    edits.sort_by_key(|(r, _)| std::cmp::Reverse(r.start));
+    let mut out = src.to_string();
+    for (range, text) in edits {
+        out.replace_range(range, &text);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FLAKE: &str = r#"{
+  description = "test; with { braces } and ''quotes''";
+
+  inputs = {
+    # nixpkgs.url = "github:NixOS/nixpkgs/nixos-23.11";
+    nixpkgs.url = "github:NixOS/nixpkgs/nixos-unstable";
+    home-manager = {
+      url = "github:nix-community/home-manager";
+      inputs.nixpkgs.follows = "nixpkgs";
+    };
+    /* block comment: foo.url = "x"; */
+    hardware.url = ''github:NixOS/nixos-hardware''; # indented string
+  };
+
+  outputs = { self, nixpkgs, home-manager, hardware }: {
+    motd = '' it''''s ''${not} a '''quote''' '';
+  };
+}
+"#;
+
+    #[test]
+    fn test_parse() {
+        let flake = FlakeNix::parse(FLAKE).unwrap();
+        let names: Vec<&str> = flake.inputs.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(names, vec!["nixpkgs", "home-manager", "hardware"]);
+        assert_eq!(
+            flake.input("hardware").unwrap().url.as_deref(),
+            Some("github:NixOS/nixos-hardware")
+        );
+        assert_eq!(
+            flake.followers_of("nixpkgs"),
+            vec!["home-manager.inputs.nixpkgs"]
+        );
+        assert_eq!(flake.outputs_pattern.map(|(_, _, e)| e), Some(false));
+    }
+
+    #[test]
+    fn test_indented_string_escapes() {
+        let src = "{ a = '' x ''' y ''${z} ''\\n ''; inputs.b.url = \"c\"; }";
+        let flake = FlakeNix::parse(src).unwrap();
+        assert_eq!(flake.input("b").unwrap().url.as_deref(), Some("c"));
+        // Only the string's end closes it
+        let tokens = tokenize(src).unwrap();
+        assert_eq!(&src[tokens[3].span.clone()], " x ''' y ''${z} ''\\n ");
+        assert!(tokenize("{ a = '' x ''' ; }").is_err());
+    }
+
+    #[test]
+    fn test_with_url() {
+        let flake = FlakeNix::parse(FLAKE).unwrap();
+        let out = flake
+            .with_url("nixpkgs", "github:NixOS/nixpkgs/nixos-24.05")
+            .unwrap();
+        assert_eq!(
+            out,
+            FLAKE.replace(
+                "nixpkgs.url = \"github:NixOS/nixpkgs/nixos-unstable\"",
+                "nixpkgs.url = \"github:NixOS/nixpkgs/nixos-24.05\""
+            )
+        );
+        // The commented-out line stays as it was
+        assert!(out.contains("# nixpkgs.url = \"github:NixOS/nixpkgs/nixos-23.11\";"));
+
+        let out = flake
+            .with_url("hardware", "github:NixOS/nixos-hardware/${rev}")
+            .unwrap();
+        assert!(out.contains("hardware.url = ''github:NixOS/nixos-hardware/''${rev}'';"));
+        assert_eq!(
+            FlakeNix::parse(&out)
+                .unwrap()
+                .input("hardware")
+                .unwrap()
+                .url
+                .as_deref(),
+            Some("github:NixOS/nixos-hardware/''${rev}")
+        );
+
+        // Same url: unchanged output
+        let same = flake
+            .with_url("home-manager", "github:nix-community/home-manager")
+            .unwrap();
+        assert_eq!(same, FLAKE);
+        assert!(flake.with_url("missing", "x").is_err());
+    }
+
+    #[test]
+    fn test_with_added() {
+        let flake = FlakeNix::parse(FLAKE).unwrap();
+        let out = flake.with_added("agenix", "github:ryantm/agenix").unwrap();
+        assert_eq!(
+            out,
+            FLAKE
+                .replace(
+                    "# indented string\n  };",
+                    "# indented string\n    agenix.url = \"github:ryantm/agenix\";\n  };"
+                )
+                .replace("hardware }:", "hardware, agenix }:")
+        );
+        assert!(flake.with_added("nixpkgs", "x").is_err());
+        assert!(flake.with_added("1bad", "x").is_err());
+
+        // Flat style, `...` in the pattern stays as it is
+        let flat = "{\n  inputs.nixpkgs.url = \"github:NixOS/nixpkgs\";\n  outputs = { self, ... }: { };\n}\n";
+        let out = FlakeNix::parse(flat)
+            .unwrap()
+            .with_added("foo", "git+https://example.org/foo")
+            .unwrap();
+        assert_eq!(
+            out,
+            "{\n  inputs.nixpkgs.url = \"github:NixOS/nixpkgs\";\n  \
+             inputs.foo.url = \"git+https://example.org/foo\";\n  \
+             outputs = { self, ... }: { };\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_without() {
+        let flake = FlakeNix::parse(FLAKE).unwrap();
+        let out = flake.without("home-manager").unwrap();
+        assert_eq!(
+            out,
+            FLAKE
+                .replace(
+                    "    home-manager = {\n      url = \"github:nix-community/home-manager\";\n      \
+                     inputs.nixpkgs.follows = \"nixpkgs\";\n    };\n",
+                    ""
+                )
+                .replace("nixpkgs, home-manager, hardware", "nixpkgs, hardware")
+        );
+        // The trailing comment belongs to the line and goes with it
+        let out = flake.without("hardware").unwrap();
+        assert!(!out.contains("indented string"));
+        assert!(out.contains("{ self, nixpkgs, home-manager }:"));
+        assert!(FlakeNix::parse(&out).unwrap().input("hardware").is_none());
+
+        // Added and removed again: back to the original
+        let added = flake.with_added("agenix", "github:ryantm/agenix").unwrap();
+        assert_eq!(
+            FlakeNix::parse(&added).unwrap().without("agenix").unwrap(),
+            FLAKE
+        );
+    }
+
+    #[test]
+    fn test_pattern_entry() {
+        let src = "{ self, nixpkgs, foo ? null, bar }";
+        let end = src.len() - 1;
+        let cut = |name| {
+            let mut out = src.to_string();
+            out.replace_range(pattern_entry(src, 1, end, name).unwrap(), "");
+            out
+        };
+        assert_eq!(cut("self"), "{ nixpkgs, foo ? null, bar }");
+        assert_eq!(cut("foo"), "{ self, nixpkgs, bar }");
+        assert_eq!(cut("bar"), "{ self, nixpkgs, foo ? null }");
+        assert_eq!(pattern_entry(src, 1, end, "nix"), None);
+    }
+}
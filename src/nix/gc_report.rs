@@ -0,0 +1,234 @@
+//! What deleting generations really frees
+//!
+//! A generation's closure is mostly shared — with the current system, the
+//! neighbouring generations, `result` links and other profiles — so
+//! deleting one often frees next to nothing. Only the paths no remaining
+//! GC root reaches become garbage. Blocking — run in a background thread!
+//!
+//! `Reclaimable` does the same for a whole profile at once, so the Manage
+//! list can show what each generation frees on its own and what any
+//! marked set of them frees, without asking Nix again.
+
+use crate::nix::storage;
+use anyhow::{bail, Context, Result};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GcReport {
+    /// (generation, bytes only it keeps alive), in the order asked for
+    pub generations: Vec<(u32, u64)>,
+    /// Bytes the deletion frees: more than the sum of the above when the
+    /// deleted generations share paths with each other
+    pub total: u64,
+    /// Bytes of the deleted closures that other roots keep alive
+    pub kept_alive: u64,
+}
+
+/// Report for deleting `ids` of the profile at `profile_path`
+pub fn gc_report(profile_path: &Path, ids: &[u32]) -> Result<GcReport> {
+    let (deleted, kept, sizes) = closures(profile_path, ids)?;
+    Ok(tally(&deleted, &kept, &sizes))
+}
+
+/// Closure per generation, closure of every other GC root, path sizes
+type Closures = (
+    Vec<(u32, HashSet<String>)>,
+    HashSet<String>,
+    HashMap<String, u64>,
+);
+
+/// Closures of generations `ids`, the closure of every other GC root and
+/// the sizes of the generations' paths
+fn closures(profile_path: &Path, ids: &[u32]) -> Result<Closures> {
+    let name = profile_path
+        .file_name()
+        .context("profile path has no name")?
+        .to_string_lossy();
+    let links: Vec<(u32, PathBuf)> = ids
+        .iter()
+        .map(|id| {
+            (
+                *id,
+                profile_path.with_file_name(format!("{}-{}-link", name, id)),
+            )
+        })
+        .collect();
+
+    let roots = storage::gc_roots();
+    if roots.is_empty() {
+        bail!("nix-store --gc --print-roots returned nothing");
+    }
+    let kept = storage::closure(
+        roots
+            .iter()
+            .filter(|(link, _)| !links.iter().any(|(_, l)| l == link))
+            .map(|(_, target)| target.as_str()),
+    );
+
+    let mut deleted: Vec<(u32, HashSet<String>)> = Vec::new();
+    for (id, link) in &links {
+        let target =
+            std::fs::read_link(link).with_context(|| format!("cannot read {}", link.display()))?;
+        deleted.push((*id, storage::closure([target.to_string_lossy().as_ref()])));
+    }
+
+    let all: HashSet<String> = deleted
+        .iter()
+        .flat_map(|(_, c)| c.iter().cloned())
+        .collect();
+    let sizes = storage::nar_sizes(&all);
+    if sizes.is_empty() && !all.is_empty() {
+        bail!("nix-store -q --size returned nothing");
+    }
+    Ok((deleted, kept, sizes))
+}
+
+/// What removing any subset of a profile's generations frees. Paths are
+/// numbered; each generation keeps the numbers of its closure.
+#[derive(Debug, Clone, Default)]
+pub struct Reclaimable {
+    generations: Vec<(u32, Vec<usize>)>,
+    /// Per path: size, whether a root outside the profile keeps it, and
+    /// how many of the profile's generations reach it
+    sizes: Vec<u64>,
+    kept: Vec<bool>,
+    reach: Vec<u32>,
+}
+
+/// Index for every generation `ids` of the profile at `profile_path`
+pub fn reclaimable(profile_path: &Path, ids: &[u32]) -> Result<Reclaimable> {
+    let (generations, kept, sizes) = closures(profile_path, ids)?;
+    Ok(Reclaimable::new(&generations, &kept, &sizes))
+}
+
+impl Reclaimable {
+    fn new(
+        generations: &[(u32, HashSet<String>)],
+        kept: &HashSet<String>,
+        sizes: &HashMap<String, u64>,
+    ) -> Self {
+        let mut index: HashMap<&str, usize> = HashMap::new();
+        let mut result = Reclaimable::default();
+        for (id, closure) in generations {
+            let paths = closure
+                .iter()
+                .map(|path| {
+                    let i = *index.entry(path.as_str()).or_insert_with(|| {
+                        result.sizes.push(sizes.get(path).copied().unwrap_or(0));
+                        result.kept.push(kept.contains(path));
+                        result.reach.push(0);
+                        result.sizes.len() - 1
+                    });
+                    result.reach[i] += 1;
+                    i
+                })
+                .collect();
+            result.generations.push((*id, paths));
+        }
+        result
+    }
+
+    /// Bytes freed by removing generation `id` and nothing else
+    pub fn alone(&self, id: u32) -> Option<u64> {
+        self.freed(&[id])
+    }
+
+    /// Bytes freed by removing all of `ids` together; None if one of them
+    /// isn't indexed
+    pub fn freed(&self, ids: &[u32]) -> Option<u64> {
+        let mut reached: HashMap<usize, u32> = HashMap::new();
+        for id in ids {
+            let (_, paths) = self.generations.iter().find(|(g, _)| g == id)?;
+            for &p in paths {
+                *reached.entry(p).or_insert(0) += 1;
+            }
+        }
+        Some(
+            reached
+                .into_iter()
+                .filter(|&(p, n)| !self.kept[p] && n == self.reach[p])
+                .map(|(p, _)| self.sizes[p])
+                .sum(),
+        )
+    }
+}
+
+/// Sizes from the closures of the deleted generations and of everything
+/// that stays
+fn tally(
+    deleted: &[(u32, HashSet<String>)],
+    kept: &HashSet<String>,
+    sizes: &HashMap<String, u64>,
+) -> GcReport {
+    // How many of the deleted generations reach each path
+    let mut reach: HashMap<&str, usize> = HashMap::new();
+    for (_, closure) in deleted {
+        for path in closure {
+            *reach.entry(path.as_str()).or_insert(0) += 1;
+        }
+    }
+    let size = |path: &str| sizes.get(path).copied().unwrap_or(0);
+
+    let mut report = GcReport::default();
+    for (&path, _) in reach.iter() {
+        if kept.contains(path) {
+            report.kept_alive += size(path);
+        } else {
+            report.total += size(path);
+        }
+    }
+    report.generations = deleted
+        .iter()
+        .map(|(id, closure)| {
+            let own = closure
+                .iter()
+                .filter(|p| reach[p.as_str()] == 1 && !kept.contains(*p))
+                .map(|p| size(p))
+                .sum();
+            (*id, own)
+        })
+        .collect();
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(paths: &[&str]) -> HashSet<String> {
+        paths.iter().map(|p| p.to_string()).collect()
+    }
+
+    #[test]
+    fn test_tally() {
+        let sizes: HashMap<String, u64> = [
+            ("glibc", 40),
+            ("kernel", 100),
+            ("old-app", 5),
+            ("older-app", 7),
+            ("shared-old", 20),
+        ]
+        .iter()
+        .map(|(p, s)| (p.to_string(), *s))
+        .collect();
+        let deleted = vec![
+            (140, set(&["glibc", "kernel", "old-app", "shared-old"])),
+            (141, set(&["glibc", "kernel", "older-app", "shared-old"])),
+        ];
+        // The current system still uses glibc and the kernel
+        let kept = set(&["glibc", "kernel", "current-app"]);
+
+        let report = tally(&deleted, &kept, &sizes);
+        assert_eq!(report.generations, vec![(140, 5), (141, 7)]);
+        // shared-old only goes when both go
+        assert_eq!(report.total, 32);
+        assert_eq!(report.kept_alive, 140);
+
+        let index = Reclaimable::new(&deleted, &kept, &sizes);
+        assert_eq!(index.alone(140), Some(5));
+        assert_eq!(index.alone(141), Some(7));
+        assert_eq!(index.freed(&[140, 141]), Some(report.total));
+        assert_eq!(index.alone(999), None);
+    }
+}
@@ -5,7 +5,7 @@
 //!   1. Filesystem-based (no permissions needed) — reads symlinks directly
 //!   2. nix-env fallback (if filesystem parsing fails)
 
-use crate::types::{Generation, ProfileType};
+use crate::types::{BootRole, Generation, ProfileType};
 use anyhow::{Context, Result};
 use chrono::{DateTime, Local, TimeZone};
 use std::path::{Path, PathBuf};
@@ -54,7 +54,7 @@ fn list_generations_from_fs(source: &GenerationSource) -> Result<Vec<Generation>
     let current_id = get_current_generation_id(&source.profile_path).unwrap_or(0);
 
     let boot_entries = if source.profile_type == ProfileType::System {
-        get_boot_entries().unwrap_or_default()
+        get_boot_entries(Path::new(BOOT)).unwrap_or_default()
     } else {
         Vec::new()
     };
@@ -93,6 +93,9 @@ fn list_generations_from_fs(source: &GenerationSource) -> Result<Vec<Generation>
 
         generations.push(generation);
     }
+    if source.profile_type == ProfileType::System {
+        mark_boot_roles(&mut generations, &boot_entries, current_id);
+    }
 
     // Sort by ID descending (newest first)
     generations.sort_by(|a, b| b.id.cmp(&a.id));
@@ -138,7 +141,7 @@ fn list_generations_from_nix_env(source: &GenerationSource) -> Result<Vec<Genera
     let current_id = get_current_generation_id(profile_path).unwrap_or(0);
 
     let boot_entries = if source.profile_type == ProfileType::System {
-        get_boot_entries().unwrap_or_default()
+        get_boot_entries(Path::new(BOOT)).unwrap_or_default()
     } else {
         Vec::new()
     };
@@ -162,6 +165,9 @@ fn list_generations_from_nix_env(source: &GenerationSource) -> Result<Vec<Genera
 
         generations.push(generation);
     }
+    if source.profile_type == ProfileType::System {
+        mark_boot_roles(&mut generations, &boot_entries, current_id);
+    }
 
     generations.sort_by(|a, b| b.id.cmp(&a.id));
     Ok(generations)
@@ -213,6 +219,29 @@ fn get_current_generation_id(profile_path: &Path) -> Result<u32> {
     extract_generation_id(&target)
 }
 
+/// Number of the system generation the system profile points to
+pub fn current_system_generation() -> Option<u32> {
+    get_current_generation_id(Path::new("/nix/var/nix/profiles/system")).ok()
+}
+
+/// The system generation `nixos-rebuild --rollback` switches to: the
+/// newest one older than the current. None if there is nothing to go back to.
+pub fn rollback_target() -> Option<u32> {
+    let current = current_system_generation()?;
+    std::fs::read_dir("/nix/var/nix/profiles")
+        .ok()?
+        .flatten()
+        .filter_map(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            name.strip_prefix("system-")?
+                .strip_suffix("-link")?
+                .parse::<u32>()
+                .ok()
+        })
+        .filter(|id| *id < current)
+        .max()
+}
+
 /// Extract generation ID from a path like "system-142-link"
 fn extract_generation_id(path: &Path) -> Result<u32> {
     let filename = path
@@ -272,9 +301,111 @@ fn parse_generation(
         store_path,
         is_pinned: false,
         in_bootloader,
+        is_booted: false,
+        is_boot_default: false,
     })
 }
 
+// ── Boot protection ──
+//
+// `nix-env --delete-generations` and `nix-collect-garbage -d` only spare the
+// profile's current generation. After a `switch` without reboot (or a
+// `nixos-rebuild boot`) that isn't the generation the machine is running
+// from, nor necessarily the one the bootloader starts.
+
+const BOOTED_SYSTEM: &str = "/run/booted-system";
+const BOOT: &str = "/boot";
+pub(crate) const SYSTEM_PROFILE: &str = "/nix/var/nix/profiles/system";
+
+fn mark_boot_roles(generations: &mut [Generation], boot_entries: &[u32], current_id: u32) {
+    let booted = booted_store_path();
+    let default_id = get_boot_default(Path::new(BOOT), boot_entries, current_id);
+    for g in generations {
+        g.is_booted = booted.as_deref() == Some(g.store_path.as_str());
+        g.is_boot_default = default_id == Some(g.id);
+    }
+}
+
+/// Store path the running system was booted from
+fn booted_store_path() -> Option<String> {
+    std::fs::read_link(BOOTED_SYSTEM)
+        .ok()
+        .map(|p| p.to_string_lossy().to_string())
+}
+
+/// Generation the bootloader in `boot` starts when nobody picks an entry
+fn get_boot_default(boot: &Path, boot_entries: &[u32], current_id: u32) -> Option<u32> {
+    // systemd-boot: `default nixos-generation-42.conf` (or a glob, which
+    // matches the newest entry)
+    if let Ok(conf) = std::fs::read_to_string(boot.join("loader/loader.conf")) {
+        let default = conf
+            .lines()
+            .filter_map(|l| l.trim().split_once(char::is_whitespace))
+            .filter(|(key, _)| *key == "default")
+            .map(|(_, v)| v.trim())
+            .next_back();
+        let explicit = default
+            .and_then(|v| v.strip_prefix("nixos-generation-"))
+            .map(|v| {
+                v.chars()
+                    .take_while(|c| c.is_ascii_digit())
+                    .collect::<String>()
+            })
+            .and_then(|id| id.parse().ok());
+        return explicit.or_else(|| boot_entries.iter().copied().max());
+    }
+    // GRUB: the first menu entry is the system profile
+    boot.join("grub/grub.cfg").exists().then_some(current_id)
+}
+
+/// The first system generation a cleanup would delete although the machine
+/// is booted from it or boots it by default. `older_than_days` mirrors
+/// `--delete-older-than`; None means every generation but the current (`-d`).
+pub fn endangered_by_cleanup(older_than_days: Option<u32>) -> Option<(u32, BootRole)> {
+    let profile = Path::new(SYSTEM_PROFILE);
+    let current_id = get_current_generation_id(profile).ok()?;
+    let boot_entries = get_boot_entries(Path::new(BOOT)).unwrap_or_default();
+    let booted = booted_store_path();
+    let default_id = get_boot_default(Path::new(BOOT), &boot_entries, current_id);
+    let min_age = older_than_days.map(|d| std::time::Duration::from_secs(d as u64 * 86400));
+
+    std::fs::read_dir(profile.parent()?)
+        .ok()?
+        .flatten()
+        .filter_map(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            let id: u32 = name
+                .strip_prefix("system-")?
+                .strip_suffix("-link")?
+                .parse()
+                .ok()?;
+            if id == current_id {
+                return None;
+            }
+            // Same age nix-collect-garbage uses: the link's mtime
+            if let Some(min_age) = min_age {
+                let age = std::fs::symlink_metadata(e.path())
+                    .ok()?
+                    .modified()
+                    .ok()?
+                    .elapsed()
+                    .ok()?;
+                if age < min_age {
+                    return None;
+                }
+            }
+            let target = std::fs::read_link(e.path()).ok()?;
+            if booted.as_deref() == Some(&*target.to_string_lossy()) {
+                Some((id, BootRole::Booted))
+            } else if default_id == Some(id) {
+                Some((id, BootRole::BootDefault))
+            } else {
+                None
+            }
+        })
+        .min_by_key(|(id, _)| *id)
+}
+
 fn get_version(gen_path: &Path, profile_type: ProfileType) -> Option<String> {
     let version_file = match profile_type {
         ProfileType::System => gen_path.join("nixos-version"),
@@ -298,6 +429,27 @@ fn get_version(gen_path: &Path, profile_type: ProfileType) -> Option<String> {
     }
 }
 
+/// Git revision of the flake a system generation was built from
+/// (`system.configurationRevision`, what `nixos-version --json` prints).
+/// Read from the generation's own `nixos-version` script, so every
+/// generation answers for itself, not just the running one.
+pub fn configuration_revision(store_path: &str) -> Option<String> {
+    let script =
+        std::fs::read_to_string(Path::new(store_path).join("sw/bin/nixos-version")).ok()?;
+    parse_configuration_revision(&script)
+}
+
+fn parse_configuration_revision(script: &str) -> Option<String> {
+    let rest = &script[script.find("\"configurationRevision\"")? + 23..];
+    let value = rest
+        .trim_start()
+        .strip_prefix(':')?
+        .trim_start()
+        .strip_prefix('"')?;
+    let rev = &value[..value.find('"')?];
+    (!rev.is_empty()).then(|| rev.to_string())
+}
+
 fn get_kernel_version(gen_path: &Path) -> Option<String> {
     let kernel_dir = gen_path.join("kernel");
 
@@ -372,13 +524,13 @@ fn get_closure_size(gen_path: &Path) -> Result<u64> {
     }
 }
 
-fn get_boot_entries() -> Result<Vec<u32>> {
+fn get_boot_entries(boot: &Path) -> Result<Vec<u32>> {
     let mut entries = Vec::new();
 
     // Check systemd-boot entries
-    let loader_entries = Path::new("/boot/loader/entries");
+    let loader_entries = boot.join("loader/entries");
     if loader_entries.exists() {
-        if let Ok(dir) = std::fs::read_dir(loader_entries) {
+        if let Ok(dir) = std::fs::read_dir(&loader_entries) {
             for entry in dir.flatten() {
                 let name = entry.file_name();
                 let name_str = name.to_string_lossy();
@@ -393,9 +545,9 @@ fn get_boot_entries() -> Result<Vec<u32>> {
     }
 
     // Check GRUB entries
-    let grub_cfg = Path::new("/boot/grub/grub.cfg");
+    let grub_cfg = boot.join("grub/grub.cfg");
     if grub_cfg.exists() && entries.is_empty() {
-        if let Ok(content) = std::fs::read_to_string(grub_cfg) {
+        if let Ok(content) = std::fs::read_to_string(&grub_cfg) {
             for line in content.lines() {
                 if line.contains("NixOS") && line.contains("Generation") {
                     if let Some(start) = line.find("Generation ") {
@@ -412,3 +564,70 @@ fn get_boot_entries() -> Result<Vec<u32>> {
 
     Ok(entries)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_boot_default() {
+        let boot =
+            std::env::temp_dir().join(format!("nixmate-boot-default-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&boot);
+        let entries = boot.join("loader/entries");
+        std::fs::create_dir_all(&entries).unwrap();
+        for id in [40, 41, 42] {
+            std::fs::write(entries.join(format!("nixos-generation-{}.conf", id)), "").unwrap();
+        }
+        std::fs::write(entries.join("memtest86.conf"), "").unwrap();
+        let found = get_boot_entries(&boot).unwrap();
+        assert_eq!(found.iter().copied().max(), Some(42));
+        assert_eq!(found.len(), 3);
+
+        // No loader.conf and no GRUB: nothing known
+        assert_eq!(get_boot_default(&boot, &found, 42), None);
+
+        // Explicit entry, the last `default` line wins
+        let conf = boot.join("loader/loader.conf");
+        std::fs::write(
+            &conf,
+            "timeout 3\ndefault nixos-generation-7.conf\ndefault nixos-generation-41.conf\n",
+        )
+        .unwrap();
+        assert_eq!(get_boot_default(&boot, &found, 42), Some(41));
+
+        // Glob: the newest entry
+        std::fs::write(&conf, "default nixos-generation-*\n").unwrap();
+        assert_eq!(get_boot_default(&boot, &found, 40), Some(42));
+
+        // No default line: the newest entry as well
+        std::fs::write(&conf, "timeout 3\n").unwrap();
+        assert_eq!(get_boot_default(&boot, &found, 40), Some(42));
+
+        // GRUB: the current generation
+        std::fs::remove_dir_all(boot.join("loader")).unwrap();
+        std::fs::create_dir_all(boot.join("grub")).unwrap();
+        std::fs::write(
+            boot.join("grub/grub.cfg"),
+            "menuentry \"NixOS - Generation 42 (2024-05-01)\" {\n}\n",
+        )
+        .unwrap();
+        assert_eq!(get_boot_entries(&boot).unwrap(), vec![42]);
+        assert_eq!(get_boot_default(&boot, &[42], 42), Some(42));
+
+        std::fs::remove_dir_all(&boot).unwrap();
+    }
+
+    #[test]
+    fn test_parse_configuration_revision() {
+        let script = r#"  --json)
+    cat << EOF
+{"nixosVersion": "24.11.20250101.abcdef0", "configurationRevision": "3d213d6c0ffee-dirty"}
+EOF"#;
+        assert_eq!(
+            parse_configuration_revision(script).as_deref(),
+            Some("3d213d6c0ffee-dirty")
+        );
+        assert_eq!(parse_configuration_revision("--revision) echo"), None);
+    }
+}
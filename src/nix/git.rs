@@ -0,0 +1,104 @@
+//! Git operations on the configuration repository
+//!
+//! Thin wrapper around the `git` CLI (no libgit2), always run with
+//! `git -C <dir>` so callers can pass any directory inside the repo.
+//! Errors are the first meaningful line of git's stderr.
+
+use std::path::Path;
+use std::process::Command;
+
+/// Run `git -C dir args…`, returning stdout
+fn run(dir: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+
+    if output.status.success() {
+        return Ok(String::from_utf8_lossy(&output.stdout).to_string());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    Err(stderr
+        .lines()
+        .map(|l| l.trim())
+        .find(|l| l.starts_with("fatal:") || l.starts_with("error:"))
+        .or_else(|| stderr.lines().find(|l| !l.trim().is_empty()))
+        .unwrap_or("git failed")
+        .to_string())
+}
+
+/// Whether `dir` is inside a git work tree
+pub fn is_repo(dir: &Path) -> bool {
+    run(dir, &["rev-parse", "--is-inside-work-tree"]).is_ok_and(|out| out.trim() == "true")
+}
+
+/// `git status --short` limited to `paths` (empty = clean)
+pub fn status_short(dir: &Path, paths: &[&str]) -> Result<Vec<String>, String> {
+    let mut args = vec!["status", "--short", "--"];
+    args.extend_from_slice(paths);
+    Ok(run(dir, &args)?
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.to_string())
+        .collect())
+}
+
+/// `git diff --stat` of the working tree against HEAD, limited to `paths`
+pub fn diff_stat(dir: &Path, paths: &[&str]) -> Result<Vec<String>, String> {
+    let mut args = vec!["diff", "--stat", "HEAD", "--"];
+    args.extend_from_slice(paths);
+    Ok(run(dir, &args)?.lines().map(|l| l.to_string()).collect())
+}
+
+/// Stage and commit exactly `paths`; returns the new short commit hash
+pub fn commit_paths(dir: &Path, paths: &[&str], message: &str) -> Result<String, String> {
+    let mut add = vec!["add", "--"];
+    add.extend_from_slice(paths);
+    run(dir, &add)?;
+
+    // `--only` keeps anything else the user has staged out of this commit
+    let mut commit = vec!["commit", "--only", "-m", message, "--"];
+    commit.extend_from_slice(paths);
+    run(dir, &commit)?;
+
+    Ok(run(dir, &["rev-parse", "--short", "HEAD"])?
+        .trim()
+        .to_string())
+}
+
+/// Content of `path` (relative to `dir`) at revision `rev`
+pub fn show_file(dir: &Path, rev: &str, path: &str) -> Result<String, String> {
+    run(dir, &["show", &format!("{}:./{}", rev, path)])
+}
+
+/// "<short hash> <relative date> <subject>" of the commit `rev` names
+pub fn describe_commit(dir: &Path, rev: &str) -> Result<String, String> {
+    Ok(
+        run(dir, &["log", "-1", "--format=%h · %cr · %s", rev, "--"])?
+            .trim()
+            .to_string(),
+    )
+}
+
+/// Where a revision sits in the repo: the branch-relative name
+/// (`main~2`, None when no branch contains it) and the commit subject
+pub fn revision_summary(dir: &Path, rev: &str) -> Result<(Option<String>, String), String> {
+    let subject = run(dir, &["log", "-1", "--format=%s", rev, "--"])?
+        .trim()
+        .to_string();
+    let branch = run(
+        dir,
+        &[
+            "name-rev",
+            "--name-only",
+            "--no-undefined",
+            "--refs=refs/heads/*",
+            rev,
+        ],
+    )
+    .ok()
+    .map(|name| name.trim().to_string());
+    Ok((branch, subject))
+}
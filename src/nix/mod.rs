@@ -1,15 +1,31 @@
 //! Nix interaction layer
 //!
 //! Handles all interactions with NixOS and Home-Manager:
+//! - Availability check (store, CLI, daemon)
 //! - System detection (Flakes vs Channels, HM standalone vs module)
 //! - Generation listing and parsing
+//! - What deleting generations really frees (GC safety report)
+//! - flake.nix inputs parsing and rewriting
+//! - Git operations on the configuration repo (status, diff, commit)
 //! - Package extraction
+//! - Reverse proxy virtual hosts (nginx, Caddy, Traefik)
+//! - Ports opened in the NixOS firewall
+//! - Network mounts and disk health
 //! - Command execution (restore, delete)
+//! - Detection of builds running on the system
 
+pub mod availability;
+pub mod builds;
 pub mod commands;
 pub mod detect;
+pub mod firewall;
+pub mod flake_nix;
+pub mod gc_report;
 pub mod generations;
+pub mod git;
+pub mod mounts;
 pub mod packages;
+pub mod proxies;
 pub mod services;
 pub mod storage;
 pub mod sysinfo;
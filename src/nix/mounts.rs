@@ -0,0 +1,442 @@
+//! Network mounts and local disks for the Services dashboard.
+//!
+//! Network mounts are the NFS, SMB/CIFS and SSHFS entries of `/etc/fstab`
+//! (NixOS writes `fileSystems` there). Each is mounted, waiting behind an
+//! automount, failed (its systemd unit) or not mounted. Their usage comes
+//! from `df` with a timeout: a hung server would block a plain statfs.
+//! Disks come from `lsblk`, their health from `smartctl -H` (usually needs
+//! root, unknown otherwise), degraded md arrays from `/proc/mdstat` and
+//! unhealthy pools from `zpool list`.
+
+use super::commands::command_exists;
+use super::storage::output_with_timeout;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Filesystem types served over the network
+const NET_TYPES: &[&str] = &[
+    "nfs",
+    "nfs4",
+    "cifs",
+    "smb3",
+    "smbfs",
+    "fuse.sshfs",
+    "sshfs",
+    "davfs",
+    "glusterfs",
+    "ceph",
+];
+
+/// Seconds `df` gets per network mount before it counts as not responding
+const DF_TIMEOUT_SECS: u64 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MountState {
+    Mounted,
+    /// Waiting behind an automount (`x-systemd.automount`)
+    Automount,
+    Failed,
+    Unmounted,
+}
+
+#[derive(Debug, Clone)]
+pub struct NetMount {
+    pub source: String,
+    pub target: String,
+    pub fstype: String,
+    pub state: MountState,
+    /// (used, size) in bytes
+    pub usage: Option<(u64, u64)>,
+    /// Mounted, but `df` got no answer in time
+    pub stale: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Health {
+    Passed,
+    Failed,
+    /// No smartctl, no permission, or no SMART support
+    Unknown,
+}
+
+#[derive(Debug, Clone)]
+pub struct Disk {
+    pub name: String,
+    pub model: String,
+    pub size: u64,
+    pub health: Health,
+    /// (used, size) summed over the disk's mounted filesystems
+    pub usage: Option<(u64, u64)>,
+}
+
+/// An md array or ZFS pool that isn't healthy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Degraded {
+    pub name: String,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MountReport {
+    pub mounts: Vec<NetMount>,
+    pub disks: Vec<Disk>,
+    pub degraded: Vec<Degraded>,
+}
+
+impl MountReport {
+    /// Failed or hung mounts, failing disks and degraded arrays
+    pub fn problems(&self) -> usize {
+        self.mounts
+            .iter()
+            .filter(|m| m.state == MountState::Failed || m.stale)
+            .count()
+            + self
+                .disks
+                .iter()
+                .filter(|d| d.health == Health::Failed)
+                .count()
+            + self.degraded.len()
+    }
+}
+
+/// Gather everything (blocking — background thread)
+pub fn load() -> MountReport {
+    MountReport {
+        mounts: load_mounts(),
+        disks: load_disks(),
+        degraded: load_degraded(),
+    }
+}
+
+fn load_mounts() -> Vec<NetMount> {
+    let fstab = std::fs::read_to_string("/etc/fstab").unwrap_or_default();
+    let entries = parse_fstab(&fstab);
+    if entries.is_empty() {
+        return Vec::new();
+    }
+    let proc_mounts = std::fs::read_to_string("/proc/self/mounts").unwrap_or_default();
+    let mounted = mounted_types(&proc_mounts);
+
+    let units: Vec<String> = entries
+        .iter()
+        .flat_map(|(_, target, _)| {
+            let unit = unit_name(target);
+            [format!("{}.mount", unit), format!("{}.automount", unit)]
+        })
+        .collect();
+    let mut args = vec!["show", "-p", "Id", "-p", "ActiveState"];
+    args.extend(units.iter().map(String::as_str));
+    let states = output_with_timeout("systemctl", &args, 5)
+        .map(|o| parse_unit_states(&String::from_utf8_lossy(&o.stdout)))
+        .unwrap_or_default();
+
+    entries
+        .into_iter()
+        .map(|(source, target, fstype)| {
+            let unit = unit_name(&target);
+            let failed = [".mount", ".automount"].iter().any(|suffix| {
+                states
+                    .get(&format!("{}{}", unit, suffix))
+                    .map(String::as_str)
+                    == Some("failed")
+            });
+            let types = mounted.get(&target);
+            let state = if types.is_some_and(|t| t.iter().any(|t| NET_TYPES.contains(t))) {
+                MountState::Mounted
+            } else if failed {
+                MountState::Failed
+            } else if types.is_some_and(|t| t.contains(&"autofs")) {
+                MountState::Automount
+            } else {
+                MountState::Unmounted
+            };
+            let (usage, stale) = if state == MountState::Mounted {
+                match output_with_timeout("df", &["-P", "-B1", &target], DF_TIMEOUT_SECS) {
+                    Some(o) => (parse_df(&String::from_utf8_lossy(&o.stdout)), false),
+                    None => (None, true),
+                }
+            } else {
+                (None, false)
+            };
+            NetMount {
+                source,
+                target,
+                fstype,
+                state,
+                usage,
+                stale,
+            }
+        })
+        .collect()
+}
+
+/// (source, target, type) of the network filesystems in an fstab
+fn parse_fstab(text: &str) -> Vec<(String, String, String)> {
+    text.lines()
+        .map(str::trim)
+        .filter(|l| !l.starts_with('#'))
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let (source, target, fstype) = (fields.next()?, fields.next()?, fields.next()?);
+            let net =
+                NET_TYPES.contains(&fstype) || (fstype == "fuse" && source.starts_with("sshfs#"));
+            net.then(|| (unescape(source), unescape(target), fstype.to_string()))
+        })
+        .collect()
+}
+
+/// Mount points of /proc/self/mounts with the types mounted there
+fn mounted_types(text: &str) -> HashMap<String, Vec<&str>> {
+    let mut map: HashMap<String, Vec<&str>> = HashMap::new();
+    for line in text.lines() {
+        let mut fields = line.split_whitespace();
+        if let (Some(_), Some(target), Some(fstype)) = (fields.next(), fields.next(), fields.next())
+        {
+            map.entry(unescape(target)).or_default().push(fstype);
+        }
+    }
+    map
+}
+
+/// fstab and /proc/mounts write a space as `\040`
+fn unescape(field: &str) -> String {
+    field.replace("\\040", " ").replace("\\011", "\t")
+}
+
+/// Unit name of a mount point, as `systemd-escape --path` builds it
+fn unit_name(path: &str) -> String {
+    let trimmed = path.trim_matches('/');
+    if trimmed.is_empty() {
+        return "-".to_string();
+    }
+    let mut name = String::new();
+    for (i, byte) in trimmed.bytes().enumerate() {
+        match byte {
+            b'/' => name.push('-'),
+            b'.' if i == 0 => name.push_str("\\x2e"),
+            b if b.is_ascii_alphanumeric() || b == b'_' || b == b'.' => name.push(b as char),
+            b => name.push_str(&format!("\\x{:02x}", b)),
+        }
+    }
+    name
+}
+
+/// Id → ActiveState from `systemctl show -p Id -p ActiveState …`
+fn parse_unit_states(text: &str) -> HashMap<String, String> {
+    let mut states = HashMap::new();
+    for block in text.split("\n\n") {
+        let mut id = None;
+        let mut state = None;
+        for line in block.lines() {
+            if let Some(v) = line.strip_prefix("Id=") {
+                id = Some(v.to_string());
+            } else if let Some(v) = line.strip_prefix("ActiveState=") {
+                state = Some(v.to_string());
+            }
+        }
+        if let (Some(id), Some(state)) = (id, state) {
+            states.insert(id, state);
+        }
+    }
+    states
+}
+
+/// (used, size) from `df -P -B1 <path>`
+fn parse_df(text: &str) -> Option<(u64, u64)> {
+    let line = text.lines().nth(1)?;
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    let size = fields.get(1)?.parse().ok()?;
+    let used = fields.get(2)?.parse().ok()?;
+    Some((used, size))
+}
+
+fn load_disks() -> Vec<Disk> {
+    let Some(output) = output_with_timeout(
+        "lsblk",
+        &["-J", "-b", "-o", "NAME,TYPE,SIZE,MODEL,FSSIZE,FSUSED"],
+        5,
+    ) else {
+        return Vec::new();
+    };
+    let Ok(json) = serde_json::from_slice::<Value>(&output.stdout) else {
+        return Vec::new();
+    };
+    let smartctl = command_exists("smartctl");
+    let mut disks = Vec::new();
+    for dev in json
+        .get("blockdevices")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        if dev.get("type").and_then(Value::as_str) != Some("disk") {
+            continue;
+        }
+        let name = dev
+            .get("name")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+        let (used, size) = fs_usage(dev);
+        disks.push(Disk {
+            health: if smartctl {
+                smart_health(&name)
+            } else {
+                Health::Unknown
+            },
+            model: dev
+                .get("model")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .trim()
+                .to_string(),
+            size: number(dev.get("size")).unwrap_or(0),
+            usage: (size > 0).then_some((used, size)),
+            name,
+        });
+    }
+    disks
+}
+
+/// Used and total bytes of the filesystems on a device and its children
+fn fs_usage(dev: &Value) -> (u64, u64) {
+    let own = (
+        number(dev.get("fsused")).unwrap_or(0),
+        number(dev.get("fssize")).unwrap_or(0),
+    );
+    dev.get("children")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .map(fs_usage)
+        .fold(own, |(u, s), (cu, cs)| (u + cu, s + cs))
+}
+
+/// lsblk prints sizes as numbers or strings depending on its version
+fn number(value: Option<&Value>) -> Option<u64> {
+    match value? {
+        Value::Number(n) => n.as_u64(),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+fn smart_health(name: &str) -> Health {
+    let dev = format!("/dev/{}", name);
+    let Some(output) = output_with_timeout("smartctl", &["-H", "-j", &dev], 5) else {
+        return Health::Unknown;
+    };
+    serde_json::from_slice::<Value>(&output.stdout)
+        .ok()
+        .and_then(|j| j.pointer("/smart_status/passed").and_then(Value::as_bool))
+        .map_or(Health::Unknown, |passed| {
+            if passed {
+                Health::Passed
+            } else {
+                Health::Failed
+            }
+        })
+}
+
+fn load_degraded() -> Vec<Degraded> {
+    let mut degraded = std::fs::read_to_string("/proc/mdstat")
+        .map(|t| parse_mdstat(&t))
+        .unwrap_or_default();
+    if command_exists("zpool") {
+        if let Some(output) = output_with_timeout("zpool", &["list", "-H", "-o", "name,health"], 5)
+        {
+            for line in String::from_utf8_lossy(&output.stdout).lines() {
+                if let Some((name, health)) = line.split_once('\t') {
+                    if health.trim() != "ONLINE" {
+                        degraded.push(Degraded {
+                            name: name.to_string(),
+                            detail: health.trim().to_string(),
+                        });
+                    }
+                }
+            }
+        }
+    }
+    degraded
+}
+
+/// md arrays with a missing member (`[U_]`)
+fn parse_mdstat(text: &str) -> Vec<Degraded> {
+    let mut result = Vec::new();
+    let mut current: Option<&str> = None;
+    for line in text.lines() {
+        if let Some((name, _)) = line.split_once(" : ") {
+            current = Some(name.trim()).filter(|n| n.starts_with("md"));
+            continue;
+        }
+        let Some(name) = current else {
+            continue;
+        };
+        let members = line
+            .split_whitespace()
+            .rfind(|w| w.starts_with('[') && w.chars().all(|c| matches!(c, '[' | ']' | 'U' | '_')));
+        if let Some(members) = members {
+            if members.contains('_') {
+                result.push(Degraded {
+                    name: name.to_string(),
+                    detail: members.to_string(),
+                });
+            }
+            current = None;
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mount_sources() {
+        let fstab = "# comment\n\
+            /dev/disk/by-uuid/abc / ext4 defaults 0 1\n\
+            nas:/export/media /mnt/media nfs4 x-systemd.automount,noauto 0 0\n\
+            //server/share\\040one /mnt/my\\040share cifs credentials=/etc/smb 0 0\n";
+        assert_eq!(
+            parse_fstab(fstab),
+            vec![
+                (
+                    "nas:/export/media".into(),
+                    "/mnt/media".into(),
+                    "nfs4".into()
+                ),
+                (
+                    "//server/share one".into(),
+                    "/mnt/my share".into(),
+                    "cifs".into()
+                ),
+            ]
+        );
+        assert_eq!(unit_name("/mnt/media"), "mnt-media");
+        assert_eq!(unit_name("/mnt/my share"), "mnt-my\\x20share");
+        assert_eq!(unit_name("/srv/nas-1"), "srv-nas\\x2d1");
+
+        let states = parse_unit_states(
+            "Id=mnt-media.mount\nActiveState=failed\n\nId=mnt-media.automount\nActiveState=active\n",
+        );
+        assert_eq!(states["mnt-media.mount"], "failed");
+        assert_eq!(
+            parse_df("Filesystem 1-blocks Used Available Capacity Mounted on\nnas:/x 1000 250 750 25% /mnt/media\n"),
+            Some((250, 1000))
+        );
+
+        let mdstat = "Personalities : [raid1]\n\
+            md0 : active raid1 sdb1[1] sda1[0]\n      \
+            976630336 blocks super 1.2 [2/2] [UU]\n\n\
+            md1 : active raid1 sdc1[0]\n      \
+            976630336 blocks super 1.2 [2/1] [U_]\n";
+        assert_eq!(
+            parse_mdstat(mdstat),
+            vec![Degraded {
+                name: "md1".into(),
+                detail: "[U_]".into()
+            }]
+        );
+    }
+}
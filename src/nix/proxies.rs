@@ -0,0 +1,416 @@
+//! Reverse proxy virtual hosts (nginx, Caddy, Traefik)
+//!
+//! Reads the configuration files the NixOS modules generate for
+//! `services.nginx.virtualHosts`, `services.caddy.virtualHosts` and
+//! `services.traefik.dynamicConfigOptions`, found via each unit's ExecStart.
+//! Evaluating the NixOS config would be exact but takes seconds; the
+//! generated files contain the same information.
+
+use super::services::run_with_timeout;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProxyKind {
+    Nginx,
+    Caddy,
+    Traefik,
+}
+
+impl ProxyKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ProxyKind::Nginx => "nginx",
+            ProxyKind::Caddy => "caddy",
+            ProxyKind::Traefik => "traefik",
+        }
+    }
+}
+
+/// One domain → upstream mapping
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProxyRoute {
+    pub proxy: ProxyKind,
+    pub domain: String,
+    /// Upstream as configured ("http://127.0.0.1:8080", "unix:/run/x.sock")
+    pub upstream: String,
+    pub port: Option<u16>,
+    /// Upstream is on this machine (port can be checked against `ss`)
+    pub local: bool,
+}
+
+/// All routes of all configured proxies. Blocking — run in background thread!
+pub fn load_routes() -> Vec<ProxyRoute> {
+    let hostname = std::fs::read_to_string("/proc/sys/kernel/hostname")
+        .map(|h| h.trim().to_string())
+        .unwrap_or_default();
+
+    let mut routes = Vec::new();
+    if let Some(conf) = config_file("nginx.service", &["-c"], "/etc/nginx/nginx.conf") {
+        routes.extend(parse_nginx(&conf, &hostname));
+    }
+    if let Some(conf) = config_file("caddy.service", &["--config"], "/etc/caddy/caddy_config") {
+        routes.extend(parse_caddyfile(&conf, &hostname));
+    }
+    if let Some(conf) = config_file("traefik.service", &["--configfile"], "") {
+        routes.extend(parse_traefik(&conf, &hostname));
+    }
+
+    routes.sort_by(|a, b| a.domain.cmp(&b.domain).then(a.upstream.cmp(&b.upstream)));
+    routes.dedup();
+    routes
+}
+
+/// Contents of the config file passed to a unit with one of `flags`
+fn config_file(unit: &str, flags: &[&str], fallback: &str) -> Option<String> {
+    let exec = run_with_timeout(
+        "systemctl",
+        &["show", "-p", "ExecStart", "--value", unit],
+        3,
+    )
+    .unwrap_or_default();
+
+    // "{ path=… ; argv[]=/nix/store/…/nginx -c '/nix/store/…-nginx.conf' ; … }"
+    let argv = exec
+        .split(" ; ")
+        .find_map(|part| part.trim().strip_prefix("argv[]="))
+        .unwrap_or("");
+    let args: Vec<&str> = argv.split_whitespace().collect();
+    let path = args.iter().enumerate().find_map(|(i, arg)| {
+        flags.iter().find_map(|flag| {
+            if *arg == *flag {
+                args.get(i + 1).copied()
+            } else {
+                arg.strip_prefix(flag)?.strip_prefix('=')
+            }
+        })
+    });
+
+    let path = path
+        .map(|p| p.trim_matches(|c| c == '\'' || c == '"').to_string())
+        .or_else(|| (!fallback.is_empty()).then(|| fallback.to_string()))?;
+    std::fs::read_to_string(path).ok()
+}
+
+// ── nginx ──
+
+fn parse_nginx(conf: &str, hostname: &str) -> Vec<ProxyRoute> {
+    // server blocks as (server_name words, proxy_pass targets)
+    let mut upstreams: HashMap<String, Vec<String>> = HashMap::new();
+    let mut servers: Vec<(Vec<String>, Vec<String>)> = Vec::new();
+
+    let mut stack: Vec<Vec<String>> = Vec::new();
+    let mut words: Vec<String> = Vec::new();
+    for token in nginx_tokens(conf) {
+        match token.as_str() {
+            "{" => {
+                if words.first().map(|w| w.as_str()) == Some("server")
+                    && stack.last().and_then(|b| b.first()).map(|w| w.as_str()) == Some("http")
+                {
+                    servers.push((Vec::new(), Vec::new()));
+                }
+                stack.push(std::mem::take(&mut words));
+            }
+            "}" => {
+                stack.pop();
+                words.clear();
+            }
+            ";" => {
+                let block: Vec<&str> = stack
+                    .iter()
+                    .map(|b| b.first().map(|w| w.as_str()).unwrap_or(""))
+                    .collect();
+                let in_server = block.len() >= 2 && block[0] == "http" && block[1] == "server";
+                match words.first().map(|w| w.as_str()) {
+                    Some("server") if block.last() == Some(&"upstream") => {
+                        if let (Some(name), Some(addr)) =
+                            (stack.last().and_then(|b| b.get(1)), words.get(1))
+                        {
+                            upstreams
+                                .entry(name.clone())
+                                .or_default()
+                                .push(addr.clone());
+                        }
+                    }
+                    Some("server_name") if in_server && block.len() == 2 => {
+                        if let Some(server) = servers.last_mut() {
+                            server.0.extend(words[1..].iter().cloned());
+                        }
+                    }
+                    Some("proxy_pass" | "grpc_pass") if in_server => {
+                        if let (Some(server), Some(target)) = (servers.last_mut(), words.get(1)) {
+                            server.1.push(target.clone());
+                        }
+                    }
+                    _ => {}
+                }
+                words.clear();
+            }
+            _ => words.push(token),
+        }
+    }
+
+    let mut routes = Vec::new();
+    for (names, targets) in servers {
+        let domains: Vec<String> = names
+            .into_iter()
+            .filter(|n| !n.is_empty() && n != "_" && n != "\"\"")
+            .collect();
+        let domains = if domains.is_empty() {
+            vec!["_".to_string()]
+        } else {
+            domains
+        };
+        for target in &targets {
+            // proxy_pass http://<upstream name>/ → the upstream's servers
+            let (scheme, authority) = split_url(target);
+            let resolved: Vec<String> = match upstreams.get(authority) {
+                Some(servers) => servers.iter().map(|s| format!("{}{}", scheme, s)).collect(),
+                None => vec![target.clone()],
+            };
+            for upstream in resolved {
+                for domain in &domains {
+                    routes.push(route(ProxyKind::Nginx, domain, &upstream, hostname));
+                }
+            }
+        }
+    }
+    routes
+}
+
+/// nginx config tokens: words, quoted strings, `{`, `}`, `;`
+fn nginx_tokens(conf: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut chars = conf.chars();
+    let mut word = String::new();
+    let flush = |word: &mut String, tokens: &mut Vec<String>| {
+        if !word.is_empty() {
+            tokens.push(std::mem::take(word));
+        }
+    };
+    while let Some(c) = chars.next() {
+        match c {
+            '#' => {
+                flush(&mut word, &mut tokens);
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            '\'' | '"' => {
+                for q in chars.by_ref() {
+                    if q == c {
+                        break;
+                    }
+                    word.push(q);
+                }
+            }
+            '{' | '}' | ';' => {
+                flush(&mut word, &mut tokens);
+                tokens.push(c.to_string());
+            }
+            c if c.is_whitespace() => flush(&mut word, &mut tokens),
+            c => word.push(c),
+        }
+    }
+    flush(&mut word, &mut tokens);
+    tokens
+}
+
+// ── Caddy ──
+
+fn parse_caddyfile(conf: &str, hostname: &str) -> Vec<ProxyRoute> {
+    let mut routes = Vec::new();
+    let mut depth = 0usize;
+    let mut sites: Vec<String> = Vec::new();
+    let mut in_proxy_block = false;
+
+    for raw in conf.lines() {
+        let line = raw.split(" #").next().unwrap_or("").trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let opens = line.ends_with('{');
+        let words: Vec<&str> = line.trim_end_matches('{').split_whitespace().collect();
+
+        if depth == 0 && opens {
+            // Global options `{` and snippets `(name) {` are not sites
+            sites = words
+                .iter()
+                .flat_map(|w| w.split(','))
+                .filter(|w| !w.is_empty() && !w.starts_with('('))
+                .map(|w| w.to_string())
+                .collect();
+        } else if depth > 0 && !sites.is_empty() {
+            let upstreams: Vec<&str> = match words.first() {
+                Some(&"reverse_proxy") => {
+                    in_proxy_block = opens;
+                    words[1..]
+                        .iter()
+                        .filter(|w| !w.starts_with('/') && !w.starts_with('@') && **w != "*")
+                        .copied()
+                        .collect()
+                }
+                Some(&"to") if in_proxy_block => words[1..].to_vec(),
+                _ => Vec::new(),
+            };
+            for upstream in upstreams {
+                for site in &sites {
+                    let domain = site
+                        .trim_start_matches("https://")
+                        .trim_start_matches("http://");
+                    routes.push(route(ProxyKind::Caddy, domain, upstream, hostname));
+                }
+            }
+        }
+
+        if opens {
+            depth += 1;
+        }
+        if line == "}" || (line.starts_with('}') && !opens) {
+            depth = depth.saturating_sub(1);
+            if depth <= 1 {
+                in_proxy_block = false;
+            }
+            if depth == 0 {
+                sites.clear();
+            }
+        }
+    }
+    routes
+}
+
+// ── Traefik ──
+
+fn parse_traefik(static_conf: &str, hostname: &str) -> Vec<ProxyRoute> {
+    let Ok(static_conf) = static_conf.parse::<toml::Value>() else {
+        return Vec::new();
+    };
+    let file = static_conf.get("providers").and_then(|p| p.get("file"));
+    let mut dynamic_files = Vec::new();
+    if let Some(f) = file
+        .and_then(|f| f.get("filename"))
+        .and_then(|f| f.as_str())
+    {
+        dynamic_files.push(std::path::PathBuf::from(f));
+    }
+    if let Some(dir) = file
+        .and_then(|f| f.get("directory"))
+        .and_then(|d| d.as_str())
+    {
+        if let Ok(entries) = std::fs::read_dir(dir) {
+            dynamic_files.extend(entries.flatten().map(|e| e.path()));
+        }
+    }
+
+    let mut routes = Vec::new();
+    for path in dynamic_files {
+        let Some(conf) = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|c| c.parse::<toml::Value>().ok())
+        else {
+            continue;
+        };
+        let Some(http) = conf.get("http") else {
+            continue;
+        };
+        let services = http.get("services").and_then(|s| s.as_table());
+        let Some(routers) = http.get("routers").and_then(|r| r.as_table()) else {
+            continue;
+        };
+        for router in routers.values() {
+            let rule = router.get("rule").and_then(|r| r.as_str()).unwrap_or("");
+            let Some(service) = router.get("service").and_then(|s| s.as_str()) else {
+                continue;
+            };
+            let urls: Vec<&str> = services
+                .and_then(|s| s.get(service))
+                .and_then(|s| s.get("loadBalancer"))
+                .and_then(|lb| lb.get("servers"))
+                .and_then(|s| s.as_array())
+                .map(|servers| {
+                    servers
+                        .iter()
+                        .filter_map(|s| s.get("url").and_then(|u| u.as_str()))
+                        .collect()
+                })
+                .unwrap_or_default();
+            for domain in traefik_hosts(rule) {
+                for url in &urls {
+                    routes.push(route(ProxyKind::Traefik, &domain, url, hostname));
+                }
+            }
+        }
+    }
+    routes
+}
+
+/// Domains in a router rule: "Host(`a.org`) || Host(`b.org`, `c.org`)"
+fn traefik_hosts(rule: &str) -> Vec<String> {
+    let mut hosts = Vec::new();
+    let mut rest = rule;
+    while let Some(pos) = rest.find("Host(") {
+        // Skip HostRegexp/HostSNI — only plain Host() names a domain
+        let args_start = pos + "Host(".len();
+        let args_end = rest[args_start..]
+            .find(')')
+            .map(|e| args_start + e)
+            .unwrap_or(rest.len());
+        let is_plain = pos == 0 || !rest[..pos].ends_with(|c: char| c.is_alphanumeric());
+        if is_plain {
+            hosts.extend(
+                rest[args_start..args_end]
+                    .split(',')
+                    .map(|h| h.trim().trim_matches(|c| c == '`' || c == '"').to_string())
+                    .filter(|h| !h.is_empty()),
+            );
+        }
+        rest = &rest[args_end..];
+    }
+    if hosts.is_empty() {
+        hosts.push("*".to_string());
+    }
+    hosts
+}
+
+// ── Helpers ──
+
+/// "http://host:port/path" → ("http://", "host:port")
+fn split_url(url: &str) -> (&str, &str) {
+    let (scheme, rest) = match url.find("://") {
+        Some(pos) => url.split_at(pos + 3),
+        None => ("", url),
+    };
+    let authority = rest.split('/').next().unwrap_or(rest);
+    (scheme, authority)
+}
+
+fn route(proxy: ProxyKind, domain: &str, upstream: &str, hostname: &str) -> ProxyRoute {
+    let (scheme, authority) = split_url(upstream);
+    let is_unix = upstream.starts_with("unix:") || authority.starts_with("unix");
+
+    // host:port, [v6]:port or :port
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((host, port)) if !port.contains(']') => (host, port.parse::<u16>().ok()),
+        _ => (authority, None),
+    };
+    let port = port.or(match scheme {
+        "http://" => Some(80),
+        "https://" => Some(443),
+        _ => None,
+    });
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    let local = !is_unix
+        && (matches!(
+            host,
+            "" | "localhost" | "127.0.0.1" | "::1" | "0.0.0.0" | "::"
+        ) || (!hostname.is_empty() && host == hostname));
+
+    ProxyRoute {
+        proxy,
+        domain: domain.to_string(),
+        upstream: upstream.to_string(),
+        port: if is_unix { None } else { port },
+        local,
+    }
+}
@@ -28,10 +28,17 @@ pub struct ServiceEntry {
     pub enabled: EnableState,
     pub description: String,
     pub pid: Option<u32>,
-    pub memory: Option<String>,
+    /// Memory of the unit's or container's cgroup, in bytes
+    pub memory: Option<u64>,
+    /// CPU time the cgroup used so far
+    pub cpu_usec: Option<u64>,
+    /// CPU use since the previous load (filled in by the Services module)
+    pub cpu_percent: Option<f64>,
     pub uptime: Option<String>,
     /// Ports this entry is listening on (filled in after port scan)
     pub ports: Vec<u16>,
+    /// Listening socket unit that starts this service on demand
+    pub socket: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -68,6 +75,9 @@ pub enum RunState {
     Paused,
     Created,
     Exited,
+    /// Not running, but its socket is listening: started on the next
+    /// connection. Healthy, not stopped.
+    SocketIdle,
     Unknown,
 }
 
@@ -80,6 +90,7 @@ impl RunState {
             RunState::Restarting => "↻",
             RunState::Paused => "⏸",
             RunState::Created => "◌",
+            RunState::SocketIdle => "◎",
             RunState::Unknown => "?",
         }
     }
@@ -87,6 +98,24 @@ impl RunState {
     pub fn is_active(&self) -> bool {
         matches!(self, RunState::Running | RunState::Restarting)
     }
+
+    /// Running, or ready to be started by its socket
+    pub fn is_up(&self) -> bool {
+        self.is_active() || *self == RunState::SocketIdle
+    }
+
+    /// Listing order: failed first, then running, then the rest
+    pub fn sort_rank(&self) -> u8 {
+        match self {
+            RunState::Failed => 0,
+            RunState::Running => 1,
+            RunState::Restarting | RunState::SocketIdle => 2,
+            RunState::Paused => 3,
+            RunState::Created => 4,
+            RunState::Stopped | RunState::Exited => 5,
+            RunState::Unknown => 6,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -165,6 +194,8 @@ impl ServiceAction {
 pub struct DashboardStats {
     pub services_running: usize,
     pub services_failed: usize,
+    /// Idle, waiting for their socket
+    pub services_socket_idle: usize,
     pub services_total: usize,
     pub containers_running: usize,
     pub containers_stopped: usize,
@@ -202,19 +233,9 @@ pub fn load_dashboard() -> Result<(Vec<ServiceEntry>, Vec<PortEntry>, DashboardS
 
     // 5. Sort: failed first, then running, then rest
     entries.sort_by(|a, b| {
-        fn rank(r: &RunState) -> u8 {
-            match r {
-                RunState::Failed => 0,
-                RunState::Running => 1,
-                RunState::Restarting => 2,
-                RunState::Paused => 3,
-                RunState::Created => 4,
-                RunState::Stopped | RunState::Exited => 5,
-                RunState::Unknown => 6,
-            }
-        }
-        rank(&a.status)
-            .cmp(&rank(&b.status))
+        a.status
+            .sort_rank()
+            .cmp(&b.status.sort_rank())
             .then(a.kind.label().cmp(b.kind.label()))
             .then(a.display_name.cmp(&b.display_name))
     });
@@ -229,6 +250,10 @@ pub fn load_dashboard() -> Result<(Vec<ServiceEntry>, Vec<PortEntry>, DashboardS
             .iter()
             .filter(|e| e.kind == EntryKind::Systemd && e.status == RunState::Failed)
             .count(),
+        services_socket_idle: entries
+            .iter()
+            .filter(|e| e.status == RunState::SocketIdle)
+            .count(),
         services_total: entries
             .iter()
             .filter(|e| e.kind == EntryKind::Systemd)
@@ -316,6 +341,7 @@ fn list_systemd_services() -> Result<Vec<ServiceEntry>> {
 
     let stdout = String::from_utf8_lossy(&output.stdout);
     let enable_states = fetch_enable_states();
+    let sockets = fetch_socket_triggers();
     let mut services = Vec::new();
 
     for line in stdout.lines() {
@@ -344,10 +370,12 @@ fn list_systemd_services() -> Result<Vec<ServiceEntry>> {
         let active = tokens[2];
         let sub = tokens[3];
 
+        let socket = sockets.get(unit_name).cloned();
         let status = match (active, sub) {
             (_, "running") => RunState::Running,
             ("failed", _) | (_, "failed") => RunState::Failed,
             (_, "activating" | "auto-restart" | "start") => RunState::Restarting,
+            ("inactive", _) | (_, "dead") if socket.is_some() => RunState::SocketIdle,
             ("inactive", _) | (_, "dead" | "exited") => RunState::Stopped,
             _ => RunState::Unknown,
         };
@@ -366,8 +394,11 @@ fn list_systemd_services() -> Result<Vec<ServiceEntry>> {
             description: desc,
             pid: None,
             memory: None,
+            cpu_usec: None,
+            cpu_percent: None,
             uptime: None,
             ports: Vec::new(),
+            socket,
         });
     }
 
@@ -407,6 +438,34 @@ fn fetch_enable_states() -> HashMap<String, EnableState> {
     map
 }
 
+/// Service → the listening socket unit that activates it.
+///
+/// `systemctl list-sockets` lines are `LISTEN UNIT ACTIVATES...`; a socket
+/// with several addresses appears once per address. Sockets that aren't
+/// listening aren't listed, so an idle service behind one is really down.
+fn fetch_socket_triggers() -> HashMap<String, String> {
+    let mut map = HashMap::new();
+    let Some(stdout) = run_with_timeout(
+        "systemctl",
+        &["list-sockets", "--no-pager", "--no-legend", "--plain"],
+        5,
+    ) else {
+        return map;
+    };
+    for line in stdout.lines() {
+        let mut tokens = line.split_whitespace().skip(1);
+        let Some(socket) = tokens.find(|t| t.ends_with(".socket")) else {
+            continue;
+        };
+        for service in tokens.flat_map(|t| t.split(',')) {
+            if service.ends_with(".service") {
+                map.insert(service.to_string(), socket.to_string());
+            }
+        }
+    }
+    map
+}
+
 fn fill_systemd_pids(services: &mut [ServiceEntry]) {
     let running: Vec<String> = services
         .iter()
@@ -421,7 +480,7 @@ fn fill_systemd_pids(services: &mut [ServiceEntry]) {
     for chunk in running.chunks(50) {
         let mut args: Vec<&str> = vec![
             "show",
-            "--property=Id,MainPID,MemoryCurrent,ActiveEnterTimestamp",
+            "--property=Id,MainPID,MemoryCurrent,CPUUsageNSec,ActiveEnterTimestamp",
         ];
         for name in chunk {
             args.push(name);
@@ -435,7 +494,8 @@ fn fill_systemd_pids(services: &mut [ServiceEntry]) {
         let stdout = String::from_utf8_lossy(&output.stdout);
         let mut cur_id = String::new();
         let mut cur_pid: Option<u32> = None;
-        let mut cur_mem: Option<String> = None;
+        let mut cur_mem: Option<u64> = None;
+        let mut cur_cpu: Option<u64> = None;
         let mut cur_up: Option<String> = None;
 
         for line in stdout.lines() {
@@ -444,12 +504,14 @@ fn fill_systemd_pids(services: &mut [ServiceEntry]) {
                     if let Some(svc) = services.iter_mut().find(|s| s.name == cur_id) {
                         svc.pid = cur_pid;
                         svc.memory = cur_mem.take();
+                        svc.cpu_usec = cur_cpu.take();
                         svc.uptime = cur_up.take();
                     }
                 }
                 cur_id.clear();
                 cur_pid = None;
                 cur_mem = None;
+                cur_cpu = None;
                 cur_up = None;
                 continue;
             }
@@ -463,12 +525,16 @@ fn fill_systemd_pids(services: &mut [ServiceEntry]) {
                             }
                         }
                     }
+                    // u64::MAX or "[not set]" without accounting
                     "MemoryCurrent" => {
-                        if let Ok(bytes) = val.parse::<u64>() {
-                            if bytes < u64::MAX {
-                                cur_mem = Some(crate::types::format_bytes(bytes));
-                            }
-                        }
+                        cur_mem = val.parse::<u64>().ok().filter(|&b| b < u64::MAX);
+                    }
+                    "CPUUsageNSec" => {
+                        cur_cpu = val
+                            .parse::<u64>()
+                            .ok()
+                            .filter(|&ns| ns < u64::MAX)
+                            .map(|ns| ns / 1000);
                     }
                     "ActiveEnterTimestamp" => {
                         if !val.is_empty() && val != "n/a" {
@@ -484,6 +550,7 @@ fn fill_systemd_pids(services: &mut [ServiceEntry]) {
             if let Some(svc) = services.iter_mut().find(|s| s.name == cur_id) {
                 svc.pid = cur_pid;
                 svc.memory = cur_mem;
+                svc.cpu_usec = cur_cpu;
                 svc.uptime = cur_up;
             }
         }
@@ -542,6 +609,7 @@ fn list_docker_containers() -> Result<Vec<ServiceEntry>> {
         } else {
             None
         };
+        let (cpu_usec, memory) = pid.map_or((None, None), cgroup_usage);
 
         containers.push(ServiceEntry {
             kind: EntryKind::Docker,
@@ -551,13 +619,16 @@ fn list_docker_containers() -> Result<Vec<ServiceEntry>> {
             enabled: EnableState::NotApplicable,
             description: image.to_string(),
             pid,
-            memory: None,
+            memory,
+            cpu_usec,
+            cpu_percent: None,
             uptime: if status_detail.is_empty() {
                 None
             } else {
                 Some(status_detail.to_string())
             },
             ports: parse_container_ports(port_map),
+            socket: None,
         });
     }
 
@@ -615,6 +686,7 @@ fn list_podman_containers() -> Result<Vec<ServiceEntry>> {
         } else {
             None
         };
+        let (cpu_usec, memory) = pid.map_or((None, None), cgroup_usage);
 
         containers.push(ServiceEntry {
             kind: EntryKind::Podman,
@@ -624,19 +696,48 @@ fn list_podman_containers() -> Result<Vec<ServiceEntry>> {
             enabled: EnableState::NotApplicable,
             description: image.to_string(),
             pid,
-            memory: None,
+            memory,
+            cpu_usec,
+            cpu_percent: None,
             uptime: if status_detail.is_empty() {
                 None
             } else {
                 Some(status_detail.to_string())
             },
             ports: parse_container_ports(port_map),
+            socket: None,
         });
     }
 
     Ok(containers)
 }
 
+/// CPU time and memory of the cgroup `pid` runs in (cgroup v2), for
+/// containers: their runtime's own stats would cost a call per container
+fn cgroup_usage(pid: u32) -> (Option<u64>, Option<u64>) {
+    let Some(path) = std::fs::read_to_string(format!("/proc/{}/cgroup", pid))
+        .ok()
+        .and_then(|c| {
+            c.lines()
+                .find_map(|l| l.strip_prefix("0::").map(String::from))
+        })
+    else {
+        return (None, None);
+    };
+    let dir = format!("/sys/fs/cgroup{}", path.trim());
+    let cpu = std::fs::read_to_string(format!("{}/cpu.stat", dir))
+        .ok()
+        .and_then(|stat| {
+            stat.lines()
+                .find_map(|l| l.strip_prefix("usage_usec "))
+                .and_then(|v| v.trim().parse().ok())
+        });
+    let memory = std::fs::read_to_string(format!("{}/memory.current", dir))
+        .ok()
+        .and_then(|v| v.trim().parse().ok());
+    (cpu, memory)
+}
+
 fn get_container_pid(runtime: &str, name: &str) -> Option<u32> {
     let stdout = run_with_timeout(runtime, &["inspect", "--format", "{{.State.Pid}}", name], 3)?;
     let pid: u32 = stdout.trim().parse().ok()?;
@@ -737,68 +838,317 @@ fn parse_ss_process(s: &str) -> (String, Option<u32>) {
 
 // ── Logs ──
 
-/// Get logs for any entry (dispatches based on kind)
-pub fn get_logs(entry: &ServiceEntry, count: u32) -> Result<Vec<String>> {
-    let count_str = count.to_string();
-    match entry.kind {
-        EntryKind::Systemd => {
-            let output = Command::new("journalctl")
-                .args([
-                    "-u",
-                    &entry.name,
-                    "--no-pager",
-                    "-n",
-                    &count_str,
-                    "--output=short-iso",
-                ])
-                .output()
-                .context("Failed to run journalctl")?;
+/// Tail sizes the log viewer cycles through
+pub const LOG_TAIL_SIZES: &[u32] = &[100, 200, 1000, 5000, 20000];
+
+/// Container logs are streamed; give up (and keep what we have) after this
+const CONTAINER_LOG_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// What part of a log to fetch. Filters are applied by journalctl / the
+/// container runtime where they support it, so huge logs never end up in
+/// memory just to find a few lines.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogQuery {
+    /// Keep at most this many (matching) lines, newest last
+    pub tail: u32,
+    /// Start of the time range, as typed by the user (see `parse_log_time`)
+    pub since: Option<String>,
+    /// End of the time range
+    pub until: Option<String>,
+    /// Case-insensitive substring / journalctl pattern
+    pub grep: Option<String>,
+    /// Preset range; a typed `since` replaces it
+    pub range: LogRange,
+    /// Only priority `err` and worse (containers: lines that say so)
+    pub errors_only: bool,
+}
 
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            Ok(stdout
-                .lines()
-                .filter(|l| !l.starts_with("-- "))
-                .map(|l| l.to_string())
-                .collect())
+/// Time range presets of the Logs tab
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogRange {
+    #[default]
+    All,
+    /// Since the current boot
+    Boot,
+    LastHour,
+    Today,
+}
+
+impl LogRange {
+    pub fn next(self) -> Self {
+        match self {
+            LogRange::All => LogRange::Boot,
+            LogRange::Boot => LogRange::LastHour,
+            LogRange::LastHour => LogRange::Today,
+            LogRange::Today => LogRange::All,
+        }
+    }
+
+    fn journal_args(self) -> &'static [&'static str] {
+        match self {
+            LogRange::All => &[],
+            LogRange::Boot => &["--boot"],
+            LogRange::LastHour => &["--since=-1h"],
+            LogRange::Today => &["--since=today"],
+        }
+    }
+
+    /// Start of the range in local time, for container runtimes
+    fn start(self) -> Option<chrono::NaiveDateTime> {
+        let now = chrono::Local::now();
+        match self {
+            LogRange::All => None,
+            LogRange::Boot => {
+                let stat = std::fs::read_to_string("/proc/stat").ok()?;
+                let btime = stat
+                    .lines()
+                    .find_map(|l| l.strip_prefix("btime "))?
+                    .trim()
+                    .parse::<i64>()
+                    .ok()?;
+                chrono::DateTime::from_timestamp(btime, 0)
+                    .map(|t| t.with_timezone(&chrono::Local).naive_local())
+            }
+            LogRange::LastHour => Some((now - chrono::Duration::hours(1)).naive_local()),
+            LogRange::Today => now.date_naive().and_hms_opt(0, 0, 0),
+        }
+    }
+}
+
+/// Container log lines that count as errors when only errors are shown
+fn is_error_line(line: &str) -> bool {
+    let lower = line.to_lowercase();
+    ["error", "fatal", "panic", "critical"]
+        .iter()
+        .any(|w| lower.contains(w))
+}
+
+impl Default for LogQuery {
+    fn default() -> Self {
+        Self {
+            tail: 200,
+            since: None,
+            until: None,
+            grep: None,
+            range: LogRange::All,
+            errors_only: false,
         }
+    }
+}
+
+impl LogQuery {
+    pub fn is_filtered(&self) -> bool {
+        self.since.is_some()
+            || self.until.is_some()
+            || self.grep.is_some()
+            || self.range != LogRange::All
+            || self.errors_only
+    }
+}
+
+/// Parse a user-entered point in time: relative ("30m", "2h", "3d") or
+/// absolute ("2024-05-01", "2024-05-01 14:30[:00]"), in local time.
+pub fn parse_log_time(input: &str) -> Option<chrono::NaiveDateTime> {
+    let input = input.trim();
+    let now = chrono::Local::now().naive_local();
+
+    if let Some(unit) = input.chars().last().filter(|c| c.is_ascii_alphabetic()) {
+        if let Ok(n) = input[..input.len() - 1].trim().parse::<i64>() {
+            let unit_secs = match unit {
+                's' => 1,
+                'm' => 60,
+                'h' => 3600,
+                'd' => 86400,
+                'w' => 7 * 86400,
+                _ => return None,
+            };
+            // Absurd amounts are no time at all rather than a crash
+            let secs = n.checked_mul(unit_secs)?;
+            return now.checked_sub_signed(chrono::Duration::try_seconds(secs)?);
+        }
+    }
+
+    for fmt in ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M:%S"] {
+        if let Ok(t) = chrono::NaiveDateTime::parse_from_str(input, fmt) {
+            return Some(t);
+        }
+    }
+    chrono::NaiveDate::parse_from_str(input, "%Y-%m-%d")
+        .ok()
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+}
+
+/// Get logs for any entry (dispatches based on kind)
+pub fn get_logs(entry: &ServiceEntry, query: &LogQuery) -> Result<Vec<String>> {
+    let since = query
+        .since
+        .as_deref()
+        .and_then(parse_log_time)
+        .or_else(|| query.range.start());
+    let until = query.until.as_deref().and_then(parse_log_time);
+
+    match entry.kind {
+        EntryKind::Systemd => get_units_logs(std::slice::from_ref(&entry.name), query),
         EntryKind::Docker | EntryKind::Podman => {
             let runtime = if entry.kind == EntryKind::Docker {
                 "docker"
             } else {
                 "podman"
             };
-            let output = match output_with_timeout(
-                runtime,
-                &[
-                    "logs",
-                    "--tail",
-                    &count_str,
-                    "--timestamps",
-                    &entry.display_name,
-                ],
-                5,
-            ) {
-                Some(o) => o,
-                None => return Ok(vec!["(timeout fetching logs)".to_string()]),
-            };
+            let mut args = vec!["logs".to_string(), "--timestamps".to_string()];
+            // Runtimes can't grep, so with a pattern the tail is applied to
+            // the matches while streaming instead
+            if query.grep.is_none() && !query.errors_only {
+                args.push("--tail".to_string());
+                args.push(query.tail.to_string());
+            }
+            if let Some(t) = since {
+                args.push(format!("--since={}", t.format("%Y-%m-%dT%H:%M:%S")));
+            }
+            if let Some(t) = until {
+                args.push(format!("--until={}", t.format("%Y-%m-%dT%H:%M:%S")));
+            }
+            args.push(entry.display_name.clone());
 
-            let mut lines: Vec<String> = String::from_utf8_lossy(&output.stdout)
-                .lines()
-                .map(|l| l.to_string())
-                .collect();
-            // Container logs can also be on stderr
-            lines.extend(
-                String::from_utf8_lossy(&output.stderr)
-                    .lines()
-                    .map(|l| l.to_string()),
-            );
-            Ok(lines)
+            stream_container_logs(runtime, &args, query)
+        }
+    }
+}
+
+/// Run `runtime logs …` and keep only the last `query.tail` lines matching
+/// `query.grep`, reading line by line so memory stays bounded.
+fn stream_container_logs(runtime: &str, args: &[String], query: &LogQuery) -> Result<Vec<String>> {
+    use std::collections::VecDeque;
+    use std::io::{BufRead, BufReader};
+    use std::process::Stdio;
+
+    // Container output goes to both streams; merge them in the shell so
+    // a single reader sees the lines in order
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg("exec \"$0\" \"$@\" 2>&1")
+        .arg(runtime)
+        .args(args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .with_context(|| format!("Failed to run {} logs", runtime))?;
+
+    let stdout = child.stdout.take().context("No log output")?;
+    let tail = query.tail.max(1) as usize;
+    let needle = query.grep.as_ref().map(|g| g.to_lowercase());
+    let errors_only = query.errors_only;
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let mut kept: VecDeque<String> = VecDeque::with_capacity(tail.min(4096));
+        for line in BufReader::new(stdout).split(b'\n').map_while(|l| l.ok()) {
+            let line = String::from_utf8_lossy(&line).into_owned();
+            if needle
+                .as_ref()
+                .is_some_and(|n| !line.to_lowercase().contains(n.as_str()))
+            {
+                continue;
+            }
+            if errors_only && !is_error_line(&line) {
+                continue;
+            }
+            if kept.len() == tail {
+                kept.pop_front();
+            }
+            kept.push_back(line);
         }
+        let _ = tx.send(kept);
+    });
+
+    if let Ok(kept) = rx.recv_timeout(CONTAINER_LOG_TIMEOUT) {
+        let _ = child.wait();
+        return Ok(kept.into());
     }
+    // Killing the runtime closes the pipe, so the reader hands over what it has
+    let _ = child.kill();
+    let _ = child.wait();
+    let mut lines: Vec<String> = rx
+        .recv_timeout(Duration::from_secs(2))
+        .map(Vec::from)
+        .unwrap_or_default();
+    lines.push("(timeout fetching logs, narrow the time range)".to_string());
+    Ok(lines)
+}
+
+/// Journal of several systemd units, interleaved by time
+pub fn get_units_logs(units: &[String], query: &LogQuery) -> Result<Vec<String>> {
+    let output = Command::new("journalctl")
+        .args(journal_args(units, query))
+        .output()
+        .context("Failed to run journalctl")?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(stdout
+        .lines()
+        .filter(|l| !l.starts_with("-- "))
+        .map(|l| l.to_string())
+        .collect())
+}
+
+/// `journalctl` arguments for `query`
+fn journal_args(units: &[String], query: &LogQuery) -> Vec<String> {
+    let mut args: Vec<String> = Vec::new();
+    for unit in units {
+        args.push("-u".to_string());
+        args.push(unit.clone());
+    }
+    args.extend([
+        "--no-pager".to_string(),
+        "-n".to_string(),
+        query.tail.to_string(),
+        "--output=short-iso".to_string(),
+    ]);
+    match query.since.as_deref().and_then(parse_log_time) {
+        Some(t) => args.push(format!("--since={}", t.format("%Y-%m-%d %H:%M:%S"))),
+        None => args.extend(query.range.journal_args().iter().map(|a| a.to_string())),
+    }
+    if let Some(t) = query.until.as_deref().and_then(parse_log_time) {
+        args.push(format!("--until={}", t.format("%Y-%m-%d %H:%M:%S")));
+    }
+    if query.errors_only {
+        args.push("--priority=err".to_string());
+    }
+    if let Some(pattern) = &query.grep {
+        // Lowercase patterns match case-insensitively (journalctl smart case)
+        args.push(format!("--grep={}", pattern));
+    }
+    args
 }
 
 // ── Management ──
 
+/// Restart a systemd unit and return the state it settles in. Uses
+/// `sudo -n`: runs from a background thread, so it can't prompt.
+pub fn restart_unit(name: &str) -> Result<String> {
+    let output = Command::new("sudo")
+        .args(["-n", "systemctl", "restart", name])
+        .output()
+        .with_context(|| format!("sudo systemctl restart {}", name))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("{}", stderr.lines().last().unwrap_or("sudo failed").trim());
+    }
+
+    // A service that crashes right after starting shows it a moment later
+    std::thread::sleep(Duration::from_secs(1));
+    let output = Command::new("systemctl")
+        .args(["is-active", name])
+        .output()
+        .context("Failed to run systemctl is-active")?;
+    let state = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    // A oneshot without RemainAfterExit is "inactive" once it succeeded
+    if state == "failed" {
+        anyhow::bail!("failed again");
+    }
+    Ok(state)
+}
+
 /// Execute an action on a service/container
 pub fn execute_action(entry: &ServiceEntry, action: ServiceAction) -> Result<String> {
     let cmd = action.as_str();
@@ -849,6 +1199,56 @@ pub fn execute_action(entry: &ServiceEntry, action: ServiceAction) -> Result<Str
     }
 }
 
+/// Whether stopping `pid` needs sudo: it belongs to another user
+pub fn kill_needs_sudo(pid: u32) -> bool {
+    let uid = std::fs::read_to_string(format!("/proc/{}/status", pid))
+        .ok()
+        .and_then(|status| {
+            status
+                .lines()
+                .find_map(|l| l.strip_prefix("Uid:"))
+                .and_then(|ids| ids.split_whitespace().next()?.parse::<u32>().ok())
+        });
+    uid != Some(unsafe { libc::getuid() })
+}
+
+/// Command name of a running process, as `ss` shows it
+fn process_name(pid: u32) -> Option<String> {
+    std::fs::read_to_string(format!("/proc/{}/comm", pid))
+        .ok()
+        .map(|comm| comm.trim_end().to_string())
+}
+
+/// SIGTERM to a process that holds a port without a service or container
+/// behind it (a stray dev server), through sudo if it isn't ours. `process`
+/// is the name the port list showed: if the PID now belongs to something
+/// else (exited and reused), nothing is killed.
+pub fn kill_process(pid: u32, process: &str, sudo: bool) -> Result<String> {
+    if process_name(pid).as_deref() != Some(process) {
+        anyhow::bail!(
+            "PID {} is no longer {} — refresh the port list",
+            pid,
+            process
+        );
+    }
+    let pid_str = pid.to_string();
+    let output = if sudo {
+        Command::new("sudo")
+            .args(["kill", "-TERM", &pid_str])
+            .output()
+    } else {
+        Command::new("kill").args(["-TERM", &pid_str]).output()
+    }
+    .context(format!("kill {}", pid))?;
+
+    if output.status.success() {
+        Ok(format!("kill {} ✓", pid))
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(anyhow::anyhow!("{}", stderr.trim()))
+    }
+}
+
 // ── Helpers ──
 
 fn tool_available(name: &str) -> bool {
@@ -873,8 +1273,24 @@ fn tool_available(name: &str) -> bool {
     }
 }
 
+/// Number of failed systemd units, None if systemctl isn't usable
+pub fn failed_unit_count() -> Option<usize> {
+    let out = run_with_timeout(
+        "systemctl",
+        &[
+            "list-units",
+            "--state=failed",
+            "--no-legend",
+            "--plain",
+            "--no-pager",
+        ],
+        5,
+    )?;
+    Some(out.lines().filter(|l| !l.trim().is_empty()).count())
+}
+
 /// Run a command with a timeout. Returns stdout on success, None on timeout/error.
-fn run_with_timeout(cmd: &str, args: &[&str], timeout_secs: u64) -> Option<String> {
+pub fn run_with_timeout(cmd: &str, args: &[&str], timeout_secs: u64) -> Option<String> {
     let mut child = Command::new(cmd)
         .args(args)
         .stdout(std::process::Stdio::piped())
@@ -940,3 +1356,51 @@ fn output_with_timeout(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kill_process_checks_name() {
+        let pid = std::process::id();
+        assert!(process_name(pid).is_some_and(|n| !n.is_empty()));
+        assert!(kill_process(pid, "not-this-process", false).is_err());
+    }
+
+    #[test]
+    fn test_journal_args() {
+        let units = vec!["nginx.service".to_string()];
+        let mut query = LogQuery {
+            range: LogRange::Boot,
+            errors_only: true,
+            grep: Some("timeout".to_string()),
+            ..LogQuery::default()
+        };
+        let args = journal_args(&units, &query);
+        assert!(args.contains(&"--boot".to_string()));
+        assert!(args.contains(&"--priority=err".to_string()));
+        assert!(args.contains(&"--grep=timeout".to_string()));
+
+        // A typed start replaces the preset
+        query.since = Some("2024-05-01".to_string());
+        let args = journal_args(&units, &query);
+        assert!(!args.contains(&"--boot".to_string()));
+        assert!(args.contains(&"--since=2024-05-01 00:00:00".to_string()));
+
+        assert_eq!(LogRange::Today.next(), LogRange::All);
+        assert!(is_error_line("level=ERROR msg=\"db down\""));
+        assert!(!LogQuery::default().is_filtered());
+    }
+
+    #[test]
+    fn test_parse_log_time() {
+        assert!(parse_log_time("2h").is_some());
+        assert!(parse_log_time("2024-05-01 14:00").is_some());
+        assert!(parse_log_time("soon").is_none());
+        // Overflowing amounts are rejected, not a panic
+        assert!(parse_log_time("999999999999999d").is_none());
+        assert!(parse_log_time("99999999999999999s").is_none());
+        assert!(parse_log_time("9223372036854775807w").is_none());
+    }
+}
@@ -3,9 +3,12 @@
 //! Provides disk usage analysis, Nix store inspection,
 //! garbage collection, store optimization, and cleanup history.
 
+use crate::nix::generations::SYSTEM_PROFILE;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::io::Read;
+use std::path::PathBuf;
 use std::process::Command;
 use std::time::Duration;
 
@@ -16,7 +19,6 @@ use std::time::Duration;
 /// A single store path with metadata
 #[derive(Debug, Clone)]
 pub struct StorePath {
-    #[allow(dead_code)] // Parsed from nix-store output
     pub path: String,
     pub name: String,
     pub size: u64,
@@ -26,7 +28,6 @@ pub struct StorePath {
 /// Filesystem usage information
 #[derive(Debug, Clone)]
 pub struct DiskUsage {
-    #[allow(dead_code)] // Parsed from df output
     pub mount_point: String,
     pub filesystem: String,
     pub total: u64,
@@ -162,7 +163,7 @@ pub fn load_store_info() -> StoreInfo {
 }
 
 /// Parse disk usage from `df` for a given path
-fn parse_disk_usage(path: &str) -> Option<DiskUsage> {
+pub fn parse_disk_usage(path: &str) -> Option<DiskUsage> {
     let output = Command::new("df")
         .args(["-B1", "--output=source,target,size,used,avail,pcent", path])
         .output()
@@ -196,6 +197,11 @@ fn parse_disk_usage(path: &str) -> Option<DiskUsage> {
     })
 }
 
+/// Free bytes on the filesystem holding /nix/store
+pub fn free_space() -> Option<u64> {
+    parse_disk_usage("/nix/store").map(|d| d.available)
+}
+
 /// Load the set of dead (unreferenced) store paths (with timeout)
 fn load_dead_set() -> HashSet<String> {
     let mut dead = HashSet::new();
@@ -285,7 +291,7 @@ fn load_paths_without_sizes(dead_set: &HashSet<String>) -> Vec<StorePath> {
 
 /// Extract a human-readable name from a store path
 /// /nix/store/abc123...xyz-package-name-1.0 → package-name-1.0
-fn path_to_name(path: &str) -> String {
+pub fn path_to_name(path: &str) -> String {
     if let Some(rest) = path.strip_prefix("/nix/store/") {
         // Hash is 32 chars, then a dash
         if rest.len() > 33 && rest.as_bytes()[32] == b'-' {
@@ -336,6 +342,51 @@ pub fn run_gc_full() -> Result<GcResult> {
     })
 }
 
+/// Delete system generations older than `days`, then GC (sudo). Other
+/// profiles (users, home-manager) are left alone: `nix-collect-garbage
+/// --delete-older-than` would prune them too.
+/// Uses `sudo -n`: runs from a background thread, so it can't prompt.
+pub fn run_delete_system_older_than(days: u32) -> Result<GcResult> {
+    let output = Command::new("sudo")
+        .args([
+            "-n",
+            "nix-env",
+            "-p",
+            SYSTEM_PROFILE,
+            "--delete-generations",
+            &format!("{}d", days),
+        ])
+        .output()
+        .context("Failed to run sudo nix-env --delete-generations")?;
+
+    let mut text = String::from_utf8_lossy(&output.stderr).to_string()
+        + &String::from_utf8_lossy(&output.stdout);
+
+    if !output.status.success() {
+        anyhow::bail!("{}", text.lines().last().unwrap_or("sudo failed").trim());
+    }
+
+    let output = Command::new("sudo")
+        .args(["-n", "nix-collect-garbage"])
+        .output()
+        .context("Failed to run sudo nix-collect-garbage")?;
+
+    text += &String::from_utf8_lossy(&output.stderr);
+    text += &String::from_utf8_lossy(&output.stdout);
+
+    if !output.status.success() {
+        anyhow::bail!("{}", text.lines().last().unwrap_or("sudo failed").trim());
+    }
+
+    let (paths_removed, bytes_freed) = parse_gc_output(&text);
+
+    Ok(GcResult {
+        paths_removed,
+        bytes_freed,
+        output: text,
+    })
+}
+
 /// Parse GC output for "N store paths deleted, X MiB freed"
 fn parse_gc_output(text: &str) -> (usize, u64) {
     let mut paths_removed = 0usize;
@@ -475,7 +526,7 @@ pub fn history_summary(entries: &[HistoryEntry]) -> (Option<String>, u64) {
 // ════════════════════════════════════════════════════════════════════
 
 /// Run a command with a timeout. Returns None on timeout or error.
-fn output_with_timeout(
+pub fn output_with_timeout(
     cmd: &str,
     args: &[&str],
     timeout_secs: u64,
@@ -487,13 +538,25 @@ fn output_with_timeout(
         .spawn()
         .ok()?;
 
+    // Read the pipes while waiting: output larger than the pipe buffer
+    // (a closure listing) would otherwise block the child until the timeout
+    let stdout = child.stdout.take().map(drain);
+    let stderr = child.stderr.take().map(drain);
+    let collect = |h: Option<std::thread::JoinHandle<Vec<u8>>>| {
+        h.and_then(|h| h.join().ok()).unwrap_or_default()
+    };
+
     let timeout = Duration::from_secs(timeout_secs);
     let start = std::time::Instant::now();
 
     loop {
         match child.try_wait() {
-            Ok(Some(_)) => {
-                return child.wait_with_output().ok();
+            Ok(Some(status)) => {
+                return Some(std::process::Output {
+                    status,
+                    stdout: collect(stdout),
+                    stderr: collect(stderr),
+                });
             }
             Ok(None) => {
                 if start.elapsed() > timeout {
@@ -507,3 +570,73 @@ fn output_with_timeout(
         }
     }
 }
+
+fn drain(mut pipe: impl Read + Send + 'static) -> std::thread::JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = pipe.read_to_end(&mut buf);
+        buf
+    })
+}
+
+/// GC roots as (link, store path). Roots of other users may show up as
+/// `{censored}` — they still keep their store path alive.
+pub fn gc_roots() -> Vec<(PathBuf, String)> {
+    let Some(out) = output_with_timeout("nix-store", &["--gc", "--print-roots"], 15) else {
+        return Vec::new();
+    };
+    String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (link, target) = line.split_once(" -> ")?;
+            let target = target.trim();
+            target
+                .starts_with("/nix/store/")
+                .then(|| (PathBuf::from(link.trim()), target.to_string()))
+        })
+        .collect()
+}
+
+/// Union of the closures of the given store paths
+pub fn closure<'a>(paths: impl IntoIterator<Item = &'a str>) -> HashSet<String> {
+    let paths: Vec<&str> = paths.into_iter().collect();
+    if paths.is_empty() {
+        return HashSet::new();
+    }
+    let mut args = vec!["-qR"];
+    args.extend(&paths);
+    output_with_timeout("nix-store", &args, 30)
+        .map(|out| {
+            String::from_utf8_lossy(&out.stdout)
+                .lines()
+                .map(|l| l.trim().to_string())
+                .filter(|l| l.starts_with("/nix/store/"))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// NAR size of each store path (not of its closure), via
+/// `nix-store -q --size`. Paths nix doesn't know are left out.
+pub fn nar_sizes(paths: &HashSet<String>) -> HashMap<String, u64> {
+    let paths: Vec<&str> = paths.iter().map(String::as_str).collect();
+    let mut sizes = HashMap::new();
+    // Batches keep the command line well below ARG_MAX
+    for batch in paths.chunks(1000) {
+        let mut args = vec!["-q", "--size"];
+        args.extend(batch);
+        let Some(out) = output_with_timeout("nix-store", &args, 30) else {
+            continue;
+        };
+        if !out.status.success() {
+            continue;
+        }
+        let text = String::from_utf8_lossy(&out.stdout);
+        for (path, line) in batch.iter().zip(text.lines()) {
+            if let Ok(size) = line.trim().parse() {
+                sizes.insert(path.to_string(), size);
+            }
+        }
+    }
+    sizes
+}
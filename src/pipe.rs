@@ -0,0 +1,224 @@
+//! Pipe mode: what kind of log was piped in, and which module shows it.
+//!
+//! `nixos-rebuild` runs open in the Rebuild log view, `nix flake update`
+//! output becomes Flake Inputs history, and everything else (`nix build`
+//! logs, journalctl dumps, bare errors) goes to the Error Translator.
+//! Detection looks at the writer's command line first, then at the text;
+//! `--as <kind>` overrides both.
+
+use crate::i18n::Strings;
+use anyhow::{bail, Result};
+
+/// Kind of piped text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PipeKind {
+    Rebuild,
+    Build,
+    FlakeUpdate,
+    Journal,
+    Error,
+}
+
+/// Names accepted by `--as`
+const NAMES: &[(&str, PipeKind)] = &[
+    ("rebuild", PipeKind::Rebuild),
+    ("build", PipeKind::Build),
+    ("flake", PipeKind::FlakeUpdate),
+    ("journal", PipeKind::Journal),
+    ("errors", PipeKind::Error),
+];
+
+impl PipeKind {
+    /// `--as <kind>` from the command line
+    pub fn from_args(args: &[String]) -> Result<Option<Self>> {
+        let Some(i) = args.iter().position(|a| a == "--as") else {
+            return Ok(None);
+        };
+        let Some(name) = args.get(i + 1) else {
+            bail!("--as needs a value");
+        };
+        match NAMES.iter().find(|(n, _)| n == name) {
+            Some((_, kind)) => Ok(Some(*kind)),
+            None => {
+                let known: Vec<&str> = NAMES.iter().map(|(n, _)| *n).collect();
+                bail!("unknown log kind `{}` (one of: {})", name, known.join(", "))
+            }
+        }
+    }
+
+    /// What the text was read as, for the flash message
+    pub fn label(&self, s: &Strings) -> &'static str {
+        match self {
+            PipeKind::Rebuild => s.pipe_kind_rebuild,
+            PipeKind::Build => s.pipe_kind_build,
+            PipeKind::FlakeUpdate => s.pipe_kind_flake,
+            PipeKind::Journal => s.pipe_kind_journal,
+            PipeKind::Error => s.pipe_kind_error,
+        }
+    }
+}
+
+/// Guess the kind from the writing command, then from the text itself
+pub fn detect(text: &str, source: Option<&str>) -> PipeKind {
+    if let Some(kind) = source.and_then(from_command) {
+        return kind;
+    }
+    if text.contains("• Updated input '") || text.contains("updating lock file '") {
+        return PipeKind::FlakeUpdate;
+    }
+    if text.contains("building the system configuration")
+        || text.contains("activating the configuration")
+        || text.contains("switching to system configuration")
+    {
+        return PipeKind::Rebuild;
+    }
+    let sample: Vec<&str> = text
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .take(20)
+        .collect();
+    let journal = sample.iter().filter(|l| is_journal_line(l)).count();
+    if !sample.is_empty() && journal * 2 >= sample.len() {
+        return PipeKind::Journal;
+    }
+    if text.contains("building '/nix/store/") || text.contains("derivations will be built") {
+        return PipeKind::Build;
+    }
+    PipeKind::Error
+}
+
+/// Kind from the command line of the process writing into the pipe
+fn from_command(cmd: &str) -> Option<PipeKind> {
+    let words: Vec<&str> = cmd
+        .split_whitespace()
+        .map(|w| w.rsplit('/').next().unwrap_or(w))
+        .collect();
+    let has = |w: &str| words.contains(&w);
+    if has("nixos-rebuild") || has("nixos-rebuild-ng") || (has("nh") && has("os")) {
+        Some(PipeKind::Rebuild)
+    } else if has("journalctl") {
+        Some(PipeKind::Journal)
+    } else if has("nix") && has("flake") && (has("update") || has("lock")) {
+        Some(PipeKind::FlakeUpdate)
+    } else if (has("nix") && has("build")) || has("nix-build") {
+        Some(PipeKind::Build)
+    } else {
+        None
+    }
+}
+
+/// "Mar 05 12:34:56 host unit[123]: …" or the short-iso variant
+fn is_journal_line(line: &str) -> bool {
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+    let mut words = line.split_whitespace();
+    let (Some(first), Some(second)) = (words.next(), words.next()) else {
+        return false;
+    };
+    let is_time = |w: &str| w.len() >= 8 && w.as_bytes()[2] == b':' && w.as_bytes()[5] == b':';
+    if MONTHS.contains(&first) {
+        return second.parse::<u8>().is_ok() && words.next().is_some_and(is_time);
+    }
+    // 2024-03-05T12:34:56+0100 host unit[123]: …
+    first.len() >= 19 && first.as_bytes()[4] == b'-' && first.as_bytes()[10] == b'T'
+}
+
+/// (input, old rev, new rev) per "• Updated input" block of
+/// `nix flake update` output; revisions shortened to 7 characters
+pub fn flake_updates(text: &str) -> Vec<(String, String, String)> {
+    let mut result = Vec::new();
+    let mut lines = text.lines().map(str::trim);
+    while let Some(line) = lines.next() {
+        let Some(name) = line
+            .strip_prefix("• Updated input '")
+            .and_then(|rest| rest.split('\'').next())
+        else {
+            continue;
+        };
+        let (Some(old), Some(new)) = (lines.next(), lines.next()) else {
+            break;
+        };
+        result.push((
+            name.to_string(),
+            locked_rev(old),
+            locked_rev(new.trim_start_matches('→').trim()),
+        ));
+    }
+    result
+}
+
+/// "'github:NixOS/nixpkgs/abc…?narHash=…' (2024-05-01)" → "abc1234";
+/// the date when the reference has no revision
+fn locked_rev(line: &str) -> String {
+    let mut parts = line.split('\'');
+    let url = parts.nth(1).unwrap_or(line);
+    let date = parts
+        .next()
+        .map(|d| d.trim().trim_matches(|c| c == '(' || c == ')'))
+        .unwrap_or_default();
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let rev = query
+        .split('&')
+        .find_map(|kv| kv.strip_prefix("rev="))
+        .or_else(|| {
+            path.rsplit('/')
+                .next()
+                .filter(|r| r.len() == 40 && r.chars().all(|c| c.is_ascii_hexdigit()))
+        });
+    match rev {
+        Some(rev) => rev.chars().take(7).collect(),
+        None => date.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_and_parse() {
+        let flake = "warning: updating lock file '/etc/nixos/flake.lock':\n\
+            • Updated input 'nixpkgs':\n    \
+            'github:NixOS/nixpkgs/0123456789abcdef0123456789abcdef01234567?narHash=sha256-x' (2024-05-01)\n  \
+            → 'github:NixOS/nixpkgs/fedcba9876543210fedcba9876543210fedcba98?narHash=sha256-y' (2024-05-08)\n\
+            • Updated input 'private':\n    \
+            'git+ssh://git@host/repo?ref=main&rev=aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa' (2024-04-01)\n  \
+            → 'git+ssh://git@host/repo?ref=main&rev=bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb' (2024-05-02)";
+        assert_eq!(detect(flake, None), PipeKind::FlakeUpdate);
+        assert_eq!(
+            flake_updates(flake),
+            vec![
+                ("nixpkgs".into(), "0123456".into(), "fedcba9".into()),
+                ("private".into(), "aaaaaaa".into(), "bbbbbbb".into()),
+            ]
+        );
+
+        let journal = "Mar 05 12:34:56 nixos nginx[812]: start\n\
+                       Mar 05 12:34:57 nixos systemd[1]: nginx.service: Failed";
+        assert_eq!(detect(journal, None), PipeKind::Journal);
+        assert_eq!(
+            detect("building the system configuration...", None),
+            PipeKind::Rebuild
+        );
+        assert_eq!(
+            detect(
+                "error: x",
+                Some("sudo /run/current-system/sw/bin/nixos-rebuild switch")
+            ),
+            PipeKind::Rebuild
+        );
+        assert_eq!(
+            detect("building '/nix/store/abc-foo.drv'...", None),
+            PipeKind::Build
+        );
+        assert_eq!(
+            detect("error: undefined variable 'x'", None),
+            PipeKind::Error
+        );
+
+        let args = vec!["--as".to_string(), "journal".to_string()];
+        assert_eq!(PipeKind::from_args(&args).unwrap(), Some(PipeKind::Journal));
+        assert!(PipeKind::from_args(&["--as".to_string(), "x".to_string()]).is_err());
+    }
+}
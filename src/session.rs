@@ -0,0 +1,106 @@
+//! Session state: where the user was when nixmate quit.
+//!
+//! With `restore_session = true` the active module, each module's sub-tab
+//! and the searches and filters that were set are written to
+//! `~/.local/state/nixmate/session.json` on quit and applied on the next
+//! launch. Piped input and dashboard mode pick their own module and
+//! neither read nor write the session.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Session {
+    /// Active module (`ModuleTab::index`)
+    pub tab: usize,
+    /// Per module, keyed by `ModuleTab::index`
+    #[serde(default)]
+    pub modules: BTreeMap<usize, ModuleSession>,
+}
+
+/// What a module had open; modules ignore the fields they don't use
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModuleSession {
+    #[serde(default)]
+    pub sub_tab: usize,
+    /// Selected row of the main list
+    #[serde(default)]
+    pub selected: usize,
+    /// Search or filter text
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub query: String,
+    /// Filter cycled with a key, as the number of steps from the first one
+    #[serde(default)]
+    pub filter: usize,
+}
+
+fn session_path() -> Option<PathBuf> {
+    dirs::state_dir()
+        .or_else(|| dirs::home_dir().map(|h| h.join(".local").join("state")))
+        .map(|p| p.join("nixmate").join("session.json"))
+}
+
+/// The last session, None if there is none or it doesn't parse
+pub fn load() -> Option<Session> {
+    let content = std::fs::read_to_string(session_path()?).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+pub fn save(session: &Session) -> Result<()> {
+    let path = session_path().context("No state directory")?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, serde_json::to_string_pretty(session)?)?;
+    Ok(())
+}
+
+/// Steps from `first` to `current` through a cycling filter's `next`
+pub fn cycle_index<T: Copy + PartialEq>(first: T, current: T, next: fn(&T) -> T) -> usize {
+    let mut value = first;
+    for steps in 0..32 {
+        if value == current {
+            return steps;
+        }
+        value = next(&value);
+    }
+    0
+}
+
+/// The filter `steps` calls of `next` after `first`
+pub fn cycle_nth<T: Copy>(first: T, steps: usize, next: fn(&T) -> T) -> T {
+    (0..steps).fold(first, |value, _| next(&value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_roundtrip_and_cycle() {
+        let mut session = Session {
+            tab: 6,
+            ..Default::default()
+        };
+        session.modules.insert(
+            6,
+            ModuleSession {
+                sub_tab: 1,
+                query: "services.nginx".into(),
+                ..Default::default()
+            },
+        );
+        let json = serde_json::to_string(&session).unwrap();
+        assert_eq!(serde_json::from_str::<Session>(&json).unwrap(), session);
+        // Older files without some fields still load
+        let partial: Session = serde_json::from_str(r#"{"tab":3,"modules":{"3":{}}}"#).unwrap();
+        assert_eq!(partial.modules[&3], ModuleSession::default());
+
+        let next = |n: &u8| (n + 1) % 4;
+        assert_eq!(cycle_index(0u8, 3, next), 3);
+        assert_eq!(cycle_nth(0u8, 3, next), 3);
+        assert_eq!(cycle_nth(0u8, 5, next), 1);
+    }
+}
@@ -0,0 +1,142 @@
+//! Staged deletions — confirmed, but not run until the undo window passes
+//!
+//! Deleting generations, collecting garbage and the other Storage cleanups
+//! (budget steps, suggestions, /boot leftovers) can't be taken back. So
+//! instead of running right after the confirmation, a module hands the
+//! action to `App` (`pending_staged`), which keeps it here for
+//! `UNDO_WINDOW` with a countdown in the flash line. `u` takes back the
+//! most recent one; once its window has passed, `App` gives the action
+//! back to its module to run. Quitting runs whatever is still waiting — it
+//! was confirmed, after all — and so does the terminal hanging up.
+
+use crate::modules::storage::boot::BootFile;
+use crate::modules::storage::budget::StepKind;
+use crate::modules::storage::suggest::SuggestionKind;
+use crate::nix::storage::CleanAction;
+use crate::types::ProfileType;
+use std::time::{Duration, Instant};
+
+pub const UNDO_WINDOW: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StagedKind {
+    DeleteGenerations {
+        profile: ProfileType,
+        ids: Vec<u32>,
+    },
+    Clean(CleanAction),
+    /// A step of the Storage budget plan
+    BudgetStep(StepKind),
+    /// The Storage suggestions selected for applying
+    Suggestions(Vec<SuggestionKind>),
+    /// Kernel and initrd files in /boot no entry references
+    BootOrphans(Vec<BootFile>),
+}
+
+pub struct Staged {
+    /// What will happen, e.g. "Delete 3 generations"
+    pub label: String,
+    pub kind: StagedKind,
+    staged_at: Instant,
+}
+
+impl Staged {
+    /// Time left to undo
+    pub fn remaining(&self) -> Duration {
+        UNDO_WINDOW.saturating_sub(self.staged_at.elapsed())
+    }
+}
+
+#[derive(Default)]
+pub struct StagedQueue {
+    /// Oldest first
+    items: Vec<Staged>,
+}
+
+impl StagedQueue {
+    pub fn push(&mut self, label: String, kind: StagedKind) {
+        self.items.push(Staged {
+            label,
+            kind,
+            staged_at: Instant::now(),
+        });
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// The most recently staged action, the one `u` takes back
+    pub fn latest(&self) -> Option<&Staged> {
+        self.items.last()
+    }
+
+    pub fn undo_latest(&mut self) -> Option<Staged> {
+        self.items.pop()
+    }
+
+    /// Remove and return the actions whose undo window has passed, oldest
+    /// first
+    pub fn take_due(&mut self) -> Vec<Staged> {
+        self.take_due_at(Instant::now())
+    }
+
+    fn take_due_at(&mut self, now: Instant) -> Vec<Staged> {
+        let (due, waiting) = std::mem::take(&mut self.items)
+            .into_iter()
+            .partition(|s| now.duration_since(s.staged_at) >= UNDO_WINDOW);
+        self.items = waiting;
+        due
+    }
+
+    /// Everything still waiting, oldest first (on quit)
+    pub fn take_all(&mut self) -> Vec<Staged> {
+        std::mem::take(&mut self.items)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_undo_and_due() {
+        let mut queue = StagedQueue::default();
+        let delete = StagedKind::DeleteGenerations {
+            profile: ProfileType::System,
+            ids: vec![41, 42],
+        };
+        queue.push("a".into(), delete.clone());
+        queue.push("b".into(), StagedKind::Clean(CleanAction::FullClean));
+        assert_eq!(queue.len(), 2);
+
+        // `u` takes back the newest
+        let undone = queue.undo_latest().unwrap();
+        assert_eq!(undone.kind, StagedKind::Clean(CleanAction::FullClean));
+
+        assert!(queue.take_due().is_empty());
+        let due = queue.take_due_at(Instant::now() + UNDO_WINDOW);
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].kind, delete);
+        assert_eq!(queue.len(), 0);
+    }
+
+    #[test]
+    fn test_budget_step_undo_within_window() {
+        let mut queue = StagedQueue::default();
+        let step = StagedKind::BudgetStep(StepKind::DeleteOlderThan {
+            days: 30,
+            generations: 4,
+        });
+        queue.push("Budget".into(), step.clone());
+        let staged_at = Instant::now();
+
+        // Still inside the window: nothing runs, `u` takes it back
+        assert!(queue.take_due_at(staged_at + UNDO_WINDOW / 2).is_empty());
+        assert!(queue
+            .latest()
+            .is_some_and(|s| s.remaining() > Duration::ZERO));
+        assert_eq!(queue.undo_latest().unwrap().kind, step);
+        assert!(queue.take_due_at(staged_at + UNDO_WINDOW * 2).is_empty());
+    }
+}
@@ -2,7 +2,8 @@
 //!
 //! Types used by the nix backend and the generations module.
 
-use chrono::{DateTime, Local};
+use crate::config::{Language, TimeFormat};
+use chrono::{DateTime, Local, NaiveDateTime};
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::time::Instant;
@@ -29,6 +30,39 @@ impl FlashMessage {
     }
 }
 
+/// An interactive program to run in the foreground while the TUI is
+/// suspended (e.g. `nixos-rebuild repl`). Modules queue one; the main loop
+/// leaves the alternate screen, runs it, and restores nixmate afterwards.
+#[derive(Debug, Clone)]
+pub struct ExternalCommand {
+    pub program: String,
+    pub args: Vec<String>,
+    /// Working directory (None = inherit)
+    pub cwd: Option<String>,
+    /// Printed above the program's output (e.g. how to get back)
+    pub notice: String,
+}
+
+impl ExternalCommand {
+    /// Command line as shown to the user
+    pub fn display(&self) -> String {
+        std::iter::once(self.program.as_str())
+            .chain(self.args.iter().map(|a| a.as_str()))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Error output piped into nixmate (`nixos-rebuild switch 2>&1 | nixmate`)
+#[derive(Debug, Clone)]
+pub struct PipedInput {
+    pub text: String,
+    /// Command line of the process writing into the pipe, if it could be found
+    pub source: Option<String>,
+    /// Kind given with `--as`, instead of detecting it
+    pub kind: Option<crate::pipe::PipeKind>,
+}
+
 /// Represents a NixOS or Home-Manager generation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Generation {
@@ -42,11 +76,35 @@ pub struct Generation {
     pub store_path: String,
     pub is_pinned: bool,
     pub in_bootloader: bool,
+    /// The running kernel was booted from it (/run/booted-system)
+    #[serde(default)]
+    pub is_booted: bool,
+    /// The bootloader starts it when no entry is picked
+    #[serde(default)]
+    pub is_boot_default: bool,
+}
+
+/// Why a generation must survive cleanups although it isn't current
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BootRole {
+    Booted,
+    BootDefault,
 }
 
 impl Generation {
-    pub fn formatted_date(&self) -> String {
-        self.date.format("%d.%m.%y %H:%M").to_string()
+    /// Booted wins: it's the fallback that is known to work
+    pub fn boot_role(&self) -> Option<BootRole> {
+        if self.is_booted {
+            Some(BootRole::Booted)
+        } else if self.is_boot_default {
+            Some(BootRole::BootDefault)
+        } else {
+            None
+        }
+    }
+
+    pub fn formatted_date(&self, format: TimeFormat, lang: Language) -> String {
+        format_time(&self.date, format, lang)
     }
 
     pub fn formatted_size(&self) -> String {
@@ -159,6 +217,52 @@ pub fn format_bytes(bytes: u64) -> String {
     }
 }
 
+/// Format a timestamp for the history views according to `time_format`
+pub fn format_time(dt: &DateTime<Local>, format: TimeFormat, lang: Language) -> String {
+    match format {
+        TimeFormat::Iso => dt.format("%Y-%m-%d %H:%M").to_string(),
+        TimeFormat::Locale => dt.format(locale_pattern(lang)).to_string(),
+        TimeFormat::Relative => {
+            let secs = (Local::now() - *dt).num_seconds();
+            relative_time(secs, lang).unwrap_or_else(|| {
+                // Older than a month: the date says more than "45 d ago"
+                let date = locale_pattern(lang).split(' ').next().unwrap_or("%Y-%m-%d");
+                dt.format(date).to_string()
+            })
+        }
+    }
+}
+
+/// Same as `format_time` for timestamps stored as text in the history
+/// files (`%Y-%m-%d %H:%M[:%S]`); anything else is shown unchanged
+pub fn format_stored_time(raw: &str, format: TimeFormat, lang: Language) -> String {
+    ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"]
+        .iter()
+        .find_map(|f| NaiveDateTime::parse_from_str(raw, f).ok())
+        .and_then(|naive| naive.and_local_timezone(Local).earliest())
+        .map_or_else(|| raw.to_string(), |dt| format_time(&dt, format, lang))
+}
+
+fn locale_pattern(lang: Language) -> &'static str {
+    match lang {
+        Language::German => "%d.%m.%Y %H:%M",
+        Language::English | Language::Locale(_) => "%d %b %Y %H:%M",
+    }
+}
+
+/// "2 h ago" for an age in seconds, None past 30 days
+fn relative_time(secs: i64, lang: Language) -> Option<String> {
+    let s = crate::i18n::get_strings(lang);
+    let secs = secs.max(0);
+    Some(match secs {
+        0..=59 => s.time_just_now.to_string(),
+        60..=3599 => s.time_min_ago.replace("{}", &(secs / 60).to_string()),
+        3600..=86_399 => s.time_h_ago.replace("{}", &(secs / 3600).to_string()),
+        _ if secs < 30 * 86_400 => s.time_d_ago.replace("{}", &(secs / 86_400).to_string()),
+        _ => return None,
+    })
+}
+
 fn is_security_package(name: &str) -> bool {
     let security_packages = [
         "openssl",
@@ -236,4 +340,23 @@ mod tests {
         assert_eq!(msg.text, "test");
         assert!(!msg.is_error);
     }
+
+    #[test]
+    fn test_time_formats() {
+        assert_eq!(relative_time(30, Language::English).unwrap(), "just now");
+        assert_eq!(relative_time(7200, Language::English).unwrap(), "2 h ago");
+        assert_eq!(
+            relative_time(3 * 86_400, Language::German).unwrap(),
+            "vor 3 T."
+        );
+        assert!(relative_time(40 * 86_400, Language::English).is_none());
+
+        let iso = |raw| format_stored_time(raw, TimeFormat::Iso, Language::English);
+        assert_eq!(iso("2026-10-17 14:05:09"), "2026-10-17 14:05");
+        assert_eq!(
+            format_stored_time("2026-10-17 14:05", TimeFormat::Locale, Language::German),
+            "17.10.2026 14:05"
+        );
+        assert_eq!(iso("not a date"), "not a date");
+    }
 }
@@ -0,0 +1,124 @@
+//! Responsive layout: terminal size thresholds, compact mode and the
+//! "terminal too small" screen
+//!
+//! Every `Event::Resize` goes to `App::handle_resize`, which stores the new
+//! `Viewport` and hands the compact flag to the modules. Below
+//! `compact_width` × `compact_height` (config) the tab strip replaces the
+//! sidebar in auto layout mode, dashboards stack into one column and tables
+//! drop their secondary columns. Below `MIN_WIDTH` × `MIN_HEIGHT` nothing is
+//! drawn but a note to make the terminal bigger.
+
+use crate::config::{Config, Language, LayoutMode};
+use crate::i18n;
+use crate::ui::Theme;
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Block, Paragraph, Wrap},
+    Frame,
+};
+
+/// Smallest terminal any view is drawn in
+pub const MIN_WIDTH: u16 = 50;
+pub const MIN_HEIGHT: u16 = 12;
+
+/// Rows taken by the status bar, sub-tab bars and borders around a
+/// module's scrollable body (roughly — views differ by a line or two)
+const CHROME_HEIGHT: u16 = 6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Viewport {
+    pub width: u16,
+    pub height: u16,
+    /// Below the configured compact thresholds
+    pub compact: bool,
+}
+
+impl Viewport {
+    pub fn new(width: u16, height: u16, config: &Config) -> Self {
+        Self {
+            width,
+            height,
+            compact: width < config.compact_width || height < config.compact_height,
+        }
+    }
+
+    /// Sidebar next to the content, or the one-line tab strip above it
+    pub fn sidebar(&self, mode: LayoutMode) -> bool {
+        match mode {
+            LayoutMode::Auto => !self.compact,
+            LayoutMode::SideBySide => true,
+            LayoutMode::TabsOnly => false,
+        }
+    }
+
+    /// About how many lines a module's scrollable body shows
+    pub fn body_height(&self) -> usize {
+        self.height.saturating_sub(CHROME_HEIGHT) as usize
+    }
+}
+
+pub fn too_small(area: Rect) -> bool {
+    area.width < MIN_WIDTH || area.height < MIN_HEIGHT
+}
+
+/// Shown instead of everything else while the terminal is below the minimum
+pub fn render_too_small(frame: &mut Frame, theme: &Theme, lang: Language, area: Rect) {
+    let s = i18n::get_strings(lang);
+    frame.render_widget(Block::default().style(theme.block_style()), area);
+
+    let size = s
+        .too_small_size
+        .replacen("{}", &format!("{}×{}", area.width, area.height), 1)
+        .replacen("{}", &format!("{}×{}", MIN_WIDTH, MIN_HEIGHT), 1);
+    let lines = vec![
+        Line::styled(
+            s.too_small_title,
+            Style::default()
+                .fg(theme.warning)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Line::raw(""),
+        Line::styled(size, theme.text()),
+        Line::styled(s.too_small_hint, theme.text_dim()),
+    ];
+    // Vertically centred as far as the lines allow
+    let top = area.height.saturating_sub(lines.len() as u16) / 2;
+    let body = Rect {
+        y: area.y + top,
+        height: area.height - top,
+        ..area
+    };
+    frame.render_widget(
+        Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true }),
+        body,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_viewport_thresholds() {
+        let config = Config {
+            compact_width: 100,
+            compact_height: 30,
+            ..Config::default()
+        };
+        let wide = Viewport::new(140, 40, &config);
+        assert!(!wide.compact && wide.sidebar(LayoutMode::Auto));
+        assert!(!wide.sidebar(LayoutMode::TabsOnly));
+
+        let short = Viewport::new(140, 24, &config);
+        assert!(short.compact && !short.sidebar(LayoutMode::Auto));
+        assert!(short.sidebar(LayoutMode::SideBySide));
+
+        assert!(!too_small(Rect::new(0, 0, 80, 24)));
+        assert!(too_small(Rect::new(0, 0, 40, 40)));
+        assert!(too_small(Rect::new(0, 0, 80, 10)));
+    }
+}
@@ -2,11 +2,14 @@
 //!
 //! Contains all UI-related code:
 //! - Theme definitions and colors (global for all modules)
-//! - Reusable widgets
+//! - Reusable widgets (popups, the shared sortable table)
 //! - Main render loop with module routing
+//! - Responsive layout (compact mode, terminal too small)
 //! - Tab bar, logo, status bar
 
+pub mod layout;
 pub mod render;
+pub mod table;
 pub mod theme;
 pub mod widgets;
 
@@ -1,7 +1,8 @@
 //! Main rendering module for nixmate
 //!
 //! Renders the complete UI:
-//! - Vertical sidebar with categories (left)
+//! - Vertical sidebar with categories (left), or a one-line tab strip
+//!   on top in tabs-only layout and on compact terminals
 //! - Active module content area (right)
 //! - Global status bar (bottom)
 //! - Popup overlays + flash messages
@@ -9,7 +10,9 @@
 use crate::app::{App, PopupState};
 use crate::config::Language;
 use crate::i18n;
-use crate::ui::widgets;
+use crate::modules::module::RenderCtx;
+use crate::nix::availability::{NixProblem, Platform};
+use crate::ui::{layout, widgets};
 use ratatui::{
     layout::{Alignment, Constraint, Layout, Rect},
     style::{Modifier, Style},
@@ -33,9 +36,65 @@ pub enum ModuleTab {
     Health,
     Settings,
     HelpAbout,
+    /// `[[external_modules]]` entry, by position
+    External(usize),
 }
 
+/// Keys of the external modules, in order
+const EXTERNAL_KEYS: &[&str] = &[
+    "F1", "F2", "F3", "F4", "F5", "F6", "F7", "F8", "F9", "F10", "F11", "F12",
+];
+
 impl ModuleTab {
+    pub fn all() -> &'static [ModuleTab] {
+        &[
+            ModuleTab::Generations,
+            ModuleTab::Errors,
+            ModuleTab::Services,
+            ModuleTab::Storage,
+            ModuleTab::Config,
+            ModuleTab::Options,
+            ModuleTab::Rebuild,
+            ModuleTab::FlakeInputs,
+            ModuleTab::Packages,
+            ModuleTab::Health,
+            ModuleTab::Settings,
+            ModuleTab::HelpAbout,
+        ]
+    }
+
+    /// Built-in module tabs (1-9, 0), without Settings, Help and the
+    /// external modules
+    pub fn is_module(&self) -> bool {
+        !matches!(
+            self,
+            ModuleTab::Settings | ModuleTab::HelpAbout | ModuleTab::External(_)
+        )
+    }
+
+    /// Modules that read or build a NixOS system, disabled elsewhere
+    pub fn nixos_only(&self) -> bool {
+        matches!(
+            self,
+            ModuleTab::Generations
+                | ModuleTab::Services
+                | ModuleTab::Config
+                | ModuleTab::Options
+                | ModuleTab::Rebuild
+                | ModuleTab::Health
+        )
+    }
+
+    /// Tab opened at startup: Generations, or Package Search where that
+    /// is disabled
+    pub fn home(platform: Platform) -> ModuleTab {
+        if platform.is_nixos() {
+            ModuleTab::Generations
+        } else {
+            ModuleTab::Packages
+        }
+    }
+
     pub fn index(&self) -> usize {
         match self {
             ModuleTab::Generations => 0,
@@ -50,11 +109,12 @@ impl ModuleTab {
             ModuleTab::Health => 9,
             ModuleTab::Settings => 10,
             ModuleTab::HelpAbout => 11,
+            ModuleTab::External(i) => 12 + i,
         }
     }
 
     /// Get the localized label for this tab
-    pub fn label(&self, app: &App) -> &'static str {
+    pub fn label<'a>(&self, app: &'a App) -> &'a str {
         let s = i18n::get_strings(app.config.language);
         match self {
             ModuleTab::Generations => s.tab_generations,
@@ -69,6 +129,7 @@ impl ModuleTab {
             ModuleTab::Health => s.tab_health,
             ModuleTab::Settings => s.tab_settings,
             ModuleTab::HelpAbout => s.tab_help,
+            ModuleTab::External(i) => app.external.get(*i).map_or("", |m| m.config.name.as_str()),
         }
     }
 
@@ -87,6 +148,7 @@ impl ModuleTab {
             ModuleTab::Health => "0",
             ModuleTab::Settings => ",",
             ModuleTab::HelpAbout => "?",
+            ModuleTab::External(i) => EXTERNAL_KEYS.get(*i).copied().unwrap_or(" "),
         }
     }
 }
@@ -115,6 +177,12 @@ pub fn render(frame: &mut Frame, app: &mut App) {
     // Reset image area each frame
     app.image_area = None;
 
+    // Below the minimum size every screen gives way to the same note
+    if layout::too_small(frame.area()) {
+        layout::render_too_small(frame, &app.theme, app.config.language, frame.area());
+        return;
+    }
+
     // Welcome screen takes over the entire screen (first run only)
     if app.welcome.active {
         app.image_area = crate::modules::splash::render_welcome(
@@ -126,31 +194,68 @@ pub fn render(frame: &mut Frame, app: &mut App) {
         return;
     }
 
+    // Start screen with the Doctor summary (later launches)
+    if app.start.active {
+        crate::modules::splash::render_start(frame, &app.health, &app.theme, app.config.language);
+        return;
+    }
+
     let area = frame.area();
     let theme = &app.theme;
 
     // Fill entire background
     frame.render_widget(Block::default().style(theme.block_style()), area);
 
-    // Main layout: sidebar | content, status bar at bottom
+    // Main layout: sidebar | content, status bar at bottom (vitals line
+    // above the key hints unless the terminal is very short)
+    let status_height = if area.height >= 20 { 2 } else { 1 };
     let vertical = Layout::vertical([
-        Constraint::Min(8),    // sidebar + content
-        Constraint::Length(1), // status bar
+        Constraint::Min(8),                // sidebar + content
+        Constraint::Length(status_height), // status bar
     ])
     .split(area);
 
-    let horizontal = Layout::horizontal([
-        Constraint::Length(SIDEBAR_WIDTH),
-        Constraint::Min(30), // content area
-    ])
-    .split(vertical[0]);
+    if app.viewport.sidebar(app.config.layout) {
+        let horizontal = Layout::horizontal([
+            Constraint::Length(SIDEBAR_WIDTH),
+            Constraint::Min(30), // content area
+        ])
+        .split(vertical[0]);
 
-    render_sidebar(frame, app, horizontal[0]);
-    render_module_content(frame, app, horizontal[1]);
+        render_sidebar(frame, app, horizontal[0]);
+        render_module_content(frame, app, horizontal[1]);
+    } else {
+        let stacked = Layout::vertical([
+            Constraint::Length(1), // tab strip
+            Constraint::Min(7),    // content area
+        ])
+        .split(vertical[0]);
+
+        render_tab_strip(frame, app, stacked[0]);
+        render_module_content(frame, app, stacked[1]);
+    }
     render_status_bar(frame, app, vertical[1]);
 
     // Popup overlays
     render_popups(frame, app, area);
+
+    if app.jobs.overlay {
+        crate::modules::jobs::render(
+            frame,
+            &app.jobs,
+            |tab| tab.label(app),
+            &app.theme,
+            app.config.language,
+            area,
+        );
+    }
+    if app.key_help {
+        let help = app.key_help_contents();
+        crate::modules::key_help::render(frame, &help, &app.theme, app.config.language, area);
+    }
+    if app.palette.active {
+        crate::modules::palette::render(frame, &app.palette, &app.theme, app.config.language, area);
+    }
 }
 
 /// Render the vertical sidebar
@@ -185,6 +290,10 @@ fn render_sidebar(frame: &mut Frame, app: &App, area: Rect) {
     for &module in SIDEBAR_MODULES {
         render_sidebar_item(&mut lines, app, module, theme);
     }
+    // External modules (F1-F12)
+    for module in app.external_tabs() {
+        render_sidebar_item(&mut lines, app, module, theme);
+    }
 
     // Separator
     lines.push(Line::raw(""));
@@ -202,6 +311,47 @@ fn render_sidebar(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(Paragraph::new(lines).style(theme.block_style()), area);
 }
 
+/// One-line replacement for the sidebar: the keys of all modules, with
+/// the label only for the active one
+fn render_tab_strip(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme;
+    let mut spans = vec![Span::raw(" ")];
+    let tabs = SIDEBAR_MODULES
+        .iter()
+        .copied()
+        .chain(app.external_tabs())
+        .chain(SIDEBAR_BOTTOM.iter().copied());
+    for module in tabs {
+        if app.active_tab == module {
+            spans.push(Span::styled(
+                format!(" {} {} ", module.key_hint(), module.label(app)),
+                theme
+                    .selected()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        } else {
+            let attention = app.module_badge(module).is_some()
+                || app
+                    .degraded_problem(module)
+                    .is_some_and(|p| p != NixProblem::NotNixOS);
+            let color = if attention {
+                theme.warning
+            } else {
+                theme.fg_dim
+            };
+            spans.push(Span::styled(
+                format!(" {} ", module.key_hint()),
+                Style::default().fg(color),
+            ));
+        }
+    }
+    frame.render_widget(
+        Paragraph::new(Line::from(spans)).style(theme.block_style()),
+        area,
+    );
+}
+
 /// Render a single sidebar item
 fn render_sidebar_item<'a>(
     lines: &mut Vec<Line<'a>>,
@@ -211,6 +361,22 @@ fn render_sidebar_item<'a>(
 ) {
     let is_active = app.active_tab == module;
     let hint = module.key_hint();
+    let problem = app.degraded_problem(module);
+    let disabled = problem == Some(NixProblem::NotNixOS);
+    let degraded = match problem {
+        Some(_) if disabled => Span::raw(""),
+        Some(_) => Span::styled(" ⚠", Style::default().fg(theme.warning)),
+        None => Span::raw(""),
+    };
+    let badge = match app.module_badge(module) {
+        Some(count) => Span::styled(
+            format!(" {}", count),
+            Style::default()
+                .fg(theme.warning)
+                .add_modifier(Modifier::BOLD),
+        ),
+        None => Span::raw(""),
+    };
 
     if is_active {
         lines.push(Line::from(vec![
@@ -222,6 +388,8 @@ fn render_sidebar_item<'a>(
                     .fg(theme.accent)
                     .add_modifier(Modifier::BOLD),
             ),
+            badge,
+            degraded,
         ]));
     } else {
         lines.push(Line::from(vec![
@@ -229,8 +397,10 @@ fn render_sidebar_item<'a>(
             Span::styled(hint.to_string(), Style::default().fg(theme.fg_dim)),
             Span::styled(
                 format!(" {}", module.label(app)),
-                Style::default().fg(theme.fg),
+                Style::default().fg(if disabled { theme.fg_dim } else { theme.fg }),
             ),
+            badge,
+            degraded,
         ]));
     }
 }
@@ -242,103 +412,139 @@ fn render_module_content(frame: &mut Frame, app: &mut App, area: Rect) {
         return;
     }
 
+    // Nix unavailable: explain instead of letting the module fail
+    if let Some(problem) = app.degraded_problem(app.active_tab) {
+        render_degraded(frame, app, problem, area);
+        return;
+    }
+
     match app.active_tab {
-        ModuleTab::Generations => {
-            crate::modules::generations::render(
-                frame,
-                &app.generations,
-                &app.theme,
-                app.config.language,
-                area,
-            );
-        }
-        ModuleTab::Errors => {
-            crate::modules::errors::render(
-                frame,
-                &app.errors,
-                &app.theme,
-                app.config.language,
-                area,
-                app.config.ai_available(),
-            );
-        }
-        ModuleTab::Services => {
-            crate::modules::services::render(
-                frame,
-                &mut app.services,
-                &app.theme,
-                app.config.language,
-                area,
-            );
-        }
-        ModuleTab::Storage => {
-            crate::modules::storage::render(
-                frame,
-                &mut app.storage,
-                &app.theme,
-                app.config.language,
-                area,
-            );
-        }
-        ModuleTab::Config => {
-            crate::modules::config_showcase::render(
-                frame,
-                &app.config_showcase,
-                &app.theme,
-                app.config.language,
-                area,
-            );
-        }
-        ModuleTab::Options => {
-            crate::modules::options::render(
-                frame,
-                &app.options,
-                &app.theme,
-                app.config.language,
-                area,
-            );
-        }
-        ModuleTab::Rebuild => {
-            crate::modules::rebuild::render(
-                frame,
-                &app.rebuild,
-                &app.theme,
-                app.config.language,
-                area,
-            );
-        }
-        ModuleTab::FlakeInputs => {
-            crate::modules::flake_inputs::render(
-                frame,
-                &app.flake_inputs,
-                &app.theme,
-                app.config.language,
-                area,
-            );
-        }
-        ModuleTab::Packages => {
-            crate::modules::packages::render(
-                frame,
-                &app.packages,
-                &app.theme,
-                app.config.language,
-                area,
-            );
-        }
-        ModuleTab::Health => {
-            crate::modules::health::render(
-                frame,
-                &app.health,
-                &app.theme,
-                app.config.language,
-                area,
-            );
-        }
         ModuleTab::Settings => render_settings(frame, app, area),
         ModuleTab::HelpAbout => render_help_about(frame, app, area),
+        tab => {
+            let theme = app.theme.clone();
+            let ctx = RenderCtx {
+                theme: &theme,
+                lang: app.config.language,
+                ai_available: app.config.ai_available(),
+            };
+            if let Some(module) = app.module_mut(tab) {
+                module.render(frame, &ctx, area);
+            }
+        }
+    }
+
+    if let Some(age) = app.stale_age() {
+        render_stale_badge(frame, app, age, area);
     }
 }
 
+/// "⏱ data 12m old" on the module's top border when data is past its TTL
+fn render_stale_badge(frame: &mut Frame, app: &App, age: std::time::Duration, area: Rect) {
+    let s = i18n::get_strings(app.config.language);
+    let secs = age.as_secs();
+    let age = match secs {
+        s if s < 60 => format!("{}s", s),
+        s if s < 3600 => format!("{}m", s / 60),
+        s if s < 86400 => format!("{}h", s / 3600),
+        s => format!("{}d", s / 86400),
+    };
+    let text = format!(" ⏱ {} ", s.stale_badge.replace("{}", &age));
+    let width = (text.chars().count() as u16).min(area.width.saturating_sub(2));
+    let badge_area = Rect {
+        x: area.x + area.width.saturating_sub(width + 1),
+        y: area.y,
+        width,
+        height: 1,
+    };
+    frame.render_widget(
+        Paragraph::new(Line::styled(
+            text,
+            Style::default()
+                .fg(app.theme.warning)
+                .add_modifier(Modifier::BOLD),
+        )),
+        badge_area,
+    );
+}
+
+/// Render the explanation shown when a module can't work without Nix, or
+/// without NixOS
+fn render_degraded(frame: &mut Frame, app: &App, problem: NixProblem, area: Rect) {
+    let theme = &app.theme;
+    let s = i18n::get_strings(app.config.language);
+
+    let block = Block::default()
+        .style(theme.block_style())
+        .title(format!(" ⚠ {} ", app.active_tab.label(app)))
+        .title_style(theme.title())
+        .borders(Borders::ALL)
+        .border_style(theme.border_focused());
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let (what, fix) = match problem {
+        NixProblem::NoStore => (s.nix_problem_no_store, s.nix_fix_no_store),
+        NixProblem::NoCli => (s.nix_problem_no_cli, s.nix_fix_no_cli),
+        NixProblem::DaemonDown => (s.nix_problem_daemon_down, s.nix_fix_daemon_down),
+        NixProblem::StoreReadOnly => (s.nix_problem_store_ro, s.nix_fix_store_ro),
+        NixProblem::NotNixOS => (s.nix_problem_not_nixos, s.nix_fix_not_nixos),
+    };
+    let not_nixos = problem == NixProblem::NotNixOS;
+    let (title, module_note, still_works) = if not_nixos {
+        (
+            s.nix_not_nixos_title,
+            s.nix_not_nixos_module,
+            s.nix_not_nixos_still_works,
+        )
+    } else {
+        (
+            s.nix_degraded_title,
+            s.nix_degraded_module,
+            s.nix_degraded_still_works,
+        )
+    };
+
+    let wrap_width = (inner.width as usize).saturating_sub(6).max(20);
+    let mut lines: Vec<Line> = vec![
+        Line::raw(""),
+        Line::styled(
+            format!("  {}", title),
+            Style::default()
+                .fg(theme.warning)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Line::styled(format!("  {}", module_note), theme.text_dim()),
+        Line::raw(""),
+    ];
+    for wrapped in word_wrap_intro(what, wrap_width) {
+        lines.push(Line::styled(format!("  {}", wrapped), theme.text()));
+    }
+    lines.push(Line::raw(""));
+    for (i, wrapped) in word_wrap_intro(fix, wrap_width).into_iter().enumerate() {
+        let prefix = if i == 0 { "  → " } else { "    " };
+        lines.push(Line::from(vec![
+            Span::styled(prefix, Style::default().fg(theme.accent)),
+            Span::styled(wrapped, theme.text()),
+        ]));
+    }
+    lines.push(Line::raw(""));
+    for wrapped in word_wrap_intro(still_works, wrap_width) {
+        lines.push(Line::styled(format!("  {}", wrapped), theme.text_dim()));
+    }
+    if !not_nixos {
+        lines.push(Line::raw(""));
+        lines.push(Line::styled(
+            format!("  {}", s.nix_degraded_retry),
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+
+    frame.render_widget(Paragraph::new(lines).style(theme.block_style()), inner);
+}
+
 /// Module intro content
 struct IntroContent {
     emoji: &'static str,
@@ -358,7 +564,7 @@ fn get_intro_content(tab: ModuleTab, lang: Language) -> Option<IntroContent> {
             features: &[
                 "All generations at a glance — size, date, kernel, package count",
                 "Side-by-side diff of added, removed, and updated packages",
-                "Delete, pin, and restore with 5-second undo safety net",
+                "Delete, pin, and restore — deletions wait 10 seconds for undo",
                 "Kernel update and security package highlighting",
             ],
             tabs: &[
@@ -375,7 +581,7 @@ fn get_intro_content(tab: ModuleTab, lang: Language) -> Option<IntroContent> {
             features: &[
                 "Alle Generationen auf einen Blick — Größe, Datum, Kernel, Paketanzahl",
                 "Seite-an-Seite Diff von hinzugefügten, entfernten und aktualisierten Paketen",
-                "Löschen, Pinnen und Wiederherstellen mit 5-Sekunden-Undo",
+                "Löschen, Pinnen und Wiederherstellen — Löschen wartet 10 Sekunden auf Rückgängig",
                 "Kernel-Update und Sicherheitspaket-Hervorhebung",
             ],
             tabs: &[
@@ -395,9 +601,11 @@ fn get_intro_content(tab: ModuleTab, lang: Language) -> Option<IntroContent> {
                 "Deep-dive explanations of why errors happen, not just what to type",
                 "AI fallback via Claude, OpenAI, or local Ollama for unknown errors",
                 "Pipe support: nixos-rebuild switch 2>&1 | nixmate",
+                "Jump to the offending file:line in $EDITOR with o",
             ],
             tabs: &[
                 "Translator  — paste an error, get a clear fix",
+                "History     — every analyzed error, kept across sessions",
                 "Submit      — submit new error patterns to help others",
             ],
         }),
@@ -410,9 +618,11 @@ fn get_intro_content(tab: ModuleTab, lang: Language) -> Option<IntroContent> {
                 "Tiefgehende Erklärungen warum Fehler auftreten, nicht nur was man tippen soll",
                 "KI-Fallback über Claude, OpenAI oder lokales Ollama",
                 "Pipe-Support: nixos-rebuild switch 2>&1 | nixmate",
+                "Mit o direkt zur fehlerhaften Datei:Zeile in $EDITOR springen",
             ],
             tabs: &[
                 "Übersetzer  — Fehler einfügen, klare Lösung erhalten",
+                "Verlauf     — jeder analysierte Fehler, auch nach Neustart",
                 "Einreichen  — neue Fehlermuster für andere einreichen",
             ],
         }),
@@ -424,12 +634,13 @@ fn get_intro_content(tab: ModuleTab, lang: Language) -> Option<IntroContent> {
             features: &[
                 "Systemd, Docker, and Podman containers in one unified view",
                 "Automatic port-to-service mapping via PID + process name",
+                "nginx/Caddy/Traefik domains mapped to upstream port and owner, dangling proxies flagged",
                 "Start, stop, restart, enable, and disable with sudo confirmation",
-                "Color-coded live logs from journalctl and container logs",
+                "Color-coded logs from journalctl and containers, with time range, grep and tail size",
             ],
             tabs: &[
                 "Overview  — all services and containers with status",
-                "Ports     — every listening TCP/UDP port mapped to its service",
+                "Ports     — every listening TCP/UDP port mapped to its service, plus proxy domains",
                 "Manage    — start, stop, restart, enable, disable",
                 "Logs      — live color-coded log viewer",
             ],
@@ -441,12 +652,13 @@ fn get_intro_content(tab: ModuleTab, lang: Language) -> Option<IntroContent> {
             features: &[
                 "Systemd, Docker und Podman in einer einheitlichen Ansicht",
                 "Automatische Port-zu-Dienst-Zuordnung via PID + Prozessname",
+                "nginx/Caddy/Traefik-Domains mit Upstream-Port und Besitzer, verwaiste Proxies markiert",
                 "Start, Stop, Neustart, Aktivieren und Deaktivieren mit Sudo-Bestätigung",
-                "Farbcodierte Live-Logs von journalctl und Container-Logs",
+                "Farbcodierte Logs von journalctl und Containern, mit Zeitraum, Grep und Zeilenzahl",
             ],
             tabs: &[
                 "Übersicht  — alle Dienste und Container mit Status",
-                "Ports      — alle TCP/UDP-Ports mit zugeordnetem Dienst",
+                "Ports      — alle TCP/UDP-Ports mit zugeordnetem Dienst, plus Proxy-Domains",
                 "Verwalten  — starten, stoppen, neustarten",
                 "Logs       — farbcodierter Live-Log-Viewer",
             ],
@@ -461,11 +673,13 @@ fn get_intro_content(tab: ModuleTab, lang: Language) -> Option<IntroContent> {
                 "Nix store breakdown: live/dead paths with sizes and percentages",
                 "Top 10 largest store paths with visual size bars",
                 "Garbage collect, store optimize, and full clean with dry-run preview",
+                "Budget mode: \"keep 50 GB free\" becomes an ordered, predicted cleanup plan",
             ],
             tabs: &[
                 "Dashboard  — disk usage overview and recommendations",
                 "Explorer   — browse all store paths sorted by size",
                 "Clean      — garbage collect, optimize, full clean",
+                "Budget     — step-by-step plan to reach a free-space target",
                 "History    — persistent log of all cleanup actions",
             ],
         }),
@@ -478,11 +692,13 @@ fn get_intro_content(tab: ModuleTab, lang: Language) -> Option<IntroContent> {
                 "Nix Store Aufschlüsselung: aktive/tote Pfade mit Größen",
                 "Top 10 größte Store-Pfade mit visuellen Balken",
                 "Garbage Collection, Store-Optimierung und Vollreinigung mit Vorschau",
+                "Budget-Modus: \"50 GB frei halten\" wird zu einem geordneten Aufräumplan",
             ],
             tabs: &[
                 "Dashboard  — Speicherplatz-Übersicht und Empfehlungen",
                 "Explorer   — Store-Pfade nach Größe durchsuchen",
                 "Aufräumen  — GC, Optimierung, Vollreinigung",
+                "Budget     — Schritt-für-Schritt-Plan für ein Speicherziel",
                 "Verlauf    — Protokoll aller Bereinigungen",
             ],
         }),
@@ -495,11 +711,13 @@ fn get_intro_content(tab: ModuleTab, lang: Language) -> Option<IntroContent> {
                 "Auto-detect your entire system: hardware, services, packages, network",
                 "Beautiful dark-themed SVG poster with gradient and glow effects",
                 "Config architecture diagram showing all .nix files and relationships",
+                "Service map from the evaluated config: enabled sets grouped by option root, linked by systemd dependencies",
                 "Designed for r/unixporn, GitHub READMEs, and flex posts",
             ],
             tabs: &[
-                "System Overview  — generate your system poster (SVG)",
+                "System Overview  — generate your system poster (SVG, or PNG via [e])",
                 "Config Diagram   — architecture visualization of your config",
+                "Services         — what your evaluated config enables, with dependencies",
             ],
         }),
         (ModuleTab::Config, Language::German) => Some(IntroContent {
@@ -510,11 +728,13 @@ fn get_intro_content(tab: ModuleTab, lang: Language) -> Option<IntroContent> {
                 "Automatische Erkennung: Hardware, Dienste, Pakete, Netzwerk",
                 "Schöner dunkler SVG-Poster mit Gradient- und Glow-Effekten",
                 "Config-Architekturdiagramm mit allen .nix-Dateien und Beziehungen",
+                "Dienstkarte aus der ausgewerteten Config: aktivierte Sets nach Options-Wurzel gruppiert, verbunden über systemd-Abhängigkeiten",
                 "Designed für r/unixporn, GitHub READMEs und Flex-Posts",
             ],
             tabs: &[
-                "Systemübersicht   — System-Poster generieren (SVG)",
+                "Systemübersicht   — System-Poster generieren (SVG, oder PNG via [e])",
                 "Config-Diagramm   — Architektur-Visualisierung der Config",
+                "Dienste           — was deine ausgewertete Config aktiviert, mit Abhängigkeiten",
             ],
         }),
         // ── Options Explorer ──
@@ -527,11 +747,14 @@ fn get_intro_content(tab: ModuleTab, lang: Language) -> Option<IntroContent> {
                 "Tree-based browsing: discover what's available without guessing names",
                 "Your current values highlighted vs. defaults — no other tool does this",
                 "Related sibling options: see everything you can configure for a service",
+                "My Overrides: every option your config sets, with value and source file",
             ],
             tabs: &[
                 "Search   — fuzzy search with detail view and current values",
                 "Browse   — tree navigation through the option hierarchy",
                 "Related  — sibling options for the selected option",
+                "My Overrides — all options your configuration changes",
+                "Changed  — options that changed between your last two nixpkgs revisions",
             ],
         }),
         (ModuleTab::Options, Language::German) => Some(IntroContent {
@@ -543,11 +766,14 @@ fn get_intro_content(tab: ModuleTab, lang: Language) -> Option<IntroContent> {
                 "Baumbasiertes Durchstöbern: entdecken was möglich ist, ohne Namen zu raten",
                 "Deine aktuellen Werte hervorgehoben vs. Standards — kein anderes Tool kann das",
                 "Verwandte Optionen: alles sehen was du für einen Service konfigurieren kannst",
+                "Meine Änderungen: jede Option die deine Config setzt, mit Wert und Quelldatei",
             ],
             tabs: &[
                 "Suche        — Fuzzy-Suche mit Detailansicht und aktuellen Werten",
                 "Durchsuchen  — Baumnavigation durch die Options-Hierarchie",
                 "Verwandte    — Schwester-Optionen der ausgewählten Option",
+                "Meine Änderungen — alle Optionen, die deine Konfiguration ändert",
+                "Geändert     — Optionen, die sich zwischen deinen letzten zwei nixpkgs-Revisionen geändert haben",
             ],
         }),
         // ── Rebuild ──
@@ -557,13 +783,15 @@ fn get_intro_content(tab: ModuleTab, lang: Language) -> Option<IntroContent> {
             problem: "nixos-rebuild is a black box — you run it and wait, with no idea what's happening, how long it will take, or what changed when it's done.",
             features: &[
                 "5-phase progress dashboard with real-time status and per-phase timers",
-                "Educational explanations: learn what NixOS does at each build step",
+                "Educational explanations: learn what NixOS does at each build step — and, with [x] in the Log, at each line",
                 "Intelligent log beautification: store paths become human-readable names",
                 "Post-rebuild diff: packages added/removed, kernel changes, service restarts",
+                "Eval console: jump into nixos-rebuild repl with your configuration loaded",
+                "Closing mid-build? Detach the rebuild into a systemd unit and reattach later",
             ],
             tabs: &[
                 "Dashboard  — 5-phase progress with live status",
-                "Build Log  — full searchable build output",
+                "Build Log  — full searchable build output, [x] explains lines",
                 "Changes    — post-rebuild package and service diff",
                 "History    — persistent log of all rebuilds",
             ],
@@ -574,13 +802,15 @@ fn get_intro_content(tab: ModuleTab, lang: Language) -> Option<IntroContent> {
             problem: "nixos-rebuild ist eine Blackbox — du startest es und wartest, ohne zu wissen was passiert, wie lange es dauert, oder was sich geändert hat.",
             features: &[
                 "5-Phasen-Fortschrittsdashboard mit Echtzeit-Status und Phasen-Timern",
-                "Lehrreiche Erklärungen: lerne was NixOS bei jedem Build-Schritt macht",
+                "Lehrreiche Erklärungen: lerne was NixOS bei jedem Build-Schritt macht — und mit [x] im Log bei jeder Zeile",
                 "Intelligente Log-Verschönerung: Store-Pfade werden zu lesbaren Namen",
                 "Nachher-Diff: Pakete hinzugefügt/entfernt, Kernel-Änderungen, Dienst-Neustarts",
+                "Eval-Konsole: direkt in nixos-rebuild repl mit geladener Konfiguration",
+                "Beenden mitten im Build? Rebuild in eine systemd-Unit abkoppeln und später wieder anhängen",
             ],
             tabs: &[
                 "Dashboard   — 5-Phasen-Fortschritt mit Live-Status",
-                "Build-Log   — vollständige durchsuchbare Build-Ausgabe",
+                "Build-Log   — vollständige durchsuchbare Build-Ausgabe, [x] erklärt Zeilen",
                 "Änderungen  — Paket- und Dienst-Diff nach Rebuild",
                 "Verlauf     — Protokoll aller Rebuilds",
             ],
@@ -594,13 +824,19 @@ fn get_intro_content(tab: ModuleTab, lang: Language) -> Option<IntroContent> {
                 "All inputs at a glance: name, URL, revision, age with color coding",
                 "Selective per-input updates with checkboxes — update only what you want",
                 "Confirmation popup before any update, live progress during update",
+                "Commit flake.lock afterwards: git status and diff first, message from config",
                 "Full detail view: type, branch, revision, NAR hash, follows relationships",
+                "Update preview for GitHub inputs: commits behind upstream and latest commit",
+                "Health badges for dead inputs: archived or moved repos, deleted branches, force-pushed tags",
+                "Edit flake.nix in place: change an input's URL or branch, add or remove inputs",
+                "Watch mode: inputs past their age threshold (e.g. nixpkgs > 14 days) counted in the sidebar",
             ],
             tabs: &[
-                "Overview  — all inputs with revision, URL, and age",
+                "Overview  — all inputs with revision, URL, and age; e/b/a/d edit",
                 "Update    — select inputs with Space, update with Enter",
-                "History   — diff of old → new revisions from last update",
+                "History   — old → new revisions, roll back an input with u",
                 "Details   — full info for the selected input",
+                "Graph     — full lock graph with transitive inputs and follows",
             ],
         }),
         (ModuleTab::FlakeInputs, Language::German) => Some(IntroContent {
@@ -611,13 +847,19 @@ fn get_intro_content(tab: ModuleTab, lang: Language) -> Option<IntroContent> {
                 "Alle Inputs auf einen Blick: Name, URL, Revision, Alter mit Farbcodierung",
                 "Selektive Einzel-Input-Updates mit Checkboxen — nur aktualisieren was du willst",
                 "Bestätigungs-Popup vor jedem Update, Live-Fortschritt während des Updates",
+                "flake.lock danach committen: erst git status und Diff, Nachricht aus der Config",
                 "Vollständige Detailansicht: Typ, Branch, Revision, NAR-Hash, Follows-Beziehungen",
+                "Update-Vorschau für GitHub-Inputs: Commits hinter Upstream und neuester Commit",
+                "Zustands-Badges für tote Inputs: archivierte oder umgezogene Repos, gelöschte Branches, force-gepushte Tags",
+                "flake.nix direkt bearbeiten: URL oder Branch eines Inputs ändern, Inputs hinzufügen oder entfernen",
+                "Beobachtung: Inputs über ihrer Altersschwelle (z.B. nixpkgs > 14 Tage) in der Seitenleiste gezählt",
             ],
             tabs: &[
-                "Übersicht      — alle Inputs mit Revision, URL und Alter",
+                "Übersicht      — alle Inputs mit Revision, URL und Alter; e/b/a/d bearbeiten",
                 "Aktualisieren  — Inputs mit Leertaste auswählen, mit Enter updaten",
-                "Verlauf        — Diff von alter → neuer Revision",
+                "Verlauf        — alte → neue Revision, Input mit u zurücksetzen",
                 "Details        — vollständige Info zum ausgewählten Input",
+                "Graph          — kompletter Lock-Graph mit transitiven Inputs und Follows",
             ],
         }),
         // ── Package Search ──
@@ -630,9 +872,11 @@ fn get_intro_content(tab: ModuleTab, lang: Language) -> Option<IntroContent> {
                 "Auto-detect whether you use Flakes or Channels (configurable in Settings)",
                 "Installed packages marked with ✓ and sorted to the top",
                 "Detail view with full description, version, and install command",
+                "Insecure / EOL audit of your system — reasons, permittedInsecurePackages and upgrade path",
             ],
             tabs: &[
                 "Press / to start searching — type your query and hit Enter",
+                "Press v to check the installed system against nixpkgs' knownVulnerabilities",
             ],
         }),
         (ModuleTab::Packages, Language::German) => Some(IntroContent {
@@ -644,9 +888,11 @@ fn get_intro_content(tab: ModuleTab, lang: Language) -> Option<IntroContent> {
                 "Automatische Erkennung von Flakes vs. Channels (konfigurierbar)",
                 "Installierte Pakete mit ✓ markiert und nach oben sortiert",
                 "Detailansicht mit Beschreibung, Version und Installationsbefehl",
+                "Unsicher/EOL-Prüfung des Systems — Gründe, permittedInsecurePackages und Upgrade-Pfad",
             ],
             tabs: &[
                 "/ drücken zum Suchen — Suchbegriff eingeben und Enter drücken",
+                "v drücken, um das installierte System gegen knownVulnerabilities aus nixpkgs zu prüfen",
             ],
         }),
         // ── Nix Doctor / Health ──
@@ -656,13 +902,14 @@ fn get_intro_content(tab: ModuleTab, lang: Language) -> Option<IntroContent> {
             problem: "Is your NixOS healthy? Are old generations piling up? Is the store bloated? When did you last update? There's no quick way to check overall system health.",
             features: &[
                 "Health score 0-100% with color coding (green/orange/red)",
-                "5 automated checks: old generations, store size, disk, updates, duplicates",
-                "One-click fixes: garbage collect, channel update, and more",
+                "14 checks in parallel: old generations, store size, disk, store optimisation, updates, flake input age, duplicates, cache signatures, store integrity, accounts, /boot space, booted generation, initrd secrets, stale boot entries",
+                "One-key fixes where safe (garbage collect, store repair, update), config snippets where not",
                 "Auto-scan on entry, rescan anytime with r",
             ],
             tabs: &[
                 "Dashboard  — health score and check overview",
-                "Fix        — select an issue and apply the fix",
+                "Fix        — why each check matters, apply the fix or copy its snippet",
+                "Accounts   — imperative users, groups and SSH keys with config snippets",
             ],
         }),
         (ModuleTab::Health, Language::German) => Some(IntroContent {
@@ -671,13 +918,14 @@ fn get_intro_content(tab: ModuleTab, lang: Language) -> Option<IntroContent> {
             problem: "Ist dein NixOS gesund? Stapeln sich alte Generationen? Ist der Store aufgebläht? Wann war das letzte Update? Kein schneller Weg um den Systemzustand zu prüfen.",
             features: &[
                 "Gesundheitsscore 0-100% mit Farbcodierung (grün/orange/rot)",
-                "5 automatische Checks: alte Generationen, Store, Speicher, Updates, Duplikate",
-                "Ein-Klick-Reparaturen: Garbage Collection, Channel-Update und mehr",
+                "14 parallele Checks: alte Generationen, Store, Speicher, Store-Optimierung, Updates, Flake-Input-Alter, Duplikate, Cache-Signaturen, Store-Integrität, Konten, /boot-Platz, gebootete Generation, initrd-Secrets, veraltete Boot-Einträge",
+                "Ein-Tasten-Reparaturen wo sicher (Garbage Collection, Store-Reparatur, Update), sonst Config-Snippets",
                 "Auto-Scan bei Aufruf, erneuter Scan jederzeit mit r",
             ],
             tabs: &[
                 "Dashboard   — Gesundheitsscore und Check-Übersicht",
-                "Reparieren  — Problem auswählen und Fix anwenden",
+                "Reparieren  — warum ein Check zählt, Fix anwenden oder Snippet übernehmen",
+                "Konten      — imperative Benutzer, Gruppen und SSH-Keys mit Config-Snippets",
             ],
         }),
         _ => None,
@@ -690,7 +938,7 @@ fn render_module_intro(frame: &mut Frame, app: &App, area: Rect) {
     let lang = app.config.language;
     let tab = app.active_tab;
 
-    let intro = match get_intro_content(tab, lang) {
+    let intro = match get_intro_content(tab, lang.builtin()) {
         Some(c) => c,
         None => return,
     };
@@ -726,7 +974,7 @@ fn render_module_intro(frame: &mut Frame, app: &App, area: Rect) {
     // ── The Problem ──
     lines.push(Line::raw(""));
     let problem_label = match lang {
-        Language::English => "── The Problem ──",
+        Language::English | Language::Locale(_) => "── The Problem ──",
         Language::German => "── Das Problem ──",
     };
     lines.push(Line::styled(
@@ -743,7 +991,7 @@ fn render_module_intro(frame: &mut Frame, app: &App, area: Rect) {
     // ── Features ──
     lines.push(Line::raw(""));
     let features_label = match lang {
-        Language::English => "── Features ──",
+        Language::English | Language::Locale(_) => "── Features ──",
         Language::German => "── Funktionen ──",
     };
     lines.push(Line::styled(
@@ -763,7 +1011,7 @@ fn render_module_intro(frame: &mut Frame, app: &App, area: Rect) {
     // ── Tabs ──
     lines.push(Line::raw(""));
     let tabs_label = match lang {
-        Language::English => "── Tabs ──",
+        Language::English | Language::Locale(_) => "── Tabs ──",
         Language::German => "── Tabs ──",
     };
     lines.push(Line::styled(
@@ -784,7 +1032,7 @@ fn render_module_intro(frame: &mut Frame, app: &App, area: Rect) {
     lines.push(Line::raw(""));
     lines.push(Line::raw(""));
     let continue_text = match lang {
-        Language::English => "─── Press Enter to start →",
+        Language::English | Language::Locale(_) => "─── Press Enter to start →",
         Language::German => "─── Enter drücken zum Starten →",
     };
     lines.push(Line::styled(
@@ -934,6 +1182,17 @@ fn render_help_about(frame: &mut Frame, app: &mut App, area: Rect) {
 }
 
 /// Render the global Settings tab
+fn data_source_label(s: &i18n::Strings, source: crate::config::DataSource) -> &'static str {
+    use crate::config::DataSource;
+    match source {
+        DataSource::Services => s.settings_src_services,
+        DataSource::Ports => s.settings_src_ports,
+        DataSource::FlakeInputs => s.settings_src_flake_inputs,
+        DataSource::Options => s.settings_src_options,
+        DataSource::Storage => s.settings_src_storage,
+    }
+}
+
 fn render_settings(frame: &mut Frame, app: &App, area: Rect) {
     let theme = &app.theme;
     let s = i18n::get_strings(app.config.language);
@@ -950,10 +1209,22 @@ fn render_settings(frame: &mut Frame, app: &App, area: Rect) {
 
     // Global settings
     let global_settings: Vec<(&str, String)> = vec![
-        (s.settings_theme, app.config.theme.as_str().to_string()),
+        (
+            s.settings_theme,
+            match &app.config.custom_theme {
+                Some(name) => format!("{} ({})", name, s.settings_theme_custom),
+                None => app.config.theme.as_str().to_string(),
+            },
+        ),
         (
             s.settings_language,
-            app.config.language.as_str().to_string(),
+            match app.config.language {
+                Language::Locale(i) => match crate::i18n::locales().get(i as usize) {
+                    Some(l) => format!("{} ({}%)", l.name, l.coverage()),
+                    None => app.config.language.as_str().to_string(),
+                },
+                _ => app.config.language.as_str().to_string(),
+            },
         ),
         (
             s.settings_layout,
@@ -1113,17 +1384,80 @@ fn render_settings(frame: &mut Frame, app: &App, area: Rect) {
         ])));
     }
 
+    // Data & refresh section
+    items.push(ListItem::new(Line::styled(
+        format!("  ── {} ──", s.settings_refresh_section),
+        theme.text_dim(),
+    )));
+    for (i, &source) in crate::config::DataSource::all().iter().enumerate() {
+        let global_idx = i + 11;
+        let editing = app.settings_editing && app.settings_selected == global_idx;
+        let style = if global_idx == app.settings_selected {
+            theme.selected()
+        } else {
+            theme.text()
+        };
+        let value = if editing {
+            format!("{}_", app.settings_edit_buffer)
+        } else {
+            let timing = app.config.refresh.get(source);
+            let fmt = |secs: u64| {
+                if secs == 0 {
+                    s.settings_off.to_string()
+                } else {
+                    crate::config::format_secs(secs)
+                }
+            };
+            s.settings_refresh_value
+                .replacen("{}", &fmt(timing.ttl_secs), 1)
+                .replacen("{}", &fmt(timing.refresh_secs), 1)
+        };
+        let value_style = if editing {
+            Style::default()
+                .fg(theme.success)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.accent)
+        };
+        items.push(ListItem::new(Line::from(vec![
+            Span::styled(format!("  {:<24}", data_source_label(s, source)), style),
+            Span::styled(format!("[{}]", value), value_style),
+        ])));
+    }
+
     // Editing hint
     if app.settings_editing {
+        let hint = if app.settings_selected >= 11 {
+            s.settings_refresh_hint
+        } else {
+            s.settings_editing_hint
+        };
         items.push(ListItem::new(Line::raw("")));
         items.push(ListItem::new(Line::styled(
-            format!("  💡 {}", s.settings_editing_hint),
+            format!("  💡 {}", hint),
             theme.text_dim(),
         )));
     }
 
+    // Keybindings next to the list when there's room
+    let body = Rect {
+        height: inner.height.saturating_sub(2),
+        ..inner
+    };
+    let list_area = if body.width >= 100 {
+        let cols = Layout::horizontal([Constraint::Min(56), Constraint::Length(44)]).split(body);
+        if app.settings_selected == 0 {
+            render_theme_preview(frame, app, cols[1]);
+        } else {
+            render_keybindings(frame, app, cols[1]);
+        }
+        cols[0]
+    } else {
+        inner
+    };
+
     let list = List::new(items);
-    frame.render_widget(list, inner);
+    frame.render_widget(list, list_area);
 
     // Config path at bottom
     let config_path = crate::config::Config::path()
@@ -1141,18 +1475,139 @@ fn render_settings(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(path_widget, path_area);
 }
 
-/// Render status bar with context-sensitive keybindings
-fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
+/// Colors of the active theme and how the common styles look with them,
+/// shown while the Theme row is selected
+fn render_theme_preview(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme;
+    let s = i18n::get_strings(app.config.language);
+
+    let mut lines = vec![Line::styled(
+        format!("── {} ──", s.settings_theme_preview),
+        theme.text_dim(),
+    )];
+    for (name, color) in theme.colors() {
+        lines.push(Line::from(vec![
+            Span::styled("████ ", Style::default().fg(color)),
+            Span::styled(format!("{:<16}", name), theme.text()),
+            Span::styled(
+                crate::ui::theme::to_hex(color).unwrap_or_else(|| "-".into()),
+                theme.text_dim(),
+            ),
+        ]));
+    }
+    lines.push(Line::raw(""));
+    lines.push(Line::from(vec![
+        Span::styled(s.settings_theme_sample, theme.text()),
+        Span::raw(" "),
+        Span::styled(s.settings_theme_sample, theme.text_dim()),
+        Span::raw(" "),
+        Span::styled(s.settings_theme_sample, theme.title()),
+    ]));
+    lines.push(Line::styled(
+        format!(" {} ", s.settings_theme_sample),
+        theme.selected(),
+    ));
+    lines.push(Line::from(vec![
+        Span::styled("✓ ok  ", theme.success()),
+        Span::styled("⚠ warn  ", theme.warning()),
+        Span::styled("✗ error  ", theme.error()),
+        Span::styled("+ ", theme.diff_added()),
+        Span::styled("- ", theme.diff_removed()),
+        Span::styled("~", theme.diff_updated()),
+    ]));
+    lines.push(Line::raw(""));
+    let dir = crate::ui::theme::themes_dir()
+        .map(|d| d.display().to_string())
+        .unwrap_or_default();
+    lines.push(Line::styled(
+        s.settings_theme_files.replace("{}", &dir),
+        theme.text_dim(),
+    ));
+
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), area);
+}
+
+/// Current bindings per action, customized ones highlighted, problems
+/// with the `[keys]` table below
+fn render_keybindings(frame: &mut Frame, app: &App, area: Rect) {
+    use crate::keymap::KeymapIssue;
     let theme = &app.theme;
     let s = i18n::get_strings(app.config.language);
 
-    let hints = match app.active_tab {
+    let mut lines = vec![Line::styled(
+        format!("── {} ──", s.settings_keys_section),
+        theme.text_dim(),
+    )];
+    let conflicted = |name: &str| {
+        app.keymap.issues.iter().any(|i| {
+            matches!(i, KeymapIssue::Conflict { actions, .. } if actions.iter().any(|a| a == name))
+        })
+    };
+    for b in &app.keymap.bindings {
+        let key_style = if conflicted(b.action.name) {
+            Style::default().fg(theme.error)
+        } else if b.custom {
+            Style::default()
+                .fg(theme.success)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.accent)
+        };
+        let mut spans = vec![
+            Span::styled(format!("{:<14}", b.action.name), theme.text()),
+            Span::styled(b.keys.join(", "), key_style),
+        ];
+        if b.custom {
+            spans.push(Span::styled(
+                format!("  ({})", s.settings_keys_custom),
+                theme.text_dim(),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    if !app.keymap.issues.is_empty() {
+        lines.push(Line::raw(""));
+    }
+    for issue in &app.keymap.issues {
+        let text = match issue {
+            KeymapIssue::UnknownAction(name) => s.settings_keys_unknown.replace("{}", name),
+            KeymapIssue::BadKey { action, key } => s
+                .settings_keys_bad_key
+                .replacen("{}", key, 1)
+                .replacen("{}", action, 1),
+            KeymapIssue::Conflict { key, actions } => s
+                .settings_keys_conflict
+                .replacen("{}", key, 1)
+                .replacen("{}", &actions.join(", "), 1),
+        };
+        lines.push(Line::styled(
+            format!("⚠ {}", text),
+            Style::default().fg(theme.warning),
+        ));
+    }
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(s.settings_keys_hint, theme.text_dim()));
+
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), area);
+}
+
+/// Keybindings of the active module for the status bar
+fn module_hints(app: &App, s: &i18n::Strings) -> String {
+    match app.active_tab {
+        tab if !app.is_intro_showing() && app.degraded_problem(tab).is_some() => {
+            if app.degraded_problem(tab) == Some(NixProblem::NotNixOS) {
+                format!("{}  {}", s.status_switch_tab, s.status_quit)
+            } else {
+                format!("[r] Retry  {}  {}", s.status_switch_tab, s.status_quit)
+            }
+        }
         ModuleTab::Generations => {
             let gen_state = &app.generations;
             match gen_state.active_sub_tab {
                 crate::modules::generations::GenSubTab::Overview => {
                     format!(
-                        "[j/k] {}  [Tab] Panel  [Enter] Pkgs  [/] Sub-Tab  {}",
+                        "[j/k] {}  [Tab] Panel  [Enter] Pkgs  [</>] Sort  [/] Sub-Tab  {}",
                         s.navigate, s.status_quit
                     )
                 }
@@ -1188,6 +1643,11 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
         }
         ModuleTab::Errors => {
             let err_state = &app.errors;
+            let open_hint = err_state
+                .location
+                .as_ref()
+                .map(|loc| format!("[o] {} {}  ", s.err_open_in_editor, loc.short()))
+                .unwrap_or_default();
             match err_state.active_sub_tab {
                 crate::modules::errors::ErrSubTab::Analyze => {
                     if err_state.input_mode {
@@ -1202,26 +1662,42 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
                         )
                     } else if err_state.ai_result.is_some() {
                         format!(
-                            "[j/k] Scroll  [n] {}  {}",
-                            s.err_new_analysis, s.status_quit
+                            "[j/k] Scroll  [n] {}  {}{}",
+                            s.err_new_analysis, open_hint, s.status_quit
                         )
                     } else if err_state.result.is_some() {
+                        // Only advertised once opted in via [ai_explain]
+                        let explain_hint = if app.config.ai_explain.enabled {
+                            format!("[e] 🤖 {}  ", s.err_explain)
+                        } else {
+                            String::new()
+                        };
                         format!(
-                            "[j/k] Scroll  [n] {}  [s] Submit  [/] Sub-Tab  {}",
-                            s.err_new_analysis, s.status_quit
+                            "[j/k] Scroll  [n] {}  [s] Submit  {}{}[/] Sub-Tab  {}",
+                            s.err_new_analysis, explain_hint, open_hint, s.status_quit
                         )
                     } else if !err_state.input_buffer.is_empty() && app.config.ai_available() {
                         format!(
-                            "[a] 🤖 {}  [n] {}  [s] {}  {}",
-                            s.err_ai_ask, s.err_new_analysis, s.err_submit_pattern, s.status_quit
+                            "[a] 🤖 {}  [n] {}  [s] {}  {}{}",
+                            s.err_ai_ask,
+                            s.err_new_analysis,
+                            s.err_submit_pattern,
+                            open_hint,
+                            s.status_quit
                         )
                     } else {
                         format!(
-                            "[i] {}  [s] {}  [/] Sub-Tab  {}",
+                            "[i] {}  [c] Clipboard  [f] File  [s] {}  [/] Sub-Tab  {}",
                             s.err_start_input, s.err_submit_pattern, s.status_quit
                         )
                     }
                 }
+                crate::modules::errors::ErrSubTab::History => {
+                    format!(
+                        "[j/k] {}  [Enter] {}  [d] {}  [/] Sub-Tab  {}",
+                        s.navigate, s.err_history_open, s.err_history_delete, s.status_quit
+                    )
+                }
                 crate::modules::errors::ErrSubTab::Submit => {
                     format!(
                         "[Tab] Next  [Enter] Submit  [Esc] {}  {}",
@@ -1234,6 +1710,11 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
             let svc_state = &app.services;
             if svc_state.loading && !svc_state.loaded {
                 format!("Loading services...  {}", s.status_quit)
+            } else if svc_state.unit_view.is_some() {
+                format!(
+                    "[j/k] Scroll  [o] Options Explorer  [Esc] {}  {}",
+                    s.back, s.status_quit
+                )
             } else {
                 match svc_state.active_sub_tab {
                     crate::modules::services::SvcSubTab::Overview => {
@@ -1241,14 +1722,14 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
                             format!("[Enter] {}  [Esc] {}  {}", s.confirm, s.back, s.status_quit)
                         } else {
                             format!(
-                            "[j/k] {}  [/] Search  [f] Filter  [r] Refresh  [Enter] Logs  [m] Manage  [/] Sub-Tab  {}",
+                            "[j/k] {}  [/] Search  [f] Filter  [s/</>] Sort  [r] Refresh  [Enter] Logs  [m] Manage  [u] Unit file  [F] Restart failed  [/] Sub-Tab  {}",
                             s.navigate, s.status_quit
                         )
                         }
                     }
                     crate::modules::services::SvcSubTab::Ports => {
                         format!(
-                            "[j/k] {}  [r] Refresh  [/] Sub-Tab  {}",
+                            "[j/k] {}  [</>] Sort  [r] Refresh  [x] Free port  [/] Sub-Tab  {}",
                             s.navigate, s.status_quit
                         )
                     }
@@ -1258,9 +1739,12 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
                             s.navigate, s.status_quit
                         )
                     }
+                    crate::modules::services::SvcSubTab::Logs if svc_state.logs_input.is_some() => {
+                        format!("[Enter] {}  [Esc] {}  {}", s.confirm, s.back, s.status_quit)
+                    }
                     crate::modules::services::SvcSubTab::Logs => {
                         format!(
-                            "[j/k] Scroll  [r] Refresh  [g/G] Top/End  [/] Sub-Tab  {}",
+                            "[j/k] Scroll  [/] Grep  [t] Range  [e] Errors  [s/u] Since/Until  [+/-] Tail  [x] Clear  [f] Follow  [r] Refresh  {}",
                             s.status_quit
                         )
                     }
@@ -1286,37 +1770,76 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
                             )
                         }
                     }
+                    crate::modules::storage::StoSubTab::Ranking => {
+                        format!(
+                            "[j/k] {}  [</>] Sort  [w] Why depends  [r] Rescan  [/] Sub-Tab  {}",
+                            s.navigate, s.status_quit
+                        )
+                    }
                     crate::modules::storage::StoSubTab::Clean => {
                         format!(
                             "[j/k] {}  [Enter] Execute  [/] Sub-Tab  {}",
                             s.navigate, s.status_quit
                         )
                     }
+                    crate::modules::storage::StoSubTab::Boot => {
+                        format!(
+                            "[j/k] {}  [x] Delete orphans  [r] Rescan  [/] Sub-Tab  {}",
+                            s.navigate, s.status_quit
+                        )
+                    }
+                    crate::modules::storage::StoSubTab::Budget => {
+                        format!(
+                            "[x] Next step  [p] Re-plan  [+/-] Target  [/] Sub-Tab  {}",
+                            s.status_quit
+                        )
+                    }
+                    crate::modules::storage::StoSubTab::Suggestions => {
+                        format!(
+                            "[j/k] {}  [Space] Select  [a] All  [x] Apply  [r] Rescan  [/] Sub-Tab  {}",
+                            s.navigate, s.status_quit
+                        )
+                    }
                     crate::modules::storage::StoSubTab::History => {
                         format!("[j/k] Scroll  [r] Refresh  [/] Sub-Tab  {}", s.status_quit)
                     }
                 }
             }
         }
+        ModuleTab::Config
+            if app.config_showcase.active_sub_tab
+                == crate::modules::config_showcase::CfgSubTab::Services =>
+        {
+            if app.config_showcase.map_loading {
+                format!("⏳ {}  [/] Sub-Tab  {}", s.cfg_map_loading, s.status_quit)
+            } else {
+                format!(
+                    "[j/k] {}  [Enter] Options Explorer  [a] All/Mine  [r] Re-evaluate  [/] Sub-Tab  {}",
+                    s.navigate, s.status_quit
+                )
+            }
+        }
         ModuleTab::Config => {
-            let is_scanning = match app.config_showcase.active_sub_tab {
-                crate::modules::config_showcase::CfgSubTab::Overview => {
-                    app.config_showcase.scanning
-                }
-                crate::modules::config_showcase::CfgSubTab::Diagram => {
-                    app.config_showcase.diagram_scanning
-                }
-            };
-            let generate_label = match app.config_showcase.active_sub_tab {
-                crate::modules::config_showcase::CfgSubTab::Overview => s.cfg_generate,
-                crate::modules::config_showcase::CfgSubTab::Diagram => s.cfg_diag_generate,
-            };
-            let scanning_label = match app.config_showcase.active_sub_tab {
-                crate::modules::config_showcase::CfgSubTab::Overview => s.cfg_scanning,
-                crate::modules::config_showcase::CfgSubTab::Diagram => s.cfg_diag_scanning,
-            };
-            if is_scanning {
+            let (is_scanning, generate_label, scanning_label) =
+                match app.config_showcase.active_sub_tab {
+                    crate::modules::config_showcase::CfgSubTab::Diagram => (
+                        app.config_showcase.diagram_scanning,
+                        s.cfg_diag_generate,
+                        s.cfg_diag_scanning,
+                    ),
+                    _ => (app.config_showcase.scanning, s.cfg_generate, s.cfg_scanning),
+                };
+            if app.config_showcase.export_input.is_some() {
+                format!("[Enter] {}  [Esc] {}  {}", s.confirm, s.back, s.status_quit)
+            } else if is_scanning {
                 format!("⏳ {}  [/] Sub-Tab  {}", scanning_label, s.status_quit)
+            } else if app.config_showcase.active_sub_tab
+                == crate::modules::config_showcase::CfgSubTab::Overview
+            {
+                format!(
+                    "[Enter/g] {}  [e] Export PNG/SVG  [/] Sub-Tab  {}",
+                    generate_label, s.status_quit
+                )
             } else {
                 format!(
                     "[Enter/g] {}  [/] Sub-Tab  {}",
@@ -1330,27 +1853,40 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
                 format!("[Enter] {}  [Esc] {}  {}", s.confirm, s.back, s.status_quit)
             } else if pkg.detail_open {
                 format!("[Esc/Enter] {}  {}", s.back, s.status_quit)
+            } else if pkg.audit_open {
+                format!(
+                    "[j/k] {}  [r] Rescan  [Esc/v] {}  {}",
+                    s.navigate, s.back, s.status_quit
+                )
             } else if !pkg.results.is_empty() {
                 format!(
-                    "[j/k] {}  [/] Search  [Enter] Details  [n] New  {}",
+                    "[j/k] {}  [/] Search  [Enter] Details  [n] New  [v] Insecure  {}",
                     s.navigate, s.status_quit
                 )
             } else {
-                format!("[/] Search  [n] New  {}", s.status_quit)
+                format!("[/] Search  [n] New  [v] Insecure  {}", s.status_quit)
             }
         }
         ModuleTab::Health => {
+            let translate = if app.health.eval_error.is_some() {
+                "[e] Translate  "
+            } else {
+                ""
+            };
             if app.health.scanning {
-                format!("⏳ Scanning...  [/] Sub-Tab  {}", s.status_quit)
-            } else if app.health.sub_tab == crate::modules::health::HealthSubTab::Fix {
                 format!(
-                    "[j/k] {}  [Enter] Fix  [r] Rescan  [/] Sub-Tab  {}",
+                    "⏳ Scanning...  [j/k] {}  [/] Sub-Tab  {}",
                     s.navigate, s.status_quit
                 )
+            } else if app.health.sub_tab == crate::modules::health::HealthSubTab::Fix {
+                format!(
+                    "[j/k] {}  [Enter] Fix  {}[r] Rescan  [/] Sub-Tab  {}",
+                    s.navigate, translate, s.status_quit
+                )
             } else {
                 format!(
-                    "[j/k] {}  [r] Rescan  [/] Sub-Tab  {}",
-                    s.navigate, s.status_quit
+                    "[j/k] {}  {}[r] Rescan  [/] Sub-Tab  {}",
+                    s.navigate, translate, s.status_quit
                 )
             }
         }
@@ -1358,9 +1894,25 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
             let rb = &app.rebuild;
             if rb.is_running() {
                 match rb.sub_tab {
-                    crate::modules::rebuild::RebuildSubTab::Dashboard
-                    | crate::modules::rebuild::RebuildSubTab::Log => {
-                        format!("[j/k] Scroll  [G] Live  [/] Sub-Tab  {}", s.status_quit)
+                    crate::modules::rebuild::RebuildSubTab::Dashboard => {
+                        format!(
+                            "[j/k] Scroll  [G] Live  [v] Tree {}  [/] Sub-Tab  {}",
+                            if rb.dashboard_tree { "✓" } else { "✗" },
+                            s.status_quit
+                        )
+                    }
+                    crate::modules::rebuild::RebuildSubTab::Log if rb.log_smart => {
+                        format!(
+                            "[j/k] Scroll  [G] Live  [Enter] Fold  [v] Smart ✓  [/] Sub-Tab  {}",
+                            s.status_quit
+                        )
+                    }
+                    crate::modules::rebuild::RebuildSubTab::Log => {
+                        format!(
+                            "[j/k] Scroll  [G] Live  [x] Explain {}  [v] Smart ✗  [/] Sub-Tab  {}",
+                            if rb.log_explain { "✓" } else { "✗" },
+                            s.status_quit
+                        )
                     }
                     _ => {
                         format!("[j/k] Scroll  [/] Sub-Tab  {}", s.status_quit)
@@ -1372,18 +1924,28 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
                 match rb.sub_tab {
                     crate::modules::rebuild::RebuildSubTab::Dashboard => {
                         format!(
-                            "[Enter/r] Rebuild  [m] Mode  [/] Sub-Tab  {}",
+                            "[Enter/r] Rebuild  [m] Mode  [e] Repl  [a] Attach  [/] Sub-Tab  {}",
+                            s.status_quit
+                        )
+                    }
+                    crate::modules::rebuild::RebuildSubTab::Log if rb.log_smart => {
+                        format!(
+                            "[j/k] Scroll  [Enter] Fold  [v] Smart ✓  [w] Save  [y] Copy error  [s] Share  [/] Sub-Tab  {}",
                             s.status_quit
                         )
                     }
                     crate::modules::rebuild::RebuildSubTab::Log => {
                         format!(
-                            "[j/k] Scroll  [/] Search  [g/G] Top/End  [/] Sub-Tab  {}",
+                            "[j/k] Scroll  [/] Search  [x] Explain {}  [v] Smart ✗  [w] Save  [y] Copy error  [s] Share  [/] Sub-Tab  {}",
+                            if rb.log_explain { "✓" } else { "✗" },
                             s.status_quit
                         )
                     }
                     crate::modules::rebuild::RebuildSubTab::Changes => {
-                        format!("[j/k] Scroll  [/] Sub-Tab  {}", s.status_quit)
+                        format!(
+                            "[j/k] Scroll  [p] Preview switch  [/] Sub-Tab  {}",
+                            s.status_quit
+                        )
                     }
                     crate::modules::rebuild::RebuildSubTab::History => {
                         format!("[j/k] {}  [/] Sub-Tab  {}", s.navigate, s.status_quit)
@@ -1397,12 +1959,22 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
                 format!("[Enter] {}  [Esc] {}  {}", s.confirm, s.back, s.status_quit)
             } else if opt.detail_open {
                 format!(
-                    "[j/k] Scroll  [r] Related  [Esc] {}  {}",
+                    "[j/k] Scroll  [r] Related  [y/Y] Link  [Esc] {}  {}",
                     s.back, s.status_quit
                 )
+            } else if opt.sub_tab == crate::modules::options::OptSubTab::Overrides {
+                format!(
+                    "[j/k] {}  [Enter] Details  [R] Re-evaluate  [/] Sub-Tab  {}",
+                    s.navigate, s.status_quit
+                )
+            } else if opt.sub_tab == crate::modules::options::OptSubTab::Changed {
+                format!(
+                    "[j/k] {}  [f] Filter  [Enter] Details  [R] Refresh  [/] Sub-Tab  {}",
+                    s.navigate, s.status_quit
+                )
             } else {
                 format!(
-                    "[j/k] {}  [/] Search  [Enter] Details  [/] Sub-Tab  {}",
+                    "[j/k] {}  [/] Search  [Enter] Details  [y] Link  [/] Sub-Tab  {}",
                     s.navigate, s.status_quit
                 )
             }
@@ -1412,24 +1984,153 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
             match fi.sub_tab {
                 crate::modules::flake_inputs::FlakeSubTab::Update => {
                     format!(
-                        "[j/k] {}  [Space] Select  [u] Update  [/] Sub-Tab  {}",
+                        "[j/k] {}  [Space] Select  [u] Update  [U] All + rebuild  [/] Sub-Tab  {}",
+                        s.navigate, s.status_quit
+                    )
+                }
+                crate::modules::flake_inputs::FlakeSubTab::History if fi.lock_diff.is_some() => {
+                    format!(
+                        "[j/k] {}  [c] Other revision  [Esc] {}  {}",
+                        s.navigate, s.back, s.status_quit
+                    )
+                }
+                crate::modules::flake_inputs::FlakeSubTab::History => {
+                    format!(
+                        "[j/k] {}  [u] Rollback  [m] Report  [c] Compare with git  [/] Sub-Tab  {}",
+                        s.navigate, s.status_quit
+                    )
+                }
+                crate::modules::flake_inputs::FlakeSubTab::Graph => {
+                    format!(
+                        "[j/k] {}  [Enter] Expand  [h] Collapse  [e/c] All  [/] Sub-Tab  {}",
                         s.navigate, s.status_quit
                     )
                 }
                 _ => {
                     format!(
-                        "[j/k] {}  [Enter] Details  [/] Sub-Tab  {}",
+                        "[j/k] {}  [Enter] Details  [e] URL  [b] Branch  [s] nixpkgs  [</>] Sort  [a] Add  [d] Remove  [/] Sub-Tab  {}",
                         s.navigate, s.status_quit
                     )
                 }
             }
         }
+        ModuleTab::External(_) => {
+            format!(
+                "[j/k] {}  [r] {}  {}",
+                s.navigate, s.pal_ext_run, s.status_quit
+            )
+        }
         _ => {
             format!("{}  {}", s.status_switch_tab, s.status_quit)
         }
+    }
+}
+
+/// Render status bar with context-sensitive keybindings
+fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme;
+    let s = i18n::get_strings(app.config.language);
+
+    let hints = match &app.kiosk {
+        Some(kiosk) => {
+            let next = if kiosk.paused {
+                s.kiosk_paused.to_string()
+            } else if kiosk.tabs.len() > 1 {
+                s.kiosk_next_in
+                    .replace("{}", &kiosk.remaining_secs().to_string())
+            } else {
+                String::new()
+            };
+            format!("{} {}  {}", s.kiosk_mode, next, s.kiosk_keys)
+        }
+        None => module_hints(app, s),
+    };
+
+    // Running jobs (or the palette hint) on the right: of the vitals line
+    // when there is one, else of the hints when it doesn't cover them
+    let running = app.jobs.running().len();
+    let right = if running > 0 {
+        s.jobs_badge
+            .replacen("{}", crate::modules::jobs::spinner(), 1)
+            .replacen("{}", &running.to_string(), 1)
+    } else {
+        s.status_palette.to_string()
+    };
+    if area.height >= 2 {
+        render_vitals(frame, app, &right, Rect { height: 1, ..area });
+        widgets::render_status_bar(frame, &hints, "", theme, area);
+        return;
+    }
+    let right = if hints.chars().count() + right.chars().count() + 2 <= area.width as usize {
+        right
+    } else {
+        String::new()
     };
+    widgets::render_status_bar(frame, &hints, &right, theme, area);
+}
+
+/// Status bar line with the system vitals, gathered in the background
+fn render_vitals(frame: &mut Frame, app: &App, right: &str, area: Rect) {
+    let theme = &app.theme;
+    let s = i18n::get_strings(app.config.language);
+    let vitals = &app.vitals.current;
+    let sep = || Span::styled("  │  ", theme.text_dim());
+
+    let mut parts: Vec<Span> = Vec::new();
+    if let Some(gen) = vitals.generation {
+        parts.push(Span::styled(
+            s.vitals_generation.replace("{}", &gen.to_string()),
+            theme.text(),
+        ));
+    }
+    if let Some(free) = vitals.store_free {
+        parts.push(Span::styled(
+            s.vitals_store_free
+                .replace("{}", &crate::types::format_bytes(free)),
+            theme.text(),
+        ));
+    }
+    match vitals.failed_units {
+        Some(0) => parts.push(Span::styled(
+            format!("✓ {}", s.vitals_units_ok),
+            Style::default().fg(theme.success),
+        )),
+        Some(n) => parts.push(Span::styled(
+            format!("✗ {}", s.vitals_units_failed.replace("{}", &n.to_string())),
+            Style::default()
+                .fg(theme.error)
+                .add_modifier(Modifier::BOLD),
+        )),
+        None => {}
+    }
+    if let Some(stale) = app.module_badge(ModuleTab::FlakeInputs) {
+        parts.push(Span::styled(
+            format!(
+                "⚠ {}",
+                s.vitals_stale_inputs.replace("{}", &stale.to_string())
+            ),
+            Style::default().fg(theme.warning),
+        ));
+    }
 
-    widgets::render_status_bar(frame, &hints, "", theme, area);
+    let mut spans = vec![Span::raw(" ")];
+    for (i, part) in parts.into_iter().enumerate() {
+        if i > 0 {
+            spans.push(sep());
+        }
+        spans.push(part);
+    }
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+
+    let right_len = right.chars().count() as u16;
+    frame.render_widget(
+        Paragraph::new(right).style(theme.text_dim()),
+        Rect {
+            x: area.x + area.width.saturating_sub(right_len + 1),
+            width: (right_len + 1).min(area.width),
+            ..area
+        },
+    );
 }
 
 /// Render popup overlays
@@ -1441,13 +2142,53 @@ fn render_popups(frame: &mut Frame, app: &App, area: Rect) {
         PopupState::Error { title, message } => {
             widgets::render_error_popup(frame, title, message, theme, area);
         }
+        PopupState::QuitDuringBuild => {
+            let s = i18n::get_strings(app.config.language);
+            // Mid-activation the build can only be waited for
+            let (note, buttons): (&str, &[(&str, char)]) = if app.rebuild.activating() {
+                (
+                    s.rb_quit_activating,
+                    &[(s.rb_quit_when_done, 'w'), (s.rb_quit_stay, 'n')],
+                )
+            } else {
+                (
+                    s.rb_quit_detach_note,
+                    &[
+                        (s.rb_quit_detach, 'd'),
+                        (s.rb_quit_stop, 'k'),
+                        (s.rb_quit_stay, 'n'),
+                    ],
+                )
+            };
+            let content = vec![
+                Line::styled(s.rb_quit_running, theme.warning()),
+                Line::raw(""),
+                Line::styled(note, theme.text_dim()),
+            ];
+            widgets::render_popup(frame, s.rb_quit_title, content, buttons, theme, area);
+        }
         PopupState::Loading { message } => {
             widgets::render_loading(frame, message, theme, area);
         }
     }
 
-    // Flash message
-    if let Some(msg) = &app.flash_message {
+    // Staged deletion countdown, else flash message
+    if let Some(staged) = app.staged.latest() {
+        let s = i18n::get_strings(app.config.language);
+        let secs = staged.remaining().as_secs_f32().ceil() as u64;
+        let mut text = s
+            .staged_countdown
+            .replacen("{}", &staged.label, 1)
+            .replacen("{}", &secs.to_string(), 1);
+        if app.staged.len() > 1 {
+            text.push_str(&format!(
+                " ({})",
+                s.staged_more
+                    .replace("{}", &(app.staged.len() - 1).to_string())
+            ));
+        }
+        widgets::render_flash_message(frame, &text, false, &app.theme, area);
+    } else if let Some(msg) = &app.flash_message {
         widgets::render_flash_message(frame, &msg.text, msg.is_error, &app.theme, area);
     }
 }
@@ -0,0 +1,408 @@
+//! Shared table widget: column definitions, sorting, column hiding and
+//! horizontal scrolling
+//!
+//! A view describes its columns once (`Column`), keeps a `TableState` and
+//! hands `Table` its rows as cells. Sorting only produces an order —
+//! `TableState::order` returns row indices — so modules keep their
+//! selection pointing into their own data and step through the order.
+//!
+//! Keys (`TableState::handle_key`): `<`/`>` move the sort column, `~` flips
+//! the direction, `-` hides the sort column, `+` shows all columns again.
+//! Columns that don't fit are scrolled off to the left or right, keeping
+//! the sort column in view. In compact mode (small terminals) secondary
+//! columns are left out altogether.
+
+use crate::ui::Theme;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+use std::cmp::Ordering;
+
+/// Room for the selection marker in front of each row
+const MARKER_WIDTH: u16 = 2;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Column {
+    pub title: &'static str,
+    /// Width, or minimum width for fill columns
+    pub width: u16,
+    /// Takes a share of the space left over
+    pub fill: bool,
+    pub align_right: bool,
+    /// Left out in compact mode
+    pub secondary: bool,
+}
+
+impl Column {
+    pub const fn fixed(title: &'static str, width: u16) -> Self {
+        Self {
+            title,
+            width,
+            fill: false,
+            align_right: false,
+            secondary: false,
+        }
+    }
+
+    pub const fn fill(title: &'static str, min: u16) -> Self {
+        Self {
+            title,
+            width: min,
+            fill: true,
+            align_right: false,
+            secondary: false,
+        }
+    }
+
+    pub const fn right(mut self) -> Self {
+        self.align_right = true;
+        self
+    }
+
+    pub const fn secondary(mut self) -> Self {
+        self.secondary = true;
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Cell {
+    pub text: String,
+    pub style: Style,
+}
+
+impl Cell {
+    pub fn new(text: impl Into<String>, style: Style) -> Self {
+        Self {
+            text: text.into(),
+            style,
+        }
+    }
+}
+
+/// What a row is sorted by in one column
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SortKey {
+    Num(i64),
+    Text(String),
+}
+
+impl SortKey {
+    /// Case-insensitive text
+    pub fn text(s: &str) -> Self {
+        SortKey::Text(s.to_lowercase())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TableState {
+    /// Column sorted by; None keeps the data's own order
+    pub sort: Option<usize>,
+    pub descending: bool,
+    pub hidden: Vec<usize>,
+}
+
+impl TableState {
+    /// Sort and column keys. True if the key was used.
+    pub fn handle_key(&mut self, key: KeyEvent, columns: &[Column]) -> bool {
+        let visible: Vec<usize> = (0..columns.len())
+            .filter(|c| !self.hidden.contains(c))
+            .collect();
+        let pos = self.sort.and_then(|s| visible.iter().position(|&c| c == s));
+        match key.code {
+            KeyCode::Char('>') => {
+                self.sort = match pos {
+                    None => visible.first().copied(),
+                    Some(p) => visible.get(p + 1).or(visible.get(p)).copied(),
+                };
+            }
+            KeyCode::Char('<') => {
+                // Left of the first column: back to the unsorted order
+                self.sort = match pos {
+                    None | Some(0) => None,
+                    Some(p) => Some(visible[p - 1]),
+                };
+                if self.sort.is_none() {
+                    self.descending = false;
+                }
+            }
+            KeyCode::Char('~') if self.sort.is_some() => {
+                self.descending = !self.descending;
+            }
+            KeyCode::Char('-') => {
+                if let Some(s) = self.sort.filter(|_| visible.len() > 1) {
+                    self.hidden.push(s);
+                    self.sort = None;
+                    self.descending = false;
+                }
+            }
+            KeyCode::Char('+') => self.hidden.clear(),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Row indices in display order (stable for equal keys)
+    pub fn order<T>(&self, rows: &[T], key: impl Fn(&T, usize) -> SortKey) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..rows.len()).collect();
+        if let Some(col) = self.sort {
+            let keys: Vec<SortKey> = rows.iter().map(|r| key(r, col)).collect();
+            order.sort_by(|&a, &b| self.compare(&keys[a], &keys[b]));
+        }
+        order
+    }
+
+    /// The rows themselves in display order (for views that already
+    /// build a filtered list)
+    pub fn sorted<T: Clone>(&self, rows: Vec<T>, key: impl Fn(&T, usize) -> SortKey) -> Vec<T> {
+        if self.sort.is_none() {
+            return rows;
+        }
+        self.order(&rows, key)
+            .into_iter()
+            .map(|i| rows[i].clone())
+            .collect()
+    }
+
+    fn compare(&self, a: &SortKey, b: &SortKey) -> Ordering {
+        if self.descending {
+            b.cmp(a)
+        } else {
+            a.cmp(b)
+        }
+    }
+
+    /// Visible columns that fit into `width` with their widths, and whether
+    /// columns were cut off on the left / right
+    fn layout(
+        &self,
+        columns: &[Column],
+        width: u16,
+        compact: bool,
+    ) -> (Vec<(usize, u16)>, bool, bool) {
+        let visible: Vec<usize> = (0..columns.len())
+            .filter(|&c| !self.hidden.contains(&c) && (!compact || !columns[c].secondary))
+            .collect();
+        let avail = width.saturating_sub(MARKER_WIDTH);
+        let cost = |c: usize| columns[c].width + 1;
+        let focus = self
+            .sort
+            .and_then(|s| visible.iter().position(|&c| c == s))
+            .unwrap_or(0);
+
+        let mut start = 0;
+        while start < focus && visible[start..=focus].iter().map(|&c| cost(c)).sum::<u16>() > avail
+        {
+            start += 1;
+        }
+        let mut shown: Vec<(usize, u16)> = Vec::new();
+        let mut used = 0;
+        for &c in &visible[start..] {
+            if !shown.is_empty() && used + cost(c) > avail {
+                break;
+            }
+            used += cost(c);
+            shown.push((c, columns[c].width));
+        }
+
+        // Leftover space goes to the fill columns
+        let fills = shown.iter().filter(|(c, _)| columns[*c].fill).count() as u16;
+        if fills > 0 {
+            let extra = avail.saturating_sub(used);
+            let mut rest = extra % fills;
+            for (_, w) in shown.iter_mut().filter(|(c, _)| columns[*c].fill) {
+                *w += extra / fills + u16::from(rest > 0);
+                rest = rest.saturating_sub(1);
+            }
+        }
+        let right_cut = start + shown.len() < visible.len();
+        (shown, start > 0, right_cut)
+    }
+}
+
+/// Text cut or padded to exactly `width` characters
+fn fit(text: &str, width: usize, align_right: bool) -> String {
+    let len = text.chars().count();
+    if len > width {
+        if width == 0 {
+            return String::new();
+        }
+        let mut cut: String = text.chars().take(width - 1).collect();
+        cut.push('…');
+        cut
+    } else if align_right {
+        format!("{:>width$}", text, width = width)
+    } else {
+        format!("{:<width$}", text, width = width)
+    }
+}
+
+/// A table over `len` rows, each built on demand by `row(i)` for the i-th
+/// row in display order
+pub struct Table<'a, F> {
+    columns: &'a [Column],
+    state: &'a TableState,
+    len: usize,
+    row: F,
+    selected: Option<usize>,
+    compact: bool,
+}
+
+impl<'a, F: Fn(usize) -> Vec<Cell>> Table<'a, F> {
+    pub fn new(columns: &'a [Column], state: &'a TableState, len: usize, row: F) -> Self {
+        Self {
+            columns,
+            state,
+            len,
+            row,
+            selected: None,
+            compact: false,
+        }
+    }
+
+    /// Highlight this row (display position) and keep it scrolled into view
+    pub fn selected(mut self, selected: Option<usize>) -> Self {
+        self.selected = selected;
+        self
+    }
+
+    /// Leave out the secondary columns
+    pub fn compact(mut self, compact: bool) -> Self {
+        self.compact = compact;
+        self
+    }
+
+    pub fn render(self, frame: &mut Frame, theme: &Theme, area: Rect) {
+        if area.height == 0 || area.width <= MARKER_WIDTH {
+            return;
+        }
+        let (shown, left_cut, right_cut) =
+            self.state.layout(self.columns, area.width, self.compact);
+
+        // Header: sort column marked with its direction
+        let header_style = Style::default()
+            .fg(theme.accent)
+            .add_modifier(Modifier::BOLD);
+        let mut header = vec![Span::styled(
+            if left_cut { "‹ " } else { "  " },
+            theme.text_dim(),
+        )];
+        for (i, &(c, w)) in shown.iter().enumerate() {
+            let col = &self.columns[c];
+            let title = match self.state.sort {
+                Some(s) if s == c => {
+                    let arrow = if self.state.descending { '▼' } else { '▲' };
+                    format!("{} {}", col.title, arrow)
+                }
+                _ => col.title.to_string(),
+            };
+            let last = i + 1 == shown.len();
+            header.push(Span::styled(
+                fit(&title, w as usize, col.align_right),
+                header_style,
+            ));
+            header.push(Span::styled(
+                if last && right_cut { "›" } else { " " },
+                theme.text_dim(),
+            ));
+        }
+        let mut lines = vec![Line::from(header)];
+
+        let body_height = area.height.saturating_sub(1) as usize;
+        let scroll = match self.selected {
+            Some(sel) if sel >= body_height => sel + 1 - body_height,
+            _ => 0,
+        };
+        let selected_bg = theme.selected().bg;
+        for i in (scroll..self.len).take(body_height) {
+            let cells = (self.row)(i);
+            let is_sel = self.selected == Some(i);
+            let mut spans = vec![Span::styled(
+                if is_sel { " ▸" } else { "  " },
+                Style::default().fg(theme.accent),
+            )];
+            for &(c, w) in &shown {
+                let col = &self.columns[c];
+                let (text, mut style) = match cells.get(c) {
+                    Some(cell) => (cell.text.as_str(), cell.style),
+                    None => ("", theme.text()),
+                };
+                if is_sel {
+                    style = style.add_modifier(Modifier::BOLD);
+                    if let Some(bg) = selected_bg {
+                        style = style.bg(bg);
+                    }
+                }
+                spans.push(Span::styled(fit(text, w as usize, col.align_right), style));
+                spans.push(Span::styled(" ", style));
+            }
+            lines.push(Line::from(spans));
+        }
+
+        frame.render_widget(Paragraph::new(lines).style(theme.block_style()), area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COLUMNS: [Column; 3] = [
+        Column::fixed("Name", 10),
+        Column::fill("Description", 20),
+        Column::fixed("Size", 8).right().secondary(),
+    ];
+
+    #[test]
+    fn test_order_and_keys() {
+        let rows = ["b", "C", "a"];
+        let mut state = TableState::default();
+        let key = |r: &&str, _: usize| SortKey::text(r);
+        assert_eq!(state.order(&rows, key), vec![0, 1, 2]);
+
+        state.handle_key(KeyEvent::from(KeyCode::Char('>')), &COLUMNS);
+        assert_eq!(state.sort, Some(0));
+        assert_eq!(state.order(&rows, key), vec![2, 0, 1]);
+        state.handle_key(KeyEvent::from(KeyCode::Char('~')), &COLUMNS);
+        assert_eq!(state.order(&rows, key), vec![1, 0, 2]);
+
+        // Hiding the sort column drops the sort, `<` past the first
+        // column goes back to the data's order
+        state.handle_key(KeyEvent::from(KeyCode::Char('-')), &COLUMNS);
+        assert_eq!((state.sort, state.hidden.clone()), (None, vec![0]));
+        state.handle_key(KeyEvent::from(KeyCode::Char('>')), &COLUMNS);
+        assert_eq!(state.sort, Some(1));
+        state.handle_key(KeyEvent::from(KeyCode::Char('<')), &COLUMNS);
+        assert_eq!(state.sort, None);
+    }
+
+    #[test]
+    fn test_layout_scrolls_to_sort_column() {
+        let state = TableState::default();
+        // Everything fits, the fill column takes the rest
+        let (shown, left, right) = state.layout(&COLUMNS, 60, false);
+        assert_eq!(shown, vec![(0, 10), (1, 37), (2, 8)]);
+        assert!(!left && !right);
+
+        // Compact: the secondary column goes, its room to the fill column
+        let (shown, _, _) = state.layout(&COLUMNS, 60, true);
+        assert_eq!(shown, vec![(0, 10), (1, 46)]);
+
+        // Too narrow: the sort column is kept in view
+        let state = TableState {
+            sort: Some(2),
+            ..TableState::default()
+        };
+        let (shown, left, _) = state.layout(&COLUMNS, 34, false);
+        assert_eq!(shown.last().map(|(c, _)| *c), Some(2));
+        assert!(left);
+
+        assert_eq!(fit("nixos-unstable", 8, false), "nixos-u…");
+        assert_eq!(fit("42", 4, true), "  42");
+    }
+}
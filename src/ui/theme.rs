@@ -1,10 +1,43 @@
 //! Theme definitions for nixmate
 //!
-//! Provides three built-in themes: Gruvbox, Nord, and Transparent.
+//! Provides the built-in themes (Gruvbox, Nord, …, Transparent) plus user
+//! themes from `~/.config/nixmate/themes/<name>.toml`.
 //! One theme instance – applied globally to every module.
+//!
+//! A theme file sets any `Theme` field as `#rrggbb`; the rest comes from
+//! `base` (a built-in name, Gruvbox if omitted):
+//!
+//! ```toml
+//! base = "nord"
+//! bg = "#1d2021"
+//! accent = "#d79921"
+//! transparent = false
+//! ```
 
 use crate::config::ThemeName;
+use anyhow::{bail, Context, Result};
 use ratatui::style::{Color, Modifier, Style};
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+/// Color fields a theme file can set, in `Theme` order
+pub const FIELDS: [&str; 15] = [
+    "bg",
+    "fg",
+    "fg_dim",
+    "accent",
+    "accent_dim",
+    "success",
+    "warning",
+    "error",
+    "border",
+    "border_focused",
+    "selection_bg",
+    "selection_fg",
+    "diff_added",
+    "diff_removed",
+    "diff_updated",
+];
 
 /// Complete theme with all required colors
 #[derive(Debug, Clone)]
@@ -344,6 +377,107 @@ impl Theme {
         }
     }
 
+    /// Parse a theme file (see module docs)
+    pub fn from_toml(text: &str) -> Result<Self> {
+        let table: toml::Table = text.parse().context("invalid TOML")?;
+        let mut theme = match table.get("base") {
+            Some(base) => Self::from_name(
+                base.clone()
+                    .try_into::<ThemeName>()
+                    .with_context(|| format!("unknown base theme {}", base))?,
+            ),
+            None => Self::gruvbox(),
+        };
+        for (key, value) in &table {
+            match key.as_str() {
+                "base" => {}
+                "transparent" => {
+                    theme.is_transparent = value
+                        .as_bool()
+                        .context("transparent: expected true/false")?;
+                }
+                field => {
+                    let Some(slot) = theme.color_mut(field) else {
+                        bail!("unknown field `{}`", field);
+                    };
+                    *slot = value
+                        .as_str()
+                        .and_then(parse_hex)
+                        .with_context(|| format!("{}: expected \"#rrggbb\"", field))?;
+                }
+            }
+        }
+        Ok(theme)
+    }
+
+    /// Load `<themes dir>/<name>.toml`
+    pub fn load_custom(name: &str) -> Result<Self> {
+        let path = custom_theme_path(name).context("no config directory")?;
+        let text = std::fs::read_to_string(&path).with_context(|| format!("{}", path.display()))?;
+        Self::from_toml(&text)
+    }
+
+    /// All colors with their field names, in `FIELDS` order
+    pub fn colors(&self) -> [(&'static str, Color); 15] {
+        let values = [
+            self.bg,
+            self.fg,
+            self.fg_dim,
+            self.accent,
+            self.accent_dim,
+            self.success,
+            self.warning,
+            self.error,
+            self.border,
+            self.border_focused,
+            self.selection_bg,
+            self.selection_fg,
+            self.diff_added,
+            self.diff_removed,
+            self.diff_updated,
+        ];
+        std::array::from_fn(|i| (FIELDS[i], values[i]))
+    }
+
+    fn color_mut(&mut self, field: &str) -> Option<&mut Color> {
+        Some(match field {
+            "bg" => &mut self.bg,
+            "fg" => &mut self.fg,
+            "fg_dim" => &mut self.fg_dim,
+            "accent" => &mut self.accent,
+            "accent_dim" => &mut self.accent_dim,
+            "success" => &mut self.success,
+            "warning" => &mut self.warning,
+            "error" => &mut self.error,
+            "border" => &mut self.border,
+            "border_focused" => &mut self.border_focused,
+            "selection_bg" => &mut self.selection_bg,
+            "selection_fg" => &mut self.selection_fg,
+            "diff_added" => &mut self.diff_added,
+            "diff_removed" => &mut self.diff_removed,
+            "diff_updated" => &mut self.diff_updated,
+            _ => return None,
+        })
+    }
+
+    /// Key colors as `#rrggbb`, for exports outside the terminal (poster).
+    /// Named colors (Transparent theme) use the xterm defaults.
+    pub fn palette_hex(&self) -> Vec<String> {
+        [
+            self.bg,
+            self.fg,
+            self.accent,
+            self.accent_dim,
+            self.success,
+            self.warning,
+            self.error,
+            self.border,
+        ]
+        .iter()
+        .filter_map(|c| to_hex(*c))
+        .collect()
+    }
+
     // === STYLE HELPERS ===
 
     pub fn text(&self) -> Style {
@@ -482,6 +616,64 @@ impl Theme {
     }
 }
 
+/// `#rrggbb`; named colors use the xterm defaults, Reset has none
+pub fn to_hex(color: Color) -> Option<String> {
+    let (r, g, b) = match color {
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::White => (229, 229, 229),
+        Color::Gray => (170, 170, 170),
+        Color::DarkGray => (127, 127, 127),
+        Color::Cyan => (0, 205, 205),
+        Color::Blue => (0, 0, 238),
+        Color::Green => (0, 205, 0),
+        Color::Yellow => (205, 205, 0),
+        Color::Red => (205, 0, 0),
+        _ => return None,
+    };
+    Some(format!("#{:02x}{:02x}{:02x}", r, g, b))
+}
+
+fn parse_hex(s: &str) -> Option<Color> {
+    let hex = s.trim().strip_prefix('#')?;
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+    let byte = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some(Color::Rgb(byte(0)?, byte(2)?, byte(4)?))
+}
+
+/// `~/.config/nixmate/themes`
+pub fn themes_dir() -> Option<PathBuf> {
+    Some(dirs::config_dir()?.join("nixmate").join("themes"))
+}
+
+pub fn custom_theme_path(name: &str) -> Option<PathBuf> {
+    Some(themes_dir()?.join(format!("{}.toml", name)))
+}
+
+pub fn custom_theme_mtime(name: &str) -> Option<SystemTime> {
+    std::fs::metadata(custom_theme_path(name)?)
+        .ok()?
+        .modified()
+        .ok()
+}
+
+/// Names of the theme files found, sorted
+pub fn custom_theme_names() -> Vec<String> {
+    let Some(entries) = themes_dir().and_then(|d| std::fs::read_dir(d).ok()) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .flatten()
+        .filter_map(|e| {
+            let path = e.path();
+            (path.extension()? == "toml").then(|| path.file_stem()?.to_str().map(String::from))?
+        })
+        .collect();
+    names.sort();
+    names
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -498,4 +690,35 @@ mod tests {
         let transparent = Theme::from_name(ThemeName::Transparent);
         assert!(transparent.is_transparent);
     }
+
+    #[test]
+    fn test_palette_hex() {
+        let gruvbox = Theme::from_name(ThemeName::Gruvbox).palette_hex();
+        assert_eq!(gruvbox.len(), 8);
+        assert_eq!(gruvbox[0], "#282828");
+
+        // Reset background is skipped, named colors are mapped
+        let transparent = Theme::from_name(ThemeName::Transparent).palette_hex();
+        assert_eq!(transparent.len(), 7);
+        assert!(transparent
+            .iter()
+            .all(|c| c.len() == 7 && c.starts_with('#')));
+    }
+
+    #[test]
+    fn test_theme_from_toml() {
+        let theme = Theme::from_toml(
+            "base = \"nord\"\naccent = \"#D79921\"\nbg = \"#1d2021\"\ntransparent = true",
+        )
+        .unwrap();
+        assert_eq!(theme.accent, Color::Rgb(215, 153, 33));
+        assert_eq!(theme.bg, Color::Rgb(29, 32, 33));
+        // Untouched fields come from the base
+        assert_eq!(theme.fg, Theme::nord().fg);
+        assert!(theme.is_transparent);
+
+        assert!(Theme::from_toml("accent = \"orange\"").is_err());
+        assert!(Theme::from_toml("accnet = \"#ffffff\"").is_err());
+        assert!(Theme::from_toml("base = \"vaporwave\"").is_err());
+    }
 }
@@ -193,7 +193,7 @@ pub fn render_status_bar(
 
     let left_widget = Paragraph::new(left_content).style(theme.text());
 
-    let right_len = right_content.len() as u16;
+    let right_len = right_content.chars().count() as u16;
     let right_area = Rect {
         x: status_area.x + status_area.width.saturating_sub(right_len + 1),
         y: status_area.y,